@@ -0,0 +1,271 @@
+//! Benchmarks for the audio hot paths at 48 kHz.
+//!
+//! Every benchmark renders one 512-frame block and reports throughput in
+//! samples, so results read as "samples per second" and can be compared
+//! against the real-time budget (48k samples per second per voice).
+//!
+//! ```text
+//! cargo bench --bench hot_paths
+//! cargo bench --bench hot_paths -- --output-format bencher   # CI-friendly
+//! ```
+//!
+//! Criterion needs a native host; there is no wasm32 build of the crate yet,
+//! so the browser numbers are not covered here.
+
+use std::hint::black_box;
+
+use criterion::measurement::WallTime;
+use criterion::{criterion_group, criterion_main, BenchmarkGroup, Criterion, Throughput};
+
+use gooey::dsl::Program;
+use gooey::effects::*;
+use gooey::engine::Instrument;
+use gooey::ffi::*;
+use gooey::instruments::*;
+use gooey::sequencer::Sequencer;
+use gooey::StereoFrame;
+
+const SR: f32 = 48_000.0;
+const BLOCK: usize = 512;
+
+/// Deterministic broadband input for the effect benchmarks.
+fn test_signal() -> Vec<f32> {
+    let mut state = 0x1234_5678_u32;
+    (0..BLOCK)
+        .map(|i| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            let noise = state as f32 / u32::MAX as f32 * 2.0 - 1.0;
+            let tone = (i as f32 * 110.0 * std::f32::consts::TAU / SR).sin();
+            0.5 * tone + 0.25 * noise
+        })
+        .collect()
+}
+
+fn group<'a>(c: &'a mut Criterion, name: &str) -> BenchmarkGroup<'a, WallTime> {
+    let mut group = c.benchmark_group(name);
+    group.throughput(Throughput::Elements(BLOCK as u64));
+    group
+}
+
+/// Retrigger at the start of every block so the voice is always sounding.
+fn bench_instrument(group: &mut BenchmarkGroup<WallTime>, name: &str, mut inst: impl Instrument) {
+    group.bench_function(name, |b| {
+        b.iter(|| {
+            inst.trigger(0.0);
+            let mut sum = 0.0;
+            for i in 0..BLOCK {
+                sum += inst.tick(i as f64 / SR as f64);
+            }
+            black_box(sum)
+        })
+    });
+}
+
+fn instruments(c: &mut Criterion) {
+    let mut group = group(c, "instrument");
+    bench_instrument(&mut group, "kick", KickDrum::new(SR));
+    bench_instrument(&mut group, "snare", SnareDrum::new(SR));
+    bench_instrument(&mut group, "hihat", HiHat::new(SR));
+    bench_instrument(&mut group, "hihat2", HiHat2::new(SR));
+    bench_instrument(&mut group, "tom", TomDrum::new(SR));
+    bench_instrument(&mut group, "tom2", Tom2::new(SR));
+    bench_instrument(&mut group, "bass", BassSynth::new(SR));
+    bench_instrument(&mut group, "poly_synth", PolySynth::new(SR));
+    let buffer = SampleBuffer::from_mono(test_signal().repeat(96), SR).unwrap();
+    bench_instrument(&mut group, "granulator", Granulator::new(SR, buffer));
+
+    // Every voice of the host kit hit together: the instrument share of a
+    // busy render, without the mixer and effects around it.
+    let mut kit: Vec<Box<dyn Instrument>> = vec![
+        Box::new(KickDrum::new(SR)),
+        Box::new(SnareDrum::new(SR)),
+        Box::new(HiHat2::new(SR)),
+        Box::new(Tom2::new(SR)),
+        Box::new(BassSynth::new(SR)),
+        Box::new(FmSnap::new(SR)),
+        Box::new(Rimshot::new(SR)),
+        Box::new(Cowbell::new(SR)),
+        Box::new(Shaker::new(SR)),
+    ];
+    group.bench_function("kit", |b| {
+        b.iter(|| {
+            let mut sum = 0.0;
+            for inst in kit.iter_mut() {
+                inst.trigger(0.0);
+                for i in 0..BLOCK {
+                    sum += inst.tick(i as f64 / SR as f64);
+                }
+            }
+            black_box(sum)
+        })
+    });
+    group.finish();
+}
+
+fn bench_effect(group: &mut BenchmarkGroup<WallTime>, name: &str, effect: impl Effect) {
+    let input = test_signal();
+    group.bench_function(format!("{name}/mono"), |b| {
+        b.iter(|| {
+            let mut sum = 0.0;
+            for &x in &input {
+                sum += effect.process(black_box(x));
+            }
+            black_box(sum)
+        })
+    });
+    group.bench_function(format!("{name}/stereo"), |b| {
+        b.iter(|| {
+            let mut sum = StereoFrame::default();
+            for &x in &input {
+                sum += effect.process_stereo(StereoFrame {
+                    l: black_box(x),
+                    r: -x,
+                });
+            }
+            black_box(sum)
+        })
+    });
+}
+
+fn effects(c: &mut Criterion) {
+    let mut group = group(c, "effect");
+    bench_effect(
+        &mut group,
+        "lowpass",
+        LowpassFilterEffect::new(SR, 800.0, 0.5),
+    );
+    bench_effect(
+        &mut group,
+        "delay",
+        DelayEffect::new(SR, DelayTiming::Eighth, 120.0, 0.5, 0.5, 4000.0),
+    );
+    bench_effect(
+        &mut group,
+        "saturation",
+        TubeSaturation::new(SR, 0.8, 0.5, 1.0),
+    );
+    bench_effect(
+        &mut group,
+        "compressor",
+        TubeCompressor::new(SR, -30.0, 8.0, 1.0, 50.0, 1.0),
+    );
+    bench_effect(&mut group, "tilt", TiltFilterEffect::new(SR));
+    bench_effect(
+        &mut group,
+        "spring_reverb",
+        SpringReverbEffect::new(SR, 0.7, 0.5, 0.3),
+    );
+    bench_effect(
+        &mut group,
+        "plate_reverb",
+        PlateReverbEffect::new(SR, 0.7, 0.5, 0.3),
+    );
+    bench_effect(&mut group, "brickwall_limiter", BrickWallLimiter::new(0.9));
+    bench_effect(&mut group, "soft_limiter", SoftLimiter::new(0.9));
+
+    // The waveshapers are plain processors rather than `Effect`s.
+    let input = test_signal();
+    let mut shaper = Waveshaper::new(4.0, 1.0);
+    group.bench_function("waveshaper", |b| {
+        b.iter(|| {
+            input
+                .iter()
+                .map(|&x| shaper.process(black_box(x)))
+                .sum::<f32>()
+        })
+    });
+    let mut feedback = FeedbackWaveshaper::new(SR, 4.0, 0.5, 2000.0, 1.0);
+    group.bench_function("feedback_waveshaper", |b| {
+        b.iter(|| {
+            input
+                .iter()
+                .map(|&x| feedback.process(black_box(x)))
+                .sum::<f32>()
+        })
+    });
+    group.finish();
+}
+
+fn sequencer(c: &mut Criterion) {
+    let mut group = group(c, "sequencer");
+    let mut seq = Sequencer::new(174.0, SR);
+    seq.start();
+    group.bench_function("tick", |b| {
+        b.iter(|| {
+            let mut steps = 0;
+            for _ in 0..BLOCK {
+                seq.tick(|step| steps += step);
+            }
+            black_box(steps)
+        })
+    });
+    group.finish();
+}
+
+fn engine(c: &mut Criterion) {
+    let mut group = group(c, "engine");
+
+    let source = r#"
+        bpm 128
+        inst kick kick
+        inst snare snare
+        inst hihat hihat
+        seq kick x..x..x.|x..x..x.
+        seq snare ....x...|....x...
+        seq hihat xxxxxxxx|xxxxxxxx
+        fx delay 1/8 fb=0.4 mix=0.3
+        fx limiter 0.9
+    "#;
+    let mut dsl = Program::parse(source).unwrap().build_engine(SR).unwrap();
+    let mut time = 0.0_f64;
+    group.bench_function("dsl/tick", |b| {
+        b.iter(|| {
+            let mut sum = 0.0;
+            for _ in 0..BLOCK {
+                sum += dsl.tick(time);
+                time += 1.0 / SR as f64;
+            }
+            black_box(sum)
+        })
+    });
+    group.bench_function("dsl/tick_stereo", |b| {
+        b.iter(|| {
+            let mut sum = StereoFrame::default();
+            for _ in 0..BLOCK {
+                sum += dsl.tick_stereo(time);
+                time += 1.0 / SR as f64;
+            }
+            black_box(sum)
+        })
+    });
+
+    // The host-facing block render, idle and with every instrument sequenced
+    // on every step.
+    let mut buffer = vec![0.0_f32; BLOCK * 2];
+    let idle = gooey_engine_new(SR);
+    group.bench_function("ffi/render_idle", |b| {
+        b.iter(|| unsafe { gooey_engine_render(idle, buffer.as_mut_ptr(), BLOCK as u32) })
+    });
+    let busy = gooey_engine_new(SR);
+    unsafe {
+        for instrument in 0..INSTRUMENT_COUNT {
+            for step in 0..16 {
+                gooey_engine_sequencer_set_instrument_step(busy, instrument, step, true);
+            }
+        }
+        gooey_engine_sequencer_start(busy);
+    }
+    group.bench_function("ffi/render_sequenced", |b| {
+        b.iter(|| unsafe { gooey_engine_render(busy, buffer.as_mut_ptr(), BLOCK as u32) })
+    });
+    unsafe {
+        gooey_engine_free(idle);
+        gooey_engine_free(busy);
+    }
+    group.finish();
+}
+
+criterion_group!(benches, instruments, effects, sequencer, engine);
+criterion_main!(benches);
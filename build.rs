@@ -1,9 +1,18 @@
 //! Build script for generating C headers via cbindgen
-
-use std::env;
-use std::path::PathBuf;
+//!
+//! Header generation runs only with the `header` feature (on by default and
+//! for `ios`), so Rust-only consumers don't pay for cbindgen.
 
 fn main() {
+    #[cfg(feature = "header")]
+    generate_header();
+}
+
+#[cfg(feature = "header")]
+fn generate_header() {
+    use std::env;
+    use std::path::PathBuf;
+
     let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
     let output_dir = PathBuf::from(&crate_dir).join("include");
 
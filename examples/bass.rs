@@ -464,7 +464,9 @@ fn main() -> anyhow::Result<()> {
                     // Trigger
                     KeyCode::Char(' ') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument_with_velocity("bass", current_velocity);
+                        engine
+                            .trigger_instrument_with_velocity("bass", current_velocity)
+                            .unwrap();
                         trigger_count += 1;
                         needs_redraw = true;
                     }
@@ -472,28 +474,36 @@ fn main() -> anyhow::Result<()> {
                     // Velocity triggers
                     KeyCode::Char('z') | KeyCode::Char('Z') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument_with_velocity("bass", 0.25);
+                        engine
+                            .trigger_instrument_with_velocity("bass", 0.25)
+                            .unwrap();
                         trigger_count += 1;
                         current_velocity = 0.25;
                         needs_redraw = true;
                     }
                     KeyCode::Char('x') | KeyCode::Char('X') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument_with_velocity("bass", 0.50);
+                        engine
+                            .trigger_instrument_with_velocity("bass", 0.50)
+                            .unwrap();
                         trigger_count += 1;
                         current_velocity = 0.50;
                         needs_redraw = true;
                     }
                     KeyCode::Char('c') | KeyCode::Char('C') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument_with_velocity("bass", 0.75);
+                        engine
+                            .trigger_instrument_with_velocity("bass", 0.75)
+                            .unwrap();
                         trigger_count += 1;
                         current_velocity = 0.75;
                         needs_redraw = true;
                     }
                     KeyCode::Char('v') | KeyCode::Char('V') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument_with_velocity("bass", 1.0);
+                        engine
+                            .trigger_instrument_with_velocity("bass", 1.0)
+                            .unwrap();
                         trigger_count += 1;
                         current_velocity = 1.0;
                         needs_redraw = true;
@@ -4,7 +4,7 @@ use std::path::Path;
 
 use gooey::bounce::{bounce_to_wav, BounceLength, WavConfig};
 use gooey::effects::{DelayEffect, DelayTiming, SoftLimiter, TubeCompressor, TubeSaturation};
-use gooey::engine::{Engine, Lfo, MusicalDivision, Sequencer, SequencerStep};
+use gooey::engine::{Engine, Lfo, MusicalDivision, Sequencer, SequencerStep, STEP_MAX_LAYERS};
 use gooey::instruments::{HiHat2, KickDrum, SnareDrum, Tom2};
 
 fn main() {
@@ -30,6 +30,10 @@ fn main() {
             velocity: if i == 0 { 1.0 } else { 0.85 },
             blend: None,
             note: None,
+            articulation: None,
+            tune: None,
+            gate: None,
+            layers: [None; STEP_MAX_LAYERS],
         })
         .collect();
     let mut kick_seq = Sequencer::with_velocity_pattern(bpm, sample_rate, kick_pattern, "kick");
@@ -50,6 +54,10 @@ fn main() {
                 velocity: hit.map(|(_, v)| *v).unwrap_or(0.0),
                 blend: None,
                 note: None,
+                articulation: None,
+                tune: None,
+                gate: None,
+                layers: [None; STEP_MAX_LAYERS],
             }
         })
         .collect();
@@ -63,6 +71,10 @@ fn main() {
             velocity: if i % 4 == 0 { 0.9 } else { 0.5 },
             blend: None,
             note: None,
+            articulation: None,
+            tune: None,
+            gate: None,
+            layers: [None; STEP_MAX_LAYERS],
         })
         .collect();
     let mut hihat_seq = Sequencer::with_velocity_pattern(bpm, sample_rate, hihat_pattern, "hihat");
@@ -79,6 +91,10 @@ fn main() {
                 velocity: hit.map(|(_, v)| *v).unwrap_or(0.0),
                 blend: None,
                 note: None,
+                articulation: None,
+                tune: None,
+                gate: None,
+                layers: [None; STEP_MAX_LAYERS],
             }
         })
         .collect();
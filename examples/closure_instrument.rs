@@ -0,0 +1,176 @@
+//! Closure Instrument Example - custom voices without writing a struct
+//!
+//! Builds two voices from closures with `ClosureInstrument` and plays them
+//! through the engine:
+//! - a pluck: a sine with a fast exponential decay and a pitch drop
+//! - a drone: two detuned saws that hold until released
+//!
+//! Controls:
+//! - SPACE = pluck
+//! - D = start the drone
+//! - R = release the drone
+//! - Q = quit
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
+};
+use std::f32::consts::TAU;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+use gooey::engine::{Engine, EngineOutput};
+use gooey::instruments::ClosureInstrument;
+
+const SAMPLE_RATE: f32 = 44100.0;
+
+/// State shared by the pluck's closures
+struct Pluck {
+    phase: f32,
+    pitch: f32,
+    level: f32,
+}
+
+/// State shared by the drone's closures
+struct Drone {
+    phases: [f32; 2],
+    level: f32,
+    target: f32,
+}
+
+fn pluck() -> ClosureInstrument<Pluck> {
+    ClosureInstrument::new(
+        Pluck {
+            phase: 0.0,
+            pitch: 0.0,
+            level: 0.0,
+        },
+        |pluck, _time, velocity| {
+            pluck.phase = 0.0;
+            pluck.pitch = 1.0;
+            pluck.level = velocity;
+        },
+        |pluck, _time| {
+            // Start an octave up and fall to 220 Hz within a few milliseconds
+            pluck.pitch *= 0.995;
+            let frequency = 220.0 * (1.0 + pluck.pitch);
+            pluck.phase = (pluck.phase + frequency / SAMPLE_RATE).fract();
+            pluck.level *= 0.9997;
+            (pluck.phase * TAU).sin() * pluck.level
+        },
+    )
+    .with_active(|pluck| pluck.level > 1e-4)
+}
+
+fn drone() -> ClosureInstrument<Drone> {
+    ClosureInstrument::new(
+        Drone {
+            phases: [0.0; 2],
+            level: 0.0,
+            target: 0.0,
+        },
+        |drone, _time, velocity| drone.target = velocity * 0.3,
+        |drone, _time| {
+            // One-pole glide towards the target level: ~20 ms attack/release
+            drone.level += (drone.target - drone.level) * 0.001;
+            let mut output = 0.0;
+            for (phase, frequency) in drone.phases.iter_mut().zip([55.0, 55.4]) {
+                *phase = (*phase + frequency / SAMPLE_RATE).fract();
+                output += *phase * 2.0 - 1.0;
+            }
+            output * 0.5 * drone.level
+        },
+    )
+    .with_release(|drone, _time| drone.target = 0.0)
+    .with_active(|drone| drone.level > 1e-4 || drone.target > 0.0)
+}
+
+fn render_display(plucks: u32, drone_on: bool) {
+    execute!(io::stdout(), cursor::MoveTo(0, 0), Clear(ClearType::All)).unwrap();
+
+    print!("=== Closure Instrument ===\r\n");
+    print!("\r\n");
+    print!("SPACE = pluck   D = drone   R = release drone   Q = quit\r\n");
+    print!("\r\n");
+    print!("Plucks: {}\r\n", plucks);
+    print!("Drone: {}\r\n", if drone_on { "holding" } else { "off" });
+
+    io::stdout().flush().unwrap();
+}
+
+#[cfg(feature = "native")]
+fn main() -> anyhow::Result<()> {
+    let mut engine = Engine::new(SAMPLE_RATE);
+    engine.add_instrument("pluck", Box::new(pluck()));
+    engine.add_instrument("drone", Box::new(drone()));
+    let audio_engine = Arc::new(Mutex::new(engine));
+
+    let mut engine_output = EngineOutput::new();
+    engine_output.initialize(SAMPLE_RATE)?;
+    engine_output.create_stream_with_engine(audio_engine.clone())?;
+    engine_output.start()?;
+
+    let mut plucks: u32 = 0;
+    let mut drone_on = false;
+    let mut needs_redraw = true;
+
+    execute!(io::stdout(), Clear(ClearType::All), cursor::Hide)?;
+    enable_raw_mode()?;
+
+    let result = loop {
+        if needs_redraw {
+            render_display(plucks, drone_on);
+            needs_redraw = false;
+        }
+
+        if event::poll(std::time::Duration::from_millis(16))? {
+            if let Event::Key(KeyEvent { code, .. }) = event::read()? {
+                match code {
+                    KeyCode::Char(' ') => {
+                        audio_engine
+                            .lock()
+                            .unwrap()
+                            .trigger_instrument_with_velocity("pluck", 1.0)
+                            .unwrap();
+                        plucks += 1;
+                        needs_redraw = true;
+                    }
+                    KeyCode::Char('d') | KeyCode::Char('D') => {
+                        audio_engine
+                            .lock()
+                            .unwrap()
+                            .trigger_instrument_with_velocity("drone", 1.0)
+                            .unwrap();
+                        drone_on = true;
+                        needs_redraw = true;
+                    }
+                    KeyCode::Char('r') | KeyCode::Char('R') => {
+                        audio_engine
+                            .lock()
+                            .unwrap()
+                            .release_instrument("drone")
+                            .unwrap();
+                        drone_on = false;
+                        needs_redraw = true;
+                    }
+                    KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => break Ok(()),
+                    _ => {}
+                }
+            }
+        }
+    };
+
+    // Restore terminal to normal mode
+    execute!(io::stdout(), cursor::Show)?;
+    disable_raw_mode()?;
+    println!("\nQuitting...");
+
+    result
+}
+
+#[cfg(not(feature = "native"))]
+fn main() {
+    println!("This example requires the 'native' feature. Run with: cargo run --example closure_instrument --features native");
+}
@@ -15,7 +15,7 @@ use gooey::effects::{DelayEffect, DelayTiming};
 use gooey::engine::{Engine, EngineOutput, Instrument, Sequencer};
 use gooey::instruments::HiHat;
 
-const DELAY_TIMINGS: [DelayTiming; 9] = [
+const DELAY_TIMINGS: [DelayTiming; 13] = [
     DelayTiming::Whole,
     DelayTiming::Half,
     DelayTiming::Quarter,
@@ -25,6 +25,10 @@ const DELAY_TIMINGS: [DelayTiming; 9] = [
     DelayTiming::QuarterTriplet,
     DelayTiming::EighthTriplet,
     DelayTiming::SixteenthTriplet,
+    DelayTiming::HalfDotted,
+    DelayTiming::QuarterDotted,
+    DelayTiming::EighthDotted,
+    DelayTiming::SixteenthDotted,
 ];
 
 fn timing_name(t: DelayTiming) -> &'static str {
@@ -38,6 +42,10 @@ fn timing_name(t: DelayTiming) -> &'static str {
         DelayTiming::QuarterTriplet => "1/4T",
         DelayTiming::EighthTriplet => "1/8T",
         DelayTiming::SixteenthTriplet => "1/16T",
+        DelayTiming::HalfDotted => "1/2D",
+        DelayTiming::QuarterDotted => "1/4D",
+        DelayTiming::EighthDotted => "1/8D",
+        DelayTiming::SixteenthDotted => "1/16D",
     }
 }
 
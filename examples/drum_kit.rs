@@ -0,0 +1,152 @@
+/* Drum Kit - every kit instrument on one keyboard.
+Plays each drum voice through the default audio output, on its own or under a
+sequenced groove.
+
+Keys:
+- 1-7 = kick, snare, hihat, tom, rimshot, cowbell, shaker
+- SPACE = start / stop the groove
+- Q = quit
+*/
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
+};
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use gooey::engine::{Engine, EngineOutput, Instrument, Sequencer};
+use gooey::instruments::{Cowbell, HiHat, KickDrum, Rimshot, Shaker, SnareDrum, Tom2};
+
+const SAMPLE_RATE: f32 = 44100.0;
+const BPM: f32 = 110.0;
+
+/// Name, key and groove (16 steps) of each voice.
+const VOICES: [(&str, char, &str); 7] = [
+    ("kick", '1', "x.....x.x......."),
+    ("snare", '2', "....x.......x..."),
+    ("hihat", '3', "x.x.x.x.x.x.x.x."),
+    ("tom", '4', "..............x."),
+    ("rimshot", '5', "...x.......x...."),
+    ("cowbell", '6', "........x......."),
+    ("shaker", '7', ".xxx.xxx.xxx.xxx"),
+];
+
+fn voice(name: &str) -> Box<dyn Instrument> {
+    match name {
+        "kick" => Box::new(KickDrum::new(SAMPLE_RATE)),
+        "snare" => Box::new(SnareDrum::new(SAMPLE_RATE)),
+        "hihat" => Box::new(HiHat::new(SAMPLE_RATE)),
+        "tom" => Box::new(Tom2::new(SAMPLE_RATE)),
+        "rimshot" => Box::new(Rimshot::new(SAMPLE_RATE)),
+        "cowbell" => Box::new(Cowbell::new(SAMPLE_RATE)),
+        _ => Box::new(Shaker::new(SAMPLE_RATE)),
+    }
+}
+
+fn render_display(playing: bool, last_hit: Option<&str>) {
+    execute!(io::stdout(), cursor::MoveTo(0, 0), Clear(ClearType::All)).unwrap();
+
+    print!("=== Drum Kit ===\r\n");
+    print!("\r\n");
+    for (name, key, pattern) in VOICES {
+        print!("{} = {:<8} {}\r\n", key, name, pattern);
+    }
+    print!("\r\n");
+    print!("SPACE = groove   Q = quit\r\n");
+    print!("\r\n");
+    print!(
+        "Groove: {}\r\n",
+        if playing { "playing" } else { "stopped" }
+    );
+    print!("Last hit: {}\r\n", last_hit.unwrap_or("-"));
+
+    io::stdout().flush().unwrap();
+}
+
+#[cfg(feature = "native")]
+fn main() -> anyhow::Result<()> {
+    let mut engine = Engine::new(SAMPLE_RATE);
+    engine.set_bpm(BPM);
+    for (name, _, pattern) in VOICES {
+        engine.add_instrument(name, voice(name));
+        let steps = pattern.chars().map(|c| c == 'x').collect();
+        engine.add_sequencer(Sequencer::with_pattern(BPM, SAMPLE_RATE, steps, name));
+    }
+    let audio_engine = Arc::new(Mutex::new(engine));
+
+    let mut engine_output = EngineOutput::new();
+    engine_output.initialize(SAMPLE_RATE)?;
+    engine_output.create_stream_with_engine(audio_engine.clone())?;
+    engine_output.start()?;
+
+    let mut playing = false;
+    let mut last_hit = None;
+    let mut needs_redraw = true;
+
+    execute!(io::stdout(), Clear(ClearType::All), cursor::Hide)?;
+    enable_raw_mode()?;
+
+    let result = loop {
+        if needs_redraw {
+            render_display(playing, last_hit);
+            needs_redraw = false;
+        }
+
+        if !event::poll(Duration::from_millis(16))? {
+            continue;
+        }
+        let Event::Key(KeyEvent {
+            code,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event::read()?
+        else {
+            continue;
+        };
+        match code {
+            KeyCode::Char(' ') => {
+                playing = !playing;
+                let mut engine = audio_engine.lock().unwrap();
+                for index in 0..engine.sequencer_count() {
+                    if let Some(sequencer) = engine.sequencer_mut(index) {
+                        if playing {
+                            sequencer.reset();
+                            sequencer.start();
+                        } else {
+                            sequencer.stop();
+                        }
+                    }
+                }
+                needs_redraw = true;
+            }
+            KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => break Ok(()),
+            KeyCode::Char(c) => {
+                if let Some((name, _, _)) = VOICES.iter().find(|(_, key, _)| *key == c) {
+                    audio_engine
+                        .lock()
+                        .unwrap()
+                        .trigger_instrument(name)
+                        .unwrap();
+                    last_hit = Some(name);
+                    needs_redraw = true;
+                }
+            }
+            _ => {}
+        }
+    };
+
+    execute!(io::stdout(), cursor::Show)?;
+    disable_raw_mode()?;
+    println!("\nQuitting...");
+
+    result
+}
+
+#[cfg(not(feature = "native"))]
+fn main() {
+    println!("This example requires the 'native' feature. Run with: cargo run --example drum_kit --features native,crossterm");
+}
@@ -0,0 +1,50 @@
+/* DSL Playback - play a .gooey program live.
+Parses the program given on the command line (default
+examples/programs/sequencer.gooey), builds its engine and plays it through
+the default audio output until ENTER is pressed.
+
+Usage: cargo run --example dsl_playback -- [program.gooey]
+*/
+
+use std::io;
+use std::sync::{Arc, Mutex};
+
+use gooey::dsl::Program;
+use gooey::engine::EngineOutput;
+
+const SAMPLE_RATE: f32 = 44100.0;
+const DEFAULT_PROGRAM: &str = "examples/programs/sequencer.gooey";
+
+#[cfg(feature = "native")]
+fn main() -> anyhow::Result<()> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_PROGRAM.to_string());
+    let source = std::fs::read_to_string(&path)?;
+    let program = Program::parse(&source).map_err(|e| anyhow::anyhow!("{}: {}", path, e))?;
+    let engine = program
+        .build_engine(SAMPLE_RATE)
+        .map_err(|e| anyhow::anyhow!("{}: {}", path, e))?;
+
+    println!("=== DSL Playback ===");
+    println!("Program: {}", path);
+    println!("BPM: {}", engine.bpm());
+    println!("Sequencers: {}", engine.sequencer_count());
+    println!("\nPress ENTER to quit");
+
+    let mut engine_output = EngineOutput::new();
+    engine_output.initialize(SAMPLE_RATE)?;
+    engine_output.create_stream_with_engine(Arc::new(Mutex::new(engine)))?;
+    engine_output.start()?;
+
+    io::stdin().read_line(&mut String::new())?;
+
+    engine_output.stop()?;
+    println!("Quitting...");
+    Ok(())
+}
+
+#[cfg(not(feature = "native"))]
+fn main() {
+    println!("This example requires the 'native' feature. Run with: cargo run --example dsl_playback --features native");
+}
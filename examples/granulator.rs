@@ -282,7 +282,8 @@ fn main() -> anyhow::Result<()> {
             audio_engine
                 .lock()
                 .unwrap()
-                .trigger_instrument_with_velocity("granulator", current_velocity);
+                .trigger_instrument_with_velocity("granulator", current_velocity)
+                .unwrap();
             trigger_count += 1;
 
             let cloud_ms = granulator.lock().unwrap().cloud_duration_ms();
@@ -341,7 +342,8 @@ fn main() -> anyhow::Result<()> {
                         audio_engine
                             .lock()
                             .unwrap()
-                            .trigger_instrument_with_velocity("granulator", current_velocity);
+                            .trigger_instrument_with_velocity("granulator", current_velocity)
+                            .unwrap();
                         trigger_count += 1;
                         needs_redraw = true;
                     }
@@ -355,7 +357,8 @@ fn main() -> anyhow::Result<()> {
                         audio_engine
                             .lock()
                             .unwrap()
-                            .trigger_instrument_with_velocity("granulator", current_velocity);
+                            .trigger_instrument_with_velocity("granulator", current_velocity)
+                            .unwrap();
                         trigger_count += 1;
                         needs_redraw = true;
                     }
@@ -364,7 +367,8 @@ fn main() -> anyhow::Result<()> {
                         audio_engine
                             .lock()
                             .unwrap()
-                            .trigger_instrument_with_velocity("granulator", current_velocity);
+                            .trigger_instrument_with_velocity("granulator", current_velocity)
+                            .unwrap();
                         trigger_count += 1;
                         needs_redraw = true;
                     }
@@ -373,7 +377,8 @@ fn main() -> anyhow::Result<()> {
                         audio_engine
                             .lock()
                             .unwrap()
-                            .trigger_instrument_with_velocity("granulator", current_velocity);
+                            .trigger_instrument_with_velocity("granulator", current_velocity)
+                            .unwrap();
                         trigger_count += 1;
                         needs_redraw = true;
                     }
@@ -382,7 +387,8 @@ fn main() -> anyhow::Result<()> {
                         audio_engine
                             .lock()
                             .unwrap()
-                            .trigger_instrument_with_velocity("granulator", current_velocity);
+                            .trigger_instrument_with_velocity("granulator", current_velocity)
+                            .unwrap();
                         trigger_count += 1;
                         needs_redraw = true;
                     }
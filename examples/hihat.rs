@@ -247,7 +247,9 @@ fn main() -> anyhow::Result<()> {
                     // Trigger hi-hat
                     KeyCode::Char(' ') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument_with_velocity("hihat", velocity);
+                        engine
+                            .trigger_instrument_with_velocity("hihat", velocity)
+                            .unwrap();
                         trigger_count += 1;
                     }
 
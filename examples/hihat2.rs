@@ -209,7 +209,9 @@ fn main() -> anyhow::Result<()> {
                 match code {
                     KeyCode::Char(' ') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument_with_velocity("hihat2", velocity);
+                        engine
+                            .trigger_instrument_with_velocity("hihat2", velocity)
+                            .unwrap();
                         trigger_count += 1;
                     }
                     KeyCode::Char('q') | KeyCode::Esc => {
@@ -0,0 +1,119 @@
+/* LFO Modulation - hear parameters move under LFOs.
+A hihat and kick groove plays through the default audio output while three
+LFOs sweep their parameters: a one-bar LFO on the hihat decay, a half-bar LFO
+on its tone, and a free-running 0.2 Hz LFO on the kick tuning. The meters show
+each LFO's value; press ENTER to quit.
+*/
+
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use gooey::engine::{Engine, EngineOutput, Lfo, MusicalDivision, Sequencer};
+use gooey::instruments::{HiHat, HiHatConfig, KickConfig, KickDrum};
+
+const SAMPLE_RATE: f32 = 44100.0;
+const BPM: f32 = 120.0;
+
+/// Label, target instrument and parameter of each LFO, in LFO index order
+const ROUTES: [(&str, &str, &str); 3] = [
+    ("1 bar", "hihat", "decay"),
+    ("1/2", "hihat", "tone"),
+    ("0.2 Hz", "kick", "tuning"),
+];
+
+/// Draw `value` (-1..1) as a bar centered in `width` columns.
+fn meter(value: f32, width: usize) -> String {
+    let center = width / 2;
+    let position = ((value.clamp(-1.0, 1.0) + 1.0) * 0.5 * (width - 1) as f32).round() as usize;
+    (0..width)
+        .map(|i| match i {
+            i if i == position => '#',
+            i if i == center => '|',
+            _ => '-',
+        })
+        .collect()
+}
+
+#[cfg(feature = "native")]
+fn main() -> anyhow::Result<()> {
+    let mut engine = Engine::new(SAMPLE_RATE);
+    engine.set_bpm(BPM);
+    engine.add_instrument(
+        "hihat",
+        Box::new(HiHat::with_config(SAMPLE_RATE, HiHatConfig::short())),
+    );
+    engine.add_instrument(
+        "kick",
+        Box::new(KickDrum::with_config(SAMPLE_RATE, KickConfig::punch())),
+    );
+
+    for (instrument, pattern) in [("hihat", "xxxxxxxxxxxxxxxx"), ("kick", "x...x...x...x...")] {
+        let steps = pattern.chars().map(|c| c == 'x').collect();
+        let mut sequencer = Sequencer::with_pattern(BPM, SAMPLE_RATE, steps, instrument);
+        sequencer.start();
+        engine.add_sequencer(sequencer);
+    }
+
+    let lfos = [
+        Lfo::new_synced(MusicalDivision::OneBar, BPM, SAMPLE_RATE),
+        Lfo::new_synced(MusicalDivision::Half, BPM, SAMPLE_RATE),
+        Lfo::new(0.2, SAMPLE_RATE),
+    ];
+    let amounts = [0.8, 0.6, 0.3];
+    for ((lfo, amount), (_, instrument, parameter)) in lfos.into_iter().zip(amounts).zip(ROUTES) {
+        let index = engine.add_lfo(lfo);
+        engine.map_lfo_to_parameter(index, instrument, parameter, amount)?;
+    }
+
+    let audio_engine = Arc::new(Mutex::new(engine));
+    let mut engine_output = EngineOutput::new();
+    engine_output.initialize(SAMPLE_RATE)?;
+    engine_output.create_stream_with_engine(audio_engine.clone())?;
+    engine_output.start()?;
+
+    // ENTER on stdin ends the demo
+    let quit = Arc::new(AtomicBool::new(false));
+    std::thread::spawn({
+        let quit = quit.clone();
+        move || {
+            let _ = io::stdin().read_line(&mut String::new());
+            quit.store(true, Ordering::Relaxed);
+        }
+    });
+
+    println!("=== LFO Modulation ===  (ENTER to quit)\n");
+    while !quit.load(Ordering::Relaxed) {
+        let line = {
+            let engine = audio_engine.lock().unwrap();
+            ROUTES
+                .iter()
+                .enumerate()
+                .filter_map(|(index, (label, instrument, parameter))| {
+                    let lfo = engine.lfo(index)?;
+                    Some(format!(
+                        "{:>6} {}.{:<7} {}",
+                        label,
+                        instrument,
+                        parameter,
+                        meter(lfo.value_at(0.0), 21)
+                    ))
+                })
+                .collect::<Vec<_>>()
+                .join("  ")
+        };
+        print!("\r{}", line);
+        io::stdout().flush()?;
+        std::thread::sleep(Duration::from_millis(33));
+    }
+
+    engine_output.stop()?;
+    println!("\nQuitting...");
+    Ok(())
+}
+
+#[cfg(not(feature = "native"))]
+fn main() {
+    println!("This example requires the 'native' feature. Run with: cargo run --example lfo_modulation --features native");
+}
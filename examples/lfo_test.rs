@@ -116,25 +116,25 @@ fn main() -> anyhow::Result<()> {
                 match code {
                     KeyCode::Char('k') | KeyCode::Char('K') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument("kick");
+                        engine.trigger_instrument("kick").unwrap();
                         print!("K");
                         io::stdout().flush().unwrap();
                     }
                     KeyCode::Char('s') | KeyCode::Char('S') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument("snare");
+                        engine.trigger_instrument("snare").unwrap();
                         print!("S");
                         io::stdout().flush().unwrap();
                     }
                     KeyCode::Char('h') | KeyCode::Char('H') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument("hihat");
+                        engine.trigger_instrument("hihat").unwrap();
                         print!("H");
                         io::stdout().flush().unwrap();
                     }
                     KeyCode::Char('t') | KeyCode::Char('T') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument("tom");
+                        engine.trigger_instrument("tom").unwrap();
                         print!("T");
                         io::stdout().flush().unwrap();
                     }
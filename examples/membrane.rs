@@ -245,7 +245,7 @@ fn main() -> anyhow::Result<()> {
                     // Trigger
                     KeyCode::Char(' ') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument("membrane");
+                        engine.trigger_instrument("membrane").unwrap();
                         trigger_count += 1;
                         needs_redraw = true;
                     }
@@ -0,0 +1,102 @@
+/* MIDI Input - play the drum kit from a MIDI controller.
+Connects to the first MIDI input port and maps General MIDI drum notes to the
+kit, with note velocity as hit velocity. Press ENTER to quit.
+
+Notes:
+- 35/36 = kick, 38/40 = snare, 42/44/46 = hihat
+- 41/43/45/47/48/50 = tom, 37 = rimshot, 56 = cowbell, 69/70 = shaker
+*/
+
+use std::io;
+use std::sync::{Arc, Mutex};
+
+use gooey::engine::{Engine, EngineOutput, Instrument};
+use gooey::instruments::{Cowbell, HiHat, KickDrum, Rimshot, Shaker, SnareDrum, Tom2};
+
+#[cfg(feature = "midi")]
+use midir::MidiInput;
+
+const SAMPLE_RATE: f32 = 44100.0;
+
+/// The kit instrument a General MIDI drum note plays, if any.
+fn instrument_for_note(note: u8) -> Option<&'static str> {
+    match note {
+        35 | 36 => Some("kick"),
+        38 | 40 => Some("snare"),
+        42 | 44 | 46 => Some("hihat"),
+        41 | 43 | 45 | 47 | 48 | 50 => Some("tom"),
+        37 => Some("rimshot"),
+        56 => Some("cowbell"),
+        69 | 70 => Some("shaker"),
+        _ => None,
+    }
+}
+
+fn kit() -> Vec<(&'static str, Box<dyn Instrument>)> {
+    vec![
+        ("kick", Box::new(KickDrum::new(SAMPLE_RATE))),
+        ("snare", Box::new(SnareDrum::new(SAMPLE_RATE))),
+        ("hihat", Box::new(HiHat::new(SAMPLE_RATE))),
+        ("tom", Box::new(Tom2::new(SAMPLE_RATE))),
+        ("rimshot", Box::new(Rimshot::new(SAMPLE_RATE))),
+        ("cowbell", Box::new(Cowbell::new(SAMPLE_RATE))),
+        ("shaker", Box::new(Shaker::new(SAMPLE_RATE))),
+    ]
+}
+
+#[cfg(all(feature = "native", feature = "midi"))]
+fn main() -> anyhow::Result<()> {
+    let mut engine = Engine::new(SAMPLE_RATE);
+    for (name, instrument) in kit() {
+        engine.add_instrument(name, instrument);
+    }
+    let audio_engine = Arc::new(Mutex::new(engine));
+
+    let midi_in = MidiInput::new("libgooey-midi-input")?;
+    let ports = midi_in.ports();
+    let Some(port) = ports.first() else {
+        anyhow::bail!("No MIDI input devices found");
+    };
+    for (index, port) in ports.iter().enumerate() {
+        println!("MIDI port {}: {}", index, midi_in.port_name(port)?);
+    }
+    println!("Connecting to MIDI: {}", midi_in.port_name(port)?);
+
+    let mut engine_output = EngineOutput::new();
+    engine_output.initialize(SAMPLE_RATE)?;
+    engine_output.create_stream_with_engine(audio_engine.clone())?;
+    engine_output.start()?;
+
+    let midi_engine = audio_engine.clone();
+    let _connection = midi_in
+        .connect(
+            port,
+            "midi-input",
+            move |_, msg, _| {
+                // Note On with velocity > 0
+                if msg.len() >= 3 && (msg[0] & 0xF0) == 0x90 && msg[2] > 0 {
+                    if let Some(name) = instrument_for_note(msg[1]) {
+                        midi_engine
+                            .lock()
+                            .unwrap()
+                            .trigger_instrument_with_velocity(name, msg[2] as f32 / 127.0)
+                            .unwrap();
+                    }
+                }
+            },
+            (),
+        )
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    println!("\nPlay some drum notes. Press ENTER to quit");
+    io::stdin().read_line(&mut String::new())?;
+
+    engine_output.stop()?;
+    println!("Quitting...");
+    Ok(())
+}
+
+#[cfg(not(all(feature = "native", feature = "midi")))]
+fn main() {
+    println!("This example requires the 'native' and 'midi' features. Run with: cargo run --example midi_input --features native,midi");
+}
@@ -0,0 +1,52 @@
+/* Offline Render - bounce a .gooey program to a WAV file.
+Builds the engine for a DSL program and renders a number of bars faster than
+real time, without an audio device.
+
+Usage: cargo run --example offline_render --features bounce -- [program.gooey] [out.wav] [bars]
+*/
+
+use std::path::Path;
+
+use gooey::bounce::{bounce_to_wav, BounceLength, WavConfig};
+use gooey::dsl::Program;
+
+const SAMPLE_RATE: f32 = 44100.0;
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let program_path = args
+        .next()
+        .unwrap_or_else(|| "examples/programs/sequencer.gooey".to_string());
+    let output_path = args.next().unwrap_or_else(|| "render.wav".to_string());
+    let bars = args.next().and_then(|b| b.parse().ok()).unwrap_or(4);
+
+    let source = std::fs::read_to_string(&program_path).unwrap_or_else(|e| {
+        eprintln!("Failed to read {}: {}", program_path, e);
+        std::process::exit(1);
+    });
+    let mut engine = Program::parse(&source)
+        .and_then(|program| program.build_engine(SAMPLE_RATE))
+        .unwrap_or_else(|e| {
+            eprintln!("{}: {}", program_path, e);
+            std::process::exit(1);
+        });
+
+    println!(
+        "Rendering {} bars of {} at {} BPM...",
+        bars,
+        program_path,
+        engine.bpm()
+    );
+    match bounce_to_wav(
+        &mut engine,
+        BounceLength::Bars(bars),
+        Path::new(&output_path),
+        WavConfig::default(),
+    ) {
+        Ok(()) => println!("Wrote {}", output_path),
+        Err(e) => {
+            eprintln!("Bounce failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
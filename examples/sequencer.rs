@@ -16,6 +16,7 @@ use gooey::engine::{
     Engine, EngineOutput, Instrument, Lfo, Modulatable, MusicalDivision, Sequencer,
 };
 use gooey::instruments::HiHat;
+use gooey::GooeyError;
 
 const LFO_DIVISIONS: [MusicalDivision; 8] = [
     MusicalDivision::FourBars,
@@ -81,7 +82,7 @@ impl Modulatable for SharedHiHat {
         self.0.lock().unwrap().modulatable_parameters()
     }
 
-    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), String> {
+    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), GooeyError> {
         self.0.lock().unwrap().apply_modulation(parameter, value)
     }
 
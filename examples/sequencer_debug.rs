@@ -0,0 +1,52 @@
+/* Sequencer Debug - native step-sequencer window for DSP work without the web UI.
+Click steps to toggle them, Space to start/stop, Escape to quit.
+*/
+
+use std::sync::{Arc, Mutex};
+
+use gooey::engine::{Engine, EngineOutput, Sequencer, SequencerDisplay};
+use gooey::instruments::{HiHat, KickDrum, SnareDrum, TomDrum};
+
+/// Playhead lookahead: roughly one output buffer at 44.1 kHz
+const LOOKAHEAD_SAMPLES: u64 = 512;
+
+fn main() -> anyhow::Result<()> {
+    let sample_rate = 44100.0;
+    let bpm = 120.0;
+
+    let mut engine = Engine::new(sample_rate);
+    engine.set_bpm(bpm);
+    engine.add_instrument("kick", Box::new(KickDrum::new(sample_rate)));
+    engine.add_instrument("snare", Box::new(SnareDrum::new(sample_rate)));
+    engine.add_instrument("hihat", Box::new(HiHat::new(sample_rate)));
+    engine.add_instrument("tom", Box::new(TomDrum::new(sample_rate)));
+
+    let lanes = [
+        ("kick", "x...x...x...x..."),
+        ("snare", "....x.......x..."),
+        ("hihat", "x.x.x.x.x.x.x.x."),
+        ("tom", "..............x."),
+    ];
+    for (name, steps) in lanes {
+        let pattern = steps.chars().map(|c| c == 'x').collect();
+        engine.add_sequencer(Sequencer::with_pattern(bpm, sample_rate, pattern, name));
+    }
+
+    let audio_engine = Arc::new(Mutex::new(engine));
+
+    let mut engine_output = EngineOutput::new();
+    engine_output.initialize(sample_rate)?;
+    engine_output.create_stream_with_engine(audio_engine.clone())?;
+    engine_output.start()?;
+
+    let mut display = SequencerDisplay::new(audio_engine, 960, 320, LOOKAHEAD_SAMPLES)
+        .map_err(|e| anyhow::anyhow!("Failed to create sequencer display: {}", e))?;
+
+    while !display.should_close() {
+        display.update();
+        std::thread::sleep(std::time::Duration::from_millis(16));
+    }
+
+    engine_output.stop()?;
+    Ok(())
+}
@@ -585,7 +585,9 @@ fn main() -> anyhow::Result<()> {
                     let mut engine = audio_engine.lock().unwrap();
                     // Convert MIDI velocity (0-127) to normalized (0.0-1.0)
                     let vel_normalized = velocity as f32 / 127.0;
-                    engine.trigger_instrument_with_velocity("snare", vel_normalized);
+                    engine
+                        .trigger_instrument_with_velocity("snare", vel_normalized)
+                        .unwrap();
                     trigger_count += 1;
                     current_velocity = vel_normalized;
                     needs_redraw = true;
@@ -715,7 +717,9 @@ fn main() -> anyhow::Result<()> {
                     // Trigger at current velocity
                     KeyCode::Char(' ') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument_with_velocity("snare", current_velocity);
+                        engine
+                            .trigger_instrument_with_velocity("snare", current_velocity)
+                            .unwrap();
                         trigger_count += 1;
                         needs_redraw = true;
                     }
@@ -723,28 +727,36 @@ fn main() -> anyhow::Result<()> {
                     // Velocity-specific triggers
                     KeyCode::Char('z') | KeyCode::Char('Z') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument_with_velocity("snare", 0.25);
+                        engine
+                            .trigger_instrument_with_velocity("snare", 0.25)
+                            .unwrap();
                         trigger_count += 1;
                         current_velocity = 0.25;
                         needs_redraw = true;
                     }
                     KeyCode::Char('x') | KeyCode::Char('X') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument_with_velocity("snare", 0.50);
+                        engine
+                            .trigger_instrument_with_velocity("snare", 0.50)
+                            .unwrap();
                         trigger_count += 1;
                         current_velocity = 0.50;
                         needs_redraw = true;
                     }
                     KeyCode::Char('c') | KeyCode::Char('C') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument_with_velocity("snare", 0.75);
+                        engine
+                            .trigger_instrument_with_velocity("snare", 0.75)
+                            .unwrap();
                         trigger_count += 1;
                         current_velocity = 0.75;
                         needs_redraw = true;
                     }
                     KeyCode::Char('v') | KeyCode::Char('V') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument_with_velocity("snare", 1.0);
+                        engine
+                            .trigger_instrument_with_velocity("snare", 1.0)
+                            .unwrap();
                         trigger_count += 1;
                         current_velocity = 1.0;
                         needs_redraw = true;
@@ -29,7 +29,7 @@ struct ParamInfo {
     max: f32,
 }
 
-const PARAM_INFO: [ParamInfo; 8] = [
+const PARAM_INFO: [ParamInfo; 11] = [
     ParamInfo {
         name: "tune",
         coarse_step: 10.0,
@@ -86,6 +86,27 @@ const PARAM_INFO: [ParamInfo; 8] = [
         min: 0.0,
         max: 1.0,
     },
+    ParamInfo {
+        name: "tri_level",
+        coarse_step: 10.0,
+        fine_step: 1.0,
+        min: 0.0,
+        max: 100.0,
+    },
+    ParamInfo {
+        name: "morph_lvl",
+        coarse_step: 10.0,
+        fine_step: 1.0,
+        min: 0.0,
+        max: 100.0,
+    },
+    ParamInfo {
+        name: "mem_gain",
+        coarse_step: 10.0,
+        fine_step: 1.0,
+        min: 0.0,
+        max: 100.0,
+    },
 ];
 
 // Wrapper to share Tom2 between audio thread and main thread
@@ -120,6 +141,9 @@ fn get_param_value(tom: &Tom2, index: usize) -> f32 {
         5 => tom.membrane(),
         6 => tom.membrane_q(),
         7 => tom.tuning(),
+        8 => tom.triangle_level(),
+        9 => tom.morph_level(),
+        10 => tom.membrane_gain(),
         _ => 0.0,
     }
 }
@@ -134,6 +158,9 @@ fn set_param_value(tom: &mut Tom2, index: usize, value: f32) {
         5 => tom.set_membrane(value),
         6 => tom.set_membrane_q(value),
         7 => tom.set_tuning(value),
+        8 => tom.set_triangle_level(value),
+        9 => tom.set_morph_level(value),
+        10 => tom.set_membrane_gain(value),
         _ => {}
     }
 }
@@ -303,7 +330,9 @@ fn main() -> anyhow::Result<()> {
                     // Trigger at current velocity
                     KeyCode::Char(' ') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument_with_velocity("tom2", current_velocity);
+                        engine
+                            .trigger_instrument_with_velocity("tom2", current_velocity)
+                            .unwrap();
                         trigger_count += 1;
                         needs_redraw = true;
                     }
@@ -311,28 +340,36 @@ fn main() -> anyhow::Result<()> {
                     // Velocity-specific triggers
                     KeyCode::Char('z') | KeyCode::Char('Z') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument_with_velocity("tom2", 0.25);
+                        engine
+                            .trigger_instrument_with_velocity("tom2", 0.25)
+                            .unwrap();
                         trigger_count += 1;
                         current_velocity = 0.25;
                         needs_redraw = true;
                     }
                     KeyCode::Char('x') | KeyCode::Char('X') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument_with_velocity("tom2", 0.50);
+                        engine
+                            .trigger_instrument_with_velocity("tom2", 0.50)
+                            .unwrap();
                         trigger_count += 1;
                         current_velocity = 0.50;
                         needs_redraw = true;
                     }
                     KeyCode::Char('c') | KeyCode::Char('C') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument_with_velocity("tom2", 0.75);
+                        engine
+                            .trigger_instrument_with_velocity("tom2", 0.75)
+                            .unwrap();
                         trigger_count += 1;
                         current_velocity = 0.75;
                         needs_redraw = true;
                     }
                     KeyCode::Char('v') | KeyCode::Char('V') => {
                         let mut engine = audio_engine.lock().unwrap();
-                        engine.trigger_instrument_with_velocity("tom2", 1.0);
+                        engine
+                            .trigger_instrument_with_velocity("tom2", 1.0)
+                            .unwrap();
                         trigger_count += 1;
                         current_velocity = 1.0;
                         needs_redraw = true;
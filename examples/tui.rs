@@ -0,0 +1,421 @@
+/* Gooey TUI - step grid, mixer and parameters on one screen.
+The reference frontend: it builds its kit through a DslSession and drives the
+Engine with the same calls a host would make, so new engine features can be
+tried here first.
+
+Keys:
+- Arrows / HJKL = move the cursor (rows are instruments, columns are steps)
+- SPACE = toggle the step under the cursor
+- P = play / stop
+- [ ] = BPM down / up (shift for 10)
+- M / S = mute / solo the selected instrument
+- TAB = next parameter, - / = = lower / raise it
+- : = type a DSL statement (ENTER runs it, ESC cancels)
+- Q = quit
+*/
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    execute, queue,
+    style::Print,
+    terminal::{self, disable_raw_mode, enable_raw_mode, Clear, ClearType},
+};
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use gooey::dsl::DslSession;
+use gooey::engine::{Engine, EngineOutput};
+use gooey::GooeyError;
+
+const SAMPLE_RATE: f32 = 44100.0;
+const STEPS: usize = 16;
+
+/// One editable instrument parameter, written to the engine as
+/// `set <track>.<field> <value>`.
+struct Param {
+    field: &'static str,
+    unit: &'static str,
+    min: f32,
+    max: f32,
+    step: f32,
+    value: f32,
+}
+
+impl Param {
+    const fn new(
+        field: &'static str,
+        unit: &'static str,
+        range: (f32, f32),
+        step: f32,
+        value: f32,
+    ) -> Self {
+        Self {
+            field,
+            unit,
+            min: range.0,
+            max: range.1,
+            step,
+            value,
+        }
+    }
+}
+
+/// A grid row: an instrument, its sequencer and its parameters.
+struct Track {
+    name: &'static str,
+    inst: &'static str,
+    pattern: &'static str,
+    params: Vec<Param>,
+}
+
+fn kit() -> Vec<Track> {
+    vec![
+        Track {
+            name: "kick",
+            inst: "inst kick kick punch",
+            pattern: "x...x...x...x...",
+            params: vec![
+                Param::new("frequency", "Hz", (30.0, 120.0), 1.0, 50.0),
+                Param::new("decay", "s", (0.01, 4.0), 0.05, 0.5),
+                Param::new("drive", "", (0.0, 1.0), 0.05, 0.0),
+                Param::new("volume", "", (0.0, 1.0), 0.05, 0.8),
+            ],
+        },
+        Track {
+            name: "snare",
+            inst: "inst snare snare tight",
+            pattern: "....x.......x...",
+            params: vec![
+                Param::new("frequency", "Hz", (100.0, 600.0), 5.0, 200.0),
+                Param::new("decay", "s", (0.05, 3.5), 0.05, 0.3),
+                Param::new("noise", "", (0.0, 1.0), 0.05, 0.6),
+                Param::new("volume", "", (0.0, 1.0), 0.05, 0.8),
+            ],
+        },
+        Track {
+            name: "hat",
+            inst: "inst hat hihat closed",
+            pattern: "x.x.x.x.x.x.x.xx",
+            params: vec![
+                Param::new("tone", "Hz", (500.0, 10000.0), 100.0, 6000.0),
+                Param::new("decay", "ms", (0.5, 4000.0), 10.0, 80.0),
+                Param::new("pitch", "Hz", (3500.0, 10000.0), 100.0, 7000.0),
+                Param::new("volume", "", (0.0, 1.0), 0.05, 0.7),
+            ],
+        },
+        Track {
+            name: "tom",
+            inst: "inst tom tom mid",
+            pattern: "..........x..x..",
+            params: vec![
+                Param::new("frequency", "Hz", (60.0, 300.0), 5.0, 120.0),
+                Param::new("decay", "s", (0.05, 2.0), 0.05, 0.4),
+                Param::new("punch", "", (0.0, 1.0), 0.05, 0.4),
+                Param::new("volume", "", (0.0, 1.0), 0.05, 0.8),
+            ],
+        },
+    ]
+}
+
+struct App {
+    engine: Arc<Mutex<Engine>>,
+    session: DslSession,
+    tracks: Vec<Track>,
+    row: usize,
+    col: usize,
+    param: usize,
+    playing: bool,
+    /// DSL statement being typed after `:`
+    input: Option<String>,
+    status: String,
+}
+
+impl App {
+    fn new(engine: Arc<Mutex<Engine>>) -> Result<Self, GooeyError> {
+        let mut app = Self {
+            engine,
+            session: DslSession::new(),
+            tracks: kit(),
+            row: 0,
+            col: 0,
+            param: 0,
+            playing: false,
+            input: None,
+            status: String::from("ready"),
+        };
+
+        // Sequencers are added in track order, so track i drives sequencer i
+        let mut setup = vec![String::from("bpm 120")];
+        for track in &app.tracks {
+            setup.push(track.inst.to_string());
+            setup.push(format!("seq {} {} stop", track.name, track.pattern));
+            for param in &track.params {
+                setup.push(format!(
+                    "set {}.{} {}",
+                    track.name, param.field, param.value
+                ));
+            }
+        }
+        for statement in &setup {
+            app.execute(statement)?;
+        }
+        Ok(app)
+    }
+
+    fn execute(&mut self, statement: &str) -> Result<(), GooeyError> {
+        let mut engine = self.engine.lock().unwrap();
+        self.session.execute(&mut engine, statement)
+    }
+
+    fn toggle_step(&mut self) {
+        let mut engine = self.engine.lock().unwrap();
+        if let Some(sequencer) = engine.sequencer_mut(self.row) {
+            let enabled = sequencer.get_step_enabled(self.col);
+            sequencer.set_step(self.col, !enabled);
+        }
+    }
+
+    fn toggle_play(&mut self) {
+        self.playing = !self.playing;
+        let mut engine = self.engine.lock().unwrap();
+        for index in 0..engine.sequencer_count() {
+            if let Some(sequencer) = engine.sequencer_mut(index) {
+                if self.playing {
+                    sequencer.reset();
+                    sequencer.start();
+                } else {
+                    sequencer.stop();
+                }
+            }
+        }
+    }
+
+    fn nudge_bpm(&mut self, delta: f32) {
+        let bpm = (self.engine.lock().unwrap().bpm() + delta).clamp(40.0, 300.0);
+        self.report(format!("bpm {}", bpm));
+    }
+
+    fn toggle_mute(&mut self, solo: bool) {
+        let name = self.tracks[self.row].name;
+        let mut engine = self.engine.lock().unwrap();
+        if solo {
+            let soloed = engine.instrument_soloed(name);
+            engine.set_instrument_soloed(name, !soloed);
+        } else {
+            let muted = engine.instrument_muted(name);
+            engine.set_instrument_muted(name, !muted);
+        }
+    }
+
+    fn nudge_param(&mut self, direction: f32) {
+        let track = &mut self.tracks[self.row];
+        let param = &mut track.params[self.param];
+        param.value = (param.value + direction * param.step).clamp(param.min, param.max);
+        let statement = format!("set {}.{} {}", track.name, param.field, param.value);
+        self.report(statement);
+    }
+
+    /// Run `statement` and show how it went on the status line.
+    fn report(&mut self, statement: String) {
+        self.status = match self.execute(&statement) {
+            Ok(()) => statement,
+            Err(err) => err.to_string(),
+        };
+    }
+
+    /// Handle a key press; false quits.
+    fn handle_key(&mut self, code: KeyCode) -> bool {
+        if let Some(input) = self.input.as_mut() {
+            match code {
+                KeyCode::Enter => {
+                    let statement = std::mem::take(input);
+                    self.input = None;
+                    self.report(statement);
+                }
+                KeyCode::Esc => self.input = None,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return true;
+        }
+
+        let param_count = self.tracks[self.row].params.len();
+        match code {
+            KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => return false,
+            KeyCode::Up | KeyCode::Char('k') => self.row = self.row.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.row = (self.row + 1).min(self.tracks.len() - 1)
+            }
+            KeyCode::Left | KeyCode::Char('h') => self.col = self.col.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => self.col = (self.col + 1).min(STEPS - 1),
+            KeyCode::Char(' ') => self.toggle_step(),
+            KeyCode::Char('p') | KeyCode::Char('P') => self.toggle_play(),
+            KeyCode::Char('[') => self.nudge_bpm(-1.0),
+            KeyCode::Char(']') => self.nudge_bpm(1.0),
+            KeyCode::Char('{') => self.nudge_bpm(-10.0),
+            KeyCode::Char('}') => self.nudge_bpm(10.0),
+            KeyCode::Char('m') | KeyCode::Char('M') => self.toggle_mute(false),
+            KeyCode::Char('s') | KeyCode::Char('S') => self.toggle_mute(true),
+            KeyCode::Tab => self.param = (self.param + 1) % param_count,
+            KeyCode::Char('-') => self.nudge_param(-1.0),
+            KeyCode::Char('=') | KeyCode::Char('+') => self.nudge_param(1.0),
+            KeyCode::Char(':') => self.input = Some(String::new()),
+            _ => {}
+        }
+        self.param = self.param.min(self.tracks[self.row].params.len() - 1);
+        true
+    }
+
+    fn lines(&self) -> Vec<String> {
+        let engine = self.engine.lock().unwrap();
+        let mut lines = Vec::new();
+
+        lines.push(format!(
+            "=== Gooey ===   {}   {:.0} BPM",
+            if self.playing { "PLAYING" } else { "stopped" },
+            engine.bpm()
+        ));
+        lines.push(String::from(
+            "arrows move  SPACE step  P play  [ ] bpm  M mute  S solo  TAB/-/= params  : dsl  Q quit",
+        ));
+        lines.push(String::new());
+
+        let mut header = String::from("              ");
+        for step in 0..STEPS {
+            header.push_str(&format!("{:^3}", step + 1));
+        }
+        lines.push(header);
+
+        for (row, track) in self.tracks.iter().enumerate() {
+            let Some(sequencer) = engine.sequencer(row) else {
+                continue;
+            };
+            let mut line = format!(
+                "{} {:<6} {}{}  ",
+                if row == self.row { '>' } else { ' ' },
+                track.name,
+                if engine.instrument_muted(track.name) {
+                    'M'
+                } else {
+                    '-'
+                },
+                if engine.instrument_soloed(track.name) {
+                    'S'
+                } else {
+                    '-'
+                },
+            );
+            for step in 0..STEPS {
+                let mark = if sequencer.get_step_enabled(step) {
+                    'x'
+                } else {
+                    '.'
+                };
+                let cell = if row == self.row && step == self.col {
+                    format!("[{}]", mark)
+                } else {
+                    format!(" {} ", mark)
+                };
+                line.push_str(&cell);
+            }
+            lines.push(line);
+        }
+
+        let mut playhead = String::from("              ");
+        if let Some(sequencer) = engine.sequencer(0).filter(|seq| seq.is_running()) {
+            playhead.push_str(&"   ".repeat(sequencer.current_step() % STEPS));
+            playhead.push_str(" ^ ");
+        }
+        lines.push(playhead);
+        lines.push(String::new());
+
+        let track = &self.tracks[self.row];
+        lines.push(format!("{} parameters:", track.name));
+        for (index, param) in track.params.iter().enumerate() {
+            lines.push(format!(
+                "{} {:<10} {:>8.2} {}",
+                if index == self.param { '>' } else { ' ' },
+                param.field,
+                param.value,
+                param.unit
+            ));
+        }
+        lines.push(String::new());
+
+        match &self.input {
+            Some(input) => lines.push(format!(": {}_", input)),
+            None => lines.push(self.status.clone()),
+        }
+        lines
+    }
+
+    fn draw(&self, out: &mut impl Write) -> io::Result<()> {
+        for (row, line) in self.lines().iter().enumerate() {
+            queue!(
+                out,
+                cursor::MoveTo(0, row as u16),
+                Print(line),
+                Clear(ClearType::UntilNewLine)
+            )?;
+        }
+        queue!(out, Clear(ClearType::FromCursorDown))?;
+        out.flush()
+    }
+}
+
+#[cfg(feature = "native")]
+fn main() -> anyhow::Result<()> {
+    let engine = Arc::new(Mutex::new(Engine::new(SAMPLE_RATE)));
+    let mut app = App::new(engine.clone())?;
+
+    let mut engine_output = EngineOutput::new();
+    engine_output.initialize(SAMPLE_RATE)?;
+    engine_output.create_stream_with_engine(engine)?;
+    engine_output.start()?;
+
+    let mut out = io::stdout();
+    execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+    enable_raw_mode()?;
+
+    let result = loop {
+        if let Err(err) = app.draw(&mut out) {
+            break Err(err.into());
+        }
+
+        // Redraw at ~30 fps so the playhead moves
+        match event::poll(Duration::from_millis(33)) {
+            Ok(false) => continue,
+            Ok(true) => {}
+            Err(err) => break Err(err.into()),
+        }
+        match event::read() {
+            Ok(Event::Key(KeyEvent {
+                code,
+                kind: KeyEventKind::Press,
+                ..
+            })) => {
+                if !app.handle_key(code) {
+                    break Ok(());
+                }
+            }
+            Ok(_) => {}
+            Err(err) => break Err(err.into()),
+        }
+    };
+
+    disable_raw_mode()?;
+    execute!(out, cursor::Show, terminal::LeaveAlternateScreen)?;
+    engine_output.stop()?;
+
+    result
+}
+
+#[cfg(not(feature = "native"))]
+fn main() {
+    println!("This example requires the 'native' feature. Run with: cargo run --example tui --features native,crossterm");
+}
@@ -0,0 +1,379 @@
+// Generated from the gooey parameter registry (param_info::typescript_definitions).
+// Do not edit; regenerate with `cargo test --test param_info --features regenerate-goldens`.
+
+export const enum Instrument {
+  Kick = 0,
+  Snare = 1,
+  Hihat = 2,
+  Tom = 3,
+  Bass = 4,
+  FmSnap = 5,
+  Rimshot = 6,
+  Cowbell = 7,
+  Shaker = 8,
+}
+
+export const enum ParamUnit {
+  Normalized = 0,
+  Hz = 1,
+  Seconds = 2,
+  Milliseconds = 3,
+  Semitones = 4,
+  Ratio = 5,
+  Cents = 6,
+  Choice = 7,
+}
+
+export const enum KickParam {
+  Frequency = 0,
+  Punch = 1,
+  Sub = 2,
+  Click = 3,
+  Decay = 4,
+  PitchEnvelope = 5,
+  Volume = 6,
+  Tuning = 7,
+}
+
+/** Kick parameters in setter space; omitted fields are left unchanged. */
+export interface KickParams {
+  /** 0-1 maps to 30-120 Hz, default 0.22 */
+  frequency?: number;
+  /** 0-1, default 0 */
+  punch?: number;
+  /** 0-1, default 1 */
+  sub?: number;
+  /** 0-1, default 0 */
+  click?: number;
+  /** 0-1 maps to 0.01-4 s, default 0.12 */
+  decay?: number;
+  /** 0-1, default 0.7 */
+  pitchEnvelope?: number;
+  /** 0-1, default 0.85 */
+  volume?: number;
+  /** 0-1 maps to -12-12 st, default 0.5 */
+  tuning?: number;
+}
+
+export const enum SnareParam {
+  Frequency = 0,
+  Decay = 1,
+  Brightness = 2,
+  Volume = 3,
+  Tonal = 4,
+  Noise = 5,
+  PitchDrop = 6,
+  TonalDecay = 7,
+  NoiseDecay = 8,
+  NoiseTailDecay = 9,
+  FilterCutoff = 10,
+  FilterResonance = 11,
+  FilterType = 12,
+  Xfade = 13,
+  PhaseModAmount = 14,
+  Overdrive = 15,
+  AmpDecay = 16,
+  AmpDecayCurve = 17,
+  TonalDecayCurve = 18,
+  Tuning = 19,
+  NoiseColor = 20,
+  CrackVelvet = 21,
+}
+
+/** Snare parameters in setter space; omitted fields are left unchanged. */
+export interface SnareParams {
+  /** 0-1 maps to 100-600 Hz, default 0.2 */
+  frequency?: number;
+  /** 0-1 maps to 0.05-3.5 s, default 0.029 */
+  decay?: number;
+  /** 0-1, default 0.5 */
+  brightness?: number;
+  /** 0-1, default 0.8 */
+  volume?: number;
+  /** 0-1, default 0.4 */
+  tonal?: number;
+  /** 0-1, default 0.7 */
+  noise?: number;
+  /** 0-1, default 0.3 */
+  pitchDrop?: number;
+  /** 0-1 maps to 0-3.5 s, default 0.0232 */
+  tonalDecay?: number;
+  /** 0-1 maps to 0-3.5 s, default 0.0174 */
+  noiseDecay?: number;
+  /** 0-1 maps to 0-3.5 s, default 0.029 */
+  noiseTailDecay?: number;
+  /** 0-1 maps to 100-10000 Hz, default 0.495 */
+  filterCutoff?: number;
+  /** 0-1 maps to 0.5-10, default 0.053 */
+  filterResonance?: number;
+  /** Choice 0-3, default 1 */
+  filterType?: number;
+  /** 0-1, default 0.5 */
+  xfade?: number;
+  /** 0-1, default 0 */
+  phaseModAmount?: number;
+  /** 0-1, default 0 */
+  overdrive?: number;
+  /** 0-1 maps to 0-4 s, default 0.125 */
+  ampDecay?: number;
+  /** 0-1 maps to 0.1-10, default 0.02 */
+  ampDecayCurve?: number;
+  /** 0-1 maps to 0.1-10, default 0.091 */
+  tonalDecayCurve?: number;
+  /** 0-1 maps to -12-12 st, default 0.5 */
+  tuning?: number;
+  /** 0-1, default 0 */
+  noiseColor?: number;
+  /** Choice 0-1, default 0 */
+  crackVelvet?: number;
+}
+
+export const enum HihatParam {
+  Pitch = 0,
+  Decay = 1,
+  Attack = 2,
+  Tone = 3,
+  Volume = 4,
+  Tuning = 5,
+  VelocityToLevel = 6,
+  VelocityToDecay = 7,
+  VelocityToTone = 8,
+  OpenDecay = 9,
+  Articulation = 10,
+  Mode = 11,
+}
+
+/** Hihat parameters in setter space; omitted fields are left unchanged. */
+export interface HihatParams {
+  /** 0-1 maps to 3500-10000 Hz, default 0.76 */
+  pitch?: number;
+  /** 0-1 maps to 0.5-4000 ms, default 0.05 */
+  decay?: number;
+  /** 0-1 maps to 0.5-200 ms, default 0 */
+  attack?: number;
+  /** 0-1 maps to 500-10000 Hz, default 1 */
+  tone?: number;
+  /** 0-1, default 1 */
+  volume?: number;
+  /** 0-1 maps to -12-12 st, default 0.5 */
+  tuning?: number;
+  /** 0-1, default 1 */
+  velocityToLevel?: number;
+  /** 0-1, default 0 */
+  velocityToDecay?: number;
+  /** 0-1, default 0 */
+  velocityToTone?: number;
+  /** 0-1 maps to 0.5-4000 ms, default 0.3 */
+  openDecay?: number;
+  /** Choice 0-2, default 0 */
+  articulation?: number;
+  /** Choice 0-1, default 0 */
+  mode?: number;
+}
+
+export const enum TomParam {
+  Tune = 0,
+  Bend = 1,
+  Tone = 2,
+  Color = 3,
+  Decay = 4,
+  Membrane = 5,
+  MembraneQ = 6,
+  Volume = 7,
+  Tuning = 8,
+  TriangleEnable = 9,
+  TriangleLevel = 10,
+  MorphLevel = 11,
+  MembraneGain = 12,
+}
+
+/** Tom parameters in setter space; omitted fields are left unchanged. */
+export interface TomParams {
+  /** 0-1 maps to 40-600 Hz, default 0.5 */
+  tune?: number;
+  /** 0-1, default 0.3 */
+  bend?: number;
+  /** 0-1, default 0.5 */
+  tone?: number;
+  /** 0-1, default 0.5 */
+  color?: number;
+  /** 0-1 maps to 0.5-4000 ms, default 0.5 */
+  decay?: number;
+  /** 0-1, default 0 */
+  membrane?: number;
+  /** 0-1, default 0.5 */
+  membraneQ?: number;
+  /** 0-1, default 1 */
+  volume?: number;
+  /** 0-1 maps to -12-12 st, default 0.5 */
+  tuning?: number;
+  /** Choice 0-1, default 1 */
+  triangleEnable?: number;
+  /** 0-1, default 0.5 */
+  triangleLevel?: number;
+  /** 0-1, default 1 */
+  morphLevel?: number;
+  /** 0-1, default 0.5 */
+  membraneGain?: number;
+}
+
+export const enum BassParam {
+  Frequency = 0,
+  SubLevel = 1,
+  OscLevel = 2,
+  DetuneLevel = 3,
+  DetuneAmount = 4,
+  OscShape = 5,
+  FilterCutoff = 6,
+  FilterResonance = 7,
+  FilterEnvAmount = 8,
+  FilterEnvDecay = 9,
+  FilterEnvCurve = 10,
+  AmpDecay = 11,
+  AmpDecayCurve = 12,
+  Overdrive = 13,
+  Volume = 14,
+  Tuning = 15,
+}
+
+/** Bass parameters in setter space; omitted fields are left unchanged. */
+export interface BassParams {
+  /** 0-1 maps to 30-200 Hz, default 0.24 */
+  frequency?: number;
+  /** 0-1, default 0.4 */
+  subLevel?: number;
+  /** 0-1, default 0.8 */
+  oscLevel?: number;
+  /** 0-1, default 0 */
+  detuneLevel?: number;
+  /** 0-1 maps to 0-30 ct, default 0 */
+  detuneAmount?: number;
+  /** 0-1, default 0.1 */
+  oscShape?: number;
+  /** 0-1 maps to 20-18000 Hz, default 0.15 */
+  filterCutoff?: number;
+  /** 0-1 maps to 0.5-15, default 0.7 */
+  filterResonance?: number;
+  /** 0-1, default 0.85 */
+  filterEnvAmount?: number;
+  /** 0-1 maps to 0.01-2 s, default 0.15 */
+  filterEnvDecay?: number;
+  /** 0-1 maps to 0.1-8, default 0.08 */
+  filterEnvCurve?: number;
+  /** 0-1 maps to 0.05-4 s, default 0.35 */
+  ampDecay?: number;
+  /** 0-1 maps to 0.1-10, default 0.1 */
+  ampDecayCurve?: number;
+  /** 0-1, default 0.3 */
+  overdrive?: number;
+  /** 0-1, default 0.8 */
+  volume?: number;
+  /** 0-1 maps to -12-12 st, default 0.5 */
+  tuning?: number;
+}
+
+export const enum FmSnapParam {
+  Frequency = 0,
+  Ratio = 1,
+  Index = 2,
+  Snap = 3,
+  Decay = 4,
+  PitchDrop = 5,
+  Volume = 6,
+  Tuning = 7,
+}
+
+/** FmSnap parameters in setter space; omitted fields are left unchanged. */
+export interface FmSnapParams {
+  /** 0-1 maps to 80-1600 Hz, default 0.55 */
+  frequency?: number;
+  /** 0-1 maps to 0.5-6, default 0.62 */
+  ratio?: number;
+  /** 0-1 maps to 0-10, default 0.7 */
+  index?: number;
+  /** 0-1 maps to 0.5-50 ms, default 0.35 */
+  snap?: number;
+  /** 0-1 maps to 5-1000 ms, default 0.2 */
+  decay?: number;
+  /** 0-1 maps to 0-24 st, default 0.15 */
+  pitchDrop?: number;
+  /** 0-1, default 0.8 */
+  volume?: number;
+  /** 0-1 maps to -12-12 st, default 0.5 */
+  tuning?: number;
+}
+
+export const enum RimshotParam {
+  Tune = 0,
+  Tone = 1,
+  Click = 2,
+  Decay = 3,
+  Volume = 4,
+  Tuning = 5,
+}
+
+/** Rimshot parameters in setter space; omitted fields are left unchanged. */
+export interface RimshotParams {
+  /** 0-1 maps to 200-2000 Hz, default 0.36 */
+  tune?: number;
+  /** 0-1 maps to 1000-10000 Hz, default 0.55 */
+  tone?: number;
+  /** 0-1, default 0.5 */
+  click?: number;
+  /** 0-1 maps to 5-300 ms, default 0.25 */
+  decay?: number;
+  /** 0-1, default 0.8 */
+  volume?: number;
+  /** 0-1 maps to -12-12 st, default 0.5 */
+  tuning?: number;
+}
+
+export const enum CowbellParam {
+  Pitch = 0,
+  Tone = 1,
+  Decay = 2,
+  Volume = 3,
+  Tuning = 4,
+}
+
+/** Cowbell parameters in setter space; omitted fields are left unchanged. */
+export interface CowbellParams {
+  /** 0-1 maps to 300-1200 Hz, default 0.42 */
+  pitch?: number;
+  /** 0-1 maps to 1000-6000 Hz, default 0.54 */
+  tone?: number;
+  /** 0-1 maps to 50-2000 ms, default 0.6 */
+  decay?: number;
+  /** 0-1, default 0.8 */
+  volume?: number;
+  /** 0-1 maps to -12-12 st, default 0.5 */
+  tuning?: number;
+}
+
+export const enum ShakerParam {
+  Density = 0,
+  Color = 1,
+  Attack = 2,
+  Decay = 3,
+  Accent = 4,
+  Volume = 5,
+  Tuning = 6,
+}
+
+/** Shaker parameters in setter space; omitted fields are left unchanged. */
+export interface ShakerParams {
+  /** 0-1 maps to 200-5000 Hz, default 0.6 */
+  density?: number;
+  /** 0-1 maps to 1000-10000 Hz, default 0.7 */
+  color?: number;
+  /** 0-1 maps to 0.5-50 ms, default 0.1 */
+  attack?: number;
+  /** 0-1 maps to 20-600 ms, default 0.2 */
+  decay?: number;
+  /** 0-1, default 0.5 */
+  accent?: number;
+  /** 0-1, default 0.8 */
+  volume?: number;
+  /** 0-1 maps to -12-12 st, default 0.5 */
+  tuning?: number;
+}
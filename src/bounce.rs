@@ -4,6 +4,9 @@
 //! real-time, without requiring audio hardware.
 
 use crate::engine::Engine;
+#[cfg(feature = "bounce")]
+use crate::error::GooeyError;
+use crate::utils::{DenormalGuard, SampleClock};
 
 /// Specifies how long to render.
 pub enum BounceLength {
@@ -39,18 +42,17 @@ impl BounceLength {
 ///
 /// Returns a `Vec<f32>` of mono audio samples at the engine's sample rate.
 pub fn bounce_to_buffer(engine: &mut Engine, length: BounceLength) -> Vec<f32> {
+    let _ftz = DenormalGuard::new();
     let total_samples = length.to_samples(engine.bpm(), engine.sample_rate());
-    let sample_rate = engine.sample_rate() as f64;
 
     engine.prepare_for_bounce();
 
     let mut buffer = Vec::with_capacity(total_samples);
-    let mut current_time: f64 = 0.0;
-    let time_step = 1.0 / sample_rate;
+    let mut clock = SampleClock::new(engine.sample_rate());
 
     for _ in 0..total_samples {
-        buffer.push(engine.tick(current_time));
-        current_time += time_step;
+        buffer.push(engine.tick_at(clock));
+        clock.advance();
     }
 
     engine.stop_all_sequencers();
@@ -82,12 +84,12 @@ pub fn bounce_to_wav(
     length: BounceLength,
     path: &std::path::Path,
     config: WavConfig,
-) -> Result<(), String> {
+) -> Result<(), GooeyError> {
     if config.bit_depth != 16 && config.bit_depth != 24 {
-        return Err(format!(
+        return Err(GooeyError::InvalidValue(format!(
             "Unsupported bit depth: {}. Use 16 or 24.",
             config.bit_depth
-        ));
+        )));
     }
 
     let sample_rate = engine.sample_rate();
@@ -100,8 +102,8 @@ pub fn bounce_to_wav(
         sample_format: hound::SampleFormat::Int,
     };
 
-    let mut writer =
-        hound::WavWriter::create(path, spec).map_err(|e| format!("Failed to create WAV: {e}"))?;
+    let mut writer = hound::WavWriter::create(path, spec)
+        .map_err(|e| GooeyError::Io(format!("Failed to create WAV: {e}")))?;
 
     match config.bit_depth {
         16 => {
@@ -110,7 +112,7 @@ pub fn bounce_to_wav(
                 let s = (sample * scale).round() as i16;
                 writer
                     .write_sample(s)
-                    .map_err(|e| format!("Failed to write sample: {e}"))?;
+                    .map_err(|e| GooeyError::Io(format!("Failed to write sample: {e}")))?;
             }
         }
         24 => {
@@ -119,7 +121,7 @@ pub fn bounce_to_wav(
                 let s = (sample * scale).round() as i32;
                 writer
                     .write_sample(s)
-                    .map_err(|e| format!("Failed to write sample: {e}"))?;
+                    .map_err(|e| GooeyError::Io(format!("Failed to write sample: {e}")))?;
             }
         }
         _ => unreachable!("bit depth validated above"),
@@ -127,7 +129,7 @@ pub fn bounce_to_wav(
 
     writer
         .finalize()
-        .map_err(|e| format!("Failed to finalize WAV: {e}"))?;
+        .map_err(|e| GooeyError::Io(format!("Failed to finalize WAV: {e}")))?;
 
     Ok(())
 }
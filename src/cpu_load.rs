@@ -0,0 +1,219 @@
+//! DSP load metering
+//!
+//! Load is the time spent rendering over the time the rendered audio lasts,
+//! as a percentage: at 100% a render takes as long as its buffer plays, so
+//! the audio callback misses its deadline. The audio thread records each
+//! render; any thread reads the smoothed average and the peak.
+//!
+//! [`OverloadGovernor`] turns the average into shed/restore decisions for
+//! hosts that would rather lose quality than glitch.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+/// Time constant of the average, in seconds of rendered audio.
+const AVERAGE_SECONDS: f32 = 0.5;
+
+/// Seconds the average must stay over the threshold before shedding a
+/// stage. Two time constants, so the average has caught up with the
+/// previous stage before the next one goes.
+const SHED_HOLD_SECONDS: f32 = 2.0 * AVERAGE_SECONDS;
+
+/// Seconds the average must stay under the recovery level before restoring
+/// a stage. Slower than shedding so the engine doesn't oscillate.
+const RESTORE_HOLD_SECONDS: f32 = 3.0;
+
+/// Recovery level as a fraction of the overload threshold.
+const RESTORE_RATIO: f32 = 0.7;
+
+/// Lock-free load meter for the whole render and, optionally, per channel.
+pub struct CpuLoad {
+    // Percentages as f32 bits
+    average: AtomicU32,
+    peak: AtomicU32,
+    channels: Box<[AtomicU32]>,
+}
+
+impl CpuLoad {
+    /// A meter with `channels` per-channel averages.
+    pub fn new(channels: usize) -> Self {
+        Self {
+            average: AtomicU32::new(0),
+            peak: AtomicU32::new(0),
+            channels: (0..channels).map(|_| AtomicU32::new(0)).collect(),
+        }
+    }
+
+    /// Record a render of `frames` frames that took `elapsed`. Audio thread
+    /// only.
+    pub fn record(&self, elapsed: Duration, frames: usize, sample_rate: f32) {
+        let Some((load, coeff)) = Self::measure(elapsed, frames, sample_rate) else {
+            return;
+        };
+        Self::smooth(&self.average, load, coeff);
+        if load > f32::from_bits(self.peak.load(Ordering::Relaxed)) {
+            self.peak.store(load.to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    /// Record the share of a render spent on `channel`. Audio thread only.
+    pub fn record_channel(
+        &self,
+        channel: usize,
+        elapsed: Duration,
+        frames: usize,
+        sample_rate: f32,
+    ) {
+        let (Some(cell), Some((load, coeff))) = (
+            self.channels.get(channel),
+            Self::measure(elapsed, frames, sample_rate),
+        ) else {
+            return;
+        };
+        Self::smooth(cell, load, coeff);
+    }
+
+    /// Load percentage and smoothing coefficient for one render.
+    fn measure(elapsed: Duration, frames: usize, sample_rate: f32) -> Option<(f32, f32)> {
+        if frames == 0 || sample_rate <= 0.0 {
+            return None;
+        }
+        let budget = frames as f32 / sample_rate;
+        let load = 100.0 * elapsed.as_secs_f32() / budget;
+        Some((load, 1.0 - (-budget / AVERAGE_SECONDS).exp()))
+    }
+
+    fn smooth(cell: &AtomicU32, load: f32, coeff: f32) {
+        let current = f32::from_bits(cell.load(Ordering::Relaxed));
+        cell.store(
+            (current + coeff * (load - current)).to_bits(),
+            Ordering::Relaxed,
+        );
+    }
+
+    /// Smoothed load percentage.
+    pub fn average(&self) -> f32 {
+        f32::from_bits(self.average.load(Ordering::Relaxed))
+    }
+
+    /// Highest load of a single render since the last call.
+    pub fn take_peak(&self) -> f32 {
+        f32::from_bits(self.peak.swap(0, Ordering::Relaxed))
+    }
+
+    /// Smoothed load percentage of `channel`, or `None` out of range.
+    pub fn channel(&self, channel: usize) -> Option<f32> {
+        Some(f32::from_bits(
+            self.channels.get(channel)?.load(Ordering::Relaxed),
+        ))
+    }
+}
+
+/// What [`OverloadGovernor::update`] wants done.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GovernorAction {
+    /// Drop the next stage of quality.
+    Shed,
+    /// Bring back the most recently shed stage.
+    Restore,
+}
+
+/// Decides when to shed or restore quality from the average load: shed one
+/// stage after the load has stayed over the threshold for a second, restore
+/// one after it has stayed under 70% of the threshold for three.
+pub struct OverloadGovernor {
+    threshold: f32,
+    over_seconds: f32,
+    under_seconds: f32,
+}
+
+impl OverloadGovernor {
+    /// A governor that treats `threshold` percent as overloaded.
+    pub fn new(threshold: f32) -> Self {
+        Self {
+            threshold,
+            over_seconds: 0.0,
+            under_seconds: 0.0,
+        }
+    }
+
+    pub fn set_threshold(&mut self, threshold: f32) {
+        self.threshold = threshold;
+    }
+
+    /// Feed `seconds` of rendered audio at `average` load. `can_shed` and
+    /// `can_restore` say whether a stage is left to drop or to bring back.
+    pub fn update(
+        &mut self,
+        average: f32,
+        seconds: f32,
+        can_shed: bool,
+        can_restore: bool,
+    ) -> Option<GovernorAction> {
+        if average > self.threshold {
+            self.under_seconds = 0.0;
+            self.over_seconds += seconds;
+            if can_shed && self.over_seconds >= SHED_HOLD_SECONDS {
+                self.over_seconds = 0.0;
+                return Some(GovernorAction::Shed);
+            }
+        } else if average < self.threshold * RESTORE_RATIO {
+            self.over_seconds = 0.0;
+            self.under_seconds += seconds;
+            if can_restore && self.under_seconds >= RESTORE_HOLD_SECONDS {
+                self.under_seconds = 0.0;
+                return Some(GovernorAction::Restore);
+            }
+        } else {
+            self.over_seconds = 0.0;
+            self.under_seconds = 0.0;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_is_render_time_over_buffer_time() {
+        let meter = CpuLoad::new(2);
+        // 512 frames at 51.2 kHz is 10 ms; 2.5 ms of work is 25%
+        for _ in 0..500 {
+            meter.record(Duration::from_micros(2_500), 512, 51_200.0);
+            meter.record_channel(1, Duration::from_micros(1_000), 512, 51_200.0);
+        }
+        assert!((meter.average() - 25.0).abs() < 0.01, "{}", meter.average());
+        assert!((meter.channel(1).unwrap() - 10.0).abs() < 0.01);
+        assert_eq!(meter.channel(0), Some(0.0));
+        assert_eq!(meter.channel(2), None);
+
+        // One slow render moves the peak at once and the average slowly
+        meter.record(Duration::from_millis(20), 512, 51_200.0);
+        assert!((meter.take_peak() - 200.0).abs() < 0.01);
+        assert!(meter.average() < 30.0);
+        assert_eq!(meter.take_peak(), 0.0);
+    }
+
+    #[test]
+    fn test_governor_sheds_on_sustained_overload_and_restores_slowly() {
+        let mut governor = OverloadGovernor::new(80.0);
+        let block = 0.01;
+        let run = |governor: &mut OverloadGovernor, load: f32, seconds: f32| {
+            (0..(seconds / block).round() as usize)
+                .filter_map(|_| governor.update(load, block, true, true))
+                .collect::<Vec<_>>()
+        };
+        // Brief spikes and the band between the levels do nothing
+        assert!(run(&mut governor, 95.0, 0.5).is_empty());
+        assert!(run(&mut governor, 70.0, 10.0).is_empty());
+
+        assert_eq!(run(&mut governor, 95.0, 2.05), [GovernorAction::Shed; 2]);
+        assert!(run(&mut governor, 50.0, 2.5).is_empty());
+        assert_eq!(run(&mut governor, 50.0, 0.55), [GovernorAction::Restore]);
+
+        // Nothing left to shed
+        assert_eq!(governor.update(95.0, 5.0, false, true), None);
+    }
+}
@@ -0,0 +1,272 @@
+//! Audio file decoding (requires the `decode` feature)
+//!
+//! Turns WAV, FLAC and Ogg Vorbis files or in-memory bytes into interleaved
+//! `f32` PCM, so hosts can hand the sampler a file instead of decoding it
+//! themselves. WAV goes through `hound`; FLAC and Ogg through `symphonia`.
+//! The format is recognised from the file's magic bytes, not its extension.
+//! Kept behind a feature so WASM builds don't carry the decoders.
+
+use std::io::{Cursor, Read, Seek};
+use std::path::Path;
+
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::{MediaSource, MediaSourceStream};
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+use crate::error::GooeyError;
+use crate::instruments::SamplerBuffer;
+
+/// Container formats the decoder recognises.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AudioFormat {
+    Wav,
+    Flac,
+    /// Ogg Vorbis
+    Ogg,
+}
+
+impl AudioFormat {
+    /// Recognise a format from the first bytes of a file.
+    pub fn sniff(bytes: &[u8]) -> Option<Self> {
+        match bytes.get(..4)? {
+            b"RIFF" | b"RIFX" | b"RF64" => Some(Self::Wav),
+            b"fLaC" => Some(Self::Flac),
+            b"OggS" => Some(Self::Ogg),
+            _ => None,
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Wav => "wav",
+            Self::Flac => "flac",
+            Self::Ogg => "ogg",
+        }
+    }
+}
+
+/// Decoded PCM: interleaved samples with every channel of the source.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedAudio {
+    pub samples: Vec<f32>,
+    pub channels: usize,
+    pub sample_rate: f32,
+}
+
+impl DecodedAudio {
+    pub fn frames(&self) -> usize {
+        self.samples.len() / self.channels.max(1)
+    }
+
+    /// A sampler buffer of the first one or two channels.
+    pub fn to_sampler_buffer(&self) -> Result<SamplerBuffer, GooeyError> {
+        let keep = self.channels.min(2);
+        let samples: Vec<f32> = if keep == self.channels {
+            self.samples.clone()
+        } else {
+            self.samples
+                .chunks_exact(self.channels)
+                .flat_map(|frame| frame[..keep].iter().copied())
+                .collect()
+        };
+        SamplerBuffer::from_interleaved(&samples, self.frames(), keep, self.sample_rate)
+            .map_err(GooeyError::invalid)
+    }
+}
+
+/// Decode an audio file.
+pub fn decode_file(path: impl AsRef<Path>) -> Result<DecodedAudio, GooeyError> {
+    let path = path.as_ref();
+    let mut file = std::fs::File::open(path)
+        .map_err(|e| GooeyError::Io(format!("Failed to open {}: {e}", path.display())))?;
+    let mut magic = [0; 4];
+    file.read_exact(&mut magic)
+        .map_err(|e| GooeyError::Io(format!("Failed to read {}: {e}", path.display())))?;
+    let format = AudioFormat::sniff(&magic)
+        .ok_or_else(|| GooeyError::Io("Unrecognised audio format".to_string()))?;
+    // Both readers expect to start at the beginning of the file
+    file.rewind()
+        .map_err(|e| GooeyError::Io(format!("Failed to read {}: {e}", path.display())))?;
+    match format {
+        AudioFormat::Wav => decode_wav(std::io::BufReader::new(file)),
+        _ => decode_compressed(Box::new(file), format),
+    }
+}
+
+/// Decode an audio file already in memory.
+pub fn decode_bytes(bytes: &[u8]) -> Result<DecodedAudio, GooeyError> {
+    let format = AudioFormat::sniff(bytes)
+        .ok_or_else(|| GooeyError::Io("Unrecognised audio format".to_string()))?;
+    match format {
+        AudioFormat::Wav => decode_wav(Cursor::new(bytes)),
+        _ => decode_compressed(Box::new(Cursor::new(bytes.to_vec())), format),
+    }
+}
+
+fn decode_wav(reader: impl Read) -> Result<DecodedAudio, GooeyError> {
+    let mut reader = hound::WavReader::new(reader)
+        .map_err(|e| GooeyError::Io(format!("Failed to open WAV: {e}")))?;
+    let spec = reader.spec();
+    if spec.channels == 0 {
+        return Err(GooeyError::Io(
+            "WAV must have at least one channel".to_string(),
+        ));
+    }
+    if spec.sample_rate == 0 {
+        return Err(GooeyError::Io(
+            "WAV sample rate must be greater than zero".to_string(),
+        ));
+    }
+    let error = |e: hound::Error| GooeyError::Io(format!("Failed to read WAV sample: {e}"));
+    let samples = match spec.sample_format {
+        hound::SampleFormat::Float => reader
+            .samples::<f32>()
+            .map(|s| s.map_err(error))
+            .collect::<Result<Vec<_>, _>>()?,
+        hound::SampleFormat::Int => {
+            let bits = spec.bits_per_sample;
+            if bits == 0 || bits > 32 {
+                return Err(GooeyError::Io(format!("Unsupported WAV bit depth: {bits}")));
+            }
+            let scale = ((1_i64 << (bits - 1)) - 1) as f32;
+            match bits {
+                1..=8 => reader
+                    .samples::<i8>()
+                    .map(|s| s.map(|v| v as f32 / scale).map_err(error))
+                    .collect::<Result<Vec<_>, _>>()?,
+                9..=16 => reader
+                    .samples::<i16>()
+                    .map(|s| s.map(|v| v as f32 / scale).map_err(error))
+                    .collect::<Result<Vec<_>, _>>()?,
+                _ => reader
+                    .samples::<i32>()
+                    .map(|s| s.map(|v| v as f32 / scale).map_err(error))
+                    .collect::<Result<Vec<_>, _>>()?,
+            }
+        }
+    };
+    Ok(DecodedAudio {
+        samples,
+        channels: spec.channels as usize,
+        sample_rate: spec.sample_rate as f32,
+    })
+}
+
+/// FLAC and Ogg Vorbis through symphonia: the first audio track, decoded to
+/// the end. Corrupt packets are skipped rather than failing the file.
+fn decode_compressed(
+    source: Box<dyn MediaSource>,
+    format: AudioFormat,
+) -> Result<DecodedAudio, GooeyError> {
+    let stream = MediaSourceStream::new(source, Default::default());
+    let mut hint = Hint::new();
+    hint.with_extension(format.extension());
+    let mut reader = symphonia::default::get_probe()
+        .format(
+            &hint,
+            stream,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(|e| GooeyError::Io(format!("Failed to open {format:?}: {e}")))?
+        .format;
+    let track = reader
+        .tracks()
+        .iter()
+        .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| GooeyError::Io("File has no audio track".to_string()))?;
+    let track_id = track.id;
+    let mut sample_rate = track.codec_params.sample_rate;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| GooeyError::Io(format!("Unsupported {format:?} codec: {e}")))?;
+
+    let mut samples = Vec::new();
+    let mut channels = 0;
+    loop {
+        let packet = match reader.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                break
+            }
+            Err(e) => return Err(GooeyError::Io(format!("Failed to read {format:?}: {e}"))),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            Err(SymphoniaError::DecodeError(_)) => continue,
+            Err(e) => return Err(GooeyError::Io(format!("Failed to decode {format:?}: {e}"))),
+        };
+        let spec = *decoded.spec();
+        channels = spec.channels.count();
+        sample_rate = Some(spec.rate);
+        let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+        buffer.copy_interleaved_ref(decoded);
+        samples.extend_from_slice(buffer.samples());
+    }
+
+    match sample_rate {
+        Some(rate) if rate > 0 && channels > 0 && !samples.is_empty() => Ok(DecodedAudio {
+            samples,
+            channels,
+            sample_rate: rate as f32,
+        }),
+        _ => Err(GooeyError::Io(format!("{format:?} file contains no audio"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wav_bytes(
+        spec: hound::WavSpec,
+        write: impl FnOnce(&mut hound::WavWriter<Cursor<&mut Vec<u8>>>),
+    ) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut writer = hound::WavWriter::new(Cursor::new(&mut bytes), spec).unwrap();
+        write(&mut writer);
+        writer.finalize().unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_sniffs_magic_bytes() {
+        assert_eq!(AudioFormat::sniff(b"RIFF...."), Some(AudioFormat::Wav));
+        assert_eq!(AudioFormat::sniff(b"fLaC\0"), Some(AudioFormat::Flac));
+        assert_eq!(AudioFormat::sniff(b"OggS\0"), Some(AudioFormat::Ogg));
+        assert_eq!(AudioFormat::sniff(b"ID3\x04"), None);
+        assert_eq!(AudioFormat::sniff(b"RI"), None);
+        assert!(decode_bytes(b"not audio").is_err());
+    }
+
+    #[test]
+    fn test_multichannel_wav_keeps_first_two_channels_for_the_sampler() {
+        let spec = hound::WavSpec {
+            channels: 3,
+            sample_rate: 48_000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let bytes = wav_bytes(spec, |writer| {
+            for _ in 0..10 {
+                for value in [i16::MAX, 0, i16::MIN + 1] {
+                    writer.write_sample(value).unwrap();
+                }
+            }
+        });
+        let decoded = decode_bytes(&bytes).unwrap();
+        assert_eq!((decoded.frames(), decoded.channels), (10, 3));
+        assert_eq!(decoded.sample_rate, 48_000.0);
+
+        let buffer = decoded.to_sampler_buffer().unwrap();
+        assert_eq!((buffer.frames(), buffer.channels()), (10, 2));
+        assert_eq!(&decoded.samples[..3], &[1.0, 0.0, -1.0]);
+    }
+}
@@ -1,10 +1,11 @@
 //! A tiny, line-based DSL for describing simple `Engine` programs.
 //!
 //! This is a prototype aimed at making the common example-style setup shorter:
-//! - Create instruments
+//! - Create instruments, tweaking their presets
 //! - Add step sequencers
 //! - Add LFO routes
 //! - Add a few global effects
+//! - Step their parameters with effect lanes
 //!
 //! The syntax is intentionally forgiving and whitespace-friendly.
 //! Lines are statements; `#` starts a comment.
@@ -13,258 +14,122 @@
 //! ```text
 //! bpm 120
 //! master 0.25
+//! key a minor
 //!
+//! inst kick kick punch freq=45 decay=0.8
 //! inst hihat hihat closed
+//! set hihat.tone 6500
 //! seq hihat x.x.x.x.|x.x.x.x.
 //!
 //! lfo 1bar hihat.decay amt=1
 //! fx lowpass 2000 0.3
+//! auto fx.lowpass.cutoff 200 400 800 . 1600 smooth
 //! ```
+//!
+//! `key=value` arguments after an `inst` preset and `set <inst>.<field> <value>`
+//! statements override single fields of the preset's config, in source order,
+//! before the instrument is built. Fields with a physical range take display
+//! units (Hz, seconds; milliseconds for hi-hat times); the rest take the
+//! config's own value (0-1 amounts, Tom2's 0-100 scale).
+//!
+//! `auto` values fill the lane's sixteen steps from the first; `.` and steps
+//! past the last value hold the value before them. `smooth` glides between
+//! values instead of stepping.
+//!
+//! [`Program`] parses and builds a whole engine at once; [`DslSession`] runs
+//! the same statements one at a time against a live engine.
+//! Both report a bad statement as [`GooeyError::Parse`] with its line
+//! number. Building a parsed [`Program`] can also fail with the engine's own
+//! errors, such as an LFO aimed at a parameter that can't be modulated.
 
-use std::collections::HashSet;
+use std::collections::HashMap;
 
 use crate::effects::{
     DelayEffect, DelayTiming, Effect, LowpassFilterEffect, SoftLimiter, TubeSaturation,
 };
-use crate::engine::{Engine, Instrument, Lfo, MusicalDivision, Sequencer, SequencerStep};
+use crate::engine::{
+    EffectLane, Engine, Instrument, Lfo, MusicalDivision, Sequencer, SequencerStep,
+    EFFECT_LANE_STEPS,
+};
+use crate::error::GooeyError;
+use crate::ffi::{
+    DELAY_PARAM_FEEDBACK, DELAY_PARAM_FILTER_CUTOFF, DELAY_PARAM_LOW_CUT, DELAY_PARAM_MIX,
+    FILTER_PARAM_CUTOFF, FILTER_PARAM_RESONANCE, SATURATION_PARAM_DRIVE, SATURATION_PARAM_MIX,
+    SATURATION_PARAM_WARMTH,
+};
 use crate::instruments::{
-    HiHat, HiHatConfig, KickConfig, KickDrum, SnareConfig, SnareDrum, Tom2, Tom2Config, TomConfig,
-    TomDrum,
+    hihat2::ranges as hihat_ranges, kick::ranges as kick_ranges, snare::ranges as snare_ranges,
+    tom::ranges as tom_ranges, HiHat, HiHatConfig, KickConfig, KickDrum, PartialHiHatConfig,
+    PartialKickConfig, PartialSnareConfig, PartialTom2Config, PartialTomConfig, SnareConfig,
+    SnareDrum, Tom2, Tom2Config, TomConfig, TomDrum,
 };
+use crate::mixer::ChannelEffect;
+use crate::music::{NoteName, Scale};
 
 #[derive(Clone, Debug)]
 pub struct Program {
     bpm: Option<f32>,
     master_gain: Option<f32>,
+    key: Option<(NoteName, Scale)>,
     clear_effects: bool,
     instruments: Vec<InstrumentDef>,
     sequencers: Vec<SequencerDef>,
     lfos: Vec<LfoDef>,
     effects: Vec<EffectDef>,
+    lanes: Vec<LaneDef>,
 }
 
 impl Program {
-    pub fn parse(source: &str) -> Result<Self, String> {
+    pub fn parse(source: &str) -> Result<Self, GooeyError> {
         let mut program = Self {
             bpm: None,
             master_gain: None,
+            key: None,
             clear_effects: false,
             instruments: Vec::new(),
             sequencers: Vec::new(),
             lfos: Vec::new(),
             effects: Vec::new(),
+            lanes: Vec::new(),
         };
 
-        let mut instrument_names: HashSet<String> = HashSet::new();
-
+        let mut kinds = HashMap::new();
         for (line_index, raw_line) in source.lines().enumerate() {
-            let line_number = line_index + 1;
-            let line = strip_comment(raw_line).trim();
-            if line.is_empty() {
+            let Some(statement) = Statement::parse(line_index + 1, raw_line, &kinds)? else {
                 continue;
-            }
-
-            let tokens: Vec<&str> = line.split_whitespace().collect();
-            let cmd = tokens[0].to_ascii_lowercase();
-
-            match cmd.as_str() {
-                "bpm" => {
-                    let bpm = parse_single_f32_arg("bpm", line_number, &tokens)?;
-                    program.bpm = Some(bpm);
-                }
-                "master" | "gain" => {
-                    let gain = parse_single_f32_arg("master", line_number, &tokens)?;
-                    program.master_gain = Some(gain);
+            };
+            match statement {
+                Statement::Bpm(bpm) => program.bpm = Some(bpm),
+                Statement::Master(gain) => program.master_gain = Some(gain),
+                Statement::Key(key) => program.key = key,
+                Statement::Inst(def) => {
+                    kinds.insert(def.name.clone(), def.kind);
+                    program.instruments.push(def);
                 }
-                "inst" | "i" => {
-                    if tokens.len() < 3 {
-                        return Err(format!(
-                            "line {}: inst expects: inst <name> <type> [preset]",
-                            line_number
-                        ));
-                    }
-
-                    let name = tokens[1].to_string();
-                    if !instrument_names.insert(name.clone()) {
-                        return Err(format!(
-                            "line {}: duplicate instrument name '{}'",
-                            line_number, name
-                        ));
-                    }
-
-                    let kind = InstrumentKind::parse(tokens[2]).ok_or_else(|| {
-                        format!(
-                            "line {}: unknown instrument type '{}'",
-                            line_number, tokens[2]
-                        )
-                    })?;
-
-                    let mut preset: Option<String> = None;
-                    for arg in &tokens[3..] {
-                        if let Some((key, value)) = arg.split_once('=') {
-                            match key.to_ascii_lowercase().as_str() {
-                                "preset" => preset = Some(value.to_string()),
-                                other => {
-                                    return Err(format!(
-                                        "line {}: unknown inst argument '{}'",
-                                        line_number, other
-                                    ));
-                                }
-                            }
-                        } else if preset.is_none() {
-                            preset = Some((*arg).to_string());
-                        } else {
-                            return Err(format!(
-                                "line {}: too many inst arguments (unexpected '{}')",
-                                line_number, arg
-                            ));
-                        }
-                    }
-
-                    program
+                Statement::Set { instrument, over } => {
+                    if let Some(def) = program
                         .instruments
-                        .push(InstrumentDef { name, kind, preset });
-                }
-                "seq" | "s" => {
-                    if tokens.len() < 3 {
-                        return Err(format!(
-                            "line {}: seq expects: seq <instrument> <pattern> [start|stop]",
-                            line_number
-                        ));
-                    }
-
-                    let instrument = tokens[1].to_string();
-                    let mut remainder_tokens: Vec<&str> = tokens[2..].to_vec();
-
-                    // Optional trailing flags.
-                    let mut start = true;
-                    while let Some(last) = remainder_tokens.last().copied() {
-                        match last.to_ascii_lowercase().as_str() {
-                            "start" | "on" => {
-                                start = true;
-                                remainder_tokens.pop();
-                            }
-                            "stop" | "stopped" | "off" => {
-                                start = false;
-                                remainder_tokens.pop();
-                            }
-                            _ => break,
-                        }
-                    }
-
-                    if remainder_tokens.is_empty() {
-                        return Err(format!(
-                            "line {}: seq expects a non-empty pattern string",
-                            line_number
-                        ));
-                    }
-
-                    let pattern_str = remainder_tokens.join(" ");
-                    let pattern = parse_pattern(line_number, &pattern_str)?;
-                    program.sequencers.push(SequencerDef {
-                        instrument,
-                        pattern,
-                        start,
-                    });
-                }
-                "lfo" | "l" => {
-                    if tokens.len() < 3 {
-                        return Err(format!(
-                            "line {}: lfo expects: lfo <rate> <inst.param> [amt=..] [offset=..]",
-                            line_number
-                        ));
-                    }
-
-                    let mut index = 1;
-                    let rate = parse_lfo_rate(line_number, &tokens, &mut index)?;
-
-                    // Optional arrow token.
-                    if tokens.get(index).copied() == Some("->") {
-                        index += 1;
+                        .iter_mut()
+                        .find(|def| def.name == instrument)
+                    {
+                        def.overrides.push(over);
                     }
-
-                    let target = tokens.get(index).copied().ok_or_else(|| {
-                        format!(
-                            "line {}: lfo expects target like 'kick.pitch_drop'",
-                            line_number
-                        )
-                    })?;
-                    index += 1;
-
-                    let (target_instrument, target_parameter) = parse_target(line_number, target)?;
-
-                    let mut amount = 1.0;
-                    let mut offset = 0.0;
-
-                    for arg in &tokens[index..] {
-                        if let Some(rest) = arg.strip_prefix('*') {
-                            amount = parse_f32(line_number, "lfo amount", rest)?;
-                            continue;
-                        }
-                        if let Some(rest) = arg.strip_prefix('@') {
-                            offset = parse_f32(line_number, "lfo offset", rest)?;
-                            continue;
-                        }
-                        if let Some((key, value)) = arg.split_once('=') {
-                            match key.to_ascii_lowercase().as_str() {
-                                "amt" | "amount" => {
-                                    amount = parse_f32(line_number, "lfo amount", value)?
-                                }
-                                "off" | "offset" => {
-                                    offset = parse_f32(line_number, "lfo offset", value)?
-                                }
-                                other => {
-                                    return Err(format!(
-                                        "line {}: unknown lfo argument '{}'",
-                                        line_number, other
-                                    ));
-                                }
-                            }
-                            continue;
-                        }
-
-                        return Err(format!(
-                            "line {}: unrecognized lfo argument '{}'",
-                            line_number, arg
-                        ));
-                    }
-
-                    program.lfos.push(LfoDef {
-                        rate,
-                        target_instrument,
-                        target_parameter,
-                        amount,
-                        offset,
-                    });
-                }
-                "fx" | "effect" => {
-                    if tokens.len() < 2 {
-                        return Err(format!("line {}: fx expects: fx <type> [...]", line_number));
-                    }
-
-                    let fx_type = tokens[1].to_ascii_lowercase();
-                    if fx_type == "clear" {
-                        program.clear_effects = true;
-                        program.effects.clear();
-                        continue;
-                    }
-
-                    let def = EffectDef::parse(line_number, &tokens[1..])?;
-                    program.effects.push(def);
                 }
-                other => {
-                    return Err(format!(
-                        "line {}: unknown statement '{}'",
-                        line_number, other
-                    ));
+                Statement::Seq(def) => program.sequencers.push(def),
+                Statement::Lfo(def) => program.lfos.push(def),
+                Statement::FxClear => {
+                    program.clear_effects = true;
+                    program.effects.clear();
                 }
+                Statement::Fx(def) => program.effects.push(def),
+                Statement::Auto(def) => program.lanes.push(def),
             }
         }
 
         Ok(program)
     }
 
-    pub fn build_engine(&self, sample_rate: f32) -> Result<Engine, String> {
+    pub fn build_engine(&self, sample_rate: f32) -> Result<Engine, GooeyError> {
         let mut engine = Engine::new(sample_rate);
 
         if let Some(bpm) = self.bpm {
@@ -273,6 +138,9 @@ impl Program {
         if let Some(master_gain) = self.master_gain {
             engine.set_master_gain(master_gain);
         }
+        if let Some((root, scale)) = self.key {
+            engine.set_scale_quantize(root, scale);
+        }
         if self.clear_effects {
             engine.clear_global_effects();
         }
@@ -288,46 +156,39 @@ impl Program {
             .map(|i| (i.name.as_str(), i.kind))
             .collect::<std::collections::HashMap<_, _>>();
 
+        let mut effect_indices = Vec::with_capacity(self.effects.len());
         for effect in &self.effects {
+            effect_indices.push((effect.kind(), engine.global_effect_count()));
             engine.add_global_effect(effect.build(sample_rate, engine.bpm())?);
         }
 
-        // Sequencers often imply "play"; default to started unless explicitly stopped.
+        // Lanes drive the first effect of their kind
+        for lane in &self.lanes {
+            let index = effect_indices
+                .iter()
+                .find(|(kind, _)| *kind == lane.effect)
+                .map(|&(_, index)| index)
+                .ok_or_else(|| {
+                    GooeyError::parse(
+                        lane.line_number,
+                        format!(
+                            "auto targets fx {} but the program has no 'fx {}'",
+                            lane.effect, lane.effect
+                        ),
+                    )
+                })?;
+            engine.add_effect_lane(index, lane.param, lane.lane)?;
+        }
+
         for sequencer in &self.sequencers {
-            let mut seq = Sequencer::with_velocity_pattern(
-                engine.bpm(),
-                sample_rate,
-                sequencer.pattern.clone(),
-                sequencer.instrument.as_str(),
-            );
-            if sequencer.start {
-                seq.start();
-            }
-            engine.add_sequencer(seq);
+            engine.add_sequencer(sequencer.build(engine.bpm(), sample_rate));
         }
 
         for lfo in &self.lfos {
-            let l = match lfo.rate {
-                LfoRate::Hz(freq) => Lfo::new(freq, sample_rate),
-                LfoRate::BpmSync(division) => Lfo::new_synced(division, engine.bpm(), sample_rate),
-            };
-            let idx = engine.add_lfo(l);
-
-            let resolved_parameter = resolve_parameter_alias(
-                instrument_kinds
-                    .get(lfo.target_instrument.as_str())
-                    .copied(),
-                lfo.target_parameter.as_str(),
-            );
-            engine.map_lfo_to_parameter(
-                idx,
-                lfo.target_instrument.as_str(),
-                resolved_parameter.as_str(),
-                lfo.amount,
-            )?;
-            if let Some(lfo_mut) = engine.lfo_mut(idx) {
-                lfo_mut.offset = lfo.offset;
-            }
+            let kind = instrument_kinds
+                .get(lfo.target_instrument.as_str())
+                .copied();
+            lfo.add_to(&mut engine, kind)?;
         }
 
         Ok(engine)
@@ -336,6 +197,399 @@ impl Program {
     pub fn bpm(&self) -> Option<f32> {
         self.bpm
     }
+
+    /// The key sequenced notes are snapped to (`key <root> <scale>`), if any.
+    pub fn key(&self) -> Option<(NoteName, Scale)> {
+        self.key
+    }
+}
+
+/// Statement-at-a-time control of a live [`Engine`], for a REPL or a debug
+/// console.
+///
+/// Each call to [`DslSession::execute`] parses one statement and applies it
+/// on top of whatever the engine already holds: `inst` adds an instrument,
+/// `seq` and `lfo` add a sequencer or LFO, `fx` appends to the global chain,
+/// and `bpm`, `master` and `key` change the engine in place. Nothing is torn
+/// down except by `fx clear`, which also drops the effect lanes. `set`
+/// rebuilds its instrument with the new override, cutting off a ringing hit.
+///
+/// A statement that fails leaves the engine as it was.
+#[derive(Debug, Default)]
+pub struct DslSession {
+    /// Instruments declared in this session, with their overrides so far
+    instruments: Vec<InstrumentDef>,
+    kinds: HashMap<String, InstrumentKind>,
+    /// Kind and global effect index of each `fx` since the last `fx clear`
+    effects: Vec<(&'static str, usize)>,
+    /// Statements executed so far, numbering error messages
+    line_count: usize,
+}
+
+impl DslSession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse one statement and apply it to `engine`. Blank and comment-only
+    /// lines do nothing.
+    pub fn execute(&mut self, engine: &mut Engine, line: &str) -> Result<(), GooeyError> {
+        self.line_count += 1;
+        let line_number = self.line_count;
+        let Some(statement) = Statement::parse(line_number, line, &self.kinds)? else {
+            return Ok(());
+        };
+
+        match statement {
+            Statement::Bpm(bpm) => {
+                engine.set_bpm(bpm);
+                for index in 0..engine.sequencer_count() {
+                    if let Some(sequencer) = engine.sequencer_mut(index) {
+                        sequencer.set_bpm(bpm);
+                    }
+                }
+            }
+            Statement::Master(gain) => engine.set_master_gain(gain),
+            Statement::Key(Some((root, scale))) => engine.set_scale_quantize(root, scale),
+            Statement::Key(None) => engine.clear_scale_quantize(),
+            Statement::Inst(def) => {
+                if engine.instrument(&def.name).is_some() {
+                    return Err(GooeyError::parse(
+                        line_number,
+                        format!("the engine already has an instrument '{}'", def.name),
+                    ));
+                }
+                let built = def
+                    .build(engine.sample_rate())
+                    .map_err(|err| GooeyError::parse(line_number, err.to_string()))?;
+                engine.add_instrument(def.name.as_str(), built);
+                self.kinds.insert(def.name.clone(), def.kind);
+                self.instruments.push(def);
+            }
+            Statement::Set { instrument, over } => {
+                if let Some(def) = self
+                    .instruments
+                    .iter_mut()
+                    .find(|def| def.name == instrument)
+                {
+                    // Only the latest value of a field counts, so a long
+                    // session of tweaks doesn't grow the list
+                    def.overrides.retain(|earlier| earlier.field != over.field);
+                    def.overrides.push(over);
+                    let built = def
+                        .build(engine.sample_rate())
+                        .map_err(|err| GooeyError::parse(line_number, err.to_string()))?;
+                    engine.add_instrument(def.name.as_str(), built);
+                }
+            }
+            Statement::Seq(def) => {
+                let sequencer = def.build(engine.bpm(), engine.sample_rate());
+                engine.add_sequencer(sequencer);
+            }
+            Statement::Lfo(def) => {
+                let kind = self.kinds.get(&def.target_instrument).copied();
+                def.add_to(engine, kind)
+                    .map_err(|err| GooeyError::parse(line_number, err.to_string()))?;
+            }
+            Statement::FxClear => {
+                engine.clear_global_effects();
+                engine.clear_effect_lanes();
+                self.effects.clear();
+            }
+            Statement::Fx(def) => {
+                let effect = def
+                    .build(engine.sample_rate(), engine.bpm())
+                    .map_err(|err| GooeyError::parse(line_number, err.to_string()))?;
+                self.effects
+                    .push((def.kind(), engine.global_effect_count()));
+                engine.add_global_effect(effect);
+            }
+            Statement::Auto(def) => {
+                let index = self
+                    .effects
+                    .iter()
+                    .find(|(kind, _)| *kind == def.effect)
+                    .map(|&(_, index)| index)
+                    .ok_or_else(|| {
+                        GooeyError::parse(
+                            line_number,
+                            format!(
+                                "auto targets fx {} but the session has no 'fx {}'",
+                                def.effect, def.effect
+                            ),
+                        )
+                    })?;
+                engine
+                    .add_effect_lane(index, def.param, def.lane)
+                    .map_err(|err| GooeyError::parse(line_number, err.to_string()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// [`DslSession::execute`] each line of `source` in turn, stopping at
+    /// the first error.
+    pub fn execute_all(&mut self, engine: &mut Engine, source: &str) -> Result<(), GooeyError> {
+        source
+            .lines()
+            .try_for_each(|line| self.execute(engine, line))
+    }
+}
+
+/// One parsed line of a program.
+#[derive(Clone, Debug)]
+enum Statement {
+    Bpm(f32),
+    Master(f32),
+    /// `None` for `key off`
+    Key(Option<(NoteName, Scale)>),
+    Inst(InstrumentDef),
+    Set {
+        instrument: String,
+        over: ConfigOverride,
+    },
+    Seq(SequencerDef),
+    Lfo(LfoDef),
+    FxClear,
+    Fx(EffectDef),
+    Auto(LaneDef),
+}
+
+impl Statement {
+    /// Parse one line; `Ok(None)` for blank and comment-only lines. `kinds`
+    /// holds the instruments declared so far, for `set` and duplicate checks.
+    fn parse(
+        line_number: usize,
+        raw_line: &str,
+        kinds: &HashMap<String, InstrumentKind>,
+    ) -> Result<Option<Self>, GooeyError> {
+        let line = strip_comment(raw_line).trim();
+        if line.is_empty() {
+            return Ok(None);
+        }
+
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let cmd = tokens[0].to_ascii_lowercase();
+
+        let statement = match cmd.as_str() {
+            "bpm" => {
+                let bpm = parse_single_f32_arg("bpm", line_number, &tokens)?;
+                Self::Bpm(bpm)
+            }
+            "master" | "gain" => {
+                let gain = parse_single_f32_arg("master", line_number, &tokens)?;
+                Self::Master(gain)
+            }
+            "key" => Self::Key(parse_key(line_number, &tokens)?),
+            "inst" | "i" => {
+                if tokens.len() < 3 {
+                    return Err(GooeyError::parse(
+                        line_number,
+                        "inst expects: inst <name> <type> [preset]",
+                    ));
+                }
+
+                let name = tokens[1].to_string();
+                if kinds.contains_key(&name) {
+                    return Err(GooeyError::parse(
+                        line_number,
+                        format!("duplicate instrument name '{}'", name),
+                    ));
+                }
+
+                let kind = InstrumentKind::parse(tokens[2]).ok_or_else(|| {
+                    GooeyError::parse(
+                        line_number,
+                        format!("unknown instrument type '{}'", tokens[2]),
+                    )
+                })?;
+
+                let mut preset: Option<String> = None;
+                let mut overrides = Vec::new();
+                for arg in &tokens[3..] {
+                    if let Some((key, value)) = arg.split_once('=') {
+                        match key.to_ascii_lowercase().as_str() {
+                            "preset" => preset = Some(value.to_string()),
+                            field => overrides.push(ConfigOverride::parse(
+                                line_number,
+                                kind,
+                                field,
+                                value,
+                            )?),
+                        }
+                    } else if preset.is_none() {
+                        preset = Some((*arg).to_string());
+                    } else {
+                        return Err(GooeyError::parse(
+                            line_number,
+                            format!("too many inst arguments (unexpected '{}')", arg),
+                        ));
+                    }
+                }
+
+                Self::Inst(InstrumentDef {
+                    name,
+                    kind,
+                    preset,
+                    overrides,
+                })
+            }
+            "set" => {
+                if tokens.len() != 3 {
+                    return Err(GooeyError::parse(
+                        line_number,
+                        "set expects: set <inst>.<field> <value>",
+                    ));
+                }
+
+                let (instrument, field) = parse_target(line_number, tokens[1])?;
+                let kind = kinds.get(&instrument).copied().ok_or_else(|| {
+                    GooeyError::parse(
+                        line_number,
+                        format!(
+                            "set targets '{}', which no earlier inst declares",
+                            instrument
+                        ),
+                    )
+                })?;
+                let field = field.to_ascii_lowercase();
+                let over = ConfigOverride::parse(line_number, kind, &field, tokens[2])?;
+                Self::Set { instrument, over }
+            }
+            "seq" | "s" => {
+                if tokens.len() < 3 {
+                    return Err(GooeyError::parse(
+                        line_number,
+                        "seq expects: seq <instrument> <pattern> [start|stop]",
+                    ));
+                }
+
+                let instrument = tokens[1].to_string();
+                let mut remainder_tokens: Vec<&str> = tokens[2..].to_vec();
+
+                // Optional trailing flags.
+                let mut start = true;
+                while let Some(last) = remainder_tokens.last().copied() {
+                    match last.to_ascii_lowercase().as_str() {
+                        "start" | "on" => {
+                            start = true;
+                            remainder_tokens.pop();
+                        }
+                        "stop" | "stopped" | "off" => {
+                            start = false;
+                            remainder_tokens.pop();
+                        }
+                        _ => break,
+                    }
+                }
+
+                if remainder_tokens.is_empty() {
+                    return Err(GooeyError::parse(
+                        line_number,
+                        "seq expects a non-empty pattern string",
+                    ));
+                }
+
+                let pattern_str = remainder_tokens.join(" ");
+                let pattern = parse_pattern(line_number, &pattern_str)?;
+                Self::Seq(SequencerDef {
+                    instrument,
+                    pattern,
+                    start,
+                })
+            }
+            "lfo" | "l" => {
+                if tokens.len() < 3 {
+                    return Err(GooeyError::parse(
+                        line_number,
+                        "lfo expects: lfo <rate> <inst.param> [amt=..] [offset=..]",
+                    ));
+                }
+
+                let mut index = 1;
+                let rate = parse_lfo_rate(line_number, &tokens, &mut index)?;
+
+                // Optional arrow token.
+                if tokens.get(index).copied() == Some("->") {
+                    index += 1;
+                }
+
+                let target = tokens.get(index).copied().ok_or_else(|| {
+                    GooeyError::parse(line_number, "lfo expects target like 'kick.pitch_drop'")
+                })?;
+                index += 1;
+
+                let (target_instrument, target_parameter) = parse_target(line_number, target)?;
+
+                let mut amount = 1.0;
+                let mut offset = 0.0;
+
+                for arg in &tokens[index..] {
+                    if let Some(rest) = arg.strip_prefix('*') {
+                        amount = parse_f32(line_number, "lfo amount", rest)?;
+                        continue;
+                    }
+                    if let Some(rest) = arg.strip_prefix('@') {
+                        offset = parse_f32(line_number, "lfo offset", rest)?;
+                        continue;
+                    }
+                    if let Some((key, value)) = arg.split_once('=') {
+                        match key.to_ascii_lowercase().as_str() {
+                            "amt" | "amount" => {
+                                amount = parse_f32(line_number, "lfo amount", value)?
+                            }
+                            "off" | "offset" => {
+                                offset = parse_f32(line_number, "lfo offset", value)?
+                            }
+                            other => {
+                                return Err(GooeyError::parse(
+                                    line_number,
+                                    format!("unknown lfo argument '{}'", other),
+                                ));
+                            }
+                        }
+                        continue;
+                    }
+
+                    return Err(GooeyError::parse(
+                        line_number,
+                        format!("unrecognized lfo argument '{}'", arg),
+                    ));
+                }
+
+                Self::Lfo(LfoDef {
+                    rate,
+                    target_instrument,
+                    target_parameter,
+                    amount,
+                    offset,
+                })
+            }
+            "fx" | "effect" => {
+                if tokens.len() < 2 {
+                    return Err(GooeyError::parse(
+                        line_number,
+                        "fx expects: fx <type> [...]",
+                    ));
+                }
+
+                if tokens[1].eq_ignore_ascii_case("clear") {
+                    Self::FxClear
+                } else {
+                    Self::Fx(EffectDef::parse(line_number, &tokens[1..])?)
+                }
+            }
+            "auto" | "a" => Self::Auto(LaneDef::parse(line_number, &tokens)?),
+            other => {
+                return Err(GooeyError::parse(
+                    line_number,
+                    format!("unknown statement '{}'", other),
+                ));
+            }
+        };
+
+        Ok(Some(statement))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -343,10 +597,12 @@ struct InstrumentDef {
     name: String,
     kind: InstrumentKind,
     preset: Option<String>,
+    /// Inline `key=value` overrides, then `set` statements, in source order
+    overrides: Vec<ConfigOverride>,
 }
 
 impl InstrumentDef {
-    fn build(&self, sample_rate: f32) -> Result<Box<dyn Instrument>, String> {
+    fn build(&self, sample_rate: f32) -> Result<Box<dyn Instrument>, GooeyError> {
         let preset = self
             .preset
             .as_deref()
@@ -354,122 +610,109 @@ impl InstrumentDef {
             .to_ascii_lowercase();
 
         match self.kind {
-            InstrumentKind::Kick => match preset.as_str() {
-                "default" => Ok(Box::new(KickDrum::new(sample_rate))),
-                "tight" => Ok(Box::new(KickDrum::with_config(
-                    sample_rate,
-                    KickConfig::tight(),
-                ))),
-                "punch" => Ok(Box::new(KickDrum::with_config(
-                    sample_rate,
-                    KickConfig::punch(),
-                ))),
-                "loose" => Ok(Box::new(KickDrum::with_config(
-                    sample_rate,
-                    KickConfig::loose(),
-                ))),
-                "dirt" | "dirty" => Ok(Box::new(KickDrum::with_config(
-                    sample_rate,
-                    KickConfig::dirt(),
-                ))),
-                other => Err(format!(
-                    "unknown kick preset '{}'. Try: default, tight, punch, loose, dirt",
-                    other
-                )),
-            },
-            InstrumentKind::Snare => match preset.as_str() {
-                "default" | "tight" => Ok(Box::new(SnareDrum::with_config(
-                    sample_rate,
-                    SnareConfig::tight(),
-                ))),
-                "loose" => Ok(Box::new(SnareDrum::with_config(
-                    sample_rate,
-                    SnareConfig::loose(),
-                ))),
-                "hiss" => Ok(Box::new(SnareDrum::with_config(
-                    sample_rate,
-                    SnareConfig::hiss(),
-                ))),
-                "smack" => Ok(Box::new(SnareDrum::with_config(
-                    sample_rate,
-                    SnareConfig::smack(),
-                ))),
-                other => Err(format!(
-                    "unknown snare preset '{}'. Try: default, tight, loose, hiss, smack",
-                    other
-                )),
-            },
-            InstrumentKind::HiHat => match preset.as_str() {
-                "default" | "short" | "closed" | "closed_default" | "closed_tight" => Ok(Box::new(
-                    HiHat::with_config(sample_rate, HiHatConfig::short()),
-                )),
-                "loose" | "open" | "open_default" | "open_long" => Ok(Box::new(
-                    HiHat::with_config(sample_rate, HiHatConfig::loose()),
-                )),
-                "dark" | "closed_dark" | "open_bright" => Ok(Box::new(HiHat::with_config(
-                    sample_rate,
-                    HiHatConfig::dark(),
-                ))),
-                "soft" => Ok(Box::new(HiHat::with_config(
-                    sample_rate,
-                    HiHatConfig::soft(),
-                ))),
-                other => Err(format!(
-                    "unknown hihat preset '{}'. Try: short, loose, dark, soft",
-                    other
-                )),
-            },
-            InstrumentKind::Tom => match preset.as_str() {
-                "default" | "mid" | "mid_tom" => Ok(Box::new(TomDrum::with_config(
-                    sample_rate,
-                    TomConfig::mid_tom(),
-                ))),
-                "high" | "high_tom" => Ok(Box::new(TomDrum::with_config(
-                    sample_rate,
-                    TomConfig::high_tom(),
-                ))),
-                "low" | "low_tom" => Ok(Box::new(TomDrum::with_config(
-                    sample_rate,
-                    TomConfig::low_tom(),
-                ))),
-                "floor" | "floor_tom" => Ok(Box::new(TomDrum::with_config(
-                    sample_rate,
-                    TomConfig::floor_tom(),
-                ))),
-                other => Err(format!(
-                    "unknown tom preset '{}'. Try: default, high, mid, low, floor",
-                    other
-                )),
-            },
-            InstrumentKind::Tom2 => match preset.as_str() {
-                "default" => Ok(Box::new(Tom2::new(sample_rate))),
-                "derp" => {
-                    let mut tom = Tom2::new(sample_rate);
-                    tom.set_config(Tom2Config::derp());
-                    Ok(Box::new(tom))
-                }
-                "ring" => {
-                    let mut tom = Tom2::new(sample_rate);
-                    tom.set_config(Tom2Config::ring());
-                    Ok(Box::new(tom))
-                }
-                "brush" => {
-                    let mut tom = Tom2::new(sample_rate);
-                    tom.set_config(Tom2Config::brush());
-                    Ok(Box::new(tom))
+            InstrumentKind::Kick => {
+                let config = match preset.as_str() {
+                    "default" => KickConfig::default(),
+                    "tight" => KickConfig::tight(),
+                    "punch" => KickConfig::punch(),
+                    "loose" => KickConfig::loose(),
+                    "dirt" | "dirty" => KickConfig::dirt(),
+                    other => {
+                        return Err(GooeyError::invalid(format!(
+                            "unknown kick preset '{}'. Try: default, tight, punch, loose, dirt",
+                            other
+                        )))
+                    }
+                };
+                let config = config.with_partial(&self.partial(KICK_FIELDS));
+                Ok(Box::new(KickDrum::with_config(sample_rate, config)))
+            }
+            InstrumentKind::Snare => {
+                let config = match preset.as_str() {
+                    "default" | "tight" => SnareConfig::tight(),
+                    "loose" => SnareConfig::loose(),
+                    "hiss" => SnareConfig::hiss(),
+                    "smack" => SnareConfig::smack(),
+                    other => {
+                        return Err(GooeyError::invalid(format!(
+                            "unknown snare preset '{}'. Try: default, tight, loose, hiss, smack",
+                            other
+                        )))
+                    }
+                };
+                let config = config.with_partial(&self.partial(SNARE_FIELDS));
+                Ok(Box::new(SnareDrum::with_config(sample_rate, config)))
+            }
+            InstrumentKind::HiHat => {
+                let config = match preset.as_str() {
+                    "default" | "short" | "closed" | "closed_default" | "closed_tight" => {
+                        HiHatConfig::short()
+                    }
+                    "loose" | "open" | "open_default" | "open_long" => HiHatConfig::loose(),
+                    "dark" | "closed_dark" | "open_bright" => HiHatConfig::dark(),
+                    "soft" => HiHatConfig::soft(),
+                    other => {
+                        return Err(GooeyError::invalid(format!(
+                            "unknown hihat preset '{}'. Try: short, loose, dark, soft",
+                            other
+                        )))
+                    }
+                };
+                let config = config.with_partial(&self.partial(HIHAT_FIELDS));
+                Ok(Box::new(HiHat::with_config(sample_rate, config)))
+            }
+            InstrumentKind::Tom => {
+                let config = match preset.as_str() {
+                    "default" | "mid" | "mid_tom" => TomConfig::mid_tom(),
+                    "high" | "high_tom" => TomConfig::high_tom(),
+                    "low" | "low_tom" => TomConfig::low_tom(),
+                    "floor" | "floor_tom" => TomConfig::floor_tom(),
+                    other => {
+                        return Err(GooeyError::invalid(format!(
+                            "unknown tom preset '{}'. Try: default, high, mid, low, floor",
+                            other
+                        )))
+                    }
+                };
+                let config = config.with_partial(&self.partial(TOM_FIELDS));
+                Ok(Box::new(TomDrum::with_config(sample_rate, config)))
+            }
+            InstrumentKind::Tom2 => {
+                // Tom2's default is the patch baked into `Tom2::new`, not a preset
+                let mut tom = Tom2::new(sample_rate);
+                match preset.as_str() {
+                    "default" => {}
+                    "derp" => tom.set_config(Tom2Config::derp()),
+                    "ring" => tom.set_config(Tom2Config::ring()),
+                    "brush" => tom.set_config(Tom2Config::brush()),
+                    "void" | "void_preset" => tom.set_config(Tom2Config::void_preset()),
+                    other => {
+                        return Err(GooeyError::invalid(format!(
+                            "unknown tom2 preset '{}'. Try: default, derp, ring, brush, void",
+                            other
+                        )))
+                    }
                 }
-                "void" | "void_preset" => {
-                    let mut tom = Tom2::new(sample_rate);
-                    tom.set_config(Tom2Config::void_preset());
-                    Ok(Box::new(tom))
+                let partial = self.partial(TOM2_FIELDS);
+                if !partial.is_empty() {
+                    tom.apply_partial(&partial);
                 }
-                other => Err(format!(
-                    "unknown tom2 preset '{}'. Try: default, derp, ring, brush, void",
-                    other
-                )),
-            },
+                Ok(Box::new(tom))
+            }
         }
     }
+
+    /// Fold the overrides into a partial config; later ones win. Field names
+    /// were checked against `fields` when parsing.
+    fn partial<P: Default>(&self, fields: &[ConfigField<P>]) -> P {
+        let mut partial = P::default();
+        for over in &self.overrides {
+            if let Some(field) = find_field(fields, &over.field) {
+                (field.set)(&mut partial, field.unit.to_config(over.value));
+            }
+        }
+        partial
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -492,8 +735,360 @@ impl InstrumentKind {
             _ => None,
         }
     }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Kick => "kick",
+            Self::Snare => "snare",
+            Self::HiHat => "hihat",
+            Self::Tom => "tom",
+            Self::Tom2 => "tom2",
+        }
+    }
+
+    fn has_field(self, name: &str) -> bool {
+        match self {
+            Self::Kick => find_field(KICK_FIELDS, name).is_some(),
+            Self::Snare => find_field(SNARE_FIELDS, name).is_some(),
+            Self::HiHat => find_field(HIHAT_FIELDS, name).is_some(),
+            Self::Tom => find_field(TOM_FIELDS, name).is_some(),
+            Self::Tom2 => find_field(TOM2_FIELDS, name).is_some(),
+        }
+    }
+
+    /// Canonical override names, for error messages.
+    fn field_names(self) -> Vec<&'static str> {
+        fn canonical<P>(fields: &[ConfigField<P>]) -> Vec<&'static str> {
+            fields.iter().map(|field| field.names[0]).collect()
+        }
+        match self {
+            Self::Kick => canonical(KICK_FIELDS),
+            Self::Snare => canonical(SNARE_FIELDS),
+            Self::HiHat => canonical(HIHAT_FIELDS),
+            Self::Tom => canonical(TOM_FIELDS),
+            Self::Tom2 => canonical(TOM2_FIELDS),
+        }
+    }
 }
 
+/// `inst <name> <type> <preset> <field>=<value>` or `set <name>.<field> <value>`
+#[derive(Clone, Debug)]
+struct ConfigOverride {
+    /// Any of the field's names, lowercased
+    field: String,
+    /// As written, before [`FieldUnit::to_config`]
+    value: f32,
+}
+
+impl ConfigOverride {
+    fn parse(
+        line_number: usize,
+        kind: InstrumentKind,
+        field: &str,
+        value: &str,
+    ) -> Result<Self, GooeyError> {
+        if !kind.has_field(field) {
+            return Err(GooeyError::parse(
+                line_number,
+                format!(
+                    "{} has no field '{}'. Try: {}",
+                    kind.name(),
+                    field,
+                    kind.field_names().join(", ")
+                ),
+            ));
+        }
+        Ok(Self {
+            field: field.to_string(),
+            value: parse_f32(line_number, field, value)?,
+        })
+    }
+}
+
+/// How an override value is written for a config field.
+#[derive(Clone, Copy, Debug)]
+enum FieldUnit {
+    /// As the config stores it: 0-1 amounts, Tom2's 0-100 scale
+    Raw,
+    /// In display units, normalized over `min..max`
+    Range(f32, f32),
+    /// In display units, normalized over `min..max` through the squared
+    /// curve the hi-hat pitch uses
+    SquaredRange(f32, f32),
+}
+
+impl FieldUnit {
+    fn to_config(self, value: f32) -> f32 {
+        match self {
+            Self::Raw => value,
+            Self::Range(min, max) => ((value - min) / (max - min)).clamp(0.0, 1.0),
+            Self::SquaredRange(min, max) => ((value - min) / (max - min)).clamp(0.0, 1.0).sqrt(),
+        }
+    }
+}
+
+/// An overridable config field: its names (canonical first), unit, and the
+/// partial-config slot it writes.
+struct ConfigField<P> {
+    names: &'static [&'static str],
+    unit: FieldUnit,
+    set: fn(&mut P, f32),
+}
+
+const fn field<P>(
+    names: &'static [&'static str],
+    unit: FieldUnit,
+    set: fn(&mut P, f32),
+) -> ConfigField<P> {
+    ConfigField { names, unit, set }
+}
+
+fn find_field<'a, P>(fields: &'a [ConfigField<P>], name: &str) -> Option<&'a ConfigField<P>> {
+    fields.iter().find(|field| field.names.contains(&name))
+}
+
+use FieldUnit::{Range, Raw, SquaredRange};
+
+static KICK_FIELDS: &[ConfigField<PartialKickConfig>] = &[
+    field(
+        &["frequency", "freq"],
+        Range(kick_ranges::FREQ_MIN, kick_ranges::FREQ_MAX),
+        |p, v| p.frequency = Some(v),
+    ),
+    field(&["punch_amount", "punch"], Raw, |p, v| {
+        p.punch_amount = Some(v)
+    }),
+    field(&["sub_amount", "sub"], Raw, |p, v| p.sub_amount = Some(v)),
+    field(&["click_amount", "click"], Raw, |p, v| {
+        p.click_amount = Some(v)
+    }),
+    field(
+        &["oscillator_decay", "osc_decay", "decay"],
+        Range(kick_ranges::OSC_DECAY_MIN, kick_ranges::OSC_DECAY_MAX),
+        |p, v| p.oscillator_decay = Some(v),
+    ),
+    field(
+        &["pitch_envelope_amount", "pitch_env_amt", "pitch_drop"],
+        Raw,
+        |p, v| p.pitch_envelope_amount = Some(v),
+    ),
+    field(
+        &["pitch_envelope_curve", "pitch_env_crv"],
+        Range(kick_ranges::PITCH_CURVE_MIN, kick_ranges::PITCH_CURVE_MAX),
+        |p, v| p.pitch_envelope_curve = Some(v),
+    ),
+    field(&["volume", "vol"], Raw, |p, v| p.volume = Some(v)),
+    field(
+        &["pitch_start_ratio", "pitch_ratio"],
+        Range(kick_ranges::PITCH_RATIO_MIN, kick_ranges::PITCH_RATIO_MAX),
+        |p, v| p.pitch_start_ratio = Some(v),
+    ),
+    field(&["phase_mod_amount", "phase_mod_amt"], Raw, |p, v| {
+        p.phase_mod_amount = Some(v)
+    }),
+    field(&["noise_amount", "noise"], Raw, |p, v| {
+        p.noise_amount = Some(v)
+    }),
+    field(
+        &["noise_cutoff"],
+        Range(kick_ranges::NOISE_CUTOFF_MIN, kick_ranges::NOISE_CUTOFF_MAX),
+        |p, v| p.noise_cutoff = Some(v),
+    ),
+    field(
+        &["noise_resonance", "noise_res"],
+        Range(kick_ranges::NOISE_RES_MIN, kick_ranges::NOISE_RES_MAX),
+        |p, v| p.noise_resonance = Some(v),
+    ),
+    field(&["overdrive_amount", "overdrive", "drive"], Raw, |p, v| {
+        p.overdrive_amount = Some(v)
+    }),
+    field(&["feedback_amount", "feedback"], Raw, |p, v| {
+        p.feedback_amount = Some(v)
+    }),
+    field(&["feedback_cutoff"], Range(200.0, 4000.0), |p, v| {
+        p.feedback_cutoff = Some(v)
+    }),
+    field(
+        &["amp_decay"],
+        Range(kick_ranges::AMP_DECAY_MIN, kick_ranges::AMP_DECAY_MAX),
+        |p, v| p.amp_decay = Some(v),
+    ),
+    field(
+        &["amp_decay_curve"],
+        Range(
+            kick_ranges::AMP_DECAY_CURVE_MIN,
+            kick_ranges::AMP_DECAY_CURVE_MAX,
+        ),
+        |p, v| p.amp_decay_curve = Some(v),
+    ),
+];
+
+static SNARE_FIELDS: &[ConfigField<PartialSnareConfig>] = &[
+    field(
+        &["frequency", "freq"],
+        Range(snare_ranges::FREQ_MIN, snare_ranges::FREQ_MAX),
+        |p, v| p.frequency = Some(v),
+    ),
+    field(&["tonal_amount", "tonal"], Raw, |p, v| {
+        p.tonal_amount = Some(v)
+    }),
+    field(&["noise_amount", "noise"], Raw, |p, v| {
+        p.noise_amount = Some(v)
+    }),
+    field(&["crack_amount", "crack"], Raw, |p, v| {
+        p.crack_amount = Some(v)
+    }),
+    field(
+        &["decay"],
+        Range(snare_ranges::DECAY_MIN, snare_ranges::DECAY_MAX),
+        |p, v| p.decay = Some(v),
+    ),
+    field(&["pitch_drop"], Raw, |p, v| p.pitch_drop = Some(v)),
+    field(&["volume", "vol"], Raw, |p, v| p.volume = Some(v)),
+    field(
+        &["tonal_decay"],
+        Range(snare_ranges::TONAL_DECAY_MIN, snare_ranges::TONAL_DECAY_MAX),
+        |p, v| p.tonal_decay = Some(v),
+    ),
+    field(
+        &["tonal_decay_curve"],
+        Range(
+            snare_ranges::TONAL_DECAY_CURVE_MIN,
+            snare_ranges::TONAL_DECAY_CURVE_MAX,
+        ),
+        |p, v| p.tonal_decay_curve = Some(v),
+    ),
+    field(
+        &["noise_decay"],
+        Range(snare_ranges::NOISE_DECAY_MIN, snare_ranges::NOISE_DECAY_MAX),
+        |p, v| p.noise_decay = Some(v),
+    ),
+    field(
+        &["noise_tail_decay"],
+        Range(
+            snare_ranges::NOISE_TAIL_DECAY_MIN,
+            snare_ranges::NOISE_TAIL_DECAY_MAX,
+        ),
+        |p, v| p.noise_tail_decay = Some(v),
+    ),
+    field(
+        &["filter_cutoff", "cutoff"],
+        Range(
+            snare_ranges::FILTER_CUTOFF_MIN,
+            snare_ranges::FILTER_CUTOFF_MAX,
+        ),
+        |p, v| p.filter_cutoff = Some(v),
+    ),
+    field(
+        &["filter_resonance", "filter_res", "resonance"],
+        Range(snare_ranges::FILTER_RES_MIN, snare_ranges::FILTER_RES_MAX),
+        |p, v| p.filter_resonance = Some(v),
+    ),
+    // 0=LP, 1=BP, 2=HP, 3=notch
+    field(&["filter_type"], Raw, |p, v| {
+        p.filter_type = Some(v.round().clamp(0.0, 3.0) as u8)
+    }),
+    field(&["xfade"], Raw, |p, v| p.xfade = Some(v)),
+    field(&["phase_mod_amount", "phase_mod_amt"], Raw, |p, v| {
+        p.phase_mod_amount = Some(v)
+    }),
+    field(&["overdrive_amount", "overdrive", "drive"], Raw, |p, v| {
+        p.overdrive_amount = Some(v)
+    }),
+    field(
+        &["amp_decay"],
+        Range(snare_ranges::AMP_DECAY_MIN, snare_ranges::AMP_DECAY_MAX),
+        |p, v| p.amp_decay = Some(v),
+    ),
+    field(
+        &["amp_decay_curve"],
+        Range(
+            snare_ranges::AMP_DECAY_CURVE_MIN,
+            snare_ranges::AMP_DECAY_CURVE_MAX,
+        ),
+        |p, v| p.amp_decay_curve = Some(v),
+    ),
+    field(&["noise_color"], Raw, |p, v| p.noise_color = Some(v)),
+    // 0 or 1
+    field(&["crack_velvet", "velvet"], Raw, |p, v| {
+        p.crack_velvet = Some(v >= 0.5)
+    }),
+];
+
+static HIHAT_FIELDS: &[ConfigField<PartialHiHatConfig>] = &[
+    field(
+        &["pitch"],
+        SquaredRange(hihat_ranges::PITCH_MIN, hihat_ranges::PITCH_MAX),
+        |p, v| p.pitch = Some(v),
+    ),
+    field(
+        &["decay"],
+        Range(hihat_ranges::DECAY_MIN_MS, hihat_ranges::DECAY_MAX_MS),
+        |p, v| p.decay = Some(v),
+    ),
+    field(
+        &["open_decay"],
+        Range(hihat_ranges::DECAY_MIN_MS, hihat_ranges::DECAY_MAX_MS),
+        |p, v| p.open_decay = Some(v),
+    ),
+    field(
+        &["attack"],
+        Range(hihat_ranges::ATTACK_MIN_MS, hihat_ranges::ATTACK_MAX_MS),
+        |p, v| p.attack = Some(v),
+    ),
+    field(
+        &["tone"],
+        Range(hihat_ranges::TONE_MIN, hihat_ranges::TONE_MAX),
+        |p, v| p.tone = Some(v),
+    ),
+    field(&["volume", "vol"], Raw, |p, v| p.volume = Some(v)),
+];
+
+static TOM_FIELDS: &[ConfigField<PartialTomConfig>] = &[
+    field(
+        &["frequency", "freq"],
+        Range(tom_ranges::FREQ_MIN, tom_ranges::FREQ_MAX),
+        |p, v| p.frequency = Some(v),
+    ),
+    field(&["tonal_amount", "tonal"], Raw, |p, v| {
+        p.tonal_amount = Some(v)
+    }),
+    field(&["punch_amount", "punch"], Raw, |p, v| {
+        p.punch_amount = Some(v)
+    }),
+    field(
+        &["decay"],
+        Range(tom_ranges::DECAY_MIN, tom_ranges::DECAY_MAX),
+        |p, v| p.decay = Some(v),
+    ),
+    field(&["pitch_drop"], Raw, |p, v| p.pitch_drop = Some(v)),
+    field(&["volume", "vol"], Raw, |p, v| p.volume = Some(v)),
+    field(
+        &["amp_decay"],
+        Range(tom_ranges::AMP_DECAY_MIN, tom_ranges::AMP_DECAY_MAX),
+        |p, v| p.amp_decay = Some(v),
+    ),
+    field(
+        &["amp_decay_curve"],
+        Range(
+            tom_ranges::AMP_DECAY_CURVE_MIN,
+            tom_ranges::AMP_DECAY_CURVE_MAX,
+        ),
+        |p, v| p.amp_decay_curve = Some(v),
+    ),
+];
+
+static TOM2_FIELDS: &[ConfigField<PartialTom2Config>] = &[
+    field(&["tune"], Raw, |p, v| p.tune = Some(v)),
+    field(&["bend"], Raw, |p, v| p.bend = Some(v)),
+    field(&["tone"], Raw, |p, v| p.tone = Some(v)),
+    field(&["color"], Raw, |p, v| p.color = Some(v)),
+    field(&["decay"], Raw, |p, v| p.decay = Some(v)),
+    field(&["membrane"], Raw, |p, v| p.membrane = Some(v)),
+    field(&["membrane_q"], Raw, |p, v| p.membrane_q = Some(v)),
+    field(&["volume", "vol"], Raw, |p, v| p.volume = Some(v)),
+];
+
 #[derive(Clone, Debug)]
 struct SequencerDef {
     instrument: String,
@@ -501,6 +1096,22 @@ struct SequencerDef {
     start: bool,
 }
 
+impl SequencerDef {
+    fn build(&self, bpm: f32, sample_rate: f32) -> Sequencer {
+        let mut seq = Sequencer::with_velocity_pattern(
+            bpm,
+            sample_rate,
+            self.pattern.clone(),
+            self.instrument.as_str(),
+        );
+        // Sequencers often imply "play"; default to started unless explicitly stopped.
+        if self.start {
+            seq.start();
+        }
+        seq
+    }
+}
+
 #[derive(Clone, Debug)]
 struct LfoDef {
     rate: LfoRate,
@@ -510,12 +1121,135 @@ struct LfoDef {
     offset: f32,
 }
 
+impl LfoDef {
+    /// Add the LFO to `engine` and map it to its target. `kind` is the
+    /// target instrument's, for parameter aliases. Nothing is added if the
+    /// target can't be modulated.
+    fn add_to(&self, engine: &mut Engine, kind: Option<InstrumentKind>) -> Result<(), GooeyError> {
+        let parameter = resolve_parameter_alias(kind, self.target_parameter.as_str());
+        engine.check_modulatable(self.target_instrument.as_str(), parameter.as_str())?;
+
+        let sample_rate = engine.sample_rate();
+        let lfo = match self.rate {
+            LfoRate::Hz(freq) => Lfo::new(freq, sample_rate),
+            LfoRate::BpmSync(division) => Lfo::new_synced(division, engine.bpm(), sample_rate),
+        };
+        let idx = engine.add_lfo(lfo);
+        engine.map_lfo_to_parameter(
+            idx,
+            self.target_instrument.as_str(),
+            parameter.as_str(),
+            self.amount,
+        )?;
+        if let Some(lfo_mut) = engine.lfo_mut(idx) {
+            lfo_mut.offset = self.offset;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum LfoRate {
     Hz(f32),
     BpmSync(MusicalDivision),
 }
 
+/// `auto fx.<effect>.<param> <values...> [smooth|step]`
+#[derive(Clone, Debug)]
+struct LaneDef {
+    line_number: usize,
+    /// Effect kind, as returned by [`EffectDef::kind`]
+    effect: &'static str,
+    param: u32,
+    lane: EffectLane,
+}
+
+impl LaneDef {
+    fn parse(line_number: usize, tokens: &[&str]) -> Result<Self, GooeyError> {
+        let usage = || {
+            GooeyError::parse(
+                line_number,
+                "auto expects: auto fx.<effect>.<param> <values...> [smooth]",
+            )
+        };
+        let target = tokens.get(1).ok_or_else(usage)?.to_ascii_lowercase();
+        let (effect, param) = target
+            .strip_prefix("fx.")
+            .and_then(|rest| rest.split_once('.'))
+            .ok_or_else(usage)?;
+        let (effect, param) = resolve_lane_target(effect, param).ok_or_else(|| {
+            GooeyError::parse(
+                line_number,
+                format!(
+                    "can't automate '{}'. Try fx.lowpass.cutoff, fx.delay.mix, fx.sat.drive",
+                    target
+                ),
+            )
+        })?;
+
+        let mut values: Vec<Option<f32>> = Vec::new();
+        let mut interpolate = false;
+        for arg in &tokens[2..] {
+            match arg.to_ascii_lowercase().as_str() {
+                "smooth" | "glide" | "interp" => interpolate = true,
+                "step" | "stepped" => interpolate = false,
+                "." | "-" | "_" => values.push(None),
+                value => values.push(Some(parse_f32(line_number, "auto value", value)?)),
+            }
+        }
+        if values.iter().all(Option::is_none) {
+            return Err(GooeyError::parse(
+                line_number,
+                "auto expects at least one value",
+            ));
+        }
+        if values.len() > EFFECT_LANE_STEPS {
+            return Err(GooeyError::parse(
+                line_number,
+                format!(
+                    "auto takes at most {} values, got {}",
+                    EFFECT_LANE_STEPS,
+                    values.len()
+                ),
+            ));
+        }
+
+        let mut lane = EffectLane::from_values(&values);
+        lane.set_interpolate(interpolate);
+        Ok(Self {
+            line_number,
+            effect,
+            param,
+            lane,
+        })
+    }
+}
+
+/// Map `fx.<effect>.<param>` names to the effect kind and its `*_PARAM_*` id.
+fn resolve_lane_target(effect: &str, param: &str) -> Option<(&'static str, u32)> {
+    match effect {
+        "lowpass" | "lp" => match param {
+            "cutoff" | "cutoff_hz" => Some(("lowpass", FILTER_PARAM_CUTOFF)),
+            "res" | "resonance" => Some(("lowpass", FILTER_PARAM_RESONANCE)),
+            _ => None,
+        },
+        "delay" => match param {
+            "fb" | "feedback" => Some(("delay", DELAY_PARAM_FEEDBACK)),
+            "mix" | "send" => Some(("delay", DELAY_PARAM_MIX)),
+            "cutoff" | "filter" | "lp" => Some(("delay", DELAY_PARAM_FILTER_CUTOFF)),
+            "lowcut" | "hp" => Some(("delay", DELAY_PARAM_LOW_CUT)),
+            _ => None,
+        },
+        "saturation" | "sat" => match param {
+            "drive" => Some(("saturation", SATURATION_PARAM_DRIVE)),
+            "warmth" => Some(("saturation", SATURATION_PARAM_WARMTH)),
+            "mix" => Some(("saturation", SATURATION_PARAM_MIX)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 enum EffectDef {
     Lowpass {
@@ -527,6 +1261,8 @@ enum EffectDef {
         feedback: f32,
         mix: f32,
         filter_cutoff: f32,
+        low_cut: f32,
+        pingpong: bool,
     },
     Saturation {
         drive: f32,
@@ -539,7 +1275,7 @@ enum EffectDef {
 }
 
 impl EffectDef {
-    fn parse(line_number: usize, tokens: &[&str]) -> Result<Self, String> {
+    fn parse(line_number: usize, tokens: &[&str]) -> Result<Self, GooeyError> {
         let fx_type = tokens[0].to_ascii_lowercase();
         match fx_type.as_str() {
             "lowpass" | "lp" => {
@@ -555,6 +1291,8 @@ impl EffectDef {
                 let mut feedback: Option<f32> = None;
                 let mut mix: Option<f32> = None;
                 let mut filter_cutoff: Option<f32> = None;
+                let mut low_cut: Option<f32> = None;
+                let mut pingpong = false;
                 let mut positional: Vec<&str> = Vec::new();
 
                 for arg in &tokens[1..] {
@@ -569,16 +1307,22 @@ impl EffectDef {
                             "mix" => {
                                 mix = Some(parse_f32(line_number, "mix", v)?);
                             }
-                            "cutoff" | "filter" => {
+                            "cutoff" | "filter" | "lp" => {
                                 filter_cutoff = Some(parse_f32(line_number, "cutoff", v)?);
                             }
+                            "lowcut" | "hp" => {
+                                low_cut = Some(parse_f32(line_number, "lowcut", v)?);
+                            }
                             other => {
-                                return Err(format!(
-                                    "line {}: unknown delay argument '{}'",
-                                    line_number, other
+                                return Err(GooeyError::parse(
+                                    line_number,
+                                    format!("unknown delay argument '{}'", other),
                                 ));
                             }
                         }
+                    } else if arg.eq_ignore_ascii_case("pingpong") || arg.eq_ignore_ascii_case("pp")
+                    {
+                        pingpong = true;
                     } else {
                         positional.push(*arg);
                     }
@@ -604,10 +1348,12 @@ impl EffectDef {
                         feedback: fb,
                         mix: m,
                         filter_cutoff: filter_cutoff.unwrap_or(20000.0),
+                        low_cut: low_cut.unwrap_or(20.0),
+                        pingpong,
                     }),
-                    _ => Err(format!(
-                        "line {}: delay expects timing, fb, mix (positional or key=value)",
-                        line_number
+                    _ => Err(GooeyError::parse(
+                        line_number,
+                        "delay expects timing, fb, mix (positional or key=value)",
                     )),
                 }
             }
@@ -625,41 +1371,51 @@ impl EffectDef {
                 let threshold = parse_one_f32_arg_named(line_number, &tokens[1..], "threshold")?;
                 Ok(Self::Limiter { threshold })
             }
-            other => Err(format!(
-                "line {}: unknown effect type '{}'",
-                line_number, other
+            other => Err(GooeyError::parse(
+                line_number,
+                format!("unknown effect type '{}'", other),
             )),
         }
     }
 
-    fn build(&self, sample_rate: f32, bpm: f32) -> Result<Box<dyn Effect>, String> {
+    /// Name `auto` lanes use to find the effect
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Lowpass { .. } => "lowpass",
+            Self::Delay { .. } => "delay",
+            Self::Saturation { .. } => "saturation",
+            Self::Limiter { .. } => "limiter",
+        }
+    }
+
+    /// Effects with automatable parameters are built as [`ChannelEffect`]s,
+    /// which take `*_PARAM_*` writes from effect lanes.
+    fn build(&self, sample_rate: f32, bpm: f32) -> Result<Box<dyn Effect>, GooeyError> {
         match *self {
             Self::Lowpass {
                 cutoff_hz,
                 resonance,
-            } => Ok(Box::new(LowpassFilterEffect::new(
+            } => Ok(Box::new(ChannelEffect::Filter(LowpassFilterEffect::new(
                 sample_rate,
                 cutoff_hz,
                 resonance,
-            ))),
+            )))),
             Self::Delay {
                 timing,
                 feedback,
                 mix,
                 filter_cutoff,
-            } => Ok(Box::new(DelayEffect::new(
-                sample_rate,
-                timing,
-                bpm,
-                feedback,
-                mix,
-                filter_cutoff,
-            ))),
-            Self::Saturation { drive, warmth, mix } => Ok(Box::new(TubeSaturation::new(
-                sample_rate,
-                drive,
-                warmth,
-                mix,
+                low_cut,
+                pingpong,
+            } => {
+                let delay =
+                    DelayEffect::new(sample_rate, timing, bpm, feedback, mix, filter_cutoff);
+                delay.set_low_cut(low_cut);
+                delay.set_pingpong(pingpong);
+                Ok(Box::new(ChannelEffect::Delay(delay)))
+            }
+            Self::Saturation { drive, warmth, mix } => Ok(Box::new(ChannelEffect::Saturation(
+                TubeSaturation::new(sample_rate, drive, warmth, mix),
             ))),
             Self::Limiter { threshold } => Ok(Box::new(SoftLimiter::new(threshold))),
         }
@@ -706,27 +1462,53 @@ fn parse_single_f32_arg(
     statement: &str,
     line_number: usize,
     tokens: &[&str],
-) -> Result<f32, String> {
+) -> Result<f32, GooeyError> {
     match tokens.len() {
         2 => parse_f32(line_number, statement, tokens[1]),
         3 if tokens[1] == "=" => parse_f32(line_number, statement, tokens[2]),
-        _ => Err(format!(
-            "line {}: {} expects a single number (e.g. '{} 120')",
-            line_number, statement, statement
+        _ => Err(GooeyError::parse(
+            line_number,
+            format!(
+                "{} expects a single number (e.g. '{} 120')",
+                statement, statement
+            ),
+        )),
+    }
+}
+
+/// `key <root> [scale]` (scale defaults to major) or `key off`.
+fn parse_key(line_number: usize, tokens: &[&str]) -> Result<Option<(NoteName, Scale)>, GooeyError> {
+    match tokens {
+        [_, off] if off.eq_ignore_ascii_case("off") => Ok(None),
+        [_, root] | [_, root, _] => {
+            let root_note = NoteName::parse(root).ok_or_else(|| {
+                GooeyError::parse(line_number, format!("key: unknown root note '{}'", root))
+            })?;
+            let scale = match tokens.get(2) {
+                Some(name) => Scale::parse(name).ok_or_else(|| {
+                    GooeyError::parse(line_number, format!("key: unknown scale '{}'", name))
+                })?,
+                None => Scale::Major,
+            };
+            Ok(Some((root_note, scale)))
+        }
+        _ => Err(GooeyError::parse(
+            line_number,
+            "key expects `key <root> [scale]` or `key off`",
         )),
     }
 }
 
-fn parse_f32(line_number: usize, what: &str, token: &str) -> Result<f32, String> {
+fn parse_f32(line_number: usize, what: &str, token: &str) -> Result<f32, GooeyError> {
     token.parse::<f32>().map_err(|_| {
-        format!(
-            "line {}: expected a number for {}, got '{}'",
-            line_number, what, token
+        GooeyError::parse(
+            line_number,
+            format!("expected a number for {}, got '{}'", what, token),
         )
     })
 }
 
-fn parse_pattern(line_number: usize, pattern: &str) -> Result<Vec<SequencerStep>, String> {
+fn parse_pattern(line_number: usize, pattern: &str) -> Result<Vec<SequencerStep>, GooeyError> {
     let mut steps: Vec<SequencerStep> = Vec::new();
 
     for ch in pattern.chars() {
@@ -741,16 +1523,19 @@ fn parse_pattern(line_number: usize, pattern: &str) -> Result<Vec<SequencerStep>
                 steps.push(SequencerStep::with_velocity(true, velocity));
             }
             other => {
-                return Err(format!(
-                    "line {}: invalid pattern character '{}'. Use x . - _ | digits 1-9",
-                    line_number, other
+                return Err(GooeyError::parse(
+                    line_number,
+                    format!(
+                        "invalid pattern character '{}'. Use x . - _ | digits 1-9",
+                        other
+                    ),
                 ));
             }
         }
     }
 
     if steps.is_empty() {
-        return Err(format!("line {}: pattern has no steps", line_number));
+        return Err(GooeyError::parse(line_number, "pattern has no steps"));
     }
 
     Ok(steps)
@@ -760,12 +1545,9 @@ fn parse_lfo_rate(
     line_number: usize,
     tokens: &[&str],
     index: &mut usize,
-) -> Result<LfoRate, String> {
+) -> Result<LfoRate, GooeyError> {
     let token = tokens.get(*index).copied().ok_or_else(|| {
-        format!(
-            "line {}: lfo expects a rate (e.g. '1bar' or 'hz 0.5')",
-            line_number
-        )
+        GooeyError::parse(line_number, "lfo expects a rate (e.g. '1bar' or 'hz 0.5')")
     })?;
 
     let token_lc = token.to_ascii_lowercase();
@@ -774,7 +1556,7 @@ fn parse_lfo_rate(
         let freq_token = tokens
             .get(*index)
             .copied()
-            .ok_or_else(|| format!("line {}: lfo hz expects a frequency number", line_number))?;
+            .ok_or_else(|| GooeyError::parse(line_number, "lfo hz expects a frequency number"))?;
         *index += 1;
         let freq = parse_f32(line_number, "lfo frequency", freq_token)?;
         return Ok(LfoRate::Hz(freq));
@@ -791,7 +1573,7 @@ fn parse_lfo_rate(
     Ok(LfoRate::BpmSync(division))
 }
 
-fn parse_division(line_number: usize, token_lc: &str) -> Result<MusicalDivision, String> {
+fn parse_division(line_number: usize, token_lc: &str) -> Result<MusicalDivision, GooeyError> {
     match token_lc {
         "4bars" | "4bar" => Ok(MusicalDivision::FourBars),
         "2bars" | "2bar" => Ok(MusicalDivision::TwoBars),
@@ -801,30 +1583,37 @@ fn parse_division(line_number: usize, token_lc: &str) -> Result<MusicalDivision,
         "eighth" | "1/8" | "1/8note" => Ok(MusicalDivision::Eighth),
         "sixteenth" | "1/16" | "1/16note" => Ok(MusicalDivision::Sixteenth),
         "thirtysecond" | "thirty_second" | "1/32" | "1/32note" => Ok(MusicalDivision::ThirtySecond),
-        _ => Err(format!(
-            "line {}: unknown lfo division '{}'. Try: 1bar, 2bars, 4bars, 1/2, 1/4, 1/8, 1/16, 1/32",
-            line_number, token_lc
+        _ => Err(GooeyError::parse(
+            line_number,
+            format!(
+                "unknown lfo division '{}'. Try: 1bar, 2bars, 4bars, 1/2, 1/4, 1/8, 1/16, 1/32",
+                token_lc
+            ),
         )),
     }
 }
 
-fn parse_target(line_number: usize, token: &str) -> Result<(String, String), String> {
+fn parse_target(line_number: usize, token: &str) -> Result<(String, String), GooeyError> {
     let (instrument, parameter) = token.split_once('.').ok_or_else(|| {
-        format!(
-            "line {}: expected target like 'kick.pitch_drop', got '{}'",
-            line_number, token
+        GooeyError::parse(
+            line_number,
+            format!("expected target like 'kick.pitch_drop', got '{}'", token),
         )
     })?;
     if instrument.is_empty() || parameter.is_empty() {
-        return Err(format!(
-            "line {}: expected target like 'kick.pitch_drop', got '{}'",
-            line_number, token
+        return Err(GooeyError::parse(
+            line_number,
+            format!("expected target like 'kick.pitch_drop', got '{}'", token),
         ));
     }
     Ok((instrument.to_string(), parameter.to_string()))
 }
 
-fn parse_one_f32_arg_named(line_number: usize, args: &[&str], key: &str) -> Result<f32, String> {
+fn parse_one_f32_arg_named(
+    line_number: usize,
+    args: &[&str],
+    key: &str,
+) -> Result<f32, GooeyError> {
     let mut positional: Vec<&str> = Vec::new();
     let mut value: Option<f32> = None;
 
@@ -833,9 +1622,9 @@ fn parse_one_f32_arg_named(line_number: usize, args: &[&str], key: &str) -> Resu
             match k.to_ascii_lowercase().as_str() {
                 "thresh" | "threshold" => value = Some(parse_f32(line_number, key, v)?),
                 other => {
-                    return Err(format!(
-                        "line {}: unknown limiter argument '{}'",
-                        line_number, other
+                    return Err(GooeyError::parse(
+                        line_number,
+                        format!("unknown limiter argument '{}'", other),
                     ));
                 }
             }
@@ -849,9 +1638,12 @@ fn parse_one_f32_arg_named(line_number: usize, args: &[&str], key: &str) -> Resu
     }
 
     value.ok_or_else(|| {
-        format!(
-            "line {}: expected {} value (e.g. 'fx limiter 1.0' or 'fx limiter threshold=1.0')",
-            line_number, key
+        GooeyError::parse(
+            line_number,
+            format!(
+                "expected {} value (e.g. 'fx limiter 1.0' or 'fx limiter threshold=1.0')",
+                key
+            ),
         )
     })
 }
@@ -861,7 +1653,7 @@ fn parse_two_f32_args_named(
     args: &[&str],
     key1: &str,
     key2: &str,
-) -> Result<(f32, f32), String> {
+) -> Result<(f32, f32), GooeyError> {
     let mut positional: Vec<&str> = Vec::new();
     let mut v1: Option<f32> = None;
     let mut v2: Option<f32> = None;
@@ -872,9 +1664,9 @@ fn parse_two_f32_args_named(
                 "cutoff" | "cutoff_hz" => v1 = Some(parse_f32(line_number, key1, v)?),
                 "res" | "resonance" => v2 = Some(parse_f32(line_number, key2, v)?),
                 other => {
-                    return Err(format!(
-                        "line {}: unknown lowpass argument '{}'",
-                        line_number, other
+                    return Err(GooeyError::parse(
+                        line_number,
+                        format!("unknown lowpass argument '{}'", other),
                     ));
                 }
             }
@@ -892,9 +1684,12 @@ fn parse_two_f32_args_named(
 
     match (v1, v2) {
         (Some(a), Some(b)) => Ok((a, b)),
-        _ => Err(format!(
-            "line {}: expected {} and {} (e.g. 'fx lowpass 2000 0.3')",
-            line_number, key1, key2
+        _ => Err(GooeyError::parse(
+            line_number,
+            format!(
+                "expected {} and {} (e.g. 'fx lowpass 2000 0.3')",
+                key1, key2
+            ),
         )),
     }
 }
@@ -905,7 +1700,7 @@ fn parse_three_f32_args_named(
     key1: &str,
     key2: &str,
     key3: &str,
-) -> Result<(f32, f32, f32), String> {
+) -> Result<(f32, f32, f32), GooeyError> {
     let mut positional: Vec<&str> = Vec::new();
     let mut v1: Option<f32> = None;
     let mut v2: Option<f32> = None;
@@ -920,9 +1715,9 @@ fn parse_three_f32_args_named(
                 "drive" => v1 = Some(parse_f32(line_number, key1, v)?),
                 "warmth" => v2 = Some(parse_f32(line_number, key2, v)?),
                 other => {
-                    return Err(format!(
-                        "line {}: unknown effect argument '{}'",
-                        line_number, other
+                    return Err(GooeyError::parse(
+                        line_number,
+                        format!("unknown effect argument '{}'", other),
                     ));
                 }
             }
@@ -943,14 +1738,17 @@ fn parse_three_f32_args_named(
 
     match (v1, v2, v3) {
         (Some(a), Some(b), Some(c)) => Ok((a, b, c)),
-        _ => Err(format!(
-            "line {}: expected {}, {}, {} (positional or key=value)",
-            line_number, key1, key2, key3
+        _ => Err(GooeyError::parse(
+            line_number,
+            format!(
+                "expected {}, {}, {} (positional or key=value)",
+                key1, key2, key3
+            ),
         )),
     }
 }
 
-fn parse_delay_timing(line_number: usize, s: &str) -> Result<DelayTiming, String> {
+fn parse_delay_timing(line_number: usize, s: &str) -> Result<DelayTiming, GooeyError> {
     match s.to_ascii_lowercase().as_str() {
         "whole" | "1" => Ok(DelayTiming::Whole),
         "half" | "1/2" => Ok(DelayTiming::Half),
@@ -961,9 +1759,10 @@ fn parse_delay_timing(line_number: usize, s: &str) -> Result<DelayTiming, String
         "quarter_triplet" | "1/4t" => Ok(DelayTiming::QuarterTriplet),
         "eighth_triplet" | "1/8t" => Ok(DelayTiming::EighthTriplet),
         "sixteenth_triplet" | "1/16t" => Ok(DelayTiming::SixteenthTriplet),
-        other => Err(format!(
-            "line {}: unknown delay timing '{}' (use whole, half, quarter, eighth, sixteenth, or triplet variants like 1/4t)",
-            line_number, other
-        )),
+        "half_dotted" | "1/2d" => Ok(DelayTiming::HalfDotted),
+        "quarter_dotted" | "1/4d" => Ok(DelayTiming::QuarterDotted),
+        "eighth_dotted" | "1/8d" => Ok(DelayTiming::EighthDotted),
+        "sixteenth_dotted" | "1/16d" => Ok(DelayTiming::SixteenthDotted),
+        other => Err(GooeyError::parse(line_number, format!("unknown delay timing '{}' (use whole, half, quarter, eighth, sixteenth, or triplet/dotted variants like 1/4t, 1/8d)", other))),
     }
 }
@@ -0,0 +1,398 @@
+//! Beat-synced stutter / repeat effect
+//!
+//! Continuously records its input into a capture ring buffer. When activated
+//! (typically momentarily, from a performance control) it waits for the next
+//! slice boundary on the beat grid, freezes the last `length` beats, and loops
+//! them in slices of a musical division. Each slice can be pitched (playback
+//! rate, slice boundaries stay on the grid) and reversed.
+
+use crate::effects::{DelayTiming, Effect};
+use crate::frame::StereoFrame;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+/// Capture buffer length in seconds (4 beats at 30 BPM)
+const MAX_CAPTURE_TIME: f32 = 8.0;
+
+/// Captured length in beats (quarter notes)
+const MIN_LENGTH_BEATS: f32 = 0.25;
+const MAX_LENGTH_BEATS: f32 = 4.0;
+
+/// Slice pitch in semitones
+const MAX_PITCH_SEMITONES: f32 = 12.0;
+
+/// Crossfade in/out of the repeat, in ms
+const ENGAGE_FADE_MS: f32 = 5.0;
+
+/// Fade at each slice edge, in ms (shortened for very short slices)
+const SLICE_FADE_MS: f32 = 2.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Stage {
+    /// Recording, not repeating.
+    Idle,
+    /// Activated; waiting for the next slice boundary.
+    Armed,
+    /// Looping the frozen capture.
+    Repeating,
+}
+
+struct BeatRepeatState {
+    // Capture ring buffer per channel (index 0 = mono/left, 1 = right)
+    buffer: [Vec<f32>; 2],
+    write_index: usize,
+    // Free-running beat clock, advanced from the BPM and re-synced by the host
+    beat: f64,
+    stage: Stage,
+    // Frozen loop: start index in the ring, slice size and count
+    loop_start: usize,
+    slice_len: usize,
+    slice_count: usize,
+    // Samples since the repeat latched
+    elapsed: usize,
+    // Engage crossfade (0.0 = dry, 1.0 = repeat)
+    engage: f32,
+}
+
+/// Beat-synced stutter effect
+///
+/// Parameters:
+/// - Slice: Musical division each slice lasts (quarter note, sixteenth, ...)
+/// - Length: How many beats of past audio are looped (0.25-4.0)
+/// - Pitch: Slice playback pitch in semitones (-12 to +12)
+/// - Reverse: Play each slice backwards
+/// - Mix: Wet/dry mix while repeating (1.0 = repeat replaces the input)
+///
+/// [`set_active`](Self::set_active) is the momentary performance toggle; the
+/// repeat starts on the next slice boundary and stops (with a short fade) as
+/// soon as it is released. The mono [`Effect::process`] path uses only the
+/// left capture buffer, for use on a single instrument.
+pub struct BeatRepeat {
+    sample_rate: f32,
+
+    // SAFETY: This is only accessed from the audio thread during process()
+    state: UnsafeCell<BeatRepeatState>,
+
+    active: AtomicBool,
+    timing_target: AtomicU32,
+    bpm_target: AtomicU32,
+    length_target: AtomicU32,
+    pitch_target: AtomicU32,
+    reverse_target: AtomicBool,
+    mix_target: AtomicU32,
+}
+
+// SAFETY: The UnsafeCell is only accessed from a single audio thread
+// The atomic fields are inherently thread-safe
+unsafe impl Send for BeatRepeat {}
+unsafe impl Sync for BeatRepeat {}
+
+impl BeatRepeat {
+    /// Create a new beat repeat
+    ///
+    /// # Arguments
+    /// * `sample_rate` - Audio sample rate in Hz
+    /// * `slice` - Initial slice division
+    /// * `bpm` - Initial BPM for the beat grid
+    /// * `length_beats` - Initial captured length in beats (0.25-4.0)
+    pub fn new(sample_rate: f32, slice: DelayTiming, bpm: f32, length_beats: f32) -> Self {
+        let buffer_size = (sample_rate * MAX_CAPTURE_TIME) as usize + 1;
+        let repeat = Self {
+            sample_rate,
+            state: UnsafeCell::new(BeatRepeatState {
+                buffer: [vec![0.0; buffer_size], vec![0.0; buffer_size]],
+                write_index: 0,
+                beat: 0.0,
+                stage: Stage::Idle,
+                loop_start: 0,
+                slice_len: 1,
+                slice_count: 1,
+                elapsed: 0,
+                engage: 0.0,
+            }),
+            active: AtomicBool::new(false),
+            timing_target: AtomicU32::new(slice.to_timing_constant()),
+            bpm_target: AtomicU32::new(0),
+            length_target: AtomicU32::new(0),
+            pitch_target: AtomicU32::new(0.0_f32.to_bits()),
+            reverse_target: AtomicBool::new(false),
+            mix_target: AtomicU32::new(1.0_f32.to_bits()),
+        };
+        repeat.set_bpm(bpm);
+        repeat.set_length(length_beats);
+        repeat
+    }
+
+    /// Clear the capture buffer and stop any repeat in progress
+    pub fn reset(&self) {
+        // SAFETY: Called from main thread when the effect is not processing
+        let state = unsafe { &mut *self.state.get() };
+        for channel in state.buffer.iter_mut() {
+            channel.fill(0.0);
+        }
+        state.write_index = 0;
+        state.stage = Stage::Idle;
+        state.elapsed = 0;
+        state.engage = 0.0;
+    }
+
+    /// Momentary performance toggle (thread-safe)
+    pub fn set_active(&self, active: bool) {
+        self.active.store(active, Ordering::Relaxed);
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    /// Whether a repeat is currently sounding (including its release fade)
+    pub fn is_repeating(&self) -> bool {
+        // SAFETY: Read-only peek at a Copy field
+        unsafe { (*self.state.get()).stage == Stage::Repeating }
+    }
+
+    /// Align the internal beat clock with the host transport.
+    ///
+    /// Call from the audio thread (e.g. once per render block) with the
+    /// transport position in quarter notes. Without it the clock free-runs
+    /// from zero at the current BPM.
+    pub fn sync_beat_position(&self, beat: f64) {
+        // SAFETY: Only called from the audio thread, like process()
+        let state = unsafe { &mut *self.state.get() };
+        state.beat = beat;
+    }
+
+    pub fn set_timing(&self, slice: DelayTiming) {
+        self.timing_target
+            .store(slice.to_timing_constant(), Ordering::Relaxed);
+    }
+
+    pub fn get_timing(&self) -> u32 {
+        self.timing_target.load(Ordering::Relaxed)
+    }
+
+    pub fn set_bpm(&self, bpm: f32) {
+        self.bpm_target
+            .store(bpm.clamp(30.0, 300.0).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn get_bpm(&self) -> f32 {
+        f32::from_bits(self.bpm_target.load(Ordering::Relaxed))
+    }
+
+    pub fn set_length(&self, beats: f32) {
+        let clamped = beats.clamp(MIN_LENGTH_BEATS, MAX_LENGTH_BEATS);
+        self.length_target
+            .store(clamped.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn get_length(&self) -> f32 {
+        f32::from_bits(self.length_target.load(Ordering::Relaxed))
+    }
+
+    pub fn set_pitch(&self, semitones: f32) {
+        let clamped = semitones.clamp(-MAX_PITCH_SEMITONES, MAX_PITCH_SEMITONES);
+        self.pitch_target
+            .store(clamped.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn get_pitch(&self) -> f32 {
+        f32::from_bits(self.pitch_target.load(Ordering::Relaxed))
+    }
+
+    pub fn set_reverse(&self, reverse: bool) {
+        self.reverse_target.store(reverse, Ordering::Relaxed);
+    }
+
+    pub fn get_reverse(&self) -> bool {
+        self.reverse_target.load(Ordering::Relaxed)
+    }
+
+    pub fn set_mix(&self, mix: f32) {
+        self.mix_target
+            .store(mix.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn get_mix(&self) -> f32 {
+        f32::from_bits(self.mix_target.load(Ordering::Relaxed))
+    }
+
+    fn slice_beats(&self) -> f64 {
+        DelayTiming::from_timing_constant(self.get_timing())
+            .unwrap_or(DelayTiming::Sixteenth)
+            .beats() as f64
+    }
+
+    /// Freeze the last `length` beats as the loop, cut into whole slices.
+    fn latch(&self, state: &mut BeatRepeatState, slice_beats: f64) {
+        let samples_per_beat = 60.0 / self.get_bpm() * self.sample_rate;
+        let capacity = state.buffer[0].len() - 1;
+        let length = ((self.get_length() * samples_per_beat) as usize).clamp(1, capacity);
+        let slice = ((slice_beats as f32 * samples_per_beat) as usize).clamp(1, length);
+
+        state.slice_len = slice;
+        state.slice_count = ((length as f32 / slice as f32).round() as usize).max(1);
+        let loop_len = state.slice_len * state.slice_count;
+        let size = state.buffer[0].len();
+        state.loop_start = (state.write_index + size - loop_len.min(capacity)) % size;
+        state.elapsed = 0;
+        state.stage = Stage::Repeating;
+    }
+
+    /// Read one channel of the repeat at the current position.
+    fn read(&self, state: &BeatRepeatState, channel: usize, rate: f32, reverse: bool) -> f32 {
+        let slice_len = state.slice_len;
+        let slice_index = (state.elapsed / slice_len) % state.slice_count;
+        let phase = (state.elapsed % slice_len) as f32;
+
+        let mut offset = (phase * rate) % slice_len as f32;
+        if reverse {
+            offset = (slice_len - 1) as f32 - offset;
+        }
+
+        let buffer = &state.buffer[channel];
+        let size = buffer.len();
+        let base = state.loop_start + slice_index * slice_len;
+        let whole = offset as usize;
+        let frac = offset - whole as f32;
+        let a = buffer[(base + whole) % size];
+        let b = buffer[(base + (whole + 1).min(slice_len - 1)) % size];
+        let sample = a + (b - a) * frac;
+
+        // Fade the slice edges so the loop points don't click
+        let fade = (SLICE_FADE_MS * 0.001 * self.sample_rate).min(slice_len as f32 / 4.0);
+        let edge = phase.min(slice_len as f32 - phase);
+        sample * (edge / fade.max(1.0)).min(1.0)
+    }
+
+    /// Advance one frame over `channels` channels of `input`.
+    fn step(&self, input: [f32; 2], channels: usize) -> [f32; 2] {
+        // SAFETY: Only accessed from the audio thread
+        let state = unsafe { &mut *self.state.get() };
+
+        let slice_beats = self.slice_beats();
+        let previous_beat = state.beat;
+        state.beat += self.get_bpm() as f64 / 60.0 / self.sample_rate as f64;
+        let on_boundary =
+            (state.beat / slice_beats).floor() != (previous_beat / slice_beats).floor();
+
+        let active = self.is_active();
+        match state.stage {
+            Stage::Idle if active => state.stage = Stage::Armed,
+            Stage::Armed if !active => state.stage = Stage::Idle,
+            Stage::Armed if on_boundary => self.latch(state, slice_beats),
+            _ => {}
+        }
+
+        let engage_step = 1.0 / (ENGAGE_FADE_MS * 0.001 * self.sample_rate);
+        if state.stage == Stage::Repeating && active {
+            state.engage = (state.engage + engage_step).min(1.0);
+        } else {
+            state.engage = (state.engage - engage_step).max(0.0);
+            if state.stage == Stage::Repeating && state.engage == 0.0 {
+                state.stage = Stage::Idle;
+            }
+        }
+
+        // Keep recording until the loop is frozen
+        if state.stage != Stage::Repeating {
+            let size = state.buffer[0].len();
+            for (channel, &sample) in input.iter().enumerate().take(channels) {
+                state.buffer[channel][state.write_index] = sample;
+            }
+            state.write_index = (state.write_index + 1) % size;
+            return input;
+        }
+
+        let rate = 2.0_f32.powf(self.get_pitch() / 12.0);
+        let reverse = self.get_reverse();
+        let wet_gain = self.get_mix() * state.engage;
+        let mut output = input;
+        for (channel, out) in output.iter_mut().enumerate().take(channels) {
+            let wet = self.read(state, channel, rate, reverse);
+            *out = *out * (1.0 - wet_gain) + wet * wet_gain;
+        }
+        state.elapsed += 1;
+        output
+    }
+}
+
+impl Effect for BeatRepeat {
+    fn process(&self, input: f32) -> f32 {
+        self.step([input, 0.0], 1)[0]
+    }
+
+    fn process_stereo(&self, input: StereoFrame) -> StereoFrame {
+        let [l, r] = self.step([input.l, input.r], 2);
+        StereoFrame { l, r }
+    }
+    fn set_bpm(&self, bpm: f32) {
+        BeatRepeat::set_bpm(self, bpm);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48_000.0;
+    // 120 BPM: one beat = 24000 samples, a sixteenth = 6000
+    const BEAT: usize = 24_000;
+
+    /// A ramp that never repeats, so any looping shows up as a repeated value.
+    fn ramp(i: usize) -> f32 {
+        i as f32 / 100_000.0
+    }
+
+    #[test]
+    fn passes_input_while_inactive() {
+        let repeat = BeatRepeat::new(SR, DelayTiming::Sixteenth, 120.0, 1.0);
+        for i in 0..1000 {
+            assert_eq!(repeat.process(ramp(i)), ramp(i));
+        }
+    }
+
+    #[test]
+    fn waits_for_the_slice_boundary_then_loops_the_last_beat() {
+        let repeat = BeatRepeat::new(SR, DelayTiming::Quarter, 120.0, 1.0);
+        repeat.set_active(true);
+        repeat.sync_beat_position(0.5);
+
+        let out: Vec<f32> = (0..3 * BEAT).map(|i| repeat.process(ramp(i))).collect();
+        // Half a beat until the next quarter-note boundary: input passes.
+        assert_eq!(out[BEAT / 2 - 2], ramp(BEAT / 2 - 2));
+        assert!(repeat.is_repeating());
+        // Mid-slice, one loop later, plays what was captured a beat earlier.
+        let mid = BEAT / 2 + 3 * BEAT / 4;
+        assert!((out[mid] - out[mid + BEAT]).abs() < 1e-6);
+        assert!((out[mid] - ramp(mid - BEAT)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn reverse_plays_the_slice_backwards() {
+        let repeat = BeatRepeat::new(SR, DelayTiming::Quarter, 120.0, 1.0);
+        repeat.set_reverse(true);
+        repeat.set_active(true);
+        let out: Vec<f32> = (0..2 * BEAT).map(|i| repeat.process(ramp(i))).collect();
+        let quarter = BEAT + BEAT / 4;
+        let three_quarters = BEAT + 3 * BEAT / 4;
+        assert!(out[quarter] > out[three_quarters]);
+    }
+
+    #[test]
+    fn release_fades_back_to_the_input() {
+        let repeat = BeatRepeat::new(SR, DelayTiming::Sixteenth, 120.0, 0.25);
+        repeat.set_active(true);
+        for i in 0..BEAT {
+            repeat.process(ramp(i));
+        }
+        repeat.set_active(false);
+        for i in 0..1000 {
+            repeat.process(ramp(i));
+        }
+        assert!(!repeat.is_repeating());
+        assert_eq!(repeat.process(0.5), 0.5);
+    }
+}
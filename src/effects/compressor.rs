@@ -1,12 +1,14 @@
 use crate::effects::Effect;
 use crate::frame::StereoFrame;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::denormal::DENORMAL_THRESHOLD;
 use crate::utils::oversampler::{Oversampler, OversamplingMode};
 use crate::utils::smoother::SmoothedParam;
-use std::cell::UnsafeCell;
-use std::f32::consts::FRAC_2_PI;
-use std::sync::atomic::{AtomicU32, AtomicU8, Ordering};
+use core::cell::UnsafeCell;
+use core::f32::consts::FRAC_2_PI;
+use core::sync::atomic::{AtomicU32, AtomicU8, Ordering};
 
-const DENORMAL_THRESHOLD: f32 = 1e-15;
 const DC_BLOCKER_COEFF: f32 = 0.995;
 const KNEE_WIDTH_DB: f32 = 6.0;
 const HALF_KNEE_DB: f32 = KNEE_WIDTH_DB * 0.5;
@@ -511,7 +513,7 @@ mod tests {
         ) -> Vec<f32> {
             (0..warmup + samples)
                 .filter_map(|i| {
-                    let input = (std::f32::consts::TAU * test_freq * i as f32 / sr).sin() * 0.9;
+                    let input = (core::f32::consts::TAU * test_freq * i as f32 / sr).sin() * 0.9;
                     let output = comp.process(input);
                     (i >= warmup).then_some(output)
                 })
@@ -522,7 +524,7 @@ mod tests {
             alias_freqs
                 .iter()
                 .map(|&af| {
-                    let phase_step = std::f64::consts::TAU * af as f64 / sr as f64;
+                    let phase_step = core::f64::consts::TAU * af as f64 / sr as f64;
                     let (real, imag) = samples.iter().enumerate().fold(
                         (0.0_f64, 0.0_f64),
                         |(real, imag), (i, &x)| {
@@ -1,20 +1,26 @@
 //! Filter delay effect with BPM-synced timing
 //!
-//! Delay effect that uses clocked musical divisions (including triplets) instead of
-//! arbitrary millisecond timing. A one-pole lowpass filter is applied in the feedback
-//! path so each repetition gets progressively darker, like a classic filter delay.
+//! Delay effect that uses clocked musical divisions (including triplets and dotted
+//! notes) instead of arbitrary millisecond timing. A lowpass and a low-cut filter sit
+//! in the feedback path so each repetition gets progressively darker and thinner,
+//! like a classic filter delay. Ping-pong mode bounces echoes between channels, and
+//! freeze holds the current buffer contents as an endless loop.
 
 use crate::effects::Effect;
 use crate::frame::StereoFrame;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::denormal::DENORMAL_THRESHOLD;
 use crate::utils::smoother::SmoothedParam;
-use std::cell::UnsafeCell;
-use std::sync::atomic::{AtomicU32, Ordering};
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicU32, Ordering};
 
 /// Maximum delay time in seconds (enough for a whole note at ~48 BPM)
 const MAX_DELAY_TIME: f32 = 5.0;
 
-/// Threshold for flushing denormal numbers to zero
-const DENORMAL_THRESHOLD: f32 = 1e-15;
+/// Length of the crossfade from the old delay time to the new one after a
+/// tempo change, in milliseconds
+const TEMPO_CROSSFADE_MS: f32 = 50.0;
 
 /// Minimum filter cutoff in Hz
 const MIN_FILTER_CUTOFF: f32 = 20.0;
@@ -43,6 +49,14 @@ pub enum DelayTiming {
     EighthTriplet,
     /// Sixteenth note triplet (2/3 of a sixteenth note = 1/6 beat)
     SixteenthTriplet,
+    /// Dotted half note (3/2 of a half note = 3 beats)
+    HalfDotted,
+    /// Dotted quarter note (3/2 of a quarter note = 3/2 beats)
+    QuarterDotted,
+    /// Dotted eighth note (3/2 of an eighth note = 3/4 beat)
+    EighthDotted,
+    /// Dotted sixteenth note (3/2 of a sixteenth note = 3/8 beat)
+    SixteenthDotted,
 }
 
 impl DelayTiming {
@@ -58,6 +72,10 @@ impl DelayTiming {
             DelayTiming::QuarterTriplet => 2.0 / 3.0,
             DelayTiming::EighthTriplet => 1.0 / 3.0,
             DelayTiming::SixteenthTriplet => 1.0 / 6.0,
+            DelayTiming::HalfDotted => 3.0,
+            DelayTiming::QuarterDotted => 1.5,
+            DelayTiming::EighthDotted => 0.75,
+            DelayTiming::SixteenthDotted => 0.375,
         }
     }
 
@@ -79,6 +97,10 @@ impl DelayTiming {
             6 => Some(DelayTiming::QuarterTriplet),
             7 => Some(DelayTiming::EighthTriplet),
             8 => Some(DelayTiming::SixteenthTriplet),
+            9 => Some(DelayTiming::HalfDotted),
+            10 => Some(DelayTiming::QuarterDotted),
+            11 => Some(DelayTiming::EighthDotted),
+            12 => Some(DelayTiming::SixteenthDotted),
             _ => None,
         }
     }
@@ -95,6 +117,10 @@ impl DelayTiming {
             DelayTiming::QuarterTriplet => 6,
             DelayTiming::EighthTriplet => 7,
             DelayTiming::SixteenthTriplet => 8,
+            DelayTiming::HalfDotted => 9,
+            DelayTiming::QuarterDotted => 10,
+            DelayTiming::EighthDotted => 11,
+            DelayTiming::SixteenthDotted => 12,
         }
     }
 }
@@ -109,14 +135,27 @@ struct DelayState {
     filter_z1: f32,
     filter_z2: f32,
 
+    // One-pole lowpass state whose output is subtracted to form the low cut
+    low_cut_z: f32,
+
     // Track previous timing to detect changes and clear the buffer
     previous_timing: u32,
 
+    // Delay time being read, in seconds. A tempo change crossfades from a
+    // tap at the old time (`fade_from`) to one at the new time over
+    // TEMPO_CROSSFADE_MS rather than sweeping the read head, which would
+    // pitch the echoes; `fade` is the progress, 1.0 once settled.
+    time: f32,
+    fade_from: f32,
+    fade: f32,
+
     // Smoothed parameters (updated per-sample for click-free changes)
-    time_smoothed: SmoothedParam,
     feedback_smoothed: SmoothedParam,
     mix_smoothed: SmoothedParam,
     filter_cutoff_smoothed: SmoothedParam,
+    low_cut_smoothed: SmoothedParam,
+    // 0 = live, 1 = frozen; smoothed so engaging freeze doesn't click
+    freeze_smoothed: SmoothedParam,
 }
 
 /// Filter delay effect with BPM-synced timing
@@ -126,8 +165,11 @@ struct DelayState {
 /// - Feedback: Amount of delayed signal fed back (0.0 to 0.95)
 /// - Mix: Wet/dry mix (0.0 = dry only, 1.0 = wet only)
 /// - Filter Cutoff: Lowpass filter cutoff in Hz applied in the feedback path (20-20000 Hz)
+/// - Low Cut: Highpass cutoff in Hz applied in the feedback path (20-20000 Hz, 20 = off)
+/// - Ping-pong: Echoes alternate between left and right
+/// - Freeze: Loop the buffer indefinitely, ignoring new input
 ///
-/// The lowpass filter is applied in the feedback loop, so each successive echo
+/// The filters are applied in the feedback loop, so each successive echo
 /// gets progressively darker — the classic "filter delay" / "tape delay" sound.
 pub struct DelayEffect {
     sample_rate: f32,
@@ -143,6 +185,9 @@ pub struct DelayEffect {
     feedback_target: AtomicU32,
     mix_target: AtomicU32,
     filter_cutoff_target: AtomicU32,
+    low_cut_target: AtomicU32,
+    // Freeze (0 = off, 1 = on)
+    freeze_target: AtomicU32,
     // Ping-pong mode (0 = off: independent dual-mono delay; 1 = on: feedback
     // crosses channels so echoes bounce left/right).
     pingpong_target: AtomicU32,
@@ -153,6 +198,10 @@ pub struct DelayEffect {
 struct DelayStep {
     /// The filtered delayed sample (the wet tap heard on this channel).
     filtered_delay: f32,
+    /// The unfiltered delayed sample, recirculated at unity while frozen.
+    raw_delay: f32,
+    /// Smoothed freeze amount (0 = live, 1 = frozen).
+    freeze: f32,
     /// Smoothed feedback amount for this sample.
     feedback: f32,
     /// Smoothed wet/dry mix for this sample.
@@ -198,9 +247,11 @@ impl DelayEffect {
             write_index: 0,
             filter_z1: 0.0,
             filter_z2: 0.0,
+            low_cut_z: 0.0,
             previous_timing: timing.to_timing_constant(),
-            // Use 50ms smoothing for delay time to avoid zipper noise
-            time_smoothed: SmoothedParam::new(time, 0.0, MAX_DELAY_TIME, sample_rate, 50.0),
+            time,
+            fade_from: time,
+            fade: 1.0,
             // Use 30ms smoothing for feedback and mix
             feedback_smoothed: SmoothedParam::new(feedback_clamped, 0.0, 0.95, sample_rate, 30.0),
             mix_smoothed: SmoothedParam::new(mix_clamped, 0.0, 1.0, sample_rate, 30.0),
@@ -211,6 +262,14 @@ impl DelayEffect {
                 sample_rate,
                 30.0,
             ),
+            low_cut_smoothed: SmoothedParam::new(
+                MIN_FILTER_CUTOFF,
+                MIN_FILTER_CUTOFF,
+                MAX_FILTER_CUTOFF,
+                sample_rate,
+                30.0,
+            ),
+            freeze_smoothed: SmoothedParam::new(0.0, 0.0, 1.0, sample_rate, 10.0),
         };
 
         Self {
@@ -221,6 +280,8 @@ impl DelayEffect {
             feedback_target: AtomicU32::new(feedback_clamped.to_bits()),
             mix_target: AtomicU32::new(mix_clamped.to_bits()),
             filter_cutoff_target: AtomicU32::new(cutoff_clamped.to_bits()),
+            low_cut_target: AtomicU32::new(MIN_FILTER_CUTOFF.to_bits()),
+            freeze_target: AtomicU32::new(0),
             pingpong_target: AtomicU32::new(0),
         }
     }
@@ -234,6 +295,7 @@ impl DelayEffect {
             state.write_index = 0;
             state.filter_z1 = 0.0;
             state.filter_z2 = 0.0;
+            state.low_cut_z = 0.0;
         }
     }
 
@@ -254,6 +316,22 @@ impl DelayEffect {
         self.pingpong_target.load(Ordering::Relaxed) != 0
     }
 
+    /// Freeze or release the delay buffer (thread-safe).
+    ///
+    /// While frozen, no new input enters the delay line and the buffer
+    /// recirculates at unity gain, bypassing the feedback filters, so whatever
+    /// was captured loops indefinitely. The wet/dry mix is unchanged, so the
+    /// dry signal still plays over the frozen loop.
+    pub fn set_freeze(&self, on: bool) {
+        self.freeze_target
+            .store(if on { 1 } else { 0 }, Ordering::Relaxed);
+    }
+
+    /// Get current freeze state (true = frozen).
+    pub fn get_freeze(&self) -> bool {
+        self.freeze_target.load(Ordering::Relaxed) != 0
+    }
+
     /// Get current timing division constant
     pub fn get_timing(&self) -> u32 {
         self.timing_target.load(Ordering::Relaxed)
@@ -279,13 +357,20 @@ impl DelayEffect {
         f32::from_bits(self.filter_cutoff_target.load(Ordering::Relaxed))
     }
 
+    /// Get current low-cut frequency in Hz
+    pub fn get_low_cut(&self) -> f32 {
+        f32::from_bits(self.low_cut_target.load(Ordering::Relaxed))
+    }
+
     /// Set timing division (thread-safe, changes are smoothed)
     pub fn set_timing(&self, timing: DelayTiming) {
         self.timing_target
             .store(timing.to_timing_constant(), Ordering::Relaxed);
     }
 
-    /// Set BPM (thread-safe, delay time recalculates automatically)
+    /// Set BPM (thread-safe, delay time recalculates automatically). The
+    /// echoes crossfade from the old time to the new one, so tempo changes
+    /// neither click nor bend the pitch.
     pub fn set_bpm(&self, bpm: f32) {
         self.bpm_target.store(bpm.to_bits(), Ordering::Relaxed);
     }
@@ -309,6 +394,14 @@ impl DelayEffect {
         self.filter_cutoff_target
             .store(clamped.to_bits(), Ordering::Relaxed);
     }
+
+    /// Set low-cut (highpass) frequency in Hz (thread-safe, changes are smoothed).
+    /// At the 20 Hz minimum the low cut is bypassed.
+    pub fn set_low_cut(&self, cutoff: f32) {
+        let clamped = cutoff.clamp(MIN_FILTER_CUTOFF, MAX_FILTER_CUTOFF);
+        self.low_cut_target
+            .store(clamped.to_bits(), Ordering::Relaxed);
+    }
 }
 
 impl DelayEffect {
@@ -328,6 +421,8 @@ impl DelayEffect {
         let feedback_target = f32::from_bits(self.feedback_target.load(Ordering::Relaxed));
         let mix_target = f32::from_bits(self.mix_target.load(Ordering::Relaxed));
         let cutoff_target = f32::from_bits(self.filter_cutoff_target.load(Ordering::Relaxed));
+        let low_cut_target = f32::from_bits(self.low_cut_target.load(Ordering::Relaxed));
+        let freeze_target = self.freeze_target.load(Ordering::Relaxed) as f32;
 
         // Detect timing changes and clear buffer to avoid sweep artifacts
         if timing_const != state.previous_timing {
@@ -335,43 +430,45 @@ impl DelayEffect {
             state.buffer.fill(0.0);
             state.filter_z1 = 0.0;
             state.filter_z2 = 0.0;
-            // Jump time smoother immediately to the new target
-            state.time_smoothed.set_immediate(time_target);
+            state.low_cut_z = 0.0;
+            // Jump straight to the new time
+            state.time = time_target;
+            state.fade = 1.0;
+        } else if state.fade >= 1.0 && time_target != state.time {
+            // Tempo change; one still fading finishes first
+            state.fade_from = state.time;
+            state.time = time_target;
+            state.fade = 0.0;
         }
 
-        state.time_smoothed.set_target(time_target);
         state.feedback_smoothed.set_target(feedback_target);
         state.mix_smoothed.set_target(mix_target);
         state.filter_cutoff_smoothed.set_target(cutoff_target);
+        state.low_cut_smoothed.set_target(low_cut_target);
+        state.freeze_smoothed.set_target(freeze_target);
 
         // Get smoothed values for this sample
-        let time = state.time_smoothed.tick();
         let feedback = state.feedback_smoothed.tick();
         let mix = state.mix_smoothed.tick();
         let cutoff = state.filter_cutoff_smoothed.tick();
-
-        // Calculate delay in samples (with fractional interpolation)
-        let delay_samples = time * self.sample_rate;
-        let delay_int = delay_samples as usize;
-        let delay_frac = delay_samples - delay_int as f32;
-
-        let buffer_len = state.buffer.len();
-
-        // Read from delay line with linear interpolation
-        let read_index_1 = (state.write_index + buffer_len - delay_int) % buffer_len;
-        let read_index_2 = (state.write_index + buffer_len - delay_int - 1) % buffer_len;
-
-        let sample_1 = state.buffer[read_index_1];
-        let sample_2 = state.buffer[read_index_2];
-
-        // Linear interpolation between adjacent samples
-        let delayed_sample = sample_1 * (1.0 - delay_frac) + sample_2 * delay_frac;
+        let low_cut = state.low_cut_smoothed.tick();
+        let freeze = state.freeze_smoothed.tick();
+
+        let delayed_sample = if state.fade < 1.0 {
+            let from = self.read_tap(state, state.fade_from);
+            let to = self.read_tap(state, state.time);
+            let sample = from + (to - from) * state.fade;
+            state.fade = (state.fade + 1000.0 / (TEMPO_CROSSFADE_MS * self.sample_rate)).min(1.0);
+            sample
+        } else {
+            self.read_tap(state, state.time)
+        };
 
         // Apply two-pole resonant lowpass filter to the delayed signal.
         // This filters both the wet output and the feedback path, so every echo
         // is audibly filtered and successive echoes get progressively darker.
         // g = 1 - exp(-2π * fc / fs)
-        let g = 1.0 - (-2.0 * std::f32::consts::PI * cutoff / self.sample_rate).exp();
+        let g = 1.0 - (-2.0 * core::f32::consts::PI * cutoff / self.sample_rate).exp();
         // Resonance: feed back the difference between poles to create a peak at cutoff.
         // Fixed moderate resonance (0.3) adds warmth without risk of self-oscillation.
         let resonance = 0.3;
@@ -381,7 +478,24 @@ impl DelayEffect {
         // Second pole (cascaded for 12dB/oct rolloff)
         state.filter_z2 = state.filter_z2 + g * (state.filter_z1 - state.filter_z2);
 
-        let filtered_delay = state.filter_z2;
+        // A non-finite filter state would otherwise recirculate forever
+        if !state.filter_z2.is_finite() {
+            state.buffer.fill(0.0);
+            state.filter_z1 = 0.0;
+            state.filter_z2 = 0.0;
+        }
+
+        let mut filtered_delay = state.filter_z2;
+
+        // One-pole low cut: subtract the signal's own lowpassed copy. Skipped
+        // at the minimum so the default delay sound is untouched.
+        if low_cut > MIN_FILTER_CUTOFF {
+            let g = 1.0 - (-2.0 * core::f32::consts::PI * low_cut / self.sample_rate).exp();
+            state.low_cut_z += g * (filtered_delay - state.low_cut_z);
+            filtered_delay -= state.low_cut_z;
+        } else {
+            state.low_cut_z = 0.0;
+        }
 
         // Flush denormals on filter state
         if state.filter_z1.abs() < DENORMAL_THRESHOLD {
@@ -390,19 +504,44 @@ impl DelayEffect {
         if state.filter_z2.abs() < DENORMAL_THRESHOLD {
             state.filter_z2 = 0.0;
         }
+        if state.low_cut_z.abs() < DENORMAL_THRESHOLD {
+            state.low_cut_z = 0.0;
+        }
 
         DelayStep {
             filtered_delay,
+            raw_delay: delayed_sample,
+            freeze,
             feedback,
             mix,
         }
     }
 
+    /// Read the delay line `time` seconds back, interpolating between samples.
+    fn read_tap(&self, state: &DelayState, time: f32) -> f32 {
+        // Calculate delay in samples (with fractional interpolation)
+        let delay_samples = time * self.sample_rate;
+        let delay_int = delay_samples as usize;
+        let delay_frac = delay_samples - delay_int as f32;
+
+        let buffer_len = state.buffer.len();
+
+        // Read from delay line with linear interpolation
+        let read_index_1 = (state.write_index + buffer_len - delay_int) % buffer_len;
+        let read_index_2 = (state.write_index + buffer_len - delay_int - 1) % buffer_len;
+
+        let sample_1 = state.buffer[read_index_1];
+        let sample_2 = state.buffer[read_index_2];
+
+        // Linear interpolation between adjacent samples
+        sample_1 * (1.0 - delay_frac) + sample_2 * delay_frac
+    }
+
     /// Per-sample write phase for one channel.
     ///
     /// `inject_input` is mixed into the delay line (driving the echoes);
-    /// `feedback_tap` is the filtered delayed sample fed back at `step.feedback`
-    /// (normally this channel's own tap, or the partner channel's in ping-pong).
+    /// `feedback_from` is the read phase whose tap is fed back at `step.feedback`
+    /// (normally this channel's own step, or the partner channel's in ping-pong).
     /// `dry_input` is the dry signal used in the wet/dry output mix.
     fn step_write(
         &self,
@@ -410,12 +549,14 @@ impl DelayEffect {
         dry_input: f32,
         inject_input: f32,
         step: &DelayStep,
-        feedback_tap: f32,
+        feedback_from: &DelayStep,
     ) -> f32 {
         let buffer_len = state.buffer.len();
 
-        // Write input plus filtered feedback to delay line
-        let write_sample = inject_input + feedback_tap * step.feedback;
+        // Write input plus filtered feedback to delay line. Freezing crossfades
+        // to the unfiltered tap at unity with no new input.
+        let live = inject_input + feedback_from.filtered_delay * step.feedback;
+        let write_sample = live * (1.0 - step.freeze) + feedback_from.raw_delay * step.freeze;
 
         // Flush denormals and NaN protection
         let write_sample = if write_sample.is_finite() && write_sample.abs() > DENORMAL_THRESHOLD {
@@ -443,8 +584,7 @@ impl DelayEffect {
         // NaN/infinity protection at input - treat invalid input as silence
         let input = if input.is_finite() { input } else { 0.0 };
         let step = self.step_read(state);
-        let tap = step.filtered_delay;
-        self.step_write(state, input, input, &step, tap)
+        self.step_write(state, input, input, &step, &step)
     }
 }
 
@@ -479,16 +619,17 @@ impl Effect for DelayEffect {
         let step_l = self.step_read(&mut states[0]);
         let step_r = self.step_read(&mut states[1]);
 
-        let tap_l = step_l.filtered_delay;
-        let tap_r = step_r.filtered_delay;
-
         // Left buffer: dry input + right's delayed tap. Right buffer: no direct
         // input, fed only by left's delayed tap.
-        let out_l = self.step_write(&mut states[0], l_in, l_in, &step_l, tap_r);
-        let out_r = self.step_write(&mut states[1], r_in, 0.0, &step_r, tap_l);
+        let out_l = self.step_write(&mut states[0], l_in, l_in, &step_l, &step_r);
+        let out_r = self.step_write(&mut states[1], r_in, 0.0, &step_r, &step_l);
 
         StereoFrame { l: out_l, r: out_r }
     }
+
+    fn set_bpm(&self, bpm: f32) {
+        DelayEffect::set_bpm(self, bpm);
+    }
 }
 
 #[cfg(test)]
@@ -505,6 +646,9 @@ mod tests {
         // Triplets: 2/3 of the straight division
         assert!((DelayTiming::QuarterTriplet.beats() - 2.0 / 3.0).abs() < 1e-6);
         assert!((DelayTiming::EighthTriplet.beats() - 1.0 / 3.0).abs() < 1e-6);
+        // Dotted: 3/2 of the straight division
+        assert_eq!(DelayTiming::QuarterDotted.beats(), 1.5);
+        assert_eq!(DelayTiming::EighthDotted.beats(), 0.75);
     }
 
     #[test]
@@ -524,11 +668,11 @@ mod tests {
 
     #[test]
     fn test_delay_timing_roundtrip() {
-        for i in 0..=8 {
+        for i in 0..=12 {
             let timing = DelayTiming::from_timing_constant(i).unwrap();
             assert_eq!(timing.to_timing_constant(), i);
         }
-        assert!(DelayTiming::from_timing_constant(9).is_none());
+        assert!(DelayTiming::from_timing_constant(13).is_none());
     }
 
     #[test]
@@ -665,4 +809,71 @@ mod tests {
         // The stored BPM target should reflect the change
         assert_eq!(delay.get_bpm(), 60.0);
     }
+    #[test]
+    fn test_delay_tempo_change_crossfades_between_taps() {
+        // Zero feedback, fully wet: an impulse echoes once, a quarter later
+        // (24000 samples at 120 BPM / 48 kHz)
+        let render = |change_at: Option<usize>| {
+            let delay = DelayEffect::new(48_000.0, DelayTiming::Quarter, 120.0, 0.0, 1.0, 20000.0);
+            (0..24_100)
+                .map(|n| {
+                    if change_at == Some(n) {
+                        delay.set_bpm(240.0);
+                    }
+                    delay.process(if n == 0 { 1.0 } else { 0.0 })
+                })
+                .collect::<Vec<_>>()
+        };
+        let steady = render(None);
+        // Doubling the tempo just before the echo is due: the new tap (12000
+        // back) reads silence and the old one fades out under it, instead of
+        // the read head sweeping across and pitching the echo
+        let changed = render(Some(23_900));
+        let fade = 100.0 / (TEMPO_CROSSFADE_MS * 48.0);
+        assert!(steady[24_000] > 0.5);
+        assert!((changed[24_000] - steady[24_000] * (1.0 - fade)).abs() < 1e-4);
+        assert_eq!(changed[..23_900], steady[..23_900]);
+    }
+
+    #[test]
+    fn test_delay_freeze_loops_buffer_and_ignores_input() {
+        // Zero feedback: a live delay would echo the impulse exactly once.
+        let delay = DelayEffect::new(44100.0, DelayTiming::Sixteenth, 120.0, 0.0, 1.0, 20000.0);
+        let period = (DelayTiming::Sixteenth.to_seconds(120.0) * 44100.0) as usize;
+
+        delay.process(1.0);
+        for _ in 1..period / 2 {
+            delay.process(0.0);
+        }
+        delay.set_freeze(true);
+        assert!(delay.get_freeze());
+
+        // New input while frozen must not enter the loop.
+        let mut peaks = Vec::new();
+        for _ in 0..6 {
+            let peak = (0..period)
+                .map(|_| delay.process(0.5).abs())
+                .fold(0.0_f32, f32::max);
+            peaks.push(peak);
+        }
+        for peak in &peaks[1..] {
+            assert!(
+                (peak - peaks[0]).abs() < 0.05,
+                "frozen loop should hold its level: {peaks:?}"
+            );
+        }
+        assert!(peaks[0] > 0.5, "frozen impulse should keep repeating");
+    }
+
+    #[test]
+    fn test_delay_low_cut_removes_dc_from_echoes() {
+        let run = |low_cut: f32| {
+            let delay = DelayEffect::new(44100.0, DelayTiming::Sixteenth, 120.0, 0.5, 1.0, 20000.0);
+            delay.set_low_cut(low_cut);
+            (0..44100).map(|_| delay.process(0.5)).last().unwrap()
+        };
+        // Constant input: the echoes build up to DC unless the low cut blocks it.
+        assert!(run(20.0) > 0.5);
+        assert!(run(500.0).abs() < 0.01);
+    }
 }
@@ -0,0 +1,250 @@
+use crate::effects::Effect;
+use crate::frame::StereoFrame;
+use alloc::sync::Arc;
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+const MAX_ATTACK_MS: f32 = 50.0;
+const MAX_HOLD_MS: f32 = 500.0;
+const MIN_RELEASE_MS: f32 = 5.0;
+const MAX_RELEASE_MS: f32 = 1000.0;
+
+/// Handle that tells a [`Ducker`] an instrument was triggered. Cheap to clone
+/// and safe to fire from any thread; the ducker picks it up on its next sample.
+#[derive(Clone, Debug)]
+pub struct DuckTrigger(Arc<AtomicU32>);
+
+impl DuckTrigger {
+    pub fn fire(&self) {
+        self.0.fetch_add(1, Ordering::Release);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Stage {
+    Idle,
+    Attack,
+    Hold,
+    Release,
+}
+
+struct DuckerState {
+    /// Current reduction amount, 0.0 (none) to 1.0 (full depth).
+    envelope: f32,
+    stage: Stage,
+    hold_remaining: u32,
+}
+
+/// Trigger-driven ducking: attenuates the bus it is inserted on whenever a
+/// source instrument fires, following an attack / hold / release envelope.
+///
+/// Unlike a sidechain compressor there is no level detection — the duck is
+/// keyed directly off trigger events (see [`Ducker::trigger_handle`]), so it
+/// is cheap and lands exactly on the hit. Ramps are linear in gain.
+pub struct Ducker {
+    sample_rate: f32,
+    state: UnsafeCell<DuckerState>,
+    pending: Arc<AtomicU32>,
+    depth: AtomicU32,
+    attack_ms: AtomicU32,
+    hold_ms: AtomicU32,
+    release_ms: AtomicU32,
+}
+
+// SAFETY: UnsafeCell is only accessed from a single audio thread.
+// Atomic fields are inherently thread-safe.
+unsafe impl Send for Ducker {}
+unsafe impl Sync for Ducker {}
+
+impl Ducker {
+    /// # Arguments
+    /// * `depth` - Attenuation at full duck (0.0 = none, 1.0 = silence)
+    /// * `attack_ms` - Time to reach full depth (0-50 ms)
+    /// * `hold_ms` - Time held at full depth (0-500 ms)
+    /// * `release_ms` - Time to recover (5-1000 ms)
+    pub fn new(
+        sample_rate: f32,
+        depth: f32,
+        attack_ms: f32,
+        hold_ms: f32,
+        release_ms: f32,
+    ) -> Self {
+        let ducker = Self {
+            sample_rate,
+            state: UnsafeCell::new(DuckerState {
+                envelope: 0.0,
+                stage: Stage::Idle,
+                hold_remaining: 0,
+            }),
+            pending: Arc::new(AtomicU32::new(0)),
+            depth: AtomicU32::new(0),
+            attack_ms: AtomicU32::new(0),
+            hold_ms: AtomicU32::new(0),
+            release_ms: AtomicU32::new(0),
+        };
+        ducker.set_depth(depth);
+        ducker.set_attack(attack_ms);
+        ducker.set_hold(hold_ms);
+        ducker.set_release(release_ms);
+        ducker
+    }
+
+    /// A handle to notify this ducker of source triggers.
+    pub fn trigger_handle(&self) -> DuckTrigger {
+        DuckTrigger(Arc::clone(&self.pending))
+    }
+
+    /// Start a duck on the next sample.
+    pub fn trigger(&self) {
+        self.pending.fetch_add(1, Ordering::Release);
+    }
+
+    pub fn set_depth(&self, depth: f32) {
+        self.depth
+            .store(depth.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn get_depth(&self) -> f32 {
+        f32::from_bits(self.depth.load(Ordering::Relaxed))
+    }
+
+    pub fn set_attack(&self, ms: f32) {
+        self.attack_ms
+            .store(ms.clamp(0.0, MAX_ATTACK_MS).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn get_attack(&self) -> f32 {
+        f32::from_bits(self.attack_ms.load(Ordering::Relaxed))
+    }
+
+    pub fn set_hold(&self, ms: f32) {
+        self.hold_ms
+            .store(ms.clamp(0.0, MAX_HOLD_MS).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn get_hold(&self) -> f32 {
+        f32::from_bits(self.hold_ms.load(Ordering::Relaxed))
+    }
+
+    pub fn set_release(&self, ms: f32) {
+        self.release_ms.store(
+            ms.clamp(MIN_RELEASE_MS, MAX_RELEASE_MS).to_bits(),
+            Ordering::Relaxed,
+        );
+    }
+
+    pub fn get_release(&self) -> f32 {
+        f32::from_bits(self.release_ms.load(Ordering::Relaxed))
+    }
+
+    pub fn reset(&self) {
+        let state = unsafe { &mut *self.state.get() };
+        state.envelope = 0.0;
+        state.stage = Stage::Idle;
+        state.hold_remaining = 0;
+        self.pending.store(0, Ordering::Relaxed);
+    }
+
+    fn samples(&self, ms: f32) -> f32 {
+        ms * 0.001 * self.sample_rate
+    }
+
+    /// Advance the envelope one sample and return the gain to apply.
+    fn next_gain(&self) -> f32 {
+        let state = unsafe { &mut *self.state.get() };
+
+        // A retrigger ramps up from wherever the envelope is, so rapid hits
+        // don't click back to full level first.
+        if self.pending.swap(0, Ordering::Acquire) > 0 {
+            state.stage = Stage::Attack;
+        }
+
+        match state.stage {
+            Stage::Idle => {}
+            Stage::Attack => {
+                let attack = self.samples(self.get_attack());
+                state.envelope = if attack < 1.0 {
+                    1.0
+                } else {
+                    (state.envelope + 1.0 / attack).min(1.0)
+                };
+                if state.envelope >= 1.0 {
+                    state.stage = Stage::Hold;
+                    state.hold_remaining = self.samples(self.get_hold()) as u32;
+                }
+            }
+            Stage::Hold => {
+                if state.hold_remaining == 0 {
+                    state.stage = Stage::Release;
+                } else {
+                    state.hold_remaining -= 1;
+                }
+            }
+            Stage::Release => {
+                state.envelope -= 1.0 / self.samples(self.get_release());
+                if state.envelope <= 0.0 {
+                    state.envelope = 0.0;
+                    state.stage = Stage::Idle;
+                }
+            }
+        }
+
+        1.0 - self.get_depth() * state.envelope
+    }
+}
+
+impl Effect for Ducker {
+    fn process(&self, input: f32) -> f32 {
+        input * self.next_gain()
+    }
+
+    /// One envelope drives both channels, so the stereo image is preserved.
+    fn process_stereo(&self, input: StereoFrame) -> StereoFrame {
+        input.scaled(self.next_gain())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 1000.0;
+
+    #[test]
+    fn passes_audio_until_triggered() {
+        let ducker = Ducker::new(SR, 0.8, 0.0, 0.0, 100.0);
+        for _ in 0..10 {
+            assert_eq!(ducker.process(1.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn follows_attack_hold_release() {
+        // 1 sample = 1 ms at this rate.
+        let ducker = Ducker::new(SR, 0.5, 4.0, 10.0, 20.0);
+        ducker.trigger_handle().fire();
+
+        let gains: Vec<f32> = (0..40).map(|_| ducker.process(1.0)).collect();
+        assert!((gains[0] - 0.875).abs() < 1e-6);
+        assert!((gains[3] - 0.5).abs() < 1e-6);
+        assert!(gains[4..14].iter().all(|&g| (g - 0.5).abs() < 1e-6));
+        assert!(gains[14..34].windows(2).all(|w| w[1] >= w[0]));
+        assert_eq!(gains[39], 1.0);
+    }
+
+    #[test]
+    fn retrigger_continues_from_current_level() {
+        let ducker = Ducker::new(SR, 1.0, 0.0, 0.0, 10.0);
+        ducker.trigger();
+        let full = ducker.process(1.0);
+        for _ in 0..5 {
+            ducker.process(1.0);
+        }
+        let recovering = ducker.process(1.0);
+        ducker.trigger();
+        let retriggered = ducker.process(1.0);
+        assert_eq!(full, 0.0);
+        assert!(recovering > 0.0);
+        assert_eq!(retriggered, 0.0);
+    }
+}
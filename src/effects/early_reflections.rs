@@ -0,0 +1,393 @@
+//! Early reflections room effect for the drum bus
+//!
+//! A cheap "room mic" emulation: a single mono-summed delay line read by a
+//! fixed set of eight taps per channel, standing in for the first bounces off
+//! the walls, floor and ceiling of a small room. There is no recirculating
+//! tank, so the cost is a handful of interpolated reads per frame and the
+//! tail ends after the last tap — enough to put drums in a space without the
+//! weight of a full reverb or a convolution.
+//!
+//! Size rescales every tap time (0.25x to 2.0x of a ~40 ms room). Damping
+//! lowpasses the reflections, the later half more than the first, since
+//! every surface bounce absorbs more of the top end. Left and right use
+//! different tap times and alternating polarities, so the room is wide and
+//! does not comb-filter the dry signal when summed to mono.
+
+use crate::effects::Effect;
+use crate::frame::StereoFrame;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::denormal::DENORMAL_THRESHOLD;
+use crate::utils::smoother::SmoothedParam;
+use core::cell::UnsafeCell;
+use core::f32::consts::TAU;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+/// Number of reflection taps per channel
+const TAP_COUNT: usize = 8;
+
+/// Number of taps (from the start) filtered by the lighter early damping
+const EARLY_TAPS: usize = 4;
+
+/// Tap times in milliseconds at size scale 1.0. Roughly exponentially spaced
+/// and mutually prime so no two taps reinforce the same comb frequency.
+const TAP_TIMES_L: [f32; TAP_COUNT] = [4.3, 7.9, 11.7, 15.8, 21.1, 26.9, 33.4, 41.2];
+const TAP_TIMES_R: [f32; TAP_COUNT] = [5.1, 8.8, 12.9, 17.3, 19.6, 28.7, 35.9, 39.4];
+
+/// Tap gains, decaying with distance. Signs alternate (and differ between
+/// channels) to decorrelate left from right.
+const TAP_GAINS_L: [f32; TAP_COUNT] = [0.85, -0.72, 0.63, -0.55, 0.46, -0.40, 0.33, -0.27];
+const TAP_GAINS_R: [f32; TAP_COUNT] = [-0.82, 0.70, -0.61, 0.52, -0.47, 0.38, -0.31, 0.26];
+
+/// Brings the summed taps back to roughly unity RMS for broadband input.
+const OUTPUT_SCALE: f32 = 0.6;
+
+/// Largest size scale; the delay line is allocated for this.
+const MAX_SIZE_SCALE: f32 = 2.0;
+
+/// Map the 0-1 size knob to a tap-time scale: 0.0 -> 0.25x, 1.0 -> 2.0x.
+fn size_to_scale(size: f32) -> f32 {
+    0.25 + 1.75 * size
+}
+
+/// Map the 0-1 damping knob to the late-reflection lowpass cutoff in Hz.
+/// Early reflections use twice this cutoff.
+fn damping_to_cutoff(damping: f32) -> f32 {
+    let open = 1.0 - damping;
+    800.0 + 17_200.0 * open * open
+}
+
+/// One-pole lowpass coefficient for `cutoff` Hz
+fn one_pole_coeff(cutoff: f32, sample_rate: f32) -> f32 {
+    1.0 - (-TAU * cutoff.min(0.45 * sample_rate) / sample_rate).exp()
+}
+
+/// Tight/big room starting points for [`EarlyReflections::apply_preset`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EarlyReflectionsPreset {
+    /// Small, dead room: short taps, well damped, a touch of ambience
+    TightRoom,
+    /// Large live room: long taps, bright, more of the room in the mix
+    BigRoom,
+}
+
+impl EarlyReflectionsPreset {
+    /// Convert from a u32 preset constant (used by FFI)
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::TightRoom),
+            1 => Some(Self::BigRoom),
+            _ => None,
+        }
+    }
+
+    /// `(size, damping, mix)` for this preset
+    pub fn values(self) -> (f32, f32, f32) {
+        match self {
+            Self::TightRoom => (0.15, 0.6, 0.2),
+            Self::BigRoom => (0.85, 0.3, 0.35),
+        }
+    }
+}
+
+/// Internal mutable state (wrapped in UnsafeCell for interior mutability)
+struct EarlyReflectionsState {
+    // Mono-summed input history shared by both channels' taps
+    buffer: Vec<f32>,
+    write_index: usize,
+
+    // Per-channel one-pole lowpass states: [early, late]
+    damp_l: [f32; 2],
+    damp_r: [f32; 2],
+
+    size_smoothed: SmoothedParam,
+    damping_smoothed: SmoothedParam,
+    mix_smoothed: SmoothedParam,
+}
+
+impl EarlyReflectionsState {
+    /// Read the value written `offset` samples ago (fractional, after this
+    /// frame's write: offset 0 = the sample just written).
+    fn tap(&self, offset: f32) -> f32 {
+        let len = self.buffer.len();
+        let offset = offset.clamp(0.0, (len - 2) as f32);
+        let whole = offset as usize;
+        let frac = offset - whole as f32;
+        let a = self.buffer[(self.write_index + len - 1 - whole) % len];
+        let b = self.buffer[(self.write_index + len - 2 - whole) % len];
+        a + frac * (b - a)
+    }
+}
+
+/// Early reflections effect: a tapped delay room for drums
+///
+/// Parameters:
+/// - Size: Room size (0.0-1.0, scales tap times 0.25x-2.0x)
+/// - Damping: High-frequency absorption (0.0 = bright, 1.0 = dark)
+/// - Mix: Wet/dry mix (0.0-1.0)
+pub struct EarlyReflections {
+    sample_rate: f32,
+
+    // Shared delay line: `process_stereo` mono-sums the input into it once
+    // per frame and reads distinct left/right taps.
+    // SAFETY: This is only accessed from the audio thread during process()
+    state: UnsafeCell<EarlyReflectionsState>,
+
+    // Atomic parameters for lock-free updates from control thread
+    size_target: AtomicU32,
+    damping_target: AtomicU32,
+    mix_target: AtomicU32,
+}
+
+// SAFETY: The UnsafeCell is only accessed from a single audio thread
+// The AtomicU32 fields are inherently thread-safe
+unsafe impl Send for EarlyReflections {}
+unsafe impl Sync for EarlyReflections {}
+
+impl EarlyReflections {
+    /// Create a new early reflections room
+    ///
+    /// # Arguments
+    /// * `sample_rate` - Audio sample rate in Hz
+    /// * `size` - Initial room size (0.0-1.0)
+    /// * `damping` - Initial damping (0.0-1.0)
+    /// * `mix` - Initial wet/dry mix (0.0-1.0)
+    pub fn new(sample_rate: f32, size: f32, damping: f32, mix: f32) -> Self {
+        let size = size.clamp(0.0, 1.0);
+        let damping = damping.clamp(0.0, 1.0);
+        let mix = mix.clamp(0.0, 1.0);
+
+        let longest = TAP_TIMES_L
+            .iter()
+            .chain(TAP_TIMES_R.iter())
+            .fold(0.0_f32, |a, &b| a.max(b));
+        let buffer_size = (longest * 0.001 * MAX_SIZE_SCALE * sample_rate).ceil() as usize + 4;
+
+        let state = EarlyReflectionsState {
+            buffer: vec![0.0; buffer_size],
+            write_index: 0,
+            damp_l: [0.0; 2],
+            damp_r: [0.0; 2],
+            size_smoothed: SmoothedParam::new(size, 0.0, 1.0, sample_rate, 50.0),
+            damping_smoothed: SmoothedParam::new_normalized(damping, sample_rate),
+            mix_smoothed: SmoothedParam::new_normalized(mix, sample_rate),
+        };
+
+        Self {
+            sample_rate,
+            state: UnsafeCell::new(state),
+            size_target: AtomicU32::new(size.to_bits()),
+            damping_target: AtomicU32::new(damping.to_bits()),
+            mix_target: AtomicU32::new(mix.to_bits()),
+        }
+    }
+
+    /// Create a room from a preset
+    pub fn from_preset(sample_rate: f32, preset: EarlyReflectionsPreset) -> Self {
+        let (size, damping, mix) = preset.values();
+        Self::new(sample_rate, size, damping, mix)
+    }
+
+    /// Set size, damping and mix from a preset (thread-safe, changes are smoothed)
+    pub fn apply_preset(&self, preset: EarlyReflectionsPreset) {
+        let (size, damping, mix) = preset.values();
+        self.set_size(size);
+        self.set_damping(damping);
+        self.set_mix(mix);
+    }
+
+    /// Size knob (0-1): rescales every tap from 0.25x to 2.0x. Sweeping it
+    /// briefly pitch-bends the reflections.
+    pub fn set_size(&self, value: f32) {
+        self.size_target
+            .store(value.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn get_size(&self) -> f32 {
+        f32::from_bits(self.size_target.load(Ordering::Relaxed))
+    }
+
+    pub fn set_damping(&self, value: f32) {
+        self.damping_target
+            .store(value.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn get_damping(&self) -> f32 {
+        f32::from_bits(self.damping_target.load(Ordering::Relaxed))
+    }
+
+    pub fn set_mix(&self, value: f32) {
+        self.mix_target
+            .store(value.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn get_mix(&self) -> f32 {
+        f32::from_bits(self.mix_target.load(Ordering::Relaxed))
+    }
+
+    /// Reset room state (clear the delay line and filters)
+    pub fn reset(&self) {
+        // SAFETY: Called from main thread when the effect is not processing
+        let state = unsafe { &mut *self.state.get() };
+        state.buffer.fill(0.0);
+        state.write_index = 0;
+        state.damp_l = [0.0; 2];
+        state.damp_r = [0.0; 2];
+    }
+
+    /// Write one mono input sample and return `(wet_l, wet_r, mix)`.
+    /// Called exactly once per frame by both the mono and stereo paths.
+    fn tick(&self, state: &mut EarlyReflectionsState, input: f32) -> (f32, f32, f32) {
+        state
+            .size_smoothed
+            .set_target(f32::from_bits(self.size_target.load(Ordering::Relaxed)));
+        state
+            .damping_smoothed
+            .set_target(f32::from_bits(self.damping_target.load(Ordering::Relaxed)));
+        state
+            .mix_smoothed
+            .set_target(f32::from_bits(self.mix_target.load(Ordering::Relaxed)));
+        let scale = size_to_scale(state.size_smoothed.tick());
+        let damping = state.damping_smoothed.tick();
+        let mix = state.mix_smoothed.tick();
+
+        let len = state.buffer.len();
+        state.buffer[state.write_index] = input;
+        state.write_index = (state.write_index + 1) % len;
+
+        let ms_to_samples = scale * 0.001 * self.sample_rate;
+        let mut sums = [[0.0_f32; 2]; 2]; // [channel][early, late]
+        for i in 0..TAP_COUNT {
+            let group = usize::from(i >= EARLY_TAPS);
+            sums[0][group] += TAP_GAINS_L[i] * state.tap(TAP_TIMES_L[i] * ms_to_samples);
+            sums[1][group] += TAP_GAINS_R[i] * state.tap(TAP_TIMES_R[i] * ms_to_samples);
+        }
+
+        let cutoff = damping_to_cutoff(damping);
+        let coeffs = [
+            one_pole_coeff(2.0 * cutoff, self.sample_rate),
+            one_pole_coeff(cutoff, self.sample_rate),
+        ];
+        let mut wet = [0.0_f32; 2];
+        for (ch, damp) in [&mut state.damp_l, &mut state.damp_r]
+            .into_iter()
+            .enumerate()
+        {
+            for group in 0..2 {
+                damp[group] += coeffs[group] * (sums[ch][group] - damp[group]);
+                if damp[group].abs() < DENORMAL_THRESHOLD {
+                    damp[group] = 0.0;
+                }
+                wet[ch] += damp[group];
+            }
+        }
+
+        (wet[0] * OUTPUT_SCALE, wet[1] * OUTPUT_SCALE, mix)
+    }
+}
+
+impl Effect for EarlyReflections {
+    fn process(&self, input: f32) -> f32 {
+        // SAFETY: process() is only called from the audio thread
+        let state = unsafe { &mut *self.state.get() };
+        let input = if input.is_finite() { input } else { 0.0 };
+        let (wet_l, wet_r, mix) = self.tick(state, input);
+        let result = input * (1.0 - mix) + 0.5 * (wet_l + wet_r) * mix;
+        if result.is_finite() {
+            result
+        } else {
+            input
+        }
+    }
+
+    fn process_stereo(&self, input: StereoFrame) -> StereoFrame {
+        // SAFETY: see process(); the delay line is shared and written once per frame.
+        let state = unsafe { &mut *self.state.get() };
+        let l = if input.l.is_finite() { input.l } else { 0.0 };
+        let r = if input.r.is_finite() { input.r } else { 0.0 };
+        let (wet_l, wet_r, mix) = self.tick(state, 0.5 * (l + r));
+        let out_l = l * (1.0 - mix) + wet_l * mix;
+        let out_r = r * (1.0 - mix) + wet_r * mix;
+        StereoFrame {
+            l: if out_l.is_finite() { out_l } else { l },
+            r: if out_r.is_finite() { out_r } else { r },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 44_100.0;
+
+    fn impulse_response(room: &EarlyReflections, frames: usize) -> Vec<StereoFrame> {
+        (0..frames)
+            .map(|i| {
+                let x = if i == 0 { 1.0 } else { 0.0 };
+                room.process_stereo(StereoFrame { l: x, r: x })
+            })
+            .collect()
+    }
+
+    /// Index of the first wet sample above `threshold`, skipping the dry impulse.
+    fn first_reflection(ir: &[StereoFrame], threshold: f32) -> usize {
+        ir.iter()
+            .skip(1)
+            .position(|f| f.l.abs() > threshold)
+            .map(|i| i + 1)
+            .unwrap()
+    }
+
+    #[test]
+    fn size_moves_the_first_reflection() {
+        let tight = impulse_response(&EarlyReflections::new(SR, 0.0, 0.0, 1.0), 8192);
+        let big = impulse_response(&EarlyReflections::new(SR, 1.0, 0.0, 1.0), 8192);
+        let tight_at = first_reflection(&tight, 0.05);
+        let big_at = first_reflection(&big, 0.05);
+        assert!(big_at > tight_at * 4, "{tight_at} vs {big_at}");
+    }
+
+    #[test]
+    fn reflections_end_after_the_last_tap() {
+        let room = EarlyReflections::new(SR, 1.0, 0.0, 1.0);
+        let ir = impulse_response(&room, 8192);
+        let last_tap = (41.2 * 0.001 * MAX_SIZE_SCALE * SR) as usize;
+        let tail: f32 = ir[last_tap + 200..].iter().map(|f| f.l.abs()).sum();
+        assert!(tail < 1e-3, "no recirculation expected, got {tail}");
+        assert!(
+            ir.iter().any(|f| (f.l - f.r).abs() > 0.05),
+            "room should be wide"
+        );
+    }
+
+    #[test]
+    fn damping_darkens_the_reflections() {
+        // Sum of absolute sample-to-sample differences: a crude brightness measure
+        let roughness = |damping: f32| {
+            let ir = impulse_response(&EarlyReflections::new(SR, 0.5, damping, 1.0), 4096);
+            ir.windows(2)
+                .skip(1)
+                .map(|w| (w[1].l - w[0].l).abs())
+                .sum::<f32>()
+        };
+        assert!(roughness(1.0) < roughness(0.0) * 0.5);
+    }
+
+    #[test]
+    fn presets_and_zero_mix() {
+        let room = EarlyReflections::new(SR, 0.5, 0.5, 0.0);
+        assert_eq!(room.process(0.3), 0.3);
+
+        room.apply_preset(EarlyReflectionsPreset::BigRoom);
+        assert_eq!(
+            (room.get_size(), room.get_damping(), room.get_mix()),
+            EarlyReflectionsPreset::BigRoom.values()
+        );
+        assert_eq!(
+            EarlyReflectionsPreset::from_u32(1),
+            Some(EarlyReflectionsPreset::BigRoom)
+        );
+        assert_eq!(EarlyReflectionsPreset::from_u32(2), None);
+    }
+}
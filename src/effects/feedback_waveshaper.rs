@@ -6,11 +6,11 @@
 //! DC drift. Higher feedback on kicks creates sub-harmonic growl,
 //! moderate feedback on snares adds a gritty, self-exciting tail.
 
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::denormal::DENORMAL_THRESHOLD;
 use crate::utils::oversampler::{Oversampler, OversamplingMode};
 
-/// Threshold for flushing denormal numbers to zero
-const DENORMAL_THRESHOLD: f32 = 1e-15;
-
 /// DC blocker coefficient (R in RC circuit, ~20Hz cutoff at 44.1kHz)
 const DC_BLOCKER_COEFF: f32 = 0.995;
 
@@ -231,7 +231,7 @@ impl FeedbackWaveshaper {
 
     #[inline]
     fn compute_filter_coeff(cutoff: f32, sample_rate: f32) -> f32 {
-        let g = 1.0 - (-2.0 * std::f32::consts::PI * cutoff / sample_rate).exp();
+        let g = 1.0 - (-2.0 * core::f32::consts::PI * cutoff / sample_rate).exp();
         g.clamp(0.0, 0.9)
     }
 
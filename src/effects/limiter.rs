@@ -1,5 +1,7 @@
 use super::Effect;
 use crate::frame::StereoFrame;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 
 /// A brick wall limiter that prevents audio signals from exceeding a threshold
 pub struct BrickWallLimiter {
@@ -15,6 +17,9 @@ impl BrickWallLimiter {
 impl Effect for BrickWallLimiter {
     /// Apply brick wall limiting to the input signal
     fn process(&self, input: f32) -> f32 {
+        if !input.is_finite() {
+            return 0.0;
+        }
         if input > self.threshold {
             self.threshold
         } else if input < -self.threshold {
@@ -65,6 +70,10 @@ impl SoftLimiter {
 
 impl Effect for SoftLimiter {
     fn process(&self, input: f32) -> f32 {
+        // Last stage before the output: never let a NaN reach the device
+        if !input.is_finite() {
+            return 0.0;
+        }
         (input * self.inv_threshold).tanh() * self.threshold
     }
 
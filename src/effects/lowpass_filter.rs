@@ -5,12 +5,12 @@
 
 use crate::effects::Effect;
 use crate::frame::StereoFrame;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::denormal::DENORMAL_THRESHOLD;
 use crate::utils::smoother::SmoothedParam;
-use std::cell::UnsafeCell;
-use std::sync::atomic::{AtomicU32, Ordering};
-
-/// Threshold for flushing denormal numbers to zero
-const DENORMAL_THRESHOLD: f32 = 1e-15;
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicU32, Ordering};
 
 /// Internal mutable state for the filter (wrapped in UnsafeCell for interior mutability)
 struct FilterState {
@@ -147,7 +147,7 @@ impl LowpassFilterEffect {
         // Using a simple but stable one-pole coefficient: g = 1 - e^(-2*pi*fc/fs)
         // This is more stable than the sin() or tan() formulations at high frequencies
         let normalized_freq = safe_cutoff / self.sample_rate;
-        let g = 1.0 - (-2.0 * std::f32::consts::PI * normalized_freq).exp();
+        let g = 1.0 - (-2.0 * core::f32::consts::PI * normalized_freq).exp();
 
         // Clamp g to ensure stability (must be well under 1.0)
         let g = g.clamp(0.0, 0.90);
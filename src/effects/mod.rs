@@ -1,24 +1,36 @@
+pub mod beat_repeat;
 pub mod compressor;
 pub mod delay;
+pub mod ducker;
+pub mod early_reflections;
 pub mod feedback_waveshaper;
 pub mod limiter;
 pub mod lowpass_filter;
+pub mod output_safety;
 pub mod plate_reverb;
 pub mod reverb;
 pub mod saturation;
+pub mod saturator;
 pub mod tilt_filter;
 pub mod waveshaper;
+pub mod widener;
 
+pub use self::beat_repeat::*;
 pub use self::compressor::*;
 pub use self::delay::*;
+pub use self::ducker::*;
+pub use self::early_reflections::*;
 pub use self::feedback_waveshaper::*;
 pub use self::limiter::*;
 pub use self::lowpass_filter::*;
+pub use self::output_safety::*;
 pub use self::plate_reverb::*;
 pub use self::reverb::*;
 pub use self::saturation::*;
+pub use self::saturator::*;
 pub use self::tilt_filter::*;
 pub use self::waveshaper::*;
+pub use self::widener::*;
 
 use crate::frame::StereoFrame;
 
@@ -45,4 +57,14 @@ pub trait Effect: Send {
     /// - Wrappers that delegate to an inner [`Effect`] should forward to the
     ///   inner `process_stereo`.
     fn process_stereo(&self, input: StereoFrame) -> StereoFrame;
+
+    /// Follow a transport tempo change. Tempo-synced effects (delay, beat
+    /// repeat) recompute their timing; everything else ignores it.
+    fn set_bpm(&self, _bpm: f32) {}
+
+    /// Set a parameter by its effect-specific `*_PARAM_*` id (the FFI's
+    /// numbering), for automation such as effect lanes. Effects without
+    /// numbered parameters ignore it; wrap one in a
+    /// `mixer::ChannelEffect` to make it automatable.
+    fn set_param(&self, _param: u32, _value: f32) {}
 }
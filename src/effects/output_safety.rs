@@ -0,0 +1,327 @@
+//! Always-on output safety stage
+//!
+//! The last thing between the engine and the speakers. Heavy saturation and
+//! resonant feedback paths can leave a DC offset or, with extreme settings,
+//! run away. Three stages catch that:
+//!
+//! - A DC blocker (one-pole highpass at ~5 Hz), inaudible on music but
+//!   enough to stop an offset from eating headroom or thumping on start/stop.
+//! - A soft-clip ceiling: transparent below 80% of the ceiling, then a tanh
+//!   knee that approaches but never exceeds it.
+//! - An emergency mute. If the DC-blocked signal stays above the fault level
+//!   (or goes NaN/infinite) for the fault hold time, the output is muted and
+//!   a fault flag is raised until the host clears it. Short transients over
+//!   the level do not trip it; only a sustained runaway does.
+
+use crate::effects::Effect;
+use crate::frame::StereoFrame;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::denormal::flush_denormal;
+use core::cell::UnsafeCell;
+use core::f32::consts::TAU;
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+/// DC blocker cutoff in Hz
+const DC_CUTOFF_HZ: f32 = 5.0;
+
+/// Fraction of the ceiling below which the soft clip is transparent
+const KNEE: f32 = 0.8;
+
+/// Ceiling range (linear)
+const MIN_CEILING: f32 = 0.25;
+const MAX_CEILING: f32 = 4.0;
+
+/// Fault level range (linear)
+const MIN_FAULT_LEVEL: f32 = 1.0;
+const MAX_FAULT_LEVEL: f32 = 64.0;
+
+/// Fault hold range in milliseconds
+const MIN_FAULT_HOLD_MS: f32 = 1.0;
+const MAX_FAULT_HOLD_MS: f32 = 2_000.0;
+
+/// A run of over-level samples ends after this long under the level. Longer
+/// than half a period of any audible tone, so an oscillating runaway counts
+/// as one continuous run.
+const FAULT_GAP_MS: f32 = 25.0;
+
+/// Soft clip `x` to `ceiling`: identity up to `KNEE * ceiling`, then a tanh
+/// curve with matching slope that approaches the ceiling.
+fn soft_clip(x: f32, ceiling: f32) -> f32 {
+    let knee = KNEE * ceiling;
+    let magnitude = x.abs();
+    if magnitude <= knee {
+        return x;
+    }
+    let range = ceiling - knee;
+    let shaped = knee + range * ((magnitude - knee) / range).tanh();
+    shaped.copysign(x)
+}
+
+/// Internal mutable state (wrapped in UnsafeCell for interior mutability)
+struct OutputSafetyState {
+    // DC blocker [x1, y1] per channel
+    dc: [[f32; 2]; 2],
+    // Samples since the current over-level run began, and since the signal
+    // was last over the level
+    over_run: u32,
+    since_over: u32,
+}
+
+/// Output safety stage: DC blocker, soft-clip ceiling and emergency mute
+///
+/// Parameters:
+/// - Ceiling: soft-clip ceiling (0.25-4.0 linear, default 1.0 = 0 dBFS)
+/// - Fault level: level that counts as a runaway (1.0-64.0 linear)
+/// - Fault hold: how long the level must be exceeded to mute (1-2000 ms)
+pub struct OutputSafety {
+    sample_rate: f32,
+    dc_coeff: f32,
+
+    // SAFETY: This is only accessed from the audio thread during process()
+    state: UnsafeCell<OutputSafetyState>,
+
+    // Atomic parameters for lock-free updates from control thread
+    ceiling: AtomicU32,
+    fault_level: AtomicU32,
+    fault_hold_ms: AtomicU32,
+
+    // Raised by the audio thread, cleared by the host
+    fault: AtomicBool,
+}
+
+// SAFETY: The UnsafeCell is only accessed from a single audio thread
+// The atomic fields are inherently thread-safe
+unsafe impl Send for OutputSafety {}
+unsafe impl Sync for OutputSafety {}
+
+impl OutputSafety {
+    /// Create a new safety stage
+    ///
+    /// # Arguments
+    /// * `sample_rate` - Audio sample rate in Hz
+    /// * `ceiling` - Soft-clip ceiling (0.25-4.0)
+    /// * `fault_level` - Runaway level (1.0-64.0)
+    /// * `fault_hold_ms` - Time over the level before muting (1-2000 ms)
+    pub fn new(sample_rate: f32, ceiling: f32, fault_level: f32, fault_hold_ms: f32) -> Self {
+        let safety = Self {
+            sample_rate,
+            dc_coeff: (-TAU * DC_CUTOFF_HZ / sample_rate).exp(),
+            state: UnsafeCell::new(OutputSafetyState {
+                dc: [[0.0; 2]; 2],
+                over_run: 0,
+                since_over: u32::MAX,
+            }),
+            ceiling: AtomicU32::new(0),
+            fault_level: AtomicU32::new(0),
+            fault_hold_ms: AtomicU32::new(0),
+            fault: AtomicBool::new(false),
+        };
+        safety.set_ceiling(ceiling);
+        safety.set_fault_level(fault_level);
+        safety.set_fault_hold(fault_hold_ms);
+        safety
+    }
+
+    pub fn set_ceiling(&self, value: f32) {
+        self.ceiling.store(
+            value.clamp(MIN_CEILING, MAX_CEILING).to_bits(),
+            Ordering::Relaxed,
+        );
+    }
+
+    pub fn get_ceiling(&self) -> f32 {
+        f32::from_bits(self.ceiling.load(Ordering::Relaxed))
+    }
+
+    pub fn set_fault_level(&self, value: f32) {
+        self.fault_level.store(
+            value.clamp(MIN_FAULT_LEVEL, MAX_FAULT_LEVEL).to_bits(),
+            Ordering::Relaxed,
+        );
+    }
+
+    pub fn get_fault_level(&self) -> f32 {
+        f32::from_bits(self.fault_level.load(Ordering::Relaxed))
+    }
+
+    pub fn set_fault_hold(&self, ms: f32) {
+        self.fault_hold_ms.store(
+            ms.clamp(MIN_FAULT_HOLD_MS, MAX_FAULT_HOLD_MS).to_bits(),
+            Ordering::Relaxed,
+        );
+    }
+
+    pub fn get_fault_hold(&self) -> f32 {
+        f32::from_bits(self.fault_hold_ms.load(Ordering::Relaxed))
+    }
+
+    /// Whether the emergency mute has tripped. Safe to call from any thread.
+    pub fn is_faulted(&self) -> bool {
+        self.fault.load(Ordering::Relaxed)
+    }
+
+    /// Unmute after a fault. The run detector starts over, so a runaway
+    /// that is still going trips again after the hold time.
+    pub fn clear_fault(&self) {
+        self.fault.store(false, Ordering::Relaxed);
+    }
+
+    /// Reset filter and detector state (does not clear a fault)
+    pub fn reset(&self) {
+        // SAFETY: Called from main thread when the effect is not processing
+        let state = unsafe { &mut *self.state.get() };
+        state.dc = [[0.0; 2]; 2];
+        state.over_run = 0;
+        state.since_over = u32::MAX;
+    }
+
+    fn dc_block(&self, dc: &mut [f32; 2], input: f32) -> f32 {
+        let output = input - dc[0] + self.dc_coeff * dc[1];
+        dc[0] = input;
+        dc[1] = flush_denormal(output);
+        dc[1]
+    }
+
+    /// Feed the run detector one frame's peak; returns true while muted.
+    fn detect(&self, state: &mut OutputSafetyState, over: bool) -> bool {
+        if self.is_faulted() {
+            // Hold the detector idle so it starts over once cleared
+            state.over_run = 0;
+            state.since_over = u32::MAX;
+            return true;
+        }
+        let ms = 1000.0 / self.sample_rate;
+        if over {
+            // A new run starts when the previous one has gone quiet
+            if state.since_over as f32 * ms > FAULT_GAP_MS {
+                state.over_run = 0;
+            }
+            state.since_over = 0;
+        } else {
+            state.since_over = state.since_over.saturating_add(1);
+        }
+        if state.since_over as f32 * ms <= FAULT_GAP_MS {
+            state.over_run = state.over_run.saturating_add(1);
+        }
+        if state.over_run as f32 * ms >= self.get_fault_hold() {
+            self.fault.store(true, Ordering::Relaxed);
+            return true;
+        }
+        false
+    }
+}
+
+impl Effect for OutputSafety {
+    fn process(&self, input: f32) -> f32 {
+        let out = self.process_stereo(StereoFrame::mono(input));
+        0.5 * (out.l + out.r)
+    }
+
+    fn process_stereo(&self, input: StereoFrame) -> StereoFrame {
+        // SAFETY: process_stereo() is only called from the audio thread
+        let state = unsafe { &mut *self.state.get() };
+        let finite = input.l.is_finite() && input.r.is_finite();
+        let (l, r) = if finite {
+            (input.l, input.r)
+        } else {
+            (0.0, 0.0)
+        };
+        let l = self.dc_block(&mut state.dc[0], l);
+        let r = self.dc_block(&mut state.dc[1], r);
+
+        let level = self.get_fault_level();
+        let over = !finite || l.abs() > level || r.abs() > level;
+        if self.detect(state, over) {
+            return StereoFrame::default();
+        }
+
+        let ceiling = self.get_ceiling();
+        StereoFrame {
+            l: soft_clip(l, ceiling),
+            r: soft_clip(r, ceiling),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 44_100.0;
+
+    fn sine(i: usize, freq: f32, amp: f32) -> f32 {
+        (TAU * freq * i as f32 / SR).sin() * amp
+    }
+
+    #[test]
+    fn test_dc_offset_is_removed() {
+        let safety = OutputSafety::new(SR, 1.0, 8.0, 100.0);
+        let mut mean = 0.0;
+        for i in 0..SR as usize {
+            let out = safety.process_stereo(StereoFrame::mono(0.3 + sine(i, 440.0, 0.2)));
+            if i >= SR as usize / 2 {
+                mean += out.l / (SR / 2.0);
+            }
+        }
+        assert!(mean.abs() < 0.01, "mean {mean}");
+    }
+
+    #[test]
+    fn test_soft_clip_is_transparent_below_the_knee_and_bounded_above() {
+        assert_eq!(soft_clip(0.5, 1.0), 0.5);
+        assert_eq!(soft_clip(-0.8, 1.0), -0.8);
+        for x in [0.9, 1.5, 10.0, 1e6] {
+            let y = soft_clip(x, 1.0);
+            assert!(y > 0.8 && y <= 1.0, "{x} -> {y}");
+            assert_eq!(soft_clip(-x, 1.0), -y);
+        }
+        // Monotonic through the knee
+        let curve: Vec<f32> = (0..400).map(|i| soft_clip(i as f32 * 0.01, 2.0)).collect();
+        assert!(curve.windows(2).all(|w| w[1] >= w[0]));
+    }
+
+    #[test]
+    fn test_sustained_runaway_trips_the_mute_but_transients_do_not() {
+        let safety = OutputSafety::new(SR, 1.0, 4.0, 50.0);
+        // 10 ms bursts at 6.0 every 100 ms: hot, but not a runaway
+        for i in 0..SR as usize {
+            let amp = if i % 4_410 < 441 { 6.0 } else { 0.1 };
+            safety.process_stereo(StereoFrame::mono(sine(i, 200.0, amp)));
+        }
+        assert!(!safety.is_faulted());
+
+        // A sustained 100 Hz oscillation at 8.0 trips after ~50 ms
+        let tripped_at = (0..SR as usize)
+            .position(|i| {
+                safety.process_stereo(StereoFrame::mono(sine(i, 100.0, 8.0)));
+                safety.is_faulted()
+            })
+            .unwrap();
+        let ms = tripped_at as f32 * 1000.0 / SR;
+        assert!((45.0..80.0).contains(&ms), "tripped after {ms} ms");
+        let out = safety.process_stereo(StereoFrame::mono(0.5));
+        assert_eq!((out.l, out.r), (0.0, 0.0));
+
+        safety.clear_fault();
+        let out = safety.process_stereo(StereoFrame::mono(0.5));
+        assert!(out.l != 0.0);
+    }
+
+    #[test]
+    fn test_nan_input_is_silenced_and_counts_toward_a_fault() {
+        let safety = OutputSafety::new(SR, 1.0, 4.0, 10.0);
+        let out = safety.process_stereo(StereoFrame {
+            l: f32::NAN,
+            r: 0.2,
+        });
+        assert_eq!((out.l, out.r), (0.0, 0.0));
+        for _ in 0..SR as usize / 50 {
+            safety.process_stereo(StereoFrame::mono(f32::INFINITY));
+        }
+        assert!(safety.is_faulted());
+        // The DC blocker was never poisoned
+        safety.clear_fault();
+        assert!(safety.process_stereo(StereoFrame::mono(0.5)).l.is_finite());
+    }
+}
@@ -20,13 +20,13 @@
 
 use crate::effects::Effect;
 use crate::frame::StereoFrame;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 use crate::utils::smoother::SmoothedParam;
 
-use std::cell::UnsafeCell;
-use std::sync::atomic::{AtomicU32, Ordering};
-
-/// Threshold for flushing denormal numbers to zero
-const DENORMAL_THRESHOLD: f32 = 1e-15;
+use crate::utils::denormal::DENORMAL_THRESHOLD;
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicU32, Ordering};
 
 /// Sample rate all of Dattorro's published delay lengths are specified at.
 /// Lengths are rescaled by `sample_rate / DATTORRO_SR` at construction.
@@ -110,7 +110,9 @@ impl DelayLine {
         }
     }
 
-    fn write(&mut self, x: f32) {
+    fn write(&mut self, mut x: f32) {
+        // Keep decaying tails out of the subnormal range
+        flush_denormal(&mut x);
         self.buf[self.idx] = x;
         self.idx = (self.idx + 1) % self.buf.len();
     }
@@ -212,6 +214,31 @@ struct PlateState {
     size_smoothed: SmoothedParam,
 }
 
+impl PlateState {
+    /// Clear all delay lines, filters, and the tank loop.
+    fn clear(&mut self) {
+        self.predelay.clear();
+        self.bandwidth_state = 0.0;
+        for ap in self.input_aps.iter_mut() {
+            ap.clear();
+        }
+        self.mod_ap_a.clear();
+        self.delay1_a.clear();
+        self.damp_state_a = 0.0;
+        self.ap2_a.clear();
+        self.delay2_a.clear();
+        self.mod_ap_b.clear();
+        self.delay1_b.clear();
+        self.damp_state_b = 0.0;
+        self.ap2_b.clear();
+        self.delay2_b.clear();
+        self.fb_a = 0.0;
+        self.fb_b = 0.0;
+        self.lfo_phase_a = 0.0;
+        self.lfo_phase_b = 0.0;
+    }
+}
+
 pub struct PlateReverbEffect {
     // Single shared tank (NOT per-channel [State; 2]): the figure-eight
     // topology mono-sums the input and derives stereo from cross-branch
@@ -380,25 +407,7 @@ impl PlateReverbEffect {
     pub fn reset(&self) {
         // SAFETY: Called from main thread when reverb is not processing
         let state = unsafe { &mut *self.state.get() };
-        state.predelay.clear();
-        state.bandwidth_state = 0.0;
-        for ap in state.input_aps.iter_mut() {
-            ap.clear();
-        }
-        state.mod_ap_a.clear();
-        state.delay1_a.clear();
-        state.damp_state_a = 0.0;
-        state.ap2_a.clear();
-        state.delay2_a.clear();
-        state.mod_ap_b.clear();
-        state.delay1_b.clear();
-        state.damp_state_b = 0.0;
-        state.ap2_b.clear();
-        state.delay2_b.clear();
-        state.fb_a = 0.0;
-        state.fb_b = 0.0;
-        state.lfo_phase_a = 0.0;
-        state.lfo_phase_b = 0.0;
+        state.clear();
     }
 
     /// Advance the tank by one sample and return `(wet_l, wet_r, mix)`.
@@ -462,8 +471,8 @@ impl PlateReverbEffect {
         // Free-running LFOs for the tank's modulated allpasses
         state.lfo_phase_a = (state.lfo_phase_a + state.lfo_inc_a).fract();
         state.lfo_phase_b = (state.lfo_phase_b + state.lfo_inc_b).fract();
-        let lfo_a = (std::f32::consts::TAU * state.lfo_phase_a).sin();
-        let lfo_b = (std::f32::consts::TAU * state.lfo_phase_b).sin();
+        let lfo_a = (core::f32::consts::TAU * state.lfo_phase_a).sin();
+        let lfo_b = (core::f32::consts::TAU * state.lfo_phase_b).sin();
 
         // Figure-eight tank. Capture BOTH cross-feeds before updating either
         // branch: the one-sample latency is what keeps the loop causal.
@@ -530,6 +539,11 @@ impl PlateReverbEffect {
         // Wet-only width control (mid/side)
         let mid = 0.5 * (yl + yr);
         let side = 0.5 * (yl - yr) * width;
+        if !mid.is_finite() || !side.is_finite() {
+            // Drop the poisoned tank so the NaN does not recirculate
+            state.clear();
+            return (0.0, 0.0, mix);
+        }
         (mid + side, mid - side, mix)
     }
 }
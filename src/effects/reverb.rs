@@ -8,13 +8,13 @@
 
 use crate::effects::Effect;
 use crate::frame::StereoFrame;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 use crate::utils::smoother::SmoothedParam;
 
-use std::cell::UnsafeCell;
-use std::sync::atomic::{AtomicU32, Ordering};
-
-/// Threshold for flushing denormal numbers to zero
-const DENORMAL_THRESHOLD: f32 = 1e-15;
+use crate::utils::denormal::DENORMAL_THRESHOLD;
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicU32, Ordering};
 
 /// Number of series allpass filters in the reverb chain
 const NUM_ALLPASSES: usize = 6;
@@ -52,7 +52,8 @@ impl AllpassFilter {
         let delayed = self.buffer[self.index];
         let v = input - gain * delayed;
         let output = gain * v + delayed;
-        self.buffer[self.index] = v;
+        // Flush denormals so a decaying tail does not linger in the buffer
+        self.buffer[self.index] = if v.abs() < DENORMAL_THRESHOLD { 0.0 } else { v };
         self.index = (self.index + 1) % self.buffer.len();
         output
     }
@@ -67,6 +68,18 @@ struct ReverbState {
     damping_smoothed: SmoothedParam,
 }
 
+impl ReverbState {
+    /// Clear the allpass buffers and feedback path.
+    fn clear(&mut self) {
+        for ap in self.allpasses.iter_mut() {
+            ap.buffer.fill(0.0);
+            ap.index = 0;
+        }
+        self.feedback_sample = 0.0;
+        self.damping_filter_state = 0.0;
+    }
+}
+
 pub struct SpringReverbEffect {
     // Per-channel state (index 0 = mono/left, index 1 = right). The mono
     // `process` path uses only index 0, so its behavior is unchanged.
@@ -89,7 +102,7 @@ impl SpringReverbEffect {
         let scale = sample_rate / 44100.0;
 
         let make_state = |delays: &[usize; NUM_ALLPASSES]| {
-            let allpasses = std::array::from_fn(|i| {
+            let allpasses = core::array::from_fn(|i| {
                 let len = ((delays[i] as f32) * scale).max(1.0) as usize;
                 AllpassFilter {
                     buffer: vec![0.0; len],
@@ -149,12 +162,7 @@ impl SpringReverbEffect {
         // SAFETY: Called from main thread when reverb is not processing
         let states = unsafe { &mut *self.state.get() };
         for state in states.iter_mut() {
-            for ap in state.allpasses.iter_mut() {
-                ap.buffer.fill(0.0);
-                ap.index = 0;
-            }
-            state.feedback_sample = 0.0;
-            state.damping_filter_state = 0.0;
+            state.clear();
         }
     }
 
@@ -212,6 +220,8 @@ impl SpringReverbEffect {
         if result.is_finite() {
             result
         } else {
+            // Drop the poisoned tail so the NaN does not recirculate
+            state.clear();
             input
         }
     }
@@ -3,22 +3,32 @@
 //! This module provides a sophisticated saturation effect that emulates
 //! tube saturation characteristics for warm, musical distortion.
 //! Uses arctangent-based soft clipping with controllable even harmonic
-//! generation for a more analog sound than simple tanh.
+//! generation for a more analog sound than simple tanh. Optional auto gain
+//! keeps the level steady while the drive moves.
 
 use crate::effects::Effect;
 use crate::frame::StereoFrame;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::denormal::DENORMAL_THRESHOLD;
 use crate::utils::oversampler::{Oversampler, OversamplingMode};
 use crate::utils::smoother::SmoothedParam;
-use std::cell::UnsafeCell;
-use std::f32::consts::FRAC_2_PI;
-use std::sync::atomic::{AtomicU32, AtomicU8, Ordering};
-
-/// Threshold for flushing denormal numbers to zero
-const DENORMAL_THRESHOLD: f32 = 1e-15;
+use core::cell::UnsafeCell;
+use core::f32::consts::{FRAC_2_PI, TAU};
+use core::sync::atomic::{AtomicBool, AtomicU32, AtomicU8, Ordering};
 
 /// DC blocker coefficient (R in RC circuit, ~20Hz cutoff at 44.1kHz)
 const DC_BLOCKER_COEFF: f32 = 0.995;
 
+/// Reference sine amplitude for the auto-gain estimate
+const MAKEUP_REFERENCE_LEVEL: f32 = 0.5;
+
+/// Points per cycle when estimating the auto gain
+const MAKEUP_POINTS: usize = 32;
+
+/// Drive or bias change that makes the cached auto gain stale
+const MAKEUP_TOLERANCE: f32 = 1e-3;
+
 /// Internal mutable state for saturation
 struct SaturationState {
     // Smoothed parameters
@@ -32,6 +42,9 @@ struct SaturationState {
 
     // Selectable oversampler to reduce aliasing from nonlinear processing
     oversampler: Oversampler,
+
+    // Auto gain cached for (drive, bias)
+    makeup: (f32, f32, f32),
 }
 
 /// Tube-style saturation effect
@@ -41,6 +54,7 @@ struct SaturationState {
 /// - Controllable even harmonic generation (warmth)
 /// - Built-in DC blocking
 /// - Smooth parameter transitions
+/// - Optional auto gain (see [`set_auto_gain`](Self::set_auto_gain))
 pub struct TubeSaturation {
     // Per-channel mutable state (index 0 = mono/left, index 1 = right). The mono
     // `process` path uses only index 0, so its behavior is unchanged.
@@ -51,6 +65,7 @@ pub struct TubeSaturation {
     warmth_target: AtomicU32,
     mix_target: AtomicU32,
     oversampling_mode_target: AtomicU8,
+    auto_gain: AtomicBool,
 }
 
 // SAFETY: UnsafeCell only accessed from single audio thread
@@ -77,6 +92,7 @@ impl TubeSaturation {
             dc_x1: 0.0,
             dc_y1: 0.0,
             oversampler: Oversampler::default(),
+            makeup: (1.0, 0.0, 1.0),
         };
 
         Self {
@@ -85,6 +101,7 @@ impl TubeSaturation {
             warmth_target: AtomicU32::new(warmth_clamped.to_bits()),
             mix_target: AtomicU32::new(mix_clamped.to_bits()),
             oversampling_mode_target: AtomicU8::new(OversamplingMode::X4 as u8),
+            auto_gain: AtomicBool::new(false),
         }
     }
 
@@ -124,6 +141,25 @@ impl TubeSaturation {
         soft_sat + second_harmonic * bias
     }
 
+    /// Gain that brings a half-scale sine through the curve back to its dry
+    /// RMS, with the DC offset the blocker removes left out.
+    fn makeup_gain(drive: f32, bias: f32) -> f32 {
+        let mut dry = 0.0;
+        let mut sum = 0.0;
+        let mut sum_sq = 0.0;
+        for i in 0..MAKEUP_POINTS {
+            let x = MAKEUP_REFERENCE_LEVEL * (TAU * i as f32 / MAKEUP_POINTS as f32).sin();
+            let y = Self::saturate(x, drive, bias);
+            dry += x * x;
+            sum += y;
+            sum_sq += y * y;
+        }
+        let points = MAKEUP_POINTS as f32;
+        let mean = sum / points;
+        let wet = sum_sq / points - mean * mean;
+        (dry / points / wet.max(1e-12)).sqrt().clamp(0.1, 10.0)
+    }
+
     /// DC blocking high-pass filter
     #[inline]
     fn dc_block(input: f32, x1: &mut f32, y1: &mut f32) -> f32 {
@@ -160,6 +196,13 @@ impl TubeSaturation {
         self.mix_target.store(clamped.to_bits(), Ordering::Relaxed);
     }
 
+    /// Turn auto gain on or off. While on, the wet signal is scaled so a
+    /// half-scale sine keeps its dry RMS level at any drive and warmth, so
+    /// drive changes alter the tone rather than the loudness. Off by default.
+    pub fn set_auto_gain(&self, enabled: bool) {
+        self.auto_gain.store(enabled, Ordering::Relaxed);
+    }
+
     /// Set the oversampling rate. The audio thread clears filter history when it applies the change.
     pub fn set_oversampling_mode(&self, mode: OversamplingMode) {
         self.oversampling_mode_target
@@ -183,6 +226,11 @@ impl TubeSaturation {
         f32::from_bits(self.mix_target.load(Ordering::Relaxed))
     }
 
+    /// Whether auto gain is on
+    pub fn get_auto_gain(&self) -> bool {
+        self.auto_gain.load(Ordering::Relaxed)
+    }
+
     /// Get the requested oversampling rate.
     pub fn get_oversampling_mode(&self) -> OversamplingMode {
         OversamplingMode::from_u8(self.oversampling_mode_target.load(Ordering::Relaxed))
@@ -238,7 +286,17 @@ impl TubeSaturation {
             .process(input, |x| Self::saturate(x, drive, warmth));
 
         // DC blocking (removes offset from asymmetric saturation)
-        let dc_blocked = Self::dc_block(saturated, &mut state.dc_x1, &mut state.dc_y1);
+        let mut dc_blocked = Self::dc_block(saturated, &mut state.dc_x1, &mut state.dc_y1);
+
+        if self.auto_gain.load(Ordering::Relaxed) {
+            let (cached_drive, cached_bias, _) = state.makeup;
+            if (drive - cached_drive).abs() > MAKEUP_TOLERANCE
+                || (warmth - cached_bias).abs() > MAKEUP_TOLERANCE
+            {
+                state.makeup = (drive, warmth, Self::makeup_gain(drive, warmth));
+            }
+            dc_blocked *= state.makeup.2;
+        }
 
         // Mix dry/wet
         let output = input * (1.0 - mix) + dc_blocked * mix;
@@ -289,7 +347,7 @@ mod tests {
         let sr = 44100.0_f32;
         let mut max_output = 0.0_f32;
         for i in 0..4000 {
-            let input = (2.0 * std::f32::consts::PI * freq * i as f32 / sr).sin();
+            let input = (2.0 * core::f32::consts::PI * freq * i as f32 / sr).sin();
             let output = sat.process(input);
             if i >= 2000 {
                 max_output = max_output.max(output.abs());
@@ -353,6 +411,30 @@ mod tests {
         assert_eq!(sat.get_oversampling_mode(), OversamplingMode::X4);
     }
 
+    #[test]
+    fn test_auto_gain_keeps_loudness_across_drive() {
+        let sine_rms = |drive: f32, auto_gain: bool| {
+            let sat = TubeSaturation::new(44100.0, drive, 0.5, 1.0);
+            sat.set_auto_gain(auto_gain);
+            let mut sum = 0.0;
+            for i in 0..8820 {
+                let x = 0.5 * (TAU * 220.0 * i as f32 / 44100.0).sin();
+                let y = sat.process(x);
+                if i >= 4410 {
+                    sum += y * y;
+                }
+            }
+            (sum / 4410.0).sqrt()
+        };
+        let db = |a: f32, b: f32| 20.0 * (b / a).log10();
+        // Without auto gain the drive swing is several dB...
+        assert!(db(sine_rms(0.0, false), sine_rms(1.0, false)) > 6.0);
+        // ...with it the level holds, at the dry sine's level
+        let (gentle, hot) = (sine_rms(0.0, true), sine_rms(1.0, true));
+        assert!(db(gentle, hot).abs() < 1.0, "{:.2} dB", db(gentle, hot));
+        assert!(db(0.5 / 2.0_f32.sqrt(), hot).abs() < 1.0);
+    }
+
     #[test]
     fn test_reset_matches_fresh_instance() {
         let reset = TubeSaturation::new(44100.0, 0.5, 0.5, 1.0);
@@ -0,0 +1,330 @@
+//! Selectable saturation models
+//!
+//! [`SaturatorModel`] names the transfer curves available to the
+//! [`Waveshaper`] overdrive stage that instruments embed, and to the
+//! [`Saturator`] global effect built on it. Every model except `Soft` (the
+//! waveshaper's original tanh curve, kept bit-identical) is loudness-matched:
+//! its output is scaled so a half-scale sine comes out at the same RMS level
+//! at any drive, so switching models or pushing the drive changes the tone,
+//! not the volume. With auto gain on, `Soft` is matched the same way.
+
+use crate::effects::waveshaper::Waveshaper;
+use crate::effects::Effect;
+use crate::frame::StereoFrame;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::smoother::SmoothedParam;
+use core::cell::UnsafeCell;
+use core::f32::consts::{FRAC_2_PI, FRAC_PI_2, TAU};
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+/// Reference sine amplitude for loudness matching
+const REFERENCE_LEVEL: f32 = 0.5;
+
+/// Points per cycle when measuring a curve's RMS
+const LOUDNESS_POINTS: usize = 32;
+
+/// Saturation transfer curve
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaturatorModel {
+    /// tanh soft clip (the original waveshaper curve)
+    Soft,
+    /// Asymmetric arctangent with even harmonics, like `TubeSaturation`
+    Tube,
+    /// Gentle rational curve between high-frequency pre- and de-emphasis
+    Tape,
+    /// Asymmetric exponential diode clipper
+    Diode,
+    /// Sine wavefolder: soft clip up to full scale, then folds back
+    Foldback,
+    /// Hard clip at ±1
+    HardClip,
+}
+
+impl SaturatorModel {
+    /// Number of models (FFI model constants are `0..COUNT`)
+    pub const COUNT: u32 = 6;
+
+    /// Convert from a u32 model constant (used by FFI)
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Soft),
+            1 => Some(Self::Tube),
+            2 => Some(Self::Tape),
+            3 => Some(Self::Diode),
+            4 => Some(Self::Foldback),
+            5 => Some(Self::HardClip),
+            _ => None,
+        }
+    }
+
+    /// Convert to the u32 model constant (used by FFI)
+    pub fn as_u32(self) -> u32 {
+        match self {
+            Self::Soft => 0,
+            Self::Tube => 1,
+            Self::Tape => 2,
+            Self::Diode => 3,
+            Self::Foldback => 4,
+            Self::HardClip => 5,
+        }
+    }
+
+    /// The static transfer curve, applied after drive gain.
+    #[inline]
+    pub fn shape(self, x: f32) -> f32 {
+        match self {
+            Self::Soft => x.tanh(),
+            Self::Tube => {
+                let biased = x + 0.2 * x.abs();
+                let sat = biased.atan() * FRAC_2_PI;
+                sat + sat * sat.abs() * 0.03
+            }
+            Self::Tape => x / (1.0 + x.abs()),
+            Self::Diode => {
+                if x >= 0.0 {
+                    1.0 - (-x).exp()
+                } else {
+                    // The reverse-biased side conducts later and clips softer
+                    -0.7 * (1.0 - (x / 0.7).exp())
+                }
+            }
+            Self::Foldback => (x * FRAC_PI_2).sin(),
+            Self::HardClip => x.clamp(-1.0, 1.0),
+        }
+    }
+
+    /// Whether the curve is asymmetric and so needs DC blocking.
+    pub(crate) fn is_asymmetric(self) -> bool {
+        matches!(self, Self::Tube | Self::Diode)
+    }
+
+    /// Gain that brings a driven half-scale sine back to its undriven RMS.
+    pub fn loudness_compensation(self, drive: f32) -> f32 {
+        let mut dry = 0.0;
+        let mut wet = 0.0;
+        for i in 0..LOUDNESS_POINTS {
+            let x = REFERENCE_LEVEL * (TAU * i as f32 / LOUDNESS_POINTS as f32).sin();
+            let y = self.shape(x * drive);
+            dry += x * x;
+            wet += y * y;
+        }
+        (dry / wet.max(1e-12)).sqrt().clamp(0.1, 10.0)
+    }
+}
+
+/// Internal per-channel state for the saturator
+struct SaturatorState {
+    shaper: Waveshaper,
+    drive_smoothed: SmoothedParam,
+    mix_smoothed: SmoothedParam,
+}
+
+/// Global saturation effect with a selectable [`SaturatorModel`]
+///
+/// Parameters:
+/// - Model: Transfer curve
+/// - Drive: Saturation amount (0.0-1.0, mapped to 1x-10x gain)
+/// - Mix: Wet/dry mix (0.0-1.0)
+/// - Auto gain: RMS-match the `Soft` curve too (off by default)
+pub struct Saturator {
+    // Per-channel mutable state (index 0 = mono/left, index 1 = right)
+    state: UnsafeCell<[SaturatorState; 2]>,
+
+    // Atomic parameters for lock-free updates from control thread
+    model_target: AtomicU32,
+    drive_target: AtomicU32,
+    mix_target: AtomicU32,
+    auto_gain: AtomicBool,
+}
+
+// SAFETY: UnsafeCell only accessed from single audio thread
+unsafe impl Send for Saturator {}
+unsafe impl Sync for Saturator {}
+
+impl Saturator {
+    /// Create a new saturator
+    ///
+    /// # Arguments
+    /// * `sample_rate` - Audio sample rate in Hz
+    /// * `model` - Initial transfer curve
+    /// * `drive` - Initial drive (0.0-1.0)
+    /// * `mix` - Initial wet/dry mix (0.0-1.0)
+    pub fn new(sample_rate: f32, model: SaturatorModel, drive: f32, mix: f32) -> Self {
+        let drive_clamped = drive.clamp(0.0, 1.0);
+        let mix_clamped = mix.clamp(0.0, 1.0);
+
+        let make_state = || {
+            let mut shaper = Waveshaper::new(1.0, 1.0);
+            shaper.set_model(model);
+            SaturatorState {
+                shaper,
+                drive_smoothed: SmoothedParam::new(drive_clamped, 0.0, 1.0, sample_rate, 30.0),
+                mix_smoothed: SmoothedParam::new(mix_clamped, 0.0, 1.0, sample_rate, 30.0),
+            }
+        };
+
+        Self {
+            state: UnsafeCell::new([make_state(), make_state()]),
+            model_target: AtomicU32::new(model.as_u32()),
+            drive_target: AtomicU32::new(drive_clamped.to_bits()),
+            mix_target: AtomicU32::new(mix_clamped.to_bits()),
+            auto_gain: AtomicBool::new(false),
+        }
+    }
+
+    /// Set the transfer curve
+    pub fn set_model(&self, model: SaturatorModel) {
+        self.model_target.store(model.as_u32(), Ordering::Relaxed);
+    }
+
+    /// Set the drive amount (0.0-1.0)
+    pub fn set_drive(&self, drive: f32) {
+        let clamped = drive.clamp(0.0, 1.0);
+        self.drive_target
+            .store(clamped.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Set the dry/wet mix (0.0-1.0)
+    pub fn set_mix(&self, mix: f32) {
+        let clamped = mix.clamp(0.0, 1.0);
+        self.mix_target.store(clamped.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Turn auto gain on or off (see [`Waveshaper::set_auto_gain`])
+    pub fn set_auto_gain(&self, enabled: bool) {
+        self.auto_gain.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Get the current transfer curve
+    pub fn get_model(&self) -> SaturatorModel {
+        SaturatorModel::from_u32(self.model_target.load(Ordering::Relaxed))
+            .unwrap_or(SaturatorModel::Soft)
+    }
+
+    /// Get the current drive setting
+    pub fn get_drive(&self) -> f32 {
+        f32::from_bits(self.drive_target.load(Ordering::Relaxed))
+    }
+
+    /// Get the current mix setting
+    pub fn get_mix(&self) -> f32 {
+        f32::from_bits(self.mix_target.load(Ordering::Relaxed))
+    }
+
+    /// Whether auto gain is on
+    pub fn get_auto_gain(&self) -> bool {
+        self.auto_gain.load(Ordering::Relaxed)
+    }
+
+    /// Reset internal filter state on all channels
+    pub fn reset(&self) {
+        let states = unsafe { &mut *self.state.get() };
+        for state in states.iter_mut() {
+            state.shaper.reset();
+        }
+    }
+
+    /// Process one sample through a single channel's state.
+    fn process_one(&self, state: &mut SaturatorState, input: f32) -> f32 {
+        state.drive_smoothed.set_target(self.get_drive());
+        state.mix_smoothed.set_target(self.get_mix());
+        let drive = state.drive_smoothed.tick();
+        let mix = state.mix_smoothed.tick();
+
+        state.shaper.set_model(self.get_model());
+        state.shaper.set_drive(1.0 + drive * 9.0);
+        state.shaper.set_auto_gain(self.get_auto_gain());
+
+        if mix < 0.0001 {
+            return input;
+        }
+        let wet = state.shaper.process(input);
+        input * (1.0 - mix) + wet * mix
+    }
+}
+
+impl Effect for Saturator {
+    fn process(&self, input: f32) -> f32 {
+        let states = unsafe { &mut *self.state.get() };
+        self.process_one(&mut states[0], input)
+    }
+
+    fn process_stereo(&self, input: StereoFrame) -> StereoFrame {
+        let states = unsafe { &mut *self.state.get() };
+        StereoFrame {
+            l: self.process_one(&mut states[0], input.l),
+            r: self.process_one(&mut states[1], input.r),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODELS: [SaturatorModel; 6] = [
+        SaturatorModel::Soft,
+        SaturatorModel::Tube,
+        SaturatorModel::Tape,
+        SaturatorModel::Diode,
+        SaturatorModel::Foldback,
+        SaturatorModel::HardClip,
+    ];
+
+    fn sine_rms(sat: &Saturator, amplitude: f32) -> f32 {
+        let sr = 44_100.0;
+        let mut sum = 0.0;
+        let mut count = 0;
+        for i in 0..8820 {
+            let x = amplitude * (TAU * 220.0 * i as f32 / sr).sin();
+            let y = sat.process(x);
+            if i >= 4410 {
+                sum += y * y;
+                count += 1;
+            }
+        }
+        (sum / count as f32).sqrt()
+    }
+
+    #[test]
+    fn model_constants_round_trip() {
+        for model in MODELS {
+            assert_eq!(SaturatorModel::from_u32(model.as_u32()), Some(model));
+        }
+        assert_eq!(SaturatorModel::from_u32(SaturatorModel::COUNT), None);
+    }
+
+    #[test]
+    fn matched_models_keep_loudness_across_drive() {
+        for model in &MODELS[1..] {
+            let gentle = Saturator::new(44_100.0, *model, 0.1, 1.0);
+            let hot = Saturator::new(44_100.0, *model, 1.0, 1.0);
+            let (a, b) = (sine_rms(&gentle, 0.5), sine_rms(&hot, 0.5));
+            let db = 20.0 * (b / a).log10();
+            assert!(db.abs() < 1.5, "{model:?}: {db:.2} dB between drives");
+        }
+    }
+
+    #[test]
+    fn models_sound_different() {
+        let outputs: Vec<f32> = MODELS
+            .iter()
+            .map(|&model| {
+                let sat = Saturator::new(44_100.0, model, 0.8, 1.0);
+                (0..64).map(|i| sat.process((i as f32 * 0.2).sin())).sum()
+            })
+            .collect();
+        for i in 0..outputs.len() {
+            for j in i + 1..outputs.len() {
+                assert_ne!(outputs[i], outputs[j], "{:?} vs {:?}", MODELS[i], MODELS[j]);
+            }
+        }
+    }
+
+    #[test]
+    fn zero_mix_bypasses() {
+        let sat = Saturator::new(44_100.0, SaturatorModel::Foldback, 1.0, 0.0);
+        assert_eq!(sat.process(0.3), 0.3);
+    }
+}
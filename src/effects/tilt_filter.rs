@@ -1,11 +1,12 @@
 use crate::effects::Effect;
 use crate::filters::state_variable_tpt::StateVariableFilterTpt;
 use crate::frame::StereoFrame;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::denormal::DENORMAL_THRESHOLD;
 use crate::utils::smoother::SmoothedParam;
-use std::cell::UnsafeCell;
-use std::sync::atomic::{AtomicU32, Ordering};
-
-const DENORMAL_THRESHOLD: f32 = 1e-15;
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicU32, Ordering};
 
 // Frequency range constants for logarithmic sweep
 const LP_FREQ_MIN: f32 = 80.0;
@@ -194,7 +195,7 @@ mod tests {
         let mut max_output: f32 = 0.0;
         for i in 0..4410 {
             let t = i as f32 / 44100.0;
-            let input = (2.0 * std::f32::consts::PI * freq * t).sin();
+            let input = (2.0 * core::f32::consts::PI * freq * t).sin();
             let output = filter.process(input);
             max_output = max_output.max(output.abs());
         }
@@ -221,7 +222,7 @@ mod tests {
         let mut max_output: f32 = 0.0;
         for i in 0..4410 {
             let t = i as f32 / 44100.0;
-            let input = (2.0 * std::f32::consts::PI * freq * t).sin();
+            let input = (2.0 * core::f32::consts::PI * freq * t).sin();
             let output = filter.process(input);
             max_output = max_output.max(output.abs());
         }
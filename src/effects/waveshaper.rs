@@ -1,10 +1,24 @@
 //! Waveshaper distortion effect for velocity-responsive harmonic generation
 //!
 //! Provides soft-clipping waveshaping similar to Max MSP's overdrive~ object,
-//! with adjustable drive and mix for saturation and warmth.
+//! with adjustable drive and mix for saturation and warmth. Other transfer
+//! curves are selectable with [`Waveshaper::set_model`].
 
+use crate::effects::saturator::SaturatorModel;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::denormal::DENORMAL_THRESHOLD;
 use crate::utils::oversampler::{Oversampler, OversamplingMode};
 
+/// One-pole coefficient of the tape model's emphasis filters (~700 Hz at 44.1kHz)
+const TAPE_EMPHASIS_COEFF: f32 = 0.1;
+
+/// High-frequency boost applied before the tape curve (and removed after)
+const TAPE_EMPHASIS_GAIN: f32 = 1.0;
+
+/// DC blocker coefficient for the asymmetric models
+const DC_BLOCKER_COEFF: f32 = 0.995;
+
 /// Waveshaper distortion with configurable drive
 ///
 /// Uses soft clipping (tanh) for smooth saturation similar to tube overdrive.
@@ -17,6 +31,18 @@ pub struct Waveshaper {
     mix: f32,
     /// Selectable oversampler for alias reduction
     oversampler: Oversampler,
+    /// Transfer curve (default `Soft`, the tanh curve described above)
+    model: SaturatorModel,
+    /// Loudness compensation cached for (model, drive)
+    compensation: (SaturatorModel, f32, f32),
+    /// RMS-match the `Soft` curve too (see [`set_auto_gain`](Self::set_auto_gain))
+    auto_gain: bool,
+    /// Tape pre-/de-emphasis lowpass states
+    emphasis_pre: f32,
+    emphasis_post: f32,
+    /// DC blocker state for the asymmetric models
+    dc_x1: f32,
+    dc_y1: f32,
 }
 
 impl Waveshaper {
@@ -30,6 +56,13 @@ impl Waveshaper {
             drive: drive.clamp(1.0, 10.0),
             mix: mix.clamp(0.0, 1.0),
             oversampler: Oversampler::default(),
+            model: SaturatorModel::Soft,
+            compensation: (SaturatorModel::Soft, 1.0, 1.0),
+            auto_gain: false,
+            emphasis_pre: 0.0,
+            emphasis_post: 0.0,
+            dc_x1: 0.0,
+            dc_y1: 0.0,
         }
     }
 
@@ -56,11 +89,21 @@ impl Waveshaper {
             return input;
         }
 
+        if self.model != SaturatorModel::Soft {
+            let saturated = self.process_model(input);
+            return input * (1.0 - self.mix) + saturated * self.mix;
+        }
+
         let drive = self.drive;
 
-        // Gain compensation: normalize output level to match drive=1.0
-        let reference = 0.5_f32;
-        let compensation = reference.tanh() / (reference * drive).tanh();
+        // Gain compensation: normalize output level to match drive=1.0, by
+        // peak or, with auto gain, by RMS
+        let compensation = if self.auto_gain {
+            self.loudness_compensation(SaturatorModel::Soft, drive)
+        } else {
+            let reference = 0.5_f32;
+            reference.tanh() / (reference * drive).tanh()
+        };
 
         // Apply drive gain and soft-clip using tanh at the selected oversampling rate
         let saturated = self
@@ -71,6 +114,79 @@ impl Waveshaper {
         input * (1.0 - self.mix) + saturated * self.mix
     }
 
+    /// Shape one sample with a loudness-matched non-`Soft` model.
+    fn process_model(&mut self, input: f32) -> f32 {
+        let model = self.model;
+        let drive = self.drive;
+        let compensation = self.loudness_compensation(model, drive);
+
+        // Tape: boost highs into the curve so they saturate first, then undo it
+        let driven = if model == SaturatorModel::Tape {
+            self.emphasis_pre += TAPE_EMPHASIS_COEFF * (input - self.emphasis_pre);
+            input + TAPE_EMPHASIS_GAIN * (input - self.emphasis_pre)
+        } else {
+            input
+        };
+
+        let mut shaped = self
+            .oversampler
+            .process(driven, |x| model.shape(x * drive) * compensation);
+
+        if model == SaturatorModel::Tape {
+            self.emphasis_post += TAPE_EMPHASIS_COEFF * (shaped - self.emphasis_post);
+            shaped =
+                self.emphasis_post + (shaped - self.emphasis_post) / (1.0 + TAPE_EMPHASIS_GAIN);
+        }
+
+        if model.is_asymmetric() {
+            let blocked = shaped - self.dc_x1 + DC_BLOCKER_COEFF * self.dc_y1;
+            self.dc_x1 = shaped;
+            self.dc_y1 = if blocked.abs() < DENORMAL_THRESHOLD {
+                0.0
+            } else {
+                blocked
+            };
+            shaped = blocked;
+        }
+        shaped
+    }
+
+    /// [`SaturatorModel::loudness_compensation`], cached for the last model
+    /// and drive.
+    fn loudness_compensation(&mut self, model: SaturatorModel, drive: f32) -> f32 {
+        if self.compensation.0 != model || self.compensation.1 != drive {
+            self.compensation = (model, drive, model.loudness_compensation(drive));
+        }
+        self.compensation.2
+    }
+
+    /// Select the transfer curve. Non-`Soft` models are loudness-matched (see
+    /// [`SaturatorModel::loudness_compensation`]).
+    pub fn set_model(&mut self, model: SaturatorModel) {
+        if model != self.model {
+            self.model = model;
+            self.reset();
+        }
+    }
+
+    /// Get the current transfer curve
+    pub fn model(&self) -> SaturatorModel {
+        self.model
+    }
+
+    /// Turn auto gain on or off. The `Soft` curve is normally matched to the
+    /// undriven level by peak, which lets the RMS climb by a few dB at high
+    /// drive; with auto gain it is matched by RMS like the other models, which
+    /// always are. Off by default.
+    pub fn set_auto_gain(&mut self, enabled: bool) {
+        self.auto_gain = enabled;
+    }
+
+    /// Whether auto gain is on
+    pub fn auto_gain(&self) -> bool {
+        self.auto_gain
+    }
+
     /// Set the drive amount (1.0-10.0)
     pub fn set_drive(&mut self, drive: f32) {
         self.drive = drive.clamp(1.0, 10.0);
@@ -101,9 +217,13 @@ impl Waveshaper {
         self.oversampler.mode()
     }
 
-    /// Reset the oversampling filter history.
+    /// Reset the oversampling, emphasis and DC blocker history.
     pub fn reset(&mut self) {
         self.oversampler.reset();
+        self.emphasis_pre = 0.0;
+        self.emphasis_post = 0.0;
+        self.dc_x1 = 0.0;
+        self.dc_y1 = 0.0;
     }
 }
 
@@ -165,6 +285,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_auto_gain_matches_soft_curve_by_rms() {
+        let sine_rms = |drive: f32, auto_gain: bool| {
+            let mut ws = Waveshaper::new(drive, 1.0);
+            ws.set_auto_gain(auto_gain);
+            let mut sum = 0.0;
+            for i in 0..8820 {
+                let x = 0.5 * (core::f32::consts::TAU * 220.0 * i as f32 / 44100.0).sin();
+                let y = ws.process(x);
+                if i >= 4410 {
+                    sum += y * y;
+                }
+            }
+            (sum / 4410.0).sqrt()
+        };
+        let spread =
+            |auto_gain| 20.0 * (sine_rms(10.0, auto_gain) / sine_rms(2.0, auto_gain)).log10();
+        let peak_matched = spread(false);
+        let rms_matched = spread(true);
+        assert!(rms_matched.abs() < 0.5, "{rms_matched:.2} dB");
+        assert!(
+            rms_matched.abs() < peak_matched.abs(),
+            "{rms_matched:.2} vs {peak_matched:.2} dB"
+        );
+    }
+
     #[test]
     fn test_parameter_clamping() {
         let ws = Waveshaper::new(100.0, 5.0);
@@ -0,0 +1,393 @@
+//! Stereo widener with a mono-compatibility safeguard
+//!
+//! Works in mid/side. The signal is split at a crossover into lows and highs
+//! (a one-pole lowpass and its complement, so the bands sum back exactly).
+//! Above the crossover, width scales the side channel and, past 1.0, adds a
+//! Haas-style decorrelated copy of the mid: the mid delayed by a few
+//! milliseconds, fed into the side only. Because it lives in the side it
+//! cancels exactly when the output is summed to mono, unlike a plain Haas
+//! delay on one channel, which comb-filters the mono sum.
+//!
+//! Below the crossover width is never allowed past 1.0, so kick and bass stay
+//! where they were. On top of that the lows are pulled toward mono while their
+//! own left/right correlation is strongly negative, catching out-of-phase low end in
+//! the source before it cancels on a mono system.
+//!
+//! The output correlation (-1 to 1, averaged over ~300 ms) is published for
+//! metering: +1 is mono, 0 is fully decorrelated, negative values mean parts
+//! of the mix will cancel in mono.
+
+use crate::effects::Effect;
+use crate::frame::StereoFrame;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::denormal::flush_denormal;
+use crate::utils::smoother::SmoothedParam;
+use core::cell::UnsafeCell;
+use core::f32::consts::TAU;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+/// Width range: 0.0 = mono, 1.0 = unchanged, 2.0 = widest
+pub const WIDENER_MAX_WIDTH: f32 = 2.0;
+
+/// Haas delay range in milliseconds
+const MIN_DELAY_MS: f32 = 1.0;
+const MAX_DELAY_MS: f32 = 30.0;
+
+/// Crossover range in Hz
+const MIN_CROSSOVER_HZ: f32 = 20.0;
+const MAX_CROSSOVER_HZ: f32 = 1_000.0;
+
+/// Level of the delayed mid added to the side at full width
+const HAAS_GAIN: f32 = 0.5;
+
+/// Averaging time of the correlation meter and the low-band guard
+const CORRELATION_MS: f32 = 300.0;
+
+/// Low-band correlation at which the lows start folding toward mono; they
+/// are fully mono at -1.0. Uncorrelated lows (around 0.0) are left alone.
+const GUARD_CORRELATION: f32 = -0.5;
+
+/// Below this mean-square energy the correlation reads +1 (silence is mono)
+const CORRELATION_FLOOR: f32 = 1e-10;
+
+/// Running left/right correlation: one-pole averages of L*R, L^2 and R^2
+#[derive(Default)]
+struct Correlation {
+    lr: f32,
+    ll: f32,
+    rr: f32,
+}
+
+impl Correlation {
+    fn tick(&mut self, l: f32, r: f32, coeff: f32) -> f32 {
+        self.lr = flush_denormal(self.lr + coeff * (l * r - self.lr));
+        self.ll = flush_denormal(self.ll + coeff * (l * l - self.ll));
+        self.rr = flush_denormal(self.rr + coeff * (r * r - self.rr));
+        let energy = (self.ll * self.rr).sqrt();
+        if energy < CORRELATION_FLOOR {
+            1.0
+        } else {
+            (self.lr / energy).clamp(-1.0, 1.0)
+        }
+    }
+}
+
+/// Internal mutable state (wrapped in UnsafeCell for interior mutability)
+struct WidenerState {
+    // Crossover lowpass states for left and right
+    low: [f32; 2],
+
+    // High-band mid history for the Haas side signal
+    buffer: Vec<f32>,
+    write_index: usize,
+
+    low_correlation: Correlation,
+    output_correlation: Correlation,
+
+    width_smoothed: SmoothedParam,
+    delay_smoothed: SmoothedParam,
+    crossover_smoothed: SmoothedParam,
+}
+
+impl WidenerState {
+    /// Read the mid written `offset` samples ago (fractional, after this
+    /// frame's write: offset 0 = the sample just written).
+    fn tap(&self, offset: f32) -> f32 {
+        let len = self.buffer.len();
+        let offset = offset.clamp(0.0, (len - 2) as f32);
+        let whole = offset as usize;
+        let frac = offset - whole as f32;
+        let a = self.buffer[(self.write_index + len - 1 - whole) % len];
+        let b = self.buffer[(self.write_index + len - 2 - whole) % len];
+        a + frac * (b - a)
+    }
+}
+
+/// Stereo widener effect
+///
+/// Parameters:
+/// - Width: 0.0 = mono, 1.0 = unchanged, 2.0 = widest
+/// - Delay: Haas delay for the decorrelated side (1.0-30.0 ms)
+/// - Crossover: frequency below which width is limited (20.0-1000.0 Hz)
+pub struct Widener {
+    sample_rate: f32,
+    correlation_coeff: f32,
+
+    // SAFETY: This is only accessed from the audio thread during process()
+    state: UnsafeCell<WidenerState>,
+
+    // Atomic parameters for lock-free updates from control thread
+    width_target: AtomicU32,
+    delay_target: AtomicU32,
+    crossover_target: AtomicU32,
+
+    // Output correlation, written by the audio thread for meters
+    correlation: AtomicU32,
+}
+
+// SAFETY: The UnsafeCell is only accessed from a single audio thread
+// The AtomicU32 fields are inherently thread-safe
+unsafe impl Send for Widener {}
+unsafe impl Sync for Widener {}
+
+impl Widener {
+    /// Create a new widener
+    ///
+    /// # Arguments
+    /// * `sample_rate` - Audio sample rate in Hz
+    /// * `width` - Initial width (0.0-2.0)
+    /// * `delay_ms` - Initial Haas delay (1.0-30.0 ms)
+    /// * `crossover` - Initial crossover frequency (20.0-1000.0 Hz)
+    pub fn new(sample_rate: f32, width: f32, delay_ms: f32, crossover: f32) -> Self {
+        let width = width.clamp(0.0, WIDENER_MAX_WIDTH);
+        let delay_ms = delay_ms.clamp(MIN_DELAY_MS, MAX_DELAY_MS);
+        let crossover = crossover.clamp(MIN_CROSSOVER_HZ, MAX_CROSSOVER_HZ);
+
+        let buffer_size = (MAX_DELAY_MS * 0.001 * sample_rate).ceil() as usize + 4;
+        let state = WidenerState {
+            low: [0.0; 2],
+            buffer: vec![0.0; buffer_size],
+            write_index: 0,
+            low_correlation: Correlation::default(),
+            output_correlation: Correlation::default(),
+            width_smoothed: SmoothedParam::new(width, 0.0, WIDENER_MAX_WIDTH, sample_rate, 20.0),
+            delay_smoothed: SmoothedParam::new(
+                delay_ms,
+                MIN_DELAY_MS,
+                MAX_DELAY_MS,
+                sample_rate,
+                50.0,
+            ),
+            crossover_smoothed: SmoothedParam::new(
+                crossover,
+                MIN_CROSSOVER_HZ,
+                MAX_CROSSOVER_HZ,
+                sample_rate,
+                20.0,
+            ),
+        };
+
+        Self {
+            sample_rate,
+            correlation_coeff: 1.0 - (-1.0 / (CORRELATION_MS * 0.001 * sample_rate)).exp(),
+            state: UnsafeCell::new(state),
+            width_target: AtomicU32::new(width.to_bits()),
+            delay_target: AtomicU32::new(delay_ms.to_bits()),
+            crossover_target: AtomicU32::new(crossover.to_bits()),
+            correlation: AtomicU32::new(1.0_f32.to_bits()),
+        }
+    }
+
+    pub fn set_width(&self, value: f32) {
+        self.width_target.store(
+            value.clamp(0.0, WIDENER_MAX_WIDTH).to_bits(),
+            Ordering::Relaxed,
+        );
+    }
+
+    pub fn get_width(&self) -> f32 {
+        f32::from_bits(self.width_target.load(Ordering::Relaxed))
+    }
+
+    pub fn set_delay(&self, ms: f32) {
+        self.delay_target.store(
+            ms.clamp(MIN_DELAY_MS, MAX_DELAY_MS).to_bits(),
+            Ordering::Relaxed,
+        );
+    }
+
+    pub fn get_delay(&self) -> f32 {
+        f32::from_bits(self.delay_target.load(Ordering::Relaxed))
+    }
+
+    pub fn set_crossover(&self, hz: f32) {
+        self.crossover_target.store(
+            hz.clamp(MIN_CROSSOVER_HZ, MAX_CROSSOVER_HZ).to_bits(),
+            Ordering::Relaxed,
+        );
+    }
+
+    pub fn get_crossover(&self) -> f32 {
+        f32::from_bits(self.crossover_target.load(Ordering::Relaxed))
+    }
+
+    /// Output left/right correlation (-1.0 to 1.0), averaged over ~300 ms.
+    /// Reads +1.0 on silence. Safe to call from any thread.
+    pub fn correlation(&self) -> f32 {
+        f32::from_bits(self.correlation.load(Ordering::Relaxed))
+    }
+
+    /// Reset widener state (clear the delay line, filters and meter)
+    pub fn reset(&self) {
+        // SAFETY: Called from main thread when the effect is not processing
+        let state = unsafe { &mut *self.state.get() };
+        state.low = [0.0; 2];
+        state.buffer.fill(0.0);
+        state.write_index = 0;
+        state.low_correlation = Correlation::default();
+        state.output_correlation = Correlation::default();
+        self.correlation.store(1.0_f32.to_bits(), Ordering::Relaxed);
+    }
+}
+
+impl Effect for Widener {
+    fn process(&self, input: f32) -> f32 {
+        // A mono signal has no side to widen
+        input
+    }
+
+    fn process_stereo(&self, input: StereoFrame) -> StereoFrame {
+        // SAFETY: process_stereo() is only called from the audio thread
+        let state = unsafe { &mut *self.state.get() };
+        let l = if input.l.is_finite() { input.l } else { 0.0 };
+        let r = if input.r.is_finite() { input.r } else { 0.0 };
+
+        state
+            .width_smoothed
+            .set_target(f32::from_bits(self.width_target.load(Ordering::Relaxed)));
+        state
+            .delay_smoothed
+            .set_target(f32::from_bits(self.delay_target.load(Ordering::Relaxed)));
+        state.crossover_smoothed.set_target(f32::from_bits(
+            self.crossover_target.load(Ordering::Relaxed),
+        ));
+        let width = state.width_smoothed.tick();
+        let delay = state.delay_smoothed.tick() * 0.001 * self.sample_rate;
+        let crossover = state.crossover_smoothed.tick();
+
+        // Complementary one-pole split: low + high == input
+        let coeff = 1.0 - (-TAU * crossover / self.sample_rate).exp();
+        for (low, x) in state.low.iter_mut().zip([l, r]) {
+            *low = flush_denormal(*low + coeff * (x - *low));
+        }
+        let [low_l, low_r] = state.low;
+        let (high_l, high_r) = (l - low_l, r - low_r);
+
+        // Lows: never wider than the source, and folded toward mono while
+        // they are out of phase.
+        let low_corr = state
+            .low_correlation
+            .tick(low_l, low_r, self.correlation_coeff);
+        let guard = ((1.0 + low_corr) / (1.0 + GUARD_CORRELATION)).clamp(0.0, 1.0);
+        let low_mid = 0.5 * (low_l + low_r);
+        let low_side = 0.5 * (low_l - low_r) * width.min(1.0) * guard;
+
+        // Highs: scaled side plus, past unity width, the delayed mid
+        let high_mid = 0.5 * (high_l + high_r);
+        let len = state.buffer.len();
+        state.buffer[state.write_index] = high_mid;
+        state.write_index = (state.write_index + 1) % len;
+        let haas = HAAS_GAIN * (width - 1.0).max(0.0) * state.tap(delay);
+        let high_side = 0.5 * (high_l - high_r) * width + haas;
+
+        let mid = low_mid + high_mid;
+        let side = low_side + high_side;
+        let out = StereoFrame {
+            l: mid + side,
+            r: mid - side,
+        };
+        if !(out.l.is_finite() && out.r.is_finite()) {
+            return StereoFrame { l, r };
+        }
+
+        let corr = state
+            .output_correlation
+            .tick(out.l, out.r, self.correlation_coeff);
+        self.correlation.store(corr.to_bits(), Ordering::Relaxed);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 44_100.0;
+
+    /// Deterministic white noise in -1..1
+    fn noise(seed: &mut u32) -> f32 {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 17;
+        *seed ^= *seed << 5;
+        *seed as f32 / u32::MAX as f32 * 2.0 - 1.0
+    }
+
+    fn run(widener: &Widener, frames: usize, mut input: impl FnMut(usize) -> StereoFrame) {
+        for i in 0..frames {
+            widener.process_stereo(input(i));
+        }
+    }
+
+    #[test]
+    fn test_unity_width_is_transparent() {
+        let widener = Widener::new(SR, 1.0, 10.0, 150.0);
+        let mut seed = 7;
+        for i in 0..SR as usize {
+            let input = StereoFrame {
+                l: noise(&mut seed),
+                r: noise(&mut seed),
+            };
+            let out = widener.process_stereo(input);
+            // Once the low-band correlation has settled on uncorrelated noise
+            if i > 4_096 {
+                assert!((out.l - input.l).abs() < 1e-5 && (out.r - input.r).abs() < 1e-5);
+            }
+        }
+        assert_eq!(widener.process(0.3), 0.3);
+    }
+
+    #[test]
+    fn test_width_spreads_mono_highs_but_mono_sum_is_untouched() {
+        let widener = Widener::new(SR, 2.0, 12.0, 150.0);
+        let mut seed = 11;
+        let mut outputs = Vec::new();
+        for _ in 0..SR as usize {
+            let x = noise(&mut seed) * 0.5;
+            let out = widener.process_stereo(StereoFrame::mono(x));
+            outputs.push((x, out));
+        }
+        // Decorrelated, but summing to mono gives back the input exactly
+        let corr = widener.correlation();
+        assert!(corr < 0.9 && corr > 0.0, "correlation {corr}");
+        for (x, out) in outputs {
+            assert!((0.5 * (out.l + out.r) - x).abs() < 1e-5);
+        }
+
+        let mono = Widener::new(SR, 0.0, 12.0, 150.0);
+        let out = mono.process_stereo(StereoFrame { l: 0.4, r: -0.2 });
+        assert!((out.l - out.r).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_out_of_phase_lows_fold_to_mono() {
+        let widener = Widener::new(SR, 2.0, 12.0, 300.0);
+        let sine = |i: usize| (TAU * 50.0 * i as f32 / SR).sin() * 0.5;
+        run(&widener, SR as usize, |i| StereoFrame {
+            l: sine(i),
+            r: -sine(i),
+        });
+        let mut peak = 0.0_f32;
+        for i in 0..2_048 {
+            let out = widener.process_stereo(StereoFrame {
+                l: sine(i),
+                r: -sine(i),
+            });
+            peak = peak.max(out.l.abs());
+        }
+        // A 50 Hz anti-phase tone would be boosted to 2x without the guard
+        assert!(peak < 0.25, "peak {peak}");
+        assert!(widener.correlation() < 0.0);
+    }
+
+    #[test]
+    fn test_silence_reads_mono_correlation() {
+        let widener = Widener::new(SR, 1.5, 10.0, 150.0);
+        run(&widener, 1_024, |_| StereoFrame::default());
+        assert_eq!(widener.correlation(), 1.0);
+        widener.set_width(5.0);
+        assert_eq!(widener.get_width(), WIDENER_MAX_WIDTH);
+        widener.set_crossover(5.0);
+        assert_eq!(widener.get_crossover(), MIN_CROSSOVER_HZ);
+    }
+}
@@ -0,0 +1,125 @@
+//! Effect lanes: step sequences of parameter values
+//!
+//! An [`EffectLane`] is a sixteen-step row of values for one effect
+//! parameter (a filter cutoff, a delay send), played in time with the
+//! sequencers. The lane only knows its values; whoever owns it decides which
+//! parameter they drive and reads the lane at the transport's step position.
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Steps in an effect lane (one 4/4 bar of sixteenths).
+pub const EFFECT_LANE_STEPS: usize = 16;
+
+/// Sixteen parameter values, one per step. Steps left unset hold the value
+/// of the last set step before them, wrapping round the bar.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct EffectLane {
+    steps: [Option<f32>; EFFECT_LANE_STEPS],
+    /// Glide from each set step to the next instead of jumping on the step
+    interpolate: bool,
+}
+
+impl EffectLane {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A lane with `values` on its first steps (extra values are ignored).
+    pub fn from_values(values: &[Option<f32>]) -> Self {
+        let mut lane = Self::default();
+        for (step, value) in lane.steps.iter_mut().zip(values) {
+            *step = value.filter(|v| v.is_finite());
+        }
+        lane
+    }
+
+    /// Set a step's value. Out-of-range steps and non-finite values are ignored.
+    pub fn set_step(&mut self, step: usize, value: f32) {
+        if let Some(slot) = self.steps.get_mut(step) {
+            if value.is_finite() {
+                *slot = Some(value);
+            }
+        }
+    }
+
+    /// Unset a step so it holds the value before it.
+    pub fn clear_step(&mut self, step: usize) {
+        if let Some(slot) = self.steps.get_mut(step) {
+            *slot = None;
+        }
+    }
+
+    pub fn step(&self, step: usize) -> Option<f32> {
+        self.steps.get(step).copied().flatten()
+    }
+
+    /// Glide between set steps (`true`) or jump on each one (`false`, the default).
+    pub fn set_interpolate(&mut self, interpolate: bool) {
+        self.interpolate = interpolate;
+    }
+
+    pub fn interpolate(&self) -> bool {
+        self.interpolate
+    }
+
+    /// The lane's value at `position`, in steps from the top of the bar
+    /// (fractions are part way through a step; the lane wraps every bar).
+    /// `None` while no step is set.
+    ///
+    /// Stepped lanes hold the last set step at or before the position.
+    /// Interpolated lanes move linearly from it to the next set step, so
+    /// `200 . . . 800` ramps across the four steps.
+    pub fn value_at(&self, position: f64) -> Option<f32> {
+        let position = position.rem_euclid(EFFECT_LANE_STEPS as f64);
+        let current = position.floor() as usize % EFFECT_LANE_STEPS;
+        let (from, from_value) = (0..EFFECT_LANE_STEPS)
+            .map(|back| (current + EFFECT_LANE_STEPS - back) % EFFECT_LANE_STEPS)
+            .find_map(|step| self.steps[step].map(|value| (step, value)))?;
+        if !self.interpolate {
+            return Some(from_value);
+        }
+        let (distance, to_value) = (1..=EFFECT_LANE_STEPS)
+            .find_map(|ahead| {
+                self.steps[(from + ahead) % EFFECT_LANE_STEPS].map(|value| (ahead, value))
+            })
+            .unwrap_or((EFFECT_LANE_STEPS, from_value));
+        let elapsed = (position - from as f64).rem_euclid(EFFECT_LANE_STEPS as f64);
+        let t = (elapsed / distance as f64) as f32;
+        Some(from_value + (to_value - from_value) * t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stepped_lane_holds_between_set_steps() {
+        let lane = EffectLane::from_values(&[Some(200.0), None, Some(400.0)]);
+        assert_eq!(lane.value_at(0.0), Some(200.0));
+        assert_eq!(lane.value_at(1.9), Some(200.0));
+        assert_eq!(lane.value_at(2.0), Some(400.0));
+        // The last set step carries round the bar
+        assert_eq!(lane.value_at(15.5), Some(400.0));
+        assert_eq!(lane.value_at(16.5), Some(200.0));
+        assert_eq!(EffectLane::new().value_at(3.0), None);
+    }
+
+    #[test]
+    fn test_interpolated_lane_ramps_to_the_next_set_step() {
+        let mut lane = EffectLane::from_values(&[Some(200.0), None, None, None, Some(800.0)]);
+        lane.set_interpolate(true);
+        assert_eq!(lane.value_at(0.0), Some(200.0));
+        assert_eq!(lane.value_at(2.0), Some(500.0));
+        assert_eq!(lane.value_at(4.0), Some(800.0));
+        // Back down to the first step over the rest of the bar
+        assert_eq!(lane.value_at(10.0), Some(500.0));
+
+        lane.clear_step(4);
+        assert_eq!(lane.value_at(9.0), Some(200.0));
+        lane.set_step(EFFECT_LANE_STEPS, 1.0);
+        lane.set_step(3, f32::NAN);
+        assert_eq!(lane.step(3), None);
+    }
+}
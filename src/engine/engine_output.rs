@@ -1,11 +1,12 @@
-use super::Engine;
+use super::{Engine, MAX_OUTPUT_PAIRS};
 use crate::frame::StereoFrame;
+use crate::utils::DenormalGuard;
 #[cfg(feature = "native")]
 use cpal::{
     traits::{DeviceTrait, HostTrait, StreamTrait},
-    Device, FromSample, Sample, SizedSample, Stream, StreamConfig,
+    Device, FromSample, Sample, SampleFormat, SizedSample, Stream, StreamConfig,
 };
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
@@ -17,10 +18,13 @@ pub struct EngineOutput {
     stream: Option<Stream>,
     device: Option<Device>,
     config: Option<StreamConfig>,
+    sample_format: Option<SampleFormat>,
+    // Device channels asked for with `request_output_channels`
+    requested_channels: u16,
     sample_rate: f32,
     is_active: bool,
     start_time: Option<Instant>,
-    sample_counter: Arc<Mutex<u64>>,
+    sample_counter: Arc<AtomicU64>,
     overrun_counter: Arc<AtomicUsize>,
     #[cfg(feature = "visualization")]
     audio_buffer: Option<AudioBuffer>,
@@ -35,10 +39,12 @@ impl EngineOutput {
             stream: None,
             device: None,
             config: None,
+            sample_format: None,
+            requested_channels: 2,
             sample_rate: 44100.0,
             is_active: false,
             start_time: None,
-            sample_counter: Arc::new(Mutex::new(0)),
+            sample_counter: Arc::new(AtomicU64::new(0)),
             overrun_counter: Arc::new(AtomicUsize::new(0)),
             #[cfg(feature = "visualization")]
             audio_buffer: None,
@@ -128,6 +134,21 @@ impl EngineOutput {
         false
     }
 
+    /// Ask for at least `channels` device channels, e.g. 8 to give three
+    /// instruments direct outs on channels 3-8 (see
+    /// [`Engine::set_instrument_output`]). Call before `initialize`. If the
+    /// device can't open that many at its default rate, `initialize` settles
+    /// for the widest layout it offers, and routes past it fold into the main
+    /// mix.
+    pub fn request_output_channels(&mut self, channels: u16) {
+        self.requested_channels = channels.max(1);
+    }
+
+    /// Device channels of the opened stream (0 before `initialize`)
+    pub fn output_channels(&self) -> u16 {
+        self.config.as_ref().map_or(0, |config| config.channels)
+    }
+
     /// Initialize the audio output with the given sample rate
     pub fn initialize(&mut self, sample_rate: f32) -> Result<(), anyhow::Error> {
         self.sample_rate = sample_rate;
@@ -148,8 +169,10 @@ impl EngineOutput {
             .config
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("Config not initialized"))?;
+        let sample_format = self
+            .sample_format
+            .ok_or_else(|| anyhow::anyhow!("Config not initialized"))?;
 
-        let supported_config = device.default_output_config()?;
         let sample_counter = self.sample_counter.clone();
         let overrun_counter = self.overrun_counter.clone();
 
@@ -159,7 +182,7 @@ impl EngineOutput {
         #[cfg(not(feature = "visualization"))]
         let audio_buffer: Option<()> = None;
 
-        let stream = match supported_config.sample_format() {
+        let stream = match sample_format {
             cpal::SampleFormat::I8 => Self::make_stream::<i8>(
                 device,
                 config,
@@ -262,10 +285,36 @@ impl EngineOutput {
 
         println!("Output device: {}", device.name()?);
 
-        let config = device.default_output_config()?;
+        let mut config = device.default_output_config()?;
         println!("Default output config: {:?}", config);
 
+        // Widen to the requested channel count (direct outs) if the device
+        // offers a layout with enough channels at the same rate and format
+        if config.channels() < self.requested_channels {
+            let rate = config.sample_rate();
+            let wider = device
+                .supported_output_configs()?
+                .filter(|range| {
+                    range.sample_format() == config.sample_format()
+                        && range.channels() > config.channels()
+                        && (range.min_sample_rate()..=range.max_sample_rate()).contains(&rate)
+                })
+                // The narrowest layout that fits, else the widest there is
+                .min_by_key(|range| {
+                    let channels = range.channels();
+                    (
+                        channels < self.requested_channels,
+                        channels.abs_diff(self.requested_channels),
+                    )
+                });
+            if let Some(range) = wider {
+                config = range.with_sample_rate(rate);
+                println!("Multi-channel output config: {:?}", config);
+            }
+        }
+
         self.sample_rate = config.sample_rate().0 as f32;
+        self.sample_format = Some(config.sample_format());
         self.device = Some(device);
         self.config = Some(config.into());
 
@@ -278,7 +327,7 @@ impl EngineOutput {
         device: &Device,
         config: &StreamConfig,
         engine: Arc<Mutex<Engine>>,
-        sample_counter: Arc<Mutex<u64>>,
+        sample_counter: Arc<AtomicU64>,
         overrun_counter: Arc<AtomicUsize>,
         audio_buffer: Option<AudioBuffer>,
     ) -> Result<Stream, anyhow::Error>
@@ -293,6 +342,7 @@ impl EngineOutput {
         let stream = device.build_output_stream(
             config,
             move |output: &mut [T], _: &cpal::OutputCallbackInfo| {
+                let _ftz = DenormalGuard::new();
                 let start = Instant::now();
                 Self::process_frame(
                     output,
@@ -322,7 +372,7 @@ impl EngineOutput {
         device: &Device,
         config: &StreamConfig,
         engine: Arc<Mutex<Engine>>,
-        sample_counter: Arc<Mutex<u64>>,
+        sample_counter: Arc<AtomicU64>,
         overrun_counter: Arc<AtomicUsize>,
         _audio_buffer: Option<()>,
     ) -> Result<Stream, anyhow::Error>
@@ -337,6 +387,7 @@ impl EngineOutput {
         let stream = device.build_output_stream(
             config,
             move |output: &mut [T], _: &cpal::OutputCallbackInfo| {
+                let _ftz = DenormalGuard::new();
                 let start = Instant::now();
                 Self::process_frame_no_viz(
                     output,
@@ -365,7 +416,7 @@ impl EngineOutput {
         output: &mut [SampleType],
         engine: &Arc<Mutex<Engine>>,
         num_channels: usize,
-        sample_counter: &Arc<Mutex<u64>>,
+        sample_counter: &Arc<AtomicU64>,
         sample_rate: f64,
         audio_buffer: Option<&AudioBuffer>,
     ) where
@@ -374,31 +425,28 @@ impl EngineOutput {
         let frames_to_process = output.len() / num_channels;
 
         // Get the current sample counter and increment it atomically
-        let start_sample = {
-            let mut counter = sample_counter.lock().unwrap();
-            let current = *counter;
-            *counter += frames_to_process as u64;
-            current
-        };
+        let start_sample = sample_counter.fetch_add(frames_to_process as u64, Ordering::Relaxed);
 
         // Lock the engine once for the entire buffer
         let mut engine_guard = engine.lock().unwrap();
+        engine_guard.begin_buffer(start_sample);
+        let pairs = Self::output_pairs(&engine_guard, num_channels);
 
-        for (frame_index, frame) in output.chunks_mut(num_channels).enumerate() {
-            // Calculate precise time using sample-based timing like Web Audio
-            let current_sample = start_sample + frame_index as u64;
-            let current_time = current_sample as f64 / sample_rate;
+        crate::rt_audit::audio_section(|| {
+            for (frame_index, frame) in output.chunks_mut(num_channels).enumerate() {
+                // Calculate precise time using sample-based timing like Web Audio
+                let current_sample = start_sample + frame_index as u64;
+                let current_time = current_sample as f64 / sample_rate;
 
-            // Call engine.tick_stereo() to generate a stereo frame
-            let stereo = engine_guard.tick_stereo(current_time);
+                let stereo =
+                    Self::render_device_frame(&mut engine_guard, frame, pairs, current_time);
 
-            // Capture audio for visualization (mono downmix; one value per sample)
-            if let Some(buffer) = audio_buffer {
-                buffer.push(stereo.downmix());
+                // Capture audio for visualization (mono downmix; one value per sample)
+                if let Some(buffer) = audio_buffer {
+                    buffer.push(stereo.downmix());
+                }
             }
-
-            Self::write_stereo_frame(frame, stereo);
-        }
+        });
     }
 
     /// Process a single frame of audio data (without visualization)
@@ -407,7 +455,7 @@ impl EngineOutput {
         output: &mut [SampleType],
         engine: &Arc<Mutex<Engine>>,
         num_channels: usize,
-        sample_counter: &Arc<Mutex<u64>>,
+        sample_counter: &Arc<AtomicU64>,
         sample_rate: f64,
     ) where
         SampleType: Sample + FromSample<f32>,
@@ -415,25 +463,66 @@ impl EngineOutput {
         let frames_to_process = output.len() / num_channels;
 
         // Get the current sample counter and increment it atomically
-        let start_sample = {
-            let mut counter = sample_counter.lock().unwrap();
-            let current = *counter;
-            *counter += frames_to_process as u64;
-            current
-        };
+        let start_sample = sample_counter.fetch_add(frames_to_process as u64, Ordering::Relaxed);
 
         // Lock the engine once for the entire buffer
         let mut engine_guard = engine.lock().unwrap();
+        engine_guard.begin_buffer(start_sample);
+        let pairs = Self::output_pairs(&engine_guard, num_channels);
 
-        for (frame_index, frame) in output.chunks_mut(num_channels).enumerate() {
-            // Calculate precise time using sample-based timing like Web Audio
-            let current_sample = start_sample + frame_index as u64;
-            let current_time = current_sample as f64 / sample_rate;
+        crate::rt_audit::audio_section(|| {
+            for (frame_index, frame) in output.chunks_mut(num_channels).enumerate() {
+                // Calculate precise time using sample-based timing like Web Audio
+                let current_sample = start_sample + frame_index as u64;
+                let current_time = current_sample as f64 / sample_rate;
 
-            // Call engine.tick_stereo() to generate a stereo frame
-            let stereo = engine_guard.tick_stereo(current_time);
+                Self::render_device_frame(&mut engine_guard, frame, pairs, current_time);
+            }
+        });
+    }
 
+    /// Output pairs to render for a buffer: 1 (plain stereo) unless the
+    /// engine has direct outs and the device has channels beyond 1/2 for them.
+    fn output_pairs(engine: &Engine, num_channels: usize) -> usize {
+        if engine.output_pairs_needed() > 1 {
+            (num_channels / 2).min(MAX_OUTPUT_PAIRS)
+        } else {
+            1
+        }
+    }
+
+    /// Render one device frame across `pairs` output pairs and return the
+    /// main mix.
+    fn render_device_frame<SampleType>(
+        engine: &mut Engine,
+        frame: &mut [SampleType],
+        pairs: usize,
+        current_time: f64,
+    ) -> StereoFrame
+    where
+        SampleType: Sample + FromSample<f32>,
+    {
+        if pairs <= 1 {
+            let stereo = engine.tick_stereo(current_time);
             Self::write_stereo_frame(frame, stereo);
+            return stereo;
+        }
+        let mut outputs = [StereoFrame::default(); MAX_OUTPUT_PAIRS];
+        engine.tick_multi(current_time, &mut outputs[..pairs]);
+        Self::write_output_pairs(frame, &outputs[..pairs]);
+        outputs[0]
+    }
+
+    /// Write output pairs into one device frame: pair `n` to channels `2n`
+    /// and `2n + 1`. Channels past the last pair are silent.
+    fn write_output_pairs<SampleType>(frame: &mut [SampleType], pairs: &[StereoFrame])
+    where
+        SampleType: Sample + FromSample<f32>,
+    {
+        frame.fill(SampleType::EQUILIBRIUM);
+        for (channels, pair) in frame.chunks_exact_mut(2).zip(pairs) {
+            channels[0] = SampleType::from_sample(pair.l);
+            channels[1] = SampleType::from_sample(pair.r);
         }
     }
 
@@ -469,7 +558,7 @@ impl EngineOutput {
     pub fn start(&mut self) -> Result<(), anyhow::Error> {
         if let Some(stream) = &self.stream {
             // Reset sample counter when starting
-            *self.sample_counter.lock().unwrap() = 0;
+            self.sample_counter.store(0, Ordering::Relaxed);
             stream.play()?;
             self.is_active = true;
             self.start_time = Some(Instant::now());
@@ -0,0 +1,363 @@
+//! Node-based routing between instruments and the master bus.
+//!
+//! An [`AudioGraph`] replaces the engine's fixed "every instrument sums into
+//! the master bus" topology. Nodes are instrument sources, effects, and buses;
+//! connections carry stereo audio between them with a per-connection gain, so
+//! sends, parallel chains, and submixes are just more nodes and edges.
+//!
+//! Forward connections must form a DAG — [`AudioGraph::connect`] rejects any
+//! edge that would close a cycle. Feedback loops are built with
+//! [`AudioGraph::connect_feedback`], which reads the source node's output from
+//! the previous sample; the one-sample delay keeps the graph computable, and a
+//! [`DelayEffect`](crate::effects::DelayEffect) inside the loop gives it an
+//! audible echo time.
+//!
+//! Editing the graph (adding nodes, connecting) allocates and re-sorts it, so
+//! do it at config time. [`AudioGraph::process`] only touches pre-sized
+//! storage.
+
+use crate::effects::Effect;
+use crate::error::GooeyError;
+use crate::frame::StereoFrame;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Index of a node in an [`AudioGraph`].
+pub type NodeId = usize;
+
+enum NodeKind {
+    /// Fed each sample with the named instrument's panned output.
+    Source(String),
+    Effect(Box<dyn Effect>),
+    /// Sums its inputs; used for submixes and send returns.
+    Bus,
+    /// The graph's single output, summed into the engine's master bus.
+    Output,
+}
+
+struct Node {
+    kind: NodeKind,
+    /// Gain applied to the node's output.
+    gain: f32,
+    input: StereoFrame,
+    output: StereoFrame,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Connection {
+    from: NodeId,
+    to: NodeId,
+    gain: f32,
+}
+
+/// A routing graph of instrument sources, effects and buses.
+pub struct AudioGraph {
+    nodes: Vec<Node>,
+    connections: Vec<Connection>,
+    feedback: Vec<Connection>,
+    /// Forward connections grouped by source node, for the render loop.
+    outgoing: Vec<Vec<Connection>>,
+    /// Processing order (topological over forward connections).
+    order: Vec<NodeId>,
+    output: NodeId,
+}
+
+impl AudioGraph {
+    /// Create a graph containing only its output node.
+    pub fn new() -> Self {
+        let mut graph = Self {
+            nodes: Vec::new(),
+            connections: Vec::new(),
+            feedback: Vec::new(),
+            outgoing: Vec::new(),
+            order: Vec::new(),
+            output: 0,
+        };
+        graph.output = graph.add_node(NodeKind::Output);
+        graph
+    }
+
+    /// The engine's fixed routing as a graph: every named instrument feeds the
+    /// output directly at unity gain.
+    pub fn direct<S: AsRef<str>>(instruments: &[S]) -> Self {
+        let mut graph = Self::new();
+        for name in instruments {
+            let source = graph.add_source(name.as_ref());
+            graph
+                .connect(source, graph.output(), 1.0)
+                .expect("source -> output cannot form a cycle");
+        }
+        graph
+    }
+
+    /// The output node.
+    pub fn output(&self) -> NodeId {
+        self.output
+    }
+
+    /// Add a source node fed by the instrument registered under `instrument`.
+    /// Several sources may name the same instrument.
+    pub fn add_source(&mut self, instrument: &str) -> NodeId {
+        self.add_node(NodeKind::Source(instrument.to_string()))
+    }
+
+    /// Add an effect node.
+    pub fn add_effect(&mut self, effect: Box<dyn Effect>) -> NodeId {
+        self.add_node(NodeKind::Effect(effect))
+    }
+
+    /// Add a bus node with the given output gain.
+    pub fn add_bus(&mut self, gain: f32) -> NodeId {
+        let bus = self.add_node(NodeKind::Bus);
+        self.nodes[bus].gain = gain;
+        bus
+    }
+
+    fn add_node(&mut self, kind: NodeKind) -> NodeId {
+        self.nodes.push(Node {
+            kind,
+            gain: 1.0,
+            input: StereoFrame::default(),
+            output: StereoFrame::default(),
+        });
+        self.outgoing.push(Vec::new());
+        self.order.push(self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Set a node's output gain.
+    pub fn set_gain(&mut self, node: NodeId, gain: f32) -> Result<(), GooeyError> {
+        self.check_node(node)?;
+        self.nodes[node].gain = gain;
+        Ok(())
+    }
+
+    /// Connect `from`'s output into `to`'s input, scaled by `gain`.
+    ///
+    /// Fails for unknown nodes, edges out of the output node, edges into a
+    /// source, or an edge that would close a cycle (use
+    /// [`connect_feedback`](Self::connect_feedback) for those).
+    pub fn connect(&mut self, from: NodeId, to: NodeId, gain: f32) -> Result<(), GooeyError> {
+        self.check_edge(from, to)?;
+        if self.reaches(to, from) {
+            return Err(GooeyError::InvalidRouting(format!(
+                "Connecting node {} -> {} would create a cycle; use connect_feedback",
+                from, to
+            )));
+        }
+        self.connections.push(Connection { from, to, gain });
+        self.rebuild();
+        Ok(())
+    }
+
+    /// Connect `from` into `to` with a one-sample delay, allowing cycles.
+    pub fn connect_feedback(
+        &mut self,
+        from: NodeId,
+        to: NodeId,
+        gain: f32,
+    ) -> Result<(), GooeyError> {
+        self.check_edge(from, to)?;
+        self.feedback.push(Connection { from, to, gain });
+        Ok(())
+    }
+
+    /// Connect `nodes` in series at unity gain.
+    pub fn chain(&mut self, nodes: &[NodeId]) -> Result<(), GooeyError> {
+        for pair in nodes.windows(2) {
+            self.connect(pair[0], pair[1], 1.0)?;
+        }
+        Ok(())
+    }
+
+    /// Remove every connection (forward and feedback) between `from` and `to`.
+    /// Returns whether any was removed.
+    pub fn disconnect(&mut self, from: NodeId, to: NodeId) -> bool {
+        let before = self.connections.len() + self.feedback.len();
+        self.connections.retain(|c| (c.from, c.to) != (from, to));
+        self.feedback.retain(|c| (c.from, c.to) != (from, to));
+        self.rebuild();
+        before != self.connections.len() + self.feedback.len()
+    }
+
+    fn check_node(&self, node: NodeId) -> Result<(), GooeyError> {
+        if node < self.nodes.len() {
+            Ok(())
+        } else {
+            Err(GooeyError::IndexOutOfRange {
+                what: "graph node",
+                index: node,
+                count: self.nodes.len(),
+            })
+        }
+    }
+
+    fn check_edge(&self, from: NodeId, to: NodeId) -> Result<(), GooeyError> {
+        self.check_node(from)?;
+        self.check_node(to)?;
+        if from == self.output {
+            return Err(GooeyError::InvalidRouting(
+                "The output node cannot feed other nodes".to_string(),
+            ));
+        }
+        if matches!(self.nodes[to].kind, NodeKind::Source(_)) {
+            return Err(GooeyError::InvalidRouting(format!(
+                "Node {} is a source and takes no inputs",
+                to
+            )));
+        }
+        Ok(())
+    }
+
+    /// Whether `target` is reachable from `start` over forward connections.
+    fn reaches(&self, start: NodeId, target: NodeId) -> bool {
+        let mut seen = vec![false; self.nodes.len()];
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            if node == target {
+                return true;
+            }
+            if !core::mem::replace(&mut seen[node], true) {
+                stack.extend(self.outgoing[node].iter().map(|c| c.to));
+            }
+        }
+        false
+    }
+
+    /// Rebuild the per-node edge lists and the topological processing order.
+    fn rebuild(&mut self) {
+        for edges in &mut self.outgoing {
+            edges.clear();
+        }
+        let mut in_degree = vec![0usize; self.nodes.len()];
+        for c in &self.connections {
+            self.outgoing[c.from].push(*c);
+            in_degree[c.to] += 1;
+        }
+        // Kahn's algorithm; `connect` keeps the forward edges acyclic.
+        self.order.clear();
+        let mut ready: Vec<NodeId> = (0..self.nodes.len())
+            .filter(|&n| in_degree[n] == 0)
+            .collect();
+        while let Some(node) = ready.pop() {
+            self.order.push(node);
+            for c in &self.outgoing[node] {
+                in_degree[c.to] -= 1;
+                if in_degree[c.to] == 0 {
+                    ready.push(c.to);
+                }
+            }
+        }
+    }
+
+    /// Clear node inputs and apply last sample's feedback. Call once per sample
+    /// before [`feed`](Self::feed).
+    pub fn begin_frame(&mut self) {
+        for node in &mut self.nodes {
+            node.input = StereoFrame::default();
+        }
+        for c in &self.feedback {
+            let delayed = self.nodes[c.from].output.scaled(c.gain);
+            self.nodes[c.to].input += delayed;
+        }
+    }
+
+    /// Feed an instrument's frame into every source node that names it.
+    pub fn feed(&mut self, instrument: &str, frame: StereoFrame) {
+        for node in &mut self.nodes {
+            if matches!(&node.kind, NodeKind::Source(name) if name == instrument) {
+                node.input += frame;
+            }
+        }
+    }
+
+    /// Run every node in order and return the output node's frame.
+    pub fn process(&mut self) -> StereoFrame {
+        for i in 0..self.order.len() {
+            let id = self.order[i];
+            let node = &mut self.nodes[id];
+            let out = match &node.kind {
+                NodeKind::Effect(effect) => effect.process_stereo(node.input),
+                NodeKind::Source(_) | NodeKind::Bus | NodeKind::Output => node.input,
+            };
+            node.output = out.scaled(node.gain);
+            let out = node.output;
+            for c in &self.outgoing[id] {
+                self.nodes[c.to].input += out.scaled(c.gain);
+            }
+        }
+        self.nodes[self.output].output
+    }
+}
+
+impl Default for AudioGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effects::BrickWallLimiter;
+
+    fn run(graph: &mut AudioGraph, name: &str, frame: StereoFrame) -> StereoFrame {
+        graph.begin_frame();
+        graph.feed(name, frame);
+        graph.process()
+    }
+
+    #[test]
+    fn direct_graph_passes_sources_through() {
+        let mut graph = AudioGraph::direct(&["kick", "snare"]);
+        graph.begin_frame();
+        graph.feed("kick", StereoFrame::mono(0.25));
+        graph.feed("snare", StereoFrame { l: 0.5, r: 0.0 });
+        let out = graph.process();
+        assert_eq!(out, StereoFrame { l: 0.75, r: 0.25 });
+    }
+
+    #[test]
+    fn send_and_bus_gains_sum_at_the_output() {
+        let mut graph = AudioGraph::new();
+        let kick = graph.add_source("kick");
+        let send = graph.add_bus(0.5);
+        graph.connect(kick, graph.output(), 1.0).unwrap();
+        graph.connect(kick, send, 0.5).unwrap();
+        graph.connect(send, graph.output(), 1.0).unwrap();
+
+        let out = run(&mut graph, "kick", StereoFrame::mono(1.0));
+        assert!((out.l - 1.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn forward_cycles_are_rejected() {
+        let mut graph = AudioGraph::new();
+        let a = graph.add_bus(1.0);
+        let b = graph.add_effect(Box::new(BrickWallLimiter::new(1.0)));
+        graph.chain(&[a, b]).unwrap();
+        assert!(graph.connect(b, a, 0.5).is_err());
+        assert!(graph.connect(a, a, 0.5).is_err());
+        assert!(graph.connect(graph.output(), a, 1.0).is_err());
+        let source = graph.add_source("kick");
+        assert!(graph.connect(a, source, 1.0).is_err());
+    }
+
+    #[test]
+    fn feedback_connections_lag_by_one_sample() {
+        let mut graph = AudioGraph::new();
+        let kick = graph.add_source("kick");
+        let bus = graph.add_bus(1.0);
+        graph.chain(&[kick, bus, graph.output()]).unwrap();
+        graph.connect_feedback(bus, bus, 0.5).unwrap();
+
+        let first = run(&mut graph, "kick", StereoFrame::mono(1.0));
+        let second = run(&mut graph, "kick", StereoFrame::default());
+        let third = run(&mut graph, "kick", StereoFrame::default());
+        assert_eq!((first.l, second.l, third.l), (1.0, 0.5, 0.25));
+    }
+}
@@ -0,0 +1,222 @@
+//! Groove templates and the groove pool
+//!
+//! A groove is the feel of a played part: how early or late each sixteenth
+//! lands and how hard it is hit relative to the rest. [`GrooveTemplate`]
+//! extracts that from a run of timed hits (live triggers, or notes of an
+//! imported MIDI clip) as per-step timing and velocity offsets over one bar,
+//! and a [`Sequencer`](super::Sequencer) given a template plays its own
+//! pattern with that feel. [`GroovePool`] keeps extracted templates so one
+//! take can groove any number of patterns.
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Steps in a groove template (one 4/4 bar of sixteenths).
+pub const GROOVE_STEPS: usize = 16;
+
+/// Largest timing offset, in steps either way. Under half a step, so
+/// neighbouring steps can't swap order.
+pub const GROOVE_MAX_OFFSET: f32 = 0.45;
+
+/// Largest velocity scale a groove applies to a step.
+pub const GROOVE_MAX_VELOCITY_SCALE: f32 = 2.0;
+
+/// Templates the pool holds.
+pub const GROOVE_POOL_CAPACITY: usize = 32;
+
+/// Per-step timing and velocity offsets for one bar of sixteenths.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GrooveTemplate {
+    /// Offset from the grid in steps (negative = early), ±`GROOVE_MAX_OFFSET`
+    timing: [f32; GROOVE_STEPS],
+    /// Velocity multiplier, 1.0 = as programmed
+    velocity: [f32; GROOVE_STEPS],
+}
+
+impl Default for GrooveTemplate {
+    fn default() -> Self {
+        Self {
+            timing: [0.0; GROOVE_STEPS],
+            velocity: [1.0; GROOVE_STEPS],
+        }
+    }
+}
+
+impl GrooveTemplate {
+    /// Extract a groove from hits given as `(beat, velocity)`, with beats in
+    /// quarter notes on the transport timeline.
+    ///
+    /// Each hit counts toward the nearest sixteenth, folded into one bar. A
+    /// step's timing is the average distance of its hits from the grid and
+    /// its velocity scale is their average velocity over the average of all
+    /// hits, so the template carries accents but not overall loudness. Steps
+    /// nobody played stay on the grid at scale 1. `None` without any hits.
+    pub fn from_hits(hits: impl IntoIterator<Item = (f64, f32)>) -> Option<Self> {
+        let mut offsets = [0.0_f64; GROOVE_STEPS];
+        let mut velocities = [0.0_f32; GROOVE_STEPS];
+        let mut counts = [0_u32; GROOVE_STEPS];
+        for (beat, velocity) in hits {
+            if !beat.is_finite() || !velocity.is_finite() {
+                continue;
+            }
+            let position = beat * 4.0;
+            let nearest = position.round();
+            let step = (nearest as i64).rem_euclid(GROOVE_STEPS as i64) as usize;
+            offsets[step] += position - nearest;
+            velocities[step] += velocity.clamp(0.0, 1.0);
+            counts[step] += 1;
+        }
+        let total: u32 = counts.iter().sum();
+        if total == 0 {
+            return None;
+        }
+        let mean_velocity = velocities.iter().sum::<f32>() / total as f32;
+
+        let mut template = Self::default();
+        for step in 0..GROOVE_STEPS {
+            let count = counts[step];
+            if count == 0 {
+                continue;
+            }
+            template.timing[step] = (offsets[step] / count as f64) as f32;
+            if mean_velocity > 0.0 {
+                template.velocity[step] = velocities[step] / count as f32 / mean_velocity;
+            }
+        }
+        Some(template.clamped())
+    }
+
+    /// Build a template from explicit per-step values (clamped to range).
+    pub fn from_steps(timing: [f32; GROOVE_STEPS], velocity: [f32; GROOVE_STEPS]) -> Self {
+        Self { timing, velocity }.clamped()
+    }
+
+    fn clamped(mut self) -> Self {
+        for (timing, velocity) in self.timing.iter_mut().zip(&mut self.velocity) {
+            *timing = if timing.is_finite() {
+                timing.clamp(-GROOVE_MAX_OFFSET, GROOVE_MAX_OFFSET)
+            } else {
+                0.0
+            };
+            *velocity = if velocity.is_finite() {
+                velocity.clamp(0.0, GROOVE_MAX_VELOCITY_SCALE)
+            } else {
+                1.0
+            };
+        }
+        self
+    }
+
+    /// The template at partial strength: `timing_amount` scales the timing
+    /// offsets and `velocity_amount` blends the velocity scales from 1
+    /// (both 0.0-1.0).
+    pub fn scaled(&self, timing_amount: f32, velocity_amount: f32) -> Self {
+        let timing_amount = timing_amount.clamp(0.0, 1.0);
+        let velocity_amount = velocity_amount.clamp(0.0, 1.0);
+        Self {
+            timing: self.timing.map(|t| t * timing_amount),
+            velocity: self.velocity.map(|v| 1.0 + (v - 1.0) * velocity_amount),
+        }
+    }
+
+    /// Timing offset of `step` in steps (the pattern wraps every bar).
+    pub fn timing(&self, step: usize) -> f32 {
+        self.timing[step % GROOVE_STEPS]
+    }
+
+    /// Velocity multiplier of `step`.
+    pub fn velocity(&self, step: usize) -> f32 {
+        self.velocity[step % GROOVE_STEPS]
+    }
+}
+
+/// Grooves extracted so far, addressed by the index they were added at.
+#[derive(Default)]
+pub struct GroovePool {
+    templates: Vec<GrooveTemplate>,
+}
+
+impl GroovePool {
+    pub fn new() -> Self {
+        Self {
+            templates: Vec::with_capacity(GROOVE_POOL_CAPACITY),
+        }
+    }
+
+    /// Add a template and return its index, or `None` when the pool is full.
+    pub fn add(&mut self, template: GrooveTemplate) -> Option<usize> {
+        if self.templates.len() >= GROOVE_POOL_CAPACITY {
+            return None;
+        }
+        self.templates.push(template);
+        Some(self.templates.len() - 1)
+    }
+
+    pub fn get(&self, index: usize) -> Option<&GrooveTemplate> {
+        self.templates.get(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.templates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+
+    /// Remove every template. Sequencers keep grooves already applied.
+    pub fn clear(&mut self) {
+        self.templates.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extracts_average_offsets_and_relative_accents() {
+        // Two bars of a laid-back hat: off-beats a tenth of a step late and
+        // softer, downbeats on time and accented
+        let hits = (0..2).flat_map(|bar| {
+            (0..8).map(move |eighth| {
+                let step = eighth * 2 + bar * 16;
+                if eighth % 2 == 0 {
+                    (step as f64 / 4.0, 1.0)
+                } else {
+                    ((step as f64 + 0.1) / 4.0, 0.5)
+                }
+            })
+        });
+        let groove = GrooveTemplate::from_hits(hits).unwrap();
+        assert!(groove.timing(0).abs() < 1e-6);
+        assert!((groove.timing(2) - 0.1).abs() < 1e-6);
+        assert!((groove.velocity(0) - 1.0 / 0.75).abs() < 1e-6);
+        assert!((groove.velocity(2) - 0.5 / 0.75).abs() < 1e-6);
+        // Unplayed steps are neutral, and the template wraps
+        assert_eq!((groove.timing(1), groove.velocity(1)), (0.0, 1.0));
+        assert_eq!(groove.timing(18), groove.timing(2));
+
+        // An early hit belongs to the step it anticipates
+        let early = GrooveTemplate::from_hits([(3.95, 1.0)]).unwrap();
+        assert!((early.timing(0) + 0.2).abs() < 1e-6);
+        assert!(GrooveTemplate::from_hits([]).is_none());
+    }
+
+    #[test]
+    fn test_scaling_and_pool_capacity() {
+        let groove = GrooveTemplate::from_steps([1.0; GROOVE_STEPS], [0.5; GROOVE_STEPS]);
+        assert_eq!(groove.timing(0), GROOVE_MAX_OFFSET);
+        let half = groove.scaled(0.5, 0.5);
+        assert!((half.timing(3) - GROOVE_MAX_OFFSET / 2.0).abs() < 1e-6);
+        assert!((half.velocity(3) - 0.75).abs() < 1e-6);
+
+        let mut pool = GroovePool::new();
+        for i in 0..GROOVE_POOL_CAPACITY {
+            assert_eq!(pool.add(groove), Some(i));
+        }
+        assert_eq!(pool.add(groove), None);
+        pool.clear();
+        assert!(pool.get(0).is_none());
+    }
+}
@@ -1,3 +1,6 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
 /// Musical time divisions for BPM-synced LFO speeds
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MusicalDivision {
@@ -59,6 +62,9 @@ impl MusicalDivision {
     }
 }
 
+/// Octaves a full-scale rate modulation (±1.0) moves an LFO's frequency
+pub const LFO_RATE_MOD_OCTAVES: f32 = 2.0;
+
 /// LFO sync mode
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum LfoSyncMode {
@@ -74,6 +80,10 @@ pub struct Lfo {
     bpm: f32, // Current BPM (used when in BpmSync mode)
     phase: f32,
     sample_rate: f32,
+    one_shot: bool,
+    // Modulation from other LFOs (-1.0 to 1.0 full scale)
+    rate_modulation: f32,
+    amount_modulation: f32,
 
     // Routing
     pub target_instrument: String,
@@ -92,6 +102,9 @@ impl Lfo {
             bpm: 120.0, // Default BPM
             phase: 0.0,
             sample_rate,
+            one_shot: false,
+            rate_modulation: 0.0,
+            amount_modulation: 0.0,
             target_instrument: String::new(),
             target_parameter: String::new(),
             amount: 1.0,
@@ -107,6 +120,9 @@ impl Lfo {
             bpm: 120.0,
             phase: 0.0,
             sample_rate,
+            one_shot: false,
+            rate_modulation: 0.0,
+            amount_modulation: 0.0,
             target_instrument: String::new(),
             target_parameter: String::new(),
             amount: 1.0,
@@ -129,6 +145,9 @@ impl Lfo {
             bpm,
             phase: 0.0,
             sample_rate,
+            one_shot: false,
+            rate_modulation: 0.0,
+            amount_modulation: 0.0,
             target_instrument: String::new(),
             target_parameter: String::new(),
             amount: 1.0,
@@ -146,7 +165,8 @@ impl Lfo {
         self.sync_mode = LfoSyncMode::BpmSync(division);
     }
 
-    /// Update the BPM (used when in BpmSync mode)
+    /// Update the BPM (used when in BpmSync mode). The phase carries on
+    /// from where it is, so a tempo change bends the rate without a jump.
     pub fn set_bpm(&mut self, bpm: f32) {
         self.bpm = bpm;
     }
@@ -164,24 +184,66 @@ impl Lfo {
         self.sync_mode
     }
 
+    /// Run a single cycle per trigger instead of free-running. The LFO only
+    /// records the mode; whoever reads it keeps the per-trigger cycle
+    /// position and passes it to [`Lfo::value_at_phase`].
+    pub fn set_one_shot(&mut self, one_shot: bool) {
+        self.one_shot = one_shot;
+    }
+
+    /// Whether the LFO runs a single cycle per trigger
+    pub fn is_one_shot(&self) -> bool {
+        self.one_shot
+    }
+
+    /// Set the modulation other LFOs apply to this one. `rate` moves the
+    /// frequency by up to `LFO_RATE_MOD_OCTAVES` either way and `amount`
+    /// scales the amplitude by `1 + amount` (so -1.0 silences it and 1.0
+    /// doubles it); both at full scale. 0.0 leaves the LFO as set.
+    pub fn set_modulation(&mut self, rate: f32, amount: f32) {
+        self.rate_modulation = rate;
+        self.amount_modulation = amount;
+    }
+
+    /// Phase advance per sample, in cycles, including rate modulation
+    pub fn phase_increment(&self) -> f32 {
+        let scale = if self.rate_modulation == 0.0 {
+            1.0
+        } else {
+            (self.rate_modulation * LFO_RATE_MOD_OCTAVES).exp2()
+        };
+        self.frequency() * scale / self.sample_rate
+    }
+
     /// Generate one sample and advance the phase
     /// Returns: offset + (sine_value * amount)
     /// With default settings (amount=1.0, offset=0.0), this returns -1.0 to 1.0
     pub fn tick(&mut self) -> f32 {
-        // Calculate sine wave
-        let value = (self.phase * 2.0 * std::f32::consts::PI).sin();
+        let value = self.value_at(0.0);
 
         // Advance phase
-        let phase_increment = self.frequency() / self.sample_rate;
-        self.phase += phase_increment;
+        self.phase += self.phase_increment();
 
         // Wrap phase to 0.0-1.0
         if self.phase >= 1.0 {
             self.phase -= 1.0;
         }
 
-        // Apply offset and amount
-        self.offset + (value * self.amount)
+        value
+    }
+
+    /// The value the next `tick` would return with the phase moved by
+    /// `phase_offset` cycles, without advancing: offset + (sine * amount)
+    pub fn value_at(&self, phase_offset: f32) -> f32 {
+        self.value_at_phase(self.phase + phase_offset)
+    }
+
+    /// The LFO's output at an absolute `phase` in cycles, ignoring its own
+    /// running phase: offset + (sine * amount)
+    pub fn value_at_phase(&self, phase: f32) -> f32 {
+        let value = (phase * 2.0 * core::f32::consts::PI).sin();
+        let amount = self.amount * (1.0 + self.amount_modulation).max(0.0);
+        self.offset + (value * amount)
     }
 
     /// Reset the phase to 0
@@ -0,0 +1,123 @@
+//! Ableton Link tempo and phase sync (requires the `link` feature)
+
+use super::{Engine, ExternalClock};
+use rusty_link::{AblLink, HostTimeFilter, SessionState};
+
+/// Default Link quantum: one 4/4 bar.
+pub const LINK_DEFAULT_QUANTUM: f64 = 4.0;
+
+/// Who owns the tempo when the session and the engine disagree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinkMode {
+    /// The engine follows the session; local tempo changes are overwritten.
+    Follow,
+    /// Local tempo changes (`Engine::set_bpm`) are pushed to the session.
+    Lead,
+}
+
+/// Keeps an [`Engine`]'s tempo and bar phase locked to an Ableton Link
+/// session.
+///
+/// Attach with [`Engine::set_link`]; the audio output then calls
+/// [`LinkSync::process`] at the start of every buffer via
+/// [`Engine::begin_buffer`]. Phase is aligned to the quantum, so with the
+/// default quantum of 4 beats bar one of every peer lands on step 0 of a
+/// 16-step pattern.
+pub struct LinkSync {
+    link: AblLink,
+    state: SessionState,
+    host_time: HostTimeFilter,
+    quantum: f64,
+    mode: LinkMode,
+    output_latency_micros: i64,
+    /// Engine tempo after the last sync, to spot local changes in Lead mode
+    last_bpm: f32,
+}
+
+impl LinkSync {
+    /// Join a Link session (disabled until [`LinkSync::enable`]) starting at
+    /// `bpm`.
+    pub fn new(bpm: f32) -> Self {
+        Self {
+            link: AblLink::new(bpm as f64),
+            state: SessionState::new(),
+            host_time: HostTimeFilter::new(),
+            quantum: LINK_DEFAULT_QUANTUM,
+            mode: LinkMode::Follow,
+            output_latency_micros: 0,
+            last_bpm: bpm,
+        }
+    }
+
+    /// Connect to or leave the network session.
+    pub fn enable(&mut self, enabled: bool) {
+        self.link.enable(enabled);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.link.is_enabled()
+    }
+
+    /// Number of other Link peers in the session.
+    pub fn num_peers(&self) -> u64 {
+        self.link.num_peers()
+    }
+
+    pub fn set_mode(&mut self, mode: LinkMode) {
+        self.mode = mode;
+    }
+
+    pub fn mode(&self) -> LinkMode {
+        self.mode
+    }
+
+    /// Beats per phase cycle (bar length). Clamped to at least one beat.
+    pub fn set_quantum(&mut self, quantum: f64) {
+        self.quantum = quantum.max(1.0);
+    }
+
+    pub fn quantum(&self) -> f64 {
+        self.quantum
+    }
+
+    /// Share start/stop state with peers that also enable it.
+    pub fn set_start_stop_sync(&mut self, enabled: bool) {
+        self.link.enable_start_stop_sync(enabled);
+    }
+
+    /// Output latency (buffer plus device) in microseconds, so what is heard
+    /// lines up with other peers rather than what is rendered.
+    pub fn set_output_latency_micros(&mut self, latency_micros: i64) {
+        self.output_latency_micros = latency_micros.max(0);
+    }
+
+    /// Sync `engine` to the session for a buffer whose first sample is
+    /// `sample_time`. Audio thread only.
+    pub fn process(&mut self, engine: &mut Engine, sample_time: u64) {
+        let host_micros = self
+            .host_time
+            .sample_time_to_host_time(self.link.clock_micros(), sample_time as f64)
+            + self.output_latency_micros;
+
+        self.link.capture_audio_session_state(&mut self.state);
+
+        if self.mode == LinkMode::Lead && engine.bpm() != self.last_bpm {
+            self.state.set_tempo(engine.bpm() as f64, host_micros);
+            self.link.commit_audio_session_state(&self.state);
+        }
+
+        let playing = self
+            .link
+            .is_start_stop_sync_enabled()
+            .then(|| self.state.is_playing());
+        let beat = self.state.beat_at_time(host_micros, self.quantum);
+        // Link counts in beats from the session origin; negative while
+        // counting in, which sync_to_beat wraps into the pattern
+        engine.sync_to_clock(ExternalClock {
+            bpm: self.state.tempo() as f32,
+            beat,
+            playing,
+        });
+        self.last_bpm = engine.bpm();
+    }
+}
@@ -0,0 +1,226 @@
+//! MIDI clock generation from the transport
+//!
+//! [`MidiClock`] runs on the audio thread alongside the sequencers and stamps
+//! every message with the sample it belongs to, so clock timing is exact
+//! relative to the audio no matter how the host buffers it. Sending the
+//! messages to a port is left to a consumer of [`Engine::enable_midi_clock`]
+//! (on native builds, `MidiClockOutput` with the `midi` feature).
+//!
+//! [`Engine::enable_midi_clock`]: super::Engine::enable_midi_clock
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// MIDI clock resolution: pulses per quarter note.
+pub const MIDI_CLOCK_PPQN: u32 = 24;
+
+/// Clock pulses per song-position unit (a 16th note, one sequencer step).
+pub const MIDI_CLOCKS_PER_STEP: u64 = (MIDI_CLOCK_PPQN / 4) as u64;
+
+/// Messages buffered between the audio thread and the MIDI sender. At 24 ppqn
+/// this is several seconds of clock even at 300 BPM.
+pub const MIDI_CLOCK_QUEUE_CAPACITY: usize = 1024;
+
+/// Largest song position pointer value (14 bits).
+const MAX_SONG_POSITION: u64 = 0x3FFF;
+
+/// A MIDI real-time or system-common message driven by the transport.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MidiClockMessage {
+    /// Timing clock (0xF8), 24 per quarter note while running.
+    Clock,
+    /// Start from song position zero (0xFA).
+    Start,
+    /// Resume from the last song position pointer (0xFB).
+    Continue,
+    /// Stop (0xFC).
+    Stop,
+    /// Song position pointer (0xF2) in 16th notes since the song start.
+    SongPosition(u16),
+}
+
+impl MidiClockMessage {
+    /// Raw MIDI bytes for the message.
+    pub fn bytes(&self) -> ([u8; 3], usize) {
+        match *self {
+            MidiClockMessage::Clock => ([0xF8, 0, 0], 1),
+            MidiClockMessage::Start => ([0xFA, 0, 0], 1),
+            MidiClockMessage::Continue => ([0xFB, 0, 0], 1),
+            MidiClockMessage::Stop => ([0xFC, 0, 0], 1),
+            MidiClockMessage::SongPosition(position) => {
+                let position = position.min(MAX_SONG_POSITION as u16);
+                ([0xF2, (position & 0x7F) as u8, (position >> 7) as u8], 3)
+            }
+        }
+    }
+}
+
+/// A clock message and the sample it falls on, counted from when the clock
+/// was enabled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MidiClockEvent {
+    pub message: MidiClockMessage,
+    pub sample_time: u64,
+}
+
+/// Sample-accurate MIDI clock generator following the engine transport.
+///
+/// Call [`MidiClock::tick`] once per sample with whether the transport is
+/// running. A stopped-to-running transition sends Start when at the song
+/// start, otherwise a song position pointer followed by Continue; the first
+/// Clock goes out on the same sample, marking the downbeat. Running-to-stopped
+/// sends Stop and holds the song position.
+pub struct MidiClock {
+    sample_rate: f32,
+    bpm: f32,
+    running: bool,
+    /// Clocks sent since the song start
+    song_clocks: u64,
+    /// Samples (fractional) until the next Clock is due
+    until_next: f64,
+    /// Samples ticked since the clock was created
+    sample_time: u64,
+}
+
+impl MidiClock {
+    pub fn new(sample_rate: f32, bpm: f32) -> Self {
+        Self {
+            sample_rate,
+            bpm: bpm.max(1.0),
+            running: false,
+            song_clocks: 0,
+            until_next: 0.0,
+            sample_time: 0,
+        }
+    }
+
+    fn samples_per_clock(&self) -> f64 {
+        self.sample_rate as f64 * 60.0 / (self.bpm as f64 * MIDI_CLOCK_PPQN as f64)
+    }
+
+    /// Change tempo. The pulse in flight is rescaled so the clock bends
+    /// smoothly rather than restarting its period.
+    pub fn set_bpm(&mut self, bpm: f32) {
+        let bpm = bpm.max(1.0);
+        self.until_next *= self.bpm as f64 / bpm as f64;
+        self.bpm = bpm;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Clocks sent since the song start.
+    pub fn song_clocks(&self) -> u64 {
+        self.song_clocks
+    }
+
+    /// Move the song position to `beat` (quarter notes) while stopped; takes
+    /// effect at the next start. Positions snap down to a 16th note, the
+    /// resolution of the song position pointer.
+    pub fn locate(&mut self, beat: f64) {
+        let steps = (beat.max(0.0) * 4.0).floor() as u64;
+        self.song_clocks = steps.min(MAX_SONG_POSITION) * MIDI_CLOCKS_PER_STEP;
+    }
+
+    /// Advance one sample, passing each message due on it to `emit`.
+    pub fn tick(&mut self, transport_running: bool, mut emit: impl FnMut(MidiClockEvent)) {
+        let sample_time = self.sample_time;
+        let mut send = |message| {
+            emit(MidiClockEvent {
+                message,
+                sample_time,
+            })
+        };
+
+        if transport_running != self.running {
+            self.running = transport_running;
+            if transport_running {
+                // Followers resume on a 16th boundary, so align to one
+                let steps = self.song_clocks / MIDI_CLOCKS_PER_STEP;
+                self.song_clocks = steps * MIDI_CLOCKS_PER_STEP;
+                if steps == 0 {
+                    send(MidiClockMessage::Start);
+                } else {
+                    send(MidiClockMessage::SongPosition(steps as u16));
+                    send(MidiClockMessage::Continue);
+                }
+                self.until_next = 0.0;
+            } else {
+                send(MidiClockMessage::Stop);
+            }
+        }
+
+        if self.running {
+            if self.until_next <= 0.0 {
+                self.until_next += self.samples_per_clock();
+                self.song_clocks += 1;
+                send(MidiClockMessage::Clock);
+            }
+            self.until_next -= 1.0;
+        }
+
+        self.sample_time += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(clock: &mut MidiClock, running: bool, samples: usize) -> Vec<MidiClockEvent> {
+        let mut events = Vec::new();
+        for _ in 0..samples {
+            clock.tick(running, |event| events.push(event));
+        }
+        events
+    }
+
+    #[test]
+    fn test_24_clocks_per_beat_on_the_grid() {
+        // 100 BPM at 48 kHz: 28800 samples per beat, 1200 per clock
+        let mut clock = MidiClock::new(48_000.0, 100.0);
+        let events = run(&mut clock, true, 28_800);
+        assert_eq!(events[0].message, MidiClockMessage::Start);
+        let clocks: Vec<u64> = events
+            .iter()
+            .filter(|e| e.message == MidiClockMessage::Clock)
+            .map(|e| e.sample_time)
+            .collect();
+        assert_eq!(clocks.len(), MIDI_CLOCK_PPQN as usize);
+        assert!(clocks
+            .iter()
+            .enumerate()
+            .all(|(i, &t)| t == i as u64 * 1200));
+    }
+
+    #[test]
+    fn test_stop_then_resume_sends_song_position_and_continue() {
+        let mut clock = MidiClock::new(48_000.0, 120.0);
+        // One beat and a bit: 25 clocks sent at 1000 samples per clock
+        run(&mut clock, true, 24_500);
+        let stop = run(&mut clock, false, 10);
+        assert_eq!(stop[0].message, MidiClockMessage::Stop);
+        assert_eq!(clock.song_clocks(), 25);
+
+        let resume = run(&mut clock, true, 1);
+        let messages: Vec<_> = resume.iter().map(|e| e.message).collect();
+        assert_eq!(
+            messages,
+            vec![
+                MidiClockMessage::SongPosition(4),
+                MidiClockMessage::Continue,
+                MidiClockMessage::Clock
+            ]
+        );
+    }
+
+    #[test]
+    fn test_song_position_bytes() {
+        assert_eq!(
+            MidiClockMessage::SongPosition(300).bytes(),
+            ([0xF2, 0x2C, 0x02], 3)
+        );
+        assert_eq!(MidiClockMessage::Clock.bytes(), ([0xF8, 0, 0], 1));
+    }
+}
@@ -0,0 +1,139 @@
+//! Sends engine MIDI clock to a hardware port (native + `midi` features)
+
+use super::MidiClockEvent;
+use midir::{MidiOutput, MidiOutputConnection};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// How long before a deadline the sender stops sleeping and spins. OS sleeps
+/// routinely overshoot by around a millisecond.
+const SPIN_WINDOW: Duration = Duration::from_millis(2);
+
+/// How often an idle sender checks whether it should shut down.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Forwards [`MidiClockEvent`]s from [`super::Engine::enable_midi_clock`] to a
+/// MIDI output port on a dedicated thread.
+///
+/// The audio thread renders a whole buffer at a time, so messages arrive in
+/// bursts. Each one is instead sent when its sample is due: sample times are
+/// mapped to wall-clock deadlines against a fixed anchor plus `latency`, and
+/// the thread sleeps then spins up to each deadline. Clock spacing follows
+/// the audio sample clock rather than the buffer callback timing.
+///
+/// `latency` should cover at least one output buffer. A message that still
+/// arrives after its deadline (an audio dropout) moves the anchor later, so
+/// one glitch costs a single late clock instead of a burst.
+pub struct MidiClockOutput {
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MidiClockOutput {
+    /// Connect to the first output port whose name contains `port_name`
+    /// (or the first port when `None`) and start forwarding `events`.
+    pub fn connect(
+        port_name: Option<&str>,
+        events: Receiver<MidiClockEvent>,
+        sample_rate: f32,
+        latency: Duration,
+    ) -> Result<Self, anyhow::Error> {
+        let midi_out = MidiOutput::new("libgooey-clock")?;
+        let ports = midi_out.ports();
+        let port = ports
+            .iter()
+            .find(|port| match port_name {
+                Some(name) => midi_out
+                    .port_name(port)
+                    .is_ok_and(|candidate| candidate.contains(name)),
+                None => true,
+            })
+            .ok_or_else(|| anyhow::anyhow!("No matching MIDI output port found"))?
+            .clone();
+        let connection = midi_out
+            .connect(&port, "gooey-clock")
+            .map_err(|e| anyhow::anyhow!("Failed to open MIDI output: {}", e))?;
+
+        let running = Arc::new(AtomicBool::new(true));
+        let thread_running = Arc::clone(&running);
+        let thread = std::thread::Builder::new()
+            .name("gooey-midi-clock".into())
+            .spawn(move || send_loop(connection, events, sample_rate, latency, thread_running))?;
+
+        Ok(Self {
+            running,
+            thread: Some(thread),
+        })
+    }
+
+    /// Names of the available MIDI output ports.
+    pub fn port_names() -> Vec<String> {
+        MidiOutput::new("libgooey-clock-list")
+            .map(|m| {
+                m.ports()
+                    .iter()
+                    .filter_map(|p| m.port_name(p).ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for MidiClockOutput {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn send_loop(
+    mut connection: MidiOutputConnection,
+    events: Receiver<MidiClockEvent>,
+    sample_rate: f32,
+    latency: Duration,
+    running: Arc<AtomicBool>,
+) {
+    // Wall-clock instant that sample time `anchor.1` is heard
+    let mut anchor: Option<(Instant, u64)> = None;
+
+    while running.load(Ordering::Relaxed) {
+        let event = match events.recv_timeout(POLL_INTERVAL) {
+            Ok(event) => event,
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+
+        let now = Instant::now();
+        let (anchor_instant, anchor_sample) =
+            *anchor.get_or_insert((now + latency, event.sample_time));
+        let offset = event.sample_time.saturating_sub(anchor_sample) as f64 / sample_rate as f64;
+        let mut deadline = anchor_instant + Duration::from_secs_f64(offset);
+        if deadline < now {
+            // Arrived late: re-anchor so later messages keep their spacing
+            deadline = now + latency;
+            anchor = Some((deadline, event.sample_time));
+        }
+        wait_until(deadline);
+
+        let (bytes, len) = event.message.bytes();
+        if let Err(e) = connection.send(&bytes[..len]) {
+            eprintln!("Warning: MIDI clock send failed: {}", e);
+        }
+    }
+}
+
+/// Sleep until shortly before `deadline`, then spin the rest of the way.
+fn wait_until(deadline: Instant) {
+    let now = Instant::now();
+    if deadline > now + SPIN_WINDOW {
+        std::thread::sleep(deadline - now - SPIN_WINDOW);
+    }
+    while Instant::now() < deadline {
+        std::hint::spin_loop();
+    }
+}
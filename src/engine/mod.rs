@@ -1,8 +1,23 @@
-use crate::effects::{Effect, SoftLimiter};
+#[cfg(feature = "std")]
+use crate::effects::{DuckTrigger, Effect, SoftLimiter};
+use crate::error::GooeyError;
+#[cfg(feature = "std")]
 use crate::frame::StereoFrame;
+#[cfg(feature = "std")]
 use crate::mixer::Mixer;
+#[cfg(feature = "std")]
+use crate::music::{MasterTuning, NoteName, Scale};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+#[cfg(feature = "std")]
+use crate::recorder::Recorder;
+use crate::utils::SampleClock;
+#[cfg(feature = "std")]
 use crate::utils::SmoothedParam;
+#[cfg(feature = "std")]
 use std::collections::{HashMap, VecDeque};
+#[cfg(feature = "std")]
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
 
 #[cfg(feature = "native")]
 pub mod engine_output;
@@ -12,15 +27,50 @@ pub use engine_output::EngineOutput;
 
 pub mod sequencer;
 pub use sequencer::{
-    Sequencer, SequencerBlendSetting, SequencerStep, SequencerStepSettings, SequencerTrigger,
+    PatternClip, Sequencer, SequencerBlendSetting, SequencerStep, SequencerStepSettings,
+    SequencerTrigger, StepLayer, STEP_GATE_MAX_STEPS, STEP_GATE_MIN_STEPS, STEP_MAX_LAYERS,
+    STEP_TUNE_MAX_SEMITONES,
 };
 
+pub mod effect_lane;
+pub use effect_lane::{EffectLane, EFFECT_LANE_STEPS};
+
+pub mod groove;
+pub use groove::{GroovePool, GrooveTemplate, GROOVE_POOL_CAPACITY, GROOVE_STEPS};
+
 pub mod lfo;
-pub use lfo::{Lfo, LfoSyncMode, MusicalDivision};
+pub use lfo::{Lfo, LfoSyncMode, MusicalDivision, LFO_RATE_MOD_OCTAVES};
+
+pub mod mod_envelope;
+pub use mod_envelope::ModEnvelope;
+
+pub mod step_note;
+pub use step_note::{NoteChange, StepNote};
+
+pub mod graph;
+pub use graph::{AudioGraph, NodeId};
+
+pub mod registry;
+pub use registry::InstrumentRegistry;
+
+pub mod midi_clock;
+pub use midi_clock::{
+    MidiClock, MidiClockEvent, MidiClockMessage, MIDI_CLOCK_PPQN, MIDI_CLOCK_QUEUE_CAPACITY,
+};
+
+#[cfg(all(feature = "native", feature = "midi"))]
+pub mod midi_clock_output;
+#[cfg(all(feature = "native", feature = "midi"))]
+pub use midi_clock_output::MidiClockOutput;
 
-// Export WaveformDisplay when both native and visualization features are enabled
+#[cfg(feature = "link")]
+pub mod link;
+#[cfg(feature = "link")]
+pub use link::{LinkMode, LinkSync};
+
+// Export the debug windows when both native and visualization features are enabled
 #[cfg(all(feature = "native", feature = "visualization"))]
-pub use crate::visualization::WaveformDisplay;
+pub use crate::visualization::{SequencerDisplay, WaveformDisplay};
 
 /// Trait that all instruments must implement
 /// Send is required because instruments are used in the audio thread
@@ -37,9 +87,38 @@ pub trait Instrument: Send {
         self.trigger_with_velocity(time, 1.0);
     }
 
+    /// Trigger with an instrument-defined articulation (for example the
+    /// hi-hat's closed/pedal/open). Called by the sequencer when a step has
+    /// a per-step articulation set.
+    /// Default implementation ignores the articulation.
+    fn trigger_articulated(&mut self, time: f64, velocity: f32, _articulation: u8) {
+        self.trigger_with_velocity(time, velocity);
+    }
+
     /// Generate one sample of audio at the current time
     fn tick(&mut self, current_time: f64) -> f32;
 
+    /// Note-off: instruments with a release stage enter it from wherever
+    /// they are, so a held or gated note ends early.
+    /// Default implementation does nothing (one-shot instruments run out
+    /// their own decay).
+    fn release(&mut self, _time: f64) {}
+
+    /// Trigger at a position on a [`SampleClock`]. Hosts that count samples
+    /// should prefer this over passing seconds they've summed themselves:
+    /// the default derives the seconds from the count and forwards to
+    /// [`Instrument::trigger_with_velocity`], so existing instruments work
+    /// unchanged and long sessions don't drift.
+    fn trigger_at(&mut self, clock: SampleClock, velocity: f32) {
+        self.trigger_with_velocity(clock.seconds(), velocity);
+    }
+
+    /// Generate one sample at a position on a [`SampleClock`]. The default
+    /// forwards the derived seconds to [`Instrument::tick`].
+    fn tick_at(&mut self, clock: SampleClock) -> f32 {
+        self.tick(clock.seconds())
+    }
+
     /// Check if the instrument is currently active
     fn is_active(&self) -> bool;
 
@@ -59,6 +138,18 @@ pub trait Instrument: Send {
         None
     }
 
+    /// Restart the instrument's noise sources from `seed`, so renders are
+    /// reproducible. Engines draw the seed from the channel's
+    /// [`crate::utils::RngStream::Noise`] stream.
+    /// Default implementation does nothing (instrument has no randomness).
+    fn reseed(&mut self, _seed: u64) {}
+
+    /// Set the kit-wide pitch multiplier from the engine's
+    /// [`MasterTuning`](crate::music::MasterTuning), applied on top of the
+    /// instrument's own tuning. 1.0 is A4 = 440 Hz with no transpose.
+    /// Default implementation does nothing (instrument is not pitched).
+    fn set_pitch_ratio(&mut self, _ratio: f32) {}
+
     /// Try to cast to Modulatable trait object
     /// Override this if the instrument supports modulation
     fn as_modulatable(&mut self) -> Option<&mut dyn Modulatable> {
@@ -73,38 +164,166 @@ pub trait Modulatable {
 
     /// Apply a modulation value to a parameter
     /// value is typically -1.0 to 1.0
-    /// Returns Ok(()) if parameter exists and was applied, Err otherwise
-    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), String>;
+    /// Returns Ok(()) if parameter exists and was applied,
+    /// [`GooeyError::UnknownParameter`] otherwise
+    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), GooeyError>;
 
     /// Get the range for a parameter (min, max)
     fn parameter_range(&self, parameter: &str) -> Option<(f32, f32)>;
 }
 
+/// Small integer handle for an instrument name, assigned by
+/// [`Engine::add_instrument`] (or the first call that names the instrument)
+/// and stable for the engine's lifetime. Queued events and sequencer
+/// targets use it so the audio side never hashes or copies names.
+pub type InstrumentId = usize;
+
+/// Maximum number of [`AudioEvent`]s queued between two ticks; further sends
+/// fail until the audio side drains the queue.
+#[cfg(feature = "std")]
+pub const AUDIO_EVENT_CAPACITY: usize = 256;
+
+/// Most stereo output pairs [`Engine::tick_multi`] fills: the main mix plus
+/// 15 direct outs, 32 device channels in all.
+#[cfg(feature = "std")]
+pub const MAX_OUTPUT_PAIRS: usize = 16;
+
+/// A control event queued for the audio side and applied in order at the
+/// start of the next tick, so events sent together all land on the same sample.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq)]
+pub enum AudioEvent {
+    /// Trigger every instrument at the given velocity.
+    TriggerAll { velocity: f32 },
+    /// Trigger one instrument by id (see [`Engine::instrument_id`]).
+    TriggerInstrument { id: InstrumentId, velocity: f32 },
+    /// Release (note-off) one instrument by id.
+    ReleaseInstrument { id: InstrumentId },
+    /// Set the master gain target (smoothed).
+    SetMasterGain(f32),
+    /// Set an instrument's pan target (0.0 = left, 0.5 = center, 1.0 = right).
+    SetInstrumentPan { id: InstrumentId, pan: f32 },
+    /// Set a modulatable instrument parameter, `value` in the range its
+    /// [`Modulatable::parameter_range`] reports. Queued by
+    /// [`Engine::set_instrument_param`], which resolves the name.
+    SetParam {
+        id: InstrumentId,
+        param: &'static str,
+        value: f32,
+    },
+    /// Start all sequencers and the loop transport.
+    TransportStart,
+    /// Stop all sequencers and the loop transport.
+    TransportStop,
+}
+
+/// A reading of an external tempo/phase source (e.g. an Ableton Link
+/// session) for the sample about to be rendered. See [`Engine::sync_to_clock`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExternalClock {
+    /// Tempo in beats per minute.
+    pub bpm: f32,
+    /// Position in quarter-note beats, aligned so a bar starts on a multiple
+    /// of the pattern length in beats.
+    pub beat: f64,
+    /// Whether the source is playing; `None` when it doesn't share
+    /// start/stop state, leaving the local transport alone.
+    pub playing: Option<bool>,
+}
+
+/// An effect lane and the global effect parameter it drives.
+#[cfg(feature = "std")]
+struct EffectLaneBinding {
+    lane: EffectLane,
+    effect_index: usize,
+    param: u32,
+    /// Value last written, so unchanged steps don't touch the effect
+    last: Option<f32>,
+}
+
+/// An interned instrument name and the state the engine keeps for it.
+/// Slots are never removed, so an [`InstrumentId`] stays valid.
+#[cfg(feature = "std")]
+struct InstrumentSlot {
+    name: String,
+    // None while the name is only referenced (panned, muted, ducked, ...)
+    // and no instrument has been added under it
+    instrument: Option<Box<dyn Instrument>>,
+    // Stereo pan (0.0 = left, 0.5 = center, 1.0 = right), smoothed for
+    // click-free moves; None = center. Only applied on the stereo path
+    // (`tick_stereo`).
+    pan: Option<SmoothedParam>,
+    // Direct out: the output pair the instrument plays on instead of the
+    // main mix (0 = main mix, 1 = device channels 3/4, ...)
+    output: usize,
+    // Sequencer hits are skipped while muted, or while others are soloed
+    muted: bool,
+    soloed: bool,
+    // Global frequency saved while per-step notes override it
+    step_note: StepNote,
+}
+
+#[cfg(feature = "std")]
+impl InstrumentSlot {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            instrument: None,
+            pan: None,
+            output: 0,
+            muted: false,
+            soloed: false,
+            step_note: StepNote::default(),
+        }
+    }
+}
+
 /// Minimal audio engine - the primary abstraction for audio generation
+#[cfg(feature = "std")]
 pub struct Engine {
     sample_rate: f32,
     bpm: f32, // Global BPM for synced LFOs and sequencers
-    instruments: HashMap<String, Box<dyn Instrument>>,
-    // Per-instrument stereo pan (0.0 = left, 0.5 = center, 1.0 = right),
-    // smoothed for click-free moves. Absent entries default to center. Only
-    // applied on the stereo path (`tick_stereo`).
-    instrument_pans: HashMap<String, SmoothedParam>,
-    // Queue of (instrument_name, velocity) to trigger on next tick
-    trigger_queue: VecDeque<(String, f32)>,
+    // Instruments and their per-instrument state, indexed by InstrumentId
+    instruments: Vec<InstrumentSlot>,
+    // Name -> id, used only where the API takes names
+    instrument_ids: HashMap<String, InstrumentId>,
+    // Bumped whenever a name is interned, so sequencers re-resolve targets
+    instrument_generation: u32,
+    // Control events applied at the start of the next tick
+    event_queue: VecDeque<AudioEvent>,
     // Active sequencers
     sequencers: Vec<Sequencer>,
     // LFOs for modulation
     lfos: Vec<Lfo>,
+    // Envelopes for modulation, restarted when their target triggers
+    mod_envelopes: Vec<ModEnvelope>,
     // Global effects applied to the final output (distinct from per-instrument effects)
     global_effects: Vec<Box<dyn Effect>>,
+    // Step sequences of global effect parameter values
+    effect_lanes: Vec<EffectLaneBinding>,
     // Master gain applied to the summed output before effects
     master_gain: SmoothedParam,
-    // Saved global frequency per instrument for restoring after per-step note overrides
-    saved_global_freq: HashMap<String, f32>,
     // Multi-channel stereo loop mixer summed into the master bus before global effects
     mixer: Mixer,
+    // Captures the final (post-effects) output while armed/recording
+    recorder: Recorder,
+    // Key that sequenced per-step notes are snapped to (None = unquantized)
+    scale_quantize: Option<(NoteName, Scale)>,
+    // A4 reference and transpose applied to every pitched instrument
+    master_tuning: MasterTuning,
+    // Routing from instruments to the master bus (None = every instrument sums
+    // straight in)
+    graph: Option<AudioGraph>,
+    // Duckers notified whenever the named instrument triggers
+    duck_triggers: Vec<(InstrumentId, DuckTrigger)>,
+    // MIDI clock following the transport, and the queue its messages go out on
+    midi_clock: Option<(MidiClock, SyncSender<MidiClockEvent>)>,
+    // Ableton Link session the transport follows, polled once per buffer
+    #[cfg(feature = "link")]
+    link: Option<LinkSync>,
 }
 
+#[cfg(feature = "std")]
 impl Engine {
     pub fn new(sample_rate: f32) -> Self {
         // Initialize with a brick wall limiter as the default global effect
@@ -114,19 +333,39 @@ impl Engine {
         Self {
             sample_rate,
             bpm: 120.0, // Default BPM
-            instruments: HashMap::new(),
-            instrument_pans: HashMap::new(),
-            trigger_queue: VecDeque::new(),
+            instruments: Vec::new(),
+            instrument_ids: HashMap::new(),
+            instrument_generation: 0,
+            event_queue: VecDeque::with_capacity(AUDIO_EVENT_CAPACITY),
             sequencers: Vec::new(),
             lfos: Vec::new(),
+            mod_envelopes: Vec::new(),
             global_effects,
+            effect_lanes: Vec::new(),
             // Default of 0.25 provides headroom for mixing multiple instruments
             master_gain: SmoothedParam::new(0.25, 0.0, 2.0, sample_rate, 30.0),
-            saved_global_freq: HashMap::new(),
             mixer: Mixer::new(sample_rate),
+            recorder: Recorder::new(sample_rate),
+            scale_quantize: None,
+            master_tuning: MasterTuning::default(),
+            graph: None,
+            duck_triggers: Vec::new(),
+            midi_clock: None,
+            #[cfg(feature = "link")]
+            link: None,
         }
     }
 
+    /// Shared access to the master output recorder.
+    pub fn recorder(&self) -> &Recorder {
+        &self.recorder
+    }
+
+    /// Mutable access to the master output recorder (arm/start/stop/take).
+    pub fn recorder_mut(&mut self) -> &mut Recorder {
+        &mut self.recorder
+    }
+
     /// Shared access to the multi-channel loop mixer.
     pub fn mixer(&self) -> &Mixer {
         &self.mixer
@@ -145,8 +384,14 @@ impl Engine {
         for lfo in &mut self.lfos {
             lfo.set_bpm(bpm);
         }
+        for effect in &self.global_effects {
+            effect.set_bpm(bpm);
+        }
         // Seed BPM for any future note-synced per-channel loop effects.
         self.mixer.set_bpm(bpm);
+        if let Some((clock, _)) = &mut self.midi_clock {
+            clock.set_bpm(bpm);
+        }
     }
 
     /// Get the global BPM
@@ -154,6 +399,119 @@ impl Engine {
         self.bpm
     }
 
+    /// Lock the tempo and transport to an external clock for the coming
+    /// sample. Call once per buffer (or more often) with a fresh reading.
+    ///
+    /// A tempo change is applied to the engine and every sequencer. Phase is
+    /// corrected per sequencer with [`Sequencer::sync_to_beat`], so drift is
+    /// nudged out and jumps re-seat the cursor without double or dropped
+    /// steps. When the clock shares start/stop state, the transport follows
+    /// it; a start lands on the clock's current beat.
+    pub fn sync_to_clock(&mut self, clock: ExternalClock) {
+        if clock.bpm.is_finite() && clock.bpm > 0.0 && clock.bpm != self.bpm {
+            self.set_bpm(clock.bpm);
+            for seq in &mut self.sequencers {
+                seq.set_bpm(clock.bpm);
+            }
+        }
+
+        let running = self.sequencers.iter().any(|seq| seq.is_running());
+        match clock.playing {
+            Some(true) if !running => {
+                if let Some((midi_clock, _)) = &mut self.midi_clock {
+                    midi_clock.locate(clock.beat);
+                }
+                for seq in &mut self.sequencers {
+                    seq.set_beat_position(clock.beat);
+                    seq.start();
+                }
+                self.mixer.transport_seek(clock.beat);
+                self.mixer.transport_start();
+                return;
+            }
+            Some(false) if running => {
+                self.stop_all_sequencers();
+                return;
+            }
+            _ => {}
+        }
+
+        for seq in &mut self.sequencers {
+            seq.sync_to_beat(clock.beat);
+        }
+    }
+
+    /// Start generating MIDI clock (24 ppqn), start/stop/continue and song
+    /// position pointer messages from the transport. Returns the receiving
+    /// end of a queue of sample-stamped messages, to be sent on by e.g.
+    /// `MidiClockOutput`. Messages are dropped if the queue fills up.
+    ///
+    /// The transport counts as running while any sequencer runs. Replaces a
+    /// previously enabled clock; its receiver disconnects.
+    pub fn enable_midi_clock(&mut self) -> Receiver<MidiClockEvent> {
+        let (tx, rx) = sync_channel(MIDI_CLOCK_QUEUE_CAPACITY);
+        self.midi_clock = Some((MidiClock::new(self.sample_rate, self.bpm), tx));
+        rx
+    }
+
+    /// Stop generating MIDI clock. The receiver disconnects.
+    pub fn disable_midi_clock(&mut self) {
+        self.midi_clock = None;
+    }
+
+    /// The MIDI clock generator, if enabled.
+    pub fn midi_clock(&self) -> Option<&MidiClock> {
+        self.midi_clock.as_ref().map(|(clock, _)| clock)
+    }
+
+    /// Follow (or lead) an Ableton Link session. The session is polled at
+    /// the start of each buffer by [`Engine::begin_buffer`].
+    #[cfg(feature = "link")]
+    pub fn set_link(&mut self, link: LinkSync) {
+        self.link = Some(link);
+    }
+
+    /// Detach the Link session, returning it. The transport keeps its last
+    /// tempo and position.
+    #[cfg(feature = "link")]
+    pub fn take_link(&mut self) -> Option<LinkSync> {
+        self.link.take()
+    }
+
+    /// The attached Link session, if any.
+    #[cfg(feature = "link")]
+    pub fn link(&self) -> Option<&LinkSync> {
+        self.link.as_ref()
+    }
+
+    /// Called by the audio output before rendering a buffer whose first
+    /// sample is `sample_time` (samples since the stream started). Polls
+    /// external sync sources; a no-op when none is attached.
+    pub fn begin_buffer(&mut self, sample_time: u64) {
+        #[cfg(feature = "link")]
+        if let Some(mut link) = self.link.take() {
+            link.process(self, sample_time);
+            self.link = Some(link);
+        }
+        #[cfg(not(feature = "link"))]
+        let _ = sample_time;
+    }
+
+    /// Snap sequenced per-step notes to `scale` on `root` from now on.
+    pub fn set_scale_quantize(&mut self, root: NoteName, scale: Scale) {
+        self.scale_quantize = Some((root, scale));
+    }
+
+    /// Stop snapping sequenced notes; they play exactly as programmed.
+    pub fn clear_scale_quantize(&mut self) {
+        self.scale_quantize = None;
+    }
+
+    /// The key sequenced notes are snapped to, if any.
+    pub fn scale_quantize(&self) -> Option<(NoteName, Scale)> {
+        self.scale_quantize
+    }
+
     /// Add a global effect to the effects chain
     /// Global effects are applied to the final output after all instruments are mixed
     pub fn add_global_effect(&mut self, effect: Box<dyn Effect>) {
@@ -170,6 +528,50 @@ impl Engine {
         self.global_effects.len()
     }
 
+    /// Add an effect lane driving `param` (the effect's `*_PARAM_*` id, see
+    /// [`Effect::set_param`]) of the global effect at `effect_index`, and
+    /// return the lane's index. The lane follows the first sequencer's
+    /// playhead while it runs and writes the parameter only when its value
+    /// changes. Fails if there is no global effect at `effect_index`.
+    pub fn add_effect_lane(
+        &mut self,
+        effect_index: usize,
+        param: u32,
+        lane: EffectLane,
+    ) -> Result<usize, GooeyError> {
+        if effect_index >= self.global_effects.len() {
+            return Err(GooeyError::IndexOutOfRange {
+                what: "global effect",
+                index: effect_index,
+                count: self.global_effects.len(),
+            });
+        }
+        self.effect_lanes.push(EffectLaneBinding {
+            lane,
+            effect_index,
+            param,
+            last: None,
+        });
+        Ok(self.effect_lanes.len() - 1)
+    }
+
+    /// Remove every effect lane. Lanes hold global effect indices, so clear
+    /// them along with [`Engine::clear_global_effects`] before rebuilding
+    /// the chain.
+    pub fn clear_effect_lanes(&mut self) {
+        self.effect_lanes.clear();
+    }
+
+    pub fn effect_lane_mut(&mut self, index: usize) -> Option<&mut EffectLane> {
+        self.effect_lanes
+            .get_mut(index)
+            .map(|binding| &mut binding.lane)
+    }
+
+    pub fn effect_lane(&self, index: usize) -> Option<&EffectLane> {
+        self.effect_lanes.get(index).map(|binding| &binding.lane)
+    }
+
     /// Set the master gain level (smoothed to prevent clicks)
     ///
     /// # Arguments
@@ -186,19 +588,112 @@ impl Engine {
         self.master_gain.target()
     }
 
-    /// Add an instrument with a unique name
-    pub fn add_instrument(&mut self, name: impl Into<String>, instrument: Box<dyn Instrument>) {
-        self.instruments.insert(name.into(), instrument);
+    /// Route instruments through `graph` instead of summing them straight into
+    /// the master bus. The graph's output then takes the place of the
+    /// instrument sum; loops, master gain and the global effects still follow.
+    pub fn set_graph(&mut self, graph: AudioGraph) {
+        self.graph = Some(graph);
+    }
+
+    /// Remove the routing graph, returning to the direct instrument sum.
+    pub fn clear_graph(&mut self) -> Option<AudioGraph> {
+        self.graph.take()
+    }
+
+    pub fn graph_mut(&mut self) -> Option<&mut AudioGraph> {
+        self.graph.as_mut()
+    }
+
+    /// Fire `trigger` every time `instrument` is triggered, by a sequencer or a
+    /// queued event. Pair with a [`Ducker`](crate::effects::Ducker) in the
+    /// global chain or on a graph bus (e.g. a delay return) to duck it under
+    /// the kick without sidechain detection.
+    pub fn duck_on_trigger(&mut self, instrument: impl Into<String>, trigger: DuckTrigger) {
+        let id = self.intern(&instrument.into());
+        self.duck_triggers.push((id, trigger));
+    }
+
+    /// Add an instrument with a unique name and return its id. Adding under
+    /// a name already in use replaces that instrument and keeps its id.
+    pub fn add_instrument(
+        &mut self,
+        name: impl Into<String>,
+        mut instrument: Box<dyn Instrument>,
+    ) -> InstrumentId {
+        instrument.set_pitch_ratio(self.master_tuning.ratio());
+        let id = self.intern(&name.into());
+        self.instruments[id].instrument = Some(instrument);
+        id
+    }
+
+    /// Id for `name`, assigning the next one if the name is new.
+    fn intern(&mut self, name: &str) -> InstrumentId {
+        if let Some(&id) = self.instrument_ids.get(name) {
+            return id;
+        }
+        let id = self.instruments.len();
+        self.instruments.push(InstrumentSlot::new(name));
+        self.instrument_ids.insert(name.to_string(), id);
+        self.instrument_generation = self.instrument_generation.wrapping_add(1);
+        id
+    }
+
+    /// Id of the instrument named `name`: assigned when it was added, or
+    /// when a setter (pan, mute, output, ducking) first named it.
+    pub fn instrument_id(&self, name: &str) -> Option<InstrumentId> {
+        self.instrument_ids.get(name).copied()
+    }
+
+    /// Name the instrument `id` was registered under.
+    pub fn instrument_name(&self, id: InstrumentId) -> Option<&str> {
+        self.instruments.get(id).map(|slot| slot.name.as_str())
+    }
+
+    fn slot(&self, name: &str) -> Option<&InstrumentSlot> {
+        self.instrument_ids
+            .get(name)
+            .map(|&id| &self.instruments[id])
+    }
+
+    fn slot_mut(&mut self, name: &str) -> Option<&mut InstrumentSlot> {
+        self.instrument_ids
+            .get(name)
+            .map(|&id| &mut self.instruments[id])
+    }
+
+    /// Id of `name` when an instrument is added under it.
+    fn playable_id(&self, name: &str) -> Option<InstrumentId> {
+        self.instrument_id(name)
+            .filter(|&id| self.instruments[id].instrument.is_some())
+    }
+
+    /// Retune every pitched instrument, including ones added later, to an
+    /// A4 reference and transpose.
+    pub fn set_master_tuning(&mut self, tuning: MasterTuning) {
+        self.master_tuning = tuning;
+        let ratio = tuning.ratio();
+        for instrument in self
+            .instruments
+            .iter_mut()
+            .filter_map(|slot| slot.instrument.as_mut())
+        {
+            instrument.set_pitch_ratio(ratio);
+        }
+    }
+
+    /// The A4 reference and transpose set by [`Engine::set_master_tuning`].
+    pub fn master_tuning(&self) -> MasterTuning {
+        self.master_tuning
     }
 
     /// Get a mutable reference to an instrument by name
     pub fn instrument_mut(&mut self, name: &str) -> Option<&mut Box<dyn Instrument>> {
-        self.instruments.get_mut(name)
+        self.slot_mut(name)?.instrument.as_mut()
     }
 
     /// Get a reference to an instrument by name
     pub fn instrument(&self, name: &str) -> Option<&Box<dyn Instrument>> {
-        self.instruments.get(name)
+        self.slot(name)?.instrument.as_ref()
     }
 
     /// Set the stereo pan for an instrument.
@@ -209,23 +704,96 @@ impl Engine {
     /// Pan is only applied on the stereo path ([`Engine::tick_stereo`]); the
     /// mono [`Engine::tick`] / bounce path ignores it.
     pub fn set_instrument_pan(&mut self, name: &str, pan: f32) {
+        let id = self.intern(name);
+        self.set_pan(id, pan);
+    }
+
+    fn set_pan(&mut self, id: InstrumentId, pan: f32) {
         let pan = pan.clamp(0.0, 1.0);
         let sample_rate = self.sample_rate;
-        self.instrument_pans
-            .entry(name.to_string())
-            // Seed at center so the first move ramps from the default position.
-            .or_insert_with(|| SmoothedParam::new(0.5, 0.0, 1.0, sample_rate, 10.0))
-            .set_target(pan);
+        if let Some(slot) = self.instruments.get_mut(id) {
+            slot.pan
+                // Seed at center so the first move ramps from the default position.
+                .get_or_insert_with(|| SmoothedParam::new(0.5, 0.0, 1.0, sample_rate, 10.0))
+                .set_target(pan);
+        }
     }
 
     /// Get the stereo pan target for an instrument (defaults to 0.5 = center).
     pub fn instrument_pan(&self, name: &str) -> f32 {
-        self.instrument_pans
-            .get(name)
+        self.slot(name)
+            .and_then(|slot| slot.pan.as_ref())
             .map(|p| p.target())
             .unwrap_or(0.5)
     }
 
+    /// Mute an instrument: sequencer hits on it are skipped while notes
+    /// already sounding ring out, so muting never clicks. Manual triggers
+    /// still play, for auditioning.
+    pub fn set_instrument_muted(&mut self, name: &str, muted: bool) {
+        let id = self.intern(name);
+        self.instruments[id].muted = muted;
+    }
+
+    pub fn instrument_muted(&self, name: &str) -> bool {
+        self.slot(name).is_some_and(|slot| slot.muted)
+    }
+
+    /// Solo an instrument: while any instrument is soloed, sequencer hits on
+    /// the others are skipped, as with [`Engine::set_instrument_muted`]. Mute
+    /// wins over solo.
+    pub fn set_instrument_soloed(&mut self, name: &str, soloed: bool) {
+        let id = self.intern(name);
+        self.instruments[id].soloed = soloed;
+    }
+
+    pub fn instrument_soloed(&self, name: &str) -> bool {
+        self.slot(name).is_some_and(|slot| slot.soloed)
+    }
+
+    /// Whether sequencer hits on `name` play, given mute and solo.
+    pub fn instrument_audible(&self, name: &str) -> bool {
+        match self.instrument_id(name) {
+            Some(id) => self.audible(id),
+            None => !self.instruments.iter().any(|slot| slot.soloed),
+        }
+    }
+
+    fn audible(&self, id: InstrumentId) -> bool {
+        let slot = &self.instruments[id];
+        !slot.muted && (slot.soloed || !self.instruments.iter().any(|slot| slot.soloed))
+    }
+
+    /// Route an instrument to its own output pair for external mixing.
+    ///
+    /// Pair 0 is the main mix (device channels 1/2), pair 1 is channels 3/4,
+    /// and so on up to `MAX_OUTPUT_PAIRS - 1`. A routed instrument leaves the
+    /// main mix and comes out of its pair panned but dry: no master gain,
+    /// global effects or recording. Only [`Engine::tick_multi`] renders the
+    /// pairs; when the device has too few channels for a pair (and on
+    /// [`Engine::tick_stereo`]/[`Engine::tick`]), the instrument falls back to
+    /// the main mix.
+    pub fn set_instrument_output(&mut self, name: &str, pair: usize) {
+        let id = self.intern(name);
+        self.instruments[id].output = pair.min(MAX_OUTPUT_PAIRS - 1);
+    }
+
+    /// Output pair an instrument plays on (0 = main mix, the default).
+    pub fn instrument_output(&self, name: &str) -> usize {
+        self.slot(name).map_or(0, |slot| slot.output)
+    }
+
+    /// Output pairs needed to play every route: 1 plus the highest pair in
+    /// use. Output sinks use this to pick a device channel count.
+    pub fn output_pairs_needed(&self) -> usize {
+        1 + self
+            .instruments
+            .iter()
+            .map(|slot| slot.output)
+            .max()
+            .unwrap_or(0)
+    }
+
     /// Add a sequencer to the engine
     pub fn add_sequencer(&mut self, sequencer: Sequencer) {
         self.sequencers.push(sequencer);
@@ -263,59 +831,259 @@ impl Engine {
     }
 
     /// Map an LFO to modulate a specific instrument parameter
-    /// Returns Ok(()) if successful, Err with message if validation fails
+    /// Returns Ok(()) if successful, the reason otherwise
     pub fn map_lfo_to_parameter(
         &mut self,
         lfo_index: usize,
         instrument_name: &str,
         parameter: &str,
         amount: f32,
-    ) -> Result<(), String> {
+    ) -> Result<(), GooeyError> {
+        self.check_modulatable(instrument_name, parameter)?;
+
+        // Set up the mapping
+        if let Some(lfo) = self.lfos.get_mut(lfo_index) {
+            lfo.target_instrument = instrument_name.to_string();
+            lfo.target_parameter = parameter.to_string();
+            lfo.amount = amount;
+            Ok(())
+        } else {
+            Err(GooeyError::IndexOutOfRange {
+                what: "LFO",
+                index: lfo_index,
+                count: self.lfos.len(),
+            })
+        }
+    }
+
+    /// Add a modulation envelope and return its index. It restarts each time
+    /// `instrument_name` is triggered and drives `parameter` like an LFO.
+    /// Fails if the target is not modulatable.
+    pub fn add_mod_envelope(
+        &mut self,
+        mut envelope: ModEnvelope,
+        instrument_name: &str,
+        parameter: &str,
+    ) -> Result<usize, GooeyError> {
+        self.check_modulatable(instrument_name, parameter)?;
+        envelope.target_instrument = instrument_name.to_string();
+        envelope.target_parameter = parameter.to_string();
+        self.mod_envelopes.push(envelope);
+        Ok(self.mod_envelopes.len() - 1)
+    }
+
+    /// Get a mutable reference to a modulation envelope by index
+    pub fn mod_envelope_mut(&mut self, index: usize) -> Option<&mut ModEnvelope> {
+        self.mod_envelopes.get_mut(index)
+    }
+
+    /// Get a reference to a modulation envelope by index
+    pub fn mod_envelope(&self, index: usize) -> Option<&ModEnvelope> {
+        self.mod_envelopes.get(index)
+    }
+
+    /// Release the sustain/loop of every modulation envelope targeting
+    /// `instrument_name`.
+    pub fn release_mod_envelopes(&mut self, instrument_name: &str, current_time: f64) {
+        for env in &mut self.mod_envelopes {
+            if env.target_instrument == instrument_name {
+                env.release(current_time);
+            }
+        }
+    }
+
+    /// Check that `parameter` on `instrument_name` can be modulated.
+    pub fn check_modulatable(
+        &mut self,
+        instrument_name: &str,
+        parameter: &str,
+    ) -> Result<(), GooeyError> {
+        self.modulatable_parameter(instrument_name, parameter)
+            .map(|_| ())
+    }
+
+    /// The instrument's own `'static` name for `parameter`, if the
+    /// instrument exists and the parameter is modulatable.
+    fn modulatable_parameter(
+        &mut self,
+        instrument_name: &str,
+        parameter: &str,
+    ) -> Result<&'static str, GooeyError> {
         // Validate instrument exists
         let instrument = self
-            .instruments
-            .get_mut(instrument_name)
-            .ok_or_else(|| format!("Instrument '{}' not found", instrument_name))?;
+            .instrument_mut(instrument_name)
+            .ok_or_else(|| GooeyError::UnknownInstrument(instrument_name.to_string()))?;
 
         // Validate parameter is modulatable
         if let Some(modulatable) = instrument.as_modulatable() {
-            if !modulatable.modulatable_parameters().contains(&parameter) {
-                return Err(format!(
-                    "Parameter '{}' is not modulatable on instrument '{}'. Available: {:?}",
-                    parameter,
-                    instrument_name,
-                    modulatable.modulatable_parameters()
-                ));
+            let available = modulatable.modulatable_parameters();
+            match available.iter().find(|&&name| name == parameter) {
+                Some(&name) => Ok(name),
+                None => Err(GooeyError::NotModulatable {
+                    instrument: instrument_name.to_string(),
+                    parameter: parameter.to_string(),
+                    available,
+                }),
             }
         } else {
-            return Err(format!(
-                "Instrument '{}' does not support modulation",
-                instrument_name
-            ));
+            Err(GooeyError::ModulationUnsupported(
+                instrument_name.to_string(),
+            ))
         }
+    }
 
-        // Set up the mapping
-        if let Some(lfo) = self.lfos.get_mut(lfo_index) {
-            lfo.target_instrument = instrument_name.to_string();
-            lfo.target_parameter = parameter.to_string();
-            lfo.amount = amount;
-            Ok(())
-        } else {
-            Err(format!("LFO index {} not found", lfo_index))
+    /// Queue a control event for the next audio tick.
+    ///
+    /// Events are applied in the order they were sent, all on the same sample.
+    /// Fails if [`AUDIO_EVENT_CAPACITY`] events are already pending.
+    pub fn send_event(&mut self, event: AudioEvent) -> Result<(), GooeyError> {
+        if self.event_queue.len() >= AUDIO_EVENT_CAPACITY {
+            return Err(GooeyError::QueueFull(AUDIO_EVENT_CAPACITY));
         }
+        self.event_queue.push_back(event);
+        Ok(())
+    }
+
+    /// Queue a change to one of an instrument's modulatable parameters for
+    /// the next audio tick, so it lands on the same sample as the events sent
+    /// alongside it.
+    ///
+    /// `value` is in the range [`Modulatable::parameter_range`] reports
+    /// (normalized 0-1 for the built-in instruments) and is clamped to it.
+    /// Fails for an unknown instrument or parameter, or a full queue.
+    pub fn set_instrument_param(
+        &mut self,
+        name: &str,
+        parameter: &str,
+        value: f32,
+    ) -> Result<(), GooeyError> {
+        let param = self.modulatable_parameter(name, parameter)?;
+        let id = self.intern(name);
+        self.send_event(AudioEvent::SetParam { id, param, value })
+    }
+
+    /// Number of events waiting for the next tick.
+    pub fn pending_event_count(&self) -> usize {
+        self.event_queue.len()
     }
 
     /// Queue an instrument to be triggered on the next audio tick at half velocity
     /// This is thread-safe to call from the main thread
-    pub fn trigger_instrument(&mut self, name: &str) {
-        self.trigger_queue.push_back((name.to_string(), 0.5));
+    /// Fails for an unknown instrument or a full queue.
+    pub fn trigger_instrument(&mut self, name: &str) -> Result<(), GooeyError> {
+        self.trigger_instrument_with_velocity(name, 0.5)
     }
 
     /// Queue an instrument to be triggered on the next audio tick with specified velocity
     /// This is thread-safe to call from the main thread
-    pub fn trigger_instrument_with_velocity(&mut self, name: &str, velocity: f32) {
-        self.trigger_queue
-            .push_back((name.to_string(), velocity.clamp(0.0, 1.0)));
+    /// Fails for an unknown instrument or a full queue.
+    pub fn trigger_instrument_with_velocity(
+        &mut self,
+        name: &str,
+        velocity: f32,
+    ) -> Result<(), GooeyError> {
+        let id = self
+            .playable_id(name)
+            .ok_or_else(|| GooeyError::UnknownInstrument(name.to_string()))?;
+        self.send_event(AudioEvent::TriggerInstrument {
+            id,
+            velocity: velocity.clamp(0.0, 1.0),
+        })
+    }
+
+    /// Queue a note-off for an instrument on the next audio tick. Instruments
+    /// without a release stage ignore it; modulation envelopes targeting the
+    /// instrument are released too.
+    /// This is thread-safe to call from the main thread
+    /// Fails for an unknown instrument or a full queue.
+    pub fn release_instrument(&mut self, name: &str) -> Result<(), GooeyError> {
+        let id = self
+            .playable_id(name)
+            .ok_or_else(|| GooeyError::UnknownInstrument(name.to_string()))?;
+        self.send_event(AudioEvent::ReleaseInstrument { id })
+    }
+
+    /// Apply one queued control event at `current_time`.
+    fn apply_event(&mut self, event: AudioEvent, current_time: f64) {
+        match event {
+            AudioEvent::TriggerAll { velocity } => {
+                let velocity = velocity.clamp(0.0, 1.0);
+                for instrument in self
+                    .instruments
+                    .iter_mut()
+                    .filter_map(|slot| slot.instrument.as_mut())
+                {
+                    instrument.trigger_with_velocity(current_time, velocity);
+                }
+                let slots = &self.instruments;
+                let playable = |id: InstrumentId| slots[id].instrument.is_some();
+                for (id, duck) in &self.duck_triggers {
+                    if playable(*id) {
+                        duck.fire();
+                    }
+                }
+                for env in &mut self.mod_envelopes {
+                    if self
+                        .instrument_ids
+                        .get(&env.target_instrument)
+                        .is_some_and(|&id| playable(id))
+                    {
+                        env.trigger(current_time);
+                    }
+                }
+            }
+            AudioEvent::TriggerInstrument { id, velocity } => {
+                let Some(slot) = self.instruments.get_mut(id) else {
+                    return;
+                };
+                if let Some(instrument) = slot.instrument.as_mut() {
+                    instrument.trigger_with_velocity(current_time, velocity.clamp(0.0, 1.0));
+                    fire_ducks(&self.duck_triggers, id);
+                    trigger_mod_envelopes(&mut self.mod_envelopes, &slot.name, current_time);
+                }
+            }
+            AudioEvent::ReleaseInstrument { id } => {
+                let Some(slot) = self.instruments.get_mut(id) else {
+                    return;
+                };
+                if let Some(instrument) = slot.instrument.as_mut() {
+                    instrument.release(current_time);
+                    for env in &mut self.mod_envelopes {
+                        if env.target_instrument == slot.name {
+                            env.release(current_time);
+                        }
+                    }
+                }
+            }
+            AudioEvent::SetMasterGain(gain) => self.set_master_gain(gain),
+            AudioEvent::SetInstrumentPan { id, pan } => self.set_pan(id, pan),
+            AudioEvent::SetParam { id, param, value } => {
+                let Some(modulatable) = self
+                    .instruments
+                    .get_mut(id)
+                    .and_then(|slot| slot.instrument.as_mut())
+                    .and_then(|instrument| instrument.as_modulatable())
+                else {
+                    return;
+                };
+                if let Some((min, max)) = modulatable.parameter_range(param) {
+                    // apply_modulation takes -1..1 across the parameter's range
+                    let normalized = if max > min {
+                        (value - min) / (max - min)
+                    } else {
+                        0.0
+                    };
+                    let _ = modulatable.apply_modulation(param, normalized * 2.0 - 1.0);
+                }
+            }
+            AudioEvent::TransportStart => {
+                for seq in &mut self.sequencers {
+                    seq.start();
+                }
+                self.mixer.transport_start();
+            }
+            AudioEvent::TransportStop => self.stop_all_sequencers(),
+        }
     }
 
     /// Advance one sample and produce the mono instrument mix BEFORE the loop
@@ -330,7 +1098,11 @@ impl Engine {
 
         // Sum all instrument outputs (mono)
         let mut output = 0.0;
-        for instrument in self.instruments.values_mut() {
+        for instrument in self
+            .instruments
+            .iter_mut()
+            .filter_map(|slot| slot.instrument.as_mut())
+        {
             output += instrument.tick(current_time);
         }
         output
@@ -347,7 +1119,10 @@ impl Engine {
 
             // Apply modulation if this LFO has a target
             if !lfo.target_instrument.is_empty() && !lfo.target_parameter.is_empty() {
-                if let Some(instrument) = self.instruments.get_mut(&lfo.target_instrument) {
+                let target = self.instrument_ids.get(&lfo.target_instrument);
+                if let Some(instrument) =
+                    target.and_then(|&id| self.instruments[id].instrument.as_mut())
+                {
                     if let Some(modulatable) = instrument.as_modulatable() {
                         let _ = modulatable.apply_modulation(&lfo.target_parameter, lfo_value);
                     }
@@ -355,40 +1130,112 @@ impl Engine {
             }
         }
 
-        // Process all sequencers (sample-accurate triggering with velocity and per-step notes)
-        for sequencer in &mut self.sequencers {
+        // Process all sequencers (sample-accurate triggering with velocity and per-step notes).
+        // Taken out for the loop so hits can use the rest of the engine; no allocation.
+        let mut sequencers = std::mem::take(&mut self.sequencers);
+        for sequencer in &mut sequencers {
+            let ids = &self.instrument_ids;
+            sequencer.resolve_targets(self.instrument_generation, |name| ids.get(name).copied());
             if let Some(trigger) = sequencer.tick_with_settings() {
-                let instrument_name = trigger.instrument_name;
-                let velocity = trigger.velocity;
-                let note = trigger.note;
-
-                if let Some(instrument) = self.instruments.get_mut(instrument_name) {
-                    if let Some(midi_note) = note {
-                        // Save global frequency before overriding (only on first note step)
-                        let key = instrument_name.to_string();
-                        if !self.saved_global_freq.contains_key(&key) {
-                            if let Some(freq) = instrument.get_frequency() {
-                                self.saved_global_freq.insert(key, freq);
-                            }
-                        }
-                        instrument.set_midi_note(midi_note);
-                    } else if let Some(saved) = self.saved_global_freq.remove(instrument_name) {
-                        // Restore global frequency when step has no note
-                        instrument.set_frequency_normalized(saved);
+                if let Some(id) = trigger.instrument_id() {
+                    self.play_hit(
+                        id,
+                        trigger.note,
+                        trigger.articulation,
+                        trigger.velocity,
+                        current_time,
+                    );
+                }
+                for (id, note, velocity) in trigger.layer_ids() {
+                    self.play_hit(id, note, None, velocity, current_time);
+                }
+            }
+        }
+        self.sequencers = sequencers;
+
+        // Effect lanes follow the first sequencer's playhead
+        if let Some(position) = self
+            .sequencers
+            .first()
+            .filter(|seq| seq.is_running())
+            .map(Sequencer::step_position)
+        {
+            for binding in &mut self.effect_lanes {
+                let Some(value) = binding.lane.value_at(position) else {
+                    continue;
+                };
+                if binding.last != Some(value) {
+                    if let Some(effect) = self.global_effects.get(binding.effect_index) {
+                        effect.set_param(binding.param, value);
                     }
-                    instrument.trigger_with_velocity(current_time, velocity);
+                    binding.last = Some(value);
+                }
+            }
+        }
+
+        // Clock out before this sample's events so a transport start lands on
+        // the same sample as the sequencers' first step (on the next tick)
+        if let Some((clock, tx)) = &mut self.midi_clock {
+            let running = self.sequencers.iter().any(|seq| seq.is_running());
+            clock.tick(running, |event| {
+                let _ = tx.try_send(event);
+            });
+        }
+
+        // Apply queued control events (triggers fire at the current audio time)
+        while let Some(event) = self.event_queue.pop_front() {
+            self.apply_event(event, current_time);
+        }
+
+        // Apply envelope modulation after triggers so a restart lands this sample
+        for env in &mut self.mod_envelopes {
+            let value = env.tick(current_time);
+            let target = self.instrument_ids.get(&env.target_instrument);
+            if let Some(instrument) =
+                target.and_then(|&id| self.instruments[id].instrument.as_mut())
+            {
+                if let Some(modulatable) = instrument.as_modulatable() {
+                    let _ = modulatable.apply_modulation(&env.target_parameter, value);
                 }
             }
         }
+    }
 
-        // Process trigger queue - trigger instruments with current audio time and velocity
-        while let Some((name, velocity)) = self.trigger_queue.pop_front() {
-            if let Some(instrument) = self.instruments.get_mut(&name) {
-                instrument.trigger_with_velocity(current_time, velocity);
-            } else {
-                eprintln!("Warning: Instrument '{}' not found", name);
+    /// Play one sequencer hit on instrument `id`: apply its per-step note
+    /// (scale-quantized, restoring the instrument's own frequency on hits
+    /// without one), trigger it, and fire the ducks and modulation envelopes
+    /// it drives. Nothing happens while mute or solo silences the instrument.
+    fn play_hit(
+        &mut self,
+        id: InstrumentId,
+        note: Option<u8>,
+        articulation: Option<u8>,
+        velocity: f32,
+        current_time: f64,
+    ) {
+        if !self.audible(id) {
+            return;
+        }
+        let slot = &mut self.instruments[id];
+        let Some(instrument) = slot.instrument.as_mut() else {
+            return;
+        };
+        match slot
+            .step_note
+            .resolve(note, self.scale_quantize, instrument.get_frequency())
+        {
+            NoteChange::Play(midi_note) => instrument.set_midi_note(midi_note),
+            NoteChange::Restore(saved) => instrument.set_frequency_normalized(saved),
+            NoteChange::Keep => {}
+        }
+        match articulation {
+            Some(articulation) => {
+                instrument.trigger_articulated(current_time, velocity, articulation)
             }
+            None => instrument.trigger_with_velocity(current_time, velocity),
         }
+        fire_ducks(&self.duck_triggers, id);
+        trigger_mod_envelopes(&mut self.mod_envelopes, &slot.name, current_time);
     }
 
     /// Generate one mono sample of audio at the given time.
@@ -398,7 +1245,14 @@ impl Engine {
     /// its behavior unchanged from before stereo effects were introduced.
     /// Per-instrument pan is a stereo-only feature and is ignored here.
     pub fn tick(&mut self, current_time: f64) -> f32 {
-        let mut output = self.render_pre_effects(current_time);
+        // A routing graph is stereo throughout; downmix its output here.
+        let mut output = if self.graph.is_some() {
+            self.advance_control(current_time);
+            self.render_instruments_stereo(current_time, &mut [])
+                .downmix()
+        } else {
+            self.render_pre_effects(current_time)
+        };
 
         // Sum the loop mixer into the master bus (downmixed for the mono path).
         output += self.mixer.tick(self.sample_rate).downmix();
@@ -411,9 +1265,17 @@ impl Engine {
             output = effect.process(output);
         }
 
+        self.recorder.push_frame(StereoFrame::mono(output));
+
         output
     }
 
+    /// [`Engine::tick`] at a position on a [`SampleClock`], for callers that
+    /// count samples rather than summing seconds.
+    pub fn tick_at(&mut self, clock: SampleClock) -> f32 {
+        self.tick(clock.seconds())
+    }
+
     /// Generate one stereo frame of audio at the given time.
     ///
     /// This is the native engine's "stereo seam": each instrument's mono output
@@ -425,19 +1287,38 @@ impl Engine {
     /// true two-channel output. With every instrument centered, no loops, and no
     /// stereo effect engaged, the two channels stay identical.
     pub fn tick_stereo(&mut self, current_time: f64) -> StereoFrame {
+        self.render_main(current_time, &mut [])
+    }
+
+    /// [`Engine::tick_stereo`] at a position on a [`SampleClock`].
+    pub fn tick_stereo_at(&mut self, clock: SampleClock) -> StereoFrame {
+        self.tick_stereo(clock.seconds())
+    }
+
+    /// Generate one frame of every output pair at the given time.
+    ///
+    /// `outputs[0]` receives the main mix, exactly as [`Engine::tick_stereo`]
+    /// renders it minus the routed instruments; `outputs[n]` receives the
+    /// instruments routed to pair `n` (see [`Engine::set_instrument_output`]).
+    /// Instruments routed past the end of `outputs` fold into the main mix,
+    /// so a device with fewer channels than the routing asks for still hears
+    /// everything.
+    pub fn tick_multi(&mut self, current_time: f64, outputs: &mut [StereoFrame]) {
+        let Some((main, direct)) = outputs.split_first_mut() else {
+            self.tick_stereo(current_time);
+            return;
+        };
+        direct.fill(StereoFrame::default());
+        *main = self.render_main(current_time, direct);
+    }
+
+    /// The stereo path behind [`Engine::tick_stereo`] and
+    /// [`Engine::tick_multi`]: routed instruments with a pair in `direct`
+    /// (pair `n` at `direct[n - 1]`) are added there instead.
+    fn render_main(&mut self, current_time: f64, direct: &mut [StereoFrame]) -> StereoFrame {
         self.advance_control(current_time);
 
-        // Sum each instrument into the stereo field via its (smoothed) pan.
-        let mut stereo = StereoFrame::default();
-        for (name, instrument) in self.instruments.iter_mut() {
-            let sample = instrument.tick(current_time);
-            let pan = self
-                .instrument_pans
-                .get_mut(name)
-                .map(|p| p.tick())
-                .unwrap_or(0.5);
-            stereo += StereoFrame::panned(sample, pan);
-        }
+        let mut stereo = self.render_instruments_stereo(current_time, direct);
 
         // Sum the loop mixer (already stereo, with its own per-channel effects)
         // into the master bus.
@@ -451,9 +1332,49 @@ impl Engine {
             stereo = effect.process_stereo(stereo);
         }
 
+        self.recorder.push_frame(stereo);
+
         stereo
     }
 
+    /// Spread each instrument across the stereo field via its (smoothed) pan
+    /// and sum them, through the routing graph when one is set. Instruments
+    /// routed to a pair in `direct` skip both and go there.
+    fn render_instruments_stereo(
+        &mut self,
+        current_time: f64,
+        direct: &mut [StereoFrame],
+    ) -> StereoFrame {
+        let mut stereo = StereoFrame::default();
+        if let Some(graph) = self.graph.as_mut() {
+            graph.begin_frame();
+        }
+        for slot in &mut self.instruments {
+            let Some(instrument) = slot.instrument.as_mut() else {
+                continue;
+            };
+            let sample = instrument.tick(current_time);
+            let pan = slot.pan.as_mut().map(|p| p.tick()).unwrap_or(0.5);
+            let frame = StereoFrame::panned(sample, pan);
+            let route = match slot.output {
+                0 => None,
+                pair => direct.get_mut(pair - 1),
+            };
+            if let Some(out) = route {
+                *out += frame;
+                continue;
+            }
+            match self.graph.as_mut() {
+                Some(graph) => graph.feed(&slot.name, frame),
+                None => stereo += frame,
+            }
+        }
+        match self.graph.as_mut() {
+            Some(graph) => graph.process(),
+            None => stereo,
+        }
+    }
+
     pub fn sample_rate(&self) -> f32 {
         self.sample_rate
     }
@@ -471,9 +1392,14 @@ impl Engine {
         }
         self.mixer.transport_reset();
         self.mixer.transport_start();
+        if let Some((clock, _)) = &mut self.midi_clock {
+            clock.locate(0.0);
+        }
         self.master_gain.snap();
-        self.trigger_queue.clear();
-        self.saved_global_freq.clear();
+        self.event_queue.clear();
+        for slot in &mut self.instruments {
+            slot.step_note.clear();
+        }
     }
 
     /// Stop all sequencers (called after a bounce completes).
@@ -484,3 +1410,23 @@ impl Engine {
         self.mixer.transport_stop();
     }
 }
+
+/// Restart every modulation envelope targeting `instrument`.
+#[cfg(feature = "std")]
+fn trigger_mod_envelopes(envelopes: &mut [ModEnvelope], instrument: &str, time: f64) {
+    for env in envelopes {
+        if env.target_instrument == instrument {
+            env.trigger(time);
+        }
+    }
+}
+
+/// Fire every duck trigger registered for `instrument`.
+#[cfg(feature = "std")]
+fn fire_ducks(duck_triggers: &[(InstrumentId, DuckTrigger)], instrument: InstrumentId) {
+    for (id, duck) in duck_triggers {
+        if *id == instrument {
+            duck.fire();
+        }
+    }
+}
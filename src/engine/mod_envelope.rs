@@ -0,0 +1,47 @@
+use crate::max_curve::SegmentEnvelope;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// A [`SegmentEnvelope`] used as a modulation source.
+///
+/// The envelope restarts every time its target instrument is triggered and
+/// writes `offset + value * amount` to the target parameter each sample, the
+/// same bipolar (-1.0 to 1.0) range an [`Lfo`](super::Lfo) drives. A pitch
+/// envelope on a kick is `offset = -1.0`, `amount = 2.0` on `frequency`.
+#[derive(Clone, Debug)]
+pub struct ModEnvelope {
+    pub envelope: SegmentEnvelope,
+
+    // Routing
+    pub target_instrument: String,
+    pub target_parameter: String,
+    pub amount: f32,
+    pub offset: f32,
+}
+
+impl ModEnvelope {
+    pub fn new(envelope: SegmentEnvelope) -> Self {
+        Self {
+            envelope,
+            target_instrument: String::new(),
+            target_parameter: String::new(),
+            amount: 1.0,
+            offset: 0.0,
+        }
+    }
+
+    /// Restart the envelope at `time`.
+    pub fn trigger(&mut self, time: f64) {
+        self.envelope.trigger(time);
+    }
+
+    /// Release the envelope's sustain/loop at `time`.
+    pub fn release(&mut self, time: f64) {
+        self.envelope.release(time);
+    }
+
+    /// Modulation value at `time`: `offset + envelope * amount`.
+    pub fn tick(&mut self, time: f64) -> f32 {
+        self.offset + self.envelope.get_value(time) * self.amount
+    }
+}
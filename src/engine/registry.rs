@@ -0,0 +1,295 @@
+//! Fixed-capacity instrument registry
+//!
+//! [`InstrumentRegistry`] holds up to `N` named instruments in a fixed array
+//! and drives them from sequencer triggers. It is the `no_std` counterpart of
+//! the engine's instrument map: no hashing, no growth, and `&'static str`
+//! names, so nothing allocates once the instruments are boxed.
+
+use super::{Instrument, SequencerTrigger};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::SampleClock;
+
+struct Entry {
+    name: &'static str,
+    instrument: Box<dyn Instrument>,
+    /// Normalized frequency to restore after per-step note overrides
+    saved_frequency: Option<f32>,
+}
+
+/// Up to `N` named instruments, looked up by a linear scan.
+pub struct InstrumentRegistry<const N: usize> {
+    entries: [Option<Entry>; N],
+}
+
+impl<const N: usize> Default for InstrumentRegistry<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> InstrumentRegistry<N> {
+    pub fn new() -> Self {
+        Self {
+            entries: [const { None }; N],
+        }
+    }
+
+    /// Maximum number of instruments.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.iter().flatten().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Add an instrument under `name`, replacing any instrument already
+    /// registered with that name. Hands the instrument back when the registry
+    /// is full.
+    pub fn add(
+        &mut self,
+        name: &'static str,
+        instrument: Box<dyn Instrument>,
+    ) -> Result<(), Box<dyn Instrument>> {
+        let slot = match self.position(name) {
+            Some(index) => &mut self.entries[index],
+            None => match self.entries.iter_mut().find(|entry| entry.is_none()) {
+                Some(slot) => slot,
+                None => return Err(instrument),
+            },
+        };
+        *slot = Some(Entry {
+            name,
+            instrument,
+            saved_frequency: None,
+        });
+        Ok(())
+    }
+
+    /// Remove and return the instrument registered as `name`.
+    pub fn remove(&mut self, name: &str) -> Option<Box<dyn Instrument>> {
+        let index = self.position(name)?;
+        self.entries[index].take().map(|entry| entry.instrument)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut (dyn Instrument + 'static)> {
+        let index = self.position(name)?;
+        self.entries[index]
+            .as_mut()
+            .map(|entry| entry.instrument.as_mut())
+    }
+
+    /// Names of the registered instruments, in slot order.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.entries.iter().flatten().map(|entry| entry.name)
+    }
+
+    /// Trigger the instrument registered as `name`. Returns false when there
+    /// is no such instrument.
+    pub fn trigger(&mut self, name: &str, time: f64, velocity: f32) -> bool {
+        match self.get_mut(name) {
+            Some(instrument) => {
+                instrument.trigger_with_velocity(time, velocity);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Release (note-off) the instrument registered as `name`. Returns false
+    /// when there is no such instrument.
+    pub fn release(&mut self, name: &str, time: f64) -> bool {
+        match self.get_mut(name) {
+            Some(instrument) => {
+                instrument.release(time);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Play a sequencer trigger: apply its per-step note (restoring the
+    /// instrument's own frequency on steps without one, as the engine does)
+    /// and trigger at its velocity and articulation, then play the step's
+    /// layers the same way. Returns false when the target instrument isn't
+    /// registered.
+    pub fn apply(&mut self, trigger: &SequencerTrigger<'_>, time: f64) -> bool {
+        let played = self.play(
+            trigger.instrument_name,
+            trigger.note,
+            trigger.articulation,
+            trigger.velocity,
+            time,
+        );
+        for (name, note, velocity) in trigger.layers() {
+            self.play(name, note, None, velocity, time);
+        }
+        played
+    }
+
+    fn play(
+        &mut self,
+        name: &str,
+        note: Option<u8>,
+        articulation: Option<u8>,
+        velocity: f32,
+        time: f64,
+    ) -> bool {
+        let Some(index) = self.position(name) else {
+            return false;
+        };
+        let Some(entry) = self.entries[index].as_mut() else {
+            return false;
+        };
+        if let Some(note) = note {
+            if entry.saved_frequency.is_none() {
+                entry.saved_frequency = entry.instrument.get_frequency();
+            }
+            entry.instrument.set_midi_note(note);
+        } else if let Some(saved) = entry.saved_frequency.take() {
+            entry.instrument.set_frequency_normalized(saved);
+        }
+        match articulation {
+            Some(articulation) => {
+                entry
+                    .instrument
+                    .trigger_articulated(time, velocity, articulation)
+            }
+            None => entry.instrument.trigger_with_velocity(time, velocity),
+        }
+        true
+    }
+
+    /// Render one sample: the sum of every registered instrument.
+    pub fn tick(&mut self, current_time: f64) -> f32 {
+        self.entries
+            .iter_mut()
+            .flatten()
+            .map(|entry| entry.instrument.tick(current_time))
+            .sum()
+    }
+
+    /// [`InstrumentRegistry::tick`] at a position on a [`SampleClock`]. Hosts
+    /// that count samples should call this and advance the clock each
+    /// sample, so timing stays exact however long they run.
+    pub fn tick_at(&mut self, clock: SampleClock) -> f32 {
+        self.entries
+            .iter_mut()
+            .flatten()
+            .map(|entry| entry.instrument.tick_at(clock))
+            .sum()
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|entry| entry.as_ref().is_some_and(|entry| entry.name == name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{Sequencer, STEP_MAX_LAYERS};
+
+    /// Counts triggers and remembers the last note it was given
+    #[derive(Default)]
+    struct Probe {
+        triggers: u32,
+        frequency: f32,
+    }
+
+    impl Instrument for Probe {
+        fn trigger_with_velocity(&mut self, _time: f64, _velocity: f32) {
+            self.triggers += 1;
+        }
+
+        fn tick(&mut self, _current_time: f64) -> f32 {
+            0.25
+        }
+
+        fn is_active(&self) -> bool {
+            true
+        }
+
+        fn set_midi_note(&mut self, note: u8) {
+            self.frequency = note as f32 / 127.0;
+        }
+
+        fn set_frequency_normalized(&mut self, value: f32) {
+            self.frequency = value;
+        }
+
+        fn get_frequency(&self) -> Option<f32> {
+            Some(self.frequency)
+        }
+    }
+
+    #[test]
+    fn test_fixed_capacity_and_replacement() {
+        let mut registry = InstrumentRegistry::<2>::new();
+        assert!(registry.add("kick", Box::new(Probe::default())).is_ok());
+        assert!(registry.add("snare", Box::new(Probe::default())).is_ok());
+        assert!(registry.add("hat", Box::new(Probe::default())).is_err());
+
+        // Same name replaces in place rather than needing a free slot
+        assert!(registry.add("kick", Box::new(Probe::default())).is_ok());
+        assert_eq!(registry.len(), 2);
+        assert!((registry.tick(0.0) - 0.5).abs() < 1e-6);
+
+        assert!(registry.remove("snare").is_some());
+        assert!(registry.add("hat", Box::new(Probe::default())).is_ok());
+        assert_eq!(registry.names().collect::<Vec<_>>(), ["kick", "hat"]);
+    }
+
+    #[test]
+    fn test_apply_restores_frequency_after_note_steps() {
+        let mut registry = InstrumentRegistry::<1>::new();
+        let probe = Probe {
+            frequency: 0.3,
+            ..Default::default()
+        };
+        registry.add("bass", Box::new(probe)).ok();
+
+        let step = |note| SequencerTrigger {
+            instrument_name: "bass",
+            velocity: 1.0,
+            blend: None,
+            note,
+            articulation: None,
+            tune: None,
+            gate_samples: None,
+            layers: [None; STEP_MAX_LAYERS],
+            layer_instruments: &[],
+            targets: &[],
+        };
+        assert!(registry.apply(&step(Some(127)), 0.0));
+        assert_eq!(registry.get_mut("bass").unwrap().get_frequency(), Some(1.0));
+        assert!(registry.apply(&step(None), 0.1));
+        assert_eq!(registry.get_mut("bass").unwrap().get_frequency(), Some(0.3));
+        assert!(!registry.trigger("kick", 0.2, 1.0));
+    }
+
+    #[test]
+    fn test_apply_plays_step_layers() {
+        let mut registry = InstrumentRegistry::<2>::new();
+        registry.add("kick", Box::new(Probe::default())).ok();
+        registry.add("bass", Box::new(Probe::default())).ok();
+
+        let mut sequencer = Sequencer::with_pattern(120.0, 44100.0, vec![true], "kick");
+        assert!(sequencer.add_step_layer(0, "bass", Some(127), 0.5));
+        sequencer.start();
+        let trigger = sequencer.tick_with_settings().unwrap();
+        assert_eq!(
+            trigger.layers().collect::<Vec<_>>(),
+            [("bass", Some(127), 0.5)]
+        );
+        assert!(registry.apply(&trigger, 0.0));
+        assert_eq!(registry.get_mut("bass").unwrap().get_frequency(), Some(1.0));
+    }
+}
@@ -1,4 +1,31 @@
-use crate::utils::{SmoothedParam, DEFAULT_SMOOTH_TIME_MS};
+use super::groove::GrooveTemplate;
+use super::InstrumentId;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::{Rng, SmoothedParam, DEFAULT_SMOOTH_TIME_MS};
+
+/// Phase error, in steps, that [`Sequencer::sync_to_beat`] absorbs by nudging
+/// the next step boundary instead of re-seating the cursor.
+pub const SYNC_NUDGE_STEPS: f64 = 0.25;
+
+/// Largest per-step tuning offset, in semitones either way (the range of the
+/// instruments' own tuning parameter).
+pub const STEP_TUNE_MAX_SEMITONES: f32 = 12.0;
+
+/// Per-step gate range, in steps.
+pub const STEP_GATE_MIN_STEPS: f32 = 0.01;
+pub const STEP_GATE_MAX_STEPS: f32 = 16.0;
+
+/// Extra hits one step can fire alongside the sequencer's own instrument.
+pub const STEP_MAX_LAYERS: usize = 4;
+
+fn clamp_step_tune(semitones: f32) -> f32 {
+    if semitones.is_finite() {
+        semitones.clamp(-STEP_TUNE_MAX_SEMITONES, STEP_TUNE_MAX_SEMITONES)
+    } else {
+        0.0
+    }
+}
 
 /// Absolute blend setting for a sequencer step (X/Y in 0.0-1.0)
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -23,6 +50,32 @@ pub struct SequencerStepSettings {
     pub velocity: Option<f32>,
     pub blend: Option<SequencerBlendSetting>,
     pub note: Option<u8>,
+    pub articulation: Option<u8>,
+    pub tune: Option<f32>,
+    pub gate: Option<f32>,
+}
+
+/// An extra hit fired by a step: another instrument, another note, or both.
+/// Added with [`Sequencer::add_step_layer`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StepLayer {
+    /// Index into the owning sequencer's layer instruments; 0 is its own instrument
+    instrument: u8,
+    /// Optional MIDI note for the layer's instrument (0-127)
+    pub note: Option<u8>,
+    /// Velocity relative to the step's (0.0-1.0)
+    pub velocity: f32,
+}
+
+impl StepLayer {
+    /// Name of the instrument this layer fires, given the sequencer's own
+    /// instrument and its layer instruments
+    fn instrument<'a>(&self, own: &'a str, others: &'a [String]) -> Option<&'a str> {
+        match self.instrument {
+            0 => Some(own),
+            i => others.get(i as usize - 1).map(String::as_str),
+        }
+    }
 }
 
 /// Represents a single sequencer step with enabled state, velocity, optional blend setting, and optional MIDI note
@@ -36,6 +89,19 @@ pub struct SequencerStep {
     pub blend: Option<SequencerBlendSetting>,
     /// Optional MIDI note for this step (0-127). When set, overrides the instrument's global frequency.
     pub note: Option<u8>,
+    /// Optional instrument-defined articulation for this step (hi-hat: 0 = closed,
+    /// 1 = pedal, 2 = open). Instruments without articulations ignore it.
+    pub articulation: Option<u8>,
+    /// Optional tuning offset for this step in semitones (±`STEP_TUNE_MAX_SEMITONES`).
+    /// `GooeyEngine` adds it to the channel's tuning, so one voice can play at several
+    /// pitches.
+    pub tune: Option<f32>,
+    /// Optional gate length in steps (0.5 = half a step, 2.0 = two steps). When set,
+    /// the engine releases the instrument that long after the trigger; without one the
+    /// hit is a one-shot with no note-off.
+    pub gate: Option<f32>,
+    /// Extra hits fired with this step (layered drums, chord notes); unused slots are `None`
+    pub layers: [Option<StepLayer>; STEP_MAX_LAYERS],
 }
 
 impl Default for SequencerStep {
@@ -45,6 +111,10 @@ impl Default for SequencerStep {
             velocity: 1.0,
             blend: None,
             note: None,
+            articulation: None,
+            tune: None,
+            gate: None,
+            layers: [None; STEP_MAX_LAYERS],
         }
     }
 }
@@ -57,6 +127,10 @@ impl SequencerStep {
             velocity: 1.0,
             blend: None,
             note: None,
+            articulation: None,
+            tune: None,
+            gate: None,
+            layers: [None; STEP_MAX_LAYERS],
         }
     }
 
@@ -67,6 +141,10 @@ impl SequencerStep {
             velocity: velocity.clamp(0.0, 1.0),
             blend: None,
             note: None,
+            articulation: None,
+            tune: None,
+            gate: None,
+            layers: [None; STEP_MAX_LAYERS],
         }
     }
 
@@ -81,6 +159,10 @@ impl SequencerStep {
             velocity: velocity.clamp(0.0, 1.0),
             blend,
             note: None,
+            articulation: None,
+            tune: None,
+            gate: None,
+            layers: [None; STEP_MAX_LAYERS],
         }
     }
 }
@@ -91,6 +173,32 @@ impl From<bool> for SequencerStep {
     }
 }
 
+/// Steps copied out of a pattern by [`Sequencer::copy_steps`], ready to
+/// paste into the same or another sequencer. Layers keep the names of the
+/// instruments they fire, so they land on the same instruments wherever
+/// they are pasted.
+#[derive(Clone, Debug, Default)]
+pub struct PatternClip {
+    steps: Vec<SequencerStep>,
+    /// Instruments the steps' layers refer to: the source sequencer's own,
+    /// then its layer instruments
+    instruments: Vec<String>,
+}
+
+impl PatternClip {
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn steps(&self) -> &[SequencerStep] {
+        &self.steps
+    }
+}
+
 /// Trigger info from a sequencer tick
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct SequencerTrigger<'a> {
@@ -98,6 +206,48 @@ pub struct SequencerTrigger<'a> {
     pub velocity: f32,
     pub blend: Option<SequencerBlendSetting>,
     pub note: Option<u8>,
+    pub articulation: Option<u8>,
+    pub tune: Option<f32>,
+    /// Samples from the trigger to the note-off, for steps with a gate
+    pub gate_samples: Option<u64>,
+    /// The step's extra hits; resolve them with [`SequencerTrigger::layers`]
+    pub layers: [Option<StepLayer>; STEP_MAX_LAYERS],
+    /// Instruments the layers refer to, after the sequencer's own
+    pub layer_instruments: &'a [String],
+    /// Engine ids of the sequencer's own instrument (index 0) and its layer
+    /// instruments, `None` where a name has no instrument. Empty when the
+    /// sequencer isn't ticked by an [`Engine`](crate::engine::Engine).
+    pub targets: &'a [Option<InstrumentId>],
+}
+
+impl<'a> SequencerTrigger<'a> {
+    /// The step's extra hits as `(instrument, note, velocity)`, with the
+    /// velocity already scaled by the step's. Layers naming an instrument
+    /// the sequencer doesn't know are skipped.
+    pub fn layers(&self) -> impl Iterator<Item = (&'a str, Option<u8>, f32)> + '_ {
+        self.layers.iter().flatten().filter_map(move |layer| {
+            let instrument = layer.instrument(self.instrument_name, self.layer_instruments)?;
+            Some((instrument, layer.note, layer.velocity * self.velocity))
+        })
+    }
+
+    /// Engine id of the sequencer's own instrument.
+    pub fn instrument_id(&self) -> Option<InstrumentId> {
+        self.targets.first().copied().flatten()
+    }
+
+    /// [`SequencerTrigger::layers`] by engine id. Layers whose instrument
+    /// has no id are skipped.
+    pub fn layer_ids(&self) -> impl Iterator<Item = (InstrumentId, Option<u8>, f32)> + '_ {
+        self.layers.iter().flatten().filter_map(move |layer| {
+            let id = self
+                .targets
+                .get(layer.instrument as usize)
+                .copied()
+                .flatten()?;
+            Some((id, layer.note, layer.velocity * self.velocity))
+        })
+    }
 }
 
 /// State for a pending armed start. The sequencer counts down
@@ -111,6 +261,14 @@ struct ArmedStart {
     beat_position: f64,
 }
 
+/// A pattern waiting to replace the current one. Swapped in by the tick that
+/// reaches a step index divisible by `division`, so timing is untouched.
+#[derive(Clone, Debug)]
+struct QueuedPattern {
+    steps: Vec<SequencerStep>,
+    division: usize,
+}
+
 /// A sample-accurate step sequencer with per-step velocity and optional blend settings
 pub struct Sequencer {
     bpm: f32,
@@ -135,6 +293,14 @@ pub struct Sequencer {
 
     // Instrument to trigger
     instrument_name: String,
+    // Other instruments step layers trigger, addressed from 1
+    layer_instruments: Vec<String>,
+    // Engine ids of the instrument and the layer instruments, in the same
+    // order; kept the same length so resolving never allocates
+    targets: Vec<Option<InstrumentId>>,
+    // Engine instrument generation `targets` was resolved against (None =
+    // names changed since)
+    targets_generation: Option<u32>,
 
     // Whether the sequencer is running
     is_running: bool,
@@ -146,6 +312,14 @@ pub struct Sequencer {
     // ticks at `beat_position`. tick_with_settings counts down and fires
     // when the countdown reaches zero.
     armed_start: Option<ArmedStart>,
+
+    // Pattern to switch to at the next step divisible by its division.
+    queued_pattern: Option<QueuedPattern>,
+
+    // Groove played on top of the pattern, and how many samples off the grid
+    // it put the step now playing
+    groove: Option<GrooveTemplate>,
+    groove_shift: f32,
 }
 
 #[cfg(test)]
@@ -167,6 +341,94 @@ mod tests {
         assert_eq!(sequencer.get_step_blend(0), None);
     }
 
+    #[test]
+    fn test_rotate_reverse_and_invert() {
+        let mut sequencer =
+            Sequencer::with_pattern(120.0, 1000.0, vec![true, true, false, false], "kick");
+        sequencer.rotate(1);
+        assert_eq!(sequencer.pattern(), [false, true, true, false]);
+        sequencer.rotate(-2);
+        assert_eq!(sequencer.pattern(), [true, false, false, true]);
+        sequencer.rotate(4);
+        assert_eq!(sequencer.pattern(), [true, false, false, true]);
+
+        sequencer.set_step_velocity(0, 0.5);
+        sequencer.reverse();
+        assert_eq!(sequencer.pattern(), [true, false, false, true]);
+        assert_eq!(sequencer.get_step_velocity(3), 0.5);
+        sequencer.invert();
+        assert_eq!(sequencer.pattern(), [false, true, true, false]);
+        assert_eq!(sequencer.get_step_velocity(3), 0.5);
+    }
+
+    #[test]
+    fn test_paste_keeps_layers_on_their_instruments() {
+        let mut snare = Sequencer::with_pattern(120.0, 1000.0, vec![true; 4], "snare");
+        assert!(snare.add_step_layer(1, "snare", Some(40), 0.5));
+        assert!(snare.add_step_layer(1, "clap", None, 1.0));
+        let clip = snare.copy_steps(1, 8);
+        assert_eq!(clip.len(), 3);
+
+        let mut kick = Sequencer::with_pattern(120.0, 1000.0, vec![false; 4], "kick");
+        assert!(kick.add_step_layer(0, "hat", None, 1.0));
+        assert_eq!(kick.paste_steps(&clip, 2), 2);
+        assert_eq!(kick.pattern(), [false, false, true, true]);
+        assert_eq!(
+            kick.step_layers(2).collect::<Vec<_>>(),
+            [("snare", Some(40), 0.5), ("clap", None, 1.0)]
+        );
+        assert_eq!(kick.paste_steps(&clip, 4), 0);
+    }
+
+    #[test]
+    fn test_velocity_scale_compress_and_humanize() {
+        let steps = [(true, 0.2), (true, 0.6), (false, 1.0), (true, 1.0)]
+            .map(|(enabled, velocity)| SequencerStep::with_velocity(enabled, velocity));
+        let mut sequencer = Sequencer::with_velocity_pattern(120.0, 1000.0, steps.to_vec(), "kick");
+        // Enabled steps average 0.6; the disabled one doesn't count
+        sequencer.compress_velocities(0.5);
+        for (step, expected) in [0.4, 0.6, 1.0, 0.8].into_iter().enumerate() {
+            assert!((sequencer.get_step_velocity(step) - expected).abs() < 1e-6);
+        }
+        sequencer.scale_velocities(2.0);
+        assert!((sequencer.get_step_velocity(0) - 0.8).abs() < 1e-6);
+        assert_eq!(sequencer.get_step_velocity(1), 1.0);
+
+        let before = sequencer.get_step_velocity(0);
+        let mut rng = Rng::new(7);
+        sequencer.humanize_velocities(0.1, &mut rng);
+        let humanized = sequencer.get_step_velocity(0);
+        assert!(humanized != before && (humanized - before).abs() <= 0.1);
+        // Disabled steps are left alone
+        assert_eq!(sequencer.get_step_velocity(2), 1.0);
+    }
+
+    #[test]
+    fn test_bpm_change_rescales_the_step_in_progress() {
+        // 125 samples per step at 120 BPM / 1 kHz
+        let mut sequencer = Sequencer::with_pattern(120.0, 1000.0, vec![true; 4], "kick");
+        sequencer.start();
+        let mut hits = Vec::new();
+        for n in 0..1000 {
+            if n == 50 {
+                // 40% into step 0
+                let before = sequencer.step_position();
+                sequencer.set_bpm(60.0);
+                assert!((sequencer.step_position() - before).abs() < 0.01);
+            }
+            if sequencer.tick().is_some() {
+                hits.push(n);
+            }
+        }
+        // The rest of step 0 plays at the new tempo too (150 samples, not 75)
+        assert_eq!(hits, [0, 200, 450, 700, 950]);
+
+        // A stopped sequencer only takes the new step length
+        let mut stopped = Sequencer::with_pattern(120.0, 1000.0, vec![true; 4], "kick");
+        stopped.set_bpm(60.0);
+        assert_eq!(stopped.samples_per_step(), 250.0);
+    }
+
     #[test]
     fn test_step_with_velocity_preserves_blend_setting() {
         let mut sequencer = Sequencer::new(120.0, 44100.0, 4, "kick");
@@ -194,6 +456,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_step_articulation_reaches_trigger() {
+        let mut sequencer = Sequencer::with_pattern(120.0, 44100.0, vec![true; 2], "hihat");
+        sequencer.set_step_articulation(1, 2);
+        sequencer.set_step_with_velocity(1, true, 0.5);
+        assert_eq!(sequencer.get_step_articulation(1), Some(2));
+
+        sequencer.start();
+        let articulations: Vec<Option<u8>> = (0..20_000)
+            .filter_map(|_| sequencer.tick_with_settings().map(|t| t.articulation))
+            .collect();
+        assert_eq!(&articulations[..2], &[None, Some(2)]);
+    }
+
+    #[test]
+    fn test_step_gate_reaches_trigger_in_samples() {
+        let mut sequencer = Sequencer::with_pattern(120.0, 44100.0, vec![true; 2], "bass");
+        sequencer.set_step_gate(0, 0.5);
+        sequencer.set_step_gate(1, 100.0);
+        assert_eq!(sequencer.get_step_gate(1), Some(STEP_GATE_MAX_STEPS));
+
+        sequencer.start();
+        let gates: Vec<Option<u64>> = (0..20_000)
+            .filter_map(|_| sequencer.tick_with_settings().map(|t| t.gate_samples))
+            .collect();
+        // 5512.5 samples per step at 120 BPM
+        assert_eq!(&gates[..2], &[Some(2756), Some(88_200)]);
+
+        sequencer.clear_step_gate(0);
+        assert_eq!(sequencer.get_step_gate(0), None);
+    }
+
     #[test]
     fn test_swing_default_neutral() {
         let seq = Sequencer::new(120.0, 44100.0, 16, "test");
@@ -303,6 +597,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_groove_shifts_steps_and_scales_velocity() {
+        use crate::engine::groove::GROOVE_STEPS;
+
+        let run = |groove: Option<GrooveTemplate>| {
+            let mut seq = Sequencer::with_pattern(120.0, 44100.0, vec![true; 16], "test");
+            seq.set_groove(groove);
+            seq.start();
+            let mut hits: Vec<(i64, f32)> = Vec::new();
+            for _ in 0..44100 * 3 {
+                let sample = seq.sample_count() as i64;
+                if let Some((_, velocity)) = seq.tick() {
+                    hits.push((sample, velocity));
+                }
+            }
+            hits
+        };
+        let mut timing = [0.0; GROOVE_STEPS];
+        let mut velocity = [1.0; GROOVE_STEPS];
+        timing[1] = 0.2;
+        timing[2] = -0.1;
+        velocity[1] = 0.5;
+        let straight = run(None);
+        let grooved = run(Some(GrooveTemplate::from_steps(timing, velocity)));
+
+        // 5512.5 samples per step: step 1 lands 0.2 late, step 2 0.1 early,
+        // and the steps after them and the next bar stay on the grid
+        let shift = |i: usize| grooved[i].0 - straight[i].0;
+        assert!((shift(1) - 1102).abs() <= 1, "{}", shift(1));
+        assert!((shift(2) + 551).abs() <= 1, "{}", shift(2));
+        assert!(shift(3).abs() <= 1 && shift(16).abs() <= 1);
+        assert!((shift(17) - 1102).abs() <= 1);
+        assert_eq!((grooved[1].1, grooved[3].1), (0.5, 1.0));
+    }
+
     #[test]
     fn test_set_beat_position_exact_beats() {
         // 16-step pattern at 120 BPM, 44100 Hz
@@ -327,6 +656,18 @@ mod tests {
         assert_eq!(seq.sample_count, 0);
     }
 
+    #[test]
+    fn test_shorter_pattern_resets_the_playhead() {
+        let mut seq = Sequencer::new(120.0, 44100.0, 16, "kick");
+        seq.set_beat_position(2.5);
+        seq.set_pattern_with_velocity(vec![SequencerStep::new(true); 8]);
+        assert_eq!((seq.current_step, seq.playhead_step), (0, 0));
+
+        seq.set_beat_position(1.0);
+        seq.set_pattern(vec![true; 4]);
+        assert_eq!((seq.current_step, seq.playhead_step), (0, 0));
+    }
+
     #[test]
     fn test_set_beat_position_fractional() {
         // 16-step pattern at 120 BPM, 44100 Hz
@@ -487,6 +828,97 @@ mod tests {
         assert!(trigger.is_some());
         assert_eq!(seq.current_step(), 4); // beat 1.0 → step 4
     }
+    /// Tick `samples` times and return the steps that fired, with the sample
+    /// they fired on.
+    fn fired_steps(sequencer: &mut Sequencer, samples: u64) -> Vec<(usize, u64)> {
+        let mut fired = Vec::new();
+        for _ in 0..samples {
+            let at = sequencer.sample_count();
+            if sequencer.tick_with_settings().is_some() {
+                fired.push((sequencer.current_step(), at));
+            }
+        }
+        fired
+    }
+
+    #[test]
+    fn test_queued_pattern_switches_on_the_bar_in_phase() {
+        let mut sequencer = Sequencer::with_pattern(120.0, 48_000.0, vec![true; 16], "kick");
+        sequencer.start();
+        fired_steps(&mut sequencer, 3 * 6_000);
+
+        let mut next = vec![SequencerStep::new(false); 16];
+        next[0] = SequencerStep::with_velocity(true, 0.5);
+        sequencer.queue_pattern(next, 16);
+        assert!(sequencer.has_queued_pattern());
+
+        // Old pattern plays out the bar: steps 3..=15 still fire on the grid
+        let fired = fired_steps(&mut sequencer, 13 * 6_000);
+        assert_eq!(fired.len(), 13);
+        assert!(sequencer.has_queued_pattern());
+
+        // The new pattern's downbeat lands exactly one bar after the start
+        let at = sequencer.sample_count();
+        let trigger = sequencer.tick_with_settings().map(|t| t.velocity);
+        assert_eq!((trigger, at), (Some(0.5), 16 * 6_000));
+        assert!(!sequencer.has_queued_pattern());
+        assert!(fired_steps(&mut sequencer, 15 * 6_000).is_empty());
+    }
+
+    #[test]
+    fn test_set_length_pads_with_off_steps_and_wraps_at_the_new_end() {
+        let mut sequencer = Sequencer::with_pattern(120.0, 48_000.0, vec![true; 4], "kick");
+        sequencer.set_length(8);
+        assert_eq!(
+            sequencer.pattern(),
+            [true, true, true, true, false, false, false, false]
+        );
+        sequencer.set_step(6, true);
+        sequencer.start();
+        let steps: Vec<usize> = fired_steps(&mut sequencer, 9 * 6_000)
+            .into_iter()
+            .map(|(step, _)| step)
+            .collect();
+        assert_eq!(steps, [0, 1, 2, 3, 6, 0]);
+
+        sequencer.set_length(2);
+        assert_eq!(sequencer.pattern(), [true, true]);
+        assert!(sequencer.next_step() < 2 && sequencer.current_step() < 2);
+    }
+
+    #[test]
+    fn test_sync_to_beat_nudges_small_drift() {
+        // 120 BPM at 48 kHz: 6000 samples per step
+        let mut sequencer = Sequencer::with_pattern(120.0, 48_000.0, vec![true; 16], "kick");
+        sequencer.start();
+        assert_eq!(fired_steps(&mut sequencer, 3_000), [(0, 0)]);
+
+        // The clock is 600 samples (0.1 step) ahead: step 1 comes early
+        sequencer.sync_to_beat(0.6 / 4.0);
+        assert_eq!(fired_steps(&mut sequencer, 6_000), [(1, 5_400)]);
+    }
+
+    #[test]
+    fn test_sync_to_beat_jump_fires_each_step_once() {
+        let mut sequencer = Sequencer::with_pattern(120.0, 48_000.0, vec![true; 16], "kick");
+        sequencer.start();
+        assert_eq!(fired_steps(&mut sequencer, 3_000), [(0, 0)]);
+
+        // A peer restarts the bar two and a half steps later
+        sequencer.sync_to_beat(10.5 / 4.0);
+        assert_eq!(sequencer.current_step(), 10);
+        let fired: Vec<usize> = fired_steps(&mut sequencer, 12_000)
+            .into_iter()
+            .map(|(step, _)| step)
+            .collect();
+        assert_eq!(fired, [11, 12], "no replay of step 10, no skipped steps");
+
+        // Wrapping: the clock is just past the pattern end
+        sequencer.sync_to_beat(16.02 / 4.0);
+        let (step, at) = fired_steps(&mut sequencer, 6_000)[0];
+        assert_eq!(step, 1);
+        assert_eq!(at, 15_000 + 5_880);
+    }
 }
 
 impl Sequencer {
@@ -517,9 +949,15 @@ impl Sequencer {
             current_step: 0,
             playhead_step: 0,
             instrument_name: instrument_name.into(),
+            layer_instruments: Vec::new(),
+            targets: vec![None],
+            targets_generation: None,
             is_running: false,
             swing: SmoothedParam::new(0.5, 0.0, 1.0, sample_rate, DEFAULT_SMOOTH_TIME_MS),
             armed_start: None,
+            queued_pattern: None,
+            groove: None,
+            groove_shift: 0.0,
         }
     }
 
@@ -546,9 +984,15 @@ impl Sequencer {
             current_step: 0,
             playhead_step: 0,
             instrument_name: instrument_name.into(),
+            layer_instruments: Vec::new(),
+            targets: vec![None],
+            targets_generation: None,
             is_running: false,
             swing: SmoothedParam::new(0.5, 0.0, 1.0, sample_rate, DEFAULT_SMOOTH_TIME_MS),
             armed_start: None,
+            queued_pattern: None,
+            groove: None,
+            groove_shift: 0.0,
         }
     }
 
@@ -572,9 +1016,15 @@ impl Sequencer {
             current_step: 0,
             playhead_step: 0,
             instrument_name: instrument_name.into(),
+            layer_instruments: Vec::new(),
+            targets: vec![None],
+            targets_generation: None,
             is_running: false,
             swing: SmoothedParam::new(0.5, 0.0, 1.0, sample_rate, DEFAULT_SMOOTH_TIME_MS),
             armed_start: None,
+            queued_pattern: None,
+            groove: None,
+            groove_shift: 0.0,
         }
     }
 
@@ -594,6 +1044,7 @@ impl Sequencer {
         self.armed_start = None;
         self.is_running = true;
         self.next_trigger_sample = self.sample_count;
+        self.groove_shift = 0.0;
     }
 
     /// Stop the sequencer.
@@ -614,6 +1065,7 @@ impl Sequencer {
         self.step_start_sample = 0;
         self.current_step = 0;
         self.playhead_step = 0;
+        self.groove_shift = 0.0;
     }
 
     /// Arm the sequencer to start in `samples_until_start` ticks with the
@@ -679,12 +1131,89 @@ impl Sequencer {
         self.next_trigger_sample = (self.samples_per_step as f64
             - fractional_step * self.samples_per_step as f64)
             .round() as u64;
+        self.groove_shift = 0.0;
+    }
+
+    /// Pull a running sequencer onto an external beat clock without
+    /// double-firing or dropping steps.
+    ///
+    /// `beat_position` is the external clock's position in quarter notes at
+    /// this sample (e.g. an Ableton Link session beat); it is wrapped to the
+    /// pattern length. Drift of up to `SYNC_NUDGE_STEPS` is absorbed by moving
+    /// the next step boundary earlier or later. A bigger jump (a peer
+    /// restarting the bar, a tempo change on another device) re-seats the
+    /// cursor so the next boundary fires the step the clock is heading into;
+    /// the step in progress is not re-fired and no skipped steps are played.
+    ///
+    /// A stopped sequencer is just moved to the position, as with
+    /// [`set_beat_position`](Self::set_beat_position).
+    pub fn sync_to_beat(&mut self, beat_position: f64) {
+        let step_count = self.pattern.len();
+        if step_count == 0 || !beat_position.is_finite() {
+            return;
+        }
+        if !self.is_running {
+            self.set_beat_position(beat_position);
+            return;
+        }
+
+        let samples_per_step = self.samples_per_step as f64;
+        let len = step_count as f64;
+        let target = (beat_position * 4.0).rem_euclid(len);
+
+        // Where the cursor is now, in steps: the boundary ahead of us is
+        // `current_step`, `remaining` samples away.
+        let remaining = self.next_trigger_sample.saturating_sub(self.sample_count) as f64;
+        let local = (self.current_step as f64 - remaining / samples_per_step).rem_euclid(len);
+        let error = (target - local + len / 2.0).rem_euclid(len) - len / 2.0;
+
+        if error.abs() <= SYNC_NUDGE_STEPS {
+            // Ahead of the clock (negative error) pushes the boundary later
+            let nudged = remaining - error * samples_per_step;
+            self.next_trigger_sample = self.sample_count + nudged.max(0.0).round() as u64;
+            return;
+        }
+
+        let next_boundary = target.ceil();
+        self.armed_start = None;
+        self.current_step = (next_boundary as usize) % step_count;
+        self.playhead_step = (target.floor() as usize) % step_count;
+        self.next_trigger_sample =
+            self.sample_count + ((next_boundary - target) * samples_per_step).round() as u64;
+        self.step_start_sample = self
+            .sample_count
+            .saturating_sub((target.fract() * samples_per_step).round() as u64);
+        self.groove_shift = 0.0;
     }
 
     /// Set the BPM and recalculate timing
+    ///
+    /// While running, the step in progress is rescaled too: the part already
+    /// played and the part still to come both stretch to the new tempo, so
+    /// the playhead keeps its place in the step and the next step lands
+    /// where the new tempo puts it instead of after the old step length.
     pub fn set_bpm(&mut self, bpm: f32) {
+        let previous = self.samples_per_step;
         self.bpm = bpm;
         self.samples_per_step = Self::calculate_samples_per_step(bpm, self.sample_rate);
+
+        let remaining = self.next_trigger_sample.saturating_sub(self.sample_count);
+        if !self.is_running
+            || remaining == 0
+            || !(self.samples_per_step.is_finite() && previous > 0.0)
+        {
+            return;
+        }
+        let ratio = self.samples_per_step as f64 / previous as f64;
+        let elapsed = self.sample_count.saturating_sub(self.step_start_sample);
+        let elapsed = (elapsed as f64 * ratio).round() as u64;
+        let remaining = (remaining as f64 * ratio).round() as u64;
+        // The counters are only compared with each other, so lift them all
+        // when the stretched step would have started before sample zero
+        self.sample_count += elapsed.saturating_sub(self.sample_count);
+        self.step_start_sample = self.sample_count - elapsed;
+        self.next_trigger_sample = self.sample_count + remaining.max(1);
+        self.groove_shift *= ratio as f32;
     }
 
     /// Set a step's enabled state in the pattern (maintains current velocity)
@@ -701,13 +1230,12 @@ impl Sequencer {
         }
     }
 
-    /// Set both enabled state and velocity for a step (preserves any blend setting and note)
+    /// Set both enabled state and velocity for a step (preserves any blend setting, note
+    /// and articulation)
     pub fn set_step_with_velocity(&mut self, step: usize, enabled: bool, velocity: f32) {
-        if step < self.pattern.len() {
-            let blend = self.pattern[step].blend;
-            let note = self.pattern[step].note;
-            self.pattern[step] = SequencerStep::with_velocity_and_blend(enabled, velocity, blend);
-            self.pattern[step].note = note;
+        if let Some(slot) = self.pattern.get_mut(step) {
+            slot.enabled = enabled;
+            slot.velocity = velocity.clamp(0.0, 1.0);
         }
     }
 
@@ -730,6 +1258,16 @@ impl Sequencer {
             if let Some(note) = settings.note {
                 self.pattern[step].note = Some(note);
             }
+            if let Some(articulation) = settings.articulation {
+                self.pattern[step].articulation = Some(articulation);
+            }
+            if let Some(tune) = settings.tune {
+                self.pattern[step].tune = Some(clamp_step_tune(tune));
+            }
+            if let Some(gate) = settings.gate {
+                self.pattern[step].gate =
+                    Some(gate.clamp(STEP_GATE_MIN_STEPS, STEP_GATE_MAX_STEPS));
+            }
         }
     }
 
@@ -796,6 +1334,116 @@ impl Sequencer {
         self.pattern.get(step).and_then(|s| s.note)
     }
 
+    /// Set a step's articulation (instrument-defined, e.g. hi-hat closed/pedal/open)
+    pub fn set_step_articulation(&mut self, step: usize, articulation: u8) {
+        if step < self.pattern.len() {
+            self.pattern[step].articulation = Some(articulation);
+        }
+    }
+
+    /// Clear a step's articulation (the instrument plays its default)
+    pub fn clear_step_articulation(&mut self, step: usize) {
+        if step < self.pattern.len() {
+            self.pattern[step].articulation = None;
+        }
+    }
+
+    /// Get a step's articulation
+    pub fn get_step_articulation(&self, step: usize) -> Option<u8> {
+        self.pattern.get(step).and_then(|s| s.articulation)
+    }
+
+    /// Set a step's tuning offset in semitones (clamped to ±`STEP_TUNE_MAX_SEMITONES`)
+    pub fn set_step_tune(&mut self, step: usize, semitones: f32) {
+        if step < self.pattern.len() {
+            self.pattern[step].tune = Some(clamp_step_tune(semitones));
+        }
+    }
+
+    /// Clear a step's tuning offset (the instrument plays at its own tuning)
+    pub fn clear_step_tune(&mut self, step: usize) {
+        if step < self.pattern.len() {
+            self.pattern[step].tune = None;
+        }
+    }
+
+    /// Get a step's tuning offset in semitones
+    pub fn get_step_tune(&self, step: usize) -> Option<f32> {
+        self.pattern.get(step).and_then(|s| s.tune)
+    }
+
+    /// Set a step's gate length in steps (clamped to `STEP_GATE_MIN_STEPS`-`STEP_GATE_MAX_STEPS`)
+    pub fn set_step_gate(&mut self, step: usize, steps: f32) {
+        if step < self.pattern.len() {
+            self.pattern[step].gate = Some(steps.clamp(STEP_GATE_MIN_STEPS, STEP_GATE_MAX_STEPS));
+        }
+    }
+
+    /// Clear a step's gate (the hit plays as a one-shot)
+    pub fn clear_step_gate(&mut self, step: usize) {
+        if step < self.pattern.len() {
+            self.pattern[step].gate = None;
+        }
+    }
+
+    /// Get a step's gate length in steps
+    pub fn get_step_gate(&self, step: usize) -> Option<f32> {
+        self.pattern.get(step).and_then(|s| s.gate)
+    }
+
+    /// Layer another hit onto a step: `instrument` (any instrument, including
+    /// the sequencer's own) fires with the step at `velocity` times the
+    /// step's, playing `note` if given. Layering several notes on one poly
+    /// instrument plays a chord; a mono instrument only keeps the last.
+    /// Layers fire only while the step is enabled.
+    ///
+    /// Returns false for a step out of range or one that already has
+    /// `STEP_MAX_LAYERS` layers.
+    pub fn add_step_layer(
+        &mut self,
+        step: usize,
+        instrument: &str,
+        note: Option<u8>,
+        velocity: f32,
+    ) -> bool {
+        let Some(slot) = self
+            .pattern
+            .get(step)
+            .and_then(|s| s.layers.iter().position(Option::is_none))
+        else {
+            return false;
+        };
+        let Some(index) = self.layer_instrument_index(instrument) else {
+            return false;
+        };
+        self.pattern[step].layers[slot] = Some(StepLayer {
+            instrument: index,
+            note: note.map(|n| n.min(127)),
+            velocity: velocity.clamp(0.0, 1.0),
+        });
+        true
+    }
+
+    /// Remove every layer from a step (the step still plays its own hit)
+    pub fn clear_step_layers(&mut self, step: usize) {
+        if step < self.pattern.len() {
+            self.pattern[step].layers = [None; STEP_MAX_LAYERS];
+        }
+    }
+
+    /// A step's layers as `(instrument, note, relative velocity)`
+    pub fn step_layers(&self, step: usize) -> impl Iterator<Item = (&str, Option<u8>, f32)> + '_ {
+        self.pattern
+            .get(step)
+            .into_iter()
+            .flat_map(|s| s.layers.iter().flatten())
+            .filter_map(move |layer| {
+                let instrument =
+                    layer.instrument(&self.instrument_name, &self.layer_instruments)?;
+                Some((instrument, layer.note, layer.velocity))
+            })
+    }
+
     /// Set MIDI notes for all steps. Values of 255 clear the note for that step.
     pub fn set_note_pattern(&mut self, notes: &[u8]) {
         let len = notes.len().min(self.pattern.len());
@@ -825,6 +1473,9 @@ impl Sequencer {
         if self.current_step >= self.pattern.len() {
             self.current_step = 0;
         }
+        if self.playhead_step >= self.pattern.len() {
+            self.playhead_step = 0;
+        }
     }
 
     /// Set the entire pattern with velocity information
@@ -834,6 +1485,164 @@ impl Sequencer {
         if self.current_step >= self.pattern.len() {
             self.current_step = 0;
         }
+        if self.playhead_step >= self.pattern.len() {
+            self.playhead_step = 0;
+        }
+    }
+
+    /// Lengthen or shorten the pattern to `steps` steps. Added steps are off;
+    /// steps past the new end are dropped.
+    pub fn set_length(&mut self, steps: usize) {
+        self.pattern.resize(steps, SequencerStep::new(false));
+        if self.current_step >= self.pattern.len() {
+            self.current_step = 0;
+        }
+        if self.playhead_step >= self.pattern.len() {
+            self.playhead_step = 0;
+        }
+    }
+
+    /// Copy `len` steps from `start` (clipped to the pattern).
+    pub fn copy_steps(&self, start: usize, len: usize) -> PatternClip {
+        let start = start.min(self.pattern.len());
+        let end = start.saturating_add(len).min(self.pattern.len());
+        PatternClip {
+            steps: self.pattern[start..end].to_vec(),
+            instruments: core::iter::once(&self.instrument_name)
+                .chain(&self.layer_instruments)
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Paste `clip` over the steps from `at` on, stopping at the end of the
+    /// pattern. Layers are re-pointed at the instruments they fired in the
+    /// clip's source; a layer whose instrument can't be added (the sequencer
+    /// already layers `u8::MAX` others) is dropped.
+    ///
+    /// Returns the number of steps pasted.
+    pub fn paste_steps(&mut self, clip: &PatternClip, at: usize) -> usize {
+        let count = clip.len().min(self.pattern.len().saturating_sub(at));
+        for (offset, step) in clip.steps[..count].iter().enumerate() {
+            let mut step = *step;
+            for slot in &mut step.layers {
+                *slot = slot.and_then(|layer| {
+                    let name = clip.instruments.get(layer.instrument as usize)?;
+                    let instrument = self.layer_instrument_index(name)?;
+                    Some(StepLayer {
+                        instrument,
+                        ..layer
+                    })
+                });
+            }
+            self.pattern[at + offset] = step;
+        }
+        count
+    }
+
+    /// Index a layer uses to fire `instrument`, adding it to the layer
+    /// instruments if needed. `None` when there is no room for another.
+    fn layer_instrument_index(&mut self, instrument: &str) -> Option<u8> {
+        if instrument == self.instrument_name {
+            Some(0)
+        } else if let Some(i) = self.layer_instruments.iter().position(|n| n == instrument) {
+            Some(i as u8 + 1)
+        } else if self.layer_instruments.len() < u8::MAX as usize {
+            self.layer_instruments.push(instrument.to_string());
+            self.targets.push(None);
+            self.targets_generation = None;
+            Some(self.layer_instruments.len() as u8)
+        } else {
+            None
+        }
+    }
+
+    /// Rotate the pattern by `steps`: positive moves every step later
+    /// (the last wraps round to the front), negative moves them earlier.
+    pub fn rotate(&mut self, steps: isize) {
+        if self.pattern.is_empty() {
+            return;
+        }
+        let shift = steps.rem_euclid(self.pattern.len() as isize) as usize;
+        self.pattern.rotate_right(shift);
+    }
+
+    /// Play the pattern backwards.
+    pub fn reverse(&mut self) {
+        self.pattern.reverse();
+    }
+
+    /// Turn every enabled step off and every disabled step on, keeping
+    /// their velocities and other settings.
+    pub fn invert(&mut self) {
+        for step in &mut self.pattern {
+            step.enabled = !step.enabled;
+        }
+    }
+
+    /// Nudge each enabled step's velocity by a random amount of up to
+    /// `amount` either way (0.0-1.0), drawn from `rng`.
+    pub fn humanize_velocities(&mut self, amount: f32, rng: &mut Rng) {
+        let amount = amount.clamp(0.0, 1.0);
+        for step in self.pattern.iter_mut().filter(|s| s.enabled) {
+            step.velocity = (step.velocity + rng.next_bipolar() * amount).clamp(0.0, 1.0);
+        }
+    }
+
+    /// Multiply every step's velocity by `scale` (clamped to 0.0-1.0).
+    pub fn scale_velocities(&mut self, scale: f32) {
+        let scale = scale.max(0.0);
+        for step in &mut self.pattern {
+            step.velocity = (step.velocity * scale).clamp(0.0, 1.0);
+        }
+    }
+
+    /// Pull the enabled steps' velocities toward their average: 0.0 leaves
+    /// them alone, 1.0 plays every hit at the average.
+    pub fn compress_velocities(&mut self, amount: f32) {
+        let amount = amount.clamp(0.0, 1.0);
+        let (sum, count) = self
+            .pattern
+            .iter()
+            .filter(|s| s.enabled)
+            .fold((0.0, 0), |(sum, count), s| (sum + s.velocity, count + 1));
+        if count == 0 {
+            return;
+        }
+        let mean = sum / count as f32;
+        for step in self.pattern.iter_mut().filter(|s| s.enabled) {
+            step.velocity = mean + (step.velocity - mean) * (1.0 - amount);
+        }
+    }
+
+    /// Switch to `pattern` when the cursor next reaches a step that is a
+    /// multiple of `division_steps` (16 = the next bar of a 16-step pattern,
+    /// 1 = the next step). The step clock is not touched, so the switch keeps
+    /// the running phase to the sample. A stopped sequencer switches at once.
+    /// Queuing again replaces the pattern waiting; an empty pattern is ignored.
+    pub fn queue_pattern(&mut self, pattern: Vec<SequencerStep>, division_steps: usize) {
+        if pattern.is_empty() {
+            return;
+        }
+        if !self.is_running {
+            self.queued_pattern = None;
+            self.set_pattern_with_velocity(pattern);
+            return;
+        }
+        self.queued_pattern = Some(QueuedPattern {
+            steps: pattern,
+            division: division_steps.max(1),
+        });
+    }
+
+    /// Drop a pattern queued by [`queue_pattern`](Self::queue_pattern).
+    pub fn cancel_queued_pattern(&mut self) {
+        self.queued_pattern = None;
+    }
+
+    /// Whether a queued pattern is waiting for its boundary.
+    pub fn has_queued_pattern(&self) -> bool {
+        self.queued_pattern.is_some()
     }
 
     /// Get the current playhead step (the step currently being played)
@@ -857,6 +1666,26 @@ impl Sequencer {
         &self.instrument_name
     }
 
+    /// Look up the engine ids of the instrument and layer instruments,
+    /// unless they were already resolved at this `generation` of the
+    /// engine's instruments. Writes in place, so it is safe on the audio
+    /// thread.
+    #[cfg(feature = "std")]
+    pub(crate) fn resolve_targets(
+        &mut self,
+        generation: u32,
+        lookup: impl Fn(&str) -> Option<InstrumentId>,
+    ) {
+        if self.targets_generation == Some(generation) {
+            return;
+        }
+        let names = core::iter::once(&self.instrument_name).chain(&self.layer_instruments);
+        for (target, name) in self.targets.iter_mut().zip(names) {
+            *target = lookup(name);
+        }
+        self.targets_generation = Some(generation);
+    }
+
     /// Set the swing amount (0.0-1.0, where 0.5 = no swing)
     ///
     /// Swing delays off-beat steps (odd-numbered: 1, 3, 5...) to create a groovy feel.
@@ -873,6 +1702,17 @@ impl Sequencer {
         self.swing.get()
     }
 
+    /// Play the pattern with a groove's timing and velocity offsets, or
+    /// straight with `None`. Takes effect from the next step.
+    pub fn set_groove(&mut self, groove: Option<GrooveTemplate>) {
+        self.groove = groove;
+    }
+
+    /// The groove applied to the pattern, if any
+    pub fn groove(&self) -> Option<&GrooveTemplate> {
+        self.groove.as_ref()
+    }
+
     /// Check if a step index is a "swing step" (off-beat)
     #[inline]
     fn is_swing_step(&self, step: usize) -> bool {
@@ -915,17 +1755,39 @@ impl Sequencer {
             // Record when this step started (for beat-position queries)
             self.step_start_sample = self.sample_count;
 
+            if self
+                .queued_pattern
+                .as_ref()
+                .is_some_and(|queued| self.current_step.is_multiple_of(queued.division))
+            {
+                if let Some(queued) = self.queued_pattern.take() {
+                    self.pattern = queued.steps;
+                    self.current_step %= self.pattern.len();
+                }
+            }
+
             // Update playhead to show the step that's about to play
             self.playhead_step = self.current_step;
 
             // Check if this step should trigger
             let step = &self.pattern[self.current_step];
             if step.enabled {
+                let groove_velocity = self
+                    .groove
+                    .map_or(1.0, |groove| groove.velocity(self.current_step));
                 should_trigger = Some(SequencerTrigger {
                     instrument_name: self.instrument_name.as_str(),
-                    velocity: step.velocity,
+                    velocity: (step.velocity * groove_velocity).clamp(0.0, 1.0),
                     blend: step.blend,
                     note: step.note,
+                    articulation: step.articulation,
+                    tune: step.tune,
+                    gate_samples: step
+                        .gate
+                        .map(|gate| ((gate * self.samples_per_step).round() as u64).max(1)),
+                    layers: step.layers,
+                    layer_instruments: &self.layer_instruments,
+                    targets: &self.targets,
                 });
             }
 
@@ -941,10 +1803,20 @@ impl Sequencer {
                 -swing_offset
             };
 
+            // A groove moves each step off the grid by its own offset, so the
+            // gap to the next step changes by the difference between the two
+            let groove_shift = self.groove.map_or(0.0, |groove| {
+                groove.timing(self.current_step) * self.samples_per_step
+            });
+            let groove_offset = groove_shift - self.groove_shift;
+            self.groove_shift = groove_shift;
+
             // Calculate the next trigger sample (accumulate fractional samples for accuracy)
-            self.next_trigger_sample =
-                (self.next_trigger_sample as f32 + self.samples_per_step + signed_swing_offset)
-                    .round() as u64;
+            self.next_trigger_sample = (self.next_trigger_sample as f32
+                + self.samples_per_step
+                + signed_swing_offset
+                + groove_offset)
+                .round() as u64;
         }
 
         self.sample_count += 1;
@@ -1001,6 +1873,22 @@ impl Sequencer {
         self.next_trigger_sample
     }
 
+    /// Playhead position in steps at the sample last ticked: the current
+    /// step plus the fraction of it played (swing-aware). Step boundaries
+    /// land exactly on the sample that triggers the step.
+    pub fn step_position(&self) -> f64 {
+        let step_duration = self
+            .next_trigger_sample
+            .saturating_sub(self.step_start_sample);
+        let frac = if step_duration > 0 {
+            let elapsed = self.sample_count.saturating_sub(self.step_start_sample + 1);
+            (elapsed as f64 / step_duration as f64).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        self.playhead_step as f64 + frac
+    }
+
     /// Get samples per step (useful for UI timing calculations)
     pub fn samples_per_step(&self) -> f32 {
         self.samples_per_step
@@ -0,0 +1,102 @@
+//! Per-step note overrides
+//!
+//! A sequencer step can carry a MIDI note for a pitched instrument. The
+//! instrument's own frequency is saved on the first step with a note and put
+//! back on the first step without one, so a pattern that only notes some
+//! steps leaves the rest at the user's setting. Both [`Engine`](super::Engine)
+//! and the FFI engine sequence notes through [`StepNote`], so the two behave
+//! the same; each applies the resulting [`NoteChange`] in its own parameter
+//! units.
+
+use crate::music::{quantize_to_scale, NoteName, Scale};
+
+/// What a sequencer hit does to its instrument's frequency.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NoteChange {
+    /// Play this MIDI note (already snapped to the key, if any).
+    Play(u8),
+    /// Put the instrument's own normalized frequency back.
+    Restore(f32),
+    /// Leave the frequency alone.
+    Keep,
+}
+
+/// Saved-frequency state for one instrument's per-step notes.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StepNote {
+    saved: Option<f32>,
+}
+
+impl StepNote {
+    /// Resolve a hit's `note`, snapped into `key` when one is set.
+    /// `frequency` is the instrument's current normalized frequency, or
+    /// `None` for an unpitched instrument; it is saved on the first note.
+    pub fn resolve(
+        &mut self,
+        note: Option<u8>,
+        key: Option<(NoteName, Scale)>,
+        frequency: Option<f32>,
+    ) -> NoteChange {
+        match note {
+            Some(note) => {
+                if self.saved.is_none() {
+                    self.saved = frequency;
+                }
+                NoteChange::Play(match key {
+                    Some((root, scale)) => quantize_to_scale(note, root, scale),
+                    None => note,
+                })
+            }
+            None => self
+                .saved
+                .take()
+                .map_or(NoteChange::Keep, NoteChange::Restore),
+        }
+    }
+
+    /// Forget the saved frequency, e.g. before a bounce restarts the pattern.
+    pub fn clear(&mut self) {
+        self.saved = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frequency_is_saved_on_the_first_note_and_restored_once() {
+        let mut step = StepNote::default();
+        assert_eq!(step.resolve(None, None, Some(0.3)), NoteChange::Keep);
+        assert_eq!(
+            step.resolve(Some(40), None, Some(0.3)),
+            NoteChange::Play(40)
+        );
+        // The instrument now sits at the note; that is not what gets restored
+        assert_eq!(
+            step.resolve(Some(43), None, Some(0.8)),
+            NoteChange::Play(43)
+        );
+        assert_eq!(
+            step.resolve(None, None, Some(0.8)),
+            NoteChange::Restore(0.3)
+        );
+        assert_eq!(step.resolve(None, None, Some(0.3)), NoteChange::Keep);
+    }
+
+    #[test]
+    fn notes_snap_into_the_key() {
+        let mut step = StepNote::default();
+        // C# is not in C major
+        assert_eq!(
+            step.resolve(Some(61), Some((NoteName::C, Scale::Major)), None),
+            NoteChange::Play(quantize_to_scale(61, NoteName::C, Scale::Major))
+        );
+        assert_ne!(
+            step.resolve(Some(61), Some((NoteName::C, Scale::Major)), None),
+            NoteChange::Play(61)
+        );
+        // Unpitched: nothing saved, nothing to restore
+        assert_eq!(step.resolve(None, None, None), NoteChange::Keep);
+    }
+}
@@ -1,3 +1,7 @@
+use crate::max_curve::max_curve;
+#[cfg(not(feature = "std"))]
+use crate::prelude::Float;
+
 /// Curve shape for envelope phases
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum EnvelopeCurve {
@@ -7,6 +11,10 @@ pub enum EnvelopeCurve {
     /// < 1.0: Fast initial change, slow approach to target (punchy pitch drop)
     /// > 1.0: Slow initial change, fast approach to target (softer)
     Exponential(f32),
+    /// Max/MSP curve~ shape, as used by `SegmentEnvelope` segments (-1.0 to 1.0).
+    /// Positive amounts are exponential (slow start, fast end), negative ones
+    /// logarithmic (fast start, slow end).
+    Curved(f32),
 }
 
 impl Default for EnvelopeCurve {
@@ -22,18 +30,66 @@ impl EnvelopeCurve {
         match self {
             EnvelopeCurve::Linear => progress,
             EnvelopeCurve::Exponential(c) => progress.powf(c.clamp(0.1, 10.0)),
+            EnvelopeCurve::Curved(c) => max_curve(progress, c.clamp(-1.0, 1.0)),
         }
     }
 }
 
+/// What a trigger does to an envelope that is still sounding
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RetriggerMode {
+    /// Restart the attack from zero (default)
+    #[default]
+    Reset,
+    /// Restart the attack from the current level, so overlapping hits and
+    /// stolen voices don't click
+    Legato,
+}
+
+/// Everything about an envelope except its segment times and sustain level:
+/// the per-segment curves, retrigger behavior and velocity sensitivity.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EnvelopeShape {
+    pub attack_curve: EnvelopeCurve,
+    pub decay_curve: EnvelopeCurve,
+    pub release_curve: EnvelopeCurve,
+    pub retrigger: RetriggerMode,
+    /// How much a soft hit lowers the envelope's level (0.0 = ignore
+    /// velocity, 1.0 = level equals velocity)
+    pub velocity_to_level: f32,
+    /// How much a soft hit lengthens the attack (0.0 = ignore velocity,
+    /// 1.0 = up to 4x the attack time at zero velocity)
+    pub velocity_to_attack: f32,
+}
+
+impl Default for EnvelopeShape {
+    fn default() -> Self {
+        Self {
+            attack_curve: EnvelopeCurve::Linear,
+            decay_curve: EnvelopeCurve::Linear,
+            release_curve: EnvelopeCurve::Linear,
+            retrigger: RetriggerMode::Reset,
+            velocity_to_level: 0.0,
+            velocity_to_attack: 0.0,
+        }
+    }
+}
+
+/// Longest attack stretch from `velocity_to_attack`, beyond the base time
+const VELOCITY_ATTACK_STRETCH: f32 = 3.0;
+
 #[derive(Clone, Copy, Debug)]
 pub struct ADSRConfig {
-    pub attack_time: f32,            // seconds
-    pub decay_time: f32,             // seconds
-    pub sustain_level: f32,          // 0.0 to 1.0
-    pub release_time: f32,           // seconds
-    pub attack_curve: EnvelopeCurve, // Curve shape for attack phase
-    pub decay_curve: EnvelopeCurve,  // Curve shape for decay phase
+    pub attack_time: f32,             // seconds
+    pub decay_time: f32,              // seconds
+    pub sustain_level: f32,           // 0.0 to 1.0
+    pub release_time: f32,            // seconds
+    pub attack_curve: EnvelopeCurve,  // Curve shape for attack phase
+    pub decay_curve: EnvelopeCurve,   // Curve shape for decay phase
+    pub release_curve: EnvelopeCurve, // Curve shape for release phase
+    pub retrigger: RetriggerMode,     // Behavior when triggered while sounding
+    pub velocity_to_level: f32,       // 0.0 to 1.0, see EnvelopeShape
+    pub velocity_to_attack: f32,      // 0.0 to 1.0, see EnvelopeShape
 }
 
 impl ADSRConfig {
@@ -45,6 +101,10 @@ impl ADSRConfig {
             release_time: release.max(0.001),    // Minimum release
             attack_curve: EnvelopeCurve::Linear, // Default to linear for backward compatibility
             decay_curve: EnvelopeCurve::Linear,  // Default to linear for backward compatibility
+            release_curve: EnvelopeCurve::Linear,
+            retrigger: RetriggerMode::Reset,
+            velocity_to_level: 0.0,
+            velocity_to_attack: 0.0,
         }
     }
 
@@ -60,22 +120,69 @@ impl ADSRConfig {
         self
     }
 
+    /// Builder method to set release curve
+    pub fn with_release_curve(mut self, curve: EnvelopeCurve) -> Self {
+        self.release_curve = curve;
+        self
+    }
+
+    /// Builder method to set retrigger behavior
+    pub fn with_retrigger(mut self, retrigger: RetriggerMode) -> Self {
+        self.retrigger = retrigger;
+        self
+    }
+
+    /// Builder method to set velocity sensitivity (each 0.0 to 1.0)
+    pub fn with_velocity_sensitivity(mut self, to_level: f32, to_attack: f32) -> Self {
+        self.velocity_to_level = to_level.clamp(0.0, 1.0);
+        self.velocity_to_attack = to_attack.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Builder method to set curves, retrigger and velocity sensitivity at once
+    pub fn with_shape(self, shape: EnvelopeShape) -> Self {
+        self.with_attack_curve(shape.attack_curve)
+            .with_decay_curve(shape.decay_curve)
+            .with_release_curve(shape.release_curve)
+            .with_retrigger(shape.retrigger)
+            .with_velocity_sensitivity(shape.velocity_to_level, shape.velocity_to_attack)
+    }
+
+    /// The curves, retrigger and velocity sensitivity of this config
+    pub fn shape(&self) -> EnvelopeShape {
+        EnvelopeShape {
+            attack_curve: self.attack_curve,
+            decay_curve: self.decay_curve,
+            release_curve: self.release_curve,
+            retrigger: self.retrigger,
+            velocity_to_level: self.velocity_to_level,
+            velocity_to_attack: self.velocity_to_attack,
+        }
+    }
+
     pub fn default() -> Self {
         Self::new(0.01, 0.3, 0.7, 0.5)
     }
 }
 
 pub struct Envelope {
-    pub attack_time: f32,            // seconds
-    pub decay_time: f32,             // seconds
-    pub sustain_level: f32,          // 0.0 to 1.0
-    pub release_time: f32,           // seconds
-    pub attack_curve: EnvelopeCurve, // Curve shape for attack phase
-    pub decay_curve: EnvelopeCurve,  // Curve shape for decay phase
-    pub current_time: f32,           // current time in the envelope
+    pub attack_time: f32,             // seconds
+    pub decay_time: f32,              // seconds
+    pub sustain_level: f32,           // 0.0 to 1.0
+    pub release_time: f32,            // seconds
+    pub attack_curve: EnvelopeCurve,  // Curve shape for attack phase
+    pub decay_curve: EnvelopeCurve,   // Curve shape for decay phase
+    pub release_curve: EnvelopeCurve, // Curve shape for release phase
+    pub retrigger: RetriggerMode,     // Behavior when triggered while sounding
+    pub velocity_to_level: f32,       // 0.0 to 1.0
+    pub velocity_to_attack: f32,      // 0.0 to 1.0
+    pub current_time: f32,            // current time in the envelope
     pub is_active: bool,
     pub trigger_time: f64,               // when the envelope was triggered
     pub release_time_start: Option<f64>, // when release was triggered
+    start_level: f32,                    // attack start (non-zero after a legato retrigger)
+    level: f32,                          // peak level from the last trigger's velocity
+    attack_scale: f32,                   // attack stretch from the last trigger's velocity
 }
 
 impl Envelope {
@@ -92,10 +199,17 @@ impl Envelope {
             release_time: config.release_time,
             attack_curve: config.attack_curve,
             decay_curve: config.decay_curve,
+            release_curve: config.release_curve,
+            retrigger: config.retrigger,
+            velocity_to_level: config.velocity_to_level,
+            velocity_to_attack: config.velocity_to_attack,
             current_time: 0.0,
             is_active: false,
             trigger_time: 0.0,
             release_time_start: None,
+            start_level: 0.0,
+            level: 1.0,
+            attack_scale: 1.0,
         }
     }
 
@@ -106,6 +220,10 @@ impl Envelope {
         self.release_time = config.release_time;
         self.attack_curve = config.attack_curve;
         self.decay_curve = config.decay_curve;
+        self.release_curve = config.release_curve;
+        self.retrigger = config.retrigger;
+        self.velocity_to_level = config.velocity_to_level;
+        self.velocity_to_attack = config.velocity_to_attack;
     }
 
     /// Set attack curve directly
@@ -118,6 +236,11 @@ impl Envelope {
         self.decay_curve = curve;
     }
 
+    /// Set release curve directly
+    pub fn set_release_curve(&mut self, curve: EnvelopeCurve) {
+        self.release_curve = curve;
+    }
+
     /// Set attack time directly (more efficient than set_config for modulation)
     pub fn set_attack_time(&mut self, attack_time: f32) {
         self.attack_time = attack_time;
@@ -139,6 +262,22 @@ impl Envelope {
     }
 
     pub fn trigger(&mut self, time: f64) {
+        self.trigger_with_velocity(time, 1.0);
+    }
+
+    /// Trigger with a velocity (0.0 to 1.0) that scales the level and
+    /// stretches the attack according to the velocity sensitivity settings.
+    pub fn trigger_with_velocity(&mut self, time: f64, velocity: f32) {
+        let softness = 1.0 - velocity.clamp(0.0, 1.0);
+        let level = 1.0 - self.velocity_to_level * softness;
+        self.start_level = match self.retrigger {
+            RetriggerMode::Legato if self.is_active => {
+                (self.amplitude_at(time) / level.max(1e-6)).min(1.0)
+            }
+            _ => 0.0,
+        };
+        self.level = level;
+        self.attack_scale = 1.0 + self.velocity_to_attack * softness * VELOCITY_ATTACK_STRETCH;
         self.is_active = true;
         self.trigger_time = time;
         self.current_time = 0.0;
@@ -151,6 +290,44 @@ impl Envelope {
         }
     }
 
+    /// Attack/decay/sustain value `elapsed` seconds after the trigger, before
+    /// release and velocity level.
+    fn held_level(&self, elapsed: f32) -> f32 {
+        let attack_time = self.attack_time * self.attack_scale;
+        if elapsed < attack_time {
+            let attack_progress = elapsed / attack_time;
+            self.start_level + (1.0 - self.start_level) * self.attack_curve.apply(attack_progress)
+        } else if elapsed < attack_time + self.decay_time {
+            let decay_elapsed = elapsed - attack_time;
+            let decay_progress = decay_elapsed / self.decay_time;
+            let curved_progress = self.decay_curve.apply(decay_progress);
+            1.0 - (1.0 - self.sustain_level) * curved_progress
+        } else {
+            self.sustain_level
+        }
+    }
+
+    /// Output at `current_time` without advancing the envelope's state.
+    fn amplitude_at(&self, current_time: f64) -> f32 {
+        if !self.is_active {
+            return 0.0;
+        }
+        let elapsed = (current_time - self.trigger_time) as f32;
+        let held = self.held_level(elapsed);
+        match self.release_time_start {
+            Some(release_start) => {
+                let release_elapsed = (current_time - release_start) as f32;
+                if release_elapsed < self.release_time {
+                    let release_progress = release_elapsed / self.release_time;
+                    held * (1.0 - self.release_curve.apply(release_progress)) * self.level
+                } else {
+                    0.0
+                }
+            }
+            None => held * self.level,
+        }
+    }
+
     pub fn get_amplitude(&mut self, current_time: f64) -> f32 {
         if !self.is_active {
             return 0.0;
@@ -159,54 +336,23 @@ impl Envelope {
         let elapsed = (current_time - self.trigger_time) as f32;
         self.current_time = elapsed;
 
-        // Check if we're in release phase
-        if let Some(release_start) = self.release_time_start {
-            let release_elapsed = (current_time - release_start) as f32;
-            if release_elapsed < self.release_time {
-                // Calculate amplitude at release start
-                let release_amplitude = if elapsed < self.attack_time {
-                    // Released during attack - apply attack curve
-                    let attack_progress = elapsed / self.attack_time;
-                    self.attack_curve.apply(attack_progress)
-                } else if elapsed < self.attack_time + self.decay_time {
-                    // Released during decay
-                    let decay_elapsed = elapsed - self.attack_time;
-                    let decay_progress = decay_elapsed / self.decay_time;
-                    let curved_progress = self.decay_curve.apply(decay_progress);
-                    1.0 - (1.0 - self.sustain_level) * curved_progress
-                } else {
-                    // Released during sustain
-                    self.sustain_level
-                };
-
-                // Apply release envelope
-                let release_progress = release_elapsed / self.release_time;
-                release_amplitude * (1.0 - release_progress)
-            } else {
-                // Release phase complete
-                self.is_active = false;
-                0.0
+        match self.release_time_start {
+            Some(release_start) => {
+                if (current_time - release_start) as f32 >= self.release_time {
+                    // Release phase complete
+                    self.is_active = false;
+                    return 0.0;
+                }
             }
-        } else {
-            // Normal ADSR without release triggered
-            if elapsed < self.attack_time {
-                // Attack phase - apply attack curve
-                let attack_progress = elapsed / self.attack_time;
-                self.attack_curve.apply(attack_progress)
-            } else if elapsed < self.attack_time + self.decay_time {
-                // Decay phase
-                let decay_elapsed = elapsed - self.attack_time;
-                let decay_progress = decay_elapsed / self.decay_time;
-                let curved_progress = self.decay_curve.apply(decay_progress);
-                1.0 - (1.0 - self.sustain_level) * curved_progress
-            } else {
-                // Sustain phase (holds until release is triggered)
+            None => {
                 // For drums with 0.0 sustain, automatically trigger release
-                if self.sustain_level == 0.0 && self.release_time_start.is_none() {
+                // once the decay reaches the sustain phase
+                let attack_time = self.attack_time * self.attack_scale;
+                if elapsed >= attack_time + self.decay_time && self.sustain_level == 0.0 {
                     self.release_time_start = Some(current_time);
                 }
-                self.sustain_level
             }
         }
+        self.amplitude_at(current_time)
     }
 }
@@ -0,0 +1,411 @@
+//! Headless envelope editing model.
+//!
+//! [`EnvelopeModel`] holds the breakpoints an envelope editor draws and drags,
+//! with hit-testing and drag constraints, and converts to and from the
+//! envelopes the engine plays ([`ADSRConfig`] and [`SegmentEnvelope`]). It
+//! has no windowing or GPU dependencies, so native, web and mobile frontends
+//! can all share it and only do the drawing themselves.
+
+use crate::envelope::{ADSRConfig, EnvelopeCurve};
+use crate::max_curve::{max_curve, SegmentEnvelope};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// One breakpoint of an [`EnvelopeModel`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EnvelopePoint {
+    /// Position from the start of the envelope, in milliseconds
+    pub time_ms: f32,
+    pub value: f32,
+    /// Max curve~ amount (-1.0 to 1.0) of the segment arriving at this point
+    pub curve: f32,
+    /// Drags may not move the point in time
+    pub lock_time: bool,
+    /// Drags may not change the point's value
+    pub lock_value: bool,
+}
+
+impl EnvelopePoint {
+    pub fn new(time_ms: f32, value: f32, curve: f32) -> Self {
+        Self {
+            time_ms,
+            value,
+            curve,
+            lock_time: false,
+            lock_value: false,
+        }
+    }
+}
+
+/// Editable breakpoint envelope.
+///
+/// Point 0 is the start value and always sits at time 0. Each later point
+/// ends one segment, so segment `i` of a [`SegmentEnvelope`] is point `i + 1`.
+/// The sustain point holds while the gate is held, and the loop jumps from
+/// its end point back to its start point.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnvelopeModel {
+    points: Vec<EnvelopePoint>,
+    sustain: Option<usize>,
+    loop_points: Option<(usize, usize)>,
+    value_range: (f32, f32),
+}
+
+impl Default for EnvelopeModel {
+    fn default() -> Self {
+        let mut start = EnvelopePoint::new(0.0, 0.0, 0.0);
+        start.lock_time = true;
+        Self {
+            points: vec![start],
+            sustain: None,
+            loop_points: None,
+            value_range: (0.0, 1.0),
+        }
+    }
+}
+
+impl EnvelopeModel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Clamp dragged and inserted values to `min..=max` (default 0.0 to 1.0).
+    pub fn with_value_range(mut self, min: f32, max: f32) -> Self {
+        self.value_range = (min.min(max), max.max(min));
+        self
+    }
+
+    pub fn points(&self) -> &[EnvelopePoint] {
+        &self.points
+    }
+
+    pub fn point(&self, index: usize) -> Option<&EnvelopePoint> {
+        self.points.get(index)
+    }
+
+    pub fn sustain_point(&self) -> Option<usize> {
+        self.sustain
+    }
+
+    /// Mark point `index` as the sustain point (None clears it).
+    pub fn set_sustain_point(&mut self, index: Option<usize>) {
+        self.sustain = index.filter(|&i| i > 0 && i < self.points.len());
+    }
+
+    pub fn loop_points(&self) -> Option<(usize, usize)> {
+        self.loop_points
+    }
+
+    /// Loop from point `end` back to point `start` (None clears the loop).
+    pub fn set_loop_points(&mut self, points: Option<(usize, usize)>) {
+        self.loop_points = points.filter(|&(start, end)| start < end && end < self.points.len());
+    }
+
+    /// Total length in milliseconds.
+    pub fn duration_ms(&self) -> f32 {
+        self.points.last().map_or(0.0, |p| p.time_ms)
+    }
+
+    /// Envelope value at `time_ms`, for drawing the curve between points.
+    pub fn value_at(&self, time_ms: f32) -> f32 {
+        let next = self.points.iter().position(|p| p.time_ms > time_ms);
+        match next {
+            None => self.points.last().map_or(0.0, |p| p.value),
+            Some(0) => self.points[0].value,
+            Some(i) => {
+                let (from, to) = (self.points[i - 1], self.points[i]);
+                let progress = (time_ms - from.time_ms) / (to.time_ms - from.time_ms);
+                from.value + (to.value - from.value) * max_curve(progress, to.curve)
+            }
+        }
+    }
+
+    /// Index of the point closest to (`time_ms`, `value`) within the given
+    /// tolerances, if any. Tolerances are in model units, so callers convert
+    /// their pick radius from screen space first.
+    pub fn hit_test(
+        &self,
+        time_ms: f32,
+        value: f32,
+        time_tolerance_ms: f32,
+        value_tolerance: f32,
+    ) -> Option<usize> {
+        let distance = |p: &EnvelopePoint| {
+            let dt = (p.time_ms - time_ms) / time_tolerance_ms.max(f32::EPSILON);
+            let dv = (p.value - value) / value_tolerance.max(f32::EPSILON);
+            dt * dt + dv * dv
+        };
+        self.points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (i, distance(p)))
+            .filter(|&(_, d)| d <= 1.0)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+
+    /// Move point `index` towards (`time_ms`, `value`) and return where it
+    /// ended up. Points stay between their neighbours in time, inside the
+    /// value range, and locked coordinates do not move.
+    pub fn drag_point(&mut self, index: usize, time_ms: f32, value: f32) -> Option<(f32, f32)> {
+        let min_time = index
+            .checked_sub(1)
+            .and_then(|i| self.points.get(i))
+            .map_or(0.0, |p| p.time_ms);
+        let max_time = self.points.get(index + 1).map_or(f32::MAX, |p| p.time_ms);
+        let (min_value, max_value) = self.value_range;
+        let point = self.points.get_mut(index)?;
+        if !point.lock_time {
+            point.time_ms = time_ms.clamp(min_time, max_time);
+        }
+        if !point.lock_value {
+            point.value = value.clamp(min_value, max_value);
+        }
+        Some((point.time_ms, point.value))
+    }
+
+    /// Set the curve of the segment arriving at point `index`.
+    pub fn set_curve(&mut self, index: usize, curve: f32) {
+        if let Some(point) = self.points.get_mut(index).filter(|_| index > 0) {
+            point.curve = curve.clamp(-1.0, 1.0);
+        }
+    }
+
+    /// Insert a linear point at `time_ms`, returning its index.
+    pub fn insert_point(&mut self, time_ms: f32, value: f32) -> usize {
+        let (min_value, max_value) = self.value_range;
+        let time_ms = time_ms.max(0.0);
+        let index = self
+            .points
+            .iter()
+            .position(|p| p.time_ms > time_ms)
+            .unwrap_or(self.points.len())
+            .max(1);
+        self.points.insert(
+            index,
+            EnvelopePoint::new(time_ms, value.clamp(min_value, max_value), 0.0),
+        );
+        let shift = |i: usize| if i >= index { i + 1 } else { i };
+        self.sustain = self.sustain.map(shift);
+        self.loop_points = self.loop_points.map(|(a, b)| (shift(a), shift(b)));
+        index
+    }
+
+    /// Remove point `index`. The start point cannot be removed, and neither
+    /// can points with a locked time or value.
+    pub fn remove_point(&mut self, index: usize) -> bool {
+        match self.points.get(index) {
+            Some(p) if index > 0 && !p.lock_time && !p.lock_value => {}
+            _ => return false,
+        }
+        self.points.remove(index);
+        let shift = |i: usize| match i.cmp(&index) {
+            core::cmp::Ordering::Less => Some(i),
+            core::cmp::Ordering::Equal => None,
+            core::cmp::Ordering::Greater => Some(i - 1),
+        };
+        self.sustain = self.sustain.and_then(shift);
+        self.loop_points = self
+            .loop_points
+            .and_then(|(a, b)| Some((shift(a)?, shift(b)?)))
+            .filter(|(a, b)| a < b);
+        true
+    }
+
+    /// Four points shaped like `config`: start, peak, sustain and end. The
+    /// start, peak and end values are locked so drags keep the ADSR shape.
+    ///
+    /// Power-law ([`EnvelopeCurve::Exponential`]) curves are approximated by
+    /// the curve~ amount that matches them halfway through the segment.
+    pub fn from_adsr(config: &ADSRConfig) -> Self {
+        let attack_ms = config.attack_time * 1000.0;
+        let decay_ms = config.decay_time * 1000.0;
+        let release_ms = config.release_time * 1000.0;
+        let mut model = Self::default();
+        model.points[0].lock_value = true;
+        let mut peak = EnvelopePoint::new(attack_ms, 1.0, curve_amount(config.attack_curve));
+        peak.lock_value = true;
+        let sustain = EnvelopePoint::new(
+            attack_ms + decay_ms,
+            config.sustain_level,
+            curve_amount(config.decay_curve),
+        );
+        let mut end = EnvelopePoint::new(
+            attack_ms + decay_ms + release_ms,
+            0.0,
+            curve_amount(config.release_curve),
+        );
+        end.lock_value = true;
+        model.points.extend([peak, sustain, end]);
+        model.sustain = Some(2);
+        model
+    }
+
+    /// Read the model back as an ADSR, keeping `template`'s retrigger and
+    /// velocity settings. None unless the model still has the four-point
+    /// shape made by [`EnvelopeModel::from_adsr`].
+    pub fn to_adsr(&self, template: ADSRConfig) -> Option<ADSRConfig> {
+        let [start, peak, sustain, end] = self.points[..] else {
+            return None;
+        };
+        if self.sustain != Some(2) || start.value != 0.0 || peak.value != 1.0 || end.value != 0.0 {
+            return None;
+        }
+        let mut config = ADSRConfig::new(
+            peak.time_ms / 1000.0,
+            (sustain.time_ms - peak.time_ms) / 1000.0,
+            sustain.value,
+            (end.time_ms - sustain.time_ms) / 1000.0,
+        );
+        config.attack_curve = envelope_curve(peak.curve);
+        config.decay_curve = envelope_curve(sustain.curve);
+        config.release_curve = envelope_curve(end.curve);
+        config.retrigger = template.retrigger;
+        config.velocity_to_level = template.velocity_to_level;
+        config.velocity_to_attack = template.velocity_to_attack;
+        Some(config)
+    }
+
+    /// Breakpoints of `envelope`, with the value range widened to fit them.
+    pub fn from_segment_envelope(envelope: &SegmentEnvelope) -> Self {
+        let mut model = Self::default();
+        model.points[0].value = envelope.initial_value();
+        let mut time_ms = 0.0;
+        for seg in envelope.segments() {
+            time_ms += seg.duration_secs * 1000.0;
+            model
+                .points
+                .push(EnvelopePoint::new(time_ms, seg.target_value, seg.curve));
+        }
+        let (min, max) = model.points.iter().fold((0.0_f32, 1.0_f32), |(lo, hi), p| {
+            (lo.min(p.value), hi.max(p.value))
+        });
+        model.value_range = (min, max);
+        model.set_sustain_point(envelope.sustain_point().map(|s| s + 1));
+        model.set_loop_points(envelope.loop_points().map(|(a, b)| (a, b + 1)));
+        model
+    }
+
+    /// The envelope these breakpoints describe.
+    pub fn to_segment_envelope(&self) -> SegmentEnvelope {
+        let mut envelope = SegmentEnvelope::default().with_initial_value(self.points[0].value);
+        for pair in self.points.windows(2) {
+            envelope = envelope.segment(
+                pair[1].value,
+                pair[1].time_ms - pair[0].time_ms,
+                pair[1].curve,
+            );
+        }
+        if let Some(sustain) = self.sustain {
+            envelope = envelope.with_sustain(sustain - 1);
+        }
+        if let Some((start, end)) = self.loop_points {
+            envelope = envelope.with_loop(start, end - 1);
+        }
+        envelope
+    }
+}
+
+/// curve~ amount for an ADSR curve. Power curves are matched at the midpoint.
+fn curve_amount(curve: EnvelopeCurve) -> f32 {
+    match curve {
+        EnvelopeCurve::Linear => 0.0,
+        EnvelopeCurve::Curved(amount) => amount.clamp(-1.0, 1.0),
+        EnvelopeCurve::Exponential(_) => {
+            // max_curve(0.5, c) falls as c rises; bisect for the matching amount
+            let target = curve.apply(0.5);
+            let (mut lo, mut hi) = (-1.0_f32, 1.0_f32);
+            for _ in 0..32 {
+                let mid = 0.5 * (lo + hi);
+                if max_curve(0.5, mid) > target {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            0.5 * (lo + hi)
+        }
+    }
+}
+
+fn envelope_curve(amount: f32) -> EnvelopeCurve {
+    if amount == 0.0 {
+        EnvelopeCurve::Linear
+    } else {
+        EnvelopeCurve::Curved(amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adsr_round_trip() {
+        let config = ADSRConfig::new(0.01, 0.2, 0.6, 0.5)
+            .with_attack_curve(EnvelopeCurve::Curved(0.4))
+            .with_retrigger(crate::envelope::RetriggerMode::Legato);
+        let model = EnvelopeModel::from_adsr(&config);
+        assert_eq!(model.points().len(), 4);
+        assert_eq!(model.sustain_point(), Some(2));
+
+        let back = model.to_adsr(config).unwrap();
+        assert!((back.attack_time - 0.01).abs() < 1e-6);
+        assert!((back.decay_time - 0.2).abs() < 1e-6);
+        assert!((back.release_time - 0.5).abs() < 1e-6);
+        assert_eq!(back.sustain_level, 0.6);
+        assert_eq!(back.attack_curve, EnvelopeCurve::Curved(0.4));
+        assert_eq!(back.decay_curve, EnvelopeCurve::Linear);
+        assert_eq!(back.retrigger, crate::envelope::RetriggerMode::Legato);
+    }
+
+    #[test]
+    fn test_exponential_curve_is_matched_at_midpoint() {
+        let config =
+            ADSRConfig::new(0.1, 0.1, 0.5, 0.1).with_attack_curve(EnvelopeCurve::Exponential(2.0));
+        let model = EnvelopeModel::from_adsr(&config);
+        // Power curve 2.0 is at 0.25 halfway through the attack
+        assert!((model.value_at(50.0) - 0.25).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_drag_respects_neighbours_and_locks() {
+        let mut model = EnvelopeModel::from_adsr(&ADSRConfig::new(0.01, 0.1, 0.5, 0.2));
+        // Sustain point cannot pass the peak or the end, nor leave the range
+        assert_eq!(model.drag_point(2, 5.0, 2.0), Some((10.0, 1.0)));
+        assert_eq!(model.drag_point(2, 1000.0, -1.0), Some((310.0, 0.0)));
+        // Peak keeps its value; the start point does not move at all
+        assert_eq!(model.drag_point(1, 20.0, 0.2), Some((20.0, 1.0)));
+        assert_eq!(model.drag_point(0, 30.0, 0.5), Some((0.0, 0.0)));
+        assert!(model.drag_point(9, 0.0, 0.0).is_none());
+        assert!(!model.remove_point(1));
+    }
+
+    #[test]
+    fn test_hit_test_picks_nearest_point() {
+        let model = EnvelopeModel::from_adsr(&ADSRConfig::new(0.01, 0.1, 0.5, 0.2));
+        assert_eq!(model.hit_test(11.0, 0.98, 5.0, 0.1), Some(1));
+        assert_eq!(model.hit_test(108.0, 0.52, 5.0, 0.1), Some(2));
+        assert_eq!(model.hit_test(60.0, 0.2, 5.0, 0.1), None);
+    }
+
+    #[test]
+    fn test_insert_and_remove_keep_markers() {
+        let envelope = SegmentEnvelope::dahdsr(0.0, 10.0, 20.0, 100.0, 0.5, 200.0).with_loop(1, 2);
+        let mut model = EnvelopeModel::from_segment_envelope(&envelope);
+        assert_eq!(model.sustain_point(), Some(4));
+        assert_eq!(model.loop_points(), Some((1, 3)));
+
+        let index = model.insert_point(5.0, 0.3);
+        assert_eq!(index, 2);
+        assert_eq!(model.sustain_point(), Some(5));
+        assert_eq!(model.loop_points(), Some((1, 4)));
+
+        assert!(model.remove_point(index));
+        let back = model.to_segment_envelope();
+        assert_eq!(back.segments(), envelope.segments());
+        assert_eq!(back.sustain_point(), Some(3));
+        assert_eq!(back.loop_points(), Some((1, 2)));
+        assert!(model.to_adsr(ADSRConfig::default()).is_none());
+    }
+}
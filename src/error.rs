@@ -0,0 +1,113 @@
+//! Error type for the public Rust API
+//!
+//! Fallible calls on the engine, audio graph, DSL, loaders, snapshots and
+//! bounce return [`GooeyError`]. The variants say what kind of thing went
+//! wrong so callers can react without matching on message text; `Display`
+//! gives the human-readable diagnostic. The C API turns them into
+//! [`GooeyResult`](crate::ffi::GooeyResult) codes at the boundary.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Why a call into the engine failed.
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum GooeyError {
+    /// No instrument is registered under the name.
+    #[error("instrument '{0}' not found")]
+    UnknownInstrument(String),
+    /// The instrument has no parameter of the name.
+    #[error("unknown parameter '{0}'")]
+    UnknownParameter(String),
+    /// The instrument can be modulated, but not this parameter.
+    #[error(
+        "parameter '{parameter}' is not modulatable on instrument '{instrument}'; available: {}",
+        available.join(", ")
+    )]
+    NotModulatable {
+        instrument: String,
+        parameter: String,
+        available: Vec<&'static str>,
+    },
+    /// The instrument doesn't support modulation at all.
+    #[error("instrument '{0}' does not support modulation")]
+    ModulationUnsupported(String),
+    /// An index into one of the engine's lists (LFOs, global effects, graph
+    /// nodes) is past its end.
+    #[error("{what} index {index} is out of range ({count} available)")]
+    IndexOutOfRange {
+        what: &'static str,
+        index: usize,
+        count: usize,
+    },
+    /// A value is out of range, not finite, or otherwise unusable.
+    #[error("{0}")]
+    InvalidValue(String),
+    /// A graph edit that would leave the routing invalid.
+    #[error("{0}")]
+    InvalidRouting(String),
+    /// [`AUDIO_EVENT_CAPACITY`](crate::engine::AUDIO_EVENT_CAPACITY) events
+    /// are already waiting for the audio side.
+    #[error("audio event queue is full ({0} pending)")]
+    QueueFull(usize),
+    /// A DSL program failed to parse or build; `line` is 1-based.
+    #[error("line {line}: {message}")]
+    Parse { line: usize, message: String },
+    /// Reading, decoding or writing a file failed.
+    #[error("{0}")]
+    Io(String),
+    /// A visualization window or its OpenGL resources couldn't be created.
+    #[error("{0}")]
+    Window(String),
+    /// A buffer of this many bytes couldn't be allocated.
+    #[error("could not allocate {0} bytes")]
+    OutOfMemory(usize),
+    /// Bytes given as an engine snapshot can't be read.
+    #[error(transparent)]
+    InvalidSnapshot(#[from] SnapshotError),
+}
+
+/// Why an engine snapshot was rejected (see `gooey::snapshot::decode`).
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum SnapshotError {
+    /// Doesn't start with the snapshot magic.
+    #[error("not an engine snapshot")]
+    NotASnapshot,
+    /// Written by a format version this build can't read.
+    #[error("snapshot version {0} is not supported")]
+    UnsupportedVersion(u16),
+    /// Ends before the data it announces.
+    #[error("snapshot is truncated")]
+    Truncated,
+    /// Well-formed bytes describing something the engine doesn't have.
+    #[error("{0}")]
+    Invalid(String),
+}
+
+impl GooeyError {
+    /// A [`GooeyError::Parse`] at `line`.
+    pub fn parse(line: usize, message: impl Into<String>) -> Self {
+        Self::Parse {
+            line,
+            message: message.into(),
+        }
+    }
+
+    /// A [`GooeyError::InvalidValue`].
+    pub fn invalid(message: impl Into<String>) -> Self {
+        Self::InvalidValue(message.into())
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for GooeyError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error.to_string())
+    }
+}
+
+#[cfg(feature = "hound")]
+impl From<hound::Error> for GooeyError {
+    fn from(error: hound::Error) -> Self {
+        Self::Io(error.to_string())
+    }
+}
@@ -3,27 +3,56 @@
 //! This module exposes the audio engine to C/Swift via C-compatible functions.
 //! Designed for integration with iOS (and other platforms in the future).
 
+use crate::cpu_load::{CpuLoad, GovernorAction, OverloadGovernor};
 use crate::effects::{
-    DelayEffect, DelayTiming, Effect, FeedbackWaveshaper, LowpassFilterEffect, PlateReverbEffect,
-    SoftLimiter, SpringReverbEffect, TiltFilterEffect, TubeCompressor, TubeSaturation, Waveshaper,
+    BeatRepeat, DelayEffect, DelayTiming, Ducker, EarlyReflections, EarlyReflectionsPreset, Effect,
+    FeedbackWaveshaper, LowpassFilterEffect, OutputSafety, PlateReverbEffect, Saturator,
+    SaturatorModel, SoftLimiter, SpringReverbEffect, TiltFilterEffect, TubeCompressor,
+    TubeSaturation, Waveshaper, Widener,
 };
 use crate::engine::lfo::{Lfo, MusicalDivision};
-use crate::engine::{Instrument, Sequencer, SequencerBlendSetting, SequencerStepSettings};
+use crate::engine::{
+    EffectLane, GroovePool, GrooveTemplate, Instrument, NoteChange, PatternClip, Sequencer,
+    SequencerBlendSetting, SequencerStep, SequencerStepSettings, StepNote, EFFECT_LANE_STEPS,
+};
+use crate::envelope::{EnvelopeCurve, EnvelopeShape, RetriggerMode};
+use crate::error::GooeyError;
 use crate::frame::StereoFrame;
 use crate::instruments::{
-    BassConfig, BassSynth, Granulator, HiHat2, HiHat2Config, KickConfig, KickDrum, PolySynth,
-    PolySynthConfig, SampleBuffer, SamplerBuffer, SamplerRack, SnareConfig, SnareDrum, Tom2,
-    Tom2Config,
+    BassConfig, BassSynth, Cowbell, CowbellConfig, FmSnap, FmSnapConfig, Granulator, HiHat2,
+    HiHat2Config, HiHatArticulation, HiHatMode, KickConfig, KickDrum, PolySynth, PolySynthConfig,
+    Rimshot, RimshotConfig, SampleBuffer, SamplerBuffer, SamplerRack, Shaker, ShakerConfig,
+    SnareConfig, SnareDrum, Tom2, Tom2Config,
 };
 use crate::mixer::{
     LaunchQuantization, Mixer, MixerGraph, PitchMode, RetrimTiming, StereoSampleBuffer,
 };
-use crate::music::{apply_voicing, available_voicings, Key, NoteName, ScaleType, VoicingType};
-use crate::performance::{ChordClipEvent, PerformanceRecorder, PlayerAction, RecordMode};
-use crate::utils::{PresetBlender, SmoothedParam};
+use crate::music::tuning::{A4_HZ_RANGE, TRANSPOSE_RANGE};
+use crate::music::{
+    apply_voicing, available_voicings, quantize_to_scale, Key, MasterTuning, NoteName, Scale,
+    ScaleType, VoicingType,
+};
+use crate::param_table::ParamTable;
+use crate::performance::{
+    CapturedHit, ChordClipEvent, PerformanceRecorder, PlayerAction, RecordMode, TriggerCapture,
+};
+use crate::recorder::{RecordState, Recorder};
+use crate::snapshot::{ChannelSnapshot, EffectSnapshot, EngineSnapshot};
+use crate::trace::{TraceEvent, TraceLog};
+use crate::utils::config_fade::{ConfigFade, CONFIG_FADE_MS};
+use crate::utils::loudness::{amplitude_to_db, db_to_amplitude, Loudness, MAX_AUTO_GAIN_DB};
+use crate::utils::oversampler::OversamplingMode;
+use crate::utils::resampler::Resampler;
+use crate::utils::{
+    random_blend, Blendable, DenormalGuard, PresetBlender, Rng, RngStream, SampleClock,
+    SmoothedParam, DEFAULT_RNG_SEED,
+};
+use std::collections::HashMap;
 use std::ffi::{c_char, c_void, CStr, CString};
 use std::slice;
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8, AtomicUsize, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::time::{Duration, Instant};
 
 // =============================================================================
 // LFO constants
@@ -53,6 +82,23 @@ pub const LFO_TIMING_THIRTY_SECOND: u32 = 7;
 /// Invalid LFO value (returned on error or when LFO is in Hz mode)
 pub const LFO_INVALID: u32 = 0xFFFFFFFF;
 
+/// Route target addressing another LFO: pass `LFO_TARGET_LFO_BASE + n` as
+/// the instrument of `gooey_engine_add_lfo_route` to modulate LFO n, with
+/// `LFO_PARAM_RATE` or `LFO_PARAM_AMOUNT` as the parameter
+pub const LFO_TARGET_LFO_BASE: u32 = 0x1000;
+/// LFO parameter: rate, ±2 octaves at full depth
+pub const LFO_PARAM_RATE: u32 = 0;
+/// LFO parameter: amount, scaled from 0x to 2x at full depth
+pub const LFO_PARAM_AMOUNT: u32 = 1;
+
+/// The LFO a route target addresses, if it addresses one
+fn lfo_route_target(instrument: u32) -> Option<usize> {
+    instrument
+        .checked_sub(LFO_TARGET_LFO_BASE)
+        .map(|index| index as usize)
+        .filter(|&index| index < LFO_COUNT)
+}
+
 /// LFO route configuration
 #[derive(Clone)]
 struct LfoRoute {
@@ -64,6 +110,36 @@ struct LfoRoute {
     param: u32,
     /// Modulation depth for this route (0.0 to 1.0)
     depth: f32,
+    /// Phase offset from the LFO, in cycles (0.0 to 1.0)
+    phase: f32,
+    /// Flip the LFO's output for this route
+    inverted: bool,
+    /// Map the (possibly inverted) -1..1 output to 0..1 before the depth
+    unipolar: bool,
+    /// Channel whose hits restart this route's cycle in one-shot mode
+    trigger_source: u32,
+    /// Position in the current one-shot cycle (1.0 once it has finished)
+    cycle: f32,
+}
+
+impl LfoRoute {
+    /// This route's modulation value for the sample `lfo` is about to output.
+    /// A one-shot LFO is read at the route's own cycle position, holding the
+    /// end of the cycle until the next trigger.
+    fn modulation(&self, lfo: &Lfo) -> f32 {
+        let mut value = if lfo.is_one_shot() {
+            lfo.value_at_phase(self.cycle + self.phase)
+        } else {
+            lfo.value_at(self.phase)
+        };
+        if self.inverted {
+            value = -value;
+        }
+        if self.unipolar {
+            value = (value + 1.0) * 0.5;
+        }
+        value * self.depth
+    }
 }
 
 /// Maximum number of MIDI events buffered per render pass.
@@ -82,21 +158,127 @@ pub struct GooeyMidiEvent {
     pub sample_offset: u32,
 }
 
+/// Maximum number of UI timeline events queued between drains. Events beyond
+/// this limit are dropped until the UI catches up.
+const TIMELINE_EVENT_CAPACITY: usize = 256;
+
+/// `GooeyTimelineEvent::step` for a hit that did not come from the sequencer
+/// (e.g. `gooey_engine_trigger_instrument`).
+pub const TIMELINE_STEP_NONE: u32 = 0xFFFFFFFF;
+
+/// An instrument hit that fired during a render call, for UI animation.
+///
+/// Unlike [`GooeyMidiEvent`] (drained on the audio thread right after each
+/// render), timeline events are queued for a UI thread to drain at its own
+/// pace. Each carries enough timing to place it on the UI's own clock: the
+/// engine frame it fired on and, when the host supplies
+/// `gooey_engine_set_render_host_time`, the host time it will be heard.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GooeyTimelineEvent {
+    /// Channel that was triggered (INSTRUMENT_KICK, etc., or a slot channel)
+    pub channel: u32,
+    /// Sequencer step that fired, or `TIMELINE_STEP_NONE`
+    pub step: u32,
+    pub velocity: f32,
+    /// Frame offset within the render buffer that produced the hit
+    pub sample_offset: u32,
+    /// Engine frame of the hit, counted from the engine's first render
+    pub sample_time: u64,
+    /// Host time (e.g. mach_absolute_time) the hit reaches the output, with
+    /// the timeline latency added; 0 if the host clock was never set
+    pub host_time: u64,
+}
+
 // =============================================================================
 // Channel instrument and blender enums
 // =============================================================================
 
+/// A whole config for any channel instrument type, so configs can be faded
+/// between (see [`ConfigFade`]) without matching on the type at each step.
+#[derive(Clone, Copy, Debug)]
+enum ChannelConfig {
+    Kick(KickConfig),
+    Snare(SnareConfig),
+    HiHat(HiHat2Config),
+    Tom(Tom2Config),
+    Bass(BassConfig),
+    FmSnap(FmSnapConfig),
+    Rimshot(RimshotConfig),
+    Cowbell(CowbellConfig),
+    Shaker(ShakerConfig),
+}
+
+impl Blendable for ChannelConfig {
+    /// Configs of different types are never faded between; `other` wins.
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        match (self, other) {
+            (Self::Kick(a), Self::Kick(b)) => Self::Kick(a.lerp(b, t)),
+            (Self::Snare(a), Self::Snare(b)) => Self::Snare(a.lerp(b, t)),
+            (Self::HiHat(a), Self::HiHat(b)) => Self::HiHat(a.lerp(b, t)),
+            (Self::Tom(a), Self::Tom(b)) => Self::Tom(a.lerp(b, t)),
+            (Self::Bass(a), Self::Bass(b)) => Self::Bass(a.lerp(b, t)),
+            (Self::FmSnap(a), Self::FmSnap(b)) => Self::FmSnap(a.lerp(b, t)),
+            (Self::Rimshot(a), Self::Rimshot(b)) => Self::Rimshot(a.lerp(b, t)),
+            (Self::Cowbell(a), Self::Cowbell(b)) => Self::Cowbell(a.lerp(b, t)),
+            (Self::Shaker(a), Self::Shaker(b)) => Self::Shaker(a.lerp(b, t)),
+            _ => *other,
+        }
+    }
+}
+
+impl ChannelConfig {
+    /// The instrument type constant of the instrument this config is for.
+    fn instrument_type(&self) -> u32 {
+        match self {
+            Self::Kick(_) => INSTRUMENT_KICK,
+            Self::Snare(_) => INSTRUMENT_SNARE,
+            Self::HiHat(_) => INSTRUMENT_HIHAT,
+            Self::Tom(_) => INSTRUMENT_TOM,
+            Self::Bass(_) => INSTRUMENT_BASS,
+            Self::FmSnap(_) => INSTRUMENT_FM_SNAP,
+            Self::Rimshot(_) => INSTRUMENT_RIMSHOT,
+            Self::Cowbell(_) => INSTRUMENT_COWBELL,
+            Self::Shaker(_) => INSTRUMENT_SHAKER,
+        }
+    }
+}
+
 /// A polymorphic instrument that can be any drum synth type.
 /// Each channel holds one of these, enabling runtime instrument reassignment.
+/// The synths are stored inline, not boxed, so the per-sample tick doesn't
+/// chase a pointer (see `ChannelEffect`).
+#[allow(clippy::large_enum_variant)]
 enum ChannelInstrument {
     Kick(KickDrum),
     Snare(SnareDrum),
     HiHat(HiHat2),
     Tom(Tom2),
     Bass(BassSynth),
+    FmSnap(FmSnap),
+    Rimshot(Rimshot),
+    Cowbell(Cowbell),
+    Shaker(Shaker),
 }
 
 impl ChannelInstrument {
+    /// A freshly initialized instrument of `instrument_type`, or `None` for an
+    /// unknown type.
+    fn new(instrument_type: u32, sample_rate: f32) -> Option<Self> {
+        Some(match instrument_type {
+            INSTRUMENT_KICK => Self::Kick(KickDrum::new(sample_rate)),
+            INSTRUMENT_SNARE => Self::Snare(SnareDrum::new(sample_rate)),
+            INSTRUMENT_HIHAT => Self::HiHat(HiHat2::new(sample_rate)),
+            INSTRUMENT_TOM => Self::Tom(Tom2::new(sample_rate)),
+            INSTRUMENT_BASS => Self::Bass(BassSynth::new(sample_rate)),
+            INSTRUMENT_FM_SNAP => Self::FmSnap(FmSnap::new(sample_rate)),
+            INSTRUMENT_RIMSHOT => Self::Rimshot(Rimshot::new(sample_rate)),
+            INSTRUMENT_COWBELL => Self::Cowbell(Cowbell::new(sample_rate)),
+            INSTRUMENT_SHAKER => Self::Shaker(Shaker::new(sample_rate)),
+            _ => return None,
+        })
+    }
+
     /// Returns the instrument type constant for this variant.
     fn instrument_type(&self) -> u32 {
         match self {
@@ -105,6 +287,10 @@ impl ChannelInstrument {
             Self::HiHat(_) => INSTRUMENT_HIHAT,
             Self::Tom(_) => INSTRUMENT_TOM,
             Self::Bass(_) => INSTRUMENT_BASS,
+            Self::FmSnap(_) => INSTRUMENT_FM_SNAP,
+            Self::Rimshot(_) => INSTRUMENT_RIMSHOT,
+            Self::Cowbell(_) => INSTRUMENT_COWBELL,
+            Self::Shaker(_) => INSTRUMENT_SHAKER,
         }
     }
 
@@ -116,6 +302,144 @@ impl ChannelInstrument {
             Self::HiHat(h) => h.trigger_with_velocity(time, velocity),
             Self::Tom(t) => t.trigger_with_velocity(time, velocity),
             Self::Bass(b) => b.trigger_with_velocity(time, velocity),
+            Self::FmSnap(f) => f.trigger_with_velocity(time, velocity),
+            Self::Rimshot(r) => r.trigger_with_velocity(time, velocity),
+            Self::Cowbell(c) => c.trigger_with_velocity(time, velocity),
+            Self::Shaker(s) => s.trigger_with_velocity(time, velocity),
+        }
+    }
+
+    /// Kit-wide pitch multiplier from the master tuning. Unpitched
+    /// instruments ignore it.
+    fn set_pitch_ratio(&mut self, ratio: f32) {
+        match self {
+            Self::Kick(k) => k.set_pitch_ratio(ratio),
+            Self::Snare(s) => s.set_pitch_ratio(ratio),
+            Self::HiHat(h) => h.set_pitch_ratio(ratio),
+            Self::Tom(t) => t.set_pitch_ratio(ratio),
+            Self::Bass(b) => b.set_pitch_ratio(ratio),
+            Self::FmSnap(f) => f.set_pitch_ratio(ratio),
+            Self::Rimshot(r) => r.set_pitch_ratio(ratio),
+            Self::Cowbell(c) => c.set_pitch_ratio(ratio),
+            Self::Shaker(s) => s.set_pitch_ratio(ratio),
+        }
+    }
+
+    /// Note-off from a gated step or `gooey_engine_release_*`. Instruments
+    /// without a release stage run out their own decay.
+    fn release(&mut self, time: f64) {
+        match self {
+            Self::Kick(k) => Instrument::release(k, time),
+            Self::Snare(s) => Instrument::release(s, time),
+            Self::HiHat(h) => Instrument::release(h, time),
+            Self::Tom(t) => Instrument::release(t, time),
+            Self::Bass(b) => Instrument::release(b, time),
+            Self::FmSnap(f) => Instrument::release(f, time),
+            Self::Rimshot(r) => Instrument::release(r, time),
+            Self::Cowbell(c) => Instrument::release(c, time),
+            Self::Shaker(s) => Instrument::release(s, time),
+        }
+    }
+
+    /// Trigger with a per-step articulation, if the instrument has any.
+    fn trigger_articulated(&mut self, time: f64, velocity: f32, articulation: Option<u8>) {
+        match (self, articulation) {
+            (Self::HiHat(h), Some(articulation)) => {
+                Instrument::trigger_articulated(h, time, velocity, articulation)
+            }
+            (instrument, _) => instrument.trigger_with_velocity(time, velocity),
+        }
+    }
+
+    /// Whether the instrument is still sounding.
+    fn is_active(&self) -> bool {
+        match self {
+            Self::Kick(k) => Instrument::is_active(k),
+            Self::Snare(s) => Instrument::is_active(s),
+            Self::HiHat(h) => Instrument::is_active(h),
+            Self::Tom(t) => Instrument::is_active(t),
+            Self::Bass(b) => Instrument::is_active(b),
+            Self::FmSnap(f) => Instrument::is_active(f),
+            Self::Rimshot(r) => Instrument::is_active(r),
+            Self::Cowbell(c) => Instrument::is_active(c),
+            Self::Shaker(s) => Instrument::is_active(s),
+        }
+    }
+
+    /// Restart the instrument's noise sources from `seed`.
+    fn reseed(&mut self, seed: u64) {
+        match self {
+            Self::Kick(k) => k.reseed(seed),
+            Self::Snare(s) => s.reseed(seed),
+            Self::HiHat(h) => h.reseed(seed),
+            Self::Tom(t) => t.reseed(seed),
+            Self::Bass(b) => b.reseed(seed),
+            Self::FmSnap(f) => f.reseed(seed),
+            Self::Rimshot(r) => r.reseed(seed),
+            Self::Cowbell(c) => c.reseed(seed),
+            Self::Shaker(s) => s.reseed(seed),
+        }
+    }
+
+    /// Select the overdrive stage's saturation curve. Returns false for
+    /// instruments without a model-selectable overdrive stage.
+    fn set_saturator_model(&mut self, model: SaturatorModel) -> bool {
+        match self {
+            Self::Snare(s) => s.set_overdrive_model(model),
+            Self::Bass(b) => b.set_overdrive_model(model),
+            Self::Kick(_)
+            | Self::HiHat(_)
+            | Self::Tom(_)
+            | Self::FmSnap(_)
+            | Self::Rimshot(_)
+            | Self::Cowbell(_)
+            | Self::Shaker(_) => return false,
+        }
+        true
+    }
+
+    /// Turn auto gain on or off for the overdrive stage. Returns false for
+    /// instruments without one.
+    fn set_overdrive_auto_gain(&mut self, enabled: bool) -> bool {
+        match self {
+            Self::Snare(s) => s.set_overdrive_auto_gain(enabled),
+            Self::Bass(b) => b.set_overdrive_auto_gain(enabled),
+            Self::Kick(_)
+            | Self::HiHat(_)
+            | Self::Tom(_)
+            | Self::FmSnap(_)
+            | Self::Rimshot(_)
+            | Self::Cowbell(_)
+            | Self::Shaker(_) => return false,
+        }
+        true
+    }
+
+    fn overdrive_auto_gain(&self) -> Option<bool> {
+        match self {
+            Self::Snare(s) => Some(s.overdrive_auto_gain()),
+            Self::Bass(b) => Some(b.overdrive_auto_gain()),
+            Self::Kick(_)
+            | Self::HiHat(_)
+            | Self::Tom(_)
+            | Self::FmSnap(_)
+            | Self::Rimshot(_)
+            | Self::Cowbell(_)
+            | Self::Shaker(_) => None,
+        }
+    }
+
+    fn saturator_model(&self) -> Option<SaturatorModel> {
+        match self {
+            Self::Snare(s) => Some(s.overdrive_model()),
+            Self::Bass(b) => Some(b.overdrive_model()),
+            Self::Kick(_)
+            | Self::HiHat(_)
+            | Self::Tom(_)
+            | Self::FmSnap(_)
+            | Self::Rimshot(_)
+            | Self::Cowbell(_)
+            | Self::Shaker(_) => None,
         }
     }
 
@@ -128,6 +452,163 @@ impl ChannelInstrument {
             Self::HiHat(h) => h.snap_params(),
             Self::Tom(_) => {} // Tom2 uses plain f32, already immediate
             Self::Bass(b) => b.snap_params(),
+            Self::FmSnap(f) => f.snap_params(),
+            Self::Rimshot(r) => r.snap_params(),
+            Self::Cowbell(c) => c.snap_params(),
+            Self::Shaker(s) => s.snap_params(),
+        }
+    }
+
+    /// Move the current config toward a random blend of this instrument's
+    /// stock presets by `amount` (0-1). Deterministic for a given `seed`.
+    fn randomize(&mut self, amount: f32, seed: u32) {
+        match self {
+            Self::Kick(k) => {
+                let presets = [
+                    KickConfig::tight(),
+                    KickConfig::punch(),
+                    KickConfig::loose(),
+                    KickConfig::dirt(),
+                ];
+                k.set_config(random_blend(&k.config(), &presets, amount, seed).clamped());
+            }
+            Self::Snare(s) => {
+                let presets = [
+                    SnareConfig::tight(),
+                    SnareConfig::loose(),
+                    SnareConfig::hiss(),
+                    SnareConfig::smack(),
+                ];
+                s.set_config(random_blend(&s.config(), &presets, amount, seed).clamped());
+            }
+            Self::HiHat(h) => {
+                let presets = [
+                    HiHat2Config::short(),
+                    HiHat2Config::loose(),
+                    HiHat2Config::dark(),
+                    HiHat2Config::soft(),
+                ];
+                h.set_config(random_blend(&h.config(), &presets, amount, seed).clamped());
+            }
+            Self::Tom(t) => {
+                let presets = [
+                    Tom2Config::derp(),
+                    Tom2Config::ring(),
+                    Tom2Config::brush(),
+                    Tom2Config::void_preset(),
+                ];
+                t.set_config(random_blend(&t.config(), &presets, amount, seed).clamped());
+            }
+            Self::Bass(b) => {
+                let presets = [
+                    BassConfig::acid(),
+                    BassConfig::sub(),
+                    BassConfig::reese(),
+                    BassConfig::stab(),
+                ];
+                b.set_config(random_blend(&b.config(), &presets, amount, seed).clamped());
+            }
+            Self::FmSnap(f) => {
+                let presets = [
+                    FmSnapConfig::snap(),
+                    FmSnapConfig::rim(),
+                    FmSnapConfig::wood(),
+                    FmSnapConfig::zap(),
+                ];
+                f.set_config(random_blend(&f.config(), &presets, amount, seed).clamped());
+            }
+            Self::Rimshot(r) => {
+                let presets = [
+                    RimshotConfig::classic(),
+                    RimshotConfig::tight(),
+                    RimshotConfig::woody(),
+                    RimshotConfig::ring(),
+                ];
+                r.set_config(random_blend(&r.config(), &presets, amount, seed).clamped());
+            }
+            Self::Cowbell(c) => {
+                let presets = [
+                    CowbellConfig::classic(),
+                    CowbellConfig::bright(),
+                    CowbellConfig::dark(),
+                    CowbellConfig::short(),
+                ];
+                c.set_config(random_blend(&c.config(), &presets, amount, seed).clamped());
+            }
+            Self::Shaker(s) => {
+                let presets = [
+                    ShakerConfig::tight(),
+                    ShakerConfig::swish(),
+                    ShakerConfig::cabasa(),
+                    ShakerConfig::egg(),
+                ];
+                s.set_config(random_blend(&s.config(), &presets, amount, seed).clamped());
+            }
+        }
+    }
+
+    /// Apply one of this instrument type's stock presets. Returns false for
+    /// an unknown preset ID.
+    fn load_preset(&mut self, preset_id: u32) -> bool {
+        let Some(config) = self.preset_config(preset_id) else {
+            return false;
+        };
+        self.set_config(config);
+        true
+    }
+
+    /// One of this instrument type's stock presets, or `None` for an unknown
+    /// preset ID.
+    fn preset_config(&self, preset_id: u32) -> Option<ChannelConfig> {
+        match self {
+            Self::Kick(_) => GooeyEngine::kick_preset_by_id(preset_id).map(ChannelConfig::Kick),
+            Self::Snare(_) => GooeyEngine::snare_preset_by_id(preset_id).map(ChannelConfig::Snare),
+            Self::HiHat(_) => GooeyEngine::hihat_preset_by_id(preset_id).map(ChannelConfig::HiHat),
+            Self::Tom(_) => GooeyEngine::tom_preset_by_id(preset_id).map(ChannelConfig::Tom),
+            Self::Bass(_) => GooeyEngine::bass_preset_by_id(preset_id).map(ChannelConfig::Bass),
+            Self::FmSnap(_) => {
+                GooeyEngine::fm_snap_preset_by_id(preset_id).map(ChannelConfig::FmSnap)
+            }
+            Self::Rimshot(_) => {
+                GooeyEngine::rimshot_preset_by_id(preset_id).map(ChannelConfig::Rimshot)
+            }
+            Self::Cowbell(_) => {
+                GooeyEngine::cowbell_preset_by_id(preset_id).map(ChannelConfig::Cowbell)
+            }
+            Self::Shaker(_) => {
+                GooeyEngine::shaker_preset_by_id(preset_id).map(ChannelConfig::Shaker)
+            }
+        }
+    }
+
+    /// Current config snapshot (smoothed parameters at their current values).
+    fn config(&self) -> ChannelConfig {
+        match self {
+            Self::Kick(k) => ChannelConfig::Kick(k.config()),
+            Self::Snare(s) => ChannelConfig::Snare(s.config()),
+            Self::HiHat(h) => ChannelConfig::HiHat(h.config()),
+            Self::Tom(t) => ChannelConfig::Tom(t.config()),
+            Self::Bass(b) => ChannelConfig::Bass(b.config()),
+            Self::FmSnap(f) => ChannelConfig::FmSnap(f.config()),
+            Self::Rimshot(r) => ChannelConfig::Rimshot(r.config()),
+            Self::Cowbell(c) => ChannelConfig::Cowbell(c.config()),
+            Self::Shaker(s) => ChannelConfig::Shaker(s.config()),
+        }
+    }
+
+    /// Apply a whole config. A config of another instrument type is ignored.
+    fn set_config(&mut self, config: ChannelConfig) {
+        match (self, config) {
+            (Self::Kick(k), ChannelConfig::Kick(c)) => k.set_config(c),
+            (Self::Snare(s), ChannelConfig::Snare(c)) => s.set_config(c),
+            (Self::HiHat(h), ChannelConfig::HiHat(c)) => h.set_config(c),
+            (Self::Tom(t), ChannelConfig::Tom(c)) => t.set_config(c),
+            (Self::Bass(b), ChannelConfig::Bass(c)) => b.set_config(c),
+            (Self::FmSnap(f), ChannelConfig::FmSnap(c)) => f.set_config(c),
+            (Self::Rimshot(r), ChannelConfig::Rimshot(c)) => r.set_config(c),
+            (Self::Cowbell(b), ChannelConfig::Cowbell(c)) => b.set_config(c),
+            (Self::Shaker(s), ChannelConfig::Shaker(c)) => s.set_config(c),
+            _ => {}
         }
     }
 
@@ -139,6 +620,10 @@ impl ChannelInstrument {
             Self::HiHat(h) => h.tick(current_time),
             Self::Tom(t) => t.tick(current_time),
             Self::Bass(b) => b.tick(current_time),
+            Self::FmSnap(f) => f.tick(current_time),
+            Self::Rimshot(r) => r.tick(current_time),
+            Self::Cowbell(c) => c.tick(current_time),
+            Self::Shaker(s) => s.tick(current_time),
         }
     }
 
@@ -152,6 +637,21 @@ impl ChannelInstrument {
         }
     }
 
+    /// Index of the instrument's tuning parameter (`*_PARAM_TUNING`).
+    fn tuning_param(&self) -> u32 {
+        match self {
+            Self::Kick(_) => KICK_PARAM_TUNING,
+            Self::Snare(_) => SNARE_PARAM_TUNING,
+            Self::HiHat(_) => HIHAT_PARAM_TUNING,
+            Self::Tom(_) => TOM_PARAM_TUNING,
+            Self::Bass(_) => BASS_PARAM_TUNING,
+            Self::FmSnap(_) => FM_SNAP_PARAM_TUNING,
+            Self::Rimshot(_) => RIMSHOT_PARAM_TUNING,
+            Self::Cowbell(_) => COWBELL_PARAM_TUNING,
+            Self::Shaker(_) => SHAKER_PARAM_TUNING,
+        }
+    }
+
     /// Get the current tuning value (0-1, 0.5 = neutral).
     fn get_tuning(&self) -> f32 {
         match self {
@@ -160,6 +660,10 @@ impl ChannelInstrument {
             Self::HiHat(h) => h.params.tuning.get(),
             Self::Tom(t) => t.tuning(),
             Self::Bass(b) => b.params.tuning.get(),
+            Self::FmSnap(f) => f.params.tuning.get(),
+            Self::Rimshot(r) => r.params.tuning.get(),
+            Self::Cowbell(c) => c.params.tuning.get(),
+            Self::Shaker(s) => s.params.tuning.get(),
         }
     }
 
@@ -199,6 +703,8 @@ impl ChannelInstrument {
                 SNARE_PARAM_AMP_DECAY_CURVE => s.set_amp_decay_curve(value),
                 SNARE_PARAM_TONAL_DECAY_CURVE => s.set_tonal_decay_curve(value),
                 SNARE_PARAM_TUNING => s.set_tuning(value),
+                SNARE_PARAM_NOISE_COLOR => s.set_noise_color(value),
+                SNARE_PARAM_CRACK_VELVET => s.set_crack_velvet(value >= 0.5),
                 _ => {}
             },
             Self::HiHat(h) => match param {
@@ -208,6 +714,20 @@ impl ChannelInstrument {
                 HIHAT_PARAM_VOLUME => h.set_volume(value),
                 HIHAT_PARAM_TONE => h.set_tone(value),
                 HIHAT_PARAM_TUNING => h.set_tuning(value),
+                HIHAT_PARAM_VELOCITY_TO_LEVEL => h.set_velocity_to_level(value),
+                HIHAT_PARAM_VELOCITY_TO_DECAY => h.set_velocity_to_decay(value),
+                HIHAT_PARAM_VELOCITY_TO_TONE => h.set_velocity_to_tone(value),
+                HIHAT_PARAM_OPEN_DECAY => h.set_open_decay(value),
+                HIHAT_PARAM_ARTICULATION => {
+                    if let Some(articulation) = HiHatArticulation::from_index(value as u8) {
+                        h.set_articulation(articulation);
+                    }
+                }
+                HIHAT_PARAM_MODE => {
+                    if let Some(mode) = HiHatMode::from_index(value as u8) {
+                        h.set_mode(mode);
+                    }
+                }
                 _ => {}
             },
             Self::Tom(t) => {
@@ -224,6 +744,10 @@ impl ChannelInstrument {
                     TOM_PARAM_VOLUME => t.set_volume(scaled),
                     // Tuning uses 0-1 directly (not 0-100)
                     TOM_PARAM_TUNING => t.set_tuning(value.clamp(0.0, 1.0)),
+                    TOM_PARAM_TRIANGLE_ENABLE => t.set_triangle_enabled(value >= 0.5),
+                    TOM_PARAM_TRIANGLE_LEVEL => t.set_triangle_level(scaled),
+                    TOM_PARAM_MORPH_LEVEL => t.set_morph_level(scaled),
+                    TOM_PARAM_MEMBRANE_GAIN => t.set_membrane_gain(scaled),
                     _ => {}
                 }
             }
@@ -246,6 +770,44 @@ impl ChannelInstrument {
                 BASS_PARAM_TUNING => b.set_tuning(value),
                 _ => {}
             },
+            Self::FmSnap(f) => match param {
+                FM_SNAP_PARAM_FREQUENCY => f.set_frequency(value),
+                FM_SNAP_PARAM_RATIO => f.set_ratio(value),
+                FM_SNAP_PARAM_INDEX => f.set_index(value),
+                FM_SNAP_PARAM_SNAP => f.set_snap(value),
+                FM_SNAP_PARAM_DECAY => f.set_decay(value),
+                FM_SNAP_PARAM_PITCH_DROP => f.set_pitch_drop(value),
+                FM_SNAP_PARAM_VOLUME => f.set_volume(value),
+                FM_SNAP_PARAM_TUNING => f.set_tuning(value),
+                _ => {}
+            },
+            Self::Rimshot(r) => match param {
+                RIMSHOT_PARAM_TUNE => r.set_tune(value),
+                RIMSHOT_PARAM_TONE => r.set_tone(value),
+                RIMSHOT_PARAM_CLICK => r.set_click(value),
+                RIMSHOT_PARAM_DECAY => r.set_decay(value),
+                RIMSHOT_PARAM_VOLUME => r.set_volume(value),
+                RIMSHOT_PARAM_TUNING => r.set_tuning(value),
+                _ => {}
+            },
+            Self::Cowbell(c) => match param {
+                COWBELL_PARAM_PITCH => c.set_pitch(value),
+                COWBELL_PARAM_TONE => c.set_tone(value),
+                COWBELL_PARAM_DECAY => c.set_decay(value),
+                COWBELL_PARAM_VOLUME => c.set_volume(value),
+                COWBELL_PARAM_TUNING => c.set_tuning(value),
+                _ => {}
+            },
+            Self::Shaker(s) => match param {
+                SHAKER_PARAM_DENSITY => s.set_density(value),
+                SHAKER_PARAM_COLOR => s.set_color(value),
+                SHAKER_PARAM_ATTACK => s.set_attack(value),
+                SHAKER_PARAM_DECAY => s.set_decay(value),
+                SHAKER_PARAM_ACCENT => s.set_accent(value),
+                SHAKER_PARAM_VOLUME => s.set_volume(value),
+                SHAKER_PARAM_TUNING => s.set_tuning(value),
+                _ => {}
+            },
         }
     }
 
@@ -289,6 +851,8 @@ impl ChannelInstrument {
                 SNARE_PARAM_AMP_DECAY_CURVE => s.params.amp_decay_curve.target(),
                 SNARE_PARAM_TONAL_DECAY_CURVE => s.params.tonal_decay_curve.target(),
                 SNARE_PARAM_TUNING => s.params.tuning.target(),
+                SNARE_PARAM_NOISE_COLOR => s.params.noise_color.target(),
+                SNARE_PARAM_CRACK_VELVET => s.params.crack_velvet as u8 as f32,
                 _ => f32::NAN,
             },
             Self::HiHat(h) => match param {
@@ -298,6 +862,12 @@ impl ChannelInstrument {
                 HIHAT_PARAM_VOLUME => h.params.volume.target(),
                 HIHAT_PARAM_TONE => h.params.tone.target(),
                 HIHAT_PARAM_TUNING => h.params.tuning.target(),
+                HIHAT_PARAM_VELOCITY_TO_LEVEL => h.velocity_routing().level,
+                HIHAT_PARAM_VELOCITY_TO_DECAY => h.velocity_routing().decay,
+                HIHAT_PARAM_VELOCITY_TO_TONE => h.velocity_routing().tone,
+                HIHAT_PARAM_OPEN_DECAY => h.params.open_decay.target(),
+                HIHAT_PARAM_ARTICULATION => h.articulation().index() as f32,
+                HIHAT_PARAM_MODE => h.mode.index() as f32,
                 _ => f32::NAN,
             },
             Self::Tom(t) => match param {
@@ -312,9 +882,75 @@ impl ChannelInstrument {
                 TOM_PARAM_VOLUME => t.volume() / 100.0,
                 // Tuning is stored 0-1 directly, mirroring the setter exception.
                 TOM_PARAM_TUNING => t.tuning(),
+                TOM_PARAM_TRIANGLE_ENABLE => {
+                    if t.triangle_enabled() {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                }
+                TOM_PARAM_TRIANGLE_LEVEL => t.triangle_level() / 100.0,
+                TOM_PARAM_MORPH_LEVEL => t.morph_level() / 100.0,
+                TOM_PARAM_MEMBRANE_GAIN => t.membrane_gain() / 100.0,
+                _ => f32::NAN,
+            },
+            Self::Bass(b) => match param {
+                BASS_PARAM_FREQUENCY => b.params.frequency.target(),
+                BASS_PARAM_SUB_LEVEL => b.params.sub_level.target(),
+                BASS_PARAM_OSC_LEVEL => b.params.osc_level.target(),
+                BASS_PARAM_DETUNE_LEVEL => b.params.detune_level.target(),
+                BASS_PARAM_DETUNE_AMOUNT => b.params.detune_amount.target(),
+                BASS_PARAM_OSC_SHAPE => b.params.osc_shape.target(),
+                BASS_PARAM_FILTER_CUTOFF => b.params.filter_cutoff.target(),
+                BASS_PARAM_FILTER_RESONANCE => b.params.filter_resonance.target(),
+                BASS_PARAM_FILTER_ENV_AMOUNT => b.params.filter_env_amount.target(),
+                BASS_PARAM_FILTER_ENV_DECAY => b.params.filter_env_decay.target(),
+                BASS_PARAM_FILTER_ENV_CURVE => b.params.filter_env_curve.target(),
+                BASS_PARAM_AMP_DECAY => b.params.amp_decay.target(),
+                BASS_PARAM_AMP_DECAY_CURVE => b.params.amp_decay_curve.target(),
+                BASS_PARAM_OVERDRIVE => b.params.overdrive.target(),
+                BASS_PARAM_VOLUME => b.params.volume.target(),
+                BASS_PARAM_TUNING => b.params.tuning.target(),
+                _ => f32::NAN,
+            },
+            Self::FmSnap(f) => match param {
+                FM_SNAP_PARAM_FREQUENCY => f.params.frequency.target(),
+                FM_SNAP_PARAM_RATIO => f.params.ratio.target(),
+                FM_SNAP_PARAM_INDEX => f.params.index.target(),
+                FM_SNAP_PARAM_SNAP => f.params.snap.target(),
+                FM_SNAP_PARAM_DECAY => f.params.decay.target(),
+                FM_SNAP_PARAM_PITCH_DROP => f.params.pitch_drop.target(),
+                FM_SNAP_PARAM_VOLUME => f.params.volume.target(),
+                FM_SNAP_PARAM_TUNING => f.params.tuning.target(),
+                _ => f32::NAN,
+            },
+            Self::Rimshot(r) => match param {
+                RIMSHOT_PARAM_TUNE => r.params.tune.target(),
+                RIMSHOT_PARAM_TONE => r.params.tone.target(),
+                RIMSHOT_PARAM_CLICK => r.params.click.target(),
+                RIMSHOT_PARAM_DECAY => r.params.decay.target(),
+                RIMSHOT_PARAM_VOLUME => r.params.volume.target(),
+                RIMSHOT_PARAM_TUNING => r.params.tuning.target(),
+                _ => f32::NAN,
+            },
+            Self::Cowbell(c) => match param {
+                COWBELL_PARAM_PITCH => c.params.pitch.target(),
+                COWBELL_PARAM_TONE => c.params.tone.target(),
+                COWBELL_PARAM_DECAY => c.params.decay.target(),
+                COWBELL_PARAM_VOLUME => c.params.volume.target(),
+                COWBELL_PARAM_TUNING => c.params.tuning.target(),
+                _ => f32::NAN,
+            },
+            Self::Shaker(s) => match param {
+                SHAKER_PARAM_DENSITY => s.params.density.target(),
+                SHAKER_PARAM_COLOR => s.params.color.target(),
+                SHAKER_PARAM_ATTACK => s.params.attack.target(),
+                SHAKER_PARAM_DECAY => s.params.decay.target(),
+                SHAKER_PARAM_ACCENT => s.params.accent.target(),
+                SHAKER_PARAM_VOLUME => s.params.volume.target(),
+                SHAKER_PARAM_TUNING => s.params.tuning.target(),
                 _ => f32::NAN,
             },
-            Self::Bass(_) => f32::NAN,
         }
     }
 
@@ -354,6 +990,7 @@ impl ChannelInstrument {
                 SNARE_PARAM_AMP_DECAY_CURVE => s.params.amp_decay_curve.set_bipolar(value),
                 SNARE_PARAM_TONAL_DECAY_CURVE => s.params.tonal_decay_curve.set_bipolar(value),
                 SNARE_PARAM_TUNING => s.params.tuning.set_bipolar(value),
+                SNARE_PARAM_NOISE_COLOR => s.params.noise_color.set_bipolar(value),
                 _ => {}
             },
             Self::HiHat(h) => match param {
@@ -363,6 +1000,7 @@ impl ChannelInstrument {
                 HIHAT_PARAM_TONE => h.params.tone.set_bipolar(value),
                 HIHAT_PARAM_VOLUME => h.params.volume.set_bipolar(value),
                 HIHAT_PARAM_TUNING => h.params.tuning.set_bipolar(value),
+                HIHAT_PARAM_OPEN_DECAY => h.params.open_decay.set_bipolar(value),
                 _ => {}
             },
             Self::Tom(t) => {
@@ -379,6 +1017,9 @@ impl ChannelInstrument {
                     TOM_PARAM_VOLUME => t.set_volume(scaled),
                     // Tuning uses 0-1 directly (not 0-100)
                     TOM_PARAM_TUNING => t.set_tuning(value.clamp(0.0, 1.0)),
+                    TOM_PARAM_TRIANGLE_LEVEL => t.set_triangle_level(scaled),
+                    TOM_PARAM_MORPH_LEVEL => t.set_morph_level(scaled),
+                    TOM_PARAM_MEMBRANE_GAIN => t.set_membrane_gain(scaled),
                     _ => {}
                 }
             }
@@ -401,6 +1042,44 @@ impl ChannelInstrument {
                 BASS_PARAM_TUNING => b.params.tuning.set_bipolar(value),
                 _ => {}
             },
+            Self::FmSnap(f) => match param {
+                FM_SNAP_PARAM_FREQUENCY => f.params.frequency.set_bipolar(value),
+                FM_SNAP_PARAM_RATIO => f.params.ratio.set_bipolar(value),
+                FM_SNAP_PARAM_INDEX => f.params.index.set_bipolar(value),
+                FM_SNAP_PARAM_SNAP => f.params.snap.set_bipolar(value),
+                FM_SNAP_PARAM_DECAY => f.params.decay.set_bipolar(value),
+                FM_SNAP_PARAM_PITCH_DROP => f.params.pitch_drop.set_bipolar(value),
+                FM_SNAP_PARAM_VOLUME => f.params.volume.set_bipolar(value),
+                FM_SNAP_PARAM_TUNING => f.params.tuning.set_bipolar(value),
+                _ => {}
+            },
+            Self::Rimshot(r) => match param {
+                RIMSHOT_PARAM_TUNE => r.params.tune.set_bipolar(value),
+                RIMSHOT_PARAM_TONE => r.params.tone.set_bipolar(value),
+                RIMSHOT_PARAM_CLICK => r.params.click.set_bipolar(value),
+                RIMSHOT_PARAM_DECAY => r.params.decay.set_bipolar(value),
+                RIMSHOT_PARAM_VOLUME => r.params.volume.set_bipolar(value),
+                RIMSHOT_PARAM_TUNING => r.params.tuning.set_bipolar(value),
+                _ => {}
+            },
+            Self::Cowbell(c) => match param {
+                COWBELL_PARAM_PITCH => c.params.pitch.set_bipolar(value),
+                COWBELL_PARAM_TONE => c.params.tone.set_bipolar(value),
+                COWBELL_PARAM_DECAY => c.params.decay.set_bipolar(value),
+                COWBELL_PARAM_VOLUME => c.params.volume.set_bipolar(value),
+                COWBELL_PARAM_TUNING => c.params.tuning.set_bipolar(value),
+                _ => {}
+            },
+            Self::Shaker(s) => match param {
+                SHAKER_PARAM_DENSITY => s.params.density.set_bipolar(value),
+                SHAKER_PARAM_COLOR => s.params.color.set_bipolar(value),
+                SHAKER_PARAM_ATTACK => s.params.attack.set_bipolar(value),
+                SHAKER_PARAM_DECAY => s.params.decay.set_bipolar(value),
+                SHAKER_PARAM_ACCENT => s.params.accent.set_bipolar(value),
+                SHAKER_PARAM_VOLUME => s.params.volume.set_bipolar(value),
+                SHAKER_PARAM_TUNING => s.params.tuning.set_bipolar(value),
+                _ => {}
+            },
         }
     }
 }
@@ -412,18 +1091,25 @@ enum ChannelBlender {
     HiHat(PresetBlender<HiHat2Config>),
     Tom(PresetBlender<Tom2Config>),
     Bass(PresetBlender<BassConfig>),
+    FmSnap(PresetBlender<FmSnapConfig>),
+    Rimshot(PresetBlender<RimshotConfig>),
+    Cowbell(PresetBlender<CowbellConfig>),
+    Shaker(PresetBlender<ShakerConfig>),
 }
 
 impl ChannelBlender {
-    /// Blend at position (x,y) and apply the result to the instrument.
-    fn blend_and_apply(&self, instrument: &mut ChannelInstrument, x: f32, y: f32) {
-        match (self, instrument) {
-            (Self::Kick(b), ChannelInstrument::Kick(k)) => k.set_config(b.blend(x, y)),
-            (Self::Snare(b), ChannelInstrument::Snare(s)) => s.set_config(b.blend(x, y)),
-            (Self::HiHat(b), ChannelInstrument::HiHat(h)) => h.set_config(b.blend(x, y)),
-            (Self::Tom(b), ChannelInstrument::Tom(t)) => t.set_config(b.blend(x, y)),
-            (Self::Bass(b), ChannelInstrument::Bass(bs)) => bs.set_config(b.blend(x, y)),
-            _ => {} // type mismatch — should not happen if blender/instrument are kept in sync
+    /// The blended config at position (x,y).
+    fn blend(&self, x: f32, y: f32) -> ChannelConfig {
+        match self {
+            Self::Kick(b) => ChannelConfig::Kick(b.blend(x, y)),
+            Self::Snare(b) => ChannelConfig::Snare(b.blend(x, y)),
+            Self::HiHat(b) => ChannelConfig::HiHat(b.blend(x, y)),
+            Self::Tom(b) => ChannelConfig::Tom(b.blend(x, y)),
+            Self::Bass(b) => ChannelConfig::Bass(b.blend(x, y)),
+            Self::FmSnap(b) => ChannelConfig::FmSnap(b.blend(x, y)),
+            Self::Rimshot(b) => ChannelConfig::Rimshot(b.blend(x, y)),
+            Self::Cowbell(b) => ChannelConfig::Cowbell(b.blend(x, y)),
+            Self::Shaker(b) => ChannelConfig::Shaker(b.blend(x, y)),
         }
     }
 
@@ -485,6 +1171,50 @@ impl ChannelBlender {
                     }
                 }
             }
+            Self::FmSnap(b) => {
+                if let Some(config) = GooeyEngine::fm_snap_preset_by_id(preset_id) {
+                    match corner {
+                        BLEND_CORNER_BOTTOM_LEFT => b.set_bottom_left(config),
+                        BLEND_CORNER_BOTTOM_RIGHT => b.set_bottom_right(config),
+                        BLEND_CORNER_TOP_LEFT => b.set_top_left(config),
+                        BLEND_CORNER_TOP_RIGHT => b.set_top_right(config),
+                        _ => {}
+                    }
+                }
+            }
+            Self::Rimshot(b) => {
+                if let Some(config) = GooeyEngine::rimshot_preset_by_id(preset_id) {
+                    match corner {
+                        BLEND_CORNER_BOTTOM_LEFT => b.set_bottom_left(config),
+                        BLEND_CORNER_BOTTOM_RIGHT => b.set_bottom_right(config),
+                        BLEND_CORNER_TOP_LEFT => b.set_top_left(config),
+                        BLEND_CORNER_TOP_RIGHT => b.set_top_right(config),
+                        _ => {}
+                    }
+                }
+            }
+            Self::Cowbell(b) => {
+                if let Some(config) = GooeyEngine::cowbell_preset_by_id(preset_id) {
+                    match corner {
+                        BLEND_CORNER_BOTTOM_LEFT => b.set_bottom_left(config),
+                        BLEND_CORNER_BOTTOM_RIGHT => b.set_bottom_right(config),
+                        BLEND_CORNER_TOP_LEFT => b.set_top_left(config),
+                        BLEND_CORNER_TOP_RIGHT => b.set_top_right(config),
+                        _ => {}
+                    }
+                }
+            }
+            Self::Shaker(b) => {
+                if let Some(config) = GooeyEngine::shaker_preset_by_id(preset_id) {
+                    match corner {
+                        BLEND_CORNER_BOTTOM_LEFT => b.set_bottom_left(config),
+                        BLEND_CORNER_BOTTOM_RIGHT => b.set_bottom_right(config),
+                        BLEND_CORNER_TOP_LEFT => b.set_top_left(config),
+                        BLEND_CORNER_TOP_RIGHT => b.set_top_right(config),
+                        _ => {}
+                    }
+                }
+            }
         }
     }
 
@@ -521,6 +1251,30 @@ impl ChannelBlender {
                 BassConfig::reese(),
                 BassConfig::stab(),
             )),
+            INSTRUMENT_FM_SNAP => Self::FmSnap(PresetBlender::new(
+                FmSnapConfig::snap(),
+                FmSnapConfig::rim(),
+                FmSnapConfig::wood(),
+                FmSnapConfig::zap(),
+            )),
+            INSTRUMENT_RIMSHOT => Self::Rimshot(PresetBlender::new(
+                RimshotConfig::classic(),
+                RimshotConfig::tight(),
+                RimshotConfig::woody(),
+                RimshotConfig::ring(),
+            )),
+            INSTRUMENT_COWBELL => Self::Cowbell(PresetBlender::new(
+                CowbellConfig::classic(),
+                CowbellConfig::bright(),
+                CowbellConfig::dark(),
+                CowbellConfig::short(),
+            )),
+            INSTRUMENT_SHAKER => Self::Shaker(PresetBlender::new(
+                ShakerConfig::tight(),
+                ShakerConfig::swish(),
+                ShakerConfig::cabasa(),
+                ShakerConfig::egg(),
+            )),
             _ => Self::Kick(PresetBlender::new(
                 KickConfig::tight(),
                 KickConfig::punch(),
@@ -563,6 +1317,30 @@ impl ChannelBlender {
                 BASS_PRESET_REESE,
                 BASS_PRESET_STAB,
             ],
+            INSTRUMENT_FM_SNAP => [
+                FM_SNAP_PRESET_SNAP,
+                FM_SNAP_PRESET_RIM,
+                FM_SNAP_PRESET_WOOD,
+                FM_SNAP_PRESET_ZAP,
+            ],
+            INSTRUMENT_RIMSHOT => [
+                RIMSHOT_PRESET_CLASSIC,
+                RIMSHOT_PRESET_TIGHT,
+                RIMSHOT_PRESET_WOODY,
+                RIMSHOT_PRESET_RING,
+            ],
+            INSTRUMENT_COWBELL => [
+                COWBELL_PRESET_CLASSIC,
+                COWBELL_PRESET_BRIGHT,
+                COWBELL_PRESET_DARK,
+                COWBELL_PRESET_SHORT,
+            ],
+            INSTRUMENT_SHAKER => [
+                SHAKER_PRESET_TIGHT,
+                SHAKER_PRESET_SWISH,
+                SHAKER_PRESET_CABASA,
+                SHAKER_PRESET_EGG,
+            ],
             _ => [0, 1, 2, 3],
         }
     }
@@ -571,20 +1349,160 @@ impl ChannelBlender {
 /// Opaque wrapper around the audio engine for FFI
 ///
 /// This struct provides a simplified C-compatible interface for iOS integration.
-/// It manages 5 channels, each with an instrument and its own
-/// 16-step sequencer with sample-accurate timing. Channels can be reassigned
-/// to any instrument type at runtime.
+/// It manages 9 built-in channels, plus up to `CHANNEL_MAX` in total with
+/// host-created slots, each with an instrument and its own 16-step sequencer
+/// with sample-accurate timing. Channels can be reassigned to any instrument
+/// type at runtime.
 ///
 /// Parameter smoothing is handled internally by each instrument,
 /// so all parameter changes are automatically smoothed to prevent clicks/pops.
-/// Number of drum voices in the kit (kick, snare, hihat, tom). Bass is a
-/// separate top-level voice, so the addressable voice space
-/// (`NUM_INSTRUMENTS` = 5) is the kit voices plus bass at index 4.
+/// Number of drum voices in the kit (kick, snare, hihat, tom). Bass, the FM
+/// snap, the rimshot, the cowbell and the shaker are separate top-level voices,
+/// so the addressable voice space (`NUM_INSTRUMENTS` = 9) is the kit voices plus
+/// bass at index 4, the FM snap at 5, the rimshot at 6, the cowbell at 7 and
+/// the shaker at 8.
 const KIT_VOICE_COUNT: usize = 4;
 /// Maximum independently routable sampler racks in one FFI engine.
 pub const SAMPLER_RACK_MAX: u32 = 4;
 /// PCM pads in each sampler rack and steps in its sequencer.
 pub const SAMPLER_SLOT_COUNT: u32 = crate::instruments::sampler::SAMPLER_SLOT_COUNT as u32;
+/// Most slices one sampler slot can be chopped into.
+pub const SAMPLER_MAX_SLICES: u32 = crate::instruments::sampler::SAMPLER_MAX_SLICES as u32;
+
+/// Largest normalized offset per-hit variation applies at depth 1.0.
+const VARIATION_MAX_OFFSET: f32 = 0.15;
+/// Parameter indices addressable by a variation mask (one bit each).
+const VARIATION_MAX_PARAMS: usize = 32;
+/// Most micro-variants a round-robin cycle can hold.
+pub const VARIATION_MAX_VARIANTS: u32 = 4;
+
+/// Per-hit parameter variation ("round robin") for one voice. Each trigger
+/// nudges the selected parameters away from the values the user set, either
+/// by a fresh random offset or by cycling through 2-4 fixed micro-variants,
+/// so repeated hits stop sounding identical.
+struct Variation {
+    /// Depth (0.0 = off, 1.0 = ±`VARIATION_MAX_OFFSET`), f32 bits.
+    depth: AtomicU32,
+    /// Bitmask of the parameter indices that vary.
+    params: AtomicU32,
+    /// 0 for a new random offset per hit, 2-4 to cycle through that many
+    /// fixed micro-variants.
+    variants: AtomicU32,
+    /// Source of random offsets.
+    rng: Rng,
+    /// Hash seed for the fixed round-robin variants.
+    seed: u32,
+    next_variant: u32,
+    /// `(base, varied)` for each parameter the last hit varied: the value the
+    /// user set and the value the instrument was given instead.
+    applied: [Option<(f32, f32)>; VARIATION_MAX_PARAMS],
+}
+
+impl Variation {
+    fn new(instrument_type: u32) -> Self {
+        let seed = 0x2545_f491 ^ (instrument_type + 1).wrapping_mul(0x9e37_79b9);
+        Self {
+            depth: AtomicU32::new(0.0_f32.to_bits()),
+            params: AtomicU32::new(0),
+            variants: AtomicU32::new(0),
+            rng: Rng::new(seed as u64),
+            seed,
+            next_variant: 0,
+            applied: [None; VARIATION_MAX_PARAMS],
+        }
+    }
+
+    /// The value the user set for `param`, looking through any offset the
+    /// last hit applied.
+    fn base_param(&self, instrument: &ChannelInstrument, param: u32) -> f32 {
+        let current = instrument.get_param(param);
+        match self.applied.get(param as usize) {
+            Some(Some((base, varied))) if *varied == current => *base,
+            _ => current,
+        }
+    }
+
+    /// Offset in -1.0..1.0 for `param` on this hit.
+    fn offset(&mut self, variant: Option<u32>, param: u32) -> f32 {
+        let bits = match variant {
+            // Fixed variants hash (seed, variant, param) so each variant
+            // always lands on the same values.
+            Some(variant) => {
+                let mut h = self.seed
+                    ^ variant.wrapping_mul(0x85eb_ca6b)
+                    ^ (param + 1).wrapping_mul(0xc2b2_ae35);
+                h ^= h >> 16;
+                h = h.wrapping_mul(0x7feb_352d);
+                h ^= h >> 15;
+                h = h.wrapping_mul(0x846c_a68b);
+                h ^ (h >> 16)
+            }
+            None => self.rng.next_u32(),
+        };
+        bits as f32 / u32::MAX as f32 * 2.0 - 1.0
+    }
+
+    /// Vary the selected parameters for the next hit, restoring any that
+    /// are no longer selected. Parameters the user changed since the last
+    /// hit take their new value as the base.
+    fn apply(&mut self, instrument: &mut ChannelInstrument) {
+        let depth = f32::from_bits(self.depth.load(Ordering::Relaxed));
+        let mask = if depth > 0.0 {
+            self.params.load(Ordering::Relaxed)
+        } else {
+            0
+        };
+        if mask == 0 && self.applied.iter().all(Option::is_none) {
+            return;
+        }
+        let variant = match self.variants.load(Ordering::Relaxed) {
+            0 => None,
+            count => {
+                let variant = self.next_variant % count;
+                self.next_variant = (variant + 1) % count;
+                Some(variant)
+            }
+        };
+
+        for param in 0..VARIATION_MAX_PARAMS as u32 {
+            let selected = mask & (1 << param) != 0;
+            if !selected && self.applied[param as usize].is_none() {
+                continue;
+            }
+            let base = self.base_param(instrument, param);
+            self.applied[param as usize] = None;
+            if base.is_nan() {
+                continue;
+            }
+            let value = if selected {
+                let offset = self.offset(variant, param) * depth * VARIATION_MAX_OFFSET;
+                (base + offset).clamp(0.0, 1.0)
+            } else {
+                base
+            };
+            instrument.set_param(param, value);
+            if selected {
+                self.applied[param as usize] = Some((base, instrument.get_param(param)));
+            }
+        }
+        // Land the hit on the varied values rather than ramping toward them.
+        instrument.snap_params();
+    }
+
+    /// Forget varied values and settings (on instrument reassignment).
+    fn clear(&mut self) {
+        self.params.store(0, Ordering::Relaxed);
+        self.applied = [None; VARIATION_MAX_PARAMS];
+        self.next_variant = 0;
+    }
+
+    /// Draw random offsets and the fixed variants' hash seed from `rng`.
+    fn reseed(&mut self, mut rng: Rng) {
+        self.seed = rng.next_u32();
+        self.rng = rng;
+        self.next_variant = 0;
+    }
+}
 
 /// One voice's complete per-channel state: the instrument plus its sequencer,
 /// preset blender, mixer strip (fader / mute-solo / pan / peak), manual-trigger
@@ -595,28 +1513,81 @@ struct VoiceStrip {
     instrument: ChannelInstrument,
     sequencer: Sequencer,
     blender: ChannelBlender,
-    blend_enabled: bool,
+    blend_enabled: AtomicBool,
     blend_x: f32,
     blend_y: f32,
     blend_corner_presets: [u32; 4],
-    /// Mixer fader (0.0–1.0), applied after synthesis/blend; the blend system
-    /// cannot override it. Was `instrument_channel_gains[i]`.
+    /// Mixer fader (linear, up to +CHANNEL_GAIN_MAX_DB), applied after
+    /// synthesis/blend; the blend system cannot override it. Was
+    /// `instrument_channel_gains[i]`.
     channel_gain: SmoothedParam,
     /// Smoothed mute/solo multiplier for click-free transitions. Was
     /// `instrument_gains[i]`.
     mute_gain: SmoothedParam,
+    /// Loudness-normalization gain for the loaded preset or blend position
+    /// (unity for the instrument's default sound or with normalization off).
+    preset_gain: SmoothedParam,
+    /// Auto-gain (dB) of each blend corner preset, blended with the configs.
+    corner_gain_db: PresetBlender<f32>,
+    /// Preset last loaded directly (not through the blend pad).
+    loaded_preset: Option<u32>,
+    /// In-progress crossfade to a newly applied whole config (blend position
+    /// or preset load), ticked by the render loop.
+    config_fade: Option<ConfigFade<ChannelConfig>>,
     /// Stereo pan (0.0 = left, 0.5 = center, 1.0 = right), equal-power. Was
     /// `instrument_pans[i]`.
     pan: SmoothedParam,
     muted: AtomicBool,
     soloed: AtomicBool,
+    /// Mute/solo changes waiting for the next quantized boundary
+    /// (`MODE_REQUEST_*`), set by the UI and applied by the audio thread.
+    mute_request: AtomicU8,
+    solo_request: AtomicU8,
     /// Peak amplitude since last read (f32 bits, read-and-reset by UI). Was
     /// `channel_peaks[i]`.
     peak: AtomicU32,
+    /// Decaying level of the raw instrument output, before the fader.
+    meter_pre: ChannelMeter,
+    /// Decaying level after fader, mute/solo, preset gain and beat repeat.
+    meter_post: ChannelMeter,
     trigger_pending: AtomicBool,
     trigger_velocity: AtomicU32, // f32 bits stored atomically
-    /// Saved global frequency for restoring after per-step MIDI note overrides.
-    saved_global_freq: Option<f32>,
+    /// MIDI note for the pending trigger, or `NO_TRIGGER_NOTE`.
+    trigger_note: AtomicU8,
+    /// Note-off requested by the UI, applied after any pending trigger.
+    release_pending: AtomicBool,
+    /// Global frequency saved while per-step MIDI notes override it.
+    step_note: StepNote,
+    /// Saved global tuning for restoring after per-step tune offsets.
+    saved_global_tuning: Option<f32>,
+    /// Random per-hit pan spread width (0.0 = off, 1.0 = full stereo field),
+    /// f32 bits. Each trigger offsets `pan` by up to ±width/2.
+    pan_spread: AtomicU32,
+    /// Pan offset drawn for the current hit.
+    pan_offset: f32,
+    /// Master effects this voice skips, as `1 << EFFECT_*` bits (see
+    /// `gooey_engine_set_channel_fx_bypass`).
+    fx_bypass: AtomicU32,
+    /// Instrument groups the voice is tagged with, as `1 << GROUP_*` bits
+    /// (see `gooey_engine_set_channel_group`).
+    groups: AtomicU32,
+    /// Product of the gains of the voice's groups (capped at
+    /// +CHANNEL_GAIN_MAX_DB), applied with the fader.
+    group_gain: SmoothedParam,
+    /// Source of `pan_offset`.
+    pan_rng: Rng,
+    /// Per-hit parameter variation.
+    variation: Variation,
+    /// Engine time of the most recent trigger, for voice-age introspection.
+    last_trigger_time: Option<f64>,
+    /// Set by a panic: the instrument keeps ticking so its envelopes run
+    /// out, but its output is dropped until the next trigger.
+    choked: bool,
+    /// Samples until a gated step's note-off, counted down by the render loop.
+    gate_remaining: Option<u64>,
+    /// Set by `gooey_engine_freeze_channel`: the pattern rendered to audio,
+    /// played instead of ticking the instrument.
+    frozen: Option<FrozenLoop>,
 }
 
 impl VoiceStrip {
@@ -632,73 +1603,937 @@ impl VoiceStrip {
             instrument,
             sequencer,
             blender: ChannelBlender::default_for_type(instrument_type),
-            blend_enabled: false,
+            blend_enabled: AtomicBool::new(false),
             blend_x: 0.5,
             blend_y: 0.5,
             blend_corner_presets: ChannelBlender::default_corner_preset_ids(instrument_type),
-            channel_gain: SmoothedParam::new(1.0, 0.0, 1.0, sample_rate, 10.0),
+            channel_gain: SmoothedParam::new(
+                1.0,
+                0.0,
+                db_to_amplitude(CHANNEL_GAIN_MAX_DB),
+                sample_rate,
+                10.0,
+            ),
             mute_gain: SmoothedParam::new(1.0, 0.0, 1.0, sample_rate, 10.0),
+            preset_gain: SmoothedParam::new(
+                1.0,
+                0.0,
+                db_to_amplitude(MAX_AUTO_GAIN_DB),
+                sample_rate,
+                30.0,
+            ),
+            corner_gain_db: PresetBlender::uniform(0.0),
+            loaded_preset: None,
+            config_fade: None,
             pan: SmoothedParam::new(0.5, 0.0, 1.0, sample_rate, 10.0),
             muted: AtomicBool::new(false),
             soloed: AtomicBool::new(false),
+            mute_request: AtomicU8::new(MODE_REQUEST_NONE),
+            solo_request: AtomicU8::new(MODE_REQUEST_NONE),
             peak: AtomicU32::new(0.0_f32.to_bits()),
+            meter_pre: ChannelMeter::new(sample_rate),
+            meter_post: ChannelMeter::new(sample_rate),
             trigger_pending: AtomicBool::new(false),
             trigger_velocity: AtomicU32::new(1.0_f32.to_bits()),
-            saved_global_freq: None,
+            trigger_note: AtomicU8::new(NO_TRIGGER_NOTE),
+            release_pending: AtomicBool::new(false),
+            step_note: StepNote::default(),
+            saved_global_tuning: None,
+            pan_spread: AtomicU32::new(0.0_f32.to_bits()),
+            pan_offset: 0.0,
+            fx_bypass: AtomicU32::new(0),
+            groups: AtomicU32::new(1 << default_group(instrument_type)),
+            group_gain: SmoothedParam::new(
+                1.0,
+                0.0,
+                db_to_amplitude(CHANNEL_GAIN_MAX_DB),
+                sample_rate,
+                10.0,
+            ),
+            // Distinct per-type seeds so a hat roll and a snare roll spread differently.
+            pan_rng: Rng::new(
+                (0x6d2b_79f5 ^ (instrument_type + 1).wrapping_mul(0x9e37_79b9)) as u64,
+            ),
+            variation: Variation::new(instrument_type),
+            last_trigger_time: None,
+            choked: false,
+            gate_remaining: None,
+            frozen: None,
         }
     }
 
-    /// Record a new peak (read-and-reset by the UI). `level` is a pre-pan mono
-    /// magnitude. Uses the same compare-and-store pattern as the old
-    /// `channel_peaks` array.
-    fn record_peak(&self, level: f32) {
-        let prev = f32::from_bits(self.peak.load(Ordering::Relaxed));
-        if level > prev {
-            self.peak.store(level.to_bits(), Ordering::Relaxed);
+    /// Restart the voice's random streams (instrument noise, pan spread,
+    /// variation) from the engine `seed`.
+    fn reseed(&mut self, seed: u64, channel: u32) {
+        self.instrument
+            .reseed(Rng::stream(seed, RngStream::Noise, channel).next_u64());
+        self.pan_rng = Rng::stream(seed, RngStream::PanSpread, channel);
+        self.variation
+            .reseed(Rng::stream(seed, RngStream::Variation, channel));
+    }
+
+    /// Trigger the instrument, drawing a new pan offset and parameter
+    /// variation for the hit when enabled. `articulation` is the step's
+    /// articulation, if it has one.
+    fn trigger(&mut self, time: f64, velocity: f32, articulation: Option<u8>) {
+        let spread = f32::from_bits(self.pan_spread.load(Ordering::Relaxed));
+        self.pan_offset = if spread > 0.0 {
+            (self.pan_rng.next_f32() - 0.5) * spread
+        } else {
+            0.0
+        };
+        self.finish_config_fade();
+        self.variation.apply(&mut self.instrument);
+        self.last_trigger_time = Some(time);
+        self.choked = false;
+        self.gate_remaining = None;
+        self.instrument
+            .trigger_articulated(time, velocity, articulation);
+    }
+
+    /// Count down a gated step by one sample, releasing the instrument when
+    /// the gate closes.
+    fn tick_gate(&mut self, time: f64) {
+        match self.gate_remaining {
+            Some(remaining) if remaining > 1 => self.gate_remaining = Some(remaining - 1),
+            Some(_) => {
+                self.release(time);
+            }
+            None => {}
         }
     }
-}
 
-/// A submixable collection of drum voices (kick, snare, hihat, tom). Each voice
-/// keeps its own sequencer, blender, and mixer strip; the whole kit is routed as
-/// one source (`SourceId::DrumKit`) in the mixer graph. Bass is intentionally not
-/// a member — it is a melodic voice routed as its own source.
-struct DrumKit {
-    voices: [VoiceStrip; KIT_VOICE_COUNT],
-}
+    /// Note-off, cancelling any gate still counting down.
+    fn release(&mut self, time: f64) {
+        self.gate_remaining = None;
+        self.instrument.release(time);
+    }
+
+    /// Apply a step's tuning offset (semitones), saving the channel's own
+    /// tuning the first time; a step without one restores it.
+    fn apply_step_tune(&mut self, tune: Option<f32>) {
+        let param = self.instrument.tuning_param();
+        if let Some(semitones) = tune {
+            let global = *self
+                .saved_global_tuning
+                .get_or_insert_with(|| self.instrument.get_tuning());
+            // The tuning parameter spans ±12 semitones over 0-1
+            let tuning = (global + semitones / 24.0).clamp(0.0, 1.0);
+            self.instrument.set_param(param, tuning);
+            self.instrument.snap_params();
+        } else if let Some(saved) = self.saved_global_tuning.take() {
+            self.instrument.set_param(param, saved);
+            self.instrument.snap_params();
+        }
+    }
 
-pub struct GooeyEngine {
-    // Drum voices (kick, snare, hihat, tom) grouped as one submixable kit.
+    /// Read a parameter as the user set it, ignoring per-hit variation.
+    fn param(&self, param: u32) -> f32 {
+        self.variation.base_param(&self.instrument, param)
+    }
+
+    /// Blend the corner presets at (x, y) into the instrument, with the
+    /// matching loudness-normalization gain.
+    fn apply_blend(&mut self, x: f32, y: f32, sample_rate: f32) {
+        let config = self.blender.blend(x, y);
+        self.fade_to_config(config, sample_rate);
+        self.preset_gain
+            .set_target(db_to_amplitude(self.corner_gain_db.blend(x, y)));
+        self.loaded_preset = None;
+    }
+
+    /// Move the instrument to `config`. While it is sounding, every field
+    /// ramps along one short [`ConfigFade`] so the change doesn't click; a
+    /// silent instrument takes the config directly.
+    fn fade_to_config(&mut self, config: ChannelConfig, sample_rate: f32) {
+        if !self.instrument.is_active() {
+            self.config_fade = None;
+            self.instrument.set_config(config);
+            return;
+        }
+        let from = self.instrument.config();
+        self.config_fade = Some(ConfigFade::new(from, config, CONFIG_FADE_MS, sample_rate));
+    }
+
+    /// Advance the config crossfade by one sample.
+    fn tick_config_fade(&mut self) {
+        let Some(fade) = self.config_fade.as_mut() else {
+            return;
+        };
+        if let Some(config) = fade.tick() {
+            self.instrument.set_config(config);
+        }
+        if fade.is_done() {
+            self.config_fade = None;
+        }
+    }
+
+    /// Jump to the end of a running config crossfade, so a parameter write
+    /// or new hit isn't overwritten by the rest of the fade.
+    fn finish_config_fade(&mut self) {
+        if let Some(fade) = self.config_fade.take() {
+            self.instrument.set_config(fade.target());
+        }
+    }
+
+    /// Current fader × group × mute/solo × preset gain and pan (with the last
+    /// hit's spread).
+    fn mix_snapshot(&self) -> (f32, f32) {
+        (
+            self.channel_gain.get()
+                * self.group_gain.get()
+                * self.mute_gain.get()
+                * self.preset_gain.get(),
+            (self.pan.get() + self.pan_offset).clamp(0.0, 1.0),
+        )
+    }
+
+    /// Apply any queued mute/solo change. Audio thread only.
+    fn apply_mode_requests(&self) {
+        for (request, state) in [
+            (&self.mute_request, &self.muted),
+            (&self.solo_request, &self.soloed),
+        ] {
+            match request.swap(MODE_REQUEST_NONE, Ordering::AcqRel) {
+                MODE_REQUEST_OFF => state.store(false, Ordering::Release),
+                MODE_REQUEST_ON => state.store(true, Ordering::Release),
+                _ => {}
+            }
+        }
+    }
+
+    /// Record a new peak (read-and-reset by the UI). `level` is a pre-pan mono
+    /// magnitude. Uses the same compare-and-store pattern as the old
+    /// `channel_peaks` array.
+    fn record_peak(&self, level: f32) {
+        let prev = f32::from_bits(self.peak.load(Ordering::Relaxed));
+        if level > prev {
+            self.peak.store(level.to_bits(), Ordering::Relaxed);
+        }
+    }
+}
+
+/// No queued mute/solo change.
+const MODE_REQUEST_NONE: u8 = 0;
+/// Queued change to off (unmute / unsolo).
+const MODE_REQUEST_OFF: u8 = 1;
+/// Queued change to on (mute / solo).
+const MODE_REQUEST_ON: u8 = 2;
+
+/// Whether a queued mute/solo request would change `state`.
+fn mode_request_pending(request: &AtomicU8, state: &AtomicBool) -> bool {
+    match request.load(Ordering::Acquire) {
+        MODE_REQUEST_OFF => state.load(Ordering::Acquire),
+        MODE_REQUEST_ON => !state.load(Ordering::Acquire),
+        _ => false,
+    }
+}
+
+/// Release time of the channel level meters (to 1/e of the peak).
+const METER_RELEASE_MS: f32 = 300.0;
+
+/// Peak level meter with instant attack and exponential release, ticked on
+/// the audio thread and read by the UI. Unlike `VoiceStrip::peak` it is not
+/// reset by reads, so any number of readers can poll it.
+struct ChannelMeter {
+    /// Current level (linear, f32 bits)
+    level: AtomicU32,
+    /// Per-sample release multiplier
+    release: f32,
+}
+
+impl ChannelMeter {
+    fn new(sample_rate: f32) -> Self {
+        Self {
+            level: AtomicU32::new(0.0_f32.to_bits()),
+            release: (-1000.0 / (METER_RELEASE_MS * sample_rate)).exp(),
+        }
+    }
+
+    fn tick(&self, sample: f32) {
+        let level = f32::from_bits(self.level.load(Ordering::Relaxed));
+        let level = sample.abs().max(level * self.release);
+        self.level.store(level.to_bits(), Ordering::Relaxed);
+    }
+
+    fn get(&self) -> f32 {
+        f32::from_bits(self.level.load(Ordering::Relaxed))
+    }
+
+    fn reset(&self) {
+        self.level.store(0.0_f32.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// Fade-out applied to an instrument's tail after it leaves its channel.
+const RETIRE_FADE_MS: f32 = 30.0;
+
+/// Debug builds time each channel's tick on one frame in this many and
+/// scale up; reading the clock per voice per frame costs more than most
+/// voices do.
+const CPU_CHANNEL_STRIDE: u32 = 64;
+
+/// Tails one channel fades out at once. A swap while all are busy cuts the
+/// quietest one short.
+const RETIRE_SLOTS: usize = 4;
+
+/// Retired instruments in flight from control threads to the audio thread.
+const RETIRE_QUEUE_CAPACITY: usize = 32;
+
+/// Length of each half (out, then back in) of the master fade around a panic.
+const PANIC_FADE_MS: f32 = 5.0;
+
+/// Length of the offline render used to measure a preset's loudness.
+const PRESET_MEASURE_SECS: f32 = 0.5;
+
+/// An instrument swapped out of (or destroyed with) its channel, left to ring
+/// out under a short linear fade so the swap doesn't click.
+///
+/// The fade-out lists belong to the audio thread: control threads send
+/// retirements through the [`ControlQueue`], and finished tails go back the
+/// same way to be freed off the audio thread (see [`GooeyEngine::retire`]).
+/// A tail is never dropped while it is still sounding.
+struct RetiringVoice {
+    instrument: ChannelInstrument,
+    /// Channel fader × mute/solo gain at retirement.
+    gain: f32,
+    /// Pan at retirement, including the last hit's spread offset.
+    pan: f32,
+    /// Remaining fade, 1.0 down to 0.0 (finished).
+    fade: f32,
+}
+
+impl RetiringVoice {
+    /// Retire `instrument` with the gain and pan of the voice it left (see
+    /// [`VoiceStrip::mix_snapshot`]).
+    fn new(instrument: ChannelInstrument, (gain, pan): (f32, f32)) -> Self {
+        Self {
+            instrument,
+            gain,
+            pan,
+            fade: 1.0,
+        }
+    }
+
+    /// A tail box carrying `instrument` into a channel, allocated on the
+    /// calling thread so the swap itself doesn't allocate: the swap trades
+    /// it for the channel's old instrument (see
+    /// [`GooeyEngine::swap_instrument`]).
+    fn carrier(instrument: ChannelInstrument) -> Box<Self> {
+        Box::new(Self::new(instrument, (0.0, 0.0)))
+    }
+
+    /// Trade the carried instrument for `voice`'s, taking on the voice's mix
+    /// so the old instrument fades out where it played.
+    fn swap_with(&mut self, voice: &mut VoiceStrip) {
+        std::mem::swap(&mut self.instrument, &mut voice.instrument);
+        (self.gain, self.pan) = voice.mix_snapshot();
+        self.fade = 1.0;
+    }
+
+    /// Render one faded sample, or `None` once the tail is done.
+    fn tick(&mut self, time: f64, fade_step: f32) -> Option<StereoFrame> {
+        if self.fade <= 0.0 {
+            return None;
+        }
+        let out = self.instrument.tick(time) * self.gain * self.fade;
+        self.fade = if self.instrument.is_active() {
+            self.fade - fade_step
+        } else {
+            0.0
+        };
+        Some(StereoFrame::panned(out, self.pan))
+    }
+}
+
+/// Give a tail the audio thread is done with back to the control side to be
+/// freed. The return channel has room for every tail that can be in flight,
+/// so nothing is freed here.
+fn hand_back(spent: &SyncSender<Box<RetiringVoice>>, voice: Box<RetiringVoice>) {
+    let _ = spent.try_send(voice);
+}
+
+/// One loop of a channel's pattern rendered to audio (see
+/// `gooey_engine_freeze_channel`). Captured in steady state, so tails from the
+/// end of the loop already ring into its start. Playback follows the
+/// channel's sequencer: it restarts whenever step 0 plays and is silent while
+/// the sequencer is stopped.
+struct FrozenLoop {
+    samples: Box<[f32]>,
+    position: usize,
+    /// `step_start_sample` of the step seen last, to spot step changes.
+    last_step_start: u64,
+}
+
+impl FrozenLoop {
+    fn new(samples: Box<[f32]>) -> Self {
+        Self {
+            samples,
+            position: 0,
+            last_step_start: u64::MAX,
+        }
+    }
+
+    /// Next sample for a sequencer that has just ticked.
+    fn tick(&mut self, sequencer: &Sequencer) -> f32 {
+        if !sequencer.is_running() {
+            self.position = 0;
+            self.last_step_start = u64::MAX;
+            return 0.0;
+        }
+        let step_start = sequencer.step_start_sample();
+        if step_start != self.last_step_start {
+            if self.last_step_start == u64::MAX || sequencer.current_step() == 0 {
+                // Started mid-pattern: land on the step's place in the loop
+                self.position = (sequencer.current_step() as f32 * sequencer.samples_per_step())
+                    as usize
+                    + (sequencer.sample_count() - 1).saturating_sub(step_start) as usize;
+            }
+            self.last_step_start = step_start;
+        }
+        let out = self.samples.get(self.position).copied().unwrap_or(0.0);
+        self.position += 1;
+        out
+    }
+}
+
+/// A submixable collection of drum voices (kick, snare, hihat, tom). Each voice
+/// keeps its own sequencer, blender, and mixer strip; the whole kit is routed as
+/// one source (`SourceId::DrumKit`) in the mixer graph. Bass is intentionally not
+/// a member — it is a melodic voice routed as its own source.
+struct DrumKit {
+    voices: [VoiceStrip; KIT_VOICE_COUNT],
+}
+
+// =============================================================================
+// Threading contract
+// =============================================================================
+
+/// Capacity of the control → audio command queue. Writes beyond this between
+/// two render calls fail with `GooeyResult::QueueFull`.
+const CONTROL_QUEUE_CAPACITY: usize = 1024;
+
+/// A parameter write staged by a control thread for the audio thread. Each
+/// variant is validated before it is queued, so applying it cannot fail.
+#[derive(Clone, Copy, Debug)]
+#[allow(clippy::large_enum_variant)]
+enum ControlCommand {
+    ChannelParam {
+        channel: u32,
+        param: u32,
+        value: f32,
+    },
+    InstrumentParam {
+        instrument_type: u32,
+        param: u32,
+        value: f32,
+    },
+    GlobalEffectParam {
+        effect: u32,
+        param: u32,
+        value: f32,
+    },
+    BlendPosition {
+        channel: u32,
+        x: f32,
+        y: f32,
+    },
+    Randomize {
+        channel: u32,
+        amount: f32,
+        seed: u32,
+    },
+    MasterGain(f32),
+    ChannelGain {
+        channel: u32,
+        gain: f32,
+    },
+    ChannelPan {
+        channel: u32,
+        pan: f32,
+    },
+    SaturatorModel {
+        channel: u32,
+        model: SaturatorModel,
+    },
+    OverdriveAutoGain {
+        channel: u32,
+        enabled: bool,
+    },
+    PreviewTrigger {
+        config: ChannelConfig,
+        velocity: f32,
+    },
+    PreviewRelease,
+    PreviewGain(f32),
+}
+
+/// Source of per-thread tokens; 0 means "no audio thread attached".
+static NEXT_THREAD_TOKEN: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    static THREAD_TOKEN: u64 = NEXT_THREAD_TOKEN.fetch_add(1, Ordering::Relaxed);
+}
+
+fn current_thread_token() -> u64 {
+    THREAD_TOKEN.with(|token| *token)
+}
+
+/// Lock-free hand-off of parameter writes from control threads to the audio
+/// thread.
+///
+/// The thread that calls `gooey_engine_render` is the audio thread. Once one
+/// is attached, writes made from any other thread are queued here and applied
+/// at the top of the next render; writes from the audio thread itself (or
+/// before any render) apply immediately, so single-threaded and offline hosts
+/// see their writes straight away. The queue is a preallocated bounded
+/// channel, so neither side allocates or blocks.
+///
+/// Batches (`push_batch`) bump `batch_epoch` to odd while their commands are
+/// being queued and back to even once complete. The audio thread only applies
+/// what it drained if the epoch was even and unchanged across the drain, so a
+/// batch never takes effect half-way through.
+///
+/// Instruments swapped out of a channel travel on a second pair of channels:
+/// `retire` carries them to the audio thread's fade-out lists and `spent`
+/// brings finished tails back to be freed.
+struct ControlQueue {
+    tx: SyncSender<ControlCommand>,
+    rx: Receiver<ControlCommand>,
+    audio_thread: AtomicU64,
+    /// Commands queued and not yet popped, for the batch capacity check.
+    len: AtomicUsize,
+    batch_epoch: AtomicU64,
+    retire_tx: SyncSender<(usize, Box<RetiringVoice>)>,
+    retire_rx: Receiver<(usize, Box<RetiringVoice>)>,
+    spent_tx: SyncSender<Box<RetiringVoice>>,
+    spent_rx: Receiver<Box<RetiringVoice>>,
+}
+
+impl ControlQueue {
+    fn new() -> Self {
+        let (tx, rx) = sync_channel(CONTROL_QUEUE_CAPACITY);
+        let (retire_tx, retire_rx) = sync_channel(RETIRE_QUEUE_CAPACITY);
+        let (spent_tx, spent_rx) =
+            sync_channel(NUM_CHANNELS * RETIRE_SLOTS + RETIRE_QUEUE_CAPACITY);
+        Self {
+            tx,
+            rx,
+            audio_thread: AtomicU64::new(0),
+            len: AtomicUsize::new(0),
+            batch_epoch: AtomicU64::new(0),
+            retire_tx,
+            retire_rx,
+            spent_tx,
+            spent_rx,
+        }
+    }
+
+    /// Mark the calling thread as the audio thread.
+    fn attach_audio_thread(&self) {
+        self.audio_thread
+            .store(current_thread_token(), Ordering::Release);
+    }
+
+    /// True when called from a thread other than the attached audio thread.
+    fn is_control_thread(&self) -> bool {
+        let audio = self.audio_thread.load(Ordering::Acquire);
+        audio != 0 && audio != current_thread_token()
+    }
+
+    /// True when called from the attached audio thread.
+    fn is_audio_thread(&self) -> bool {
+        self.audio_thread.load(Ordering::Acquire) == current_thread_token()
+    }
+
+    fn push(&self, command: ControlCommand) -> bool {
+        let pushed = self.tx.try_send(command).is_ok();
+        if pushed {
+            self.len.fetch_add(1, Ordering::AcqRel);
+        }
+        pushed
+    }
+
+    /// Queue `commands` so the audio thread applies all of them in the same
+    /// render. Fails without queuing anything when they do not all fit.
+    fn push_batch(&self, commands: &[ControlCommand]) -> bool {
+        if self.len.load(Ordering::Acquire) + commands.len() > CONTROL_QUEUE_CAPACITY {
+            return false;
+        }
+        self.batch_epoch.fetch_add(1, Ordering::AcqRel);
+        let pushed = commands.iter().all(|&command| self.push(command));
+        self.batch_epoch.fetch_add(1, Ordering::AcqRel);
+        pushed
+    }
+
+    fn pop(&self) -> Option<ControlCommand> {
+        let command = self.rx.try_recv().ok();
+        if command.is_some() {
+            self.len.fetch_sub(1, Ordering::AcqRel);
+        }
+        command
+    }
+
+    fn batch_epoch(&self) -> u64 {
+        self.batch_epoch.load(Ordering::Acquire)
+    }
+}
+
+/// `RenderGate::owner` while nobody holds the gate.
+const GATE_FREE: u64 = 0;
+/// `RenderGate::owner` while the audio thread is rendering.
+const GATE_RENDERING: u64 = u64::MAX;
+/// How often a control thread checks whether the audio thread has run its
+/// posted edit.
+const EDIT_POLL: Duration = Duration::from_micros(100);
+/// How long without a render before rendering counts as stopped and edits
+/// are made in place (see [`RenderGate`]); four blocks when those are longer.
+const EDIT_IDLE_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// An edit a control thread has handed to the audio thread (see
+/// [`RenderGate::post_edit`]). Lives on the posting thread's stack, which
+/// waits for `done` before it goes.
+struct PostedEdit<'a> {
+    run: &'a mut dyn FnMut(),
+    /// How `run` went, and the error and warnings it recorded on the audio
+    /// thread, for the posting thread to take over
+    outcome: std::thread::Result<()>,
+    diagnostics: Diagnostics,
+    done: AtomicBool,
+}
+
+/// Serializes calls that edit engine state in place against rendering.
+///
+/// Sequencer steps, tempo, LFO routes, slot swaps and the other structural
+/// calls change state the audio thread reads without atomics, and many of
+/// them return results, so they cannot ride the [`ControlQueue`] as plain
+/// commands. Made from a control thread once rendering has started, such a
+/// call is posted here instead and the audio thread runs it at the top of its
+/// next render, before the block (see [`edit`]); the caller waits for it. The
+/// audio thread never skips or silences a block for an edit.
+///
+/// Before the first render, or when rendering has stopped (no block for
+/// [`EDIT_IDLE_TIMEOUT`] or four block lengths), edits are made in place on
+/// the calling thread under `owner`. A render that starts meanwhile waits for
+/// that edit to finish.
+struct RenderGate {
+    /// `GATE_FREE`, `GATE_RENDERING`, or the thread token of a control
+    /// thread editing in place.
+    owner: AtomicU64,
+    /// The edit waiting for the next render, if any
+    posted: std::sync::atomic::AtomicPtr<PostedEdit<'static>>,
+    /// Held by the control thread that has an edit posted or in progress
+    posting: std::sync::Mutex<()>,
+    /// When the last render started, in nanoseconds since `epoch`, and the
+    /// host block length it rendered
+    epoch: Instant,
+    last_render: AtomicU64,
+    block_nanos: AtomicU64,
+}
+
+impl RenderGate {
+    fn new() -> Self {
+        Self {
+            owner: AtomicU64::new(GATE_FREE),
+            posted: std::sync::atomic::AtomicPtr::new(std::ptr::null_mut()),
+            posting: std::sync::Mutex::new(()),
+            epoch: Instant::now(),
+            last_render: AtomicU64::new(0),
+            block_nanos: AtomicU64::new(0),
+        }
+    }
+
+    /// Whether no render has started for [`EDIT_IDLE_TIMEOUT`] or four
+    /// blocks, whichever is longer.
+    fn is_idle(&self) -> bool {
+        let block = Duration::from_nanos(self.block_nanos.load(Ordering::Relaxed));
+        let last = Duration::from_nanos(self.last_render.load(Ordering::Acquire));
+        self.epoch.elapsed().saturating_sub(last) > EDIT_IDLE_TIMEOUT.max(4 * block)
+    }
+
+    /// Take the gate for a render of `block`, waiting out an edit being made
+    /// in place (which only happens while rendering had stopped).
+    fn begin_render(&self, block: impl FnOnce() -> Duration) {
+        while self
+            .owner
+            .compare_exchange_weak(
+                GATE_FREE,
+                GATE_RENDERING,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            )
+            .is_err()
+        {
+            std::hint::spin_loop();
+        }
+        self.block_nanos
+            .store(block().as_nanos() as u64, Ordering::Relaxed);
+        self.last_render
+            .store(self.epoch.elapsed().as_nanos() as u64, Ordering::Release);
+    }
+
+    /// Run the edit a control thread posted, if there is one. Audio thread,
+    /// holding the gate, with no reference into the engine alive.
+    fn run_posted_edit(&self) {
+        let posted = self.posted.swap(std::ptr::null_mut(), Ordering::AcqRel);
+        if posted.is_null() {
+            return;
+        }
+        // SAFETY: the posting thread keeps the edit alive until `done`.
+        let posted = unsafe { &mut *posted };
+        let own = Diagnostics::take();
+        posted.outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| (posted.run)()));
+        posted.diagnostics = Diagnostics::take();
+        own.restore();
+        // Nothing may touch `posted` after this: its owner returns.
+        posted.done.store(true, Ordering::Release);
+    }
+
+    /// Take the gate for audio-thread work between renders, without
+    /// waiting. False while an edit is being made in place.
+    #[cfg(feature = "plugin")]
+    fn try_enter_audio(&self) -> bool {
+        self.owner
+            .compare_exchange(
+                GATE_FREE,
+                GATE_RENDERING,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            )
+            .is_ok()
+    }
+
+    fn end_render(&self) {
+        self.owner.store(GATE_FREE, Ordering::Release);
+    }
+
+    /// Hand `run` to the audio thread and wait until it has run. False,
+    /// with `run` not called, if no render came to take it (rendering has
+    /// stopped); the caller then makes the edit in place. Hold `posting`.
+    fn post_edit(&self, run: &mut dyn FnMut()) -> bool {
+        if self.is_idle() {
+            return false;
+        }
+        let mut posted = PostedEdit {
+            run,
+            outcome: Ok(()),
+            diagnostics: Diagnostics::default(),
+            done: AtomicBool::new(false),
+        };
+        let ptr = std::ptr::addr_of_mut!(posted).cast::<PostedEdit<'static>>();
+        self.posted.store(ptr, Ordering::Release);
+
+        // SAFETY (all reads of `ptr`): the audio thread writes `outcome` and
+        // `diagnostics` only before it sets `done`, and never after.
+        while !unsafe { (*ptr).done.load(Ordering::Acquire) } {
+            if self.is_idle()
+                && self
+                    .posted
+                    .compare_exchange(
+                        ptr,
+                        std::ptr::null_mut(),
+                        Ordering::AcqRel,
+                        Ordering::Acquire,
+                    )
+                    .is_ok()
+            {
+                return false;
+            }
+            std::thread::sleep(EDIT_POLL);
+        }
+        let PostedEdit {
+            outcome,
+            diagnostics,
+            ..
+        } = posted;
+        diagnostics.publish();
+        if let Err(panic) = outcome {
+            std::panic::resume_unwind(panic);
+        }
+        true
+    }
+
+    /// Make an edit in place on the calling control thread, waiting out a
+    /// render in progress.
+    fn edit_in_place(&self, run: &mut dyn FnMut()) {
+        let token = current_thread_token();
+        while self
+            .owner
+            .compare_exchange_weak(GATE_FREE, token, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            std::thread::yield_now();
+        }
+        // Released on the way out even if the edit panics
+        struct Release<'a>(&'a AtomicU64);
+        impl Drop for Release<'_> {
+            fn drop(&mut self) {
+                self.0.store(GATE_FREE, Ordering::Release);
+            }
+        }
+        let _release = Release(&self.owner);
+        run();
+    }
+
+    /// True while the audio thread is rendering or running a posted edit.
+    fn is_rendering(&self) -> bool {
+        self.owner.load(Ordering::Acquire) == GATE_RENDERING
+    }
+
+    /// True while the calling thread is making an edit in place.
+    fn is_editing_in_place(&self) -> bool {
+        self.owner.load(Ordering::Relaxed) == current_thread_token()
+    }
+}
+
+/// Run `f`, an FFI call's edit of engine state, without overlapping a
+/// render (see [`RenderGate`]), and return what it returns.
+///
+/// On the audio thread, inside another edit, or for a null engine `f` runs
+/// straight away. From a control thread it is run by the audio thread at the
+/// top of its next render while this thread waits, or in place under the gate
+/// if nothing is rendering. Errors and warnings `f` records are reported on
+/// the calling thread either way.
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`.
+/// `f` may run on the audio thread: it must not rely on thread-locals other
+/// than the error and warning records.
+unsafe fn edit<R>(engine: *const GooeyEngine, f: impl FnOnce() -> R) -> R {
+    if engine.is_null() {
+        return f();
+    }
+    let gate = &*std::ptr::addr_of!((*engine).gate);
+    if (*engine).control.is_audio_thread() {
+        let result = f();
+        // Between renders the audio thread may free; inside one (a posted
+        // edit) the tails wait for the posting thread
+        if !gate.is_rendering() {
+            while (*engine).control.spent_rx.try_recv().is_ok() {}
+        }
+        return result;
+    }
+    if gate.is_editing_in_place() {
+        return f();
+    }
+    let mut f = Some(f);
+    let mut result = None;
+    let mut run = || result = f.take().map(|f| f());
+
+    let _posting = gate
+        .posting
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if !(*engine).control.is_control_thread() || !gate.post_edit(&mut run) {
+        gate.edit_in_place(&mut run);
+    }
+    // Free the tails the audio thread has finished fading out
+    while (*engine).control.spent_rx.try_recv().is_ok() {}
+    result.expect("an edit runs exactly once")
+}
+
+/// The engine behind the C API.
+///
+/// # Threading
+///
+/// One thread renders (`gooey_engine_render`, the *audio thread*); any other
+/// thread is a *control thread*. State shared between the two is kept in one
+/// of two forms:
+///
+/// - flags and single values read on every render (mute/solo, effect and LFO
+///   enables, blend enable, manual triggers, peaks) are atomics and take
+///   effect on the next render;
+/// - DSP parameter writes (instrument, global effect, blend position, master
+///   gain) from a control thread go through the internal [`ControlQueue`] and
+///   are applied by the audio thread before it renders. Getters reflect such a
+///   write once the next render has run.
+///
+/// Every other mutating call (sequencer edits, tempo, LFO routes, instrument
+/// swaps, sample loads, mixer layout edits, bounces) edits engine state
+/// through the internal [`RenderGate`], so it never overlaps a render: made
+/// from a control thread while rendering, the call is handed to the audio
+/// thread, which runs it between two blocks, and returns once it has run. A
+/// render never skips or silences a block for an edit. Buffers such calls
+/// need (instruments, recorder storage, stretched samples) are built on the
+/// calling thread first where possible; the remaining heavy calls (bounces,
+/// freezes, state import) delay the next block by as long as they take and
+/// are best made while transport is stopped.
+pub struct GooeyEngine {
+    // Drum voices (kick, snare, hihat, tom) grouped as one submixable kit.
     kit: DrumKit,
 
     // Bass voice — a melodic instrument routed as its own source, sibling to the
     // kit. Addressed as legacy instrument index 4.
     bass: VoiceStrip,
 
+    // FM snap voice. Added after bass, so it is addressed as instrument index
+    // 5, but it is percussion and sums into the kit source.
+    fm_snap: VoiceStrip,
+
+    // Rimshot, cowbell and shaker voices (instrument indices 6 to 8),
+    // percussion summed into the kit source like the FM snap.
+    rimshot: VoiceStrip,
+    cowbell: VoiceStrip,
+    shaker: VoiceStrip,
+
+    // Host-created instrument slots, addressed as channels
+    // `INSTRUMENT_COUNT..CHANNEL_MAX`. Empty entries are skipped everywhere;
+    // occupied ones sum into the kit source like the FM snap.
+    slots: [Option<VoiceStrip>; SLOT_COUNT],
+
+    // Instruments swapped out of each channel, fading out their tails.
+    retiring: [[Option<Box<RetiringVoice>>; RETIRE_SLOTS]; NUM_CHANNELS],
+
+    // Isolated voices for auditioning configs, indexed by instrument type so
+    // switching type never allocates. Mixed to master after the effects.
+    preview_voices: [ChannelInstrument; NUM_INSTRUMENTS],
+    preview_gain: SmoothedParam,
+
+    // Level-match presets to each instrument's default sound.
+    preset_normalization: bool,
+    // Offline loudness per (instrument type, preset ID); None is the default sound.
+    preset_loudness: HashMap<(u32, Option<u32>), Loudness>,
+
     /// Global effects. Applied in the order described by `effect_order`,
     /// followed by the optional limiter which is always last.
     /// Processing order when enabled: saturation -> lowpass filter -> tilt filter -> delay -> compressor -> reverb -> limiter.
     delay: DelayEffect,
-    delay_enabled: bool,
+    delay_enabled: AtomicBool,
     lowpass_filter: LowpassFilterEffect,
-    lowpass_filter_enabled: bool,
+    lowpass_filter_enabled: AtomicBool,
     tilt_filter: TiltFilterEffect,
-    tilt_filter_enabled: bool,
+    tilt_filter_enabled: AtomicBool,
     saturation: TubeSaturation,
-    saturation_enabled: bool,
+    saturation_enabled: AtomicBool,
+    // Non-tube models of the saturation slot (SATURATION_PARAM_MODEL)
+    saturator: Saturator,
+    saturation_model: AtomicU32,
     compressor: TubeCompressor,
-    compressor_enabled: bool,
+    compressor_enabled: AtomicBool,
     compressor_sidechain: u32,
     reverb: SpringReverbEffect,
-    reverb_enabled: bool,
+    reverb_enabled: AtomicBool,
     plate_reverb: PlateReverbEffect,
-    plate_reverb_enabled: bool,
+    plate_reverb_enabled: AtomicBool,
     waveshaper: Waveshaper,
-    waveshaper_enabled: bool,
+    waveshaper_enabled: AtomicBool,
     feedback_waveshaper: FeedbackWaveshaper,
-    feedback_waveshaper_enabled: bool,
+    feedback_waveshaper_enabled: AtomicBool,
+    ducker: Ducker,
+    ducker_enabled: AtomicBool,
+    ducker_source: u32,
+    beat_repeat: BeatRepeat,
+    beat_repeat_enabled: AtomicBool,
+    beat_repeat_source: u32,
+    early_reflections: EarlyReflections,
+    early_reflections_enabled: AtomicBool,
+    widener: Widener,
+    widener_enabled: AtomicBool,
     limiter: SoftLimiter,
-    limiter_enabled: bool,
+    limiter_enabled: AtomicBool,
+    /// Always-on DC blocker, soft-clip ceiling and emergency mute, after
+    /// everything else.
+    output_safety: OutputSafety,
 
     /// Order in which the reorderable effects are applied. Stores `EFFECT_*`
     /// IDs (excluding `EFFECT_LIMITER`, which is pinned at the end of the chain).
@@ -708,19 +2543,78 @@ pub struct GooeyEngine {
     sample_rate: f32,
     bpm: f32,
     swing: f32,
-    current_time: f64,
+    /// A4 reference and transpose applied to every pitched voice
+    master_tuning: MasterTuning,
+    /// Samples rendered since start (or the last offline reset); voice
+    /// times are derived from it so long sessions don't drift.
+    clock: SampleClock,
     /// Smoothed gain applied to the complete instrument sum before global effects.
     master_gain: SmoothedParam,
 
     // LFO pool (8 LFOs with multi-target routing)
     lfos: [Lfo; LFO_COUNT],
-    lfo_enabled: [bool; LFO_COUNT],
+    lfo_enabled: [AtomicBool; LFO_COUNT],
     lfo_routes: [Vec<LfoRoute>; LFO_COUNT],
     lfo_next_route_id: [u32; LFO_COUNT],
+    /// Evaluation order: LFOs before the LFOs they modulate
+    lfo_order: [usize; LFO_COUNT],
+    /// Rate and amount modulation each LFO sent to each other LFO, indexed
+    /// [source][target][param], as of the source's latest evaluation
+    lfo_to_lfo: [[[f32; 2]; LFO_COUNT]; LFO_COUNT],
 
     // Pending MIDI events from the most recent render pass (pre-allocated, no audio-thread alloc)
     pending_midi_events: Vec<GooeyMidiEvent>,
 
+    // UI timeline events: pushed by the audio thread, drained by the UI
+    // thread. Bounded, so pushing never allocates.
+    timeline_enabled: AtomicBool,
+    timeline_latency_frames: AtomicU32,
+    timeline_tx: SyncSender<GooeyTimelineEvent>,
+    timeline_rx: Receiver<GooeyTimelineEvent>,
+
+    // Shared-memory parameter table, polled at the top of each render while
+    // enabled (see `crate::param_table`)
+    param_table_enabled: AtomicBool,
+    param_table: ParamTable,
+
+    // Debug event log (compiled out without the `trace` feature), and the
+    // step each sequencer was last logged at
+    trace: TraceLog,
+    trace_steps: [u32; NUM_CHANNELS],
+
+    // DSP load meter, and the time each channel's instrument took on the
+    // frames sampled so far in the current render (debug builds only)
+    cpu_load: CpuLoad,
+    cpu_channel_time: [Duration; NUM_CHANNELS],
+    cpu_channel_frames: u32,
+
+    // Dry output of one channel collected during `freeze_channel`'s offline
+    // render
+    freeze_capture: Option<(usize, Vec<f32>)>,
+
+    // Converts from `sample_rate` to the device rate when the engine was
+    // created with a different output rate (`gooey_engine_new_with_output_rate`)
+    resampler: Option<Resampler>,
+
+    // Overload protection: while on, the governor sheds the stages in
+    // `overload_priority` (stage + 1 per byte, 0 ends the list) one at a
+    // time under sustained overload, and restores them in reverse order.
+    // `degradation` is the mask of shed stages for the host to read.
+    overload_enabled: AtomicBool,
+    overload_threshold: AtomicU32,
+    overload_priority: AtomicU32,
+    governor: OverloadGovernor,
+    degradation: AtomicU32,
+    shed_order: Vec<u32>,
+    // Oversampling of saturation, compressor, waveshaper and feedback
+    // waveshaper from before the oversampling stage was shed
+    saved_oversampling: [OversamplingMode; 4],
+
+    // Frames rendered since creation, and the engine frame of sample 0 of
+    // the current render call.
+    rendered_frames: u64,
+    render_first_frame: u64,
+
     // When false, sequencers still advance position but don't trigger instruments or emit MIDI events.
     // Used to let host MIDI input drive instruments instead of the internal sequencer.
     // Atomic so the host thread (FFI setter) and audio thread (render read) don't race;
@@ -756,6 +2650,12 @@ pub struct GooeyEngine {
     error_callback: Option<extern "C" fn(*mut c_void, *const c_char)>,
     error_callback_context: *mut c_void,
 
+    // Host hook for instrument hits (e.g. kick-driven visuals), called from
+    // the audio thread for channels whose bit is set in the mask
+    trigger_callback: Option<GooeyTriggerCallback>,
+    trigger_callback_context: *mut c_void,
+    trigger_callback_mask: u32,
+
     // Host-clock state for scheduled (Link-synced) sequencer start.
     // `host_clock_anchor` is set by `gooey_engine_set_render_host_time` once
     // per buffer in the audio callback. `pending_arm_host_time` is staged by
@@ -766,8 +2666,65 @@ pub struct GooeyEngine {
 
     // Live performance clip recorder/player (Stage 1: chord pad events).
     performance: PerformanceRecorder,
+    // Rolling record of manual hits for retrospective capture.
+    capture: TriggerCapture,
+    // Grooves extracted from played hits, for applying to channels.
+    grooves: GroovePool,
+    // Step sequences of effect parameter values, following the transport.
+    effect_lanes: [Option<EffectLaneSlot>; EFFECT_LANE_MAX as usize],
+    // Set by `gooey_engine_set_motion_recording`: instrument parameter
+    // changes are written into motion lanes while the transport runs.
+    motion_recording: AtomicBool,
     // Config-time registered sample-pad instruments. Empty entries are not graph sources.
     samplers: [Option<SamplerRack>; SAMPLER_RACK_MAX as usize],
+    // Master output recorder ("record your jam"), fed the final frame of every render.
+    recorder: Recorder,
+    // Parameter writes staged by control threads, drained at the top of render.
+    control: ControlQueue,
+    // Keeps in-place edits from control threads and rendering apart.
+    gate: RenderGate,
+    // Commands drained while a batch was still being queued, held back until
+    // the batch is complete.
+    control_stash: Vec<ControlCommand>,
+    // Key that sequenced per-step notes are snapped to: `root | scale << 8`, or
+    // SCALE_QUANTIZE_OFF. Packed into one atomic so root and scale change together.
+    scale_quantize: AtomicU32,
+    // Grid that mute/solo changes wait for (a CLIP_QUANTIZE_* constant;
+    // IMMEDIATE applies them straight away).
+    mute_quantize: AtomicU32,
+    // Gain of each instrument group (f32 bits), multiplied into the
+    // members' group gain once per buffer.
+    group_gains: [AtomicU32; GROUP_MAX as usize],
+    // Set by the UI when a voice has a queued mute/solo request.
+    mode_requests_pending: AtomicBool,
+    // Transport beat the queued mute/solo requests land on, once the audio
+    // thread has picked them up.
+    mode_change_beat: Option<f64>,
+    // Stored whole-kit patterns (one entry per channel, `None` for channels
+    // that were empty when stored), indexed by pattern slot.
+    pattern_slots: Vec<Option<Vec<Option<Vec<SequencerStep>>>>>,
+    // Slot whose patterns are playing, and the slot queued to replace it.
+    active_pattern: Option<u32>,
+    queued_pattern: Option<u32>,
+    // Steps copied by `gooey_engine_sequencer_copy_instrument_steps`
+    pattern_clipboard: Option<PatternClip>,
+    // Seed every random stream derives from (see `reseed`)
+    rng_seed: u64,
+    // Draws for `gooey_engine_sequencer_humanize_instrument_velocities`
+    humanize_rng: Rng,
+    // Set by `gooey_engine_panic` from any thread, picked up by the next render.
+    panic_requested: AtomicBool,
+    // Master ramp of a panic in progress (audio thread only).
+    panic_fade: Option<PanicFade>,
+}
+
+/// Progress of a panic, in samples left: the master fades out, everything is
+/// cleared at silence, then it fades back in for whatever keeps playing
+/// (loops, new hits).
+#[derive(Clone, Copy, Debug)]
+enum PanicFade {
+    Out(u32),
+    In(u32),
 }
 
 /// Host-clock reference for the next render buffer. The audio callback sets
@@ -779,6 +2736,9 @@ struct HostClockAnchor {
     host_time_first_sample: u64,
     /// Host-clock ticks per audio sample (host_ticks_per_second / sample_rate).
     host_ticks_per_sample: f64,
+    /// Engine frame (see `rendered_frames`) of that first sample, so host
+    /// times can be extrapolated to later buffers.
+    frame: u64,
 }
 
 /// A pending scheduled start. Resolved at render time using the current
@@ -808,6 +2768,9 @@ enum ArmResolution {
 impl GooeyEngine {
     fn new(sample_rate: f32) -> Self {
         let bpm = 120.0;
+        // Build the parameter tables here rather than on the first setter
+        // call, which may come from the audio thread
+        crate::param_info::instrument_params(INSTRUMENT_KICK);
 
         // Drum kit: four voices (kick, snare, hihat, tom), each with its own
         // 16-step sequencer, blender, and mixer strip.
@@ -848,6 +2811,34 @@ impl GooeyEngine {
             sample_rate,
         );
 
+        let fm_snap = VoiceStrip::new(
+            ChannelInstrument::FmSnap(FmSnap::new(sample_rate)),
+            Sequencer::with_pattern(bpm, sample_rate, vec![false; 16], "fm_snap"),
+            INSTRUMENT_FM_SNAP,
+            sample_rate,
+        );
+
+        let rimshot = VoiceStrip::new(
+            ChannelInstrument::Rimshot(Rimshot::new(sample_rate)),
+            Sequencer::with_pattern(bpm, sample_rate, vec![false; 16], "rimshot"),
+            INSTRUMENT_RIMSHOT,
+            sample_rate,
+        );
+
+        let cowbell = VoiceStrip::new(
+            ChannelInstrument::Cowbell(Cowbell::new(sample_rate)),
+            Sequencer::with_pattern(bpm, sample_rate, vec![false; 16], "cowbell"),
+            INSTRUMENT_COWBELL,
+            sample_rate,
+        );
+
+        let shaker = VoiceStrip::new(
+            ChannelInstrument::Shaker(Shaker::new(sample_rate)),
+            Sequencer::with_pattern(bpm, sample_rate, vec![false; 16], "shaker"),
+            INSTRUMENT_SHAKER,
+            sample_rate,
+        );
+
         // Create delay with default settings (quarter note timing, no feedback, no mix, filter open)
         let delay = DelayEffect::new(sample_rate, DelayTiming::Quarter, bpm, 0.0, 0.0, 20000.0);
 
@@ -859,6 +2850,7 @@ impl GooeyEngine {
 
         // Create saturation with default light warmth settings
         let saturation = TubeSaturation::new(sample_rate, 0.3, 0.4, 0.5);
+        let saturator = Saturator::new(sample_rate, SaturatorModel::Soft, 0.3, 0.5);
 
         // Create compressor with drum-friendly defaults
         // threshold: -12 dB, ratio: 4:1, attack: 5ms, release: 100ms, mix: 0.5
@@ -876,48 +2868,129 @@ impl GooeyEngine {
         // Create feedback waveshaper with default bypass settings
         let feedback_waveshaper = FeedbackWaveshaper::new(sample_rate, 1.0, 0.0, 2000.0, 0.0);
 
+        // Create ducker with a moderate pump (depth: 0.6, attack: 2ms, hold: 30ms, release: 150ms)
+        let ducker = Ducker::new(sample_rate, 0.6, 2.0, 30.0, 150.0);
+
+        // Create beat repeat (sixteenth slices over the last beat, 120 BPM until set)
+        let beat_repeat = BeatRepeat::new(sample_rate, DelayTiming::Sixteenth, 120.0, 1.0);
+
+        // Create early reflections room (tight room settings)
+        let early_reflections =
+            EarlyReflections::from_preset(sample_rate, EarlyReflectionsPreset::TightRoom);
+
+        // Create widener at unity width (12ms Haas delay, lows limited below 150 Hz)
+        let widener = Widener::new(sample_rate, 1.0, 12.0, 150.0);
+
         // Create LFO pool (8 LFOs, all disabled by default with quarter note timing)
         let lfos = std::array::from_fn(|_| Lfo::with_sample_rate(sample_rate));
         let lfo_routes: [Vec<LfoRoute>; LFO_COUNT] = std::array::from_fn(|_| Vec::new());
 
-        Self {
+        let (timeline_tx, timeline_rx) = sync_channel(TIMELINE_EVENT_CAPACITY);
+
+        let mut engine = Self {
             kit,
             bass,
+            fm_snap,
+            rimshot,
+            cowbell,
+            shaker,
+            slots: std::array::from_fn(|_| None),
+            retiring: std::array::from_fn(|_| std::array::from_fn(|_| None)),
+            // In INSTRUMENT_* order
+            preview_voices: [
+                ChannelInstrument::Kick(KickDrum::new(sample_rate)),
+                ChannelInstrument::Snare(SnareDrum::new(sample_rate)),
+                ChannelInstrument::HiHat(HiHat2::new(sample_rate)),
+                ChannelInstrument::Tom(Tom2::new(sample_rate)),
+                ChannelInstrument::Bass(BassSynth::new(sample_rate)),
+                ChannelInstrument::FmSnap(FmSnap::new(sample_rate)),
+                ChannelInstrument::Rimshot(Rimshot::new(sample_rate)),
+                ChannelInstrument::Cowbell(Cowbell::new(sample_rate)),
+                ChannelInstrument::Shaker(Shaker::new(sample_rate)),
+            ],
+            preview_gain: SmoothedParam::new(1.0, 0.0, 1.0, sample_rate, 10.0),
+            preset_normalization: true,
+            preset_loudness: HashMap::new(),
             delay,
-            delay_enabled: false,
+            delay_enabled: AtomicBool::new(false),
             lowpass_filter,
-            lowpass_filter_enabled: false,
+            lowpass_filter_enabled: AtomicBool::new(false),
             tilt_filter,
-            tilt_filter_enabled: false,
+            tilt_filter_enabled: AtomicBool::new(false),
             saturation,
-            saturation_enabled: false,
+            saturation_enabled: AtomicBool::new(false),
+            saturator,
+            saturation_model: AtomicU32::new(SATURATOR_MODEL_TUBE),
             compressor,
-            compressor_enabled: false,
+            compressor_enabled: AtomicBool::new(false),
             compressor_sidechain: COMPRESSOR_SIDECHAIN_NONE,
             reverb,
-            reverb_enabled: false,
+            reverb_enabled: AtomicBool::new(false),
             plate_reverb,
-            plate_reverb_enabled: false,
+            plate_reverb_enabled: AtomicBool::new(false),
             waveshaper,
-            waveshaper_enabled: false,
+            waveshaper_enabled: AtomicBool::new(false),
             feedback_waveshaper,
-            feedback_waveshaper_enabled: false,
+            feedback_waveshaper_enabled: AtomicBool::new(false),
+            ducker,
+            ducker_enabled: AtomicBool::new(false),
+            ducker_source: DUCKER_SOURCE_NONE,
+            beat_repeat,
+            beat_repeat_enabled: AtomicBool::new(false),
+            beat_repeat_source: BEAT_REPEAT_SOURCE_MASTER,
+            early_reflections,
+            early_reflections_enabled: AtomicBool::new(false),
+            widener,
+            widener_enabled: AtomicBool::new(false),
             limiter: SoftLimiter::new(1.0),
-            limiter_enabled: false,
+            limiter_enabled: AtomicBool::new(false),
+            output_safety: OutputSafety::new(
+                sample_rate,
+                DEFAULT_OUTPUT_CEILING,
+                DEFAULT_OUTPUT_FAULT_LEVEL,
+                DEFAULT_OUTPUT_FAULT_HOLD_MS,
+            ),
             effect_order: DEFAULT_EFFECT_ORDER,
             sample_rate,
             bpm,
             swing: 0.5,
-            current_time: 0.0,
+            master_tuning: MasterTuning::default(),
+            clock: SampleClock::new(sample_rate),
             // Match the native Engine's default summing headroom.
             master_gain: SmoothedParam::new(DEFAULT_MASTER_GAIN, 0.0, 2.0, sample_rate, 30.0),
             // LFO pool
             lfos,
-            lfo_enabled: [false; LFO_COUNT],
+            lfo_enabled: std::array::from_fn(|_| AtomicBool::new(false)),
             lfo_routes,
             lfo_next_route_id: [0; LFO_COUNT],
+            lfo_order: std::array::from_fn(|index| index),
+            lfo_to_lfo: [[[0.0; 2]; LFO_COUNT]; LFO_COUNT],
             // MIDI event buffer (pre-allocated for audio thread safety)
             pending_midi_events: Vec::with_capacity(MIDI_EVENT_CAPACITY),
+            // UI timeline (off until a UI asks for it)
+            timeline_enabled: AtomicBool::new(false),
+            timeline_latency_frames: AtomicU32::new(0),
+            timeline_tx,
+            timeline_rx,
+            // Parameter table (off until a host maps it)
+            param_table_enabled: AtomicBool::new(false),
+            param_table: ParamTable::new(),
+            trace: TraceLog::new(),
+            trace_steps: [u32::MAX; NUM_CHANNELS],
+            cpu_load: CpuLoad::new(NUM_CHANNELS),
+            cpu_channel_time: [Duration::ZERO; NUM_CHANNELS],
+            cpu_channel_frames: 0,
+            freeze_capture: None,
+            resampler: None,
+            overload_enabled: AtomicBool::new(false),
+            overload_threshold: AtomicU32::new(DEFAULT_OVERLOAD_THRESHOLD.to_bits()),
+            overload_priority: AtomicU32::new(pack_overload_priority(&DEFAULT_OVERLOAD_PRIORITY)),
+            governor: OverloadGovernor::new(DEFAULT_OVERLOAD_THRESHOLD),
+            degradation: AtomicU32::new(0),
+            shed_order: Vec::with_capacity(OVERLOAD_STAGE_COUNT as usize),
+            saved_oversampling: [OversamplingMode::default(); 4],
+            rendered_frames: 0,
+            render_first_frame: 0,
             // Sequencer triggers enabled by default (internal sequencer drives instruments)
             sequencer_triggers_enabled: AtomicBool::new(true),
             // Polyphonic synthesizer for chord playback
@@ -944,13 +3017,55 @@ impl GooeyEngine {
             error_message: None,
             error_callback: None,
             error_callback_context: std::ptr::null_mut(),
+            // Trigger notifications (none until the host registers one)
+            trigger_callback: None,
+            trigger_callback_context: std::ptr::null_mut(),
+            trigger_callback_mask: 0,
             // Scheduled-start state (used for Ableton Link Sync Start/Stop)
             host_clock_anchor: None,
             pending_arm_host_time: None,
             // Chord performance clip (disarmed by default)
             performance: PerformanceRecorder::new(),
+            capture: TriggerCapture::new(),
+            grooves: GroovePool::new(),
+            effect_lanes: [None; EFFECT_LANE_MAX as usize],
+            motion_recording: AtomicBool::new(false),
             samplers: std::array::from_fn(|_| None),
+            recorder: Recorder::new(sample_rate),
+            control: ControlQueue::new(),
+            gate: RenderGate::new(),
+            control_stash: Vec::with_capacity(2 * CONTROL_QUEUE_CAPACITY),
+            scale_quantize: AtomicU32::new(SCALE_QUANTIZE_OFF),
+            mute_quantize: AtomicU32::new(CLIP_QUANTIZE_IMMEDIATE),
+            group_gains: std::array::from_fn(|_| AtomicU32::new(1.0_f32.to_bits())),
+            mode_requests_pending: AtomicBool::new(false),
+            mode_change_beat: None,
+            pattern_slots: vec![None; PATTERN_SLOT_COUNT as usize],
+            active_pattern: None,
+            queued_pattern: None,
+            pattern_clipboard: None,
+            rng_seed: DEFAULT_RNG_SEED,
+            humanize_rng: Rng::new(DEFAULT_RNG_SEED),
+            panic_requested: AtomicBool::new(false),
+            panic_fade: None,
+        };
+        engine.reseed();
+        engine
+    }
+
+    /// Restart every random stream (instrument noise, pan spread, variation,
+    /// grain scatter, velocity humanizing) from `rng_seed`, so what follows
+    /// renders the same way every time.
+    fn reseed(&mut self) {
+        let seed = self.rng_seed;
+        for channel in 0..NUM_CHANNELS {
+            if let Some(voice) = self.voice_mut(channel) {
+                voice.reseed(seed, channel as u32);
+            }
         }
+        self.granulator
+            .reseed(Rng::stream(seed, RngStream::Granulator, 0).next_u64());
+        self.humanize_rng = Rng::stream(seed, RngStream::Humanize, 0);
     }
 
     /// Resolve any `pending_arm_host_time` against the current
@@ -991,45 +3106,274 @@ impl GooeyEngine {
         }
     }
 
-    /// Push a MIDI event without growing the buffer. Drops the event if at capacity.
-    #[inline]
-    fn push_midi_event(&mut self, instrument_index: u32, velocity: f32, sample_offset: u32) {
-        if self.pending_midi_events.len() < MIDI_EVENT_CAPACITY {
-            self.pending_midi_events.push(GooeyMidiEvent {
-                instrument_index,
-                velocity,
-                sample_offset,
-            });
+    /// Queue a UI timeline event for a hit at `sample_offset` in the current
+    /// render call. Dropped when the timeline is off or the queue is full.
+    fn push_timeline_event(&self, channel: u32, step: u32, velocity: f32, sample_offset: u32) {
+        if !self.timeline_enabled.load(Ordering::Relaxed) {
+            return;
         }
+        let sample_time = self.render_first_frame + sample_offset as u64;
+        let latency = self.timeline_latency_frames.load(Ordering::Relaxed) as u64;
+        let host_time = self.host_clock_anchor.map_or(0, |anchor| {
+            let frames = (sample_time + latency) as f64 - anchor.frame as f64;
+            (anchor.host_time_first_sample as f64 + frames * anchor.host_ticks_per_sample) as u64
+        });
+        let _ = self.timeline_tx.try_send(GooeyTimelineEvent {
+            channel,
+            step,
+            velocity,
+            sample_offset,
+            sample_time,
+            host_time,
+        });
     }
 
-    /// Render audio into an interleaved stereo `buffer` of `buffer.len() / 2`
-    /// frames. Each frame occupies two consecutive slots: `[left, right]`. The
-    /// signal path is mono, so left and right are currently identical (see the
-    /// "stereo seam" near the end of the per-frame loop), but the engine writes
-    /// two-channel output so hosts (and future stereo features) consume stereo.
-    /// Borrow a voice by legacy instrument index: 0..=3 are the kit drum voices
-    /// (kick, snare, hihat, tom), 4 is bass. Returns `None` for out-of-range.
-    fn voice(&self, idx: usize) -> Option<&VoiceStrip> {
-        match idx {
-            i if i < KIT_VOICE_COUNT => self.kit.voices.get(i),
-            i if i == KIT_VOICE_COUNT => Some(&self.bass),
-            _ => None,
+    /// Tell the host's trigger callback about a hit at `sample_offset` in
+    /// the current render call, if one is registered for `channel`.
+    fn notify_trigger(&self, channel: u32, velocity: f32, sample_offset: u32) {
+        let Some(callback) = self.trigger_callback else {
+            return;
+        };
+        if channel < u32::BITS && self.trigger_callback_mask & (1 << channel) != 0 {
+            callback(
+                self.trigger_callback_context,
+                channel,
+                velocity,
+                sample_offset,
+            );
         }
     }
 
-    /// Mutable counterpart to [`voice`](Self::voice).
-    fn voice_mut(&mut self, idx: usize) -> Option<&mut VoiceStrip> {
-        match idx {
-            i if i < KIT_VOICE_COUNT => self.kit.voices.get_mut(i),
-            i if i == KIT_VOICE_COUNT => Some(&mut self.bass),
-            _ => None,
+    /// Re-sort the LFO evaluation order after routes change: each LFO after
+    /// the LFOs modulating it, pool order otherwise. LFOs in a feedback loop
+    /// keep pool order among themselves.
+    fn update_lfo_order(&mut self) {
+        let mut modulates = [[false; LFO_COUNT]; LFO_COUNT];
+        let mut sources = [0_usize; LFO_COUNT];
+        for (source, routes) in self.lfo_routes.iter().enumerate() {
+            for target in routes.iter().filter_map(|r| lfo_route_target(r.instrument)) {
+                if !modulates[source][target] {
+                    modulates[source][target] = true;
+                    sources[target] += 1;
+                }
+            }
+        }
+        let mut placed = [false; LFO_COUNT];
+        for slot in 0..LFO_COUNT {
+            let Some(next) = (0..LFO_COUNT)
+                .find(|&i| !placed[i] && sources[i] == 0)
+                .or_else(|| (0..LFO_COUNT).find(|&i| !placed[i]))
+            else {
+                break;
+            };
+            placed[next] = true;
+            self.lfo_order[slot] = next;
+            for target in 0..LFO_COUNT {
+                if modulates[next][target] {
+                    sources[target] -= 1;
+                }
+            }
         }
     }
 
-    /// Iterate all addressable voices in index order (kit drums then bass).
+    /// Restart the cycle of every route of an enabled one-shot LFO that
+    /// listens to `channel`.
+    fn retrigger_lfo_routes(&mut self, channel: u32) {
+        for lfo_idx in 0..LFO_COUNT {
+            if !self.lfos[lfo_idx].is_one_shot()
+                || !self.lfo_enabled[lfo_idx].load(Ordering::Relaxed)
+            {
+                continue;
+            }
+            for route in &mut self.lfo_routes[lfo_idx] {
+                if route.trigger_source == channel {
+                    route.cycle = 0.0;
+                }
+            }
+        }
+    }
+
+    /// Feed the load meter with a render of `frames` that took `elapsed`,
+    /// and with the per-channel times sampled during it, scaled up to the
+    /// whole render.
+    fn record_cpu_load(&mut self, elapsed: Duration, frames: usize) {
+        self.cpu_load.record(elapsed, frames, self.sample_rate);
+        let sampled = std::mem::take(&mut self.cpu_channel_frames);
+        if cfg!(debug_assertions) && sampled > 0 {
+            let scale = frames as f64 / sampled as f64;
+            for (ch, time) in self.cpu_channel_time.iter_mut().enumerate() {
+                let time = std::mem::take(time).mul_f64(scale);
+                self.cpu_load
+                    .record_channel(ch, time, frames, self.sample_rate);
+            }
+        }
+        self.update_overload(frames);
+    }
+
+    /// Run the overload governor for a render of `frames`, shedding or
+    /// restoring one stage when it says so. Turning protection off restores
+    /// everything at once.
+    fn update_overload(&mut self, frames: usize) {
+        if !self.overload_enabled.load(Ordering::Relaxed) {
+            while let Some(stage) = self.shed_order.pop() {
+                self.restore_stage(stage);
+            }
+            return;
+        }
+        self.governor.set_threshold(f32::from_bits(
+            self.overload_threshold.load(Ordering::Relaxed),
+        ));
+        let next = self.next_stage_to_shed();
+        let action = self.governor.update(
+            self.cpu_load.average(),
+            frames as f32 / self.sample_rate,
+            next.is_some(),
+            !self.shed_order.is_empty(),
+        );
+        match action {
+            Some(GovernorAction::Shed) => {
+                if let Some(stage) = next {
+                    self.shed_stage(stage);
+                    self.shed_order.push(stage);
+                }
+            }
+            Some(GovernorAction::Restore) => {
+                if let Some(stage) = self.shed_order.pop() {
+                    self.restore_stage(stage);
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// First stage in the priority list that is not shed yet.
+    fn next_stage_to_shed(&self) -> Option<u32> {
+        let priority = self.overload_priority.load(Ordering::Relaxed);
+        let shed = self.degradation.load(Ordering::Relaxed);
+        (0..OVERLOAD_STAGE_COUNT)
+            .map(|i| (priority >> (8 * i)) & 0xFF)
+            .take_while(|&entry| entry != 0)
+            .map(|entry| entry - 1)
+            .find(|stage| shed & (1 << stage) == 0)
+    }
+
+    fn shed_stage(&mut self, stage: u32) {
+        match stage {
+            OVERLOAD_STAGE_OVERSAMPLING => {
+                self.saved_oversampling = [
+                    self.saturation.get_oversampling_mode(),
+                    self.compressor.oversampling_mode(),
+                    self.waveshaper.oversampling_mode(),
+                    self.feedback_waveshaper.oversampling_mode(),
+                ];
+                self.set_oversampling([OversamplingMode::Off; 4]);
+            }
+            OVERLOAD_STAGE_VOICES => self.poly_synth.set_voice_limit(OVERLOAD_POLY_VOICES),
+            // Reverbs are skipped in the effect loop while the bit is set
+            _ => {}
+        }
+        self.degradation.fetch_or(1 << stage, Ordering::Relaxed);
+    }
+
+    fn restore_stage(&mut self, stage: u32) {
+        match stage {
+            OVERLOAD_STAGE_OVERSAMPLING => self.set_oversampling(self.saved_oversampling),
+            OVERLOAD_STAGE_VOICES => self.poly_synth.set_voice_limit(usize::MAX),
+            _ => {}
+        }
+        self.degradation.fetch_and(!(1 << stage), Ordering::Relaxed);
+    }
+
+    fn set_oversampling(&mut self, modes: [OversamplingMode; 4]) {
+        self.saturation.set_oversampling_mode(modes[0]);
+        self.compressor.set_oversampling_mode(modes[1]);
+        self.waveshaper.set_oversampling_mode(modes[2]);
+        self.feedback_waveshaper.set_oversampling_mode(modes[3]);
+    }
+
+    /// Log every running sequencer that moved to a new step.
+    fn trace_step_changes(&mut self, sample_offset: u32) {
+        for ch in 0..NUM_CHANNELS {
+            let Some(step) = self
+                .voice(ch)
+                .filter(|v| v.sequencer.is_running())
+                .map(|v| v.sequencer.current_step() as u32)
+            else {
+                continue;
+            };
+            if step != self.trace_steps[ch] {
+                self.trace_steps[ch] = step;
+                self.trace.record(
+                    self.render_first_frame + sample_offset as u64,
+                    TraceEvent::Step {
+                        channel: ch as u32,
+                        step,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Push a MIDI event without growing the buffer. Drops the event if at capacity.
+    #[inline]
+    fn push_midi_event(&mut self, instrument_index: u32, velocity: f32, sample_offset: u32) {
+        if self.pending_midi_events.len() < MIDI_EVENT_CAPACITY {
+            self.pending_midi_events.push(GooeyMidiEvent {
+                instrument_index,
+                velocity,
+                sample_offset,
+            });
+        }
+    }
+
+    /// Render audio into an interleaved stereo `buffer` of `buffer.len() / 2`
+    /// frames. Each frame occupies two consecutive slots: `[left, right]`. The
+    /// signal path is mono, so left and right are currently identical (see the
+    /// "stereo seam" near the end of the per-frame loop), but the engine writes
+    /// two-channel output so hosts (and future stereo features) consume stereo.
+    /// Borrow a voice by channel index: 0..=3 are the kit drum voices (kick,
+    /// snare, hihat, tom), 4 is bass, 5 is the FM snap, 6 the rimshot, 7 the
+    /// cowbell, 8 the shaker, and higher channels are host-created slots.
+    /// Returns `None` for out-of-range or empty slots.
+    fn voice(&self, idx: usize) -> Option<&VoiceStrip> {
+        match idx {
+            i if i < KIT_VOICE_COUNT => self.kit.voices.get(i),
+            i if i == KIT_VOICE_COUNT => Some(&self.bass),
+            i if i == INSTRUMENT_FM_SNAP as usize => Some(&self.fm_snap),
+            i if i == INSTRUMENT_RIMSHOT as usize => Some(&self.rimshot),
+            i if i == INSTRUMENT_COWBELL as usize => Some(&self.cowbell),
+            i if i == INSTRUMENT_SHAKER as usize => Some(&self.shaker),
+            i => self.slots.get(i - NUM_INSTRUMENTS)?.as_ref(),
+        }
+    }
+
+    /// Mutable counterpart to [`voice`](Self::voice).
+    fn voice_mut(&mut self, idx: usize) -> Option<&mut VoiceStrip> {
+        match idx {
+            i if i < KIT_VOICE_COUNT => self.kit.voices.get_mut(i),
+            i if i == KIT_VOICE_COUNT => Some(&mut self.bass),
+            i if i == INSTRUMENT_FM_SNAP as usize => Some(&mut self.fm_snap),
+            i if i == INSTRUMENT_RIMSHOT as usize => Some(&mut self.rimshot),
+            i if i == INSTRUMENT_COWBELL as usize => Some(&mut self.cowbell),
+            i if i == INSTRUMENT_SHAKER as usize => Some(&mut self.shaker),
+            i => self.slots.get_mut(i - NUM_INSTRUMENTS)?.as_mut(),
+        }
+    }
+
+    /// Iterate all addressable voices in index order (kit drums, bass, FM snap,
+    /// rimshot, cowbell, shaker, then occupied slots).
     fn voices_iter(&self) -> impl Iterator<Item = &VoiceStrip> {
-        self.kit.voices.iter().chain(std::iter::once(&self.bass))
+        self.kit
+            .voices
+            .iter()
+            .chain([
+                &self.bass,
+                &self.fm_snap,
+                &self.rimshot,
+                &self.cowbell,
+                &self.shaker,
+            ])
+            .chain(self.slots.iter().flatten())
     }
 
     /// Mutable counterpart to [`voices_iter`](Self::voices_iter).
@@ -1037,15 +3381,134 @@ impl GooeyEngine {
         self.kit
             .voices
             .iter_mut()
-            .chain(std::iter::once(&mut self.bass))
+            .chain([
+                &mut self.bass,
+                &mut self.fm_snap,
+                &mut self.rimshot,
+                &mut self.cowbell,
+                &mut self.shaker,
+            ])
+            .chain(self.slots.iter_mut().flatten())
+    }
+
+    /// Fill the first empty slot with a fresh `instrument_type` voice and
+    /// return its channel. The new sequencer picks up the engine's tempo,
+    /// swing and, if the transport is running, its position.
+    /// A fresh `instrument_type` voice for slot `index`, built without the
+    /// engine so it can be made off the audio thread; `None` for an unknown
+    /// type. [`install_slot`](Self::install_slot) puts it in place.
+    fn slot_voice(index: usize, instrument_type: u32, sample_rate: f32) -> Option<VoiceStrip> {
+        let channel = NUM_INSTRUMENTS + index;
+        let instrument = ChannelInstrument::new(instrument_type, sample_rate)?;
+        // Retimed to the engine by `install_slot`
+        let sequencer = Sequencer::with_pattern(
+            120.0,
+            sample_rate,
+            vec![false; 16],
+            format!("slot-{channel}"),
+        );
+        Some(VoiceStrip::new(
+            instrument,
+            sequencer,
+            instrument_type,
+            sample_rate,
+        ))
+    }
+
+    /// Put `voice`, from [`slot_voice`](Self::slot_voice), in empty slot
+    /// `index`, following the engine's tuning, tempo, swing and transport.
+    /// Allocation-free; returns the slot's channel.
+    fn install_slot(&mut self, index: usize, mut voice: VoiceStrip) -> usize {
+        let channel = NUM_INSTRUMENTS + index;
+        voice.instrument.set_pitch_ratio(self.master_tuning.ratio());
+        voice.sequencer.set_bpm(self.bpm);
+        voice.sequencer.set_swing(self.swing);
+        if let Some(reference) = self.reference_sequencer() {
+            if reference.is_running() {
+                voice
+                    .sequencer
+                    .set_beat_position(self.compute_beat_position());
+                voice.sequencer.start();
+            }
+        }
+        voice.reseed(self.rng_seed, channel as u32);
+        self.slots[index] = Some(voice);
+        channel
+    }
+
+    /// Create an `instrument_type` voice in slot `index`, which must be empty.
+    /// Allocates; `None` for an unknown type.
+    fn create_slot_at(&mut self, index: usize, instrument_type: u32) -> Option<usize> {
+        let voice = Self::slot_voice(index, instrument_type, self.sample_rate)?;
+        Some(self.install_slot(index, voice))
+    }
+
+    /// Empty a host-created slot, dropping LFO routes and effect sources that
+    /// pointed at it so a later slot in the same channel starts clean.
+    ///
+    /// The slot's instrument fades out in `carrier` (see
+    /// [`RetiringVoice::carrier`]; one is allocated here if it is `None`),
+    /// and the rest of the voice, now holding the carrier's placeholder
+    /// instrument, is returned for the caller to free. `None` if `channel`
+    /// is not an occupied slot.
+    fn destroy_slot(
+        &mut self,
+        channel: usize,
+        carrier: &mut Option<Box<RetiringVoice>>,
+    ) -> Option<VoiceStrip> {
+        let index = channel.checked_sub(NUM_INSTRUMENTS)?;
+        self.slots.get(index)?.as_ref()?;
+        let mut tail = match carrier.take() {
+            Some(tail) => tail,
+            None => {
+                RetiringVoice::carrier(ChannelInstrument::new(INSTRUMENT_KICK, self.sample_rate)?)
+            }
+        };
+        let mut voice = self.slots[index].take()?;
+        tail.swap_with(&mut voice);
+        self.retire(channel, tail);
+        let channel = channel as u32;
+        for routes in &mut self.lfo_routes {
+            routes.retain(|route| route.instrument != channel);
+            for route in routes.iter_mut() {
+                if route.trigger_source == channel {
+                    route.trigger_source = route.instrument;
+                }
+            }
+        }
+        if self.compressor_sidechain == channel {
+            self.compressor_sidechain = COMPRESSOR_SIDECHAIN_NONE;
+        }
+        if self.ducker_source == channel {
+            self.ducker_source = DUCKER_SOURCE_NONE;
+        }
+        if self.beat_repeat_source == channel {
+            self.beat_repeat_source = BEAT_REPEAT_SOURCE_MASTER;
+        }
+        Some(voice)
     }
 
     fn render(&mut self, buffer: &mut [f32]) {
+        // Apply parameter writes queued by control threads since the last render
+        self.drain_control();
+        self.take_retiring();
+        if self.param_table_enabled.load(Ordering::Relaxed) {
+            self.poll_param_table();
+        }
+
+        if self.panic_requested.swap(false, Ordering::Acquire) {
+            self.panic_fade = Some(PanicFade::Out(self.panic_fade_len()));
+        }
+
         // Clear pending MIDI events from previous render pass
         self.pending_midi_events.clear();
 
         // Number of stereo frames this buffer holds (two slots per frame).
         let frame_count = buffer.len() / 2;
+        self.render_first_frame = self.rendered_frames;
+        self.rendered_frames += frame_count as u64;
+        let reverb_shed =
+            self.degradation.load(Ordering::Relaxed) & (1 << OVERLOAD_STAGE_REVERB) != 0;
 
         // Resolve any host-time-armed start against this buffer's host clock.
         // Possible outcomes:
@@ -1065,6 +3528,8 @@ impl GooeyEngine {
                 // on the next render call", and this render produced silence.
                 for voice in self.voices_iter() {
                     voice.trigger_pending.store(false, Ordering::Release);
+                    voice.trigger_note.store(NO_TRIGGER_NOTE, Ordering::Release);
+                    voice.release_pending.store(false, Ordering::Release);
                 }
                 for sample in buffer.iter_mut() {
                     *sample = 0.0;
@@ -1076,37 +3541,66 @@ impl GooeyEngine {
 
         // Check for pending manual triggers with velocity (all channels)
         // Manual triggers fire at sample_offset 0 (start of buffer)
-        for ch in 0..NUM_INSTRUMENTS {
+        for ch in 0..NUM_CHANNELS {
             let fired = self.voice(ch).and_then(|v| {
                 if v.trigger_pending.swap(false, Ordering::Acquire) {
-                    Some(f32::from_bits(v.trigger_velocity.load(Ordering::Acquire)))
+                    let note = v.trigger_note.swap(NO_TRIGGER_NOTE, Ordering::Acquire);
+                    Some((
+                        f32::from_bits(v.trigger_velocity.load(Ordering::Acquire)),
+                        (note != NO_TRIGGER_NOTE).then_some(note),
+                    ))
                 } else {
                     None
                 }
             });
-            if let Some(velocity) = fired {
+            if let Some((velocity, note)) = fired {
                 self.push_midi_event(ch as u32, velocity, 0);
-                let time = self.current_time;
+                self.push_timeline_event(ch as u32, TIMELINE_STEP_NONE, velocity, 0);
+                self.notify_trigger(ch as u32, velocity, 0);
+                self.retrigger_lfo_routes(ch as u32);
+                self.trace.record(
+                    self.render_first_frame,
+                    TraceEvent::Trigger {
+                        channel: ch as u32,
+                        velocity,
+                    },
+                );
+                if self.mixer.transport_running() {
+                    self.capture.record(CapturedHit {
+                        channel: ch as u32,
+                        beat: self.mixer.transport_beat(),
+                        velocity,
+                    });
+                }
+                if ch as u32 == self.ducker_source {
+                    self.ducker.trigger();
+                }
+                let time = self.clock.seconds();
                 if let Some(voice) = self.voice_mut(ch) {
-                    voice.instrument.trigger_with_velocity(time, velocity);
+                    let range = Self::freq_range_for_instrument(voice.instrument.instrument_type());
+                    if let (Some(note), Some((freq_min, freq_max))) = (note, range) {
+                        let normalized =
+                            Self::midi_note_to_normalized_freq(note, freq_min, freq_max);
+                        voice.instrument.set_param(0, normalized);
+                        voice.instrument.snap_params();
+                    }
+                    voice.trigger(time, velocity, None);
+                }
+            }
+            let time = self.clock.seconds();
+            if let Some(voice) = self.voice_mut(ch) {
+                if voice.release_pending.swap(false, Ordering::Acquire) {
+                    voice.release(time);
                 }
             }
         }
 
-        let sample_period = 1.0 / self.sample_rate as f64;
-
-        // Update mute/solo gain targets (check once per buffer for efficiency)
-        let any_soloed = self.voices_iter().any(|v| v.soloed.load(Ordering::Relaxed));
-        for voice in self.voices_iter_mut() {
-            let target = Self::calculate_instrument_gain(
-                voice.muted.load(Ordering::Relaxed),
-                voice.soloed.load(Ordering::Relaxed),
-                any_soloed,
-            );
-            voice.mute_gain.set_target(target);
-        }
+        // Update mute/solo and group gain targets (check once per buffer for efficiency)
+        self.update_mute_gain_targets();
+        self.update_group_gain_targets();
         // Recompute per-track mute/solo targets (scoped across tracks) once per buffer.
         self.graph.update_mute_solo_targets();
+        let dry_join = self.fx_bypass_join();
 
         let mut sample_offset: u32 = 0;
         for frame in buffer.chunks_mut(2) {
@@ -1146,61 +3640,105 @@ impl GooeyEngine {
                 }
             }
 
+            // Quantized mute/solo changes land before this sample's steps fire
+            if self.mode_requests_pending.load(Ordering::Acquire) {
+                self.apply_mode_requests_if_due();
+            }
+
             // Tick ALL sequencers first to ensure sample-accurate synchronization
-            let mut seq_triggers: [Option<(f32, Option<SequencerBlendSetting>, Option<u8>)>;
-                NUM_INSTRUMENTS] = [None; NUM_INSTRUMENTS];
-            for ch in 0..NUM_INSTRUMENTS {
+            // (velocity, blend, note, articulation, tune, gate_samples)
+            type StepTrigger = (
+                f32,
+                Option<SequencerBlendSetting>,
+                Option<u8>,
+                Option<u8>,
+                Option<f32>,
+                Option<u64>,
+            );
+            let mut seq_triggers: [Option<StepTrigger>; NUM_CHANNELS] = [None; NUM_CHANNELS];
+            for ch in 0..NUM_CHANNELS {
                 if let Some(voice) = self.voice_mut(ch) {
-                    seq_triggers[ch] = voice
-                        .sequencer
-                        .tick_with_settings()
-                        .map(|trigger| (trigger.velocity, trigger.blend, trigger.note));
+                    seq_triggers[ch] = voice.sequencer.tick_with_settings().map(|trigger| {
+                        (
+                            trigger.velocity,
+                            trigger.blend,
+                            trigger.note,
+                            trigger.articulation,
+                            trigger.tune,
+                            trigger.gate_samples,
+                        )
+                    });
                 }
             }
+            if TraceLog::ENABLED {
+                self.trace_step_changes(sample_offset);
+            }
 
             // Apply triggers with velocity after all sequencers have been ticked.
             if self.sequencer_triggers_enabled.load(Ordering::Relaxed) {
-                let time = self.current_time;
-                for ch in 0..NUM_INSTRUMENTS {
-                    if let Some((velocity, blend, note)) = seq_triggers[ch] {
+                let time = self.clock.seconds();
+                let quantize = self.scale_quantize();
+                for ch in 0..NUM_CHANNELS {
+                    if let Some((velocity, blend, note, articulation, tune, gate_samples)) =
+                        seq_triggers[ch]
+                    {
                         self.apply_sequencer_blend_setting(ch as u32, blend);
                         if let Some(voice) = self.voice_mut(ch) {
                             // Snap params only when a blend was actually applied,
                             // so we don't clobber in-flight UI/LFO smoothing.
-                            if blend.is_some() || voice.blend_enabled {
+                            if blend.is_some() || voice.blend_enabled.load(Ordering::Relaxed) {
+                                voice.finish_config_fade();
                                 voice.instrument.snap_params();
                             }
-                            // Apply per-step MIDI note frequency override (sample-accurate).
-                            // When a step has a note, save the global freq and override.
-                            // When a step has no note, restore the saved global freq.
-                            if let Some(midi_note) = note {
-                                let instr_type = voice.instrument.instrument_type();
-                                if let Some((freq_min, freq_max)) =
-                                    Self::freq_range_for_instrument(instr_type)
-                                {
-                                    if voice.saved_global_freq.is_none() {
-                                        voice.saved_global_freq = voice.instrument.get_freq_param();
+                            // Apply per-step MIDI note frequency override (sample-accurate),
+                            // shared with `Engine` through `StepNote`.
+                            let range =
+                                Self::freq_range_for_instrument(voice.instrument.instrument_type());
+                            let frequency = range.and(voice.instrument.get_freq_param());
+                            match voice.step_note.resolve(note, quantize, frequency) {
+                                NoteChange::Play(midi_note) => {
+                                    if let Some((freq_min, freq_max)) = range {
+                                        let normalized = Self::midi_note_to_normalized_freq(
+                                            midi_note, freq_min, freq_max,
+                                        );
+                                        voice.instrument.set_param(0, normalized);
+                                        voice.instrument.snap_params();
                                     }
-                                    let normalized = Self::midi_note_to_normalized_freq(
-                                        midi_note, freq_min, freq_max,
-                                    );
-                                    voice.instrument.set_param(0, normalized);
+                                }
+                                NoteChange::Restore(saved) => {
+                                    voice.instrument.set_param(0, saved);
                                     voice.instrument.snap_params();
                                 }
-                            } else if let Some(saved) = voice.saved_global_freq.take() {
-                                voice.instrument.set_param(0, saved);
-                                voice.instrument.snap_params();
+                                NoteChange::Keep => {}
                             }
-                            voice.instrument.trigger_with_velocity(time, velocity);
+                            voice.apply_step_tune(tune);
+                            voice.trigger(time, velocity, articulation);
+                            voice.gate_remaining = gate_samples;
                         }
                         self.push_midi_event(ch as u32, velocity, sample_offset);
+                        let step = self
+                            .voice(ch)
+                            .map_or(TIMELINE_STEP_NONE, |v| v.sequencer.current_step() as u32);
+                        self.push_timeline_event(ch as u32, step, velocity, sample_offset);
+                        self.notify_trigger(ch as u32, velocity, sample_offset);
+                        self.retrigger_lfo_routes(ch as u32);
+                        self.trace.record(
+                            self.render_first_frame + sample_offset as u64,
+                            TraceEvent::Trigger {
+                                channel: ch as u32,
+                                velocity,
+                            },
+                        );
+                        if ch as u32 == self.ducker_source {
+                            self.ducker.trigger();
+                        }
                     }
                 }
                 // Sampler patterns share the transport, but their slot hits are
                 // intentionally not performance-recorded.
                 for rack in self.samplers.iter_mut().flatten() {
-                    if let Some((slot, velocity)) = rack.tick_sequencer() {
-                        rack.trigger(slot, velocity);
+                    if let Some((slot, slice, velocity)) = rack.tick_sequencer() {
+                        rack.trigger_slice(slot, slice, velocity);
                     }
                 }
             } else {
@@ -1219,7 +3757,10 @@ impl GooeyEngine {
                     .map(|s| s.is_running())
                     .unwrap_or(false);
                 if let Some(action) = self.performance.update_clock(beat, running) {
-                    self.apply_performance_action(action);
+                    self.apply_performance_action(
+                        action,
+                        self.render_first_frame + sample_offset as u64,
+                    );
                 }
                 let sampler_hits = self.performance.take_sampler_hits();
                 for hit in sampler_hits {
@@ -1234,19 +3775,42 @@ impl GooeyEngine {
                 self.performance.clear_pending_sampler_hits();
             }
 
-            // Process LFOs and apply modulation to routed parameters
-            for lfo_idx in 0..LFO_COUNT {
-                if self.lfo_enabled[lfo_idx] {
-                    let lfo_value = self.lfos[lfo_idx].tick();
+            self.advance_effect_lanes();
+
+            // Process LFOs and apply modulation to routed parameters. LFOs
+            // run in dependency order so one modulating another's rate or
+            // amount is evaluated first and the target sees this sample's
+            // value; inside a feedback loop it sees the previous sample's.
+            for lfo_idx in self.lfo_order {
+                let (rate, amount) =
+                    self.lfo_to_lfo
+                        .iter()
+                        .fold((0.0, 0.0), |(rate, amount), sent| {
+                            let [r, a] = sent[lfo_idx];
+                            (rate + r, amount + a)
+                        });
+                self.lfos[lfo_idx].set_modulation(rate, amount);
+                self.lfo_to_lfo[lfo_idx] = [[0.0; 2]; LFO_COUNT];
+                if self.lfo_enabled[lfo_idx].load(Ordering::Relaxed) {
                     let route_count = self.lfo_routes[lfo_idx].len();
 
+                    let one_shot_step = self.lfos[lfo_idx]
+                        .is_one_shot()
+                        .then(|| self.lfos[lfo_idx].phase_increment());
                     for route_idx in 0..route_count {
-                        let channel = self.lfo_routes[lfo_idx][route_idx].instrument;
-                        let param = self.lfo_routes[lfo_idx][route_idx].param;
-                        let depth = self.lfo_routes[lfo_idx][route_idx].depth;
-                        let modulation = lfo_value * depth;
-                        self.apply_modulation_by_index(channel, param, modulation);
+                        let route = &mut self.lfo_routes[lfo_idx][route_idx];
+                        let (channel, param) = (route.instrument, route.param);
+                        let modulation = route.modulation(&self.lfos[lfo_idx]);
+                        if let Some(step) = one_shot_step {
+                            route.cycle = (route.cycle + step).min(1.0);
+                        }
+                        if let Some(target) = lfo_route_target(channel) {
+                            self.lfo_to_lfo[lfo_idx][target][param as usize] += modulation;
+                        } else {
+                            self.apply_modulation_by_index(channel, param, modulation);
+                        }
                     }
+                    self.lfos[lfo_idx].tick();
                 }
             }
 
@@ -1257,30 +3821,111 @@ impl GooeyEngine {
             // each effect can process true left/right. Effects are stereo-aware
             // (per-channel state); with every channel centered and no stereo
             // effect engaged the two channels stay identical.
-            // Sum each voice into its source frame: kit voices (0..KIT_VOICE_COUNT)
-            // form the DrumKit source, bass forms the Bass source. Per-voice gain,
-            // mute/solo, pan, and peak metering are unchanged; only the routing
-            // target differs. `channel_outs` still feeds the compressor sidechain.
-            let mut channel_outs = [0.0_f32; NUM_INSTRUMENTS];
+            // Sum each voice into its source frame: kit voices (0..KIT_VOICE_COUNT),
+            // the FM snap, rimshot, cowbell, shaker and host-created slots form the
+            // DrumKit source, bass forms
+            // the Bass source. Per-voice gain, mute/solo, pan, and peak metering are
+            // unchanged; only the routing target differs. `channel_outs` still feeds
+            // the compressor sidechain.
+            let mut channel_outs = [0.0_f32; NUM_CHANNELS];
             let mut kit_frame = StereoFrame::default();
             let mut bass_frame = StereoFrame::default();
-            let time = self.current_time;
-            for (ch, voice) in self.voices_iter_mut().enumerate() {
-                let ch_out = voice.instrument.tick(time)
+            // Voices that bypass master effects, summed apart (dry-routed)
+            let mut kit_dry = StereoFrame::default();
+            let mut bass_dry = StereoFrame::default();
+            let time = self.clock.seconds();
+            let beat_repeat_enabled = self.beat_repeat_enabled.load(Ordering::Relaxed);
+            if beat_repeat_enabled && self.reference_sequencer().is_some_and(|s| s.is_running()) {
+                self.beat_repeat
+                    .sync_beat_position(self.compute_beat_position());
+            }
+            // A beat repeat sourced from one instrument runs on that voice,
+            // before pan. Voices are borrowed field-by-field (rather than via
+            // `voices_iter_mut`) so the effect stays reachable in the loop.
+            let repeat_channel = if beat_repeat_enabled {
+                self.beat_repeat_source as usize
+            } else {
+                usize::MAX
+            };
+            let beat_repeat = &self.beat_repeat;
+            let timed = cfg!(debug_assertions) && sample_offset % CPU_CHANNEL_STRIDE == 0;
+            self.cpu_channel_frames += timed as u32;
+            let voices = self
+                .kit
+                .voices
+                .iter_mut()
+                .chain([
+                    &mut self.bass,
+                    &mut self.fm_snap,
+                    &mut self.rimshot,
+                    &mut self.cowbell,
+                    &mut self.shaker,
+                ])
+                .map(Some)
+                .chain(self.slots.iter_mut().map(Option::as_mut))
+                .enumerate()
+                .filter_map(|(ch, voice)| Some((ch, voice?)));
+            for (ch, voice) in voices {
+                voice.tick_config_fade();
+                voice.tick_gate(time);
+                let started = timed.then(Instant::now);
+                let dry = match voice.frozen.as_mut() {
+                    Some(frozen) => frozen.tick(&voice.sequencer),
+                    None => voice.instrument.tick(time),
+                };
+                if let Some(started) = started {
+                    self.cpu_channel_time[ch] += started.elapsed();
+                }
+                let dry = if voice.choked { 0.0 } else { dry };
+                if let Some((_, captured)) = self
+                    .freeze_capture
+                    .as_mut()
+                    .filter(|(target, _)| *target == ch)
+                {
+                    captured.push(dry);
+                }
+                voice.meter_pre.tick(dry);
+                let mut ch_out = dry
                     * voice.channel_gain.tick()
-                    * voice.mute_gain.tick();
+                    * voice.group_gain.tick()
+                    * voice.mute_gain.tick()
+                    * voice.preset_gain.tick();
+                if ch == repeat_channel {
+                    ch_out = beat_repeat.process(ch_out);
+                }
                 channel_outs[ch] = ch_out;
-
-                let panned = StereoFrame::panned(ch_out, voice.pan.tick());
-                if ch < KIT_VOICE_COUNT {
-                    kit_frame += panned;
-                } else {
-                    bass_frame += panned;
+                voice.meter_post.tick(ch_out);
+
+                let pan = (voice.pan.tick() + voice.pan_offset).clamp(0.0, 1.0);
+                let panned = StereoFrame::panned(ch_out, pan);
+                let dry_routed = voice.fx_bypass.load(Ordering::Relaxed) != 0;
+                match (ch == INSTRUMENT_BASS as usize, dry_routed) {
+                    (true, false) => bass_frame += panned,
+                    (true, true) => bass_dry += panned,
+                    (false, false) => kit_frame += panned,
+                    (false, true) => kit_dry += panned,
                 }
 
                 // Track per-voice peak for UI metering (pre-pan mono level)
                 voice.record_peak(ch_out.abs());
             }
+            let fade_step = 1000.0 / (RETIRE_FADE_MS * self.sample_rate);
+            for (ch, tails) in self.retiring.iter_mut().enumerate() {
+                for entry in tails.iter_mut() {
+                    let Some(retiring) = entry.as_mut() else {
+                        continue;
+                    };
+                    match retiring.tick(time, fade_step) {
+                        Some(tail) if ch == INSTRUMENT_BASS as usize => bass_frame += tail,
+                        Some(tail) => kit_frame += tail,
+                        None => {
+                            if let Some(done) = entry.take() {
+                                hand_back(&self.control.spent_tx, done);
+                            }
+                        }
+                    }
+                }
+            }
 
             // Poly synth and granulator have no pan control yet — center them
             // for consistency with the equal-power law used above.
@@ -1306,31 +3951,67 @@ impl GooeyEngine {
             for (rack, frame) in sampler_frames.into_iter().enumerate() {
                 self.graph.scatter(SOURCE_SAMPLER_BASE + rack as u32, frame);
             }
-            let mut stereo = self.graph.mix_down();
+            self.graph.scatter_dry(SOURCE_DRUMKIT, kit_dry);
+            self.graph.scatter_dry(SOURCE_BASS, bass_dry);
+            let (mut stereo, dry) = self.graph.mix_down_split();
 
             // Apply master headroom to the full mix (instruments + loops) before
             // the optional global effects + limiter, so the master fader scales
             // loops too.
-            stereo = stereo.scaled(self.master_gain.tick());
+            let master_gain = self.master_gain.tick();
+            stereo = stereo.scaled(master_gain);
+            // The dry-routed sum rejoins the mix after the last master stage
+            // any bypassing voice skips (see `fx_bypass_join`).
+            let dry = dry.scaled(master_gain);
+            let mut stage = 0;
+            let mut join = |stereo: &mut StereoFrame| {
+                if dry_join == Some(stage) {
+                    *stereo += dry;
+                }
+                stage += 1;
+            };
+
+            // A master-sourced beat repeat leads the chain, so the effects
+            // below (delay, reverb, ...) treat the repeats like live input.
+            join(&mut stereo);
+            if beat_repeat_enabled && self.beat_repeat_source == BEAT_REPEAT_SOURCE_MASTER {
+                stereo = self.beat_repeat.process_stereo(stereo);
+            }
+
+            // The room sits ahead of the chain, like room mics on the drum
+            // bus: saturation and compression act on the drums in the space.
+            join(&mut stereo);
+            if self.early_reflections_enabled.load(Ordering::Relaxed) {
+                stereo = self.early_reflections.process_stereo(stereo);
+            }
 
             // Apply global effects chain (order is user-configurable; limiter is always last)
             for &effect_id in &self.effect_order {
+                join(&mut stereo);
                 match effect_id {
-                    EFFECT_SATURATION if self.saturation_enabled => {
-                        stereo = self.saturation.process_stereo(stereo);
+                    EFFECT_SATURATION if self.saturation_enabled.load(Ordering::Relaxed) => {
+                        stereo = if self.saturation_model.load(Ordering::Relaxed)
+                            == SATURATOR_MODEL_TUBE
+                        {
+                            self.saturation.process_stereo(stereo)
+                        } else {
+                            self.saturator.process_stereo(stereo)
+                        };
                     }
-                    EFFECT_LOWPASS_FILTER if self.lowpass_filter_enabled => {
+                    EFFECT_LOWPASS_FILTER
+                        if self.lowpass_filter_enabled.load(Ordering::Relaxed) =>
+                    {
                         stereo = self.lowpass_filter.process_stereo(stereo);
                     }
-                    EFFECT_TILT_FILTER if self.tilt_filter_enabled => {
+                    EFFECT_TILT_FILTER if self.tilt_filter_enabled.load(Ordering::Relaxed) => {
                         stereo = self.tilt_filter.process_stereo(stereo);
                     }
-                    EFFECT_DELAY if self.delay_enabled => {
+                    EFFECT_DELAY if self.delay_enabled.load(Ordering::Relaxed) => {
                         stereo = self.delay.process_stereo(stereo);
                     }
-                    EFFECT_COMPRESSOR if self.compressor_enabled => {
+                    EFFECT_COMPRESSOR if self.compressor_enabled.load(Ordering::Relaxed) => {
                         let sc = self.compressor_sidechain as usize;
-                        stereo = if sc < NUM_INSTRUMENTS {
+                        stereo = if sc < NUM_CHANNELS {
                             // The sidechain source is a mono per-instrument
                             // sample; feed it to both detectors equally.
                             self.compressor.process_stereo_with_sidechain(
@@ -1341,42 +4022,85 @@ impl GooeyEngine {
                             self.compressor.process_stereo(stereo)
                         };
                     }
-                    EFFECT_WAVESHAPER if self.waveshaper_enabled => {
+                    EFFECT_WAVESHAPER if self.waveshaper_enabled.load(Ordering::Relaxed) => {
                         stereo = StereoFrame {
                             l: self.waveshaper.process(stereo.l),
                             r: self.waveshaper.process(stereo.r),
                         };
                     }
-                    EFFECT_FEEDBACK_WAVESHAPER if self.feedback_waveshaper_enabled => {
+                    EFFECT_FEEDBACK_WAVESHAPER
+                        if self.feedback_waveshaper_enabled.load(Ordering::Relaxed) =>
+                    {
                         stereo = StereoFrame {
                             l: self.feedback_waveshaper.process(stereo.l),
                             r: self.feedback_waveshaper.process(stereo.r),
                         };
                     }
-                    EFFECT_REVERB if self.reverb_enabled => {
+                    EFFECT_REVERB
+                        if self.reverb_enabled.load(Ordering::Relaxed) && !reverb_shed =>
+                    {
                         stereo = self.reverb.process_stereo(stereo);
                     }
-                    EFFECT_PLATE_REVERB if self.plate_reverb_enabled => {
+                    EFFECT_PLATE_REVERB
+                        if self.plate_reverb_enabled.load(Ordering::Relaxed) && !reverb_shed =>
+                    {
                         stereo = self.plate_reverb.process_stereo(stereo);
                     }
                     _ => {}
                 }
             }
 
+            // Trigger-driven ducking sits after the reorderable chain so it
+            // pumps delay and reverb tails along with the dry mix.
+            join(&mut stereo);
+            if self.ducker_enabled.load(Ordering::Relaxed) {
+                stereo = self.ducker.process_stereo(stereo);
+            }
+
+            // Width is set on the finished mix, so the correlation meter
+            // reads what the limiter (and a mono speaker) will get.
+            join(&mut stereo);
+            if self.widener_enabled.load(Ordering::Relaxed) {
+                stereo = self.widener.process_stereo(stereo);
+            }
+            join(&mut stereo);
+
             // Optional limiter (always last when enabled)
-            let stereo = if self.limiter_enabled {
+            let stereo = if self.limiter_enabled.load(Ordering::Relaxed) {
                 self.limiter.process_stereo(stereo)
             } else {
                 stereo
             };
 
+            // Preview voices join after the effects and limiter, so an
+            // audition sounds the same whatever the kit is doing.
+            let mut preview = 0.0;
+            for voice in &mut self.preview_voices {
+                if voice.is_active() {
+                    preview += voice.tick(time);
+                }
+            }
+            let preview_gain = self.preview_gain.tick();
+            let mut stereo = stereo;
+            stereo += StereoFrame::panned(preview * preview_gain * master_gain, 0.5);
+
+            // Safety stage: not bypassable, so the recorder and the host
+            // never see DC, overs past the ceiling or a runaway.
+            let stereo = self.output_safety.process_stereo(stereo);
+            let stereo = match self.panic_fade {
+                Some(_) => stereo.scaled(self.tick_panic()),
+                None => stereo,
+            };
+
+            self.recorder.push_frame(stereo);
+
             // Write the frame interleaved as [left, right].
             frame[0] = stereo.l;
             if let Some(right) = frame.get_mut(1) {
                 *right = stereo.r;
             }
 
-            self.current_time += sample_period;
+            self.clock.advance();
             sample_offset += 1;
         }
     }
@@ -1394,3836 +4118,10687 @@ impl GooeyEngine {
         let idx = channel as usize;
         if let Some((x, y)) = self
             .voice(idx)
-            .filter(|v| v.blend_enabled)
+            .filter(|v| v.blend_enabled.load(Ordering::Relaxed))
             .map(|v| (v.blend_x, v.blend_y))
         {
             self.apply_blend_position(channel, x, y);
         }
     }
 
-    fn apply_blend_position(&mut self, channel: u32, x: f32, y: f32) {
-        let x = x.clamp(0.0, 1.0);
-        let y = y.clamp(0.0, 1.0);
-        let idx = channel as usize;
-        if let Some(voice) = self.voice_mut(idx) {
-            voice.blender.blend_and_apply(&mut voice.instrument, x, y);
+    /// The key sequenced notes are snapped to, if quantization is on.
+    fn scale_quantize(&self) -> Option<(NoteName, Scale)> {
+        let packed = self.scale_quantize.load(Ordering::Relaxed);
+        if packed == SCALE_QUANTIZE_OFF {
+            return None;
         }
+        let scale = Scale::ALL.get((packed >> 8) as usize)?;
+        Some((root_from_id(packed & 0xFF), *scale))
     }
 
-    /// Clear internal state of all reorderable effects so a new chain order
-    /// does not inherit stale buffers/envelopes from the previous routing.
-    /// Limiter is intentionally skipped — keeping its gain-reduction state
-    /// stable avoids transients if the optional final stage is enabled.
-    fn reset_effect_states(&self) {
-        self.saturation.reset();
-        self.lowpass_filter.reset();
-        self.tilt_filter.reset();
-        self.delay.reset();
-        self.compressor.reset();
-        self.reverb.reset();
-        self.plate_reverb.reset();
-    }
-
-    /// Apply LFO modulation to a channel's instrument parameter by index
-    fn apply_modulation_by_index(&mut self, channel: u32, param: u32, value: f32) {
-        if let Some(voice) = self.voice_mut(channel as usize) {
-            voice.instrument.apply_modulation(param, value);
+    /// Apply a parameter write, or queue it for the audio thread when called
+    /// from a control thread. See the threading notes on [`GooeyEngine`].
+    fn submit(&mut self, function: &str, command: ControlCommand) -> GooeyResult {
+        if !self.control.is_control_thread() {
+            self.apply_control(command);
+            return GooeyResult::Ok;
         }
-    }
-
-    /// Calculate the target gain for an instrument based on mute/solo state
-    /// Returns 1.0 (full volume) or 0.0 (silent)
-    #[inline]
-    fn calculate_instrument_gain(muted: bool, soloed: bool, any_soloed: bool) -> f32 {
-        // Solo takes precedence: if this instrument is soloed, it plays
-        if soloed {
-            return 1.0;
+        if self.control.push(command) {
+            GooeyResult::Ok
+        } else {
+            fail(
+                GooeyResult::QueueFull,
+                format!("{function}: control queue is full; render to drain it"),
+            )
         }
+    }
 
-        // If any instrument is soloed but this one isn't, silence it
-        if any_soloed {
-            return 0.0;
+    /// [`submit`](Self::submit) for a group of writes that must land in the
+    /// same render.
+    fn submit_batch(&mut self, function: &str, commands: &[ControlCommand]) -> GooeyResult {
+        if !self.control.is_control_thread() {
+            for command in commands {
+                self.apply_control(*command);
+            }
+            return GooeyResult::Ok;
         }
-
-        // No solo active: check mute state
-        if muted {
-            return 0.0;
+        if self.control.push_batch(commands) {
+            GooeyResult::Ok
+        } else {
+            fail(
+                GooeyResult::QueueFull,
+                format!(
+                    "{function}: control queue has no room for {} updates; render to drain it",
+                    commands.len()
+                ),
+            )
         }
-
-        // Not muted, not affected by solo: full volume
-        1.0
     }
 
-    /// Borrow the first voice's instrument matching the given type.
-    fn instrument_by_type(&self, instrument_type: u32) -> Option<&ChannelInstrument> {
-        self.voices_iter()
-            .map(|v| &v.instrument)
-            .find(|i| i.instrument_type() == instrument_type)
+    /// Apply queued control writes, holding them back for a render if a batch
+    /// was being queued meanwhile (see [`ControlQueue`]).
+    fn drain_control(&mut self) {
+        let epoch = self.control.batch_epoch();
+        while let Some(command) = self.control.pop() {
+            self.control_stash.push(command);
+        }
+        let settled = epoch.is_multiple_of(2) && epoch == self.control.batch_epoch();
+        // The stash never grows past its preallocated capacity: a producer
+        // that keeps a batch open that long loses atomicity, not real time.
+        if settled || self.control_stash.len() >= CONTROL_QUEUE_CAPACITY {
+            let mut stash = std::mem::take(&mut self.control_stash);
+            for command in stash.drain(..) {
+                self.apply_control(command);
+            }
+            self.control_stash = stash;
+        }
     }
 
-    /// Mutable counterpart to [`instrument_by_type`](Self::instrument_by_type).
-    fn instrument_by_type_mut(&mut self, instrument_type: u32) -> Option<&mut ChannelInstrument> {
-        self.voices_iter_mut()
-            .map(|v| &mut v.instrument)
-            .find(|i| i.instrument_type() == instrument_type)
+    /// Hand `voice`, a tail just swapped out of `channel`, to the audio
+    /// thread to fade out. Tails it has finished with are freed by [`edit`].
+    fn retire(&mut self, channel: usize, voice: Box<RetiringVoice>) {
+        if self.control.is_control_thread() {
+            // Full only after dozens of swaps inside one buffer; the extra
+            // tail is cut instead of faded.
+            let _ = self.control.retire_tx.try_send((channel, voice));
+        } else {
+            self.accept_retiring(channel, voice);
+        }
     }
 
-    /// Get a KickConfig preset by ID
-    fn kick_preset_by_id(id: u32) -> Option<KickConfig> {
-        match id {
-            KICK_PRESET_TIGHT => Some(KickConfig::tight()),
-            KICK_PRESET_PUNCH => Some(KickConfig::punch()),
-            KICK_PRESET_LOOSE => Some(KickConfig::loose()),
-            KICK_PRESET_DIRT => Some(KickConfig::dirt()),
-            _ => None,
+    /// Move instruments retired by control threads into their channels'
+    /// fade-out lists. Audio thread.
+    fn take_retiring(&mut self) {
+        while let Ok((channel, voice)) = self.control.retire_rx.try_recv() {
+            self.accept_retiring(channel, voice);
         }
     }
 
-    /// Get a Tom2Config preset by ID
-    fn tom_preset_by_id(id: u32) -> Option<Tom2Config> {
-        match id {
-            TOM_PRESET_DERP => Some(Tom2Config::derp()),
-            TOM_PRESET_RING => Some(Tom2Config::ring()),
-            TOM_PRESET_BRUSH => Some(Tom2Config::brush()),
-            TOM_PRESET_VOID => Some(Tom2Config::void_preset()),
-            _ => None,
+    /// Start fading `voice` on `channel`. When every slot on the channel is
+    /// busy, the quietest tail is cut to make room.
+    fn accept_retiring(&mut self, channel: usize, voice: Box<RetiringVoice>) {
+        let spent = &self.control.spent_tx;
+        let Some(tails) = self.retiring.get_mut(channel) else {
+            return hand_back(spent, voice);
+        };
+        let slot = tails.iter().position(Option::is_none).unwrap_or_else(|| {
+            (0..RETIRE_SLOTS)
+                .min_by(|&a, &b| {
+                    let fade = |i: usize| tails[i].as_ref().map_or(0.0, |tail| tail.fade);
+                    fade(a).total_cmp(&fade(b))
+                })
+                .unwrap_or(0)
+        });
+        if let Some(cut) = tails[slot].replace(voice) {
+            hand_back(spent, cut);
         }
     }
 
-    /// Get a BassConfig preset by ID
-    fn bass_preset_by_id(id: u32) -> Option<BassConfig> {
-        match id {
-            BASS_PRESET_ACID => Some(BassConfig::acid()),
-            BASS_PRESET_SUB => Some(BassConfig::sub()),
-            BASS_PRESET_REESE => Some(BassConfig::reese()),
-            BASS_PRESET_STAB => Some(BassConfig::stab()),
-            _ => None,
+    /// Apply parameter table slots the host changed since the last render.
+    fn poll_param_table(&mut self) {
+        for slot in 0..self.param_table.len() {
+            if let Some((instrument_type, param, value)) = self.param_table.poll_slot(slot) {
+                self.apply_control(ControlCommand::InstrumentParam {
+                    instrument_type,
+                    param,
+                    value,
+                });
+            }
         }
     }
 
-    /// Convert a MIDI note number to a normalized frequency value for an instrument's range.
-    fn midi_note_to_normalized_freq(note: u8, freq_min: f32, freq_max: f32) -> f32 {
-        let hz = 440.0 * 2f32.powf((note as f32 - 69.0) / 12.0);
-        ((hz - freq_min) / (freq_max - freq_min)).clamp(0.0, 1.0)
+    /// Log a parameter write, stamped with the next frame to render.
+    fn trace_param(&self, channel: u32, param: u32, value: f32) {
+        self.trace.record(
+            self.rendered_frames,
+            TraceEvent::Param {
+                channel,
+                param,
+                value,
+            },
+        );
     }
 
-    /// Get the frequency range (min, max) for a pitched instrument type.
-    fn freq_range_for_instrument(instrument_type: u32) -> Option<(f32, f32)> {
-        match instrument_type {
-            INSTRUMENT_BASS => Some((30.0, 200.0)),
-            INSTRUMENT_KICK => Some((30.0, 120.0)),
-            INSTRUMENT_TOM => Some((40.0, 600.0)),
-            _ => None,
+    fn apply_control(&mut self, command: ControlCommand) {
+        let sample_rate = self.sample_rate;
+        match command {
+            ControlCommand::ChannelParam {
+                channel,
+                param,
+                value,
+            } => {
+                if let Some(voice) = self.voice_mut(channel as usize) {
+                    voice.finish_config_fade();
+                    voice.instrument.set_param(param, value);
+                    self.trace_param(channel, param, value);
+                    self.record_motion(channel, param, value);
+                }
+            }
+            ControlCommand::InstrumentParam {
+                instrument_type,
+                param,
+                value,
+            } => {
+                if let Some(voice) = self
+                    .voices_iter_mut()
+                    .find(|v| v.instrument.instrument_type() == instrument_type)
+                {
+                    voice.finish_config_fade();
+                    voice.instrument.set_param(param, value);
+                }
+                if TraceLog::ENABLED || self.motion_recording.load(Ordering::Relaxed) {
+                    let channel = (0..NUM_CHANNELS).find(|&ch| {
+                        self.voice(ch)
+                            .is_some_and(|v| v.instrument.instrument_type() == instrument_type)
+                    });
+                    if let Some(channel) = channel {
+                        self.trace_param(channel as u32, param, value);
+                        self.record_motion(channel as u32, param, value);
+                    }
+                }
+            }
+            ControlCommand::GlobalEffectParam {
+                effect,
+                param,
+                value,
+            } => self.apply_global_effect_param(effect, param, value),
+            ControlCommand::SaturatorModel { channel, model } => {
+                if let Some(voice) = self.voice_mut(channel as usize) {
+                    voice.instrument.set_saturator_model(model);
+                }
+            }
+            ControlCommand::OverdriveAutoGain { channel, enabled } => {
+                if let Some(voice) = self.voice_mut(channel as usize) {
+                    voice.instrument.set_overdrive_auto_gain(enabled);
+                }
+            }
+            ControlCommand::BlendPosition { channel, x, y } => {
+                if let Some(voice) = self.voice_mut(channel as usize) {
+                    voice.blend_x = x;
+                    voice.blend_y = y;
+                    voice.apply_blend(x, y, sample_rate);
+                }
+            }
+            ControlCommand::Randomize {
+                channel,
+                amount,
+                seed,
+            } => {
+                if let Some(voice) = self.voice_mut(channel as usize) {
+                    voice.finish_config_fade();
+                    voice.instrument.randomize(amount, seed);
+                }
+            }
+            ControlCommand::MasterGain(gain) => self.master_gain.set_target(gain),
+            ControlCommand::PreviewTrigger { config, velocity } => {
+                let time = self.clock.seconds();
+                if let Some(voice) = self
+                    .preview_voices
+                    .get_mut(config.instrument_type() as usize)
+                {
+                    voice.set_config(config);
+                    voice.snap_params();
+                    voice.trigger_articulated(time, velocity, None);
+                }
+            }
+            ControlCommand::PreviewRelease => {
+                let time = self.clock.seconds();
+                for voice in &mut self.preview_voices {
+                    voice.release(time);
+                }
+            }
+            ControlCommand::PreviewGain(gain) => self.preview_gain.set_target(gain),
+            ControlCommand::ChannelGain { channel, gain } => {
+                if let Some(voice) = self.voice_mut(channel as usize) {
+                    voice.channel_gain.set_target(gain);
+                }
+            }
+            ControlCommand::ChannelPan { channel, pan } => {
+                if let Some(voice) = self.voice_mut(channel as usize) {
+                    voice.pan.set_target(pan);
+                }
+            }
         }
     }
 
-    /// Get a SnareConfig preset by ID
-    fn snare_preset_by_id(id: u32) -> Option<SnareConfig> {
-        match id {
-            SNARE_PRESET_TIGHT => Some(SnareConfig::tight()),
-            SNARE_PRESET_LOOSE => Some(SnareConfig::loose()),
-            SNARE_PRESET_HISS => Some(SnareConfig::hiss()),
-            SNARE_PRESET_SMACK => Some(SnareConfig::smack()),
-            _ => None,
-        }
+    /// Master stages a channel can bypass, in processing order.
+    fn master_stages(&self) -> impl Iterator<Item = u32> + '_ {
+        [EFFECT_BEAT_REPEAT, EFFECT_EARLY_REFLECTIONS]
+            .into_iter()
+            .chain(self.effect_order.iter().copied())
+            .chain([EFFECT_DUCKER, EFFECT_WIDENER])
     }
 
-    /// Get a HiHat2Config preset by ID
-    fn hihat_preset_by_id(id: u32) -> Option<HiHat2Config> {
-        match id {
-            HIHAT_PRESET_SHORT => Some(HiHat2Config::short()),
-            HIHAT_PRESET_LOOSE => Some(HiHat2Config::loose()),
-            HIHAT_PRESET_DARK => Some(HiHat2Config::dark()),
-            HIHAT_PRESET_SOFT => Some(HiHat2Config::soft()),
-            _ => None,
+    /// Where the dry-routed sum rejoins the master path: the index into
+    /// [`master_stages`](Self::master_stages) just after the last stage any
+    /// voice bypasses, or `None` while no voice bypasses anything. One sum
+    /// serves every bypassing voice, so each also skips the stages before
+    /// that point.
+    fn fx_bypass_join(&self) -> Option<usize> {
+        let bypassed = self.voices_iter().fold(0, |mask, voice| {
+            mask | voice.fx_bypass.load(Ordering::Relaxed)
+        });
+        self.master_stages()
+            .enumerate()
+            .filter(|&(_, effect)| bypassed & (1 << effect) != 0)
+            .map(|(stage, _)| stage + 1)
+            .last()
+    }
+
+    /// Write each effect lane's value at the transport's step position to
+    /// its parameter, when it has changed. While stopped nothing is written,
+    /// and the next start writes every lane afresh. A motion lane leaves its
+    /// parameter alone for the rest of a step it is recording, so the knob
+    /// being turned is what plays.
+    fn advance_effect_lanes(&mut self) {
+        let position = self
+            .reference_sequencer()
+            .filter(|seq| seq.is_running())
+            .map(Sequencer::step_position);
+        for index in 0..self.effect_lanes.len() {
+            let Some(slot) = &mut self.effect_lanes[index] else {
+                continue;
+            };
+            let Some(value) = position.and_then(|position| slot.lane.value_at(position)) else {
+                slot.last = None;
+                slot.held = None;
+                continue;
+            };
+            if let Some(held) = slot.held {
+                if position.map(effect_lane_step) == Some(held) {
+                    continue;
+                }
+                slot.held = None;
+            }
+            if slot.last == Some(value) {
+                continue;
+            }
+            slot.last = Some(value);
+            match slot.target {
+                EffectLaneTarget::Global { effect, param } => {
+                    self.apply_global_effect_param(effect, param, value)
+                }
+                EffectLaneTarget::Track { track, slot, param } => {
+                    self.graph
+                        .effect_set_param(track as usize, slot as usize, param, value)
+                }
+                EffectLaneTarget::Instrument { channel, param } => {
+                    if let Some(voice) = self.voice_mut(channel as usize) {
+                        voice.instrument.set_param(param, value);
+                    }
+                }
+            }
         }
     }
-}
 
-// =============================================================================
-// Global effect IDs (must match Swift GlobalEffect enum)
-// =============================================================================
+    /// Write an instrument parameter change into its motion lane at the
+    /// step playing, creating the lane on the first change. Does nothing
+    /// unless motion recording is on and the transport is running, or when
+    /// every lane is in use.
+    fn record_motion(&mut self, channel: u32, param: u32, value: f32) {
+        if !self.motion_recording.load(Ordering::Relaxed) {
+            return;
+        }
+        let Some(step) = self
+            .reference_sequencer()
+            .filter(|seq| seq.is_running())
+            .map(|seq| effect_lane_step(seq.step_position()))
+        else {
+            return;
+        };
+        let target = EffectLaneTarget::Instrument { channel, param };
+        let Some(index) = self.motion_lane(channel, param).or_else(|| {
+            let index = self.add_effect_lane(target)?;
+            self.effect_lane_mut(index as u32)?.set_interpolate(true);
+            Some(index)
+        }) else {
+            return;
+        };
+        if let Some(slot) = &mut self.effect_lanes[index] {
+            slot.lane.set_step(step, value);
+            slot.last = Some(value);
+            slot.held = Some(step);
+        }
+    }
 
-/// Global effect: Lowpass filter
-pub const EFFECT_LOWPASS_FILTER: u32 = 0;
-/// Global effect: Delay
-pub const EFFECT_DELAY: u32 = 1;
-/// Global effect: Saturation
-pub const EFFECT_SATURATION: u32 = 2;
-/// Global effect: Compressor
-pub const EFFECT_COMPRESSOR: u32 = 3;
-/// Global effect: Tilt filter (unified lowpass/highpass)
-pub const EFFECT_TILT_FILTER: u32 = 4;
-/// Global effect: Optional soft limiter
-pub const EFFECT_LIMITER: u32 = 5;
+    /// Index of the motion lane for a channel's parameter, if it has one.
+    fn motion_lane(&self, channel: u32, param: u32) -> Option<usize> {
+        self.effect_lanes.iter().position(|slot| {
+            matches!(
+                slot,
+                Some(EffectLaneSlot {
+                    target: EffectLaneTarget::Instrument { channel: c, param: p },
+                    ..
+                }) if *c == channel && *p == param
+            )
+        })
+    }
 
-// =============================================================================
-// Limiter parameter indices (must match Swift LimiterParam enum)
-// =============================================================================
+    /// Set a global effect parameter. `effect` and `param` are validated by
+    /// `gooey_engine_set_global_effect_param` before this runs.
+    fn apply_global_effect_param(&mut self, effect: u32, param: u32, value: f32) {
+        match effect {
+            EFFECT_LOWPASS_FILTER => match param {
+                FILTER_PARAM_CUTOFF => self.lowpass_filter.set_cutoff_freq(value),
+                FILTER_PARAM_RESONANCE => self.lowpass_filter.set_resonance(value),
+                _ => {}
+            },
+            EFFECT_DELAY => match param {
+                DELAY_PARAM_TIMING => {
+                    if let Some(timing) = DelayTiming::from_timing_constant(value as u32) {
+                        self.delay.set_timing(timing);
+                    }
+                }
+                DELAY_PARAM_FEEDBACK => self.delay.set_feedback(value),
+                DELAY_PARAM_MIX => self.delay.set_mix(value),
+                DELAY_PARAM_FILTER_CUTOFF => self.delay.set_filter_cutoff(value),
+                DELAY_PARAM_PINGPONG => self.delay.set_pingpong(value >= 0.5),
+                DELAY_PARAM_LOW_CUT => self.delay.set_low_cut(value),
+                DELAY_PARAM_FREEZE => self.delay.set_freeze(value >= 0.5),
+                _ => {}
+            },
+            EFFECT_SATURATION => match param {
+                SATURATION_PARAM_DRIVE => {
+                    self.saturation.set_drive(value);
+                    self.saturator.set_drive(value);
+                }
+                SATURATION_PARAM_WARMTH => self.saturation.set_warmth(value),
+                SATURATION_PARAM_MIX => {
+                    self.saturation.set_mix(value);
+                    self.saturator.set_mix(value);
+                }
+                SATURATION_PARAM_MODEL => {
+                    if let Some(model) = SaturatorModel::from_u32(value as u32) {
+                        if model != SaturatorModel::Tube {
+                            self.saturator.set_model(model);
+                        }
+                        self.saturation_model
+                            .store(model.as_u32(), Ordering::Relaxed);
+                    }
+                }
+                SATURATION_PARAM_AUTO_GAIN => {
+                    self.saturation.set_auto_gain(value >= 0.5);
+                    self.saturator.set_auto_gain(value >= 0.5);
+                }
+                _ => {}
+            },
+            EFFECT_COMPRESSOR => match param {
+                COMPRESSOR_PARAM_THRESHOLD => self.compressor.set_threshold(value),
+                COMPRESSOR_PARAM_RATIO => self.compressor.set_ratio(value),
+                COMPRESSOR_PARAM_ATTACK => self.compressor.set_attack(value),
+                COMPRESSOR_PARAM_RELEASE => self.compressor.set_release(value),
+                COMPRESSOR_PARAM_MIX => self.compressor.set_mix(value),
+                _ => {}
+            },
+            EFFECT_TILT_FILTER => match param {
+                TILT_PARAM_CUTOFF => self.tilt_filter.set_cutoff(value),
+                TILT_PARAM_RESONANCE => self.tilt_filter.set_resonance(value),
+                _ => {}
+            },
+            EFFECT_WAVESHAPER => match param {
+                WAVESHAPER_PARAM_DRIVE => self.waveshaper.set_drive(value),
+                WAVESHAPER_PARAM_MIX => self.waveshaper.set_mix(value),
+                _ => {}
+            },
+            EFFECT_FEEDBACK_WAVESHAPER => match param {
+                FEEDBACK_WAVESHAPER_PARAM_DRIVE => self.feedback_waveshaper.set_drive(value),
+                FEEDBACK_WAVESHAPER_PARAM_FEEDBACK => self.feedback_waveshaper.set_feedback(value),
+                FEEDBACK_WAVESHAPER_PARAM_FILTER_CUTOFF => {
+                    self.feedback_waveshaper.set_filter_cutoff(value)
+                }
+                FEEDBACK_WAVESHAPER_PARAM_MIX => self.feedback_waveshaper.set_mix(value),
+                _ => {}
+            },
+            EFFECT_REVERB => match param {
+                REVERB_PARAM_DECAY => self.reverb.set_decay(value),
+                REVERB_PARAM_MIX => self.reverb.set_mix(value),
+                REVERB_PARAM_DAMPING => self.reverb.set_damping(value),
+                _ => {}
+            },
+            EFFECT_PLATE_REVERB => match param {
+                PLATE_PARAM_DECAY => self.plate_reverb.set_decay(value),
+                PLATE_PARAM_MIX => self.plate_reverb.set_mix(value),
+                PLATE_PARAM_DAMPING => self.plate_reverb.set_damping(value),
+                PLATE_PARAM_PREDELAY => self.plate_reverb.set_predelay(value),
+                PLATE_PARAM_WIDTH => self.plate_reverb.set_width(value),
+                PLATE_PARAM_SIZE => self.plate_reverb.set_size(value),
+                _ => {}
+            },
+            EFFECT_BEAT_REPEAT => match param {
+                BEAT_REPEAT_PARAM_SLICE => {
+                    if let Some(timing) = DelayTiming::from_timing_constant(value as u32) {
+                        self.beat_repeat.set_timing(timing);
+                    }
+                }
+                BEAT_REPEAT_PARAM_LENGTH => self.beat_repeat.set_length(value),
+                BEAT_REPEAT_PARAM_PITCH => self.beat_repeat.set_pitch(value),
+                BEAT_REPEAT_PARAM_REVERSE => self.beat_repeat.set_reverse(value >= 0.5),
+                BEAT_REPEAT_PARAM_MIX => self.beat_repeat.set_mix(value),
+                _ => {}
+            },
+            EFFECT_EARLY_REFLECTIONS => match param {
+                EARLY_REFLECTIONS_PARAM_SIZE => self.early_reflections.set_size(value),
+                EARLY_REFLECTIONS_PARAM_DAMPING => self.early_reflections.set_damping(value),
+                EARLY_REFLECTIONS_PARAM_MIX => self.early_reflections.set_mix(value),
+                _ => {}
+            },
+            EFFECT_WIDENER => match param {
+                WIDENER_PARAM_WIDTH => self.widener.set_width(value),
+                WIDENER_PARAM_DELAY => self.widener.set_delay(value),
+                WIDENER_PARAM_CROSSOVER => self.widener.set_crossover(value),
+                _ => {}
+            },
+            EFFECT_DUCKER => match param {
+                DUCKER_PARAM_DEPTH => self.ducker.set_depth(value),
+                DUCKER_PARAM_ATTACK => self.ducker.set_attack(value),
+                DUCKER_PARAM_HOLD => self.ducker.set_hold(value),
+                DUCKER_PARAM_RELEASE => self.ducker.set_release(value),
+                _ => {}
+            },
+            EFFECT_LIMITER if param == LIMITER_PARAM_THRESHOLD => self.limiter.set_threshold(value),
+            _ => {}
+        }
+    }
 
-/// Limiter parameter: threshold (0.001-1.0)
-pub const LIMITER_PARAM_THRESHOLD: u32 = 0;
-/// Global effect: Spring reverb
-pub const EFFECT_REVERB: u32 = 6;
-/// Global effect: Waveshaper (tanh soft-clip distortion)
-pub const EFFECT_WAVESHAPER: u32 = 7;
-/// Global effect: Feedback waveshaper (self-exciting distortion)
-pub const EFFECT_FEEDBACK_WAVESHAPER: u32 = 8;
-/// Global effect: Plate reverb (Dattorro figure-eight tank)
-pub const EFFECT_PLATE_REVERB: u32 = 9;
-/// Total number of global effects
-pub const EFFECT_COUNT: u32 = 10;
+    fn apply_blend_position(&mut self, channel: u32, x: f32, y: f32) {
+        let x = x.clamp(0.0, 1.0);
+        let y = y.clamp(0.0, 1.0);
+        let idx = channel as usize;
+        let sample_rate = self.sample_rate;
+        if let Some(voice) = self.voice_mut(idx) {
+            voice.apply_blend(x, y, sample_rate);
+        }
+    }
 
-/// Number of reorderable effects in the chain. Excludes the optional limiter,
-/// which is pinned at the end of the chain when enabled.
-pub const REORDERABLE_EFFECT_COUNT: u32 = 9;
+    /// Clear internal state of all reorderable effects so a new chain order
+    /// does not inherit stale buffers/envelopes from the previous routing.
+    /// Limiter is intentionally skipped — keeping its gain-reduction state
+    /// stable avoids transients if the optional final stage is enabled.
+    fn reset_effect_states(&self) {
+        self.saturation.reset();
+        self.saturator.reset();
+        self.lowpass_filter.reset();
+        self.tilt_filter.reset();
+        self.delay.reset();
+        self.compressor.reset();
+        self.reverb.reset();
+        self.plate_reverb.reset();
+    }
 
-/// Default order for the reorderable effects, matching the historical
-/// hardcoded chain (saturation -> lowpass -> tilt -> delay -> compressor -> reverb).
-const DEFAULT_EFFECT_ORDER: [u32; REORDERABLE_EFFECT_COUNT as usize] = [
-    EFFECT_WAVESHAPER,
-    EFFECT_SATURATION,
-    EFFECT_LOWPASS_FILTER,
-    EFFECT_TILT_FILTER,
-    EFFECT_DELAY,
-    EFFECT_COMPRESSOR,
-    EFFECT_FEEDBACK_WAVESHAPER,
-    EFFECT_REVERB,
-    EFFECT_PLATE_REVERB,
-];
+    /// Samples in each half of a panic's fade.
+    fn panic_fade_len(&self) -> u32 {
+        ((PANIC_FADE_MS * 0.001 * self.sample_rate) as u32).max(1)
+    }
 
-// =============================================================================
-// Lowpass filter parameter indices (must match Swift FilterParam enum)
-// =============================================================================
+    /// Advance a running panic by one sample and return the master gain for
+    /// it. Clears everything on the first silent sample.
+    fn tick_panic(&mut self) -> f32 {
+        let len = self.panic_fade_len();
+        match self.panic_fade {
+            Some(PanicFade::Out(left)) if left > 1 => {
+                self.panic_fade = Some(PanicFade::Out(left - 1));
+                (left - 1) as f32 / len as f32
+            }
+            Some(PanicFade::Out(_)) => {
+                self.silence_all();
+                self.panic_fade = Some(PanicFade::In(len));
+                0.0
+            }
+            Some(PanicFade::In(left)) => {
+                let left = left.saturating_sub(1);
+                self.panic_fade = (left > 0).then_some(PanicFade::In(left));
+                1.0 - left as f32 / len as f32
+            }
+            None => 1.0,
+        }
+    }
 
-/// Filter parameter: cutoff frequency (20-20000 Hz)
-pub const FILTER_PARAM_CUTOFF: u32 = 0;
-/// Filter parameter: resonance (0.0-0.95)
-pub const FILTER_PARAM_RESONANCE: u32 = 1;
+    /// Cut everything that is sounding: choke every voice, kill sampler,
+    /// poly synth and granulator voices, drop retiring tails and clear the
+    /// state of every effect (global, track racks and loop channels). Loops
+    /// keep playing. Audio thread only.
+    fn silence_all(&mut self) {
+        for voice in self.voices_iter_mut() {
+            voice.finish_config_fade();
+            voice.choked = true;
+        }
+        for entry in self.retiring.iter_mut().flatten() {
+            if let Some(tail) = entry.take() {
+                hand_back(&self.control.spent_tx, tail);
+            }
+        }
+        for rack in self.samplers.iter_mut().flatten() {
+            rack.stop_all();
+        }
+        self.poly_synth.kill_all();
+        self.granulator.kill_all_grains();
 
-// =============================================================================
-// Delay parameter indices (must match Swift DelayParam enum)
-// =============================================================================
+        self.reset_effect_states();
+        self.ducker.reset();
+        self.beat_repeat.reset();
+        self.early_reflections.reset();
+        self.widener.reset();
+        self.graph.reset_effects();
+        self.mixer.reset_effects();
+        self.output_safety.reset();
+    }
 
-/// Delay parameter: timing division (see DELAY_TIMING_* constants)
-pub const DELAY_PARAM_TIMING: u32 = 0;
-/// Delay parameter: feedback amount (0.0-0.95)
-pub const DELAY_PARAM_FEEDBACK: u32 = 1;
-/// Delay parameter: wet/dry mix (0.0-1.0)
-pub const DELAY_PARAM_MIX: u32 = 2;
-/// Delay parameter: filter cutoff in Hz (20-20000)
-pub const DELAY_PARAM_FILTER_CUTOFF: u32 = 3;
-/// Delay parameter: ping-pong mode (0.0 = off, >= 0.5 = on). When on, the delay
-/// feedback crosses channels so echoes bounce between left and right.
-pub const DELAY_PARAM_PINGPONG: u32 = 4;
+    /// Apply LFO modulation to a channel's instrument parameter by index
+    fn apply_modulation_by_index(&mut self, channel: u32, param: u32, value: f32) {
+        if let Some(voice) = self.voice_mut(channel as usize) {
+            voice.instrument.apply_modulation(param, value);
+        }
+    }
 
-// =============================================================================
-// Delay timing constants (must match Swift DelayTiming enum)
-// =============================================================================
+    /// Point each voice's mute/solo gain at its current mute/solo state.
+    fn update_mute_gain_targets(&mut self) {
+        let any_soloed = self.voices_iter().any(|v| v.soloed.load(Ordering::Relaxed));
+        for voice in self.voices_iter_mut() {
+            let target = Self::calculate_instrument_gain(
+                voice.muted.load(Ordering::Relaxed),
+                voice.soloed.load(Ordering::Relaxed),
+                any_soloed,
+            );
+            voice.mute_gain.set_target(target);
+        }
+    }
 
-/// Delay timing: whole note (4 beats)
-pub const DELAY_TIMING_WHOLE: u32 = 0;
-/// Delay timing: half note (2 beats)
-pub const DELAY_TIMING_HALF: u32 = 1;
-/// Delay timing: quarter note (1 beat)
-pub const DELAY_TIMING_QUARTER: u32 = 2;
-/// Delay timing: eighth note (1/2 beat)
-pub const DELAY_TIMING_EIGHTH: u32 = 3;
-/// Delay timing: sixteenth note (1/4 beat)
-pub const DELAY_TIMING_SIXTEENTH: u32 = 4;
-/// Delay timing: half note triplet (4/3 beats)
-pub const DELAY_TIMING_HALF_TRIPLET: u32 = 5;
-/// Delay timing: quarter note triplet (2/3 beat)
-pub const DELAY_TIMING_QUARTER_TRIPLET: u32 = 6;
-/// Delay timing: eighth note triplet (1/3 beat)
-pub const DELAY_TIMING_EIGHTH_TRIPLET: u32 = 7;
-/// Delay timing: sixteenth note triplet (1/6 beat)
-pub const DELAY_TIMING_SIXTEENTH_TRIPLET: u32 = 8;
+    /// Point each voice's group gain at the product of its groups' gains.
+    fn update_group_gain_targets(&mut self) {
+        let gains: [f32; GROUP_MAX as usize] = std::array::from_fn(|group| {
+            f32::from_bits(self.group_gains[group].load(Ordering::Relaxed))
+        });
+        for voice in self.voices_iter_mut() {
+            let groups = voice.groups.load(Ordering::Relaxed);
+            let target = gains
+                .iter()
+                .enumerate()
+                .filter(|(group, _)| groups & (1 << group) != 0)
+                .map(|(_, gain)| gain)
+                .product();
+            voice.group_gain.set_target(target);
+        }
+    }
 
-// =============================================================================
-// Saturation parameter indices (must match Swift SaturationParam enum)
-// =============================================================================
+    /// Channels tagged with `group`, in index order.
+    fn group_channels(&self, group: u32) -> impl Iterator<Item = u32> + '_ {
+        (0..CHANNEL_MAX).filter(move |&channel| {
+            self.voice(channel as usize)
+                .is_some_and(|v| v.groups.load(Ordering::Relaxed) & (1 << group) != 0)
+        })
+    }
 
-/// Saturation parameter: drive amount (0.0-1.0)
-pub const SATURATION_PARAM_DRIVE: u32 = 0;
-/// Saturation parameter: warmth/even harmonics (0.0-1.0)
-pub const SATURATION_PARAM_WARMTH: u32 = 1;
-/// Saturation parameter: wet/dry mix (0.0-1.0)
-pub const SATURATION_PARAM_MIX: u32 = 2;
+    /// Apply queued mute/solo requests once the transport reaches the next
+    /// boundary of the mute quantization grid. A stopped transport (or
+    /// quantization switched back to immediate) applies them at once.
+    fn apply_mode_requests_if_due(&mut self) {
+        let quantization = LaunchQuantization::from_id(self.mute_quantize.load(Ordering::Relaxed));
+        match quantization {
+            Some(quantization) if self.mixer.transport_running() => {
+                let beat = self.mixer.transport_beat();
+                // Re-target after a seek back leaves the boundary more than one
+                // grid interval away
+                let target = match self.mode_change_beat {
+                    Some(target) if target <= beat + quantization.beats() => target,
+                    _ => self.mixer.quantized_target(quantization),
+                };
+                if beat + 1.0e-8 < target {
+                    self.mode_change_beat = Some(target);
+                    return;
+                }
+            }
+            _ => {}
+        }
+        self.mode_change_beat = None;
+        // Cleared before the swaps so a request racing in keeps the flag set
+        // and waits for the following boundary.
+        self.mode_requests_pending.store(false, Ordering::Release);
+        for voice in self.voices_iter() {
+            voice.apply_mode_requests();
+        }
+        self.update_mute_gain_targets();
+    }
 
-// =============================================================================
-// Compressor parameter indices (must match Swift CompressorParam enum)
-// =============================================================================
+    /// Set a voice's mute (or solo) state, or queue it for the next mute
+    /// quantization boundary when mute quantization is on.
+    fn set_mode(&self, channel: u32, solo: bool, on: bool) {
+        let Some(voice) = self.voice(channel as usize) else {
+            return;
+        };
+        let (request, state) = if solo {
+            (&voice.solo_request, &voice.soloed)
+        } else {
+            (&voice.mute_request, &voice.muted)
+        };
+        if self.mute_quantize.load(Ordering::Relaxed) == CLIP_QUANTIZE_IMMEDIATE {
+            request.store(MODE_REQUEST_NONE, Ordering::Release);
+            state.store(on, Ordering::Release);
+        } else {
+            let value = if on {
+                MODE_REQUEST_ON
+            } else {
+                MODE_REQUEST_OFF
+            };
+            request.store(value, Ordering::Release);
+            self.mode_requests_pending.store(true, Ordering::Release);
+        }
+    }
 
-/// Compressor parameter: threshold in dB (-60.0 to 0.0)
-pub const COMPRESSOR_PARAM_THRESHOLD: u32 = 0;
-/// Compressor parameter: ratio (1.0 to 20.0)
-pub const COMPRESSOR_PARAM_RATIO: u32 = 1;
-/// Compressor parameter: attack time in ms (0.1 to 100.0)
-pub const COMPRESSOR_PARAM_ATTACK: u32 = 2;
-/// Compressor parameter: release time in ms (5.0 to 1000.0)
-pub const COMPRESSOR_PARAM_RELEASE: u32 = 3;
-/// Compressor parameter: wet/dry mix (0.0 to 1.0)
-pub const COMPRESSOR_PARAM_MIX: u32 = 4;
+    /// Calculate the target gain for an instrument based on mute/solo state
+    /// Returns 1.0 (full volume) or 0.0 (silent)
+    #[inline]
+    fn calculate_instrument_gain(muted: bool, soloed: bool, any_soloed: bool) -> f32 {
+        // Solo takes precedence: if this instrument is soloed, it plays
+        if soloed {
+            return 1.0;
+        }
 
-/// No sidechain — compressor uses main mix as input (default)
-pub const COMPRESSOR_SIDECHAIN_NONE: u32 = 0xFFFFFFFF;
+        // If any instrument is soloed but this one isn't, silence it
+        if any_soloed {
+            return 0.0;
+        }
 
-// =============================================================================
-// Tilt filter parameter indices
-// =============================================================================
+        // No solo active: check mute state
+        if muted {
+            return 0.0;
+        }
 
-/// Tilt filter parameter: cutoff position (0.0-1.0, 0.5 = center/passthrough)
-pub const TILT_PARAM_CUTOFF: u32 = 0;
-/// Tilt filter parameter: resonance (0.0-1.0)
-pub const TILT_PARAM_RESONANCE: u32 = 1;
+        // Not muted, not affected by solo: full volume
+        1.0
+    }
 
-// =============================================================================
-// Reverb parameter indices
-// =============================================================================
+    /// Load a stock preset into the first channel holding `instrument_type`,
+    /// level-matched when normalization is on. Returns false if no channel
+    /// holds that type or the preset is unknown.
+    fn load_preset_by_type(&mut self, instrument_type: u32, preset_id: u32) -> bool {
+        let Some(channel) = (0..NUM_CHANNELS).find(|&ch| {
+            self.voice(ch)
+                .is_some_and(|v| v.instrument.instrument_type() == instrument_type)
+        }) else {
+            return false;
+        };
+        let sample_rate = self.sample_rate;
+        let Some(voice) = self.voice_mut(channel) else {
+            return false;
+        };
+        let Some(config) = voice.instrument.preset_config(preset_id) else {
+            return false;
+        };
+        voice.fade_to_config(config, sample_rate);
+        voice.loaded_preset = Some(preset_id);
+        self.refresh_preset_gain(channel);
+        true
+    }
 
-/// Reverb parameter: decay amount (0.0-1.0)
-pub const REVERB_PARAM_DECAY: u32 = 0;
-/// Reverb parameter: wet/dry mix (0.0-1.0)
-pub const REVERB_PARAM_MIX: u32 = 1;
-/// Reverb parameter: high-frequency damping (0.0-1.0)
-pub const REVERB_PARAM_DAMPING: u32 = 2;
+    /// Borrow the first voice whose instrument matches the given type.
+    fn voice_by_type(&self, instrument_type: u32) -> Option<&VoiceStrip> {
+        self.voices_iter()
+            .find(|v| v.instrument.instrument_type() == instrument_type)
+    }
 
-// =============================================================================
-// Plate reverb parameter indices (must match Swift PlateParam enum)
-// =============================================================================
+    /// Borrow the first voice's instrument matching the given type.
+    fn instrument_by_type(&self, instrument_type: u32) -> Option<&ChannelInstrument> {
+        self.voice_by_type(instrument_type).map(|v| &v.instrument)
+    }
 
-/// Plate reverb parameter: decay amount (0.0-1.0)
-pub const PLATE_PARAM_DECAY: u32 = 0;
-/// Plate reverb parameter: wet/dry mix (0.0-1.0)
-pub const PLATE_PARAM_MIX: u32 = 1;
-/// Plate reverb parameter: high-frequency damping (0.0-1.0)
-pub const PLATE_PARAM_DAMPING: u32 = 2;
-/// Plate reverb parameter: predelay (0.0-1.0, maps linearly to 0-200 ms)
-pub const PLATE_PARAM_PREDELAY: u32 = 3;
-/// Plate reverb parameter: stereo width of the wet signal (0.0 = mono, 1.0 = full)
-pub const PLATE_PARAM_WIDTH: u32 = 4;
-/// Plate reverb parameter: tank size (0.0-1.0; 0.5 = the published Dattorro
-/// plate, endpoints scale the tank from 0.25x to 2.0x)
-pub const PLATE_PARAM_SIZE: u32 = 5;
+    /// Loudness of one full-velocity hit of `instrument_type` with `preset_id`
+    /// loaded (its default sound for None), rendered offline once and cached.
+    fn preset_loudness(
+        &mut self,
+        instrument_type: u32,
+        preset_id: Option<u32>,
+    ) -> Option<Loudness> {
+        let key = (instrument_type, preset_id);
+        if let Some(loudness) = self.preset_loudness.get(&key) {
+            return Some(*loudness);
+        }
+        let mut instrument = ChannelInstrument::new(instrument_type, self.sample_rate)?;
+        if let Some(id) = preset_id {
+            if !instrument.load_preset(id) {
+                return None;
+            }
+        }
+        instrument.snap_params();
+        instrument.trigger_with_velocity(0.0, 1.0);
+        let period = 1.0 / self.sample_rate as f64;
+        let samples: Vec<f32> = (0..(PRESET_MEASURE_SECS * self.sample_rate) as usize)
+            .map(|i| instrument.tick(i as f64 * period))
+            .collect();
+        let loudness = Loudness::measure(&samples, self.sample_rate);
+        self.preset_loudness.insert(key, loudness);
+        Some(loudness)
+    }
+
+    /// Auto-gain (dB) that brings `preset_id` to the level of the instrument's
+    /// default sound; 0 dB with normalization off or for an unknown preset.
+    fn preset_gain_db(&mut self, instrument_type: u32, preset_id: u32) -> f32 {
+        if !self.preset_normalization {
+            return 0.0;
+        }
+        match (
+            self.preset_loudness(instrument_type, None),
+            self.preset_loudness(instrument_type, Some(preset_id)),
+        ) {
+            (Some(reference), Some(preset)) => preset.gain_db_to(reference.rms_db),
+            _ => 0.0,
+        }
+    }
 
-// =============================================================================
-// Waveshaper parameter indices
-// =============================================================================
+    /// Re-measure a voice's blend corners and re-target its preset gain for
+    /// the current blend position or directly loaded preset.
+    fn refresh_preset_gain(&mut self, channel: usize) {
+        let Some(voice) = self.voice(channel) else {
+            return;
+        };
+        let instrument_type = voice.instrument.instrument_type();
+        let corners = voice.blend_corner_presets;
+        let loaded = voice.loaded_preset;
+        let corner_db = corners.map(|id| self.preset_gain_db(instrument_type, id));
+        let loaded_db = loaded.map(|id| self.preset_gain_db(instrument_type, id));
+        let normalize = self.preset_normalization;
+        let Some(voice) = self.voice_mut(channel) else {
+            return;
+        };
+        voice.corner_gain_db =
+            PresetBlender::new(corner_db[0], corner_db[1], corner_db[2], corner_db[3]);
+        let target_db = if !normalize {
+            Some(0.0)
+        } else if loaded_db.is_some() {
+            loaded_db
+        } else if voice.blend_enabled.load(Ordering::Relaxed) {
+            Some(voice.corner_gain_db.blend(voice.blend_x, voice.blend_y))
+        } else {
+            None
+        };
+        if let Some(db) = target_db {
+            voice.preset_gain.set_target(db_to_amplitude(db));
+        }
+    }
 
-/// Waveshaper parameter: drive amount (1.0-10.0)
-pub const WAVESHAPER_PARAM_DRIVE: u32 = 0;
-/// Waveshaper parameter: dry/wet mix (0.0-1.0)
-pub const WAVESHAPER_PARAM_MIX: u32 = 1;
+    /// Get a KickConfig preset by ID
+    fn kick_preset_by_id(id: u32) -> Option<KickConfig> {
+        match id {
+            KICK_PRESET_TIGHT => Some(KickConfig::tight()),
+            KICK_PRESET_PUNCH => Some(KickConfig::punch()),
+            KICK_PRESET_LOOSE => Some(KickConfig::loose()),
+            KICK_PRESET_DIRT => Some(KickConfig::dirt()),
+            _ => None,
+        }
+    }
 
-// =============================================================================
-// Feedback waveshaper parameter indices
-// =============================================================================
+    /// Get a Tom2Config preset by ID
+    fn tom_preset_by_id(id: u32) -> Option<Tom2Config> {
+        match id {
+            TOM_PRESET_DERP => Some(Tom2Config::derp()),
+            TOM_PRESET_RING => Some(Tom2Config::ring()),
+            TOM_PRESET_BRUSH => Some(Tom2Config::brush()),
+            TOM_PRESET_VOID => Some(Tom2Config::void_preset()),
+            _ => None,
+        }
+    }
 
-/// Feedback waveshaper parameter: drive amount (1.0-100.0)
-pub const FEEDBACK_WAVESHAPER_PARAM_DRIVE: u32 = 0;
-/// Feedback waveshaper parameter: feedback gain (0.0-0.98)
-pub const FEEDBACK_WAVESHAPER_PARAM_FEEDBACK: u32 = 1;
-/// Feedback waveshaper parameter: feedback lowpass cutoff (200-20000 Hz)
-pub const FEEDBACK_WAVESHAPER_PARAM_FILTER_CUTOFF: u32 = 2;
-/// Feedback waveshaper parameter: dry/wet mix (0.0-1.0)
-pub const FEEDBACK_WAVESHAPER_PARAM_MIX: u32 = 3;
+    /// Get a BassConfig preset by ID
+    fn bass_preset_by_id(id: u32) -> Option<BassConfig> {
+        match id {
+            BASS_PRESET_ACID => Some(BassConfig::acid()),
+            BASS_PRESET_SUB => Some(BassConfig::sub()),
+            BASS_PRESET_REESE => Some(BassConfig::reese()),
+            BASS_PRESET_STAB => Some(BassConfig::stab()),
+            _ => None,
+        }
+    }
 
-// =============================================================================
-// Kick drum parameter indices (must match Swift KickParam enum)
-// =============================================================================
+    /// Get an FmSnapConfig preset by ID
+    fn fm_snap_preset_by_id(id: u32) -> Option<FmSnapConfig> {
+        match id {
+            FM_SNAP_PRESET_SNAP => Some(FmSnapConfig::snap()),
+            FM_SNAP_PRESET_RIM => Some(FmSnapConfig::rim()),
+            FM_SNAP_PRESET_WOOD => Some(FmSnapConfig::wood()),
+            FM_SNAP_PRESET_ZAP => Some(FmSnapConfig::zap()),
+            _ => None,
+        }
+    }
 
-/// Kick parameter: base frequency (30-80 Hz)
-pub const KICK_PARAM_FREQUENCY: u32 = 0;
-/// Kick parameter: punch/mid presence (0-1)
-pub const KICK_PARAM_PUNCH: u32 = 1;
-/// Kick parameter: sub bass presence (0-1)
-pub const KICK_PARAM_SUB: u32 = 2;
-/// Kick parameter: click/transient amount (0-1)
-pub const KICK_PARAM_CLICK: u32 = 3;
-/// Kick parameter: decay time (0.01-5.0 seconds)
-pub const KICK_PARAM_DECAY: u32 = 4;
-/// Kick parameter: pitch envelope amount (0-1)
-pub const KICK_PARAM_PITCH_ENVELOPE: u32 = 5;
-/// Kick parameter: overall volume (0-1)
-pub const KICK_PARAM_VOLUME: u32 = 6;
-/// Kick parameter: tuning offset (0=−12 semitones, 0.5=neutral, 1=+12 semitones)
-pub const KICK_PARAM_TUNING: u32 = 7;
+    /// Get a RimshotConfig preset by ID
+    fn rimshot_preset_by_id(id: u32) -> Option<RimshotConfig> {
+        match id {
+            RIMSHOT_PRESET_CLASSIC => Some(RimshotConfig::classic()),
+            RIMSHOT_PRESET_TIGHT => Some(RimshotConfig::tight()),
+            RIMSHOT_PRESET_WOODY => Some(RimshotConfig::woody()),
+            RIMSHOT_PRESET_RING => Some(RimshotConfig::ring()),
+            _ => None,
+        }
+    }
 
-// =============================================================================
-// Hi-hat parameter indices (must match Swift HiHatParam enum)
-// =============================================================================
+    /// Get a CowbellConfig preset by ID
+    fn cowbell_preset_by_id(id: u32) -> Option<CowbellConfig> {
+        match id {
+            COWBELL_PRESET_CLASSIC => Some(CowbellConfig::classic()),
+            COWBELL_PRESET_BRIGHT => Some(CowbellConfig::bright()),
+            COWBELL_PRESET_DARK => Some(CowbellConfig::dark()),
+            COWBELL_PRESET_SHORT => Some(CowbellConfig::short()),
+            _ => None,
+        }
+    }
 
-/// Hi-hat parameter: pitch (0-1 normalized)
-pub const HIHAT_PARAM_PITCH: u32 = 0;
-/// Hi-hat parameter: decay (0-1 normalized)
-pub const HIHAT_PARAM_DECAY: u32 = 1;
-/// Hi-hat parameter: attack (0-1 normalized)
-pub const HIHAT_PARAM_ATTACK: u32 = 2;
-/// Hi-hat parameter: tone (0-1 normalized)
-pub const HIHAT_PARAM_TONE: u32 = 3;
-/// Hi-hat parameter: volume (0-1 normalized)
-pub const HIHAT_PARAM_VOLUME: u32 = 4;
-/// Hi-hat parameter: tuning offset (0=−12 semitones, 0.5=neutral, 1=+12 semitones)
-pub const HIHAT_PARAM_TUNING: u32 = 5;
+    /// Get a ShakerConfig preset by ID
+    fn shaker_preset_by_id(id: u32) -> Option<ShakerConfig> {
+        match id {
+            SHAKER_PRESET_TIGHT => Some(ShakerConfig::tight()),
+            SHAKER_PRESET_SWISH => Some(ShakerConfig::swish()),
+            SHAKER_PRESET_CABASA => Some(ShakerConfig::cabasa()),
+            SHAKER_PRESET_EGG => Some(ShakerConfig::egg()),
+            _ => None,
+        }
+    }
+
+    /// Convert a MIDI note number to a normalized frequency value for an instrument's range.
+    fn midi_note_to_normalized_freq(note: u8, freq_min: f32, freq_max: f32) -> f32 {
+        let hz = 440.0 * 2f32.powf((note as f32 - 69.0) / 12.0);
+        ((hz - freq_min) / (freq_max - freq_min)).clamp(0.0, 1.0)
+    }
+
+    /// Get the frequency range (min, max) for a pitched instrument type.
+    fn freq_range_for_instrument(instrument_type: u32) -> Option<(f32, f32)> {
+        match instrument_type {
+            INSTRUMENT_BASS => Some((30.0, 200.0)),
+            INSTRUMENT_KICK => Some((30.0, 120.0)),
+            INSTRUMENT_TOM => Some((40.0, 600.0)),
+            _ => None,
+        }
+    }
+
+    /// Get a SnareConfig preset by ID
+    fn snare_preset_by_id(id: u32) -> Option<SnareConfig> {
+        match id {
+            SNARE_PRESET_TIGHT => Some(SnareConfig::tight()),
+            SNARE_PRESET_LOOSE => Some(SnareConfig::loose()),
+            SNARE_PRESET_HISS => Some(SnareConfig::hiss()),
+            SNARE_PRESET_SMACK => Some(SnareConfig::smack()),
+            _ => None,
+        }
+    }
+
+    /// Get a HiHat2Config preset by ID
+    fn hihat_preset_by_id(id: u32) -> Option<HiHat2Config> {
+        match id {
+            HIHAT_PRESET_SHORT => Some(HiHat2Config::short()),
+            HIHAT_PRESET_LOOSE => Some(HiHat2Config::loose()),
+            HIHAT_PRESET_DARK => Some(HiHat2Config::dark()),
+            HIHAT_PRESET_SOFT => Some(HiHat2Config::soft()),
+            HIHAT_PRESET_CLASSIC => Some(HiHat2Config::classic()),
+            HIHAT_PRESET_CLASSIC_LOOSE => Some(HiHat2Config::classic_loose()),
+            _ => None,
+        }
+    }
+}
 
 // =============================================================================
-// Snare drum parameter indices (must match Swift SnareParam enum)
+// Global effect IDs (must match Swift GlobalEffect enum)
 // =============================================================================
 
-/// Snare parameter: base frequency (0-1 → 100-600 Hz)
-pub const SNARE_PARAM_FREQUENCY: u32 = 0;
-/// Snare parameter: decay time (0-1 → 0.05-3.5 seconds)
-pub const SNARE_PARAM_DECAY: u32 = 1;
-/// Snare parameter: brightness/snap amount (0-1)
-pub const SNARE_PARAM_BRIGHTNESS: u32 = 2;
-/// Snare parameter: overall volume (0-1)
-pub const SNARE_PARAM_VOLUME: u32 = 3;
-/// Snare parameter: tonal body amount (0-1)
-pub const SNARE_PARAM_TONAL: u32 = 4;
-/// Snare parameter: noise amount (0-1)
-pub const SNARE_PARAM_NOISE: u32 = 5;
-/// Snare parameter: pitch drop amount (0-1)
-pub const SNARE_PARAM_PITCH_DROP: u32 = 6;
-/// Snare parameter: tonal decay (0-1 → 0-3.5s)
-pub const SNARE_PARAM_TONAL_DECAY: u32 = 7;
-/// Snare parameter: noise decay (0-1 → 0-3.5s)
-pub const SNARE_PARAM_NOISE_DECAY: u32 = 8;
-/// Snare parameter: noise tail decay (0-1 → 0-3.5s)
-pub const SNARE_PARAM_NOISE_TAIL_DECAY: u32 = 9;
-/// Snare parameter: filter cutoff (0-1 → 100-10000 Hz)
-pub const SNARE_PARAM_FILTER_CUTOFF: u32 = 10;
-/// Snare parameter: filter resonance (0-1 → 0.5-10.0)
-pub const SNARE_PARAM_FILTER_RESONANCE: u32 = 11;
-/// Snare parameter: filter type (0=LP, 1=BP, 2=HP, 3=notch)
-pub const SNARE_PARAM_FILTER_TYPE: u32 = 12;
-/// Snare parameter: tonal/noise crossfade (0-1)
-pub const SNARE_PARAM_XFADE: u32 = 13;
-/// Snare parameter: phase modulation amount (0-1, 0 = disabled)
-pub const SNARE_PARAM_PHASE_MOD_AMOUNT: u32 = 14;
-/// Snare parameter: overdrive/saturation (0-1, 0 = bypass)
-pub const SNARE_PARAM_OVERDRIVE: u32 = 15;
-/// Snare parameter: master amplitude decay (0-1 → 0-4.0s)
-pub const SNARE_PARAM_AMP_DECAY: u32 = 16;
-/// Snare parameter: amplitude decay curve (0-1 → 0.1-10.0)
-pub const SNARE_PARAM_AMP_DECAY_CURVE: u32 = 17;
-/// Snare parameter: tonal decay curve (0-1 → 0.1-10.0)
-pub const SNARE_PARAM_TONAL_DECAY_CURVE: u32 = 18;
-/// Snare parameter: tuning offset (0=−12 semitones, 0.5=neutral, 1=+12 semitones)
-pub const SNARE_PARAM_TUNING: u32 = 19;
+/// Global effect: Lowpass filter
+pub const EFFECT_LOWPASS_FILTER: u32 = 0;
+/// Global effect: Delay
+pub const EFFECT_DELAY: u32 = 1;
+/// Global effect: Saturation
+pub const EFFECT_SATURATION: u32 = 2;
+/// Global effect: Compressor
+pub const EFFECT_COMPRESSOR: u32 = 3;
+/// Global effect: Tilt filter (unified lowpass/highpass)
+pub const EFFECT_TILT_FILTER: u32 = 4;
+/// Global effect: Optional soft limiter
+pub const EFFECT_LIMITER: u32 = 5;
 
 // =============================================================================
-// Tom drum parameter indices (Tom2 - must match Swift TomParam enum)
+// Limiter parameter indices (must match Swift LimiterParam enum)
 // =============================================================================
 
-/// Tom parameter: tune (0-1 → 0-100, maps to 40-600 Hz)
-pub const TOM_PARAM_TUNE: u32 = 0;
-/// Tom parameter: bend (0-1 → 0-100, pitch envelope depth)
-pub const TOM_PARAM_BEND: u32 = 1;
-/// Tom parameter: tone (0-1 → 0-100, mix control)
-pub const TOM_PARAM_TONE: u32 = 2;
-/// Tom parameter: color (0-1 → 0-100, noise rate / filter cutoff)
-pub const TOM_PARAM_COLOR: u32 = 3;
-/// Tom parameter: decay (0-1 → 0-100, maps to 0.5-4000ms)
-pub const TOM_PARAM_DECAY: u32 = 4;
-/// Tom parameter: membrane mix (0-1 → 0-100, resonator mix amount)
-pub const TOM_PARAM_MEMBRANE: u32 = 5;
-/// Tom parameter: membrane Q (0-1 → 0-100, resonator Q scale)
-pub const TOM_PARAM_MEMBRANE_Q: u32 = 6;
-/// Tom parameter: volume (0-1 → 0-100, overall volume)
-pub const TOM_PARAM_VOLUME: u32 = 7;
-/// Tom parameter: tuning offset (0=−12 semitones, 0.5=neutral, 1=+12 semitones)
-pub const TOM_PARAM_TUNING: u32 = 8;
+/// Limiter parameter: threshold (0.001-1.0)
+pub const LIMITER_PARAM_THRESHOLD: u32 = 0;
+/// Global effect: Spring reverb
+pub const EFFECT_REVERB: u32 = 6;
+/// Global effect: Waveshaper (tanh soft-clip distortion)
+pub const EFFECT_WAVESHAPER: u32 = 7;
+/// Global effect: Feedback waveshaper (self-exciting distortion)
+pub const EFFECT_FEEDBACK_WAVESHAPER: u32 = 8;
+/// Global effect: Plate reverb (Dattorro figure-eight tank)
+pub const EFFECT_PLATE_REVERB: u32 = 9;
+/// Global effect: Trigger-driven ducker (applied after the reorderable chain,
+/// before the limiter)
+pub const EFFECT_DUCKER: u32 = 10;
+/// Global effect: Beat repeat (applied before the reorderable chain, or on a
+/// single instrument — see `gooey_engine_set_beat_repeat_source`)
+pub const EFFECT_BEAT_REPEAT: u32 = 11;
+/// Global effect: Early reflections room (applied before the reorderable
+/// chain, after a master-sourced beat repeat)
+pub const EFFECT_EARLY_REFLECTIONS: u32 = 12;
+/// Global effect: Stereo widener (applied after the ducker, before the
+/// limiter)
+pub const EFFECT_WIDENER: u32 = 13;
+/// Total number of global effects
+pub const EFFECT_COUNT: u32 = 14;
+
+/// Every master effect a channel can bypass, as `1 << EFFECT_*` bits (all
+/// but the limiter; see `gooey_engine_set_channel_fx_bypass`).
+pub const FX_BYPASS_ALL: u32 = ((1 << EFFECT_COUNT) - 1) & !(1 << EFFECT_LIMITER);
+
+/// Number of reorderable effects in the chain. Excludes the beat repeat, the
+/// early reflections, the ducker, the widener and the optional limiter, which
+/// have fixed positions.
+pub const REORDERABLE_EFFECT_COUNT: u32 = 9;
+
+/// Default order for the reorderable effects, matching the historical
+/// hardcoded chain (saturation -> lowpass -> tilt -> delay -> compressor -> reverb).
+const DEFAULT_EFFECT_ORDER: [u32; REORDERABLE_EFFECT_COUNT as usize] = [
+    EFFECT_WAVESHAPER,
+    EFFECT_SATURATION,
+    EFFECT_LOWPASS_FILTER,
+    EFFECT_TILT_FILTER,
+    EFFECT_DELAY,
+    EFFECT_COMPRESSOR,
+    EFFECT_FEEDBACK_WAVESHAPER,
+    EFFECT_REVERB,
+    EFFECT_PLATE_REVERB,
+];
 
 // =============================================================================
-// Instrument IDs (must match Swift/C enum if used)
+// Lowpass filter parameter indices (must match Swift FilterParam enum)
 // =============================================================================
 
-/// Instrument ID: kick drum
-pub const INSTRUMENT_KICK: u32 = 0;
-/// Instrument ID: snare drum
-pub const INSTRUMENT_SNARE: u32 = 1;
-/// Instrument ID: hi-hat
-pub const INSTRUMENT_HIHAT: u32 = 2;
-/// Instrument ID: tom drum
-pub const INSTRUMENT_TOM: u32 = 3;
-/// Instrument ID: bass synth
-pub const INSTRUMENT_BASS: u32 = 4;
-/// Total number of instruments
-pub const INSTRUMENT_COUNT: u32 = 5;
-/// Internal usize version for array indexing
-const NUM_INSTRUMENTS: usize = INSTRUMENT_COUNT as usize;
-const DEFAULT_MASTER_GAIN: f32 = 0.25;
+/// Filter parameter: cutoff frequency (20-20000 Hz)
+pub const FILTER_PARAM_CUTOFF: u32 = 0;
+/// Filter parameter: resonance (0.0-0.95)
+pub const FILTER_PARAM_RESONANCE: u32 = 1;
 
-/// Number of stereo loop-mixer channels (see `gooey_engine_loop_*`).
-pub const LOOP_CHANNEL_COUNT: u32 = crate::mixer::LOOP_CHANNEL_COUNT as u32;
+// =============================================================================
+// Delay parameter indices (must match Swift DelayParam enum)
+// =============================================================================
 
-/// Mixer graph source: the drum kit (kick/snare/hihat/tom summed).
-pub const SOURCE_DRUMKIT: u32 = crate::mixer::graph::SOURCE_DRUMKIT;
-/// Mixer graph source: the bass voice.
-pub const SOURCE_BASS: u32 = crate::mixer::graph::SOURCE_BASS;
-/// Mixer graph source: the polyphonic synth.
-pub const SOURCE_POLYSYNTH: u32 = crate::mixer::graph::SOURCE_POLYSYNTH;
-/// Mixer graph source: the granulator.
-pub const SOURCE_GRANULATOR: u32 = crate::mixer::graph::SOURCE_GRANULATOR;
-/// Mixer graph source: the stereo loop mixer.
-pub const SOURCE_LOOPMIXER: u32 = crate::mixer::graph::SOURCE_LOOPMIXER;
-/// First source ID reserved for registered sampler racks. Rack `n` uses
-/// `SOURCE_SAMPLER_BASE + n`; only registered racks are routable.
-pub const SOURCE_SAMPLER_BASE: u32 = crate::mixer::graph::SOURCE_SAMPLER_BASE;
-/// Number of routable mixer graph sources.
-pub const SOURCE_COUNT: u32 = crate::mixer::graph::SOURCE_COUNT as u32;
+/// Delay parameter: timing division (see DELAY_TIMING_* constants)
+pub const DELAY_PARAM_TIMING: u32 = 0;
+/// Delay parameter: feedback amount (0.0-0.95)
+pub const DELAY_PARAM_FEEDBACK: u32 = 1;
+/// Delay parameter: wet/dry mix (0.0-1.0)
+pub const DELAY_PARAM_MIX: u32 = 2;
+/// Delay parameter: filter cutoff in Hz (20-20000)
+pub const DELAY_PARAM_FILTER_CUTOFF: u32 = 3;
+/// Delay parameter: ping-pong mode (0.0 = off, >= 0.5 = on). When on, the delay
+/// feedback crosses channels so echoes bounce between left and right.
+pub const DELAY_PARAM_PINGPONG: u32 = 4;
+/// Delay parameter: low-cut (highpass) frequency in Hz in the feedback path
+/// (20-20000, 20 = off)
+pub const DELAY_PARAM_LOW_CUT: u32 = 5;
+/// Delay parameter: freeze (0.0 = off, >= 0.5 = on). While frozen, the buffer
+/// loops indefinitely and no new input is written.
+pub const DELAY_PARAM_FREEZE: u32 = 6;
 
 // =============================================================================
-// Preset blend constants
+// Delay timing constants (must match Swift DelayTiming enum)
 // =============================================================================
 
-/// Kick preset: Tight - short, punchy kick with strong pitch envelope
-pub const KICK_PRESET_TIGHT: u32 = 0;
-/// Kick preset: Punch - mid-focused with click and resonant noise
-pub const KICK_PRESET_PUNCH: u32 = 1;
-/// Kick preset: Loose - longer decay, more punch, subtle pitch envelope
-pub const KICK_PRESET_LOOSE: u32 = 2;
-/// Kick preset: Dirt - higher frequency, more noise with high resonance
-pub const KICK_PRESET_DIRT: u32 = 3;
+/// Delay timing: whole note (4 beats)
+pub const DELAY_TIMING_WHOLE: u32 = 0;
+/// Delay timing: half note (2 beats)
+pub const DELAY_TIMING_HALF: u32 = 1;
+/// Delay timing: quarter note (1 beat)
+pub const DELAY_TIMING_QUARTER: u32 = 2;
+/// Delay timing: eighth note (1/2 beat)
+pub const DELAY_TIMING_EIGHTH: u32 = 3;
+/// Delay timing: sixteenth note (1/4 beat)
+pub const DELAY_TIMING_SIXTEENTH: u32 = 4;
+/// Delay timing: half note triplet (4/3 beats)
+pub const DELAY_TIMING_HALF_TRIPLET: u32 = 5;
+/// Delay timing: quarter note triplet (2/3 beat)
+pub const DELAY_TIMING_QUARTER_TRIPLET: u32 = 6;
+/// Delay timing: eighth note triplet (1/3 beat)
+pub const DELAY_TIMING_EIGHTH_TRIPLET: u32 = 7;
+/// Delay timing: sixteenth note triplet (1/6 beat)
+pub const DELAY_TIMING_SIXTEENTH_TRIPLET: u32 = 8;
+/// Delay timing: dotted half note (3 beats)
+pub const DELAY_TIMING_HALF_DOTTED: u32 = 9;
+/// Delay timing: dotted quarter note (3/2 beats)
+pub const DELAY_TIMING_QUARTER_DOTTED: u32 = 10;
+/// Delay timing: dotted eighth note (3/4 beat)
+pub const DELAY_TIMING_EIGHTH_DOTTED: u32 = 11;
+/// Delay timing: dotted sixteenth note (3/8 beat)
+pub const DELAY_TIMING_SIXTEENTH_DOTTED: u32 = 12;
 
-/// Tom preset: Derp - punchy mid tom
-pub const TOM_PRESET_DERP: u32 = 0;
-/// Tom preset: Ring - high, long decay
-pub const TOM_PRESET_RING: u32 = 1;
-/// Tom preset: Brush - low, textured
-pub const TOM_PRESET_BRUSH: u32 = 2;
-/// Tom preset: Void - atmospheric, long
-pub const TOM_PRESET_VOID: u32 = 3;
+// =============================================================================
+// Saturation parameter indices (must match Swift SaturationParam enum)
+// =============================================================================
+
+/// Saturation parameter: drive amount (0.0-1.0)
+pub const SATURATION_PARAM_DRIVE: u32 = 0;
+/// Saturation parameter: warmth/even harmonics (0.0-1.0)
+pub const SATURATION_PARAM_WARMTH: u32 = 1;
+/// Saturation parameter: wet/dry mix (0.0-1.0)
+pub const SATURATION_PARAM_MIX: u32 = 2;
+/// Saturation parameter: model (see SATURATOR_MODEL_* constants; default
+/// SATURATOR_MODEL_TUBE). Warmth only applies to the tube model.
+pub const SATURATION_PARAM_MODEL: u32 = 3;
+/// Saturation parameter: auto gain (0.0 = off, >= 0.5 = on; default off).
+/// Matches every model's output RMS to its input at any drive.
+pub const SATURATION_PARAM_AUTO_GAIN: u32 = 4;
 
 // =============================================================================
-// Bass synth parameter constants
+// Saturator models (global saturation and instrument overdrive stages)
 // =============================================================================
 
-/// Bass parameter: base frequency (0-1 -> 30-200 Hz)
-pub const BASS_PARAM_FREQUENCY: u32 = 0;
-/// Bass parameter: sub sine level (0-1)
-pub const BASS_PARAM_SUB_LEVEL: u32 = 1;
-/// Bass parameter: main saw/square level (0-1)
-pub const BASS_PARAM_OSC_LEVEL: u32 = 2;
-/// Bass parameter: detuned layer level (0-1)
-pub const BASS_PARAM_DETUNE_LEVEL: u32 = 3;
-/// Bass parameter: detune spread (0-1 -> 0-30 cents)
-pub const BASS_PARAM_DETUNE_AMOUNT: u32 = 4;
-/// Bass parameter: oscillator shape - saw(0) to square(1)
-pub const BASS_PARAM_OSC_SHAPE: u32 = 5;
-/// Bass parameter: filter cutoff (0-1 -> 20-18000 Hz exp)
-pub const BASS_PARAM_FILTER_CUTOFF: u32 = 6;
-/// Bass parameter: filter resonance (0-1 -> 0.5-15.0 Q)
-pub const BASS_PARAM_FILTER_RESONANCE: u32 = 7;
-/// Bass parameter: filter envelope depth (0-1)
-pub const BASS_PARAM_FILTER_ENV_AMOUNT: u32 = 8;
-/// Bass parameter: filter envelope decay (0-1 -> 0.01-2.0s)
-pub const BASS_PARAM_FILTER_ENV_DECAY: u32 = 9;
-/// Bass parameter: filter envelope curve (0-1 -> 0.1-8.0)
-pub const BASS_PARAM_FILTER_ENV_CURVE: u32 = 10;
-/// Bass parameter: amplitude decay (0-1 -> 0.05-4.0s)
-pub const BASS_PARAM_AMP_DECAY: u32 = 11;
-/// Bass parameter: amplitude decay curve (0-1 -> 0.1-10.0)
-pub const BASS_PARAM_AMP_DECAY_CURVE: u32 = 12;
-/// Bass parameter: pre-filter overdrive/saturation (0-1)
-pub const BASS_PARAM_OVERDRIVE: u32 = 13;
-/// Bass parameter: master volume (0-1)
-pub const BASS_PARAM_VOLUME: u32 = 14;
-/// Bass parameter: tuning offset (0=−12 semitones, 0.5=neutral, 1=+12 semitones)
-pub const BASS_PARAM_TUNING: u32 = 15;
+/// Saturator model: tanh soft clip (the instruments' original overdrive curve)
+pub const SATURATOR_MODEL_SOFT: u32 = 0;
+/// Saturator model: tube (asymmetric arctangent with even harmonics)
+pub const SATURATOR_MODEL_TUBE: u32 = 1;
+/// Saturator model: tape (soft curve with high-frequency pre/de-emphasis)
+pub const SATURATOR_MODEL_TAPE: u32 = 2;
+/// Saturator model: diode clipper (asymmetric)
+pub const SATURATOR_MODEL_DIODE: u32 = 3;
+/// Saturator model: sine foldback
+pub const SATURATOR_MODEL_FOLDBACK: u32 = 4;
+/// Saturator model: hard clip
+pub const SATURATOR_MODEL_HARD_CLIP: u32 = 5;
+/// Returned for instruments without a model-selectable overdrive stage
+pub const SATURATOR_MODEL_NONE: u32 = 0xFFFFFFFF;
+
+// =============================================================================
+// Compressor parameter indices (must match Swift CompressorParam enum)
+// =============================================================================
+
+/// Compressor parameter: threshold in dB (-60.0 to 0.0)
+pub const COMPRESSOR_PARAM_THRESHOLD: u32 = 0;
+/// Compressor parameter: ratio (1.0 to 20.0)
+pub const COMPRESSOR_PARAM_RATIO: u32 = 1;
+/// Compressor parameter: attack time in ms (0.1 to 100.0)
+pub const COMPRESSOR_PARAM_ATTACK: u32 = 2;
+/// Compressor parameter: release time in ms (5.0 to 1000.0)
+pub const COMPRESSOR_PARAM_RELEASE: u32 = 3;
+/// Compressor parameter: wet/dry mix (0.0 to 1.0)
+pub const COMPRESSOR_PARAM_MIX: u32 = 4;
+
+/// No sidechain — compressor uses main mix as input (default)
+pub const COMPRESSOR_SIDECHAIN_NONE: u32 = 0xFFFFFFFF;
+
+// =============================================================================
+// Tilt filter parameter indices
+// =============================================================================
+
+/// Tilt filter parameter: cutoff position (0.0-1.0, 0.5 = center/passthrough)
+pub const TILT_PARAM_CUTOFF: u32 = 0;
+/// Tilt filter parameter: resonance (0.0-1.0)
+pub const TILT_PARAM_RESONANCE: u32 = 1;
+
+// =============================================================================
+// Reverb parameter indices
+// =============================================================================
+
+/// Reverb parameter: decay amount (0.0-1.0)
+pub const REVERB_PARAM_DECAY: u32 = 0;
+/// Reverb parameter: wet/dry mix (0.0-1.0)
+pub const REVERB_PARAM_MIX: u32 = 1;
+/// Reverb parameter: high-frequency damping (0.0-1.0)
+pub const REVERB_PARAM_DAMPING: u32 = 2;
+
+// =============================================================================
+// Plate reverb parameter indices (must match Swift PlateParam enum)
+// =============================================================================
+
+/// Plate reverb parameter: decay amount (0.0-1.0)
+pub const PLATE_PARAM_DECAY: u32 = 0;
+/// Plate reverb parameter: wet/dry mix (0.0-1.0)
+pub const PLATE_PARAM_MIX: u32 = 1;
+/// Plate reverb parameter: high-frequency damping (0.0-1.0)
+pub const PLATE_PARAM_DAMPING: u32 = 2;
+/// Plate reverb parameter: predelay (0.0-1.0, maps linearly to 0-200 ms)
+pub const PLATE_PARAM_PREDELAY: u32 = 3;
+/// Plate reverb parameter: stereo width of the wet signal (0.0 = mono, 1.0 = full)
+pub const PLATE_PARAM_WIDTH: u32 = 4;
+/// Plate reverb parameter: tank size (0.0-1.0; 0.5 = the published Dattorro
+/// plate, endpoints scale the tank from 0.25x to 2.0x)
+pub const PLATE_PARAM_SIZE: u32 = 5;
+
+// =============================================================================
+// Ducker parameter indices
+// =============================================================================
+
+/// Ducker parameter: depth at full duck (0.0 = none, 1.0 = silence)
+pub const DUCKER_PARAM_DEPTH: u32 = 0;
+/// Ducker parameter: attack time in ms (0.0 to 50.0)
+pub const DUCKER_PARAM_ATTACK: u32 = 1;
+/// Ducker parameter: hold time in ms (0.0 to 500.0)
+pub const DUCKER_PARAM_HOLD: u32 = 2;
+/// Ducker parameter: release time in ms (5.0 to 1000.0)
+pub const DUCKER_PARAM_RELEASE: u32 = 3;
+
+/// No ducker source — the ducker never fires (default)
+pub const DUCKER_SOURCE_NONE: u32 = 0xFFFFFFFF;
+
+// =============================================================================
+// Beat repeat parameter indices
+// =============================================================================
+
+/// Beat repeat parameter: slice division (see DELAY_TIMING_* constants)
+pub const BEAT_REPEAT_PARAM_SLICE: u32 = 0;
+/// Beat repeat parameter: captured length in beats (0.25 to 4.0)
+pub const BEAT_REPEAT_PARAM_LENGTH: u32 = 1;
+/// Beat repeat parameter: slice pitch in semitones (-12.0 to 12.0)
+pub const BEAT_REPEAT_PARAM_PITCH: u32 = 2;
+/// Beat repeat parameter: reverse slices (0.0 = off, >= 0.5 = on)
+pub const BEAT_REPEAT_PARAM_REVERSE: u32 = 3;
+/// Beat repeat parameter: wet/dry mix while repeating (0.0-1.0)
+pub const BEAT_REPEAT_PARAM_MIX: u32 = 4;
+
+/// Beat repeat source: the master bus (default)
+pub const BEAT_REPEAT_SOURCE_MASTER: u32 = 0xFFFFFFFF;
+
+// =============================================================================
+// Early reflections parameter indices
+// =============================================================================
+
+/// Early reflections parameter: room size (0.0-1.0, scales tap times 0.25x-2.0x)
+pub const EARLY_REFLECTIONS_PARAM_SIZE: u32 = 0;
+/// Early reflections parameter: damping (0.0 = bright, 1.0 = dark)
+pub const EARLY_REFLECTIONS_PARAM_DAMPING: u32 = 1;
+/// Early reflections parameter: wet/dry mix (0.0-1.0)
+pub const EARLY_REFLECTIONS_PARAM_MIX: u32 = 2;
+
+/// Early reflections preset: Tight room - short, damped, subtle (the default)
+pub const EARLY_REFLECTIONS_PRESET_TIGHT_ROOM: u32 = 0;
+/// Early reflections preset: Big room - long, bright, more room in the mix
+pub const EARLY_REFLECTIONS_PRESET_BIG_ROOM: u32 = 1;
+
+// =============================================================================
+// Widener parameter indices
+// =============================================================================
+
+/// Widener parameter: width (0.0 = mono, 1.0 = unchanged, 2.0 = widest)
+pub const WIDENER_PARAM_WIDTH: u32 = 0;
+/// Widener parameter: Haas delay of the decorrelated side in ms (1.0 to 30.0)
+pub const WIDENER_PARAM_DELAY: u32 = 1;
+/// Widener parameter: crossover in Hz (20.0 to 1000.0); below it width is
+/// capped at 1.0 and out-of-phase lows fold to mono
+pub const WIDENER_PARAM_CROSSOVER: u32 = 2;
+
+// =============================================================================
+// Output safety parameter indices
+// =============================================================================
+
+/// Output safety parameter: soft-clip ceiling (0.25-4.0 linear, default 1.0)
+pub const OUTPUT_SAFETY_PARAM_CEILING: u32 = 0;
+/// Output safety parameter: level that counts as a runaway (1.0-64.0 linear,
+/// default 4.0)
+pub const OUTPUT_SAFETY_PARAM_FAULT_LEVEL: u32 = 1;
+/// Output safety parameter: time over the fault level before the emergency
+/// mute trips, in ms (1.0-2000.0, default 100.0)
+pub const OUTPUT_SAFETY_PARAM_FAULT_HOLD: u32 = 2;
+
+const DEFAULT_OUTPUT_CEILING: f32 = 1.0;
+const DEFAULT_OUTPUT_FAULT_LEVEL: f32 = 4.0;
+const DEFAULT_OUTPUT_FAULT_HOLD_MS: f32 = 100.0;
+
+// =============================================================================
+// Waveshaper parameter indices
+// =============================================================================
+
+/// Waveshaper parameter: drive amount (1.0-10.0)
+pub const WAVESHAPER_PARAM_DRIVE: u32 = 0;
+/// Waveshaper parameter: dry/wet mix (0.0-1.0)
+pub const WAVESHAPER_PARAM_MIX: u32 = 1;
+
+// =============================================================================
+// Feedback waveshaper parameter indices
+// =============================================================================
+
+/// Feedback waveshaper parameter: drive amount (1.0-100.0)
+pub const FEEDBACK_WAVESHAPER_PARAM_DRIVE: u32 = 0;
+/// Feedback waveshaper parameter: feedback gain (0.0-0.98)
+pub const FEEDBACK_WAVESHAPER_PARAM_FEEDBACK: u32 = 1;
+/// Feedback waveshaper parameter: feedback lowpass cutoff (200-20000 Hz)
+pub const FEEDBACK_WAVESHAPER_PARAM_FILTER_CUTOFF: u32 = 2;
+/// Feedback waveshaper parameter: dry/wet mix (0.0-1.0)
+pub const FEEDBACK_WAVESHAPER_PARAM_MIX: u32 = 3;
+
+// =============================================================================
+// Kick drum parameter indices (must match Swift KickParam enum)
+// =============================================================================
+
+/// Kick parameter: base frequency (30-80 Hz)
+pub const KICK_PARAM_FREQUENCY: u32 = 0;
+/// Kick parameter: punch/mid presence (0-1)
+pub const KICK_PARAM_PUNCH: u32 = 1;
+/// Kick parameter: sub bass presence (0-1)
+pub const KICK_PARAM_SUB: u32 = 2;
+/// Kick parameter: click/transient amount (0-1)
+pub const KICK_PARAM_CLICK: u32 = 3;
+/// Kick parameter: decay time (0.01-5.0 seconds)
+pub const KICK_PARAM_DECAY: u32 = 4;
+/// Kick parameter: pitch envelope amount (0-1)
+pub const KICK_PARAM_PITCH_ENVELOPE: u32 = 5;
+/// Kick parameter: overall volume (0-1)
+pub const KICK_PARAM_VOLUME: u32 = 6;
+/// Kick parameter: tuning offset (0=−12 semitones, 0.5=neutral, 1=+12 semitones)
+pub const KICK_PARAM_TUNING: u32 = 7;
+
+// =============================================================================
+// Hi-hat parameter indices (must match Swift HiHatParam enum)
+// =============================================================================
+
+/// Hi-hat parameter: pitch (0-1 normalized)
+pub const HIHAT_PARAM_PITCH: u32 = 0;
+/// Hi-hat parameter: decay (0-1 normalized)
+pub const HIHAT_PARAM_DECAY: u32 = 1;
+/// Hi-hat parameter: attack (0-1 normalized)
+pub const HIHAT_PARAM_ATTACK: u32 = 2;
+/// Hi-hat parameter: tone (0-1 normalized)
+pub const HIHAT_PARAM_TONE: u32 = 3;
+/// Hi-hat parameter: volume (0-1 normalized)
+pub const HIHAT_PARAM_VOLUME: u32 = 4;
+/// Hi-hat parameter: tuning offset (0=−12 semitones, 0.5=neutral, 1=+12 semitones)
+pub const HIHAT_PARAM_TUNING: u32 = 5;
+/// Hi-hat parameter: velocity-to-level depth (0-1; 1 = loudness follows velocity)
+pub const HIHAT_PARAM_VELOCITY_TO_LEVEL: u32 = 6;
+/// Hi-hat parameter: velocity-to-decay depth (0-1; soft hits are shorter)
+pub const HIHAT_PARAM_VELOCITY_TO_DECAY: u32 = 7;
+/// Hi-hat parameter: velocity-to-tone depth (0-1; soft hits are darker)
+pub const HIHAT_PARAM_VELOCITY_TO_TONE: u32 = 8;
+/// Hi-hat parameter: decay of open hits (0-1 normalized)
+pub const HIHAT_PARAM_OPEN_DECAY: u32 = 9;
+/// Hi-hat parameter: articulation for hits without a per-step articulation
+/// (HIHAT_ARTICULATION_*)
+pub const HIHAT_PARAM_ARTICULATION: u32 = 10;
+/// Hi-hat parameter: synthesis engine (HIHAT_MODE_*)
+pub const HIHAT_PARAM_MODE: u32 = 11;
+
+/// Hi-hat mode: two phase-modulated sines (the default)
+pub const HIHAT_MODE_PHASE_MOD: u8 = 0;
+/// Hi-hat mode: six square oscillators at the TR-808's ratios into a bandpass
+pub const HIHAT_MODE_808: u8 = 1;
+
+/// Hi-hat articulation: closed (the configured decay)
+pub const HIHAT_ARTICULATION_CLOSED: u8 = 0;
+/// Hi-hat articulation: pedal (shorter, softer and darker than closed)
+pub const HIHAT_ARTICULATION_PEDAL: u8 = 1;
+/// Hi-hat articulation: open (the open decay; choked by the next closed or pedal hit)
+pub const HIHAT_ARTICULATION_OPEN: u8 = 2;
+
+// =============================================================================
+// Snare drum parameter indices (must match Swift SnareParam enum)
+// =============================================================================
+
+/// Snare parameter: base frequency (0-1 → 100-600 Hz)
+pub const SNARE_PARAM_FREQUENCY: u32 = 0;
+/// Snare parameter: decay time (0-1 → 0.05-3.5 seconds)
+pub const SNARE_PARAM_DECAY: u32 = 1;
+/// Snare parameter: brightness/snap amount (0-1)
+pub const SNARE_PARAM_BRIGHTNESS: u32 = 2;
+/// Snare parameter: overall volume (0-1)
+pub const SNARE_PARAM_VOLUME: u32 = 3;
+/// Snare parameter: tonal body amount (0-1)
+pub const SNARE_PARAM_TONAL: u32 = 4;
+/// Snare parameter: noise amount (0-1)
+pub const SNARE_PARAM_NOISE: u32 = 5;
+/// Snare parameter: pitch drop amount (0-1)
+pub const SNARE_PARAM_PITCH_DROP: u32 = 6;
+/// Snare parameter: tonal decay (0-1 → 0-3.5s)
+pub const SNARE_PARAM_TONAL_DECAY: u32 = 7;
+/// Snare parameter: noise decay (0-1 → 0-3.5s)
+pub const SNARE_PARAM_NOISE_DECAY: u32 = 8;
+/// Snare parameter: noise tail decay (0-1 → 0-3.5s)
+pub const SNARE_PARAM_NOISE_TAIL_DECAY: u32 = 9;
+/// Snare parameter: filter cutoff (0-1 → 100-10000 Hz)
+pub const SNARE_PARAM_FILTER_CUTOFF: u32 = 10;
+/// Snare parameter: filter resonance (0-1 → 0.5-10.0)
+pub const SNARE_PARAM_FILTER_RESONANCE: u32 = 11;
+/// Snare parameter: filter type (0=LP, 1=BP, 2=HP, 3=notch)
+pub const SNARE_PARAM_FILTER_TYPE: u32 = 12;
+/// Snare parameter: tonal/noise crossfade (0-1)
+pub const SNARE_PARAM_XFADE: u32 = 13;
+/// Snare parameter: phase modulation amount (0-1, 0 = disabled)
+pub const SNARE_PARAM_PHASE_MOD_AMOUNT: u32 = 14;
+/// Snare parameter: overdrive/saturation (0-1, 0 = bypass)
+pub const SNARE_PARAM_OVERDRIVE: u32 = 15;
+/// Snare parameter: master amplitude decay (0-1 → 0-4.0s)
+pub const SNARE_PARAM_AMP_DECAY: u32 = 16;
+/// Snare parameter: amplitude decay curve (0-1 → 0.1-10.0)
+pub const SNARE_PARAM_AMP_DECAY_CURVE: u32 = 17;
+/// Snare parameter: tonal decay curve (0-1 → 0.1-10.0)
+pub const SNARE_PARAM_TONAL_DECAY_CURVE: u32 = 18;
+/// Snare parameter: tuning offset (0=−12 semitones, 0.5=neutral, 1=+12 semitones)
+pub const SNARE_PARAM_TUNING: u32 = 19;
+/// Snare parameter: noise color (0 = white, 0.5 = pink, 1 = brown, blending between)
+pub const SNARE_PARAM_NOISE_COLOR: u32 = 20;
+/// Snare parameter: crack noise (0 = white, 1 = velvet: sparse impulses, crisper)
+pub const SNARE_PARAM_CRACK_VELVET: u32 = 21;
+
+// =============================================================================
+// Tom drum parameter indices (Tom2 - must match Swift TomParam enum)
+// =============================================================================
+
+/// Tom parameter: tune (0-1 → 0-100, maps to 40-600 Hz)
+pub const TOM_PARAM_TUNE: u32 = 0;
+/// Tom parameter: bend (0-1 → 0-100, pitch envelope depth)
+pub const TOM_PARAM_BEND: u32 = 1;
+/// Tom parameter: tone (0-1 → 0-100, mix control)
+pub const TOM_PARAM_TONE: u32 = 2;
+/// Tom parameter: color (0-1 → 0-100, noise rate / filter cutoff)
+pub const TOM_PARAM_COLOR: u32 = 3;
+/// Tom parameter: decay (0-1 → 0-100, maps to 0.5-4000ms)
+pub const TOM_PARAM_DECAY: u32 = 4;
+/// Tom parameter: membrane mix (0-1 → 0-100, resonator mix amount)
+pub const TOM_PARAM_MEMBRANE: u32 = 5;
+/// Tom parameter: membrane Q (0-1 → 0-100, resonator Q scale)
+pub const TOM_PARAM_MEMBRANE_Q: u32 = 6;
+/// Tom parameter: volume (0-1 → 0-100, overall volume)
+pub const TOM_PARAM_VOLUME: u32 = 7;
+/// Tom parameter: tuning offset (0=−12 semitones, 0.5=neutral, 1=+12 semitones)
+pub const TOM_PARAM_TUNING: u32 = 8;
+/// Tom parameter: standalone triangle oscillator (0 = off, 1 = on)
+pub const TOM_PARAM_TRIANGLE_ENABLE: u32 = 9;
+/// Tom parameter: standalone triangle gain (0-1 → 0-100, 0.5 = Max patch level)
+pub const TOM_PARAM_TRIANGLE_LEVEL: u32 = 10;
+/// Tom parameter: morph oscillator gain (0-1 → 0-100)
+pub const TOM_PARAM_MORPH_LEVEL: u32 = 11;
+/// Tom parameter: membrane resonator input gain (0-1 → 0-100, 0.5 = default,
+/// ±2 octaves at the ends)
+pub const TOM_PARAM_MEMBRANE_GAIN: u32 = 12;
+
+// =============================================================================
+// FM snap parameter indices
+// =============================================================================
+
+/// FM snap parameter: carrier frequency (0-1 → 80-1600 Hz exp)
+pub const FM_SNAP_PARAM_FREQUENCY: u32 = 0;
+/// FM snap parameter: modulator:carrier ratio (0-1 → 0.5-6.0)
+pub const FM_SNAP_PARAM_RATIO: u32 = 1;
+/// FM snap parameter: peak modulation index (0-1 → 0-10)
+pub const FM_SNAP_PARAM_INDEX: u32 = 2;
+/// FM snap parameter: modulation envelope decay (0-1 → 0.5-50 ms exp)
+pub const FM_SNAP_PARAM_SNAP: u32 = 3;
+/// FM snap parameter: amplitude decay (0-1 → 5-1000 ms exp)
+pub const FM_SNAP_PARAM_DECAY: u32 = 4;
+/// FM snap parameter: carrier pitch sweep depth (0-1 → 0-24 semitones)
+pub const FM_SNAP_PARAM_PITCH_DROP: u32 = 5;
+/// FM snap parameter: overall volume (0-1)
+pub const FM_SNAP_PARAM_VOLUME: u32 = 6;
+/// FM snap parameter: tuning offset (0=−12 semitones, 0.5=neutral, 1=+12 semitones)
+pub const FM_SNAP_PARAM_TUNING: u32 = 7;
+
+// =============================================================================
+// Rimshot parameter indices
+// =============================================================================
+
+/// Rimshot parameter: ping pitch (0-1 → 200-2000 Hz exp)
+pub const RIMSHOT_PARAM_TUNE: u32 = 0;
+/// Rimshot parameter: click bandpass centre (0-1 → 1-10 kHz exp)
+pub const RIMSHOT_PARAM_TONE: u32 = 1;
+/// Rimshot parameter: click level (0-1)
+pub const RIMSHOT_PARAM_CLICK: u32 = 2;
+/// Rimshot parameter: ping decay (0-1 → 5-300 ms exp)
+pub const RIMSHOT_PARAM_DECAY: u32 = 3;
+/// Rimshot parameter: overall volume (0-1)
+pub const RIMSHOT_PARAM_VOLUME: u32 = 4;
+/// Rimshot parameter: tuning offset (0=−12 semitones, 0.5=neutral, 1=+12 semitones)
+pub const RIMSHOT_PARAM_TUNING: u32 = 5;
+
+// =============================================================================
+// Cowbell parameter indices
+// =============================================================================
+
+/// Cowbell parameter: lower square's pitch (0-1 → 300-1200 Hz exp; the upper
+/// square sits at 800/540 of it)
+pub const COWBELL_PARAM_PITCH: u32 = 0;
+/// Cowbell parameter: bandpass centre (0-1 → 1-6 kHz exp)
+pub const COWBELL_PARAM_TONE: u32 = 1;
+/// Cowbell parameter: tail decay (0-1 → 50-2000 ms exp)
+pub const COWBELL_PARAM_DECAY: u32 = 2;
+/// Cowbell parameter: overall volume (0-1)
+pub const COWBELL_PARAM_VOLUME: u32 = 3;
+/// Cowbell parameter: tuning offset (0=−12 semitones, 0.5=neutral, 1=+12 semitones)
+pub const COWBELL_PARAM_TUNING: u32 = 4;
+
+// =============================================================================
+// Shaker parameter indices
+// =============================================================================
+
+/// Shaker parameter: grain density (0-1 → 200-5000 grains/s exp)
+pub const SHAKER_PARAM_DENSITY: u32 = 0;
+/// Shaker parameter: highpass cutoff (0-1 → 1-10 kHz exp)
+pub const SHAKER_PARAM_COLOR: u32 = 1;
+/// Shaker parameter: swell into the hit (0-1 → 0.5-50 ms exp)
+pub const SHAKER_PARAM_ATTACK: u32 = 2;
+/// Shaker parameter: hit decay (0-1 → 20-600 ms exp)
+pub const SHAKER_PARAM_DECAY: u32 = 3;
+/// Shaker parameter: how far velocity moves density and color (0-1)
+pub const SHAKER_PARAM_ACCENT: u32 = 4;
+/// Shaker parameter: overall volume (0-1)
+pub const SHAKER_PARAM_VOLUME: u32 = 5;
+/// Shaker parameter: tuning offset, moving the highpass (0=−12 semitones,
+/// 0.5=neutral, 1=+12 semitones)
+pub const SHAKER_PARAM_TUNING: u32 = 6;
+
+// =============================================================================
+// Instrument IDs (must match Swift/C enum if used)
+// =============================================================================
+
+/// Instrument ID: kick drum
+pub const INSTRUMENT_KICK: u32 = 0;
+/// Instrument ID: snare drum
+pub const INSTRUMENT_SNARE: u32 = 1;
+/// Instrument ID: hi-hat
+pub const INSTRUMENT_HIHAT: u32 = 2;
+/// Instrument ID: tom drum
+pub const INSTRUMENT_TOM: u32 = 3;
+/// Instrument ID: bass synth
+pub const INSTRUMENT_BASS: u32 = 4;
+/// Instrument ID: FM snap (two-operator FM percussion)
+pub const INSTRUMENT_FM_SNAP: u32 = 5;
+/// Instrument ID: rimshot (bandpassed click over a tuned ping)
+pub const INSTRUMENT_RIMSHOT: u32 = 6;
+/// Instrument ID: cowbell (two detuned squares through a bandpass)
+pub const INSTRUMENT_COWBELL: u32 = 7;
+/// Instrument ID: shaker (granular noise bursts)
+pub const INSTRUMENT_SHAKER: u32 = 8;
+/// Total number of instruments
+pub const INSTRUMENT_COUNT: u32 = 9;
+/// Internal usize version for array indexing
+const NUM_INSTRUMENTS: usize = INSTRUMENT_COUNT as usize;
+/// Maximum addressable channels: the built-in voices (channels
+/// `0..INSTRUMENT_COUNT`) followed by host-created instrument slots.
+pub const CHANNEL_MAX: u32 = 16;
+/// Internal usize version for array indexing
+const NUM_CHANNELS: usize = CHANNEL_MAX as usize;
+/// Number of host-created slots after the built-in voices.
+const SLOT_COUNT: usize = NUM_CHANNELS - NUM_INSTRUMENTS;
+const DEFAULT_MASTER_GAIN: f32 = 0.25;
+
+/// Number of stereo loop-mixer channels (see `gooey_engine_loop_*`).
+pub const LOOP_CHANNEL_COUNT: u32 = crate::mixer::LOOP_CHANNEL_COUNT as u32;
+
+/// Mixer graph source: the drum kit (kick/snare/hihat/tom summed).
+pub const SOURCE_DRUMKIT: u32 = crate::mixer::graph::SOURCE_DRUMKIT;
+/// Mixer graph source: the bass voice.
+pub const SOURCE_BASS: u32 = crate::mixer::graph::SOURCE_BASS;
+/// Mixer graph source: the polyphonic synth.
+pub const SOURCE_POLYSYNTH: u32 = crate::mixer::graph::SOURCE_POLYSYNTH;
+/// Mixer graph source: the granulator.
+pub const SOURCE_GRANULATOR: u32 = crate::mixer::graph::SOURCE_GRANULATOR;
+/// Mixer graph source: the stereo loop mixer.
+pub const SOURCE_LOOPMIXER: u32 = crate::mixer::graph::SOURCE_LOOPMIXER;
+/// First source ID reserved for registered sampler racks. Rack `n` uses
+/// `SOURCE_SAMPLER_BASE + n`; only registered racks are routable.
+pub const SOURCE_SAMPLER_BASE: u32 = crate::mixer::graph::SOURCE_SAMPLER_BASE;
+/// Number of routable mixer graph sources.
+pub const SOURCE_COUNT: u32 = crate::mixer::graph::SOURCE_COUNT as u32;
+
+// =============================================================================
+// Preset blend constants
+// =============================================================================
+
+/// Kick preset: Tight - short, punchy kick with strong pitch envelope
+pub const KICK_PRESET_TIGHT: u32 = 0;
+/// Kick preset: Punch - mid-focused with click and resonant noise
+pub const KICK_PRESET_PUNCH: u32 = 1;
+/// Kick preset: Loose - longer decay, more punch, subtle pitch envelope
+pub const KICK_PRESET_LOOSE: u32 = 2;
+/// Kick preset: Dirt - higher frequency, more noise with high resonance
+pub const KICK_PRESET_DIRT: u32 = 3;
+
+/// Tom preset: Derp - punchy mid tom
+pub const TOM_PRESET_DERP: u32 = 0;
+/// Tom preset: Ring - high, long decay
+pub const TOM_PRESET_RING: u32 = 1;
+/// Tom preset: Brush - low, textured
+pub const TOM_PRESET_BRUSH: u32 = 2;
+/// Tom preset: Void - atmospheric, long
+pub const TOM_PRESET_VOID: u32 = 3;
+
+// =============================================================================
+// Bass synth parameter constants
+// =============================================================================
+
+/// Bass parameter: base frequency (0-1 -> 30-200 Hz)
+pub const BASS_PARAM_FREQUENCY: u32 = 0;
+/// Bass parameter: sub sine level (0-1)
+pub const BASS_PARAM_SUB_LEVEL: u32 = 1;
+/// Bass parameter: main saw/square level (0-1)
+pub const BASS_PARAM_OSC_LEVEL: u32 = 2;
+/// Bass parameter: detuned layer level (0-1)
+pub const BASS_PARAM_DETUNE_LEVEL: u32 = 3;
+/// Bass parameter: detune spread (0-1 -> 0-30 cents)
+pub const BASS_PARAM_DETUNE_AMOUNT: u32 = 4;
+/// Bass parameter: oscillator shape - saw(0) to square(1)
+pub const BASS_PARAM_OSC_SHAPE: u32 = 5;
+/// Bass parameter: filter cutoff (0-1 -> 20-18000 Hz exp)
+pub const BASS_PARAM_FILTER_CUTOFF: u32 = 6;
+/// Bass parameter: filter resonance (0-1 -> 0.5-15.0 Q)
+pub const BASS_PARAM_FILTER_RESONANCE: u32 = 7;
+/// Bass parameter: filter envelope depth (0-1)
+pub const BASS_PARAM_FILTER_ENV_AMOUNT: u32 = 8;
+/// Bass parameter: filter envelope decay (0-1 -> 0.01-2.0s)
+pub const BASS_PARAM_FILTER_ENV_DECAY: u32 = 9;
+/// Bass parameter: filter envelope curve (0-1 -> 0.1-8.0)
+pub const BASS_PARAM_FILTER_ENV_CURVE: u32 = 10;
+/// Bass parameter: amplitude decay (0-1 -> 0.05-4.0s)
+pub const BASS_PARAM_AMP_DECAY: u32 = 11;
+/// Bass parameter: amplitude decay curve (0-1 -> 0.1-10.0)
+pub const BASS_PARAM_AMP_DECAY_CURVE: u32 = 12;
+/// Bass parameter: pre-filter overdrive/saturation (0-1)
+pub const BASS_PARAM_OVERDRIVE: u32 = 13;
+/// Bass parameter: master volume (0-1)
+pub const BASS_PARAM_VOLUME: u32 = 14;
+/// Bass parameter: tuning offset (0=−12 semitones, 0.5=neutral, 1=+12 semitones)
+pub const BASS_PARAM_TUNING: u32 = 15;
+
+// =============================================================================
+// Granulator parameter constants
+// =============================================================================
+//
+// All values are normalized 0.0-1.0. Mapping to internal units (ms, Hz, ratio)
+// happens inside `Granulator`; see `src/instruments/granulator.rs`.
+
+/// Granulator parameter: scan position into the loaded buffer (0=start, 1=end)
+pub const GRANULATOR_PARAM_SCAN_POSITION: u32 = 0;
+/// Granulator parameter: grain length (0-1 -> 5-3000 ms, quadratic curve)
+pub const GRANULATOR_PARAM_GRAIN_LENGTH: u32 = 1;
+/// Granulator parameter: spray / randomization of grain start (0-1 -> 0-10 s, cubic)
+pub const GRANULATOR_PARAM_SPRAY: u32 = 2;
+/// Granulator parameter: playback pitch (0-1 -> 0.25x-4x, exponential)
+pub const GRANULATOR_PARAM_PITCH: u32 = 3;
+/// Granulator parameter: density of grains per second (0-1 -> 0-80 g/s)
+pub const GRANULATOR_PARAM_DENSITY: u32 = 4;
+/// Granulator parameter: window shape / texture (0=soft, 1=hard)
+pub const GRANULATOR_PARAM_TEXTURE: u32 = 5;
+/// Granulator parameter: probability a grain plays in reverse (0=forward only, 1=reverse only)
+pub const GRANULATOR_PARAM_DIRECTION: u32 = 6;
+/// Granulator parameter: cloud duration after a trigger (0-1 -> 50-8000 ms, quadratic)
+pub const GRANULATOR_PARAM_CLOUD_DURATION: u32 = 7;
+/// Granulator parameter: output volume (0-1)
+pub const GRANULATOR_PARAM_VOLUME: u32 = 8;
+/// Granulator parameter: per-grain spawn-time jitter (0=periodic, 1=±100% of interval)
+pub const GRANULATOR_PARAM_RANDOM_TIMING: u32 = 9;
+/// Granulator parameter: per-grain random amplitude (0=uniform, 1=full random duck to 0)
+pub const GRANULATOR_PARAM_RANDOM_AMP: u32 = 10;
+/// Granulator parameter: output soft-saturation drive (0=clean, 1=heavy tanh)
+pub const GRANULATOR_PARAM_DRIVE: u32 = 11;
+/// Total number of granulator parameters
+pub const GRANULATOR_PARAM_COUNT: u32 = 12;
+
+/// Bass preset: Acid - TB-303-style, high resonance, short filter sweep
+pub const BASS_PRESET_ACID: u32 = 0;
+/// Bass preset: Sub - clean sub-bass, sine dominant
+pub const BASS_PRESET_SUB: u32 = 1;
+/// Bass preset: Reese - detuned saws, heavy overdrive
+pub const BASS_PRESET_REESE: u32 = 2;
+/// Bass preset: Stab - square wave, sharp filter, short decay
+pub const BASS_PRESET_STAB: u32 = 3;
+
+/// Sentinel value indicating no MIDI note is set for a step (use instrument's global frequency)
+pub const STEP_NOTE_NONE: u8 = 255;
+
+/// Sentinel value indicating no articulation is set for a step (use the instrument's default)
+pub const STEP_ARTICULATION_NONE: u8 = 255;
+
+/// Snare preset: Tight - short, punchy snare
+pub const SNARE_PRESET_TIGHT: u32 = 0;
+/// Snare preset: Loose - longer decay, more body
+pub const SNARE_PRESET_LOOSE: u32 = 1;
+/// Snare preset: Hiss - noise-focused with phase modulation
+pub const SNARE_PRESET_HISS: u32 = 2;
+/// Snare preset: Smack - DS-style transient with SVF noise
+pub const SNARE_PRESET_SMACK: u32 = 3;
+
+/// Hi-hat preset: Short
+pub const HIHAT_PRESET_SHORT: u32 = 0;
+/// Hi-hat preset: Loose
+pub const HIHAT_PRESET_LOOSE: u32 = 1;
+/// Hi-hat preset: Dark
+pub const HIHAT_PRESET_DARK: u32 = 2;
+/// Hi-hat preset: Soft
+pub const HIHAT_PRESET_SOFT: u32 = 3;
+/// Hi-hat preset: Classic - Short on the 808 engine
+pub const HIHAT_PRESET_CLASSIC: u32 = 4;
+/// Hi-hat preset: Classic Loose - Loose on the 808 engine
+pub const HIHAT_PRESET_CLASSIC_LOOSE: u32 = 5;
+
+/// FM snap preset: Snap - bright, metallic and very short
+pub const FM_SNAP_PRESET_SNAP: u32 = 0;
+/// FM snap preset: Rim - high, woody-metallic rimshot click
+pub const FM_SNAP_PRESET_RIM: u32 = 1;
+/// FM snap preset: Wood - nearly pure, clave-like knock
+pub const FM_SNAP_PRESET_WOOD: u32 = 2;
+/// FM snap preset: Zap - deep pitch sweep with a long modulation tail
+pub const FM_SNAP_PRESET_ZAP: u32 = 3;
+
+/// Rimshot preset: Classic - the 808 rimshot, a ~455 Hz ping with a bright tick
+pub const RIMSHOT_PRESET_CLASSIC: u32 = 0;
+/// Rimshot preset: Tight - higher, shorter and mostly click
+pub const RIMSHOT_PRESET_TIGHT: u32 = 1;
+/// Rimshot preset: Woody - low ping and a dull click
+pub const RIMSHOT_PRESET_WOODY: u32 = 2;
+/// Rimshot preset: Ring - a long, pitched ping with little click
+pub const RIMSHOT_PRESET_RING: u32 = 3;
+
+/// Cowbell preset: Classic - the 808 cowbell, 540/800 Hz through a ~2.6 kHz bandpass
+pub const COWBELL_PRESET_CLASSIC: u32 = 0;
+/// Cowbell preset: Bright - higher and cutting, with a shorter tail
+pub const COWBELL_PRESET_BRIGHT: u32 = 1;
+/// Cowbell preset: Dark - low and hollow, ringing longer
+pub const COWBELL_PRESET_DARK: u32 = 2;
+/// Cowbell preset: Short - a choked clank
+pub const COWBELL_PRESET_SHORT: u32 = 3;
+
+/// Shaker preset: Tight - a short, bright maraca stroke
+pub const SHAKER_PRESET_TIGHT: u32 = 0;
+/// Shaker preset: Swish - a slower shake that swells in
+pub const SHAKER_PRESET_SWISH: u32 = 1;
+/// Shaker preset: Cabasa - dense beads on metal, very bright
+pub const SHAKER_PRESET_CABASA: u32 = 2;
+/// Shaker preset: Egg - sparse, darker seeds in a small shell
+pub const SHAKER_PRESET_EGG: u32 = 3;
+
+/// Blend corner: bottom-left (x=0, y=0)
+pub const BLEND_CORNER_BOTTOM_LEFT: u32 = 0;
+/// Blend corner: bottom-right (x=1, y=0)
+pub const BLEND_CORNER_BOTTOM_RIGHT: u32 = 1;
+/// Blend corner: top-left (x=0, y=1)
+pub const BLEND_CORNER_TOP_LEFT: u32 = 2;
+/// Blend corner: top-right (x=1, y=1)
+pub const BLEND_CORNER_TOP_RIGHT: u32 = 3;
+
+/// Preset ID standing for an instrument's default sound (its freshly created
+/// config), for `gooey_engine_measure_preset_loudness`
+pub const PRESET_DEFAULT_SOUND: u32 = 0xFFFFFFFF;
+
+// =============================================================================
+// Engine lifecycle
+// =============================================================================
+
+/// Create a new gooey engine
+///
+/// # Arguments
+/// * `sample_rate` - Audio sample rate (e.g., 44100.0 or 48000.0)
+///
+/// # Returns
+/// Pointer to a new GooeyEngine instance. Must be freed with `gooey_engine_free`.
+///
+/// # Safety
+/// The returned pointer must be freed with `gooey_engine_free` to avoid memory leaks.
+#[no_mangle]
+pub extern "C" fn gooey_engine_new(sample_rate: f32) -> *mut GooeyEngine {
+    let engine = Box::new(GooeyEngine::new(sample_rate));
+    Box::into_raw(engine)
+}
+
+/// Create a gooey engine that runs its DSP at one rate and renders at another
+///
+/// Use this when the device rate differs from the rate content is tuned for
+/// (e.g. a 48 kHz output for an engine built around 44.1 kHz). Everything
+/// inside the engine (instruments, sequencer timing, effects, recordings,
+/// bounces) runs at `sample_rate`; `gooey_engine_render` converts to
+/// `output_sample_rate` with a windowed-sinc resampler, so pitches and tempo
+/// stay where they are. `frames` passed to `gooey_engine_render` then count
+/// output frames, and the engine renders ahead by up to a few hundred of its
+/// own frames to feed the converter. Equal rates behave like
+/// `gooey_engine_new`.
+///
+/// # Arguments
+/// * `sample_rate` - Internal DSP rate (e.g., 44100.0)
+/// * `output_sample_rate` - Rate of the buffers passed to `gooey_engine_render`
+///   (e.g., 48000.0)
+///
+/// # Returns
+/// Pointer to a new GooeyEngine instance, or null if either rate is not
+/// positive or they are more than 8x apart. Must be freed with
+/// `gooey_engine_free`.
+///
+/// # Safety
+/// The returned pointer must be freed with `gooey_engine_free` to avoid memory leaks.
+#[no_mangle]
+pub extern "C" fn gooey_engine_new_with_output_rate(
+    sample_rate: f32,
+    output_sample_rate: f32,
+) -> *mut GooeyEngine {
+    const FN: &str = "gooey_engine_new_with_output_rate";
+    let ratio = sample_rate / output_sample_rate;
+    if !(sample_rate > 0.0 && output_sample_rate > 0.0 && (0.125..=8.0).contains(&ratio)) {
+        fail(
+            GooeyResult::InvalidValue,
+            format!("{FN}: cannot convert {sample_rate} Hz to {output_sample_rate} Hz"),
+        );
+        return std::ptr::null_mut();
+    }
+    let mut engine = Box::new(GooeyEngine::new(sample_rate));
+    if sample_rate != output_sample_rate {
+        engine.resampler = Some(Resampler::new(sample_rate, output_sample_rate));
+    }
+    Box::into_raw(engine)
+}
+
+/// Rate of the buffers `gooey_engine_render` fills: the output rate given
+/// to `gooey_engine_new_with_output_rate`, otherwise the engine's own rate.
+/// 0 for a null engine.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_output_sample_rate(engine: *const GooeyEngine) -> f32 {
+    engine
+        .as_ref()
+        .map_or(0.0, |engine| match &engine.resampler {
+            Some(resampler) => (engine.sample_rate as f64 / resampler.ratio()) as f32,
+            None => engine.sample_rate,
+        })
+}
+
+/// Free a gooey engine
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`, or null.
+/// After calling this function, the pointer is invalid and must not be used.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_free(engine: *mut GooeyEngine) {
+    if !engine.is_null() {
+        drop(Box::from_raw(engine));
+    }
+}
+
+// =============================================================================
+// Audio rendering
+// =============================================================================
+
+/// Number of interleaved output channels produced by `gooey_engine_render`.
+/// The render buffer must hold `frames * GOOEY_OUTPUT_CHANNELS` floats.
+pub const GOOEY_OUTPUT_CHANNELS: u32 = 2;
+
+/// Render interleaved stereo audio into the provided buffer
+///
+/// This is the main audio callback function. Call this from your audio thread
+/// to generate audio samples. Output is two-channel: the buffer holds `frames`
+/// stereo frames laid out as interleaved `[left, right]` pairs, so the caller
+/// must provide `frames * GOOEY_OUTPUT_CHANNELS` (`frames * 2`) floats. The
+/// signal path is currently mono, so left and right are identical, but callers
+/// should always treat the buffer as stereo.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `buffer` - Pointer to a buffer of floats to fill with interleaved L/R audio
+/// * `frames` - Number of stereo frames to render
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `buffer` must point to at least `frames * 2` floats of allocated memory
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_render(
+    engine: *mut GooeyEngine,
+    buffer: *mut f32,
+    frames: u32,
+) {
+    if engine.is_null() || buffer.is_null() {
+        return;
+    }
+
+    // Flush-to-zero for the whole block; the previous FPU mode is restored on return.
+    let _ftz = DenormalGuard::new();
+
+    // Whichever thread renders is the audio thread; writes from other threads
+    // are queued from here on (see the threading notes on `GooeyEngine`).
+    (*engine).control.attach_audio_thread();
+    let buffer_slice = slice::from_raw_parts_mut(buffer, frames as usize * 2);
+
+    // Edits posted by control threads run before the block, before `&mut`
+    // is taken (see `RenderGate`).
+    let gate = std::ptr::addr_of!((*engine).gate);
+    (*gate).begin_render(|| {
+        let rate = gooey_engine_get_output_sample_rate(engine).max(1.0);
+        Duration::from_secs_f64(frames as f64 / rate as f64)
+    });
+    (*gate).run_posted_edit();
+    render_gated(&mut *engine, buffer, buffer_slice, frames);
+    (*gate).end_render();
+}
+
+/// The body of `gooey_engine_render`, run while the audio thread holds the
+/// render gate.
+unsafe fn render_gated(
+    engine_ref: &mut GooeyEngine,
+    buffer: *mut f32,
+    buffer_slice: &mut [f32],
+    frames: u32,
+) {
+    // If engine is already in error state, output silence
+    if engine_ref.error_occurred.load(Ordering::Relaxed) {
+        for sample in buffer_slice.iter_mut() {
+            *sample = 0.0;
+        }
+        return;
+    }
+
+    // Wrap render in catch_unwind to prevent panics from crossing the FFI boundary.
+    // AssertUnwindSafe is sound here: after a panic we mark the engine as permanently
+    // errored and never call render() on it again.
+    let started = Instant::now();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        crate::rt_audit::audio_section(|| match engine_ref.resampler.take() {
+            Some(mut resampler) => {
+                resampler.process(buffer_slice, |block| engine_ref.render(block));
+                engine_ref.resampler = Some(resampler);
+            }
+            None => engine_ref.render(buffer_slice),
+        })
+    }));
+    // Load is measured against the engine-rate frames this buffer lasts
+    let engine_frames = match &engine_ref.resampler {
+        Some(resampler) => (frames as f64 * resampler.ratio()).round() as usize,
+        None => frames as usize,
+    };
+    engine_ref.record_cpu_load(started.elapsed(), engine_frames);
+
+    if let Err(panic_payload) = result {
+        let msg = if let Some(s) = panic_payload.downcast_ref::<&str>() {
+            format!("gooey: render panic: {}", s)
+        } else if let Some(s) = panic_payload.downcast_ref::<String>() {
+            format!("gooey: render panic: {}", s)
+        } else {
+            "gooey: render panic (unknown cause)".to_string()
+        };
+
+        let c_msg = CString::new(msg).unwrap_or_else(|_| {
+            CString::new("gooey: render panic (message contained null byte)").unwrap()
+        });
+        let msg_ptr = c_msg.as_ptr();
+
+        engine_ref.error_message = Some(c_msg);
+        engine_ref.error_occurred.store(true, Ordering::Release);
+
+        // Zero the output buffer (interleaved stereo: frames * 2 floats)
+        let buffer_slice = slice::from_raw_parts_mut(buffer, frames as usize * 2);
+        for sample in buffer_slice.iter_mut() {
+            *sample = 0.0;
+        }
+
+        // Invoke error callback if registered
+        if let Some(callback) = engine_ref.error_callback {
+            callback(engine_ref.error_callback_context, msg_ptr);
+        }
+    }
+}
+
+/// Frames rendered by `gooey_engine_render_block`: one Web Audio
+/// AudioWorklet render quantum.
+pub const RENDER_BLOCK_FRAMES: u32 = 128;
+
+/// Render one fixed-size block of `RENDER_BLOCK_FRAMES` interleaved stereo
+/// frames.
+///
+/// Same as `gooey_engine_render` with `frames = RENDER_BLOCK_FRAMES`, for
+/// hosts with a fixed render quantum (AudioWorklet) that want to keep a single
+/// preallocated output block. Rendering never allocates once the engine is
+/// running.
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `block` must point to at least `RENDER_BLOCK_FRAMES * 2` floats
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_render_block(engine: *mut GooeyEngine, block: *mut f32) {
+    gooey_engine_render(engine, block, RENDER_BLOCK_FRAMES);
+}
+
+impl GooeyEngine {
+    /// Render a block of `N` stereo frames, with the block size fixed at
+    /// compile time (e.g. `render_block::<128>` for an AudioWorklet). The
+    /// block can live on the stack; nothing in the render path allocates.
+    pub fn render_block<const N: usize>(&mut self, block: &mut [[f32; 2]; N]) {
+        // SAFETY: `self` is a live engine and the flattened block holds
+        // exactly N interleaved frames.
+        unsafe { gooey_engine_render(self, block.as_flattened_mut().as_mut_ptr(), N as u32) }
+    }
+}
+
+// =============================================================================
+// MIDI event output
+// =============================================================================
+
+/// Copies pending MIDI events into `out_events` and returns how many were written.
+///
+/// Events are cleared after draining — the caller gets each event exactly once.
+/// Must be called from the audio thread, immediately after `gooey_engine_render()`.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `out_events` - Pointer to a caller-allocated array of GooeyMidiEvent
+/// * `max_events` - Capacity of the `out_events` array
+///
+/// # Returns
+/// Number of events written (0 if none pending or on null input)
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `out_events` must point to at least `max_events` elements of allocated memory
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_drain_midi_events(
+    engine: *mut GooeyEngine,
+    out_events: *mut GooeyMidiEvent,
+    max_events: u32,
+) -> u32 {
+    if engine.is_null() || out_events.is_null() || max_events == 0 {
+        return 0;
+    }
+
+    let engine_ref = &mut *engine;
+    let count = engine_ref
+        .pending_midi_events
+        .len()
+        .min(max_events as usize);
+
+    if count > 0 {
+        std::ptr::copy_nonoverlapping(engine_ref.pending_midi_events.as_ptr(), out_events, count);
+        // Remove only the events that were copied; retain any overflow
+        engine_ref.pending_midi_events.drain(..count);
+    }
+
+    count as u32
+}
+
+// =============================================================================
+// UI event timeline
+// =============================================================================
+
+/// Turn the UI event timeline on or off (off by default)
+///
+/// While on, every instrument hit (sequencer steps and manual triggers) is
+/// queued as a `GooeyTimelineEvent` for `gooey_engine_drain_timeline_events`.
+/// Turning it off discards any events not yet drained, so a UI that goes
+/// away and comes back doesn't replay stale hits.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_timeline_enabled(
+    engine: *mut GooeyEngine,
+    enabled: bool,
+) {
+    let Some(engine) = engine.as_ref() else {
+        return;
+    };
+    engine.timeline_enabled.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        while engine.timeline_rx.try_recv().is_ok() {}
+    }
+}
+
+/// Whether the UI event timeline is on
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_timeline_enabled(engine: *const GooeyEngine) -> bool {
+    engine
+        .as_ref()
+        .is_some_and(|engine| engine.timeline_enabled.load(Ordering::Relaxed))
+}
+
+/// Set the output latency added to timeline host times
+///
+/// `host_time` in each event is when the hit's frame reaches the output:
+/// the host time given to `gooey_engine_set_render_host_time` plus
+/// `latency_frames` (e.g. the hardware output latency plus one buffer, if
+/// the host's render timestamp does not already include them).
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_timeline_latency(
+    engine: *mut GooeyEngine,
+    latency_frames: u32,
+) {
+    if let Some(engine) = engine.as_ref() {
+        engine
+            .timeline_latency_frames
+            .store(latency_frames, Ordering::Relaxed);
+    }
+}
+
+/// Copies queued timeline events into `out_events`, oldest first, and
+/// returns how many were written
+///
+/// Safe to call from a UI thread while the audio thread renders. Events are
+/// removed as they are drained; call repeatedly (e.g. once per display
+/// frame) and schedule each pad flash for its `host_time` on the UI clock,
+/// or compare `sample_time` against the playback position. Up to 256 events
+/// are held between drains; further hits are dropped until the UI catches up.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `out_events` - Pointer to a caller-allocated array of GooeyTimelineEvent
+/// * `max_events` - Capacity of the `out_events` array
+///
+/// # Returns
+/// Number of events written (0 if none pending or on null input)
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `out_events` must point to at least `max_events` elements of allocated memory
+/// - Only one thread may drain timeline events at a time
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_drain_timeline_events(
+    engine: *const GooeyEngine,
+    out_events: *mut GooeyTimelineEvent,
+    max_events: u32,
+) -> u32 {
+    if engine.is_null() || out_events.is_null() || max_events == 0 {
+        return 0;
+    }
+
+    let engine_ref = &*engine;
+    let out = slice::from_raw_parts_mut(out_events, max_events as usize);
+    let mut count = 0;
+    for slot in out.iter_mut() {
+        let Ok(event) = engine_ref.timeline_rx.try_recv() else {
+            break;
+        };
+        *slot = event;
+        count += 1;
+    }
+    count
+}
+
+// =============================================================================
+// Shared parameter table
+// =============================================================================
+
+/// Slots reserved per instrument type in the parameter table.
+pub const PARAM_TABLE_STRIDE: u32 = crate::param_table::PARAM_TABLE_STRIDE;
+/// Total number of slots in the parameter table.
+pub const PARAM_TABLE_LEN: u32 = crate::param_table::PARAM_TABLE_LEN;
+
+/// Turn the shared parameter table on or off (off by default)
+///
+/// While on, each render applies every table slot the host changed since
+/// the previous render, as if it had been passed to the instrument's
+/// `gooey_engine_set_*_param` setter (values are clamped the same way, but
+/// no warnings are recorded). Changes made while off are applied once it is
+/// turned back on.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_param_table_enabled(
+    engine: *mut GooeyEngine,
+    enabled: bool,
+) {
+    if let Some(engine) = engine.as_ref() {
+        engine.param_table_enabled.store(enabled, Ordering::Relaxed);
+    }
+}
+
+/// Whether the shared parameter table is on
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_param_table_enabled(engine: *const GooeyEngine) -> bool {
+    engine
+        .as_ref()
+        .is_some_and(|engine| engine.param_table_enabled.load(Ordering::Relaxed))
+}
+
+/// Pointer to the first of `PARAM_TABLE_LEN` `f32` slots of the parameter
+/// table, or null for a null engine
+///
+/// The pointer is fixed for the lifetime of the engine. Slot
+/// `instrument * PARAM_TABLE_STRIDE + param` drives that registry parameter
+/// (see `gooey_engine_param_table_slot`); other slots are ignored. Write
+/// setter-space values from any thread with plain aligned 32-bit stores;
+/// in a WASM AudioWorklet that is a `Float32Array` over the shared memory
+/// at this offset. Write NaN to stop driving a slot. Every slot starts NaN.
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - Writes must stay within the `PARAM_TABLE_LEN` slots and stop before the
+///   engine is freed
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_param_table_ptr(engine: *mut GooeyEngine) -> *mut f32 {
+    engine.as_ref().map_or(std::ptr::null_mut(), |engine| {
+        engine.param_table.as_mut_ptr()
+    })
+}
+
+/// Parameter table slot of an instrument parameter, or -1 if the instrument
+/// type has no such parameter
+#[no_mangle]
+pub extern "C" fn gooey_engine_param_table_slot(instrument_type: u32, param: u32) -> i32 {
+    crate::param_table::slot_index(instrument_type, param).map_or(-1, |slot| slot as i32)
+}
+
+// =============================================================================
+// DSP load
+// =============================================================================
+
+/// DSP load of the render callback, filled by `gooey_engine_get_cpu_load`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GooeyCpuLoad {
+    /// Render time over buffer time, percent, averaged over about half a
+    /// second of audio
+    pub average: f32,
+    /// Highest single-render load since the previous call, percent
+    pub peak: f32,
+}
+
+/// Read the DSP load: how long `gooey_engine_render` takes relative to the
+/// duration of the audio it renders. Above 100% the audio callback cannot
+/// keep up; on mobile, leave headroom well below that.
+///
+/// # Returns
+/// false (and `out` untouched) for a null pointer
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `out` must point to a writable `GooeyCpuLoad`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_cpu_load(
+    engine: *const GooeyEngine,
+    out: *mut GooeyCpuLoad,
+) -> bool {
+    let (Some(engine), Some(out)) = (engine.as_ref(), out.as_mut()) else {
+        return false;
+    };
+    *out = GooeyCpuLoad {
+        average: engine.cpu_load.average(),
+        peak: engine.cpu_load.take_peak(),
+    };
+    true
+}
+
+/// Average DSP load of one channel's instrument, as a percentage of the
+/// buffer time (the share of `gooey_engine_get_cpu_load` it accounts for)
+///
+/// Measured in debug builds only, since timing every voice on every sample
+/// costs more than it reports.
+///
+/// # Returns
+/// The load, or `f32::NAN` in release builds, for a null engine or an
+/// out-of-range channel
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_channel_cpu_load(
+    engine: *const GooeyEngine,
+    channel: u32,
+) -> f32 {
+    if !cfg!(debug_assertions) {
+        return f32::NAN;
+    }
+    engine
+        .as_ref()
+        .and_then(|engine| engine.cpu_load.channel(channel as usize))
+        .unwrap_or(f32::NAN)
+}
+
+// =============================================================================
+// Overload protection
+// =============================================================================
+
+/// Overload stage: run saturation, compressor and waveshapers without
+/// oversampling.
+pub const OVERLOAD_STAGE_OVERSAMPLING: u32 = 0;
+/// Overload stage: bypass the spring and plate reverbs.
+pub const OVERLOAD_STAGE_REVERB: u32 = 1;
+/// Overload stage: cap the poly synth at 3 voices.
+pub const OVERLOAD_STAGE_VOICES: u32 = 2;
+/// Number of overload stages.
+pub const OVERLOAD_STAGE_COUNT: u32 = 3;
+
+/// Average DSP load (percent) treated as overload until the host sets one.
+const DEFAULT_OVERLOAD_THRESHOLD: f32 = 85.0;
+const DEFAULT_OVERLOAD_PRIORITY: [u32; 3] = [
+    OVERLOAD_STAGE_OVERSAMPLING,
+    OVERLOAD_STAGE_VOICES,
+    OVERLOAD_STAGE_REVERB,
+];
+/// Poly synth voices while `OVERLOAD_STAGE_VOICES` is shed.
+const OVERLOAD_POLY_VOICES: usize = 3;
+
+/// One stage ID per byte, offset by one so 0 ends the list.
+fn pack_overload_priority(stages: &[u32]) -> u32 {
+    stages
+        .iter()
+        .enumerate()
+        .fold(0, |packed, (i, stage)| packed | (stage + 1) << (8 * i))
+}
+
+/// Turn overload protection on or off (off by default)
+///
+/// While on, when the average DSP load (see `gooey_engine_get_cpu_load`)
+/// stays above `threshold_percent` for a second, the engine sheds the next
+/// stage of the priority list (see `gooey_engine_set_overload_priority`)
+/// instead of letting the audio glitch. Each further second of overload
+/// sheds another. Once the load has stayed below 70% of the threshold for
+/// three seconds, the most recently shed stage comes back. Turning
+/// protection off restores every stage on the next render.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `enabled` - Whether to shed quality under overload
+/// * `threshold_percent` - Average load counted as overload (clamped to
+///   0-1000; the default is 85)
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_overload_protection(
+    engine: *mut GooeyEngine,
+    enabled: bool,
+    threshold_percent: f32,
+) {
+    let Some(engine) = engine.as_ref() else {
+        return;
+    };
+    if threshold_percent.is_finite() {
+        engine.overload_threshold.store(
+            threshold_percent.clamp(0.0, 1000.0).to_bits(),
+            Ordering::Relaxed,
+        );
+    }
+    engine.overload_enabled.store(enabled, Ordering::Relaxed);
+}
+
+/// Set the order in which overload protection sheds stages
+///
+/// `stages` lists `OVERLOAD_STAGE_*` IDs, first shed first; stages left out
+/// are never shed. The default order is oversampling, voices, reverb.
+/// Stages already shed stay shed until restored.
+///
+/// # Returns
+/// `GooeyResult::Ok`, `NullPointer` for a null engine (or null `stages`
+/// with a non-zero count), or `InvalidValue` for an unknown or repeated
+/// stage or more than `OVERLOAD_STAGE_COUNT` entries
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `stages` must point to `count` readable `u32`s
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_overload_priority(
+    engine: *mut GooeyEngine,
+    stages: *const u32,
+    count: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_overload_priority";
+    let Some(engine) = engine.as_ref() else {
+        return null_engine(FN);
+    };
+    if count > OVERLOAD_STAGE_COUNT {
+        return fail(
+            GooeyResult::InvalidValue,
+            format!("{FN}: {count} stages exceed the {OVERLOAD_STAGE_COUNT} that exist"),
+        );
+    }
+    if count > 0 && stages.is_null() {
+        return fail(GooeyResult::NullPointer, format!("{FN}: stages is null"));
+    }
+    let stages = if count == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(stages, count as usize)
+    };
+    for (i, &stage) in stages.iter().enumerate() {
+        if stage >= OVERLOAD_STAGE_COUNT || stages[..i].contains(&stage) {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: stage {stage} is unknown or repeated"),
+            );
+        }
+    }
+    engine
+        .overload_priority
+        .store(pack_overload_priority(stages), Ordering::Relaxed);
+    GooeyResult::Ok
+}
+
+/// Stages overload protection has shed: bit `n` is set while
+/// `OVERLOAD_STAGE_n` is shed. 0 means full quality (and for a null engine).
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_degradation(engine: *const GooeyEngine) -> u32 {
+    engine
+        .as_ref()
+        .map_or(0, |engine| engine.degradation.load(Ordering::Relaxed))
+}
+
+// =============================================================================
+// Debug event log
+// =============================================================================
+
+/// Trace event: an instrument hit (`channel`, velocity in `value`).
+pub const TRACE_EVENT_TRIGGER: u32 = 0;
+/// Trace event: a running sequencer moved to step `index` on `channel`.
+pub const TRACE_EVENT_STEP: u32 = 1;
+/// Trace event: parameter `index` on `channel` was set to `value`.
+pub const TRACE_EVENT_PARAM: u32 = 2;
+/// Trace event: the poly synth stole a voice to play MIDI note `index`.
+pub const TRACE_EVENT_VOICE_STEAL: u32 = 3;
+/// Trace event: the host clock skipped `index` frames ahead of the audio.
+pub const TRACE_EVENT_XRUN: u32 = 4;
+
+/// One entry of the debug event log, filled by
+/// `gooey_engine_drain_trace_events`. Fields a kind doesn't use are 0.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GooeyTraceEvent {
+    /// One of the `TRACE_EVENT_*` constants
+    pub kind: u32,
+    /// Engine frame the event happened at (frames rendered since creation)
+    pub frame: u64,
+    pub channel: u32,
+    /// Step, parameter index, MIDI note or missed frames, by kind
+    pub index: u32,
+    /// Velocity or parameter value, by kind
+    pub value: f32,
+}
+
+impl From<crate::trace::TraceRecord> for GooeyTraceEvent {
+    fn from(record: crate::trace::TraceRecord) -> Self {
+        let frame = record.frame;
+        match record.event {
+            TraceEvent::Trigger { channel, velocity } => Self {
+                kind: TRACE_EVENT_TRIGGER,
+                frame,
+                channel,
+                index: 0,
+                value: velocity,
+            },
+            TraceEvent::Step { channel, step } => Self {
+                kind: TRACE_EVENT_STEP,
+                frame,
+                channel,
+                index: step,
+                value: 0.0,
+            },
+            TraceEvent::Param {
+                channel,
+                param,
+                value,
+            } => Self {
+                kind: TRACE_EVENT_PARAM,
+                frame,
+                channel,
+                index: param,
+                value,
+            },
+            TraceEvent::VoiceSteal { note } => Self {
+                kind: TRACE_EVENT_VOICE_STEAL,
+                frame,
+                channel: 0,
+                index: note,
+                value: 0.0,
+            },
+            TraceEvent::Xrun { missed_frames } => Self {
+                kind: TRACE_EVENT_XRUN,
+                frame,
+                channel: 0,
+                index: missed_frames,
+                value: 0.0,
+            },
+        }
+    }
+}
+
+/// Whether the debug event log is compiled in (the `trace` feature). When
+/// it isn't, the engine records nothing and draining always returns 0.
+#[no_mangle]
+pub extern "C" fn gooey_engine_trace_available() -> bool {
+    TraceLog::ENABLED
+}
+
+/// Copies logged engine events into `out_events`, oldest first, and returns
+/// how many were written
+///
+/// The audio thread logs hits, sequencer step changes, parameter writes,
+/// poly voice steals and xruns (detected from gaps in the host time passed
+/// to `gooey_engine_set_render_host_time`, so only while the host supplies
+/// it every buffer). Up to 4096 events are held between drains; events past
+/// that are dropped and counted (see `gooey_engine_trace_take_dropped`).
+/// Safe to call from any one thread while the audio thread renders.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `out_events` - Pointer to a caller-allocated array of GooeyTraceEvent
+/// * `max_events` - Capacity of the `out_events` array
+///
+/// # Returns
+/// Number of events written (0 if none pending, on null input, or when
+/// tracing is not compiled in)
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `out_events` must point to at least `max_events` elements of allocated memory
+/// - Only one thread may drain trace events at a time
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_drain_trace_events(
+    engine: *const GooeyEngine,
+    out_events: *mut GooeyTraceEvent,
+    max_events: u32,
+) -> u32 {
+    if engine.is_null() || out_events.is_null() || max_events == 0 {
+        return 0;
+    }
+
+    let engine_ref = &*engine;
+    let out = slice::from_raw_parts_mut(out_events, max_events as usize);
+    let mut count = 0;
+    for slot in out.iter_mut() {
+        let Some(record) = engine_ref.trace.pop() else {
+            break;
+        };
+        *slot = record.into();
+        count += 1;
+    }
+    count
+}
+
+/// Number of trace events dropped because the log was full, since the last
+/// call (0 for a null engine)
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_trace_take_dropped(engine: *const GooeyEngine) -> u32 {
+    engine
+        .as_ref()
+        .map_or(0, |engine| engine.trace.take_dropped())
+}
+
+// =============================================================================
+// Trigger callback
+// =============================================================================
+
+/// Called for each instrument hit with the host's context pointer, the
+/// channel, the hit velocity and the frame offset within the render call.
+pub type GooeyTriggerCallback = extern "C" fn(*mut c_void, u32, f32, u32);
+
+/// Register a callback for instrument hits
+///
+/// The callback fires for every hit (sequencer steps and manual triggers)
+/// on a channel whose bit is set in `channel_mask`, e.g.
+/// `1 << INSTRUMENT_KICK` to pump visuals with the kick. It runs on the
+/// audio thread inside `gooey_engine_render`, in hit order, before the
+/// render returns; `sample_offset` is the hit's frame within that buffer
+/// (manual triggers land at 0). It must not block, allocate or call back
+/// into the engine; hand the event to another thread if it needs real work.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `context` - Opaque user pointer passed back to the callback (can be null)
+/// * `channel_mask` - Bit `n` selects channel `n`; 0 silences the callback
+/// * `callback` - C function pointer, or null to unregister
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `context` must remain valid while the callback is registered
+/// - Must not be called while another thread is rendering
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_trigger_callback(
+    engine: *mut GooeyEngine,
+    context: *mut c_void,
+    channel_mask: u32,
+    callback: Option<GooeyTriggerCallback>,
+) {
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return;
+        };
+        engine.trigger_callback = callback;
+        engine.trigger_callback_context = context;
+        engine.trigger_callback_mask = channel_mask;
+    })
+}
+
+// =============================================================================
+// Sequencer trigger control
+// =============================================================================
+
+/// Enable or disable note triggering from the internal sequencer.
+///
+/// When disabled (`enabled = false`):
+/// - The sequencer still advances its position each render cycle (step tracking works)
+/// - But the sequencer does NOT call instrument trigger functions or emit MIDI events
+///
+/// When enabled (`enabled = true`, the default):
+/// - Normal behavior — sequencer triggers instruments on active steps
+///
+/// Use this to let the host's MIDI input drive the instruments instead
+/// of the internal sequencer, while keeping position tracking intact.
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_sequencer_triggers_enabled(
+    engine: *mut GooeyEngine,
+    enabled: bool,
+) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        (*engine)
+            .sequencer_triggers_enabled
+            .store(enabled, Ordering::Release);
+    })
+}
+
+/// Query whether sequencer triggers are currently enabled.
+///
+/// Returns `true` if the engine pointer is null (safe default).
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`, or null
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_sequencer_triggers_enabled(
+    engine: *const GooeyEngine,
+) -> bool {
+    if engine.is_null() {
+        return true;
+    }
+    (*engine).sequencer_triggers_enabled.load(Ordering::Acquire)
+}
+
+// =============================================================================
+// Error handling
+// =============================================================================
+
+/// Result code returned by fallible FFI functions.
+///
+/// On anything other than `Ok`, a human-readable diagnostic is available from
+/// `gooey_engine_last_error_message` on the calling thread.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GooeyResult {
+    /// The call succeeded.
+    Ok = 0,
+    /// A required pointer argument (usually `engine`) was null.
+    NullPointer = 1,
+    /// The channel index is out of range.
+    InvalidChannel = 2,
+    /// The instrument ID (`INSTRUMENT_*`) is unknown, or the instrument is not
+    /// loaded on any channel.
+    InvalidInstrument = 3,
+    /// The parameter index is unknown for the target instrument or effect.
+    InvalidParam = 4,
+    /// The effect ID (`EFFECT_*`) is unknown.
+    InvalidEffect = 5,
+    /// The value is out of range or not finite.
+    InvalidValue = 6,
+    /// A write from a control thread could not be queued because the audio
+    /// thread has not drained the control queue; retry after the next render.
+    QueueFull = 7,
+    /// A buffer the call needed could not be allocated.
+    OutOfMemory = 8,
+}
+
+thread_local! {
+    /// Diagnostic for the most recent failing FFI call on this thread.
+    static LAST_ERROR: std::cell::RefCell<Option<CString>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// Record `message` as this thread's last error and return `result`.
+fn fail(result: GooeyResult, message: impl Into<String>) -> GooeyResult {
+    let message = CString::new(message.into())
+        .unwrap_or_else(|_| CString::new("gooey: error message contained null byte").unwrap());
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(message));
+    result
+}
+
+/// The result code a [`GooeyError`] from the Rust API surfaces as.
+impl From<&GooeyError> for GooeyResult {
+    fn from(error: &GooeyError) -> Self {
+        match error {
+            GooeyError::UnknownInstrument(_) => GooeyResult::InvalidInstrument,
+            GooeyError::UnknownParameter(_)
+            | GooeyError::NotModulatable { .. }
+            | GooeyError::ModulationUnsupported(_) => GooeyResult::InvalidParam,
+            GooeyError::QueueFull(_) => GooeyResult::QueueFull,
+            GooeyError::OutOfMemory(_) => GooeyResult::OutOfMemory,
+            GooeyError::IndexOutOfRange { .. }
+            | GooeyError::InvalidValue(_)
+            | GooeyError::InvalidRouting(_)
+            | GooeyError::Parse { .. }
+            | GooeyError::Io(_)
+            | GooeyError::Window(_)
+            | GooeyError::InvalidSnapshot(_) => GooeyResult::InvalidValue,
+        }
+    }
+}
+
+/// Record `error` from a Rust API call as this thread's last error and
+/// return its result code.
+fn fail_with(function: &str, error: GooeyError) -> GooeyResult {
+    fail(GooeyResult::from(&error), format!("{function}: {error}"))
+}
+
+/// Maximum number of warnings kept per thread; the oldest are dropped first.
+pub const MAX_WARNINGS: usize = 32;
+
+thread_local! {
+    /// Non-fatal diagnostics (e.g. clamped values) recorded on this thread.
+    static WARNINGS: std::cell::RefCell<std::collections::VecDeque<CString>> =
+        const { std::cell::RefCell::new(std::collections::VecDeque::new()) };
+}
+
+/// Record a non-fatal diagnostic for the calling thread.
+fn warn(message: impl Into<String>) {
+    let Ok(message) = CString::new(message.into()) else {
+        return;
+    };
+    WARNINGS.with(|slot| {
+        let mut warnings = slot.borrow_mut();
+        if warnings.len() == MAX_WARNINGS {
+            warnings.pop_front();
+        }
+        warnings.push_back(message);
+    });
+}
+
+/// A thread's last error and warnings, moved out of its records. An edit
+/// the audio thread runs for a control thread records into empty ones, and
+/// what it recorded is published on the control thread.
+#[derive(Default)]
+struct Diagnostics {
+    error: Option<CString>,
+    warnings: std::collections::VecDeque<CString>,
+}
+
+impl Diagnostics {
+    /// Empty the calling thread's records into a `Diagnostics`.
+    fn take() -> Self {
+        Self {
+            error: LAST_ERROR.with(|slot| slot.borrow_mut().take()),
+            warnings: WARNINGS.with(|slot| std::mem::take(&mut *slot.borrow_mut())),
+        }
+    }
+
+    /// Put records taken with [`take`](Self::take) back on the calling
+    /// thread.
+    fn restore(self) {
+        LAST_ERROR.with(|slot| *slot.borrow_mut() = self.error);
+        WARNINGS.with(|slot| *slot.borrow_mut() = self.warnings);
+    }
+
+    /// Record these on the calling thread as if its own call had.
+    fn publish(self) {
+        if let Some(error) = self.error {
+            LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(error));
+        }
+        WARNINGS.with(|slot| {
+            let mut warnings = slot.borrow_mut();
+            for warning in self.warnings {
+                if warnings.len() == MAX_WARNINGS {
+                    warnings.pop_front();
+                }
+                warnings.push_back(warning);
+            }
+        });
+    }
+}
+
+fn null_engine(function: &str) -> GooeyResult {
+    fail(
+        GooeyResult::NullPointer,
+        format!("{function}: engine is null"),
+    )
+}
+
+/// Validate a setter `param` and `value` against the parameter registry for
+/// `instrument_type`, indexing its static table by param id. Unknown params
+/// and non-finite values are rejected; out-of-range values are clamped and a
+/// warning is recorded.
+fn clamp_param_value(
+    function: &str,
+    instrument_type: u32,
+    param: u32,
+    value: f32,
+) -> Result<f32, GooeyResult> {
+    let Some(info) = crate::param_info::instrument_params(instrument_type).get(param as usize)
+    else {
+        let name = crate::param_info::instrument_name(instrument_type).unwrap_or("unknown");
+        return Err(fail(
+            GooeyResult::InvalidParam,
+            format!("{function}: param {param} is not a {name} parameter"),
+        ));
+    };
+    if !value.is_finite() {
+        return Err(fail(
+            GooeyResult::InvalidValue,
+            format!("{function}: {} value {value} is not finite", info.name()),
+        ));
+    }
+    let clamped = info.clamp(value);
+    if clamped != value {
+        let (min, max) = info.setter_range();
+        warn(format!(
+            "{function}: {} value {value} is outside {min}..={max}; clamped to {clamped}",
+            info.name()
+        ));
+    }
+    Ok(clamped)
+}
+
+/// Set a parameter on whichever channel holds `instrument_type`, through the
+/// matching `gooey_engine_set_*_param` setter. For Rust front ends (OSC,
+/// plugin) that address parameters by registry entry rather than by setter.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+pub unsafe fn set_instrument_type_param(
+    engine: *mut GooeyEngine,
+    instrument_type: u32,
+    param: u32,
+    value: f32,
+) -> GooeyResult {
+    let set = match instrument_type {
+        INSTRUMENT_KICK => gooey_engine_set_kick_param,
+        INSTRUMENT_SNARE => gooey_engine_set_snare_param,
+        INSTRUMENT_HIHAT => gooey_engine_set_hihat_param,
+        INSTRUMENT_TOM => gooey_engine_set_tom_param,
+        INSTRUMENT_BASS => gooey_engine_set_bass_param,
+        INSTRUMENT_FM_SNAP => gooey_engine_set_fm_snap_param,
+        INSTRUMENT_RIMSHOT => gooey_engine_set_rimshot_param,
+        INSTRUMENT_COWBELL => gooey_engine_set_cowbell_param,
+        INSTRUMENT_SHAKER => gooey_engine_set_shaker_param,
+        _ => {
+            return fail(
+                GooeyResult::InvalidInstrument,
+                format!("set_instrument_type_param: unknown instrument type {instrument_type}"),
+            )
+        }
+    };
+    set(engine, param, value)
+}
+
+/// Get the diagnostic for the most recent failing FFI call made on the
+/// calling thread (a call that returned anything other than `GooeyResult::Ok`).
+///
+/// Returns null if no call has failed on this thread. Successful calls do not
+/// clear the message. The string is owned by the library and remains valid
+/// until the next failing call on the same thread; copy it if you need to
+/// keep it.
+#[no_mangle]
+pub extern "C" fn gooey_engine_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|slot| {
+        slot.borrow()
+            .as_ref()
+            .map_or(std::ptr::null(), |msg| msg.as_ptr())
+    })
+}
+
+/// Number of warnings recorded on the calling thread since the last
+/// `gooey_engine_clear_warnings` (at most `MAX_WARNINGS`).
+///
+/// Warnings describe calls that succeeded only after adjusting their input,
+/// such as a parameter value clamped into its valid range.
+#[no_mangle]
+pub extern "C" fn gooey_engine_warning_count() -> u32 {
+    WARNINGS.with(|slot| slot.borrow().len() as u32)
+}
+
+/// Get a recorded warning, oldest first.
+///
+/// Returns null if `index` is out of range. The string is owned by the
+/// library and remains valid until warnings are cleared or evicted on the
+/// same thread; copy it if you need to keep it.
+#[no_mangle]
+pub extern "C" fn gooey_engine_get_warning(index: u32) -> *const c_char {
+    WARNINGS.with(|slot| {
+        slot.borrow()
+            .get(index as usize)
+            .map_or(std::ptr::null(), |msg| msg.as_ptr())
+    })
+}
+
+/// Discard every warning recorded on the calling thread.
+#[no_mangle]
+pub extern "C" fn gooey_engine_clear_warnings() {
+    WARNINGS.with(|slot| slot.borrow_mut().clear());
+}
+
+/// Register an error callback
+///
+/// The callback will be invoked if a fatal error (e.g., panic) occurs during rendering.
+/// It is called at most once, from the audio thread, immediately after the error.
+/// After the callback fires, the engine is in a terminal error state and must be freed
+/// with `gooey_engine_free` and recreated with `gooey_engine_new`.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `context` - Opaque user pointer passed back to the callback (can be null)
+/// * `callback` - C function pointer, or null to unregister
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `context` must remain valid for the lifetime of the engine
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_error_callback(
+    engine: *mut GooeyEngine,
+    context: *mut c_void,
+    callback: Option<extern "C" fn(*mut c_void, *const c_char)>,
+) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        engine.error_callback = callback;
+        engine.error_callback_context = context;
+    })
+}
+
+/// Check if the engine is in an error state
+///
+/// Returns true if a fatal error has occurred during rendering.
+/// Once in an error state, the engine outputs silence and must be freed and recreated.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_has_error(engine: *const GooeyEngine) -> bool {
+    if engine.is_null() {
+        return false;
+    }
+    (*engine).error_occurred.load(Ordering::Relaxed)
+}
+
+/// Get the error message if the engine is in an error state
+///
+/// Returns a pointer to a null-terminated C string describing the error,
+/// or null if no error has occurred. The string is owned by the engine
+/// and remains valid until `gooey_engine_free` is called.
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - The returned string must not be freed by the caller
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_error_message(
+    engine: *const GooeyEngine,
+) -> *const c_char {
+    if engine.is_null() {
+        return std::ptr::null();
+    }
+    match &(*engine).error_message {
+        Some(msg) => msg.as_ptr(),
+        None => std::ptr::null(),
+    }
+}
+
+// =============================================================================
+// Channel instrument type swap
+// =============================================================================
+
+/// Reassign which synthesizer type runs on a given channel.
+///
+/// After calling this, the channel produces the new instrument's sound.
+/// Resets synth DSP state for that channel (new instrument starts fresh).
+/// A still-sounding old instrument keeps ringing under a 30 ms fade-out, so
+/// swapping mid-hit doesn't click.
+/// Preserves channel-level state: gain, mute, solo, sequencer pattern, blend position.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `channel` - Channel index (a built-in channel or an occupied slot)
+/// * `instrument_type` - Instrument type (an INSTRUMENT_* constant)
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an invalid channel, or an
+/// unknown instrument type.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_channel_instrument_type(
+    engine: *mut GooeyEngine,
+    channel: u32,
+    instrument_type: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_channel_instrument_type";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+    let mut carrier =
+        ChannelInstrument::new(instrument_type, (*engine).sample_rate).map(RetiringVoice::carrier);
+    // The frozen loop and any unused carrier are freed here, not in the edit
+    let (result, _frozen) = edit(engine, || {
+        match (*engine).set_instrument_type(channel, instrument_type, &mut carrier) {
+            Ok(frozen) => (GooeyResult::Ok, frozen),
+            Err(result) => (result, None),
+        }
+    });
+    result
+}
+
+impl GooeyEngine {
+    /// The body of `gooey_engine_set_channel_instrument_type`: swap
+    /// `carrier`'s instrument, built for `instrument_type` on the calling
+    /// thread, onto `channel` (one is built here if `carrier` is `None`).
+    /// Returns the channel's frozen loop, if it had one, for the caller to
+    /// free.
+    fn set_instrument_type(
+        &mut self,
+        channel: u32,
+        instrument_type: u32,
+        carrier: &mut Option<Box<RetiringVoice>>,
+    ) -> Result<Option<FrozenLoop>, GooeyResult> {
+        const FN: &str = "gooey_engine_set_channel_instrument_type";
+        let Some(voice) = self.voice(channel as usize) else {
+            return Err(fail(
+                GooeyResult::InvalidChannel,
+                format!("{FN}: channel {channel} is out of range"),
+            ));
+        };
+
+        // No-op if already the requested type
+        if voice.instrument.instrument_type() == instrument_type {
+            return Ok(None);
+        }
+
+        if carrier.is_none() {
+            *carrier = ChannelInstrument::new(instrument_type, self.sample_rate)
+                .map(RetiringVoice::carrier);
+        }
+        match carrier {
+            Some(tail) if tail.instrument.instrument_type() == instrument_type => {}
+            _ => {
+                return Err(fail(
+                    GooeyResult::InvalidInstrument,
+                    format!("{FN}: unknown instrument type {instrument_type}"),
+                ))
+            }
+        }
+        Ok(self.swap_instrument(channel as usize, carrier))
+    }
+
+    /// Put `carrier`'s instrument on `channel` in place of the old one, which
+    /// fades out in the carrier. Resets the synth-side state of the voice
+    /// (presets, blender, variation) and keeps the channel-level state (gain,
+    /// mute, solo, sequencer pattern, blend position). Allocation-free;
+    /// returns the frozen loop the swap drops, for the caller to free.
+    fn swap_instrument(
+        &mut self,
+        channel: usize,
+        carrier: &mut Option<Box<RetiringVoice>>,
+    ) -> Option<FrozenLoop> {
+        let sample_rate = self.sample_rate;
+        let rng_seed = self.rng_seed;
+        let pitch_ratio = self.master_tuning.ratio();
+        let voice = self.voice_mut(channel)?;
+        let mut tail = carrier.take()?;
+        let instrument_type = tail.instrument.instrument_type();
+
+        tail.swap_with(voice);
+        let frozen = voice.frozen.take();
+        voice
+            .instrument
+            .reseed(Rng::stream(rng_seed, RngStream::Noise, channel as u32).next_u64());
+        voice.instrument.set_pitch_ratio(pitch_ratio);
+        voice.config_fade = None;
+        voice.variation.clear();
+        voice.blender = ChannelBlender::default_for_type(instrument_type);
+        voice.blend_corner_presets = ChannelBlender::default_corner_preset_ids(instrument_type);
+        // The new instrument starts from its default sound at unity preset gain
+        voice.corner_gain_db = PresetBlender::uniform(0.0);
+        voice.loaded_preset = None;
+        voice.preset_gain.set_target(1.0);
+        voice.preset_gain.snap();
+
+        // If blend is enabled, re-apply position with the new blender
+        let blending = voice.blend_enabled.load(Ordering::Relaxed);
+        if blending {
+            let x = voice.blend_x;
+            let y = voice.blend_y;
+            voice.apply_blend(x, y, sample_rate);
+        }
+        // channel gain, mute, solo, sequencer pattern all preserved on the voice
+
+        // Let the old instrument's tail ring out rather than cutting it off
+        self.retire(channel, tail);
+        if blending {
+            self.refresh_preset_gain(channel);
+        }
+        frozen
+    }
+}
+
+/// Returns the current instrument type for a channel.
+///
+/// Default mapping: channel N has instrument_type N.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `channel` - Channel index (0-3)
+///
+/// # Returns
+/// Instrument type (INSTRUMENT_KICK=0, INSTRUMENT_SNARE=1, INSTRUMENT_HIHAT=2, INSTRUMENT_TOM=3),
+/// or 0xFFFFFFFF if invalid.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_channel_instrument_type(
+    engine: *const GooeyEngine,
+    channel: u32,
+) -> u32 {
+    if engine.is_null() {
+        return 0xFFFFFFFF;
+    }
+    let engine = &*engine;
+    match engine.voice(channel as usize) {
+        Some(voice) => voice.instrument.instrument_type(),
+        None => 0xFFFFFFFF,
+    }
+}
+
+// =============================================================================
+// Instrument slots
+// =============================================================================
+
+/// Create an instrument slot holding a fresh `instrument_type` voice, for kits
+/// bigger than the built-in channels (a second hi-hat, an extra snap, ...).
+///
+/// The slot gets its own sequencer (following the engine's tempo, swing and
+/// running position), mixer strip, mute/solo, pan and blend state, and sums
+/// into the drum kit source. Address it with the returned channel in any
+/// channel-based call (`gooey_engine_set_channel_param`,
+/// `gooey_engine_trigger_instrument`, sequencer, blend and mute/solo
+/// functions); `gooey_engine_set_channel_instrument_type` replaces its
+/// instrument.
+///
+/// The voice is built on the calling thread and moved into the slot between
+/// two blocks.
+///
+/// # Returns
+/// The new channel (`INSTRUMENT_COUNT..CHANNEL_MAX`), or -1 for a null engine,
+/// an unknown instrument type, or when every slot is in use.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_create_slot(
+    engine: *mut GooeyEngine,
+    instrument_type: u32,
+) -> i32 {
+    const FN: &str = "gooey_engine_create_slot";
+    if engine.is_null() {
+        null_engine(FN);
+        return -1;
+    }
+    // The voice is built here, then moved into the slot in a short edit. If
+    // another thread fills the slot in between, try the next free one.
+    loop {
+        let Some(index) = edit(engine, || (*engine).slots.iter().position(Option::is_none)) else {
+            fail(
+                GooeyResult::InvalidChannel,
+                format!("{FN}: all {SLOT_COUNT} slots are in use"),
+            );
+            return -1;
+        };
+        let Some(voice) = GooeyEngine::slot_voice(index, instrument_type, (*engine).sample_rate)
+        else {
+            fail(
+                GooeyResult::InvalidInstrument,
+                format!("{FN}: unknown instrument type {instrument_type}"),
+            );
+            return -1;
+        };
+        let mut voice = Some(voice);
+        let channel = edit(engine, || {
+            let engine = &mut *engine;
+            match (engine.slots[index].is_none(), voice.take()) {
+                (true, Some(voice)) => Some(engine.install_slot(index, voice)),
+                _ => None,
+            }
+        });
+        if let Some(channel) = channel {
+            return channel as i32;
+        }
+    }
+}
+
+/// Destroy a slot created by [`gooey_engine_create_slot`], freeing its
+/// channel for reuse. LFO routes targeting the channel are removed, and the
+/// compressor sidechain, ducker and beat repeat fall back to their defaults if
+/// they were sourced from it.
+///
+/// Built-in channels (`0..INSTRUMENT_COUNT`) cannot be destroyed.
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine or a channel that is not
+/// an occupied slot.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_destroy_slot(
+    engine: *mut GooeyEngine,
+    channel: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_destroy_slot";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+    if channel < INSTRUMENT_COUNT {
+        return fail(
+            GooeyResult::InvalidChannel,
+            format!("{FN}: built-in channel {channel} cannot be destroyed"),
+        );
+    }
+    let mut carrier =
+        ChannelInstrument::new(INSTRUMENT_KICK, (*engine).sample_rate).map(RetiringVoice::carrier);
+    // The removed voice is freed here, not in the edit
+    let removed = edit(engine, || {
+        (*engine).destroy_slot(channel as usize, &mut carrier)
+    });
+    if removed.is_none() {
+        return fail(
+            GooeyResult::InvalidChannel,
+            format!("{FN}: channel {channel} is not an occupied slot"),
+        );
+    }
+    GooeyResult::Ok
+}
+
+/// Whether `channel` currently holds an instrument: always true for the
+/// built-in channels, true for slots between create and destroy.
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_channel_exists(
+    engine: *const GooeyEngine,
+    channel: u32,
+) -> bool {
+    engine
+        .as_ref()
+        .is_some_and(|engine| engine.voice(channel as usize).is_some())
+}
+
+/// Set a parameter on a channel's instrument, regardless of what synth type it holds.
+///
+/// Parameter index meaning depends on the channel's current instrument type.
+/// For kick: param 0=frequency, 1=punch, etc. (same as `gooey_engine_set_kick_param`)
+/// For snare: param 0=frequency, 1=decay, etc. (same as `gooey_engine_set_snare_param`)
+/// For hihat: param 0=pitch, 1=decay, etc. (same as `gooey_engine_set_hihat_param`)
+/// For tom: param 0=tune, 1=bend, etc. (same as `gooey_engine_set_tom_param`)
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `channel` - Channel index (0-3)
+/// * `param` - Parameter index (meaning depends on instrument type)
+/// * `value` - Parameter value (0-1 normalized)
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an invalid channel, a
+/// parameter index the channel's current instrument does not have, or a
+/// non-finite value. Out-of-range values are clamped and recorded as a
+/// warning (see `gooey_engine_get_warning`).
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_channel_param(
+    engine: *mut GooeyEngine,
+    channel: u32,
+    param: u32,
+    value: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_channel_param";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+    let engine = &mut *engine;
+    let Some(voice) = engine.voice_mut(channel as usize) else {
+        return fail(
+            GooeyResult::InvalidChannel,
+            format!("{FN}: channel {channel} is out of range"),
+        );
+    };
+    let value = match clamp_param_value(FN, voice.instrument.instrument_type(), param, value) {
+        Ok(value) => value,
+        Err(result) => return result,
+    };
+    engine.submit(
+        FN,
+        ControlCommand::ChannelParam {
+            channel,
+            param,
+            value,
+        },
+    )
+}
+
+/// One entry of a `gooey_engine_set_params_batch` call.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GooeyParamUpdate {
+    /// Channel index; the built-in INSTRUMENT_* IDs are channels 0-5
+    pub channel: u32,
+    /// Parameter index for the channel's current instrument type
+    pub param: u32,
+    /// Parameter value, as for `gooey_engine_set_channel_param`
+    pub value: f32,
+}
+
+/// Set many channel parameters in one call, all taking effect together.
+///
+/// Equivalent to calling `gooey_engine_set_channel_param` for each entry, in
+/// order, except that the whole batch is checked first (nothing is applied if
+/// any entry is invalid) and, from a control thread, is applied by the audio
+/// thread within a single render, so a preset load never plays half old and
+/// half new. One boundary crossing replaces one per parameter.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `updates` - Array of `count` parameter updates
+/// * `count` - Number of updates, at most 1024 (the control queue capacity)
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null pointer, an invalid channel,
+/// parameter or value in any entry, more than 1024 entries, or a control
+/// queue without room for the whole batch. Out-of-range values are clamped
+/// and recorded as warnings.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`, and
+/// `updates` must point to `count` readable `GooeyParamUpdate`s.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_params_batch(
+    engine: *mut GooeyEngine,
+    updates: *const GooeyParamUpdate,
+    count: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_params_batch";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+    if count == 0 {
+        return GooeyResult::Ok;
+    }
+    if updates.is_null() {
+        return fail(GooeyResult::NullPointer, format!("{FN}: updates is null"));
+    }
+    if count as usize > CONTROL_QUEUE_CAPACITY {
+        return fail(
+            GooeyResult::InvalidValue,
+            format!("{FN}: {count} updates exceed the limit of {CONTROL_QUEUE_CAPACITY}"),
+        );
+    }
+    let engine = &mut *engine;
+    let mut commands = Vec::with_capacity(count as usize);
+    for (index, update) in slice::from_raw_parts(updates, count as usize)
+        .iter()
+        .enumerate()
+    {
+        let GooeyParamUpdate {
+            channel,
+            param,
+            value,
+        } = *update;
+        let Some(voice) = engine.voice(channel as usize) else {
+            return fail(
+                GooeyResult::InvalidChannel,
+                format!("{FN}: update {index}: channel {channel} is out of range"),
+            );
+        };
+        let instrument_type = voice.instrument.instrument_type();
+        let value = match clamp_param_value(FN, instrument_type, param, value) {
+            Ok(value) => value,
+            Err(result) => return result,
+        };
+        commands.push(ControlCommand::ChannelParam {
+            channel,
+            param,
+            value,
+        });
+    }
+    engine.submit_batch(FN, &commands)
+}
+
+/// Set parameters of a channel's instrument from a JSON object, all taking
+/// effect together.
+///
+/// `json` is a flat object of parameter names and values, e.g.
+/// `{"frequency": 0.3, "punch": 0.7}`: the shape of the per-instrument
+/// `*Params` interfaces in `include/gooey.d.ts`, so a web host can pass
+/// `JSON.stringify(params)`. Names may be snake_case or camelCase; values
+/// are in setter space, as for `gooey_engine_set_channel_param`. Only the
+/// parameters present are changed. The object is checked and applied like
+/// `gooey_engine_set_params_batch`: nothing changes if any entry is invalid.
+///
+/// # Returns
+/// `GooeyResult::Ok`; `NullPointer` for a null engine or `json`;
+/// `InvalidChannel` for an empty or out-of-range channel; `InvalidValue` for
+/// malformed JSON, a non-numeric value or invalid UTF-8; `InvalidParam` for
+/// an unknown name; `QueueFull` as for the batch setter. Out-of-range
+/// values are clamped and recorded as warnings.
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `json` must be a valid nul-terminated string
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_channel_params_json(
+    engine: *mut GooeyEngine,
+    channel: u32,
+    json: *const c_char,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_channel_params_json";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+    if json.is_null() {
+        return fail(GooeyResult::NullPointer, format!("{FN}: json is null"));
+    }
+    let Ok(json) = CStr::from_ptr(json).to_str() else {
+        return fail(
+            GooeyResult::InvalidValue,
+            format!("{FN}: json is not valid UTF-8"),
+        );
+    };
+    let engine = &mut *engine;
+    let Some(voice) = engine.voice(channel as usize) else {
+        return fail(
+            GooeyResult::InvalidChannel,
+            format!("{FN}: channel {channel} is out of range"),
+        );
+    };
+    let instrument_type = voice.instrument.instrument_type();
+    let params = match crate::param_info::parse_params_json(instrument_type, json) {
+        Ok(params) => params,
+        Err(error) => {
+            let result = match error {
+                crate::param_info::ParamsJsonError::Malformed(_) => GooeyResult::InvalidValue,
+                crate::param_info::ParamsJsonError::UnknownParam(_) => GooeyResult::InvalidParam,
+            };
+            return fail(result, format!("{FN}: {error}"));
+        }
+    };
+    let mut commands = Vec::with_capacity(params.len());
+    for (param, value) in params {
+        let value = match clamp_param_value(FN, instrument_type, param, value) {
+            Ok(value) => value,
+            Err(result) => return result,
+        };
+        commands.push(ControlCommand::ChannelParam {
+            channel,
+            param,
+            value,
+        });
+    }
+    engine.submit_batch(FN, &commands)
+}
+
+/// Get the current value of a parameter on a channel's instrument, in the same
+/// normalized space as [`gooey_engine_set_channel_param`]. This is the way to
+/// read parameters on a slot, or on a second voice of the same type, which
+/// the per-instrument getters do not reach.
+///
+/// # Returns
+/// The current parameter value, or `f32::NAN` if `engine` is null, the
+/// channel is empty or out of range, or `param` is unrecognized.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_channel_param(
+    engine: *const GooeyEngine,
+    channel: u32,
+    param: u32,
+) -> f32 {
+    if engine.is_null() {
+        return f32::NAN;
+    }
+    match (*engine).voice(channel as usize) {
+        Some(voice) => voice.param(param),
+        None => f32::NAN,
+    }
+}
+
+/// Set the tuning offset for a channel (0.0 = −12 semitones, 0.5 = neutral, 1.0 = +12 semitones).
+///
+/// This is a convenience function that dispatches to the correct tuning parameter
+/// for whatever instrument type is currently loaded on the channel.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_channel_tuning(
+    engine: *mut GooeyEngine,
+    channel: u32,
+    value: f32,
+) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        let Some(voice) = engine.voice_mut(channel as usize) else {
+            return;
+        };
+        let tuning_param = voice.instrument.tuning_param();
+        voice.instrument.set_param(tuning_param, value);
+    })
+}
+
+/// Get the current tuning value for a channel (0.0–1.0).
+///
+/// Returns 0.5 (neutral) if the channel index is out of range or engine is null.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_channel_tuning(
+    engine: *const GooeyEngine,
+    channel: u32,
+) -> f32 {
+    if engine.is_null() {
+        return 0.5;
+    }
+    let engine = &*engine;
+    match engine.voice(channel as usize) {
+        Some(voice) => voice.instrument.get_tuning(),
+        None => 0.5,
+    }
+}
+
+/// Trigger a specific channel (regardless of what instrument type it holds).
+///
+/// The trigger will be processed on the next call to `gooey_engine_render`.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `channel` - Channel index (0-3)
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_trigger_channel(
+    engine: *mut GooeyEngine,
+    channel: u32,
+) -> GooeyResult {
+    gooey_engine_trigger_channel_with_velocity(engine, channel, 1.0)
+}
+
+/// Trigger a specific channel with velocity.
+///
+/// The trigger will be processed on the next call to `gooey_engine_render`.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `channel` - Channel index (0-3)
+/// * `velocity` - Velocity from 0.0 (softest) to 1.0 (hardest)
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_trigger_channel_with_velocity(
+    engine: *mut GooeyEngine,
+    channel: u32,
+    velocity: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_trigger_channel_with_velocity";
+    let Some(engine) = engine.as_ref() else {
+        return null_engine(FN);
+    };
+    let Some(voice) = engine.voice(channel as usize) else {
+        return fail(
+            GooeyResult::InvalidChannel,
+            format!("{FN}: channel {channel} is out of range"),
+        );
+    };
+    let vel_clamped = velocity.clamp(0.0, 1.0);
+    voice
+        .trigger_velocity
+        .store(vel_clamped.to_bits(), Ordering::Release);
+    voice.trigger_pending.store(true, Ordering::Release);
+    GooeyResult::Ok
+}
+
+/// Release (note-off) a specific channel.
+///
+/// The release will be processed on the next call to `gooey_engine_render`,
+/// after any trigger queued for the same render. Channels whose instrument
+/// has no release stage ignore it.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `channel` - Channel index
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_release_channel(
+    engine: *mut GooeyEngine,
+    channel: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_release_channel";
+    let Some(engine) = engine.as_ref() else {
+        return null_engine(FN);
+    };
+    let Some(voice) = engine.voice(channel as usize) else {
+        return fail(
+            GooeyResult::InvalidChannel,
+            format!("{FN}: channel {channel} is out of range"),
+        );
+    };
+    voice.release_pending.store(true, Ordering::Release);
+    GooeyResult::Ok
+}
+
+// =============================================================================
+// Instrument triggering
+// =============================================================================
+
+/// Trigger any instrument manually by ID with velocity
+///
+/// Use this for manual triggering with velocity sensitivity (e.g., velocity-sensitive pads, MIDI input).
+/// The trigger will be processed on the next call to `gooey_engine_render`.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// * `velocity` - Velocity from 0.0 (softest) to 1.0 (hardest)
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_trigger_instrument_with_velocity(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    velocity: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_trigger_instrument_with_velocity";
+    let Some(engine) = engine.as_ref() else {
+        return null_engine(FN);
+    };
+    let Some(voice) = engine.voice(instrument as usize) else {
+        return fail(
+            GooeyResult::InvalidInstrument,
+            format!("{FN}: instrument {instrument} is out of range"),
+        );
+    };
+    let vel_clamped = velocity.clamp(0.0, 1.0);
+    voice
+        .trigger_velocity
+        .store(vel_clamped.to_bits(), Ordering::Release);
+    voice.trigger_pending.store(true, Ordering::Release);
+    GooeyResult::Ok
+}
+
+/// Marks a manual trigger that carries no note.
+const NO_TRIGGER_NOTE: u8 = u8::MAX;
+
+/// Trigger an instrument at a MIDI note (e.g. from a keyboard)
+///
+/// Pitched instruments (bass, kick, tom) take the note as their frequency, as
+/// they would for a sequencer step note, and keep it after the hit. The
+/// others ignore the note and are triggered as by
+/// `gooey_engine_trigger_instrument_with_velocity`.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `instrument` - Instrument ID (INSTRUMENT_BASS, INSTRUMENT_TOM, etc.)
+/// * `note` - MIDI note number, 0-127
+/// * `velocity` - Velocity from 0.0 (softest) to 1.0 (hardest)
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_trigger_instrument_note(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    note: u8,
+    velocity: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_trigger_instrument_note";
+    let Some(engine) = engine.as_ref() else {
+        return null_engine(FN);
+    };
+    let Some(voice) = engine.voice(instrument as usize) else {
+        return fail(
+            GooeyResult::InvalidInstrument,
+            format!("{FN}: instrument {instrument} is out of range"),
+        );
+    };
+    if note > 127 {
+        return fail(
+            GooeyResult::InvalidValue,
+            format!("{FN}: note {note} is not a MIDI note"),
+        );
+    }
+    voice.trigger_note.store(note, Ordering::Release);
+    voice
+        .trigger_velocity
+        .store(velocity.clamp(0.0, 1.0).to_bits(), Ordering::Release);
+    voice.trigger_pending.store(true, Ordering::Release);
+    GooeyResult::Ok
+}
+
+/// Trigger any instrument manually by ID at full velocity
+///
+/// Use this for manual triggering outside of the sequencer (e.g., user tap).
+/// The trigger will be processed on the next call to `gooey_engine_render`.
+/// For velocity-sensitive triggering, use `gooey_engine_trigger_instrument_with_velocity`.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_trigger_instrument(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+) -> GooeyResult {
+    gooey_engine_trigger_instrument_with_velocity(engine, instrument, 1.0)
+}
+
+/// Release (note-off) an instrument by ID.
+///
+/// Instruments with a release stage (bass, kick, snare) end their note from
+/// wherever it is; the others run out their own decay. The release is
+/// processed on the next call to `gooey_engine_render`, after any trigger
+/// queued for the same render.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_release_instrument(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_release_instrument";
+    let Some(engine) = engine.as_ref() else {
+        return null_engine(FN);
+    };
+    let Some(voice) = engine.voice(instrument as usize) else {
+        return fail(
+            GooeyResult::InvalidInstrument,
+            format!("{FN}: instrument {instrument} is out of range"),
+        );
+    };
+    voice.release_pending.store(true, Ordering::Release);
+    GooeyResult::Ok
+}
+
+// =============================================================================
+// Preview voices (auditioning configs without touching the kit)
+// =============================================================================
+
+/// Audition a config on an isolated preview voice, e.g. while a knob in a
+/// preset editor is being dragged.
+///
+/// The engine keeps one preview voice per instrument type, outside every
+/// channel: the hit doesn't change any channel's parameters, patterns, mute
+/// or meters, and doesn't interrupt the sequence. It is mixed to master
+/// after the master effects and limiter (scaled by the master gain and
+/// `gooey_engine_set_preview_gain`), so it sounds the same whatever the kit is
+/// doing. Each call retriggers the voice with the new config; the previous
+/// hit of the same type is cut, other types ring out. Like
+/// `gooey_engine_trigger_instrument`, it lands at the start of the next
+/// render.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `instrument_type` - Instrument type (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// * `params` - Parameter values in setter space, indexed by the type's
+///   `*_PARAM_*` constants, as for `gooey_engine_set_channel_param`.
+///   Parameters past `param_count` keep their defaults. May be null when
+///   `param_count` is 0.
+/// * `param_count` - Number of values in `params`
+/// * `velocity` - Hit velocity, clamped to 0.0-1.0
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an unknown instrument
+/// type, a null `params` with a nonzero count, more values than the type has
+/// parameters, a non-finite value, or a full control queue. Out-of-range
+/// values are clamped and recorded as warnings.
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `params` must point to at least `param_count` floats
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_preview_trigger_with_config(
+    engine: *mut GooeyEngine,
+    instrument_type: u32,
+    params: *const f32,
+    param_count: u32,
+    velocity: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_preview_trigger_with_config";
+    let Some(engine) = engine.as_mut() else {
+        return null_engine(FN);
+    };
+    let Some(mut scratch) = ChannelInstrument::new(instrument_type, engine.sample_rate) else {
+        return fail(
+            GooeyResult::InvalidInstrument,
+            format!("{FN}: unknown instrument type {instrument_type}"),
+        );
+    };
+    let values: &[f32] = if param_count == 0 {
+        &[]
+    } else if params.is_null() {
+        return fail(GooeyResult::NullPointer, format!("{FN}: params is null"));
+    } else {
+        std::slice::from_raw_parts(params, param_count as usize)
+    };
+    let available = crate::param_info::instrument_params(instrument_type).len();
+    if values.len() > available {
+        return fail(
+            GooeyResult::InvalidParam,
+            format!(
+                "{FN}: {} values given, instrument type {instrument_type} has {available} parameters",
+                values.len()
+            ),
+        );
+    }
+    for (param, &value) in values.iter().enumerate() {
+        match clamp_param_value(FN, instrument_type, param as u32, value) {
+            Ok(value) => scratch.set_param(param as u32, value),
+            Err(result) => return result,
+        }
+    }
+    scratch.snap_params();
+    engine.submit(
+        FN,
+        ControlCommand::PreviewTrigger {
+            config: scratch.config(),
+            velocity: velocity.clamp(0.0, 1.0),
+        },
+    )
+}
+
+/// Release the preview voices (a note-off for a held bass audition).
+/// Percussion runs out its own decay.
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine or a full control queue.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_preview_release(engine: *mut GooeyEngine) -> GooeyResult {
+    const FN: &str = "gooey_engine_preview_release";
+    let Some(engine) = engine.as_mut() else {
+        return null_engine(FN);
+    };
+    engine.submit(FN, ControlCommand::PreviewRelease)
+}
+
+/// Set the preview level (0.0–1.0, default 1.0), applied on top of the master
+/// gain. Smoothed over 10ms.
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, a non-finite gain, or a
+/// full control queue.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_preview_gain(
+    engine: *mut GooeyEngine,
+    gain: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_preview_gain";
+    let Some(engine) = engine.as_mut() else {
+        return null_engine(FN);
+    };
+    if !gain.is_finite() {
+        return fail(
+            GooeyResult::InvalidValue,
+            format!("{FN}: gain {gain} is not finite"),
+        );
+    }
+    engine.submit(FN, ControlCommand::PreviewGain(gain.clamp(0.0, 1.0)))
+}
+
+/// Get the preview level target.
+///
+/// # Returns
+/// The linear gain target (0.0–1.0), or 1.0 for a null engine
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_preview_gain(engine: *const GooeyEngine) -> f32 {
+    engine
+        .as_ref()
+        .map_or(1.0, |engine| engine.preview_gain.target())
+}
+
+// =============================================================================
+// Per-channel peak metering
+// =============================================================================
+
+/// Read per-channel peak amplitudes since the last call and reset them to zero.
+///
+/// Each value represents the maximum absolute amplitude seen on that channel
+/// since the previous call. Useful for driving UI level meters.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `out_peaks` - Pointer to a float buffer to receive peak values (0.0–1.0+)
+/// * `count` - Number of channels to read (clamped to CHANNEL_MAX); empty slots
+///   read as 0.0
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`.
+/// `out_peaks` must point to a buffer of at least `count` floats.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_channel_peaks(
+    engine: *mut GooeyEngine,
+    out_peaks: *mut f32,
+    count: u32,
+) {
+    if let Some(engine) = engine.as_ref() {
+        let n = (count as usize).min(NUM_CHANNELS);
+        for i in 0..n {
+            let bits = engine.voice(i).map_or(0, |voice| {
+                voice.peak.swap(0.0_f32.to_bits(), Ordering::Relaxed)
+            });
+            *out_peaks.add(i) = f32::from_bits(bits);
+        }
+    }
+}
+
+/// Trigger the kick drum manually (legacy function, prefer `gooey_engine_trigger_instrument`)
+///
+/// Use this for manual triggering outside of the sequencer (e.g., user tap).
+/// The trigger will be processed on the next call to `gooey_engine_render`.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_trigger_kick(engine: *mut GooeyEngine) {
+    if let Some(engine) = engine.as_ref() {
+        if let Some(voice) = engine.voice(INSTRUMENT_KICK as usize) {
+            voice.trigger_pending.store(true, Ordering::Release);
+        }
+    }
+}
+
+/// Set a kick drum parameter
+///
+/// All parameters are automatically smoothed to prevent clicks/pops.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `param` - Parameter index (see KICK_PARAM_* constants)
+/// * `value` - Parameter value (range depends on parameter)
+///
+/// # Parameter indices and ranges
+/// - 0 (FREQUENCY): 30-80 Hz
+/// - 1 (PUNCH): 0-1
+/// - 2 (SUB): 0-1
+/// - 3 (CLICK): 0-1
+/// - 4 (DECAY): 0.01-5.0 seconds
+/// - 5 (PITCH_ENVELOPE): 0-1
+/// - 6 (VOLUME): 0-1
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an unknown parameter
+/// index, a non-finite value, or no channel currently holding a kick.
+/// Out-of-range values are clamped and recorded as a warning.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_kick_param(
+    engine: *mut GooeyEngine,
+    param: u32,
+    value: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_kick_param";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+    let value = match clamp_param_value(FN, INSTRUMENT_KICK, param, value) {
+        Ok(value) => value,
+        Err(result) => return result,
+    };
+    let engine = &mut *engine;
+    if engine.instrument_by_type(INSTRUMENT_KICK).is_none() {
+        return fail(
+            GooeyResult::InvalidInstrument,
+            format!("{FN}: no channel holds a kick"),
+        );
+    }
+    engine.submit(
+        FN,
+        ControlCommand::InstrumentParam {
+            instrument_type: INSTRUMENT_KICK,
+            param,
+            value,
+        },
+    )
+}
+
+/// Read a kick drum parameter in the same normalized form used by
+/// `gooey_engine_set_kick_param`.
+///
+/// Returns the most-recently-set target value (not the in-flight smoothed sample),
+/// so set→get round-trips exactly. Use this to capture mutations applied by
+/// randomize/mutate flows for snapshot/undo.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `param` - Parameter index (see KICK_PARAM_* constants)
+///
+/// # Returns
+/// The current parameter value, or `f32::NAN` if `engine` is null, the kick
+/// channel is missing, or `param` is unrecognized. Note that
+/// `KICK_PARAM_PITCH_ENVELOPE` reports the value set via the setter; it only
+/// takes audible effect at the next trigger.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_kick_param(
+    engine: *const GooeyEngine,
+    param: u32,
+) -> f32 {
+    if engine.is_null() {
+        return f32::NAN;
+    }
+    let engine = &*engine;
+    match engine.voice_by_type(INSTRUMENT_KICK) {
+        Some(voice) => voice.param(param),
+        None => f32::NAN,
+    }
+}
+
+/// Set a hi-hat parameter
+///
+/// All parameters are automatically smoothed to prevent clicks/pops.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `param` - Parameter index (see HIHAT_PARAM_* constants)
+/// * `value` - Parameter value (range depends on parameter)
+///
+/// # Parameter indices and ranges
+/// - 0 (PITCH): 0-1 normalized
+/// - 1 (DECAY): 0-1 normalized
+/// - 2 (ATTACK): 0-1 normalized
+/// - 3 (TONE): 0-1 normalized
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an unknown parameter
+/// index, a non-finite value, or no channel currently holding a hi-hat.
+/// Out-of-range values are clamped and recorded as a warning.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_hihat_param(
+    engine: *mut GooeyEngine,
+    param: u32,
+    value: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_hihat_param";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+    let value = match clamp_param_value(FN, INSTRUMENT_HIHAT, param, value) {
+        Ok(value) => value,
+        Err(result) => return result,
+    };
+    let engine = &mut *engine;
+    if engine.instrument_by_type(INSTRUMENT_HIHAT).is_none() {
+        return fail(
+            GooeyResult::InvalidInstrument,
+            format!("{FN}: no channel holds a hihat"),
+        );
+    }
+    engine.submit(
+        FN,
+        ControlCommand::InstrumentParam {
+            instrument_type: INSTRUMENT_HIHAT,
+            param,
+            value,
+        },
+    )
+}
+
+/// Read a hi-hat parameter in the same normalized form used by
+/// `gooey_engine_set_hihat_param`.
+///
+/// Returns the most-recently-set target value (not the in-flight smoothed sample),
+/// so set→get round-trips exactly.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `param` - Parameter index (see HIHAT_PARAM_* constants)
+///
+/// # Returns
+/// The current parameter value, or `f32::NAN` if `engine` is null, the hi-hat
+/// channel is missing, or `param` is unrecognized.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_hihat_param(
+    engine: *const GooeyEngine,
+    param: u32,
+) -> f32 {
+    if engine.is_null() {
+        return f32::NAN;
+    }
+    let engine = &*engine;
+    match engine.voice_by_type(INSTRUMENT_HIHAT) {
+        Some(voice) => voice.param(param),
+        None => f32::NAN,
+    }
+}
+
+/// Set a snare drum parameter
+///
+/// All parameters are automatically smoothed to prevent clicks/pops.
+/// All parameters use normalized 0-1 range.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `param` - Parameter index (see SNARE_PARAM_* constants)
+/// * `value` - Parameter value (0-1 normalized)
+///
+/// # Parameter indices and ranges (all 0-1 normalized)
+/// - 0 (FREQUENCY): 0-1 → 100-600 Hz
+/// - 1 (DECAY): 0-1 → 0.05-3.5 seconds
+/// - 2 (BRIGHTNESS): 0-1
+/// - 3 (VOLUME): 0-1
+/// - 4 (TONAL): 0-1
+/// - 5 (NOISE): 0-1
+/// - 6 (PITCH_DROP): 0-1
+/// - 7 (TONAL_DECAY): 0-1 → 0-3.5s
+/// - 8 (NOISE_DECAY): 0-1 → 0-3.5s
+/// - 9 (NOISE_TAIL_DECAY): 0-1 → 0-3.5s
+/// - 10 (FILTER_CUTOFF): 0-1 → 100-10000 Hz
+/// - 11 (FILTER_RESONANCE): 0-1 → 0.5-10.0
+/// - 12 (FILTER_TYPE): 0-3 (LP/BP/HP/notch)
+/// - 13 (XFADE): 0-1
+/// - 14 (PHASE_MOD_AMOUNT): 0-1
+/// - 15 (OVERDRIVE): 0-1
+/// - 16 (AMP_DECAY): 0-1 → 0-4.0s
+/// - 17 (AMP_DECAY_CURVE): 0-1 → 0.1-10.0
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an unknown parameter
+/// index, a non-finite value, or no channel currently holding a snare.
+/// Out-of-range values are clamped and recorded as a warning.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_snare_param(
+    engine: *mut GooeyEngine,
+    param: u32,
+    value: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_snare_param";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+    let value = match clamp_param_value(FN, INSTRUMENT_SNARE, param, value) {
+        Ok(value) => value,
+        Err(result) => return result,
+    };
+    let engine = &mut *engine;
+    if engine.instrument_by_type(INSTRUMENT_SNARE).is_none() {
+        return fail(
+            GooeyResult::InvalidInstrument,
+            format!("{FN}: no channel holds a snare"),
+        );
+    }
+    engine.submit(
+        FN,
+        ControlCommand::InstrumentParam {
+            instrument_type: INSTRUMENT_SNARE,
+            param,
+            value,
+        },
+    )
+}
+
+/// Read a snare drum parameter in the same normalized form used by
+/// `gooey_engine_set_snare_param`.
+///
+/// Returns the most-recently-set target value (not the in-flight smoothed sample),
+/// so set→get round-trips exactly. `SNARE_PARAM_FILTER_TYPE` is reported as the
+/// raw 0-3 enum value cast to `f32`.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `param` - Parameter index (see SNARE_PARAM_* constants)
+///
+/// # Returns
+/// The current parameter value, or `f32::NAN` if `engine` is null, the snare
+/// channel is missing, or `param` is unrecognized.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_snare_param(
+    engine: *const GooeyEngine,
+    param: u32,
+) -> f32 {
+    if engine.is_null() {
+        return f32::NAN;
+    }
+    let engine = &*engine;
+    match engine.voice_by_type(INSTRUMENT_SNARE) {
+        Some(voice) => voice.param(param),
+        None => f32::NAN,
+    }
+}
+
+/// Set a tom drum parameter
+///
+/// All parameters use normalized 0-1 range. Values are internally scaled
+/// to Tom2's 0-100 range.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `param` - Parameter index (see TOM_PARAM_* constants)
+/// * `value` - Parameter value (0-1 normalized)
+///
+/// # Parameter indices and ranges (all 0-1 normalized → 0-100 internal)
+/// - 0 (TUNE): 0-1 → 0-100 (maps to 40-600 Hz)
+/// - 1 (BEND): 0-1 → 0-100 (pitch envelope depth)
+/// - 2 (TONE): 0-1 → 0-100 (mix control)
+/// - 3 (COLOR): 0-1 → 0-100 (noise rate / filter cutoff)
+/// - 4 (DECAY): 0-1 → 0-100 (maps to 0.5-4000ms)
+/// - 5 (MEMBRANE): 0-1 → 0-100 (resonator mix)
+/// - 6 (MEMBRANE_Q): 0-1 → 0-100 (resonator Q scale)
+/// - 7 (VOLUME): 0-1 → 0-100 (overall volume)
+/// - 8 (TUNING): 0-1 used directly (0.5 = neutral, ±12 semitones)
+/// - 9 (TRIANGLE_ENABLE): 0 or 1 (standalone triangle off/on)
+/// - 10 (TRIANGLE_LEVEL): 0-1 → 0-100 (standalone triangle gain)
+/// - 11 (MORPH_LEVEL): 0-1 → 0-100 (morph oscillator gain)
+/// - 12 (MEMBRANE_GAIN): 0-1 → 0-100 (resonator input gain)
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an unknown parameter
+/// index, a non-finite value, or no channel currently holding a tom.
+/// Out-of-range values are clamped and recorded as a warning.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_tom_param(
+    engine: *mut GooeyEngine,
+    param: u32,
+    value: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_tom_param";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+    let value = match clamp_param_value(FN, INSTRUMENT_TOM, param, value) {
+        Ok(value) => value,
+        Err(result) => return result,
+    };
+    let engine = &mut *engine;
+    if engine.instrument_by_type(INSTRUMENT_TOM).is_none() {
+        return fail(
+            GooeyResult::InvalidInstrument,
+            format!("{FN}: no channel holds a tom"),
+        );
+    }
+    engine.submit(
+        FN,
+        ControlCommand::InstrumentParam {
+            instrument_type: INSTRUMENT_TOM,
+            param,
+            value,
+        },
+    )
+}
+
+/// Read a tom drum parameter in the same normalized form used by
+/// `gooey_engine_set_tom_param`.
+///
+/// Tom2 stores its parameters internally on a 0-100 scale; the getter
+/// renormalizes back to 0-1 to match the setter contract. `TOM_PARAM_TUNING`
+/// (param 8) is the exception: it's already 0-1 in both directions, and
+/// `TOM_PARAM_TRIANGLE_ENABLE` reads back as 0 or 1.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `param` - Parameter index (see TOM_PARAM_* constants)
+///
+/// # Returns
+/// The current parameter value, or `f32::NAN` if `engine` is null, the tom
+/// channel is missing, or `param` is unrecognized.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_tom_param(engine: *const GooeyEngine, param: u32) -> f32 {
+    if engine.is_null() {
+        return f32::NAN;
+    }
+    let engine = &*engine;
+    match engine.voice_by_type(INSTRUMENT_TOM) {
+        Some(voice) => voice.param(param),
+        None => f32::NAN,
+    }
+}
+
+/// Set a bass synth parameter
+///
+/// All parameters use normalized 0-1 range. Values are internally scaled.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `param` - Parameter index (see BASS_PARAM_* constants)
+/// * `value` - Parameter value (0.0-1.0 normalized)
+///
+/// # Parameter indices and ranges
+/// - 0 (FREQUENCY): 0-1 → 30-200 Hz
+/// - 1 (SUB_LEVEL): 0-1
+/// - 2 (OSC_LEVEL): 0-1
+/// - 3 (DETUNE_LEVEL): 0-1
+/// - 4 (DETUNE_AMOUNT): 0-1 → 0-30 cents
+/// - 5 (OSC_SHAPE): 0-1 (saw to square)
+/// - 6 (FILTER_CUTOFF): 0-1 → 20-18000 Hz exp
+/// - 7 (FILTER_RESONANCE): 0-1 → 0.5-15.0 Q
+/// - 8 (FILTER_ENV_AMOUNT): 0-1
+/// - 9 (FILTER_ENV_DECAY): 0-1 → 0.01-2.0s
+/// - 10 (FILTER_ENV_CURVE): 0-1 → 0.1-8.0
+/// - 11 (AMP_DECAY): 0-1 → 0.05-4.0s
+/// - 12 (AMP_DECAY_CURVE): 0-1 → 0.1-10.0
+/// - 13 (OVERDRIVE): 0-1
+/// - 14 (VOLUME): 0-1
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an unknown parameter
+/// index, a non-finite value, or no channel currently holding a bass.
+/// Out-of-range values are clamped and recorded as a warning.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_bass_param(
+    engine: *mut GooeyEngine,
+    param: u32,
+    value: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_bass_param";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+    let value = match clamp_param_value(FN, INSTRUMENT_BASS, param, value) {
+        Ok(value) => value,
+        Err(result) => return result,
+    };
+    let engine = &mut *engine;
+    if engine.instrument_by_type(INSTRUMENT_BASS).is_none() {
+        return fail(
+            GooeyResult::InvalidInstrument,
+            format!("{FN}: no channel holds a bass"),
+        );
+    }
+    engine.submit(
+        FN,
+        ControlCommand::InstrumentParam {
+            instrument_type: INSTRUMENT_BASS,
+            param,
+            value,
+        },
+    )
+}
+
+/// Load a bass preset, setting all bass parameters to the preset's values.
+///
+/// This directly applies the preset's parameter values to the bass instrument
+/// without requiring the blend system.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `preset_id` - Preset ID (BASS_PRESET_ACID, BASS_PRESET_SUB, etc.)
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_load_bass_preset(engine: *mut GooeyEngine, preset_id: u32) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        engine.load_preset_by_type(INSTRUMENT_BASS, preset_id);
+    })
+}
+
+/// Set an FM snap parameter
+///
+/// All parameters are automatically smoothed to prevent clicks/pops.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `param` - Parameter index (see FM_SNAP_PARAM_* constants)
+/// * `value` - Parameter value (0.0-1.0 normalized)
+///
+/// # Parameter indices and ranges
+/// - 0 (FREQUENCY): 0-1 → 80-1600 Hz exp
+/// - 1 (RATIO): 0-1 → 0.5-6.0 modulator:carrier
+/// - 2 (INDEX): 0-1 → 0-10 peak modulation index
+/// - 3 (SNAP): 0-1 → 0.5-50 ms index decay, exp
+/// - 4 (DECAY): 0-1 → 5-1000 ms amplitude decay, exp
+/// - 5 (PITCH_DROP): 0-1 → 0-24 semitones
+/// - 6 (VOLUME): 0-1
+/// - 7 (TUNING): 0-1 → -12 to +12 semitones
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an unknown parameter
+/// index, a non-finite value, or no channel currently holding an FM snap.
+/// Out-of-range values are clamped and recorded as a warning.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_fm_snap_param(
+    engine: *mut GooeyEngine,
+    param: u32,
+    value: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_fm_snap_param";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+    let value = match clamp_param_value(FN, INSTRUMENT_FM_SNAP, param, value) {
+        Ok(value) => value,
+        Err(result) => return result,
+    };
+    let engine = &mut *engine;
+    if engine.instrument_by_type(INSTRUMENT_FM_SNAP).is_none() {
+        return fail(
+            GooeyResult::InvalidInstrument,
+            format!("{FN}: no channel holds an FM snap"),
+        );
+    }
+    engine.submit(
+        FN,
+        ControlCommand::InstrumentParam {
+            instrument_type: INSTRUMENT_FM_SNAP,
+            param,
+            value,
+        },
+    )
+}
+
+/// Get the current value of an FM snap parameter (normalized 0-1, the
+/// same space as [`gooey_engine_set_fm_snap_param`]).
+///
+/// # Returns
+/// The current parameter value, or `f32::NAN` if `engine` is null, no
+/// channel holds an FM snap, or `param` is unrecognized.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_fm_snap_param(
+    engine: *const GooeyEngine,
+    param: u32,
+) -> f32 {
+    if engine.is_null() {
+        return f32::NAN;
+    }
+    let engine = &*engine;
+    match engine.voice_by_type(INSTRUMENT_FM_SNAP) {
+        Some(voice) => voice.param(param),
+        None => f32::NAN,
+    }
+}
+
+/// Load an FM snap preset, setting all FM snap parameters to the preset's
+/// values.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `preset_id` - Preset ID (FM_SNAP_PRESET_SNAP, FM_SNAP_PRESET_RIM, etc.)
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an unknown preset ID,
+/// or no channel currently holding an FM snap.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_load_fm_snap_preset(
+    engine: *mut GooeyEngine,
+    preset_id: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_load_fm_snap_preset";
+    edit(engine, || {
+        if engine.is_null() {
+            return null_engine(FN);
+        }
+        if GooeyEngine::fm_snap_preset_by_id(preset_id).is_none() {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: unknown preset {preset_id}"),
+            );
+        }
+        let engine = &mut *engine;
+        if engine.load_preset_by_type(INSTRUMENT_FM_SNAP, preset_id) {
+            GooeyResult::Ok
+        } else {
+            fail(
+                GooeyResult::InvalidInstrument,
+                format!("{FN}: no channel holds an FM snap"),
+            )
+        }
+    })
+}
+
+/// Set a rimshot parameter
+///
+/// All parameters are automatically smoothed to prevent clicks/pops.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `param` - Parameter index (see RIMSHOT_PARAM_* constants)
+/// * `value` - Parameter value (0.0-1.0 normalized)
+///
+/// # Parameter indices and ranges
+/// - 0 (TUNE): 0-1 → 200-2000 Hz ping, exp
+/// - 1 (TONE): 0-1 → 1-10 kHz click bandpass, exp
+/// - 2 (CLICK): 0-1 click level
+/// - 3 (DECAY): 0-1 → 5-300 ms ping decay, exp
+/// - 4 (VOLUME): 0-1
+/// - 5 (TUNING): 0-1 → -12 to +12 semitones
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an unknown parameter
+/// index, a non-finite value, or no channel currently holding a rimshot.
+/// Out-of-range values are clamped and recorded as a warning.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_rimshot_param(
+    engine: *mut GooeyEngine,
+    param: u32,
+    value: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_rimshot_param";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+    let value = match clamp_param_value(FN, INSTRUMENT_RIMSHOT, param, value) {
+        Ok(value) => value,
+        Err(result) => return result,
+    };
+    let engine = &mut *engine;
+    if engine.instrument_by_type(INSTRUMENT_RIMSHOT).is_none() {
+        return fail(
+            GooeyResult::InvalidInstrument,
+            format!("{FN}: no channel holds a rimshot"),
+        );
+    }
+    engine.submit(
+        FN,
+        ControlCommand::InstrumentParam {
+            instrument_type: INSTRUMENT_RIMSHOT,
+            param,
+            value,
+        },
+    )
+}
+
+/// Get the current value of a rimshot parameter (normalized 0-1, the
+/// same space as [`gooey_engine_set_rimshot_param`]).
+///
+/// # Returns
+/// The current parameter value, or `f32::NAN` if `engine` is null, no
+/// channel holds a rimshot, or `param` is unrecognized.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_rimshot_param(
+    engine: *const GooeyEngine,
+    param: u32,
+) -> f32 {
+    if engine.is_null() {
+        return f32::NAN;
+    }
+    let engine = &*engine;
+    match engine.voice_by_type(INSTRUMENT_RIMSHOT) {
+        Some(voice) => voice.param(param),
+        None => f32::NAN,
+    }
+}
+
+/// Load a rimshot preset, setting all rimshot parameters to the preset's
+/// values.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `preset_id` - Preset ID (RIMSHOT_PRESET_CLASSIC, RIMSHOT_PRESET_TIGHT, etc.)
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an unknown preset ID,
+/// or no channel currently holding a rimshot.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_load_rimshot_preset(
+    engine: *mut GooeyEngine,
+    preset_id: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_load_rimshot_preset";
+    edit(engine, || {
+        if engine.is_null() {
+            return null_engine(FN);
+        }
+        if GooeyEngine::rimshot_preset_by_id(preset_id).is_none() {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: unknown preset {preset_id}"),
+            );
+        }
+        let engine = &mut *engine;
+        if engine.load_preset_by_type(INSTRUMENT_RIMSHOT, preset_id) {
+            GooeyResult::Ok
+        } else {
+            fail(
+                GooeyResult::InvalidInstrument,
+                format!("{FN}: no channel holds a rimshot"),
+            )
+        }
+    })
+}
+
+/// Set a cowbell parameter
+///
+/// All parameters are automatically smoothed to prevent clicks/pops.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `param` - Parameter index (see COWBELL_PARAM_* constants)
+/// * `value` - Parameter value (0.0-1.0 normalized)
+///
+/// # Parameter indices and ranges
+/// - 0 (PITCH): 0-1 → 300-1200 Hz lower square, exp
+/// - 1 (TONE): 0-1 → 1-6 kHz bandpass, exp
+/// - 2 (DECAY): 0-1 → 50-2000 ms tail decay, exp
+/// - 3 (VOLUME): 0-1
+/// - 4 (TUNING): 0-1 → -12 to +12 semitones
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an unknown parameter
+/// index, a non-finite value, or no channel currently holding a cowbell.
+/// Out-of-range values are clamped and recorded as a warning.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_cowbell_param(
+    engine: *mut GooeyEngine,
+    param: u32,
+    value: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_cowbell_param";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+    let value = match clamp_param_value(FN, INSTRUMENT_COWBELL, param, value) {
+        Ok(value) => value,
+        Err(result) => return result,
+    };
+    let engine = &mut *engine;
+    if engine.instrument_by_type(INSTRUMENT_COWBELL).is_none() {
+        return fail(
+            GooeyResult::InvalidInstrument,
+            format!("{FN}: no channel holds a cowbell"),
+        );
+    }
+    engine.submit(
+        FN,
+        ControlCommand::InstrumentParam {
+            instrument_type: INSTRUMENT_COWBELL,
+            param,
+            value,
+        },
+    )
+}
+
+/// Get the current value of a cowbell parameter (normalized 0-1, the
+/// same space as [`gooey_engine_set_cowbell_param`]).
+///
+/// # Returns
+/// The current parameter value, or `f32::NAN` if `engine` is null, no
+/// channel holds a cowbell, or `param` is unrecognized.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_cowbell_param(
+    engine: *const GooeyEngine,
+    param: u32,
+) -> f32 {
+    if engine.is_null() {
+        return f32::NAN;
+    }
+    let engine = &*engine;
+    match engine.voice_by_type(INSTRUMENT_COWBELL) {
+        Some(voice) => voice.param(param),
+        None => f32::NAN,
+    }
+}
+
+/// Load a cowbell preset, setting all cowbell parameters to the preset's
+/// values.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `preset_id` - Preset ID (COWBELL_PRESET_CLASSIC, COWBELL_PRESET_BRIGHT, etc.)
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an unknown preset ID,
+/// or no channel currently holding a cowbell.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_load_cowbell_preset(
+    engine: *mut GooeyEngine,
+    preset_id: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_load_cowbell_preset";
+    edit(engine, || {
+        if engine.is_null() {
+            return null_engine(FN);
+        }
+        if GooeyEngine::cowbell_preset_by_id(preset_id).is_none() {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: unknown preset {preset_id}"),
+            );
+        }
+        let engine = &mut *engine;
+        if engine.load_preset_by_type(INSTRUMENT_COWBELL, preset_id) {
+            GooeyResult::Ok
+        } else {
+            fail(
+                GooeyResult::InvalidInstrument,
+                format!("{FN}: no channel holds a cowbell"),
+            )
+        }
+    })
+}
+
+/// Set a shaker parameter
+///
+/// All parameters are automatically smoothed to prevent clicks/pops.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `param` - Parameter index (see SHAKER_PARAM_* constants)
+/// * `value` - Parameter value (0.0-1.0 normalized)
+///
+/// # Parameter indices and ranges
+/// - 0 (DENSITY): 0-1 → 200-5000 grains/s, exp
+/// - 1 (COLOR): 0-1 → 1-10 kHz highpass, exp
+/// - 2 (ATTACK): 0-1 → 0.5-50 ms swell, exp
+/// - 3 (DECAY): 0-1 → 20-600 ms, exp
+/// - 4 (ACCENT): 0-1 velocity-to-density/color depth
+/// - 5 (VOLUME): 0-1
+/// - 6 (TUNING): 0-1 → -12 to +12 semitones on the highpass
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an unknown parameter
+/// index, a non-finite value, or no channel currently holding a shaker.
+/// Out-of-range values are clamped and recorded as a warning.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_shaker_param(
+    engine: *mut GooeyEngine,
+    param: u32,
+    value: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_shaker_param";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+    let value = match clamp_param_value(FN, INSTRUMENT_SHAKER, param, value) {
+        Ok(value) => value,
+        Err(result) => return result,
+    };
+    let engine = &mut *engine;
+    if engine.instrument_by_type(INSTRUMENT_SHAKER).is_none() {
+        return fail(
+            GooeyResult::InvalidInstrument,
+            format!("{FN}: no channel holds a shaker"),
+        );
+    }
+    engine.submit(
+        FN,
+        ControlCommand::InstrumentParam {
+            instrument_type: INSTRUMENT_SHAKER,
+            param,
+            value,
+        },
+    )
+}
+
+/// Get the current value of a shaker parameter (normalized 0-1, the
+/// same space as [`gooey_engine_set_shaker_param`]).
+///
+/// # Returns
+/// The current parameter value, or `f32::NAN` if `engine` is null, no
+/// channel holds a shaker, or `param` is unrecognized.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_shaker_param(
+    engine: *const GooeyEngine,
+    param: u32,
+) -> f32 {
+    if engine.is_null() {
+        return f32::NAN;
+    }
+    let engine = &*engine;
+    match engine.voice_by_type(INSTRUMENT_SHAKER) {
+        Some(voice) => voice.param(param),
+        None => f32::NAN,
+    }
+}
+
+/// Load a shaker preset, setting all shaker parameters to the preset's
+/// values.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `preset_id` - Preset ID (SHAKER_PRESET_TIGHT, SHAKER_PRESET_SWISH, etc.)
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an unknown preset ID,
+/// or no channel currently holding a shaker.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_load_shaker_preset(
+    engine: *mut GooeyEngine,
+    preset_id: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_load_shaker_preset";
+    edit(engine, || {
+        if engine.is_null() {
+            return null_engine(FN);
+        }
+        if GooeyEngine::shaker_preset_by_id(preset_id).is_none() {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: unknown preset {preset_id}"),
+            );
+        }
+        let engine = &mut *engine;
+        if engine.load_preset_by_type(INSTRUMENT_SHAKER, preset_id) {
+            GooeyResult::Ok
+        } else {
+            fail(
+                GooeyResult::InvalidInstrument,
+                format!("{FN}: no channel holds a shaker"),
+            )
+        }
+    })
+}
+
+// =============================================================================
+// Global effects control
+// =============================================================================
+
+/// Number of `*_PARAM_*` ids a global effect takes, or `None` for an
+/// unknown effect.
+pub(crate) fn global_effect_param_count(effect: u32) -> Option<u32> {
+    Some(match effect {
+        EFFECT_LOWPASS_FILTER => 2,
+        EFFECT_DELAY => 7,
+        EFFECT_SATURATION => 5,
+        EFFECT_COMPRESSOR => 5,
+        EFFECT_TILT_FILTER => 2,
+        EFFECT_LIMITER => 1,
+        EFFECT_REVERB => 3,
+        EFFECT_WAVESHAPER => 2,
+        EFFECT_FEEDBACK_WAVESHAPER => 4,
+        EFFECT_PLATE_REVERB => 6,
+        EFFECT_DUCKER => 4,
+        EFFECT_BEAT_REPEAT => 5,
+        EFFECT_EARLY_REFLECTIONS => 3,
+        EFFECT_WIDENER => 3,
+        _ => return None,
+    })
+}
+
+/// Set a parameter on a global effect
+///
+/// This provides a generic interface for controlling any global effect's parameters.
+/// Use effect ID constants (EFFECT_LOWPASS_FILTER, etc.) and corresponding parameter
+/// constants (FILTER_PARAM_CUTOFF, FILTER_PARAM_RESONANCE, etc.).
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `effect` - Effect ID (see EFFECT_* constants)
+/// * `param` - Parameter ID (depends on effect type, see *_PARAM_* constants)
+/// * `value` - Parameter value (range depends on parameter)
+///
+/// # Effect and Parameter Reference
+/// - EFFECT_LOWPASS_FILTER (0):
+///   - FILTER_PARAM_CUTOFF (0): 20-20000 Hz
+///   - FILTER_PARAM_RESONANCE (1): 0.0-0.95
+/// - EFFECT_DELAY (1):
+///   - DELAY_PARAM_TIMING (0): DELAY_TIMING_* constant (0-12)
+///   - DELAY_PARAM_FEEDBACK (1): 0.0-0.95
+///   - DELAY_PARAM_MIX (2): 0.0-1.0
+///   - DELAY_PARAM_FILTER_CUTOFF (3): 20-20000 Hz
+///   - DELAY_PARAM_PINGPONG (4): 0.0 = off, >= 0.5 = on
+///   - DELAY_PARAM_LOW_CUT (5): 20-20000 Hz (20 = off)
+///   - DELAY_PARAM_FREEZE (6): 0.0 = off, >= 0.5 = on
+/// - EFFECT_SATURATION (2):
+///   - SATURATION_PARAM_DRIVE (0): 0.0-1.0
+///   - SATURATION_PARAM_WARMTH (1): 0.0-1.0
+///   - SATURATION_PARAM_MIX (2): 0.0-1.0
+///   - SATURATION_PARAM_MODEL (3): SATURATOR_MODEL_* constant (0-5)
+///   - SATURATION_PARAM_AUTO_GAIN (4): 0.0 = off, >= 0.5 = on
+/// - EFFECT_COMPRESSOR (3):
+///   - COMPRESSOR_PARAM_THRESHOLD (0): -60.0 to 0.0 dB
+///   - COMPRESSOR_PARAM_RATIO (1): 1.0-20.0
+///   - COMPRESSOR_PARAM_ATTACK (2): 0.1-100.0 ms
+///   - COMPRESSOR_PARAM_RELEASE (3): 5.0-1000.0 ms
+///   - COMPRESSOR_PARAM_MIX (4): 0.0-1.0
+/// - EFFECT_LIMITER (5):
+///   - LIMITER_PARAM_THRESHOLD (0): 0.001-1.0
+/// - EFFECT_DUCKER (10):
+///   - DUCKER_PARAM_DEPTH (0): 0.0-1.0
+///   - DUCKER_PARAM_ATTACK (1): 0.0-50.0 ms
+///   - DUCKER_PARAM_HOLD (2): 0.0-500.0 ms
+///   - DUCKER_PARAM_RELEASE (3): 5.0-1000.0 ms
+/// - EFFECT_BEAT_REPEAT (11):
+///   - BEAT_REPEAT_PARAM_SLICE (0): DELAY_TIMING_* constant (0-12)
+///   - BEAT_REPEAT_PARAM_LENGTH (1): 0.25-4.0 beats
+///   - BEAT_REPEAT_PARAM_PITCH (2): -12.0 to 12.0 semitones
+///   - BEAT_REPEAT_PARAM_REVERSE (3): 0.0 = off, >= 0.5 = on
+///   - BEAT_REPEAT_PARAM_MIX (4): 0.0-1.0
+/// - EFFECT_EARLY_REFLECTIONS (12):
+///   - EARLY_REFLECTIONS_PARAM_SIZE (0): 0.0-1.0
+///   - EARLY_REFLECTIONS_PARAM_DAMPING (1): 0.0-1.0
+///   - EARLY_REFLECTIONS_PARAM_MIX (2): 0.0-1.0
+/// - EFFECT_WIDENER (13):
+///   - WIDENER_PARAM_WIDTH (0): 0.0-2.0
+///   - WIDENER_PARAM_DELAY (1): 1.0-30.0 ms
+///   - WIDENER_PARAM_CROSSOVER (2): 20.0-1000.0 Hz
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an unknown effect or
+/// parameter, or an invalid delay timing constant.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_global_effect_param(
+    engine: *mut GooeyEngine,
+    effect: u32,
+    param: u32,
+    value: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_global_effect_param";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+
+    let Some(param_count) = global_effect_param_count(effect) else {
+        return fail(
+            GooeyResult::InvalidEffect,
+            format!("{FN}: unknown effect {effect}"),
+        );
+    };
+    if param >= param_count {
+        return fail(
+            GooeyResult::InvalidParam,
+            format!("{FN}: param {param} is not valid for effect {effect}"),
+        );
+    }
+    if !value.is_finite() {
+        return fail(
+            GooeyResult::InvalidValue,
+            format!("{FN}: value {value} is not finite"),
+        );
+    }
+    if effect == EFFECT_SATURATION
+        && param == SATURATION_PARAM_MODEL
+        && SaturatorModel::from_u32(value as u32).is_none()
+    {
+        return fail(
+            GooeyResult::InvalidValue,
+            format!("{FN}: {value} is not a SATURATOR_MODEL_* constant"),
+        );
+    }
+    let is_timing = (effect == EFFECT_DELAY && param == DELAY_PARAM_TIMING)
+        || (effect == EFFECT_BEAT_REPEAT && param == BEAT_REPEAT_PARAM_SLICE);
+    if is_timing && DelayTiming::from_timing_constant(value as u32).is_none() {
+        return fail(
+            GooeyResult::InvalidValue,
+            format!("{FN}: {value} is not a DELAY_TIMING_* constant"),
+        );
+    }
+
+    (*engine).submit(
+        FN,
+        ControlCommand::GlobalEffectParam {
+            effect,
+            param,
+            value,
+        },
+    )
+}
+
+/// Get a parameter value from a global effect
+///
+/// This provides a generic interface for reading any global effect's parameters.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `effect` - Effect ID (see EFFECT_* constants)
+/// * `param` - Parameter ID (depends on effect type)
+///
+/// # Returns
+/// The current parameter value, or -1.0 if the effect or parameter is invalid
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_global_effect_param(
+    engine: *mut GooeyEngine,
+    effect: u32,
+    param: u32,
+) -> f32 {
+    if engine.is_null() {
+        return -1.0;
+    }
+
+    let engine = &*engine;
+
+    match effect {
+        EFFECT_LOWPASS_FILTER => match param {
+            FILTER_PARAM_CUTOFF => engine.lowpass_filter.get_cutoff_freq(),
+            FILTER_PARAM_RESONANCE => engine.lowpass_filter.get_resonance(),
+            _ => -1.0, // Unknown parameter
+        },
+        EFFECT_DELAY => match param {
+            DELAY_PARAM_TIMING => engine.delay.get_timing() as f32,
+            DELAY_PARAM_FEEDBACK => engine.delay.get_feedback(),
+            DELAY_PARAM_MIX => engine.delay.get_mix(),
+            DELAY_PARAM_FILTER_CUTOFF => engine.delay.get_filter_cutoff(),
+            DELAY_PARAM_PINGPONG => {
+                if engine.delay.get_pingpong() {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            DELAY_PARAM_LOW_CUT => engine.delay.get_low_cut(),
+            DELAY_PARAM_FREEZE => {
+                if engine.delay.get_freeze() {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            _ => -1.0, // Unknown parameter
+        },
+        EFFECT_SATURATION => match param {
+            SATURATION_PARAM_DRIVE => engine.saturation.get_drive(),
+            SATURATION_PARAM_WARMTH => engine.saturation.get_warmth(),
+            SATURATION_PARAM_MIX => engine.saturation.get_mix(),
+            SATURATION_PARAM_MODEL => engine.saturation_model.load(Ordering::Relaxed) as f32,
+            SATURATION_PARAM_AUTO_GAIN => {
+                if engine.saturation.get_auto_gain() {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            _ => -1.0, // Unknown parameter
+        },
+        EFFECT_COMPRESSOR => match param {
+            COMPRESSOR_PARAM_THRESHOLD => engine.compressor.get_threshold(),
+            COMPRESSOR_PARAM_RATIO => engine.compressor.get_ratio(),
+            COMPRESSOR_PARAM_ATTACK => engine.compressor.get_attack(),
+            COMPRESSOR_PARAM_RELEASE => engine.compressor.get_release(),
+            COMPRESSOR_PARAM_MIX => engine.compressor.get_mix(),
+            _ => -1.0, // Unknown parameter
+        },
+        EFFECT_TILT_FILTER => match param {
+            TILT_PARAM_CUTOFF => engine.tilt_filter.get_cutoff(),
+            TILT_PARAM_RESONANCE => engine.tilt_filter.get_resonance(),
+            _ => -1.0, // Unknown parameter
+        },
+        EFFECT_REVERB => match param {
+            REVERB_PARAM_DECAY => engine.reverb.get_decay(),
+            REVERB_PARAM_MIX => engine.reverb.get_mix(),
+            REVERB_PARAM_DAMPING => engine.reverb.get_damping(),
+            _ => -1.0, // Unknown parameter
+        },
+        EFFECT_PLATE_REVERB => match param {
+            PLATE_PARAM_DECAY => engine.plate_reverb.get_decay(),
+            PLATE_PARAM_MIX => engine.plate_reverb.get_mix(),
+            PLATE_PARAM_DAMPING => engine.plate_reverb.get_damping(),
+            PLATE_PARAM_PREDELAY => engine.plate_reverb.get_predelay(),
+            PLATE_PARAM_WIDTH => engine.plate_reverb.get_width(),
+            PLATE_PARAM_SIZE => engine.plate_reverb.get_size(),
+            _ => -1.0, // Unknown parameter
+        },
+        EFFECT_LIMITER => match param {
+            LIMITER_PARAM_THRESHOLD => engine.limiter.get_threshold(),
+            _ => -1.0, // Unknown parameter
+        },
+        EFFECT_BEAT_REPEAT => match param {
+            BEAT_REPEAT_PARAM_SLICE => engine.beat_repeat.get_timing() as f32,
+            BEAT_REPEAT_PARAM_LENGTH => engine.beat_repeat.get_length(),
+            BEAT_REPEAT_PARAM_PITCH => engine.beat_repeat.get_pitch(),
+            BEAT_REPEAT_PARAM_REVERSE => {
+                if engine.beat_repeat.get_reverse() {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            BEAT_REPEAT_PARAM_MIX => engine.beat_repeat.get_mix(),
+            _ => -1.0, // Unknown parameter
+        },
+        EFFECT_DUCKER => match param {
+            DUCKER_PARAM_DEPTH => engine.ducker.get_depth(),
+            DUCKER_PARAM_ATTACK => engine.ducker.get_attack(),
+            DUCKER_PARAM_HOLD => engine.ducker.get_hold(),
+            DUCKER_PARAM_RELEASE => engine.ducker.get_release(),
+            _ => -1.0, // Unknown parameter
+        },
+        EFFECT_EARLY_REFLECTIONS => match param {
+            EARLY_REFLECTIONS_PARAM_SIZE => engine.early_reflections.get_size(),
+            EARLY_REFLECTIONS_PARAM_DAMPING => engine.early_reflections.get_damping(),
+            EARLY_REFLECTIONS_PARAM_MIX => engine.early_reflections.get_mix(),
+            _ => -1.0, // Unknown parameter
+        },
+        EFFECT_WIDENER => match param {
+            WIDENER_PARAM_WIDTH => engine.widener.get_width(),
+            WIDENER_PARAM_DELAY => engine.widener.get_delay(),
+            WIDENER_PARAM_CROSSOVER => engine.widener.get_crossover(),
+            _ => -1.0, // Unknown parameter
+        },
+        _ => -1.0, // Unknown effect
+    }
+}
+
+/// Enable or disable a global effect
+///
+/// When disabled, the effect is bypassed and does not process audio.
+/// This is useful for A/B comparison or saving CPU when an effect is not needed.
+/// Saturation, compressor, and limiter are disabled by default.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `effect` - Effect ID (see EFFECT_* constants)
+/// * `enabled` - Whether the effect should be active
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_global_effect_enabled(
+    engine: *mut GooeyEngine,
+    effect: u32,
+    enabled: bool,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_global_effect_enabled";
+    edit(engine, || {
+        if engine.is_null() {
+            return null_engine(FN);
+        }
+
+        let engine = &mut *engine;
+
+        match effect {
+            EFFECT_LOWPASS_FILTER => engine
+                .lowpass_filter_enabled
+                .store(enabled, Ordering::Relaxed),
+            EFFECT_DELAY => engine.delay_enabled.store(enabled, Ordering::Relaxed),
+            EFFECT_SATURATION => engine.saturation_enabled.store(enabled, Ordering::Relaxed),
+            EFFECT_COMPRESSOR => engine.compressor_enabled.store(enabled, Ordering::Relaxed),
+            EFFECT_TILT_FILTER => engine.tilt_filter_enabled.store(enabled, Ordering::Relaxed),
+            EFFECT_LIMITER => engine.limiter_enabled.store(enabled, Ordering::Relaxed),
+            EFFECT_REVERB => engine.reverb_enabled.store(enabled, Ordering::Relaxed),
+            EFFECT_PLATE_REVERB => engine
+                .plate_reverb_enabled
+                .store(enabled, Ordering::Relaxed),
+            EFFECT_WAVESHAPER => engine.waveshaper_enabled.store(enabled, Ordering::Relaxed),
+            EFFECT_FEEDBACK_WAVESHAPER => engine
+                .feedback_waveshaper_enabled
+                .store(enabled, Ordering::Relaxed),
+            EFFECT_DUCKER => engine.ducker_enabled.store(enabled, Ordering::Relaxed),
+            EFFECT_BEAT_REPEAT => engine.beat_repeat_enabled.store(enabled, Ordering::Relaxed),
+            EFFECT_EARLY_REFLECTIONS => engine
+                .early_reflections_enabled
+                .store(enabled, Ordering::Relaxed),
+            EFFECT_WIDENER => engine.widener_enabled.store(enabled, Ordering::Relaxed),
+            _ => {
+                return fail(
+                    GooeyResult::InvalidEffect,
+                    format!("{FN}: unknown effect {effect}"),
+                )
+            }
+        }
+        GooeyResult::Ok
+    })
+}
+
+/// Check if a global effect is enabled
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `effect` - Effect ID (see EFFECT_* constants)
+///
+/// # Returns
+/// `true` if the effect is enabled, `false` if disabled or if the effect ID is invalid
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_global_effect_enabled(
+    engine: *mut GooeyEngine,
+    effect: u32,
+) -> bool {
+    if engine.is_null() {
+        return false;
+    }
+
+    let engine = &*engine;
+
+    match effect {
+        EFFECT_LOWPASS_FILTER => engine.lowpass_filter_enabled.load(Ordering::Relaxed),
+        EFFECT_DELAY => engine.delay_enabled.load(Ordering::Relaxed),
+        EFFECT_SATURATION => engine.saturation_enabled.load(Ordering::Relaxed),
+        EFFECT_COMPRESSOR => engine.compressor_enabled.load(Ordering::Relaxed),
+        EFFECT_TILT_FILTER => engine.tilt_filter_enabled.load(Ordering::Relaxed),
+        EFFECT_LIMITER => engine.limiter_enabled.load(Ordering::Relaxed),
+        EFFECT_REVERB => engine.reverb_enabled.load(Ordering::Relaxed),
+        EFFECT_PLATE_REVERB => engine.plate_reverb_enabled.load(Ordering::Relaxed),
+        EFFECT_WAVESHAPER => engine.waveshaper_enabled.load(Ordering::Relaxed),
+        EFFECT_FEEDBACK_WAVESHAPER => engine.feedback_waveshaper_enabled.load(Ordering::Relaxed),
+        EFFECT_DUCKER => engine.ducker_enabled.load(Ordering::Relaxed),
+        EFFECT_BEAT_REPEAT => engine.beat_repeat_enabled.load(Ordering::Relaxed),
+        EFFECT_EARLY_REFLECTIONS => engine.early_reflections_enabled.load(Ordering::Relaxed),
+        EFFECT_WIDENER => engine.widener_enabled.load(Ordering::Relaxed),
+        _ => false, // Unknown effect
+    }
+}
+
+// =============================================================================
+// Effect lanes
+// =============================================================================
+
+/// Number of effect lanes the engine holds, motion lanes included
+pub const EFFECT_LANE_MAX: u32 = 32;
+/// Steps in an effect lane (one bar of sixteenths)
+pub const EFFECT_LANE_STEP_COUNT: u32 = EFFECT_LANE_STEPS as u32;
+
+/// The parameter an effect lane drives.
+#[derive(Clone, Copy)]
+enum EffectLaneTarget {
+    Global {
+        effect: u32,
+        param: u32,
+    },
+    Track {
+        track: u32,
+        slot: u32,
+        param: u32,
+    },
+    /// A channel's instrument parameter, recorded by motion recording
+    Instrument {
+        channel: u32,
+        param: u32,
+    },
+}
+
+#[derive(Clone, Copy)]
+struct EffectLaneSlot {
+    lane: EffectLane,
+    target: EffectLaneTarget,
+    /// Value last written, so unchanged steps don't touch the effect
+    last: Option<f32>,
+    /// Step being recorded into, which plays the live value instead
+    held: Option<usize>,
+}
+
+/// The lane step a transport step position falls in.
+fn effect_lane_step(position: f64) -> usize {
+    position.floor() as usize % EFFECT_LANE_STEPS
+}
+
+impl GooeyEngine {
+    /// Put a new, empty lane in the first free slot.
+    fn add_effect_lane(&mut self, target: EffectLaneTarget) -> Option<usize> {
+        let index = self.effect_lanes.iter().position(Option::is_none)?;
+        self.effect_lanes[index] = Some(EffectLaneSlot {
+            lane: EffectLane::new(),
+            target,
+            last: None,
+            held: None,
+        });
+        Some(index)
+    }
+
+    fn effect_lane_mut(&mut self, lane: u32) -> Option<&mut EffectLane> {
+        self.effect_lanes
+            .get_mut(lane as usize)?
+            .as_mut()
+            .map(|slot| &mut slot.lane)
+    }
+}
+
+/// Add the lane for `target`, returning its index or -1 when all are in use.
+fn create_effect_lane(fn_name: &str, engine: &mut GooeyEngine, target: EffectLaneTarget) -> i32 {
+    match engine.add_effect_lane(target) {
+        Some(index) => index as i32,
+        None => {
+            fail(
+                GooeyResult::InvalidValue,
+                format!("{fn_name}: all {EFFECT_LANE_MAX} effect lanes are in use"),
+            );
+            -1
+        }
+    }
+}
+
+/// Create an effect lane driving a global effect parameter
+///
+/// An effect lane is a 16-step sequence of values for one effect parameter
+/// (filter cutoff steps, delay mix steps, ...). It follows the sequencers'
+/// playhead while the transport runs, and on each step writes its value to
+/// the parameter exactly as `gooey_engine_set_global_effect_param` would.
+/// Steps start unset; fill them with `gooey_engine_effect_lane_set_step`.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `effect` - Effect ID (see EFFECT_* constants)
+/// * `param` - Parameter ID (see `gooey_engine_set_global_effect_param`)
+///
+/// # Returns
+/// The lane index (`0..EFFECT_LANE_MAX`), or -1 for a null engine, an unknown
+/// effect or parameter, or when every lane is in use.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_effect_lane_create_global(
+    engine: *mut GooeyEngine,
+    effect: u32,
+    param: u32,
+) -> i32 {
+    const FN: &str = "gooey_engine_effect_lane_create_global";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            null_engine(FN);
+            return -1;
+        };
+        let Some(param_count) = global_effect_param_count(effect) else {
+            fail(
+                GooeyResult::InvalidEffect,
+                format!("{FN}: unknown effect {effect}"),
+            );
+            return -1;
+        };
+        if param >= param_count {
+            fail(
+                GooeyResult::InvalidParam,
+                format!("{FN}: param {param} is not valid for effect {effect}"),
+            );
+            return -1;
+        }
+        create_effect_lane(FN, engine, EffectLaneTarget::Global { effect, param })
+    })
+}
+
+/// Create an effect lane driving a parameter of a mixer track effect
+///
+/// Like `gooey_engine_effect_lane_create_global`, for the effect at `slot`
+/// of a mixer graph track (see `gooey_engine_track_effect_add`), so one
+/// instrument's own filter or delay can be stepped. The lane addresses the
+/// slot, so it follows whatever effect is moved into that slot later.
+///
+/// # Returns
+/// The lane index, or -1 for a null engine, a track slot with no effect, or
+/// when every lane is in use.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_effect_lane_create_track(
+    engine: *mut GooeyEngine,
+    track: u32,
+    slot: u32,
+    param: u32,
+) -> i32 {
+    const FN: &str = "gooey_engine_effect_lane_create_track";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            null_engine(FN);
+            return -1;
+        };
+        if engine
+            .graph
+            .effect_type_at(track as usize, slot as usize)
+            .is_none()
+        {
+            fail(
+                GooeyResult::InvalidEffect,
+                format!("{FN}: track {track} has no effect in slot {slot}"),
+            );
+            return -1;
+        }
+        create_effect_lane(FN, engine, EffectLaneTarget::Track { track, slot, param })
+    })
+}
+
+/// Remove an effect lane, freeing its index. The parameter keeps the value
+/// the lane last wrote.
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine or an unused lane index.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_effect_lane_remove(
+    engine: *mut GooeyEngine,
+    lane: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_effect_lane_remove";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return null_engine(FN);
+        };
+        match engine.effect_lanes.get_mut(lane as usize) {
+            Some(slot @ Some(_)) => {
+                *slot = None;
+                GooeyResult::Ok
+            }
+            _ => fail(GooeyResult::InvalidValue, format!("{FN}: no lane {lane}")),
+        }
+    })
+}
+
+/// Set the value of one step of an effect lane
+///
+/// Steps left unset hold the value of the set step before them.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `lane` - Lane index from `gooey_engine_effect_lane_create_*`
+/// * `step` - Step (0 to EFFECT_LANE_STEP_COUNT - 1)
+/// * `value` - Parameter value, in the parameter's own range
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an unused lane, an
+/// out-of-range step or a non-finite value.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_effect_lane_set_step(
+    engine: *mut GooeyEngine,
+    lane: u32,
+    step: u32,
+    value: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_effect_lane_set_step";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return null_engine(FN);
+        };
+        let Some(effect_lane) = engine.effect_lane_mut(lane) else {
+            return fail(GooeyResult::InvalidValue, format!("{FN}: no lane {lane}"));
+        };
+        if step >= EFFECT_LANE_STEP_COUNT {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: step {step} is out of range"),
+            );
+        }
+        if !value.is_finite() {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: value {value} is not finite"),
+            );
+        }
+        effect_lane.set_step(step as usize, value);
+        GooeyResult::Ok
+    })
+}
+
+/// Unset one step of an effect lane, so it holds the value before it
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an unused lane or an
+/// out-of-range step.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_effect_lane_clear_step(
+    engine: *mut GooeyEngine,
+    lane: u32,
+    step: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_effect_lane_clear_step";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return null_engine(FN);
+        };
+        let Some(effect_lane) = engine.effect_lane_mut(lane) else {
+            return fail(GooeyResult::InvalidValue, format!("{FN}: no lane {lane}"));
+        };
+        if step >= EFFECT_LANE_STEP_COUNT {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: step {step} is out of range"),
+            );
+        }
+        effect_lane.clear_step(step as usize);
+        GooeyResult::Ok
+    })
+}
+
+/// Read one step of an effect lane
+///
+/// # Returns
+/// `true` with the value in `out_value` for a set step, `false` for an unset
+/// step, an invalid lane or step, or a null pointer.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`, and
+/// `out_value` must be a valid pointer to an `f32`.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_effect_lane_get_step(
+    engine: *const GooeyEngine,
+    lane: u32,
+    step: u32,
+    out_value: *mut f32,
+) -> bool {
+    if out_value.is_null() {
+        return false;
+    }
+    let value = engine
+        .as_ref()
+        .and_then(|engine| engine.effect_lanes.get(lane as usize)?.as_ref())
+        .and_then(|slot| slot.lane.step(step as usize));
+    match value {
+        Some(value) => {
+            *out_value = value;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Switch an effect lane between stepped and interpolated playback
+///
+/// Stepped lanes (the default) jump to each set step's value on the step.
+/// Interpolated lanes glide linearly from each set step to the next, so a
+/// cutoff set on steps 0 and 4 sweeps across the steps between.
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine or an unused lane.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_effect_lane_set_interpolated(
+    engine: *mut GooeyEngine,
+    lane: u32,
+    interpolated: bool,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_effect_lane_set_interpolated";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return null_engine(FN);
+        };
+        let Some(effect_lane) = engine.effect_lane_mut(lane) else {
+            return fail(GooeyResult::InvalidValue, format!("{FN}: no lane {lane}"));
+        };
+        effect_lane.set_interpolate(interpolated);
+        GooeyResult::Ok
+    })
+}
+
+/// Turn motion recording on or off
+///
+/// While motion recording is on and the transport runs, every change to a
+/// channel's instrument parameter (`gooey_engine_set_channel_param`, the
+/// per-instrument setters, batches and the parameter table) is written into
+/// a motion lane for that parameter at the step playing. The lane is an
+/// interpolated effect lane created on the first change, so each following
+/// cycle replays the recorded moves, gliding between them. Steps nobody
+/// touched hold the move before them. Recording again overdubs: steps the
+/// knob passes over are replaced, the rest kept.
+///
+/// Motion lanes are ordinary effect lanes: find one with
+/// `gooey_engine_motion_lane`, then read, edit, switch to stepped playback
+/// or remove it with the `gooey_engine_effect_lane_*` functions. Changes
+/// made while stopped, or once every lane is in use, are not recorded.
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_motion_recording(
+    engine: *mut GooeyEngine,
+    recording: bool,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_motion_recording";
+    let Some(engine) = engine.as_ref() else {
+        return null_engine(FN);
+    };
+    engine.motion_recording.store(recording, Ordering::Relaxed);
+    GooeyResult::Ok
+}
+
+/// Whether motion recording is on. Returns false if `engine` is null.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_motion_recording(engine: *const GooeyEngine) -> bool {
+    engine
+        .as_ref()
+        .is_some_and(|engine| engine.motion_recording.load(Ordering::Relaxed))
+}
+
+/// The effect lane holding a channel parameter's recorded motion
+///
+/// # Returns
+/// The lane index, or -1 for a null engine or a parameter with no motion
+/// recorded
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_motion_lane(
+    engine: *const GooeyEngine,
+    channel: u32,
+    param: u32,
+) -> i32 {
+    engine
+        .as_ref()
+        .and_then(|engine| engine.motion_lane(channel, param))
+        .map_or(-1, |index| index as i32)
+}
+
+// =============================================================================
+// Compressor sidechain control
+// =============================================================================
+
+/// Set the compressor sidechain source instrument
+///
+/// When set to an instrument (INSTRUMENT_KICK, INSTRUMENT_SNARE, INSTRUMENT_HIHAT,
+/// INSTRUMENT_TOM), the compressor's envelope follower tracks that instrument's output
+/// while gain reduction is applied to the full mix. This enables techniques like
+/// kick-driven ducking.
+///
+/// Use COMPRESSOR_SIDECHAIN_NONE (0xFFFFFFFF) to use the main mix as input (default).
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_compressor_sidechain(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+
+        let engine = &mut *engine;
+        engine.compressor_sidechain = instrument;
+    })
+}
+
+/// Get the current compressor sidechain source
+///
+/// # Returns
+/// An INSTRUMENT_* constant, or COMPRESSOR_SIDECHAIN_NONE if using the main mix
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_compressor_sidechain(engine: *mut GooeyEngine) -> u32 {
+    if engine.is_null() {
+        return COMPRESSOR_SIDECHAIN_NONE;
+    }
+
+    let engine = &*engine;
+    engine.compressor_sidechain
+}
+
+/// Set the instrument whose triggers fire the ducker
+///
+/// Every trigger of the source instrument (manual or sequenced) starts the
+/// ducker's attack / hold / release envelope on the master bus, sample-aligned
+/// with the hit. Unlike the compressor sidechain there is no level detection,
+/// so the duck is the same on every hit regardless of the source's sound.
+/// Enable it with `EFFECT_DUCKER`.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `instrument` - An INSTRUMENT_* constant, or DUCKER_SOURCE_NONE (default)
+///
+/// # Returns
+/// `GooeyResult::Ok`, `NullPointer`, or `InvalidInstrument`.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_ducker_source(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_ducker_source";
+    edit(engine, || {
+        if engine.is_null() {
+            return null_engine(FN);
+        }
+        if instrument != DUCKER_SOURCE_NONE && instrument as usize >= NUM_CHANNELS {
+            return fail(
+                GooeyResult::InvalidInstrument,
+                format!("{FN}: unknown instrument {instrument}"),
+            );
+        }
+
+        let engine = &mut *engine;
+        engine.ducker_source = instrument;
+        GooeyResult::Ok
+    })
+}
+
+/// Get the instrument whose triggers fire the ducker
+///
+/// # Returns
+/// An INSTRUMENT_* constant, or DUCKER_SOURCE_NONE if unset
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_ducker_source(engine: *mut GooeyEngine) -> u32 {
+    if engine.is_null() {
+        return DUCKER_SOURCE_NONE;
+    }
+
+    let engine = &*engine;
+    engine.ducker_source
+}
+
+/// Choose what the beat repeat captures and replaces
+///
+/// With BEAT_REPEAT_SOURCE_MASTER (default) the repeat runs on the master bus
+/// ahead of the reorderable effects. With an INSTRUMENT_* constant it runs on
+/// that voice only (before pan), so e.g. the hats can stutter while the rest
+/// of the kit plays on. Switching source while a repeat is held continues the
+/// loop on the new source; release first for a clean handover.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `source` - BEAT_REPEAT_SOURCE_MASTER or an INSTRUMENT_* constant
+///
+/// # Returns
+/// `GooeyResult::Ok`, `NullPointer`, or `InvalidInstrument`.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_beat_repeat_source(
+    engine: *mut GooeyEngine,
+    source: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_beat_repeat_source";
+    edit(engine, || {
+        if engine.is_null() {
+            return null_engine(FN);
+        }
+        if source != BEAT_REPEAT_SOURCE_MASTER && source as usize >= NUM_CHANNELS {
+            return fail(
+                GooeyResult::InvalidInstrument,
+                format!("{FN}: unknown instrument {source}"),
+            );
+        }
+
+        let engine = &mut *engine;
+        engine.beat_repeat_source = source;
+        GooeyResult::Ok
+    })
+}
+
+/// Get what the beat repeat captures
+///
+/// # Returns
+/// An INSTRUMENT_* constant, or BEAT_REPEAT_SOURCE_MASTER
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_beat_repeat_source(engine: *mut GooeyEngine) -> u32 {
+    if engine.is_null() {
+        return BEAT_REPEAT_SOURCE_MASTER;
+    }
+
+    let engine = &*engine;
+    engine.beat_repeat_source
+}
+
+/// Hold or release the beat repeat (momentary performance control)
+///
+/// Holding arms the repeat; it latches on the next slice boundary of the
+/// transport grid and loops until released, then fades back to the live
+/// signal within a few milliseconds. Has no audible effect unless
+/// `EFFECT_BEAT_REPEAT` is enabled. Safe to call from any thread.
+///
+/// # Returns
+/// `GooeyResult::Ok`, or `NullPointer` for a null engine.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_beat_repeat_active(
+    engine: *mut GooeyEngine,
+    active: bool,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_beat_repeat_active";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+
+    let engine = &*engine;
+    engine.beat_repeat.set_active(active);
+    GooeyResult::Ok
+}
+
+/// Check whether the beat repeat is held
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_beat_repeat_active(engine: *const GooeyEngine) -> bool {
+    if engine.is_null() {
+        return false;
+    }
+
+    let engine = &*engine;
+    engine.beat_repeat.is_active()
+}
+
+// =============================================================================
+// Early reflections presets
+// =============================================================================
+
+/// Load an early reflections preset, setting size, damping and mix
+///
+/// The change is queued like any other global effect parameter and smoothed
+/// on the audio thread. Does not enable the effect; use
+/// `EFFECT_EARLY_REFLECTIONS` with `gooey_engine_set_global_effect_enabled`.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `preset_id` - EARLY_REFLECTIONS_PRESET_TIGHT_ROOM or EARLY_REFLECTIONS_PRESET_BIG_ROOM
+///
+/// # Returns
+/// `GooeyResult::Ok`, `NullPointer` for a null engine, `InvalidValue` for an
+/// unknown preset, or `QueueFull` if the control queue has no room.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_load_early_reflections_preset(
+    engine: *mut GooeyEngine,
+    preset_id: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_load_early_reflections_preset";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+    let Some(preset) = EarlyReflectionsPreset::from_u32(preset_id) else {
+        return fail(
+            GooeyResult::InvalidValue,
+            format!("{FN}: unknown preset {preset_id}"),
+        );
+    };
+
+    let engine = &mut *engine;
+    let (size, damping, mix) = preset.values();
+    for (param, value) in [
+        (EARLY_REFLECTIONS_PARAM_SIZE, size),
+        (EARLY_REFLECTIONS_PARAM_DAMPING, damping),
+        (EARLY_REFLECTIONS_PARAM_MIX, mix),
+    ] {
+        let result = engine.submit(
+            FN,
+            ControlCommand::GlobalEffectParam {
+                effect: EFFECT_EARLY_REFLECTIONS,
+                param,
+                value,
+            },
+        );
+        if result != GooeyResult::Ok {
+            return result;
+        }
+    }
+    GooeyResult::Ok
+}
+
+// =============================================================================
+// Widener correlation meter
+// =============================================================================
+
+/// Get the widener's output correlation meter
+///
+/// The left/right correlation of the widened mix, averaged over ~300 ms:
+/// +1.0 is mono, 0.0 fully decorrelated, and negative values mean parts of
+/// the mix will cancel when summed to mono. Reads +1.0 on silence, and holds
+/// its last value while `EFFECT_WIDENER` is disabled. Safe to call from any
+/// thread.
+///
+/// # Returns
+/// The correlation (-1.0 to 1.0), or 1.0 for a null engine
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_widener_correlation(engine: *const GooeyEngine) -> f32 {
+    engine.as_ref().map_or(1.0, |e| e.widener.correlation())
+}
+
+// =============================================================================
+// Output safety
+// =============================================================================
+
+/// Set an output safety parameter
+///
+/// The safety stage is always on and sits after the limiter: a DC blocker,
+/// a soft-clip ceiling, and an emergency mute that trips when the output
+/// stays above the fault level for the fault hold time.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `param` - OUTPUT_SAFETY_PARAM_* constant
+/// * `value` - New value, clamped to the parameter's range
+///
+/// # Returns
+/// `GooeyResult::Ok`, `NullPointer`, `InvalidParam` for an unknown
+/// parameter, or `InvalidValue` for a non-finite value.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_output_safety_param(
+    engine: *mut GooeyEngine,
+    param: u32,
+    value: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_output_safety_param";
+    edit(engine, || {
+        if engine.is_null() {
+            return null_engine(FN);
+        }
+        if !value.is_finite() {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: value {value} is not finite"),
+            );
+        }
+
+        let safety = &(*engine).output_safety;
+        match param {
+            OUTPUT_SAFETY_PARAM_CEILING => safety.set_ceiling(value),
+            OUTPUT_SAFETY_PARAM_FAULT_LEVEL => safety.set_fault_level(value),
+            OUTPUT_SAFETY_PARAM_FAULT_HOLD => safety.set_fault_hold(value),
+            _ => {
+                return fail(
+                    GooeyResult::InvalidParam,
+                    format!("{FN}: unknown param {param}"),
+                )
+            }
+        }
+        GooeyResult::Ok
+    })
+}
+
+/// Get an output safety parameter
+///
+/// # Returns
+/// The current value, or -1.0 for a null engine or an unknown parameter
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_output_safety_param(
+    engine: *const GooeyEngine,
+    param: u32,
+) -> f32 {
+    let Some(engine) = engine.as_ref() else {
+        return -1.0;
+    };
+    let safety = &engine.output_safety;
+    match param {
+        OUTPUT_SAFETY_PARAM_CEILING => safety.get_ceiling(),
+        OUTPUT_SAFETY_PARAM_FAULT_LEVEL => safety.get_fault_level(),
+        OUTPUT_SAFETY_PARAM_FAULT_HOLD => safety.get_fault_hold(),
+        _ => -1.0, // Unknown parameter
+    }
+}
+
+/// Check whether the emergency mute has tripped
+///
+/// While set, the engine renders silence. Poll it from the UI to surface the
+/// fault, fix the cause (a runaway feedback or resonance setting), then call
+/// `gooey_engine_clear_output_fault`. Safe to call from any thread.
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_output_fault(engine: *const GooeyEngine) -> bool {
+    engine
+        .as_ref()
+        .is_some_and(|e| e.output_safety.is_faulted())
+}
+
+/// Clear an output fault and unmute
+///
+/// If the runaway is still going, the mute trips again after the fault hold
+/// time. Safe to call from any thread.
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_clear_output_fault(engine: *const GooeyEngine) {
+    if let Some(engine) = engine.as_ref() {
+        engine.output_safety.clear_fault();
+    }
+}
+
+// =============================================================================
+// Panic
+// =============================================================================
+
+/// Panic: silence everything that is sounding
+///
+/// For stuck feedback or hanging notes. Starting with the next render, the
+/// master output fades out over 5 ms; at silence every voice is choked
+/// (instruments, sampler racks, poly synth, granulator), delay/reverb and
+/// other effect tails are cleared on the master, the mixer tracks and the
+/// loop channels, and the output fades back in. Manual triggers that have not
+/// fired yet are dropped. The transport, patterns and loops keep running, so
+/// the next hit plays normally. Safe to call from any thread.
+///
+/// # Returns
+/// `GooeyResult::Ok`, or `NullPointer` for a null engine.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_panic(engine: *mut GooeyEngine) -> GooeyResult {
+    const FN: &str = "gooey_engine_panic";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+
+    let engine = &*engine;
+    for voice in engine.voices_iter() {
+        voice.trigger_pending.store(false, Ordering::Release);
+        voice.trigger_note.store(NO_TRIGGER_NOTE, Ordering::Release);
+        voice.release_pending.store(false, Ordering::Release);
+    }
+    engine.panic_requested.store(true, Ordering::Release);
+    GooeyResult::Ok
+}
+
+// =============================================================================
+// Master gain
+// =============================================================================
+
+/// Set the master output gain.
+///
+/// This gain is applied to the complete instrument sum before global effects.
+/// It is smoothed over 30ms to prevent clicks.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `gain` - Linear gain, clamped to 0.0-2.0. The default is 0.25.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_master_gain(engine: *mut GooeyEngine, gain: f32) {
+    if engine.is_null() || !gain.is_finite() {
+        return;
+    }
+    (*engine).submit(
+        "gooey_engine_set_master_gain",
+        ControlCommand::MasterGain(gain),
+    );
+}
+
+/// Get the current master output gain target.
+///
+/// # Returns
+/// The current linear gain target, or 0.25 if `engine` is null.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_master_gain(engine: *const GooeyEngine) -> f32 {
+    if engine.is_null() {
+        return DEFAULT_MASTER_GAIN;
+    }
+    (*engine).master_gain.target()
+}
+
+// =============================================================================
+// Master tuning
+// =============================================================================
+
+/// Retune every pitched voice at once: set the A4 reference and a transpose.
+///
+/// Kick, snare, tom, bass, FM snap, rimshot, cowbell and the poly synth
+/// follow it on top of their own tuning parameters, including per-step
+/// notes; the hi-hat and shaker are unpitched and ignore it. Voices created
+/// later (slots, instrument swaps) pick it up too.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `a4_hz` - A4 reference in Hz (440 = concert pitch), clamped to 400-480
+/// * `transpose` - Semitones (fractions allowed), clamped to -24..=24
+///
+/// # Returns
+/// `GooeyResult::Ok`, `NullPointer` for a null engine, or `InvalidValue`
+/// for a non-finite value. Out-of-range values are clamped and recorded as
+/// a warning.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_master_tuning(
+    engine: *mut GooeyEngine,
+    a4_hz: f32,
+    transpose: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_master_tuning";
+    edit(engine, || {
+        if engine.is_null() {
+            return null_engine(FN);
+        }
+        if !a4_hz.is_finite() || !transpose.is_finite() {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: a4_hz {a4_hz} / transpose {transpose} must be finite"),
+            );
+        }
+        let tuning = MasterTuning::new(a4_hz, transpose);
+        if tuning.a4_hz() != a4_hz {
+            let (min, max) = A4_HZ_RANGE;
+            warn(format!(
+                "{FN}: a4_hz {a4_hz} is outside {min}..={max}; clamped to {}",
+                tuning.a4_hz()
+            ));
+        }
+        if tuning.transpose() != transpose {
+            let (min, max) = TRANSPOSE_RANGE;
+            warn(format!(
+                "{FN}: transpose {transpose} is outside {min}..={max}; clamped to {}",
+                tuning.transpose()
+            ));
+        }
+
+        let engine = &mut *engine;
+        engine.master_tuning = tuning;
+        let ratio = tuning.ratio();
+        for voice in engine.voices_iter_mut() {
+            voice.instrument.set_pitch_ratio(ratio);
+        }
+        for voice in &mut engine.preview_voices {
+            voice.set_pitch_ratio(ratio);
+        }
+        engine.poly_synth.set_pitch_ratio(ratio);
+        GooeyResult::Ok
+    })
+}
+
+/// Get the A4 reference set by `gooey_engine_set_master_tuning`.
+///
+/// # Returns
+/// The reference in Hz, or 440.0 if `engine` is null.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_master_a4(engine: *const GooeyEngine) -> f32 {
+    if engine.is_null() {
+        return MasterTuning::default().a4_hz();
+    }
+    (*engine).master_tuning.a4_hz()
+}
+
+/// Get the transpose set by `gooey_engine_set_master_tuning`.
+///
+/// # Returns
+/// The transpose in semitones, or 0.0 if `engine` is null.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_master_transpose(engine: *const GooeyEngine) -> f32 {
+    if engine.is_null() {
+        return 0.0;
+    }
+    (*engine).master_tuning.transpose()
+}
+
+// =============================================================================
+// BPM control
+// =============================================================================
+
+/// Slowest tempo `gooey_engine_set_bpm` accepts.
+pub const MIN_BPM: f32 = 1.0;
+/// Fastest tempo `gooey_engine_set_bpm` accepts.
+pub const MAX_BPM: f32 = 999.0;
+
+/// Set the global BPM (beats per minute)
+///
+/// This affects all sequencer timing (kick, snare, hihat, tom).
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `bpm` - Beats per minute (typically 60-200); clamped to
+///   [`MIN_BPM`]..=[`MAX_BPM`], NaN and infinities are ignored
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_bpm(engine: *mut GooeyEngine, bpm: f32) {
+    edit(engine, || {
+        if engine.is_null() || !bpm.is_finite() {
+            return;
+        }
+
+        (*engine).retime(bpm);
+    });
+    restretch_samplers(engine);
+}
+
+/// Re-render tempo-following sampler slots still stretched for an earlier
+/// BPM.
+///
+/// `gooey_engine_set_bpm` does this itself. A host that takes the tempo on
+/// the audio thread (the plugin wrapper follows the DAW transport there)
+/// retimes the engine without stretching and calls this from another
+/// thread, since stretching allocates and takes time.
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sampler_follow_tempo(engine: *mut GooeyEngine) {
+    restretch_samplers(engine);
+}
+
+/// Bring every sampler slot's stretch up to date with its tempo and pitch.
+/// The stale slots are listed in one edit, stretched on the calling thread,
+/// and installed in a second, short edit, so the audio thread never waits on
+/// a stretch. A slot reloaded or retimed in between keeps its state for the
+/// next call.
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`
+unsafe fn restretch_samplers(engine: *mut GooeyEngine) {
+    if engine.is_null() {
+        return;
+    }
+    // Reserved up front: the listing runs on the audio thread
+    let mut stretches = Vec::with_capacity(SAMPLER_RACK_MAX as usize * SAMPLER_SLOT_COUNT as usize);
+    edit(engine, || {
+        for (index, rack) in (*engine).samplers.iter().enumerate() {
+            if let Some(rack) = rack {
+                rack.stretches(|stretch| stretches.push((index, stretch)));
+            }
+        }
+    });
+    if stretches.is_empty() {
+        return;
+    }
+    for (_, stretch) in &mut stretches {
+        stretch.render();
+    }
+    edit(engine, || {
+        for (index, stretch) in &mut stretches {
+            if let Some(Some(rack)) = (*engine).samplers.get_mut(*index) {
+                rack.install(stretch);
+            }
+        }
+    });
+    // `stretches` now holds the buffers the slots played before, freed here
+}
+
+impl GooeyEngine {
+    /// Set the tempo everywhere except the stretch of tempo-following sampler
+    /// slots (see [`follow_tempo`](Self::follow_tempo)). Allocation-free, so
+    /// a host may call it on the audio thread. NaN and infinities are ignored.
+    fn retime(&mut self, bpm: f32) {
+        if !bpm.is_finite() {
+            return;
+        }
+        // A zero or vanishing tempo would make a step infinitely long
+        let bpm = bpm.clamp(MIN_BPM, MAX_BPM);
+        self.bpm = bpm;
+        for seq in self.sequencers_iter_mut() {
+            seq.set_bpm(bpm);
+        }
+        for rack in self.samplers.iter_mut().flatten() {
+            rack.set_bpm(bpm);
+        }
+
+        // Update delay BPM for clocked timing
+        self.delay.set_bpm(bpm);
+        self.beat_repeat.set_bpm(bpm);
+
+        // Update LFO BPM values for BPM-synced LFOs
+        for lfo in &mut self.lfos {
+            lfo.set_bpm(bpm);
+        }
+
+        // Seed BPM for any future note-synced per-channel loop effects.
+        self.mixer.set_bpm(bpm);
+
+        // Propagate BPM to note-synced effects in per-track racks.
+        self.graph.set_bpm(bpm);
+    }
+
+    /// [`retime`](Self::retime) from the audio thread, between renders. An
+    /// edit being made in place (see [`RenderGate`]) is not waited for:
+    /// nothing changes and this returns false, so the caller retries next
+    /// block.
+    #[cfg(feature = "plugin")]
+    pub(crate) fn try_retime(&mut self, bpm: f32) -> bool {
+        if !self.gate.try_enter_audio() {
+            return false;
+        }
+        self.retime(bpm);
+        self.gate.end_render();
+        true
+    }
+}
+
+/// Get the current BPM.
+///
+/// # Returns
+/// The current beats per minute, or 120.0 if `engine` is null.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_bpm(engine: *const GooeyEngine) -> f32 {
+    if engine.is_null() {
+        return 120.0;
+    }
+    let engine = &*engine;
+    engine.bpm
+}
+
+/// Mark the engine as being driven by an external tempo source (e.g. Ableton Link).
+///
+/// When enabled, the host should route local BPM changes through the external
+/// sync source instead of calling `gooey_engine_set_bpm` directly, to avoid
+/// feedback loops.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_link_enabled(engine: *mut GooeyEngine, enabled: bool) {
+    if engine.is_null() {
+        return;
+    }
+    let engine = &*engine;
+    engine.link_enabled.store(enabled, Ordering::Release);
+}
+
+/// Check whether the engine is being driven by an external tempo source.
+///
+/// # Returns
+/// `true` if an external sync source (e.g. Ableton Link) is active.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_is_link_enabled(engine: *const GooeyEngine) -> bool {
+    if engine.is_null() {
+        return false;
+    }
+    let engine = &*engine;
+    engine.link_enabled.load(Ordering::Acquire)
+}
+
+/// Get the current sequencer beat position in quarter notes.
+///
+/// Uses the kick sequencer as reference (all sequencers are synchronized).
+/// Returns the fractional beat position, e.g. 0.0 = bar start, 1.25 = one
+/// beat and one 16th note in.  The fraction within the current step is
+/// derived from the actual swing-aware step boundaries, so the value
+/// progresses monotonically even when swing shifts step durations.
+///
+/// This is useful for drift detection when syncing to an external clock
+/// (e.g. Ableton Link): compare the returned value with the external
+/// beat position and call `gooey_engine_sequencer_set_beat_position` if
+/// the drift exceeds a threshold.
+///
+/// # Returns
+/// Current position in quarter notes, or 0.0 if the engine pointer is null.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sequencer_get_beat_position(
+    engine: *const GooeyEngine,
+) -> f64 {
+    if engine.is_null() {
+        return 0.0;
+    }
+    let engine = &*engine;
+    let Some(seq) = engine.reference_sequencer() else {
+        return 0.0;
+    };
+
+    let step = seq.current_step() as f64;
+
+    // Interpolate within the current step using swing-aware boundaries.
+    // step_start_sample and next_trigger_sample define the true duration
+    // of this step (which differs from samples_per_step when swing != 0.5).
+    let step_start = seq.step_start_sample();
+    let step_end = seq.next_trigger_sample();
+    let step_duration = step_end.saturating_sub(step_start);
+    let frac = if step_duration > 0 {
+        let elapsed = seq.sample_count().saturating_sub(step_start);
+        (elapsed as f64 / step_duration as f64).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    // Each step is a 16th note; 4 steps = 1 quarter note
+    (step + frac) / 4.0
+}
+
+/// Set the global swing amount for all sequencers (0.0-1.0, where 0.5 = no swing)
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_swing(engine: *mut GooeyEngine, swing: f32) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+
+        let engine = &mut *engine;
+        let clamped = swing.clamp(0.0, 1.0);
+        engine.swing = clamped;
+        for seq in engine.sequencers_iter_mut() {
+            seq.set_swing(clamped);
+        }
+    })
+}
+
+/// Get the current global swing amount
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_swing(engine: *mut GooeyEngine) -> f32 {
+    if engine.is_null() {
+        return 0.5;
+    }
+
+    let engine = &*engine;
+    engine.swing
+}
+
+// =============================================================================
+// Sequencer control (all instruments)
+// =============================================================================
+
+/// Start all sequencers.
+///
+/// Cancels any pending `gooey_engine_sequencer_start_at_host_time` arm.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sequencer_start(engine: *mut GooeyEngine) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+
+        let engine = &mut *engine;
+        engine.pending_arm_host_time = None;
+        for seq in engine.sequencers_iter_mut() {
+            seq.start();
+        }
+        engine.mixer.transport_start();
+    })
+}
+
+/// Stop all sequencers.
+///
+/// Cancels any pending `gooey_engine_sequencer_start_at_host_time` arm.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sequencer_stop(engine: *mut GooeyEngine) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+
+        let engine = &mut *engine;
+        engine.pending_arm_host_time = None;
+        for seq in engine.sequencers_iter_mut() {
+            seq.stop();
+        }
+        for rack in engine.samplers.iter_mut().flatten() {
+            rack.transport_stop();
+        }
+        engine.mixer.transport_stop();
+    })
+}
+
+/// Reset all sequencers to step 0.
+///
+/// Cancels any pending `gooey_engine_sequencer_start_at_host_time` arm.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sequencer_reset(engine: *mut GooeyEngine) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+
+        let engine = &mut *engine;
+        engine.pending_arm_host_time = None;
+        for seq in engine.sequencers_iter_mut() {
+            seq.reset();
+        }
+        for rack in engine.samplers.iter_mut().flatten() {
+            rack.transport_reset();
+        }
+        engine.mixer.transport_reset();
+    })
+}
+
+/// Set all sequencers to a specific beat position in quarter notes.
+///
+/// Silently teleports the cursor — no step is fired by this call, including
+/// the step that the new position lands on. The step at the target position
+/// fires when the cursor next crosses its boundary on the regular tick path.
+/// Suitable for external phase locking (e.g. Ableton Link drift correction)
+/// and for AUv3 host transport sync.
+///
+/// Each step is a 16th note (4 steps per quarter-note beat).
+///
+/// Cancels any pending `gooey_engine_sequencer_start_at_host_time` arm. Call
+/// this before `sequencer_start()` when the host resumes transport.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `beat_position` - Position in quarter notes (e.g. 0.0 = bar start, 1.0 = second beat)
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sequencer_set_beat_position(
+    engine: *mut GooeyEngine,
+    beat_position: f64,
+) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+
+        let engine = &mut *engine;
+        engine.pending_arm_host_time = None;
+        for seq in engine.sequencers_iter_mut() {
+            seq.set_beat_position(beat_position);
+        }
+        engine.mixer.transport_seek(beat_position);
+    })
+}
+
+/// Tell the engine the host time corresponding to sample 0 of the next
+/// render call, plus the host-tick-to-sample conversion factor. The engine
+/// uses this to evaluate any pending
+/// `gooey_engine_sequencer_start_at_host_time` arm.
+///
+/// Call this once per buffer from the audio callback, immediately before
+/// `gooey_engine_render`. Only required while a host-time arm is pending;
+/// calling it otherwise is a cheap no-op that simply updates the host-clock
+/// reference.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `host_time_first_sample` - mach_absolute_time of the first sample to be
+///   rendered.
+/// * `host_ticks_per_sample` - Host-clock ticks per audio sample
+///   (e.g. host_ticks_per_second / sample_rate). Must be > 0 to take effect.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_render_host_time(
+    engine: *mut GooeyEngine,
+    host_time_first_sample: u64,
+    host_ticks_per_sample: f64,
+) {
+    edit(engine, || {
+        if engine.is_null() || !host_ticks_per_sample.is_finite() || host_ticks_per_sample <= 0.0 {
+            return;
+        }
+        let engine = &mut *engine;
+        if TraceLog::ENABLED {
+            // A host clock that ran ahead of the audio rendered since the last
+            // anchor by more than half that span means buffers were skipped
+            if let Some(previous) = engine.host_clock_anchor {
+                let frames = engine.rendered_frames - previous.frame;
+                let expected = previous.host_time_first_sample as f64
+                    + frames as f64 * previous.host_ticks_per_sample;
+                let missed = (host_time_first_sample as f64 - expected) / host_ticks_per_sample;
+                if frames > 0 && missed > frames as f64 / 2.0 {
+                    engine.trace.record(
+                        engine.rendered_frames,
+                        TraceEvent::Xrun {
+                            missed_frames: missed.round().min(u32::MAX as f64) as u32,
+                        },
+                    );
+                }
+            }
+        }
+        engine.host_clock_anchor = Some(HostClockAnchor {
+            host_time_first_sample,
+            host_ticks_per_sample,
+            frame: engine.rendered_frames,
+        });
+    })
+}
+
+/// Arm all sequencers to start playback at `start_host_time` (in
+/// mach_absolute_time units) with the cursor at `beat_position` at that
+/// instant. Until the host clock reaches `start_host_time`, render produces
+/// silence and emits no step events. From `start_host_time` onward the
+/// cursor advances normally starting at `beat_position`.
+///
+/// Requires the audio callback to call `gooey_engine_set_render_host_time`
+/// before each `gooey_engine_render` while armed. Without a host clock
+/// reference the arm fires immediately (fail-safe behaviour).
+///
+/// Safe to call from the main thread; takes effect on the next render call.
+/// If `start_host_time` has already been crossed by the time render reaches
+/// it, behaves like an immediate start at `beat_position`. Subsequent calls
+/// to `sequencer_set_beat_position`, `sequencer_start`, `sequencer_stop`, or
+/// `sequencer_reset` cancel the pending arm.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `start_host_time` - Absolute host time at which the sequencer should
+///   start (mach_absolute_time units).
+/// * `beat_position` - Cursor position in quarter notes at the moment the
+///   arm fires (e.g. 0.0 = bar start).
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sequencer_start_at_host_time(
+    engine: *mut GooeyEngine,
+    start_host_time: u64,
+    beat_position: f64,
+) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        // Stage the arm; it is resolved against the host clock at render time.
+        // Stop the underlying sequencers so they emit nothing until the arm fires.
+        engine.pending_arm_host_time = Some(PendingArm {
+            start_host_time,
+            beat_position,
+        });
+        engine.mixer.transport_stop();
+        for seq in engine.sequencers_iter_mut() {
+            seq.cancel_arm();
+            seq.stop();
+        }
+    })
+}
+
+/// Set a sequencer step on or off for the kick drum (legacy, prefer per-instrument functions)
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `step` - Step index (0-15 for a 16-step sequencer)
+/// * `enabled` - Whether the step should trigger
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sequencer_set_step(
+    engine: *mut GooeyEngine,
+    step: u32,
+    enabled: bool,
+) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+
+        let engine = &mut *engine;
+        if let Some(seq) = engine.sequencer_for_instrument(INSTRUMENT_KICK) {
+            seq.set_step(step as usize, enabled);
+        }
+    })
+}
+
+/// Get the current sequencer step (uses kick sequencer, all are synchronized)
+///
+/// # Returns
+/// The current step index (0-15), or -1 if the sequencer is not running
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sequencer_get_current_step(engine: *mut GooeyEngine) -> i32 {
+    if engine.is_null() {
+        return -1;
+    }
+
+    let engine = &*engine;
+    match engine.reference_sequencer() {
+        Some(seq) if seq.is_running() => seq.current_step() as i32,
+        _ => -1,
+    }
+}
+
+/// Get the sequencer step that will be playing after a lookahead period
+///
+/// This compensates for audio buffer latency by looking ahead.
+/// Use this for UI display to sync visuals with audio output.
+/// Uses first sequencer as reference (all sequencers are synchronized).
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `lookahead_samples` - Number of samples to look ahead (typically audio buffer size)
+///
+/// # Returns
+/// The step index that will be playing after the lookahead, or -1 if not running
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sequencer_get_step_with_lookahead(
+    engine: *mut GooeyEngine,
+    lookahead_samples: u32,
+) -> i32 {
+    if engine.is_null() {
+        return -1;
+    }
+
+    let engine = &*engine;
+    match engine.reference_sequencer() {
+        Some(seq) if seq.is_running() => seq.step_at_lookahead(lookahead_samples as u64) as i32,
+        _ => -1,
+    }
+}
+
+// =============================================================================
+// Per-instrument sequencer control
+// =============================================================================
+
+/// Helper to get a mutable reference to an instrument's sequencer
+impl GooeyEngine {
+    fn sequencer_for_instrument(&mut self, instrument: u32) -> Option<&mut Sequencer> {
+        self.voice_mut(instrument as usize)
+            .map(|v| &mut v.sequencer)
+    }
+
+    fn sequencer_for_instrument_ref(&self, instrument: u32) -> Option<&Sequencer> {
+        self.voice(instrument as usize).map(|v| &v.sequencer)
+    }
+
+    /// Iterate all voice sequencers in index order (kit drums then bass).
+    fn sequencers_iter_mut(&mut self) -> impl Iterator<Item = &mut Sequencer> {
+        self.kit
+            .voices
+            .iter_mut()
+            .map(|v| &mut v.sequencer)
+            .chain([
+                &mut self.bass.sequencer,
+                &mut self.fm_snap.sequencer,
+                &mut self.rimshot.sequencer,
+                &mut self.cowbell.sequencer,
+                &mut self.shaker.sequencer,
+            ])
+            .chain(self.slots.iter_mut().flatten().map(|v| &mut v.sequencer))
+            .chain(
+                self.samplers
+                    .iter_mut()
+                    .filter_map(|rack| rack.as_mut().map(SamplerRack::sequencer_mut)),
+            )
+    }
 
-// =============================================================================
-// Granulator parameter constants
-// =============================================================================
-//
-// All values are normalized 0.0-1.0. Mapping to internal units (ms, Hz, ratio)
-// happens inside `Granulator`; see `src/instruments/granulator.rs`.
+    /// Borrow the reference sequencer (voice 0 / kick). All sequencers are kept
+    /// sample-synchronized, so any voice can serve as the position reference.
+    fn reference_sequencer(&self) -> Option<&Sequencer> {
+        self.voice(0).map(|v| &v.sequencer)
+    }
 
-/// Granulator parameter: scan position into the loaded buffer (0=start, 1=end)
-pub const GRANULATOR_PARAM_SCAN_POSITION: u32 = 0;
-/// Granulator parameter: grain length (0-1 -> 5-3000 ms, quadratic curve)
-pub const GRANULATOR_PARAM_GRAIN_LENGTH: u32 = 1;
-/// Granulator parameter: spray / randomization of grain start (0-1 -> 0-10 s, cubic)
-pub const GRANULATOR_PARAM_SPRAY: u32 = 2;
-/// Granulator parameter: playback pitch (0-1 -> 0.25x-4x, exponential)
-pub const GRANULATOR_PARAM_PITCH: u32 = 3;
-/// Granulator parameter: density of grains per second (0-1 -> 0-80 g/s)
-pub const GRANULATOR_PARAM_DENSITY: u32 = 4;
-/// Granulator parameter: window shape / texture (0=soft, 1=hard)
-pub const GRANULATOR_PARAM_TEXTURE: u32 = 5;
-/// Granulator parameter: probability a grain plays in reverse (0=forward only, 1=reverse only)
-pub const GRANULATOR_PARAM_DIRECTION: u32 = 6;
-/// Granulator parameter: cloud duration after a trigger (0-1 -> 50-8000 ms, quadratic)
-pub const GRANULATOR_PARAM_CLOUD_DURATION: u32 = 7;
-/// Granulator parameter: output volume (0-1)
-pub const GRANULATOR_PARAM_VOLUME: u32 = 8;
-/// Granulator parameter: per-grain spawn-time jitter (0=periodic, 1=±100% of interval)
-pub const GRANULATOR_PARAM_RANDOM_TIMING: u32 = 9;
-/// Granulator parameter: per-grain random amplitude (0=uniform, 1=full random duck to 0)
-pub const GRANULATOR_PARAM_RANDOM_AMP: u32 = 10;
-/// Granulator parameter: output soft-saturation drive (0=clean, 1=heavy tanh)
-pub const GRANULATOR_PARAM_DRIVE: u32 = 11;
-/// Total number of granulator parameters
-pub const GRANULATOR_PARAM_COUNT: u32 = 12;
+    /// Fractional beat position (quarter notes) from the reference sequencer.
+    /// Same math as `gooey_engine_sequencer_get_beat_position`.
+    fn compute_beat_position(&self) -> f64 {
+        let Some(seq) = self.reference_sequencer() else {
+            return 0.0;
+        };
+        let step = seq.current_step() as f64;
+        let step_start = seq.step_start_sample();
+        let step_end = seq.next_trigger_sample();
+        let step_duration = step_end.saturating_sub(step_start);
+        let frac = if step_duration > 0 {
+            let elapsed = seq.sample_count().saturating_sub(step_start);
+            (elapsed as f64 / step_duration as f64).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        (step + frac) / 4.0
+    }
 
-/// Bass preset: Acid - TB-303-style, high resonance, short filter sweep
-pub const BASS_PRESET_ACID: u32 = 0;
-/// Bass preset: Sub - clean sub-bass, sine dominant
-pub const BASS_PRESET_SUB: u32 = 1;
-/// Bass preset: Reese - detuned saws, heavy overdrive
-pub const BASS_PRESET_REESE: u32 = 2;
-/// Bass preset: Stab - square wave, sharp filter, short decay
-pub const BASS_PRESET_STAB: u32 = 3;
+    /// Apply a clip player action to the poly synth without recording.
+    fn apply_performance_action(&mut self, action: PlayerAction, frame: u64) {
+        self.performance.set_applying_playback(true);
+        match action {
+            PlayerAction::Trigger(event) => {
+                self.trigger_poly_chord_from_event(event, frame);
+            }
+            PlayerAction::Release => {
+                self.poly_synth.release_all();
+            }
+        }
+        self.performance.set_applying_playback(false);
+    }
 
-/// Sentinel value indicating no MIDI note is set for a step (use instrument's global frequency)
-pub const STEP_NOTE_NONE: u8 = 255;
+    /// Build and trigger a chord from a recorded pad-parameter event.
+    fn trigger_poly_chord_from_event(&mut self, event: ChordClipEvent, frame: u64) {
+        let root_note = root_from_id(event.root);
+        let scale = scale_from_id(event.scale_type);
+        let key = Key::new(root_note, scale);
+        let voicing_type = voicing_from_id(event.voicing);
+        let octave = event.octave.clamp(0, 8) as i8;
+        let velocity = event.velocity.clamp(0.0, 1.0);
 
-/// Snare preset: Tight - short, punchy snare
-pub const SNARE_PRESET_TIGHT: u32 = 0;
-/// Snare preset: Loose - longer decay, more body
-pub const SNARE_PRESET_LOOSE: u32 = 1;
-/// Snare preset: Hiss - noise-focused with phase modulation
-pub const SNARE_PRESET_HISS: u32 = 2;
-/// Snare preset: Smack - DS-style transient with SVF noise
-pub const SNARE_PRESET_SMACK: u32 = 3;
+        // Smoothed targets only — avoid snap_params on every clip replay hit.
+        self.poly_synth.set_config(preset_config(event.preset));
 
-/// Hi-hat preset: Short
-pub const HIHAT_PRESET_SHORT: u32 = 0;
-/// Hi-hat preset: Loose
-pub const HIHAT_PRESET_LOOSE: u32 = 1;
-/// Hi-hat preset: Dark
-pub const HIHAT_PRESET_DARK: u32 = 2;
-/// Hi-hat preset: Soft
-pub const HIHAT_PRESET_SOFT: u32 = 3;
+        let chords = key.diatonic_sevenths();
+        let degree = event.degree as usize % chords.len().max(1);
+        let chord = &chords[degree];
+        let midi_notes = apply_voicing(chord, voicing_type, octave);
 
-/// Blend corner: bottom-left (x=0, y=0)
-pub const BLEND_CORNER_BOTTOM_LEFT: u32 = 0;
-/// Blend corner: bottom-right (x=1, y=0)
-pub const BLEND_CORNER_BOTTOM_RIGHT: u32 = 1;
-/// Blend corner: top-left (x=0, y=1)
-pub const BLEND_CORNER_TOP_LEFT: u32 = 2;
-/// Blend corner: top-right (x=1, y=1)
-pub const BLEND_CORNER_TOP_RIGHT: u32 = 3;
+        self.poly_synth.release_all();
+        for note in &midi_notes {
+            self.trigger_poly_note(*note, velocity, frame);
+        }
+    }
 
-// =============================================================================
-// Engine lifecycle
-// =============================================================================
+    /// Play a poly synth note, logging a voice steal at `frame`.
+    fn trigger_poly_note(&mut self, note: u8, velocity: f32, frame: u64) {
+        let stolen = self.poly_synth.voices_stolen();
+        self.poly_synth.trigger_note(note, velocity);
+        if self.poly_synth.voices_stolen() != stolen {
+            self.trace
+                .record(frame, TraceEvent::VoiceSteal { note: note as u32 });
+        }
+    }
+}
 
-/// Create a new gooey engine
+/// Set a sequencer step on or off for a specific instrument
 ///
 /// # Arguments
-/// * `sample_rate` - Audio sample rate (e.g., 44100.0 or 48000.0)
-///
-/// # Returns
-/// Pointer to a new GooeyEngine instance. Must be freed with `gooey_engine_free`.
+/// * `engine` - Pointer to a GooeyEngine
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// * `step` - Step index (0-15 for a 16-step sequencer)
+/// * `enabled` - Whether the step should trigger
 ///
 /// # Safety
-/// The returned pointer must be freed with `gooey_engine_free` to avoid memory leaks.
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub extern "C" fn gooey_engine_new(sample_rate: f32) -> *mut GooeyEngine {
-    let engine = Box::new(GooeyEngine::new(sample_rate));
-    Box::into_raw(engine)
+pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_step(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    step: u32,
+    enabled: bool,
+) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+
+        let engine = &mut *engine;
+        if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
+            sequencer.set_step(step as usize, enabled);
+        }
+    })
 }
 
-/// Free a gooey engine
+/// Set the velocity for a specific step in an instrument's sequencer
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// * `step` - Step index (0-15 for a 16-step sequencer)
+/// * `velocity` - Velocity from 0.0 to 1.0
 ///
 /// # Safety
-/// `engine` must be a valid pointer returned by `gooey_engine_new`, or null.
-/// After calling this function, the pointer is invalid and must not be used.
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_free(engine: *mut GooeyEngine) {
-    if !engine.is_null() {
-        drop(Box::from_raw(engine));
-    }
+pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_step_velocity(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    step: u32,
+    velocity: f32,
+) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+
+        let engine = &mut *engine;
+        if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
+            sequencer.set_step_velocity(step as usize, velocity);
+        }
+    })
 }
 
-// =============================================================================
-// Audio rendering
-// =============================================================================
+/// Set both enabled state and velocity for a sequencer step
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// * `step` - Step index (0-15 for a 16-step sequencer)
+/// * `enabled` - Whether the step should trigger
+/// * `velocity` - Velocity from 0.0 to 1.0
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_step_with_velocity(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    step: u32,
+    enabled: bool,
+    velocity: f32,
+) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
 
-/// Number of interleaved output channels produced by `gooey_engine_render`.
-/// The render buffer must hold `frames * GOOEY_OUTPUT_CHANNELS` floats.
-pub const GOOEY_OUTPUT_CHANNELS: u32 = 2;
+        let engine = &mut *engine;
+        if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
+            sequencer.set_step_with_velocity(step as usize, enabled, velocity);
+        }
+    })
+}
 
-/// Render interleaved stereo audio into the provided buffer
+/// Set a step with optional velocity, optional blend setting, and optional MIDI note.
 ///
-/// This is the main audio callback function. Call this from your audio thread
-/// to generate audio samples. Output is two-channel: the buffer holds `frames`
-/// stereo frames laid out as interleaved `[left, right]` pairs, so the caller
-/// must provide `frames * GOOEY_OUTPUT_CHANNELS` (`frames * 2`) floats. The
-/// signal path is currently mono, so left and right are identical, but callers
-/// should always treat the buffer as stereo.
+/// Omitted settings are left unchanged.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// * `step` - Step index (0-15 for a 16-step sequencer)
+/// * `enabled` - Whether the step should trigger
+/// * `set_velocity` - Whether to apply the `velocity` value
+/// * `velocity` - Velocity from 0.0 to 1.0 (used only when `set_velocity` is true)
+/// * `set_blend` - Whether to apply blend X/Y
+/// * `blend_x` - Blend X position (0.0-1.0, used only when `set_blend` is true)
+/// * `blend_y` - Blend Y position (0.0-1.0, used only when `set_blend` is true)
+/// * `set_note` - Whether to apply the `midi_note` value
+/// * `midi_note` - MIDI note number (0-127, or STEP_NOTE_NONE); only applied when `set_note` is true
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_step_settings(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    step: u32,
+    enabled: bool,
+    set_velocity: bool,
+    velocity: f32,
+    set_blend: bool,
+    blend_x: f32,
+    blend_y: f32,
+    set_note: bool,
+    midi_note: u8,
+) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+
+        let engine = &mut *engine;
+        if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
+            let settings = SequencerStepSettings {
+                velocity: if set_velocity { Some(velocity) } else { None },
+                blend: if set_blend {
+                    Some(SequencerBlendSetting::new(blend_x, blend_y))
+                } else {
+                    None
+                },
+                note: None,
+                articulation: None,
+                tune: None,
+                gate: None,
+            };
+            sequencer.set_step_with_settings(step as usize, enabled, settings);
+            // Handle note separately: set_note=true with STEP_NOTE_NONE clears the note
+            if set_note {
+                let step_idx = step as usize;
+                if midi_note == STEP_NOTE_NONE {
+                    sequencer.clear_step_note(step_idx);
+                } else {
+                    sequencer.set_step_note(step_idx, midi_note);
+                }
+            }
+        }
+        if set_blend {
+            engine.refresh_preset_gain(instrument as usize);
+        }
+    })
+}
+
+/// Set an absolute blend setting for a specific step (0.0-1.0 X/Y)
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `buffer` - Pointer to a buffer of floats to fill with interleaved L/R audio
-/// * `frames` - Number of stereo frames to render
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// * `step` - Step index (0-15 for a 16-step sequencer)
+/// * `x` - Blend X position (0.0-1.0)
+/// * `y` - Blend Y position (0.0-1.0)
 ///
 /// # Safety
-/// - `engine` must be a valid pointer returned by `gooey_engine_new`
-/// - `buffer` must point to at least `frames * 2` floats of allocated memory
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_render(
+pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_step_blend(
     engine: *mut GooeyEngine,
-    buffer: *mut f32,
-    frames: u32,
+    instrument: u32,
+    step: u32,
+    x: f32,
+    y: f32,
 ) {
-    if engine.is_null() || buffer.is_null() {
-        return;
-    }
-
-    let engine_ref = &mut *engine;
-    let buffer_slice = slice::from_raw_parts_mut(buffer, frames as usize * 2);
-
-    // If engine is already in error state, output silence
-    if engine_ref.error_occurred.load(Ordering::Relaxed) {
-        for sample in buffer_slice.iter_mut() {
-            *sample = 0.0;
-        }
-        return;
-    }
-
-    // Wrap render in catch_unwind to prevent panics from crossing the FFI boundary.
-    // AssertUnwindSafe is sound here: after a panic we mark the engine as permanently
-    // errored and never call render() on it again.
-    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-        engine_ref.render(buffer_slice);
-    }));
-
-    if let Err(panic_payload) = result {
-        let msg = if let Some(s) = panic_payload.downcast_ref::<&str>() {
-            format!("gooey: render panic: {}", s)
-        } else if let Some(s) = panic_payload.downcast_ref::<String>() {
-            format!("gooey: render panic: {}", s)
-        } else {
-            "gooey: render panic (unknown cause)".to_string()
-        };
-
-        let c_msg = CString::new(msg).unwrap_or_else(|_| {
-            CString::new("gooey: render panic (message contained null byte)").unwrap()
-        });
-        let msg_ptr = c_msg.as_ptr();
-
-        engine_ref.error_message = Some(c_msg);
-        engine_ref.error_occurred.store(true, Ordering::Release);
-
-        // Zero the output buffer (interleaved stereo: frames * 2 floats)
-        let buffer_slice = slice::from_raw_parts_mut(buffer, frames as usize * 2);
-        for sample in buffer_slice.iter_mut() {
-            *sample = 0.0;
+    edit(engine, || {
+        if engine.is_null() {
+            return;
         }
 
-        // Invoke error callback if registered
-        if let Some(callback) = engine_ref.error_callback {
-            callback(engine_ref.error_callback_context, msg_ptr);
+        let engine = &mut *engine;
+        if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
+            sequencer.set_step_blend(step as usize, x, y);
         }
-    }
+        engine.refresh_preset_gain(instrument as usize);
+    })
 }
 
-// =============================================================================
-// MIDI event output
-// =============================================================================
+/// Legacy alias for `gooey_engine_sequencer_set_instrument_step_blend`.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_step_blend_override(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    step: u32,
+    x: f32,
+    y: f32,
+) {
+    gooey_engine_sequencer_set_instrument_step_blend(engine, instrument, step, x, y);
+}
 
-/// Copies pending MIDI events into `out_events` and returns how many were written.
-///
-/// Events are cleared after draining — the caller gets each event exactly once.
-/// Must be called from the audio thread, immediately after `gooey_engine_render()`.
+/// Clear the blend setting for a specific step
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `out_events` - Pointer to a caller-allocated array of GooeyMidiEvent
-/// * `max_events` - Capacity of the `out_events` array
-///
-/// # Returns
-/// Number of events written (0 if none pending or on null input)
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// * `step` - Step index (0-15 for a 16-step sequencer)
 ///
 /// # Safety
-/// - `engine` must be a valid pointer returned by `gooey_engine_new`
-/// - `out_events` must point to at least `max_events` elements of allocated memory
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_drain_midi_events(
+pub unsafe extern "C" fn gooey_engine_sequencer_clear_instrument_step_blend(
     engine: *mut GooeyEngine,
-    out_events: *mut GooeyMidiEvent,
-    max_events: u32,
-) -> u32 {
-    if engine.is_null() || out_events.is_null() || max_events == 0 {
-        return 0;
-    }
-
-    let engine_ref = &mut *engine;
-    let count = engine_ref
-        .pending_midi_events
-        .len()
-        .min(max_events as usize);
-
-    if count > 0 {
-        std::ptr::copy_nonoverlapping(engine_ref.pending_midi_events.as_ptr(), out_events, count);
-        // Remove only the events that were copied; retain any overflow
-        engine_ref.pending_midi_events.drain(..count);
-    }
+    instrument: u32,
+    step: u32,
+) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
 
-    count as u32
+        let engine = &mut *engine;
+        if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
+            sequencer.clear_step_blend(step as usize);
+        }
+    })
 }
 
-// =============================================================================
-// Sequencer trigger control
-// =============================================================================
+/// Legacy alias for `gooey_engine_sequencer_clear_instrument_step_blend`.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sequencer_clear_instrument_step_blend_override(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    step: u32,
+) {
+    gooey_engine_sequencer_clear_instrument_step_blend(engine, instrument, step);
+}
 
-/// Enable or disable note triggering from the internal sequencer.
-///
-/// When disabled (`enabled = false`):
-/// - The sequencer still advances its position each render cycle (step tracking works)
-/// - But the sequencer does NOT call instrument trigger functions or emit MIDI events
+/// Set the MIDI note for a specific step in an instrument's sequencer.
 ///
-/// When enabled (`enabled = true`, the default):
-/// - Normal behavior — sequencer triggers instruments on active steps
+/// When a step with a note triggers, the engine sets the instrument's
+/// frequency parameter to the note's frequency at the exact trigger sample.
+/// Steps without a note (STEP_NOTE_NONE) use the instrument's global
+/// frequency parameter as before (backward compatible).
 ///
-/// Use this to let the host's MIDI input drive the instruments instead
-/// of the internal sequencer, while keeping position tracking intact.
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `instrument` - Instrument ID (INSTRUMENT_BASS, etc.)
+/// * `step` - Step index (0-15)
+/// * `midi_note` - MIDI note number (0-127), or STEP_NOTE_NONE to clear
 ///
 /// # Safety
-/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_sequencer_triggers_enabled(
+pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_step_note(
     engine: *mut GooeyEngine,
-    enabled: bool,
+    instrument: u32,
+    step: u32,
+    midi_note: u8,
 ) {
-    if engine.is_null() {
-        return;
-    }
-    (*engine)
-        .sequencer_triggers_enabled
-        .store(enabled, Ordering::Release);
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
+            if midi_note == STEP_NOTE_NONE {
+                sequencer.clear_step_note(step as usize);
+            } else {
+                sequencer.set_step_note(step as usize, midi_note);
+            }
+        }
+    })
 }
 
-/// Query whether sequencer triggers are currently enabled.
+/// Get the MIDI note for a specific step.
 ///
-/// Returns `true` if the engine pointer is null (safe default).
+/// # Returns
+/// The MIDI note number (0-127), or STEP_NOTE_NONE (255) if no note is set.
 ///
 /// # Safety
-/// - `engine` must be a valid pointer returned by `gooey_engine_new`, or null
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_sequencer_triggers_enabled(
+pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_step_note(
     engine: *const GooeyEngine,
-) -> bool {
+    instrument: u32,
+    step: u32,
+) -> u8 {
     if engine.is_null() {
-        return true;
+        return STEP_NOTE_NONE;
+    }
+    let engine = &*engine;
+    if let Some(sequencer) = engine.sequencer_for_instrument_ref(instrument) {
+        sequencer
+            .get_step_note(step as usize)
+            .unwrap_or(STEP_NOTE_NONE)
+    } else {
+        STEP_NOTE_NONE
     }
-    (*engine).sequencer_triggers_enabled.load(Ordering::Acquire)
 }
 
-// =============================================================================
-// Error handling
-// =============================================================================
-
-/// Register an error callback
-///
-/// The callback will be invoked if a fatal error (e.g., panic) occurs during rendering.
-/// It is called at most once, from the audio thread, immediately after the error.
-/// After the callback fires, the engine is in a terminal error state and must be freed
-/// with `gooey_engine_free` and recreated with `gooey_engine_new`.
-///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `context` - Opaque user pointer passed back to the callback (can be null)
-/// * `callback` - C function pointer, or null to unregister
+/// Clear the MIDI note for a step (reverts to global frequency).
+/// Equivalent to set_step_note(..., STEP_NOTE_NONE).
 ///
 /// # Safety
-/// - `engine` must be a valid pointer returned by `gooey_engine_new`
-/// - `context` must remain valid for the lifetime of the engine
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_error_callback(
+pub unsafe extern "C" fn gooey_engine_sequencer_clear_instrument_step_note(
     engine: *mut GooeyEngine,
-    context: *mut c_void,
-    callback: Option<extern "C" fn(*mut c_void, *const c_char)>,
+    instrument: u32,
+    step: u32,
 ) {
-    if engine.is_null() {
-        return;
-    }
-    let engine = &mut *engine;
-    engine.error_callback = callback;
-    engine.error_callback_context = context;
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
+            sequencer.clear_step_note(step as usize);
+        }
+    })
 }
 
-/// Check if the engine is in an error state
+/// Set the articulation for a specific step in an instrument's sequencer.
 ///
-/// Returns true if a fatal error has occurred during rendering.
-/// Once in an error state, the engine outputs silence and must be freed and recreated.
+/// Only the hi-hat has articulations (HIHAT_ARTICULATION_CLOSED, _PEDAL,
+/// _OPEN); other instruments store the value but play it as a normal hit.
+/// Steps without an articulation (STEP_ARTICULATION_NONE) play the
+/// instrument's default, `HIHAT_PARAM_ARTICULATION`.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `instrument` - Instrument ID (INSTRUMENT_HIHAT, etc.)
+/// * `step` - Step index (0-15)
+/// * `articulation` - Articulation, or STEP_ARTICULATION_NONE to clear
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_has_error(engine: *const GooeyEngine) -> bool {
-    if engine.is_null() {
-        return false;
-    }
-    (*engine).error_occurred.load(Ordering::Relaxed)
+pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_step_articulation(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    step: u32,
+    articulation: u8,
+) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
+            if articulation == STEP_ARTICULATION_NONE {
+                sequencer.clear_step_articulation(step as usize);
+            } else {
+                sequencer.set_step_articulation(step as usize, articulation);
+            }
+        }
+    })
 }
 
-/// Get the error message if the engine is in an error state
+/// Get the articulation for a specific step.
 ///
-/// Returns a pointer to a null-terminated C string describing the error,
-/// or null if no error has occurred. The string is owned by the engine
-/// and remains valid until `gooey_engine_free` is called.
+/// # Returns
+/// The articulation, or STEP_ARTICULATION_NONE (255) if none is set.
 ///
 /// # Safety
-/// - `engine` must be a valid pointer returned by `gooey_engine_new`
-/// - The returned string must not be freed by the caller
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_error_message(
+pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_step_articulation(
     engine: *const GooeyEngine,
-) -> *const c_char {
+    instrument: u32,
+    step: u32,
+) -> u8 {
     if engine.is_null() {
-        return std::ptr::null();
-    }
-    match &(*engine).error_message {
-        Some(msg) => msg.as_ptr(),
-        None => std::ptr::null(),
+        return STEP_ARTICULATION_NONE;
     }
+    let engine = &*engine;
+    engine
+        .sequencer_for_instrument_ref(instrument)
+        .and_then(|sequencer| sequencer.get_step_articulation(step as usize))
+        .unwrap_or(STEP_ARTICULATION_NONE)
 }
 
-// =============================================================================
-// Channel instrument type swap
-// =============================================================================
-
-/// Reassign which synthesizer type runs on a given channel.
+/// Set a tuning offset for a specific step in an instrument's sequencer.
 ///
-/// After calling this, the channel produces the new instrument's sound.
-/// Resets synth DSP state for that channel (new instrument starts fresh).
-/// Preserves channel-level state: gain, mute, solo, sequencer pattern, blend position.
+/// The step plays at the instrument's own tuning plus `semitones`, so one
+/// voice can cover several pitches, e.g. a single tom lane playing a
+/// floor/low/mid/high fill. The offset is applied at the exact trigger sample
+/// and the channel's tuning is restored on the next step without one. The
+/// result is limited to the tuning parameter's ±12 semitone range.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `channel` - Channel index (0-3)
-/// * `instrument_type` - Instrument type (INSTRUMENT_KICK=0, INSTRUMENT_SNARE=1, INSTRUMENT_HIHAT=2, INSTRUMENT_TOM=3)
+/// * `instrument` - Instrument ID (INSTRUMENT_TOM, etc.)
+/// * `step` - Step index (0-15)
+/// * `semitones` - Offset in semitones (clamped to ±12)
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_channel_instrument_type(
+pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_step_tune(
     engine: *mut GooeyEngine,
-    channel: u32,
-    instrument_type: u32,
+    instrument: u32,
+    step: u32,
+    semitones: f32,
 ) {
-    if engine.is_null() {
-        return;
-    }
-    let engine = &mut *engine;
-    let sample_rate = engine.sample_rate;
-    let Some(voice) = engine.voice_mut(channel as usize) else {
-        return;
-    };
-
-    // No-op if already the requested type
-    if voice.instrument.instrument_type() == instrument_type {
-        return;
-    }
-
-    let new_instrument = match instrument_type {
-        INSTRUMENT_KICK => ChannelInstrument::Kick(KickDrum::new(sample_rate)),
-        INSTRUMENT_SNARE => ChannelInstrument::Snare(SnareDrum::new(sample_rate)),
-        INSTRUMENT_HIHAT => ChannelInstrument::HiHat(HiHat2::new(sample_rate)),
-        INSTRUMENT_TOM => ChannelInstrument::Tom(Tom2::new(sample_rate)),
-        INSTRUMENT_BASS => ChannelInstrument::Bass(BassSynth::new(sample_rate)),
-        _ => return,
-    };
-
-    voice.instrument = new_instrument;
-    voice.blender = ChannelBlender::default_for_type(instrument_type);
-    voice.blend_corner_presets = ChannelBlender::default_corner_preset_ids(instrument_type);
-
-    // If blend is enabled, re-apply position with the new blender
-    if voice.blend_enabled {
-        let x = voice.blend_x;
-        let y = voice.blend_y;
-        voice.blender.blend_and_apply(&mut voice.instrument, x, y);
-    }
-    // channel gain, mute, solo, sequencer pattern all preserved on the voice
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
+            sequencer.set_step_tune(step as usize, semitones);
+        }
+    })
 }
 
-/// Returns the current instrument type for a channel.
-///
-/// Default mapping: channel N has instrument_type N.
-///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `channel` - Channel index (0-3)
+/// Get the tuning offset for a specific step.
 ///
 /// # Returns
-/// Instrument type (INSTRUMENT_KICK=0, INSTRUMENT_SNARE=1, INSTRUMENT_HIHAT=2, INSTRUMENT_TOM=3),
-/// or 0xFFFFFFFF if invalid.
+/// The offset in semitones, or NaN if the step has none (or invalid
+/// engine/instrument/step).
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_channel_instrument_type(
+pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_step_tune(
     engine: *const GooeyEngine,
-    channel: u32,
-) -> u32 {
+    instrument: u32,
+    step: u32,
+) -> f32 {
     if engine.is_null() {
-        return 0xFFFFFFFF;
+        return f32::NAN;
     }
     let engine = &*engine;
-    match engine.voice(channel as usize) {
-        Some(voice) => voice.instrument.instrument_type(),
-        None => 0xFFFFFFFF,
-    }
+    engine
+        .sequencer_for_instrument_ref(instrument)
+        .and_then(|sequencer| sequencer.get_step_tune(step as usize))
+        .unwrap_or(f32::NAN)
 }
 
-/// Set a parameter on a channel's instrument, regardless of what synth type it holds.
-///
-/// Parameter index meaning depends on the channel's current instrument type.
-/// For kick: param 0=frequency, 1=punch, etc. (same as `gooey_engine_set_kick_param`)
-/// For snare: param 0=frequency, 1=decay, etc. (same as `gooey_engine_set_snare_param`)
-/// For hihat: param 0=pitch, 1=decay, etc. (same as `gooey_engine_set_hihat_param`)
-/// For tom: param 0=tune, 1=bend, etc. (same as `gooey_engine_set_tom_param`)
-///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `channel` - Channel index (0-3)
-/// * `param` - Parameter index (meaning depends on instrument type)
-/// * `value` - Parameter value (0-1 normalized)
+/// Clear the tuning offset for a step (plays at the instrument's own tuning).
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_channel_param(
+pub unsafe extern "C" fn gooey_engine_sequencer_clear_instrument_step_tune(
     engine: *mut GooeyEngine,
-    channel: u32,
-    param: u32,
-    value: f32,
+    instrument: u32,
+    step: u32,
 ) {
-    if engine.is_null() {
-        return;
-    }
-    let engine = &mut *engine;
-    if let Some(voice) = engine.voice_mut(channel as usize) {
-        voice.instrument.set_param(param, value);
-    }
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
+            sequencer.clear_step_tune(step as usize);
+        }
+    })
 }
 
-/// Set the tuning offset for a channel (0.0 = −12 semitones, 0.5 = neutral, 1.0 = +12 semitones).
+/// Set the gate length for a specific step in an instrument's sequencer.
 ///
-/// This is a convenience function that dispatches to the correct tuning parameter
-/// for whatever instrument type is currently loaded on the channel.
+/// A gated step is released `steps` steps after it triggers (0.5 = half a
+/// step), so instruments with a release stage (the bass) play notes of that
+/// length. Steps without a gate are one-shots with no note-off, as before.
+/// The drums have no release stage and ignore the gate.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `instrument` - Instrument ID (INSTRUMENT_BASS, etc.)
+/// * `step` - Step index (0-15)
+/// * `steps` - Gate length in steps (clamped to 0.01-16), or 0 to clear
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_channel_tuning(
+pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_step_gate(
     engine: *mut GooeyEngine,
-    channel: u32,
-    value: f32,
+    instrument: u32,
+    step: u32,
+    steps: f32,
 ) {
-    if engine.is_null() {
-        return;
-    }
-    let engine = &mut *engine;
-    let Some(voice) = engine.voice_mut(channel as usize) else {
-        return;
-    };
-    let tuning_param = match voice.instrument.instrument_type() {
-        INSTRUMENT_KICK => KICK_PARAM_TUNING,
-        INSTRUMENT_SNARE => SNARE_PARAM_TUNING,
-        INSTRUMENT_HIHAT => HIHAT_PARAM_TUNING,
-        INSTRUMENT_TOM => TOM_PARAM_TUNING,
-        INSTRUMENT_BASS => BASS_PARAM_TUNING,
-        _ => return,
-    };
-    voice.instrument.set_param(tuning_param, value);
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
+            if steps > 0.0 {
+                sequencer.set_step_gate(step as usize, steps);
+            } else {
+                sequencer.clear_step_gate(step as usize);
+            }
+        }
+    })
 }
 
-/// Get the current tuning value for a channel (0.0–1.0).
+/// Get the gate length for a specific step.
 ///
-/// Returns 0.5 (neutral) if the channel index is out of range or engine is null.
+/// # Returns
+/// The gate length in steps, or 0.0 if the step has no gate (or invalid
+/// engine/instrument/step).
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_channel_tuning(
+pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_step_gate(
     engine: *const GooeyEngine,
-    channel: u32,
+    instrument: u32,
+    step: u32,
 ) -> f32 {
     if engine.is_null() {
-        return 0.5;
+        return 0.0;
     }
     let engine = &*engine;
-    match engine.voice(channel as usize) {
-        Some(voice) => voice.instrument.get_tuning(),
-        None => 0.5,
-    }
+    engine
+        .sequencer_for_instrument_ref(instrument)
+        .and_then(|sequencer| sequencer.get_step_gate(step as usize))
+        .unwrap_or(0.0)
 }
 
-/// Trigger a specific channel (regardless of what instrument type it holds).
-///
-/// The trigger will be processed on the next call to `gooey_engine_render`.
+/// Set MIDI notes for all 16 steps of an instrument's sequencer.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `channel` - Channel index (0-3)
+/// * `instrument` - Instrument ID
+/// * `notes` - Pointer to 16 uint8_t values (MIDI notes, or STEP_NOTE_NONE)
 ///
 /// # Safety
-/// `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `notes` must point to at least 16 bytes of allocated memory
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_trigger_channel(engine: *mut GooeyEngine, channel: u32) {
-    gooey_engine_trigger_channel_with_velocity(engine, channel, 1.0);
+pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_note_pattern(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    notes: *const u8,
+) {
+    edit(engine, || {
+        if engine.is_null() || notes.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        let notes_slice = slice::from_raw_parts(notes, 16);
+        if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
+            sequencer.set_note_pattern(notes_slice);
+        }
+    })
 }
 
-/// Trigger a specific channel with velocity.
-///
-/// The trigger will be processed on the next call to `gooey_engine_render`.
+/// Set the entire 16-step pattern for an instrument's sequencer
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `channel` - Channel index (0-3)
-/// * `velocity` - Velocity from 0.0 (softest) to 1.0 (hardest)
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// * `pattern` - Pointer to 16 bools representing step states
 ///
 /// # Safety
-/// `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `pattern` must point to at least 16 bools of allocated memory
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_trigger_channel_with_velocity(
+pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_pattern(
     engine: *mut GooeyEngine,
-    channel: u32,
-    velocity: f32,
+    instrument: u32,
+    pattern: *const bool,
 ) {
-    if let Some(engine) = engine.as_ref() {
-        if let Some(voice) = engine.voice(channel as usize) {
-            let vel_clamped = velocity.clamp(0.0, 1.0);
-            voice
-                .trigger_velocity
-                .store(vel_clamped.to_bits(), Ordering::Release);
-            voice.trigger_pending.store(true, Ordering::Release);
+    edit(engine, || {
+        if engine.is_null() || pattern.is_null() {
+            return;
         }
-    }
+
+        let engine = &mut *engine;
+        let pattern_slice = slice::from_raw_parts(pattern, 16);
+        let pattern_vec: Vec<bool> = pattern_slice.to_vec();
+
+        if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
+            sequencer.set_pattern(pattern_vec);
+        }
+    })
 }
 
-// =============================================================================
-// Instrument triggering
-// =============================================================================
+/// Look up an instrument's sequencer for a pattern operation, reporting
+/// `InvalidInstrument` when it has none.
+fn pattern_sequencer<'a>(
+    engine: &'a mut GooeyEngine,
+    instrument: u32,
+    fn_name: &str,
+) -> Result<&'a mut Sequencer, GooeyResult> {
+    engine.sequencer_for_instrument(instrument).ok_or_else(|| {
+        fail(
+            GooeyResult::InvalidInstrument,
+            format!("{fn_name}: instrument {instrument} has no sequencer"),
+        )
+    })
+}
 
-/// Trigger any instrument manually by ID with velocity
+/// Copy `len` steps of an instrument's pattern from `start` to the engine's
+/// pattern clipboard, replacing what was there. Steps past the end of the
+/// pattern are left out. Steps keep every setting, including layers, which
+/// stay on the instruments they fire when pasted elsewhere.
 ///
-/// Use this for manual triggering with velocity sensitivity (e.g., velocity-sensitive pads, MIDI input).
-/// The trigger will be processed on the next call to `gooey_engine_render`.
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine or an invalid instrument
 ///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
-/// * `velocity` - Velocity from 0.0 (softest) to 1.0 (hardest)
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sequencer_copy_instrument_steps(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    start: u32,
+    len: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_sequencer_copy_instrument_steps";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return null_engine(FN);
+        };
+        let clip = match pattern_sequencer(engine, instrument, FN) {
+            Ok(sequencer) => sequencer.copy_steps(start as usize, len as usize),
+            Err(result) => return result,
+        };
+        engine.pattern_clipboard = Some(clip);
+        GooeyResult::Ok
+    })
+}
+
+/// Paste the pattern clipboard over an instrument's steps from `at` on.
+/// The paste stops at the end of the pattern rather than wrapping. The
+/// clipboard is kept, so it can be pasted again.
+///
+/// # Returns
+/// The number of steps pasted, or -1 for a null engine, an invalid
+/// instrument or an empty clipboard
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_trigger_instrument_with_velocity(
+pub unsafe extern "C" fn gooey_engine_sequencer_paste_instrument_steps(
     engine: *mut GooeyEngine,
     instrument: u32,
-    velocity: f32,
-) {
-    if let Some(engine) = engine.as_ref() {
-        if let Some(voice) = engine.voice(instrument as usize) {
-            let vel_clamped = velocity.clamp(0.0, 1.0);
-            voice
-                .trigger_velocity
-                .store(vel_clamped.to_bits(), Ordering::Release);
-            voice.trigger_pending.store(true, Ordering::Release);
-        }
-    }
+    at: u32,
+) -> i32 {
+    const FN: &str = "gooey_engine_sequencer_paste_instrument_steps";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            null_engine(FN);
+            return -1;
+        };
+        let Some(clip) = engine.pattern_clipboard.take() else {
+            fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: nothing has been copied"),
+            );
+            return -1;
+        };
+        let pasted = match pattern_sequencer(engine, instrument, FN) {
+            Ok(sequencer) => sequencer.paste_steps(&clip, at as usize) as i32,
+            Err(_) => -1,
+        };
+        engine.pattern_clipboard = Some(clip);
+        pasted
+    })
 }
 
-/// Trigger any instrument manually by ID at full velocity
+/// Rotate an instrument's pattern by `steps`: positive moves every step
+/// later, wrapping the last ones round to the front; negative moves them
+/// earlier.
 ///
-/// Use this for manual triggering outside of the sequencer (e.g., user tap).
-/// The trigger will be processed on the next call to `gooey_engine_render`.
-/// For velocity-sensitive triggering, use `gooey_engine_trigger_instrument_with_velocity`.
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine or an invalid instrument
 ///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sequencer_rotate_instrument_pattern(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    steps: i32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_sequencer_rotate_instrument_pattern";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return null_engine(FN);
+        };
+        match pattern_sequencer(engine, instrument, FN) {
+            Ok(sequencer) => {
+                sequencer.rotate(steps as isize);
+                GooeyResult::Ok
+            }
+            Err(result) => result,
+        }
+    })
+}
+
+/// Reverse an instrument's pattern, step settings and all.
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine or an invalid instrument
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_trigger_instrument(
+pub unsafe extern "C" fn gooey_engine_sequencer_reverse_instrument_pattern(
     engine: *mut GooeyEngine,
     instrument: u32,
-) {
-    gooey_engine_trigger_instrument_with_velocity(engine, instrument, 1.0);
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_sequencer_reverse_instrument_pattern";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return null_engine(FN);
+        };
+        match pattern_sequencer(engine, instrument, FN) {
+            Ok(sequencer) => {
+                sequencer.reverse();
+                GooeyResult::Ok
+            }
+            Err(result) => result,
+        }
+    })
 }
 
-// =============================================================================
-// Per-channel peak metering
-// =============================================================================
-
-/// Read per-channel peak amplitudes since the last call and reset them to zero.
-///
-/// Each value represents the maximum absolute amplitude seen on that channel
-/// since the previous call. Useful for driving UI level meters.
+/// Flip every step of an instrument's pattern on or off. Steps keep their
+/// velocities and other settings, so inverting twice restores the pattern.
 ///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `out_peaks` - Pointer to a float buffer to receive peak values (0.0–1.0+)
-/// * `count` - Number of channels to read (clamped to NUM_INSTRUMENTS)
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine or an invalid instrument
 ///
 /// # Safety
-/// `engine` must be a valid pointer returned by `gooey_engine_new`.
-/// `out_peaks` must point to a buffer of at least `count` floats.
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_channel_peaks(
+pub unsafe extern "C" fn gooey_engine_sequencer_invert_instrument_pattern(
     engine: *mut GooeyEngine,
-    out_peaks: *mut f32,
-    count: u32,
-) {
-    if let Some(engine) = engine.as_ref() {
-        let n = (count as usize).min(NUM_INSTRUMENTS);
-        for (i, voice) in engine.voices_iter().take(n).enumerate() {
-            let bits = voice.peak.swap(0.0_f32.to_bits(), Ordering::Relaxed);
-            *out_peaks.add(i) = f32::from_bits(bits);
+    instrument: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_sequencer_invert_instrument_pattern";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return null_engine(FN);
+        };
+        match pattern_sequencer(engine, instrument, FN) {
+            Ok(sequencer) => {
+                sequencer.invert();
+                GooeyResult::Ok
+            }
+            Err(result) => result,
         }
-    }
+    })
 }
 
-/// Trigger the kick drum manually (legacy function, prefer `gooey_engine_trigger_instrument`)
+/// Randomize the velocities of an instrument's enabled steps by up to
+/// `amount` either way (0.0-1.0). The offsets come from the engine's seed
+/// (see `gooey_engine_set_seed`), so the same seed humanizes the same way.
 ///
-/// Use this for manual triggering outside of the sequencer (e.g., user tap).
-/// The trigger will be processed on the next call to `gooey_engine_render`.
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an invalid instrument
+/// or an amount outside 0.0-1.0
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_trigger_kick(engine: *mut GooeyEngine) {
-    if let Some(engine) = engine.as_ref() {
-        if let Some(voice) = engine.voice(INSTRUMENT_KICK as usize) {
-            voice.trigger_pending.store(true, Ordering::Release);
+pub unsafe extern "C" fn gooey_engine_sequencer_humanize_instrument_velocities(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    amount: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_sequencer_humanize_instrument_velocities";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return null_engine(FN);
+        };
+        if !(0.0..=1.0).contains(&amount) {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: amount {amount} is outside 0.0-1.0"),
+            );
         }
-    }
+        let mut rng = engine.humanize_rng;
+        let result = match pattern_sequencer(engine, instrument, FN) {
+            Ok(sequencer) => {
+                sequencer.humanize_velocities(amount, &mut rng);
+                GooeyResult::Ok
+            }
+            Err(result) => result,
+        };
+        engine.humanize_rng = rng;
+        result
+    })
 }
 
-/// Set a kick drum parameter
-///
-/// All parameters are automatically smoothed to prevent clicks/pops.
-///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `param` - Parameter index (see KICK_PARAM_* constants)
-/// * `value` - Parameter value (range depends on parameter)
+/// Multiply the velocities of an instrument's steps by `scale`, clamping
+/// the results to 0.0-1.0.
 ///
-/// # Parameter indices and ranges
-/// - 0 (FREQUENCY): 30-80 Hz
-/// - 1 (PUNCH): 0-1
-/// - 2 (SUB): 0-1
-/// - 3 (CLICK): 0-1
-/// - 4 (DECAY): 0.01-5.0 seconds
-/// - 5 (PITCH_ENVELOPE): 0-1
-/// - 6 (VOLUME): 0-1
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an invalid instrument
+/// or a negative or non-finite scale
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_kick_param(
+pub unsafe extern "C" fn gooey_engine_sequencer_scale_instrument_velocities(
     engine: *mut GooeyEngine,
-    param: u32,
-    value: f32,
-) {
-    if engine.is_null() {
-        return;
-    }
-    let engine = &mut *engine;
-    if let Some(instr) = engine.instrument_by_type_mut(INSTRUMENT_KICK) {
-        instr.set_param(param, value);
-    }
+    instrument: u32,
+    scale: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_sequencer_scale_instrument_velocities";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return null_engine(FN);
+        };
+        if !scale.is_finite() || scale < 0.0 {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: scale {scale} must be finite and not negative"),
+            );
+        }
+        match pattern_sequencer(engine, instrument, FN) {
+            Ok(sequencer) => {
+                sequencer.scale_velocities(scale);
+                GooeyResult::Ok
+            }
+            Err(result) => result,
+        }
+    })
 }
 
-/// Read a kick drum parameter in the same normalized form used by
-/// `gooey_engine_set_kick_param`.
-///
-/// Returns the most-recently-set target value (not the in-flight smoothed sample),
-/// so set→get round-trips exactly. Use this to capture mutations applied by
-/// randomize/mutate flows for snapshot/undo.
-///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `param` - Parameter index (see KICK_PARAM_* constants)
+/// Pull the velocities of an instrument's enabled steps toward their
+/// average: 0.0 leaves them alone, 1.0 plays every hit at the average.
 ///
 /// # Returns
-/// The current parameter value, or `f32::NAN` if `engine` is null, the kick
-/// channel is missing, or `param` is unrecognized. Note that
-/// `KICK_PARAM_PITCH_ENVELOPE` reports the value set via the setter; it only
-/// takes audible effect at the next trigger.
+/// `GooeyResult::Ok`, or an error for a null engine, an invalid instrument
+/// or an amount outside 0.0-1.0
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_kick_param(
-    engine: *const GooeyEngine,
-    param: u32,
-) -> f32 {
-    if engine.is_null() {
-        return f32::NAN;
-    }
-    let engine = &*engine;
-    match engine.instrument_by_type(INSTRUMENT_KICK) {
-        Some(instr) => instr.get_param(param),
-        None => f32::NAN,
-    }
+pub unsafe extern "C" fn gooey_engine_sequencer_compress_instrument_velocities(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    amount: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_sequencer_compress_instrument_velocities";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return null_engine(FN);
+        };
+        if !(0.0..=1.0).contains(&amount) {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: amount {amount} is outside 0.0-1.0"),
+            );
+        }
+        match pattern_sequencer(engine, instrument, FN) {
+            Ok(sequencer) => {
+                sequencer.compress_velocities(amount);
+                GooeyResult::Ok
+            }
+            Err(result) => result,
+        }
+    })
 }
 
-/// Set a hi-hat parameter
-///
-/// All parameters are automatically smoothed to prevent clicks/pops.
+/// Get the current step for an instrument's sequencer
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `param` - Parameter index (see HIHAT_PARAM_* constants)
-/// * `value` - Parameter value (range depends on parameter)
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
 ///
-/// # Parameter indices and ranges
-/// - 0 (PITCH): 0-1 normalized
-/// - 1 (DECAY): 0-1 normalized
-/// - 2 (ATTACK): 0-1 normalized
-/// - 3 (TONE): 0-1 normalized
+/// # Returns
+/// The current step index (0-15), or -1 if the sequencer is not running or invalid instrument
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_hihat_param(
+pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_step(
     engine: *mut GooeyEngine,
-    param: u32,
-    value: f32,
-) {
+    instrument: u32,
+) -> i32 {
     if engine.is_null() {
-        return;
+        return -1;
     }
-    let engine = &mut *engine;
-    if let Some(instr) = engine.instrument_by_type_mut(INSTRUMENT_HIHAT) {
-        instr.set_param(param, value);
+
+    let engine = &*engine;
+    if let Some(sequencer) = engine.sequencer_for_instrument_ref(instrument) {
+        if sequencer.is_running() {
+            return sequencer.current_step() as i32;
+        }
     }
+    -1
 }
 
-/// Read a hi-hat parameter in the same normalized form used by
-/// `gooey_engine_set_hihat_param`.
+/// Get the step that will be playing after a lookahead period for an instrument
 ///
-/// Returns the most-recently-set target value (not the in-flight smoothed sample),
-/// so set→get round-trips exactly.
+/// This compensates for audio buffer latency by looking ahead.
+/// Use this for UI display to sync visuals with audio output.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `param` - Parameter index (see HIHAT_PARAM_* constants)
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// * `lookahead_samples` - Number of samples to look ahead (typically audio buffer size)
 ///
 /// # Returns
-/// The current parameter value, or `f32::NAN` if `engine` is null, the hi-hat
-/// channel is missing, or `param` is unrecognized.
+/// The step index that will be playing after the lookahead, or -1 if not running
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_hihat_param(
-    engine: *const GooeyEngine,
-    param: u32,
-) -> f32 {
+pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_step_with_lookahead(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    lookahead_samples: u32,
+) -> i32 {
     if engine.is_null() {
-        return f32::NAN;
+        return -1;
     }
+
     let engine = &*engine;
-    match engine.instrument_by_type(INSTRUMENT_HIHAT) {
-        Some(instr) => instr.get_param(param),
-        None => f32::NAN,
+    if let Some(sequencer) = engine.sequencer_for_instrument_ref(instrument) {
+        if sequencer.is_running() {
+            return sequencer.step_at_lookahead(lookahead_samples as u64) as i32;
+        }
     }
+    -1
 }
 
-/// Set a snare drum parameter
-///
-/// All parameters are automatically smoothed to prevent clicks/pops.
-/// All parameters use normalized 0-1 range.
+/// Get the velocity for a specific step in an instrument's sequencer
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `param` - Parameter index (see SNARE_PARAM_* constants)
-/// * `value` - Parameter value (0-1 normalized)
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// * `step` - Step index (0-15 for a 16-step sequencer)
 ///
-/// # Parameter indices and ranges (all 0-1 normalized)
-/// - 0 (FREQUENCY): 0-1 → 100-600 Hz
-/// - 1 (DECAY): 0-1 → 0.05-3.5 seconds
-/// - 2 (BRIGHTNESS): 0-1
-/// - 3 (VOLUME): 0-1
-/// - 4 (TONAL): 0-1
-/// - 5 (NOISE): 0-1
-/// - 6 (PITCH_DROP): 0-1
-/// - 7 (TONAL_DECAY): 0-1 → 0-3.5s
-/// - 8 (NOISE_DECAY): 0-1 → 0-3.5s
-/// - 9 (NOISE_TAIL_DECAY): 0-1 → 0-3.5s
-/// - 10 (FILTER_CUTOFF): 0-1 → 100-10000 Hz
-/// - 11 (FILTER_RESONANCE): 0-1 → 0.5-10.0
-/// - 12 (FILTER_TYPE): 0-3 (LP/BP/HP/notch)
-/// - 13 (XFADE): 0-1
-/// - 14 (PHASE_MOD_AMOUNT): 0-1
-/// - 15 (OVERDRIVE): 0-1
-/// - 16 (AMP_DECAY): 0-1 → 0-4.0s
-/// - 17 (AMP_DECAY_CURVE): 0-1 → 0.1-10.0
+/// # Returns
+/// The velocity (0.0-1.0), or 0.0 if invalid engine/instrument/step
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_snare_param(
+pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_step_velocity(
     engine: *mut GooeyEngine,
-    param: u32,
-    value: f32,
-) {
+    instrument: u32,
+    step: u32,
+) -> f32 {
     if engine.is_null() {
-        return;
+        return 0.0;
     }
-    let engine = &mut *engine;
-    if let Some(instr) = engine.instrument_by_type_mut(INSTRUMENT_SNARE) {
-        instr.set_param(param, value);
+
+    let engine = &*engine;
+    if let Some(sequencer) = engine.sequencer_for_instrument_ref(instrument) {
+        return sequencer.get_step_velocity(step as usize);
     }
+    0.0
 }
 
-/// Read a snare drum parameter in the same normalized form used by
-/// `gooey_engine_set_snare_param`.
-///
-/// Returns the most-recently-set target value (not the in-flight smoothed sample),
-/// so set→get round-trips exactly. `SNARE_PARAM_FILTER_TYPE` is reported as the
-/// raw 0-3 enum value cast to `f32`.
+/// Get the blend X setting for a specific step
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `param` - Parameter index (see SNARE_PARAM_* constants)
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// * `step` - Step index (0-15 for a 16-step sequencer)
 ///
 /// # Returns
-/// The current parameter value, or `f32::NAN` if `engine` is null, the snare
-/// channel is missing, or `param` is unrecognized.
+/// The X position (0.0-1.0), or -1.0 if no blend setting or invalid engine/instrument/step
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_snare_param(
-    engine: *const GooeyEngine,
-    param: u32,
+pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_step_blend_x(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    step: u32,
 ) -> f32 {
     if engine.is_null() {
-        return f32::NAN;
+        return -1.0;
     }
+
     let engine = &*engine;
-    match engine.instrument_by_type(INSTRUMENT_SNARE) {
-        Some(instr) => instr.get_param(param),
-        None => f32::NAN,
+    if let Some(sequencer) = engine.sequencer_for_instrument_ref(instrument) {
+        if let Some(blend_setting) = sequencer.get_step_blend(step as usize) {
+            return blend_setting.x;
+        }
     }
+    -1.0
+}
+
+/// Legacy alias for `gooey_engine_sequencer_get_instrument_step_blend_x`.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_step_blend_override_x(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    step: u32,
+) -> f32 {
+    gooey_engine_sequencer_get_instrument_step_blend_x(engine, instrument, step)
 }
 
-/// Set a tom drum parameter
-///
-/// All parameters use normalized 0-1 range. Values are internally scaled
-/// to Tom2's 0-100 range.
+/// Get the blend Y setting for a specific step
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `param` - Parameter index (see TOM_PARAM_* constants)
-/// * `value` - Parameter value (0-1 normalized)
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// * `step` - Step index (0-15 for a 16-step sequencer)
 ///
-/// # Parameter indices and ranges (all 0-1 normalized → 0-100 internal)
-/// - 0 (TUNE): 0-1 → 0-100 (maps to 40-600 Hz)
-/// - 1 (BEND): 0-1 → 0-100 (pitch envelope depth)
-/// - 2 (TONE): 0-1 → 0-100 (mix control)
-/// - 3 (COLOR): 0-1 → 0-100 (noise rate / filter cutoff)
-/// - 4 (DECAY): 0-1 → 0-100 (maps to 0.5-4000ms)
-/// - 5 (MEMBRANE): 0-1 → 0-100 (resonator mix)
-/// - 6 (MEMBRANE_Q): 0-1 → 0-100 (resonator Q scale)
+/// # Returns
+/// The Y position (0.0-1.0), or -1.0 if no blend setting or invalid engine/instrument/step
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_tom_param(
+pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_step_blend_y(
     engine: *mut GooeyEngine,
-    param: u32,
-    value: f32,
-) {
+    instrument: u32,
+    step: u32,
+) -> f32 {
     if engine.is_null() {
-        return;
+        return -1.0;
     }
-    let engine = &mut *engine;
-    if let Some(instr) = engine.instrument_by_type_mut(INSTRUMENT_TOM) {
-        instr.set_param(param, value);
+
+    let engine = &*engine;
+    if let Some(sequencer) = engine.sequencer_for_instrument_ref(instrument) {
+        if let Some(blend_setting) = sequencer.get_step_blend(step as usize) {
+            return blend_setting.y;
+        }
     }
+    -1.0
 }
 
-/// Read a tom drum parameter in the same normalized form used by
-/// `gooey_engine_set_tom_param`.
-///
-/// Tom2 stores parameters 0-7 internally on a 0-100 scale; the getter
-/// renormalizes back to 0-1 to match the setter contract. `TOM_PARAM_TUNING`
-/// (param 8) is the exception: it's already 0-1 in both directions.
+/// Legacy alias for `gooey_engine_sequencer_get_instrument_step_blend_y`.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_step_blend_override_y(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    step: u32,
+) -> f32 {
+    gooey_engine_sequencer_get_instrument_step_blend_y(engine, instrument, step)
+}
+
+/// Get the enabled state for a specific step in an instrument's sequencer
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `param` - Parameter index (see TOM_PARAM_* constants)
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// * `step` - Step index (0-15 for a 16-step sequencer)
 ///
 /// # Returns
-/// The current parameter value, or `f32::NAN` if `engine` is null, the tom
-/// channel is missing, or `param` is unrecognized.
+/// Whether the step is enabled (true/false), or false if invalid engine/instrument/step
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_tom_param(engine: *const GooeyEngine, param: u32) -> f32 {
+pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_step_enabled(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    step: u32,
+) -> bool {
     if engine.is_null() {
-        return f32::NAN;
+        return false;
     }
+
     let engine = &*engine;
-    match engine.instrument_by_type(INSTRUMENT_TOM) {
-        Some(instr) => instr.get_param(param),
-        None => f32::NAN,
+    if let Some(sequencer) = engine.sequencer_for_instrument_ref(instrument) {
+        return sequencer.get_step_enabled(step as usize);
     }
+    false
 }
 
-/// Set a bass synth parameter
+// =============================================================================
+// Step pages (A/B/C/D) for patterns longer than 16 steps
+// =============================================================================
+
+/// Steps per page: one row of 16 pads.
+pub const SEQUENCER_PAGE_STEPS: u32 = 16;
+/// Pages a pattern can span (A-D), for patterns of up to 64 steps.
+pub const SEQUENCER_PAGE_MAX: u32 = 4;
+
+/// Reject a page count outside `1..=SEQUENCER_PAGE_MAX`.
+fn check_page_count(pages: u32, fn_name: &str) -> Result<(), GooeyResult> {
+    if (1..=SEQUENCER_PAGE_MAX).contains(&pages) {
+        Ok(())
+    } else {
+        Err(fail(
+            GooeyResult::InvalidValue,
+            format!("{fn_name}: {pages} pages is outside 1-{SEQUENCER_PAGE_MAX}"),
+        ))
+    }
+}
+
+/// Pattern step for `step` of `page`, or `InvalidValue` when either is past
+/// the end of a pattern of `len` steps.
+fn page_step_index(len: usize, page: u32, step: u32, fn_name: &str) -> Result<usize, GooeyResult> {
+    let index = page as usize * SEQUENCER_PAGE_STEPS as usize + step as usize;
+    if step >= SEQUENCER_PAGE_STEPS || index >= len {
+        return Err(fail(
+            GooeyResult::InvalidValue,
+            format!("{fn_name}: page {page} step {step} is past the end of the pattern"),
+        ));
+    }
+    Ok(index)
+}
+
+/// Page the playhead of `sequencer` is on after `lookahead_samples`, or -1
+/// when it is stopped.
+fn playhead_page(sequencer: &Sequencer, lookahead_samples: u32) -> i32 {
+    if !sequencer.is_running() {
+        return -1;
+    }
+    (sequencer.step_at_lookahead(lookahead_samples as u64) / SEQUENCER_PAGE_STEPS as usize) as i32
+}
+
+/// Set every sequencer's pattern length to `pages` pages of
+/// SEQUENCER_PAGE_STEPS steps, keeping them in step with each other.
 ///
-/// All parameters use normalized 0-1 range. Values are internally scaled.
+/// Pages added at the end start empty; pages cut from the end are dropped.
+/// A playhead left past the new end restarts from the first step.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `param` - Parameter index (see BASS_PARAM_* constants)
-/// * `value` - Parameter value (0.0-1.0 normalized)
+/// * `pages` - Page count (1 to SEQUENCER_PAGE_MAX)
 ///
-/// # Parameter indices and ranges
-/// - 0 (FREQUENCY): 0-1 → 30-200 Hz
-/// - 1 (SUB_LEVEL): 0-1
-/// - 2 (OSC_LEVEL): 0-1
-/// - 3 (DETUNE_LEVEL): 0-1
-/// - 4 (DETUNE_AMOUNT): 0-1 → 0-30 cents
-/// - 5 (OSC_SHAPE): 0-1 (saw to square)
-/// - 6 (FILTER_CUTOFF): 0-1 → 20-18000 Hz exp
-/// - 7 (FILTER_RESONANCE): 0-1 → 0.5-15.0 Q
-/// - 8 (FILTER_ENV_AMOUNT): 0-1
-/// - 9 (FILTER_ENV_DECAY): 0-1 → 0.01-2.0s
-/// - 10 (FILTER_ENV_CURVE): 0-1 → 0.1-8.0
-/// - 11 (AMP_DECAY): 0-1 → 0.05-4.0s
-/// - 12 (AMP_DECAY_CURVE): 0-1 → 0.1-10.0
-/// - 13 (OVERDRIVE): 0-1
-/// - 14 (VOLUME): 0-1
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine or an invalid page count.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_bass_param(
+pub unsafe extern "C" fn gooey_engine_sequencer_set_page_count(
     engine: *mut GooeyEngine,
-    param: u32,
-    value: f32,
-) {
-    if engine.is_null() {
-        return;
-    }
-    let engine = &mut *engine;
-    if let Some(instr) = engine.instrument_by_type_mut(INSTRUMENT_BASS) {
-        instr.set_param(param, value);
-    }
+    pages: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_sequencer_set_page_count";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return null_engine(FN);
+        };
+        if let Err(result) = check_page_count(pages, FN) {
+            return result;
+        }
+        for sequencer in engine.sequencers_iter_mut() {
+            sequencer.set_length((pages * SEQUENCER_PAGE_STEPS) as usize);
+        }
+        GooeyResult::Ok
+    })
 }
 
-/// Load a bass preset, setting all bass parameters to the preset's values.
-///
-/// This directly applies the preset's parameter values to the bass instrument
-/// without requiring the blend system.
+/// Set one instrument's pattern length to `pages` pages, for a polymetric
+/// part that loops against the others. See
+/// `gooey_engine_sequencer_set_page_count`.
 ///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `preset_id` - Preset ID (BASS_PRESET_ACID, BASS_PRESET_SUB, etc.)
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an invalid instrument
+/// or an invalid page count.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_load_bass_preset(engine: *mut GooeyEngine, preset_id: u32) {
-    if engine.is_null() {
-        return;
-    }
-    let engine = &mut *engine;
-    if let Some(config) = GooeyEngine::bass_preset_by_id(preset_id) {
-        if let Some(ChannelInstrument::Bass(bass)) = engine.instrument_by_type_mut(INSTRUMENT_BASS)
-        {
-            bass.set_config(config);
+pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_page_count(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    pages: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_sequencer_set_instrument_page_count";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return null_engine(FN);
+        };
+        if let Err(result) = check_page_count(pages, FN) {
+            return result;
         }
-    }
+        match pattern_sequencer(engine, instrument, FN) {
+            Ok(sequencer) => {
+                sequencer.set_length((pages * SEQUENCER_PAGE_STEPS) as usize);
+                GooeyResult::Ok
+            }
+            Err(result) => result,
+        }
+    })
 }
 
-// =============================================================================
-// Global effects control
-// =============================================================================
-
-/// Set a parameter on a global effect
+/// Get the number of pages an instrument's pattern spans. A pattern whose
+/// length is not a whole number of pages (set through the DSL or a
+/// snapshot) counts its last, partial page.
 ///
-/// This provides a generic interface for controlling any global effect's parameters.
-/// Use effect ID constants (EFFECT_LOWPASS_FILTER, etc.) and corresponding parameter
-/// constants (FILTER_PARAM_CUTOFF, FILTER_PARAM_RESONANCE, etc.).
+/// # Returns
+/// The page count, or 0 for a null engine or an invalid instrument
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_page_count(
+    engine: *const GooeyEngine,
+    instrument: u32,
+) -> u32 {
+    engine
+        .as_ref()
+        .and_then(|engine| engine.sequencer_for_instrument_ref(instrument))
+        .map_or(0, |sequencer| {
+            sequencer
+                .pattern_steps()
+                .len()
+                .div_ceil(SEQUENCER_PAGE_STEPS as usize) as u32
+        })
+}
+
+/// Set a step by page and pad position, so a 16-pad UI can edit any page
+/// without computing absolute step indices.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `effect` - Effect ID (see EFFECT_* constants)
-/// * `param` - Parameter ID (depends on effect type, see *_PARAM_* constants)
-/// * `value` - Parameter value (range depends on parameter)
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// * `page` - Page index (0 = A, 1 = B, ...)
+/// * `step` - Step within the page (0 to SEQUENCER_PAGE_STEPS - 1)
+/// * `enabled` - Whether the step should trigger
+/// * `velocity` - Velocity (0.0-1.0)
 ///
-/// # Effect and Parameter Reference
-/// - EFFECT_LOWPASS_FILTER (0):
-///   - FILTER_PARAM_CUTOFF (0): 20-20000 Hz
-///   - FILTER_PARAM_RESONANCE (1): 0.0-0.95
-/// - EFFECT_DELAY (1):
-///   - DELAY_PARAM_TIMING (0): DELAY_TIMING_* constant (0-8)
-///   - DELAY_PARAM_FEEDBACK (1): 0.0-0.95
-///   - DELAY_PARAM_MIX (2): 0.0-1.0
-///   - DELAY_PARAM_FILTER_CUTOFF (3): 20-20000 Hz
-///   - DELAY_PARAM_PINGPONG (4): 0.0 = off, >= 0.5 = on
-/// - EFFECT_SATURATION (2):
-///   - SATURATION_PARAM_DRIVE (0): 0.0-1.0
-///   - SATURATION_PARAM_WARMTH (1): 0.0-1.0
-///   - SATURATION_PARAM_MIX (2): 0.0-1.0
-/// - EFFECT_COMPRESSOR (3):
-///   - COMPRESSOR_PARAM_THRESHOLD (0): -60.0 to 0.0 dB
-///   - COMPRESSOR_PARAM_RATIO (1): 1.0-20.0
-///   - COMPRESSOR_PARAM_ATTACK (2): 0.1-100.0 ms
-///   - COMPRESSOR_PARAM_RELEASE (3): 5.0-1000.0 ms
-///   - COMPRESSOR_PARAM_MIX (4): 0.0-1.0
-/// - EFFECT_LIMITER (5):
-///   - LIMITER_PARAM_THRESHOLD (0): 0.001-1.0
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an invalid instrument,
+/// or a page or step past the end of the pattern.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_global_effect_param(
+pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_page_step(
     engine: *mut GooeyEngine,
-    effect: u32,
-    param: u32,
-    value: f32,
-) {
-    if engine.is_null() {
-        return;
-    }
-
-    let engine = &mut *engine;
-
-    match effect {
-        EFFECT_LOWPASS_FILTER => match param {
-            FILTER_PARAM_CUTOFF => engine.lowpass_filter.set_cutoff_freq(value),
-            FILTER_PARAM_RESONANCE => engine.lowpass_filter.set_resonance(value),
-            _ => {} // Unknown parameter, ignore
-        },
-        EFFECT_DELAY => match param {
-            DELAY_PARAM_TIMING => {
-                if let Some(timing) = DelayTiming::from_timing_constant(value as u32) {
-                    engine.delay.set_timing(timing);
-                }
-            }
-            DELAY_PARAM_FEEDBACK => engine.delay.set_feedback(value),
-            DELAY_PARAM_MIX => engine.delay.set_mix(value),
-            DELAY_PARAM_FILTER_CUTOFF => engine.delay.set_filter_cutoff(value),
-            DELAY_PARAM_PINGPONG => engine.delay.set_pingpong(value >= 0.5),
-            _ => {} // Unknown parameter, ignore
-        },
-        EFFECT_SATURATION => match param {
-            SATURATION_PARAM_DRIVE => engine.saturation.set_drive(value),
-            SATURATION_PARAM_WARMTH => engine.saturation.set_warmth(value),
-            SATURATION_PARAM_MIX => engine.saturation.set_mix(value),
-            _ => {} // Unknown parameter, ignore
-        },
-        EFFECT_COMPRESSOR => match param {
-            COMPRESSOR_PARAM_THRESHOLD => engine.compressor.set_threshold(value),
-            COMPRESSOR_PARAM_RATIO => engine.compressor.set_ratio(value),
-            COMPRESSOR_PARAM_ATTACK => engine.compressor.set_attack(value),
-            COMPRESSOR_PARAM_RELEASE => engine.compressor.set_release(value),
-            COMPRESSOR_PARAM_MIX => engine.compressor.set_mix(value),
-            _ => {} // Unknown parameter, ignore
-        },
-        EFFECT_TILT_FILTER => match param {
-            TILT_PARAM_CUTOFF => engine.tilt_filter.set_cutoff(value),
-            TILT_PARAM_RESONANCE => engine.tilt_filter.set_resonance(value),
-            _ => {} // Unknown parameter, ignore
-        },
-        EFFECT_WAVESHAPER => match param {
-            WAVESHAPER_PARAM_DRIVE => engine.waveshaper.set_drive(value),
-            WAVESHAPER_PARAM_MIX => engine.waveshaper.set_mix(value),
-            _ => {}
-        },
-        EFFECT_FEEDBACK_WAVESHAPER => match param {
-            FEEDBACK_WAVESHAPER_PARAM_DRIVE => engine.feedback_waveshaper.set_drive(value),
-            FEEDBACK_WAVESHAPER_PARAM_FEEDBACK => engine.feedback_waveshaper.set_feedback(value),
-            FEEDBACK_WAVESHAPER_PARAM_FILTER_CUTOFF => {
-                engine.feedback_waveshaper.set_filter_cutoff(value)
+    instrument: u32,
+    page: u32,
+    step: u32,
+    enabled: bool,
+    velocity: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_sequencer_set_instrument_page_step";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return null_engine(FN);
+        };
+        let sequencer = match pattern_sequencer(engine, instrument, FN) {
+            Ok(sequencer) => sequencer,
+            Err(result) => return result,
+        };
+        match page_step_index(sequencer.pattern_steps().len(), page, step, FN) {
+            Ok(index) => {
+                sequencer.set_step_with_velocity(index, enabled, velocity);
+                GooeyResult::Ok
             }
-            FEEDBACK_WAVESHAPER_PARAM_MIX => engine.feedback_waveshaper.set_mix(value),
-            _ => {}
-        },
-        EFFECT_REVERB => match param {
-            REVERB_PARAM_DECAY => engine.reverb.set_decay(value),
-            REVERB_PARAM_MIX => engine.reverb.set_mix(value),
-            REVERB_PARAM_DAMPING => engine.reverb.set_damping(value),
-            _ => {} // Unknown parameter, ignore
-        },
-        EFFECT_PLATE_REVERB => match param {
-            PLATE_PARAM_DECAY => engine.plate_reverb.set_decay(value),
-            PLATE_PARAM_MIX => engine.plate_reverb.set_mix(value),
-            PLATE_PARAM_DAMPING => engine.plate_reverb.set_damping(value),
-            PLATE_PARAM_PREDELAY => engine.plate_reverb.set_predelay(value),
-            PLATE_PARAM_WIDTH => engine.plate_reverb.set_width(value),
-            PLATE_PARAM_SIZE => engine.plate_reverb.set_size(value),
-            _ => {} // Unknown parameter, ignore
-        },
-        EFFECT_LIMITER => match param {
-            LIMITER_PARAM_THRESHOLD => engine.limiter.set_threshold(value),
-            _ => {} // Unknown parameter, ignore
-        },
-        _ => {} // Unknown effect, ignore
-    }
+            Err(result) => result,
+        }
+    })
 }
 
-/// Get a parameter value from a global effect
+/// Read one page of an instrument's pattern into SEQUENCER_PAGE_STEPS-long
+/// arrays, to redraw the pads when the user flips pages. Pads past the end
+/// of a partial last page read as off with velocity 0.
 ///
-/// This provides a generic interface for reading any global effect's parameters.
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// * `page` - Page index (0 = A, 1 = B, ...)
+/// * `enabled_out` - Receives each step's enabled state, or null
+/// * `velocities_out` - Receives each step's velocity, or null
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an invalid instrument
+/// or a page past the end of the pattern.
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `enabled_out` and `velocities_out` must each be null or point to
+///   SEQUENCER_PAGE_STEPS writable values
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_page(
+    engine: *const GooeyEngine,
+    instrument: u32,
+    page: u32,
+    enabled_out: *mut bool,
+    velocities_out: *mut f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_sequencer_get_instrument_page";
+    let Some(engine) = engine.as_ref() else {
+        return null_engine(FN);
+    };
+    let Some(sequencer) = engine.sequencer_for_instrument_ref(instrument) else {
+        return fail(
+            GooeyResult::InvalidInstrument,
+            format!("{FN}: instrument {instrument} has no sequencer"),
+        );
+    };
+    let steps = sequencer.pattern_steps();
+    let start = match page_step_index(steps.len(), page, 0, FN) {
+        Ok(start) => start,
+        Err(result) => return result,
+    };
+    let page_steps = &steps[start..steps.len().min(start + SEQUENCER_PAGE_STEPS as usize)];
+    for pad in 0..SEQUENCER_PAGE_STEPS as usize {
+        let step = page_steps.get(pad);
+        if !enabled_out.is_null() {
+            *enabled_out.add(pad) = step.is_some_and(|s| s.enabled);
+        }
+        if !velocities_out.is_null() {
+            *velocities_out.add(pad) = step.map_or(0.0, |s| s.velocity);
+        }
+    }
+    GooeyResult::Ok
+}
+
+/// Get the page the playhead is on, so a paged UI can follow playback.
+/// Uses the reference (kick) sequencer; with per-instrument page counts use
+/// `gooey_engine_sequencer_get_instrument_current_page`.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `effect` - Effect ID (see EFFECT_* constants)
-/// * `param` - Parameter ID (depends on effect type)
+/// * `lookahead_samples` - Samples to look ahead (typically the audio
+///   buffer size, or 0)
 ///
 /// # Returns
-/// The current parameter value, or -1.0 if the effect or parameter is invalid
+/// The page index (0 = A), or -1 if the sequencer is not running
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_global_effect_param(
-    engine: *mut GooeyEngine,
-    effect: u32,
-    param: u32,
-) -> f32 {
-    if engine.is_null() {
-        return -1.0;
-    }
+pub unsafe extern "C" fn gooey_engine_sequencer_get_current_page(
+    engine: *const GooeyEngine,
+    lookahead_samples: u32,
+) -> i32 {
+    engine
+        .as_ref()
+        .and_then(GooeyEngine::reference_sequencer)
+        .map_or(-1, |sequencer| playhead_page(sequencer, lookahead_samples))
+}
 
-    let engine = &*engine;
+/// Get the page an instrument's playhead is on. See
+/// `gooey_engine_sequencer_get_current_page`.
+///
+/// # Returns
+/// The page index (0 = A), or -1 if the sequencer is not running or the
+/// instrument is invalid
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_current_page(
+    engine: *const GooeyEngine,
+    instrument: u32,
+    lookahead_samples: u32,
+) -> i32 {
+    engine
+        .as_ref()
+        .and_then(|engine| engine.sequencer_for_instrument_ref(instrument))
+        .map_or(-1, |sequencer| playhead_page(sequencer, lookahead_samples))
+}
 
-    match effect {
-        EFFECT_LOWPASS_FILTER => match param {
-            FILTER_PARAM_CUTOFF => engine.lowpass_filter.get_cutoff_freq(),
-            FILTER_PARAM_RESONANCE => engine.lowpass_filter.get_resonance(),
-            _ => -1.0, // Unknown parameter
-        },
-        EFFECT_DELAY => match param {
-            DELAY_PARAM_TIMING => engine.delay.get_timing() as f32,
-            DELAY_PARAM_FEEDBACK => engine.delay.get_feedback(),
-            DELAY_PARAM_MIX => engine.delay.get_mix(),
-            DELAY_PARAM_FILTER_CUTOFF => engine.delay.get_filter_cutoff(),
-            DELAY_PARAM_PINGPONG => {
-                if engine.delay.get_pingpong() {
-                    1.0
-                } else {
-                    0.0
-                }
-            }
-            _ => -1.0, // Unknown parameter
-        },
-        EFFECT_SATURATION => match param {
-            SATURATION_PARAM_DRIVE => engine.saturation.get_drive(),
-            SATURATION_PARAM_WARMTH => engine.saturation.get_warmth(),
-            SATURATION_PARAM_MIX => engine.saturation.get_mix(),
-            _ => -1.0, // Unknown parameter
-        },
-        EFFECT_COMPRESSOR => match param {
-            COMPRESSOR_PARAM_THRESHOLD => engine.compressor.get_threshold(),
-            COMPRESSOR_PARAM_RATIO => engine.compressor.get_ratio(),
-            COMPRESSOR_PARAM_ATTACK => engine.compressor.get_attack(),
-            COMPRESSOR_PARAM_RELEASE => engine.compressor.get_release(),
-            COMPRESSOR_PARAM_MIX => engine.compressor.get_mix(),
-            _ => -1.0, // Unknown parameter
-        },
-        EFFECT_TILT_FILTER => match param {
-            TILT_PARAM_CUTOFF => engine.tilt_filter.get_cutoff(),
-            TILT_PARAM_RESONANCE => engine.tilt_filter.get_resonance(),
-            _ => -1.0, // Unknown parameter
-        },
-        EFFECT_REVERB => match param {
-            REVERB_PARAM_DECAY => engine.reverb.get_decay(),
-            REVERB_PARAM_MIX => engine.reverb.get_mix(),
-            REVERB_PARAM_DAMPING => engine.reverb.get_damping(),
-            _ => -1.0, // Unknown parameter
-        },
-        EFFECT_PLATE_REVERB => match param {
-            PLATE_PARAM_DECAY => engine.plate_reverb.get_decay(),
-            PLATE_PARAM_MIX => engine.plate_reverb.get_mix(),
-            PLATE_PARAM_DAMPING => engine.plate_reverb.get_damping(),
-            PLATE_PARAM_PREDELAY => engine.plate_reverb.get_predelay(),
-            PLATE_PARAM_WIDTH => engine.plate_reverb.get_width(),
-            PLATE_PARAM_SIZE => engine.plate_reverb.get_size(),
-            _ => -1.0, // Unknown parameter
-        },
-        EFFECT_LIMITER => match param {
-            LIMITER_PARAM_THRESHOLD => engine.limiter.get_threshold(),
-            _ => -1.0, // Unknown parameter
-        },
-        _ => -1.0, // Unknown effect
+// =============================================================================
+// Pattern slots and quantized pattern launch
+// =============================================================================
+
+/// Number of stored pattern slots.
+pub const PATTERN_SLOT_COUNT: u32 = 16;
+
+impl GooeyEngine {
+    /// Whether every sequencer has switched to the last launched pattern.
+    fn pattern_launch_landed(&self) -> bool {
+        !self.voices_iter().any(|v| v.sequencer.has_queued_pattern())
+    }
+
+    /// Slot currently heard, counting a launch that has landed.
+    fn playing_pattern(&self) -> Option<u32> {
+        match self.queued_pattern {
+            Some(slot) if self.pattern_launch_landed() => Some(slot),
+            _ => self.active_pattern,
+        }
     }
 }
 
-/// Enable or disable a global effect
-///
-/// When disabled, the effect is bypassed and does not process audio.
-/// This is useful for A/B comparison or saving CPU when an effect is not needed.
-/// Saturation, compressor, and limiter are disabled by default.
+/// Store every channel's current sequencer pattern in a pattern slot.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `effect` - Effect ID (see EFFECT_* constants)
-/// * `enabled` - Whether the effect should be active
+/// * `slot` - Pattern slot (0 to PATTERN_SLOT_COUNT - 1)
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine or an invalid slot.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_global_effect_enabled(
+pub unsafe extern "C" fn gooey_engine_pattern_store(
     engine: *mut GooeyEngine,
-    effect: u32,
-    enabled: bool,
-) {
-    if engine.is_null() {
-        return;
-    }
-
-    let engine = &mut *engine;
-
-    match effect {
-        EFFECT_LOWPASS_FILTER => engine.lowpass_filter_enabled = enabled,
-        EFFECT_DELAY => engine.delay_enabled = enabled,
-        EFFECT_SATURATION => engine.saturation_enabled = enabled,
-        EFFECT_COMPRESSOR => engine.compressor_enabled = enabled,
-        EFFECT_TILT_FILTER => engine.tilt_filter_enabled = enabled,
-        EFFECT_LIMITER => engine.limiter_enabled = enabled,
-        EFFECT_REVERB => engine.reverb_enabled = enabled,
-        EFFECT_PLATE_REVERB => engine.plate_reverb_enabled = enabled,
-        EFFECT_WAVESHAPER => engine.waveshaper_enabled = enabled,
-        EFFECT_FEEDBACK_WAVESHAPER => engine.feedback_waveshaper_enabled = enabled,
-        _ => {} // Unknown effect, ignore
-    }
+    slot: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_pattern_store";
+    edit(engine, || {
+        if engine.is_null() {
+            return null_engine(FN);
+        }
+        let engine = &mut *engine;
+        if slot >= PATTERN_SLOT_COUNT {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: slot {slot} is out of range"),
+            );
+        }
+        let patterns = (0..NUM_CHANNELS)
+            .map(|ch| {
+                engine
+                    .voice(ch)
+                    .map(|v| v.sequencer.pattern_steps().to_vec())
+            })
+            .collect();
+        engine.pattern_slots[slot as usize] = Some(patterns);
+        GooeyResult::Ok
+    })
 }
 
-/// Check if a global effect is enabled
+/// Queue a stored pattern slot to replace the playing patterns.
+///
+/// Every channel's sequencer switches when its cursor reaches the next step
+/// on the quantization grid (a bar is 16 steps), keeping the running phase to
+/// the sample; the old patterns play up to that point. With the sequencers
+/// stopped, or with CLIP_QUANTIZE_IMMEDIATE, the patterns switch at once.
+/// Launching again before the switch replaces the queued slot. Sampler racks
+/// keep their own patterns.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `effect` - Effect ID (see EFFECT_* constants)
+/// * `slot` - Pattern slot stored with `gooey_engine_pattern_store`
+/// * `quantization` - CLIP_QUANTIZE_SIXTEENTH, _QUARTER, _BAR or _IMMEDIATE
 ///
 /// # Returns
-/// `true` if the effect is enabled, `false` if disabled or if the effect ID is invalid
+/// `GooeyResult::Ok`, or an error for a null engine, an invalid or empty slot,
+/// or an unknown quantization.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_global_effect_enabled(
+pub unsafe extern "C" fn gooey_engine_pattern_launch(
     engine: *mut GooeyEngine,
-    effect: u32,
-) -> bool {
-    if engine.is_null() {
-        return false;
-    }
-
-    let engine = &*engine;
+    slot: u32,
+    quantization: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_pattern_launch";
+    edit(engine, || {
+        if engine.is_null() {
+            return null_engine(FN);
+        }
+        let engine = &mut *engine;
+        let division = if quantization == CLIP_QUANTIZE_IMMEDIATE {
+            1
+        } else if let Some(quantization) = LaunchQuantization::from_id(quantization) {
+            (quantization.beats() * 4.0) as usize
+        } else {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: {quantization} is not a CLIP_QUANTIZE_* constant"),
+            );
+        };
+        let Some(Some(patterns)) = engine.pattern_slots.get(slot as usize) else {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: slot {slot} is out of range or empty"),
+            );
+        };
+        let patterns = patterns.clone();
 
-    match effect {
-        EFFECT_LOWPASS_FILTER => engine.lowpass_filter_enabled,
-        EFFECT_DELAY => engine.delay_enabled,
-        EFFECT_SATURATION => engine.saturation_enabled,
-        EFFECT_COMPRESSOR => engine.compressor_enabled,
-        EFFECT_TILT_FILTER => engine.tilt_filter_enabled,
-        EFFECT_LIMITER => engine.limiter_enabled,
-        EFFECT_REVERB => engine.reverb_enabled,
-        EFFECT_PLATE_REVERB => engine.plate_reverb_enabled,
-        EFFECT_WAVESHAPER => engine.waveshaper_enabled,
-        EFFECT_FEEDBACK_WAVESHAPER => engine.feedback_waveshaper_enabled,
-        _ => false, // Unknown effect
-    }
+        engine.active_pattern = engine.playing_pattern();
+        for (ch, pattern) in patterns.into_iter().enumerate() {
+            let Some(voice) = engine.voice_mut(ch) else {
+                continue;
+            };
+            match pattern {
+                Some(pattern) if quantization == CLIP_QUANTIZE_IMMEDIATE => {
+                    voice.sequencer.cancel_queued_pattern();
+                    voice.sequencer.set_pattern_with_velocity(pattern);
+                }
+                Some(pattern) => voice.sequencer.queue_pattern(pattern, division),
+                None => voice.sequencer.cancel_queued_pattern(),
+            }
+        }
+        engine.queued_pattern = Some(slot);
+        GooeyResult::Ok
+    })
 }
 
-// =============================================================================
-// Compressor sidechain control
-// =============================================================================
-
-/// Set the compressor sidechain source instrument
+/// Cancel a queued pattern launch; the playing patterns carry on.
 ///
-/// When set to an instrument (INSTRUMENT_KICK, INSTRUMENT_SNARE, INSTRUMENT_HIHAT,
-/// INSTRUMENT_TOM), the compressor's envelope follower tracks that instrument's output
-/// while gain reduction is applied to the full mix. This enables techniques like
-/// kick-driven ducking.
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_pattern_cancel(engine: *mut GooeyEngine) {
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return;
+        };
+        engine.active_pattern = engine.playing_pattern();
+        engine.queued_pattern = None;
+        for voice in engine.voices_iter_mut() {
+            voice.sequencer.cancel_queued_pattern();
+        }
+    })
+}
+
+/// Get the pattern slot waiting to launch, for flashing its button.
 ///
-/// Use COMPRESSOR_SIDECHAIN_NONE (0xFFFFFFFF) to use the main mix as input (default).
+/// # Returns
+/// The queued slot, or -1 when nothing is queued (or the launch has landed)
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_compressor_sidechain(
-    engine: *mut GooeyEngine,
-    instrument: u32,
-) {
-    if engine.is_null() {
-        return;
-    }
-
-    let engine = &mut *engine;
-    engine.compressor_sidechain = instrument;
+pub unsafe extern "C" fn gooey_engine_get_queued_pattern(engine: *const GooeyEngine) -> i32 {
+    engine
+        .as_ref()
+        .filter(|engine| !engine.pattern_launch_landed())
+        .and_then(|engine| engine.queued_pattern)
+        .map_or(-1, |slot| slot as i32)
 }
 
-/// Get the current compressor sidechain source
+/// Get the pattern slot that is playing.
 ///
 /// # Returns
-/// An INSTRUMENT_* constant, or COMPRESSOR_SIDECHAIN_NONE if using the main mix
+/// The last launched slot that has taken effect, or -1 if none has
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_compressor_sidechain(engine: *mut GooeyEngine) -> u32 {
-    if engine.is_null() {
-        return COMPRESSOR_SIDECHAIN_NONE;
-    }
+pub unsafe extern "C" fn gooey_engine_get_active_pattern(engine: *const GooeyEngine) -> i32 {
+    engine
+        .as_ref()
+        .and_then(GooeyEngine::playing_pattern)
+        .map_or(-1, |slot| slot as i32)
+}
 
-    let engine = &*engine;
-    engine.compressor_sidechain
+// =============================================================================
+// Utility functions
+// =============================================================================
+
+/// Get the number of kick parameters
+#[no_mangle]
+pub extern "C" fn gooey_engine_kick_param_count() -> u32 {
+    8
+}
+
+/// Get the number of hi-hat parameters
+#[no_mangle]
+pub extern "C" fn gooey_engine_hihat_param_count() -> u32 {
+    12
+}
+
+/// Get the number of sequencer steps in a default pattern (one page; see
+/// `gooey_engine_sequencer_set_page_count` for longer patterns)
+#[no_mangle]
+pub extern "C" fn gooey_engine_sequencer_step_count() -> u32 {
+    16
+}
+
+/// Get the number of available instruments
+#[no_mangle]
+pub extern "C" fn gooey_engine_instrument_count() -> u32 {
+    INSTRUMENT_COUNT
+}
+
+/// Get the number of available global effects
+#[no_mangle]
+pub extern "C" fn gooey_engine_global_effect_count() -> u32 {
+    EFFECT_COUNT
+}
+
+// =============================================================================
+// Effect chain reordering
+// =============================================================================
+
+/// Get the number of reorderable effects in the chain.
+///
+/// The optional limiter is excluded because it is pinned at the end of the
+/// chain when enabled. Only the other effects can be reordered.
+#[no_mangle]
+pub extern "C" fn gooey_engine_reorderable_effect_count() -> u32 {
+    REORDERABLE_EFFECT_COUNT
+}
+
+/// Returns true iff `id` is a valid reorderable effect ID
+/// (any `EFFECT_*` constant except `EFFECT_LIMITER`).
+fn is_reorderable_effect(id: u32) -> bool {
+    matches!(
+        id,
+        EFFECT_WAVESHAPER
+            | EFFECT_LOWPASS_FILTER
+            | EFFECT_DELAY
+            | EFFECT_SATURATION
+            | EFFECT_COMPRESSOR
+            | EFFECT_TILT_FILTER
+            | EFFECT_FEEDBACK_WAVESHAPER
+            | EFFECT_REVERB
+            | EFFECT_PLATE_REVERB
+    )
 }
 
-// =============================================================================
-// Master gain
-// =============================================================================
-
-/// Set the master output gain.
-///
-/// This gain is applied to the complete instrument sum before global effects.
-/// It is smoothed over 30ms to prevent clicks.
+/// Set the full effect-chain order in one call.
 ///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `gain` - Linear gain, clamped to 0.0-2.0. The default is 0.25.
+/// `ids` must point to exactly `REORDERABLE_EFFECT_COUNT` u32 values, each a
+/// distinct reorderable effect ID (any of `EFFECT_LOWPASS_FILTER`,
+/// `EFFECT_DELAY`, `EFFECT_SATURATION`, `EFFECT_COMPRESSOR`,
+/// `EFFECT_TILT_FILTER`, `EFFECT_REVERB`, `EFFECT_PLATE_REVERB`,
+/// `EFFECT_WAVESHAPER`, `EFFECT_FEEDBACK_WAVESHAPER`). `EFFECT_LIMITER` is
+/// pinned at the end of the chain and must not appear in `ids`.
 ///
-/// # Safety
-/// `engine` must be a valid pointer returned by `gooey_engine_new`
-#[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_master_gain(engine: *mut GooeyEngine, gain: f32) {
-    if engine.is_null() || !gain.is_finite() {
-        return;
-    }
-    (*engine).master_gain.set_target(gain);
-}
-
-/// Get the current master output gain target.
+/// On success, the chain order is replaced and the internal state of every
+/// reorderable effect is reset (delay buffer, reverb tail, compressor envelope,
+/// filter poles) so a stale routing does not bleed into the new one.
 ///
-/// # Returns
-/// The current linear gain target, or 0.25 if `engine` is null.
+/// Returns `true` on success. Returns `false` (leaving the chain unchanged) if
+/// `len != REORDERABLE_EFFECT_COUNT`, any ID is not reorderable, or any ID
+/// appears more than once.
 ///
 /// # Safety
-/// `engine` must be a valid pointer returned by `gooey_engine_new`
+/// `engine` must be a valid pointer returned by `gooey_engine_new`. `ids` must
+/// point to at least `len` u32 values. Caller must ensure the engine is not
+/// concurrently mutated from another thread.
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_master_gain(engine: *const GooeyEngine) -> f32 {
-    if engine.is_null() {
-        return DEFAULT_MASTER_GAIN;
-    }
-    (*engine).master_gain.target()
-}
+pub unsafe extern "C" fn gooey_engine_set_effect_order(
+    engine: *mut GooeyEngine,
+    ids: *const u32,
+    len: u32,
+) -> bool {
+    edit(engine, || {
+        if engine.is_null() || ids.is_null() {
+            return false;
+        }
+        if len != REORDERABLE_EFFECT_COUNT {
+            return false;
+        }
 
-// =============================================================================
-// BPM control
-// =============================================================================
+        let slice = std::slice::from_raw_parts(ids, len as usize);
+        let mut new_order = [0u32; REORDERABLE_EFFECT_COUNT as usize];
+        for (i, &id) in slice.iter().enumerate() {
+            if !is_reorderable_effect(id) {
+                return false;
+            }
+            if slice[..i].contains(&id) {
+                return false;
+            }
+            new_order[i] = id;
+        }
 
-/// Set the global BPM (beats per minute)
+        let engine = &mut *engine;
+        engine.effect_order = new_order;
+        engine.reset_effect_states();
+        true
+    })
+}
+
+/// Move a single effect to `new_position` (0-indexed within the reorderable
+/// section of the chain). Other effects shift to fill the gap, preserving
+/// their relative order.
 ///
-/// This affects all sequencer timing (kick, snare, hihat, tom).
+/// On success, internal state of every reorderable effect is reset (see
+/// `gooey_engine_set_effect_order` for rationale).
 ///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `bpm` - Beats per minute (typically 60-200)
+/// Returns `false` (leaving the chain unchanged) if `effect_id` is not
+/// reorderable (e.g. `EFFECT_LIMITER`) or `new_position >=
+/// REORDERABLE_EFFECT_COUNT`.
 ///
 /// # Safety
-/// `engine` must be a valid pointer returned by `gooey_engine_new`
+/// `engine` must be a valid pointer returned by `gooey_engine_new`. Caller
+/// must ensure the engine is not concurrently mutated from another thread.
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_bpm(engine: *mut GooeyEngine, bpm: f32) {
-    if engine.is_null() {
-        return;
-    }
-
-    let engine = &mut *engine;
-    engine.bpm = bpm;
-    for seq in engine.sequencers_iter_mut() {
-        seq.set_bpm(bpm);
-    }
-    for rack in engine.samplers.iter_mut().flatten() {
-        rack.sequencer_mut().set_bpm(bpm);
-    }
-
-    // Update delay BPM for clocked timing
-    engine.delay.set_bpm(bpm);
-
-    // Update LFO BPM values for BPM-synced LFOs
-    for lfo in &mut engine.lfos {
-        lfo.set_bpm(bpm);
-    }
+pub unsafe extern "C" fn gooey_engine_move_effect(
+    engine: *mut GooeyEngine,
+    effect_id: u32,
+    new_position: u32,
+) -> bool {
+    edit(engine, || {
+        if engine.is_null() {
+            return false;
+        }
+        if !is_reorderable_effect(effect_id) {
+            return false;
+        }
+        if new_position >= REORDERABLE_EFFECT_COUNT {
+            return false;
+        }
 
-    // Seed BPM for any future note-synced per-channel loop effects.
-    engine.mixer.set_bpm(bpm);
+        let engine = &mut *engine;
+        let Some(current_pos) = engine.effect_order.iter().position(|&id| id == effect_id) else {
+            return false;
+        };
+        let new_pos = new_position as usize;
+        if current_pos == new_pos {
+            return true;
+        }
 
-    // Propagate BPM to note-synced effects in per-track racks.
-    engine.graph.set_bpm(bpm);
+        if new_pos > current_pos {
+            // Shift left: elements (current_pos+1 ..= new_pos) move down by one.
+            for i in current_pos..new_pos {
+                engine.effect_order[i] = engine.effect_order[i + 1];
+            }
+        } else {
+            // Shift right: elements (new_pos .. current_pos) move up by one.
+            for i in (new_pos..current_pos).rev() {
+                engine.effect_order[i + 1] = engine.effect_order[i];
+            }
+        }
+        engine.effect_order[new_pos] = effect_id;
+        engine.reset_effect_states();
+        true
+    })
 }
 
-/// Get the current BPM.
+/// Read the current effect-chain order. Writes up to `max_len` IDs into
+/// `out_ids` in chain order (position 0 first). The optional limiter is pinned
+/// last when enabled and is not written.
 ///
-/// # Returns
-/// The current beats per minute, or 120.0 if `engine` is null.
+/// Returns the number of IDs written, which equals `REORDERABLE_EFFECT_COUNT`
+/// when `max_len` is large enough, or `max_len` otherwise. Returns `0` if
+/// `engine` or `out_ids` is null.
 ///
 /// # Safety
-/// `engine` must be a valid pointer returned by `gooey_engine_new`
+/// `engine` must be a valid pointer returned by `gooey_engine_new`. `out_ids`
+/// must point to a buffer of at least `max_len` u32 values.
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_bpm(engine: *const GooeyEngine) -> f32 {
-    if engine.is_null() {
-        return 120.0;
+pub unsafe extern "C" fn gooey_engine_get_effect_order(
+    engine: *const GooeyEngine,
+    out_ids: *mut u32,
+    max_len: u32,
+) -> u32 {
+    if engine.is_null() || out_ids.is_null() || max_len == 0 {
+        return 0;
     }
     let engine = &*engine;
-    engine.bpm
+    let n = (max_len as usize).min(engine.effect_order.len());
+    let dst = std::slice::from_raw_parts_mut(out_ids, n);
+    dst.copy_from_slice(&engine.effect_order[..n]);
+    n as u32
 }
 
-/// Mark the engine as being driven by an external tempo source (e.g. Ableton Link).
-///
-/// When enabled, the host should route local BPM changes through the external
-/// sync source instead of calling `gooey_engine_set_bpm` directly, to avoid
-/// feedback loops.
-///
-/// # Safety
-/// `engine` must be a valid pointer returned by `gooey_engine_new`
+// =============================================================================
+// LFO control
+// =============================================================================
+
+/// Get the number of LFOs in the pool
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_link_enabled(engine: *mut GooeyEngine, enabled: bool) {
-    if engine.is_null() {
-        return;
-    }
-    let engine = &*engine;
-    engine.link_enabled.store(enabled, Ordering::Release);
+pub extern "C" fn gooey_engine_lfo_count() -> u32 {
+    LFO_COUNT as u32
 }
 
-/// Check whether the engine is being driven by an external tempo source.
+/// Get the number of LFO timing options
+#[no_mangle]
+pub extern "C" fn gooey_engine_lfo_timing_count() -> u32 {
+    8 // FourBars, TwoBars, OneBar, Half, Quarter, Eighth, Sixteenth, ThirtySecond
+}
+
+/// Enable or disable an LFO
 ///
-/// # Returns
-/// `true` if an external sync source (e.g. Ableton Link) is active.
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `lfo_index` - LFO index (0-7)
+/// * `enabled` - Whether the LFO should be active
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_is_link_enabled(engine: *const GooeyEngine) -> bool {
-    if engine.is_null() {
-        return false;
-    }
-    let engine = &*engine;
-    engine.link_enabled.load(Ordering::Acquire)
+pub unsafe extern "C" fn gooey_engine_set_lfo_enabled(
+    engine: *mut GooeyEngine,
+    lfo_index: u32,
+    enabled: bool,
+) {
+    edit(engine, || {
+        if engine.is_null() || lfo_index as usize >= LFO_COUNT {
+            return;
+        }
+        let engine = &mut *engine;
+        engine.lfo_enabled[lfo_index as usize].store(enabled, Ordering::Relaxed);
+    })
 }
 
-/// Get the current sequencer beat position in quarter notes.
-///
-/// Uses the kick sequencer as reference (all sequencers are synchronized).
-/// Returns the fractional beat position, e.g. 0.0 = bar start, 1.25 = one
-/// beat and one 16th note in.  The fraction within the current step is
-/// derived from the actual swing-aware step boundaries, so the value
-/// progresses monotonically even when swing shifts step durations.
+/// Check if an LFO is enabled
 ///
-/// This is useful for drift detection when syncing to an external clock
-/// (e.g. Ableton Link): compare the returned value with the external
-/// beat position and call `gooey_engine_sequencer_set_beat_position` if
-/// the drift exceeds a threshold.
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `lfo_index` - LFO index (0-7)
 ///
 /// # Returns
-/// Current position in quarter notes, or 0.0 if the engine pointer is null.
+/// `true` if the LFO is enabled, `false` otherwise
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_get_beat_position(
+pub unsafe extern "C" fn gooey_engine_get_lfo_enabled(
     engine: *const GooeyEngine,
-) -> f64 {
-    if engine.is_null() {
-        return 0.0;
+    lfo_index: u32,
+) -> bool {
+    if engine.is_null() || lfo_index as usize >= LFO_COUNT {
+        return false;
     }
     let engine = &*engine;
-    let Some(seq) = engine.reference_sequencer() else {
-        return 0.0;
-    };
-
-    let step = seq.current_step() as f64;
-
-    // Interpolate within the current step using swing-aware boundaries.
-    // step_start_sample and next_trigger_sample define the true duration
-    // of this step (which differs from samples_per_step when swing != 0.5).
-    let step_start = seq.step_start_sample();
-    let step_end = seq.next_trigger_sample();
-    let step_duration = step_end.saturating_sub(step_start);
-    let frac = if step_duration > 0 {
-        let elapsed = seq.sample_count().saturating_sub(step_start);
-        (elapsed as f64 / step_duration as f64).clamp(0.0, 1.0)
-    } else {
-        0.0
-    };
-
-    // Each step is a 16th note; 4 steps = 1 quarter note
-    (step + frac) / 4.0
+    engine.lfo_enabled[lfo_index as usize].load(Ordering::Relaxed)
 }
 
-/// Set the global swing amount for all sequencers (0.0-1.0, where 0.5 = no swing)
+/// Set the timing (musical division) for an LFO
 ///
-/// # Safety
-/// `engine` must be a valid pointer returned by `gooey_engine_new`
-#[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_swing(engine: *mut GooeyEngine, swing: f32) {
-    if engine.is_null() {
-        return;
-    }
-
-    let engine = &mut *engine;
-    let clamped = swing.clamp(0.0, 1.0);
-    engine.swing = clamped;
-    for seq in engine.sequencers_iter_mut() {
-        seq.set_swing(clamped);
-    }
-}
-
-/// Get the current global swing amount
+/// The LFO will sync to the global BPM using the specified timing.
+/// Phase is preserved when changing timing, allowing smooth transitions.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `lfo_index` - LFO index (0-7)
+/// * `timing` - LFO timing constant (LFO_TIMING_QUARTER, etc.)
+///
+/// # Timing constants
+/// - LFO_TIMING_FOUR_BARS (0): 4 bars / 16 beats
+/// - LFO_TIMING_TWO_BARS (1): 2 bars / 8 beats
+/// - LFO_TIMING_ONE_BAR (2): 1 bar / 4 beats
+/// - LFO_TIMING_HALF (3): Half note / 2 beats
+/// - LFO_TIMING_QUARTER (4): Quarter note / 1 beat
+/// - LFO_TIMING_EIGHTH (5): Eighth note / 1/2 beat
+/// - LFO_TIMING_SIXTEENTH (6): Sixteenth note / 1/4 beat
+/// - LFO_TIMING_THIRTY_SECOND (7): Thirty-second note / 1/8 beat
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_swing(engine: *mut GooeyEngine) -> f32 {
-    if engine.is_null() {
-        return 0.5;
-    }
+pub unsafe extern "C" fn gooey_engine_set_lfo_timing(
+    engine: *mut GooeyEngine,
+    lfo_index: u32,
+    timing: u32,
+) {
+    edit(engine, || {
+        if engine.is_null() || lfo_index as usize >= LFO_COUNT {
+            return;
+        }
+        let engine = &mut *engine;
 
-    let engine = &*engine;
-    engine.swing
+        if let Some(division) = MusicalDivision::from_timing_constant(timing) {
+            engine.lfos[lfo_index as usize].set_sync_mode(division);
+        }
+    })
 }
 
-// =============================================================================
-// Sequencer control (all instruments)
-// =============================================================================
-
-/// Start all sequencers.
+/// Get the current timing for an LFO
 ///
-/// Cancels any pending `gooey_engine_sequencer_start_at_host_time` arm.
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `lfo_index` - LFO index (0-7)
+///
+/// # Returns
+/// The current timing constant, or LFO_INVALID if invalid
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_start(engine: *mut GooeyEngine) {
-    if engine.is_null() {
-        return;
+pub unsafe extern "C" fn gooey_engine_get_lfo_timing(
+    engine: *const GooeyEngine,
+    lfo_index: u32,
+) -> u32 {
+    if engine.is_null() || lfo_index as usize >= LFO_COUNT {
+        return LFO_INVALID;
     }
+    let engine = &*engine;
 
-    let engine = &mut *engine;
-    engine.pending_arm_host_time = None;
-    for seq in engine.sequencers_iter_mut() {
-        seq.start();
+    match engine.lfos[lfo_index as usize].sync_mode() {
+        crate::engine::lfo::LfoSyncMode::BpmSync(division) => match division {
+            MusicalDivision::FourBars => LFO_TIMING_FOUR_BARS,
+            MusicalDivision::TwoBars => LFO_TIMING_TWO_BARS,
+            MusicalDivision::OneBar => LFO_TIMING_ONE_BAR,
+            MusicalDivision::Half => LFO_TIMING_HALF,
+            MusicalDivision::Quarter => LFO_TIMING_QUARTER,
+            MusicalDivision::Eighth => LFO_TIMING_EIGHTH,
+            MusicalDivision::Sixteenth => LFO_TIMING_SIXTEENTH,
+            MusicalDivision::ThirtySecond => LFO_TIMING_THIRTY_SECOND,
+        },
+        crate::engine::lfo::LfoSyncMode::Hz(_) => LFO_INVALID, // Hz mode, not BPM synced
     }
-    engine.mixer.transport_start();
 }
 
-/// Stop all sequencers.
+/// Set the global modulation amount for an LFO
 ///
-/// Cancels any pending `gooey_engine_sequencer_start_at_host_time` arm.
+/// This scales the LFO's sine wave amplitude before it's distributed to routes.
+/// Final modulation = (offset + sine * amount) * route_depth
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `lfo_index` - LFO index (0-7)
+/// * `amount` - Global amplitude scale (0.0 to 1.0, default 1.0)
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_stop(engine: *mut GooeyEngine) {
-    if engine.is_null() {
-        return;
-    }
-
-    let engine = &mut *engine;
-    engine.pending_arm_host_time = None;
-    for seq in engine.sequencers_iter_mut() {
-        seq.stop();
-    }
-    for rack in engine.samplers.iter_mut().flatten() {
-        rack.transport_stop();
-    }
-    engine.mixer.transport_stop();
+pub unsafe extern "C" fn gooey_engine_set_lfo_amount(
+    engine: *mut GooeyEngine,
+    lfo_index: u32,
+    amount: f32,
+) {
+    edit(engine, || {
+        if engine.is_null() || lfo_index as usize >= LFO_COUNT {
+            return;
+        }
+        let engine = &mut *engine;
+        engine.lfos[lfo_index as usize].amount = amount;
+    })
 }
 
-/// Reset all sequencers to step 0.
+/// Get the global modulation amount for an LFO
 ///
-/// Cancels any pending `gooey_engine_sequencer_start_at_host_time` arm.
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `lfo_index` - LFO index (0-7)
+///
+/// # Returns
+/// The current global amplitude scale, or 0.0 if invalid
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_reset(engine: *mut GooeyEngine) {
-    if engine.is_null() {
-        return;
-    }
-
-    let engine = &mut *engine;
-    engine.pending_arm_host_time = None;
-    for seq in engine.sequencers_iter_mut() {
-        seq.reset();
-    }
-    for rack in engine.samplers.iter_mut().flatten() {
-        rack.transport_reset();
+pub unsafe extern "C" fn gooey_engine_get_lfo_amount(
+    engine: *const GooeyEngine,
+    lfo_index: u32,
+) -> f32 {
+    if engine.is_null() || lfo_index as usize >= LFO_COUNT {
+        return 0.0;
     }
-    engine.mixer.transport_reset();
+    let engine = &*engine;
+    engine.lfos[lfo_index as usize].amount
 }
 
-/// Set all sequencers to a specific beat position in quarter notes.
-///
-/// Silently teleports the cursor — no step is fired by this call, including
-/// the step that the new position lands on. The step at the target position
-/// fires when the cursor next crosses its boundary on the regular tick path.
-/// Suitable for external phase locking (e.g. Ableton Link drift correction)
-/// and for AUv3 host transport sync.
+/// Set the center offset (DC bias) for an LFO
 ///
-/// Each step is a 16th note (4 steps per quarter-note beat).
+/// This adds a constant value to the LFO output before distribution to routes.
+/// Final modulation = (offset + sine * amount) * route_depth
 ///
-/// Cancels any pending `gooey_engine_sequencer_start_at_host_time` arm. Call
-/// this before `sequencer_start()` when the host resumes transport.
+/// Use offset to bias the modulation (e.g., offset=0.5 with amount=0.5 gives 0.0-1.0 range)
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `beat_position` - Position in quarter notes (e.g. 0.0 = bar start, 1.0 = second beat)
+/// * `lfo_index` - LFO index (0-7)
+/// * `offset` - DC bias (-1.0 to 1.0, default 0.0 for centered bipolar modulation)
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_set_beat_position(
+pub unsafe extern "C" fn gooey_engine_set_lfo_offset(
     engine: *mut GooeyEngine,
-    beat_position: f64,
+    lfo_index: u32,
+    offset: f32,
 ) {
-    if engine.is_null() {
-        return;
-    }
-
-    let engine = &mut *engine;
-    engine.pending_arm_host_time = None;
-    for seq in engine.sequencers_iter_mut() {
-        seq.set_beat_position(beat_position);
-    }
-    engine.mixer.transport_seek(beat_position);
+    edit(engine, || {
+        if engine.is_null() || lfo_index as usize >= LFO_COUNT {
+            return;
+        }
+        let engine = &mut *engine;
+        engine.lfos[lfo_index as usize].offset = offset;
+    })
 }
 
-/// Tell the engine the host time corresponding to sample 0 of the next
-/// render call, plus the host-tick-to-sample conversion factor. The engine
-/// uses this to evaluate any pending
-/// `gooey_engine_sequencer_start_at_host_time` arm.
-///
-/// Call this once per buffer from the audio callback, immediately before
-/// `gooey_engine_render`. Only required while a host-time arm is pending;
-/// calling it otherwise is a cheap no-op that simply updates the host-clock
-/// reference.
+/// Get the center offset (DC bias) for an LFO
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `host_time_first_sample` - mach_absolute_time of the first sample to be
-///   rendered.
-/// * `host_ticks_per_sample` - Host-clock ticks per audio sample
-///   (e.g. host_ticks_per_second / sample_rate). Must be > 0 to take effect.
+/// * `lfo_index` - LFO index (0-7)
+///
+/// # Returns
+/// The current DC bias, or 0.0 if invalid
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_render_host_time(
-    engine: *mut GooeyEngine,
-    host_time_first_sample: u64,
-    host_ticks_per_sample: f64,
-) {
-    if engine.is_null() || !host_ticks_per_sample.is_finite() || host_ticks_per_sample <= 0.0 {
-        return;
+pub unsafe extern "C" fn gooey_engine_get_lfo_offset(
+    engine: *const GooeyEngine,
+    lfo_index: u32,
+) -> f32 {
+    if engine.is_null() || lfo_index as usize >= LFO_COUNT {
+        return 0.0;
     }
-    let engine = &mut *engine;
-    engine.host_clock_anchor = Some(HostClockAnchor {
-        host_time_first_sample,
-        host_ticks_per_sample,
-    });
+    let engine = &*engine;
+    engine.lfos[lfo_index as usize].offset
 }
 
-/// Arm all sequencers to start playback at `start_host_time` (in
-/// mach_absolute_time units) with the cursor at `beat_position` at that
-/// instant. Until the host clock reaches `start_host_time`, render produces
-/// silence and emits no step events. From `start_host_time` onward the
-/// cursor advances normally starting at `beat_position`.
+/// Add a route from an LFO to an instrument parameter
 ///
-/// Requires the audio callback to call `gooey_engine_set_render_host_time`
-/// before each `gooey_engine_render` while armed. Without a host clock
-/// reference the arm fires immediately (fail-safe behaviour).
+/// Each LFO can have multiple routes to different parameters.
+/// Final modulation applied to target = (offset + sine * amount) * depth
 ///
-/// Safe to call from the main thread; takes effect on the next render call.
-/// If `start_host_time` has already been crossed by the time render reaches
-/// it, behaves like an immediate start at `beat_position`. Subsequent calls
-/// to `sequencer_set_beat_position`, `sequencer_start`, `sequencer_stop`, or
-/// `sequencer_reset` cancel the pending arm.
+/// A route can also target another LFO's rate or amount (instrument
+/// `LFO_TARGET_LFO_BASE + n`, param `LFO_PARAM_RATE` or `LFO_PARAM_AMOUNT`),
+/// so one LFO speeds up, slows down or swells another. Modulation from
+/// several routes into the same LFO parameter adds up. An LFO can't target
+/// itself; longer feedback loops are allowed and run a sample behind.
+///
+/// Routes start in phase with the LFO, not inverted, and bipolar; see
+/// `gooey_engine_set_lfo_route_phase`, `gooey_engine_set_lfo_route_inverted`
+/// and `gooey_engine_set_lfo_route_unipolar`. In one-shot mode a route is
+/// retriggered by its target instrument unless
+/// `gooey_engine_set_lfo_route_trigger_source` picks another channel.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `start_host_time` - Absolute host time at which the sequencer should
-///   start (mach_absolute_time units).
-/// * `beat_position` - Cursor position in quarter notes at the moment the
-///   arm fires (e.g. 0.0 = bar start).
+/// * `lfo_index` - LFO index (0-7)
+/// * `instrument` - Target instrument (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.),
+///   or `LFO_TARGET_LFO_BASE + n` for LFO n
+/// * `param` - Target parameter index (KICK_PARAM_FREQUENCY, LFO_PARAM_RATE, etc.)
+/// * `depth` - Per-route depth (0.0 to 1.0) - scales the LFO output for this target
+///
+/// # Returns
+/// A route ID that can be used to remove this specific route, or LFO_INVALID on error
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_start_at_host_time(
+pub unsafe extern "C" fn gooey_engine_add_lfo_route(
     engine: *mut GooeyEngine,
-    start_host_time: u64,
-    beat_position: f64,
-) {
-    if engine.is_null() {
-        return;
-    }
-    let engine = &mut *engine;
-    // Stage the arm; it is resolved against the host clock at render time.
-    // Stop the underlying sequencers so they emit nothing until the arm fires.
-    engine.pending_arm_host_time = Some(PendingArm {
-        start_host_time,
-        beat_position,
-    });
-    engine.mixer.transport_stop();
-    for seq in engine.sequencers_iter_mut() {
-        seq.cancel_arm();
-        seq.stop();
-    }
+    lfo_index: u32,
+    instrument: u32,
+    param: u32,
+    depth: f32,
+) -> u32 {
+    edit(engine, || {
+        if engine.is_null() || lfo_index as usize >= LFO_COUNT {
+            return LFO_INVALID;
+        }
+        let engine = &mut *engine;
+        let idx = lfo_index as usize;
+
+        // Check if we've hit the max routes limit
+        if engine.lfo_routes[idx].len() >= LFO_MAX_ROUTES {
+            return LFO_INVALID;
+        }
+
+        // An LFO target must be another LFO in the pool, given one of its params
+        if instrument >= LFO_TARGET_LFO_BASE {
+            match lfo_route_target(instrument) {
+                Some(target) if target != idx && param <= LFO_PARAM_AMOUNT => {}
+                _ => return LFO_INVALID,
+            }
+        }
+
+        let route_id = engine.lfo_next_route_id[idx];
+        engine.lfo_next_route_id[idx] = route_id.wrapping_add(1);
+
+        engine.lfo_routes[idx].push(LfoRoute {
+            id: route_id,
+            instrument,
+            param,
+            depth,
+            phase: 0.0,
+            inverted: false,
+            unipolar: false,
+            trigger_source: instrument,
+            cycle: 1.0,
+        });
+        engine.update_lfo_order();
+
+        route_id
+    })
 }
 
-/// Set a sequencer step on or off for the kick drum (legacy, prefer per-instrument functions)
+/// Remove a specific route from an LFO by route ID
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `step` - Step index (0-15 for a 16-step sequencer)
-/// * `enabled` - Whether the step should trigger
+/// * `lfo_index` - LFO index (0-7)
+/// * `route_id` - The route ID returned by `gooey_engine_add_lfo_route`
+///
+/// # Returns
+/// `true` if the route was found and removed, `false` otherwise
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_set_step(
+pub unsafe extern "C" fn gooey_engine_remove_lfo_route(
     engine: *mut GooeyEngine,
-    step: u32,
-    enabled: bool,
-) {
-    if engine.is_null() {
-        return;
-    }
+    lfo_index: u32,
+    route_id: u32,
+) -> bool {
+    edit(engine, || {
+        if engine.is_null() || lfo_index as usize >= LFO_COUNT {
+            return false;
+        }
+        let engine = &mut *engine;
+        let idx = lfo_index as usize;
 
-    let engine = &mut *engine;
-    if let Some(seq) = engine.sequencer_for_instrument(INSTRUMENT_KICK) {
-        seq.set_step(step as usize, enabled);
-    }
+        if let Some(pos) = engine.lfo_routes[idx].iter().position(|r| r.id == route_id) {
+            engine.lfo_routes[idx].remove(pos);
+            engine.update_lfo_order();
+            true
+        } else {
+            false
+        }
+    })
 }
 
-/// Get the current sequencer step (uses kick sequencer, all are synchronized)
+/// Clear all routes for an LFO
 ///
-/// # Returns
-/// The current step index (0-15), or -1 if the sequencer is not running
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `lfo_index` - LFO index (0-7)
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_get_current_step(engine: *mut GooeyEngine) -> i32 {
-    if engine.is_null() {
-        return -1;
-    }
-
-    let engine = &*engine;
-    match engine.reference_sequencer() {
-        Some(seq) if seq.is_running() => seq.current_step() as i32,
-        _ => -1,
-    }
+pub unsafe extern "C" fn gooey_engine_clear_lfo_routes(engine: *mut GooeyEngine, lfo_index: u32) {
+    edit(engine, || {
+        if engine.is_null() || lfo_index as usize >= LFO_COUNT {
+            return;
+        }
+        let engine = &mut *engine;
+        engine.lfo_routes[lfo_index as usize].clear();
+        engine.update_lfo_order();
+    })
 }
 
-/// Get the sequencer step that will be playing after a lookahead period
-///
-/// This compensates for audio buffer latency by looking ahead.
-/// Use this for UI display to sync visuals with audio output.
-/// Uses first sequencer as reference (all sequencers are synchronized).
+/// Get the number of routes for an LFO
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `lookahead_samples` - Number of samples to look ahead (typically audio buffer size)
+/// * `lfo_index` - LFO index (0-7)
 ///
 /// # Returns
-/// The step index that will be playing after the lookahead, or -1 if not running
+/// The number of active routes, or 0 if invalid
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_get_step_with_lookahead(
-    engine: *mut GooeyEngine,
-    lookahead_samples: u32,
-) -> i32 {
-    if engine.is_null() {
-        return -1;
+pub unsafe extern "C" fn gooey_engine_get_lfo_route_count(
+    engine: *const GooeyEngine,
+    lfo_index: u32,
+) -> u32 {
+    if engine.is_null() || lfo_index as usize >= LFO_COUNT {
+        return 0;
     }
-
     let engine = &*engine;
-    match engine.reference_sequencer() {
-        Some(seq) if seq.is_running() => seq.step_at_lookahead(lookahead_samples as u64) as i32,
-        _ => -1,
-    }
+    engine.lfo_routes[lfo_index as usize].len() as u32
 }
 
-// =============================================================================
-// Per-instrument sequencer control
-// =============================================================================
-
-/// Helper to get a mutable reference to an instrument's sequencer
 impl GooeyEngine {
-    fn sequencer_for_instrument(&mut self, instrument: u32) -> Option<&mut Sequencer> {
-        self.voice_mut(instrument as usize)
-            .map(|v| &mut v.sequencer)
-    }
-
-    fn sequencer_for_instrument_ref(&self, instrument: u32) -> Option<&Sequencer> {
-        self.voice(instrument as usize).map(|v| &v.sequencer)
-    }
-
-    /// Iterate all voice sequencers in index order (kit drums then bass).
-    fn sequencers_iter_mut(&mut self) -> impl Iterator<Item = &mut Sequencer> {
-        self.kit
-            .voices
+    fn lfo_route_mut(&mut self, lfo_index: u32, route_id: u32) -> Option<&mut LfoRoute> {
+        self.lfo_routes
+            .get_mut(lfo_index as usize)?
             .iter_mut()
-            .map(|v| &mut v.sequencer)
-            .chain(std::iter::once(&mut self.bass.sequencer))
-            .chain(
-                self.samplers
-                    .iter_mut()
-                    .filter_map(|rack| rack.as_mut().map(SamplerRack::sequencer_mut)),
-            )
-    }
-
-    /// Borrow the reference sequencer (voice 0 / kick). All sequencers are kept
-    /// sample-synchronized, so any voice can serve as the position reference.
-    fn reference_sequencer(&self) -> Option<&Sequencer> {
-        self.voice(0).map(|v| &v.sequencer)
-    }
-
-    /// Fractional beat position (quarter notes) from the reference sequencer.
-    /// Same math as `gooey_engine_sequencer_get_beat_position`.
-    fn compute_beat_position(&self) -> f64 {
-        let Some(seq) = self.reference_sequencer() else {
-            return 0.0;
-        };
-        let step = seq.current_step() as f64;
-        let step_start = seq.step_start_sample();
-        let step_end = seq.next_trigger_sample();
-        let step_duration = step_end.saturating_sub(step_start);
-        let frac = if step_duration > 0 {
-            let elapsed = seq.sample_count().saturating_sub(step_start);
-            (elapsed as f64 / step_duration as f64).clamp(0.0, 1.0)
-        } else {
-            0.0
-        };
-        (step + frac) / 4.0
-    }
-
-    /// Apply a clip player action to the poly synth without recording.
-    fn apply_performance_action(&mut self, action: PlayerAction) {
-        self.performance.set_applying_playback(true);
-        match action {
-            PlayerAction::Trigger(event) => {
-                self.trigger_poly_chord_from_event(event);
-            }
-            PlayerAction::Release => {
-                self.poly_synth.release_all();
-            }
-        }
-        self.performance.set_applying_playback(false);
+            .find(|route| route.id == route_id)
     }
 
-    /// Build and trigger a chord from a recorded pad-parameter event.
-    fn trigger_poly_chord_from_event(&mut self, event: ChordClipEvent) {
-        let root_note = root_from_id(event.root);
-        let scale = scale_from_id(event.scale_type);
-        let key = Key::new(root_note, scale);
-        let voicing_type = voicing_from_id(event.voicing);
-        let octave = event.octave.clamp(0, 8) as i8;
-        let velocity = event.velocity.clamp(0.0, 1.0);
-
-        // Smoothed targets only — avoid snap_params on every clip replay hit.
-        self.poly_synth.set_config(preset_config(event.preset));
-
-        let chords = key.diatonic_sevenths();
-        let degree = event.degree as usize % chords.len().max(1);
-        let chord = &chords[degree];
-        let midi_notes = apply_voicing(chord, voicing_type, octave);
-
-        self.poly_synth.release_all();
-        for note in &midi_notes {
-            self.poly_synth.trigger_note(*note, velocity);
-        }
+    fn lfo_route(&self, lfo_index: u32, route_id: u32) -> Option<&LfoRoute> {
+        self.lfo_routes
+            .get(lfo_index as usize)?
+            .iter()
+            .find(|route| route.id == route_id)
     }
 }
 
-/// Set a sequencer step on or off for a specific instrument
+/// Set a route's phase offset from its LFO
+///
+/// Each route reads the LFO shifted by its own phase, so routes of one LFO
+/// can move out of step (90° for a quadrature pair, 180° for opposite
+/// motion).
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
-/// * `step` - Step index (0-15 for a 16-step sequencer)
-/// * `enabled` - Whether the step should trigger
+/// * `lfo_index` - LFO index (0-7)
+/// * `route_id` - The route ID returned by `gooey_engine_add_lfo_route`
+/// * `degrees` - Phase offset in degrees (wrapped to 0-360)
+///
+/// # Returns
+/// `true` if the route was found and the phase is finite, `false` otherwise
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_step(
+pub unsafe extern "C" fn gooey_engine_set_lfo_route_phase(
     engine: *mut GooeyEngine,
-    instrument: u32,
-    step: u32,
-    enabled: bool,
-) {
-    if engine.is_null() {
-        return;
-    }
-
-    let engine = &mut *engine;
-    if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
-        sequencer.set_step(step as usize, enabled);
-    }
+    lfo_index: u32,
+    route_id: u32,
+    degrees: f32,
+) -> bool {
+    edit(engine, || {
+        if !degrees.is_finite() {
+            return false;
+        }
+        let Some(route) = engine
+            .as_mut()
+            .and_then(|engine| engine.lfo_route_mut(lfo_index, route_id))
+        else {
+            return false;
+        };
+        route.phase = (degrees / 360.0).rem_euclid(1.0);
+        true
+    })
 }
 
-/// Set the velocity for a specific step in an instrument's sequencer
+/// Get a route's phase offset from its LFO
 ///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
-/// * `step` - Step index (0-15 for a 16-step sequencer)
-/// * `velocity` - Velocity from 0.0 to 1.0
+/// # Returns
+/// The phase offset in degrees (0-360), or -1.0 if the route is invalid
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_step_velocity(
-    engine: *mut GooeyEngine,
-    instrument: u32,
-    step: u32,
-    velocity: f32,
-) {
-    if engine.is_null() {
-        return;
-    }
-
-    let engine = &mut *engine;
-    if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
-        sequencer.set_step_velocity(step as usize, velocity);
-    }
+pub unsafe extern "C" fn gooey_engine_get_lfo_route_phase(
+    engine: *const GooeyEngine,
+    lfo_index: u32,
+    route_id: u32,
+) -> f32 {
+    engine
+        .as_ref()
+        .and_then(|engine| engine.lfo_route(lfo_index, route_id))
+        .map_or(-1.0, |route| route.phase * 360.0)
 }
 
-/// Set both enabled state and velocity for a sequencer step
+/// Invert a route's polarity
 ///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
-/// * `step` - Step index (0-15 for a 16-step sequencer)
-/// * `enabled` - Whether the step should trigger
-/// * `velocity` - Velocity from 0.0 to 1.0
+/// An inverted route moves the opposite way to the LFO: with one LFO routed
+/// plainly to the kick's filter and inverted to the snare's, one opens as
+/// the other closes.
+///
+/// # Returns
+/// `true` if the route was found, `false` otherwise
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_step_with_velocity(
+pub unsafe extern "C" fn gooey_engine_set_lfo_route_inverted(
     engine: *mut GooeyEngine,
-    instrument: u32,
-    step: u32,
-    enabled: bool,
-    velocity: f32,
-) {
-    if engine.is_null() {
-        return;
-    }
-
-    let engine = &mut *engine;
-    if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
-        sequencer.set_step_with_velocity(step as usize, enabled, velocity);
-    }
+    lfo_index: u32,
+    route_id: u32,
+    inverted: bool,
+) -> bool {
+    edit(engine, || {
+        let Some(route) = engine
+            .as_mut()
+            .and_then(|engine| engine.lfo_route_mut(lfo_index, route_id))
+        else {
+            return false;
+        };
+        route.inverted = inverted;
+        true
+    })
 }
 
-/// Set a step with optional velocity, optional blend setting, and optional MIDI note.
-///
-/// Omitted settings are left unchanged.
-///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
-/// * `step` - Step index (0-15 for a 16-step sequencer)
-/// * `enabled` - Whether the step should trigger
-/// * `set_velocity` - Whether to apply the `velocity` value
-/// * `velocity` - Velocity from 0.0 to 1.0 (used only when `set_velocity` is true)
-/// * `set_blend` - Whether to apply blend X/Y
-/// * `blend_x` - Blend X position (0.0-1.0, used only when `set_blend` is true)
-/// * `blend_y` - Blend Y position (0.0-1.0, used only when `set_blend` is true)
-/// * `set_note` - Whether to apply the `midi_note` value
-/// * `midi_note` - MIDI note number (0-127, or STEP_NOTE_NONE); only applied when `set_note` is true
+/// Check whether a route's polarity is inverted (`false` for an invalid route)
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_step_settings(
-    engine: *mut GooeyEngine,
-    instrument: u32,
-    step: u32,
-    enabled: bool,
-    set_velocity: bool,
-    velocity: f32,
-    set_blend: bool,
-    blend_x: f32,
-    blend_y: f32,
-    set_note: bool,
-    midi_note: u8,
-) {
-    if engine.is_null() {
-        return;
-    }
-
-    let engine = &mut *engine;
-    if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
-        let settings = SequencerStepSettings {
-            velocity: if set_velocity { Some(velocity) } else { None },
-            blend: if set_blend {
-                Some(SequencerBlendSetting::new(blend_x, blend_y))
-            } else {
-                None
-            },
-            note: None,
-        };
-        sequencer.set_step_with_settings(step as usize, enabled, settings);
-        // Handle note separately: set_note=true with STEP_NOTE_NONE clears the note
-        if set_note {
-            let step_idx = step as usize;
-            if midi_note == STEP_NOTE_NONE {
-                sequencer.clear_step_note(step_idx);
-            } else {
-                sequencer.set_step_note(step_idx, midi_note);
-            }
-        }
-    }
+pub unsafe extern "C" fn gooey_engine_get_lfo_route_inverted(
+    engine: *const GooeyEngine,
+    lfo_index: u32,
+    route_id: u32,
+) -> bool {
+    engine
+        .as_ref()
+        .and_then(|engine| engine.lfo_route(lfo_index, route_id))
+        .is_some_and(|route| route.inverted)
 }
 
-/// Set an absolute blend setting for a specific step (0.0-1.0 X/Y)
+/// Switch a route between bipolar and unipolar modulation
 ///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
-/// * `step` - Step index (0-15 for a 16-step sequencer)
-/// * `x` - Blend X position (0.0-1.0)
-/// * `y` - Blend Y position (0.0-1.0)
+/// Bipolar (the default) passes the LFO's -1 to 1 swing, so the target moves
+/// both ways around its set value. Unipolar maps it to 0 to 1 first, so the
+/// target only moves up from its set value (or down, with a negative
+/// effect). Inversion applies before the mapping.
+///
+/// Final modulation = shape(offset + sine(phase + route phase) * amount) * depth
+///
+/// # Returns
+/// `true` if the route was found, `false` otherwise
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_step_blend(
+pub unsafe extern "C" fn gooey_engine_set_lfo_route_unipolar(
     engine: *mut GooeyEngine,
-    instrument: u32,
-    step: u32,
-    x: f32,
-    y: f32,
-) {
-    if engine.is_null() {
-        return;
-    }
-
-    let engine = &mut *engine;
-    if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
-        sequencer.set_step_blend(step as usize, x, y);
-    }
+    lfo_index: u32,
+    route_id: u32,
+    unipolar: bool,
+) -> bool {
+    edit(engine, || {
+        let Some(route) = engine
+            .as_mut()
+            .and_then(|engine| engine.lfo_route_mut(lfo_index, route_id))
+        else {
+            return false;
+        };
+        route.unipolar = unipolar;
+        true
+    })
 }
 
-/// Legacy alias for `gooey_engine_sequencer_set_instrument_step_blend`.
+/// Check whether a route is unipolar (`false` for an invalid route)
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_step_blend_override(
-    engine: *mut GooeyEngine,
-    instrument: u32,
-    step: u32,
-    x: f32,
-    y: f32,
-) {
-    gooey_engine_sequencer_set_instrument_step_blend(engine, instrument, step, x, y);
+pub unsafe extern "C" fn gooey_engine_get_lfo_route_unipolar(
+    engine: *const GooeyEngine,
+    lfo_index: u32,
+    route_id: u32,
+) -> bool {
+    engine
+        .as_ref()
+        .and_then(|engine| engine.lfo_route(lfo_index, route_id))
+        .is_some_and(|route| route.unipolar)
 }
 
-/// Clear the blend setting for a specific step
+/// Switch an LFO between free-running and one-shot mode
+///
+/// A one-shot LFO runs a single cycle each time a route's trigger source
+/// fires, then holds the value it ends on until the next hit, so with a
+/// route phase it works as a retriggerable modulation envelope (a unipolar
+/// route at 270° rises from 0 to the full depth and falls back over the
+/// cycle). Each route keeps its own cycle, so routes of one LFO listening
+/// to different channels play independently. Routes sit at the end of the
+/// cycle until first triggered.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
-/// * `step` - Step index (0-15 for a 16-step sequencer)
+/// * `lfo_index` - LFO index (0-7)
+/// * `one_shot` - `true` for one cycle per trigger, `false` to free-run
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_clear_instrument_step_blend(
+pub unsafe extern "C" fn gooey_engine_set_lfo_one_shot(
     engine: *mut GooeyEngine,
-    instrument: u32,
-    step: u32,
+    lfo_index: u32,
+    one_shot: bool,
 ) {
-    if engine.is_null() {
-        return;
-    }
-
-    let engine = &mut *engine;
-    if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
-        sequencer.clear_step_blend(step as usize);
-    }
+    edit(engine, || {
+        if engine.is_null() || lfo_index as usize >= LFO_COUNT {
+            return;
+        }
+        let engine = &mut *engine;
+        engine.lfos[lfo_index as usize].set_one_shot(one_shot);
+    })
 }
 
-/// Legacy alias for `gooey_engine_sequencer_clear_instrument_step_blend`.
+/// Check whether an LFO is in one-shot mode (`false` if invalid)
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_clear_instrument_step_blend_override(
-    engine: *mut GooeyEngine,
-    instrument: u32,
-    step: u32,
-) {
-    gooey_engine_sequencer_clear_instrument_step_blend(engine, instrument, step);
+pub unsafe extern "C" fn gooey_engine_get_lfo_one_shot(
+    engine: *const GooeyEngine,
+    lfo_index: u32,
+) -> bool {
+    if engine.is_null() || lfo_index as usize >= LFO_COUNT {
+        return false;
+    }
+    let engine = &*engine;
+    engine.lfos[lfo_index as usize].is_one_shot()
 }
 
-/// Set the MIDI note for a specific step in an instrument's sequencer.
+/// Choose which channel's hits restart a route's cycle in one-shot mode
 ///
-/// When a step with a note triggers, the engine sets the instrument's
-/// frequency parameter to the note's frequency at the exact trigger sample.
-/// Steps without a note (STEP_NOTE_NONE) use the instrument's global
-/// frequency parameter as before (backward compatible).
+/// Defaults to the route's target instrument. Pointing it elsewhere lets,
+/// say, every kick hit sweep the bass filter. Manual and sequencer triggers
+/// both count. If a host-created slot used as a source is destroyed, the
+/// route falls back to its target.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_BASS, etc.)
-/// * `step` - Step index (0-15)
-/// * `midi_note` - MIDI note number (0-127), or STEP_NOTE_NONE to clear
+/// * `lfo_index` - LFO index (0-7)
+/// * `route_id` - The route ID returned by `gooey_engine_add_lfo_route`
+/// * `channel` - Source channel (INSTRUMENT_KICK, etc., or a slot channel)
+///
+/// # Returns
+/// `true` if the route was found and the channel is in range, `false` otherwise
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_step_note(
+pub unsafe extern "C" fn gooey_engine_set_lfo_route_trigger_source(
     engine: *mut GooeyEngine,
-    instrument: u32,
-    step: u32,
-    midi_note: u8,
-) {
-    if engine.is_null() {
-        return;
-    }
-    let engine = &mut *engine;
-    if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
-        if midi_note == STEP_NOTE_NONE {
-            sequencer.clear_step_note(step as usize);
-        } else {
-            sequencer.set_step_note(step as usize, midi_note);
+    lfo_index: u32,
+    route_id: u32,
+    channel: u32,
+) -> bool {
+    edit(engine, || {
+        if channel as usize >= NUM_CHANNELS {
+            return false;
         }
-    }
+        let Some(route) = engine
+            .as_mut()
+            .and_then(|engine| engine.lfo_route_mut(lfo_index, route_id))
+        else {
+            return false;
+        };
+        route.trigger_source = channel;
+        true
+    })
 }
 
-/// Get the MIDI note for a specific step.
+/// Get the channel that retriggers a route in one-shot mode
 ///
 /// # Returns
-/// The MIDI note number (0-127), or STEP_NOTE_NONE (255) if no note is set.
+/// The source channel, or LFO_INVALID if the route is invalid
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_step_note(
+pub unsafe extern "C" fn gooey_engine_get_lfo_route_trigger_source(
     engine: *const GooeyEngine,
-    instrument: u32,
-    step: u32,
-) -> u8 {
-    if engine.is_null() {
-        return STEP_NOTE_NONE;
-    }
-    let engine = &*engine;
-    if let Some(sequencer) = engine.sequencer_for_instrument_ref(instrument) {
-        sequencer
-            .get_step_note(step as usize)
-            .unwrap_or(STEP_NOTE_NONE)
-    } else {
-        STEP_NOTE_NONE
-    }
+    lfo_index: u32,
+    route_id: u32,
+) -> u32 {
+    engine
+        .as_ref()
+        .and_then(|engine| engine.lfo_route(lfo_index, route_id))
+        .map_or(LFO_INVALID, |route| route.trigger_source)
 }
 
-/// Clear the MIDI note for a step (reverts to global frequency).
-/// Equivalent to set_step_note(..., STEP_NOTE_NONE).
+/// Reset an LFO's phase to 0
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `lfo_index` - LFO index (0-7)
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_clear_instrument_step_note(
-    engine: *mut GooeyEngine,
-    instrument: u32,
-    step: u32,
-) {
-    if engine.is_null() {
-        return;
-    }
-    let engine = &mut *engine;
-    if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
-        sequencer.clear_step_note(step as usize);
-    }
+pub unsafe extern "C" fn gooey_engine_reset_lfo_phase(engine: *mut GooeyEngine, lfo_index: u32) {
+    edit(engine, || {
+        if engine.is_null() || lfo_index as usize >= LFO_COUNT {
+            return;
+        }
+        let engine = &mut *engine;
+        engine.lfos[lfo_index as usize].reset();
+    })
 }
 
-/// Set MIDI notes for all 16 steps of an instrument's sequencer.
+/// Get an LFO's current phase
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID
-/// * `notes` - Pointer to 16 uint8_t values (MIDI notes, or STEP_NOTE_NONE)
+/// * `lfo_index` - LFO index (0-7)
+///
+/// # Returns
+/// The current phase (0.0 to 1.0), or -1.0 if invalid
 ///
 /// # Safety
-/// - `engine` must be a valid pointer returned by `gooey_engine_new`
-/// - `notes` must point to at least 16 bytes of allocated memory
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_note_pattern(
-    engine: *mut GooeyEngine,
-    instrument: u32,
-    notes: *const u8,
-) {
-    if engine.is_null() || notes.is_null() {
-        return;
-    }
-    let engine = &mut *engine;
-    let notes_slice = slice::from_raw_parts(notes, 16);
-    if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
-        sequencer.set_note_pattern(notes_slice);
+pub unsafe extern "C" fn gooey_engine_get_lfo_phase(
+    engine: *const GooeyEngine,
+    lfo_index: u32,
+) -> f32 {
+    if engine.is_null() || lfo_index as usize >= LFO_COUNT {
+        return -1.0;
     }
+    let engine = &*engine;
+    engine.lfos[lfo_index as usize].phase()
 }
 
-/// Set the entire 16-step pattern for an instrument's sequencer
-///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
-/// * `pattern` - Pointer to 16 bools representing step states
-///
-/// # Safety
-/// - `engine` must be a valid pointer returned by `gooey_engine_new`
-/// - `pattern` must point to at least 16 bools of allocated memory
+/// Get the number of snare parameters
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_set_instrument_pattern(
-    engine: *mut GooeyEngine,
-    instrument: u32,
-    pattern: *const bool,
-) {
-    if engine.is_null() || pattern.is_null() {
-        return;
-    }
+pub extern "C" fn gooey_engine_snare_param_count() -> u32 {
+    22 // frequency, decay, brightness, volume, tonal, noise, pitch_drop,
+       // tonal_decay, noise_decay, noise_tail_decay, filter_cutoff, filter_resonance,
+       // filter_type, xfade, phase_mod_amount, overdrive, amp_decay, amp_decay_curve,
+       // tonal_decay_curve, tuning, noise_color, crack_velvet
+}
 
-    let engine = &mut *engine;
-    let pattern_slice = slice::from_raw_parts(pattern, 16);
-    let pattern_vec: Vec<bool> = pattern_slice.to_vec();
+/// Get the number of tom parameters
+#[no_mangle]
+pub extern "C" fn gooey_engine_tom_param_count() -> u32 {
+    9 // tune, bend, tone, color, decay, membrane, membrane_q, volume, tuning (Tom2)
+}
 
-    if let Some(sequencer) = engine.sequencer_for_instrument(instrument) {
-        sequencer.set_pattern(pattern_vec);
-    }
+/// Get the number of FM snap parameters
+#[no_mangle]
+pub extern "C" fn gooey_engine_fm_snap_param_count() -> u32 {
+    8 // frequency, ratio, index, snap, decay, pitch_drop, volume, tuning
 }
 
-/// Get the current step for an instrument's sequencer
+/// Get the number of rimshot parameters
+#[no_mangle]
+pub extern "C" fn gooey_engine_rimshot_param_count() -> u32 {
+    6 // tune, tone, click, decay, volume, tuning
+}
+
+/// Get the number of cowbell parameters
+#[no_mangle]
+pub extern "C" fn gooey_engine_cowbell_param_count() -> u32 {
+    5 // pitch, tone, decay, volume, tuning
+}
+
+/// Get the number of shaker parameters
+#[no_mangle]
+pub extern "C" fn gooey_engine_shaker_param_count() -> u32 {
+    7 // density, color, attack, decay, accent, volume, tuning
+}
+
+// =============================================================================
+// Instrument mute/solo control
+// =============================================================================
+
+/// Set the mute state for an instrument
+///
+/// When muted, the instrument's audio output is silenced.
+/// Solo takes precedence: if an instrument is both muted and soloed, it will play.
+/// With mute quantization on (`gooey_engine_set_mute_quantization`) the change
+/// waits for the next boundary of the transport grid.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
 /// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
-///
-/// # Returns
-/// The current step index (0-15), or -1 if the sequencer is not running or invalid instrument
+/// * `muted` - Whether the instrument should be muted
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_step(
+pub unsafe extern "C" fn gooey_engine_set_instrument_mute(
     engine: *mut GooeyEngine,
     instrument: u32,
-) -> i32 {
-    if engine.is_null() {
-        return -1;
-    }
-
-    let engine = &*engine;
-    if let Some(sequencer) = engine.sequencer_for_instrument_ref(instrument) {
-        if sequencer.is_running() {
-            return sequencer.current_step() as i32;
+    muted: bool,
+) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
         }
-    }
-    -1
+        (*engine).set_mode(instrument, false, muted);
+    })
 }
 
-/// Get the step that will be playing after a lookahead period for an instrument
-///
-/// This compensates for audio buffer latency by looking ahead.
-/// Use this for UI display to sync visuals with audio output.
+/// Get the mute state for an instrument
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
 /// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
-/// * `lookahead_samples` - Number of samples to look ahead (typically audio buffer size)
 ///
 /// # Returns
-/// The step index that will be playing after the lookahead, or -1 if not running
+/// `true` if the instrument is muted, `false` otherwise (or if invalid
+/// instrument). A quantized change reads back only once it has taken effect;
+/// see `gooey_engine_get_instrument_mute_pending`.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_step_with_lookahead(
-    engine: *mut GooeyEngine,
+pub unsafe extern "C" fn gooey_engine_get_instrument_mute(
+    engine: *const GooeyEngine,
     instrument: u32,
-    lookahead_samples: u32,
-) -> i32 {
+) -> bool {
     if engine.is_null() {
-        return -1;
-    }
-
-    let engine = &*engine;
-    if let Some(sequencer) = engine.sequencer_for_instrument_ref(instrument) {
-        if sequencer.is_running() {
-            return sequencer.step_at_lookahead(lookahead_samples as u64) as i32;
-        }
+        return false;
     }
-    -1
+    (*engine)
+        .voice(instrument as usize)
+        .is_some_and(|v| v.muted.load(Ordering::Acquire))
 }
 
-/// Get the velocity for a specific step in an instrument's sequencer
+/// Set the solo state for an instrument
+///
+/// When any instrument is soloed, only soloed instruments produce audio.
+/// Multiple instruments can be soloed simultaneously.
+/// Solo takes precedence over mute. Quantized like
+/// `gooey_engine_set_instrument_mute`.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
 /// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
-/// * `step` - Step index (0-15 for a 16-step sequencer)
-///
-/// # Returns
-/// The velocity (0.0-1.0), or 0.0 if invalid engine/instrument/step
+/// * `soloed` - Whether the instrument should be soloed
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_step_velocity(
+pub unsafe extern "C" fn gooey_engine_set_instrument_solo(
     engine: *mut GooeyEngine,
     instrument: u32,
-    step: u32,
-) -> f32 {
-    if engine.is_null() {
-        return 0.0;
-    }
-
-    let engine = &*engine;
-    if let Some(sequencer) = engine.sequencer_for_instrument_ref(instrument) {
-        return sequencer.get_step_velocity(step as usize);
-    }
-    0.0
+    soloed: bool,
+) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        (*engine).set_mode(instrument, true, soloed);
+    })
 }
 
-/// Get the blend X setting for a specific step
+/// Get the solo state for an instrument
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
 /// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
-/// * `step` - Step index (0-15 for a 16-step sequencer)
 ///
 /// # Returns
-/// The X position (0.0-1.0), or -1.0 if no blend setting or invalid engine/instrument/step
+/// `true` if the instrument is soloed, `false` otherwise (or if invalid instrument)
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_step_blend_x(
-    engine: *mut GooeyEngine,
+pub unsafe extern "C" fn gooey_engine_get_instrument_solo(
+    engine: *const GooeyEngine,
     instrument: u32,
-    step: u32,
-) -> f32 {
+) -> bool {
     if engine.is_null() {
-        return -1.0;
-    }
-
-    let engine = &*engine;
-    if let Some(sequencer) = engine.sequencer_for_instrument_ref(instrument) {
-        if let Some(blend_setting) = sequencer.get_step_blend(step as usize) {
-            return blend_setting.x;
-        }
+        return false;
     }
-    -1.0
-}
-
-/// Legacy alias for `gooey_engine_sequencer_get_instrument_step_blend_x`.
-#[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_step_blend_override_x(
-    engine: *mut GooeyEngine,
-    instrument: u32,
-    step: u32,
-) -> f32 {
-    gooey_engine_sequencer_get_instrument_step_blend_x(engine, instrument, step)
+    (*engine)
+        .voice(instrument as usize)
+        .is_some_and(|v| v.soloed.load(Ordering::Acquire))
 }
 
-/// Get the blend Y setting for a specific step
+/// Set the grid that mute and solo changes are quantized to.
+///
+/// With a grid selected, `gooey_engine_set_instrument_mute`/`_solo` queue the
+/// change and it lands on the next grid boundary of the shared transport (the
+/// clip-grid clock), so parts drop in and out on the bar. Changes still fade
+/// over the usual 10ms. While the transport is stopped, queued changes apply
+/// at the next render. Switching back to immediate applies anything queued.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
-/// * `step` - Step index (0-15 for a 16-step sequencer)
+/// * `quantization` - CLIP_QUANTIZE_IMMEDIATE (the default), _SIXTEENTH,
+///   _QUARTER or _BAR
 ///
 /// # Returns
-/// The Y position (0.0-1.0), or -1.0 if no blend setting or invalid engine/instrument/step
+/// `GooeyResult::Ok`, or an error for a null engine or an unknown
+/// quantization.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_step_blend_y(
+pub unsafe extern "C" fn gooey_engine_set_mute_quantization(
     engine: *mut GooeyEngine,
-    instrument: u32,
-    step: u32,
-) -> f32 {
-    if engine.is_null() {
-        return -1.0;
-    }
-
-    let engine = &*engine;
-    if let Some(sequencer) = engine.sequencer_for_instrument_ref(instrument) {
-        if let Some(blend_setting) = sequencer.get_step_blend(step as usize) {
-            return blend_setting.y;
+    quantization: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_mute_quantization";
+    edit(engine, || {
+        if engine.is_null() {
+            return null_engine(FN);
         }
-    }
-    -1.0
+        if quantization != CLIP_QUANTIZE_IMMEDIATE
+            && LaunchQuantization::from_id(quantization).is_none()
+        {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: {quantization} is not a CLIP_QUANTIZE_* constant"),
+            );
+        }
+        (*engine)
+            .mute_quantize
+            .store(quantization, Ordering::Relaxed);
+        GooeyResult::Ok
+    })
 }
 
-/// Legacy alias for `gooey_engine_sequencer_get_instrument_step_blend_y`.
+/// Get the grid that mute and solo changes are quantized to.
+///
+/// # Returns
+/// A CLIP_QUANTIZE_* constant (CLIP_QUANTIZE_IMMEDIATE for a null engine)
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_step_blend_override_y(
-    engine: *mut GooeyEngine,
-    instrument: u32,
-    step: u32,
-) -> f32 {
-    gooey_engine_sequencer_get_instrument_step_blend_y(engine, instrument, step)
+pub unsafe extern "C" fn gooey_engine_get_mute_quantization(engine: *const GooeyEngine) -> u32 {
+    engine.as_ref().map_or(CLIP_QUANTIZE_IMMEDIATE, |engine| {
+        engine.mute_quantize.load(Ordering::Relaxed)
+    })
 }
 
-/// Get the enabled state for a specific step in an instrument's sequencer
+/// Whether an instrument has a queued mute change that has not yet landed.
 ///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
-/// * `step` - Step index (0-15 for a 16-step sequencer)
+/// While pending, `gooey_engine_get_instrument_mute` still returns the state
+/// being heard; the pending change flips it. UIs typically blink the button.
 ///
 /// # Returns
-/// Whether the step is enabled (true/false), or false if invalid engine/instrument/step
+/// `true` if a quantized mute change is waiting for its boundary, `false`
+/// otherwise (or if invalid instrument)
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sequencer_get_instrument_step_enabled(
-    engine: *mut GooeyEngine,
+pub unsafe extern "C" fn gooey_engine_get_instrument_mute_pending(
+    engine: *const GooeyEngine,
     instrument: u32,
-    step: u32,
 ) -> bool {
-    if engine.is_null() {
-        return false;
-    }
-
-    let engine = &*engine;
-    if let Some(sequencer) = engine.sequencer_for_instrument_ref(instrument) {
-        return sequencer.get_step_enabled(step as usize);
-    }
-    false
-}
-
-// =============================================================================
-// Utility functions
-// =============================================================================
-
-/// Get the number of kick parameters
-#[no_mangle]
-pub extern "C" fn gooey_engine_kick_param_count() -> u32 {
-    8
-}
-
-/// Get the number of hi-hat parameters
-#[no_mangle]
-pub extern "C" fn gooey_engine_hihat_param_count() -> u32 {
-    6
-}
-
-/// Get the number of sequencer steps
-#[no_mangle]
-pub extern "C" fn gooey_engine_sequencer_step_count() -> u32 {
-    16
-}
-
-/// Get the number of available instruments
-#[no_mangle]
-pub extern "C" fn gooey_engine_instrument_count() -> u32 {
-    INSTRUMENT_COUNT
+    engine
+        .as_ref()
+        .and_then(|engine| engine.voice(instrument as usize))
+        .is_some_and(|v| mode_request_pending(&v.mute_request, &v.muted))
 }
 
-/// Get the number of available global effects
+/// Whether an instrument has a queued solo change that has not yet landed.
+///
+/// # Returns
+/// `true` if a quantized solo change is waiting for its boundary, `false`
+/// otherwise (or if invalid instrument)
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub extern "C" fn gooey_engine_global_effect_count() -> u32 {
-    EFFECT_COUNT
+pub unsafe extern "C" fn gooey_engine_get_instrument_solo_pending(
+    engine: *const GooeyEngine,
+    instrument: u32,
+) -> bool {
+    engine
+        .as_ref()
+        .and_then(|engine| engine.voice(instrument as usize))
+        .is_some_and(|v| mode_request_pending(&v.solo_request, &v.soloed))
 }
 
 // =============================================================================
-// Effect chain reordering
+// Per-instrument channel gain (mixer fader, independent of blend system)
 // =============================================================================
 
-/// Get the number of reorderable effects in the chain.
-///
-/// The optional limiter is excluded because it is pinned at the end of the
-/// chain when enabled. Only the other effects can be reordered.
-#[no_mangle]
-pub extern "C" fn gooey_engine_reorderable_effect_count() -> u32 {
-    REORDERABLE_EFFECT_COUNT
-}
-
-/// Returns true iff `id` is a valid reorderable effect ID
-/// (any `EFFECT_*` constant except `EFFECT_LIMITER`).
-fn is_reorderable_effect(id: u32) -> bool {
-    matches!(
-        id,
-        EFFECT_WAVESHAPER
-            | EFFECT_LOWPASS_FILTER
-            | EFFECT_DELAY
-            | EFFECT_SATURATION
-            | EFFECT_COMPRESSOR
-            | EFFECT_TILT_FILTER
-            | EFFECT_FEEDBACK_WAVESHAPER
-            | EFFECT_REVERB
-            | EFFECT_PLATE_REVERB
-    )
-}
-
-/// Set the full effect-chain order in one call.
-///
-/// `ids` must point to exactly `REORDERABLE_EFFECT_COUNT` u32 values, each a
-/// distinct reorderable effect ID (any of `EFFECT_LOWPASS_FILTER`,
-/// `EFFECT_DELAY`, `EFFECT_SATURATION`, `EFFECT_COMPRESSOR`,
-/// `EFFECT_TILT_FILTER`, `EFFECT_REVERB`, `EFFECT_PLATE_REVERB`,
-/// `EFFECT_WAVESHAPER`, `EFFECT_FEEDBACK_WAVESHAPER`). `EFFECT_LIMITER` is
-/// pinned at the end of the chain and must not appear in `ids`.
-///
-/// On success, the chain order is replaced and the internal state of every
-/// reorderable effect is reset (delay buffer, reverb tail, compressor envelope,
-/// filter poles) so a stale routing does not bleed into the new one.
+/// Set the channel gain for an instrument (0.0–1.0)
 ///
-/// Returns `true` on success. Returns `false` (leaving the chain unchanged) if
-/// `len != REORDERABLE_EFFECT_COUNT`, any ID is not reorderable, or any ID
-/// appears more than once.
+/// This gain is applied after synthesis and blending, so it acts as a mixer
+/// fader that the blend system cannot override. Smoothed over 10ms to prevent
+/// clicks.
 ///
-/// # Safety
-/// `engine` must be a valid pointer returned by `gooey_engine_new`. `ids` must
-/// point to at least `len` u32 values. Caller must ensure the engine is not
-/// concurrently mutated from another thread.
-#[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_effect_order(
-    engine: *mut GooeyEngine,
-    ids: *const u32,
-    len: u32,
-) -> bool {
-    if engine.is_null() || ids.is_null() {
-        return false;
-    }
-    if len != REORDERABLE_EFFECT_COUNT {
-        return false;
-    }
-
-    let slice = std::slice::from_raw_parts(ids, len as usize);
-    let mut new_order = [0u32; REORDERABLE_EFFECT_COUNT as usize];
-    for (i, &id) in slice.iter().enumerate() {
-        if !is_reorderable_effect(id) {
-            return false;
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// * `gain` - Gain level, clamped to 0.0–1.0
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_instrument_gain(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    gain: f32,
+) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
         }
-        if slice[..i].contains(&id) {
-            return false;
+        let gain = gain.clamp(0.0, 1.0);
+        if let Some(voice) = (*engine).voice_mut(instrument as usize) {
+            voice.channel_gain.set_target(gain);
         }
-        new_order[i] = id;
-    }
-
-    let engine = &mut *engine;
-    engine.effect_order = new_order;
-    engine.reset_effect_states();
-    true
+    })
 }
 
-/// Move a single effect to `new_position` (0-indexed within the reorderable
-/// section of the chain). Other effects shift to fill the gap, preserving
-/// their relative order.
+/// Get the channel gain for an instrument
 ///
-/// On success, internal state of every reorderable effect is reset (see
-/// `gooey_engine_set_effect_order` for rationale).
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
 ///
-/// Returns `false` (leaving the chain unchanged) if `effect_id` is not
-/// reorderable (e.g. `EFFECT_LIMITER`) or `new_position >=
-/// REORDERABLE_EFFECT_COUNT`.
+/// # Returns
+/// The current gain target (0.0–1.0, or above 1.0 when boosted through
+/// `gooey_engine_set_channel_gain`), or 1.0 if invalid instrument
 ///
 /// # Safety
-/// `engine` must be a valid pointer returned by `gooey_engine_new`. Caller
-/// must ensure the engine is not concurrently mutated from another thread.
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_move_effect(
-    engine: *mut GooeyEngine,
-    effect_id: u32,
-    new_position: u32,
-) -> bool {
+pub unsafe extern "C" fn gooey_engine_get_instrument_gain(
+    engine: *const GooeyEngine,
+    instrument: u32,
+) -> f32 {
     if engine.is_null() {
-        return false;
-    }
-    if !is_reorderable_effect(effect_id) {
-        return false;
-    }
-    if new_position >= REORDERABLE_EFFECT_COUNT {
-        return false;
-    }
-
-    let engine = &mut *engine;
-    let Some(current_pos) = engine.effect_order.iter().position(|&id| id == effect_id) else {
-        return false;
-    };
-    let new_pos = new_position as usize;
-    if current_pos == new_pos {
-        return true;
+        return 1.0;
     }
+    (*engine)
+        .voice(instrument as usize)
+        .map_or(1.0, |v| v.channel_gain.target())
+}
 
-    if new_pos > current_pos {
-        // Shift left: elements (current_pos+1 ..= new_pos) move down by one.
-        for i in current_pos..new_pos {
-            engine.effect_order[i] = engine.effect_order[i + 1];
+/// Set the stereo pan for an instrument.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// * `pan` - Pan position, clamped to 0.0 (hard left) – 0.5 (center) – 1.0
+///   (hard right). Uses an equal-power law.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_instrument_pan(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    pan: f32,
+) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
         }
-    } else {
-        // Shift right: elements (new_pos .. current_pos) move up by one.
-        for i in (new_pos..current_pos).rev() {
-            engine.effect_order[i + 1] = engine.effect_order[i];
+        let pan = pan.clamp(0.0, 1.0);
+        if let Some(voice) = (*engine).voice_mut(instrument as usize) {
+            voice.pan.set_target(pan);
         }
-    }
-    engine.effect_order[new_pos] = effect_id;
-    engine.reset_effect_states();
-    true
+    })
 }
 
-/// Read the current effect-chain order. Writes up to `max_len` IDs into
-/// `out_ids` in chain order (position 0 first). The optional limiter is pinned
-/// last when enabled and is not written.
+/// Get the stereo pan for an instrument
 ///
-/// Returns the number of IDs written, which equals `REORDERABLE_EFFECT_COUNT`
-/// when `max_len` is large enough, or `max_len` otherwise. Returns `0` if
-/// `engine` or `out_ids` is null.
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+///
+/// # Returns
+/// The current pan target (0.0–1.0), or 0.5 (center) if invalid instrument
 ///
 /// # Safety
-/// `engine` must be a valid pointer returned by `gooey_engine_new`. `out_ids`
-/// must point to a buffer of at least `max_len` u32 values.
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_effect_order(
+pub unsafe extern "C" fn gooey_engine_get_instrument_pan(
     engine: *const GooeyEngine,
-    out_ids: *mut u32,
-    max_len: u32,
-) -> u32 {
-    if engine.is_null() || out_ids.is_null() || max_len == 0 {
-        return 0;
+    instrument: u32,
+) -> f32 {
+    if engine.is_null() {
+        return 0.5;
     }
-    let engine = &*engine;
-    let n = (max_len as usize).min(engine.effect_order.len());
-    let dst = std::slice::from_raw_parts_mut(out_ids, n);
-    dst.copy_from_slice(&engine.effect_order[..n]);
-    n as u32
+    (*engine)
+        .voice(instrument as usize)
+        .map_or(0.5, |v| v.pan.target())
 }
 
 // =============================================================================
-// LFO control
+// Channel mixer (fader in dB, pan, pre/post-fader meters)
 // =============================================================================
 
-/// Get the number of LFOs in the pool
+/// Lowest channel gain in dB; at or below this the channel is silent.
+pub const CHANNEL_GAIN_MIN_DB: f32 = -60.0;
+/// Highest channel gain in dB.
+pub const CHANNEL_GAIN_MAX_DB: f32 = 6.0;
+/// Meter point: the raw instrument output, before the fader.
+pub const CHANNEL_METER_PRE: u32 = 0;
+/// Meter point: after fader, mute/solo, preset gain and beat repeat (pre-pan).
+pub const CHANNEL_METER_POST: u32 = 1;
+
+/// Set a channel's fader in decibels.
+///
+/// This is the same fader as `gooey_engine_set_instrument_gain`, addressed in
+/// dB and with up to CHANNEL_GAIN_MAX_DB of boost. Changes are smoothed over
+/// 10ms.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `channel` - Channel index (0-3 kit, 4 bass, 5 fm snap, 6+ slots)
+/// * `gain_db` - Gain in dB, clamped to CHANNEL_GAIN_MIN_DB–CHANNEL_GAIN_MAX_DB;
+///   CHANNEL_GAIN_MIN_DB and below mute the channel
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an invalid channel, a
+/// non-finite gain, or a full control queue.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub extern "C" fn gooey_engine_lfo_count() -> u32 {
-    LFO_COUNT as u32
+pub unsafe extern "C" fn gooey_engine_set_channel_gain(
+    engine: *mut GooeyEngine,
+    channel: u32,
+    gain_db: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_channel_gain";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+    let engine = &mut *engine;
+    if engine.voice(channel as usize).is_none() {
+        return fail(
+            GooeyResult::InvalidChannel,
+            format!("{FN}: channel {channel} is out of range"),
+        );
+    }
+    if !gain_db.is_finite() {
+        return fail(
+            GooeyResult::InvalidValue,
+            format!("{FN}: gain {gain_db} dB is not finite"),
+        );
+    }
+    let gain_db = gain_db.clamp(CHANNEL_GAIN_MIN_DB, CHANNEL_GAIN_MAX_DB);
+    let gain = if gain_db <= CHANNEL_GAIN_MIN_DB {
+        0.0
+    } else {
+        db_to_amplitude(gain_db)
+    };
+    engine.submit(FN, ControlCommand::ChannelGain { channel, gain })
 }
 
-/// Get the number of LFO timing options
+/// Get a channel's fader in decibels.
+///
+/// # Returns
+/// The fader target in dB (CHANNEL_GAIN_MIN_DB when silent), or 0.0 for a
+/// null engine or invalid channel
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub extern "C" fn gooey_engine_lfo_timing_count() -> u32 {
-    8 // FourBars, TwoBars, OneBar, Half, Quarter, Eighth, Sixteenth, ThirtySecond
+pub unsafe extern "C" fn gooey_engine_get_channel_gain(
+    engine: *const GooeyEngine,
+    channel: u32,
+) -> f32 {
+    if engine.is_null() {
+        return 0.0;
+    }
+    (*engine).voice(channel as usize).map_or(0.0, |v| {
+        amplitude_to_db(v.channel_gain.target()).clamp(CHANNEL_GAIN_MIN_DB, CHANNEL_GAIN_MAX_DB)
+    })
 }
 
-/// Enable or disable an LFO
+/// Set a channel's stereo pan. Changes are smoothed over 10ms.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `lfo_index` - LFO index (0-7)
-/// * `enabled` - Whether the LFO should be active
+/// * `channel` - Channel index (0-3 kit, 4 bass, 5 fm snap, 6+ slots)
+/// * `pan` - 0.0 (hard left) – 0.5 (center) – 1.0 (hard right), equal-power
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an invalid channel, a pan
+/// outside 0.0-1.0, or a full control queue.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_lfo_enabled(
+pub unsafe extern "C" fn gooey_engine_set_channel_pan(
     engine: *mut GooeyEngine,
-    lfo_index: u32,
-    enabled: bool,
-) {
-    if engine.is_null() || lfo_index as usize >= LFO_COUNT {
-        return;
+    channel: u32,
+    pan: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_channel_pan";
+    if engine.is_null() {
+        return null_engine(FN);
     }
     let engine = &mut *engine;
-    engine.lfo_enabled[lfo_index as usize] = enabled;
+    if engine.voice(channel as usize).is_none() {
+        return fail(
+            GooeyResult::InvalidChannel,
+            format!("{FN}: channel {channel} is out of range"),
+        );
+    }
+    if !(0.0..=1.0).contains(&pan) {
+        return fail(
+            GooeyResult::InvalidValue,
+            format!("{FN}: pan {pan} is outside 0.0-1.0"),
+        );
+    }
+    engine.submit(FN, ControlCommand::ChannelPan { channel, pan })
 }
 
-/// Check if an LFO is enabled
-///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `lfo_index` - LFO index (0-7)
+/// Get a channel's stereo pan.
 ///
 /// # Returns
-/// `true` if the LFO is enabled, `false` otherwise
+/// The pan target (0.0–1.0), or 0.5 (center) for a null engine or invalid
+/// channel
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_lfo_enabled(
+pub unsafe extern "C" fn gooey_engine_get_channel_pan(
     engine: *const GooeyEngine,
-    lfo_index: u32,
-) -> bool {
-    if engine.is_null() || lfo_index as usize >= LFO_COUNT {
-        return false;
-    }
-    let engine = &*engine;
-    engine.lfo_enabled[lfo_index as usize]
+    channel: u32,
+) -> f32 {
+    gooey_engine_get_instrument_pan(engine, channel)
 }
 
-/// Set the timing (musical division) for an LFO
+/// Keep a channel out of some master effects.
 ///
-/// The LFO will sync to the global BPM using the specified timing.
-/// Phase is preserved when changing timing, allowing smooth transitions.
+/// `mask` holds `1 << EFFECT_*` bits, so a kick set to
+/// `1 << EFFECT_DELAY | 1 << EFFECT_REVERB` stays out of the delay and the
+/// reverb. Channels with any bit set are summed apart from the rest of the
+/// mix (still through their track's fader and pan, but not its effect rack)
+/// and that dry-routed sum rejoins the master path right after the last
+/// effect any of them bypasses, so they also skip the effects ahead of it
+/// in the chain. Order the chain (`gooey_engine_set_effect_order`) with the
+/// bypassed effects first to keep these channels on the rest. The limiter
+/// can't be bypassed. 0 (the default) sends the channel through everything.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `lfo_index` - LFO index (0-7)
-/// * `timing` - LFO timing constant (LFO_TIMING_QUARTER, etc.)
+/// * `channel` - Channel index (0-3 kit, 4 bass, 5 fm snap, 6+ slots)
+/// * `mask` - Effects to bypass, within FX_BYPASS_ALL
 ///
-/// # Timing constants
-/// - LFO_TIMING_FOUR_BARS (0): 4 bars / 16 beats
-/// - LFO_TIMING_TWO_BARS (1): 2 bars / 8 beats
-/// - LFO_TIMING_ONE_BAR (2): 1 bar / 4 beats
-/// - LFO_TIMING_HALF (3): Half note / 2 beats
-/// - LFO_TIMING_QUARTER (4): Quarter note / 1 beat
-/// - LFO_TIMING_EIGHTH (5): Eighth note / 1/2 beat
-/// - LFO_TIMING_SIXTEENTH (6): Sixteenth note / 1/4 beat
-/// - LFO_TIMING_THIRTY_SECOND (7): Thirty-second note / 1/8 beat
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an invalid channel, or a
+/// mask with bits outside FX_BYPASS_ALL.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_lfo_timing(
+pub unsafe extern "C" fn gooey_engine_set_channel_fx_bypass(
     engine: *mut GooeyEngine,
-    lfo_index: u32,
-    timing: u32,
-) {
-    if engine.is_null() || lfo_index as usize >= LFO_COUNT {
-        return;
+    channel: u32,
+    mask: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_channel_fx_bypass";
+    let Some(engine) = engine.as_ref() else {
+        return null_engine(FN);
+    };
+    let Some(voice) = engine.voice(channel as usize) else {
+        return fail(
+            GooeyResult::InvalidChannel,
+            format!("{FN}: channel {channel} is out of range"),
+        );
+    };
+    if mask & !FX_BYPASS_ALL != 0 {
+        return fail(
+            GooeyResult::InvalidValue,
+            format!("{FN}: mask {mask:#x} has bits outside FX_BYPASS_ALL"),
+        );
     }
-    let engine = &mut *engine;
+    voice.fx_bypass.store(mask, Ordering::Relaxed);
+    GooeyResult::Ok
+}
 
-    if let Some(division) = MusicalDivision::from_timing_constant(timing) {
-        engine.lfos[lfo_index as usize].set_sync_mode(division);
-    }
+/// Get the master effects a channel bypasses.
+///
+/// # Returns
+/// The `1 << EFFECT_*` bypass mask, or 0 for a null engine or invalid channel
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_channel_fx_bypass(
+    engine: *const GooeyEngine,
+    channel: u32,
+) -> u32 {
+    engine
+        .as_ref()
+        .and_then(|engine| engine.voice(channel as usize))
+        .map_or(0, |v| v.fx_bypass.load(Ordering::Relaxed))
 }
 
-/// Get the current timing for an LFO
+/// Read a channel's level meter in dBFS.
+///
+/// Meters hold the peak with an instant attack and a 300ms release, so they
+/// can be polled at any rate and by several readers; reading does not reset
+/// them (unlike `gooey_engine_get_channel_peaks`).
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `lfo_index` - LFO index (0-7)
+/// * `channel` - Channel index (0-3 kit, 4 bass, 5 fm snap, 6+ slots)
+/// * `point` - CHANNEL_METER_PRE or CHANNEL_METER_POST
 ///
 /// # Returns
-/// The current timing constant, or LFO_INVALID if invalid
+/// The level in dBFS (-120.0 for silence), or -120.0 for a null engine, an
+/// invalid channel or an unknown meter point
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_lfo_timing(
+pub unsafe extern "C" fn gooey_engine_get_channel_meter(
     engine: *const GooeyEngine,
-    lfo_index: u32,
-) -> u32 {
-    if engine.is_null() || lfo_index as usize >= LFO_COUNT {
-        return LFO_INVALID;
-    }
-    let engine = &*engine;
-
-    match engine.lfos[lfo_index as usize].sync_mode() {
-        crate::engine::lfo::LfoSyncMode::BpmSync(division) => match division {
-            MusicalDivision::FourBars => LFO_TIMING_FOUR_BARS,
-            MusicalDivision::TwoBars => LFO_TIMING_TWO_BARS,
-            MusicalDivision::OneBar => LFO_TIMING_ONE_BAR,
-            MusicalDivision::Half => LFO_TIMING_HALF,
-            MusicalDivision::Quarter => LFO_TIMING_QUARTER,
-            MusicalDivision::Eighth => LFO_TIMING_EIGHTH,
-            MusicalDivision::Sixteenth => LFO_TIMING_SIXTEENTH,
-            MusicalDivision::ThirtySecond => LFO_TIMING_THIRTY_SECOND,
-        },
-        crate::engine::lfo::LfoSyncMode::Hz(_) => LFO_INVALID, // Hz mode, not BPM synced
-    }
+    channel: u32,
+    point: u32,
+) -> f32 {
+    let level = engine
+        .as_ref()
+        .and_then(|engine| engine.voice(channel as usize))
+        .map_or(0.0, |v| match point {
+            CHANNEL_METER_PRE => v.meter_pre.get(),
+            CHANNEL_METER_POST => v.meter_post.get(),
+            _ => 0.0,
+        });
+    amplitude_to_db(level)
 }
 
-/// Set the global modulation amount for an LFO
+/// Set the random per-hit pan spread for an instrument.
 ///
-/// This scales the LFO's sine wave amplitude before it's distributed to routes.
-/// Final modulation = (offset + sine * amount) * route_depth
+/// Each trigger offsets the instrument's pan by a random amount of up to
+/// ±`width`/2, so fast rolls (hi-hats in particular) move around the stereo
+/// field instead of stacking in one spot. The result is clamped to hard
+/// left/right.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `lfo_index` - LFO index (0-7)
-/// * `amount` - Global amplitude scale (0.0 to 1.0, default 1.0)
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// * `width` - Spread width, 0.0 (off, the default) to 1.0 (full field)
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an invalid instrument, or
+/// a width outside 0.0-1.0.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_lfo_amount(
+pub unsafe extern "C" fn gooey_engine_set_instrument_pan_spread(
     engine: *mut GooeyEngine,
-    lfo_index: u32,
-    amount: f32,
-) {
-    if engine.is_null() || lfo_index as usize >= LFO_COUNT {
-        return;
-    }
-    let engine = &mut *engine;
-    engine.lfos[lfo_index as usize].amount = amount;
+    instrument: u32,
+    width: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_instrument_pan_spread";
+    edit(engine, || {
+        if engine.is_null() {
+            return null_engine(FN);
+        }
+        if !(0.0..=1.0).contains(&width) {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: width {width} is outside 0.0-1.0"),
+            );
+        }
+        let Some(voice) = (*engine).voice(instrument as usize) else {
+            return fail(
+                GooeyResult::InvalidInstrument,
+                format!("{FN}: instrument {instrument} is out of range"),
+            );
+        };
+        voice.pan_spread.store(width.to_bits(), Ordering::Relaxed);
+        GooeyResult::Ok
+    })
 }
 
-/// Get the global modulation amount for an LFO
-///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `lfo_index` - LFO index (0-7)
+/// Get the random per-hit pan spread for an instrument.
 ///
 /// # Returns
-/// The current global amplitude scale, or 0.0 if invalid
+/// The spread width (0.0–1.0), or 0.0 if invalid instrument
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_lfo_amount(
+pub unsafe extern "C" fn gooey_engine_get_instrument_pan_spread(
     engine: *const GooeyEngine,
-    lfo_index: u32,
+    instrument: u32,
 ) -> f32 {
-    if engine.is_null() || lfo_index as usize >= LFO_COUNT {
+    if engine.is_null() {
         return 0.0;
     }
-    let engine = &*engine;
-    engine.lfos[lfo_index as usize].amount
+    (*engine).voice(instrument as usize).map_or(0.0, |v| {
+        f32::from_bits(v.pan_spread.load(Ordering::Relaxed))
+    })
 }
 
-/// Set the center offset (DC bias) for an LFO
+/// Configure per-hit variation for an instrument.
 ///
-/// This adds a constant value to the LFO output before distribution to routes.
-/// Final modulation = (offset + sine * amount) * route_depth
-///
-/// Use offset to bias the modulation (e.g., offset=0.5 with amount=0.5 gives 0.0-1.0 range)
+/// Each trigger offsets the parameters selected with
+/// `gooey_engine_set_instrument_variation_params` by up to ±0.15 × `depth`
+/// (in the normalized 0-1 parameter range), so repeated 16th-note hats and
+/// snares stop sounding machine-gunned. With `variants` 0 every hit draws a
+/// new random offset; with 2-4 the hits cycle through that many fixed
+/// micro-variants, like round-robin samples. Offsets are deterministic: the
+/// same pattern renders the same way every time. Parameter getters keep
+/// reporting the values you set, not the varied ones.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `lfo_index` - LFO index (0-7)
-/// * `offset` - DC bias (-1.0 to 1.0, default 0.0 for centered bipolar modulation)
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// * `depth` - Variation depth, 0.0 (off, the default) to 1.0
+/// * `variants` - 0 for random per hit, or 2-4 round-robin variants
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an invalid instrument, a
+/// depth outside 0.0-1.0, or a variant count of 1 or above 4.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_lfo_offset(
+pub unsafe extern "C" fn gooey_engine_set_instrument_variation(
     engine: *mut GooeyEngine,
-    lfo_index: u32,
-    offset: f32,
-) {
-    if engine.is_null() || lfo_index as usize >= LFO_COUNT {
-        return;
-    }
-    let engine = &mut *engine;
-    engine.lfos[lfo_index as usize].offset = offset;
+    instrument: u32,
+    depth: f32,
+    variants: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_instrument_variation";
+    edit(engine, || {
+        if engine.is_null() {
+            return null_engine(FN);
+        }
+        if !(0.0..=1.0).contains(&depth) {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: depth {depth} is outside 0.0-1.0"),
+            );
+        }
+        if variants == 1 || variants > VARIATION_MAX_VARIANTS {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: variants must be 0 or 2-{VARIATION_MAX_VARIANTS}, got {variants}"),
+            );
+        }
+        let Some(voice) = (*engine).voice(instrument as usize) else {
+            return fail(
+                GooeyResult::InvalidInstrument,
+                format!("{FN}: instrument {instrument} is out of range"),
+            );
+        };
+        voice
+            .variation
+            .depth
+            .store(depth.to_bits(), Ordering::Relaxed);
+        voice.variation.variants.store(variants, Ordering::Relaxed);
+        GooeyResult::Ok
+    })
 }
 
-/// Get the center offset (DC bias) for an LFO
-///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `lfo_index` - LFO index (0-7)
+/// Get the per-hit variation depth for an instrument.
 ///
 /// # Returns
-/// The current DC bias, or 0.0 if invalid
+/// The depth (0.0–1.0), or 0.0 if invalid instrument
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_lfo_offset(
+pub unsafe extern "C" fn gooey_engine_get_instrument_variation_depth(
     engine: *const GooeyEngine,
-    lfo_index: u32,
+    instrument: u32,
 ) -> f32 {
-    if engine.is_null() || lfo_index as usize >= LFO_COUNT {
+    if engine.is_null() {
         return 0.0;
     }
-    let engine = &*engine;
-    engine.lfos[lfo_index as usize].offset
+    (*engine).voice(instrument as usize).map_or(0.0, |v| {
+        f32::from_bits(v.variation.depth.load(Ordering::Relaxed))
+    })
 }
 
-/// Add a route from an LFO to an instrument parameter
-///
-/// Each LFO can have multiple routes to different parameters.
-/// Final modulation applied to target = (offset + sine * amount) * depth
-///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `lfo_index` - LFO index (0-7)
-/// * `instrument` - Target instrument (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
-/// * `param` - Target parameter index (KICK_PARAM_FREQUENCY, etc.)
-/// * `depth` - Per-route depth (0.0 to 1.0) - scales the LFO output for this target
+/// Get the round-robin variant count for an instrument.
 ///
 /// # Returns
-/// A route ID that can be used to remove this specific route, or LFO_INVALID on error
+/// 0 (random per hit) or 2-4, or 0 if invalid instrument
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_add_lfo_route(
-    engine: *mut GooeyEngine,
-    lfo_index: u32,
+pub unsafe extern "C" fn gooey_engine_get_instrument_variation_variants(
+    engine: *const GooeyEngine,
     instrument: u32,
-    param: u32,
-    depth: f32,
 ) -> u32 {
-    if engine.is_null() || lfo_index as usize >= LFO_COUNT {
-        return LFO_INVALID;
-    }
-    let engine = &mut *engine;
-    let idx = lfo_index as usize;
-
-    // Check if we've hit the max routes limit
-    if engine.lfo_routes[idx].len() >= LFO_MAX_ROUTES {
-        return LFO_INVALID;
+    if engine.is_null() {
+        return 0;
     }
-
-    let route_id = engine.lfo_next_route_id[idx];
-    engine.lfo_next_route_id[idx] = route_id.wrapping_add(1);
-
-    engine.lfo_routes[idx].push(LfoRoute {
-        id: route_id,
-        instrument,
-        param,
-        depth,
-    });
-
-    route_id
+    (*engine)
+        .voice(instrument as usize)
+        .map_or(0, |v| v.variation.variants.load(Ordering::Relaxed))
 }
 
-/// Remove a specific route from an LFO by route ID
+/// Select which parameters per-hit variation moves.
+///
+/// Bit `n` of `param_mask` selects parameter index `n` of the instrument
+/// currently on that channel (e.g. `1 << HIHAT_PARAM_DECAY`). Choice
+/// parameters cannot vary. The selection is cleared when the channel's
+/// instrument type changes.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `lfo_index` - LFO index (0-7)
-/// * `route_id` - The route ID returned by `gooey_engine_add_lfo_route`
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// * `param_mask` - Bitmask of parameter indices, 0 for none (the default)
 ///
 /// # Returns
-/// `true` if the route was found and removed, `false` otherwise
+/// `GooeyResult::Ok`, or an error for a null engine or an invalid
+/// instrument, `InvalidParam` for a bit that is not a continuous parameter of
+/// the instrument, or `InvalidInstrument` for a non-zero mask on the bass,
+/// whose parameters cannot be read back.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_remove_lfo_route(
+pub unsafe extern "C" fn gooey_engine_set_instrument_variation_params(
     engine: *mut GooeyEngine,
-    lfo_index: u32,
-    route_id: u32,
-) -> bool {
-    if engine.is_null() || lfo_index as usize >= LFO_COUNT {
-        return false;
+    instrument: u32,
+    param_mask: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_instrument_variation_params";
+    edit(engine, || {
+        if engine.is_null() {
+            return null_engine(FN);
+        }
+        let Some(voice) = (*engine).voice(instrument as usize) else {
+            return fail(
+                GooeyResult::InvalidInstrument,
+                format!("{FN}: instrument {instrument} is out of range"),
+            );
+        };
+        let instrument_type = voice.instrument.instrument_type();
+        if instrument_type == INSTRUMENT_BASS && param_mask != 0 {
+            return fail(
+                GooeyResult::InvalidInstrument,
+                format!("{FN}: bass parameters cannot vary per hit"),
+            );
+        }
+        for param in 0..VARIATION_MAX_PARAMS as u32 {
+            if param_mask & (1 << param) == 0 {
+                continue;
+            }
+            let continuous = crate::param_info::param_info(instrument_type, param)
+                .is_some_and(|info| info.unit != crate::param_info::ParamUnit::Choice);
+            if !continuous {
+                return fail(
+                    GooeyResult::InvalidParam,
+                    format!(
+                        "{FN}: parameter {param} cannot vary on instrument type {instrument_type}"
+                    ),
+                );
+            }
+        }
+        voice.variation.params.store(param_mask, Ordering::Relaxed);
+        GooeyResult::Ok
+    })
+}
+
+/// Get the bitmask of parameters per-hit variation moves for an instrument.
+///
+/// # Returns
+/// The parameter bitmask, or 0 if invalid instrument
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_instrument_variation_params(
+    engine: *const GooeyEngine,
+    instrument: u32,
+) -> u32 {
+    if engine.is_null() {
+        return 0;
     }
-    let engine = &mut *engine;
-    let idx = lfo_index as usize;
+    (*engine)
+        .voice(instrument as usize)
+        .map_or(0, |v| v.variation.params.load(Ordering::Relaxed))
+}
 
-    if let Some(pos) = engine.lfo_routes[idx].iter().position(|r| r.id == route_id) {
-        engine.lfo_routes[idx].remove(pos);
-        true
-    } else {
-        false
+/// Playback status of one instrument's voices, filled by
+/// `gooey_engine_get_voice_status` for debug UI.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GooeyVoiceStatus {
+    /// Voices currently sounding. Each instrument is monophonic today, so this
+    /// is 0 or 1.
+    pub active_voices: u32,
+    /// Seconds since the most recent trigger, or -1.0 if never triggered.
+    pub age_seconds: f32,
+    /// Pan of the current hit including its spread offset (0.0–1.0).
+    pub pan: f32,
+}
+
+/// Read the voice status of an instrument.
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine or `out`, or an invalid
+/// instrument.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`, and `out`
+/// must be null or a valid pointer to a `GooeyVoiceStatus`.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_voice_status(
+    engine: *const GooeyEngine,
+    instrument: u32,
+    out: *mut GooeyVoiceStatus,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_get_voice_status";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+    if out.is_null() {
+        return fail(GooeyResult::NullPointer, format!("{FN}: out is null"));
     }
+    let engine = &*engine;
+    let Some(voice) = engine.voice(instrument as usize) else {
+        return fail(
+            GooeyResult::InvalidInstrument,
+            format!("{FN}: instrument {instrument} is out of range"),
+        );
+    };
+    *out = GooeyVoiceStatus {
+        active_voices: voice.instrument.is_active() as u32,
+        age_seconds: voice
+            .last_trigger_time
+            .map_or(-1.0, |t| (engine.clock.seconds() - t) as f32),
+        pan: (voice.pan.get() + voice.pan_offset).clamp(0.0, 1.0),
+    };
+    GooeyResult::Ok
 }
 
-/// Clear all routes for an LFO
+/// Select the saturation curve of an instrument's overdrive stage.
+///
+/// Supported by the snare and bass, whose overdrive amount is their existing
+/// `overdrive` parameter. Every model except SATURATOR_MODEL_SOFT (the
+/// default) is loudness-matched, so the curve changes tone rather than level.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `lfo_index` - LFO index (0-7)
+/// * `instrument` - Instrument index (INSTRUMENT_* constant)
+/// * `model` - SATURATOR_MODEL_* constant
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an invalid instrument
+/// or one without a model-selectable overdrive stage, or an unknown model.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_clear_lfo_routes(engine: *mut GooeyEngine, lfo_index: u32) {
-    if engine.is_null() || lfo_index as usize >= LFO_COUNT {
-        return;
+pub unsafe extern "C" fn gooey_engine_set_instrument_saturator_model(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    model: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_instrument_saturator_model";
+    if engine.is_null() {
+        return null_engine(FN);
     }
     let engine = &mut *engine;
-    engine.lfo_routes[lfo_index as usize].clear();
+    let Some(voice) = engine.voice(instrument as usize) else {
+        return fail(
+            GooeyResult::InvalidInstrument,
+            format!("{FN}: instrument {instrument} is out of range"),
+        );
+    };
+    if voice.instrument.saturator_model().is_none() {
+        return fail(
+            GooeyResult::InvalidInstrument,
+            format!("{FN}: instrument {instrument} has no selectable overdrive model"),
+        );
+    }
+    let Some(model) = SaturatorModel::from_u32(model) else {
+        return fail(
+            GooeyResult::InvalidValue,
+            format!("{FN}: {model} is not a SATURATOR_MODEL_* constant"),
+        );
+    };
+    engine.submit(
+        FN,
+        ControlCommand::SaturatorModel {
+            channel: instrument,
+            model,
+        },
+    )
 }
 
-/// Get the number of routes for an LFO
-///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `lfo_index` - LFO index (0-7)
+/// Get the saturation curve of an instrument's overdrive stage.
 ///
 /// # Returns
-/// The number of active routes, or 0 if invalid
+/// A SATURATOR_MODEL_* constant, or SATURATOR_MODEL_NONE for a null engine,
+/// an invalid instrument, or one without a model-selectable overdrive stage.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_lfo_route_count(
+pub unsafe extern "C" fn gooey_engine_get_instrument_saturator_model(
     engine: *const GooeyEngine,
-    lfo_index: u32,
+    instrument: u32,
 ) -> u32 {
-    if engine.is_null() || lfo_index as usize >= LFO_COUNT {
-        return 0;
+    if engine.is_null() {
+        return SATURATOR_MODEL_NONE;
     }
-    let engine = &*engine;
-    engine.lfo_routes[lfo_index as usize].len() as u32
+    (*engine)
+        .voice(instrument as usize)
+        .and_then(|v| v.instrument.saturator_model())
+        .map_or(SATURATOR_MODEL_NONE, SaturatorModel::as_u32)
 }
 
-/// Reset an LFO's phase to 0
+/// Turn auto gain on or off for an instrument's overdrive stage.
+///
+/// Supported by the snare and bass. The default SATURATOR_MODEL_SOFT curve
+/// is matched to the undriven level by peak, so its RMS climbs by a few dB
+/// as `overdrive` rises; with auto gain it is RMS-matched like the other
+/// models, so overdrive changes the tone rather than the level. Off by
+/// default.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `lfo_index` - LFO index (0-7)
+/// * `instrument` - Instrument index (INSTRUMENT_* constant)
+/// * `enabled` - true to turn auto gain on
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine or an invalid
+/// instrument or one without an overdrive stage.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_reset_lfo_phase(engine: *mut GooeyEngine, lfo_index: u32) {
-    if engine.is_null() || lfo_index as usize >= LFO_COUNT {
-        return;
+pub unsafe extern "C" fn gooey_engine_set_instrument_overdrive_auto_gain(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+    enabled: bool,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_instrument_overdrive_auto_gain";
+    if engine.is_null() {
+        return null_engine(FN);
     }
     let engine = &mut *engine;
-    engine.lfos[lfo_index as usize].reset();
+    let Some(voice) = engine.voice(instrument as usize) else {
+        return fail(
+            GooeyResult::InvalidInstrument,
+            format!("{FN}: instrument {instrument} is out of range"),
+        );
+    };
+    if voice.instrument.overdrive_auto_gain().is_none() {
+        return fail(
+            GooeyResult::InvalidInstrument,
+            format!("{FN}: instrument {instrument} has no overdrive stage"),
+        );
+    }
+    engine.submit(
+        FN,
+        ControlCommand::OverdriveAutoGain {
+            channel: instrument,
+            enabled,
+        },
+    )
 }
 
-/// Get an LFO's current phase
-///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `lfo_index` - LFO index (0-7)
+/// Whether an instrument's overdrive stage has auto gain on.
 ///
 /// # Returns
-/// The current phase (0.0 to 1.0), or -1.0 if invalid
+/// false for a null engine, an invalid instrument, or one without an
+/// overdrive stage.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_lfo_phase(
+pub unsafe extern "C" fn gooey_engine_get_instrument_overdrive_auto_gain(
     engine: *const GooeyEngine,
-    lfo_index: u32,
-) -> f32 {
-    if engine.is_null() || lfo_index as usize >= LFO_COUNT {
-        return -1.0;
+    instrument: u32,
+) -> bool {
+    if engine.is_null() {
+        return false;
     }
-    let engine = &*engine;
-    engine.lfos[lfo_index as usize].phase()
+    (*engine)
+        .voice(instrument as usize)
+        .and_then(|v| v.instrument.overdrive_auto_gain())
+        .unwrap_or(false)
 }
 
-/// Get the number of snare parameters
-#[no_mangle]
-pub extern "C" fn gooey_engine_snare_param_count() -> u32 {
-    20 // frequency, decay, brightness, volume, tonal, noise, pitch_drop,
-       // tonal_decay, noise_decay, noise_tail_decay, filter_cutoff, filter_resonance,
-       // filter_type, xfade, phase_mod_amount, overdrive, amp_decay, amp_decay_curve,
-       // tonal_decay_curve, tuning
+// =============================================================================
+// Instrument groups (tags with group gain, mute, effect bypass and humanize)
+// =============================================================================
+
+/// Number of instrument groups. Group IDs are `0..GROUP_MAX`; the first two
+/// are tagged by default, the rest are free for the host.
+pub const GROUP_MAX: u32 = 8;
+/// Group ID: percussion. Every built-in voice but the bass, and slots created
+/// with a percussion instrument, start in this group.
+pub const GROUP_PERCUSSION: u32 = 0;
+/// Group ID: melodic. The bass (and bass slots) start in this group.
+pub const GROUP_MELODIC: u32 = 1;
+
+/// The group a fresh `instrument_type` voice is tagged with.
+fn default_group(instrument_type: u32) -> u32 {
+    if instrument_type == INSTRUMENT_BASS {
+        GROUP_MELODIC
+    } else {
+        GROUP_PERCUSSION
+    }
 }
 
-/// Get the number of tom parameters
-#[no_mangle]
-pub extern "C" fn gooey_engine_tom_param_count() -> u32 {
-    9 // tune, bend, tone, color, decay, membrane, membrane_q, volume, tuning (Tom2)
+/// Reject group IDs outside `0..GROUP_MAX`.
+fn check_group(group: u32, fn_name: &str) -> Result<(), GooeyResult> {
+    if group < GROUP_MAX {
+        Ok(())
+    } else {
+        Err(fail(
+            GooeyResult::InvalidValue,
+            format!("{fn_name}: group {group} is out of range (GROUP_MAX is {GROUP_MAX})"),
+        ))
+    }
 }
 
-// =============================================================================
-// Instrument mute/solo control
-// =============================================================================
-
-/// Set the mute state for an instrument
+/// Tag a channel with a group, or remove the tag.
 ///
-/// When muted, the instrument's audio output is silenced.
-/// Solo takes precedence: if an instrument is both muted and soloed, it will play.
+/// A channel can be in any number of groups. Tags stay with the channel when
+/// its instrument is replaced; a destroyed slot's tags go with it, and a new
+/// slot starts with its instrument's default group.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
-/// * `muted` - Whether the instrument should be muted
+/// * `channel` - Channel index (0-3 kit, 4 bass, 5 fm snap, 6+ slots)
+/// * `group` - Group ID (GROUP_PERCUSSION, GROUP_MELODIC, or up to GROUP_MAX - 1)
+/// * `member` - Whether the channel belongs to the group
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an invalid channel or a
+/// group outside `0..GROUP_MAX`.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_instrument_mute(
+pub unsafe extern "C" fn gooey_engine_set_channel_group(
     engine: *mut GooeyEngine,
-    instrument: u32,
-    muted: bool,
-) {
-    if engine.is_null() {
-        return;
+    channel: u32,
+    group: u32,
+    member: bool,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_channel_group";
+    let Some(engine) = engine.as_ref() else {
+        return null_engine(FN);
+    };
+    if let Err(result) = check_group(group, FN) {
+        return result;
     }
-    if let Some(voice) = (*engine).voice(instrument as usize) {
-        voice.muted.store(muted, Ordering::Release);
+    let Some(voice) = engine.voice(channel as usize) else {
+        return fail(
+            GooeyResult::InvalidChannel,
+            format!("{FN}: channel {channel} is out of range"),
+        );
+    };
+    if member {
+        voice.groups.fetch_or(1 << group, Ordering::Relaxed);
+    } else {
+        voice.groups.fetch_and(!(1 << group), Ordering::Relaxed);
     }
+    GooeyResult::Ok
 }
 
-/// Get the mute state for an instrument
-///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// Get the groups a channel is tagged with.
 ///
 /// # Returns
-/// `true` if the instrument is muted, `false` otherwise (or if invalid instrument)
+/// The `1 << GROUP_*` membership mask, or 0 for a null engine or invalid
+/// channel
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_instrument_mute(
+pub unsafe extern "C" fn gooey_engine_get_channel_groups(
     engine: *const GooeyEngine,
-    instrument: u32,
-) -> bool {
-    if engine.is_null() {
-        return false;
-    }
-    (*engine)
-        .voice(instrument as usize)
-        .is_some_and(|v| v.muted.load(Ordering::Acquire))
+    channel: u32,
+) -> u32 {
+    engine
+        .as_ref()
+        .and_then(|engine| engine.voice(channel as usize))
+        .map_or(0, |v| v.groups.load(Ordering::Relaxed))
 }
 
-/// Set the solo state for an instrument
+/// Set a group's gain in decibels.
 ///
-/// When any instrument is soloed, only soloed instruments produce audio.
-/// Multiple instruments can be soloed simultaneously.
-/// Solo takes precedence over mute.
+/// The group gain is a stage of its own, multiplied with each member's
+/// fader, so members keep their relative balance and a channel in several
+/// groups gets the product of their gains (capped at CHANNEL_GAIN_MAX_DB).
+/// Applied at the next render and smoothed over 10ms.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
-/// * `soloed` - Whether the instrument should be soloed
+/// * `group` - Group ID
+/// * `gain_db` - Gain in dB, clamped to CHANNEL_GAIN_MIN_DB–CHANNEL_GAIN_MAX_DB;
+///   CHANNEL_GAIN_MIN_DB and below silence the group
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, a group outside
+/// `0..GROUP_MAX` or a non-finite gain.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_instrument_solo(
+pub unsafe extern "C" fn gooey_engine_set_group_gain(
     engine: *mut GooeyEngine,
-    instrument: u32,
-    soloed: bool,
-) {
-    if engine.is_null() {
-        return;
+    group: u32,
+    gain_db: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_group_gain";
+    let Some(engine) = engine.as_ref() else {
+        return null_engine(FN);
+    };
+    if let Err(result) = check_group(group, FN) {
+        return result;
     }
-    if let Some(voice) = (*engine).voice(instrument as usize) {
-        voice.soloed.store(soloed, Ordering::Release);
+    if !gain_db.is_finite() {
+        return fail(
+            GooeyResult::InvalidValue,
+            format!("{FN}: gain {gain_db} dB is not finite"),
+        );
     }
+    let gain_db = gain_db.clamp(CHANNEL_GAIN_MIN_DB, CHANNEL_GAIN_MAX_DB);
+    let gain = if gain_db <= CHANNEL_GAIN_MIN_DB {
+        0.0
+    } else {
+        db_to_amplitude(gain_db)
+    };
+    engine.group_gains[group as usize].store(gain.to_bits(), Ordering::Relaxed);
+    GooeyResult::Ok
 }
 
-/// Get the solo state for an instrument
-///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// Get a group's gain in decibels.
 ///
 /// # Returns
-/// `true` if the instrument is soloed, `false` otherwise (or if invalid instrument)
+/// The gain in dB (CHANNEL_GAIN_MIN_DB when silent), or 0.0 for a null engine
+/// or a group outside `0..GROUP_MAX`
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_instrument_solo(
+pub unsafe extern "C" fn gooey_engine_get_group_gain(
     engine: *const GooeyEngine,
-    instrument: u32,
-) -> bool {
-    if engine.is_null() {
-        return false;
-    }
-    (*engine)
-        .voice(instrument as usize)
-        .is_some_and(|v| v.soloed.load(Ordering::Acquire))
+    group: u32,
+) -> f32 {
+    engine
+        .as_ref()
+        .and_then(|engine| engine.group_gains.get(group as usize))
+        .map_or(0.0, |gain| {
+            amplitude_to_db(f32::from_bits(gain.load(Ordering::Relaxed)))
+                .clamp(CHANNEL_GAIN_MIN_DB, CHANNEL_GAIN_MAX_DB)
+        })
 }
 
-// =============================================================================
-// Per-instrument channel gain (mixer fader, independent of blend system)
-// =============================================================================
-
-/// Set the channel gain for an instrument (0.0–1.0)
+/// Mute or unmute every channel in a group.
 ///
-/// This gain is applied after synthesis and blending, so it acts as a mixer
-/// fader that the blend system cannot override. Smoothed over 10ms to prevent
-/// clicks.
+/// Sets each member's own mute (as `gooey_engine_set_instrument_mute` does,
+/// quantized the same way), so a member can still be unmuted on its own
+/// afterwards. Channels tagged later are not affected.
 ///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
-/// * `gain` - Gain level, clamped to 0.0–1.0
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine or a group outside
+/// `0..GROUP_MAX`.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_instrument_gain(
+pub unsafe extern "C" fn gooey_engine_set_group_mute(
     engine: *mut GooeyEngine,
-    instrument: u32,
-    gain: f32,
-) {
-    if engine.is_null() {
-        return;
+    group: u32,
+    muted: bool,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_group_mute";
+    let Some(engine) = engine.as_ref() else {
+        return null_engine(FN);
+    };
+    if let Err(result) = check_group(group, FN) {
+        return result;
     }
-    let gain = gain.clamp(0.0, 1.0);
-    if let Some(voice) = (*engine).voice_mut(instrument as usize) {
-        voice.channel_gain.set_target(gain);
+    for channel in engine.group_channels(group) {
+        engine.set_mode(channel, false, muted);
     }
+    GooeyResult::Ok
 }
 
-/// Get the channel gain for an instrument
-///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// Whether every channel in a group is muted.
 ///
 /// # Returns
-/// The current gain target (0.0–1.0), or 1.0 if invalid instrument
+/// `true` if the group has members and all of them are muted, `false`
+/// otherwise (or for a null engine or invalid group)
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_instrument_gain(
+pub unsafe extern "C" fn gooey_engine_get_group_mute(
     engine: *const GooeyEngine,
-    instrument: u32,
-) -> f32 {
-    if engine.is_null() {
-        return 1.0;
-    }
-    (*engine)
-        .voice(instrument as usize)
-        .map_or(1.0, |v| v.channel_gain.target())
+    group: u32,
+) -> bool {
+    let Some(engine) = engine.as_ref().filter(|_| group < GROUP_MAX) else {
+        return false;
+    };
+    let mut members = engine.group_channels(group).peekable();
+    members.peek().is_some()
+        && members.all(|channel| {
+            engine
+                .voice(channel as usize)
+                .is_some_and(|v| v.muted.load(Ordering::Acquire))
+        })
 }
 
-/// Set the stereo pan for an instrument.
+/// Keep every channel in a group out of some master effects.
 ///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
-/// * `pan` - Pan position, clamped to 0.0 (hard left) – 0.5 (center) – 1.0
-///   (hard right). Uses an equal-power law.
+/// Sets each member's bypass mask (see `gooey_engine_set_channel_fx_bypass`),
+/// e.g. `1 << EFFECT_REVERB` on GROUP_PERCUSSION to keep the drums dry while
+/// melodic parts stay in the reverb.
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, a group outside
+/// `0..GROUP_MAX`, or a mask with bits outside FX_BYPASS_ALL.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_set_instrument_pan(
+pub unsafe extern "C" fn gooey_engine_set_group_fx_bypass(
     engine: *mut GooeyEngine,
-    instrument: u32,
-    pan: f32,
-) {
-    if engine.is_null() {
-        return;
+    group: u32,
+    mask: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_group_fx_bypass";
+    let Some(engine) = engine.as_ref() else {
+        return null_engine(FN);
+    };
+    if let Err(result) = check_group(group, FN) {
+        return result;
+    }
+    if mask & !FX_BYPASS_ALL != 0 {
+        return fail(
+            GooeyResult::InvalidValue,
+            format!("{FN}: mask {mask:#x} has bits outside FX_BYPASS_ALL"),
+        );
     }
-    let pan = pan.clamp(0.0, 1.0);
-    if let Some(voice) = (*engine).voice_mut(instrument as usize) {
-        voice.pan.set_target(pan);
+    for channel in engine.group_channels(group) {
+        if let Some(voice) = engine.voice(channel as usize) {
+            voice.fx_bypass.store(mask, Ordering::Relaxed);
+        }
     }
+    GooeyResult::Ok
 }
 
-/// Get the stereo pan for an instrument
-///
-/// # Arguments
-/// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_KICK, INSTRUMENT_SNARE, etc.)
+/// Randomize the velocities of every group member's enabled steps by up to
+/// `amount` either way (0.0-1.0), as
+/// `gooey_engine_sequencer_humanize_instrument_velocities` does per channel.
+/// Members draw from the engine's seed in channel order.
 ///
 /// # Returns
-/// The current pan target (0.0–1.0), or 0.5 (center) if invalid instrument
+/// `GooeyResult::Ok`, or an error for a null engine, a group outside
+/// `0..GROUP_MAX` or an amount outside 0.0-1.0
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_get_instrument_pan(
-    engine: *const GooeyEngine,
-    instrument: u32,
-) -> f32 {
-    if engine.is_null() {
-        return 0.5;
-    }
-    (*engine)
-        .voice(instrument as usize)
-        .map_or(0.5, |v| v.pan.target())
+pub unsafe extern "C" fn gooey_engine_sequencer_humanize_group_velocities(
+    engine: *mut GooeyEngine,
+    group: u32,
+    amount: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_sequencer_humanize_group_velocities";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return null_engine(FN);
+        };
+        if let Err(result) = check_group(group, FN) {
+            return result;
+        }
+        if !(0.0..=1.0).contains(&amount) {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: amount {amount} is outside 0.0-1.0"),
+            );
+        }
+        let members: Vec<u32> = engine.group_channels(group).collect();
+        let mut rng = engine.humanize_rng;
+        for channel in members {
+            if let Some(sequencer) = engine.sequencer_for_instrument(channel) {
+                sequencer.humanize_velocities(amount, &mut rng);
+            }
+        }
+        engine.humanize_rng = rng;
+        GooeyResult::Ok
+    })
 }
 
 // =============================================================================
@@ -5243,13 +14818,16 @@ pub unsafe extern "C" fn gooey_engine_get_instrument_pan(
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
 pub unsafe extern "C" fn gooey_engine_blend_enable(engine: *mut GooeyEngine, instrument: u32) {
-    if engine.is_null() {
-        return;
-    }
-    let engine = &mut *engine;
-    if let Some(voice) = engine.voice_mut(instrument as usize) {
-        voice.blend_enabled = true;
-    }
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        if let Some(voice) = engine.voice_mut(instrument as usize) {
+            voice.blend_enabled.store(true, Ordering::Relaxed);
+        }
+        engine.refresh_preset_gain(instrument as usize);
+    })
 }
 
 /// Disable preset blend mode for an instrument
@@ -5262,13 +14840,15 @@ pub unsafe extern "C" fn gooey_engine_blend_enable(engine: *mut GooeyEngine, ins
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
 pub unsafe extern "C" fn gooey_engine_blend_disable(engine: *mut GooeyEngine, instrument: u32) {
-    if engine.is_null() {
-        return;
-    }
-    let engine = &mut *engine;
-    if let Some(voice) = engine.voice_mut(instrument as usize) {
-        voice.blend_enabled = false;
-    }
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        if let Some(voice) = engine.voice_mut(instrument as usize) {
+            voice.blend_enabled.store(false, Ordering::Relaxed);
+        }
+    })
 }
 
 /// Check if preset blend mode is enabled for an instrument
@@ -5293,7 +14873,7 @@ pub unsafe extern "C" fn gooey_engine_blend_is_enabled(
     let engine = &*engine;
     engine
         .voice(instrument as usize)
-        .is_some_and(|v| v.blend_enabled)
+        .is_some_and(|v| v.blend_enabled.load(Ordering::Relaxed))
 }
 
 /// Set the X/Y blend position for an instrument
@@ -5331,18 +14911,21 @@ pub unsafe extern "C" fn gooey_engine_blend_set_position(
         return;
     }
     let engine = &mut *engine;
-    let Some(voice) = engine.voice_mut(instrument as usize) else {
+    let Some(voice) = engine.voice(instrument as usize) else {
         return;
     };
-    if !voice.blend_enabled {
+    if !voice.blend_enabled.load(Ordering::Relaxed) {
         return;
     }
 
-    voice.blend_x = x.clamp(0.0, 1.0);
-    voice.blend_y = y.clamp(0.0, 1.0);
-
-    let (x, y) = (voice.blend_x, voice.blend_y);
-    voice.blender.blend_and_apply(&mut voice.instrument, x, y);
+    engine.submit(
+        "gooey_engine_blend_set_position",
+        ControlCommand::BlendPosition {
+            channel: instrument,
+            x: x.clamp(0.0, 1.0),
+            y: y.clamp(0.0, 1.0),
+        },
+    );
 }
 
 /// Get the current X blend position for an instrument
@@ -5415,76 +14998,237 @@ pub unsafe extern "C" fn gooey_engine_blend_set_corner_preset(
     corner: u32,
     preset_id: u32,
 ) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        let corner_idx = corner as usize;
+        if corner_idx >= 4 {
+            return;
+        }
+        if let Some(voice) = engine.voice_mut(instrument as usize) {
+            voice.blend_corner_presets[corner_idx] = preset_id;
+            voice.blender.set_corner_preset(corner, preset_id);
+        }
+        engine.refresh_preset_gain(instrument as usize);
+    })
+}
+
+/// Get the preset ID at a corner
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, etc.)
+/// * `corner` - Corner position (BLEND_CORNER_BOTTOM_LEFT, etc.)
+///
+/// # Returns
+/// Preset ID, or 0xFFFFFFFF if invalid
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_blend_get_corner_preset(
+    engine: *const GooeyEngine,
+    instrument: u32,
+    corner: u32,
+) -> u32 {
     if engine.is_null() {
-        return;
+        return 0xFFFFFFFF;
     }
-    let engine = &mut *engine;
+    let engine = &*engine;
     let corner_idx = corner as usize;
     if corner_idx >= 4 {
-        return;
-    }
-    if let Some(voice) = engine.voice_mut(instrument as usize) {
-        voice.blend_corner_presets[corner_idx] = preset_id;
-        voice.blender.set_corner_preset(corner, preset_id);
+        return 0xFFFFFFFF;
     }
+    engine
+        .voice(instrument as usize)
+        .map_or(0xFFFFFFFF, |v| v.blend_corner_presets[corner_idx])
+}
+
+/// Reset blend corners to default presets
+///
+/// Restores the default corner configuration for an instrument.
+/// For kick: BL=Tight, BR=Punch, TL=Loose, TR=Dirt
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `instrument` - Instrument ID (INSTRUMENT_KICK, etc.)
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_blend_reset_corners(
+    engine: *mut GooeyEngine,
+    instrument: u32,
+) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        if let Some(voice) = engine.voice_mut(instrument as usize) {
+            let inst_type = voice.instrument.instrument_type();
+            voice.blender = ChannelBlender::default_for_type(inst_type);
+            voice.blend_corner_presets = ChannelBlender::default_corner_preset_ids(inst_type);
+        }
+        engine.refresh_preset_gain(instrument as usize);
+    })
+}
+
+/// Turn preset loudness normalization on or off (on by default)
+///
+/// When on, stock presets loaded directly or used as blend corners get an
+/// auto-gain that matches their level to the instrument's default sound
+/// within ±1 dB, so switching presets (e.g. kick "tight" to "dirt") does not
+/// jump in volume. Blend positions get the blended corner gains. Each preset
+/// is measured once by rendering a single full-velocity hit offline, so the
+/// first use of an instrument's presets costs a short render on the calling
+/// thread. Turning it off returns every channel to unity preset gain.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_preset_normalization(
+    engine: *mut GooeyEngine,
+    enabled: bool,
+) {
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return;
+        };
+        engine.preset_normalization = enabled;
+        for channel in 0..NUM_CHANNELS {
+            engine.refresh_preset_gain(channel);
+        }
+    })
+}
+
+/// Whether preset loudness normalization is on
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_preset_normalization(engine: *const GooeyEngine) -> bool {
+    engine
+        .as_ref()
+        .is_some_and(|engine| engine.preset_normalization)
 }
 
-/// Get the preset ID at a corner
+/// Measure a stock preset's loudness
+///
+/// Renders (or reads from cache) one full-velocity hit of the preset and
+/// reports its loudest 50 ms RMS and sample peak in dBFS, plus the auto-gain
+/// normalization applies to it (0 dB with normalization off).
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_KICK, etc.)
-/// * `corner` - Corner position (BLEND_CORNER_BOTTOM_LEFT, etc.)
+/// * `instrument_type` - Instrument type (INSTRUMENT_KICK, etc.)
+/// * `preset_id` - Preset ID for that type (KICK_PRESET_TIGHT, etc.), or
+///   `PRESET_DEFAULT_SOUND` for the instrument's default sound
+/// * `out_rms_db`, `out_peak_db`, `out_gain_db` - Receive the results
 ///
 /// # Returns
-/// Preset ID, or 0xFFFFFFFF if invalid
+/// `GooeyResult::Ok`, or an error for a null pointer, an unknown instrument
+/// type, or an unknown preset ID.
 ///
 /// # Safety
-/// `engine` must be a valid pointer returned by `gooey_engine_new`
+/// `engine` must be a valid pointer returned by `gooey_engine_new`, and the
+/// out pointers must be valid for writes
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_blend_get_corner_preset(
-    engine: *const GooeyEngine,
-    instrument: u32,
-    corner: u32,
-) -> u32 {
-    if engine.is_null() {
-        return 0xFFFFFFFF;
-    }
-    let engine = &*engine;
-    let corner_idx = corner as usize;
-    if corner_idx >= 4 {
-        return 0xFFFFFFFF;
-    }
-    engine
-        .voice(instrument as usize)
-        .map_or(0xFFFFFFFF, |v| v.blend_corner_presets[corner_idx])
+pub unsafe extern "C" fn gooey_engine_measure_preset_loudness(
+    engine: *mut GooeyEngine,
+    instrument_type: u32,
+    preset_id: u32,
+    out_rms_db: *mut f32,
+    out_peak_db: *mut f32,
+    out_gain_db: *mut f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_measure_preset_loudness";
+    edit(engine, || {
+        if engine.is_null() {
+            return null_engine(FN);
+        }
+        if out_rms_db.is_null() || out_peak_db.is_null() || out_gain_db.is_null() {
+            return fail(
+                GooeyResult::NullPointer,
+                format!("{FN}: output pointer is null"),
+            );
+        }
+        let engine = &mut *engine;
+        if instrument_type >= INSTRUMENT_COUNT {
+            return fail(
+                GooeyResult::InvalidInstrument,
+                format!("{FN}: unknown instrument type {instrument_type}"),
+            );
+        }
+        let preset = (preset_id != PRESET_DEFAULT_SOUND).then_some(preset_id);
+        let Some(loudness) = engine.preset_loudness(instrument_type, preset) else {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: unknown preset {preset_id} for instrument type {instrument_type}"),
+            );
+        };
+        *out_rms_db = loudness.rms_db;
+        *out_peak_db = loudness.peak_db;
+        *out_gain_db = preset.map_or(0.0, |id| engine.preset_gain_db(instrument_type, id));
+        GooeyResult::Ok
+    })
 }
 
-/// Reset blend corners to default presets
+/// Randomize an instrument's sound around its stock presets
 ///
-/// Restores the default corner configuration for an instrument.
-/// For kick: BL=Tight, BR=Punch, TL=Loose, TR=Dirt
+/// Picks a random point between the instrument type's four built-in presets
+/// and moves the current config toward it by `amount`. Because every target
+/// lies between existing presets, the result stays musical, and calling this
+/// repeatedly with small amounts walks the sound around gradually. The same
+/// `seed` on the same starting sound always gives the same result. Tuning,
+/// gain, pan, and the blend corners are left untouched.
 ///
 /// # Arguments
 /// * `engine` - Pointer to a GooeyEngine
-/// * `instrument` - Instrument ID (INSTRUMENT_KICK, etc.)
+/// * `instrument` - Channel index (INSTRUMENT_KICK, etc.)
+/// * `amount` - How far to move (0.0 = unchanged, 1.0 = fully random), clamped
+/// * `seed` - Random seed
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an invalid channel, or a
+/// non-finite `amount`.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_blend_reset_corners(
+pub unsafe extern "C" fn gooey_engine_randomize_instrument(
     engine: *mut GooeyEngine,
     instrument: u32,
-) {
+    amount: f32,
+    seed: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_randomize_instrument";
     if engine.is_null() {
-        return;
+        return null_engine(FN);
     }
     let engine = &mut *engine;
-    if let Some(voice) = engine.voice_mut(instrument as usize) {
-        let inst_type = voice.instrument.instrument_type();
-        voice.blender = ChannelBlender::default_for_type(inst_type);
-        voice.blend_corner_presets = ChannelBlender::default_corner_preset_ids(inst_type);
+    if engine.voice(instrument as usize).is_none() {
+        return fail(
+            GooeyResult::InvalidChannel,
+            format!("{FN}: channel {instrument} is out of range"),
+        );
     }
+    if !amount.is_finite() {
+        return fail(
+            GooeyResult::InvalidValue,
+            format!("{FN}: amount {amount} is not finite"),
+        );
+    }
+    engine.submit(
+        FN,
+        ControlCommand::Randomize {
+            channel: instrument,
+            amount: amount.clamp(0.0, 1.0),
+            seed,
+        },
+    )
 }
 
 // =============================================================================
@@ -5498,9 +15242,35 @@ pub const POLY_PRESET_PLUCK: u32 = 2;
 pub const POLY_PRESET_KEYS: u32 = 3;
 pub const POLY_PRESET_STRINGS: u32 = 4;
 
-// Scale type IDs
+// Poly synth envelope IDs (see `gooey_engine_poly_set_adsr_ex`)
+pub const POLY_ENVELOPE_AMP: u32 = 0;
+pub const POLY_ENVELOPE_FILTER: u32 = 1;
+
+// Envelope retrigger modes
+/// Restart the attack from zero
+pub const ENVELOPE_RETRIGGER_RESET: u32 = 0;
+/// Restart the attack from the current level
+pub const ENVELOPE_RETRIGGER_LEGATO: u32 = 1;
+
+// Scale type IDs. Chord playback only understands major and minor (other IDs
+// fall back to major there); scale quantization accepts all of them.
 pub const SCALE_MAJOR: u32 = 0;
 pub const SCALE_MINOR: u32 = 1;
+pub const SCALE_HARMONIC_MINOR: u32 = 2;
+pub const SCALE_MELODIC_MINOR: u32 = 3;
+pub const SCALE_DORIAN: u32 = 4;
+pub const SCALE_PHRYGIAN: u32 = 5;
+pub const SCALE_LYDIAN: u32 = 6;
+pub const SCALE_MIXOLYDIAN: u32 = 7;
+pub const SCALE_LOCRIAN: u32 = 8;
+pub const SCALE_MAJOR_PENTATONIC: u32 = 9;
+pub const SCALE_MINOR_PENTATONIC: u32 = 10;
+pub const SCALE_BLUES: u32 = 11;
+pub const SCALE_CHROMATIC: u32 = 12;
+pub const SCALE_COUNT: u32 = 13;
+
+/// Returned by the scale quantize getters when quantization is off.
+pub const SCALE_QUANTIZE_OFF: u32 = 0xFFFFFFFF;
 
 // Voicing type IDs
 pub const VOICING_ROOT_POSITION: u32 = 0;
@@ -5578,42 +15348,45 @@ pub unsafe extern "C" fn gooey_engine_poly_trigger_chord(
     octave: i32,
     velocity: f32,
 ) {
-    if engine.is_null() {
-        return;
-    }
-    let engine = &mut *engine;
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
 
-    let root_note = root_from_id(root);
-    let scale = scale_from_id(scale_type);
-    let key = Key::new(root_note, scale);
-    let voicing_type = voicing_from_id(voicing);
-    let octave_clamped = octave.clamp(0, 8) as i8;
-    let velocity = velocity.clamp(0.0, 1.0);
+        let root_note = root_from_id(root);
+        let scale = scale_from_id(scale_type);
+        let key = Key::new(root_note, scale);
+        let voicing_type = voicing_from_id(voicing);
+        let octave_clamped = octave.clamp(0, 8) as i8;
+        let velocity = velocity.clamp(0.0, 1.0);
 
-    // Apply preset only as smoothed targets — do not snap_params here.
-    // Snapping on every chord change forces discontinuous filter/volume jumps
-    // while voices may still be releasing, which clicks.
-    engine.poly_synth.set_config(preset_config(preset));
+        // Apply preset only as smoothed targets — do not snap_params here.
+        // Snapping on every chord change forces discontinuous filter/volume jumps
+        // while voices may still be releasing, which clicks.
+        engine.poly_synth.set_config(preset_config(preset));
 
-    // Get diatonic seventh chords and pick the requested degree
-    let chords = key.diatonic_sevenths();
-    let degree_idx = degree as usize % chords.len();
-    let chord = &chords[degree_idx];
+        // Get diatonic seventh chords and pick the requested degree
+        let chords = key.diatonic_sevenths();
+        let degree_idx = degree as usize % chords.len();
+        let chord = &chords[degree_idx];
 
-    // Apply voicing to get MIDI notes
-    let midi_notes = apply_voicing(chord, voicing_type, octave_clamped);
+        // Apply voicing to get MIDI notes
+        let midi_notes = apply_voicing(chord, voicing_type, octave_clamped);
 
-    // Release any currently sounding notes, then trigger the new chord
-    engine.poly_synth.release_all();
-    for note in &midi_notes {
-        engine.poly_synth.trigger_note(*note, velocity);
-    }
+        // Release any currently sounding notes, then trigger the new chord
+        engine.poly_synth.release_all();
+        let frame = engine.rendered_frames;
+        for note in &midi_notes {
+            engine.trigger_poly_note(*note, velocity, frame);
+        }
 
-    // Stamp into the performance clip when record-armed and transport is running.
-    // Playback-driven triggers set applying_playback and are ignored.
-    let _ = engine
-        .performance
-        .record_chord_on(root, scale_type, degree, voicing, preset, octave, velocity);
+        // Stamp into the performance clip when record-armed and transport is running.
+        // Playback-driven triggers set applying_playback and are ignored.
+        let _ = engine
+            .performance
+            .record_chord_on(root, scale_type, degree, voicing, preset, octave, velocity);
+    })
 }
 
 /// Release all sounding poly synth notes.
@@ -5622,12 +15395,14 @@ pub unsafe extern "C" fn gooey_engine_poly_trigger_chord(
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
 pub unsafe extern "C" fn gooey_engine_poly_release(engine: *mut GooeyEngine) {
-    if engine.is_null() {
-        return;
-    }
-    let engine = &mut *engine;
-    engine.poly_synth.release_all();
-    let _ = engine.performance.record_chord_off();
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        engine.poly_synth.release_all();
+        let _ = engine.performance.record_chord_off();
+    })
 }
 
 /// Set the poly synth preset.
@@ -5640,11 +15415,392 @@ pub unsafe extern "C" fn gooey_engine_poly_release(engine: *mut GooeyEngine) {
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
 pub unsafe extern "C" fn gooey_engine_poly_set_preset(engine: *mut GooeyEngine, preset: u32) {
-    if engine.is_null() {
-        return;
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        engine.poly_synth.set_config(preset_config(preset));
+    })
+}
+
+// =============================================================================
+// Retrospective capture ("always-on record")
+// =============================================================================
+
+/// Longest capture window in bars.
+pub const CAPTURE_MAX_BARS: u32 = crate::performance::CAPTURE_MAX_BARS;
+
+/// Count the manual hits a capture of the last `bars` bars would use.
+///
+/// Manual triggers are remembered while the transport runs, whether or not
+/// anything is armed. The window is bar-aligned and includes the bar being
+/// played.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `bars` - Window length, clamped to 1–CAPTURE_MAX_BARS
+///
+/// # Returns
+/// The number of hits in the window, or 0 for a null engine
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_capture_hit_count(
+    engine: *const GooeyEngine,
+    bars: u32,
+) -> u32 {
+    engine.as_ref().map_or(0, |engine| {
+        engine
+            .capture
+            .window(engine.mixer.transport_beat(), bars)
+            .count() as u32
+    })
+}
+
+/// Turn the manual hits of the last `bars` bars into sequencer patterns.
+///
+/// Hits are quantized to the nearest sixteenth and folded into a 16-step bar;
+/// where several land on one step the loudest velocity wins. Each channel
+/// with hits in the window gets its pattern replaced (steps keep their blend
+/// and note settings); channels without hits are left alone. The running
+/// step position is unchanged, so playback carries on in time.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `bars` - Window length, 1–CAPTURE_MAX_BARS
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine or an out-of-range bar
+/// count.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_capture_to_patterns(
+    engine: *mut GooeyEngine,
+    bars: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_capture_to_patterns";
+    edit(engine, || {
+        if engine.is_null() {
+            return null_engine(FN);
+        }
+        if !(1..=CAPTURE_MAX_BARS).contains(&bars) {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: bars {bars} is outside 1-{CAPTURE_MAX_BARS}"),
+            );
+        }
+        let engine = &mut *engine;
+        let now = engine.mixer.transport_beat();
+        for ch in 0..NUM_CHANNELS {
+            let Some(steps) = engine.capture.pattern(ch as u32, now, bars) else {
+                continue;
+            };
+            let Some(voice) = engine.voice_mut(ch) else {
+                continue;
+            };
+            for (step, velocity) in steps.into_iter().enumerate() {
+                voice.sequencer.set_step_with_velocity(
+                    step,
+                    velocity.is_some(),
+                    velocity.unwrap_or(1.0),
+                );
+            }
+        }
+        GooeyResult::Ok
+    })
+}
+
+/// Forget all remembered manual hits.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_capture_clear(engine: *mut GooeyEngine) {
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            engine.capture.clear();
+        }
+    })
+}
+
+// =============================================================================
+// Groove pool
+// =============================================================================
+
+/// Grooves the pool holds.
+pub const GROOVE_POOL_CAPACITY: u32 = crate::engine::GROOVE_POOL_CAPACITY as u32;
+
+/// Steps in a groove (one bar of sixteenths; patterns wrap over it).
+pub const GROOVE_STEPS: u32 = crate::engine::GROOVE_STEPS as u32;
+
+impl GooeyEngine {
+    /// Add `template` to the pool, recording an error when it is full.
+    fn add_groove(&mut self, template: GrooveTemplate, fn_name: &str) -> i32 {
+        match self.grooves.add(template) {
+            Some(index) => index as i32,
+            None => {
+                fail(
+                    GooeyResult::InvalidValue,
+                    format!("{fn_name}: the groove pool is full ({GROOVE_POOL_CAPACITY} grooves)"),
+                );
+                -1
+            }
+        }
     }
-    let engine = &mut *engine;
-    engine.poly_synth.set_config(preset_config(preset));
+}
+
+/// Extract a groove from the manual hits on `channel` in the last `bars`
+/// bars and add it to the groove pool.
+///
+/// Uses the same hits as `gooey_engine_capture_to_patterns`. Each hit counts
+/// toward its nearest sixteenth, folded into one bar: a step's timing offset
+/// is how far off the grid its hits landed on average, and its velocity
+/// scale is their average velocity relative to the whole take, so accents
+/// carry over but overall loudness doesn't. Steps with no hits stay neutral.
+///
+/// # Returns
+/// The groove's index in the pool, or -1 for a null engine, an out-of-range
+/// bar count, no hits on the channel, or a full pool
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_groove_extract_capture(
+    engine: *mut GooeyEngine,
+    channel: u32,
+    bars: u32,
+) -> i32 {
+    const FN: &str = "gooey_engine_groove_extract_capture";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            null_engine(FN);
+            return -1;
+        };
+        if !(1..=CAPTURE_MAX_BARS).contains(&bars) {
+            fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: bars {bars} is outside 1-{CAPTURE_MAX_BARS}"),
+            );
+            return -1;
+        }
+        let now = engine.mixer.transport_beat();
+        let hits = engine
+            .capture
+            .window(now, bars)
+            .filter(|hit| hit.channel == channel)
+            .map(|hit| (hit.beat, hit.velocity));
+        let Some(template) = GrooveTemplate::from_hits(hits) else {
+            fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: no hits on channel {channel} in the last {bars} bars"),
+            );
+            return -1;
+        };
+        engine.add_groove(template, FN)
+    })
+}
+
+/// Extract a groove from a list of hits and add it to the groove pool, e.g.
+/// the notes of an imported MIDI clip.
+///
+/// Hit `i` sounds at `beats[i]` quarter notes from the start of the clip with
+/// velocity `velocities[i]` (0.0-1.0). The analysis is the one described at
+/// `gooey_engine_groove_extract_capture`; non-finite hits are skipped.
+///
+/// # Returns
+/// The groove's index in the pool, or -1 for a null engine or array, no
+/// usable hits, or a full pool
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `beats` and `velocities` must each point to `count` values
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_groove_extract_hits(
+    engine: *mut GooeyEngine,
+    beats: *const f64,
+    velocities: *const f32,
+    count: u32,
+) -> i32 {
+    const FN: &str = "gooey_engine_groove_extract_hits";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            null_engine(FN);
+            return -1;
+        };
+        if count > 0 && (beats.is_null() || velocities.is_null()) {
+            fail(
+                GooeyResult::NullPointer,
+                format!("{FN}: beats or velocities is null"),
+            );
+            return -1;
+        }
+        let hits = if count == 0 {
+            None
+        } else {
+            let beats = slice::from_raw_parts(beats, count as usize);
+            let velocities = slice::from_raw_parts(velocities, count as usize);
+            GrooveTemplate::from_hits(beats.iter().copied().zip(velocities.iter().copied()))
+        };
+        let Some(template) = hits else {
+            fail(GooeyResult::InvalidValue, format!("{FN}: no usable hits"));
+            return -1;
+        };
+        engine.add_groove(template, FN)
+    })
+}
+
+/// Number of grooves in the pool (0 for a null engine).
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_groove_count(engine: *const GooeyEngine) -> u32 {
+    engine
+        .as_ref()
+        .map_or(0, |engine| engine.grooves.len() as u32)
+}
+
+/// Read one step of a pooled groove: its timing offset in steps (negative =
+/// early) and its velocity scale (1.0 = unchanged).
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null pointer, an unknown groove, or
+/// a step outside `0..GROOVE_STEPS`
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `out_timing` and `out_velocity` must be valid pointers to `f32`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_groove_get_step(
+    engine: *const GooeyEngine,
+    groove: u32,
+    step: u32,
+    out_timing: *mut f32,
+    out_velocity: *mut f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_groove_get_step";
+    let Some(engine) = engine.as_ref() else {
+        return null_engine(FN);
+    };
+    if out_timing.is_null() || out_velocity.is_null() {
+        return fail(
+            GooeyResult::NullPointer,
+            format!("{FN}: out_timing or out_velocity is null"),
+        );
+    }
+    let Some(template) = engine.grooves.get(groove as usize) else {
+        return fail(
+            GooeyResult::InvalidValue,
+            format!("{FN}: groove {groove} is not in the pool"),
+        );
+    };
+    if step >= GROOVE_STEPS {
+        return fail(
+            GooeyResult::InvalidValue,
+            format!("{FN}: step {step} is outside 0-{}", GROOVE_STEPS - 1),
+        );
+    }
+    *out_timing = template.timing(step as usize);
+    *out_velocity = template.velocity(step as usize);
+    GooeyResult::Ok
+}
+
+/// Empty the groove pool. Channels keep grooves already applied to them.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_groove_clear(engine: *mut GooeyEngine) {
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            engine.grooves.clear();
+        }
+    })
+}
+
+/// Play a channel's pattern with a pooled groove.
+///
+/// Each step moves by the groove's timing offset for it, scaled by
+/// `timing_amount`, and its velocity is multiplied by the groove's scale
+/// blended toward 1.0 by `velocity_amount` (both 0.0-1.0, 1.0 = the groove
+/// as extracted). Swing still applies on top. The channel keeps a copy, so
+/// later changes to the pool don't affect it. Takes effect from the next
+/// step.
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an invalid channel, an
+/// unknown groove, or a non-finite amount
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_channel_groove(
+    engine: *mut GooeyEngine,
+    channel: u32,
+    groove: u32,
+    timing_amount: f32,
+    velocity_amount: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_channel_groove";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return null_engine(FN);
+        };
+        if !timing_amount.is_finite() || !velocity_amount.is_finite() {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: amounts must be finite"),
+            );
+        }
+        let Some(template) = engine.grooves.get(groove as usize).copied() else {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: groove {groove} is not in the pool"),
+            );
+        };
+        let Some(voice) = engine.voice_mut(channel as usize) else {
+            return fail(
+                GooeyResult::InvalidChannel,
+                format!("{FN}: channel {channel} is out of range"),
+            );
+        };
+        voice
+            .sequencer
+            .set_groove(Some(template.scaled(timing_amount, velocity_amount)));
+        GooeyResult::Ok
+    })
+}
+
+/// Play a channel's pattern straight again.
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine or an invalid channel
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_clear_channel_groove(
+    engine: *mut GooeyEngine,
+    channel: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_clear_channel_groove";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return null_engine(FN);
+        };
+        let Some(voice) = engine.voice_mut(channel as usize) else {
+            return fail(
+                GooeyResult::InvalidChannel,
+                format!("{FN}: channel {channel} is out of range"),
+            );
+        };
+        voice.sequencer.set_groove(None);
+        GooeyResult::Ok
+    })
 }
 
 // =============================================================================
@@ -5664,12 +15820,14 @@ pub const PERF_RECORD_MODE_PUNCH_OUT: u32 = crate::performance::PERF_RECORD_MODE
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
-#[no_mangle]
-pub unsafe extern "C" fn gooey_engine_perf_set_record_armed(engine: *mut GooeyEngine, armed: bool) {
-    if engine.is_null() {
-        return;
-    }
-    (*engine).performance.set_armed(armed);
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_perf_set_record_armed(engine: *mut GooeyEngine, armed: bool) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        (*engine).performance.set_armed(armed);
+    })
 }
 
 /// Returns true if performance record-arm is on.
@@ -5706,12 +15864,14 @@ pub unsafe extern "C" fn gooey_engine_perf_is_recording(engine: *const GooeyEngi
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
 pub unsafe extern "C" fn gooey_engine_perf_set_record_mode(engine: *mut GooeyEngine, mode: u32) {
-    if engine.is_null() {
-        return;
-    }
-    if let Some(m) = RecordMode::from_u32(mode) {
-        (*engine).performance.set_mode(m);
-    }
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        if let Some(m) = RecordMode::from_u32(mode) {
+            (*engine).performance.set_mode(m);
+        }
+    })
 }
 
 /// Get the performance record mode (`PERF_RECORD_MODE_*`).
@@ -5732,10 +15892,12 @@ pub unsafe extern "C" fn gooey_engine_perf_get_record_mode(engine: *const GooeyE
 /// `engine` must be a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
 pub unsafe extern "C" fn gooey_engine_perf_clear_clip(engine: *mut GooeyEngine) {
-    if engine.is_null() {
-        return;
-    }
-    (*engine).performance.clear_clip();
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        (*engine).performance.clear_clip();
+    })
 }
 
 /// Number of events in the performance clip.
@@ -5901,29 +16063,135 @@ pub unsafe extern "C" fn gooey_engine_poly_set_param(
     param: u32,
     value: f32,
 ) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        let value = value.clamp(0.0, 1.0);
+
+        match param {
+            0 => engine.poly_synth.params.osc_shape.set_target(value),
+            1 => engine.poly_synth.params.detune_amount.set_target(value),
+            2 => engine.poly_synth.params.filter_cutoff.set_target(value),
+            3 => engine.poly_synth.params.filter_resonance.set_target(value),
+            4 => engine.poly_synth.params.filter_env_amount.set_target(value),
+            5 => engine.poly_synth.params.amp_attack.set_target(value),
+            6 => engine.poly_synth.params.amp_decay.set_target(value),
+            7 => engine.poly_synth.params.amp_sustain.set_target(value),
+            8 => engine.poly_synth.params.amp_release.set_target(value),
+            9 => engine.poly_synth.params.filter_attack.set_target(value),
+            10 => engine.poly_synth.params.filter_decay.set_target(value),
+            11 => engine.poly_synth.params.filter_sustain.set_target(value),
+            12 => engine.poly_synth.params.filter_release.set_target(value),
+            13 => engine.poly_synth.params.volume.set_target(value),
+            _ => {}
+        }
+    })
+}
+
+/// Shape one of the poly synth's envelopes beyond its ADSR times: per-segment
+/// curves, retrigger behavior and velocity sensitivity. Applies from the next
+/// note.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `envelope` - POLY_ENVELOPE_AMP or POLY_ENVELOPE_FILTER
+/// * `attack_curve`, `decay_curve`, `release_curve` - Curve amount per segment
+///   (-1.0 to 1.0, as in Max's curve~): 0.0 = linear, positive = slow start /
+///   fast end, negative = fast start / slow end
+/// * `retrigger` - ENVELOPE_RETRIGGER_RESET or ENVELOPE_RETRIGGER_LEGATO
+///   (a stolen voice restarts from its current level instead of clicking to 0)
+/// * `velocity_to_level` - 0.0-1.0: how much soft notes lower the envelope
+/// * `velocity_to_attack` - 0.0-1.0: how much soft notes lengthen the attack
+///   (up to 4x at zero velocity)
+///
+/// Until this is called, both envelopes use a linear attack and release and
+/// an exponential decay, with reset retriggering and no velocity sensitivity.
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, an unknown envelope or
+/// retrigger mode, or a non-finite value. Out-of-range amounts are clamped
+/// and recorded as a warning.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn gooey_engine_poly_set_adsr_ex(
+    engine: *mut GooeyEngine,
+    envelope: u32,
+    attack_curve: f32,
+    decay_curve: f32,
+    release_curve: f32,
+    retrigger: u32,
+    velocity_to_level: f32,
+    velocity_to_attack: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_poly_set_adsr_ex";
     if engine.is_null() {
-        return;
+        return null_engine(FN);
     }
-    let engine = &mut *engine;
-    let value = value.clamp(0.0, 1.0);
-
-    match param {
-        0 => engine.poly_synth.params.osc_shape.set_target(value),
-        1 => engine.poly_synth.params.detune_amount.set_target(value),
-        2 => engine.poly_synth.params.filter_cutoff.set_target(value),
-        3 => engine.poly_synth.params.filter_resonance.set_target(value),
-        4 => engine.poly_synth.params.filter_env_amount.set_target(value),
-        5 => engine.poly_synth.params.amp_attack.set_target(value),
-        6 => engine.poly_synth.params.amp_decay.set_target(value),
-        7 => engine.poly_synth.params.amp_sustain.set_target(value),
-        8 => engine.poly_synth.params.amp_release.set_target(value),
-        9 => engine.poly_synth.params.filter_attack.set_target(value),
-        10 => engine.poly_synth.params.filter_decay.set_target(value),
-        11 => engine.poly_synth.params.filter_sustain.set_target(value),
-        12 => engine.poly_synth.params.filter_release.set_target(value),
-        13 => engine.poly_synth.params.volume.set_target(value),
-        _ => {}
+    if envelope > POLY_ENVELOPE_FILTER {
+        return fail(
+            GooeyResult::InvalidParam,
+            format!("{FN}: unknown envelope {envelope}"),
+        );
+    }
+    let retrigger = match retrigger {
+        ENVELOPE_RETRIGGER_RESET => RetriggerMode::Reset,
+        ENVELOPE_RETRIGGER_LEGATO => RetriggerMode::Legato,
+        _ => {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: unknown retrigger mode {retrigger}"),
+            )
+        }
+    };
+    let mut values = [
+        ("attack_curve", attack_curve, -1.0),
+        ("decay_curve", decay_curve, -1.0),
+        ("release_curve", release_curve, -1.0),
+        ("velocity_to_level", velocity_to_level, 0.0),
+        ("velocity_to_attack", velocity_to_attack, 0.0),
+    ];
+    for (name, value, min) in &mut values {
+        if !value.is_finite() {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: {name} value {value} is not finite"),
+            );
+        }
+        let clamped = value.clamp(*min, 1.0);
+        if clamped != *value {
+            warn(format!(
+                "{FN}: {name} value {value} is outside {min}..=1; clamped to {clamped}"
+            ));
+            *value = clamped;
+        }
     }
+    let curve = |amount: f32| {
+        if amount == 0.0 {
+            EnvelopeCurve::Linear
+        } else {
+            EnvelopeCurve::Curved(amount)
+        }
+    };
+    let shape = EnvelopeShape {
+        attack_curve: curve(values[0].1),
+        decay_curve: curve(values[1].1),
+        release_curve: curve(values[2].1),
+        retrigger,
+        velocity_to_level: values[3].1,
+        velocity_to_attack: values[4].1,
+    };
+    let synth = &mut (*engine).poly_synth;
+    if envelope == POLY_ENVELOPE_AMP {
+        synth.set_amp_shape(shape);
+    } else {
+        synth.set_filter_shape(shape);
+    }
+    GooeyResult::Ok
 }
 
 /// Query how many voicings are available for a given chord quality.
@@ -5946,6 +16214,106 @@ pub unsafe extern "C" fn gooey_engine_poly_available_voicing_count(
     available_voicings(&chords[degree].quality).len() as u32
 }
 
+// ---------------------------------------------------------------------------
+// Scale quantization
+// ---------------------------------------------------------------------------
+
+/// Snap sequenced per-step notes to a key.
+///
+/// While enabled, every per-step MIDI note is moved to the nearest note of
+/// `scale_type` on `root` as it triggers (ties resolve downward). Stored
+/// patterns are not modified, so turning quantization off restores them.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `root` - Root note (0=C, 1=C#, ... 11=B)
+/// * `scale_type` - Scale (SCALE_* constants, below SCALE_COUNT)
+///
+/// # Returns
+/// `GooeyResult::Ok`, or an error for a null engine, a root above 11, or an
+/// unknown scale.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_scale_quantize(
+    engine: *mut GooeyEngine,
+    root: u32,
+    scale_type: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_scale_quantize";
+    let Some(engine) = engine.as_ref() else {
+        return null_engine(FN);
+    };
+    if root > 11 {
+        return fail(
+            GooeyResult::InvalidValue,
+            format!("{FN}: root {root} is not 0-11"),
+        );
+    }
+    if scale_type >= SCALE_COUNT {
+        return fail(
+            GooeyResult::InvalidValue,
+            format!("{FN}: unknown scale {scale_type}"),
+        );
+    }
+    engine
+        .scale_quantize
+        .store(root | (scale_type << 8), Ordering::Relaxed);
+    GooeyResult::Ok
+}
+
+/// Turn scale quantization off; per-step notes play exactly as programmed.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_clear_scale_quantize(engine: *mut GooeyEngine) {
+    if let Some(engine) = engine.as_ref() {
+        engine
+            .scale_quantize
+            .store(SCALE_QUANTIZE_OFF, Ordering::Relaxed);
+    }
+}
+
+/// Get the root of the quantization key (0-11), or SCALE_QUANTIZE_OFF.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_scale_quantize_root(engine: *const GooeyEngine) -> u32 {
+    engine
+        .as_ref()
+        .and_then(|e| e.scale_quantize())
+        .map_or(SCALE_QUANTIZE_OFF, |(root, _)| root.to_index() as u32)
+}
+
+/// Get the scale of the quantization key (SCALE_*), or SCALE_QUANTIZE_OFF.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_scale_quantize_scale(engine: *const GooeyEngine) -> u32 {
+    engine
+        .as_ref()
+        .and_then(|e| e.scale_quantize())
+        .map_or(SCALE_QUANTIZE_OFF, |(_, scale)| {
+            Scale::ALL.iter().position(|s| *s == scale).unwrap_or(0) as u32
+        })
+}
+
+/// Snap a MIDI note to the nearest note of a key, without touching any engine.
+///
+/// Lets a UI preview what quantization will do (e.g. to grey out piano-roll
+/// rows). Returns `midi_note` unchanged for an unknown scale.
+#[no_mangle]
+pub extern "C" fn gooey_engine_quantize_note(midi_note: u8, root: u32, scale_type: u32) -> u8 {
+    match Scale::ALL.get(scale_type as usize) {
+        Some(&scale) => quantize_to_scale(midi_note, root_from_id(root), scale),
+        None => midi_note,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Granulator
 // ---------------------------------------------------------------------------
@@ -5975,15 +16343,18 @@ pub unsafe extern "C" fn gooey_engine_granulator_set_buffer(
     if engine.is_null() || samples.is_null() || len == 0 {
         return false;
     }
-    let engine = &mut *engine;
     let slice = slice::from_raw_parts(samples, len as usize);
     let owned = slice.to_vec();
     match SampleBuffer::from_mono(owned, sample_rate) {
         Ok(buffer) => {
-            engine.granulator.set_buffer(buffer);
+            // The replaced buffer is freed here, not in the edit
+            let _replaced = edit(engine, || (*engine).granulator.set_buffer(buffer));
             true
         }
-        Err(_) => false,
+        Err(error) => {
+            fail_with("gooey_engine_granulator_set_buffer", error);
+            false
+        }
     }
 }
 
@@ -6005,25 +16376,27 @@ pub unsafe extern "C" fn gooey_engine_granulator_set_buffer(
 /// is addressed in the mixer graph as `SOURCE_SAMPLER_BASE + rack_id`.
 #[no_mangle]
 pub unsafe extern "C" fn gooey_engine_sampler_register(engine: *mut GooeyEngine) -> i32 {
-    let Some(engine) = engine.as_mut() else {
-        return -1;
-    };
-    let Some(index) = engine.samplers.iter().position(Option::is_none) else {
-        return -1;
-    };
-    engine.samplers[index] = Some(SamplerRack::new(
-        engine.sample_rate,
-        engine.bpm,
-        format!("sampler-{index}"),
-    ));
-    if !engine
-        .graph
-        .register_source(SOURCE_SAMPLER_BASE + index as u32)
-    {
-        engine.samplers[index] = None;
-        return -1;
-    }
-    index as i32
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return -1;
+        };
+        let Some(index) = engine.samplers.iter().position(Option::is_none) else {
+            return -1;
+        };
+        engine.samplers[index] = Some(SamplerRack::new(
+            engine.sample_rate,
+            engine.bpm,
+            format!("sampler-{index}"),
+        ));
+        if !engine
+            .graph
+            .register_source(SOURCE_SAMPLER_BASE + index as u32)
+        {
+            engine.samplers[index] = None;
+            return -1;
+        }
+        index as i32
+    })
 }
 
 /// Return a registered rack's mixer source ID, or `u32::MAX` for an invalid rack.
@@ -6050,26 +16423,109 @@ pub unsafe extern "C" fn gooey_engine_sampler_set_slot_buffer(
     channels: u32,
     sample_rate: f32,
 ) -> bool {
-    if samples.is_null() {
+    if samples.is_null() || engine.is_null() {
         return false;
     }
-    let Some(engine) = engine.as_mut() else {
-        return false;
-    };
     let channels = channels as usize;
     let frames = frames as usize;
     let Some(count) = frames.checked_mul(channels) else {
         return false;
     };
+    // Copied before the edit, so the audio thread only moves it in
     let data = slice::from_raw_parts(samples, count);
     let Ok(buffer) = SamplerBuffer::from_interleaved(data, frames, channels, sample_rate) else {
         return false;
     };
-    engine
-        .samplers
-        .get_mut(rack as usize)
-        .and_then(Option::as_mut)
-        .is_some_and(|rack| rack.set_buffer(slot as usize, buffer))
+    load_sampler_slot(engine, rack, slot, buffer)
+}
+
+/// Put `buffer` in a sampler slot with an edit. What the slot held before is
+/// handed out of the edit and freed on the calling thread. False for a null
+/// engine or a bad rack or slot.
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`
+unsafe fn load_sampler_slot(
+    engine: *mut GooeyEngine,
+    rack: u32,
+    slot: u32,
+    buffer: SamplerBuffer,
+) -> bool {
+    if engine.is_null() {
+        return false;
+    }
+    let mut buffer = Some(buffer);
+    let replaced = edit(engine, || {
+        let rack = (*engine).samplers.get_mut(rack as usize)?.as_mut()?;
+        let replaced = rack.take_slot(slot as usize)?;
+        rack.set_buffer(slot as usize, buffer.take()?);
+        Some(replaced)
+    });
+    replaced.is_some()
+}
+
+/// Decode a WAV, FLAC or Ogg Vorbis file into a sampler slot. The format is
+/// recognised from the file contents; files with more than two channels keep
+/// the first two. Decoding runs on the calling thread. Returns false for a
+/// bad rack or slot, or a file that can't be read or decoded.
+///
+/// # Safety
+/// - `engine` must be null or a valid pointer returned by `gooey_engine_new`.
+/// - `path` must be null or a valid null-terminated UTF-8 string.
+#[cfg(feature = "decode")]
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sampler_load_slot_file(
+    engine: *mut GooeyEngine,
+    rack: u32,
+    slot: u32,
+    path: *const c_char,
+) -> bool {
+    if path.is_null() {
+        return false;
+    }
+    let Ok(path) = CStr::from_ptr(path).to_str() else {
+        return false;
+    };
+    let buffer = match crate::decode::decode_file(path).and_then(|audio| audio.to_sampler_buffer())
+    {
+        Ok(buffer) => buffer,
+        Err(error) => {
+            fail_with("gooey_engine_sampler_load_slot_file", error);
+            return false;
+        }
+    };
+    // Decoded before the edit, so it never holds up a render
+    load_sampler_slot(engine, rack, slot, buffer)
+}
+
+/// Decode `len` bytes of an in-memory WAV, FLAC or Ogg Vorbis file into a
+/// sampler slot, as `gooey_engine_sampler_load_slot_file` does for paths.
+///
+/// # Safety
+/// - `engine` must be null or a valid pointer returned by `gooey_engine_new`.
+/// - `data` must be null or point to `len` readable bytes.
+#[cfg(feature = "decode")]
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sampler_load_slot_bytes(
+    engine: *mut GooeyEngine,
+    rack: u32,
+    slot: u32,
+    data: *const u8,
+    len: usize,
+) -> bool {
+    if data.is_null() {
+        return false;
+    }
+    let bytes = slice::from_raw_parts(data, len);
+    let buffer =
+        match crate::decode::decode_bytes(bytes).and_then(|audio| audio.to_sampler_buffer()) {
+            Ok(buffer) => buffer,
+            Err(error) => {
+                fail_with("gooey_engine_sampler_load_slot_bytes", error);
+                return false;
+            }
+        };
+    load_sampler_slot(engine, rack, slot, buffer)
 }
 
 #[no_mangle]
@@ -6078,11 +16534,15 @@ pub unsafe extern "C" fn gooey_engine_sampler_clear_slot(
     rack: u32,
     slot: u32,
 ) -> bool {
-    engine
-        .as_mut()
-        .and_then(|engine| engine.samplers.get_mut(rack as usize))
-        .and_then(Option::as_mut)
-        .is_some_and(|rack| rack.clear_slot(slot as usize))
+    // The slot's contents are freed here, not in the edit
+    let cleared = edit(engine, || {
+        engine
+            .as_mut()
+            .and_then(|engine| engine.samplers.get_mut(rack as usize))
+            .and_then(Option::as_mut)
+            .and_then(|rack| rack.take_slot(slot as usize))
+    });
+    cleared.is_some()
 }
 
 /// Return whether a slot contains a buffer.
@@ -6096,8 +16556,7 @@ pub unsafe extern "C" fn gooey_engine_sampler_slot_is_loaded(
         .as_ref()
         .and_then(|engine| engine.samplers.get(rack as usize))
         .and_then(Option::as_ref)
-        .and_then(|rack| rack.slot(slot as usize))
-        .is_some()
+        .is_some_and(|rack| rack.is_loaded(slot as usize))
 }
 
 #[no_mangle]
@@ -6110,8 +16569,8 @@ pub unsafe extern "C" fn gooey_engine_sampler_slot_frames(
         .as_ref()
         .and_then(|engine| engine.samplers.get(rack as usize))
         .and_then(Option::as_ref)
-        .and_then(|rack| rack.slot(slot as usize))
-        .map_or(0, |buffer| buffer.frames() as u32)
+        .and_then(|rack| rack.slot_format(slot as usize))
+        .map_or(0, |(frames, _, _)| frames as u32)
 }
 
 /// Return a slot's channel count (1 or 2), or 0 when it is not loaded.
@@ -6125,13 +16584,194 @@ pub unsafe extern "C" fn gooey_engine_sampler_slot_channels(
         .as_ref()
         .and_then(|engine| engine.samplers.get(rack as usize))
         .and_then(Option::as_ref)
-        .and_then(|rack| rack.slot(slot as usize))
-        .map_or(0, |buffer| buffer.channels() as u32)
+        .and_then(|rack| rack.slot_format(slot as usize))
+        .map_or(0, |(_, channels, _)| channels as u32)
+}
+
+/// Return a slot's source sample rate, or 0.0 when it is not loaded.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sampler_slot_sample_rate(
+    engine: *const GooeyEngine,
+    rack: u32,
+    slot: u32,
+) -> f32 {
+    engine
+        .as_ref()
+        .and_then(|engine| engine.samplers.get(rack as usize))
+        .and_then(Option::as_ref)
+        .and_then(|rack| rack.slot_format(slot as usize))
+        .map_or(0.0, |(_, _, sample_rate)| sample_rate)
+}
+
+/// Stream a slot from a WAV file on disk instead of loading it into memory,
+/// for long backing loops. The first second stays resident so triggers start
+/// at once; a prefetch thread reads the rest ahead of playback. With
+/// `looping` the file repeats until the slot is stopped. Returns false for a
+/// bad rack or slot or a file that can't be opened. Streamed slots don't
+/// follow the tempo, transpose, or slice. Not available on WASM, which loads
+/// slots with `gooey_engine_sampler_set_slot_buffer` only.
+///
+/// # Safety
+/// - `engine` must be null or a valid pointer returned by `gooey_engine_new`.
+/// - `path` must be null or a valid null-terminated UTF-8 string.
+#[cfg(all(feature = "stream", not(target_arch = "wasm32")))]
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sampler_stream_slot_file(
+    engine: *mut GooeyEngine,
+    rack: u32,
+    slot: u32,
+    path: *const c_char,
+    looping: bool,
+) -> bool {
+    if path.is_null() || engine.is_null() {
+        return false;
+    }
+    let Ok(path) = CStr::from_ptr(path).to_str() else {
+        return false;
+    };
+    let has_rack = edit(engine, || {
+        (*engine)
+            .samplers
+            .get(rack as usize)
+            .is_some_and(Option::is_some)
+    });
+    if !has_rack || slot >= SAMPLER_SLOT_COUNT {
+        return false;
+    }
+    // Opened before the edit, so the file is read off the audio thread
+    let stream: Box<dyn crate::instruments::SlotStream> =
+        match crate::instruments::DiskStream::open(path, (*engine).sample_rate, looping) {
+            Ok(stream) => Box::new(stream),
+            Err(error) => {
+                fail_with("gooey_engine_sampler_stream_slot_file", error);
+                return false;
+            }
+        };
+    // A stream the slot held is stopped and joined here, not in the edit
+    let mut stream = Some(stream);
+    let replaced = edit(engine, || {
+        let rack = (*engine).samplers.get_mut(rack as usize)?.as_mut()?;
+        let replaced = rack.take_slot(slot as usize)?;
+        rack.set_stream(slot as usize, stream.take()?);
+        Some(replaced)
+    });
+    replaced.is_some()
+}
+
+/// Return whether a slot plays from a disk stream.
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sampler_slot_is_streamed(
+    engine: *const GooeyEngine,
+    rack: u32,
+    slot: u32,
+) -> bool {
+    engine
+        .as_ref()
+        .and_then(|engine| engine.samplers.get(rack as usize))
+        .and_then(Option::as_ref)
+        .is_some_and(|rack| rack.is_streamed(slot as usize))
+}
+
+/// Number of times a streamed slot ran out of prefetched audio and played
+/// silence instead (each dropout counts once). 0 for in-memory slots.
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sampler_get_stream_underruns(
+    engine: *const GooeyEngine,
+    rack: u32,
+    slot: u32,
+) -> u64 {
+    engine
+        .as_ref()
+        .and_then(|engine| engine.samplers.get(rack as usize))
+        .and_then(Option::as_ref)
+        .and_then(|rack| rack.stream_underruns(slot as usize))
+        .unwrap_or(0)
+}
+
+/// Make a loaded slot follow the engine tempo. `source_bpm` is the tempo the
+/// material was recorded at; it is time-stretched (pitch unchanged) to the
+/// current BPM now and again on every `gooey_engine_set_bpm`. Pass 0.0 to play
+/// the slot at its own speed. Stretching allocates and runs on the calling
+/// thread. Returns false for a bad rack, unloaded slot, or negative BPM.
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sampler_set_slot_tempo(
+    engine: *mut GooeyEngine,
+    rack: u32,
+    slot: u32,
+    source_bpm: f32,
+) -> bool {
+    let set = edit(engine, || {
+        let source_bpm = (source_bpm != 0.0).then_some(source_bpm);
+        engine
+            .as_mut()
+            .and_then(|engine| engine.samplers.get_mut(rack as usize))
+            .and_then(Option::as_mut)
+            .is_some_and(|rack| rack.retempo_slot(slot as usize, source_bpm))
+    });
+    if set {
+        restretch_samplers(engine);
+    }
+    set
+}
+
+/// Return a slot's source tempo, or 0.0 when it doesn't follow the BPM.
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sampler_get_slot_tempo(
+    engine: *const GooeyEngine,
+    rack: u32,
+    slot: u32,
+) -> f32 {
+    engine
+        .as_ref()
+        .and_then(|engine| engine.samplers.get(rack as usize))
+        .and_then(Option::as_ref)
+        .and_then(|rack| rack.slot_tempo(slot as usize))
+        .unwrap_or(0.0)
 }
 
-/// Return a slot's source sample rate, or 0.0 when it is not loaded.
+/// Transpose a loaded slot by `semitones` (clamped to +/-24) without changing
+/// its length. Rendered on the calling thread, like the tempo stretch.
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`
 #[no_mangle]
-pub unsafe extern "C" fn gooey_engine_sampler_slot_sample_rate(
+pub unsafe extern "C" fn gooey_engine_sampler_set_slot_pitch(
+    engine: *mut GooeyEngine,
+    rack: u32,
+    slot: u32,
+    semitones: f32,
+) -> bool {
+    let set = edit(engine, || {
+        engine
+            .as_mut()
+            .and_then(|engine| engine.samplers.get_mut(rack as usize))
+            .and_then(Option::as_mut)
+            .is_some_and(|rack| rack.retune_slot(slot as usize, semitones))
+    });
+    if set {
+        restretch_samplers(engine);
+    }
+    set
+}
+
+/// Return a slot's pitch shift in semitones, or 0.0 when it is not loaded.
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sampler_get_slot_pitch(
     engine: *const GooeyEngine,
     rack: u32,
     slot: u32,
@@ -6140,8 +16780,8 @@ pub unsafe extern "C" fn gooey_engine_sampler_slot_sample_rate(
         .as_ref()
         .and_then(|engine| engine.samplers.get(rack as usize))
         .and_then(Option::as_ref)
-        .and_then(|rack| rack.slot(slot as usize))
-        .map_or(0.0, |buffer| buffer.sample_rate())
+        .and_then(|rack| rack.slot_pitch(slot as usize))
+        .unwrap_or(0.0)
 }
 
 /// Trigger a loaded pad now and stamp it into the shared performance clip when
@@ -6153,18 +16793,20 @@ pub unsafe extern "C" fn gooey_engine_sampler_trigger(
     slot: u32,
     velocity: f32,
 ) -> bool {
-    let Some(engine) = engine.as_mut() else {
-        return false;
-    };
-    let fired = engine
-        .samplers
-        .get_mut(rack as usize)
-        .and_then(Option::as_mut)
-        .is_some_and(|sampler| sampler.trigger(slot as usize, velocity));
-    if fired {
-        engine.performance.record_sampler_hit(rack, slot, velocity);
-    }
-    fired
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return false;
+        };
+        let fired = engine
+            .samplers
+            .get_mut(rack as usize)
+            .and_then(Option::as_mut)
+            .is_some_and(|sampler| sampler.trigger(slot as usize, velocity));
+        if fired {
+            engine.performance.record_sampler_hit(rack, slot, velocity);
+        }
+        fired
+    })
 }
 
 /// Configure one 16-step sampler pattern cell. An enabled cell triggers its
@@ -6178,11 +16820,15 @@ pub unsafe extern "C" fn gooey_engine_sampler_set_step(
     slot: u32,
     velocity: f32,
 ) -> bool {
-    engine
-        .as_mut()
-        .and_then(|engine| engine.samplers.get_mut(rack as usize))
-        .and_then(Option::as_mut)
-        .is_some_and(|sampler| sampler.set_step(step as usize, enabled, slot as usize, velocity))
+    edit(engine, || {
+        engine
+            .as_mut()
+            .and_then(|engine| engine.samplers.get_mut(rack as usize))
+            .and_then(Option::as_mut)
+            .is_some_and(|sampler| {
+                sampler.set_step(step as usize, enabled, slot as usize, velocity)
+            })
+    })
 }
 
 /// Queue a sampler pattern to start at the next selected shared-transport
@@ -6194,16 +16840,20 @@ pub unsafe extern "C" fn gooey_engine_sampler_start_pattern(
     rack: u32,
     quantization: u32,
 ) -> bool {
-    let Some(engine) = engine.as_mut() else { return false; };
-    let Some(quantization) = LaunchQuantization::from_id(quantization) else {
-        return false;
-    };
-    let target = engine.mixer.quantized_target(quantization);
-    engine
-        .samplers
-        .get_mut(rack as usize)
-        .and_then(Option::as_mut)
-        .is_some_and(|rack| rack.schedule_start(target))
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return false;
+        };
+        let Some(quantization) = LaunchQuantization::from_id(quantization) else {
+            return false;
+        };
+        let target = engine.mixer.quantized_target(quantization);
+        engine
+            .samplers
+            .get_mut(rack as usize)
+            .and_then(Option::as_mut)
+            .is_some_and(|rack| rack.schedule_start(target))
+    })
 }
 
 /// Stop a sampler pattern immediately and cancel a queued pattern start.
@@ -6212,12 +16862,17 @@ pub unsafe extern "C" fn gooey_engine_sampler_stop_pattern(
     engine: *mut GooeyEngine,
     rack: u32,
 ) -> bool {
-    engine
-        .as_mut()
-        .and_then(|engine| engine.samplers.get_mut(rack as usize))
-        .and_then(Option::as_mut)
-        .map(|rack| { rack.stop_pattern(); true })
-        .unwrap_or(false)
+    edit(engine, || {
+        engine
+            .as_mut()
+            .and_then(|engine| engine.samplers.get_mut(rack as usize))
+            .and_then(Option::as_mut)
+            .map(|rack| {
+                rack.stop_pattern();
+                true
+            })
+            .unwrap_or(false)
+    })
 }
 
 /// Cancel a queued sampler-pattern start without stopping an already running rack.
@@ -6226,12 +16881,17 @@ pub unsafe extern "C" fn gooey_engine_sampler_cancel_pattern_start(
     engine: *mut GooeyEngine,
     rack: u32,
 ) -> bool {
-    engine
-        .as_mut()
-        .and_then(|engine| engine.samplers.get_mut(rack as usize))
-        .and_then(Option::as_mut)
-        .map(|rack| { rack.cancel_pending_start(); true })
-        .unwrap_or(false)
+    edit(engine, || {
+        engine
+            .as_mut()
+            .and_then(|engine| engine.samplers.get_mut(rack as usize))
+            .and_then(Option::as_mut)
+            .map(|rack| {
+                rack.cancel_pending_start();
+                true
+            })
+            .unwrap_or(false)
+    })
 }
 
 /// Return a rack's pending start beat, or -1.0 when it is invalid or not queued.
@@ -6287,22 +16947,208 @@ pub unsafe extern "C" fn gooey_engine_sampler_get_step(
     true
 }
 
+/// Chop a loaded slot into `count` equal slices (at most
+/// `SAMPLER_MAX_SLICES`); 0 removes the slicing.
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sampler_slice_equal(
+    engine: *mut GooeyEngine,
+    rack: u32,
+    slot: u32,
+    count: u32,
+) -> bool {
+    edit(engine, || {
+        engine
+            .as_mut()
+            .and_then(|engine| engine.samplers.get_mut(rack as usize))
+            .and_then(Option::as_mut)
+            .is_some_and(|rack| rack.slice_equal(slot as usize, count as usize))
+    })
+}
+
+/// Slice a loaded slot at its detected transients. `sensitivity` (0-1) lowers
+/// the level jump that counts as a new hit. Returns the slice count, or 0 for
+/// a bad rack or unloaded slot.
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sampler_slice_transients(
+    engine: *mut GooeyEngine,
+    rack: u32,
+    slot: u32,
+    sensitivity: f32,
+) -> u32 {
+    edit(engine, || {
+        engine
+            .as_mut()
+            .and_then(|engine| engine.samplers.get_mut(rack as usize))
+            .and_then(Option::as_mut)
+            .and_then(|rack| rack.slice_transients(slot as usize, sensitivity))
+            .map_or(0, |count| count as u32)
+    })
+}
+
+/// Slice a loaded slot at `count` start frames from a waveform UI. Markers
+/// may be unordered; frame 0 is always a slice start. Fails for markers past
+/// the end of the slot or more than `SAMPLER_MAX_SLICES` slices.
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`;
+/// `markers` must point to `count` values (it may be null when `count` is 0)
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sampler_set_slice_markers(
+    engine: *mut GooeyEngine,
+    rack: u32,
+    slot: u32,
+    markers: *const u32,
+    count: u32,
+) -> bool {
+    edit(engine, || {
+        let markers: Vec<usize> = if count == 0 {
+            Vec::new()
+        } else if markers.is_null() {
+            return false;
+        } else {
+            slice::from_raw_parts(markers, count as usize)
+                .iter()
+                .map(|&marker| marker as usize)
+                .collect()
+        };
+        engine
+            .as_mut()
+            .and_then(|engine| engine.samplers.get_mut(rack as usize))
+            .and_then(Option::as_mut)
+            .is_some_and(|rack| rack.set_slice_markers(slot as usize, &markers))
+    })
+}
+
+/// Return how many slices a slot has, or 0 when it isn't sliced.
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sampler_get_slice_count(
+    engine: *const GooeyEngine,
+    rack: u32,
+    slot: u32,
+) -> u32 {
+    engine
+        .as_ref()
+        .and_then(|engine| engine.samplers.get(rack as usize))
+        .and_then(Option::as_ref)
+        .map_or(0, |rack| rack.slice_markers(slot as usize).len() as u32)
+}
+
+/// Return a slice's start frame in the loaded PCM (for drawing markers over
+/// the waveform), or `u32::MAX` for a slice the slot doesn't have.
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sampler_get_slice_marker(
+    engine: *const GooeyEngine,
+    rack: u32,
+    slot: u32,
+    slice: u32,
+) -> u32 {
+    engine
+        .as_ref()
+        .and_then(|engine| engine.samplers.get(rack as usize))
+        .and_then(Option::as_ref)
+        .and_then(|rack| {
+            rack.slice_markers(slot as usize)
+                .get(slice as usize)
+                .copied()
+        })
+        .map_or(u32::MAX, |marker| marker as u32)
+}
+
+/// Trigger one slice of a loaded slot now. Slice hits are not stamped into
+/// the performance clip. Returns false for a bad rack, unloaded slot, or a
+/// slice the slot doesn't have.
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sampler_trigger_slice(
+    engine: *mut GooeyEngine,
+    rack: u32,
+    slot: u32,
+    slice: u32,
+    velocity: f32,
+) -> bool {
+    edit(engine, || {
+        engine
+            .as_mut()
+            .and_then(|engine| engine.samplers.get_mut(rack as usize))
+            .and_then(Option::as_mut)
+            .is_some_and(|rack| rack.trigger_slice(slot as usize, Some(slice as usize), velocity))
+    })
+}
+
+/// Make a pattern step play one slice of its slot; a negative `slice` plays
+/// the whole slot again. A step naming a slice its slot doesn't have stays
+/// silent.
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sampler_set_step_slice(
+    engine: *mut GooeyEngine,
+    rack: u32,
+    step: u32,
+    slice: i32,
+) -> bool {
+    edit(engine, || {
+        let slice = usize::try_from(slice).ok();
+        engine
+            .as_mut()
+            .and_then(|engine| engine.samplers.get_mut(rack as usize))
+            .and_then(Option::as_mut)
+            .is_some_and(|rack| rack.set_step_slice(step as usize, slice))
+    })
+}
+
+/// Return the slice a pattern step plays, or -1 for the whole slot (and for
+/// a bad rack or step).
+///
+/// # Safety
+/// `engine` must be null or a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_sampler_get_step_slice(
+    engine: *const GooeyEngine,
+    rack: u32,
+    step: u32,
+) -> i32 {
+    engine
+        .as_ref()
+        .and_then(|engine| engine.samplers.get(rack as usize))
+        .and_then(Option::as_ref)
+        .and_then(|rack| rack.step_slice(step as usize))
+        .map_or(-1, |slice| slice as i32)
+}
+
 /// Restore the default graph layout: Drums, Bass, Synth, Loops.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`.
 #[no_mangle]
 pub unsafe extern "C" fn gooey_engine_mixer_reset_default_layout(engine: *mut GooeyEngine) {
-    if let Some(engine) = engine.as_mut() {
-        engine.graph = MixerGraph::with_default_layout(engine.sample_rate, engine.bpm);
-        for (rack, sampler) in engine.samplers.iter().enumerate() {
-            if sampler.is_some() {
-                let _ = engine
-                    .graph
-                    .register_source(SOURCE_SAMPLER_BASE + rack as u32);
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            engine.graph = MixerGraph::with_default_layout(engine.sample_rate, engine.bpm);
+            for (rack, sampler) in engine.samplers.iter().enumerate() {
+                if sampler.is_some() {
+                    let _ = engine
+                        .graph
+                        .register_source(SOURCE_SAMPLER_BASE + rack as u32);
+                }
             }
         }
-    }
+    })
 }
 
 /// Clear every graph track and source route. All graph-routed sources become silent.
@@ -6311,9 +17157,11 @@ pub unsafe extern "C" fn gooey_engine_mixer_reset_default_layout(engine: *mut Go
 /// `engine` must be a valid pointer returned by `gooey_engine_new`.
 #[no_mangle]
 pub unsafe extern "C" fn gooey_engine_mixer_clear_layout(engine: *mut GooeyEngine) {
-    if let Some(engine) = engine.as_mut() {
-        engine.graph.reset();
-    }
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            engine.graph.reset();
+        }
+    })
 }
 
 /// Add a named mixer track. Returns the new track index, or -1 on failure.
@@ -6325,13 +17173,13 @@ pub unsafe extern "C" fn gooey_engine_mixer_add_track(
     engine: *mut GooeyEngine,
     name: *const c_char,
 ) -> i32 {
-    match (engine.as_mut(), name.as_ref()) {
+    edit(engine, || match (engine.as_mut(), name.as_ref()) {
         (Some(engine), Some(_)) => {
             let name = CStr::from_ptr(name).to_owned();
             engine.graph.add_track(name) as i32
         }
         _ => -1,
-    }
+    })
 }
 
 /// Return the number of mixer graph tracks.
@@ -6373,13 +17221,13 @@ pub unsafe extern "C" fn gooey_engine_mixer_set_track_name(
     track: u32,
     name: *const c_char,
 ) -> bool {
-    match (engine.as_mut(), name.as_ref()) {
+    edit(engine, || match (engine.as_mut(), name.as_ref()) {
         (Some(engine), Some(_)) => {
             let name = CStr::from_ptr(name).to_owned();
             engine.graph.set_track_name(track as usize, name)
         }
         _ => false,
-    }
+    })
 }
 
 /// Find the first track with `name`. Returns -1 if none is found.
@@ -6413,10 +17261,10 @@ pub unsafe extern "C" fn gooey_engine_mixer_route_source(
     source: u32,
     track: u32,
 ) -> bool {
-    match engine.as_mut() {
+    edit(engine, || match engine.as_mut() {
         Some(engine) => engine.graph.route(source, track as usize),
         None => false,
-    }
+    })
 }
 
 /// Unroute an engine source. Returns false for invalid or already-unrouted sources.
@@ -6428,10 +17276,10 @@ pub unsafe extern "C" fn gooey_engine_mixer_unroute_source(
     engine: *mut GooeyEngine,
     source: u32,
 ) -> bool {
-    match engine.as_mut() {
+    edit(engine, || match engine.as_mut() {
         Some(engine) => engine.graph.unroute(source),
         None => false,
-    }
+    })
 }
 
 /// Return the track a source is routed to, or -1 if invalid/unrouted.
@@ -6459,9 +17307,11 @@ pub unsafe extern "C" fn gooey_engine_mixer_set_track_gain(
     track: u32,
     gain: f32,
 ) {
-    if let Some(engine) = engine.as_mut() {
-        engine.graph.set_track_gain(track as usize, gain);
-    }
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            engine.graph.set_track_gain(track as usize, gain);
+        }
+    })
 }
 
 /// Get a track fader gain, or 1.0 for null/bad track.
@@ -6488,9 +17338,11 @@ pub unsafe extern "C" fn gooey_engine_mixer_set_track_pan(
     track: u32,
     pan: f32,
 ) {
-    if let Some(engine) = engine.as_mut() {
-        engine.graph.set_track_pan(track as usize, pan);
-    }
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            engine.graph.set_track_pan(track as usize, pan);
+        }
+    })
 }
 
 /// Get a track stereo balance, or 0.5 for null/bad track.
@@ -6590,13 +17442,13 @@ pub unsafe extern "C" fn gooey_engine_track_effect_add(
     track: u32,
     effect_id: u32,
 ) -> i32 {
-    match engine.as_mut() {
+    edit(engine, || match engine.as_mut() {
         Some(engine) => engine
             .graph
             .effect_add(track as usize, effect_id)
             .map_or(-1, |slot| slot as i32),
         None => -1,
-    }
+    })
 }
 
 /// Remove an effect from a mixer track.
@@ -6609,10 +17461,10 @@ pub unsafe extern "C" fn gooey_engine_track_effect_remove(
     track: u32,
     slot: u32,
 ) -> bool {
-    match engine.as_mut() {
+    edit(engine, || match engine.as_mut() {
         Some(engine) => engine.graph.effect_remove(track as usize, slot as usize),
         None => false,
-    }
+    })
 }
 
 /// Move an effect within a mixer track's rack.
@@ -6626,14 +17478,14 @@ pub unsafe extern "C" fn gooey_engine_track_effect_move(
     slot: u32,
     new_position: u32,
 ) -> bool {
-    match engine.as_mut() {
+    edit(engine, || match engine.as_mut() {
         Some(engine) => {
             engine
                 .graph
                 .effect_move(track as usize, slot as usize, new_position as usize)
         }
         None => false,
-    }
+    })
 }
 
 /// Clear all effects from a mixer track.
@@ -6642,9 +17494,11 @@ pub unsafe extern "C" fn gooey_engine_track_effect_move(
 /// `engine` must be a valid pointer returned by `gooey_engine_new`.
 #[no_mangle]
 pub unsafe extern "C" fn gooey_engine_track_effect_clear(engine: *mut GooeyEngine, track: u32) {
-    if let Some(engine) = engine.as_mut() {
-        engine.graph.effect_clear(track as usize);
-    }
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            engine.graph.effect_clear(track as usize);
+        }
+    })
 }
 
 /// Set a parameter on a mixer track effect.
@@ -6744,27 +17598,34 @@ pub unsafe extern "C" fn gooey_engine_clip_load(
     sample_rate: f32,
     source_bpm: f32,
 ) -> bool {
-    if engine.is_null()
-        || samples.is_null()
-        || frames == 0
-        || channels == 0
-        || !source_bpm.is_finite()
-        || source_bpm <= 0.0
-        || column >= CLIP_COLUMN_COUNT
-        || row >= CLIP_ROW_COUNT
-    {
-        return false;
-    }
-    let Some(total) = (frames as usize).checked_mul(channels as usize) else {
-        return false;
-    };
-    let samples = slice::from_raw_parts(samples, total);
-    match StereoSampleBuffer::from_interleaved(samples, channels as usize, sample_rate) {
-        Ok(buffer) => (*engine)
-            .mixer
-            .clip_load(column as usize, row as usize, buffer, source_bpm),
-        Err(_) => false,
-    }
+    edit(engine, || {
+        if engine.is_null()
+            || samples.is_null()
+            || frames == 0
+            || channels == 0
+            || !source_bpm.is_finite()
+            || source_bpm <= 0.0
+            || column >= CLIP_COLUMN_COUNT
+            || row >= CLIP_ROW_COUNT
+        {
+            return false;
+        }
+        let Some(total) = (frames as usize).checked_mul(channels as usize) else {
+            return false;
+        };
+        let samples = slice::from_raw_parts(samples, total);
+        match StereoSampleBuffer::from_interleaved(samples, channels as usize, sample_rate) {
+            Ok(buffer) => {
+                (*engine)
+                    .mixer
+                    .clip_load(column as usize, row as usize, buffer, source_bpm)
+            }
+            Err(error) => {
+                fail_with("gooey_engine_clip_load", error);
+                false
+            }
+        }
+    })
 }
 
 /// Unload a slot. An active slot stops and disappears at the default boundary;
@@ -6778,9 +17639,11 @@ pub unsafe extern "C" fn gooey_engine_clip_unload(
     column: u32,
     row: u32,
 ) -> bool {
-    engine
-        .as_mut()
-        .is_some_and(|engine| engine.mixer.clip_unload(column as usize, row as usize))
+    edit(engine, || {
+        engine
+            .as_mut()
+            .is_some_and(|engine| engine.mixer.clip_unload(column as usize, row as usize))
+    })
 }
 
 /// Stop all grid-owned columns and unload every slot.
@@ -6789,9 +17652,11 @@ pub unsafe extern "C" fn gooey_engine_clip_unload(
 /// `engine` must be a valid pointer returned by `gooey_engine_new`.
 #[no_mangle]
 pub unsafe extern "C" fn gooey_engine_clip_clear(engine: *mut GooeyEngine) {
-    if let Some(engine) = engine.as_mut() {
-        engine.mixer.clip_clear();
-    }
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            engine.mixer.clip_clear();
+        }
+    })
 }
 
 /// Queue one clip for a musical boundary. Returns false for an invalid slot,
@@ -6806,15 +17671,17 @@ pub unsafe extern "C" fn gooey_engine_clip_launch(
     row: u32,
     quantization: u32,
 ) -> bool {
-    let Some(engine) = engine.as_mut() else {
-        return false;
-    };
-    let Some(quantization) = LaunchQuantization::from_id(quantization) else {
-        return false;
-    };
-    engine
-        .mixer
-        .clip_launch(column as usize, row as usize, quantization)
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return false;
+        };
+        let Some(quantization) = LaunchQuantization::from_id(quantization) else {
+            return false;
+        };
+        engine
+            .mixer
+            .clip_launch(column as usize, row as usize, quantization)
+    })
 }
 
 /// Queue one clip at an absolute future quarter-note position.
@@ -6828,10 +17695,12 @@ pub unsafe extern "C" fn gooey_engine_clip_launch_at_beat(
     row: u32,
     beat: f64,
 ) -> bool {
-    engine.as_mut().is_some_and(|engine| {
-        engine
-            .mixer
-            .clip_launch_at(column as usize, row as usize, beat)
+    edit(engine, || {
+        engine.as_mut().is_some_and(|engine| {
+            engine
+                .mixer
+                .clip_launch_at(column as usize, row as usize, beat)
+        })
     })
 }
 
@@ -6846,13 +17715,15 @@ pub unsafe extern "C" fn gooey_engine_clip_launch_scene(
     row: u32,
     quantization: u32,
 ) -> bool {
-    let Some(engine) = engine.as_mut() else {
-        return false;
-    };
-    let Some(quantization) = LaunchQuantization::from_id(quantization) else {
-        return false;
-    };
-    engine.mixer.clip_launch_scene(row as usize, quantization)
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return false;
+        };
+        let Some(quantization) = LaunchQuantization::from_id(quantization) else {
+            return false;
+        };
+        engine.mixer.clip_launch_scene(row as usize, quantization)
+    })
 }
 
 /// Queue an entire scene row at an absolute future beat.
@@ -6865,9 +17736,11 @@ pub unsafe extern "C" fn gooey_engine_clip_launch_scene_at_beat(
     row: u32,
     beat: f64,
 ) -> bool {
-    engine
-        .as_mut()
-        .is_some_and(|engine| engine.mixer.clip_launch_scene_at(row as usize, beat))
+    edit(engine, || {
+        engine
+            .as_mut()
+            .is_some_and(|engine| engine.mixer.clip_launch_scene_at(row as usize, beat))
+    })
 }
 
 /// Queue a column stop at a musical boundary.
@@ -6880,13 +17753,15 @@ pub unsafe extern "C" fn gooey_engine_clip_stop(
     column: u32,
     quantization: u32,
 ) -> bool {
-    let Some(engine) = engine.as_mut() else {
-        return false;
-    };
-    let Some(quantization) = LaunchQuantization::from_id(quantization) else {
-        return false;
-    };
-    engine.mixer.clip_stop(column as usize, quantization)
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return false;
+        };
+        let Some(quantization) = LaunchQuantization::from_id(quantization) else {
+            return false;
+        };
+        engine.mixer.clip_stop(column as usize, quantization)
+    })
 }
 
 /// Queue a column stop at an absolute future beat.
@@ -6899,9 +17774,11 @@ pub unsafe extern "C" fn gooey_engine_clip_stop_at_beat(
     column: u32,
     beat: f64,
 ) -> bool {
-    engine
-        .as_mut()
-        .is_some_and(|engine| engine.mixer.clip_stop_at(column as usize, beat))
+    edit(engine, || {
+        engine
+            .as_mut()
+            .is_some_and(|engine| engine.mixer.clip_stop_at(column as usize, beat))
+    })
 }
 
 /// Cancel one column's pending clip action without changing its active clip.
@@ -6910,9 +17787,11 @@ pub unsafe extern "C" fn gooey_engine_clip_stop_at_beat(
 /// `engine` must be a valid pointer returned by `gooey_engine_new`.
 #[no_mangle]
 pub unsafe extern "C" fn gooey_engine_clip_cancel(engine: *mut GooeyEngine, column: u32) {
-    if let Some(engine) = engine.as_mut() {
-        engine.mixer.clip_cancel(column as usize);
-    }
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            engine.mixer.clip_cancel(column as usize);
+        }
+    })
 }
 
 /// Cancel every pending clip action.
@@ -6921,9 +17800,11 @@ pub unsafe extern "C" fn gooey_engine_clip_cancel(engine: *mut GooeyEngine, colu
 /// `engine` must be a valid pointer returned by `gooey_engine_new`.
 #[no_mangle]
 pub unsafe extern "C" fn gooey_engine_clip_cancel_all(engine: *mut GooeyEngine) {
-    if let Some(engine) = engine.as_mut() {
-        engine.mixer.clip_cancel_all();
-    }
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            engine.mixer.clip_cancel_all();
+        }
+    })
 }
 
 /// Set the quantization used by active-slot replacement and unload.
@@ -6932,17 +17813,19 @@ pub unsafe extern "C" fn gooey_engine_clip_cancel_all(engine: *mut GooeyEngine)
 /// `engine` must be a valid pointer returned by `gooey_engine_new`.
 #[no_mangle]
 pub unsafe extern "C" fn gooey_engine_clip_set_default_quantization(
-    engine: *mut GooeyEngine,
-    quantization: u32,
-) -> bool {
-    let Some(engine) = engine.as_mut() else {
-        return false;
-    };
-    let Some(quantization) = LaunchQuantization::from_id(quantization) else {
-        return false;
-    };
-    engine.mixer.clip_set_default_quantization(quantization);
-    true
+    engine: *mut GooeyEngine,
+    quantization: u32,
+) -> bool {
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return false;
+        };
+        let Some(quantization) = LaunchQuantization::from_id(quantization) else {
+            return false;
+        };
+        engine.mixer.clip_set_default_quantization(quantization);
+        true
+    })
 }
 
 /// Return the current default `CLIP_QUANTIZE_*` value (bar for a null engine).
@@ -7079,25 +17962,27 @@ pub unsafe extern "C" fn gooey_engine_clip_set_trim(
     end: f64,
     quantization: u32,
 ) -> bool {
-    let Some(engine) = engine.as_mut() else {
-        return false;
-    };
-    if column >= CLIP_COLUMN_COUNT
-        || row >= CLIP_ROW_COUNT
-        || !start.is_finite()
-        || !end.is_finite()
-        || !(0.0..=1.0).contains(&start)
-        || !(0.0..=1.0).contains(&end)
-        || start == end
-    {
-        return false;
-    }
-    let Some(timing) = RetrimTiming::from_id(quantization) else {
-        return false;
-    };
-    engine
-        .mixer
-        .clip_set_trim(column as usize, row as usize, start, end, timing)
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return false;
+        };
+        if column >= CLIP_COLUMN_COUNT
+            || row >= CLIP_ROW_COUNT
+            || !start.is_finite()
+            || !end.is_finite()
+            || !(0.0..=1.0).contains(&start)
+            || !(0.0..=1.0).contains(&end)
+            || start == end
+        {
+            return false;
+        }
+        let Some(timing) = RetrimTiming::from_id(quantization) else {
+            return false;
+        };
+        engine
+            .mixer
+            .clip_set_trim(column as usize, row as usize, start, end, timing)
+    })
 }
 
 /// Return a slot's normalized loop-start marker, or `-1.0` for a null engine or
@@ -7192,12 +18077,15 @@ pub unsafe extern "C" fn gooey_engine_loop_load(
     if engine.is_null() || samples.is_null() || frames == 0 || channels == 0 {
         return false;
     }
-    let engine = &mut *engine;
     let total = frames as usize * channels as usize;
     let slice = slice::from_raw_parts(samples, total);
+    // Copied before the edit, so the audio thread only moves it in
     match StereoSampleBuffer::from_interleaved(slice, channels as usize, sample_rate) {
-        Ok(buffer) => engine.mixer.load(channel as usize, buffer),
-        Err(_) => false,
+        Ok(buffer) => edit(engine, || (*engine).mixer.load(channel as usize, buffer)),
+        Err(error) => {
+            fail_with("gooey_engine_loop_load", error);
+            false
+        }
     }
 }
 
@@ -7211,9 +18099,11 @@ pub unsafe extern "C" fn gooey_engine_loop_set_playing(
     channel: u32,
     playing: bool,
 ) {
-    if let Some(engine) = engine.as_mut() {
-        engine.mixer.set_playing(channel as usize, playing);
-    }
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            engine.mixer.set_playing(channel as usize, playing);
+        }
+    })
 }
 
 /// Set a loop channel's fader gain (0.0 = silence, 1.0 = unity, up to 2.0).
@@ -7226,9 +18116,11 @@ pub unsafe extern "C" fn gooey_engine_loop_set_gain(
     channel: u32,
     gain: f32,
 ) {
-    if let Some(engine) = engine.as_mut() {
-        engine.mixer.set_gain(channel as usize, gain);
-    }
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            engine.mixer.set_gain(channel as usize, gain);
+        }
+    })
 }
 
 /// Mute or unmute a loop channel (click-free, applied to the post-effect signal).
@@ -7241,9 +18133,11 @@ pub unsafe extern "C" fn gooey_engine_loop_set_mute(
     channel: u32,
     muted: bool,
 ) {
-    if let Some(engine) = engine.as_mut() {
-        engine.mixer.set_muted(channel as usize, muted);
-    }
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            engine.mixer.set_muted(channel as usize, muted);
+        }
+    })
 }
 
 /// Solo or un-solo a loop channel. While any channel is soloed, only soloed
@@ -7257,9 +18151,11 @@ pub unsafe extern "C" fn gooey_engine_loop_set_solo(
     channel: u32,
     soloed: bool,
 ) {
-    if let Some(engine) = engine.as_mut() {
-        engine.mixer.set_soloed(channel as usize, soloed);
-    }
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            engine.mixer.set_soloed(channel as usize, soloed);
+        }
+    })
 }
 
 /// Set a loop channel's loop start point as a normalized `[0, 1]` position in
@@ -7277,9 +18173,11 @@ pub unsafe extern "C" fn gooey_engine_loop_set_start(
     channel: u32,
     normalized: f32,
 ) {
-    if let Some(engine) = engine.as_mut() {
-        engine.mixer.set_loop_start(channel as usize, normalized);
-    }
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            engine.mixer.set_loop_start(channel as usize, normalized);
+        }
+    })
 }
 
 /// Set a loop channel's loop end point as a normalized `[0, 1]` position in
@@ -7297,9 +18195,11 @@ pub unsafe extern "C" fn gooey_engine_loop_set_end(
     channel: u32,
     normalized: f32,
 ) {
-    if let Some(engine) = engine.as_mut() {
-        engine.mixer.set_loop_end(channel as usize, normalized);
-    }
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            engine.mixer.set_loop_end(channel as usize, normalized);
+        }
+    })
 }
 
 /// Set a loop channel's playback speed (varispeed). 1.0 = normal, 0.5 = half
@@ -7313,9 +18213,11 @@ pub unsafe extern "C" fn gooey_engine_loop_set_speed(
     channel: u32,
     speed: f32,
 ) {
-    if let Some(engine) = engine.as_mut() {
-        engine.mixer.set_speed(channel as usize, speed);
-    }
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            engine.mixer.set_speed(channel as usize, speed);
+        }
+    })
 }
 
 /// Tag a loop channel's loaded buffer with the tempo its source material was
@@ -7333,14 +18235,16 @@ pub unsafe extern "C" fn gooey_engine_loop_set_source_bpm(
     channel: u32,
     source_bpm: f32,
 ) {
-    if let Some(engine) = engine.as_mut() {
-        let bpm = if source_bpm > 0.0 {
-            Some(source_bpm)
-        } else {
-            None
-        };
-        engine.mixer.set_source_bpm(channel as usize, bpm);
-    }
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            let bpm = if source_bpm > 0.0 {
+                Some(source_bpm)
+            } else {
+                None
+            };
+            engine.mixer.set_source_bpm(channel as usize, bpm);
+        }
+    })
 }
 
 /// Get a loop channel's tagged source BPM, or `0.0` if unset, no buffer is
@@ -7370,14 +18274,16 @@ pub unsafe extern "C" fn gooey_engine_loop_set_pitch_mode(
     channel: u32,
     mode: u32,
 ) {
-    if let Some(engine) = engine.as_mut() {
-        let mode = match mode {
-            PITCH_MODE_RESAMPLE => PitchMode::Resample,
-            PITCH_MODE_PRESERVE_PITCH => PitchMode::PreservePitch,
-            _ => PitchMode::Off,
-        };
-        engine.mixer.set_pitch_mode(channel as usize, mode);
-    }
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            let mode = match mode {
+                PITCH_MODE_RESAMPLE => PitchMode::Resample,
+                PITCH_MODE_PRESERVE_PITCH => PitchMode::PreservePitch,
+                _ => PitchMode::Off,
+            };
+            engine.mixer.set_pitch_mode(channel as usize, mode);
+        }
+    })
 }
 
 /// Get a loop channel's tempo-warp/pitch mode. See `PITCH_MODE_*` constants.
@@ -7406,9 +18312,11 @@ pub unsafe extern "C" fn gooey_engine_loop_get_pitch_mode(
 /// `engine` must be a valid pointer returned by `gooey_engine_new`.
 #[no_mangle]
 pub unsafe extern "C" fn gooey_engine_loop_restart(engine: *mut GooeyEngine, channel: u32) {
-    if let Some(engine) = engine.as_mut() {
-        engine.mixer.restart(channel as usize);
-    }
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            engine.mixer.restart(channel as usize);
+        }
+    })
 }
 
 /// Set a loop channel's playhead to a normalized `[0, 1]` position within its
@@ -7424,9 +18332,11 @@ pub unsafe extern "C" fn gooey_engine_loop_set_position(
     channel: u32,
     normalized: f32,
 ) {
-    if let Some(engine) = engine.as_mut() {
-        engine.mixer.set_position(channel as usize, normalized);
-    }
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            engine.mixer.set_position(channel as usize, normalized);
+        }
+    })
 }
 
 /// Stage a buffer to atomically replace this channel's loop at the next bar-grid
@@ -7456,22 +18366,27 @@ pub unsafe extern "C" fn gooey_engine_loop_queue_swap(
     source_bpm: f32,
     divisions: u32,
 ) -> bool {
-    if engine.is_null() || samples.is_null() || frames == 0 || channels == 0 {
-        return false;
-    }
-    let engine = &mut *engine;
-    let total = frames as usize * channels as usize;
-    let slice = slice::from_raw_parts(samples, total);
-    match StereoSampleBuffer::from_interleaved(slice, channels as usize, sample_rate) {
-        Ok(mut buffer) => {
-            // Tag the pending take so `warp_ratio()` is correct the moment it lands,
-            // rather than falling back to 1.0 until the host retags post-swap.
-            // `set_source_bpm` filters non-finite/<= 0, so 0.0 means "untagged".
-            buffer.set_source_bpm((source_bpm > 0.0).then_some(source_bpm));
-            engine.mixer.queue_swap(channel as usize, buffer, divisions)
+    edit(engine, || {
+        if engine.is_null() || samples.is_null() || frames == 0 || channels == 0 {
+            return false;
         }
-        Err(_) => false,
-    }
+        let engine = &mut *engine;
+        let total = frames as usize * channels as usize;
+        let slice = slice::from_raw_parts(samples, total);
+        match StereoSampleBuffer::from_interleaved(slice, channels as usize, sample_rate) {
+            Ok(mut buffer) => {
+                // Tag the pending take so `warp_ratio()` is correct the moment it lands,
+                // rather than falling back to 1.0 until the host retags post-swap.
+                // `set_source_bpm` filters non-finite/<= 0, so 0.0 means "untagged".
+                buffer.set_source_bpm((source_bpm > 0.0).then_some(source_bpm));
+                engine.mixer.queue_swap(channel as usize, buffer, divisions)
+            }
+            Err(error) => {
+                fail_with("gooey_engine_loop_queue_swap", error);
+                false
+            }
+        }
+    })
 }
 
 /// Drop a pending queued swap on a loop channel (Cancel / re-select the playing
@@ -7484,9 +18399,11 @@ pub unsafe extern "C" fn gooey_engine_loop_cancel_queued_swap(
     engine: *mut GooeyEngine,
     channel: u32,
 ) {
-    if let Some(engine) = engine.as_mut() {
-        engine.mixer.cancel_queued_swap(channel as usize);
-    }
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            engine.mixer.cancel_queued_swap(channel as usize);
+        }
+    })
 }
 
 /// Count of queued swaps that have completed on this channel since engine creation.
@@ -7538,13 +18455,13 @@ pub unsafe extern "C" fn gooey_engine_loop_effect_add(
     channel: u32,
     effect_id: u32,
 ) -> i32 {
-    match engine.as_mut() {
+    edit(engine, || match engine.as_mut() {
         Some(engine) => engine
             .mixer
             .effect_add(channel as usize, effect_id)
             .map_or(-1, |slot| slot as i32),
         None => -1,
-    }
+    })
 }
 
 /// Remove the effect at `slot` from a loop channel's chain. Returns `true` if an
@@ -7558,10 +18475,10 @@ pub unsafe extern "C" fn gooey_engine_loop_effect_remove(
     channel: u32,
     slot: u32,
 ) -> bool {
-    match engine.as_mut() {
+    edit(engine, || match engine.as_mut() {
         Some(engine) => engine.mixer.effect_remove(channel as usize, slot as usize),
         None => false,
-    }
+    })
 }
 
 /// Move the effect at `slot` to `new_position` within a loop channel's chain.
@@ -7576,14 +18493,14 @@ pub unsafe extern "C" fn gooey_engine_loop_effect_move(
     slot: u32,
     new_position: u32,
 ) -> bool {
-    match engine.as_mut() {
+    edit(engine, || match engine.as_mut() {
         Some(engine) => {
             engine
                 .mixer
                 .effect_move(channel as usize, slot as usize, new_position as usize)
         }
         None => false,
-    }
+    })
 }
 
 /// Remove all effects from a loop channel's chain.
@@ -7592,9 +18509,11 @@ pub unsafe extern "C" fn gooey_engine_loop_effect_move(
 /// `engine` must be a valid pointer returned by `gooey_engine_new`.
 #[no_mangle]
 pub unsafe extern "C" fn gooey_engine_loop_effect_clear(engine: *mut GooeyEngine, channel: u32) {
-    if let Some(engine) = engine.as_mut() {
-        engine.mixer.effect_clear(channel as usize);
-    }
+    edit(engine, || {
+        if let Some(engine) = engine.as_mut() {
+            engine.mixer.effect_clear(channel as usize);
+        }
+    })
 }
 
 /// Set a parameter (`*_PARAM_*` id) on the effect at `slot` of a loop channel.
@@ -7700,14 +18619,16 @@ pub unsafe extern "C" fn gooey_engine_granulator_buffer_sample_rate(
 /// `engine` must be a valid pointer returned by `gooey_engine_new`.
 #[no_mangle]
 pub unsafe extern "C" fn gooey_engine_granulator_trigger(engine: *mut GooeyEngine, velocity: f32) {
-    if engine.is_null() {
-        return;
-    }
-    let engine = &mut *engine;
-    let velocity = velocity.clamp(0.0, 1.0);
-    engine
-        .granulator
-        .trigger_with_velocity(engine.current_time, velocity);
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        let velocity = velocity.clamp(0.0, 1.0);
+        engine
+            .granulator
+            .trigger_with_velocity(engine.clock.seconds(), velocity);
+    })
 }
 
 /// Set a granulator parameter by index. All values are normalized 0.0-1.0.
@@ -7724,26 +18645,28 @@ pub unsafe extern "C" fn gooey_engine_granulator_set_param(
     param: u32,
     value: f32,
 ) {
-    if engine.is_null() {
-        return;
-    }
-    let engine = &mut *engine;
-    let value = value.clamp(0.0, 1.0);
-    match param {
-        GRANULATOR_PARAM_SCAN_POSITION => engine.granulator.set_scan_position(value),
-        GRANULATOR_PARAM_GRAIN_LENGTH => engine.granulator.set_grain_length(value),
-        GRANULATOR_PARAM_SPRAY => engine.granulator.set_spray(value),
-        GRANULATOR_PARAM_PITCH => engine.granulator.set_pitch(value),
-        GRANULATOR_PARAM_DENSITY => engine.granulator.set_density(value),
-        GRANULATOR_PARAM_TEXTURE => engine.granulator.set_texture(value),
-        GRANULATOR_PARAM_DIRECTION => engine.granulator.set_direction(value),
-        GRANULATOR_PARAM_CLOUD_DURATION => engine.granulator.set_cloud_duration(value),
-        GRANULATOR_PARAM_VOLUME => engine.granulator.set_volume(value),
-        GRANULATOR_PARAM_RANDOM_TIMING => engine.granulator.set_random_timing(value),
-        GRANULATOR_PARAM_RANDOM_AMP => engine.granulator.set_random_amp(value),
-        GRANULATOR_PARAM_DRIVE => engine.granulator.set_drive(value),
-        _ => {}
-    }
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        let value = value.clamp(0.0, 1.0);
+        match param {
+            GRANULATOR_PARAM_SCAN_POSITION => engine.granulator.set_scan_position(value),
+            GRANULATOR_PARAM_GRAIN_LENGTH => engine.granulator.set_grain_length(value),
+            GRANULATOR_PARAM_SPRAY => engine.granulator.set_spray(value),
+            GRANULATOR_PARAM_PITCH => engine.granulator.set_pitch(value),
+            GRANULATOR_PARAM_DENSITY => engine.granulator.set_density(value),
+            GRANULATOR_PARAM_TEXTURE => engine.granulator.set_texture(value),
+            GRANULATOR_PARAM_DIRECTION => engine.granulator.set_direction(value),
+            GRANULATOR_PARAM_CLOUD_DURATION => engine.granulator.set_cloud_duration(value),
+            GRANULATOR_PARAM_VOLUME => engine.granulator.set_volume(value),
+            GRANULATOR_PARAM_RANDOM_TIMING => engine.granulator.set_random_timing(value),
+            GRANULATOR_PARAM_RANDOM_AMP => engine.granulator.set_random_amp(value),
+            GRANULATOR_PARAM_DRIVE => engine.granulator.set_drive(value),
+            _ => {}
+        }
+    })
 }
 
 /// Read the most-recently-set value of a granulator parameter, in the same
@@ -7782,17 +18705,20 @@ pub unsafe extern "C" fn gooey_engine_granulator_get_param(
 }
 
 /// Seed the granulator's grain-spray PRNG. Used for reproducible output in
-/// tests; callers that don't need determinism can ignore this.
+/// tests; callers that don't need determinism can ignore this. Overridden by
+/// the engine seed on [`gooey_engine_set_seed`] and at the start of a bounce.
 ///
 /// # Safety
 /// `engine` must be a valid pointer returned by `gooey_engine_new`.
 #[no_mangle]
 pub unsafe extern "C" fn gooey_engine_granulator_set_seed(engine: *mut GooeyEngine, seed: u32) {
-    if engine.is_null() {
-        return;
-    }
-    let engine = &mut *engine;
-    engine.granulator.set_seed(seed);
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        engine.granulator.set_seed(seed);
+    })
 }
 
 /// Return the number of grains currently sounding inside the granulator.
@@ -7819,11 +18745,381 @@ pub unsafe extern "C" fn gooey_engine_granulator_active_grain_count(
 /// `engine` must be a valid pointer returned by `gooey_engine_new`.
 #[no_mangle]
 pub unsafe extern "C" fn gooey_engine_granulator_snap_params(engine: *mut GooeyEngine) {
-    if engine.is_null() {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        let engine = &mut *engine;
+        engine.granulator.snap_params();
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Random seed
+// ---------------------------------------------------------------------------
+
+/// Set the seed every random source in the engine derives from (instrument
+/// noise, pan spread, per-hit variation, grain scatter) and restart them all
+/// from it. Each channel draws from its own stream, so the same seed and the
+/// same input render the same audio. Bounces restart the streams from the
+/// seed, so repeated exports of a groove match bit for bit.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_seed(engine: *mut GooeyEngine, seed: u64) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_seed";
+    edit(engine, || {
+        if engine.is_null() {
+            return null_engine(FN);
+        }
+        let engine = &mut *engine;
+        engine.rng_seed = seed;
+        engine.reseed();
+        GooeyResult::Ok
+    })
+}
+
+/// The engine's random seed (a fixed default until [`gooey_engine_set_seed`]).
+/// Returns 0 if `engine` is null.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_seed(engine: *const GooeyEngine) -> u64 {
+    engine.as_ref().map_or(0, |engine| engine.rng_seed)
+}
+
+// ---------------------------------------------------------------------------
+// State snapshots
+// ---------------------------------------------------------------------------
+
+/// Capture the settings a snapshot carries (see `crate::snapshot`) through
+/// the regular getters.
+unsafe fn capture_snapshot(engine: *mut GooeyEngine) -> EngineSnapshot {
+    let effects = (0..EFFECT_COUNT)
+        .map(|effect| EffectSnapshot {
+            effect,
+            enabled: gooey_engine_get_global_effect_enabled(engine, effect),
+            params: (0..global_effect_param_count(effect).unwrap_or(0))
+                .map(|param| gooey_engine_get_global_effect_param(engine, effect, param))
+                .collect(),
+        })
+        .collect();
+    let channels = (0..CHANNEL_MAX)
+        .filter_map(|channel| {
+            let voice = (*engine).voice(channel as usize)?;
+            let instrument_type = voice.instrument.instrument_type();
+            let param_count = crate::param_info::instrument_params(instrument_type).len() as u32;
+            Some(ChannelSnapshot {
+                channel,
+                instrument_type,
+                params: (0..param_count).map(|param| voice.param(param)).collect(),
+                tuning: gooey_engine_get_channel_tuning(engine, channel),
+                gain_db: gooey_engine_get_channel_gain(engine, channel),
+                pan: gooey_engine_get_channel_pan(engine, channel),
+                muted: gooey_engine_get_instrument_mute(engine, channel),
+                soloed: gooey_engine_get_instrument_solo(engine, channel),
+                steps: voice.sequencer.pattern_steps().to_vec(),
+            })
+        })
+        .collect();
+    let engine = &*engine;
+    EngineSnapshot {
+        bpm: engine.bpm,
+        swing: engine.swing,
+        master_gain: engine.master_gain.target(),
+        a4_hz: engine.master_tuning.a4_hz(),
+        transpose: engine.master_tuning.transpose(),
+        seed: engine.rng_seed,
+        effect_order: engine.effect_order.to_vec(),
+        effects,
+        channels,
+    }
+}
+
+/// Make the engine match a decoded snapshot through the regular setters,
+/// returning the first failure (the remaining settings still apply).
+unsafe fn apply_snapshot(
+    engine: *mut GooeyEngine,
+    snapshot: &EngineSnapshot,
+    spares: &mut SnapshotSpares,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_import_state";
+    let mut result = GooeyResult::Ok;
+    let mut keep = |next: GooeyResult| {
+        if result == GooeyResult::Ok {
+            result = next;
+        }
+    };
+
+    // Sampler stretches follow once the edit is done (see `restretch_samplers`)
+    (*engine).retime(snapshot.bpm);
+    gooey_engine_set_swing(engine, snapshot.swing);
+    gooey_engine_set_master_gain(engine, snapshot.master_gain);
+    keep(gooey_engine_set_master_tuning(
+        engine,
+        snapshot.a4_hz,
+        snapshot.transpose,
+    ));
+    keep(gooey_engine_set_seed(engine, snapshot.seed));
+
+    if !gooey_engine_set_effect_order(
+        engine,
+        snapshot.effect_order.as_ptr(),
+        snapshot.effect_order.len() as u32,
+    ) {
+        keep(fail(
+            GooeyResult::InvalidEffect,
+            format!(
+                "{FN}: effect order {:?} is not valid",
+                snapshot.effect_order
+            ),
+        ));
+    }
+    for effect in &snapshot.effects {
+        keep(gooey_engine_set_global_effect_enabled(
+            engine,
+            effect.effect,
+            effect.enabled,
+        ));
+        for (param, &value) in effect.params.iter().enumerate() {
+            keep(gooey_engine_set_global_effect_param(
+                engine,
+                effect.effect,
+                param as u32,
+                value,
+            ));
+        }
+    }
+
+    // Slots the snapshot doesn't have go; the ones it has are created in
+    // place so every channel keeps its number
+    for index in 0..SLOT_COUNT {
+        let channel = NUM_INSTRUMENTS + index;
+        if !snapshot
+            .channels
+            .iter()
+            .any(|c| c.channel as usize == channel)
+        {
+            let carrier = &mut spares.placeholders[index];
+            spares
+                .removed
+                .extend((*engine).destroy_slot(channel, carrier));
+        }
+    }
+    for channel in &snapshot.channels {
+        let number = channel.channel;
+        if (*engine).voice(number as usize).is_none() {
+            let index = number as usize - NUM_INSTRUMENTS;
+            match spares.slots[index].take() {
+                Some(voice) => {
+                    (*engine).install_slot(index, voice);
+                }
+                None => {
+                    (*engine).create_slot_at(index, channel.instrument_type);
+                }
+            }
+        } else {
+            let carrier = &mut spares.instruments[number as usize];
+            match (*engine).set_instrument_type(number, channel.instrument_type, carrier) {
+                Ok(frozen) => spares.frozen.extend(frozen),
+                Err(result) => keep(result),
+            }
+        }
+        for (param, &value) in channel.params.iter().enumerate() {
+            keep(gooey_engine_set_channel_param(
+                engine,
+                number,
+                param as u32,
+                value,
+            ));
+        }
+        gooey_engine_set_channel_tuning(engine, number, channel.tuning);
+        keep(gooey_engine_set_channel_gain(
+            engine,
+            number,
+            channel.gain_db,
+        ));
+        keep(gooey_engine_set_channel_pan(engine, number, channel.pan));
+        gooey_engine_set_instrument_mute(engine, number, channel.muted);
+        gooey_engine_set_instrument_solo(engine, number, channel.soloed);
+        if !channel.steps.is_empty() {
+            if let Some(sequencer) = (*engine).sequencer_for_instrument(number) {
+                sequencer.set_pattern_with_velocity(channel.steps.clone());
+            }
+        }
+    }
+    result
+}
+
+/// Serialize the engine's state into a compact binary snapshot, for moving a
+/// session to another engine (e.g. between a UI thread and an audio
+/// worklet) or storing it.
+///
+/// The snapshot holds tempo, swing, master gain and tuning, the seed, the
+/// global effect chain (order, on/off, parameters) and every occupied
+/// channel: instrument type, parameters, tuning, fader, pan, mute/solo and
+/// step pattern. Loaded samples, loops, clips, LFOs, lanes and the mixer
+/// layout are not included. The format is described in `crate::snapshot`.
+///
+/// # Arguments
+/// * `engine` - Pointer to a GooeyEngine
+/// * `out_length` - Receives the snapshot's length in bytes
+///
+/// # Returns
+/// The snapshot bytes, or null if `engine` or `out_length` is null. Free
+/// with `gooey_engine_free_state`.
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `out_length` must be a valid pointer to a `u32`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_export_state(
+    engine: *mut GooeyEngine,
+    out_length: *mut u32,
+) -> *mut u8 {
+    if engine.is_null() || out_length.is_null() {
+        return std::ptr::null_mut();
+    }
+    // Captured in the edit, encoded outside it
+    let snapshot = edit(engine, || capture_snapshot(engine));
+    let bytes = crate::snapshot::encode(&snapshot).into_boxed_slice();
+    *out_length = bytes.len() as u32;
+    Box::into_raw(bytes) as *mut u8
+}
+
+/// Free a snapshot returned by `gooey_engine_export_state`.
+///
+/// # Safety
+/// `bytes` must be null or a pointer returned by `gooey_engine_export_state`
+/// with its `length`, not already freed.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_free_state(bytes: *mut u8, length: u32) {
+    if bytes.is_null() {
         return;
     }
-    let engine = &mut *engine;
-    engine.granulator.snap_params();
+    let slice = std::slice::from_raw_parts_mut(bytes, length as usize);
+    drop(Box::from_raw(slice as *mut [u8]));
+}
+
+/// What applying a snapshot allocates, made on the importing thread before
+/// the edit that applies it so the audio thread only moves it into place.
+/// Built for the layout the engine had when [`SnapshotSpares::new`] looked;
+/// `apply_snapshot` allocates whatever a change since then leaves missing.
+/// Dropping it frees the leftovers and everything the snapshot replaced.
+struct SnapshotSpares {
+    /// Carriers for built-in or slot channels whose instrument type changes,
+    /// by channel
+    instruments: [Option<Box<RetiringVoice>>; NUM_CHANNELS],
+    /// Voices for slots the snapshot creates, by slot index
+    slots: [Option<VoiceStrip>; SLOT_COUNT],
+    /// Placeholders for slots the snapshot destroys, by slot index
+    placeholders: [Option<Box<RetiringVoice>>; SLOT_COUNT],
+    /// Removed slot voices and frozen loops, reserved up front
+    removed: Vec<VoiceStrip>,
+    frozen: Vec<FrozenLoop>,
+}
+
+impl SnapshotSpares {
+    /// Build what `snapshot` needs to turn `layout` (the instrument type on
+    /// each channel, `None` for an empty slot) into its own.
+    fn new(
+        snapshot: &EngineSnapshot,
+        layout: &[Option<u32>; NUM_CHANNELS],
+        sample_rate: f32,
+    ) -> Self {
+        let mut spares = Self {
+            instruments: std::array::from_fn(|_| None),
+            slots: std::array::from_fn(|_| None),
+            placeholders: std::array::from_fn(|_| None),
+            removed: Vec::with_capacity(SLOT_COUNT),
+            frozen: Vec::with_capacity(NUM_CHANNELS),
+        };
+        for channel in &snapshot.channels {
+            let number = channel.channel as usize;
+            let instrument_type = channel.instrument_type;
+            match layout.get(number) {
+                Some(None) => {
+                    spares.slots[number - NUM_INSTRUMENTS] = GooeyEngine::slot_voice(
+                        number - NUM_INSTRUMENTS,
+                        instrument_type,
+                        sample_rate,
+                    );
+                }
+                Some(Some(current)) if *current != instrument_type => {
+                    spares.instruments[number] =
+                        ChannelInstrument::new(instrument_type, sample_rate)
+                            .map(RetiringVoice::carrier);
+                }
+                _ => {}
+            }
+        }
+        for index in 0..SLOT_COUNT {
+            let channel = NUM_INSTRUMENTS + index;
+            if layout[channel].is_some()
+                && !snapshot
+                    .channels
+                    .iter()
+                    .any(|c| c.channel as usize == channel)
+            {
+                spares.placeholders[index] = ChannelInstrument::new(INSTRUMENT_KICK, sample_rate)
+                    .map(RetiringVoice::carrier);
+            }
+        }
+        spares
+    }
+}
+
+/// Restore a snapshot from `gooey_engine_export_state`, possibly taken from
+/// another engine.
+///
+/// The snapshot is decoded and checked in full first, so a damaged or
+/// foreign byte string changes nothing. It is then applied through the
+/// regular setters, so parameter changes are smoothed as usual. Slots are
+/// created or destroyed to match the snapshot's channels; the instruments
+/// this needs are built on the calling thread first, and the audio thread
+/// applies the snapshot between two blocks.
+///
+/// # Returns
+/// `GooeyResult::Ok`; `NullPointer` for a null engine or `bytes`;
+/// `InvalidValue` if the bytes are not a readable snapshot (see
+/// `gooey_engine_last_error_message`); otherwise the first error a setter
+/// returned while applying it (e.g. `QueueFull`), with the remaining
+/// settings still applied.
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `bytes` must point to `length` readable bytes
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_import_state(
+    engine: *mut GooeyEngine,
+    bytes: *const u8,
+    length: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_import_state";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+    if bytes.is_null() {
+        return fail(GooeyResult::NullPointer, format!("{FN}: bytes is null"));
+    }
+    let bytes = std::slice::from_raw_parts(bytes, length as usize);
+    let snapshot = match crate::snapshot::decode(bytes) {
+        Ok(snapshot) => snapshot,
+        Err(error) => return fail(GooeyResult::InvalidValue, format!("{FN}: {error}")),
+    };
+    let layout = edit(engine, || {
+        std::array::from_fn(|channel| {
+            (*engine)
+                .voice(channel)
+                .map(|voice| voice.instrument.instrument_type())
+        })
+    });
+    let mut spares = SnapshotSpares::new(&snapshot, &layout, (*engine).sample_rate);
+    let result = edit(engine, || apply_snapshot(engine, &snapshot, &mut spares));
+    restretch_samplers(engine);
+    result
 }
 
 // ---------------------------------------------------------------------------
@@ -7836,8 +19132,26 @@ impl GooeyEngine {
         let samples_per_bar = 4.0_f64 * (60.0 / self.bpm as f64) * self.sample_rate as f64;
         let total_samples = (bars as f64 * samples_per_bar).round() as usize;
 
+        // `render` writes interleaved stereo (`[l, r]` per frame), so each
+        // frame is downmixed to a single mono sample for this mono bounce
+        // buffer — otherwise a panned channel (e.g. hard-left `[l, 0]`) would
+        // be written as alternating samples and zeros.
+        let mut output = Vec::with_capacity(total_samples);
+        self.render_offline(total_samples, |frame| {
+            output.push(0.5 * (frame[0] + frame[1]))
+        });
+        output
+    }
+
+    /// Render `total_samples` frames offline from the top of the pattern,
+    /// handing each interleaved stereo frame to `sink`. Sequencers are stopped
+    /// afterwards and the recorder is left as it was.
+    fn render_offline(&mut self, total_samples: usize, mut sink: impl FnMut(&[f32])) {
+        let _ftz = DenormalGuard::new();
+
         // Reset engine to a clean state
-        self.current_time = 0.0;
+        self.clock.reset();
+        self.reseed();
         for seq in self.sequencers_iter_mut() {
             seq.reset();
             seq.start();
@@ -7848,17 +19162,21 @@ impl GooeyEngine {
         for voice in self.voices_iter_mut() {
             voice.mute_gain.snap();
             voice.channel_gain.snap();
+            voice.group_gain.snap();
             voice.pan.snap();
+            voice.meter_pre.reset();
+            voice.meter_post.reset();
         }
         self.graph.snap_strip_params();
         self.master_gain.snap();
+        self.preview_gain.snap();
+        self.output_safety.reset();
 
-        // Render in chunks using the same path as real-time playback. `render`
-        // writes interleaved stereo (`[l, r]` per frame), so each frame is
-        // downmixed to a single mono sample for this mono bounce buffer —
-        // otherwise a panned channel (e.g. hard-left `[l, 0]`) would be written
-        // as alternating samples and zeros.
-        let mut output = Vec::with_capacity(total_samples);
+        // An offline bounce must not leak into a live take.
+        let record_state = self.recorder.state();
+        self.recorder.stop();
+
+        // Render in chunks using the same path as real-time playback.
         let frames_per_chunk = 512;
         let mut chunk_buf = vec![0.0_f32; frames_per_chunk * 2];
 
@@ -7870,9 +19188,7 @@ impl GooeyEngine {
                 *s = 0.0;
             }
             self.render(slice);
-            for frame in slice.chunks_exact(2) {
-                output.push(0.5 * (frame[0] + frame[1]));
-            }
+            slice.chunks_exact(2).for_each(&mut sink);
             remaining -= frames;
         }
 
@@ -7880,7 +19196,42 @@ impl GooeyEngine {
             seq.stop();
         }
 
-        output
+        // The take's buffer is still allocated, so resuming can't fail
+        let _ = match record_state {
+            RecordState::Idle => Ok(()),
+            RecordState::Armed => self.recorder.arm(),
+            RecordState::Recording => self.recorder.start(),
+        };
+    }
+
+    /// Render one loop of `channel`'s pattern offline and play that audio in
+    /// place of the instrument (see `gooey_engine_freeze_channel`). Returns
+    /// false for an invalid channel or an empty pattern.
+    fn freeze_channel(&mut self, channel: usize) -> bool {
+        let Some(voice) = self.voice_mut(channel) else {
+            return false;
+        };
+        let pattern = voice.sequencer.pattern();
+        if !pattern.contains(&true) {
+            return false;
+        }
+        let steps = pattern.len();
+        let loop_frames =
+            (steps as f64 * voice.sequencer.samples_per_step() as f64).round() as usize;
+        voice.frozen = None;
+
+        // Two loops, keeping the second: by then the first loop's tails have
+        // wrapped around into it, as they do when the pattern repeats live.
+        self.freeze_capture = Some((channel, Vec::with_capacity(2 * loop_frames)));
+        self.render_offline(2 * loop_frames, |_| {});
+        let Some((_, captured)) = self.freeze_capture.take() else {
+            return false;
+        };
+        let samples = captured[loop_frames.min(captured.len())..].into();
+        if let Some(voice) = self.voice_mut(channel) {
+            voice.frozen = Some(FrozenLoop::new(samples));
+        }
+        true
     }
 }
 
@@ -7899,19 +19250,21 @@ pub unsafe extern "C" fn gooey_engine_bounce_to_buffer(
     bars: u32,
     out_length: *mut u32,
 ) -> *mut f32 {
-    if engine.is_null() || out_length.is_null() {
-        return std::ptr::null_mut();
-    }
-    let engine = &mut *engine;
-    let buffer = engine.bounce_to_buffer(bars);
-    if buffer.len() > u32::MAX as usize {
-        return std::ptr::null_mut();
-    }
-    let len = buffer.len() as u32;
-    let boxed = buffer.into_boxed_slice();
-    let ptr = Box::into_raw(boxed) as *mut f32;
-    *out_length = len;
-    ptr
+    edit(engine, || {
+        if engine.is_null() || out_length.is_null() {
+            return std::ptr::null_mut();
+        }
+        let engine = &mut *engine;
+        let buffer = engine.bounce_to_buffer(bars);
+        if buffer.len() > u32::MAX as usize {
+            return std::ptr::null_mut();
+        }
+        let len = buffer.len() as u32;
+        let boxed = buffer.into_boxed_slice();
+        let ptr = Box::into_raw(boxed) as *mut f32;
+        *out_length = len;
+        ptr
+    })
 }
 
 /// Free a buffer returned by [`gooey_engine_bounce_to_buffer`].
@@ -7947,16 +19300,16 @@ pub unsafe extern "C" fn gooey_engine_bounce_to_wav(
     if engine.is_null() || path.is_null() {
         return false;
     }
-    let engine = &mut *engine;
     let path_str = match std::ffi::CStr::from_ptr(path).to_str() {
         Ok(s) => s,
         Err(_) => return false,
     };
 
-    let buffer = engine.bounce_to_buffer(bars);
+    // Rendered in the edit, written to disk outside it
+    let buffer = edit(engine, || (*engine).bounce_to_buffer(bars));
     let spec = hound::WavSpec {
         channels: 1,
-        sample_rate: engine.sample_rate as u32,
+        sample_rate: (*engine).sample_rate as u32,
         bits_per_sample: 16,
         sample_format: hound::SampleFormat::Int,
     };
@@ -7979,6 +19332,112 @@ pub unsafe extern "C" fn gooey_engine_bounce_to_wav(
     writer.finalize().is_ok()
 }
 
+// ---------------------------------------------------------------------------
+// Channel freeze
+// ---------------------------------------------------------------------------
+
+/// Freeze a channel: render its pattern to audio and play that instead
+///
+/// Renders one loop of the channel's sequencer pattern offline (the way
+/// `gooey_engine_bounce_to_buffer` does, after a loop of warm-up so tails
+/// wrap around) and from then on plays the recording in sync with the
+/// sequencer instead of running the synth, saving its CPU. The channel
+/// strip still applies: gain, mute/solo, pan and effects act on the frozen
+/// audio. The instrument and its config are kept untouched, so
+/// `gooey_engine_unfreeze_channel` brings the synth back as it was.
+///
+/// While frozen, pattern edits, parameter changes and manual triggers don't
+/// change what plays; freeze again to pick them up. Swapping the channel's
+/// instrument type unfreezes it.
+///
+/// Like a bounce this resets the transport and leaves the sequencers
+/// stopped. On an engine that is rendering live, the audio thread runs the
+/// offline render between two blocks, so the next block is late by as long
+/// as it takes: freeze while transport is stopped.
+///
+/// # Returns
+/// `GooeyResult::Ok`, `NullPointer` for a null engine, `InvalidChannel` for
+/// a channel that doesn't exist, or `InvalidValue` if its pattern has no
+/// steps enabled
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_freeze_channel(
+    engine: *mut GooeyEngine,
+    channel: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_freeze_channel";
+    edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return null_engine(FN);
+        };
+        if engine.voice(channel as usize).is_none() {
+            return fail(
+                GooeyResult::InvalidChannel,
+                format!("{FN}: channel {channel} is out of range"),
+            );
+        }
+        if !engine.freeze_channel(channel as usize) {
+            return fail(
+                GooeyResult::InvalidValue,
+                format!("{FN}: channel {channel} has no steps enabled"),
+            );
+        }
+        GooeyResult::Ok
+    })
+}
+
+/// Unfreeze a channel: drop its frozen audio and run the synth again
+///
+/// A no-op for a channel that isn't frozen. The frozen audio is freed on the
+/// calling thread.
+///
+/// # Returns
+/// `GooeyResult::Ok`, `NullPointer` for a null engine, or `InvalidChannel`
+/// for a channel that doesn't exist
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_unfreeze_channel(
+    engine: *mut GooeyEngine,
+    channel: u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_unfreeze_channel";
+    let (result, _frozen) = edit(engine, || {
+        let Some(engine) = engine.as_mut() else {
+            return (null_engine(FN), None);
+        };
+        let Some(voice) = engine.voice_mut(channel as usize) else {
+            return (
+                fail(
+                    GooeyResult::InvalidChannel,
+                    format!("{FN}: channel {channel} is out of range"),
+                ),
+                None,
+            );
+        };
+        (GooeyResult::Ok, voice.frozen.take())
+    });
+    result
+}
+
+/// Whether a channel is frozen (false for a null engine or invalid channel).
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_is_channel_frozen(
+    engine: *const GooeyEngine,
+    channel: u32,
+) -> bool {
+    engine
+        .as_ref()
+        .and_then(|engine| engine.voice(channel as usize))
+        .is_some_and(|voice| voice.frozen.is_some())
+}
+
 /// Render a single loop channel offline to a stereo WAV file.
 ///
 /// Renders only `channel`, **after** its gain fader and effect chain but
@@ -7994,8 +19453,9 @@ pub unsafe extern "C" fn gooey_engine_bounce_to_wav(
 /// path, `frame_count == 0`, an out-of-range `channel`, a channel with no
 /// loaded buffer, or any file-write error.
 ///
-/// This drives the channel directly and is **not** real-time safe: it must not
-/// run concurrently against the same engine's realtime render callback. Callers
+/// This drives the channel directly and is **not** real-time safe: on an
+/// engine that is rendering live, the audio thread runs the render between
+/// two blocks and the next block is late by as long as it takes. Callers
 /// (e.g. Whirlpool) invoke it on a disposable offline engine.
 ///
 /// # Safety
@@ -8013,25 +19473,28 @@ pub unsafe extern "C" fn gooey_engine_loop_render_to_wav(
     if engine.is_null() || path.is_null() || frame_count == 0 {
         return false;
     }
-    let engine = &mut *engine;
     let path_str = match std::ffi::CStr::from_ptr(path).to_str() {
         Ok(s) if !s.is_empty() => s,
         _ => return false,
     };
 
+    // Rendered in the edit, written to disk outside it
     let mut interleaved: Vec<f32> = Vec::new();
-    if !engine.mixer.render_channel_to_interleaved(
-        channel as usize,
-        frame_count as usize,
-        preroll_frame_count as usize,
-        &mut interleaved,
-    ) {
+    let rendered = edit(engine, || {
+        (*engine).mixer.render_channel_to_interleaved(
+            channel as usize,
+            frame_count as usize,
+            preroll_frame_count as usize,
+            &mut interleaved,
+        )
+    });
+    if !rendered {
         return false;
     }
 
     let spec = hound::WavSpec {
         channels: 2,
-        sample_rate: engine.sample_rate as u32,
+        sample_rate: (*engine).sample_rate as u32,
         bits_per_sample: 32,
         sample_format: hound::SampleFormat::Float,
     };
@@ -8046,3 +19509,477 @@ pub unsafe extern "C" fn gooey_engine_loop_render_to_wav(
     }
     writer.finalize().is_ok()
 }
+
+// =============================================================================
+// Master output recording
+// =============================================================================
+
+/// Recorder is idle: rendered audio is not captured.
+pub const RECORD_STATE_IDLE: u32 = crate::recorder::RECORD_STATE_IDLE;
+/// Recorder is armed: capture starts on the first non-silent rendered frame.
+pub const RECORD_STATE_ARMED: u32 = crate::recorder::RECORD_STATE_ARMED;
+/// Recorder is capturing every rendered frame.
+pub const RECORD_STATE_RECORDING: u32 = crate::recorder::RECORD_STATE_RECORDING;
+/// Longest take `gooey_engine_record_reserve_seconds` reserves, in seconds.
+pub const RECORD_MAX_RESERVE_SECONDS: f32 = crate::recorder::MAX_RESERVE_SECONDS;
+
+/// Arm the master recorder. Capture begins on the first rendered frame that
+/// is not silent, so a take started before pressing play has no leading gap.
+/// Has no effect while already recording.
+///
+/// Returns `GooeyResult::OutOfMemory`, leaving the recorder idle, if the
+/// take's buffer can't be allocated.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_record_arm(engine: *mut GooeyEngine) -> GooeyResult {
+    const FN: &str = "gooey_engine_record_arm";
+    if engine.is_null() {
+        return GooeyResult::NullPointer;
+    }
+    if let Err(error) = prepare_record_storage(engine) {
+        return fail_with(FN, error);
+    }
+    edit(engine, || match (*engine).recorder.arm() {
+        Ok(()) => GooeyResult::Ok,
+        Err(error) => fail_with(FN, error),
+    })
+}
+
+/// Allocate the recorder's buffer for the next take on the calling thread
+/// and swap it in with a short edit, so arming or starting the take doesn't
+/// allocate on the audio thread. The buffer it replaces is freed here.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`.
+unsafe fn prepare_record_storage(engine: *mut GooeyEngine) -> Result<(), GooeyError> {
+    let Some(len) = edit(engine, || (*engine).recorder.storage_needed()) else {
+        return Ok(());
+    };
+    let mut storage = Recorder::alloc_storage(len)?;
+    edit(engine, || {
+        storage = (*engine)
+            .recorder
+            .install_storage(std::mem::take(&mut storage));
+    });
+    Ok(())
+}
+
+/// Start capturing the master output immediately. A new take is appended to
+/// any audio already captured; call `gooey_engine_record_clear` first to
+/// start over.
+///
+/// Returns `GooeyResult::OutOfMemory`, leaving the recorder as it was, if
+/// the take's buffer can't be allocated.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_record_start(engine: *mut GooeyEngine) -> GooeyResult {
+    const FN: &str = "gooey_engine_record_start";
+    if engine.is_null() {
+        return GooeyResult::NullPointer;
+    }
+    if let Err(error) = prepare_record_storage(engine) {
+        return fail_with(FN, error);
+    }
+    edit(engine, || match (*engine).recorder.start() {
+        Ok(()) => GooeyResult::Ok,
+        Err(error) => fail_with(FN, error),
+    })
+}
+
+/// Stop capturing (or disarm). The captured audio is kept until cleared.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_record_stop(engine: *mut GooeyEngine) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        (*engine).recorder.stop();
+    })
+}
+
+/// Discard the captured audio. The recorder's allocation is kept.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_record_clear(engine: *mut GooeyEngine) {
+    edit(engine, || {
+        if engine.is_null() {
+            return;
+        }
+        (*engine).recorder.clear();
+    })
+}
+
+/// Get the recorder state (`RECORD_STATE_*`). Returns `RECORD_STATE_IDLE`
+/// for a null engine.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_record_get_state(engine: *const GooeyEngine) -> u32 {
+    if engine.is_null() {
+        return RECORD_STATE_IDLE;
+    }
+    (*engine).recorder.state().as_u32()
+}
+
+/// Set the take length to `seconds`, clamped to `RECORD_MAX_RESERVE_SECONDS`
+/// (30 minutes). The buffer is allocated on the host thread when a take is
+/// armed or started (one minute by default) and never grows on the audio
+/// thread: a take that fills it stops, and `gooey_engine_record_overflowed`
+/// reports it. Call before a longer take.
+///
+/// Returns `GooeyResult::InvalidValue` for a non-positive or non-finite
+/// length and `GooeyResult::OutOfMemory` if an allocated take can't be
+/// grown; the previous buffer is kept either way.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_record_reserve_seconds(
+    engine: *mut GooeyEngine,
+    seconds: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_record_reserve_seconds";
+    if engine.is_null() {
+        return GooeyResult::NullPointer;
+    }
+    let set = edit(engine, || {
+        let recorder = &mut (*engine).recorder;
+        recorder
+            .set_take_length(seconds)
+            .map(|()| recorder.is_allocated())
+    });
+    match set {
+        Ok(true) => match prepare_record_storage(engine) {
+            Ok(()) => GooeyResult::Ok,
+            Err(error) => fail_with(FN, error),
+        },
+        Ok(false) => GooeyResult::Ok,
+        Err(error) => fail_with(FN, error),
+    }
+}
+
+/// Whether the last take filled the recording buffer and was stopped.
+/// Cleared by the next arm, start or clear. Returns false for a null engine.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_record_overflowed(engine: *const GooeyEngine) -> bool {
+    if engine.is_null() {
+        return false;
+    }
+    (*engine).recorder.overflowed()
+}
+
+/// Number of stereo frames captured so far. Returns 0 for a null engine.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_record_get_frame_count(engine: *const GooeyEngine) -> u32 {
+    if engine.is_null() {
+        return 0;
+    }
+    (*engine).recorder.frame_count().min(u32::MAX as usize) as u32
+}
+
+/// Copy up to `max_frames` captured frames into `out` as interleaved stereo
+/// `[l, r]` PCM. `out` must have room for `max_frames * 2` floats. Returns the
+/// number of frames copied.
+///
+/// Call after `gooey_engine_record_stop`: the capture buffer is owned by the
+/// audio thread while recording.
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`.
+/// - `out` must point to at least `max_frames * 2` writable floats.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_record_copy_buffer(
+    engine: *const GooeyEngine,
+    out: *mut f32,
+    max_frames: u32,
+) -> u32 {
+    if engine.is_null() || out.is_null() {
+        return 0;
+    }
+    let engine = &*engine;
+    let frames = engine.recorder.frame_count().min(max_frames as usize);
+    let src = &engine.recorder.samples()[..frames * 2];
+    let dst = slice::from_raw_parts_mut(out, frames * 2);
+    dst.copy_from_slice(src);
+    frames as u32
+}
+
+/// Write the captured audio to a stereo 16-bit WAV file at the engine's
+/// sample rate. Returns `true` on success; `false` for a null engine/path, an
+/// empty take, or any file-write error.
+///
+/// Call after `gooey_engine_record_stop`: the capture buffer is owned by the
+/// audio thread while recording.
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`.
+/// - `path` must be a valid null-terminated UTF-8 string.
+#[cfg(feature = "bounce")]
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_record_write_wav(
+    engine: *const GooeyEngine,
+    path: *const std::os::raw::c_char,
+) -> bool {
+    if engine.is_null() || path.is_null() {
+        return false;
+    }
+    let engine = &*engine;
+    let path_str = match CStr::from_ptr(path).to_str() {
+        Ok(s) if !s.is_empty() => s,
+        _ => return false,
+    };
+    if engine.recorder.frame_count() == 0 {
+        return false;
+    }
+    match engine.recorder.write_wav(
+        std::path::Path::new(path_str),
+        crate::bounce::WavConfig::default(),
+    ) {
+        Ok(()) => true,
+        Err(error) => {
+            fail_with("gooey_engine_record_write_wav", error);
+            false
+        }
+    }
+}
+
+// =============================================================================
+// Parameter metadata
+// =============================================================================
+
+/// Unit: plain 0-1 amount.
+pub const PARAM_UNIT_NORMALIZED: u32 = crate::param_info::PARAM_UNIT_NORMALIZED;
+/// Unit: frequency in Hz.
+pub const PARAM_UNIT_HZ: u32 = crate::param_info::PARAM_UNIT_HZ;
+/// Unit: time in seconds.
+pub const PARAM_UNIT_SECONDS: u32 = crate::param_info::PARAM_UNIT_SECONDS;
+/// Unit: time in milliseconds.
+pub const PARAM_UNIT_MILLISECONDS: u32 = crate::param_info::PARAM_UNIT_MILLISECONDS;
+/// Unit: pitch offset in semitones.
+pub const PARAM_UNIT_SEMITONES: u32 = crate::param_info::PARAM_UNIT_SEMITONES;
+/// Unit: dimensionless ratio / curve shape.
+pub const PARAM_UNIT_RATIO: u32 = crate::param_info::PARAM_UNIT_RATIO;
+/// Unit: pitch offset in cents.
+pub const PARAM_UNIT_CENTS: u32 = crate::param_info::PARAM_UNIT_CENTS;
+/// Unit: discrete choice index (the setter takes the index, not 0-1).
+pub const PARAM_UNIT_CHOICE: u32 = crate::param_info::PARAM_UNIT_CHOICE;
+
+/// Metadata describing one instrument parameter, filled by
+/// `gooey_engine_get_param_info`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct GooeyParamInfo {
+    /// Snake-case identifier (static, nul-terminated; do not free).
+    pub name: *const c_char,
+    /// Display range minimum, in `unit`.
+    pub min: f32,
+    /// Display range maximum, in `unit`.
+    pub max: f32,
+    /// Display unit (`PARAM_UNIT_*`).
+    pub unit: u32,
+    /// Default value in setter space (normalized 0-1, or a choice index for
+    /// `PARAM_UNIT_CHOICE`).
+    pub default_value: f32,
+    /// True if the parameter can be targeted by an LFO route.
+    pub modulatable: bool,
+}
+
+/// Number of parameters exposed by an instrument type (`INSTRUMENT_*`).
+/// Valid parameter indices are `0..count`. Returns 0 for unknown IDs.
+#[no_mangle]
+pub extern "C" fn gooey_engine_get_param_count(instrument: u32) -> u32 {
+    crate::param_info::instrument_params(instrument).len() as u32
+}
+
+/// Describe parameter `param` of instrument type `instrument` (`INSTRUMENT_*`)
+/// so hosts can generate controls instead of hard-coding ranges.
+///
+/// Returns `false` (leaving `out` untouched) for a null `out` or an unknown
+/// instrument/parameter.
+///
+/// # Safety
+/// `out` must be null or a valid pointer to a `GooeyParamInfo`.
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_param_info(
+    instrument: u32,
+    param: u32,
+    out: *mut GooeyParamInfo,
+) -> bool {
+    if out.is_null() {
+        return false;
+    }
+    let Some(info) = crate::param_info::param_info(instrument, param) else {
+        return false;
+    };
+    *out = GooeyParamInfo {
+        name: info.c_name().as_ptr(),
+        min: info.min,
+        max: info.max,
+        unit: info.unit.as_u32(),
+        default_value: info.default,
+        modulatable: info.modulatable,
+    };
+    true
+}
+
+/// The full parameter registry as a JSON document (see
+/// `param_info::registry_json`). The string is built once and lives for the
+/// life of the process; do not free it.
+#[no_mangle]
+pub extern "C" fn gooey_engine_get_param_registry_json() -> *const c_char {
+    static JSON: std::sync::OnceLock<CString> = std::sync::OnceLock::new();
+    JSON.get_or_init(|| CString::new(crate::param_info::registry_json()).unwrap_or_default())
+        .as_ptr()
+}
+
+// =============================================================================
+// Named parameters
+// =============================================================================
+
+/// Resolve a dotted parameter name for the calling FFI function, recording
+/// the failure if it names nothing.
+unsafe fn resolve_param_name(
+    function: &str,
+    name: *const c_char,
+) -> Result<(u32, crate::param_info::ParamInfo), GooeyResult> {
+    use crate::param_info::ParamPathError;
+
+    if name.is_null() {
+        return Err(fail(
+            GooeyResult::NullPointer,
+            format!("{function}: name is null"),
+        ));
+    }
+    let Ok(name) = CStr::from_ptr(name).to_str() else {
+        return Err(fail(
+            GooeyResult::InvalidValue,
+            format!("{function}: name is not valid UTF-8"),
+        ));
+    };
+    crate::param_info::param_by_path(name).map_err(|error| {
+        let result = match error {
+            ParamPathError::Malformed(_) => GooeyResult::InvalidValue,
+            ParamPathError::UnknownInstrument(_) => GooeyResult::InvalidInstrument,
+            ParamPathError::UnknownParam(_) => GooeyResult::InvalidParam,
+        };
+        fail(result, format!("{function}: {error}"))
+    })
+}
+
+/// Look up the instrument type and parameter index behind a dotted
+/// parameter name, e.g. `"snare.filter_cutoff"`.
+///
+/// Names are `instrument.param`, with the instrument as in the registry
+/// (`kick`, `snare`, `hihat`, `tom`, `bass`, `fm_snap`, `rimshot`,
+/// `cowbell`, `shaker`) and the parameter in snake_case or camelCase. Hosts
+/// that drive a parameter continuously can resolve it once here and then use
+/// the index setters, which skip the string handling.
+///
+/// # Returns
+/// `GooeyResult::Ok` with `out_instrument` (`INSTRUMENT_*`) and `out_param`
+/// (`*_PARAM_*`) filled in; `NullPointer` for a null argument;
+/// `InvalidValue` for invalid UTF-8 or a name without a dot;
+/// `InvalidInstrument` or `InvalidParam` for an unknown instrument or
+/// parameter. The outputs are untouched on failure.
+///
+/// # Safety
+/// - `name` must be a valid nul-terminated string
+/// - `out_instrument` and `out_param` must be valid pointers to `u32`
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_resolve_param(
+    name: *const c_char,
+    out_instrument: *mut u32,
+    out_param: *mut u32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_resolve_param";
+    if out_instrument.is_null() || out_param.is_null() {
+        return fail(
+            GooeyResult::NullPointer,
+            format!("{FN}: output pointer is null"),
+        );
+    }
+    match resolve_param_name(FN, name) {
+        Ok((instrument, info)) => {
+            *out_instrument = instrument;
+            *out_param = info.index;
+            GooeyResult::Ok
+        }
+        Err(result) => result,
+    }
+}
+
+/// Set an instrument parameter by name, e.g.
+/// `gooey_engine_set_param(engine, "snare.filter_cutoff", 0.4)`.
+///
+/// Equivalent to the matching `gooey_engine_set_*_param` call with the index
+/// the name resolves to (see `gooey_engine_resolve_param`): the value is in
+/// the same setter space, is checked the same way and is smoothed the same
+/// way. The name is resolved on the calling thread; the audio thread only
+/// ever sees the index.
+///
+/// # Returns
+/// `GooeyResult::Ok`; `NullPointer` for a null engine or name; the
+/// `gooey_engine_resolve_param` errors for a bad name; otherwise whatever
+/// the indexed setter returns.
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `name` must be a valid nul-terminated string
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_set_param(
+    engine: *mut GooeyEngine,
+    name: *const c_char,
+    value: f32,
+) -> GooeyResult {
+    const FN: &str = "gooey_engine_set_param";
+    if engine.is_null() {
+        return null_engine(FN);
+    }
+    match resolve_param_name(FN, name) {
+        Ok((instrument, info)) => set_instrument_type_param(engine, instrument, info.index, value),
+        Err(result) => result,
+    }
+}
+
+/// Get an instrument parameter by name, in the same space as
+/// `gooey_engine_set_param`.
+///
+/// # Returns
+/// The current parameter value, or `f32::NAN` if `engine` or `name` is
+/// null, the name resolves to nothing (see `gooey_engine_last_error_message`)
+/// or no channel holds the instrument.
+///
+/// # Safety
+/// - `engine` must be a valid pointer returned by `gooey_engine_new`
+/// - `name` must be a valid nul-terminated string
+#[no_mangle]
+pub unsafe extern "C" fn gooey_engine_get_param(
+    engine: *const GooeyEngine,
+    name: *const c_char,
+) -> f32 {
+    if engine.is_null() {
+        return f32::NAN;
+    }
+    match resolve_param_name("gooey_engine_get_param", name) {
+        Ok((instrument, info)) => (*engine)
+            .voice_by_type(instrument)
+            .map_or(f32::NAN, |voice| voice.param(info.index)),
+        Err(_) => f32::NAN,
+    }
+}
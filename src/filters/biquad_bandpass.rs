@@ -1,4 +1,7 @@
-use std::f32::consts::PI;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::denormal::flush_denormal;
+use core::f32::consts::PI;
 
 /// Biquad Bandpass Filter - RBJ Audio EQ Cookbook implementation
 ///
@@ -129,17 +132,21 @@ impl BiquadBandpass {
             - self.a1 * self.y1
             - self.a2 * self.y2;
 
+        // A NaN/infinity would otherwise stay in the delay line forever
+        if !output.is_finite() {
+            self.reset();
+            return 0.0;
+        }
+
+        // Flush denormals before they reach the feedback taps
+        let output = flush_denormal(output);
+
         // Update delay line
         self.x2 = self.x1;
         self.x1 = input;
         self.y2 = self.y1;
         self.y1 = output;
 
-        // Flush denormals
-        if output.abs() < 1e-15 {
-            return 0.0;
-        }
-
         output
     }
 
@@ -1,4 +1,7 @@
-use std::f32::consts::PI;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::denormal::flush_denormal;
+use core::f32::consts::PI;
 
 /// Biquad Highpass Filter - RBJ Audio EQ Cookbook implementation
 ///
@@ -112,15 +115,17 @@ impl BiquadHighpass {
             - self.a1 * self.y1
             - self.a2 * self.y2;
 
+        if !output.is_finite() {
+            self.reset();
+            return 0.0;
+        }
+        let output = flush_denormal(output);
+
         self.x2 = self.x1;
         self.x1 = input;
         self.y2 = self.y1;
         self.y1 = output;
 
-        if output.abs() < 1e-15 {
-            return 0.0;
-        }
-
         output
     }
 }
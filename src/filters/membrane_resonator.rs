@@ -8,6 +8,8 @@
 //! The filters will "ring" after excitation, decaying naturally based on Q.
 
 use super::BiquadBandpass;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 
 /// Default membrane filter parameters from Max patch preset 1: (gain, freq_hz, q)
 pub const DEFAULT_MEMBRANE_PARAMS: [(f32, f32, f32); 5] = [
@@ -1,3 +1,7 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::denormal::flush_denormal;
+
 pub struct ResonantHighpassFilter {
     pub sample_rate: f32,
     pub cutoff_freq: f32,
@@ -22,7 +26,7 @@ impl ResonantHighpassFilter {
     pub fn process(&mut self, input: f32) -> f32 {
         // Resonant high-pass filter implementation
         // Calculate filter coefficients
-        // let omega = 2.0 * std::f32::consts::PI * self.cutoff_freq / self.sample_rate;
+        // let omega = 2.0 * core::f32::consts::PI * self.cutoff_freq / self.sample_rate;
 
         // let sin_omega = omega.sin();
         // let cos_omega = omega.cos();
@@ -45,9 +49,13 @@ impl ResonantHighpassFilter {
 
         // Apply filter (simple one-pole approximation for efficiency)
         let alpha_simple =
-            1.0 - (-2.0 * std::f32::consts::PI * self.cutoff_freq / self.sample_rate).exp();
+            1.0 - (-2.0 * core::f32::consts::PI * self.cutoff_freq / self.sample_rate).exp();
         let high_pass = input - self.filter_state;
-        self.filter_state += alpha_simple * high_pass;
+        self.filter_state = flush_denormal(self.filter_state + alpha_simple * high_pass);
+        if !self.filter_state.is_finite() || !high_pass.is_finite() {
+            self.reset();
+            return 0.0;
+        }
 
         // Add resonance boost
         high_pass * (1.0 + self.resonance * 0.1)
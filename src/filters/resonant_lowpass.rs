@@ -1,4 +1,7 @@
-use std::f32::consts::PI;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::denormal::flush_denormal;
+use core::f32::consts::PI;
 
 /// Stable two-pole resonant low-pass filter for instrument use.
 ///
@@ -50,14 +53,15 @@ impl ResonantLowpassFilter {
         let v1 = (self.g * (input - self.ic2eq) + self.ic1eq) * self.h;
         let v2 = self.ic2eq + self.g * v1;
 
-        self.ic1eq = 2.0 * v1 - self.ic1eq;
-        self.ic2eq = 2.0 * v2 - self.ic2eq;
-
-        if v2.abs() < 1e-15 {
-            0.0
-        } else {
-            v2
+        if !v2.is_finite() {
+            self.reset();
+            return 0.0;
         }
+
+        self.ic1eq = flush_denormal(2.0 * v1 - self.ic1eq);
+        self.ic2eq = flush_denormal(2.0 * v2 - self.ic2eq);
+
+        flush_denormal(v2)
     }
 
     /// Set cutoff frequency.
@@ -106,7 +110,7 @@ impl ResonantLowpassFilter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::f32::consts::TAU;
+    use core::f32::consts::TAU;
 
     fn response_rms(sample_rate: f32, cutoff: f32, q: f32, frequency: f32) -> f32 {
         let mut filter = ResonantLowpassFilter::new(sample_rate, cutoff, q);
@@ -1,4 +1,7 @@
-use std::f32::consts::PI;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::denormal::flush_denormal;
+use core::f32::consts::PI;
 
 /// State Variable Filter - 2nd order resonant filter
 ///
@@ -49,6 +52,19 @@ impl StateVariableFilter {
         self.band = 0.0;
     }
 
+    /// Flush denormal integrator state, resetting the filter if it has gone
+    /// non-finite. Returns false after a reset.
+    #[inline]
+    fn sanitize_state(&mut self) -> bool {
+        if !self.low.is_finite() || !self.band.is_finite() {
+            self.reset();
+            return false;
+        }
+        self.low = flush_denormal(self.low);
+        self.band = flush_denormal(self.band);
+        true
+    }
+
     /// Update internal coefficients from cutoff and resonance
     fn update_coefficients(&mut self) {
         // Chamberlin SVF coefficients
@@ -71,6 +87,9 @@ impl StateVariableFilter {
             self.band = self.f * high + self.band;
         }
 
+        if !self.sanitize_state() {
+            return 0.0;
+        }
         self.band
     }
 
@@ -87,6 +106,9 @@ impl StateVariableFilter {
             self.band = self.f * high + self.band;
         }
 
+        if !self.sanitize_state() {
+            return (0.0, 0.0, 0.0);
+        }
         (self.low, self.band, high)
     }
 
@@ -127,11 +149,19 @@ impl StateVariableFilter {
         }
     }
 
-    /// Set both cutoff and resonance at once (more efficient)
+    /// Set both cutoff and resonance at once (more efficient). Coefficients
+    /// are only recomputed when either changes, so this is cheap to call
+    /// every sample with settled values.
     pub fn set_params(&mut self, cutoff_freq: f32, resonance: f32) {
-        self.cutoff_freq = cutoff_freq.clamp(20.0, 20000.0);
-        self.resonance = resonance.max(0.5);
-        self.update_coefficients();
+        let new_cutoff = cutoff_freq.clamp(20.0, 20000.0);
+        let new_resonance = resonance.max(0.5);
+        if (new_cutoff - self.cutoff_freq).abs() > 0.001
+            || (new_resonance - self.resonance).abs() > 0.001
+        {
+            self.cutoff_freq = new_cutoff;
+            self.resonance = new_resonance;
+            self.update_coefficients();
+        }
     }
 }
 
@@ -1,4 +1,7 @@
-use std::f32::consts::PI;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::denormal::flush_denormal;
+use core::f32::consts::PI;
 
 /// State Variable Filter (TPT/ZDF) - stable at high cutoff
 ///
@@ -58,8 +61,14 @@ impl StateVariableFilterTpt {
         let v1 = (self.g * (input - self.ic2eq) + self.ic1eq) * self.h;
         let v2 = self.ic2eq + self.g * v1;
 
-        self.ic1eq = 2.0 * v1 - self.ic1eq;
-        self.ic2eq = 2.0 * v2 - self.ic2eq;
+        // A NaN/infinity would otherwise stay in the integrators forever
+        if !v1.is_finite() || !v2.is_finite() {
+            self.reset();
+            return (0.0, 0.0, 0.0);
+        }
+
+        self.ic1eq = flush_denormal(2.0 * v1 - self.ic1eq);
+        self.ic2eq = flush_denormal(2.0 * v2 - self.ic2eq);
 
         let low = v2;
         let band = v1;
@@ -9,6 +9,9 @@
 //! treats the signal as stereo, so it only ever has to change how frames are
 //! produced, not how they are consumed.
 
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
 /// A single stereo sample: a left and a right channel value.
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct StereoFrame {
@@ -29,7 +32,7 @@ impl StereoFrame {
     /// right. Center is -3 dB per channel (`0.707`), keeping constant power
     /// across the sweep.
     pub fn panned(x: f32, pan: f32) -> Self {
-        let angle = pan.clamp(0.0, 1.0) * std::f32::consts::FRAC_PI_2;
+        let angle = pan.clamp(0.0, 1.0) * core::f32::consts::FRAC_PI_2;
         Self {
             l: x * angle.cos(),
             r: x * angle.sin(),
@@ -53,7 +56,7 @@ impl StereoFrame {
     }
 }
 
-impl std::ops::Add for StereoFrame {
+impl core::ops::Add for StereoFrame {
     type Output = StereoFrame;
 
     #[inline]
@@ -65,7 +68,7 @@ impl std::ops::Add for StereoFrame {
     }
 }
 
-impl std::ops::AddAssign for StereoFrame {
+impl core::ops::AddAssign for StereoFrame {
     #[inline]
     fn add_assign(&mut self, rhs: StereoFrame) {
         self.l += rhs.l;
@@ -73,7 +76,7 @@ impl std::ops::AddAssign for StereoFrame {
     }
 }
 
-impl std::ops::Mul<f32> for StereoFrame {
+impl core::ops::Mul<f32> for StereoFrame {
     type Output = StereoFrame;
 
     #[inline]
@@ -128,7 +131,7 @@ mod tests {
     fn panned_center_is_equal_and_minus_three_db() {
         let f = StereoFrame::panned(1.0, 0.5);
         assert!((f.l - f.r).abs() < 1e-6);
-        assert!((f.l - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+        assert!((f.l - core::f32::consts::FRAC_1_SQRT_2).abs() < 1e-6);
     }
 
     #[test]
@@ -1,5 +1,6 @@
 pub mod click_osc;
 pub mod morph_osc;
+pub mod noise_color;
 pub mod oscillator;
 pub mod pink_noise;
 pub mod polyblep;
@@ -7,7 +8,31 @@ pub mod waveform;
 
 pub use self::click_osc::*;
 pub use self::morph_osc::*;
+pub use self::noise_color::*;
 pub use self::oscillator::*;
 pub use self::pink_noise::*;
 pub use self::polyblep::*;
 pub use self::waveform::*;
+
+/// Hash a counter for hash-based white noise. `std` builds keep the
+/// `DefaultHasher` output the goldens were recorded with; `no_std` builds use
+/// the SplitMix64 finalizer, which is just as white but a different sequence.
+#[inline]
+pub(crate) fn noise_hash(value: u64) -> u64 {
+    #[cfg(feature = "std")]
+    {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
@@ -11,13 +11,13 @@
 //! - Combined noise is scaled by 0.4
 //! - Channel 3 also includes a gated sine when tone < 99
 
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 
 /// Generate sine wave from phase (0.0 to 1.0)
 #[inline]
 fn sine(phase: f32) -> f32 {
-    (phase * 2.0 * std::f32::consts::PI).sin()
+    (phase * 2.0 * core::f32::consts::PI).sin()
 }
 
 /// Generate triangle wave from phase (0.0 to 1.0)
@@ -40,9 +40,7 @@ fn mtof(midi: f32) -> f32 {
 /// Generate white noise using hash function (same pattern as PinkNoise/Oscillator)
 #[inline]
 fn white_noise(counter: u64) -> f32 {
-    let mut hasher = DefaultHasher::new();
-    counter.hash(&mut hasher);
-    let hash = hasher.finish();
+    let hash = crate::gen::noise_hash(counter);
     (hash as f32) / (u64::MAX as f32) * 2.0 - 1.0
 }
 
@@ -66,6 +64,7 @@ pub struct MorphOsc {
 
     // Noise channel state
     noise_counter: u64,    // Counter for hash-based white noise
+    noise_seed: u64,       // Counter value each reset starts from
     rand_phase: f32,       // Phase for rand~ timing (0 to 1)
     rand_current: f32,     // Current interpolation start value
     rand_target: f32,      // Target value to ramp toward
@@ -81,6 +80,7 @@ impl MorphOsc {
             tri_phase: 0.0,
             fixed_sine_phase: 0.0,
             noise_counter: 0,
+            noise_seed: 0,
             rand_phase: 0.0,
             rand_current: 0.0,
             rand_target: 0.0,
@@ -88,12 +88,19 @@ impl MorphOsc {
         }
     }
 
+    /// Start the noise channel from `seed` at every reset, picking a
+    /// different (but repeatable) noise sequence.
+    pub fn set_noise_seed(&mut self, seed: u64) {
+        self.noise_seed = seed;
+        self.noise_counter = seed;
+    }
+
     /// Reset all phase accumulators (call on trigger)
     pub fn reset(&mut self) {
         self.main_sine_phase = 0.0;
         self.tri_phase = 0.0;
         self.fixed_sine_phase = 0.0;
-        self.noise_counter = 0;
+        self.noise_counter = self.noise_seed;
         self.rand_phase = 0.0;
         self.rand_current = 0.0;
         self.rand_target = 0.0;
@@ -0,0 +1,99 @@
+//! Noise color: white noise turned pink or brown.
+//!
+//! [`NoiseColor`] filters an existing white-noise signal rather than
+//! generating its own, so an instrument keeps its noise source (and its
+//! determinism) and only gains a color control. Pink falls 3 dB per octave,
+//! brown 6 dB per octave above a low corner. Every color comes out at about
+//! the input's level, so sweeping the color changes the tone, not the volume.
+
+use crate::gen::pink_noise::PinkFilter;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Brings the pinking filter's output back to its input's RMS.
+const PINK_GAIN: f32 = 0.336;
+/// Below this the brown filter flattens out instead of rising without bound.
+const BROWN_CORNER_HZ: f32 = 40.0;
+
+/// Blends white noise continuously through pink to brown.
+pub struct NoiseColor {
+    pink: PinkFilter,
+    brown: f32,
+    brown_pole: f32,
+    brown_gain: f32,
+}
+
+impl NoiseColor {
+    pub fn new(sample_rate: f32) -> Self {
+        let sample_rate = sample_rate.max(1.0);
+        let brown_pole = (-core::f32::consts::TAU * BROWN_CORNER_HZ / sample_rate).exp();
+        Self {
+            pink: PinkFilter::new(sample_rate),
+            brown: 0.0,
+            brown_pole,
+            // A leaky integrator's gain for unit output variance
+            brown_gain: (1.0 - brown_pole * brown_pole).sqrt(),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.pink.reset();
+        self.brown = 0.0;
+    }
+
+    /// Color one white-noise sample. `color` runs from white (0.0, the input
+    /// unchanged) through pink (0.5) to brown (1.0), crossfading between.
+    /// Both filters run whatever the color, so it can move while noise plays.
+    #[inline]
+    pub fn process(&mut self, white: f32, color: f32) -> f32 {
+        let pink = self.pink.process(white) * PINK_GAIN;
+        self.brown = self.brown_pole * self.brown + self.brown_gain * white;
+        let position = color.clamp(0.0, 1.0) * 2.0;
+        if position <= 1.0 {
+            white + (pink - white) * position
+        } else {
+            pink + (self.brown - pink) * (position - 1.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::Rng;
+
+    /// RMS and lag-one autocorrelation of white noise colored at `color`.
+    fn stats(color: f32) -> (f32, f32) {
+        let mut rng = Rng::new(3);
+        let mut noise = NoiseColor::new(48_000.0);
+        let samples: Vec<f32> = (0..96_000)
+            .map(|_| noise.process(rng.next_bipolar(), color))
+            .skip(4_800)
+            .collect();
+        let power = samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32;
+        let lag = samples.windows(2).map(|w| w[0] * w[1]).sum::<f32>() / samples.len() as f32;
+        (power.sqrt(), lag / power)
+    }
+
+    #[test]
+    fn colors_keep_the_level_and_darken_in_order() {
+        let (white_rms, white_corr) = stats(0.0);
+        let (pink_rms, pink_corr) = stats(0.5);
+        let (brown_rms, brown_corr) = stats(1.0);
+        for rms in [pink_rms, brown_rms] {
+            assert!((rms / white_rms - 1.0).abs() < 0.1, "{rms} vs {white_rms}");
+        }
+        // Darker noise changes less from one sample to the next
+        assert!(white_corr.abs() < 0.05);
+        assert!(pink_corr > white_corr + 0.2);
+        assert!(brown_corr > pink_corr && brown_corr > 0.95);
+    }
+
+    #[test]
+    fn white_passes_through_untouched() {
+        let mut noise = NoiseColor::new(44_100.0);
+        for sample in [0.5, -0.25, 1.0, -1.0] {
+            assert_eq!(noise.process(sample, 0.0), sample);
+        }
+    }
+}
@@ -1,8 +1,8 @@
 use crate::envelope::{ADSRConfig, Envelope};
 use crate::gen::polyblep;
 use crate::gen::waveform::Waveform;
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 
 pub struct Oscillator {
     pub sample_rate: f32,
@@ -40,7 +40,7 @@ impl Oscillator {
     // }
 
     fn calculate_sine_output_from_freq(&self, freq: f32) -> f32 {
-        let two_pi = 2.0 * std::f32::consts::PI;
+        let two_pi = 2.0 * core::f32::consts::PI;
         // current_sample_index is now in samples, so use the original calculation
         (self.current_sample_index * freq * two_pi / self.sample_rate).sin()
     }
@@ -184,19 +184,50 @@ impl Oscillator {
         carrier * modulator
     }
 
+    /// Whole samples since the trigger. Rounded rather than truncated: the
+    /// elapsed time is a difference of two large times late in a session, and
+    /// a hair under an integer must still land on that sample.
+    fn sample_index(&self) -> u64 {
+        self.current_sample_index.round() as u64
+    }
+
     fn noise_wave_time_based(&self) -> f32 {
         // Use current sample index to generate pseudo-random noise
-        let mut hasher = DefaultHasher::new();
-        (self.current_sample_index as u64).hash(&mut hasher);
-        let hash = hasher.finish();
+        let hash = crate::gen::noise_hash(self.sample_index());
 
         // Convert hash to float in range [-1, 1.0]
         let normalized = (hash as f32) / (u64::MAX as f32);
         (normalized * 2.0) - 1.0
     }
 
+    /// Velvet noise: one impulse of random sign at a random sample in every
+    /// period of `frequency_hz`, silence between. It sounds as smooth as
+    /// white noise at high densities but keeps sharper, crisper transients.
+    fn velvet_wave_time_based(&self) -> f32 {
+        let period = (self.sample_rate / self.frequency_hz.max(1.0))
+            .round()
+            .max(1.0) as u64;
+        let index = self.sample_index();
+        let cell = index / period;
+        // Salted so the impulses don't follow the white-noise sequence
+        let hash = crate::gen::noise_hash(cell ^ 0x7665_6c76_6574);
+        if index != cell * period + (hash >> 1) % period {
+            0.0
+        } else if hash & 1 == 0 {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+
     pub fn trigger(&mut self, time: f64) {
-        self.envelope.trigger(time);
+        self.trigger_with_velocity(time, 1.0);
+    }
+
+    /// Trigger with a velocity (0.0 to 1.0), applied through the envelope's
+    /// velocity sensitivity (see `ADSRConfig::with_velocity_sensitivity`).
+    pub fn trigger_with_velocity(&mut self, time: f64, velocity: f32) {
+        self.envelope.trigger_with_velocity(time, velocity);
         // Reset phase for consistent sound on each trigger
         self.current_sample_index = 0.0;
     }
@@ -279,6 +310,7 @@ impl Oscillator {
             }
             Waveform::RingMod => self.ring_mod_wave_time_based(),
             Waveform::Noise => self.noise_wave_time_based(),
+            Waveform::Velvet => self.velvet_wave_time_based(),
         };
 
         let envelope_amplitude = self.envelope.get_amplitude(current_time);
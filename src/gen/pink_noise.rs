@@ -4,6 +4,9 @@
 //! (1/f). This implementation uses a deterministic white-noise source followed
 //! by a sample-rate-aware version of Paul Kellet's economy pink-noise filter.
 
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
 const REFERENCE_SAMPLE_RATE: f32 = 44_100.0;
 const RNG_SEED: u64 = 0x1234_5678_9abc_def0;
 const REFERENCE_POLES: [f32; 3] = [0.99765, 0.96300, 0.57000];
@@ -13,14 +16,20 @@ const OUTPUT_GAIN: f32 = 0.11;
 
 /// Pink noise generator with an approximately 1/f frequency spectrum.
 pub struct PinkNoise {
+    seed: u64,
     rng_state: u64,
-    filter_state: [f32; 3],
+    filter: PinkFilter,
+}
+
+/// The pinking filter on its own: turns any white-noise input into pink noise.
+pub struct PinkFilter {
+    state: [f32; 3],
     poles: [f32; 3],
     gains: [f32; 3],
 }
 
-impl PinkNoise {
-    /// Create a pink-noise generator for a fixed audio sample rate.
+impl PinkFilter {
+    /// Create a pinking filter for a fixed audio sample rate.
     pub fn new(sample_rate: f32) -> Self {
         let sample_rate = sample_rate.max(1.0);
         let rate_ratio = REFERENCE_SAMPLE_RATE / sample_rate;
@@ -38,29 +47,56 @@ impl PinkNoise {
         }
 
         Self {
-            rng_state: RNG_SEED,
-            filter_state: [0.0; 3],
+            state: [0.0; 3],
             poles,
             gains,
         }
     }
 
-    /// Reset the generator to its initial deterministic sequence.
     pub fn reset(&mut self) {
-        self.rng_state = RNG_SEED;
-        self.filter_state = [0.0; 3];
+        self.state = [0.0; 3];
     }
 
-    /// Generate the next pink-noise sample.
+    /// Filter one white-noise sample. The output is unscaled: for white input
+    /// of RMS `r` it has an RMS of about `3r`.
     #[inline]
-    pub fn tick(&mut self) -> f32 {
-        let white = self.next_white_sample();
-
+    pub fn process(&mut self, white: f32) -> f32 {
         for i in 0..3 {
-            self.filter_state[i] = self.poles[i] * self.filter_state[i] + self.gains[i] * white;
+            self.state[i] = self.poles[i] * self.state[i] + self.gains[i] * white;
+        }
+
+        self.state.iter().sum::<f32>() + white * DIRECT_GAIN
+    }
+}
+
+impl PinkNoise {
+    /// Create a pink-noise generator for a fixed audio sample rate.
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            seed: RNG_SEED,
+            rng_state: RNG_SEED,
+            filter: PinkFilter::new(sample_rate),
         }
+    }
 
-        (self.filter_state.iter().sum::<f32>() + white * DIRECT_GAIN) * OUTPUT_GAIN
+    /// Reset the generator to the start of its deterministic sequence.
+    pub fn reset(&mut self) {
+        self.rng_state = self.seed;
+        self.filter.reset();
+    }
+
+    /// Switch to the sequence for `seed` and reset.
+    pub fn set_seed(&mut self, seed: u64) {
+        // xorshift sticks at zero
+        self.seed = if seed == 0 { RNG_SEED } else { seed };
+        self.reset();
+    }
+
+    /// Generate the next pink-noise sample.
+    #[inline]
+    pub fn tick(&mut self) -> f32 {
+        let white = self.next_white_sample();
+        self.filter.process(white) * OUTPUT_GAIN
     }
 
     #[inline]
@@ -82,7 +118,7 @@ impl PinkNoise {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::f32::consts::TAU;
+    use core::f32::consts::TAU;
 
     fn octave_bin_powers(sample_rate: f32) -> Vec<f64> {
         const BLOCK_SIZE: usize = 4096;
@@ -6,4 +6,6 @@ pub enum Waveform {
     Triangle,
     RingMod,
     Noise,
+    /// Sparse ±1 impulses, one at a random point in each period
+    Velvet,
 }
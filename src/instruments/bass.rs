@@ -1,13 +1,24 @@
+use crate::effects::saturator::SaturatorModel;
 use crate::effects::waveshaper::Waveshaper;
 use crate::envelope::{ADSRConfig, Envelope, EnvelopeCurve};
+use crate::error::GooeyError;
 use crate::filters::StateVariableFilterTpt;
 use crate::gen::polyblep::{polyblep_saw, polyblep_square};
-use crate::utils::{tuning_to_multiplier, Blendable, SmoothedParam, DEFAULT_SMOOTH_TIME_MS};
-use std::f64::consts::TAU;
+use crate::partial_config;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::{
+    tuning_to_multiplier, Blendable, ControlRate, RetriggerFade, SmoothedParam,
+    DEFAULT_SMOOTH_TIME_MS,
+};
+use core::f64::consts::TAU;
 
 /// Normalization ranges for bass synth parameters.
 /// All external-facing parameters use 0.0-1.0 normalized values.
 pub(crate) mod ranges {
+    #[cfg(not(feature = "std"))]
+    use crate::prelude::Float;
+
     /// Frequency: 0-1 maps to 30-200 Hz
     pub const FREQ_MIN: f32 = 30.0;
     pub const FREQ_MAX: f32 = 200.0;
@@ -269,6 +280,27 @@ impl BassConfig {
     }
 }
 
+partial_config!(
+    /// [`BassConfig`] with every field optional: only the fields set are applied
+    pub struct PartialBassConfig for BassConfig {
+        frequency: f32,
+        sub_level: f32,
+        osc_level: f32,
+        detune_level: f32,
+        detune_amount: f32,
+        osc_shape: f32,
+        filter_cutoff: f32,
+        filter_resonance: f32,
+        filter_env_amount: f32,
+        filter_env_decay: f32,
+        filter_env_curve: f32,
+        amp_decay: f32,
+        amp_decay_curve: f32,
+        overdrive: f32,
+        volume: f32,
+    }
+);
+
 impl Blendable for BassConfig {
     fn lerp(&self, other: &Self, t: f32) -> Self {
         let t = t.clamp(0.0, 1.0);
@@ -588,6 +620,9 @@ pub struct BassSynth {
 
     // Filter (TPT SVF for stability at high resonance)
     filter: StateVariableFilterTpt,
+    /// The cutoff follows the filter envelope; coefficients are updated at
+    /// block rate
+    filter_rate: ControlRate,
 
     // Envelopes
     amp_envelope: Envelope,
@@ -598,6 +633,10 @@ pub struct BassSynth {
 
     // State
     is_active: bool,
+    /// Releases the previous hit when retriggered mid-decay
+    retrigger_fade: RetriggerFade,
+    /// Master tuning multiplier (A4 reference and transpose)
+    pitch_ratio: f32,
     current_velocity: f32,
 
     // Frequency snapshot frozen at trigger time
@@ -624,15 +663,29 @@ impl BassSynth {
                 config.filter_cutoff_hz(),
                 config.filter_resonance_q(),
             ),
+            filter_rate: ControlRate::default(),
             amp_envelope: Envelope::new(),
             filter_envelope: Envelope::new(),
             waveshaper: Waveshaper::new(config.overdrive, 1.0),
             is_active: false,
+            retrigger_fade: RetriggerFade::new(sample_rate),
+            pitch_ratio: 1.0,
             current_velocity: 1.0,
             triggered_frequency: config.frequency_hz(),
         }
     }
 
+    /// Change only the fields set in `partial`; the rest keep their current
+    /// values.
+    pub fn apply_partial(&mut self, partial: &PartialBassConfig) {
+        self.set_config(self.config().with_partial(partial));
+    }
+
+    /// Get current config snapshot (reads current smoothed values)
+    pub fn config(&self) -> BassConfig {
+        self.params.to_config()
+    }
+
     pub fn set_config(&mut self, config: BassConfig) {
         self.params.frequency.set_target(config.frequency);
         self.params.sub_level.set_target(config.sub_level);
@@ -665,6 +718,13 @@ impl BassSynth {
         self.params.snap_all();
     }
 
+    /// Note-off: both envelopes enter their release from wherever they are,
+    /// so a gated note ends early instead of running out its decay.
+    pub fn release(&mut self, time: f64) {
+        self.amp_envelope.release(time);
+        self.filter_envelope.release(time);
+    }
+
     // Individual parameter setters (normalized 0-1)
 
     pub fn set_frequency(&mut self, value: f32) {
@@ -733,64 +793,36 @@ impl BassSynth {
         self.params.overdrive.set_target(value.clamp(0.0, 1.0));
     }
 
-    pub fn set_volume(&mut self, value: f32) {
-        self.params.volume.set_target(value.clamp(0.0, 1.0));
+    /// Select the pre-filter overdrive stage's saturation curve
+    pub fn set_overdrive_model(&mut self, model: SaturatorModel) {
+        self.waveshaper.set_model(model);
     }
 
-    /// Set tuning offset (smoothed, 0-1: 0=−12 semitones, 0.5=neutral, 1=+12 semitones)
-    pub fn set_tuning(&mut self, value: f32) {
-        self.params.tuning.set_target(value.clamp(0.0, 1.0));
+    /// Get the pre-filter overdrive stage's saturation curve
+    pub fn overdrive_model(&self) -> SaturatorModel {
+        self.waveshaper.model()
     }
-}
-
-impl crate::engine::Instrument for BassSynth {
-    fn trigger_with_velocity(&mut self, time: f64, velocity: f32) {
-        self.current_velocity = velocity.clamp(0.0, 1.0);
-        self.is_active = true;
 
-        // Reset phase accumulators
-        self.sub_phase = 0.0;
-        self.osc_phase = 0.0;
-        self.detune_phase = 0.0;
-
-        // Snapshot frequency at trigger time
-        self.triggered_frequency = self.params.frequency_hz();
-
-        // Configure amplitude envelope
-        let amp_decay = self.params.amp_decay_secs();
-        let amp_curve = self.params.amp_decay_curve_value();
-        self.amp_envelope.set_config(ADSRConfig {
-            attack_time: 0.002, // 2ms click-free attack
-            decay_time: amp_decay,
-            sustain_level: 0.0,
-            release_time: amp_decay * 0.1,
-            attack_curve: EnvelopeCurve::Linear,
-            decay_curve: EnvelopeCurve::Exponential(amp_curve),
-        });
-        self.amp_envelope.trigger(time);
+    /// Turn auto gain on or off for the pre-filter overdrive stage
+    pub fn set_overdrive_auto_gain(&mut self, enabled: bool) {
+        self.waveshaper.set_auto_gain(enabled);
+    }
 
-        // Configure filter envelope
-        let filter_decay = self.params.filter_env_decay_secs();
-        let filter_curve = self.params.filter_env_curve_value();
-        self.filter_envelope.set_config(ADSRConfig {
-            attack_time: 0.001, // Nearly instant attack for snappy filter
-            decay_time: filter_decay,
-            sustain_level: 0.0,
-            release_time: filter_decay * 0.1,
-            attack_curve: EnvelopeCurve::Linear,
-            decay_curve: EnvelopeCurve::Exponential(filter_curve),
-        });
-        self.filter_envelope.trigger(time);
+    /// Whether the pre-filter overdrive stage has auto gain on
+    pub fn overdrive_auto_gain(&self) -> bool {
+        self.waveshaper.auto_gain()
+    }
 
-        // Reset filter state to avoid artifacts from previous note
-        self.filter.reset();
+    pub fn set_volume(&mut self, value: f32) {
+        self.params.volume.set_target(value.clamp(0.0, 1.0));
+    }
 
-        // Update waveshaper drive
-        let overdrive = self.params.overdrive.get();
-        self.waveshaper.set_drive(1.0 + overdrive * 9.0);
+    /// Set tuning offset (smoothed, 0-1: 0=−12 semitones, 0.5=neutral, 1=+12 semitones)
+    pub fn set_tuning(&mut self, value: f32) {
+        self.params.tuning.set_target(value.clamp(0.0, 1.0));
     }
 
-    fn tick(&mut self, current_time: f64) -> f32 {
+    fn render(&mut self, current_time: f64) -> f32 {
         // Always tick smoothed params
         self.params.tick();
 
@@ -799,7 +831,9 @@ impl crate::engine::Instrument for BassSynth {
         }
 
         // Read params
-        let freq = self.triggered_frequency * tuning_to_multiplier(self.params.tuning.get());
+        let freq = self.triggered_frequency
+            * tuning_to_multiplier(self.params.tuning.get())
+            * self.pitch_ratio;
         let sub_level = self.params.sub_level.get();
         let osc_level = self.params.osc_level.get();
         let detune_level = self.params.detune_level.get();
@@ -850,15 +884,17 @@ impl crate::engine::Instrument for BassSynth {
         };
 
         // Filter with envelope modulation
-        let filter_env = self.filter_envelope.get_amplitude(current_time);
-        let base_cutoff = self.params.filter_cutoff_hz();
-        let env_amount = self.params.filter_env_amount.get();
-        // Envelope sweeps from (base + offset) down to base
-        let env_offset = (ranges::FILTER_CUTOFF_MAX - base_cutoff) * env_amount * filter_env;
-        let cutoff =
-            (base_cutoff + env_offset).clamp(ranges::FILTER_CUTOFF_MIN, ranges::FILTER_CUTOFF_MAX);
-        let resonance = self.params.filter_resonance_q();
-        self.filter.set_params(cutoff, resonance);
+        if self.filter_rate.tick() {
+            let filter_env = self.filter_envelope.get_amplitude(current_time);
+            let base_cutoff = self.params.filter_cutoff_hz();
+            let env_amount = self.params.filter_env_amount.get();
+            // Envelope sweeps from (base + offset) down to base
+            let env_offset = (ranges::FILTER_CUTOFF_MAX - base_cutoff) * env_amount * filter_env;
+            let cutoff = (base_cutoff + env_offset)
+                .clamp(ranges::FILTER_CUTOFF_MIN, ranges::FILTER_CUTOFF_MAX);
+            let resonance = self.params.filter_resonance_q();
+            self.filter.set_params(cutoff, resonance);
+        }
         let (filtered, _, _) = self.filter.process_all(saturated);
 
         // Amplitude envelope
@@ -875,6 +911,67 @@ impl crate::engine::Instrument for BassSynth {
 
         output
     }
+}
+
+impl crate::engine::Instrument for BassSynth {
+    fn trigger_with_velocity(&mut self, time: f64, velocity: f32) {
+        self.retrigger_fade.retrigger();
+        self.filter_rate.reset();
+        self.current_velocity = velocity.clamp(0.0, 1.0);
+        self.is_active = true;
+
+        // Reset phase accumulators
+        self.sub_phase = 0.0;
+        self.osc_phase = 0.0;
+        self.detune_phase = 0.0;
+
+        // Snapshot frequency at trigger time
+        self.triggered_frequency = self.params.frequency_hz();
+
+        // Configure amplitude envelope
+        let amp_decay = self.params.amp_decay_secs();
+        let amp_curve = self.params.amp_decay_curve_value();
+        self.amp_envelope.set_config(ADSRConfig {
+            attack_time: 0.002, // 2ms click-free attack
+            decay_time: amp_decay,
+            sustain_level: 0.0,
+            release_time: amp_decay * 0.1,
+            attack_curve: EnvelopeCurve::Linear,
+            decay_curve: EnvelopeCurve::Exponential(amp_curve),
+            ..ADSRConfig::default()
+        });
+        self.amp_envelope.trigger(time);
+
+        // Configure filter envelope
+        let filter_decay = self.params.filter_env_decay_secs();
+        let filter_curve = self.params.filter_env_curve_value();
+        self.filter_envelope.set_config(ADSRConfig {
+            attack_time: 0.001, // Nearly instant attack for snappy filter
+            decay_time: filter_decay,
+            sustain_level: 0.0,
+            release_time: filter_decay * 0.1,
+            attack_curve: EnvelopeCurve::Linear,
+            decay_curve: EnvelopeCurve::Exponential(filter_curve),
+            ..ADSRConfig::default()
+        });
+        self.filter_envelope.trigger(time);
+
+        // Reset filter state to avoid artifacts from previous note
+        self.filter.reset();
+
+        // Update waveshaper drive
+        let overdrive = self.params.overdrive.get();
+        self.waveshaper.set_drive(1.0 + overdrive * 9.0);
+    }
+
+    fn tick(&mut self, current_time: f64) -> f32 {
+        let output = self.render(current_time);
+        self.retrigger_fade.process(output)
+    }
+
+    fn release(&mut self, time: f64) {
+        self.release(time)
+    }
 
     fn is_active(&self) -> bool {
         self.is_active
@@ -883,6 +980,10 @@ impl crate::engine::Instrument for BassSynth {
     fn as_modulatable(&mut self) -> Option<&mut dyn crate::engine::Modulatable> {
         Some(self)
     }
+
+    fn set_pitch_ratio(&mut self, ratio: f32) {
+        self.pitch_ratio = ratio;
+    }
 }
 
 // Implement modulation support for BassSynth
@@ -909,7 +1010,7 @@ impl crate::engine::Modulatable for BassSynth {
         ]
     }
 
-    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), String> {
+    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), GooeyError> {
         match parameter {
             "frequency" => {
                 self.params.frequency.set_bipolar(value);
@@ -975,7 +1076,7 @@ impl crate::engine::Modulatable for BassSynth {
                 self.params.tuning.set_bipolar(value);
                 Ok(())
             }
-            _ => Err(format!("Unknown parameter: {}", parameter)),
+            _ => Err(GooeyError::UnknownParameter(parameter.to_string())),
         }
     }
 
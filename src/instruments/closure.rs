@@ -0,0 +1,164 @@
+//! An instrument built from closures, for prototyping custom voices
+//!
+//! Writing an [`Instrument`] normally means a struct, a constructor and a
+//! trait impl. `ClosureInstrument` skips that: it owns a piece of state and
+//! calls user closures to trigger and render it, so a voice can be sketched
+//! in a few lines and added to an [`crate::engine::Engine`] like any other.
+//!
+//! ```
+//! use gooey::engine::Instrument;
+//! use gooey::instruments::ClosureInstrument;
+//!
+//! // A decaying sine blip
+//! struct Blip {
+//!     phase: f32,
+//!     level: f32,
+//! }
+//!
+//! let mut blip = ClosureInstrument::new(
+//!     Blip { phase: 0.0, level: 0.0 },
+//!     |blip, _time, velocity| {
+//!         blip.phase = 0.0;
+//!         blip.level = velocity;
+//!     },
+//!     |blip, _time| {
+//!         blip.phase = (blip.phase + 880.0 / 44_100.0).fract();
+//!         blip.level *= 0.9995;
+//!         (blip.phase * core::f32::consts::TAU).sin() * blip.level
+//!     },
+//! )
+//! .with_active(|blip| blip.level > 1e-4);
+//!
+//! blip.trigger(0.0);
+//! assert!(blip.is_active());
+//! ```
+
+use alloc::boxed::Box;
+
+use crate::engine::Instrument;
+
+type TriggerFn<S> = Box<dyn FnMut(&mut S, f64, f32) + Send>;
+type TickFn<S> = Box<dyn FnMut(&mut S, f64) -> f32 + Send>;
+type ReleaseFn<S> = Box<dyn FnMut(&mut S, f64) + Send>;
+type ActiveFn<S> = Box<dyn Fn(&S) -> bool + Send>;
+
+/// An [`Instrument`] whose trigger and tick are closures over a state `S`.
+///
+/// The closures get the state by `&mut`, so they share it without locks:
+/// `trigger` receives the time and velocity, `tick` the time and returns the
+/// next sample. Release and the activity check are optional; without them a
+/// release does nothing and the voice reports active from its first trigger
+/// on.
+pub struct ClosureInstrument<S> {
+    state: S,
+    trigger: TriggerFn<S>,
+    tick: TickFn<S>,
+    release: Option<ReleaseFn<S>>,
+    active: Option<ActiveFn<S>>,
+    triggered: bool,
+}
+
+impl<S: Send> ClosureInstrument<S> {
+    pub fn new(
+        state: S,
+        trigger: impl FnMut(&mut S, f64, f32) + Send + 'static,
+        tick: impl FnMut(&mut S, f64) -> f32 + Send + 'static,
+    ) -> Self {
+        Self {
+            state,
+            trigger: Box::new(trigger),
+            tick: Box::new(tick),
+            release: None,
+            active: None,
+            triggered: false,
+        }
+    }
+
+    /// Handle note-off with `release`, called with the time of the release.
+    pub fn with_release(mut self, release: impl FnMut(&mut S, f64) + Send + 'static) -> Self {
+        self.release = Some(Box::new(release));
+        self
+    }
+
+    /// Report activity with `active` instead of "triggered at least once".
+    pub fn with_active(mut self, active: impl Fn(&S) -> bool + Send + 'static) -> Self {
+        self.active = Some(Box::new(active));
+        self
+    }
+
+    /// The voice's state.
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// The voice's state, for changing its parameters between hits.
+    pub fn state_mut(&mut self) -> &mut S {
+        &mut self.state
+    }
+}
+
+impl<S: Send> Instrument for ClosureInstrument<S> {
+    fn trigger_with_velocity(&mut self, time: f64, velocity: f32) {
+        self.triggered = true;
+        (self.trigger)(&mut self.state, time, velocity.clamp(0.0, 1.0));
+    }
+
+    fn tick(&mut self, current_time: f64) -> f32 {
+        (self.tick)(&mut self.state, current_time)
+    }
+
+    fn release(&mut self, time: f64) {
+        if let Some(release) = &mut self.release {
+            release(&mut self.state, time);
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        match &self.active {
+            Some(active) => active(&self.state),
+            None => self.triggered,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A gate that outputs its velocity until released.
+    fn gate() -> ClosureInstrument<f32> {
+        ClosureInstrument::new(
+            0.0,
+            |level, _, velocity| *level = velocity,
+            |level, _| *level,
+        )
+        .with_release(|level, _| *level = 0.0)
+        .with_active(|level| *level > 0.0)
+    }
+
+    #[test]
+    fn test_closures_share_state() {
+        let mut voice = gate();
+        assert!(!voice.is_active());
+        voice.trigger_with_velocity(0.0, 0.5);
+        assert_eq!(voice.tick(0.0), 0.5);
+        assert!(voice.is_active());
+        voice.release(0.1);
+        assert_eq!(voice.tick(0.1), 0.0);
+        assert!(!voice.is_active());
+        *voice.state_mut() = 0.25;
+        assert_eq!(voice.tick(0.2), 0.25);
+    }
+
+    #[test]
+    fn test_defaults_without_release_or_activity() {
+        let mut voice =
+            ClosureInstrument::new(0.0_f32, |level, _, v| *level = v, |level, _| *level);
+        assert!(!voice.is_active());
+        voice.trigger_with_velocity(0.0, 2.0);
+        // Velocity arrives clamped; release is a no-op
+        voice.release(0.0);
+        assert_eq!(voice.tick(0.0), 1.0);
+        assert!(voice.is_active());
+    }
+}
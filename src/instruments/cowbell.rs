@@ -0,0 +1,441 @@
+use crate::error::GooeyError;
+use crate::filters::StateVariableFilterTpt;
+use crate::gen::polyblep::polyblep_square;
+use crate::partial_config;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::{
+    tuning_to_multiplier, Blendable, RetriggerFade, SmoothedParam, DEFAULT_SMOOTH_TIME_MS,
+};
+
+/// Normalization ranges for cowbell parameters.
+/// All external-facing parameters use 0.0-1.0 normalized values.
+pub(crate) mod ranges {
+    #[cfg(not(feature = "std"))]
+    use crate::prelude::Float;
+
+    /// Pitch: 0-1 maps exponentially to a 300-1200 Hz lower square
+    pub const PITCH_MIN: f32 = 300.0;
+    pub const PITCH_MAX: f32 = 1200.0;
+
+    /// Tone: 0-1 maps exponentially to a 1-6 kHz bandpass centre
+    pub const TONE_MIN: f32 = 1000.0;
+    pub const TONE_MAX: f32 = 6000.0;
+
+    /// Decay: 0-1 maps exponentially to a 50-2000 ms tail (to -60 dB)
+    pub const DECAY_MIN_MS: f32 = 50.0;
+    pub const DECAY_MAX_MS: f32 = 2000.0;
+
+    #[inline]
+    pub fn exp_denormalize(normalized: f32, min: f32, max: f32) -> f32 {
+        min * (max / min).powf(normalized.clamp(0.0, 1.0))
+    }
+}
+
+/// The 808 cowbell's squares sit at 540 and 800 Hz; the upper one keeps
+/// that ratio to the pitch
+const UPPER_RATIO: f32 = 800.0 / 540.0;
+
+/// Q of the bandpass both squares go through
+const BANDPASS_Q: f32 = 3.0;
+
+/// Decay of the bright strike at the front of the hit (to -60 dB)
+const STRIKE_MS: f32 = 40.0;
+
+/// Share of the envelope in the strike; the rest is the tail
+const STRIKE_LEVEL: f32 = 0.6;
+
+/// Fade-in so the squares don't start on a step
+const ATTACK_MS: f32 = 0.5;
+
+/// Brings the bandpassed squares up to about full scale
+const OUTPUT_GAIN: f32 = 1.0;
+
+/// Level below which a decaying hit is considered finished (-80 dB)
+const SILENCE: f32 = 1e-4;
+
+/// ln(1000): time constants that reach -60 dB after the decay time
+const LN_1000: f32 = 6.907_755;
+
+/// Static configuration for cowbell presets.
+/// All parameters use normalized 0.0-1.0 values.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CowbellConfig {
+    pub pitch: f32,  // Lower square (0-1 -> 300-1200 Hz exp)
+    pub tone: f32,   // Bandpass centre (0-1 -> 1-6 kHz exp)
+    pub decay: f32,  // Tail decay (0-1 -> 50-2000 ms exp)
+    pub volume: f32, // Output volume (0-1)
+}
+
+impl CowbellConfig {
+    pub fn new(pitch: f32, tone: f32, decay: f32, volume: f32) -> Self {
+        Self {
+            pitch: pitch.clamp(0.0, 1.0),
+            tone: tone.clamp(0.0, 1.0),
+            decay: decay.clamp(0.0, 1.0),
+            volume: volume.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Classic preset: the 808 cowbell, 540/800 Hz through a ~2.6 kHz bandpass
+    pub fn classic() -> Self {
+        Self::new(0.42, 0.54, 0.6, 0.8)
+    }
+
+    /// Bright preset: higher and cutting, with a shorter tail
+    pub fn bright() -> Self {
+        Self::new(0.6, 0.8, 0.45, 0.75)
+    }
+
+    /// Dark preset: low and hollow, ringing longer
+    pub fn dark() -> Self {
+        Self::new(0.3, 0.3, 0.7, 0.85)
+    }
+
+    /// Short preset: a choked clank
+    pub fn short() -> Self {
+        Self::new(0.45, 0.6, 0.05, 0.8)
+    }
+
+    #[inline]
+    pub fn pitch_hz(&self) -> f32 {
+        ranges::exp_denormalize(self.pitch, ranges::PITCH_MIN, ranges::PITCH_MAX)
+    }
+
+    #[inline]
+    pub fn tone_hz(&self) -> f32 {
+        ranges::exp_denormalize(self.tone, ranges::TONE_MIN, ranges::TONE_MAX)
+    }
+}
+
+impl Default for CowbellConfig {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+partial_config!(
+    /// [`CowbellConfig`] with every field optional: only the fields set are applied
+    pub struct PartialCowbellConfig for CowbellConfig {
+        pitch: f32,
+        tone: f32,
+        decay: f32,
+        volume: f32,
+    }
+);
+
+impl Blendable for CowbellConfig {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let inv_t = 1.0 - t;
+
+        Self {
+            pitch: self.pitch * inv_t + other.pitch * t,
+            tone: self.tone * inv_t + other.tone * t,
+            decay: self.decay * inv_t + other.decay * t,
+            volume: self.volume * inv_t + other.volume * t,
+        }
+    }
+}
+
+/// Smoothed parameters for real-time control
+pub struct CowbellParams {
+    pub pitch: SmoothedParam,
+    pub tone: SmoothedParam,
+    pub decay: SmoothedParam,
+    pub volume: SmoothedParam,
+    // Per-instrument tuning (0=−12 semitones, 0.5=neutral, 1=+12 semitones)
+    pub tuning: SmoothedParam,
+}
+
+impl CowbellParams {
+    pub fn from_config(config: &CowbellConfig, sample_rate: f32) -> Self {
+        let smoothed =
+            |value: f32| SmoothedParam::new(value, 0.0, 1.0, sample_rate, DEFAULT_SMOOTH_TIME_MS);
+        Self {
+            pitch: smoothed(config.pitch),
+            tone: smoothed(config.tone),
+            decay: smoothed(config.decay),
+            volume: smoothed(config.volume),
+            tuning: smoothed(0.5),
+        }
+    }
+
+    #[inline]
+    pub fn tick(&mut self) {
+        self.pitch.tick();
+        self.tone.tick();
+        self.decay.tick();
+        self.volume.tick();
+        self.tuning.tick();
+    }
+
+    /// Snap all smoothed parameters to their targets instantly.
+    pub fn snap_all(&mut self) {
+        self.pitch.snap();
+        self.tone.snap();
+        self.decay.snap();
+        self.volume.snap();
+        self.tuning.snap();
+    }
+
+    pub fn to_config(&self) -> CowbellConfig {
+        CowbellConfig {
+            pitch: self.pitch.get(),
+            tone: self.tone.get(),
+            decay: self.decay.get(),
+            volume: self.volume.get(),
+        }
+    }
+
+    #[inline]
+    pub fn pitch_hz(&self) -> f32 {
+        ranges::exp_denormalize(self.pitch.get(), ranges::PITCH_MIN, ranges::PITCH_MAX)
+    }
+
+    #[inline]
+    pub fn tone_hz(&self) -> f32 {
+        ranges::exp_denormalize(self.tone.get(), ranges::TONE_MIN, ranges::TONE_MAX)
+    }
+
+    #[inline]
+    pub fn decay_ms(&self) -> f32 {
+        ranges::exp_denormalize(self.decay.get(), ranges::DECAY_MIN_MS, ranges::DECAY_MAX_MS)
+    }
+}
+
+/// Cowbell: two detuned squares a fifth-ish apart through a bandpass, after
+/// the 808. The envelope is a fast strike over a slower tail, which gives the
+/// clank and then the ring; decay sets the tail.
+pub struct Cowbell {
+    pub sample_rate: f32,
+    pub params: CowbellParams,
+
+    lower_phase: f64,
+    upper_phase: f64,
+    bandpass: StateVariableFilterTpt,
+    // Samples since the last trigger (counted, not summed as seconds)
+    elapsed: u64,
+
+    is_active: bool,
+    /// Releases the previous hit when retriggered mid-decay
+    retrigger_fade: RetriggerFade,
+    /// Master tuning multiplier (A4 reference and transpose)
+    pitch_ratio: f32,
+    current_velocity: f32,
+}
+
+impl Cowbell {
+    pub fn new(sample_rate: f32) -> Self {
+        Self::with_config(sample_rate, CowbellConfig::default())
+    }
+
+    pub fn with_config(sample_rate: f32, config: CowbellConfig) -> Self {
+        Self {
+            sample_rate,
+            params: CowbellParams::from_config(&config, sample_rate),
+            lower_phase: 0.0,
+            upper_phase: 0.0,
+            bandpass: StateVariableFilterTpt::new(sample_rate, config.tone_hz(), BANDPASS_Q),
+            elapsed: 0,
+            is_active: false,
+            retrigger_fade: RetriggerFade::new(sample_rate),
+            pitch_ratio: 1.0,
+            current_velocity: 1.0,
+        }
+    }
+
+    /// Change only the fields set in `partial`; the rest keep their current
+    /// values.
+    pub fn apply_partial(&mut self, partial: &PartialCowbellConfig) {
+        self.set_config(self.config().with_partial(partial));
+    }
+
+    /// Get current config snapshot (reads current smoothed values)
+    pub fn config(&self) -> CowbellConfig {
+        self.params.to_config()
+    }
+
+    pub fn set_config(&mut self, config: CowbellConfig) {
+        self.params.pitch.set_target(config.pitch);
+        self.params.tone.set_target(config.tone);
+        self.params.decay.set_target(config.decay);
+        self.params.volume.set_target(config.volume);
+    }
+
+    pub fn snap_params(&mut self) {
+        self.params.snap_all();
+    }
+
+    // Individual parameter setters (normalized 0-1)
+
+    pub fn set_pitch(&mut self, value: f32) {
+        self.params.pitch.set_target(value.clamp(0.0, 1.0));
+    }
+
+    pub fn set_tone(&mut self, value: f32) {
+        self.params.tone.set_target(value.clamp(0.0, 1.0));
+    }
+
+    pub fn set_decay(&mut self, value: f32) {
+        self.params.decay.set_target(value.clamp(0.0, 1.0));
+    }
+
+    pub fn set_volume(&mut self, value: f32) {
+        self.params.volume.set_target(value.clamp(0.0, 1.0));
+    }
+
+    /// Set tuning offset (smoothed, 0-1: 0=−12 semitones, 0.5=neutral, 1=+12 semitones)
+    pub fn set_tuning(&mut self, value: f32) {
+        self.params.tuning.set_target(value.clamp(0.0, 1.0));
+    }
+
+    pub fn trigger(&mut self, time: f64) {
+        self.trigger_with_velocity(time, 1.0);
+    }
+
+    pub fn trigger_with_velocity(&mut self, _time: f64, velocity: f32) {
+        self.retrigger_fade.retrigger();
+        self.is_active = true;
+        self.current_velocity = velocity.clamp(0.0, 1.0);
+        self.lower_phase = 0.0;
+        self.upper_phase = 0.0;
+        self.bandpass.reset();
+        self.elapsed = 0;
+    }
+
+    pub fn tick(&mut self, current_time: f64) -> f32 {
+        let output = self.render(current_time);
+        self.retrigger_fade.process(output)
+    }
+
+    fn render(&mut self, _current_time: f64) -> f32 {
+        self.params.tick();
+
+        if !self.is_active {
+            return 0.0;
+        }
+
+        let t = self.elapsed as f32 / self.sample_rate;
+        self.elapsed += 1;
+
+        let strike = (-t * 1000.0 * LN_1000 / STRIKE_MS).exp();
+        let tail = (-t * 1000.0 * LN_1000 / self.params.decay_ms()).exp();
+        let envelope = STRIKE_LEVEL * strike + (1.0 - STRIKE_LEVEL) * tail;
+        if envelope < SILENCE {
+            self.is_active = false;
+            return 0.0;
+        }
+        let attack = (t * 1000.0 / ATTACK_MS).min(1.0);
+
+        let lower_hz = self.params.pitch_hz()
+            * tuning_to_multiplier(self.params.tuning.get())
+            * self.pitch_ratio;
+        let lower_inc = lower_hz as f64 / self.sample_rate as f64;
+        let upper_inc = lower_inc * UPPER_RATIO as f64;
+        let squares = 0.5
+            * (polyblep_square(self.lower_phase, lower_inc)
+                + polyblep_square(self.upper_phase, upper_inc));
+        self.lower_phase = (self.lower_phase + lower_inc) % 1.0;
+        self.upper_phase = (self.upper_phase + upper_inc) % 1.0;
+
+        self.bandpass.set_params(self.params.tone_hz(), BANDPASS_Q);
+        let (_, band, _) = self.bandpass.process_all(squares);
+
+        band * envelope * attack * OUTPUT_GAIN * self.current_velocity * self.params.volume.get()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.is_active
+    }
+}
+
+impl crate::engine::Instrument for Cowbell {
+    fn trigger_with_velocity(&mut self, time: f64, velocity: f32) {
+        Cowbell::trigger_with_velocity(self, time, velocity);
+    }
+
+    fn tick(&mut self, current_time: f64) -> f32 {
+        self.tick(current_time)
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_active()
+    }
+
+    fn as_modulatable(&mut self) -> Option<&mut dyn crate::engine::Modulatable> {
+        Some(self)
+    }
+
+    fn set_pitch_ratio(&mut self, ratio: f32) {
+        self.pitch_ratio = ratio;
+    }
+}
+
+impl crate::engine::Modulatable for Cowbell {
+    fn modulatable_parameters(&self) -> Vec<&'static str> {
+        vec!["decay", "pitch", "tone", "tuning", "volume"]
+    }
+
+    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), GooeyError> {
+        let param = match parameter {
+            "decay" => &mut self.params.decay,
+            "pitch" => &mut self.params.pitch,
+            "tone" => &mut self.params.tone,
+            "tuning" => &mut self.params.tuning,
+            "volume" => &mut self.params.volume,
+            _ => return Err(GooeyError::UnknownParameter(parameter.to_string())),
+        };
+        param.set_bipolar(value);
+        Ok(())
+    }
+
+    fn parameter_range(&self, parameter: &str) -> Option<(f32, f32)> {
+        match parameter {
+            "decay" => Some(self.params.decay.range()),
+            "pitch" => Some(self.params.pitch.range()),
+            "tone" => Some(self.params.tone.range()),
+            "tuning" => Some(self.params.tuning.range()),
+            "volume" => Some(self.params.volume.range()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 44_100.0;
+
+    fn render_hit(bell: &mut Cowbell, frames: usize) -> Vec<f32> {
+        bell.trigger_with_velocity(0.0, 1.0);
+        (0..frames)
+            .map(|i| bell.tick(i as f64 / SR as f64))
+            .collect()
+    }
+
+    fn peak(samples: &[f32]) -> f32 {
+        samples.iter().fold(0.0, |m, s| m.max(s.abs()))
+    }
+
+    #[test]
+    fn hit_rings_then_goes_inactive() {
+        let mut bell = Cowbell::new(SR);
+        let out = render_hit(&mut bell, 2 * SR as usize);
+        assert!(out.iter().all(|s| s.is_finite() && s.abs() <= 1.0));
+        assert!(peak(&out[..2048]) > 0.2);
+        assert!(!bell.is_active());
+    }
+
+    #[test]
+    fn decay_sets_the_tail() {
+        let tail_peak = |decay| {
+            let mut bell = Cowbell::with_config(SR, CowbellConfig::new(0.42, 0.54, decay, 0.8));
+            let out = render_hit(&mut bell, SR as usize / 2);
+            peak(&out[SR as usize / 5..])
+        };
+        // The strike is gone by 200 ms; only the tail is left
+        assert!(tail_peak(0.9) > tail_peak(0.3) * 10.0);
+    }
+}
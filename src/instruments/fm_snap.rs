@@ -1,95 +1,485 @@
-use std::f32::consts::PI;
+use crate::error::GooeyError;
+use crate::partial_config;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::{
+    tuning_to_multiplier, Blendable, RetriggerFade, SmoothedParam, DEFAULT_SMOOTH_TIME_MS,
+};
+use core::f32::consts::TAU;
 
-pub struct FMSnapSynthesizer {
+/// Normalization ranges for FM snap parameters.
+/// All external-facing parameters use 0.0-1.0 normalized values.
+pub(crate) mod ranges {
+    #[cfg(not(feature = "std"))]
+    use crate::prelude::Float;
+
+    /// Frequency: 0-1 maps exponentially to 80-1600 Hz (carrier)
+    pub const FREQ_MIN: f32 = 80.0;
+    pub const FREQ_MAX: f32 = 1600.0;
+
+    /// Ratio: 0-1 maps to a 0.5-6.0 modulator:carrier frequency ratio
+    pub const RATIO_MIN: f32 = 0.5;
+    pub const RATIO_MAX: f32 = 6.0;
+
+    /// Index: 0-1 maps to a 0-10 peak modulation index (radians)
+    pub const INDEX_MIN: f32 = 0.0;
+    pub const INDEX_MAX: f32 = 10.0;
+
+    /// Snap: 0-1 maps exponentially to a 0.5-50 ms modulation envelope decay
+    pub const SNAP_MIN_MS: f32 = 0.5;
+    pub const SNAP_MAX_MS: f32 = 50.0;
+
+    /// Decay: 0-1 maps exponentially to a 5-1000 ms amplitude decay (to -60 dB)
+    pub const DECAY_MIN_MS: f32 = 5.0;
+    pub const DECAY_MAX_MS: f32 = 1000.0;
+
+    /// Pitch drop: 0-1 maps to a 0-24 semitone downward sweep onto the carrier
+    pub const PITCH_DROP_MAX_SEMITONES: f32 = 24.0;
+
+    #[inline]
+    pub fn denormalize(normalized: f32, min: f32, max: f32) -> f32 {
+        min + normalized.clamp(0.0, 1.0) * (max - min)
+    }
+
+    #[inline]
+    pub fn exp_denormalize(normalized: f32, min: f32, max: f32) -> f32 {
+        min * (max / min).powf(normalized.clamp(0.0, 1.0))
+    }
+}
+
+/// Amplitude envelope attack, long enough to avoid a click at phase zero
+const ATTACK_MS: f32 = 0.5;
+
+/// Level below which a decaying hit is considered finished (-80 dB)
+const SILENCE: f32 = 1e-4;
+
+/// ln(1000): time constants that reach -60 dB after the decay time
+const LN_1000: f32 = 6.907_755;
+
+/// Static configuration for FM snap presets.
+/// All parameters use normalized 0.0-1.0 values.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FmSnapConfig {
+    pub frequency: f32,  // Carrier frequency (0-1 -> 80-1600 Hz exp)
+    pub ratio: f32,      // Modulator:carrier ratio (0-1 -> 0.5-6.0)
+    pub index: f32,      // Peak modulation index (0-1 -> 0-10)
+    pub snap: f32,       // Modulation envelope decay (0-1 -> 0.5-50 ms exp)
+    pub decay: f32,      // Amplitude decay (0-1 -> 5-1000 ms exp)
+    pub pitch_drop: f32, // Carrier pitch sweep depth (0-1 -> 0-24 semitones)
+    pub volume: f32,     // Output volume (0-1)
+}
+
+impl FmSnapConfig {
+    pub fn new(
+        frequency: f32,
+        ratio: f32,
+        index: f32,
+        snap: f32,
+        decay: f32,
+        pitch_drop: f32,
+        volume: f32,
+    ) -> Self {
+        Self {
+            frequency: frequency.clamp(0.0, 1.0),
+            ratio: ratio.clamp(0.0, 1.0),
+            index: index.clamp(0.0, 1.0),
+            snap: snap.clamp(0.0, 1.0),
+            decay: decay.clamp(0.0, 1.0),
+            pitch_drop: pitch_drop.clamp(0.0, 1.0),
+            volume: volume.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Snap preset: bright, metallic and very short
+    pub fn snap() -> Self {
+        Self::new(0.55, 0.62, 0.7, 0.35, 0.2, 0.15, 0.8)
+    }
+
+    /// Rim preset: high, woody-metallic rimshot click
+    pub fn rim() -> Self {
+        Self::new(0.78, 0.45, 0.45, 0.25, 0.12, 0.05, 0.8)
+    }
+
+    /// Wood preset: nearly pure, clave-like knock
+    pub fn wood() -> Self {
+        Self::new(0.62, 0.1, 0.15, 0.5, 0.18, 0.1, 0.85)
+    }
+
+    /// Zap preset: deep pitch sweep with a long modulation tail
+    pub fn zap() -> Self {
+        Self::new(0.3, 0.25, 0.9, 0.75, 0.4, 0.8, 0.75)
+    }
+
+    #[inline]
+    pub fn frequency_hz(&self) -> f32 {
+        ranges::exp_denormalize(self.frequency, ranges::FREQ_MIN, ranges::FREQ_MAX)
+    }
+
+    #[inline]
+    pub fn decay_ms(&self) -> f32 {
+        ranges::exp_denormalize(self.decay, ranges::DECAY_MIN_MS, ranges::DECAY_MAX_MS)
+    }
+}
+
+impl Default for FmSnapConfig {
+    fn default() -> Self {
+        Self::snap()
+    }
+}
+
+partial_config!(
+    /// [`FmSnapConfig`] with every field optional: only the fields set are applied
+    pub struct PartialFmSnapConfig for FmSnapConfig {
+        frequency: f32,
+        ratio: f32,
+        index: f32,
+        snap: f32,
+        decay: f32,
+        pitch_drop: f32,
+        volume: f32,
+    }
+);
+
+impl Blendable for FmSnapConfig {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let inv_t = 1.0 - t;
+
+        Self {
+            frequency: self.frequency * inv_t + other.frequency * t,
+            ratio: self.ratio * inv_t + other.ratio * t,
+            index: self.index * inv_t + other.index * t,
+            snap: self.snap * inv_t + other.snap * t,
+            decay: self.decay * inv_t + other.decay * t,
+            pitch_drop: self.pitch_drop * inv_t + other.pitch_drop * t,
+            volume: self.volume * inv_t + other.volume * t,
+        }
+    }
+}
+
+/// Smoothed parameters for real-time control
+pub struct FmSnapParams {
+    pub frequency: SmoothedParam,
+    pub ratio: SmoothedParam,
+    pub index: SmoothedParam,
+    pub snap: SmoothedParam,
+    pub decay: SmoothedParam,
+    pub pitch_drop: SmoothedParam,
+    pub volume: SmoothedParam,
+    // Per-instrument tuning (0=−12 semitones, 0.5=neutral, 1=+12 semitones)
+    pub tuning: SmoothedParam,
+}
+
+impl FmSnapParams {
+    pub fn from_config(config: &FmSnapConfig, sample_rate: f32) -> Self {
+        let smoothed =
+            |value: f32| SmoothedParam::new(value, 0.0, 1.0, sample_rate, DEFAULT_SMOOTH_TIME_MS);
+        Self {
+            frequency: smoothed(config.frequency),
+            ratio: smoothed(config.ratio),
+            index: smoothed(config.index),
+            snap: smoothed(config.snap),
+            decay: smoothed(config.decay),
+            pitch_drop: smoothed(config.pitch_drop),
+            volume: smoothed(config.volume),
+            tuning: smoothed(0.5),
+        }
+    }
+
+    #[inline]
+    pub fn tick(&mut self) {
+        self.frequency.tick();
+        self.ratio.tick();
+        self.index.tick();
+        self.snap.tick();
+        self.decay.tick();
+        self.pitch_drop.tick();
+        self.volume.tick();
+        self.tuning.tick();
+    }
+
+    /// Snap all smoothed parameters to their targets instantly.
+    pub fn snap_all(&mut self) {
+        self.frequency.snap();
+        self.ratio.snap();
+        self.index.snap();
+        self.snap.snap();
+        self.decay.snap();
+        self.pitch_drop.snap();
+        self.volume.snap();
+        self.tuning.snap();
+    }
+
+    pub fn to_config(&self) -> FmSnapConfig {
+        FmSnapConfig {
+            frequency: self.frequency.get(),
+            ratio: self.ratio.get(),
+            index: self.index.get(),
+            snap: self.snap.get(),
+            decay: self.decay.get(),
+            pitch_drop: self.pitch_drop.get(),
+            volume: self.volume.get(),
+        }
+    }
+
+    #[inline]
+    pub fn frequency_hz(&self) -> f32 {
+        ranges::exp_denormalize(self.frequency.get(), ranges::FREQ_MIN, ranges::FREQ_MAX)
+    }
+
+    #[inline]
+    pub fn ratio_value(&self) -> f32 {
+        ranges::denormalize(self.ratio.get(), ranges::RATIO_MIN, ranges::RATIO_MAX)
+    }
+
+    #[inline]
+    pub fn index_value(&self) -> f32 {
+        ranges::denormalize(self.index.get(), ranges::INDEX_MIN, ranges::INDEX_MAX)
+    }
+
+    #[inline]
+    pub fn snap_ms(&self) -> f32 {
+        ranges::exp_denormalize(self.snap.get(), ranges::SNAP_MIN_MS, ranges::SNAP_MAX_MS)
+    }
+
+    #[inline]
+    pub fn decay_ms(&self) -> f32 {
+        ranges::exp_denormalize(self.decay.get(), ranges::DECAY_MIN_MS, ranges::DECAY_MAX_MS)
+    }
+}
+
+/// Two-operator FM percussion: a sine carrier phase-modulated by a sine at
+/// `ratio` times its frequency. The modulation index decays on its own fast
+/// "snap" envelope, so each hit starts bright and inharmonic and settles to
+/// a purer tone, and the carrier can sweep down onto its pitch. Covers snaps,
+/// rims, claves and zaps.
+pub struct FmSnap {
     pub sample_rate: f32,
-    pub attack_time: f32,
-    pub decay_time: f32,
-    pub carrier_freq: f32,
-    pub modulator_freq: f32,
-    pub modulation_index: f32,
-    pub phase: f32,
-    pub trigger_time: f64,
-    pub is_active: bool,
+    pub params: FmSnapParams,
+
+    carrier_phase: f32,
+    modulator_phase: f32,
+    // Samples since the last trigger (counted, not summed as seconds)
+    elapsed: u64,
+
+    is_active: bool,
+    /// Releases the previous hit when retriggered mid-decay
+    retrigger_fade: RetriggerFade,
+    /// Master tuning multiplier (A4 reference and transpose)
+    pitch_ratio: f32,
+    current_velocity: f32,
 }
 
-impl FMSnapSynthesizer {
+impl FmSnap {
     pub fn new(sample_rate: f32) -> Self {
+        Self::with_config(sample_rate, FmSnapConfig::default())
+    }
+
+    pub fn with_config(sample_rate: f32, config: FmSnapConfig) -> Self {
         Self {
             sample_rate,
-            attack_time: 0.001, // 1ms attack
-            decay_time: 0.008,  // 8ms decay
-            carrier_freq: 50.0,
-            modulator_freq: 500.0,
-            modulation_index: 2.0,
-            phase: 0.0,
-            trigger_time: 0.0,
+            params: FmSnapParams::from_config(&config, sample_rate),
+            carrier_phase: 0.0,
+            modulator_phase: 0.0,
+            elapsed: 0,
             is_active: false,
+            retrigger_fade: RetriggerFade::new(sample_rate),
+            pitch_ratio: 1.0,
+            current_velocity: 1.0,
         }
     }
 
+    /// Change only the fields set in `partial`; the rest keep their current
+    /// values.
+    pub fn apply_partial(&mut self, partial: &PartialFmSnapConfig) {
+        self.set_config(self.config().with_partial(partial));
+    }
+
+    /// Get current config snapshot (reads current smoothed values)
+    pub fn config(&self) -> FmSnapConfig {
+        self.params.to_config()
+    }
+
+    pub fn set_config(&mut self, config: FmSnapConfig) {
+        self.params.frequency.set_target(config.frequency);
+        self.params.ratio.set_target(config.ratio);
+        self.params.index.set_target(config.index);
+        self.params.snap.set_target(config.snap);
+        self.params.decay.set_target(config.decay);
+        self.params.pitch_drop.set_target(config.pitch_drop);
+        self.params.volume.set_target(config.volume);
+    }
+
+    pub fn snap_params(&mut self) {
+        self.params.snap_all();
+    }
+
+    // Individual parameter setters (normalized 0-1)
+
+    pub fn set_frequency(&mut self, value: f32) {
+        self.params.frequency.set_target(value.clamp(0.0, 1.0));
+    }
+
+    pub fn set_ratio(&mut self, value: f32) {
+        self.params.ratio.set_target(value.clamp(0.0, 1.0));
+    }
+
+    pub fn set_index(&mut self, value: f32) {
+        self.params.index.set_target(value.clamp(0.0, 1.0));
+    }
+
+    pub fn set_snap(&mut self, value: f32) {
+        self.params.snap.set_target(value.clamp(0.0, 1.0));
+    }
+
+    pub fn set_decay(&mut self, value: f32) {
+        self.params.decay.set_target(value.clamp(0.0, 1.0));
+    }
+
+    pub fn set_pitch_drop(&mut self, value: f32) {
+        self.params.pitch_drop.set_target(value.clamp(0.0, 1.0));
+    }
+
+    pub fn set_volume(&mut self, value: f32) {
+        self.params.volume.set_target(value.clamp(0.0, 1.0));
+    }
+
+    /// Set tuning offset (smoothed, 0-1: 0=−12 semitones, 0.5=neutral, 1=+12 semitones)
+    pub fn set_tuning(&mut self, value: f32) {
+        self.params.tuning.set_target(value.clamp(0.0, 1.0));
+    }
+
     pub fn trigger(&mut self, time: f64) {
-        self.trigger_time = time;
-        self.phase = 0.0;
+        self.trigger_with_velocity(time, 1.0);
+    }
+
+    /// Start a hit. Velocity scales both level and modulation depth, so soft
+    /// hits are quieter and less bright.
+    pub fn trigger_with_velocity(&mut self, _time: f64, velocity: f32) {
+        self.retrigger_fade.retrigger();
         self.is_active = true;
+        self.current_velocity = velocity.clamp(0.0, 1.0);
+        self.carrier_phase = 0.0;
+        self.modulator_phase = 0.0;
+        self.elapsed = 0;
     }
 
     pub fn tick(&mut self, current_time: f64) -> f32 {
+        let output = self.render(current_time);
+        self.retrigger_fade.process(output)
+    }
+
+    fn render(&mut self, _current_time: f64) -> f32 {
+        self.params.tick();
+
         if !self.is_active {
             return 0.0;
         }
 
-        let t = (current_time - self.trigger_time) as f32;
+        let t = self.elapsed as f32 / self.sample_rate;
+        self.elapsed += 1;
 
-        // Check if we're past the envelope duration
-        if t > self.attack_time + self.decay_time {
+        let attack = (t * 1000.0 / ATTACK_MS).min(1.0);
+        let amp_env = attack * (-t * 1000.0 * LN_1000 / self.params.decay_ms()).exp();
+        if t * 1000.0 > ATTACK_MS && amp_env < SILENCE {
             self.is_active = false;
             return 0.0;
         }
 
-        // Generate envelope (exponential decay)
-        let env = if t < self.attack_time {
-            t / self.attack_time
-        } else {
-            let decay = (-(t - self.attack_time) / self.decay_time).exp();
-            decay.clamp(0.0, 1.0)
-        };
-
-        // FM synthesis
-        let dt = 1.0 / self.sample_rate;
-        let mod_signal = (2.0 * PI * self.modulator_freq * t).sin();
-        let instantaneous_freq = self.carrier_freq + self.modulation_index * mod_signal * env;
+        // The snap envelope drives the modulation index and, at twice the
+        // time, the pitch sweep.
+        let snap_s = self.params.snap_ms() * 0.001;
+        let mod_env = (-t / snap_s).exp();
+        let drop_semitones = self.params.pitch_drop.get()
+            * ranges::PITCH_DROP_MAX_SEMITONES
+            * (-t / (2.0 * snap_s)).exp();
 
-        // Update phase
-        self.phase += 2.0 * PI * instantaneous_freq * dt;
+        let carrier_hz = self.params.frequency_hz()
+            * tuning_to_multiplier(self.params.tuning.get())
+            * self.pitch_ratio
+            * (drop_semitones / 12.0).exp2();
+        let modulator_hz = carrier_hz * self.params.ratio_value();
+        let velocity = self.current_velocity;
+        let index = self.params.index_value() * mod_env * (0.5 + 0.5 * velocity);
 
-        // Wrap phase to prevent overflow
-        if self.phase > 2.0 * PI {
-            self.phase -= 2.0 * PI;
-        }
+        let modulator = (TAU * self.modulator_phase).sin();
+        let output = (TAU * self.carrier_phase + index * modulator).sin();
 
-        // Generate output
-        let output = self.phase.sin() * env;
+        self.carrier_phase = (self.carrier_phase + carrier_hz / self.sample_rate).fract();
+        self.modulator_phase = (self.modulator_phase + modulator_hz / self.sample_rate).fract();
 
-        output
+        output * amp_env * velocity * self.params.volume.get()
     }
 
     pub fn is_active(&self) -> bool {
         self.is_active
     }
+}
+
+impl crate::engine::Instrument for FmSnap {
+    fn trigger_with_velocity(&mut self, time: f64, velocity: f32) {
+        FmSnap::trigger_with_velocity(self, time, velocity);
+    }
+
+    fn tick(&mut self, current_time: f64) -> f32 {
+        self.tick(current_time)
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_active()
+    }
+
+    fn as_modulatable(&mut self) -> Option<&mut dyn crate::engine::Modulatable> {
+        Some(self)
+    }
+
+    fn set_pitch_ratio(&mut self, ratio: f32) {
+        self.pitch_ratio = ratio;
+    }
+}
+
+impl crate::engine::Modulatable for FmSnap {
+    fn modulatable_parameters(&self) -> Vec<&'static str> {
+        vec![
+            "decay",
+            "frequency",
+            "index",
+            "pitch_drop",
+            "ratio",
+            "snap",
+            "tuning",
+            "volume",
+        ]
+    }
+
+    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), GooeyError> {
+        let param = match parameter {
+            "decay" => &mut self.params.decay,
+            "frequency" => &mut self.params.frequency,
+            "index" => &mut self.params.index,
+            "pitch_drop" => &mut self.params.pitch_drop,
+            "ratio" => &mut self.params.ratio,
+            "snap" => &mut self.params.snap,
+            "tuning" => &mut self.params.tuning,
+            "volume" => &mut self.params.volume,
+            _ => return Err(GooeyError::UnknownParameter(parameter.to_string())),
+        };
+        param.set_bipolar(value);
+        Ok(())
+    }
 
-    pub fn set_params(
-        &mut self,
-        attack_time: f32,
-        decay_time: f32,
-        carrier_freq: f32,
-        modulator_freq: f32,
-        modulation_index: f32,
-    ) {
-        self.attack_time = attack_time;
-        self.decay_time = decay_time;
-        self.carrier_freq = carrier_freq;
-        self.modulator_freq = modulator_freq;
-        self.modulation_index = modulation_index;
+    fn parameter_range(&self, parameter: &str) -> Option<(f32, f32)> {
+        match parameter {
+            "decay" => Some(self.params.decay.range()),
+            "frequency" => Some(self.params.frequency.range()),
+            "index" => Some(self.params.index.range()),
+            "pitch_drop" => Some(self.params.pitch_drop.range()),
+            "ratio" => Some(self.params.ratio.range()),
+            "snap" => Some(self.params.snap.range()),
+            "tuning" => Some(self.params.tuning.range()),
+            "volume" => Some(self.params.volume.range()),
+            _ => None,
+        }
     }
 }
 
@@ -168,3 +558,57 @@ impl PhaseModulator {
         self.decay_curve = decay_curve.clamp(0.1, 10.0);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 44_100.0;
+
+    fn render_hit(snap: &mut FmSnap, velocity: f32, frames: usize) -> Vec<f32> {
+        snap.trigger_with_velocity(0.0, velocity);
+        (0..frames)
+            .map(|i| snap.tick(i as f64 / SR as f64))
+            .collect()
+    }
+
+    fn energy(samples: &[f32]) -> f32 {
+        samples.iter().map(|s| s * s).sum()
+    }
+
+    #[test]
+    fn hit_decays_to_silence_and_goes_inactive() {
+        let mut snap = FmSnap::new(SR);
+        let out = render_hit(&mut snap, 1.0, SR as usize);
+        assert!(out.iter().all(|s| s.is_finite() && s.abs() <= 1.0));
+        assert!(energy(&out[..2048]) > 1.0);
+        assert!(!snap.is_active());
+        assert_eq!(snap.tick(0.0), 0.0);
+    }
+
+    #[test]
+    fn velocity_and_decay_shape_the_hit() {
+        let soft = render_hit(&mut FmSnap::new(SR), 0.3, 4096);
+        let hard = render_hit(&mut FmSnap::new(SR), 1.0, 4096);
+        assert!(energy(&soft) < energy(&hard) * 0.2);
+
+        let mut long = FmSnap::new(SR);
+        long.set_decay(1.0);
+        long.snap_params();
+        let long = render_hit(&mut long, 1.0, 8192);
+        let tail = |s: &[f32]| energy(&s[4096..]) / energy(s);
+        let short = render_hit(&mut FmSnap::new(SR), 1.0, 8192);
+        assert!(tail(&long) > tail(&short) * 10.0);
+    }
+
+    #[test]
+    fn presets_blend_and_round_trip_through_params() {
+        let blended = FmSnapConfig::rim().lerp(&FmSnapConfig::zap(), 0.5);
+        assert!((blended.pitch_drop - 0.425).abs() < 1e-6);
+
+        let mut snap = FmSnap::new(SR);
+        snap.set_config(FmSnapConfig::wood());
+        snap.snap_params();
+        assert_eq!(snap.config(), FmSnapConfig::wood());
+    }
+}
@@ -7,8 +7,11 @@
 
 use crate::effects::Waveshaper;
 use crate::engine::{Instrument, Modulatable};
+use crate::error::GooeyError;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 use crate::utils::{cubic_interpolate, raised_sine_window, SmoothedParam};
-use std::sync::Arc;
+use alloc::sync::Arc;
 
 const MAX_GRAINS: usize = 64;
 // Capacity of the auxiliary "release pool" used to hold stolen victims while
@@ -47,15 +50,21 @@ pub struct SampleBuffer {
 }
 
 impl SampleBuffer {
-    pub fn from_mono(samples: Vec<f32>, sample_rate: f32) -> Result<Self, String> {
+    pub fn from_mono(samples: Vec<f32>, sample_rate: f32) -> Result<Self, GooeyError> {
         if samples.is_empty() {
-            return Err("SampleBuffer requires at least one sample".to_string());
+            return Err(GooeyError::InvalidValue(
+                "SampleBuffer requires at least one sample".to_string(),
+            ));
         }
         if !sample_rate.is_finite() || sample_rate <= 0.0 {
-            return Err(format!("Invalid sample rate: {sample_rate}"));
+            return Err(GooeyError::InvalidValue(format!(
+                "Invalid sample rate: {sample_rate}"
+            )));
         }
         if samples.iter().any(|sample| !sample.is_finite()) {
-            return Err("SampleBuffer samples must be finite".to_string());
+            return Err(GooeyError::InvalidValue(
+                "SampleBuffer samples must be finite".to_string(),
+            ));
         }
 
         Ok(Self {
@@ -65,32 +74,41 @@ impl SampleBuffer {
     }
 
     #[cfg(feature = "bounce")]
-    pub fn from_wav_mono(path: impl AsRef<std::path::Path>) -> Result<Self, String> {
+    pub fn from_wav_mono(path: impl AsRef<std::path::Path>) -> Result<Self, GooeyError> {
         let mut reader = hound::WavReader::open(path.as_ref())
-            .map_err(|e| format!("Failed to open WAV: {e}"))?;
+            .map_err(|e| GooeyError::Io(format!("Failed to open WAV: {e}")))?;
         let spec = reader.spec();
         if spec.channels == 0 {
-            return Err("WAV must have at least one channel".to_string());
+            return Err(GooeyError::Io(
+                "WAV must have at least one channel".to_string(),
+            ));
         }
         if spec.sample_rate == 0 {
-            return Err("WAV sample rate must be greater than zero".to_string());
+            return Err(GooeyError::Io(
+                "WAV sample rate must be greater than zero".to_string(),
+            ));
         }
 
         let channels = spec.channels as usize;
         let interleaved = match spec.sample_format {
             hound::SampleFormat::Float => reader
                 .samples::<f32>()
-                .map(|s| s.map_err(|e| format!("Failed to read WAV sample: {e}")))
+                .map(|s| s.map_err(|e| GooeyError::Io(format!("Failed to read WAV sample: {e}"))))
                 .collect::<Result<Vec<_>, _>>()?,
             hound::SampleFormat::Int => match spec.bits_per_sample {
-                0 => return Err("WAV bit depth must be greater than zero".to_string()),
+                0 => {
+                    return Err(GooeyError::Io(
+                        "WAV bit depth must be greater than zero".to_string(),
+                    ))
+                }
                 1..=8 => {
                     let scale = ((1_i32 << (spec.bits_per_sample - 1)) - 1) as f32;
                     reader
                         .samples::<i8>()
                         .map(|s| {
-                            s.map(|v| v as f32 / scale)
-                                .map_err(|e| format!("Failed to read WAV sample: {e}"))
+                            s.map(|v| v as f32 / scale).map_err(|e| {
+                                GooeyError::Io(format!("Failed to read WAV sample: {e}"))
+                            })
                         })
                         .collect::<Result<Vec<_>, _>>()?
                 }
@@ -99,8 +117,9 @@ impl SampleBuffer {
                     reader
                         .samples::<i16>()
                         .map(|s| {
-                            s.map(|v| v as f32 / scale)
-                                .map_err(|e| format!("Failed to read WAV sample: {e}"))
+                            s.map(|v| v as f32 / scale).map_err(|e| {
+                                GooeyError::Io(format!("Failed to read WAV sample: {e}"))
+                            })
                         })
                         .collect::<Result<Vec<_>, _>>()?
                 }
@@ -109,17 +128,18 @@ impl SampleBuffer {
                     reader
                         .samples::<i32>()
                         .map(|s| {
-                            s.map(|v| v as f32 / scale)
-                                .map_err(|e| format!("Failed to read WAV sample: {e}"))
+                            s.map(|v| v as f32 / scale).map_err(|e| {
+                                GooeyError::Io(format!("Failed to read WAV sample: {e}"))
+                            })
                         })
                         .collect::<Result<Vec<_>, _>>()?
                 }
-                bits => return Err(format!("Unsupported WAV bit depth: {bits}")),
+                bits => return Err(GooeyError::Io(format!("Unsupported WAV bit depth: {bits}"))),
             },
         };
 
         if interleaved.is_empty() {
-            return Err("WAV contains no samples".to_string());
+            return Err(GooeyError::Io("WAV contains no samples".to_string()));
         }
 
         let mut mono = Vec::with_capacity(interleaved.len() / channels);
@@ -351,9 +371,12 @@ impl Granulator {
         }
     }
 
-    pub fn set_buffer(&mut self, buffer: SampleBuffer) {
-        self.buffer = buffer;
+    /// Play `buffer` from now on, killing all grains. Returns the buffer it
+    /// replaces, so the caller can free it off the audio thread.
+    pub fn set_buffer(&mut self, buffer: SampleBuffer) -> SampleBuffer {
+        let old = core::mem::replace(&mut self.buffer, buffer);
         self.kill_all_grains();
+        old
     }
 
     pub fn set_seed(&mut self, seed: u32) {
@@ -498,7 +521,8 @@ impl Granulator {
                 .count()
     }
 
-    fn kill_all_grains(&mut self) {
+    /// Stop the cloud and silence every grain at once
+    pub fn kill_all_grains(&mut self) {
         for grain in &mut self.grains {
             grain.active = false;
         }
@@ -745,6 +769,10 @@ impl Instrument for Granulator {
         self.cloud_active || self.grains.iter().any(|grain| grain.active)
     }
 
+    fn reseed(&mut self, seed: u64) {
+        self.set_seed((seed ^ (seed >> 32)) as u32);
+    }
+
     fn as_modulatable(&mut self) -> Option<&mut dyn Modulatable> {
         Some(self)
     }
@@ -767,7 +795,7 @@ impl Modulatable for Granulator {
         ]
     }
 
-    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), String> {
+    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), GooeyError> {
         match parameter {
             "scan_position" => self.params.scan_position.set_bipolar(value),
             "grain_length" => self.params.grain_length.set_bipolar(value),
@@ -780,7 +808,7 @@ impl Modulatable for Granulator {
             "random_amp" => self.params.random_amp.set_bipolar(value),
             "drive" => self.params.drive.set_bipolar(value),
             "volume" => self.params.volume.set_bipolar(value),
-            _ => return Err(format!("Unknown granulator parameter: {parameter}")),
+            _ => return Err(GooeyError::UnknownParameter(parameter.to_string())),
         }
         Ok(())
     }
@@ -868,7 +896,7 @@ mod tests {
 
     fn test_buffer() -> SampleBuffer {
         let samples = (0..4410)
-            .map(|i| ((i as f32 / 44100.0) * 440.0 * std::f32::consts::TAU).sin() * 0.5)
+            .map(|i| ((i as f32 / 44100.0) * 440.0 * core::f32::consts::TAU).sin() * 0.5)
             .collect();
         SampleBuffer::from_mono(samples, 44100.0).unwrap()
     }
@@ -1031,7 +1059,7 @@ mod tests {
         // their slots naturally and the pool never saturates.
         let sample_rate = 44100.0;
         let long_buffer_samples: Vec<f32> = (0..(sample_rate as usize * 3))
-            .map(|i| ((i as f32 / sample_rate) * 440.0 * std::f32::consts::TAU).sin() * 0.5)
+            .map(|i| ((i as f32 / sample_rate) * 440.0 * core::f32::consts::TAU).sin() * 0.5)
             .collect();
         let buffer = SampleBuffer::from_mono(long_buffer_samples, sample_rate).unwrap();
         let mut granulator = Granulator::new(sample_rate, buffer);
@@ -1,4 +1,5 @@
 use crate::envelope::{ADSRConfig, Envelope, EnvelopeCurve};
+use crate::error::GooeyError;
 use crate::gen::oscillator::Oscillator;
 use crate::gen::waveform::Waveform;
 use crate::utils::{SmoothedParam, DEFAULT_SMOOTH_TIME_MS};
@@ -655,7 +656,7 @@ impl HiHat {
             .min(self.sample_rate * 0.45);
         let normalized_freq = cutoff / self.sample_rate;
         // One-pole coefficient: g = 1 - e^(-2*pi*fc/fs)
-        let g = 1.0 - (-2.0 * std::f32::consts::PI * normalized_freq).exp();
+        let g = 1.0 - (-2.0 * core::f32::consts::PI * normalized_freq).exp();
         let g = g.clamp(0.0, 1.0);
 
         // Apply filter: y[n] = y[n-1] + g * (x[n] - y[n-1])
@@ -737,6 +738,10 @@ impl crate::engine::Instrument for HiHat {
         self.tick(current_time)
     }
 
+    fn release(&mut self, time: f64) {
+        self.release(time)
+    }
+
     fn is_active(&self) -> bool {
         self.is_active()
     }
@@ -759,7 +764,7 @@ impl crate::engine::Modulatable for HiHat {
         ]
     }
 
-    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), String> {
+    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), GooeyError> {
         // value is -1.0 to 1.0 (bipolar), set_bipolar maps this to the param range
         match parameter {
             "amp_decay" => {
@@ -786,7 +791,7 @@ impl crate::engine::Modulatable for HiHat {
                 self.params.volume.set_bipolar(value);
                 Ok(())
             }
-            _ => Err(format!("Unknown parameter: {}", parameter)),
+            _ => Err(GooeyError::UnknownParameter(parameter.to_string())),
         }
     }
 
@@ -1,10 +1,17 @@
-use std::f32::consts::PI;
+use crate::error::GooeyError;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use core::f32::consts::PI;
 
 use crate::filters::{BiquadHighpass, StateVariableFilterTpt};
 use crate::gen::pink_noise::PinkNoise;
+use crate::gen::polyblep::polyblep_square;
 use crate::max_curve::MaxCurveEnvelope;
+use crate::partial_config;
 use crate::utils::Blendable;
-use crate::utils::{tuning_to_multiplier, SmoothedParam, DEFAULT_SMOOTH_TIME_MS};
+use crate::utils::{
+    tuning_to_multiplier, ControlRate, RetriggerFade, SmoothedParam, DEFAULT_SMOOTH_TIME_MS,
+};
 
 /// Normalization ranges for HiHat2 parameters
 /// All external-facing parameters use 0.0-1.0 normalized values
@@ -25,6 +32,35 @@ pub(crate) mod ranges {
     pub const TONE_MIN: f32 = 500.0;
     pub const TONE_MAX: f32 = 10000.0;
 
+    /// Velocity-to-decay at full depth: a zero-velocity hit keeps 25% of the decay
+    pub const VELOCITY_DECAY_RANGE: f32 = 0.75;
+
+    /// Velocity-to-tone at full depth: octaves the highpass filters drop for
+    /// a zero-velocity hit
+    pub const VELOCITY_TONE_OCTAVES: f32 = 1.5;
+
+    /// Open decay used by the presets (0-1 normalized, ~1.2 s)
+    pub const DEFAULT_OPEN_DECAY: f32 = 0.3;
+
+    /// Pedal hits: fraction of the closed decay, level, and octaves the
+    /// highpass filters drop for the softer "chick"
+    pub const PEDAL_DECAY_SCALE: f32 = 0.5;
+    pub const PEDAL_GAIN: f32 = 0.6;
+    pub const PEDAL_TONE_OCTAVES: f32 = 0.75;
+
+    /// The TR-808's six metal oscillators in Hz. Their inharmonic ratios give
+    /// the hat its clang; the pitch control transposes all six together.
+    pub const METAL_808_HZ: [f32; 6] = [205.3, 304.4, 369.6, 522.7, 540.0, 800.0];
+
+    /// Pitch (Hz) at which the 808 bank plays at its original frequencies
+    pub const METAL_808_REFERENCE_HZ: f32 = 7000.0;
+
+    /// Q of the 808 bandpass, centred on the pitch
+    pub const METAL_808_BANDPASS_Q: f32 = 1.5;
+
+    /// Brings the 808 engine to about the phase-mod engine's level
+    pub const METAL_808_GAIN: f32 = 10.0;
+
     /// Map normalized 0-1 value to actual range
     #[inline]
     pub fn denormalize(normalized: f32, min: f32, max: f32) -> f32 {
@@ -44,15 +80,82 @@ pub enum FilterSlope {
     Db24,
 }
 
+/// Which synthesis engine makes the raw metal before the shared filters and
+/// envelope.
+///
+/// - `PhaseMod`: two sine oscillators phase-modulated by noise
+/// - `Classic808`: six detuned square waves (the TR-808 bank) through a
+///   bandpass. Noise color doesn't apply; the bank has no noise.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HiHatMode {
+    #[default]
+    PhaseMod,
+    Classic808,
+}
+
+impl HiHatMode {
+    /// Mode from its index (0 = phase mod, 1 = 808)
+    pub fn from_index(index: u8) -> Option<Self> {
+        match index {
+            0 => Some(Self::PhaseMod),
+            1 => Some(Self::Classic808),
+            _ => None,
+        }
+    }
+
+    pub fn index(self) -> u8 {
+        match self {
+            Self::PhaseMod => 0,
+            Self::Classic808 => 1,
+        }
+    }
+}
+
+/// How a hit is played. All three share the hat's timbre; they differ in
+/// envelope and level.
+///
+/// - `Closed`: the configured decay
+/// - `Pedal`: foot-closed "chick", shorter, softer and darker than closed
+/// - `Open`: the open decay; a following closed or pedal hit chokes it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HiHatArticulation {
+    #[default]
+    Closed,
+    Pedal,
+    Open,
+}
+
+impl HiHatArticulation {
+    /// Articulation from its index (0 = closed, 1 = pedal, 2 = open)
+    pub fn from_index(index: u8) -> Option<Self> {
+        match index {
+            0 => Some(Self::Closed),
+            1 => Some(Self::Pedal),
+            2 => Some(Self::Open),
+            _ => None,
+        }
+    }
+
+    pub fn index(self) -> u8 {
+        match self {
+            Self::Closed => 0,
+            Self::Pedal => 1,
+            Self::Open => 2,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct HiHat2Config {
-    pub pitch: f32,  // 0-1 normalized (pow2 curve -> 3500-10000 Hz)
-    pub decay: f32,  // 0-1 normalized (0.5-4000 ms)
-    pub attack: f32, // 0-1 normalized (0.5-200 ms)
+    pub pitch: f32,      // 0-1 normalized (pow2 curve -> 3500-10000 Hz)
+    pub decay: f32,      // 0-1 normalized (0.5-4000 ms)
+    pub open_decay: f32, // 0-1 normalized (0.5-4000 ms), for open hits
+    pub attack: f32,     // 0-1 normalized (0.5-200 ms)
     pub noise_color: NoiseColor,
     pub filter_slope: FilterSlope,
     pub tone: f32,   // 0-1 normalized (500-10000 Hz)
     pub volume: f32, // 0-1 overall volume
+    pub mode: HiHatMode,
 }
 
 impl HiHat2Config {
@@ -67,11 +170,13 @@ impl HiHat2Config {
         Self {
             pitch: pitch.clamp(0.0, 1.0),
             decay: decay.clamp(0.0, 1.0),
+            open_decay: ranges::DEFAULT_OPEN_DECAY,
             attack: attack.clamp(0.0, 1.0),
             noise_color,
             filter_slope,
             tone: tone.clamp(0.0, 1.0),
             volume: 1.0,
+            mode: HiHatMode::PhaseMod,
         }
     }
 
@@ -95,6 +200,22 @@ impl HiHat2Config {
         Self::new(0.41, 0.05, 0.15, NoiseColor::White, FilterSlope::Db24, 0.60)
     }
 
+    /// Classic preset: `short` played on the 808 bank, for A/B against it
+    pub fn classic() -> Self {
+        Self {
+            mode: HiHatMode::Classic808,
+            ..Self::short()
+        }
+    }
+
+    /// Classic loose preset: `loose` played on the 808 bank
+    pub fn classic_loose() -> Self {
+        Self {
+            mode: HiHatMode::Classic808,
+            ..Self::loose()
+        }
+    }
+
     #[inline]
     pub fn pitch_hz(&self) -> f32 {
         let curved = self.pitch * self.pitch;
@@ -111,6 +232,11 @@ impl HiHat2Config {
         ranges::denormalize(self.decay, ranges::DECAY_MIN_MS, ranges::DECAY_MAX_MS)
     }
 
+    #[inline]
+    pub fn open_decay_ms(&self) -> f32 {
+        ranges::denormalize(self.open_decay, ranges::DECAY_MIN_MS, ranges::DECAY_MAX_MS)
+    }
+
     #[inline]
     pub fn tone_hz(&self) -> f32 {
         ranges::denormalize(self.tone, ranges::TONE_MIN, ranges::TONE_MAX)
@@ -123,6 +249,21 @@ impl Default for HiHat2Config {
     }
 }
 
+partial_config!(
+    /// [`HiHat2Config`] with every field optional: only the fields set are applied
+    pub struct PartialHiHat2Config for HiHat2Config {
+        pitch: f32,
+        decay: f32,
+        open_decay: f32,
+        attack: f32,
+        noise_color: NoiseColor,
+        filter_slope: FilterSlope,
+        tone: f32,
+        volume: f32,
+        mode: HiHatMode,
+    }
+);
+
 impl Blendable for HiHat2Config {
     fn lerp(&self, other: &Self, t: f32) -> Self {
         let t = t.clamp(0.0, 1.0);
@@ -131,6 +272,7 @@ impl Blendable for HiHat2Config {
         Self {
             pitch: self.pitch * inv_t + other.pitch * t,
             decay: self.decay * inv_t + other.decay * t,
+            open_decay: self.open_decay * inv_t + other.open_decay * t,
             attack: self.attack * inv_t + other.attack * t,
             noise_color: if t < 0.5 {
                 self.noise_color
@@ -144,6 +286,7 @@ impl Blendable for HiHat2Config {
             },
             tone: self.tone * inv_t + other.tone * t,
             volume: self.volume * inv_t + other.volume * t,
+            mode: if t < 0.5 { self.mode } else { other.mode },
         }
     }
 }
@@ -152,6 +295,7 @@ impl Blendable for HiHat2Config {
 pub struct HiHat2Params {
     pub pitch: SmoothedParam,
     pub decay: SmoothedParam,
+    pub open_decay: SmoothedParam,
     pub attack: SmoothedParam,
     pub tone: SmoothedParam,
     pub volume: SmoothedParam,
@@ -164,6 +308,13 @@ impl HiHat2Params {
         Self {
             pitch: SmoothedParam::new(config.pitch, 0.0, 1.0, sample_rate, DEFAULT_SMOOTH_TIME_MS),
             decay: SmoothedParam::new(config.decay, 0.0, 1.0, sample_rate, DEFAULT_SMOOTH_TIME_MS),
+            open_decay: SmoothedParam::new(
+                config.open_decay,
+                0.0,
+                1.0,
+                sample_rate,
+                DEFAULT_SMOOTH_TIME_MS,
+            ),
             attack: SmoothedParam::new(
                 config.attack,
                 0.0,
@@ -187,6 +338,7 @@ impl HiHat2Params {
     pub fn tick(&mut self) -> bool {
         self.pitch.tick();
         self.decay.tick();
+        self.open_decay.tick();
         self.attack.tick();
         self.tone.tick();
         self.volume.tick();
@@ -198,6 +350,7 @@ impl HiHat2Params {
     pub fn is_settled(&self) -> bool {
         self.pitch.is_settled()
             && self.decay.is_settled()
+            && self.open_decay.is_settled()
             && self.attack.is_settled()
             && self.tone.is_settled()
             && self.volume.is_settled()
@@ -224,6 +377,15 @@ impl HiHat2Params {
         ranges::denormalize(self.decay.get(), ranges::DECAY_MIN_MS, ranges::DECAY_MAX_MS)
     }
 
+    #[inline]
+    pub fn open_decay_ms(&self) -> f32 {
+        ranges::denormalize(
+            self.open_decay.get(),
+            ranges::DECAY_MIN_MS,
+            ranges::DECAY_MAX_MS,
+        )
+    }
+
     #[inline]
     pub fn tone_hz(&self) -> f32 {
         ranges::denormalize(self.tone.get(), ranges::TONE_MIN, ranges::TONE_MAX)
@@ -233,21 +395,29 @@ impl HiHat2Params {
     pub fn snap_all(&mut self) {
         self.pitch.snap();
         self.decay.snap();
+        self.open_decay.snap();
         self.attack.snap();
         self.tone.snap();
         self.volume.snap();
         self.tuning.snap();
     }
 
-    pub fn to_config(&self, noise_color: NoiseColor, filter_slope: FilterSlope) -> HiHat2Config {
+    pub fn to_config(
+        &self,
+        noise_color: NoiseColor,
+        filter_slope: FilterSlope,
+        mode: HiHatMode,
+    ) -> HiHat2Config {
         HiHat2Config {
             pitch: self.pitch.get(),
             decay: self.decay.get(),
+            open_decay: self.open_decay.get(),
             attack: self.attack.get(),
             noise_color,
             filter_slope,
             tone: self.tone.get(),
             volume: self.volume.get(),
+            mode,
         }
     }
 }
@@ -286,6 +456,32 @@ impl PhaseModOsc {
     }
 }
 
+/// The 808 metal: six free-running band-limited squares, summed.
+struct SquareBank {
+    sample_rate: f64,
+    phases: [f64; 6],
+}
+
+impl SquareBank {
+    fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate: sample_rate as f64,
+            phases: [0.0; 6],
+        }
+    }
+
+    /// One sample with the bank transposed by `ratio`, about ±1
+    fn tick(&mut self, ratio: f32) -> f32 {
+        let mut sum = 0.0;
+        for (phase, hz) in self.phases.iter_mut().zip(ranges::METAL_808_HZ) {
+            let phase_inc = (hz * ratio) as f64 / self.sample_rate;
+            sum += polyblep_square(*phase, phase_inc);
+            *phase = (*phase + phase_inc) % 1.0;
+        }
+        sum / 6.0
+    }
+}
+
 /// Asymmetric one-pole smoothing (instant up, smoothed down)
 struct AsymmetricSmoother {
     current: f32,
@@ -323,14 +519,40 @@ impl AsymmetricSmoother {
     }
 }
 
+/// Per-hit velocity modulation depths (each 0-1)
+///
+/// `level` scales loudness (1.0 = amplitude follows velocity, the classic
+/// behavior; 0.0 = every hit at full level). `decay` shortens soft hits and
+/// `tone` darkens them by lowering the highpass filters (the oscillator pitch
+/// is unchanged); at 0.0 they leave the sound unchanged.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VelocityRouting {
+    pub level: f32,
+    pub decay: f32,
+    pub tone: f32,
+}
+
+impl Default for VelocityRouting {
+    fn default() -> Self {
+        Self {
+            level: 1.0,
+            decay: 0.0,
+            tone: 0.0,
+        }
+    }
+}
+
 pub struct HiHat2 {
     pub sample_rate: f32,
     pub params: HiHat2Params,
     pub noise_color: NoiseColor,
     pub filter_slope: FilterSlope,
+    pub mode: HiHatMode,
 
     mod_osc: PhaseModOsc,
     main_osc: PhaseModOsc,
+    square_bank: SquareBank,
+    bandpass: StateVariableFilterTpt,
 
     envelope: MaxCurveEnvelope,
     envelope_smoother: AsymmetricSmoother,
@@ -338,12 +560,28 @@ pub struct HiHat2 {
     hpf_stage_1: BiquadHighpass,
     hpf_stage_2: BiquadHighpass,
     svf: StateVariableFilterTpt,
+    /// Pitch after tuning, and the filter coefficients that follow it and
+    /// the tone, are refreshed at block rate
+    control_rate: ControlRate,
+    pitch_hz: f32,
 
     white_noise_state: u64,
     pink_noise: PinkNoise,
 
     is_active: bool,
+    /// Releases the previous hit when retriggered mid-decay
+    retrigger_fade: RetriggerFade,
     current_velocity: f32,
+
+    // Articulation for hits that don't name one, and the one now sounding
+    articulation: HiHatArticulation,
+    sounding: HiHatArticulation,
+
+    // Velocity modulation matrix and the per-hit values it produced
+    velocity_routing: VelocityRouting,
+    velocity_gain: f32,
+    velocity_decay_scale: f32,
+    velocity_tone_scale: f32,
 }
 
 impl HiHat2 {
@@ -361,32 +599,58 @@ impl HiHat2 {
             params,
             noise_color: config.noise_color,
             filter_slope: config.filter_slope,
+            mode: config.mode,
             mod_osc: PhaseModOsc::new(sample_rate, pitch_hz * 0.1),
             main_osc: PhaseModOsc::new(sample_rate, pitch_hz),
-            envelope: MaxCurveEnvelope::new(Vec::new()),
+            square_bank: SquareBank::new(sample_rate),
+            bandpass: StateVariableFilterTpt::new(
+                sample_rate,
+                pitch_hz,
+                ranges::METAL_808_BANDPASS_Q,
+            ),
+            // Segment times are set per hit
+            envelope: MaxCurveEnvelope::new(vec![(1.0, 0.0, -0.3), (0.0, 0.0, -0.8)]),
             envelope_smoother: AsymmetricSmoother::new(100.0),
             hpf_stage_1: BiquadHighpass::new(sample_rate),
             hpf_stage_2: BiquadHighpass::new(sample_rate),
             svf: StateVariableFilterTpt::new(sample_rate, tone_hz, 0.5),
+            control_rate: ControlRate::default(),
+            pitch_hz,
             white_noise_state: 0x1234_5678_9abc_def0,
             pink_noise: PinkNoise::new(sample_rate),
             is_active: false,
+            retrigger_fade: RetriggerFade::new(sample_rate),
             current_velocity: 1.0,
+            articulation: HiHatArticulation::Closed,
+            sounding: HiHatArticulation::Closed,
+            velocity_routing: VelocityRouting::default(),
+            velocity_gain: 1.0,
+            velocity_decay_scale: 1.0,
+            velocity_tone_scale: 1.0,
         }
     }
 
+    /// Change only the fields set in `partial`; the rest keep their current
+    /// values.
+    pub fn apply_partial(&mut self, partial: &PartialHiHat2Config) {
+        self.set_config(self.config().with_partial(partial));
+    }
+
     pub fn config(&self) -> HiHat2Config {
-        self.params.to_config(self.noise_color, self.filter_slope)
+        self.params
+            .to_config(self.noise_color, self.filter_slope, self.mode)
     }
 
     pub fn set_config(&mut self, config: HiHat2Config) {
         self.params.pitch.set_target(config.pitch);
         self.params.decay.set_target(config.decay);
+        self.params.open_decay.set_target(config.open_decay);
         self.params.attack.set_target(config.attack);
         self.params.tone.set_target(config.tone);
         self.params.volume.set_target(config.volume);
         self.noise_color = config.noise_color;
         self.filter_slope = config.filter_slope;
+        self.mode = config.mode;
     }
 
     /// Snap all smoothed parameters to their targets instantly.
@@ -402,6 +666,10 @@ impl HiHat2 {
         self.params.decay.set_target(decay);
     }
 
+    pub fn set_open_decay(&mut self, open_decay: f32) {
+        self.params.open_decay.set_target(open_decay);
+    }
+
     pub fn set_attack(&mut self, attack: f32) {
         self.params.attack.set_target(attack);
     }
@@ -427,30 +695,105 @@ impl HiHat2 {
         self.filter_slope = filter_slope;
     }
 
+    /// Switch the synthesis engine. Pitch, decay, tone and the rest carry
+    /// over, so the same settings can be heard on either.
+    pub fn set_mode(&mut self, mode: HiHatMode) {
+        self.mode = mode;
+    }
+
+    pub fn articulation(&self) -> HiHatArticulation {
+        self.articulation
+    }
+
+    /// Set the articulation used by hits that don't name one
+    pub fn set_articulation(&mut self, articulation: HiHatArticulation) {
+        self.articulation = articulation;
+    }
+
+    pub fn velocity_routing(&self) -> VelocityRouting {
+        self.velocity_routing
+    }
+
+    /// Set how much velocity scales loudness (0-1). Applies from the next hit.
+    pub fn set_velocity_to_level(&mut self, depth: f32) {
+        self.velocity_routing.level = depth.clamp(0.0, 1.0);
+    }
+
+    /// Set how much soft hits shorten the decay (0-1). Applies from the next hit.
+    pub fn set_velocity_to_decay(&mut self, depth: f32) {
+        self.velocity_routing.decay = depth.clamp(0.0, 1.0);
+    }
+
+    /// Set how much soft hits darken the tone (0-1). Applies from the next hit.
+    pub fn set_velocity_to_tone(&mut self, depth: f32) {
+        self.velocity_routing.tone = depth.clamp(0.0, 1.0);
+    }
+
     pub fn trigger(&mut self, time: f64) {
         self.trigger_with_velocity(time, 1.0);
     }
 
     pub fn trigger_with_velocity(&mut self, time: f64, velocity: f32) {
+        self.trigger_articulated(time, velocity, self.articulation);
+    }
+
+    /// Trigger one hit with the given articulation. A closed or pedal hit
+    /// while an open hit is still ringing chokes it: the open tail falls
+    /// away through the envelope smoother instead of being cut.
+    pub fn trigger_articulated(
+        &mut self,
+        time: f64,
+        velocity: f32,
+        articulation: HiHatArticulation,
+    ) {
+        self.retrigger_fade.retrigger();
+        self.control_rate.reset();
+        let choke = self.is_active
+            && self.sounding == HiHatArticulation::Open
+            && articulation != HiHatArticulation::Open;
         self.is_active = true;
+        self.sounding = articulation;
         self.current_velocity = velocity.clamp(0.0, 1.0);
 
+        // Resolve the velocity matrix once per hit. "Softness" is how far the
+        // hit falls below full velocity.
+        let routing = self.velocity_routing;
+        let softness = 1.0 - self.current_velocity;
+        self.velocity_gain = self.current_velocity * routing.level + (1.0 - routing.level);
+        self.velocity_decay_scale = 1.0 - routing.decay * ranges::VELOCITY_DECAY_RANGE * softness;
+        self.velocity_tone_scale =
+            (-routing.tone * ranges::VELOCITY_TONE_OCTAVES * softness).exp2();
+        if articulation == HiHatArticulation::Pedal {
+            self.velocity_gain *= ranges::PEDAL_GAIN;
+            self.velocity_tone_scale *= (-ranges::PEDAL_TONE_OCTAVES).exp2();
+        }
+
         let attack_ms = self.params.attack_ms();
-        let decay_ms = self.params.decay_ms();
+        let decay_ms = self.decay_ms();
 
-        self.envelope = MaxCurveEnvelope::new(vec![(1.0, attack_ms, -0.3), (0.0, decay_ms, -0.8)]);
+        // Retimed in place: the audio thread must not allocate a new envelope
+        self.envelope.set_segment_duration_ms(0, attack_ms);
+        self.envelope.set_segment_duration_ms(1, decay_ms);
         self.envelope.set_initial_value(0.0);
         self.envelope.trigger(time);
-        self.envelope_smoother.reset(0.0);
+        if !choke {
+            self.envelope_smoother.reset(0.0);
+        }
 
         self.mod_osc.reset_phase();
         self.main_osc.reset_phase();
         self.hpf_stage_1.reset();
         self.hpf_stage_2.reset();
         self.svf.reset();
+        self.bandpass.reset();
     }
 
     pub fn tick(&mut self, current_time: f64) -> f32 {
+        let output = self.render(current_time);
+        self.retrigger_fade.process(output)
+    }
+
+    fn render(&mut self, current_time: f64) -> f32 {
         self.params.tick();
 
         if !self.is_active {
@@ -459,40 +802,35 @@ impl HiHat2 {
 
         self.envelope
             .set_segment_duration_ms(0, self.params.attack_ms());
-        self.envelope
-            .set_segment_duration_ms(1, self.params.decay_ms());
-
-        let pitch_hz = self.params.pitch_hz() * tuning_to_multiplier(self.params.tuning.get());
-        let mod_freq = pitch_hz * 0.1;
-        self.mod_osc.set_frequency(mod_freq);
-        self.main_osc.set_frequency(pitch_hz);
-
-        let noise = match self.noise_color {
-            NoiseColor::White => self.white_noise_tick(),
-            NoiseColor::Pink => self.pink_noise.tick(),
-        };
+        self.envelope.set_segment_duration_ms(1, self.decay_ms());
+
+        if self.control_rate.tick() {
+            self.pitch_hz = self.params.pitch_hz() * tuning_to_multiplier(self.params.tuning.get());
+            let hpf_hz = self.pitch_hz * self.velocity_tone_scale;
+            self.hpf_stage_1.set_params(hpf_hz, 1.0);
+            if self.filter_slope == FilterSlope::Db24 {
+                self.hpf_stage_2.set_params(hpf_hz, 1.0);
+            }
+            let tone_hz = self.params.tone_hz() * self.velocity_tone_scale;
+            self.svf.set_params(tone_hz, 0.5);
+        }
 
-        let mod_signal = noise * 0.25;
-        let mod_output = self.mod_osc.tick(mod_signal);
-        let main_output = self.main_osc.tick(mod_output * 0.75);
-        //
-        let mut filtered = {
-            self.hpf_stage_1.set_params(pitch_hz, 1.0);
-            self.hpf_stage_1.process(main_output)
+        let pitch_hz = self.pitch_hz;
+        let main_output = match self.mode {
+            HiHatMode::PhaseMod => self.phase_mod_tick(pitch_hz),
+            HiHatMode::Classic808 => self.metal_808_tick(pitch_hz),
         };
 
+        let mut filtered = self.hpf_stage_1.process(main_output);
         if self.filter_slope == FilterSlope::Db24 {
-            self.hpf_stage_2.set_params(pitch_hz, 1.0);
             filtered = self.hpf_stage_2.process(filtered) * 0.8;
         }
 
         let env = self.envelope.get_value(current_time);
         let env = self.envelope_smoother.process(env);
 
-        let output = filtered * env * self.current_velocity * 0.35;
+        let output = filtered * env * self.velocity_gain * 0.35;
 
-        let tone_hz = self.params.tone_hz();
-        self.svf.set_params(tone_hz, 0.5);
         let (_, _, high) = self.svf.process_all(output);
 
         // Apply volume after SVF to guarantee silence at volume=0
@@ -511,6 +849,43 @@ impl HiHat2 {
         self.is_active
     }
 
+    /// The phase-mod engine: a sine phase-modulated by a noise-modulated sine
+    fn phase_mod_tick(&mut self, pitch_hz: f32) -> f32 {
+        let mod_freq = pitch_hz * 0.1;
+        self.mod_osc.set_frequency(mod_freq);
+        self.main_osc.set_frequency(pitch_hz);
+
+        let noise = match self.noise_color {
+            NoiseColor::White => self.white_noise_tick(),
+            NoiseColor::Pink => self.pink_noise.tick(),
+        };
+
+        let mod_signal = noise * 0.25;
+        let mod_output = self.mod_osc.tick(mod_signal);
+        self.main_osc.tick(mod_output * 0.75)
+    }
+
+    /// The 808 engine: the square bank through a bandpass at the pitch
+    fn metal_808_tick(&mut self, pitch_hz: f32) -> f32 {
+        let metal = self
+            .square_bank
+            .tick(pitch_hz / ranges::METAL_808_REFERENCE_HZ);
+        self.bandpass
+            .set_params(pitch_hz, ranges::METAL_808_BANDPASS_Q);
+        let (_, band, _) = self.bandpass.process_all(metal);
+        band * ranges::METAL_808_GAIN
+    }
+
+    /// Decay of the sounding hit, after articulation and velocity
+    fn decay_ms(&self) -> f32 {
+        let decay_ms = match self.sounding {
+            HiHatArticulation::Closed => self.params.decay_ms(),
+            HiHatArticulation::Pedal => self.params.decay_ms() * ranges::PEDAL_DECAY_SCALE,
+            HiHatArticulation::Open => self.params.open_decay_ms(),
+        };
+        decay_ms * self.velocity_decay_scale
+    }
+
     fn white_noise_tick(&mut self) -> f32 {
         // xorshift64*
         let mut x = self.white_noise_state;
@@ -530,6 +905,11 @@ impl crate::engine::Instrument for HiHat2 {
         HiHat2::trigger_with_velocity(self, time, velocity);
     }
 
+    fn trigger_articulated(&mut self, time: f64, velocity: f32, articulation: u8) {
+        let articulation = HiHatArticulation::from_index(articulation).unwrap_or(self.articulation);
+        HiHat2::trigger_articulated(self, time, velocity, articulation);
+    }
+
     fn tick(&mut self, current_time: f64) -> f32 {
         self.tick(current_time)
     }
@@ -538,6 +918,12 @@ impl crate::engine::Instrument for HiHat2 {
         self.is_active()
     }
 
+    fn reseed(&mut self, seed: u64) {
+        // xorshift64* sticks at zero
+        self.white_noise_state = seed | 1;
+        self.pink_noise.set_seed(seed.rotate_left(32));
+    }
+
     fn as_modulatable(&mut self) -> Option<&mut dyn crate::engine::Modulatable> {
         Some(self)
     }
@@ -545,10 +931,18 @@ impl crate::engine::Instrument for HiHat2 {
 
 impl crate::engine::Modulatable for HiHat2 {
     fn modulatable_parameters(&self) -> Vec<&'static str> {
-        vec!["attack", "decay", "pitch", "tone", "tuning", "volume"]
-    }
-
-    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), String> {
+        vec![
+            "attack",
+            "decay",
+            "open_decay",
+            "pitch",
+            "tone",
+            "tuning",
+            "volume",
+        ]
+    }
+
+    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), GooeyError> {
         match parameter {
             "attack" => {
                 self.params.attack.set_bipolar(value);
@@ -558,6 +952,10 @@ impl crate::engine::Modulatable for HiHat2 {
                 self.params.decay.set_bipolar(value);
                 Ok(())
             }
+            "open_decay" => {
+                self.params.open_decay.set_bipolar(value);
+                Ok(())
+            }
             "pitch" => {
                 self.params.pitch.set_bipolar(value);
                 Ok(())
@@ -574,7 +972,7 @@ impl crate::engine::Modulatable for HiHat2 {
                 self.params.volume.set_bipolar(value);
                 Ok(())
             }
-            _ => Err(format!("Unknown parameter: {}", parameter)),
+            _ => Err(GooeyError::UnknownParameter(parameter.to_string())),
         }
     }
 
@@ -582,6 +980,7 @@ impl crate::engine::Modulatable for HiHat2 {
         match parameter {
             "attack" => Some(self.params.attack.range()),
             "decay" => Some(self.params.decay.range()),
+            "open_decay" => Some(self.params.open_decay.range()),
             "pitch" => Some(self.params.pitch.range()),
             "tone" => Some(self.params.tone.range()),
             "tuning" => Some(self.params.tuning.range()),
@@ -1,12 +1,17 @@
 use crate::effects::feedback_waveshaper::FeedbackWaveshaper;
 use crate::envelope::{ADSRConfig, Envelope, EnvelopeCurve};
+use crate::error::GooeyError;
 use crate::filters::{ResonantHighpassFilter, ResonantLowpassFilter};
 use crate::gen::oscillator::Oscillator;
 use crate::gen::pink_noise::PinkNoise;
 use crate::gen::waveform::Waveform;
 use crate::instruments::fm_snap::PhaseModulator;
+use crate::partial_config;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 use crate::utils::{
-    tuning_to_multiplier, Blendable, OversamplingMode, SmoothedParam, DEFAULT_SMOOTH_TIME_MS,
+    tuning_to_multiplier, Blendable, OversamplingMode, RetriggerFade, SmoothedParam,
+    DEFAULT_SMOOTH_TIME_MS,
 };
 
 /// Normalization ranges for kick drum parameters
@@ -350,6 +355,30 @@ impl KickConfig {
     }
 }
 
+partial_config!(
+    /// [`KickConfig`] with every field optional: only the fields set are applied
+    pub struct PartialKickConfig for KickConfig {
+        frequency: f32,
+        punch_amount: f32,
+        sub_amount: f32,
+        click_amount: f32,
+        oscillator_decay: f32,
+        pitch_envelope_amount: f32,
+        pitch_envelope_curve: f32,
+        volume: f32,
+        pitch_start_ratio: f32,
+        phase_mod_amount: f32,
+        noise_amount: f32,
+        noise_cutoff: f32,
+        noise_resonance: f32,
+        overdrive_amount: f32,
+        feedback_amount: f32,
+        feedback_cutoff: f32,
+        amp_decay: f32,
+        amp_decay_curve: f32,
+    }
+);
+
 impl Blendable for KickConfig {
     fn lerp(&self, other: &Self, t: f32) -> Self {
         let t = t.clamp(0.0, 1.0);
@@ -752,6 +781,10 @@ pub struct KickDrum {
     pub amplitude_envelope: Envelope,
 
     pub is_active: bool,
+    /// Releases the previous hit when retriggered mid-decay
+    retrigger_fade: RetriggerFade,
+    /// Master tuning multiplier (A4 reference and transpose)
+    pitch_ratio: f32,
 
     // Velocity-responsive state
     /// Current trigger velocity (0.0-1.0), set on trigger
@@ -809,6 +842,8 @@ impl KickDrum {
             ),
             amplitude_envelope: Envelope::new(),
             is_active: false,
+            retrigger_fade: RetriggerFade::new(sample_rate),
+            pitch_ratio: 1.0,
 
             // Initialize velocity state
             current_velocity: 1.0,
@@ -953,6 +988,12 @@ impl KickDrum {
         self.params.snap_all();
     }
 
+    /// Change only the fields set in `partial`; the rest keep their current
+    /// values.
+    pub fn apply_partial(&mut self, partial: &PartialKickConfig) {
+        self.set_config(self.config().with_partial(partial));
+    }
+
     /// Get current config snapshot (reads current smoothed values)
     pub fn config(&self) -> KickConfig {
         self.params.to_config()
@@ -969,6 +1010,7 @@ impl KickDrum {
     /// - Higher velocity = shorter decay (tighter, punchier sound)
     /// - Lower velocity = longer decay (deeper, more sustained sound)
     pub fn trigger_with_velocity(&mut self, time: f64, velocity: f32) {
+        self.retrigger_fade.retrigger();
         self.current_velocity = velocity.clamp(0.0, 1.0);
         self.is_active = true;
 
@@ -1095,6 +1137,11 @@ impl KickDrum {
     }
 
     pub fn tick(&mut self, current_time: f64) -> f32 {
+        let output = self.render(current_time);
+        self.retrigger_fade.process(output)
+    }
+
+    fn render(&mut self, current_time: f64) -> f32 {
         // Always tick smoothers (even when not active, to settle values)
         self.params.tick();
 
@@ -1137,8 +1184,9 @@ impl KickDrum {
 
         // Read frequency per-sample so LFO modulation of `frequency` is audible
         // mid-note. Tuning was already live; frequency now matches.
-        let base_frequency =
-            self.params.frequency_hz() * tuning_to_multiplier(self.params.tuning.get());
+        let base_frequency = self.params.frequency_hz()
+            * tuning_to_multiplier(self.params.tuning.get())
+            * self.pitch_ratio;
 
         // Calculate pitch modulation from envelope using triggered pitch multiplier
         let pitch_envelope_value = self.pitch_envelope.get_amplitude(current_time);
@@ -1374,13 +1422,25 @@ impl crate::engine::Instrument for KickDrum {
         self.tick(current_time)
     }
 
+    fn release(&mut self, time: f64) {
+        self.release(time)
+    }
+
     fn is_active(&self) -> bool {
         self.is_active()
     }
 
+    fn reseed(&mut self, seed: u64) {
+        self.pink_noise.set_seed(seed);
+    }
+
     fn as_modulatable(&mut self) -> Option<&mut dyn crate::engine::Modulatable> {
         Some(self)
     }
+
+    fn set_pitch_ratio(&mut self, ratio: f32) {
+        self.pitch_ratio = ratio;
+    }
 }
 
 // Implement modulation support for KickDrum
@@ -1407,7 +1467,7 @@ impl crate::engine::Modulatable for KickDrum {
         ]
     }
 
-    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), String> {
+    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), GooeyError> {
         // value is -1.0 to 1.0 (bipolar), set_bipolar maps this to the param range
         match parameter {
             "frequency" => {
@@ -1474,7 +1534,7 @@ impl crate::engine::Modulatable for KickDrum {
                 self.params.tuning.set_bipolar(value);
                 Ok(())
             }
-            _ => Err(format!("Unknown parameter: {}", parameter)),
+            _ => Err(GooeyError::UnknownParameter(parameter.to_string())),
         }
     }
 
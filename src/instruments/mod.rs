@@ -1,24 +1,37 @@
 pub mod bass;
+pub mod closure;
+pub mod cowbell;
 pub mod fm_snap;
 pub mod granulator;
 pub mod hihat2;
 pub mod kick;
 pub mod poly_synth;
+pub mod rimshot;
+#[cfg(all(feature = "stream", not(target_arch = "wasm32")))]
+pub mod sample_stream;
 pub mod sampler;
+pub mod shaker;
 pub mod snare;
 pub mod tom;
 pub mod tom2;
 
 pub use self::bass::*;
+pub use self::closure::*;
+pub use self::cowbell::*;
 pub use self::fm_snap::*;
 pub use self::granulator::*;
 pub use self::hihat2::*;
 pub use self::kick::*;
 pub use self::poly_synth::*;
+pub use self::rimshot::*;
+#[cfg(all(feature = "stream", not(target_arch = "wasm32")))]
+pub use self::sample_stream::*;
 pub use self::sampler::*;
+pub use self::shaker::*;
 pub use self::snare::*;
 pub use self::tom::*;
 pub use self::tom2::*;
 
 pub type HiHat = HiHat2;
 pub type HiHatConfig = HiHat2Config;
+pub type PartialHiHatConfig = PartialHiHat2Config;
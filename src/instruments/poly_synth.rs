@@ -1,11 +1,16 @@
 use crate::engine::Instrument;
-use crate::envelope::{ADSRConfig, Envelope, EnvelopeCurve};
+use crate::envelope::{ADSRConfig, Envelope, EnvelopeCurve, EnvelopeShape};
 use crate::filters::StateVariableFilterTpt;
 use crate::gen::polyblep::{polyblep_saw, polyblep_square};
 use crate::music::note::midi_to_freq;
-use crate::utils::SmoothedParam;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::{ControlRate, SmoothedParam};
 
 mod ranges {
+    #[cfg(not(feature = "std"))]
+    use crate::prelude::Float;
+
     pub fn filter_cutoff_hz(normalized: f32) -> f32 {
         // Exponential mapping: 0.0 = 20 Hz, 1.0 = 18000 Hz
         20.0 * (18000.0_f32 / 20.0).powf(normalized)
@@ -226,6 +231,8 @@ struct Voice {
     amp_envelope: Envelope,
     filter_envelope: Envelope,
     filter: StateVariableFilterTpt,
+    /// Filter coefficients follow the envelope at block rate
+    filter_rate: ControlRate,
     velocity: f32,
     active: bool,
     trigger_order: u64,
@@ -241,6 +248,7 @@ impl Voice {
             amp_envelope: Envelope::new(),
             filter_envelope: Envelope::new(),
             filter: StateVariableFilterTpt::new(sample_rate, 1000.0, 1.0),
+            filter_rate: ControlRate::default(),
             velocity: 1.0,
             active: false,
             trigger_order: 0,
@@ -253,11 +261,20 @@ pub struct PolySynth {
     pub params: PolySynthParams,
     voices: Vec<Voice>,
     trigger_counter: u64,
+    /// Notes that took over a still-sounding voice, since creation
+    voices_stolen: u32,
+    /// New notes only take the first `voice_limit` voices
+    voice_limit: usize,
     pending_note: Option<u8>,
+    /// Master tuning multiplier (A4 reference and transpose)
+    pitch_ratio: f64,
     /// Tracks the latest audio clock time from tick() so that
     /// trigger_note/release_note called from the UI thread can
     /// use the real audio time instead of a stale value.
     current_time: f64,
+    /// Curves, retrigger and velocity sensitivity of each voice's envelopes
+    amp_shape: EnvelopeShape,
+    filter_shape: EnvelopeShape,
 }
 
 impl PolySynth {
@@ -274,11 +291,44 @@ impl PolySynth {
             params,
             voices,
             trigger_counter: 0,
+            voices_stolen: 0,
+            voice_limit: NUM_VOICES,
             pending_note: None,
+            pitch_ratio: 1.0,
             current_time: 0.0,
+            amp_shape: Self::default_envelope_shape(),
+            filter_shape: Self::default_envelope_shape(),
         }
     }
 
+    /// Linear attack and release, exponential decay
+    fn default_envelope_shape() -> EnvelopeShape {
+        EnvelopeShape {
+            decay_curve: EnvelopeCurve::Exponential(0.5),
+            ..EnvelopeShape::default()
+        }
+    }
+
+    /// Set the amp envelope's curves, retrigger and velocity sensitivity
+    /// (applied from the next note)
+    pub fn set_amp_shape(&mut self, shape: EnvelopeShape) {
+        self.amp_shape = shape;
+    }
+
+    pub fn amp_shape(&self) -> EnvelopeShape {
+        self.amp_shape
+    }
+
+    /// Set the filter envelope's curves, retrigger and velocity sensitivity
+    /// (applied from the next note)
+    pub fn set_filter_shape(&mut self, shape: EnvelopeShape) {
+        self.filter_shape = shape;
+    }
+
+    pub fn filter_shape(&self) -> EnvelopeShape {
+        self.filter_shape
+    }
+
     pub fn set_config(&mut self, config: PolySynthConfig) {
         self.params.osc_shape.set_target(config.osc_shape);
         self.params.detune_amount.set_target(config.detune_amount);
@@ -309,6 +359,9 @@ impl PolySynth {
     pub fn trigger_note(&mut self, note: u8, velocity: f32) {
         let time = self.current_time;
         let voice_idx = self.allocate_voice();
+        if self.voices[voice_idx].active {
+            self.voices_stolen = self.voices_stolen.wrapping_add(1);
+        }
         let voice = &mut self.voices[voice_idx];
 
         voice.midi_note = note;
@@ -317,6 +370,7 @@ impl PolySynth {
         voice.phase_b = 0.0;
         voice.velocity = velocity;
         voice.active = true;
+        voice.filter_rate.reset();
         voice.trigger_order = self.trigger_counter;
         self.trigger_counter += 1;
 
@@ -327,10 +381,10 @@ impl PolySynth {
             self.params.amp_sustain.get(),
             ranges::env_time(self.params.amp_release.get()),
         )
-        .with_decay_curve(EnvelopeCurve::Exponential(0.5));
+        .with_shape(self.amp_shape);
 
         voice.amp_envelope.set_config(amp_config);
-        voice.amp_envelope.trigger(time);
+        voice.amp_envelope.trigger_with_velocity(time, velocity);
 
         // Configure filter envelope
         let filt_config = ADSRConfig::new(
@@ -339,10 +393,10 @@ impl PolySynth {
             self.params.filter_sustain.get(),
             ranges::env_time(self.params.filter_release.get()),
         )
-        .with_decay_curve(EnvelopeCurve::Exponential(0.5));
+        .with_shape(self.filter_shape);
 
         voice.filter_envelope.set_config(filt_config);
-        voice.filter_envelope.trigger(time);
+        voice.filter_envelope.trigger_with_velocity(time, velocity);
 
         // Reset filter state
         voice.filter.reset();
@@ -373,6 +427,16 @@ impl PolySynth {
         }
     }
 
+    /// Silence every voice at once, skipping release tails (used by the
+    /// engine's panic)
+    pub fn kill_all(&mut self) {
+        self.pending_note = None;
+        for voice in &mut self.voices {
+            voice.active = false;
+            voice.filter.reset();
+        }
+    }
+
     // Individual parameter setters (normalized 0-1)
 
     pub fn set_osc_shape(&mut self, value: f32) {
@@ -419,14 +483,30 @@ impl PolySynth {
         self.params.volume.set_target(value.clamp(0.0, 1.0));
     }
 
+    /// Running count of notes that stole a sounding voice (wraps).
+    pub fn voices_stolen(&self) -> u32 {
+        self.voices_stolen
+    }
+
+    /// Cap polyphony for new notes (1 to the full voice count), e.g. to save
+    /// CPU. Notes already sounding on voices past the cap ring out.
+    pub fn set_voice_limit(&mut self, limit: usize) {
+        self.voice_limit = limit.clamp(1, NUM_VOICES);
+    }
+
+    pub fn voice_limit(&self) -> usize {
+        self.voice_limit
+    }
+
     fn allocate_voice(&self) -> usize {
+        let voices = &self.voices[..self.voice_limit];
         // Prefer an inactive voice
-        if let Some(idx) = self.voices.iter().position(|v| !v.active) {
+        if let Some(idx) = voices.iter().position(|v| !v.active) {
             return idx;
         }
 
         // Steal the oldest active voice
-        self.voices
+        voices
             .iter()
             .enumerate()
             .min_by_key(|(_, v)| v.trigger_order)
@@ -455,11 +535,8 @@ impl PolySynth {
             return 0.0;
         }
 
-        // Filter envelope
-        let filter_env = voice.filter_envelope.get_amplitude(current_time);
-
         // Oscillators
-        let freq = voice.frequency;
+        let freq = voice.frequency * self.pitch_ratio;
         let detune_ratio = ranges::detune_ratio(detune);
         let dt = 1.0 / self.sample_rate as f64;
 
@@ -486,15 +563,18 @@ impl PolySynth {
         voice.phase_b -= voice.phase_b.floor();
 
         // Filter with envelope modulation
-        let base_cutoff = ranges::filter_cutoff_hz(cutoff_norm);
-        let max_cutoff = 18000.0_f32;
-        let modulated_cutoff =
-            base_cutoff + filter_env_amount * filter_env * (max_cutoff - base_cutoff);
-        let q = ranges::filter_resonance_q(resonance_norm);
-
-        voice
-            .filter
-            .set_params(modulated_cutoff.clamp(20.0, 18000.0), q);
+        if voice.filter_rate.tick() {
+            let filter_env = voice.filter_envelope.get_amplitude(current_time);
+            let base_cutoff = ranges::filter_cutoff_hz(cutoff_norm);
+            let max_cutoff = 18000.0_f32;
+            let modulated_cutoff =
+                base_cutoff + filter_env_amount * filter_env * (max_cutoff - base_cutoff);
+            let q = ranges::filter_resonance_q(resonance_norm);
+
+            voice
+                .filter
+                .set_params(modulated_cutoff.clamp(20.0, 18000.0), q);
+        }
         let (filtered, _, _) = voice.filter.process_all(osc_mix);
 
         // Apply amplitude envelope and velocity
@@ -524,6 +604,10 @@ impl Instrument for PolySynth {
         output * (1.0 / 4.0)
     }
 
+    fn release(&mut self, _time: f64) {
+        self.release_all();
+    }
+
     fn is_active(&self) -> bool {
         self.voices.iter().any(|v| v.active)
     }
@@ -535,6 +619,10 @@ impl Instrument for PolySynth {
     fn get_frequency(&self) -> Option<f32> {
         None
     }
+
+    fn set_pitch_ratio(&mut self, ratio: f32) {
+        self.pitch_ratio = ratio as f64;
+    }
 }
 
 #[cfg(test)]
@@ -587,14 +675,29 @@ mod tests {
         for note in 60..66 {
             synth.trigger_note(note, 1.0);
         }
+        assert_eq!(synth.voices_stolen(), 0);
         // Trigger a 7th - should steal oldest
         synth.trigger_note(66, 1.0);
+        assert_eq!(synth.voices_stolen(), 1);
 
         let active_count = synth.voices.iter().filter(|v| v.active).count();
         assert_eq!(active_count, 6);
         assert!(synth.voices.iter().any(|v| v.midi_note == 66));
     }
 
+    #[test]
+    fn test_voice_limit_steals_within_the_cap() {
+        let mut synth = PolySynth::new(44100.0);
+        synth.set_voice_limit(2);
+        for note in 60..63 {
+            synth.trigger_note(note, 1.0);
+        }
+        assert_eq!(synth.voices.iter().filter(|v| v.active).count(), 2);
+        assert_eq!(synth.voices_stolen(), 1);
+        synth.set_voice_limit(0);
+        assert_eq!(synth.voice_limit(), 1);
+    }
+
     #[test]
     fn test_poly_synth_release() {
         let mut synth = PolySynth::new(44100.0);
@@ -0,0 +1,498 @@
+use crate::error::GooeyError;
+use crate::filters::StateVariableFilterTpt;
+use crate::partial_config;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::{
+    tuning_to_multiplier, Blendable, RetriggerFade, SmoothedParam, DEFAULT_SMOOTH_TIME_MS,
+};
+use core::f32::consts::TAU;
+
+/// Normalization ranges for rimshot parameters.
+/// All external-facing parameters use 0.0-1.0 normalized values.
+pub(crate) mod ranges {
+    #[cfg(not(feature = "std"))]
+    use crate::prelude::Float;
+
+    /// Tune: 0-1 maps exponentially to a 200-2000 Hz ping
+    pub const TUNE_MIN: f32 = 200.0;
+    pub const TUNE_MAX: f32 = 2000.0;
+
+    /// Tone: 0-1 maps exponentially to a 1-10 kHz click bandpass centre
+    pub const TONE_MIN: f32 = 1000.0;
+    pub const TONE_MAX: f32 = 10000.0;
+
+    /// Decay: 0-1 maps exponentially to a 5-300 ms ping decay (to -60 dB)
+    pub const DECAY_MIN_MS: f32 = 5.0;
+    pub const DECAY_MAX_MS: f32 = 300.0;
+
+    #[inline]
+    pub fn exp_denormalize(normalized: f32, min: f32, max: f32) -> f32 {
+        min * (max / min).powf(normalized.clamp(0.0, 1.0))
+    }
+}
+
+/// The 808 rimshot rings two resonators at about 455 and 1667 Hz; the
+/// ping's overtone keeps that ratio to its fundamental
+const OVERTONE_RATIO: f32 = 3.66;
+
+/// Overtone level relative to the fundamental
+const OVERTONE_LEVEL: f32 = 0.5;
+
+/// Decay of the noise burst behind the click (to -60 dB)
+const CLICK_DECAY_MS: f32 = 3.0;
+
+/// Q of the click bandpass
+const CLICK_Q: f32 = 2.0;
+
+/// Brings the bandpassed click up to about the ping's level
+const CLICK_GAIN: f32 = 2.5;
+
+/// Headroom for the ping and click summed at full level
+const OUTPUT_GAIN: f32 = 0.7;
+
+/// Level below which a decaying hit is considered finished (-80 dB)
+const SILENCE: f32 = 1e-4;
+
+/// ln(1000): time constants that reach -60 dB after the decay time
+const LN_1000: f32 = 6.907_755;
+
+/// Static configuration for rimshot presets.
+/// All parameters use normalized 0.0-1.0 values.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RimshotConfig {
+    pub tune: f32,   // Ping pitch (0-1 -> 200-2000 Hz exp)
+    pub tone: f32,   // Click bandpass centre (0-1 -> 1-10 kHz exp)
+    pub click: f32,  // Click level (0-1)
+    pub decay: f32,  // Ping decay (0-1 -> 5-300 ms exp)
+    pub volume: f32, // Output volume (0-1)
+}
+
+impl RimshotConfig {
+    pub fn new(tune: f32, tone: f32, click: f32, decay: f32, volume: f32) -> Self {
+        Self {
+            tune: tune.clamp(0.0, 1.0),
+            tone: tone.clamp(0.0, 1.0),
+            click: click.clamp(0.0, 1.0),
+            decay: decay.clamp(0.0, 1.0),
+            volume: volume.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Classic preset: the 808 rimshot, a ~455 Hz ping with a bright tick
+    pub fn classic() -> Self {
+        Self::new(0.36, 0.55, 0.5, 0.25, 0.8)
+    }
+
+    /// Tight preset: higher, shorter and mostly click
+    pub fn tight() -> Self {
+        Self::new(0.5, 0.7, 0.75, 0.1, 0.8)
+    }
+
+    /// Woody preset: low ping and a dull click, like a stick on a rim
+    pub fn woody() -> Self {
+        Self::new(0.28, 0.3, 0.35, 0.35, 0.85)
+    }
+
+    /// Ring preset: a long, pitched ping with little click
+    pub fn ring() -> Self {
+        Self::new(0.55, 0.5, 0.2, 0.75, 0.75)
+    }
+
+    #[inline]
+    pub fn tune_hz(&self) -> f32 {
+        ranges::exp_denormalize(self.tune, ranges::TUNE_MIN, ranges::TUNE_MAX)
+    }
+
+    #[inline]
+    pub fn decay_ms(&self) -> f32 {
+        ranges::exp_denormalize(self.decay, ranges::DECAY_MIN_MS, ranges::DECAY_MAX_MS)
+    }
+}
+
+impl Default for RimshotConfig {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+partial_config!(
+    /// [`RimshotConfig`] with every field optional: only the fields set are applied
+    pub struct PartialRimshotConfig for RimshotConfig {
+        tune: f32,
+        tone: f32,
+        click: f32,
+        decay: f32,
+        volume: f32,
+    }
+);
+
+impl Blendable for RimshotConfig {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let inv_t = 1.0 - t;
+
+        Self {
+            tune: self.tune * inv_t + other.tune * t,
+            tone: self.tone * inv_t + other.tone * t,
+            click: self.click * inv_t + other.click * t,
+            decay: self.decay * inv_t + other.decay * t,
+            volume: self.volume * inv_t + other.volume * t,
+        }
+    }
+}
+
+/// Smoothed parameters for real-time control
+pub struct RimshotParams {
+    pub tune: SmoothedParam,
+    pub tone: SmoothedParam,
+    pub click: SmoothedParam,
+    pub decay: SmoothedParam,
+    pub volume: SmoothedParam,
+    // Per-instrument tuning (0=−12 semitones, 0.5=neutral, 1=+12 semitones)
+    pub tuning: SmoothedParam,
+}
+
+impl RimshotParams {
+    pub fn from_config(config: &RimshotConfig, sample_rate: f32) -> Self {
+        let smoothed =
+            |value: f32| SmoothedParam::new(value, 0.0, 1.0, sample_rate, DEFAULT_SMOOTH_TIME_MS);
+        Self {
+            tune: smoothed(config.tune),
+            tone: smoothed(config.tone),
+            click: smoothed(config.click),
+            decay: smoothed(config.decay),
+            volume: smoothed(config.volume),
+            tuning: smoothed(0.5),
+        }
+    }
+
+    #[inline]
+    pub fn tick(&mut self) {
+        self.tune.tick();
+        self.tone.tick();
+        self.click.tick();
+        self.decay.tick();
+        self.volume.tick();
+        self.tuning.tick();
+    }
+
+    /// Snap all smoothed parameters to their targets instantly.
+    pub fn snap_all(&mut self) {
+        self.tune.snap();
+        self.tone.snap();
+        self.click.snap();
+        self.decay.snap();
+        self.volume.snap();
+        self.tuning.snap();
+    }
+
+    pub fn to_config(&self) -> RimshotConfig {
+        RimshotConfig {
+            tune: self.tune.get(),
+            tone: self.tone.get(),
+            click: self.click.get(),
+            decay: self.decay.get(),
+            volume: self.volume.get(),
+        }
+    }
+
+    #[inline]
+    pub fn tune_hz(&self) -> f32 {
+        ranges::exp_denormalize(self.tune.get(), ranges::TUNE_MIN, ranges::TUNE_MAX)
+    }
+
+    #[inline]
+    pub fn tone_hz(&self) -> f32 {
+        ranges::exp_denormalize(self.tone.get(), ranges::TONE_MIN, ranges::TONE_MAX)
+    }
+
+    #[inline]
+    pub fn decay_ms(&self) -> f32 {
+        ranges::exp_denormalize(self.decay.get(), ranges::DECAY_MIN_MS, ranges::DECAY_MAX_MS)
+    }
+}
+
+/// Rimshot: a few milliseconds of bandpassed noise for the stick's click
+/// over a short, tuned sine ping with an inharmonic overtone, after the
+/// 808's bridged-T rimshot. Velocity scales the level and, more steeply, the
+/// click, so soft hits are rounder.
+pub struct Rimshot {
+    pub sample_rate: f32,
+    pub params: RimshotParams,
+
+    ping_phase: f32,
+    overtone_phase: f32,
+    click_filter: StateVariableFilterTpt,
+    noise_state: u64,
+    // Samples since the last trigger (counted, not summed as seconds)
+    elapsed: u64,
+
+    is_active: bool,
+    /// Releases the previous hit when retriggered mid-decay
+    retrigger_fade: RetriggerFade,
+    /// Master tuning multiplier (A4 reference and transpose)
+    pitch_ratio: f32,
+    current_velocity: f32,
+}
+
+impl Rimshot {
+    pub fn new(sample_rate: f32) -> Self {
+        Self::with_config(sample_rate, RimshotConfig::default())
+    }
+
+    pub fn with_config(sample_rate: f32, config: RimshotConfig) -> Self {
+        let params = RimshotParams::from_config(&config, sample_rate);
+        let click_filter = StateVariableFilterTpt::new(sample_rate, params.tone_hz(), CLICK_Q);
+        Self {
+            sample_rate,
+            params,
+            ping_phase: 0.0,
+            overtone_phase: 0.0,
+            click_filter,
+            noise_state: 0x9e37_79b9_7f4a_7c15,
+            elapsed: 0,
+            is_active: false,
+            retrigger_fade: RetriggerFade::new(sample_rate),
+            pitch_ratio: 1.0,
+            current_velocity: 1.0,
+        }
+    }
+
+    /// Change only the fields set in `partial`; the rest keep their current
+    /// values.
+    pub fn apply_partial(&mut self, partial: &PartialRimshotConfig) {
+        self.set_config(self.config().with_partial(partial));
+    }
+
+    /// Get current config snapshot (reads current smoothed values)
+    pub fn config(&self) -> RimshotConfig {
+        self.params.to_config()
+    }
+
+    pub fn set_config(&mut self, config: RimshotConfig) {
+        self.params.tune.set_target(config.tune);
+        self.params.tone.set_target(config.tone);
+        self.params.click.set_target(config.click);
+        self.params.decay.set_target(config.decay);
+        self.params.volume.set_target(config.volume);
+    }
+
+    pub fn snap_params(&mut self) {
+        self.params.snap_all();
+    }
+
+    // Individual parameter setters (normalized 0-1)
+
+    pub fn set_tune(&mut self, value: f32) {
+        self.params.tune.set_target(value.clamp(0.0, 1.0));
+    }
+
+    pub fn set_tone(&mut self, value: f32) {
+        self.params.tone.set_target(value.clamp(0.0, 1.0));
+    }
+
+    pub fn set_click(&mut self, value: f32) {
+        self.params.click.set_target(value.clamp(0.0, 1.0));
+    }
+
+    pub fn set_decay(&mut self, value: f32) {
+        self.params.decay.set_target(value.clamp(0.0, 1.0));
+    }
+
+    pub fn set_volume(&mut self, value: f32) {
+        self.params.volume.set_target(value.clamp(0.0, 1.0));
+    }
+
+    /// Set tuning offset (smoothed, 0-1: 0=−12 semitones, 0.5=neutral, 1=+12 semitones)
+    pub fn set_tuning(&mut self, value: f32) {
+        self.params.tuning.set_target(value.clamp(0.0, 1.0));
+    }
+
+    pub fn trigger(&mut self, time: f64) {
+        self.trigger_with_velocity(time, 1.0);
+    }
+
+    pub fn trigger_with_velocity(&mut self, _time: f64, velocity: f32) {
+        self.retrigger_fade.retrigger();
+        self.is_active = true;
+        self.current_velocity = velocity.clamp(0.0, 1.0);
+        self.ping_phase = 0.0;
+        self.overtone_phase = 0.0;
+        self.click_filter.reset();
+        self.elapsed = 0;
+    }
+
+    pub fn tick(&mut self, current_time: f64) -> f32 {
+        let output = self.render(current_time);
+        self.retrigger_fade.process(output)
+    }
+
+    fn render(&mut self, _current_time: f64) -> f32 {
+        self.params.tick();
+
+        if !self.is_active {
+            return 0.0;
+        }
+
+        let t = self.elapsed as f32 / self.sample_rate;
+        self.elapsed += 1;
+
+        let ping_env = (-t * 1000.0 * LN_1000 / self.params.decay_ms()).exp();
+        let click_env = (-t * 1000.0 * LN_1000 / CLICK_DECAY_MS).exp();
+        if ping_env < SILENCE && click_env < SILENCE {
+            self.is_active = false;
+            return 0.0;
+        }
+
+        // The overtone dies at twice the fundamental's rate
+        let ping_hz = self.params.tune_hz()
+            * tuning_to_multiplier(self.params.tuning.get())
+            * self.pitch_ratio;
+        let ping = (TAU * self.ping_phase).sin() * ping_env
+            + (TAU * self.overtone_phase).sin() * OVERTONE_LEVEL * ping_env * ping_env;
+        self.ping_phase = (self.ping_phase + ping_hz / self.sample_rate).fract();
+        self.overtone_phase =
+            (self.overtone_phase + ping_hz * OVERTONE_RATIO / self.sample_rate).fract();
+
+        self.click_filter.set_params(self.params.tone_hz(), CLICK_Q);
+        let noise = self.noise_tick() * click_env;
+        let (_, click, _) = self.click_filter.process_all(noise);
+
+        let velocity = self.current_velocity;
+        let click_level = self.params.click.get() * CLICK_GAIN * velocity;
+        (ping + click * click_level) * OUTPUT_GAIN * velocity * self.params.volume.get()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    fn noise_tick(&mut self) -> f32 {
+        // xorshift64*
+        let mut x = self.noise_state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.noise_state = x;
+        let hashed = x.wrapping_mul(0x2545F4914F6CDD1D);
+        (hashed >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+    }
+}
+
+impl crate::engine::Instrument for Rimshot {
+    fn trigger_with_velocity(&mut self, time: f64, velocity: f32) {
+        Rimshot::trigger_with_velocity(self, time, velocity);
+    }
+
+    fn tick(&mut self, current_time: f64) -> f32 {
+        self.tick(current_time)
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_active()
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        // xorshift64* sticks at zero
+        self.noise_state = seed | 1;
+    }
+
+    fn as_modulatable(&mut self) -> Option<&mut dyn crate::engine::Modulatable> {
+        Some(self)
+    }
+
+    fn set_pitch_ratio(&mut self, ratio: f32) {
+        self.pitch_ratio = ratio;
+    }
+}
+
+impl crate::engine::Modulatable for Rimshot {
+    fn modulatable_parameters(&self) -> Vec<&'static str> {
+        vec!["click", "decay", "tone", "tune", "tuning", "volume"]
+    }
+
+    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), GooeyError> {
+        let param = match parameter {
+            "click" => &mut self.params.click,
+            "decay" => &mut self.params.decay,
+            "tone" => &mut self.params.tone,
+            "tune" => &mut self.params.tune,
+            "tuning" => &mut self.params.tuning,
+            "volume" => &mut self.params.volume,
+            _ => return Err(GooeyError::UnknownParameter(parameter.to_string())),
+        };
+        param.set_bipolar(value);
+        Ok(())
+    }
+
+    fn parameter_range(&self, parameter: &str) -> Option<(f32, f32)> {
+        match parameter {
+            "click" => Some(self.params.click.range()),
+            "decay" => Some(self.params.decay.range()),
+            "tone" => Some(self.params.tone.range()),
+            "tune" => Some(self.params.tune.range()),
+            "tuning" => Some(self.params.tuning.range()),
+            "volume" => Some(self.params.volume.range()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 44_100.0;
+
+    fn render_hit(rim: &mut Rimshot, velocity: f32, frames: usize) -> Vec<f32> {
+        rim.trigger_with_velocity(0.0, velocity);
+        (0..frames)
+            .map(|i| rim.tick(i as f64 / SR as f64))
+            .collect()
+    }
+
+    fn energy(samples: &[f32]) -> f32 {
+        samples.iter().map(|s| s * s).sum()
+    }
+
+    #[test]
+    fn hit_is_short_and_goes_inactive() {
+        let mut rim = Rimshot::new(SR);
+        let out = render_hit(&mut rim, 1.0, SR as usize / 2);
+        assert!(out.iter().all(|s| s.is_finite() && s.abs() <= 1.0));
+        assert!(energy(&out[..1024]) > 1.0);
+        assert!(!rim.is_active());
+        assert_eq!(rim.tick(0.0), 0.0);
+    }
+
+    #[test]
+    fn click_and_velocity_shape_the_hit() {
+        let with_click = |click| {
+            let mut rim = Rimshot::new(SR);
+            rim.set_click(click);
+            rim.snap_params();
+            render_hit(&mut rim, 1.0, 2048)
+        };
+        let (no_click, full) = (with_click(0.0), with_click(1.0));
+        // The click lives in the first few milliseconds
+        assert!(energy(&full[..64]) > energy(&no_click[..64]) * 1.5);
+
+        let mut soft = Rimshot::new(SR);
+        soft.set_click(1.0);
+        soft.snap_params();
+        let soft = render_hit(&mut soft, 0.3, 2048);
+        assert!(energy(&soft) < energy(&full) * 0.2);
+    }
+
+    #[test]
+    fn presets_blend_and_round_trip_through_params() {
+        let blended = RimshotConfig::tight().lerp(&RimshotConfig::ring(), 0.5);
+        assert!((blended.decay - 0.425).abs() < 1e-6);
+
+        let mut rim = Rimshot::new(SR);
+        rim.set_config(RimshotConfig::woody());
+        rim.snap_params();
+        assert_eq!(rim.config(), RimshotConfig::woody());
+    }
+}
@@ -0,0 +1,450 @@
+//! Disk-streamed sampler slots (native builds with the `stream` feature)
+//!
+//! Long backing loops don't have to live in memory. A [`DiskStream`] keeps
+//! the first second of a WAV file resident and a prefetch thread decodes the
+//! rest into a lock-free single-producer/single-consumer FIFO ahead of the
+//! audio thread. A trigger starts from the resident head at once while the
+//! thread seeks back to just behind it. When the thread falls behind, the
+//! stream outputs silence and counts an under-run rather than waiting.
+//!
+//! WASM builds have no threads or filesystem; they keep loading slots into
+//! memory.
+
+use std::cell::UnsafeCell;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use super::sampler::SlotStream;
+use crate::error::GooeyError;
+use crate::frame::StereoFrame;
+
+/// Audio kept in memory from the start of the file, in seconds. Covers the
+/// prefetch thread's seek after a trigger.
+pub const STREAM_PRELOAD_SECONDS: f32 = 1.0;
+/// Decoded audio buffered ahead of the playhead, in seconds.
+pub const STREAM_FIFO_SECONDS: f32 = 2.0;
+/// Frames the prefetch thread decodes per pass.
+const DECODE_CHUNK_FRAMES: usize = 4096;
+/// How long an idle or caught-up prefetch thread sleeps.
+const IDLE_SLEEP: Duration = Duration::from_millis(5);
+/// Fade-in applied on each trigger, in frames (click guard only).
+const START_FADE_FRAMES: f32 = 32.0;
+
+type WavFile = hound::WavReader<BufReader<File>>;
+
+/// State shared between the audio thread (consumer) and the prefetch thread
+/// (producer). Frame counters only ever grow; FIFO positions are taken
+/// modulo the capacity.
+struct Shared {
+    /// Interleaved stereo frames
+    fifo: Box<[UnsafeCell<f32>]>,
+    capacity: usize,
+    /// Frames published by the producer
+    written: AtomicUsize,
+    /// Frames released by the consumer
+    read: AtomicUsize,
+    /// Bumped by the consumer on every trigger; 0 = never triggered
+    generation: AtomicU32,
+    /// Generation the producer is currently filling for
+    acked: AtomicU32,
+    /// `written` when the producer acked: where that generation's frames begin
+    restart_at: AtomicUsize,
+    /// Generation whose final frame has been written (non-looping streams)
+    finished: AtomicU32,
+    looping: bool,
+    running: AtomicBool,
+    underruns: AtomicU64,
+}
+
+// SAFETY: FIFO cells in `read..written` are only read by the consumer and
+// cells outside it only written by the producer; the counters are published
+// with release/acquire ordering.
+unsafe impl Sync for Shared {}
+
+/// A WAV file played from disk through a prefetch thread.
+pub struct DiskStream {
+    shared: Arc<Shared>,
+    thread: Option<JoinHandle<()>>,
+    /// Interleaved stereo frames from the start of the file
+    head: Vec<f32>,
+    frames: usize,
+    channels: usize,
+    sample_rate: f32,
+    /// Source frames per output frame
+    increment: f64,
+    playing: bool,
+    velocity: f32,
+    generation: u32,
+    /// Whether stale FIFO frames from before the last trigger were discarded
+    synced: bool,
+    /// Source frames taken since the last trigger
+    consumed: usize,
+    /// Interpolation between `prev` and `next`
+    frac: f64,
+    prev: StereoFrame,
+    next: StereoFrame,
+    /// Output frames since the last trigger, for the fade-in
+    age: f32,
+    /// Inside an under-run (each run counts once)
+    starved: bool,
+}
+
+impl DiskStream {
+    /// Open a (mono or stereo) WAV file for streaming at `engine_rate`. Files
+    /// with more than two channels play channels 0 and 1. With `looping` the
+    /// file repeats until stopped.
+    pub fn open(
+        path: impl AsRef<Path>,
+        engine_rate: f32,
+        looping: bool,
+    ) -> Result<Self, GooeyError> {
+        let path = path.as_ref();
+        let mut reader = hound::WavReader::open(path)
+            .map_err(|e| GooeyError::Io(format!("Failed to open WAV: {e}")))?;
+        let spec = reader.spec();
+        if spec.channels == 0 {
+            return Err(GooeyError::Io(
+                "WAV must have at least one channel".to_string(),
+            ));
+        }
+        if spec.sample_rate == 0 {
+            return Err(GooeyError::Io(
+                "WAV sample rate must be greater than zero".to_string(),
+            ));
+        }
+        if !(engine_rate.is_finite() && engine_rate > 0.0) {
+            return Err(GooeyError::InvalidValue(
+                "Engine sample rate must be greater than zero".to_string(),
+            ));
+        }
+        let frames = reader.duration() as usize;
+        if frames == 0 {
+            return Err(GooeyError::Io("WAV contains no audio".to_string()));
+        }
+        let sample_rate = spec.sample_rate as f32;
+
+        let head_frames = frames.min((STREAM_PRELOAD_SECONDS * sample_rate) as usize);
+        let mut head = Vec::with_capacity(head_frames * 2);
+        read_frames(&mut reader, head_frames, &mut head)?;
+
+        let capacity = ((STREAM_FIFO_SECONDS * sample_rate) as usize).max(DECODE_CHUNK_FRAMES);
+        let shared = Arc::new(Shared {
+            fifo: (0..capacity * 2).map(|_| UnsafeCell::new(0.0)).collect(),
+            capacity,
+            written: AtomicUsize::new(0),
+            read: AtomicUsize::new(0),
+            generation: AtomicU32::new(0),
+            acked: AtomicU32::new(0),
+            restart_at: AtomicUsize::new(0),
+            finished: AtomicU32::new(0),
+            looping,
+            running: AtomicBool::new(true),
+            underruns: AtomicU64::new(0),
+        });
+        let thread_shared = Arc::clone(&shared);
+        let thread = std::thread::Builder::new()
+            .name("gooey-sample-stream".into())
+            .spawn(move || prefetch(reader, thread_shared, head_frames, frames))
+            .map_err(|e| GooeyError::Io(format!("Failed to start stream thread: {e}")))?;
+
+        Ok(Self {
+            shared,
+            thread: Some(thread),
+            head,
+            frames,
+            channels: spec.channels.min(2) as usize,
+            sample_rate,
+            increment: sample_rate as f64 / engine_rate as f64,
+            playing: false,
+            velocity: 0.0,
+            generation: 0,
+            synced: false,
+            consumed: 0,
+            frac: 0.0,
+            prev: StereoFrame::default(),
+            next: StereoFrame::default(),
+            age: 0.0,
+            starved: false,
+        })
+    }
+
+    /// Next source frame: silence during an under-run, `None` at the end of
+    /// a non-looping file.
+    fn next_source_frame(&mut self) -> Option<StereoFrame> {
+        let head_frames = self.head.len() / 2;
+        if self.consumed < head_frames {
+            let i = self.consumed * 2;
+            self.consumed += 1;
+            return Some(StereoFrame {
+                l: self.head[i],
+                r: self.head[i + 1],
+            });
+        }
+
+        let shared = &*self.shared;
+        if !self.synced {
+            if shared.acked.load(Ordering::Acquire) != self.generation {
+                return Some(self.underrun());
+            }
+            // Drop whatever was buffered before this trigger
+            let restart = shared.restart_at.load(Ordering::Relaxed);
+            shared.read.store(restart, Ordering::Release);
+            self.synced = true;
+        }
+
+        let read = shared.read.load(Ordering::Relaxed);
+        if read == shared.written.load(Ordering::Acquire) {
+            if shared.finished.load(Ordering::Acquire) == self.generation {
+                return None;
+            }
+            return Some(self.underrun());
+        }
+        let i = (read % shared.capacity) * 2;
+        // SAFETY: `read < written`, so the producer has published this frame
+        // and won't touch it until `read` moves past it.
+        let frame = unsafe {
+            StereoFrame {
+                l: *shared.fifo[i].get(),
+                r: *shared.fifo[i + 1].get(),
+            }
+        };
+        shared.read.store(read + 1, Ordering::Release);
+        self.consumed += 1;
+        self.starved = false;
+        Some(frame)
+    }
+
+    fn underrun(&mut self) -> StereoFrame {
+        if !self.starved {
+            self.starved = true;
+            self.shared.underruns.fetch_add(1, Ordering::Relaxed);
+        }
+        StereoFrame::default()
+    }
+}
+
+impl SlotStream for DiskStream {
+    fn start(&mut self, velocity: f32) {
+        // Skip 0, which means "never triggered" to the producer
+        self.generation = self.generation.wrapping_add(1).max(1);
+        self.shared
+            .generation
+            .store(self.generation, Ordering::Release);
+        self.synced = false;
+        self.consumed = 0;
+        self.starved = false;
+        self.velocity = velocity.clamp(0.0, 1.0);
+        self.frac = 0.0;
+        self.age = 0.0;
+        self.prev = self.next_source_frame().unwrap_or_default();
+        self.next = self.next_source_frame().unwrap_or_default();
+        self.playing = true;
+    }
+
+    fn stop(&mut self) {
+        self.playing = false;
+    }
+
+    fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    fn tick(&mut self) -> StereoFrame {
+        if !self.playing {
+            return StereoFrame::default();
+        }
+        let t = self.frac as f32;
+        let frame = StereoFrame {
+            l: self.prev.l + (self.next.l - self.prev.l) * t,
+            r: self.prev.r + (self.next.r - self.prev.r) * t,
+        };
+        let gain = (self.age / START_FADE_FRAMES).min(1.0) * self.velocity;
+        self.age += 1.0;
+
+        self.frac += self.increment;
+        while self.frac >= 1.0 {
+            self.frac -= 1.0;
+            self.prev = self.next;
+            match self.next_source_frame() {
+                Some(next) => self.next = next,
+                None => {
+                    self.playing = false;
+                    break;
+                }
+            }
+        }
+        frame.scaled(gain)
+    }
+
+    fn format(&self) -> (usize, usize, f32) {
+        (self.frames, self.channels, self.sample_rate)
+    }
+
+    fn underruns(&self) -> u64 {
+        self.shared.underruns.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for DiskStream {
+    fn drop(&mut self) {
+        self.shared.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Prefetch thread: keep the FIFO full of the frames after the resident head
+/// for the latest trigger.
+fn prefetch(mut reader: WavFile, shared: Arc<Shared>, head_frames: usize, frames: usize) {
+    let mut generation = 0;
+    let mut position = 0;
+    let mut done = true;
+    let mut chunk = Vec::with_capacity(DECODE_CHUNK_FRAMES * 2);
+
+    while shared.running.load(Ordering::Relaxed) {
+        let requested = shared.generation.load(Ordering::Acquire);
+        if requested != generation {
+            generation = requested;
+            position = head_frames;
+            done = false;
+            if reader.seek(position as u32).is_err() {
+                position = frames;
+            }
+            shared
+                .restart_at
+                .store(shared.written.load(Ordering::Relaxed), Ordering::Relaxed);
+            shared.acked.store(generation, Ordering::Release);
+        }
+
+        let written = shared.written.load(Ordering::Relaxed);
+        let space = shared.capacity - (written - shared.read.load(Ordering::Acquire));
+        if done || space == 0 {
+            std::thread::sleep(IDLE_SLEEP);
+            continue;
+        }
+
+        if position == frames {
+            if shared.looping && reader.seek(0).is_ok() {
+                position = 0;
+            } else {
+                shared.finished.store(generation, Ordering::Release);
+                done = true;
+            }
+            continue;
+        }
+
+        let count = space.min(DECODE_CHUNK_FRAMES).min(frames - position);
+        chunk.clear();
+        if read_frames(&mut reader, count, &mut chunk).is_err() {
+            // A read error ends the stream like the end of the file
+            position = frames;
+            continue;
+        }
+        let count = chunk.len() / 2;
+        for (offset, frame) in chunk.chunks_exact(2).enumerate() {
+            let i = ((written + offset) % shared.capacity) * 2;
+            // SAFETY: these cells are outside `read..written`, which the
+            // consumer doesn't read until `written` is published below.
+            unsafe {
+                *shared.fifo[i].get() = frame[0];
+                *shared.fifo[i + 1].get() = frame[1];
+            }
+        }
+        shared.written.store(written + count, Ordering::Release);
+        position += count;
+    }
+}
+
+/// Decode `count` frames from the reader's position into `out` as
+/// interleaved stereo, duplicating mono and keeping channels 0 and 1.
+fn read_frames(reader: &mut WavFile, count: usize, out: &mut Vec<f32>) -> Result<(), GooeyError> {
+    let spec = reader.spec();
+    let channels = spec.channels as usize;
+    let mut frame = Vec::with_capacity(channels);
+    let mut push = |sample: f32, out: &mut Vec<f32>| {
+        frame.push(sample);
+        if frame.len() == channels {
+            out.push(frame[0]);
+            out.push(frame[channels.min(2) - 1]);
+            frame.clear();
+        }
+    };
+    let total = count * channels;
+    let error = |e: hound::Error| GooeyError::Io(format!("Failed to read WAV sample: {e}"));
+    match spec.sample_format {
+        hound::SampleFormat::Float => {
+            for sample in reader.samples::<f32>().take(total) {
+                push(sample.map_err(error)?, out);
+            }
+        }
+        hound::SampleFormat::Int => {
+            let bits = spec.bits_per_sample;
+            if bits == 0 || bits > 32 {
+                return Err(GooeyError::Io(format!("Unsupported WAV bit depth: {bits}")));
+            }
+            let scale = ((1_i64 << (bits - 1)) - 1) as f32;
+            match bits {
+                1..=8 => {
+                    for sample in reader.samples::<i8>().take(total) {
+                        push(sample.map_err(error)? as f32 / scale, out);
+                    }
+                }
+                9..=16 => {
+                    for sample in reader.samples::<i16>().take(total) {
+                        push(sample.map_err(error)? as f32 / scale, out);
+                    }
+                }
+                _ => {
+                    for sample in reader.samples::<i32>().take(total) {
+                        push(sample.map_err(error)? as f32 / scale, out);
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stalled_prefetch_outputs_silence_and_reports_one_underrun() {
+        let path = std::env::temp_dir().join(format!(
+            "gooey_sample_stream_stall_{}.wav",
+            std::process::id()
+        ));
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 8_000,
+            bits_per_sample: 32,
+            sample_format: hound::SampleFormat::Float,
+        };
+        let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+        for _ in 0..16_000 {
+            writer.write_sample(0.5_f32).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let mut stream = DiskStream::open(&path, 8_000.0, false).unwrap();
+        // Simulate a disk that never delivers: stop the prefetch thread
+        stream.shared.running.store(false, Ordering::Relaxed);
+        stream.thread.take().unwrap().join().unwrap();
+
+        stream.start(1.0);
+        let head: Vec<f32> = (0..8_000).map(|_| stream.tick().l).collect();
+        assert!(head[100..7_990].iter().all(|s| (s - 0.5).abs() < 1e-6));
+        let starved: Vec<f32> = (0..1_000).map(|_| stream.tick().l).collect();
+        assert!(starved[10..].iter().all(|s| *s == 0.0));
+        assert!(stream.is_playing());
+        assert_eq!(stream.underruns(), 1);
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -4,14 +4,42 @@
 //! not allocate on the audio thread.  The small `voice_gain` helper is kept
 //! separate from decoding/playback deliberately: a future amplitude envelope
 //! can replace it without changing slot storage or voice scheduling.
+//!
+//! Slots can follow the tempo and be transposed independently of speed. Both
+//! are rendered ahead of time with an offline WSOLA stretch when the setting
+//! or the BPM changes (on the calling thread), so voices only ever read a
+//! ready-made buffer.
+//!
+//! A slot can also be chopped into slices (equal divisions, detected
+//! transients, or markers from a waveform UI). Slices are triggered like
+//! sub-pads, and each pattern step can name the slice it plays.
+//!
+//! A slot can instead hold a [`SlotStream`]: audio produced outside the rack,
+//! such as a long backing loop streamed from disk on native builds. Streamed
+//! slots are triggered and stopped like any other pad but don't stretch or
+//! slice.
 
-use std::sync::Arc;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use alloc::sync::Arc;
 
 use crate::engine::Sequencer;
 use crate::frame::StereoFrame;
+use crate::utils::wsola_stretch;
 
 pub const SAMPLER_SLOT_COUNT: usize = 16;
 pub const SAMPLER_VOICE_COUNT: usize = 32;
+/// Pitch-shift range of a slot, in semitones either way.
+pub const SAMPLER_MAX_PITCH_SEMITONES: f32 = 24.0;
+/// Most slices one slot can be chopped into.
+pub const SAMPLER_MAX_SLICES: usize = 64;
+
+/// Analysis hop for transient detection, in seconds.
+const TRANSIENT_HOP_SECONDS: f32 = 0.005;
+/// Shortest slice transient detection will produce, in seconds.
+const TRANSIENT_MIN_GAP_SECONDS: f32 = 0.05;
+/// Hops of history an onset's energy is compared against.
+const TRANSIENT_HISTORY_HOPS: usize = 8;
 
 #[derive(Clone, Debug)]
 pub struct SamplerBuffer {
@@ -59,6 +87,54 @@ impl SamplerBuffer {
         self.sample_rate
     }
 
+    /// Time-stretched copy, `factor` times as long at the same pitch.
+    fn stretched(&self, factor: f64) -> Self {
+        let samples = wsola_stretch(&self.samples, self.channels, self.sample_rate, factor);
+        let frames = samples.len() / self.channels;
+        if frames == 0 {
+            return self.clone();
+        }
+        Self {
+            samples: Arc::from(samples),
+            frames,
+            ..*self
+        }
+    }
+
+    /// Start frames of the hits in the buffer, always beginning with 0. A
+    /// hop counts as an onset when its energy jumps above the average of the
+    /// hops before it; `sensitivity` (0-1) lowers the jump needed from 8x to
+    /// 1.5x.
+    fn transients(&self, sensitivity: f32) -> Vec<usize> {
+        let hop = ((TRANSIENT_HOP_SECONDS * self.sample_rate) as usize).max(1);
+        let min_gap = (TRANSIENT_MIN_GAP_SECONDS * self.sample_rate) as usize;
+        let ratio = 8.0 - 6.5 * sensitivity.clamp(0.0, 1.0);
+        let energies: Vec<f32> = self
+            .samples
+            .chunks(hop * self.channels)
+            .map(|chunk| chunk.iter().map(|s| s * s).sum::<f32>() / chunk.len() as f32)
+            .collect();
+        // Ignore anything quieter than -60 dBFS
+        let floor = 1.0e-6;
+
+        let mut markers = vec![0];
+        for (i, &energy) in energies.iter().enumerate().skip(1) {
+            let history = &energies[i.saturating_sub(TRANSIENT_HISTORY_HOPS)..i];
+            let average = history.iter().sum::<f32>() / history.len() as f32;
+            let start = i * hop;
+            if energy > floor
+                && energy > average.max(floor) * ratio
+                && start - markers[markers.len() - 1] >= min_gap
+            {
+                markers.push(start);
+                if markers.len() == SAMPLER_MAX_SLICES {
+                    break;
+                }
+            }
+        }
+        markers
+    }
+
     #[inline]
     fn frame(&self, position: f64) -> StereoFrame {
         let position = position.clamp(0.0, (self.frames - 1) as f64);
@@ -78,11 +154,117 @@ impl SamplerBuffer {
     }
 }
 
+/// One slot's playback buffer, rendered away from its rack so the stretch
+/// can run on another thread than the audio (see [`SamplerRack::stretches`]).
+pub struct SlotStretch {
+    slot: usize,
+    source: SamplerBuffer,
+    factor: f64,
+    /// The rendered buffer, and after [`SamplerRack::install`] the one it
+    /// replaced
+    playback: Option<SamplerBuffer>,
+}
+
+impl SlotStretch {
+    /// Render the stretch. Allocates and takes time: call off the audio
+    /// thread.
+    pub fn render(&mut self) {
+        self.playback = Some(if (self.factor - 1.0).abs() < 1e-9 {
+            self.source.clone()
+        } else {
+            self.source.stretched(self.factor)
+        });
+    }
+}
+
+/// What [`SamplerRack::take_slot`] took out of a slot, freed when dropped.
+pub struct SlotContents {
+    _buffer: Option<SamplerSlot>,
+    _stream: Option<Box<dyn SlotStream>>,
+}
+
+/// A loaded pad: the PCM as given plus the version voices actually play.
+struct SamplerSlot {
+    source: SamplerBuffer,
+    /// `source` stretched for the current tempo and pitch; shares the source
+    /// data when neither applies
+    playback: SamplerBuffer,
+    /// Tempo the material was recorded at, when the slot follows the BPM
+    source_bpm: Option<f32>,
+    pitch_semitones: f32,
+    /// Stretch factor `playback` was rendered with
+    rendered_factor: f64,
+    /// Slice start frames in `source`, ascending from 0; empty when the slot
+    /// isn't sliced
+    slices: Vec<usize>,
+}
+
+impl SamplerSlot {
+    /// Playback speed relative to the source rate that gives the pitch shift.
+    fn rate(&self) -> f64 {
+        2.0_f64.powf(self.pitch_semitones as f64 / 12.0)
+    }
+
+    /// Playback position advance per output frame at `engine_rate`.
+    fn increment(&self, engine_rate: f32) -> f64 {
+        self.playback.sample_rate() as f64 / engine_rate as f64 * self.rate()
+    }
+
+    /// Stretch factor `playback` needs at `bpm`. Raising the pitch plays the
+    /// buffer faster, so it is pre-stretched by the same ratio to keep the
+    /// length.
+    fn factor(&self, bpm: f32) -> f64 {
+        let tempo = match self.source_bpm {
+            Some(source_bpm) if bpm > 0.0 => source_bpm as f64 / bpm as f64,
+            _ => 1.0,
+        };
+        tempo * self.rate()
+    }
+
+    /// Frame range of `slice` in `playback`, or the whole buffer for `None`.
+    fn region(&self, slice: Option<usize>) -> Option<(f64, f64)> {
+        let frames = self.playback.frames() as f64;
+        let Some(slice) = slice else {
+            return Some((0.0, frames));
+        };
+        let scale = frames / self.source.frames() as f64;
+        let start = *self.slices.get(slice)?;
+        let end = self
+            .slices
+            .get(slice + 1)
+            .copied()
+            .unwrap_or(self.source.frames());
+        Some((start as f64 * scale, end as f64 * scale))
+    }
+}
+
+/// Audio for a slot that isn't held in memory (see
+/// `sample_stream::DiskStream`). The stream plays one voice of its own;
+/// triggering again restarts it.
+pub trait SlotStream: Send {
+    /// Restart from the top at `velocity`.
+    fn start(&mut self, velocity: f32);
+    fn stop(&mut self);
+    fn is_playing(&self) -> bool;
+    /// Next frame at the engine rate; silence when stopped or starved.
+    /// Must not block or allocate.
+    fn tick(&mut self) -> StereoFrame;
+    /// Length, channel count and sample rate of the source.
+    fn format(&self) -> (usize, usize, f32);
+    /// Times playback ran dry and output silence instead.
+    fn underruns(&self) -> u64 {
+        0
+    }
+}
+
 #[derive(Clone)]
 struct SampleVoice {
     buffer: Option<SamplerBuffer>,
     slot: usize,
     position: f64,
+    /// Playback region in buffer frames (a slice, or the whole buffer)
+    start: f64,
+    end: f64,
     increment: f64,
     velocity: f32,
     age: u64,
@@ -94,6 +276,8 @@ impl Default for SampleVoice {
             buffer: None,
             slot: 0,
             position: 0.0,
+            start: 0.0,
+            end: 0.0,
             increment: 1.0,
             velocity: 0.0,
             age: 0,
@@ -110,13 +294,16 @@ impl SampleVoice {
         &mut self,
         slot: usize,
         buffer: SamplerBuffer,
-        engine_rate: f32,
+        (start, end): (f64, f64),
+        increment: f64,
         velocity: f32,
         age: u64,
     ) {
         self.slot = slot;
-        self.position = 0.0;
-        self.increment = buffer.sample_rate() as f64 / engine_rate as f64;
+        self.position = start;
+        self.start = start;
+        self.end = end;
+        self.increment = increment;
         self.velocity = velocity.clamp(0.0, 1.0);
         self.age = age;
         self.buffer = Some(buffer);
@@ -129,8 +316,8 @@ impl SampleVoice {
         let frame = buffer.frame(self.position);
         // Fixed click guard only. This is intentionally not an exposed envelope.
         let fade = 32.0_f64;
-        let end = buffer.frames() as f64;
-        let gain = (self.position / fade)
+        let end = self.end;
+        let gain = ((self.position - self.start) / fade)
             .min(((end - self.position) / fade).max(0.0))
             .min(1.0) as f32
             * self.velocity;
@@ -144,7 +331,8 @@ impl SampleVoice {
 
 pub struct SamplerRack {
     sample_rate: f32,
-    slots: [Option<SamplerBuffer>; SAMPLER_SLOT_COUNT],
+    slots: [Option<SamplerSlot>; SAMPLER_SLOT_COUNT],
+    streams: [Option<Box<dyn SlotStream>>; SAMPLER_SLOT_COUNT],
     voices: [SampleVoice; SAMPLER_VOICE_COUNT],
     next_age: u64,
     sequencer: Sequencer,
@@ -153,14 +341,17 @@ pub struct SamplerRack {
     pattern_running: bool,
     /// Absolute shared-transport beat at which a requested start lands.
     pending_start_beat: Option<f64>,
+    /// Slice each pattern step plays (`None` = the whole slot)
+    step_slices: [Option<u8>; SAMPLER_SLOT_COUNT],
 }
 
 impl SamplerRack {
     pub fn new(sample_rate: f32, bpm: f32, name: impl Into<String>) -> Self {
         Self {
             sample_rate,
-            slots: std::array::from_fn(|_| None),
-            voices: std::array::from_fn(|_| SampleVoice::default()),
+            slots: core::array::from_fn(|_| None),
+            streams: core::array::from_fn(|_| None),
+            voices: core::array::from_fn(|_| SampleVoice::default()),
             next_age: 0,
             sequencer: Sequencer::with_pattern(
                 bpm,
@@ -170,33 +361,312 @@ impl SamplerRack {
             ),
             pattern_running: false,
             pending_start_beat: None,
+            step_slices: [None; SAMPLER_SLOT_COUNT],
         }
     }
 
+    /// Load `buffer` into `slot`, stopping its voices. Tempo following and
+    /// pitch start out off for the new material.
     pub fn set_buffer(&mut self, slot: usize, buffer: SamplerBuffer) -> bool {
         let Some(target) = self.slots.get_mut(slot) else {
             return false;
         };
-        *target = Some(buffer);
+        *target = Some(SamplerSlot {
+            playback: buffer.clone(),
+            source: buffer,
+            source_bpm: None,
+            pitch_semitones: 0.0,
+            rendered_factor: 1.0,
+            slices: Vec::new(),
+        });
+        self.streams[slot] = None;
         self.stop_slot(slot);
         true
     }
 
-    pub fn clear_slot(&mut self, slot: usize) -> bool {
-        let Some(target) = self.slots.get_mut(slot) else {
+    /// Play `slot` from a stream instead of a buffer. Replacing or clearing
+    /// the slot drops the stream on the calling thread.
+    pub fn set_stream(&mut self, slot: usize, stream: Box<dyn SlotStream>) -> bool {
+        let Some(target) = self.streams.get_mut(slot) else {
             return false;
         };
-        *target = None;
+        *target = Some(stream);
+        self.slots[slot] = None;
         self.stop_slot(slot);
         true
     }
 
+    pub fn clear_slot(&mut self, slot: usize) -> bool {
+        self.take_slot(slot).is_some()
+    }
+
+    /// Empty `slot` like [`clear_slot`](Self::clear_slot), handing back what
+    /// it held so the caller frees it off the audio thread: dropping a stream
+    /// joins its prefetch thread. `None` for a slot out of range.
+    pub fn take_slot(&mut self, slot: usize) -> Option<SlotContents> {
+        let contents = SlotContents {
+            _buffer: self.slots.get_mut(slot)?.take(),
+            _stream: self.streams[slot].take(),
+        };
+        self.stop_slot(slot);
+        Some(contents)
+    }
+
+    /// Whether the slot holds a buffer or a stream.
+    pub fn is_loaded(&self, slot: usize) -> bool {
+        self.slot(slot).is_some() || self.is_streamed(slot)
+    }
+
+    pub fn is_streamed(&self, slot: usize) -> bool {
+        self.streams.get(slot).is_some_and(Option::is_some)
+    }
+
+    /// `(frames, channels, sample_rate)` of a loaded or streamed slot.
+    pub fn slot_format(&self, slot: usize) -> Option<(usize, usize, f32)> {
+        if let Some(stream) = self.streams.get(slot)?.as_ref() {
+            return Some(stream.format());
+        }
+        self.slot(slot)
+            .map(|buffer| (buffer.frames(), buffer.channels(), buffer.sample_rate()))
+    }
+
+    /// Under-runs a streamed slot has reported.
+    pub fn stream_underruns(&self, slot: usize) -> Option<u64> {
+        Some(self.streams.get(slot)?.as_ref()?.underruns())
+    }
+
+    /// The slot's PCM as loaded.
     pub fn slot(&self, slot: usize) -> Option<&SamplerBuffer> {
-        self.slots.get(slot)?.as_ref()
+        self.slots.get(slot)?.as_ref().map(|slot| &slot.source)
+    }
+
+    /// The stretched buffer the slot's voices play.
+    pub fn slot_playback(&self, slot: usize) -> Option<&SamplerBuffer> {
+        self.slots.get(slot)?.as_ref().map(|slot| &slot.playback)
+    }
+
+    /// Make a loaded slot follow the tempo: material recorded at `source_bpm`
+    /// is stretched to the rack's BPM. `None` plays it at its own speed.
+    /// Renders the stretch immediately; call off the audio thread.
+    pub fn set_slot_tempo(&mut self, slot: usize, source_bpm: Option<f32>) -> bool {
+        self.retempo_slot(slot, source_bpm) && self.render_slot(slot)
+    }
+
+    /// [`set_slot_tempo`](Self::set_slot_tempo) without the render: the slot
+    /// keeps playing its current stretch until one is installed (see
+    /// [`stretches`](Self::stretches)). Allocation-free.
+    pub fn retempo_slot(&mut self, slot: usize, source_bpm: Option<f32>) -> bool {
+        if source_bpm.is_some_and(|bpm| !bpm.is_finite() || bpm <= 0.0) {
+            return false;
+        }
+        let Some(target) = self.slots.get_mut(slot).and_then(Option::as_mut) else {
+            return false;
+        };
+        target.source_bpm = source_bpm;
+        true
+    }
+
+    pub fn slot_tempo(&self, slot: usize) -> Option<f32> {
+        self.slots.get(slot)?.as_ref()?.source_bpm
+    }
+
+    /// Transpose a loaded slot by `semitones` (clamped to
+    /// ±[`SAMPLER_MAX_PITCH_SEMITONES`]) without changing its length. Renders
+    /// immediately; call off the audio thread.
+    pub fn set_slot_pitch(&mut self, slot: usize, semitones: f32) -> bool {
+        self.retune_slot(slot, semitones) && self.render_slot(slot)
+    }
+
+    /// [`set_slot_pitch`](Self::set_slot_pitch) without the render, like
+    /// [`retempo_slot`](Self::retempo_slot).
+    pub fn retune_slot(&mut self, slot: usize, semitones: f32) -> bool {
+        if !semitones.is_finite() {
+            return false;
+        }
+        let Some(target) = self.slots.get_mut(slot).and_then(Option::as_mut) else {
+            return false;
+        };
+        target.pitch_semitones =
+            semitones.clamp(-SAMPLER_MAX_PITCH_SEMITONES, SAMPLER_MAX_PITCH_SEMITONES);
+        true
+    }
+
+    pub fn slot_pitch(&self, slot: usize) -> Option<f32> {
+        Some(self.slots.get(slot)?.as_ref()?.pitch_semitones)
+    }
+
+    /// Retime the pattern to `bpm`. Slots that follow the tempo keep their
+    /// current stretch until [`follow_tempo`](Self::follow_tempo).
+    pub fn set_bpm(&mut self, bpm: f32) {
+        self.sequencer.set_bpm(bpm);
+    }
+
+    /// Re-render every slot that tracks the tempo and was stretched for a
+    /// different BPM. Stretching allocates, so this belongs on the host
+    /// thread, not inside a render callback.
+    pub fn follow_tempo(&mut self) {
+        for slot in 0..SAMPLER_SLOT_COUNT {
+            self.render_slot(slot);
+        }
+    }
+
+    /// Hand `stretch` each slot whose playback was rendered for another
+    /// tempo or pitch, to be rendered with [`SlotStretch::render`] and put
+    /// back with [`install`](Self::install). Allocation-free, so the audio
+    /// thread can list the work while another thread does it.
+    pub fn stretches(&self, mut stretch: impl FnMut(SlotStretch)) {
+        let bpm = self.sequencer.bpm();
+        for (slot, target) in self.slots.iter().enumerate() {
+            let Some(target) = target else {
+                continue;
+            };
+            let factor = target.factor(bpm);
+            if factor != target.rendered_factor {
+                stretch(SlotStretch {
+                    slot,
+                    source: target.source.clone(),
+                    factor,
+                    playback: None,
+                });
+            }
+        }
+    }
+
+    /// Play the buffer `stretch` rendered, moving sounding voices onto it at
+    /// the same relative position. The buffer it replaces goes back into
+    /// `stretch`, so it is freed wherever `stretch` is dropped rather than
+    /// here. Does nothing, and returns false, for an unrendered stretch or
+    /// one the slot has moved on from (reloaded, or retimed again).
+    pub fn install(&mut self, stretch: &mut SlotStretch) -> bool {
+        let bpm = self.sequencer.bpm();
+        let Some(target) = self.slots.get_mut(stretch.slot).and_then(Option::as_mut) else {
+            return false;
+        };
+        let Some(playback) = stretch.playback.as_mut() else {
+            return false;
+        };
+        if !Arc::ptr_eq(&target.source.samples, &stretch.source.samples)
+            || target.factor(bpm) != stretch.factor
+        {
+            return false;
+        }
+        let old_frames = target.playback.frames() as f64;
+        core::mem::swap(&mut target.playback, playback);
+        target.rendered_factor = stretch.factor;
+        let scale = target.playback.frames() as f64 / old_frames;
+        let increment = target.increment(self.sample_rate);
+        for voice in &mut self.voices {
+            if voice.active() && voice.slot == stretch.slot {
+                voice.position *= scale;
+                voice.start *= scale;
+                voice.end *= scale;
+                voice.increment = increment;
+                voice.buffer = Some(target.playback.clone());
+            }
+        }
+        true
+    }
+
+    /// Re-render one slot if its stretch is out of date. False for an
+    /// unloaded slot.
+    fn render_slot(&mut self, slot: usize) -> bool {
+        if self.slots.get(slot).and_then(Option::as_ref).is_none() {
+            return false;
+        }
+        let mut pending = None;
+        self.stretches(|stretch| {
+            if stretch.slot == slot {
+                pending = Some(stretch);
+            }
+        });
+        if let Some(mut stretch) = pending {
+            stretch.render();
+            self.install(&mut stretch);
+        }
+        true
+    }
+
+    /// Chop a loaded slot into `count` equal slices; 0 removes the slicing.
+    pub fn slice_equal(&mut self, slot: usize, count: usize) -> bool {
+        if count > SAMPLER_MAX_SLICES {
+            return false;
+        }
+        let Some(target) = self.slots.get_mut(slot).and_then(Option::as_mut) else {
+            return false;
+        };
+        let frames = target.source.frames();
+        let mut markers: Vec<usize> = (0..count).map(|i| i * frames / count).collect();
+        markers.dedup();
+        target.slices = markers;
+        true
+    }
+
+    /// Slice a loaded slot at its detected transients (`sensitivity` 0-1,
+    /// higher finds quieter hits). Returns the slice count.
+    pub fn slice_transients(&mut self, slot: usize, sensitivity: f32) -> Option<usize> {
+        let target = self.slots.get_mut(slot)?.as_mut()?;
+        target.slices = target.source.transients(sensitivity);
+        Some(target.slices.len())
+    }
+
+    /// Slice a loaded slot at explicit start frames (e.g. moved in a waveform
+    /// UI). Markers are sorted and deduplicated, and the first slice always
+    /// starts at frame 0. Fails for markers past the end or too many slices.
+    pub fn set_slice_markers(&mut self, slot: usize, markers: &[usize]) -> bool {
+        let Some(target) = self.slots.get_mut(slot).and_then(Option::as_mut) else {
+            return false;
+        };
+        let frames = target.source.frames();
+        if markers.iter().any(|&marker| marker >= frames) {
+            return false;
+        }
+        let mut sorted = Vec::with_capacity(markers.len() + 1);
+        sorted.push(0);
+        sorted.extend_from_slice(markers);
+        sorted.sort_unstable();
+        sorted.dedup();
+        if sorted.len() > SAMPLER_MAX_SLICES {
+            return false;
+        }
+        target.slices = sorted;
+        true
+    }
+
+    /// Slice start frames (in the loaded PCM) of a slot; empty when unsliced.
+    pub fn slice_markers(&self, slot: usize) -> &[usize] {
+        self.slots
+            .get(slot)
+            .and_then(Option::as_ref)
+            .map_or(&[], |slot| &slot.slices)
     }
 
     pub fn trigger(&mut self, slot: usize, velocity: f32) -> bool {
-        let Some(buffer) = self.slot(slot).cloned() else {
+        self.trigger_slice(slot, None, velocity)
+    }
+
+    /// Play one slice of a slot, or the whole slot for `None`. Returns false
+    /// for an unloaded slot or a slice it doesn't have.
+    pub fn trigger_slice(&mut self, slot: usize, slice: Option<usize>, velocity: f32) -> bool {
+        if let Some(stream) = self.streams.get_mut(slot).and_then(Option::as_mut) {
+            if slice.is_some() {
+                return false;
+            }
+            stream.start(velocity);
+            return true;
+        }
+        let Some((buffer, region, increment)) = self
+            .slots
+            .get(slot)
+            .and_then(Option::as_ref)
+            .and_then(|slot| {
+                let region = slot.region(slice)?;
+                Some((
+                    slot.playback.clone(),
+                    region,
+                    slot.increment(self.sample_rate),
+                ))
+            })
+        else {
             return false;
         };
         let voice_index = self
@@ -212,14 +682,19 @@ impl SamplerRack {
                     .unwrap_or(0)
             });
         self.next_age = self.next_age.wrapping_add(1);
-        self.voices[voice_index].start(slot, buffer, self.sample_rate, velocity, self.next_age);
+        self.voices[voice_index].start(slot, buffer, region, increment, velocity, self.next_age);
         true
     }
 
     pub fn tick(&mut self) -> StereoFrame {
-        self.voices
+        let voices = self
+            .voices
+            .iter_mut()
+            .fold(StereoFrame::default(), |out, voice| out + voice.tick());
+        self.streams
             .iter_mut()
-            .fold(StereoFrame::default(), |out, voice| out + voice.tick())
+            .flatten()
+            .fold(voices, |out, stream| out + stream.tick())
     }
 
     pub fn set_step(&mut self, step: usize, enabled: bool, slot: usize, velocity: f32) -> bool {
@@ -232,6 +707,20 @@ impl SamplerRack {
         true
     }
 
+    /// Make a pattern step play one slice of its slot, or the whole slot for
+    /// `None`.
+    pub fn set_step_slice(&mut self, step: usize, slice: Option<usize>) -> bool {
+        if step >= SAMPLER_SLOT_COUNT || slice.is_some_and(|slice| slice >= SAMPLER_MAX_SLICES) {
+            return false;
+        }
+        self.step_slices[step] = slice.map(|slice| slice as u8);
+        true
+    }
+
+    pub fn step_slice(&self, step: usize) -> Option<usize> {
+        self.step_slices.get(step)?.map(usize::from)
+    }
+
     pub fn step(&self, step: usize) -> Option<(bool, usize, f32)> {
         (step < SAMPLER_SLOT_COUNT).then(|| {
             (
@@ -242,13 +731,21 @@ impl SamplerRack {
         })
     }
 
-    pub fn tick_sequencer(&mut self) -> Option<(usize, f32)> {
+    /// Advance the pattern one sample. Returns the `(slot, slice, velocity)`
+    /// of a step that fires.
+    pub fn tick_sequencer(&mut self) -> Option<(usize, Option<usize>, f32)> {
         if !self.pattern_running {
             return None;
         }
-        self.sequencer
+        let (slot, velocity) = self
+            .sequencer
             .tick_with_settings()
-            .map(|trigger| (trigger.note.unwrap_or(0) as usize, trigger.velocity))
+            .map(|trigger| (trigger.note.unwrap_or(0) as usize, trigger.velocity))?;
+        Some((
+            slot,
+            self.step_slice(self.sequencer.current_step()),
+            velocity,
+        ))
     }
     pub fn sequencer_mut(&mut self) -> &mut Sequencer {
         &mut self.sequencer
@@ -269,7 +766,9 @@ impl SamplerRack {
 
     /// Called from the render thread before the sequencer is ticked.
     pub fn activate_start_if_due(&mut self, transport_beat: f64) {
-        let Some(target) = self.pending_start_beat else { return };
+        let Some(target) = self.pending_start_beat else {
+            return;
+        };
         if transport_beat + 1.0e-8 < target {
             return;
         }
@@ -312,10 +811,14 @@ impl SamplerRack {
         self.stop_all();
     }
 
-    fn stop_all(&mut self) {
+    /// Cut every sounding voice and stream in the rack
+    pub fn stop_all(&mut self) {
         for voice in &mut self.voices {
             voice.buffer = None;
         }
+        for stream in self.streams.iter_mut().flatten() {
+            stream.stop();
+        }
     }
 
     fn stop_slot(&mut self, slot: usize) {
@@ -324,6 +827,9 @@ impl SamplerRack {
                 voice.buffer = None;
             }
         }
+        if let Some(stream) = self.streams[slot].as_mut() {
+            stream.stop();
+        }
     }
 }
 
@@ -353,4 +859,59 @@ mod tests {
             assert!(rack.tick().l.is_finite());
         }
     }
+
+    #[test]
+    fn tempo_change_moves_sounding_voices_to_the_new_render() {
+        let mut rack = SamplerRack::new(44_100.0, 120.0, "test");
+        rack.set_buffer(
+            0,
+            SamplerBuffer::from_interleaved(&vec![0.5; 44_100], 44_100, 1, 44_100.0).unwrap(),
+        );
+        assert!(rack.set_slot_tempo(0, Some(120.0)));
+        assert!(rack.trigger(0, 1.0));
+        for _ in 0..11_025 {
+            rack.tick();
+        }
+
+        rack.set_bpm(60.0);
+        assert_eq!(rack.slot_playback(0).unwrap().frames(), 44_100);
+        rack.follow_tempo();
+        assert_eq!(rack.slot_playback(0).unwrap().frames(), 88_200);
+        assert_eq!(rack.slot(0).unwrap().frames(), 44_100);
+        // A quarter of the way in stays a quarter of the way in
+        let voice = rack.voices.iter().find(|voice| voice.active()).unwrap();
+        assert!((voice.position - 22_050.0).abs() < 1.0);
+        assert!(Arc::ptr_eq(
+            &voice.buffer.as_ref().unwrap().samples,
+            &rack.slot_playback(0).unwrap().samples
+        ));
+    }
+
+    #[test]
+    fn stretch_is_installed_only_while_current() {
+        let mut rack = SamplerRack::new(44_100.0, 120.0, "test");
+        rack.set_buffer(
+            0,
+            SamplerBuffer::from_interleaved(&vec![0.5; 4_410], 4_410, 1, 44_100.0).unwrap(),
+        );
+        assert!(rack.retempo_slot(0, Some(120.0)));
+        rack.set_bpm(60.0);
+        let mut pending = Vec::new();
+        rack.stretches(|stretch| pending.push(stretch));
+        assert_eq!(pending.len(), 1);
+        let mut stretch = pending.pop().unwrap();
+        stretch.render();
+
+        // Retimed again while the stretch rendered: it's stale
+        rack.set_bpm(90.0);
+        assert!(!rack.install(&mut stretch));
+        assert_eq!(rack.slot_playback(0).unwrap().frames(), 4_410);
+
+        rack.set_bpm(60.0);
+        assert!(rack.install(&mut stretch));
+        assert_eq!(rack.slot_playback(0).unwrap().frames(), 8_820);
+        // The replaced buffer came back out to be freed by the caller
+        assert_eq!(stretch.playback.as_ref().unwrap().frames(), 4_410);
+        rack.stretches(|_| panic!("slot is up to date"));
+    }
 }
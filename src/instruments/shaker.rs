@@ -0,0 +1,521 @@
+use crate::error::GooeyError;
+use crate::filters::BiquadHighpass;
+use crate::partial_config;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::{
+    tuning_to_multiplier, Blendable, RetriggerFade, SmoothedParam, DEFAULT_SMOOTH_TIME_MS,
+};
+
+/// Normalization ranges for shaker parameters.
+/// All external-facing parameters use 0.0-1.0 normalized values.
+pub(crate) mod ranges {
+    #[cfg(not(feature = "std"))]
+    use crate::prelude::Float;
+
+    /// Density: 0-1 maps exponentially to 200-5000 grains per second
+    pub const DENSITY_MIN: f32 = 200.0;
+    pub const DENSITY_MAX: f32 = 5000.0;
+
+    /// Color: 0-1 maps exponentially to a 1-10 kHz highpass
+    pub const COLOR_MIN: f32 = 1000.0;
+    pub const COLOR_MAX: f32 = 10000.0;
+
+    /// Attack: 0-1 maps exponentially to a 0.5-50 ms swell
+    pub const ATTACK_MIN_MS: f32 = 0.5;
+    pub const ATTACK_MAX_MS: f32 = 50.0;
+
+    /// Decay: 0-1 maps exponentially to 20-600 ms (to -60 dB)
+    pub const DECAY_MIN_MS: f32 = 20.0;
+    pub const DECAY_MAX_MS: f32 = 600.0;
+
+    #[inline]
+    pub fn exp_denormalize(normalized: f32, min: f32, max: f32) -> f32 {
+        min * (max / min).powf(normalized.clamp(0.0, 1.0))
+    }
+}
+
+/// Decay of a single grain (to -60 dB): one bead hitting the shell
+const GRAIN_MS: f32 = 2.0;
+
+/// Q of the highpass; a little resonance gives the shell's ring
+const HIGHPASS_Q: f32 = 1.2;
+
+/// At full accent, how far a hard hit's grain density and color move up
+/// (and a soft one's down), in normalized units per unit of velocity
+/// away from the middle
+const ACCENT_COLOR: f32 = 0.3;
+const ACCENT_DENSITY: f32 = 0.5;
+
+/// Brings the grain stream up to about the level of the other drums
+const OUTPUT_GAIN: f32 = 1.2;
+
+/// Level below which a decaying hit is considered finished (-80 dB)
+const SILENCE: f32 = 1e-4;
+
+/// ln(1000): time constants that reach -60 dB after the decay time
+const LN_1000: f32 = 6.907_755;
+
+/// Static configuration for shaker presets.
+/// All parameters use normalized 0.0-1.0 values.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShakerConfig {
+    pub density: f32, // Grains per second (0-1 -> 200-5000 exp)
+    pub color: f32,   // Highpass cutoff (0-1 -> 1-10 kHz exp)
+    pub attack: f32,  // Swell into the hit (0-1 -> 0.5-50 ms exp)
+    pub decay: f32,   // Hit decay (0-1 -> 20-600 ms exp)
+    pub accent: f32,  // How far velocity moves density and color (0-1)
+    pub volume: f32,  // Output volume (0-1)
+}
+
+impl ShakerConfig {
+    pub fn new(
+        density: f32,
+        color: f32,
+        attack: f32,
+        decay: f32,
+        accent: f32,
+        volume: f32,
+    ) -> Self {
+        Self {
+            density: density.clamp(0.0, 1.0),
+            color: color.clamp(0.0, 1.0),
+            attack: attack.clamp(0.0, 1.0),
+            decay: decay.clamp(0.0, 1.0),
+            accent: accent.clamp(0.0, 1.0),
+            volume: volume.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Tight preset: a short, bright maraca stroke
+    pub fn tight() -> Self {
+        Self::new(0.6, 0.7, 0.1, 0.2, 0.5, 0.8)
+    }
+
+    /// Swish preset: a slower shake that swells in, for house sixteenths
+    pub fn swish() -> Self {
+        Self::new(0.55, 0.6, 0.6, 0.45, 0.6, 0.8)
+    }
+
+    /// Cabasa preset: dense beads on metal, very bright
+    pub fn cabasa() -> Self {
+        Self::new(0.9, 0.85, 0.25, 0.3, 0.4, 0.75)
+    }
+
+    /// Egg preset: sparse, darker seeds in a small shell
+    pub fn egg() -> Self {
+        Self::new(0.3, 0.4, 0.3, 0.3, 0.7, 0.85)
+    }
+}
+
+impl Default for ShakerConfig {
+    fn default() -> Self {
+        Self::tight()
+    }
+}
+
+partial_config!(
+    /// [`ShakerConfig`] with every field optional: only the fields set are applied
+    pub struct PartialShakerConfig for ShakerConfig {
+        density: f32,
+        color: f32,
+        attack: f32,
+        decay: f32,
+        accent: f32,
+        volume: f32,
+    }
+);
+
+impl Blendable for ShakerConfig {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let inv_t = 1.0 - t;
+
+        Self {
+            density: self.density * inv_t + other.density * t,
+            color: self.color * inv_t + other.color * t,
+            attack: self.attack * inv_t + other.attack * t,
+            decay: self.decay * inv_t + other.decay * t,
+            accent: self.accent * inv_t + other.accent * t,
+            volume: self.volume * inv_t + other.volume * t,
+        }
+    }
+}
+
+/// Smoothed parameters for real-time control
+pub struct ShakerParams {
+    pub density: SmoothedParam,
+    pub color: SmoothedParam,
+    pub attack: SmoothedParam,
+    pub decay: SmoothedParam,
+    pub accent: SmoothedParam,
+    pub volume: SmoothedParam,
+    // Per-instrument tuning (0=−12 semitones, 0.5=neutral, 1=+12 semitones);
+    // moves the highpass, as the shaker has no pitch
+    pub tuning: SmoothedParam,
+}
+
+impl ShakerParams {
+    pub fn from_config(config: &ShakerConfig, sample_rate: f32) -> Self {
+        let smoothed =
+            |value: f32| SmoothedParam::new(value, 0.0, 1.0, sample_rate, DEFAULT_SMOOTH_TIME_MS);
+        Self {
+            density: smoothed(config.density),
+            color: smoothed(config.color),
+            attack: smoothed(config.attack),
+            decay: smoothed(config.decay),
+            accent: smoothed(config.accent),
+            volume: smoothed(config.volume),
+            tuning: smoothed(0.5),
+        }
+    }
+
+    #[inline]
+    pub fn tick(&mut self) {
+        self.density.tick();
+        self.color.tick();
+        self.attack.tick();
+        self.decay.tick();
+        self.accent.tick();
+        self.volume.tick();
+        self.tuning.tick();
+    }
+
+    /// Snap all smoothed parameters to their targets instantly.
+    pub fn snap_all(&mut self) {
+        self.density.snap();
+        self.color.snap();
+        self.attack.snap();
+        self.decay.snap();
+        self.accent.snap();
+        self.volume.snap();
+        self.tuning.snap();
+    }
+
+    pub fn to_config(&self) -> ShakerConfig {
+        ShakerConfig {
+            density: self.density.get(),
+            color: self.color.get(),
+            attack: self.attack.get(),
+            decay: self.decay.get(),
+            accent: self.accent.get(),
+            volume: self.volume.get(),
+        }
+    }
+
+    #[inline]
+    pub fn attack_ms(&self) -> f32 {
+        ranges::exp_denormalize(
+            self.attack.get(),
+            ranges::ATTACK_MIN_MS,
+            ranges::ATTACK_MAX_MS,
+        )
+    }
+
+    #[inline]
+    pub fn decay_ms(&self) -> f32 {
+        ranges::exp_denormalize(self.decay.get(), ranges::DECAY_MIN_MS, ranges::DECAY_MAX_MS)
+    }
+}
+
+/// Shaker: a stream of very short noise grains, one per bead striking the
+/// shell, scheduled at random around the density, then shaped by an
+/// attack/decay envelope and highpassed. Accent lets velocity move the hit
+/// as well as scale it: hard strokes get denser and brighter, ghost strokes
+/// sparser and darker.
+pub struct Shaker {
+    pub sample_rate: f32,
+    pub params: ShakerParams,
+
+    highpass: BiquadHighpass,
+    rng_state: u64,
+    // Envelope of the overlapping grains, and samples until the next one
+    grain_level: f32,
+    next_grain: f32,
+    // Samples since the last trigger (counted, not summed as seconds)
+    elapsed: u64,
+
+    is_active: bool,
+    /// Releases the previous hit when retriggered mid-decay
+    retrigger_fade: RetriggerFade,
+    current_velocity: f32,
+}
+
+impl Shaker {
+    pub fn new(sample_rate: f32) -> Self {
+        Self::with_config(sample_rate, ShakerConfig::default())
+    }
+
+    pub fn with_config(sample_rate: f32, config: ShakerConfig) -> Self {
+        Self {
+            sample_rate,
+            params: ShakerParams::from_config(&config, sample_rate),
+            highpass: BiquadHighpass::new(sample_rate),
+            rng_state: 0x2545_f491_4f6c_dd1d,
+            grain_level: 0.0,
+            next_grain: 0.0,
+            elapsed: 0,
+            is_active: false,
+            retrigger_fade: RetriggerFade::new(sample_rate),
+            current_velocity: 1.0,
+        }
+    }
+
+    /// Change only the fields set in `partial`; the rest keep their current
+    /// values.
+    pub fn apply_partial(&mut self, partial: &PartialShakerConfig) {
+        self.set_config(self.config().with_partial(partial));
+    }
+
+    /// Get current config snapshot (reads current smoothed values)
+    pub fn config(&self) -> ShakerConfig {
+        self.params.to_config()
+    }
+
+    pub fn set_config(&mut self, config: ShakerConfig) {
+        self.params.density.set_target(config.density);
+        self.params.color.set_target(config.color);
+        self.params.attack.set_target(config.attack);
+        self.params.decay.set_target(config.decay);
+        self.params.accent.set_target(config.accent);
+        self.params.volume.set_target(config.volume);
+    }
+
+    pub fn snap_params(&mut self) {
+        self.params.snap_all();
+    }
+
+    // Individual parameter setters (normalized 0-1)
+
+    pub fn set_density(&mut self, value: f32) {
+        self.params.density.set_target(value.clamp(0.0, 1.0));
+    }
+
+    pub fn set_color(&mut self, value: f32) {
+        self.params.color.set_target(value.clamp(0.0, 1.0));
+    }
+
+    pub fn set_attack(&mut self, value: f32) {
+        self.params.attack.set_target(value.clamp(0.0, 1.0));
+    }
+
+    pub fn set_decay(&mut self, value: f32) {
+        self.params.decay.set_target(value.clamp(0.0, 1.0));
+    }
+
+    pub fn set_accent(&mut self, value: f32) {
+        self.params.accent.set_target(value.clamp(0.0, 1.0));
+    }
+
+    pub fn set_volume(&mut self, value: f32) {
+        self.params.volume.set_target(value.clamp(0.0, 1.0));
+    }
+
+    /// Set tuning offset (smoothed, 0-1: 0=−12 semitones, 0.5=neutral, 1=+12 semitones)
+    pub fn set_tuning(&mut self, value: f32) {
+        self.params.tuning.set_target(value.clamp(0.0, 1.0));
+    }
+
+    pub fn trigger(&mut self, time: f64) {
+        self.trigger_with_velocity(time, 1.0);
+    }
+
+    pub fn trigger_with_velocity(&mut self, _time: f64, velocity: f32) {
+        self.retrigger_fade.retrigger();
+        self.is_active = true;
+        self.current_velocity = velocity.clamp(0.0, 1.0);
+        // Retriggers keep the grains already sounding and the filter state,
+        // the way a shaker does not go quiet between strokes
+        self.next_grain = 0.0;
+        self.elapsed = 0;
+    }
+
+    pub fn tick(&mut self, current_time: f64) -> f32 {
+        let output = self.render(current_time);
+        self.retrigger_fade.process(output)
+    }
+
+    fn render(&mut self, _current_time: f64) -> f32 {
+        self.params.tick();
+
+        if !self.is_active {
+            return 0.0;
+        }
+
+        let t = self.elapsed as f32 / self.sample_rate;
+        self.elapsed += 1;
+
+        let attack_ms = self.params.attack_ms();
+        let t_ms = t * 1000.0;
+        let envelope = if t_ms < attack_ms {
+            t_ms / attack_ms
+        } else {
+            (-(t_ms - attack_ms) * LN_1000 / self.params.decay_ms()).exp()
+        };
+        if envelope < SILENCE && t_ms >= attack_ms {
+            self.is_active = false;
+            self.grain_level = 0.0;
+            return 0.0;
+        }
+
+        let (density, color_hz) = self.accented();
+
+        // Grains land at random, on average `density` per second, each at a
+        // random strength
+        self.grain_level *= (-LN_1000 * 1000.0 / (GRAIN_MS * self.sample_rate)).exp();
+        self.next_grain -= 1.0;
+        if self.next_grain <= 0.0 {
+            let strength = 0.3 + 0.7 * self.random();
+            self.grain_level = self.grain_level.max(strength);
+            self.next_grain += 2.0 * self.random() * self.sample_rate / density;
+        }
+
+        let noise = self.random() * 2.0 - 1.0;
+        self.highpass.set_params(color_hz, HIGHPASS_Q);
+        let out = self.highpass.process(noise * self.grain_level);
+
+        out * envelope * OUTPUT_GAIN * self.current_velocity * self.params.volume.get()
+    }
+
+    /// Grains per second and highpass cutoff (Hz) for the sounding hit.
+    /// Accent pushes both away from the set values by how far the hit's
+    /// velocity is from the middle.
+    fn accented(&self) -> (f32, f32) {
+        let accent = self.params.accent.get() * (self.current_velocity - 0.5);
+        let density = ranges::exp_denormalize(
+            self.params.density.get() + accent * ACCENT_DENSITY,
+            ranges::DENSITY_MIN,
+            ranges::DENSITY_MAX,
+        );
+        let color_hz = ranges::exp_denormalize(
+            self.params.color.get() + accent * ACCENT_COLOR,
+            ranges::COLOR_MIN,
+            ranges::COLOR_MAX,
+        ) * tuning_to_multiplier(self.params.tuning.get());
+        (density, color_hz)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    /// Uniform in 0..1 (xorshift64*)
+    fn random(&mut self) -> f32 {
+        let mut x = self.rng_state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.rng_state = x;
+        (x.wrapping_mul(0x2545F4914F6CDD1D) >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+impl crate::engine::Instrument for Shaker {
+    fn trigger_with_velocity(&mut self, time: f64, velocity: f32) {
+        Shaker::trigger_with_velocity(self, time, velocity);
+    }
+
+    fn tick(&mut self, current_time: f64) -> f32 {
+        self.tick(current_time)
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_active()
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        // xorshift64* sticks at zero
+        self.rng_state = seed | 1;
+    }
+
+    fn as_modulatable(&mut self) -> Option<&mut dyn crate::engine::Modulatable> {
+        Some(self)
+    }
+}
+
+impl crate::engine::Modulatable for Shaker {
+    fn modulatable_parameters(&self) -> Vec<&'static str> {
+        vec![
+            "accent", "attack", "color", "decay", "density", "tuning", "volume",
+        ]
+    }
+
+    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), GooeyError> {
+        let param = match parameter {
+            "accent" => &mut self.params.accent,
+            "attack" => &mut self.params.attack,
+            "color" => &mut self.params.color,
+            "decay" => &mut self.params.decay,
+            "density" => &mut self.params.density,
+            "tuning" => &mut self.params.tuning,
+            "volume" => &mut self.params.volume,
+            _ => return Err(GooeyError::UnknownParameter(parameter.to_string())),
+        };
+        param.set_bipolar(value);
+        Ok(())
+    }
+
+    fn parameter_range(&self, parameter: &str) -> Option<(f32, f32)> {
+        match parameter {
+            "accent" => Some(self.params.accent.range()),
+            "attack" => Some(self.params.attack.range()),
+            "color" => Some(self.params.color.range()),
+            "decay" => Some(self.params.decay.range()),
+            "density" => Some(self.params.density.range()),
+            "tuning" => Some(self.params.tuning.range()),
+            "volume" => Some(self.params.volume.range()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 44_100.0;
+
+    fn render_hit(shaker: &mut Shaker, velocity: f32, frames: usize) -> Vec<f32> {
+        shaker.trigger_with_velocity(0.0, velocity);
+        (0..frames)
+            .map(|i| shaker.tick(i as f64 / SR as f64))
+            .collect()
+    }
+
+    fn energy(samples: &[f32]) -> f32 {
+        samples.iter().map(|s| s * s).sum()
+    }
+
+    #[test]
+    fn hit_decays_and_goes_inactive() {
+        let mut shaker = Shaker::new(SR);
+        let out = render_hit(&mut shaker, 1.0, SR as usize);
+        assert!(out.iter().all(|s| s.is_finite() && s.abs() <= 1.0));
+        assert!(energy(&out[..2048]) > 1.0);
+        assert!(!shaker.is_active());
+    }
+
+    #[test]
+    fn attack_swells_into_the_hit() {
+        let mut snappy = Shaker::with_config(SR, ShakerConfig::new(0.6, 0.7, 0.0, 0.4, 0.0, 0.8));
+        let mut swell = Shaker::with_config(SR, ShakerConfig::new(0.6, 0.7, 1.0, 0.4, 0.0, 0.8));
+        let snappy = render_hit(&mut snappy, 1.0, 4096);
+        let swell = render_hit(&mut swell, 1.0, 4096);
+        // The first 5 ms of a 50 ms swell are a tenth of the way up
+        assert!(energy(&swell[..220]) < energy(&snappy[..220]) * 0.1);
+    }
+
+    #[test]
+    fn accent_makes_hard_hits_denser_and_brighter() {
+        let stroke = |accent, velocity| {
+            let config = ShakerConfig::new(0.6, 0.3, 0.0, 0.6, accent, 0.8);
+            let mut shaker = Shaker::with_config(SR, config);
+            shaker.trigger_with_velocity(0.0, velocity);
+            shaker.accented()
+        };
+        let (hard, soft) = (stroke(1.0, 1.0), stroke(1.0, 0.2));
+        assert!(hard.0 > soft.0 * 1.5 && hard.1 > soft.1 * 1.5);
+        // Without accent velocity only scales the level
+        assert_eq!(stroke(0.0, 1.0), stroke(0.0, 0.2));
+    }
+}
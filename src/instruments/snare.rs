@@ -1,10 +1,18 @@
+use crate::effects::saturator::SaturatorModel;
 use crate::effects::waveshaper::Waveshaper;
 use crate::envelope::{ADSRConfig, Envelope, EnvelopeCurve};
+use crate::error::GooeyError;
 use crate::filters::StateVariableFilter;
+use crate::gen::noise_color::NoiseColor;
 use crate::gen::oscillator::Oscillator;
 use crate::gen::waveform::Waveform;
 use crate::instruments::fm_snap::PhaseModulator;
-use crate::utils::{tuning_to_multiplier, Blendable, SmoothedParam, DEFAULT_SMOOTH_TIME_MS};
+use crate::partial_config;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::{
+    tuning_to_multiplier, Blendable, RetriggerFade, SmoothedParam, DEFAULT_SMOOTH_TIME_MS,
+};
 
 /// Normalization ranges for snare drum parameters
 /// All external-facing parameters use 0.0-1.0 normalized values
@@ -91,6 +99,10 @@ pub struct SnareConfig {
     pub overdrive_amount: f32, // Overdrive/saturation (0.0-1.0, 0.0 = bypass)
     pub amp_decay: f32,        // Master amplitude decay (0-1 → 0-4.0s)
     pub amp_decay_curve: f32,  // Decay curve shape (0-1 → 0.1-10.0)
+
+    // Noise character
+    pub noise_color: f32,   // Noise color (0 = white, 0.5 = pink, 1 = brown)
+    pub crack_velvet: bool, // Crack from velvet noise instead of white
 }
 
 impl SnareConfig {
@@ -127,6 +139,8 @@ impl SnareConfig {
             overdrive_amount: 0.0,
             amp_decay: 0.125,      // ~0.5s
             amp_decay_curve: 0.02, // ~0.3 (steep-then-long)
+            noise_color: 0.0,
+            crack_velvet: false,
         }
     }
 
@@ -173,6 +187,8 @@ impl SnareConfig {
             overdrive_amount: overdrive_amount.clamp(0.0, 1.0),
             amp_decay: amp_decay.clamp(0.0, 1.0),
             amp_decay_curve: amp_decay_curve.clamp(0.0, 1.0),
+            noise_color: 0.0,
+            crack_velvet: false,
         }
     }
 
@@ -351,6 +367,33 @@ impl SnareConfig {
     }
 }
 
+partial_config!(
+    /// [`SnareConfig`] with every field optional: only the fields set are applied
+    pub struct PartialSnareConfig for SnareConfig {
+        frequency: f32,
+        tonal_amount: f32,
+        noise_amount: f32,
+        crack_amount: f32,
+        decay: f32,
+        pitch_drop: f32,
+        volume: f32,
+        tonal_decay: f32,
+        tonal_decay_curve: f32,
+        noise_decay: f32,
+        noise_tail_decay: f32,
+        filter_cutoff: f32,
+        filter_resonance: f32,
+        filter_type: u8,
+        xfade: f32,
+        phase_mod_amount: f32,
+        overdrive_amount: f32,
+        amp_decay: f32,
+        amp_decay_curve: f32,
+        noise_color: f32,
+        crack_velvet: bool,
+    }
+);
+
 impl Blendable for SnareConfig {
     fn lerp(&self, other: &Self, t: f32) -> Self {
         let t = t.clamp(0.0, 1.0);
@@ -379,6 +422,12 @@ impl Blendable for SnareConfig {
             overdrive_amount: self.overdrive_amount * inv_t + other.overdrive_amount * t,
             amp_decay: self.amp_decay * inv_t + other.amp_decay * t,
             amp_decay_curve: self.amp_decay_curve * inv_t + other.amp_decay_curve * t,
+            noise_color: self.noise_color * inv_t + other.noise_color * t,
+            crack_velvet: if t < 0.5 {
+                self.crack_velvet
+            } else {
+                other.crack_velvet
+            },
         }
     }
 }
@@ -412,6 +461,9 @@ pub struct SnareParams {
     pub amp_decay_curve: SmoothedParam, // Decay curve shape (0-1 → 0.1-10.0)
     // Per-instrument tuning (0=−12 semitones, 0.5=neutral, 1=+12 semitones)
     pub tuning: SmoothedParam,
+
+    pub noise_color: SmoothedParam, // Noise color (0 = white, 0.5 = pink, 1 = brown)
+    pub crack_velvet: bool,         // Velvet-noise crack (not smoothed)
 }
 
 impl SnareParams {
@@ -537,6 +589,14 @@ impl SnareParams {
                 DEFAULT_SMOOTH_TIME_MS,
             ),
             tuning: SmoothedParam::new(0.5, 0.0, 1.0, sample_rate, DEFAULT_SMOOTH_TIME_MS),
+            noise_color: SmoothedParam::new(
+                config.noise_color,
+                0.0,
+                1.0,
+                sample_rate,
+                DEFAULT_SMOOTH_TIME_MS,
+            ),
+            crack_velvet: config.crack_velvet,
         }
     }
 
@@ -656,6 +716,7 @@ impl SnareParams {
         self.amp_decay.tick();
         self.amp_decay_curve.tick();
         self.tuning.tick();
+        self.noise_color.tick();
         !self.is_settled()
     }
 
@@ -680,6 +741,7 @@ impl SnareParams {
             && self.amp_decay.is_settled()
             && self.amp_decay_curve.is_settled()
             && self.tuning.is_settled()
+            && self.noise_color.is_settled()
     }
 
     /// Snap all smoothed parameters to their targets instantly.
@@ -703,6 +765,34 @@ impl SnareParams {
         self.amp_decay.snap();
         self.amp_decay_curve.snap();
         self.tuning.snap();
+        self.noise_color.snap();
+    }
+
+    /// Get a snapshot of current normalized values as a SnareConfig
+    pub fn to_config(&self) -> SnareConfig {
+        SnareConfig {
+            frequency: self.frequency.get(),
+            tonal_amount: self.tonal.get(),
+            noise_amount: self.noise.get(),
+            crack_amount: self.brightness.get(),
+            decay: self.decay.get(),
+            pitch_drop: self.pitch_drop.get(),
+            volume: self.volume.get(),
+            tonal_decay: self.tonal_decay.get(),
+            tonal_decay_curve: self.tonal_decay_curve.get(),
+            noise_decay: self.noise_decay.get(),
+            noise_tail_decay: self.noise_tail_decay.get(),
+            filter_cutoff: self.filter_cutoff.get(),
+            filter_resonance: self.filter_resonance.get(),
+            filter_type: self.filter_type,
+            xfade: self.xfade.get(),
+            phase_mod_amount: self.phase_mod_amount.get(),
+            overdrive_amount: self.overdrive.get(),
+            amp_decay: self.amp_decay.get(),
+            amp_decay_curve: self.amp_decay_curve.get(),
+            noise_color: self.noise_color.get(),
+            crack_velvet: self.crack_velvet,
+        }
     }
 }
 
@@ -724,6 +814,10 @@ pub struct SnareDrum {
     pub pitch_start_multiplier: f32,
 
     pub is_active: bool,
+    /// Releases the previous hit when retriggered mid-decay
+    retrigger_fade: RetriggerFade,
+    /// Master tuning multiplier (A4 reference and transpose)
+    pitch_ratio: f32,
 
     // Velocity-responsive state
     /// Current trigger velocity (0.0-1.0), set on trigger
@@ -744,6 +838,9 @@ pub struct SnareDrum {
     /// Phase modulator for DS-style transient
     phase_modulator: PhaseModulator,
 
+    /// Turns the white noise pink or brown before the SVF
+    noise_color: NoiseColor,
+
     /// Noise tail envelope (separate from main noise)
     noise_tail_envelope: Envelope,
 
@@ -780,6 +877,8 @@ impl SnareDrum {
             base_frequency: base_freq,
             pitch_start_multiplier: 1.0 + config.pitch_drop * 1.5, // Start 1-2.5x higher
             is_active: false,
+            retrigger_fade: RetriggerFade::new(sample_rate),
+            pitch_ratio: 1.0,
 
             // Initialize velocity state (matches default trigger velocity)
             current_velocity: 0.5,
@@ -795,6 +894,7 @@ impl SnareDrum {
                 config.filter_resonance_value(),
             ),
             phase_modulator: PhaseModulator::new(sample_rate),
+            noise_color: NoiseColor::new(sample_rate),
             noise_tail_envelope: Envelope::new(),
             tonal_envelope: Envelope::new(),
             main_noise_envelope: Envelope::new(),
@@ -812,7 +912,18 @@ impl SnareDrum {
     fn setup_waveforms(&mut self) {
         self.tonal_oscillator.waveform = Waveform::Triangle;
         self.noise_oscillator.waveform = Waveform::Noise;
-        self.crack_oscillator.waveform = Waveform::Noise;
+        self.set_crack_velvet(self.params.crack_velvet);
+    }
+
+    /// Change only the fields set in `partial`; the rest keep their current
+    /// values.
+    pub fn apply_partial(&mut self, partial: &PartialSnareConfig) {
+        self.set_config(self.config().with_partial(partial));
+    }
+
+    /// Get current config snapshot (reads current smoothed values)
+    pub fn config(&self) -> SnareConfig {
+        self.params.to_config()
     }
 
     pub fn set_config(&mut self, config: SnareConfig) {
@@ -851,6 +962,8 @@ impl SnareDrum {
         self.params
             .amp_decay_curve
             .set_target(config.amp_decay_curve);
+        self.params.noise_color.set_target(config.noise_color);
+        self.set_crack_velvet(config.crack_velvet);
     }
 
     /// Snap all smoothed parameters to their targets instantly.
@@ -871,6 +984,7 @@ impl SnareDrum {
     /// - Crack volume: Higher velocity = more crack (brighter, snappier)
     /// - Amplitude: Perceptually linear scaling via sqrt
     pub fn trigger_with_velocity(&mut self, time: f64, velocity: f32) {
+        self.retrigger_fade.retrigger();
         self.current_velocity = velocity.clamp(0.0, 1.0);
         self.is_active = true;
 
@@ -1024,6 +1138,7 @@ impl SnareDrum {
 
         // Reset filter state for clean transient
         self.noise_filter.reset();
+        self.noise_color.reset();
     }
 
     pub fn release(&mut self, time: f64) {
@@ -1042,6 +1157,11 @@ impl SnareDrum {
     }
 
     pub fn tick(&mut self, current_time: f64) -> f32 {
+        let output = self.render(current_time);
+        self.retrigger_fade.process(output)
+    }
+
+    fn render(&mut self, current_time: f64) -> f32 {
         // Always tick smoothers (even when not active, to settle values)
         // Returns true if any params are still changing
         let params_changing = self.params.tick();
@@ -1104,8 +1224,9 @@ impl SnareDrum {
             .set_release_time(scaled_amp_decay * 0.2);
 
         // Use denormalized frequency for pitch calculations, with per-instrument tuning
-        let base_frequency =
-            self.params.frequency_hz() * tuning_to_multiplier(self.params.tuning.get());
+        let base_frequency = self.params.frequency_hz()
+            * tuning_to_multiplier(self.params.tuning.get())
+            * self.pitch_ratio;
 
         // Calculate pitch modulation from envelope
         let pitch_envelope_value = self.pitch_envelope.get_amplitude(current_time);
@@ -1141,7 +1262,10 @@ impl SnareDrum {
         let tonal_output = raw_tonal_output * tonal_env * tonal_mix;
 
         // --- Generate noise component ---
-        let raw_noise_output = self.noise_oscillator.tick(current_time);
+        let raw_noise_output = self.noise_color.process(
+            self.noise_oscillator.tick(current_time),
+            self.params.noise_color.get(),
+        );
 
         // Apply SVF filter to noise based on filter_type
         let filter_type = self.params.filter_type;
@@ -1326,6 +1450,26 @@ impl SnareDrum {
         self.params.overdrive.set_target(amount.clamp(0.0, 1.0));
     }
 
+    /// Select the overdrive stage's saturation curve
+    pub fn set_overdrive_model(&mut self, model: SaturatorModel) {
+        self.waveshaper.set_model(model);
+    }
+
+    /// Get the overdrive stage's saturation curve
+    pub fn overdrive_model(&self) -> SaturatorModel {
+        self.waveshaper.model()
+    }
+
+    /// Turn auto gain on or off for the overdrive stage
+    pub fn set_overdrive_auto_gain(&mut self, enabled: bool) {
+        self.waveshaper.set_auto_gain(enabled);
+    }
+
+    /// Whether the overdrive stage has auto gain on
+    pub fn overdrive_auto_gain(&self) -> bool {
+        self.waveshaper.auto_gain()
+    }
+
     /// Set master amplitude decay time (smoothed, normalized 0-1 → 0-4.0s)
     pub fn set_amp_decay(&mut self, decay: f32) {
         self.params.amp_decay.set_target(decay.clamp(0.0, 1.0));
@@ -1342,6 +1486,23 @@ impl SnareDrum {
     pub fn set_tuning(&mut self, value: f32) {
         self.params.tuning.set_target(value.clamp(0.0, 1.0));
     }
+
+    /// Set noise color (smoothed, 0-1: 0 = white, 0.5 = pink, 1 = brown,
+    /// blending continuously between)
+    pub fn set_noise_color(&mut self, color: f32) {
+        self.params.noise_color.set_target(color.clamp(0.0, 1.0));
+    }
+
+    /// Draw the crack transient from velvet noise (sparse random impulses,
+    /// crisper and more clicky) instead of white noise
+    pub fn set_crack_velvet(&mut self, velvet: bool) {
+        self.params.crack_velvet = velvet;
+        self.crack_oscillator.waveform = if velvet {
+            Waveform::Velvet
+        } else {
+            Waveform::Noise
+        };
+    }
 }
 
 impl crate::engine::Instrument for SnareDrum {
@@ -1353,6 +1514,10 @@ impl crate::engine::Instrument for SnareDrum {
         self.tick(current_time)
     }
 
+    fn release(&mut self, time: f64) {
+        self.release(time)
+    }
+
     fn is_active(&self) -> bool {
         self.is_active()
     }
@@ -1360,6 +1525,10 @@ impl crate::engine::Instrument for SnareDrum {
     fn as_modulatable(&mut self) -> Option<&mut dyn crate::engine::Modulatable> {
         Some(self)
     }
+
+    fn set_pitch_ratio(&mut self, ratio: f32) {
+        self.pitch_ratio = ratio;
+    }
 }
 
 // Implement modulation support for SnareDrum
@@ -1388,10 +1557,11 @@ impl crate::engine::Modulatable for SnareDrum {
             "amp_decay",
             "amp_decay_curve",
             "tuning",
+            "noise_color",
         ]
     }
 
-    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), String> {
+    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), GooeyError> {
         match parameter {
             "frequency" => {
                 self.params.frequency.set_bipolar(value);
@@ -1471,7 +1641,11 @@ impl crate::engine::Modulatable for SnareDrum {
                 self.params.tuning.set_bipolar(value);
                 Ok(())
             }
-            _ => Err(format!("Unknown parameter: {}", parameter)),
+            "noise_color" => {
+                self.params.noise_color.set_bipolar(value);
+                Ok(())
+            }
+            _ => Err(GooeyError::UnknownParameter(parameter.to_string())),
         }
     }
 
@@ -1498,6 +1672,7 @@ impl crate::engine::Modulatable for SnareDrum {
             "amp_decay" => Some(self.params.amp_decay.range()),
             "amp_decay_curve" => Some(self.params.amp_decay_curve.range()),
             "tuning" => Some(self.params.tuning.range()),
+            "noise_color" => Some(self.params.noise_color.range()),
             _ => None,
         }
     }
@@ -1,7 +1,12 @@
 use crate::envelope::{ADSRConfig, Envelope, EnvelopeCurve};
+use crate::error::GooeyError;
 use crate::gen::oscillator::Oscillator;
 use crate::gen::waveform::Waveform;
+use crate::partial_config;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 use crate::utils::smoother::{SmoothedParam, DEFAULT_SMOOTH_TIME_MS};
+use crate::utils::RetriggerFade;
 
 /// Normalization ranges for tom drum parameters
 /// All external-facing parameters use 0.0-1.0 normalized values
@@ -190,6 +195,20 @@ impl TomConfig {
     }
 }
 
+partial_config!(
+    /// [`TomConfig`] with every field optional: only the fields set are applied
+    pub struct PartialTomConfig for TomConfig {
+        frequency: f32,
+        tonal_amount: f32,
+        punch_amount: f32,
+        decay: f32,
+        pitch_drop: f32,
+        volume: f32,
+        amp_decay: f32,
+        amp_decay_curve: f32,
+    }
+);
+
 /// Smoothed parameters for real-time control of the tom drum
 /// All parameters use normalized 0-1 values
 pub struct TomParams {
@@ -331,6 +350,8 @@ pub struct TomDrum {
     pub current_velocity: f32,
 
     pub is_active: bool,
+    /// Releases the previous hit when retriggered mid-decay
+    retrigger_fade: RetriggerFade,
 }
 
 impl TomDrum {
@@ -357,6 +378,7 @@ impl TomDrum {
             amplitude_envelope: Envelope::new(),
             current_velocity: 1.0,
             is_active: false,
+            retrigger_fade: RetriggerFade::new(sample_rate),
         };
 
         tom.configure_oscillators(freq_hz, decay_secs, pitch_drop);
@@ -423,11 +445,18 @@ impl TomDrum {
         self.configure_oscillators(freq_hz, decay_secs, config.pitch_drop);
     }
 
+    /// Change only the fields set in `partial`; the rest keep their current
+    /// values.
+    pub fn apply_partial(&mut self, partial: &PartialTomConfig) {
+        self.set_config(self.config.with_partial(partial));
+    }
+
     pub fn trigger(&mut self, time: f64) {
         self.trigger_with_velocity_internal(time, 1.0);
     }
 
     fn trigger_with_velocity_internal(&mut self, time: f64, velocity: f32) {
+        self.retrigger_fade.retrigger();
         self.is_active = true;
         self.current_velocity = velocity.clamp(0.0, 1.0);
 
@@ -481,6 +510,11 @@ impl TomDrum {
     }
 
     pub fn tick(&mut self, current_time: f64) -> f32 {
+        let output = self.render(current_time);
+        self.retrigger_fade.process(output)
+    }
+
+    fn render(&mut self, current_time: f64) -> f32 {
         // Tick smoothed parameters for click-free modulation
         self.params.tick();
 
@@ -583,6 +617,10 @@ impl crate::engine::Instrument for TomDrum {
         self.tick(current_time)
     }
 
+    fn release(&mut self, time: f64) {
+        self.release(time)
+    }
+
     fn is_active(&self) -> bool {
         self.is_active()
     }
@@ -607,7 +645,7 @@ impl crate::engine::Modulatable for TomDrum {
         ]
     }
 
-    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), String> {
+    fn apply_modulation(&mut self, parameter: &str, value: f32) -> Result<(), GooeyError> {
         // value is -1.0 to 1.0 (bipolar), set_bipolar maps this to the param range
         match parameter {
             "frequency" => {
@@ -642,7 +680,7 @@ impl crate::engine::Modulatable for TomDrum {
                 self.params.amp_decay_curve.set_bipolar(value);
                 Ok(())
             }
-            _ => Err(format!("Unknown parameter: {}", parameter)),
+            _ => Err(GooeyError::UnknownParameter(parameter.to_string())),
         }
     }
 
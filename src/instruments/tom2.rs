@@ -18,13 +18,23 @@
 //! - tone: Mix control position (0-100, crossfades between ring mod, triangle+noise, noise+gated sine)
 //! - color: Noise rand~ rate (0-100 → double-mtof chain → ~116-2794 Hz)
 //! - decay: Envelope decay time (0-100 maps to 0.5-4000ms)
+//!
+//! A/B testing controls (not part of `Tom2Config`):
+//! - triangle_enabled / triangle_level: standalone tri~ toggle and gain (0-100, 50 = Max's 0.5)
+//! - morph_level: MorphOsc output gain (0-100)
+//! - membrane_gain: resonator input gain (0-100, 50 = 0.003, ±2 octaves either side)
 
 use crate::engine::Instrument;
 use crate::filters::{BiquadBandpass, MembraneResonator};
 use crate::gen::{ClickOsc, MorphOsc};
 use crate::max_curve::MaxCurveEnvelope;
+use crate::partial_config;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 use crate::utils::tuning_to_multiplier;
 use crate::utils::Blendable;
+use crate::utils::ControlRate;
+use crate::utils::RetriggerFade;
 
 /// Frequency range constants (from Max zmap 0 1 40 600)
 const FREQ_MIN: f32 = 40.0;
@@ -34,6 +44,9 @@ const FREQ_MAX: f32 = 600.0;
 const FADE_START_FREQ: f32 = 40.0; // Start fading at 40 Hz
 const MIN_AUDIBLE_FREQ: f32 = 20.0; // Full cutoff at 20 Hz
 
+/// Membrane resonator input gain at membrane_gain = 50
+const MEMBRANE_GAIN_SCALE: f32 = 0.003;
+
 /// Decay range constants (from Max zmap 1 100 0.5 4000)
 const DECAY_MIN_MS: f32 = 0.5;
 const DECAY_MAX_MS: f32 = 4000.0;
@@ -64,8 +77,15 @@ pub struct Tom2 {
     morph_osc: MorphOsc,
     click_osc: ClickOsc,
     bandpass_filter: BiquadBandpass,
+    /// The bandpass follows the pitch envelope; its coefficients are
+    /// updated at block rate
+    filter_rate: ControlRate,
     envelope: MaxCurveEnvelope,
     is_active: bool,
+    /// Releases the previous hit when retriggered mid-decay
+    retrigger_fade: RetriggerFade,
+    /// Master tuning multiplier (A4 reference and transpose)
+    pitch_ratio: f32,
     #[allow(dead_code)]
     trigger_time: f64,
 
@@ -84,11 +104,14 @@ pub struct Tom2 {
 
     // Toggle for standalone triangle oscillator (for A/B testing)
     triangle_enabled: bool,
+    triangle_level: f32, // 0-100: standalone triangle gain (50 = Max's *~ 0.5)
+    morph_level: f32,    // 0-100: MorphOsc output gain
 
     // Membrane resonator effect - uses tom sound as input, rings independently of VCA
     membrane_resonator: MembraneResonator,
-    membrane: f32,   // 0-100: mix amount
-    membrane_q: f32, // 0-100: Q scale (maps to 0.005-0.02, centered at 0.01)
+    membrane: f32,      // 0-100: mix amount
+    membrane_q: f32,    // 0-100: Q scale (maps to 0.005-0.02, centered at 0.01)
+    membrane_gain: f32, // 0-100: resonator input gain (50 = 0.003)
 
     // Per-instrument tuning (0=−12 semitones, 0.5=neutral, 1=+12 semitones)
     tuning: f32,
@@ -177,6 +200,20 @@ impl Tom2Config {
     }
 }
 
+partial_config!(
+    /// [`Tom2Config`] with every field optional: only the fields set are applied
+    pub struct PartialTom2Config for Tom2Config {
+        tune: f32,
+        bend: f32,
+        tone: f32,
+        color: f32,
+        decay: f32,
+        membrane: f32,
+        membrane_q: f32,
+        volume: f32,
+    }
+);
+
 impl Blendable for Tom2Config {
     fn lerp(&self, other: &Self, t: f32) -> Self {
         let t = t.clamp(0.0, 1.0);
@@ -210,8 +247,11 @@ impl Tom2 {
             morph_osc: MorphOsc::new(sample_rate),
             click_osc: ClickOsc::new(),
             bandpass_filter: BiquadBandpass::new(sample_rate),
+            filter_rate: ControlRate::default(),
             envelope,
             is_active: false,
+            retrigger_fade: RetriggerFade::new(sample_rate),
+            pitch_ratio: 1.0,
             trigger_time: 0.0,
             tri_phase: 0.0,
             past_attack: false,
@@ -222,12 +262,15 @@ impl Tom2 {
             color: 50.0,            // Middle rand~ rate AND filter cutoff (squared mapping)
             decay: 50.0,            // ~2000ms decay (maps via zmap 1 100 0.5 4000)
             triangle_enabled: true, // Standalone triangle on by default
+            triangle_level: 50.0,
+            morph_level: 100.0,
             // Membrane resonator effect
             membrane_resonator: MembraneResonator::new(sample_rate),
             membrane: 0.0,    // Off by default
             membrane_q: 50.0, // Middle Q scale
-            tuning: 0.5,      // Neutral tuning
-            volume: 100.0,    // Full volume by default
+            membrane_gain: 50.0,
+            tuning: 0.5,   // Neutral tuning
+            volume: 100.0, // Full volume by default
             main_sound_done: false,
         };
         tom.update_membrane_params();
@@ -346,6 +389,26 @@ impl Tom2 {
         self.triangle_enabled
     }
 
+    /// Set standalone triangle gain (0-100): 50 matches the Max patch
+    pub fn set_triangle_level(&mut self, level: f32) {
+        self.triangle_level = level.clamp(0.0, 100.0);
+    }
+
+    /// Get standalone triangle gain (0-100)
+    pub fn triangle_level(&self) -> f32 {
+        self.triangle_level
+    }
+
+    /// Set MorphOsc output gain (0-100)
+    pub fn set_morph_level(&mut self, level: f32) {
+        self.morph_level = level.clamp(0.0, 100.0);
+    }
+
+    /// Get MorphOsc output gain (0-100)
+    pub fn morph_level(&self) -> f32 {
+        self.morph_level
+    }
+
     /// Set membrane mix amount (0-100)
     pub fn set_membrane(&mut self, membrane: f32) {
         self.membrane = membrane.clamp(0.0, 100.0);
@@ -367,6 +430,18 @@ impl Tom2 {
         self.membrane_q
     }
 
+    /// Set membrane resonator input gain (0-100): 50 is the default drive,
+    /// 0 and 100 are two octaves below and above it
+    pub fn set_membrane_gain(&mut self, membrane_gain: f32) {
+        self.membrane_gain = membrane_gain.clamp(0.0, 100.0);
+        self.update_membrane_params();
+    }
+
+    /// Get membrane resonator input gain (0-100)
+    pub fn membrane_gain(&self) -> f32 {
+        self.membrane_gain
+    }
+
     /// Set volume (0-100)
     pub fn set_volume(&mut self, volume: f32) {
         self.volume = volume.clamp(0.0, 100.0);
@@ -393,7 +468,9 @@ impl Tom2 {
         let q_scale = 0.005 + (self.membrane_q / 100.0) * 0.015;
         self.membrane_resonator.set_q_scale(q_scale);
         // Higher gain scale for tom input (lower energy than noise)
-        self.membrane_resonator.set_gain_scale(0.003);
+        let gain_octaves = (self.membrane_gain - 50.0) / 25.0;
+        self.membrane_resonator
+            .set_gain_scale(MEMBRANE_GAIN_SCALE * 2.0_f32.powf(gain_octaves));
     }
 
     /// Apply a config to this Tom2 instance
@@ -409,6 +486,12 @@ impl Tom2 {
         self.update_membrane_params();
     }
 
+    /// Change only the fields set in `partial`; the rest keep their current
+    /// values.
+    pub fn apply_partial(&mut self, partial: &PartialTom2Config) {
+        self.set_config(self.config().with_partial(partial));
+    }
+
     /// Get current parameters as a config snapshot
     pub fn config(&self) -> Tom2Config {
         Tom2Config {
@@ -422,32 +505,8 @@ impl Tom2 {
             volume: self.volume,
         }
     }
-}
-
-impl Instrument for Tom2 {
-    fn trigger_with_velocity(&mut self, time: f64, _velocity: f32) {
-        self.is_active = true;
-        self.trigger_time = time;
-        self.past_attack = false; // Reset attack phase tracking
-        self.morph_osc.reset(); // Reset oscillator phases on trigger
-        self.click_osc.trigger(); // Start click impulse playback
-        self.tri_phase = 0.0; // Reset standalone triangle phase
-        self.bandpass_filter.reset(); // Clear filter state
 
-        // Reset membrane resonator state
-        self.membrane_resonator.reset();
-        self.main_sound_done = false;
-
-        // Rebuild envelope with current decay value (mapped from 0-100 to ms)
-        let decay_ms = Self::decay_to_ms(self.decay);
-        self.envelope = MaxCurveEnvelope::new(vec![
-            (1.0, 1.0, 0.8),        // Attack: value=1.0, time=1ms, curve=0.8
-            (0.0, decay_ms, -0.83), // Decay: value=0.0, time=decay ms, curve=-0.83
-        ]);
-        self.envelope.trigger(time);
-    }
-
-    fn tick(&mut self, current_time: f64) -> f32 {
+    fn render(&mut self, current_time: f64) -> f32 {
         if !self.is_active {
             return 0.0;
         }
@@ -461,7 +520,8 @@ impl Instrument for Tom2 {
         }
 
         // Get base frequency from tune parameter, adjusted by per-instrument tuning
-        let base_frequency = Self::tune_to_freq(self.tune) * tuning_to_multiplier(self.tuning);
+        let base_frequency =
+            Self::tune_to_freq(self.tune) * tuning_to_multiplier(self.tuning) * self.pitch_ratio;
 
         // Bend controls pitch envelope depth (how much pitch drops from peak to base)
         // bend=0: no pitch modulation, frequency stays at base_frequency
@@ -505,7 +565,7 @@ impl Instrument for Tom2 {
         // tri~ at modulated frequency, scaled by 0.5 (matches Max's standalone tri~ outside morphosc)
         // Can be toggled off for A/B testing since it creates sub-bass at low frequencies
         let tri_output = if self.triangle_enabled {
-            triangle(self.tri_phase) * 0.5
+            triangle(self.tri_phase) * (self.triangle_level / 100.0)
         } else {
             0.0
         };
@@ -526,7 +586,8 @@ impl Instrument for Tom2 {
         // Note: morph_osc applies second mtof internally to match Max's double-mtof chain
         let morph_output =
             self.morph_osc
-                .tick(modulated_freq, mix_control, color_freq_1, self.tone);
+                .tick(modulated_freq, mix_control, color_freq_1, self.tone)
+                * (self.morph_level / 100.0);
 
         // === Mixing (before filter, NO envelope yet!) ===
         // Max signal flow: click + tri + morphosc → biquad → *envelope → *0.5 → *1.4
@@ -536,15 +597,17 @@ impl Instrument for Tom2 {
         // Filter frequency TRACKS THE PITCH (same formula as oscillator)
         // Max patch: curve~ × bend → pow~ 2 → *~ tune_freq → filtercoeff~
         // This centers the bandpass on the fundamental, attenuating noise
-        let filter_freq = modulated_freq.max(20.0); // Same as oscillator pitch!
+        if self.filter_rate.tick() {
+            let filter_freq = modulated_freq.max(20.0); // Same as oscillator pitch!
 
-        // Q from color squared: zmap 0 1 1 2
-        let color_norm = self.color / 100.0;
-        let color_squared = color_norm * color_norm;
-        let filter_q = 1.0 + color_squared;
+            // Q from color squared: zmap 0 1 1 2
+            let color_norm = self.color / 100.0;
+            let color_squared = color_norm * color_norm;
+            let filter_q = 1.0 + color_squared;
 
-        // Apply bandpass filter with gain 1.1 (from Max patch loadbang)
-        self.bandpass_filter.set_params(filter_freq, filter_q, 1.1);
+            // Apply bandpass filter with gain 1.1 (from Max patch loadbang)
+            self.bandpass_filter.set_params(filter_freq, filter_q, 1.1);
+        }
         let filtered = self.bandpass_filter.process(mixed);
 
         // === Membrane Resonator ===
@@ -583,12 +646,49 @@ impl Instrument for Tom2 {
         // Combined gain: 0.5 * 1.4 = 0.7
         final_signal * fade_factor * 0.7 * (self.volume / 100.0)
     }
+}
+
+impl Instrument for Tom2 {
+    fn trigger_with_velocity(&mut self, time: f64, _velocity: f32) {
+        self.retrigger_fade.retrigger();
+        self.filter_rate.reset();
+        self.is_active = true;
+        self.trigger_time = time;
+        self.past_attack = false; // Reset attack phase tracking
+        self.morph_osc.reset(); // Reset oscillator phases on trigger
+        self.click_osc.trigger(); // Start click impulse playback
+        self.tri_phase = 0.0; // Reset standalone triangle phase
+        self.bandpass_filter.reset(); // Clear filter state
+
+        // Reset membrane resonator state
+        self.membrane_resonator.reset();
+        self.main_sound_done = false;
+
+        // Retime the decay segment with the current decay value (mapped from
+        // 0-100 to ms) in place, so triggering doesn't allocate
+        self.envelope
+            .set_segment_duration_ms(1, Self::decay_to_ms(self.decay));
+        self.envelope.trigger(time);
+    }
+
+    fn tick(&mut self, current_time: f64) -> f32 {
+        let output = self.render(current_time);
+        self.retrigger_fade.process(output)
+    }
 
     fn is_active(&self) -> bool {
         self.is_active
     }
 
+    fn reseed(&mut self, seed: u64) {
+        self.morph_osc.set_noise_seed(seed);
+    }
+
     fn as_modulatable(&mut self) -> Option<&mut dyn crate::engine::Modulatable> {
         None
     }
+
+    fn set_pitch_ratio(&mut self, ratio: f32) {
+        self.pitch_ratio = ratio;
+    }
 }
@@ -1,27 +1,79 @@
 //! Shared audio engine logic for native (CPAL) and iOS targets
+//!
+//! Without the default `std` feature only the DSP core builds, on `no_std` +
+//! `alloc` (enable `libm` for float math): instruments, envelopes, filters,
+//! effects, generators and the sequencer, with
+//! [`engine::InstrumentRegistry`] standing in for the engine. The engine,
+//! mixer, FFI and DSL need `std`. The `staticlib`/`cdylib` targets want a
+//! panic handler and allocator, so build the embedded core as an rlib:
+//! `cargo rustc --lib --no-default-features --features libm --crate-type rlib`.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(all(not(feature = "std"), not(feature = "libm")))]
+compile_error!("no_std builds need the `libm` feature for float math");
+
+#[cfg(not(feature = "std"))]
+mod prelude;
+
+#[cfg(feature = "std")]
+pub mod cpu_load;
+#[cfg(feature = "std")]
 pub mod dsl;
 pub mod envelope;
+pub mod envelope_model;
+pub mod error;
 pub mod filters;
 pub mod max_curve;
 
 // New organized modules
 pub mod effects;
 pub mod engine;
+#[cfg(feature = "std")]
 pub mod ffi;
 pub mod frame;
 pub mod gen;
 pub mod instruments;
+#[cfg(feature = "std")]
 pub mod mixer;
 pub mod music;
+#[cfg(feature = "std")]
+pub mod param_info;
+#[cfg(feature = "std")]
+pub mod param_table;
+#[cfg(feature = "std")]
 pub mod performance;
 pub mod sequencer;
+#[cfg(feature = "std")]
+pub mod snapshot;
 pub mod utils;
 
+#[cfg(feature = "std")]
 pub mod bounce;
+#[cfg(feature = "decode")]
+pub mod decode;
+#[cfg(feature = "std")]
+pub mod recorder;
+#[cfg(feature = "std")]
+pub mod rt_audit;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+#[cfg(feature = "std")]
+pub mod trace;
 
+pub use error::GooeyError;
 pub use frame::StereoFrame;
 
+// OSC control surface (optional)
+#[cfg(feature = "osc")]
+pub mod osc;
+
+// CLAP/VST3 plugin wrapper (optional)
+#[cfg(feature = "plugin")]
+pub mod plugin;
+
 // Visualization module (optional)
 #[cfg(feature = "visualization")]
 pub mod visualization;
@@ -4,6 +4,12 @@
 //! exponential interpolation algorithm, allowing for accurate reproduction of
 //! Max patches in Rust.
 
+use crate::error::GooeyError;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use core::fmt;
+use core::str::FromStr;
+
 /// Calculate curved interpolation using the Max/MSP curve~ algorithm.
 ///
 /// This is the exact formula used by Max/MSP's curve~ object, derived from
@@ -48,7 +54,7 @@ pub fn max_curve(progress: f32, curve: f32) -> f32 {
 }
 
 /// A single segment of a multi-segment envelope
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct EnvelopeSegment {
     /// Target value to reach at end of segment
     pub target_value: f32,
@@ -68,23 +74,50 @@ impl EnvelopeSegment {
     }
 }
 
+/// The original name of [`SegmentEnvelope`], kept for Max patch ports.
+pub type MaxCurveEnvelope = SegmentEnvelope;
+
 /// Multi-segment envelope using Max/MSP curve~ algorithm.
 ///
 /// Accepts segments in the same format as Max's curve~ object:
-/// (target_value, time_ms, curve)
-#[derive(Clone)]
-pub struct MaxCurveEnvelope {
+/// (target_value, time_ms, curve). On top of plain one-shot curves it can
+/// hold at a sustain point and cycle a loop of segments while the gate is
+/// held; [`SegmentEnvelope::release`] then jumps to the segments after them.
+/// Values are not limited to 0..1, so the same type drives amplitude and
+/// modulation (see [`crate::engine::ModEnvelope`]).
+///
+/// ```
+/// use gooey::max_curve::SegmentEnvelope;
+///
+/// // Delay 0 / attack 5 / hold 20 / decay 150 / sustain 0.6 / release 300
+/// let amp = SegmentEnvelope::dahdsr(0.0, 5.0, 20.0, 150.0, 0.6, 300.0);
+///
+/// // A pitch wobble that loops 1 -> -1 -> 1 until released, then settles at 0
+/// let wobble = SegmentEnvelope::default()
+///     .segment(1.0, 10.0, 0.0)
+///     .segment(-1.0, 80.0, 0.5)
+///     .segment(1.0, 80.0, -0.5)
+///     .segment(0.0, 40.0, 0.0)
+///     .with_loop(1, 2);
+/// assert_eq!(wobble.to_string().parse::<SegmentEnvelope>().unwrap().segments().len(), 4);
+/// # let _ = amp;
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SegmentEnvelope {
     segments: Vec<EnvelopeSegment>,
+    sustain_point: Option<usize>,
+    loop_points: Option<(usize, usize)>,
     current_segment: usize,
     segment_start_time: f64,
     segment_start_value: f32,
     current_value: f32,
     pub is_active: bool,
+    gate: bool,
     trigger_time: f64,
     initial_value: f32,
 }
 
-impl MaxCurveEnvelope {
+impl SegmentEnvelope {
     /// Create a new envelope from segments.
     ///
     /// Each tuple is (target_value, time_ms, curve).
@@ -97,16 +130,84 @@ impl MaxCurveEnvelope {
 
         Self {
             segments: envelope_segments,
-            current_segment: 0,
-            segment_start_time: 0.0,
-            segment_start_value: 0.0,
-            current_value: 0.0,
-            is_active: false,
-            trigger_time: 0.0,
-            initial_value: 0.0,
+            ..Self::default()
         }
     }
 
+    /// Delay / attack / hold / decay / sustain / release envelope (times in ms).
+    ///
+    /// Segments are, in order: delay (0), attack (1), hold (2), decay (3, the
+    /// sustain point) and release (4). All are linear; shape them with
+    /// [`SegmentEnvelope::with_segment_curve`].
+    pub fn dahdsr(
+        delay_ms: f32,
+        attack_ms: f32,
+        hold_ms: f32,
+        decay_ms: f32,
+        sustain: f32,
+        release_ms: f32,
+    ) -> Self {
+        Self::default()
+            .segment(0.0, delay_ms, 0.0)
+            .segment(1.0, attack_ms, 0.0)
+            .segment(1.0, hold_ms, 0.0)
+            .segment(sustain, decay_ms, 0.0)
+            .segment(0.0, release_ms, 0.0)
+            .with_sustain(3)
+    }
+
+    /// Append a segment ramping to `target_value` over `duration_ms`.
+    pub fn segment(mut self, target_value: f32, duration_ms: f32, curve: f32) -> Self {
+        self.segments.push(EnvelopeSegment::new(
+            target_value,
+            duration_ms.max(0.0),
+            curve.clamp(-1.0, 1.0),
+        ));
+        self
+    }
+
+    /// Hold at the end of segment `index` until released.
+    pub fn with_sustain(mut self, index: usize) -> Self {
+        self.sustain_point = Some(index);
+        self
+    }
+
+    /// Cycle segments `start..=end` until released. Ignored if `start > end`.
+    pub fn with_loop(mut self, start: usize, end: usize) -> Self {
+        self.loop_points = (start <= end).then_some((start, end));
+        self
+    }
+
+    /// Value the envelope starts from on each trigger.
+    pub fn with_initial_value(mut self, value: f32) -> Self {
+        self.initial_value = value;
+        self
+    }
+
+    /// Set the curve of segment `index` (no-op if out of range).
+    pub fn with_segment_curve(mut self, index: usize, curve: f32) -> Self {
+        if let Some(seg) = self.segments.get_mut(index) {
+            seg.curve = curve.clamp(-1.0, 1.0);
+        }
+        self
+    }
+
+    pub fn segments(&self) -> &[EnvelopeSegment] {
+        &self.segments
+    }
+
+    pub fn sustain_point(&self) -> Option<usize> {
+        self.sustain_point
+    }
+
+    pub fn loop_points(&self) -> Option<(usize, usize)> {
+        self.loop_points
+    }
+
+    pub fn initial_value(&self) -> f32 {
+        self.initial_value
+    }
+
     /// Set the initial value before triggering
     pub fn set_initial_value(&mut self, value: f32) {
         self.initial_value = value;
@@ -122,6 +223,7 @@ impl MaxCurveEnvelope {
     /// Trigger the envelope at the given time
     pub fn trigger(&mut self, time: f64) {
         self.is_active = true;
+        self.gate = true;
         self.trigger_time = time;
         self.current_segment = 0;
         self.segment_start_time = time;
@@ -129,6 +231,68 @@ impl MaxCurveEnvelope {
         self.current_value = self.initial_value;
     }
 
+    /// Release the gate: leave the sustain point or loop and run the
+    /// segments after them, starting from the current value.
+    ///
+    /// Envelopes without a sustain point or loop are one-shots and keep
+    /// running untouched.
+    pub fn release(&mut self, time: f64) {
+        if !self.gate {
+            return;
+        }
+        self.get_value(time);
+        self.gate = false;
+        let Some(release_segment) = self.release_segment() else {
+            return;
+        };
+        if self.is_active && self.current_segment < release_segment {
+            self.current_segment = release_segment;
+            self.segment_start_time = time;
+            self.segment_start_value = self.current_value;
+        }
+    }
+
+    /// Whether the gate is held (triggered and not yet released).
+    pub fn is_gated(&self) -> bool {
+        self.gate
+    }
+
+    /// The most recently computed value.
+    pub fn value(&self) -> f32 {
+        self.current_value
+    }
+
+    /// First segment played after release, if the envelope has a gated stage.
+    fn release_segment(&self) -> Option<usize> {
+        let sustain = self.sustain_point;
+        let loop_end = self.loop_points.map(|(_, end)| end);
+        sustain.max(loop_end).map(|index| index + 1)
+    }
+
+    /// The loop to jump back into after finishing `segment`, if any. Loops
+    /// with no length hold instead so `get_value` never spins.
+    fn loop_restart(&self, segment: usize) -> Option<usize> {
+        let (start, end) = self.loop_points?;
+        if !self.gate || end != segment {
+            return None;
+        }
+        let length: f32 = self
+            .segments
+            .get(start..=end)?
+            .iter()
+            .map(|s| s.duration_secs)
+            .sum();
+        (length > 0.0).then_some(start)
+    }
+
+    fn holds_after(&self, segment: usize) -> bool {
+        if !self.gate {
+            return false;
+        }
+        self.sustain_point == Some(segment)
+            || self.loop_points.is_some_and(|(_, end)| end == segment)
+    }
+
     /// Get the current envelope value at the given time
     pub fn get_value(&mut self, current_time: f64) -> f32 {
         if !self.is_active {
@@ -150,6 +314,15 @@ impl MaxCurveEnvelope {
                 // Move to next segment
                 self.segment_start_value = segment.target_value;
                 self.current_value = segment.target_value;
+                if let Some(start) = self.loop_restart(self.current_segment) {
+                    self.segment_start_time += segment.duration_secs as f64;
+                    self.current_segment = start;
+                    continue;
+                }
+                if self.holds_after(self.current_segment) {
+                    // Sustain: sit on the target until `release`
+                    return self.current_value;
+                }
                 self.segment_start_time += segment.duration_secs as f64;
                 self.current_segment += 1;
                 continue;
@@ -179,6 +352,98 @@ impl MaxCurveEnvelope {
     }
 }
 
+/// Text form: `from=<v> [sustain=<i>] [loop=<a>..<b>] seg=<target>,<ms>,<curve> ...`,
+/// read back by [`SegmentEnvelope::parse`].
+impl fmt::Display for SegmentEnvelope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "from={}", self.initial_value)?;
+        if let Some(index) = self.sustain_point {
+            write!(f, " sustain={}", index)?;
+        }
+        if let Some((start, end)) = self.loop_points {
+            write!(f, " loop={}..{}", start, end)?;
+        }
+        for seg in &self.segments {
+            write!(
+                f,
+                " seg={},{},{}",
+                seg.target_value,
+                seg.duration_secs * 1000.0,
+                seg.curve
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl SegmentEnvelope {
+    /// Parse the text form written by `Display`. Tokens other than `seg` may
+    /// come in any order; segments keep the order they are listed in.
+    pub fn parse(source: &str) -> Result<Self, GooeyError> {
+        fn number<T: FromStr>(token: &str, text: &str) -> Result<T, GooeyError> {
+            text.trim().parse().map_err(|_| {
+                GooeyError::InvalidValue(format!("Invalid number '{}' in '{}'", text, token))
+            })
+        }
+
+        let mut envelope = Self::default();
+        for token in source.split_whitespace() {
+            let (key, value) = token.split_once('=').ok_or_else(|| {
+                GooeyError::InvalidValue(format!("Expected key=value, got '{}'", token))
+            })?;
+            match key {
+                "from" => envelope.initial_value = number(token, value)?,
+                "sustain" => envelope.sustain_point = Some(number(token, value)?),
+                "loop" => {
+                    let (start, end) = value.split_once("..").ok_or_else(|| {
+                        GooeyError::InvalidValue(format!(
+                            "Expected loop=<start>..<end>, got '{}'",
+                            token
+                        ))
+                    })?;
+                    let (start, end) = (number(token, start)?, number(token, end)?);
+                    if start > end {
+                        return Err(GooeyError::InvalidValue(format!(
+                            "Loop start after end in '{}'",
+                            token
+                        )));
+                    }
+                    envelope.loop_points = Some((start, end));
+                }
+                "seg" => {
+                    let parts: Vec<&str> = value.split(',').collect();
+                    let [target, ms, curve] = parts[..] else {
+                        return Err(GooeyError::InvalidValue(format!(
+                            "Expected seg=<target>,<ms>,<curve>, got '{}'",
+                            token
+                        )));
+                    };
+                    envelope = envelope.segment(
+                        number(token, target)?,
+                        number(token, ms)?,
+                        number(token, curve)?,
+                    );
+                }
+                _ => {
+                    return Err(GooeyError::InvalidValue(format!(
+                        "Unknown envelope key '{}'",
+                        key
+                    )))
+                }
+            }
+        }
+        Ok(envelope)
+    }
+}
+
+impl FromStr for SegmentEnvelope {
+    type Err = GooeyError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Self::parse(source)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -264,4 +529,78 @@ mod tests {
             "Should be ~0.5 at midpoint of second segment"
         );
     }
+
+    #[test]
+    fn test_envelope_holds_at_sustain_until_release() {
+        let mut env = SegmentEnvelope::dahdsr(0.0, 10.0, 0.0, 10.0, 0.5, 100.0);
+        env.trigger(0.0);
+        assert!((env.get_value(0.005) - 0.5).abs() < 0.01);
+        assert!((env.get_value(0.02) - 0.5).abs() < 1e-6);
+        assert!(
+            (env.get_value(5.0) - 0.5).abs() < 1e-6,
+            "sustain should hold"
+        );
+        assert!(env.is_active);
+
+        env.release(5.0);
+        assert!((env.get_value(5.05) - 0.25).abs() < 0.01);
+        assert_eq!(env.get_value(5.2), 0.0);
+        assert!(env.is_complete());
+    }
+
+    #[test]
+    fn test_early_release_ramps_from_current_value() {
+        let mut env = SegmentEnvelope::dahdsr(0.0, 100.0, 0.0, 10.0, 0.5, 100.0);
+        env.trigger(0.0);
+        env.release(0.05);
+        assert!((env.value() - 0.5).abs() < 0.01);
+        assert!((env.get_value(0.1) - 0.25).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_loop_cycles_while_gated() {
+        let mut env = SegmentEnvelope::default()
+            .segment(1.0, 10.0, 0.0)
+            .segment(0.0, 10.0, 0.0)
+            .with_loop(0, 1);
+        env.trigger(0.0);
+        for cycle in 0..5 {
+            let start = cycle as f64 * 0.02;
+            assert!((env.get_value(start + 0.01) - 1.0).abs() < 0.01);
+            assert!(env.get_value(start + 0.015) < 0.6);
+        }
+        assert!(env.is_active);
+    }
+
+    #[test]
+    fn test_zero_length_loop_holds() {
+        let mut env = SegmentEnvelope::default()
+            .segment(1.0, 0.0, 0.0)
+            .with_loop(0, 0);
+        env.trigger(0.0);
+        assert_eq!(env.get_value(1.0), 1.0);
+        assert!(env.is_active);
+    }
+
+    #[test]
+    fn test_text_round_trip() {
+        let env = SegmentEnvelope::dahdsr(2.0, 5.0, 20.0, 150.0, 0.6, 300.0)
+            .with_segment_curve(1, 0.8)
+            .with_loop(2, 3)
+            .with_initial_value(0.1);
+        let parsed = SegmentEnvelope::parse(&env.to_string()).unwrap();
+        assert_eq!(parsed.sustain_point(), Some(3));
+        assert_eq!(parsed.loop_points(), Some((2, 3)));
+        assert_eq!(parsed.initial_value(), 0.1);
+        assert_eq!(parsed.segments().len(), 5);
+        for (a, b) in env.segments().iter().zip(parsed.segments()) {
+            assert_eq!(a.target_value, b.target_value);
+            assert_eq!(a.curve, b.curve);
+            assert!((a.duration_secs - b.duration_secs).abs() < 1e-6);
+        }
+
+        assert!(SegmentEnvelope::parse("seg=1,2").is_err());
+        assert!(SegmentEnvelope::parse("loop=3..1").is_err());
+        assert!(SegmentEnvelope::parse("attack=3").is_err());
+    }
 }
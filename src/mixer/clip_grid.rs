@@ -41,7 +41,8 @@ impl LaunchQuantization {
         }
     }
 
-    fn beats(self) -> f64 {
+    /// Grid interval in beats (quarter notes).
+    pub fn beats(self) -> f64 {
         match self {
             Self::Sixteenth => 0.25,
             Self::Quarter => 1.0,
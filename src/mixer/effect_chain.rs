@@ -14,14 +14,15 @@ use crate::effects::{
 use crate::ffi::{
     COMPRESSOR_PARAM_ATTACK, COMPRESSOR_PARAM_MIX, COMPRESSOR_PARAM_RATIO,
     COMPRESSOR_PARAM_RELEASE, COMPRESSOR_PARAM_THRESHOLD, DELAY_PARAM_FEEDBACK,
-    DELAY_PARAM_FILTER_CUTOFF, DELAY_PARAM_MIX, DELAY_PARAM_PINGPONG, DELAY_PARAM_TIMING,
-    EFFECT_COMPRESSOR, EFFECT_DELAY, EFFECT_FEEDBACK_WAVESHAPER, EFFECT_LOWPASS_FILTER,
-    EFFECT_PLATE_REVERB, EFFECT_REVERB, EFFECT_SATURATION, EFFECT_TILT_FILTER, EFFECT_WAVESHAPER,
-    FEEDBACK_WAVESHAPER_PARAM_DRIVE, FEEDBACK_WAVESHAPER_PARAM_FEEDBACK,
-    FEEDBACK_WAVESHAPER_PARAM_FILTER_CUTOFF, FEEDBACK_WAVESHAPER_PARAM_MIX, FILTER_PARAM_CUTOFF,
-    FILTER_PARAM_RESONANCE, PLATE_PARAM_DAMPING, PLATE_PARAM_DECAY, PLATE_PARAM_MIX,
-    PLATE_PARAM_PREDELAY, PLATE_PARAM_SIZE, PLATE_PARAM_WIDTH, REVERB_PARAM_DAMPING,
-    REVERB_PARAM_DECAY, REVERB_PARAM_MIX, SATURATION_PARAM_DRIVE, SATURATION_PARAM_MIX,
+    DELAY_PARAM_FILTER_CUTOFF, DELAY_PARAM_FREEZE, DELAY_PARAM_LOW_CUT, DELAY_PARAM_MIX,
+    DELAY_PARAM_PINGPONG, DELAY_PARAM_TIMING, EFFECT_COMPRESSOR, EFFECT_DELAY,
+    EFFECT_FEEDBACK_WAVESHAPER, EFFECT_LOWPASS_FILTER, EFFECT_PLATE_REVERB, EFFECT_REVERB,
+    EFFECT_SATURATION, EFFECT_TILT_FILTER, EFFECT_WAVESHAPER, FEEDBACK_WAVESHAPER_PARAM_DRIVE,
+    FEEDBACK_WAVESHAPER_PARAM_FEEDBACK, FEEDBACK_WAVESHAPER_PARAM_FILTER_CUTOFF,
+    FEEDBACK_WAVESHAPER_PARAM_MIX, FILTER_PARAM_CUTOFF, FILTER_PARAM_RESONANCE,
+    PLATE_PARAM_DAMPING, PLATE_PARAM_DECAY, PLATE_PARAM_MIX, PLATE_PARAM_PREDELAY,
+    PLATE_PARAM_SIZE, PLATE_PARAM_WIDTH, REVERB_PARAM_DAMPING, REVERB_PARAM_DECAY,
+    REVERB_PARAM_MIX, SATURATION_PARAM_AUTO_GAIN, SATURATION_PARAM_DRIVE, SATURATION_PARAM_MIX,
     SATURATION_PARAM_WARMTH, TILT_PARAM_CUTOFF, TILT_PARAM_RESONANCE, WAVESHAPER_PARAM_DRIVE,
     WAVESHAPER_PARAM_MIX,
 };
@@ -170,12 +171,15 @@ impl ChannelEffect {
                 DELAY_PARAM_MIX => e.set_mix(value),
                 DELAY_PARAM_FILTER_CUTOFF => e.set_filter_cutoff(value),
                 DELAY_PARAM_PINGPONG => e.set_pingpong(value >= 0.5),
+                DELAY_PARAM_LOW_CUT => e.set_low_cut(value),
+                DELAY_PARAM_FREEZE => e.set_freeze(value >= 0.5),
                 _ => {}
             },
             Self::Saturation(e) => match param {
                 SATURATION_PARAM_DRIVE => e.set_drive(value),
                 SATURATION_PARAM_WARMTH => e.set_warmth(value),
                 SATURATION_PARAM_MIX => e.set_mix(value),
+                SATURATION_PARAM_AUTO_GAIN => e.set_auto_gain(value >= 0.5),
                 _ => {}
             },
             Self::Compressor(e) => match param {
@@ -278,6 +282,42 @@ impl ChannelEffect {
     }
 }
 
+/// Lets a [`ChannelEffect`] stand in the engine's global effects chain, where
+/// its typed parameters can be automated through [`Effect::set_param`].
+impl Effect for ChannelEffect {
+    fn process(&self, input: f32) -> f32 {
+        match self {
+            Self::Filter(e) => e.process(input),
+            Self::Delay(e) => e.process(input),
+            Self::Saturation(e) => e.process(input),
+            Self::Compressor(e) => e.process(input),
+            Self::Tilt(e) => e.process(input),
+            Self::Reverb(e) => e.process(input),
+            Self::PlateReverb(e) => e.process(input),
+            Self::Waveshaper(ws) => {
+                let ws = unsafe { &mut *ws.get() };
+                ws[0].process(input)
+            }
+            Self::FeedbackWaveshaper(fb) => {
+                let fb = unsafe { &mut *fb.get() };
+                fb[0].process(input)
+            }
+        }
+    }
+
+    fn process_stereo(&self, input: StereoFrame) -> StereoFrame {
+        ChannelEffect::process_stereo(self, input)
+    }
+
+    fn set_bpm(&self, bpm: f32) {
+        ChannelEffect::set_bpm(self, bpm);
+    }
+
+    fn set_param(&self, param: u32, value: f32) {
+        ChannelEffect::set_param(self, param, value);
+    }
+}
+
 /// An ordered, runtime-editable chain of per-channel effects.
 #[derive(Default)]
 pub struct EffectChain {
@@ -107,6 +107,9 @@ pub struct MixerGraph {
     /// Per-track accumulator, always `tracks.len()` long. Resized only when the
     /// layout changes (config time); cleared and summed each sample.
     scratch: Vec<StereoFrame>,
+    /// Per-track accumulator for the dry-routed share of each source (see
+    /// [`scatter_dry`](Self::scatter_dry)), sized with `scratch`.
+    dry_scratch: Vec<StereoFrame>,
     sample_rate: f32,
     bpm: f32,
 }
@@ -119,6 +122,7 @@ impl MixerGraph {
             routes: [None; SOURCE_CAPACITY],
             active_sources: std::array::from_fn(|index| index < SOURCE_COUNT),
             scratch: Vec::new(),
+            dry_scratch: Vec::new(),
             sample_rate,
             bpm,
         }
@@ -146,14 +150,24 @@ impl MixerGraph {
     pub fn reset(&mut self) {
         self.tracks.clear();
         self.scratch.clear();
+        self.dry_scratch.clear();
         self.routes = [None; SOURCE_CAPACITY];
     }
 
+    /// Clear every track rack's DSP state (delay lines, reverb tails),
+    /// keeping the racks and the layout.
+    pub fn reset_effects(&self) {
+        for track in &self.tracks {
+            track.rack.reset();
+        }
+    }
+
     /// Append a named track. Returns its index. Grows the render scratch so the
     /// audio thread never allocates.
     pub fn add_track(&mut self, name: CString) -> usize {
         self.tracks.push(Track::new(name, self.sample_rate));
         self.scratch.push(StereoFrame::default());
+        self.dry_scratch.push(StereoFrame::default());
         self.tracks.len() - 1
     }
 
@@ -334,7 +348,7 @@ impl MixerGraph {
 
     /// Zero every per-track accumulator. Call once at the top of each sample.
     pub fn clear_scratch(&mut self) {
-        for s in &mut self.scratch {
+        for s in self.scratch.iter_mut().chain(&mut self.dry_scratch) {
             *s = StereoFrame::default();
         }
     }
@@ -349,6 +363,18 @@ impl MixerGraph {
         }
     }
 
+    /// Add a source's dry-routed share (voices that bypass master effects)
+    /// into its routed track. It takes the track's strip but skips the rack,
+    /// which runs once on the track's routed sum, and comes out of
+    /// [`mix_down_split`](Self::mix_down_split) separately.
+    pub fn scatter_dry(&mut self, source_kind: u32, frame: StereoFrame) {
+        if let Some(track) = self.route_of(source_kind) {
+            if let Some(slot) = self.dry_scratch.get_mut(track) {
+                *slot += frame;
+            }
+        }
+    }
+
     /// Recompute per-track mute/solo targets. Call once per buffer. Solo is
     /// scoped across tracks: any soloed track silences the un-soloed ones.
     pub fn update_mute_solo_targets(&mut self) {
@@ -383,19 +409,35 @@ impl MixerGraph {
     /// its accumulated frame, capture its post-strip peak, and return the summed
     /// master frame. Allocation-free.
     pub fn mix_down(&mut self) -> StereoFrame {
+        let (master, dry) = self.mix_down_split();
+        master + dry
+    }
+
+    /// [`mix_down`](Self::mix_down), keeping the dry-routed sum (see
+    /// [`scatter_dry`](Self::scatter_dry)) apart from the master frame so
+    /// the engine can route it around its global effects.
+    pub fn mix_down_split(&mut self) -> (StereoFrame, StereoFrame) {
         let MixerGraph {
-            tracks, scratch, ..
+            tracks,
+            scratch,
+            dry_scratch,
+            ..
         } = self;
         let mut master = StereoFrame::default();
+        let mut dry = StereoFrame::default();
         for (i, track) in tracks.iter_mut().enumerate() {
             let gain = track.gain.tick() * track.mute_gain.tick();
+            let pan = track.pan.tick();
             let mut f = scratch[i].scaled(gain);
-            f = balanced(f, track.pan.tick());
+            f = balanced(f, pan);
             f = track.rack.process(f);
-            track.record_peak(f.l.abs().max(f.r.abs()));
+            let d = balanced(dry_scratch[i].scaled(gain), pan);
+            let peak = f + d;
+            track.record_peak(peak.l.abs().max(peak.r.abs()));
             master += f;
+            dry += d;
         }
-        master
+        (master, dry)
     }
 }
 
@@ -502,6 +544,24 @@ mod tests {
         assert_eq!(graph.track_peak_swap(track + 1), None);
     }
 
+    #[test]
+    fn dry_routed_share_takes_the_strip_but_comes_out_apart() {
+        let mut graph = MixerGraph::new(SR, BPM);
+        let track = graph.add_track(CString::new("A").unwrap());
+        assert!(graph.route(SOURCE_DRUMKIT, track));
+        graph.set_track_gain(track, 0.5);
+        graph.snap_strip_params();
+
+        graph.clear_scratch();
+        graph.scatter(SOURCE_DRUMKIT, StereoFrame::mono(1.0));
+        graph.scatter_dry(SOURCE_DRUMKIT, StereoFrame::mono(0.5));
+        graph.scatter_dry(SOURCE_BASS, StereoFrame::mono(1.0));
+        let (master, dry) = graph.mix_down_split();
+        assert_eq!(master, StereoFrame::mono(0.5));
+        assert_eq!(dry, StereoFrame::mono(0.25));
+        assert_eq!(graph.track_peak_swap(track), Some(0.75));
+    }
+
     #[test]
     fn snap_strip_params_applies_current_targets_immediately() {
         let mut graph = MixerGraph::new(SR, BPM);
@@ -245,7 +245,7 @@ impl LoopChannel {
         // is in PreservePitch mode. `maybe_swap_pending` resets the stretcher on a
         // swap so the next tick re-seeds on the new buffer at its loop start.
         //
-        // Note: `self.cursor` only advances on hop refills (~`wsola::HOP_MS`), so in
+        // Note: `self.cursor` only advances on hop refills (~`time_stretch::HOP_MS`), so in
         // this mode the boundary check is at hop granularity — a queued swap can land
         // up to one hop after the exact grid sample, whereas the resample/off paths
         // are sample-accurate. This is accepted: the swap restarts the phrase from the
@@ -524,6 +524,7 @@ impl LoopChannel {
     pub fn queue_swap(&mut self, buffer: StereoSampleBuffer, divisions: u32) {
         self.pending_divisions
             .store(divisions.max(1), Ordering::Relaxed);
+        crate::rt_audit::lock_acquired("loop channel pending swap");
         *self.pending.lock().unwrap() = Some(buffer);
         self.has_pending.store(true, Ordering::Release);
     }
@@ -531,6 +532,7 @@ impl LoopChannel {
     /// Drop a pending queued swap. No-op if nothing is queued.
     pub fn cancel_queued_swap(&mut self) {
         self.has_pending.store(false, Ordering::Release);
+        crate::rt_audit::lock_acquired("loop channel pending swap");
         *self.pending.lock().unwrap() = None;
     }
 
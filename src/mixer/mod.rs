@@ -86,6 +86,14 @@ impl Mixer {
         }
     }
 
+    /// Clear every channel's effect DSP state (delay lines, reverb tails)
+    /// without stopping playback.
+    pub fn reset_effects(&self) {
+        for channel in &self.channels {
+            channel.effects().reset();
+        }
+    }
+
     pub fn channel_count(&self) -> usize {
         self.channels.len()
     }
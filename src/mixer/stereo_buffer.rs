@@ -7,6 +7,7 @@
 
 use std::sync::Arc;
 
+use crate::error::GooeyError;
 use crate::frame::StereoFrame;
 use crate::utils::cubic_interpolate;
 
@@ -30,22 +31,28 @@ impl StereoSampleBuffer {
         left: Vec<f32>,
         right: Vec<f32>,
         sample_rate: f32,
-    ) -> Result<Self, String> {
+    ) -> Result<Self, GooeyError> {
         if left.is_empty() || right.is_empty() {
-            return Err("StereoSampleBuffer requires at least one frame".to_string());
+            return Err(GooeyError::InvalidValue(
+                "StereoSampleBuffer requires at least one frame".to_string(),
+            ));
         }
         if left.len() != right.len() {
-            return Err(format!(
+            return Err(GooeyError::InvalidValue(format!(
                 "StereoSampleBuffer channels must match: left={}, right={}",
                 left.len(),
                 right.len()
-            ));
+            )));
         }
         if !sample_rate.is_finite() || sample_rate <= 0.0 {
-            return Err(format!("Invalid sample rate: {sample_rate}"));
+            return Err(GooeyError::InvalidValue(format!(
+                "Invalid sample rate: {sample_rate}"
+            )));
         }
         if left.iter().chain(right.iter()).any(|s| !s.is_finite()) {
-            return Err("StereoSampleBuffer samples must be finite".to_string());
+            return Err(GooeyError::InvalidValue(
+                "StereoSampleBuffer samples must be finite".to_string(),
+            ));
         }
 
         Ok(Self {
@@ -63,17 +70,23 @@ impl StereoSampleBuffer {
         samples: &[f32],
         channels: usize,
         sample_rate: f32,
-    ) -> Result<Self, String> {
+    ) -> Result<Self, GooeyError> {
         if channels == 0 {
-            return Err("StereoSampleBuffer requires at least one channel".to_string());
+            return Err(GooeyError::InvalidValue(
+                "StereoSampleBuffer requires at least one channel".to_string(),
+            ));
         }
         if samples.is_empty() {
-            return Err("StereoSampleBuffer requires at least one sample".to_string());
+            return Err(GooeyError::InvalidValue(
+                "StereoSampleBuffer requires at least one sample".to_string(),
+            ));
         }
 
         let frames = samples.len() / channels;
         if frames == 0 {
-            return Err("StereoSampleBuffer requires at least one full frame".to_string());
+            return Err(GooeyError::InvalidValue(
+                "StereoSampleBuffer requires at least one full frame".to_string(),
+            ));
         }
 
         let mut left = Vec::with_capacity(frames);
@@ -95,32 +108,41 @@ impl StereoSampleBuffer {
     /// Mono files are duplicated to both channels; files with more than two
     /// channels keep channels 0 and 1.
     #[cfg(feature = "bounce")]
-    pub fn from_wav(path: impl AsRef<std::path::Path>) -> Result<Self, String> {
+    pub fn from_wav(path: impl AsRef<std::path::Path>) -> Result<Self, GooeyError> {
         let mut reader = hound::WavReader::open(path.as_ref())
-            .map_err(|e| format!("Failed to open WAV: {e}"))?;
+            .map_err(|e| GooeyError::Io(format!("Failed to open WAV: {e}")))?;
         let spec = reader.spec();
         if spec.channels == 0 {
-            return Err("WAV must have at least one channel".to_string());
+            return Err(GooeyError::Io(
+                "WAV must have at least one channel".to_string(),
+            ));
         }
         if spec.sample_rate == 0 {
-            return Err("WAV sample rate must be greater than zero".to_string());
+            return Err(GooeyError::Io(
+                "WAV sample rate must be greater than zero".to_string(),
+            ));
         }
 
         let channels = spec.channels as usize;
         let interleaved = match spec.sample_format {
             hound::SampleFormat::Float => reader
                 .samples::<f32>()
-                .map(|s| s.map_err(|e| format!("Failed to read WAV sample: {e}")))
+                .map(|s| s.map_err(|e| GooeyError::Io(format!("Failed to read WAV sample: {e}"))))
                 .collect::<Result<Vec<_>, _>>()?,
             hound::SampleFormat::Int => match spec.bits_per_sample {
-                0 => return Err("WAV bit depth must be greater than zero".to_string()),
+                0 => {
+                    return Err(GooeyError::Io(
+                        "WAV bit depth must be greater than zero".to_string(),
+                    ))
+                }
                 1..=8 => {
                     let scale = ((1_i32 << (spec.bits_per_sample - 1)) - 1) as f32;
                     reader
                         .samples::<i8>()
                         .map(|s| {
-                            s.map(|v| v as f32 / scale)
-                                .map_err(|e| format!("Failed to read WAV sample: {e}"))
+                            s.map(|v| v as f32 / scale).map_err(|e| {
+                                GooeyError::Io(format!("Failed to read WAV sample: {e}"))
+                            })
                         })
                         .collect::<Result<Vec<_>, _>>()?
                 }
@@ -129,8 +151,9 @@ impl StereoSampleBuffer {
                     reader
                         .samples::<i16>()
                         .map(|s| {
-                            s.map(|v| v as f32 / scale)
-                                .map_err(|e| format!("Failed to read WAV sample: {e}"))
+                            s.map(|v| v as f32 / scale).map_err(|e| {
+                                GooeyError::Io(format!("Failed to read WAV sample: {e}"))
+                            })
                         })
                         .collect::<Result<Vec<_>, _>>()?
                 }
@@ -139,17 +162,18 @@ impl StereoSampleBuffer {
                     reader
                         .samples::<i32>()
                         .map(|s| {
-                            s.map(|v| v as f32 / scale)
-                                .map_err(|e| format!("Failed to read WAV sample: {e}"))
+                            s.map(|v| v as f32 / scale).map_err(|e| {
+                                GooeyError::Io(format!("Failed to read WAV sample: {e}"))
+                            })
                         })
                         .collect::<Result<Vec<_>, _>>()?
                 }
-                bits => return Err(format!("Unsupported WAV bit depth: {bits}")),
+                bits => return Err(GooeyError::Io(format!("Unsupported WAV bit depth: {bits}"))),
             },
         };
 
         if interleaved.is_empty() {
-            return Err("WAV contains no samples".to_string());
+            return Err(GooeyError::Io("WAV contains no samples".to_string()));
         }
 
         Self::from_interleaved(&interleaved, channels, spec.sample_rate as f32)
@@ -25,16 +25,7 @@ use crate::frame::StereoFrame;
 use crate::mixer::loop_channel::LoopWindow;
 use crate::mixer::stereo_buffer::StereoSampleBuffer;
 use crate::utils::raised_sine_window;
-
-/// Fixed synthesis (output-side) hop length, in milliseconds.
-const HOP_MS: f32 = 20.0;
-/// Search tolerance around the ideal analysis position, in milliseconds of
-/// *source* audio (converted to source frames using the buffer's own sample
-/// rate at search time).
-const SEARCH_MS: f32 = 10.0;
-/// Number of steps in the coarse pass of the coarse-to-fine correlation
-/// search. Bounds worst-case search cost regardless of `SEARCH_MS`/sample rate.
-const COARSE_STEPS: usize = 64;
+use crate::utils::time_stretch::{best_grain_start, hop_frames, search_radius};
 
 pub(crate) struct WsolaStretcher {
     /// Output frames produced per hop (fixed for this stretcher's lifetime).
@@ -69,8 +60,7 @@ impl WsolaStretcher {
     /// Build a stretcher sized from the engine's sample rate, with playback
     /// starting at `initial_cursor` (source frames).
     pub(crate) fn new(engine_sample_rate: f32, initial_cursor: f64) -> Self {
-        let sr = engine_sample_rate.max(1.0) as f64;
-        let hop_len = ((HOP_MS as f64 / 1000.0) * sr).round().max(1.0) as usize;
+        let hop_len = hop_frames(engine_sample_rate.max(1.0));
         let window_len = hop_len * 2;
 
         // Periodic Hann (denominator `window_len`, not `window_len - 1`) so
@@ -319,70 +309,23 @@ impl WsolaStretcher {
         step: f64,
         max_start: f64,
     ) -> f64 {
-        let radius = ((SEARCH_MS as f64 / 1000.0) * buffer.sample_rate() as f64)
-            .round()
-            .max(1.0);
+        let radius = search_radius(buffer.sample_rate());
         let lo_bound = (center - radius).max(0.0);
         let hi_bound = (center + radius).min(max_start);
         if hi_bound <= lo_bound {
             return center.clamp(0.0, max_start);
         }
-
-        let score_at = |start: f64| -> f32 {
-            let mut num = 0.0f32;
-            let mut ref_energy = 0.0f32;
-            let mut cand_energy = 0.0f32;
-            for (i, reference) in self.prev_tail_mono.iter().enumerate() {
-                let pos_v = (start + i as f64 * step).clamp(0.0, max_start + step);
-                let raw = buffer.read_wrapped(window.to_physical(pos_v));
-                let cand = raw.l + raw.r;
-                num += cand * reference;
-                ref_energy += reference * reference;
-                cand_energy += cand * cand;
-            }
-            if ref_energy <= f32::EPSILON || cand_energy <= f32::EPSILON {
-                0.0
-            } else {
-                num / (ref_energy.sqrt() * cand_energy.sqrt())
-            }
-        };
-
-        let span = hi_bound - lo_bound;
-        let coarse_stride = (span / COARSE_STEPS as f64).max(1.0);
-
-        let mut best = lo_bound;
-        let mut best_score = f32::MIN;
-        let mut c = lo_bound;
-        while c <= hi_bound {
-            let s = score_at(c);
-            if s > best_score {
-                best_score = s;
-                best = c;
-            }
-            c += coarse_stride;
-        }
-
-        let refine_lo = (best - coarse_stride).max(lo_bound);
-        let refine_hi = (best + coarse_stride).min(hi_bound);
-        let mut c = refine_lo;
-        while c <= refine_hi {
-            let s = score_at(c);
-            if s > best_score {
-                best_score = s;
-                best = c;
-            }
-            c += 1.0;
-        }
-
-        best
+        best_grain_start(&self.prev_tail_mono, lo_bound, hi_bound, step, |pos_v| {
+            let raw = buffer.read_wrapped(window.to_physical(pos_v.clamp(0.0, max_start + step)));
+            raw.l + raw.r
+        })
     }
 
-    /// Coarse-to-fine normalized cross-correlation search for the source
-    /// position (within `[center - Δ, center + Δ]`, clamped to
-    /// `[loop_lo, max_start]`) whose next `hop_len` samples best match
-    /// `prev_tail_mono`. Cost is bounded to `~(COARSE_STEPS + 2*refine)`
-    /// candidate evaluations regardless of `Δ`, so it stays well inside the
-    /// per-hop real-time budget.
+    /// Normalized cross-correlation search (see [`best_grain_start`]) for the
+    /// source position within `[center - Δ, center + Δ]`, clamped to
+    /// `[loop_lo, max_start]`, whose next `hop_len` samples best match
+    /// `prev_tail_mono`. The cost is bounded regardless of `Δ`, so it stays
+    /// well inside the per-hop real-time budget.
     fn search_best_start(
         &self,
         buffer: &StereoSampleBuffer,
@@ -391,62 +334,16 @@ impl WsolaStretcher {
         loop_lo: f64,
         max_start: f64,
     ) -> f64 {
-        let radius = ((SEARCH_MS as f64 / 1000.0) * buffer.sample_rate() as f64)
-            .round()
-            .max(1.0);
+        let radius = search_radius(buffer.sample_rate());
         let lo_bound = (center - radius).max(loop_lo);
         let hi_bound = (center + radius).min(max_start);
         if hi_bound <= lo_bound {
             return center.clamp(loop_lo, max_start);
         }
-
-        let score_at = |start: f64| -> f32 {
-            let mut num = 0.0f32;
-            let mut ref_energy = 0.0f32;
-            let mut cand_energy = 0.0f32;
-            for (i, reference) in self.prev_tail_mono.iter().enumerate() {
-                let pos = (start + i as f64 * step).clamp(loop_lo, max_start + step);
-                let raw = buffer.read_interpolated(pos);
-                let cand = raw.l + raw.r;
-                num += cand * reference;
-                ref_energy += reference * reference;
-                cand_energy += cand * cand;
-            }
-            if ref_energy <= f32::EPSILON || cand_energy <= f32::EPSILON {
-                0.0
-            } else {
-                num / (ref_energy.sqrt() * cand_energy.sqrt())
-            }
-        };
-
-        let span = hi_bound - lo_bound;
-        let coarse_stride = (span / COARSE_STEPS as f64).max(1.0);
-
-        let mut best = lo_bound;
-        let mut best_score = f32::MIN;
-        let mut c = lo_bound;
-        while c <= hi_bound {
-            let s = score_at(c);
-            if s > best_score {
-                best_score = s;
-                best = c;
-            }
-            c += coarse_stride;
-        }
-
-        let refine_lo = (best - coarse_stride).max(lo_bound);
-        let refine_hi = (best + coarse_stride).min(hi_bound);
-        let mut c = refine_lo;
-        while c <= refine_hi {
-            let s = score_at(c);
-            if s > best_score {
-                best_score = s;
-                best = c;
-            }
-            c += 1.0;
-        }
-
-        best
+        best_grain_start(&self.prev_tail_mono, lo_bound, hi_bound, step, |pos| {
+            let raw = buffer.read_interpolated(pos.clamp(loop_lo, max_start + step));
+            raw.l + raw.r
+        })
     }
 }
 
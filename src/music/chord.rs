@@ -1,4 +1,6 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use core::fmt;
 
 use super::interval::Interval;
 use super::note::{note_to_midi, NoteName};
@@ -1,4 +1,6 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use core::fmt;
 
 use super::chord::{Chord, ChordQuality};
 use super::note::NoteName;
@@ -2,11 +2,15 @@ pub mod chord;
 pub mod interval;
 pub mod key;
 pub mod note;
+pub mod quantize;
 pub mod scale;
+pub mod tuning;
 pub mod voicing;
 
 pub use self::chord::{Chord, ChordQuality};
 pub use self::key::Key;
 pub use self::note::{midi_to_freq, midi_to_note, midi_to_string, note_to_midi, NoteName};
+pub use self::quantize::{quantize_to_scale, Scale};
 pub use self::scale::ScaleType;
+pub use self::tuning::MasterTuning;
 pub use self::voicing::{apply_voicing, available_voicings, VoicingType};
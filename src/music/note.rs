@@ -1,4 +1,6 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use core::fmt;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum NoteName {
@@ -56,6 +58,28 @@ impl NoteName {
     pub fn transpose(self, semitones: u8) -> Self {
         Self::from_index(self.to_index().wrapping_add(semitones) % 12)
     }
+
+    /// Parse a note name like `a`, `C#`, `f#`, or `Bb` (case-insensitive).
+    pub fn parse(name: &str) -> Option<Self> {
+        let mut chars = name.chars();
+        let natural = match chars.next()?.to_ascii_lowercase() {
+            'c' => 0,
+            'd' => 2,
+            'e' => 4,
+            'f' => 5,
+            'g' => 7,
+            'a' => 9,
+            'b' => 11,
+            _ => return None,
+        };
+        let index = match chars.as_str() {
+            "" => natural,
+            "#" | "s" => natural + 1,
+            "b" => natural + 11,
+            _ => return None,
+        };
+        Some(Self::from_index(index))
+    }
 }
 
 impl fmt::Display for NoteName {
@@ -135,6 +159,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_note_name() {
+        assert_eq!(NoteName::parse("a"), Some(NoteName::A));
+        assert_eq!(NoteName::parse("F#"), Some(NoteName::Fs));
+        assert_eq!(NoteName::parse("Bb"), Some(NoteName::As));
+        assert_eq!(NoteName::parse("cb"), Some(NoteName::B));
+        assert_eq!(NoteName::parse("h"), None);
+    }
+
     #[test]
     fn test_transpose() {
         assert_eq!(NoteName::C.transpose(4), NoteName::E);
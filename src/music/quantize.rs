@@ -0,0 +1,200 @@
+use core::fmt;
+
+use super::note::NoteName;
+use super::scale::ScaleType;
+
+/// A scale used to snap pitched notes into a key.
+///
+/// Unlike [`ScaleType`], which only covers the heptatonic scales the chord
+/// builder understands, these may have any number of degrees.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Scale {
+    Major,
+    NaturalMinor,
+    HarmonicMinor,
+    MelodicMinor,
+    Dorian,
+    Phrygian,
+    Lydian,
+    Mixolydian,
+    Locrian,
+    MajorPentatonic,
+    MinorPentatonic,
+    Blues,
+    Chromatic,
+}
+
+impl Scale {
+    pub const ALL: [Scale; 13] = [
+        Scale::Major,
+        Scale::NaturalMinor,
+        Scale::HarmonicMinor,
+        Scale::MelodicMinor,
+        Scale::Dorian,
+        Scale::Phrygian,
+        Scale::Lydian,
+        Scale::Mixolydian,
+        Scale::Locrian,
+        Scale::MajorPentatonic,
+        Scale::MinorPentatonic,
+        Scale::Blues,
+        Scale::Chromatic,
+    ];
+
+    /// Semitone offsets from the root, ascending, starting at 0.
+    pub fn intervals(self) -> &'static [u8] {
+        match self {
+            Scale::Major => &[0, 2, 4, 5, 7, 9, 11],
+            Scale::NaturalMinor => &[0, 2, 3, 5, 7, 8, 10],
+            Scale::HarmonicMinor => &[0, 2, 3, 5, 7, 8, 11],
+            Scale::MelodicMinor => &[0, 2, 3, 5, 7, 9, 11],
+            Scale::Dorian => &[0, 2, 3, 5, 7, 9, 10],
+            Scale::Phrygian => &[0, 1, 3, 5, 7, 8, 10],
+            Scale::Lydian => &[0, 2, 4, 6, 7, 9, 11],
+            Scale::Mixolydian => &[0, 2, 4, 5, 7, 9, 10],
+            Scale::Locrian => &[0, 1, 3, 5, 6, 8, 10],
+            Scale::MajorPentatonic => &[0, 2, 4, 7, 9],
+            Scale::MinorPentatonic => &[0, 3, 5, 7, 10],
+            Scale::Blues => &[0, 3, 5, 6, 7, 10],
+            Scale::Chromatic => &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+        }
+    }
+
+    /// Whether `pitch_class` (0-11, relative to C) belongs to this scale on `root`.
+    pub fn contains(self, root: NoteName, pitch_class: u8) -> bool {
+        let degree = (pitch_class % 12 + 12 - root.to_index()) % 12;
+        self.intervals().contains(&degree)
+    }
+
+    /// Parse a scale name such as `minor`, `dorian`, or `minor_pentatonic`.
+    pub fn parse(name: &str) -> Option<Self> {
+        let scale = match name.to_ascii_lowercase().replace('-', "_").as_str() {
+            "major" | "maj" | "ionian" => Scale::Major,
+            "minor" | "min" | "natural_minor" | "aeolian" => Scale::NaturalMinor,
+            "harmonic_minor" => Scale::HarmonicMinor,
+            "melodic_minor" => Scale::MelodicMinor,
+            "dorian" => Scale::Dorian,
+            "phrygian" => Scale::Phrygian,
+            "lydian" => Scale::Lydian,
+            "mixolydian" => Scale::Mixolydian,
+            "locrian" => Scale::Locrian,
+            "major_pentatonic" | "pentatonic" => Scale::MajorPentatonic,
+            "minor_pentatonic" => Scale::MinorPentatonic,
+            "blues" => Scale::Blues,
+            "chromatic" => Scale::Chromatic,
+            _ => return None,
+        };
+        Some(scale)
+    }
+}
+
+impl From<ScaleType> for Scale {
+    fn from(scale_type: ScaleType) -> Self {
+        match scale_type {
+            ScaleType::Major => Scale::Major,
+            ScaleType::NaturalMinor => Scale::NaturalMinor,
+        }
+    }
+}
+
+impl fmt::Display for Scale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Scale::Major => "Major",
+            Scale::NaturalMinor => "Minor",
+            Scale::HarmonicMinor => "Harmonic Minor",
+            Scale::MelodicMinor => "Melodic Minor",
+            Scale::Dorian => "Dorian",
+            Scale::Phrygian => "Phrygian",
+            Scale::Lydian => "Lydian",
+            Scale::Mixolydian => "Mixolydian",
+            Scale::Locrian => "Locrian",
+            Scale::MajorPentatonic => "Major Pentatonic",
+            Scale::MinorPentatonic => "Minor Pentatonic",
+            Scale::Blues => "Blues",
+            Scale::Chromatic => "Chromatic",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Snap a MIDI note to the nearest note of `scale` on `root`.
+///
+/// Notes already in the scale are returned unchanged. Otherwise the closest
+/// scale note is chosen, preferring the lower one on a tie, and the result is
+/// kept within the MIDI range (0-127).
+pub fn quantize_to_scale(midi_note: u8, root: NoteName, scale: Scale) -> u8 {
+    let note = midi_note.min(127);
+    for distance in 0..12u8 {
+        if let Some(below) = note.checked_sub(distance) {
+            if scale.contains(root, below % 12) {
+                return below;
+            }
+        }
+        let above = note + distance;
+        if above <= 127 && scale.contains(root, above % 12) {
+            return above;
+        }
+    }
+    note
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_scale_notes_unchanged() {
+        // A minor: A B C D E F G
+        for midi in [57u8, 59, 60, 62, 64, 65, 67, 69] {
+            assert_eq!(
+                quantize_to_scale(midi, NoteName::A, Scale::NaturalMinor),
+                midi
+            );
+        }
+    }
+
+    #[test]
+    fn test_out_of_scale_snaps_to_nearest() {
+        // C# -> C (tie between C and D goes down), F# -> F (tie F/G goes down)
+        assert_eq!(quantize_to_scale(61, NoteName::A, Scale::NaturalMinor), 60);
+        assert_eq!(quantize_to_scale(66, NoteName::A, Scale::NaturalMinor), 65);
+        // C major pentatonic (C D E G A): F (65) is nearer E (64) than G (67)
+        assert_eq!(
+            quantize_to_scale(65, NoteName::C, Scale::MajorPentatonic),
+            64
+        );
+        // B (71) is nearer C (72) than A (69)
+        assert_eq!(
+            quantize_to_scale(71, NoteName::C, Scale::MajorPentatonic),
+            72
+        );
+    }
+
+    #[test]
+    fn test_quantize_stays_in_midi_range() {
+        // 127 is G9; in C# major the nearest scale notes are F#9 (126) and
+        // G#9 (128, out of range), so it snaps down.
+        assert_eq!(quantize_to_scale(127, NoteName::Cs, Scale::Major), 126);
+        // Nothing lies below 0, so D major pentatonic snaps C-1 up to D-1.
+        assert_eq!(quantize_to_scale(0, NoteName::D, Scale::MajorPentatonic), 2);
+    }
+
+    #[test]
+    fn test_chromatic_is_identity() {
+        for midi in 0..=127u8 {
+            assert_eq!(quantize_to_scale(midi, NoteName::E, Scale::Chromatic), midi);
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Scale::parse("minor"), Some(Scale::NaturalMinor));
+        assert_eq!(
+            Scale::parse("Minor-Pentatonic"),
+            Some(Scale::MinorPentatonic)
+        );
+        assert_eq!(Scale::parse("dorian"), Some(Scale::Dorian));
+        assert_eq!(Scale::parse("bebop"), None);
+    }
+}
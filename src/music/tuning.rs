@@ -0,0 +1,93 @@
+//! Kit-wide pitch reference: A4 frequency and transpose
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Concert pitch: the A4 reference every instrument is voiced against
+pub const DEFAULT_A4_HZ: f32 = 440.0;
+/// A4 references accepted by [`MasterTuning`] (covers baroque 415 Hz and
+/// the common 432-446 Hz orchestral pitches)
+pub const A4_HZ_RANGE: (f32, f32) = (400.0, 480.0);
+/// Transposes accepted by [`MasterTuning`], in semitones
+pub const TRANSPOSE_RANGE: (f32, f32) = (-24.0, 24.0);
+
+/// A4 reference plus a transpose in (fractional) semitones, shared by every
+/// pitched instrument so a whole kit can be retuned at once.
+///
+/// Instruments apply [`ratio`](Self::ratio) on top of their own tuning, so
+/// the default (A4 = 440 Hz, no transpose) leaves them unchanged.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MasterTuning {
+    a4_hz: f32,
+    transpose: f32,
+}
+
+impl MasterTuning {
+    /// Clamps both values into [`A4_HZ_RANGE`] and [`TRANSPOSE_RANGE`];
+    /// non-finite values take the default.
+    pub fn new(a4_hz: f32, transpose: f32) -> Self {
+        let a4_hz = if a4_hz.is_finite() {
+            a4_hz.clamp(A4_HZ_RANGE.0, A4_HZ_RANGE.1)
+        } else {
+            DEFAULT_A4_HZ
+        };
+        let transpose = if transpose.is_finite() {
+            transpose.clamp(TRANSPOSE_RANGE.0, TRANSPOSE_RANGE.1)
+        } else {
+            0.0
+        };
+        Self { a4_hz, transpose }
+    }
+
+    /// A4 reference in Hz.
+    pub fn a4_hz(&self) -> f32 {
+        self.a4_hz
+    }
+
+    /// Transpose in semitones.
+    pub fn transpose(&self) -> f32 {
+        self.transpose
+    }
+
+    /// Frequency multiplier relative to A4 = 440 Hz with no transpose.
+    pub fn ratio(&self) -> f32 {
+        self.a4_hz / DEFAULT_A4_HZ * 2.0_f32.powf(self.transpose / 12.0)
+    }
+
+    /// Frequency of a MIDI note under this tuning.
+    pub fn midi_to_freq(&self, note: u8) -> f32 {
+        self.a4_hz * 2.0_f32.powf((note as f32 - 69.0 + self.transpose) / 12.0)
+    }
+}
+
+impl Default for MasterTuning {
+    fn default() -> Self {
+        Self {
+            a4_hz: DEFAULT_A4_HZ,
+            transpose: 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_unity() {
+        let tuning = MasterTuning::default();
+        assert_eq!(tuning.ratio(), 1.0);
+        assert_eq!(tuning.midi_to_freq(69), 440.0);
+    }
+
+    #[test]
+    fn test_reference_and_transpose_combine() {
+        let tuning = MasterTuning::new(432.0, 12.0);
+        assert!((tuning.ratio() - 432.0 / 440.0 * 2.0).abs() < 1e-5);
+        assert!((tuning.midi_to_freq(57) - 432.0).abs() < 1e-3);
+        // Out-of-range and non-finite values are tamed
+        let wild = MasterTuning::new(1000.0, f32::NAN);
+        assert_eq!(wild.a4_hz(), A4_HZ_RANGE.1);
+        assert_eq!(wild.transpose(), 0.0);
+    }
+}
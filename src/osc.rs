@@ -0,0 +1,454 @@
+//! OSC control surface (requires the `osc` feature)
+//!
+//! Makes a [`GooeyEngine`]'s instruments addressable over OSC, so control
+//! surfaces such as TouchOSC or Max can drive it directly:
+//!
+//! - `/gooey/<instrument>/trigger [velocity]` triggers an instrument. A
+//!   velocity of 0 (a button release) is ignored; no argument means 1.0.
+//! - `/gooey/<instrument>/<param> <value>` sets a parameter, in the same
+//!   normalized (or choice-index) form as the `gooey_engine_set_*_param`
+//!   setters.
+//!
+//! Instrument and parameter names come from the [`crate::param_info`]
+//! registry, e.g. `/gooey/kick/frequency 0.5` or `/gooey/fm_snap/decay 0.2`.
+//! Triggers and parameter writes go through the engine's thread-safe paths, so
+//! the [`OscServer`] thread can run alongside the render thread.
+
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::error::GooeyError;
+use crate::ffi::*;
+use crate::param_info::{instrument_name, instrument_params};
+
+/// Address prefix every engine address starts with.
+pub const OSC_ADDRESS_PREFIX: &str = "/gooey";
+
+/// Largest UDP datagram the server reads.
+const MAX_PACKET_SIZE: usize = 8192;
+
+/// How often the server thread checks whether it should shut down.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// One OSC argument.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OscArg {
+    Int(i32),
+    Float(f32),
+    Double(f64),
+    String(String),
+    Bool(bool),
+}
+
+impl OscArg {
+    /// Numeric value of the argument (booleans are 0 or 1).
+    pub fn as_f32(&self) -> Option<f32> {
+        match *self {
+            OscArg::Int(value) => Some(value as f32),
+            OscArg::Float(value) => Some(value),
+            OscArg::Double(value) => Some(value as f32),
+            OscArg::Bool(value) => Some(if value { 1.0 } else { 0.0 }),
+            OscArg::String(_) => None,
+        }
+    }
+}
+
+/// An OSC message: an address pattern and its arguments.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OscMessage {
+    pub address: String,
+    pub args: Vec<OscArg>,
+}
+
+impl OscMessage {
+    pub fn new(address: &str, args: Vec<OscArg>) -> Self {
+        Self {
+            address: address.to_string(),
+            args,
+        }
+    }
+
+    /// Encode as an OSC 1.0 packet.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_padded_str(&mut out, &self.address);
+        let mut tags = String::from(",");
+        for arg in &self.args {
+            tags.push(match arg {
+                OscArg::Int(_) => 'i',
+                OscArg::Float(_) => 'f',
+                OscArg::Double(_) => 'd',
+                OscArg::String(_) => 's',
+                OscArg::Bool(true) => 'T',
+                OscArg::Bool(false) => 'F',
+            });
+        }
+        write_padded_str(&mut out, &tags);
+        for arg in &self.args {
+            match arg {
+                OscArg::Int(value) => out.extend_from_slice(&value.to_be_bytes()),
+                OscArg::Float(value) => out.extend_from_slice(&value.to_be_bytes()),
+                OscArg::Double(value) => out.extend_from_slice(&value.to_be_bytes()),
+                OscArg::String(value) => write_padded_str(&mut out, value),
+                OscArg::Bool(_) => {}
+            }
+        }
+        out
+    }
+}
+
+fn write_padded_str(out: &mut Vec<u8>, value: &str) {
+    out.extend_from_slice(value.as_bytes());
+    out.push(0);
+    while !out.len().is_multiple_of(4) {
+        out.push(0);
+    }
+}
+
+/// Reads 4-byte-aligned OSC fields from a packet.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], GooeyError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| GooeyError::invalid("OSC packet is truncated"))?;
+        let field = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(field)
+    }
+
+    fn word(&mut self) -> Result<[u8; 4], GooeyError> {
+        Ok(self.take(4)?.try_into().unwrap_or_default())
+    }
+
+    fn string(&mut self) -> Result<&'a str, GooeyError> {
+        let rest = &self.bytes[self.pos..];
+        let len = rest
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or_else(|| GooeyError::invalid("OSC string is not terminated"))?;
+        let padded = (len + 4) & !3;
+        let field = self.take(padded)?;
+        std::str::from_utf8(&field[..len])
+            .map_err(|_| GooeyError::invalid("OSC string is not UTF-8"))
+    }
+}
+
+/// Decode an OSC packet (a message or a bundle, possibly nested) into its
+/// messages. Bundle time tags are ignored; messages apply on arrival.
+pub fn decode_packet(bytes: &[u8]) -> Result<Vec<OscMessage>, GooeyError> {
+    let mut messages = Vec::new();
+    decode_into(bytes, &mut messages)?;
+    Ok(messages)
+}
+
+fn decode_into(bytes: &[u8], messages: &mut Vec<OscMessage>) -> Result<(), GooeyError> {
+    let mut reader = Reader { bytes, pos: 0 };
+    if bytes.starts_with(b"#bundle\0") {
+        reader.take(16)?; // "#bundle" + time tag
+        while reader.pos < bytes.len() {
+            let len = u32::from_be_bytes(reader.word()?) as usize;
+            decode_into(reader.take(len)?, messages)?;
+        }
+        return Ok(());
+    }
+
+    let address = reader.string()?;
+    if !address.starts_with('/') {
+        return Err(GooeyError::InvalidValue(format!(
+            "OSC address '{address}' does not start with '/'"
+        )));
+    }
+    // Type tags are optional in old implementations; no tags means no args
+    let tags = if reader.pos < bytes.len() {
+        reader.string()?
+    } else {
+        ","
+    };
+    let Some(tags) = tags.strip_prefix(',') else {
+        return Err(GooeyError::InvalidValue(format!(
+            "{address}: OSC type tags must start with ','"
+        )));
+    };
+
+    let mut args = Vec::with_capacity(tags.len());
+    for tag in tags.chars() {
+        args.push(match tag {
+            'i' => OscArg::Int(i32::from_be_bytes(reader.word()?)),
+            'f' => OscArg::Float(f32::from_be_bytes(reader.word()?)),
+            'd' => {
+                let mut bits = [0; 8];
+                bits.copy_from_slice(reader.take(8)?);
+                OscArg::Double(f64::from_be_bytes(bits))
+            }
+            's' => OscArg::String(reader.string()?.to_string()),
+            'T' => OscArg::Bool(true),
+            'F' => OscArg::Bool(false),
+            other => {
+                return Err(GooeyError::InvalidValue(format!(
+                    "{address}: unsupported OSC type tag '{other}'"
+                )))
+            }
+        });
+    }
+    messages.push(OscMessage {
+        address: address.to_string(),
+        args,
+    });
+    Ok(())
+}
+
+/// An engine action addressed by an OSC message.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OscCommand {
+    /// Trigger `instrument` (an `INSTRUMENT_*` ID) at `velocity`.
+    Trigger { instrument: u32, velocity: f32 },
+    /// Set `param` (a `*_PARAM_*` index) of `instrument`.
+    SetParam {
+        instrument: u32,
+        param: u32,
+        value: f32,
+    },
+}
+
+/// Map a message to an engine command using the parameter registry.
+///
+/// Returns `Ok(None)` for messages that are understood but do nothing (a
+/// trigger with velocity 0, i.e. a button release).
+pub fn route(message: &OscMessage) -> Result<Option<OscCommand>, GooeyError> {
+    let address = message.address.as_str();
+    let path = address
+        .strip_prefix(OSC_ADDRESS_PREFIX)
+        .and_then(|rest| rest.strip_prefix('/'))
+        .ok_or_else(|| {
+            GooeyError::InvalidValue(format!("{address}: not under {OSC_ADDRESS_PREFIX}/"))
+        })?;
+    let (name, action) = path.split_once('/').ok_or_else(|| {
+        GooeyError::InvalidValue(format!(
+            "{address}: expected {OSC_ADDRESS_PREFIX}/<instrument>/<action>"
+        ))
+    })?;
+    let instrument = (0..INSTRUMENT_COUNT)
+        .find(|&id| instrument_name(id) == Some(name))
+        .ok_or_else(|| GooeyError::UnknownInstrument(name.to_string()))?;
+    let value = match message.args.first() {
+        Some(arg) => Some(arg.as_f32().ok_or_else(|| {
+            GooeyError::InvalidValue(format!("{address}: argument must be numeric"))
+        })?),
+        None => None,
+    };
+
+    if action == "trigger" {
+        let velocity = value.unwrap_or(1.0);
+        return Ok((velocity > 0.0).then_some(OscCommand::Trigger {
+            instrument,
+            velocity,
+        }));
+    }
+
+    let param = instrument_params(instrument)
+        .iter()
+        .find(|info| info.name() == action)
+        .ok_or_else(|| GooeyError::UnknownParameter(format!("{name}.{action}")))?;
+    let value =
+        value.ok_or_else(|| GooeyError::InvalidValue(format!("{address}: missing value")))?;
+    Ok(Some(OscCommand::SetParam {
+        instrument,
+        param: param.index,
+        value,
+    }))
+}
+
+/// Apply a routed command to `engine`.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`.
+pub unsafe fn apply(engine: *mut GooeyEngine, command: OscCommand) -> GooeyResult {
+    match command {
+        OscCommand::Trigger {
+            instrument,
+            velocity,
+        } => gooey_engine_trigger_instrument_with_velocity(engine, instrument, velocity),
+        OscCommand::SetParam {
+            instrument,
+            param,
+            value,
+        } => set_instrument_type_param(engine, instrument, param, value),
+    }
+}
+
+/// Decode, route and apply every message in `packet`. Problems are reported
+/// as warnings and skip only the offending message.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by `gooey_engine_new`.
+pub unsafe fn handle_packet(engine: *mut GooeyEngine, packet: &[u8]) {
+    let messages = match decode_packet(packet) {
+        Ok(messages) => messages,
+        Err(e) => {
+            eprintln!("Warning: {}", e);
+            return;
+        }
+    };
+    for message in &messages {
+        match route(message) {
+            Ok(Some(command)) => {
+                apply(engine, command);
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("Warning: {}", e),
+        }
+    }
+}
+
+/// Engine pointer handed to the server thread.
+struct EnginePtr(*mut GooeyEngine);
+
+// SAFETY: the server only uses the engine's thread-safe trigger and
+// parameter paths; `OscServer::bind` requires the engine to outlive it.
+unsafe impl Send for EnginePtr {}
+
+/// Listens for OSC over UDP and applies it to an engine on its own thread.
+///
+/// Dropping the server stops the thread.
+pub struct OscServer {
+    local_addr: SocketAddr,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl OscServer {
+    /// Bind a UDP socket at `addr` (e.g. `"0.0.0.0:9000"`) and start serving
+    /// `engine`.
+    ///
+    /// # Safety
+    /// `engine` must be a valid pointer returned by `gooey_engine_new` and
+    /// must not be freed until the server is dropped.
+    pub unsafe fn bind(
+        engine: *mut GooeyEngine,
+        addr: impl ToSocketAddrs,
+    ) -> std::io::Result<Self> {
+        let socket = UdpSocket::bind(addr)?;
+        socket.set_read_timeout(Some(POLL_INTERVAL))?;
+        let local_addr = socket.local_addr()?;
+
+        let running = Arc::new(AtomicBool::new(true));
+        let thread_running = Arc::clone(&running);
+        let engine = EnginePtr(engine);
+        let thread = std::thread::Builder::new()
+            .name("gooey-osc".into())
+            .spawn(move || {
+                let engine = engine;
+                let mut buffer = [0u8; MAX_PACKET_SIZE];
+                while thread_running.load(Ordering::Relaxed) {
+                    match socket.recv_from(&mut buffer) {
+                        Ok((len, _)) => handle_packet(engine.0, &buffer[..len]),
+                        Err(e)
+                            if matches!(
+                                e.kind(),
+                                std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                            ) => {}
+                        Err(e) => eprintln!("Warning: OSC receive failed: {}", e),
+                    }
+                }
+            })?;
+
+        Ok(Self {
+            local_addr,
+            running,
+            thread: Some(thread),
+        })
+    }
+
+    /// Address the server is listening on (useful after binding port 0).
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+impl Drop for OscServer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_round_trip() {
+        let message = OscMessage::new(
+            "/gooey/kick/frequency",
+            vec![
+                OscArg::Float(0.5),
+                OscArg::Int(3),
+                OscArg::String("abc".into()),
+                OscArg::Bool(true),
+                OscArg::Double(0.25),
+            ],
+        );
+        let bytes = message.to_bytes();
+        assert_eq!(bytes.len() % 4, 0);
+        assert_eq!(decode_packet(&bytes), Ok(vec![message]));
+    }
+
+    #[test]
+    fn test_bundle_is_flattened() {
+        let a = OscMessage::new("/gooey/kick/trigger", vec![]);
+        let b = OscMessage::new("/gooey/snare/trigger", vec![OscArg::Float(0.5)]);
+        let mut bundle = b"#bundle\0".to_vec();
+        bundle.extend_from_slice(&1u64.to_be_bytes());
+        for message in [&a, &b] {
+            let bytes = message.to_bytes();
+            bundle.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+            bundle.extend_from_slice(&bytes);
+        }
+        assert_eq!(decode_packet(&bundle), Ok(vec![a, b]));
+    }
+
+    #[test]
+    fn test_truncated_packet_is_rejected() {
+        let bytes = OscMessage::new("/gooey/kick/decay", vec![OscArg::Float(0.5)]).to_bytes();
+        assert!(decode_packet(&bytes[..bytes.len() - 2]).is_err());
+    }
+
+    #[test]
+    fn test_route_uses_registry_names() {
+        let set = OscMessage::new("/gooey/kick/frequency", vec![OscArg::Float(0.5)]);
+        assert_eq!(
+            route(&set),
+            Ok(Some(OscCommand::SetParam {
+                instrument: INSTRUMENT_KICK,
+                param: KICK_PARAM_FREQUENCY,
+                value: 0.5,
+            }))
+        );
+        let trigger = OscMessage::new("/gooey/fm_snap/trigger", vec![]);
+        assert_eq!(
+            route(&trigger),
+            Ok(Some(OscCommand::Trigger {
+                instrument: INSTRUMENT_FM_SNAP,
+                velocity: 1.0,
+            }))
+        );
+        let release = OscMessage::new("/gooey/kick/trigger", vec![OscArg::Float(0.0)]);
+        assert_eq!(route(&release), Ok(None));
+
+        for bad in ["/gooey/triangle/trigger", "/gooey/kick/nope", "/other/kick"] {
+            assert!(route(&OscMessage::new(bad, vec![OscArg::Float(1.0)])).is_err());
+        }
+    }
+}
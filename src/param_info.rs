@@ -0,0 +1,1708 @@
+//! Parameter metadata registry
+//!
+//! Reifies the FFI `*_PARAM_*` indices into data so hosts can build controls
+//! (names, display ranges, units, defaults, modulation capability) instead of
+//! hard-coding enums that must be kept in sync with this crate.
+//!
+//! Values passed to the per-instrument setters are normalized 0.0-1.0 (or a
+//! choice index for [`ParamUnit::Choice`]); `min`/`max` describe the
+//! denormalized display range in `unit`.
+
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::sync::OnceLock;
+
+use crate::ffi::*;
+use crate::instruments::{
+    bass, cowbell, fm_snap, hihat2, kick, rimshot, shaker, snare, BassConfig, CowbellConfig,
+    FmSnapConfig, HiHat2Config, KickConfig, RimshotConfig, ShakerConfig, SnareConfig, Tom2Config,
+    VelocityRouting,
+};
+
+/// Unit: plain 0-1 amount.
+pub const PARAM_UNIT_NORMALIZED: u32 = 0;
+/// Unit: frequency in Hz.
+pub const PARAM_UNIT_HZ: u32 = 1;
+/// Unit: time in seconds.
+pub const PARAM_UNIT_SECONDS: u32 = 2;
+/// Unit: time in milliseconds.
+pub const PARAM_UNIT_MILLISECONDS: u32 = 3;
+/// Unit: pitch offset in semitones.
+pub const PARAM_UNIT_SEMITONES: u32 = 4;
+/// Unit: dimensionless ratio / curve shape.
+pub const PARAM_UNIT_RATIO: u32 = 5;
+/// Unit: pitch offset in cents.
+pub const PARAM_UNIT_CENTS: u32 = 6;
+/// Unit: discrete choice index (the setter takes the index, not 0-1).
+pub const PARAM_UNIT_CHOICE: u32 = 7;
+
+/// Display unit of a parameter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParamUnit {
+    Normalized,
+    Hz,
+    Seconds,
+    Milliseconds,
+    Semitones,
+    Ratio,
+    Cents,
+    Choice,
+}
+
+impl ParamUnit {
+    pub fn as_u32(self) -> u32 {
+        match self {
+            Self::Normalized => PARAM_UNIT_NORMALIZED,
+            Self::Hz => PARAM_UNIT_HZ,
+            Self::Seconds => PARAM_UNIT_SECONDS,
+            Self::Milliseconds => PARAM_UNIT_MILLISECONDS,
+            Self::Semitones => PARAM_UNIT_SEMITONES,
+            Self::Ratio => PARAM_UNIT_RATIO,
+            Self::Cents => PARAM_UNIT_CENTS,
+            Self::Choice => PARAM_UNIT_CHOICE,
+        }
+    }
+
+    /// Short unit suffix for display ("" for unitless values).
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Normalized | Self::Ratio | Self::Choice => "",
+            Self::Hz => "Hz",
+            Self::Seconds => "s",
+            Self::Milliseconds => "ms",
+            Self::Semitones => "st",
+            Self::Cents => "ct",
+        }
+    }
+}
+
+/// Metadata for one instrument parameter.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParamInfo {
+    /// FFI parameter index (`KICK_PARAM_*`, `SNARE_PARAM_*`, ...).
+    pub index: u32,
+    /// Nul-terminated so it can be handed to C without allocating.
+    name: &'static str,
+    /// Display range minimum, in `unit`.
+    pub min: f32,
+    /// Display range maximum, in `unit`.
+    pub max: f32,
+    pub unit: ParamUnit,
+    /// Default value in setter space (normalized 0-1, or a choice index).
+    pub default: f32,
+    /// Whether the parameter can be an LFO route target.
+    pub modulatable: bool,
+}
+
+impl ParamInfo {
+    /// Snake-case identifier, e.g. `"filter_cutoff"`.
+    pub fn name(&self) -> &'static str {
+        self.name.trim_end_matches('\0')
+    }
+
+    /// Nul-terminated identifier for C callers.
+    pub fn c_name(&self) -> &'static CStr {
+        CStr::from_bytes_with_nul(self.name.as_bytes()).unwrap_or_default()
+    }
+
+    /// Range accepted by the setters: [`NORMALIZED_RANGE`], or `min..=max`
+    /// for choice parameters.
+    pub fn setter_range(&self) -> (f32, f32) {
+        match self.unit {
+            ParamUnit::Choice => (self.min, self.max),
+            _ => NORMALIZED_RANGE,
+        }
+    }
+
+    /// Clamp `value` into [`ParamInfo::setter_range`]. Non-finite values fall
+    /// back to the default.
+    pub fn clamp(&self, value: f32) -> f32 {
+        if !value.is_finite() {
+            return self.default;
+        }
+        let (min, max) = self.setter_range();
+        value.clamp(min, max)
+    }
+}
+
+/// Setter-space range of every non-choice parameter, and of the normalized
+/// fields of the instrument configs.
+pub const NORMALIZED_RANGE: (f32, f32) = (0.0, 1.0);
+
+/// Range of the [`Tom2Config`] fields, which use the Max patch's 0-100 scale.
+pub const TOM_CONFIG_RANGE: (f32, f32) = (0.0, 100.0);
+
+const fn param(
+    index: u32,
+    name: &'static str,
+    min: f32,
+    max: f32,
+    unit: ParamUnit,
+    default: f32,
+    modulatable: bool,
+) -> ParamInfo {
+    ParamInfo {
+        index,
+        name,
+        min,
+        max,
+        unit,
+        default,
+        modulatable,
+    }
+}
+
+/// Tuning is shared by every instrument: 0 = -12 st, 0.5 = neutral, 1 = +12 st.
+const fn tuning(index: u32) -> ParamInfo {
+    param(
+        index,
+        "tuning\0",
+        -12.0,
+        12.0,
+        ParamUnit::Semitones,
+        0.5,
+        true,
+    )
+}
+
+/// Lower-case instrument name for an `INSTRUMENT_*` ID.
+pub fn instrument_name(instrument: u32) -> Option<&'static str> {
+    match instrument {
+        INSTRUMENT_KICK => Some("kick"),
+        INSTRUMENT_SNARE => Some("snare"),
+        INSTRUMENT_HIHAT => Some("hihat"),
+        INSTRUMENT_TOM => Some("tom"),
+        INSTRUMENT_BASS => Some("bass"),
+        INSTRUMENT_FM_SNAP => Some("fm_snap"),
+        INSTRUMENT_RIMSHOT => Some("rimshot"),
+        INSTRUMENT_COWBELL => Some("cowbell"),
+        INSTRUMENT_SHAKER => Some("shaker"),
+        _ => None,
+    }
+}
+
+/// All parameters of an instrument type, ordered by FFI index.
+/// Returns an empty slice for unknown instrument IDs.
+///
+/// The tables are built once, on first use (`gooey_engine_new` forces it),
+/// so lookups on the setter path neither allocate nor build configs.
+pub fn instrument_params(instrument: u32) -> &'static [ParamInfo] {
+    static TABLES: OnceLock<Box<[Box<[ParamInfo]>]>> = OnceLock::new();
+    TABLES
+        .get_or_init(|| {
+            (0..INSTRUMENT_COUNT)
+                .map(|instrument| build_instrument_params(instrument).into_boxed_slice())
+                .collect()
+        })
+        .get(instrument as usize)
+        .map_or(&[], |params| params)
+}
+
+fn build_instrument_params(instrument: u32) -> Vec<ParamInfo> {
+    use ParamUnit::*;
+
+    match instrument {
+        INSTRUMENT_KICK => {
+            let d = KickConfig::default();
+            vec![
+                param(
+                    KICK_PARAM_FREQUENCY,
+                    "frequency\0",
+                    kick::ranges::FREQ_MIN,
+                    kick::ranges::FREQ_MAX,
+                    Hz,
+                    d.frequency,
+                    true,
+                ),
+                param(
+                    KICK_PARAM_PUNCH,
+                    "punch\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.punch_amount,
+                    true,
+                ),
+                param(
+                    KICK_PARAM_SUB,
+                    "sub\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.sub_amount,
+                    true,
+                ),
+                param(
+                    KICK_PARAM_CLICK,
+                    "click\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.click_amount,
+                    true,
+                ),
+                param(
+                    KICK_PARAM_DECAY,
+                    "decay\0",
+                    kick::ranges::OSC_DECAY_MIN,
+                    kick::ranges::OSC_DECAY_MAX,
+                    Seconds,
+                    d.oscillator_decay,
+                    true,
+                ),
+                // Baked at trigger time, so not an LFO target (see ChannelInstrument::apply_modulation).
+                param(
+                    KICK_PARAM_PITCH_ENVELOPE,
+                    "pitch_envelope\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.pitch_envelope_amount,
+                    false,
+                ),
+                param(
+                    KICK_PARAM_VOLUME,
+                    "volume\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.volume,
+                    true,
+                ),
+                tuning(KICK_PARAM_TUNING),
+            ]
+        }
+        INSTRUMENT_SNARE => {
+            // SnareDrum::new starts from the tight preset.
+            let d = SnareConfig::tight();
+            vec![
+                param(
+                    SNARE_PARAM_FREQUENCY,
+                    "frequency\0",
+                    snare::ranges::FREQ_MIN,
+                    snare::ranges::FREQ_MAX,
+                    Hz,
+                    d.frequency,
+                    true,
+                ),
+                param(
+                    SNARE_PARAM_DECAY,
+                    "decay\0",
+                    snare::ranges::DECAY_MIN,
+                    snare::ranges::DECAY_MAX,
+                    Seconds,
+                    d.decay,
+                    true,
+                ),
+                param(
+                    SNARE_PARAM_BRIGHTNESS,
+                    "brightness\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.crack_amount,
+                    true,
+                ),
+                param(
+                    SNARE_PARAM_VOLUME,
+                    "volume\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.volume,
+                    true,
+                ),
+                param(
+                    SNARE_PARAM_TONAL,
+                    "tonal\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.tonal_amount,
+                    true,
+                ),
+                param(
+                    SNARE_PARAM_NOISE,
+                    "noise\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.noise_amount,
+                    true,
+                ),
+                param(
+                    SNARE_PARAM_PITCH_DROP,
+                    "pitch_drop\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.pitch_drop,
+                    true,
+                ),
+                param(
+                    SNARE_PARAM_TONAL_DECAY,
+                    "tonal_decay\0",
+                    snare::ranges::TONAL_DECAY_MIN,
+                    snare::ranges::TONAL_DECAY_MAX,
+                    Seconds,
+                    d.tonal_decay,
+                    true,
+                ),
+                param(
+                    SNARE_PARAM_NOISE_DECAY,
+                    "noise_decay\0",
+                    snare::ranges::NOISE_DECAY_MIN,
+                    snare::ranges::NOISE_DECAY_MAX,
+                    Seconds,
+                    d.noise_decay,
+                    true,
+                ),
+                param(
+                    SNARE_PARAM_NOISE_TAIL_DECAY,
+                    "noise_tail_decay\0",
+                    snare::ranges::NOISE_TAIL_DECAY_MIN,
+                    snare::ranges::NOISE_TAIL_DECAY_MAX,
+                    Seconds,
+                    d.noise_tail_decay,
+                    true,
+                ),
+                param(
+                    SNARE_PARAM_FILTER_CUTOFF,
+                    "filter_cutoff\0",
+                    snare::ranges::FILTER_CUTOFF_MIN,
+                    snare::ranges::FILTER_CUTOFF_MAX,
+                    Hz,
+                    d.filter_cutoff,
+                    true,
+                ),
+                param(
+                    SNARE_PARAM_FILTER_RESONANCE,
+                    "filter_resonance\0",
+                    snare::ranges::FILTER_RES_MIN,
+                    snare::ranges::FILTER_RES_MAX,
+                    Ratio,
+                    d.filter_resonance,
+                    true,
+                ),
+                // 0 = LP, 1 = BP, 2 = HP, 3 = notch
+                param(
+                    SNARE_PARAM_FILTER_TYPE,
+                    "filter_type\0",
+                    0.0,
+                    3.0,
+                    Choice,
+                    d.filter_type as f32,
+                    false,
+                ),
+                param(
+                    SNARE_PARAM_XFADE,
+                    "xfade\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.xfade,
+                    true,
+                ),
+                param(
+                    SNARE_PARAM_PHASE_MOD_AMOUNT,
+                    "phase_mod_amount\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.phase_mod_amount,
+                    true,
+                ),
+                param(
+                    SNARE_PARAM_OVERDRIVE,
+                    "overdrive\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.overdrive_amount,
+                    true,
+                ),
+                param(
+                    SNARE_PARAM_AMP_DECAY,
+                    "amp_decay\0",
+                    snare::ranges::AMP_DECAY_MIN,
+                    snare::ranges::AMP_DECAY_MAX,
+                    Seconds,
+                    d.amp_decay,
+                    true,
+                ),
+                param(
+                    SNARE_PARAM_AMP_DECAY_CURVE,
+                    "amp_decay_curve\0",
+                    snare::ranges::AMP_DECAY_CURVE_MIN,
+                    snare::ranges::AMP_DECAY_CURVE_MAX,
+                    Ratio,
+                    d.amp_decay_curve,
+                    true,
+                ),
+                param(
+                    SNARE_PARAM_TONAL_DECAY_CURVE,
+                    "tonal_decay_curve\0",
+                    snare::ranges::TONAL_DECAY_CURVE_MIN,
+                    snare::ranges::TONAL_DECAY_CURVE_MAX,
+                    Ratio,
+                    d.tonal_decay_curve,
+                    true,
+                ),
+                tuning(SNARE_PARAM_TUNING),
+                param(
+                    SNARE_PARAM_NOISE_COLOR,
+                    "noise_color\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.noise_color,
+                    true,
+                ),
+                // 0 = white, 1 = velvet
+                param(
+                    SNARE_PARAM_CRACK_VELVET,
+                    "crack_velvet\0",
+                    0.0,
+                    1.0,
+                    Choice,
+                    d.crack_velvet as u8 as f32,
+                    false,
+                ),
+            ]
+        }
+        INSTRUMENT_HIHAT => {
+            let d = HiHat2Config::default();
+            let v = VelocityRouting::default();
+            vec![
+                param(
+                    HIHAT_PARAM_PITCH,
+                    "pitch\0",
+                    hihat2::ranges::PITCH_MIN,
+                    hihat2::ranges::PITCH_MAX,
+                    Hz,
+                    d.pitch,
+                    true,
+                ),
+                param(
+                    HIHAT_PARAM_DECAY,
+                    "decay\0",
+                    hihat2::ranges::DECAY_MIN_MS,
+                    hihat2::ranges::DECAY_MAX_MS,
+                    Milliseconds,
+                    d.decay,
+                    true,
+                ),
+                param(
+                    HIHAT_PARAM_ATTACK,
+                    "attack\0",
+                    hihat2::ranges::ATTACK_MIN_MS,
+                    hihat2::ranges::ATTACK_MAX_MS,
+                    Milliseconds,
+                    d.attack,
+                    true,
+                ),
+                param(
+                    HIHAT_PARAM_TONE,
+                    "tone\0",
+                    hihat2::ranges::TONE_MIN,
+                    hihat2::ranges::TONE_MAX,
+                    Hz,
+                    d.tone,
+                    true,
+                ),
+                param(
+                    HIHAT_PARAM_VOLUME,
+                    "volume\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.volume,
+                    true,
+                ),
+                tuning(HIHAT_PARAM_TUNING),
+                // Velocity routing depths are read once per hit, so they
+                // are not LFO targets.
+                param(
+                    HIHAT_PARAM_VELOCITY_TO_LEVEL,
+                    "velocity_to_level\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    v.level,
+                    false,
+                ),
+                param(
+                    HIHAT_PARAM_VELOCITY_TO_DECAY,
+                    "velocity_to_decay\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    v.decay,
+                    false,
+                ),
+                param(
+                    HIHAT_PARAM_VELOCITY_TO_TONE,
+                    "velocity_to_tone\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    v.tone,
+                    false,
+                ),
+                param(
+                    HIHAT_PARAM_OPEN_DECAY,
+                    "open_decay\0",
+                    hihat2::ranges::DECAY_MIN_MS,
+                    hihat2::ranges::DECAY_MAX_MS,
+                    Milliseconds,
+                    d.open_decay,
+                    true,
+                ),
+                // 0 = closed, 1 = pedal, 2 = open
+                param(
+                    HIHAT_PARAM_ARTICULATION,
+                    "articulation\0",
+                    0.0,
+                    2.0,
+                    Choice,
+                    0.0,
+                    false,
+                ),
+                // 0 = phase mod, 1 = 808
+                param(
+                    HIHAT_PARAM_MODE,
+                    "mode\0",
+                    0.0,
+                    1.0,
+                    Choice,
+                    d.mode.index() as f32,
+                    false,
+                ),
+            ]
+        }
+        // Tom2 has no config-backed defaults; these mirror `Tom2::new` (0-100 / 100).
+        INSTRUMENT_TOM => vec![
+            param(TOM_PARAM_TUNE, "tune\0", 40.0, 600.0, Hz, 0.5, true),
+            param(TOM_PARAM_BEND, "bend\0", 0.0, 1.0, Normalized, 0.3, true),
+            param(TOM_PARAM_TONE, "tone\0", 0.0, 1.0, Normalized, 0.5, true),
+            param(TOM_PARAM_COLOR, "color\0", 0.0, 1.0, Normalized, 0.5, true),
+            param(
+                TOM_PARAM_DECAY,
+                "decay\0",
+                0.5,
+                4000.0,
+                Milliseconds,
+                0.5,
+                true,
+            ),
+            param(
+                TOM_PARAM_MEMBRANE,
+                "membrane\0",
+                0.0,
+                1.0,
+                Normalized,
+                0.0,
+                true,
+            ),
+            param(
+                TOM_PARAM_MEMBRANE_Q,
+                "membrane_q\0",
+                0.0,
+                1.0,
+                Normalized,
+                0.5,
+                true,
+            ),
+            param(
+                TOM_PARAM_VOLUME,
+                "volume\0",
+                0.0,
+                1.0,
+                Normalized,
+                1.0,
+                true,
+            ),
+            tuning(TOM_PARAM_TUNING),
+            // 0 = off, 1 = on
+            param(
+                TOM_PARAM_TRIANGLE_ENABLE,
+                "triangle_enable\0",
+                0.0,
+                1.0,
+                Choice,
+                1.0,
+                false,
+            ),
+            param(
+                TOM_PARAM_TRIANGLE_LEVEL,
+                "triangle_level\0",
+                0.0,
+                1.0,
+                Normalized,
+                0.5,
+                true,
+            ),
+            param(
+                TOM_PARAM_MORPH_LEVEL,
+                "morph_level\0",
+                0.0,
+                1.0,
+                Normalized,
+                1.0,
+                true,
+            ),
+            param(
+                TOM_PARAM_MEMBRANE_GAIN,
+                "membrane_gain\0",
+                0.0,
+                1.0,
+                Normalized,
+                0.5,
+                true,
+            ),
+        ],
+        INSTRUMENT_BASS => {
+            let d = BassConfig::default();
+            vec![
+                param(
+                    BASS_PARAM_FREQUENCY,
+                    "frequency\0",
+                    bass::ranges::FREQ_MIN,
+                    bass::ranges::FREQ_MAX,
+                    Hz,
+                    d.frequency,
+                    true,
+                ),
+                param(
+                    BASS_PARAM_SUB_LEVEL,
+                    "sub_level\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.sub_level,
+                    true,
+                ),
+                param(
+                    BASS_PARAM_OSC_LEVEL,
+                    "osc_level\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.osc_level,
+                    true,
+                ),
+                param(
+                    BASS_PARAM_DETUNE_LEVEL,
+                    "detune_level\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.detune_level,
+                    true,
+                ),
+                param(
+                    BASS_PARAM_DETUNE_AMOUNT,
+                    "detune_amount\0",
+                    bass::ranges::DETUNE_MIN,
+                    bass::ranges::DETUNE_MAX,
+                    Cents,
+                    d.detune_amount,
+                    true,
+                ),
+                param(
+                    BASS_PARAM_OSC_SHAPE,
+                    "osc_shape\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.osc_shape,
+                    true,
+                ),
+                param(
+                    BASS_PARAM_FILTER_CUTOFF,
+                    "filter_cutoff\0",
+                    bass::ranges::FILTER_CUTOFF_MIN,
+                    bass::ranges::FILTER_CUTOFF_MAX,
+                    Hz,
+                    d.filter_cutoff,
+                    true,
+                ),
+                param(
+                    BASS_PARAM_FILTER_RESONANCE,
+                    "filter_resonance\0",
+                    bass::ranges::FILTER_RES_MIN,
+                    bass::ranges::FILTER_RES_MAX,
+                    Ratio,
+                    d.filter_resonance,
+                    true,
+                ),
+                param(
+                    BASS_PARAM_FILTER_ENV_AMOUNT,
+                    "filter_env_amount\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.filter_env_amount,
+                    true,
+                ),
+                param(
+                    BASS_PARAM_FILTER_ENV_DECAY,
+                    "filter_env_decay\0",
+                    bass::ranges::FILTER_ENV_DECAY_MIN,
+                    bass::ranges::FILTER_ENV_DECAY_MAX,
+                    Seconds,
+                    d.filter_env_decay,
+                    true,
+                ),
+                param(
+                    BASS_PARAM_FILTER_ENV_CURVE,
+                    "filter_env_curve\0",
+                    bass::ranges::FILTER_ENV_CURVE_MIN,
+                    bass::ranges::FILTER_ENV_CURVE_MAX,
+                    Ratio,
+                    d.filter_env_curve,
+                    true,
+                ),
+                param(
+                    BASS_PARAM_AMP_DECAY,
+                    "amp_decay\0",
+                    bass::ranges::AMP_DECAY_MIN,
+                    bass::ranges::AMP_DECAY_MAX,
+                    Seconds,
+                    d.amp_decay,
+                    true,
+                ),
+                param(
+                    BASS_PARAM_AMP_DECAY_CURVE,
+                    "amp_decay_curve\0",
+                    bass::ranges::AMP_DECAY_CURVE_MIN,
+                    bass::ranges::AMP_DECAY_CURVE_MAX,
+                    Ratio,
+                    d.amp_decay_curve,
+                    true,
+                ),
+                param(
+                    BASS_PARAM_OVERDRIVE,
+                    "overdrive\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.overdrive,
+                    true,
+                ),
+                param(
+                    BASS_PARAM_VOLUME,
+                    "volume\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.volume,
+                    true,
+                ),
+                tuning(BASS_PARAM_TUNING),
+            ]
+        }
+        INSTRUMENT_FM_SNAP => {
+            let d = FmSnapConfig::default();
+            vec![
+                param(
+                    FM_SNAP_PARAM_FREQUENCY,
+                    "frequency\0",
+                    fm_snap::ranges::FREQ_MIN,
+                    fm_snap::ranges::FREQ_MAX,
+                    Hz,
+                    d.frequency,
+                    true,
+                ),
+                param(
+                    FM_SNAP_PARAM_RATIO,
+                    "ratio\0",
+                    fm_snap::ranges::RATIO_MIN,
+                    fm_snap::ranges::RATIO_MAX,
+                    Ratio,
+                    d.ratio,
+                    true,
+                ),
+                param(
+                    FM_SNAP_PARAM_INDEX,
+                    "index\0",
+                    fm_snap::ranges::INDEX_MIN,
+                    fm_snap::ranges::INDEX_MAX,
+                    Ratio,
+                    d.index,
+                    true,
+                ),
+                param(
+                    FM_SNAP_PARAM_SNAP,
+                    "snap\0",
+                    fm_snap::ranges::SNAP_MIN_MS,
+                    fm_snap::ranges::SNAP_MAX_MS,
+                    Milliseconds,
+                    d.snap,
+                    true,
+                ),
+                param(
+                    FM_SNAP_PARAM_DECAY,
+                    "decay\0",
+                    fm_snap::ranges::DECAY_MIN_MS,
+                    fm_snap::ranges::DECAY_MAX_MS,
+                    Milliseconds,
+                    d.decay,
+                    true,
+                ),
+                param(
+                    FM_SNAP_PARAM_PITCH_DROP,
+                    "pitch_drop\0",
+                    0.0,
+                    fm_snap::ranges::PITCH_DROP_MAX_SEMITONES,
+                    Semitones,
+                    d.pitch_drop,
+                    true,
+                ),
+                param(
+                    FM_SNAP_PARAM_VOLUME,
+                    "volume\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.volume,
+                    true,
+                ),
+                tuning(FM_SNAP_PARAM_TUNING),
+            ]
+        }
+        INSTRUMENT_RIMSHOT => {
+            let d = RimshotConfig::default();
+            vec![
+                param(
+                    RIMSHOT_PARAM_TUNE,
+                    "tune\0",
+                    rimshot::ranges::TUNE_MIN,
+                    rimshot::ranges::TUNE_MAX,
+                    Hz,
+                    d.tune,
+                    true,
+                ),
+                param(
+                    RIMSHOT_PARAM_TONE,
+                    "tone\0",
+                    rimshot::ranges::TONE_MIN,
+                    rimshot::ranges::TONE_MAX,
+                    Hz,
+                    d.tone,
+                    true,
+                ),
+                param(
+                    RIMSHOT_PARAM_CLICK,
+                    "click\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.click,
+                    true,
+                ),
+                param(
+                    RIMSHOT_PARAM_DECAY,
+                    "decay\0",
+                    rimshot::ranges::DECAY_MIN_MS,
+                    rimshot::ranges::DECAY_MAX_MS,
+                    Milliseconds,
+                    d.decay,
+                    true,
+                ),
+                param(
+                    RIMSHOT_PARAM_VOLUME,
+                    "volume\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.volume,
+                    true,
+                ),
+                tuning(RIMSHOT_PARAM_TUNING),
+            ]
+        }
+        INSTRUMENT_COWBELL => {
+            let d = CowbellConfig::default();
+            vec![
+                param(
+                    COWBELL_PARAM_PITCH,
+                    "pitch\0",
+                    cowbell::ranges::PITCH_MIN,
+                    cowbell::ranges::PITCH_MAX,
+                    Hz,
+                    d.pitch,
+                    true,
+                ),
+                param(
+                    COWBELL_PARAM_TONE,
+                    "tone\0",
+                    cowbell::ranges::TONE_MIN,
+                    cowbell::ranges::TONE_MAX,
+                    Hz,
+                    d.tone,
+                    true,
+                ),
+                param(
+                    COWBELL_PARAM_DECAY,
+                    "decay\0",
+                    cowbell::ranges::DECAY_MIN_MS,
+                    cowbell::ranges::DECAY_MAX_MS,
+                    Milliseconds,
+                    d.decay,
+                    true,
+                ),
+                param(
+                    COWBELL_PARAM_VOLUME,
+                    "volume\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.volume,
+                    true,
+                ),
+                tuning(COWBELL_PARAM_TUNING),
+            ]
+        }
+        INSTRUMENT_SHAKER => {
+            let d = ShakerConfig::default();
+            vec![
+                param(
+                    SHAKER_PARAM_DENSITY,
+                    "density\0",
+                    shaker::ranges::DENSITY_MIN,
+                    shaker::ranges::DENSITY_MAX,
+                    Hz,
+                    d.density,
+                    true,
+                ),
+                param(
+                    SHAKER_PARAM_COLOR,
+                    "color\0",
+                    shaker::ranges::COLOR_MIN,
+                    shaker::ranges::COLOR_MAX,
+                    Hz,
+                    d.color,
+                    true,
+                ),
+                param(
+                    SHAKER_PARAM_ATTACK,
+                    "attack\0",
+                    shaker::ranges::ATTACK_MIN_MS,
+                    shaker::ranges::ATTACK_MAX_MS,
+                    Milliseconds,
+                    d.attack,
+                    true,
+                ),
+                param(
+                    SHAKER_PARAM_DECAY,
+                    "decay\0",
+                    shaker::ranges::DECAY_MIN_MS,
+                    shaker::ranges::DECAY_MAX_MS,
+                    Milliseconds,
+                    d.decay,
+                    true,
+                ),
+                param(
+                    SHAKER_PARAM_ACCENT,
+                    "accent\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.accent,
+                    true,
+                ),
+                param(
+                    SHAKER_PARAM_VOLUME,
+                    "volume\0",
+                    0.0,
+                    1.0,
+                    Normalized,
+                    d.volume,
+                    true,
+                ),
+                tuning(SHAKER_PARAM_TUNING),
+            ]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Metadata for a single parameter, or `None` for an unknown instrument/index.
+pub fn param_info(instrument: u32, param: u32) -> Option<ParamInfo> {
+    instrument_params(instrument).get(param as usize).copied()
+}
+
+/// The whole registry as a JSON document, for hosts (e.g. web UIs) that
+/// prefer data over per-parameter calls:
+///
+/// `{"instruments":[{"id":0,"name":"kick","params":[{"index":0,"name":"frequency",...}]}]}`
+pub fn registry_json() -> String {
+    let mut out = String::from("{\"instruments\":[");
+    for instrument in 0..INSTRUMENT_COUNT {
+        if instrument > 0 {
+            out.push(',');
+        }
+        let name = instrument_name(instrument).unwrap_or("");
+        out.push_str(&format!(
+            "{{\"id\":{instrument},\"name\":\"{name}\",\"params\":["
+        ));
+        for (i, p) in instrument_params(instrument).iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push_str(&format!(
+                "{{\"index\":{},\"name\":\"{}\",\"min\":{},\"max\":{},\"unit\":\"{}\",\"default\":{},\"modulatable\":{}}}",
+                p.index,
+                p.name(),
+                p.min,
+                p.max,
+                p.unit.symbol(),
+                p.default,
+                p.modulatable
+            ));
+        }
+        out.push_str("]}");
+    }
+    out.push_str("]}");
+    out
+}
+
+/// `"filter_cutoff"` -> `"FilterCutoff"`.
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_ascii_uppercase().to_string() + chars.as_str()
+            })
+        })
+        .collect()
+}
+
+/// `"filter_cutoff"` -> `"filterCutoff"`.
+fn camel_case(name: &str) -> String {
+    let pascal = pascal_case(name);
+    let mut chars = pascal.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_ascii_lowercase().to_string() + chars.as_str()
+    })
+}
+
+/// TypeScript definitions for the registry, so web hosts get typed enums and
+/// parameter objects instead of hand-maintained `.d.ts` files. Checked in as
+/// `include/gooey.d.ts` alongside the C header.
+///
+/// Per instrument: a `const enum` of parameter indices (`KickParam.Punch`)
+/// and an interface of optional setter-space values (`KickParams`), so an
+/// object can carry a partial update.
+pub fn typescript_definitions() -> String {
+    let mut out = String::from(
+        "// Generated from the gooey parameter registry (param_info::typescript_definitions).\n\
+         // Do not edit; regenerate with `cargo test --test param_info --features regenerate-goldens`.\n\n",
+    );
+    out.push_str("export const enum Instrument {\n");
+    for instrument in 0..INSTRUMENT_COUNT {
+        let name = pascal_case(instrument_name(instrument).unwrap_or(""));
+        out.push_str(&format!("  {name} = {instrument},\n"));
+    }
+    out.push_str("}\n\nexport const enum ParamUnit {\n");
+    for unit in [
+        ParamUnit::Normalized,
+        ParamUnit::Hz,
+        ParamUnit::Seconds,
+        ParamUnit::Milliseconds,
+        ParamUnit::Semitones,
+        ParamUnit::Ratio,
+        ParamUnit::Cents,
+        ParamUnit::Choice,
+    ] {
+        out.push_str(&format!("  {unit:?} = {},\n", unit.as_u32()));
+    }
+    out.push_str("}\n");
+
+    for instrument in 0..INSTRUMENT_COUNT {
+        let name = pascal_case(instrument_name(instrument).unwrap_or(""));
+        let params = instrument_params(instrument);
+        out.push_str(&format!("\nexport const enum {name}Param {{\n"));
+        for p in params {
+            out.push_str(&format!("  {} = {},\n", pascal_case(p.name()), p.index));
+        }
+        out.push_str(&format!(
+            "}}\n\n/** {name} parameters in setter space; omitted fields are left unchanged. */\n\
+             export interface {name}Params {{\n"
+        ));
+        for p in params {
+            let range = match p.unit {
+                ParamUnit::Choice => format!("Choice {}-{}", p.min, p.max),
+                ParamUnit::Normalized => "0-1".to_string(),
+                _ => format!("0-1 maps to {}-{} {}", p.min, p.max, p.unit.symbol())
+                    .trim_end()
+                    .to_string(),
+            };
+            out.push_str(&format!(
+                "  /** {range}, default {} */\n  {}?: number;\n",
+                p.default,
+                camel_case(p.name())
+            ));
+        }
+        out.push_str("}\n");
+    }
+    out
+}
+
+/// Registry parameter of `instrument` called `name`, in snake_case or the
+/// camelCase used by the TypeScript definitions.
+pub fn param_by_name(instrument: u32, name: &str) -> Option<ParamInfo> {
+    instrument_params(instrument)
+        .iter()
+        .find(|p| p.name() == name || camel_case(p.name()) == name)
+        .copied()
+}
+
+/// `INSTRUMENT_*` ID of an [`instrument_name`].
+pub fn instrument_by_name(name: &str) -> Option<u32> {
+    (0..INSTRUMENT_COUNT).find(|&instrument| instrument_name(instrument) == Some(name))
+}
+
+/// Why [`param_by_path`] rejected a name.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParamPathError {
+    /// Not of the form `instrument.param`.
+    Malformed(String),
+    /// The part before the dot names no instrument.
+    UnknownInstrument(String),
+    /// The part after the dot names no parameter of the instrument.
+    UnknownParam(String),
+}
+
+impl std::fmt::Display for ParamPathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Malformed(message) => f.write_str(message),
+            Self::UnknownInstrument(message) => f.write_str(message),
+            Self::UnknownParam(message) => f.write_str(message),
+        }
+    }
+}
+
+/// Resolve a dotted parameter name such as `"snare.filter_cutoff"` (or
+/// `"snare.filterCutoff"`) to its instrument ID and registry entry.
+///
+/// Every valid name is hashed into a table the first time this is called, so
+/// a lookup is one hash probe and never walks the registry. That still
+/// allocates on first use: resolve names on a control thread and hand the
+/// indices to anything running on the audio thread.
+pub fn param_by_path(path: &str) -> Result<(u32, ParamInfo), ParamPathError> {
+    static PATHS: OnceLock<HashMap<String, (u32, ParamInfo)>> = OnceLock::new();
+    let paths = PATHS.get_or_init(|| {
+        let mut paths = HashMap::new();
+        for instrument in 0..INSTRUMENT_COUNT {
+            let prefix = instrument_name(instrument).unwrap_or("");
+            for &p in instrument_params(instrument) {
+                paths.insert(format!("{prefix}.{}", p.name()), (instrument, p));
+                paths.insert(
+                    format!("{prefix}.{}", camel_case(p.name())),
+                    (instrument, p),
+                );
+            }
+        }
+        paths
+    });
+    if let Some(&found) = paths.get(path) {
+        return Ok(found);
+    }
+
+    // Miss: work out which half is wrong
+    let Some((instrument, param)) = path.split_once('.') else {
+        return Err(ParamPathError::Malformed(format!(
+            "\"{path}\" is not of the form instrument.param"
+        )));
+    };
+    if instrument_by_name(instrument).is_none() {
+        return Err(ParamPathError::UnknownInstrument(format!(
+            "\"{instrument}\" is not an instrument"
+        )));
+    }
+    Err(ParamPathError::UnknownParam(format!(
+        "\"{param}\" is not a {instrument} parameter"
+    )))
+}
+
+/// Why [`parse_params_json`] rejected a document.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParamsJsonError {
+    /// Not a flat object of numbers.
+    Malformed(String),
+    /// A key that names no parameter of the instrument.
+    UnknownParam(String),
+}
+
+impl std::fmt::Display for ParamsJsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Malformed(message) => f.write_str(message),
+            Self::UnknownParam(message) => f.write_str(message),
+        }
+    }
+}
+
+/// Parse a flat JSON object of parameter values for `instrument`, e.g.
+/// `{"frequency": 0.3, "punch": 0.7}` (what `JSON.stringify` gives for the
+/// TypeScript `KickParams` interface), into `(param index, value)` pairs in
+/// document order. Keys go through [`param_by_name`]; values must be plain
+/// numbers. Fails on the first unknown key or malformed entry.
+pub fn parse_params_json(instrument: u32, json: &str) -> Result<Vec<(u32, f32)>, ParamsJsonError> {
+    let malformed = |message: &str| ParamsJsonError::Malformed(message.to_string());
+    let mut rest = json.trim();
+    rest = rest
+        .strip_prefix('{')
+        .and_then(|r| r.strip_suffix('}'))
+        .ok_or_else(|| malformed("expected a JSON object"))?
+        .trim();
+    let mut params = Vec::new();
+    while !rest.is_empty() {
+        let key_start = rest
+            .strip_prefix('"')
+            .ok_or_else(|| malformed("expected a quoted key"))?;
+        let key_end = key_start
+            .find('"')
+            .ok_or_else(|| malformed("unterminated key"))?;
+        let key = &key_start[..key_end];
+        let after_key = key_start[key_end + 1..].trim_start();
+        let value_start = after_key
+            .strip_prefix(':')
+            .ok_or_else(|| malformed(&format!("expected ':' after \"{key}\"")))?
+            .trim_start();
+        let value_end = value_start.find(',').unwrap_or(value_start.len());
+        let text = value_start[..value_end].trim();
+        let value = text
+            .parse::<f32>()
+            .ok()
+            .filter(|v| v.is_finite() && !text.starts_with(['i', 'I', 'n', 'N', '+']))
+            .ok_or_else(|| malformed(&format!("\"{key}\": {text} is not a number")))?;
+        let info = param_by_name(instrument, key).ok_or_else(|| {
+            let name = instrument_name(instrument).unwrap_or("unknown");
+            ParamsJsonError::UnknownParam(format!("\"{key}\" is not a {name} parameter"))
+        })?;
+        params.push((info.index, value));
+        rest = value_start[value_end..].trim_start();
+        if let Some(next) = rest.strip_prefix(',') {
+            rest = next.trim_start();
+            if rest.is_empty() {
+                return Err(malformed("trailing ','"));
+            }
+        }
+    }
+    Ok(params)
+}
+
+// ============================================================================
+// Config validation
+// ============================================================================
+
+/// A config field that [`validate`](KickConfig::validate) found out of range.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConfigWarning {
+    pub field: &'static str,
+    pub value: f32,
+    pub min: f32,
+    pub max: f32,
+}
+
+impl std::fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} = {} is outside {}..={}",
+            self.field, self.value, self.min, self.max
+        )
+    }
+}
+
+/// Implements `validate()` / `clamped()` for an instrument config from a
+/// table of `f32` field ranges, plus optional `u8` choice fields (`0..=max`).
+/// Non-finite fields are clamped to the value from `$default`.
+macro_rules! config_ranges {
+    (
+        $config:ty,
+        default: $default:expr,
+        ranges: { $($field:ident: $range:expr),* $(,)? }
+        $(, choices: { $($choice:ident: $choice_max:expr),* $(,)? })?
+    ) => {
+        impl $config {
+            /// Check every field against its range. Lists each out-of-range or
+            /// non-finite field; `Ok` means [`clamped`](Self::clamped) is a no-op.
+            pub fn validate(&self) -> Result<(), Vec<ConfigWarning>> {
+                let mut warnings = Vec::new();
+                $(
+                    let (min, max) = $range;
+                    if !(min..=max).contains(&self.$field) {
+                        warnings.push(ConfigWarning {
+                            field: stringify!($field),
+                            value: self.$field,
+                            min,
+                            max,
+                        });
+                    }
+                )*
+                $($(
+                    if self.$choice > $choice_max {
+                        warnings.push(ConfigWarning {
+                            field: stringify!($choice),
+                            value: self.$choice as f32,
+                            min: 0.0,
+                            max: $choice_max as f32,
+                        });
+                    }
+                )*)?
+                if warnings.is_empty() {
+                    Ok(())
+                } else {
+                    Err(warnings)
+                }
+            }
+
+            /// Copy with every field clamped into range. Non-finite fields take
+            /// the default preset's value.
+            pub fn clamped(&self) -> Self {
+                let default = $default;
+                let mut out = *self;
+                $(
+                    let (min, max) = $range;
+                    out.$field = if self.$field.is_finite() {
+                        self.$field.clamp(min, max)
+                    } else {
+                        default.$field
+                    };
+                )*
+                $($(
+                    out.$choice = self.$choice.min($choice_max);
+                )*)?
+                out
+            }
+        }
+    };
+}
+
+config_ranges!(KickConfig, default: KickConfig::default(), ranges: {
+    frequency: NORMALIZED_RANGE,
+    punch_amount: NORMALIZED_RANGE,
+    sub_amount: NORMALIZED_RANGE,
+    click_amount: NORMALIZED_RANGE,
+    oscillator_decay: NORMALIZED_RANGE,
+    pitch_envelope_amount: NORMALIZED_RANGE,
+    pitch_envelope_curve: NORMALIZED_RANGE,
+    volume: NORMALIZED_RANGE,
+    pitch_start_ratio: NORMALIZED_RANGE,
+    phase_mod_amount: NORMALIZED_RANGE,
+    noise_amount: NORMALIZED_RANGE,
+    noise_cutoff: NORMALIZED_RANGE,
+    noise_resonance: NORMALIZED_RANGE,
+    overdrive_amount: NORMALIZED_RANGE,
+    feedback_amount: NORMALIZED_RANGE,
+    feedback_cutoff: NORMALIZED_RANGE,
+    amp_decay: NORMALIZED_RANGE,
+    amp_decay_curve: NORMALIZED_RANGE,
+});
+
+config_ranges!(SnareConfig, default: SnareConfig::tight(), ranges: {
+    frequency: NORMALIZED_RANGE,
+    tonal_amount: NORMALIZED_RANGE,
+    noise_amount: NORMALIZED_RANGE,
+    crack_amount: NORMALIZED_RANGE,
+    decay: NORMALIZED_RANGE,
+    pitch_drop: NORMALIZED_RANGE,
+    volume: NORMALIZED_RANGE,
+    tonal_decay: NORMALIZED_RANGE,
+    tonal_decay_curve: NORMALIZED_RANGE,
+    noise_decay: NORMALIZED_RANGE,
+    noise_tail_decay: NORMALIZED_RANGE,
+    filter_cutoff: NORMALIZED_RANGE,
+    filter_resonance: NORMALIZED_RANGE,
+    xfade: NORMALIZED_RANGE,
+    phase_mod_amount: NORMALIZED_RANGE,
+    overdrive_amount: NORMALIZED_RANGE,
+    amp_decay: NORMALIZED_RANGE,
+    amp_decay_curve: NORMALIZED_RANGE,
+    noise_color: NORMALIZED_RANGE,
+}, choices: {
+    filter_type: 3u8,
+});
+
+config_ranges!(HiHat2Config, default: HiHat2Config::default(), ranges: {
+    pitch: NORMALIZED_RANGE,
+    decay: NORMALIZED_RANGE,
+    attack: NORMALIZED_RANGE,
+    tone: NORMALIZED_RANGE,
+    volume: NORMALIZED_RANGE,
+});
+
+config_ranges!(Tom2Config, default: Tom2Config::default(), ranges: {
+    tune: TOM_CONFIG_RANGE,
+    bend: TOM_CONFIG_RANGE,
+    tone: TOM_CONFIG_RANGE,
+    color: TOM_CONFIG_RANGE,
+    decay: TOM_CONFIG_RANGE,
+    membrane: TOM_CONFIG_RANGE,
+    membrane_q: TOM_CONFIG_RANGE,
+    volume: TOM_CONFIG_RANGE,
+});
+
+config_ranges!(BassConfig, default: BassConfig::default(), ranges: {
+    frequency: NORMALIZED_RANGE,
+    sub_level: NORMALIZED_RANGE,
+    osc_level: NORMALIZED_RANGE,
+    detune_level: NORMALIZED_RANGE,
+    detune_amount: NORMALIZED_RANGE,
+    osc_shape: NORMALIZED_RANGE,
+    filter_cutoff: NORMALIZED_RANGE,
+    filter_resonance: NORMALIZED_RANGE,
+    filter_env_amount: NORMALIZED_RANGE,
+    filter_env_decay: NORMALIZED_RANGE,
+    filter_env_curve: NORMALIZED_RANGE,
+    amp_decay: NORMALIZED_RANGE,
+    amp_decay_curve: NORMALIZED_RANGE,
+    overdrive: NORMALIZED_RANGE,
+    volume: NORMALIZED_RANGE,
+});
+
+config_ranges!(FmSnapConfig, default: FmSnapConfig::default(), ranges: {
+    frequency: NORMALIZED_RANGE,
+    ratio: NORMALIZED_RANGE,
+    index: NORMALIZED_RANGE,
+    snap: NORMALIZED_RANGE,
+    decay: NORMALIZED_RANGE,
+    pitch_drop: NORMALIZED_RANGE,
+    volume: NORMALIZED_RANGE,
+});
+
+config_ranges!(RimshotConfig, default: RimshotConfig::default(), ranges: {
+    tune: NORMALIZED_RANGE,
+    tone: NORMALIZED_RANGE,
+    click: NORMALIZED_RANGE,
+    decay: NORMALIZED_RANGE,
+    volume: NORMALIZED_RANGE,
+});
+
+config_ranges!(CowbellConfig, default: CowbellConfig::default(), ranges: {
+    pitch: NORMALIZED_RANGE,
+    tone: NORMALIZED_RANGE,
+    decay: NORMALIZED_RANGE,
+    volume: NORMALIZED_RANGE,
+});
+
+config_ranges!(ShakerConfig, default: ShakerConfig::default(), ranges: {
+    density: NORMALIZED_RANGE,
+    color: NORMALIZED_RANGE,
+    attack: NORMALIZED_RANGE,
+    decay: NORMALIZED_RANGE,
+    accent: NORMALIZED_RANGE,
+    volume: NORMALIZED_RANGE,
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_instrument_has_contiguous_indices() {
+        for instrument in 0..INSTRUMENT_COUNT {
+            let params = instrument_params(instrument);
+            assert!(!params.is_empty());
+            for (i, p) in params.iter().enumerate() {
+                assert_eq!(p.index, i as u32, "{} param {i}", p.name());
+                assert!(p.min < p.max);
+            }
+        }
+        assert!(instrument_params(INSTRUMENT_COUNT).is_empty());
+        // Lookups share one static table rather than building a new one
+        assert!(core::ptr::eq(
+            instrument_params(INSTRUMENT_KICK),
+            instrument_params(INSTRUMENT_KICK)
+        ));
+    }
+
+    #[test]
+    fn registry_json_is_balanced() {
+        let json = registry_json();
+        assert!(json.starts_with("{\"instruments\":["));
+        assert_eq!(json.matches('{').count(), json.matches('}').count());
+        assert_eq!(json.matches('[').count(), json.matches(']').count());
+        assert!(json.contains("\"name\":\"filter_type\""));
+    }
+
+    #[test]
+    fn typescript_names_are_converted() {
+        assert_eq!(pascal_case("fm_snap"), "FmSnap");
+        assert_eq!(camel_case("filter_cutoff"), "filterCutoff");
+        let ts = typescript_definitions();
+        assert!(ts.contains("  FmSnap = 5,\n"));
+        assert!(ts.contains("export interface HihatParams {"));
+        assert_eq!(ts.matches('{').count(), ts.matches('}').count());
+    }
+
+    #[test]
+    fn params_json_accepts_both_name_styles() {
+        let parsed = parse_params_json(
+            INSTRUMENT_KICK,
+            r#" { "frequency": 0.25, "pitch_envelope":1e-1 , "pitchEnvelope": -0.5 } "#,
+        );
+        assert_eq!(
+            parsed,
+            Ok(vec![
+                (KICK_PARAM_FREQUENCY, 0.25),
+                (KICK_PARAM_PITCH_ENVELOPE, 0.1),
+                (KICK_PARAM_PITCH_ENVELOPE, -0.5),
+            ])
+        );
+        assert_eq!(parse_params_json(INSTRUMENT_KICK, "{}"), Ok(vec![]));
+        for bad in [
+            "",
+            "[1]",
+            "{\"frequency\": \"0.5\"}",
+            "{\"frequency\": NaN}",
+            "{\"frequency\": 0.5,}",
+            "{frequency: 0.5}",
+        ] {
+            assert!(
+                matches!(
+                    parse_params_json(INSTRUMENT_KICK, bad),
+                    Err(ParamsJsonError::Malformed(_))
+                ),
+                "{bad}"
+            );
+        }
+        assert!(matches!(
+            parse_params_json(INSTRUMENT_KICK, "{\"cutoff\": 0.5}"),
+            Err(ParamsJsonError::UnknownParam(_))
+        ));
+    }
+    #[test]
+    fn param_paths_resolve_both_name_styles() {
+        let (instrument, info) = param_by_path("snare.filter_cutoff").unwrap();
+        assert_eq!(
+            (instrument, info.index),
+            (INSTRUMENT_SNARE, SNARE_PARAM_FILTER_CUTOFF)
+        );
+        assert_eq!(param_by_path("snare.filterCutoff"), Ok((instrument, info)));
+        assert_eq!(instrument_by_name("fm_snap"), Some(INSTRUMENT_FM_SNAP));
+        assert!(matches!(
+            param_by_path("snare"),
+            Err(ParamPathError::Malformed(_))
+        ));
+        assert!(matches!(
+            param_by_path("snares.decay"),
+            Err(ParamPathError::UnknownInstrument(_))
+        ));
+        assert!(matches!(
+            param_by_path("snare.filter_cutoff.x"),
+            Err(ParamPathError::UnknownParam(_))
+        ));
+    }
+
+    #[test]
+    fn stock_presets_validate() {
+        for kick in [
+            KickConfig::tight(),
+            KickConfig::punch(),
+            KickConfig::loose(),
+            KickConfig::dirt(),
+        ] {
+            assert_eq!(kick.validate(), Ok(()));
+        }
+        for snare in [
+            SnareConfig::tight(),
+            SnareConfig::loose(),
+            SnareConfig::hiss(),
+            SnareConfig::smack(),
+        ] {
+            assert_eq!(snare.validate(), Ok(()));
+        }
+        for hat in [
+            HiHat2Config::short(),
+            HiHat2Config::loose(),
+            HiHat2Config::dark(),
+            HiHat2Config::soft(),
+            HiHat2Config::classic(),
+            HiHat2Config::classic_loose(),
+        ] {
+            assert_eq!(hat.validate(), Ok(()));
+        }
+        for tom in [
+            Tom2Config::derp(),
+            Tom2Config::ring(),
+            Tom2Config::brush(),
+            Tom2Config::void_preset(),
+        ] {
+            assert_eq!(tom.validate(), Ok(()));
+        }
+        for bass in [
+            BassConfig::acid(),
+            BassConfig::sub(),
+            BassConfig::reese(),
+            BassConfig::stab(),
+        ] {
+            assert_eq!(bass.validate(), Ok(()));
+        }
+        for rim in [
+            RimshotConfig::classic(),
+            RimshotConfig::tight(),
+            RimshotConfig::woody(),
+            RimshotConfig::ring(),
+        ] {
+            assert_eq!(rim.validate(), Ok(()));
+        }
+        for bell in [
+            CowbellConfig::classic(),
+            CowbellConfig::bright(),
+            CowbellConfig::dark(),
+            CowbellConfig::short(),
+        ] {
+            assert_eq!(bell.validate(), Ok(()));
+        }
+        for shaker in [
+            ShakerConfig::tight(),
+            ShakerConfig::swish(),
+            ShakerConfig::cabasa(),
+            ShakerConfig::egg(),
+        ] {
+            assert_eq!(shaker.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn clamped_config_fixes_every_warning() {
+        let mut kick = KickConfig::default();
+        kick.frequency = 1.5;
+        kick.amp_decay = f32::NAN;
+        let warnings = kick.validate().unwrap_err();
+        let fields: Vec<_> = warnings.iter().map(|w| w.field).collect();
+        assert_eq!(fields, ["frequency", "amp_decay"]);
+
+        let fixed = kick.clamped();
+        assert_eq!(fixed.frequency, 1.0);
+        assert_eq!(fixed.amp_decay, KickConfig::default().amp_decay);
+        assert_eq!(fixed.validate(), Ok(()));
+
+        let mut snare = SnareConfig::tight();
+        snare.filter_type = 9;
+        assert_eq!(snare.validate().unwrap_err()[0].field, "filter_type");
+        assert_eq!(snare.clamped().filter_type, 3);
+
+        let mut tom = Tom2Config::default();
+        tom.decay = -5.0;
+        assert_eq!(tom.clamped().decay, 0.0);
+    }
+
+    #[test]
+    fn param_clamp_uses_setter_range() {
+        let freq = param_info(INSTRUMENT_KICK, KICK_PARAM_FREQUENCY).unwrap();
+        assert_eq!(freq.setter_range(), NORMALIZED_RANGE);
+        assert_eq!(freq.clamp(2.0), 1.0);
+        assert_eq!(freq.clamp(f32::INFINITY), freq.default);
+
+        let filter_type = param_info(INSTRUMENT_SNARE, SNARE_PARAM_FILTER_TYPE).unwrap();
+        assert_eq!(filter_type.clamp(7.0), 3.0);
+    }
+}
@@ -0,0 +1,171 @@
+//! Shared-memory parameter table
+//!
+//! For hosts whose control thread can't cheaply message the audio thread,
+//! e.g. an AudioWorklet running the engine as WASM over a SharedArrayBuffer:
+//! the main thread writes plain `f32` values into a fixed table in the
+//! engine's memory, and the engine picks up whatever changed at the start of
+//! each render. No queue and no postMessage round trip.
+//!
+//! Slot `instrument * PARAM_TABLE_STRIDE + param` holds registry parameter
+//! `param` (see [`crate::param_info`]) of instrument type `instrument`, so
+//! hosts can compute indices without asking and they stay put as parameters
+//! are added. Values are in setter space. Every slot starts out NaN, meaning
+//! "not driven": the engine only applies finite values that changed since
+//! the last render, so slots the host never writes leave the regular
+//! setters in charge. Applied values go through the instruments' parameter
+//! smoothers like any other write.
+
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::ffi::INSTRUMENT_COUNT;
+use crate::param_info::{param_info, ParamInfo};
+
+/// Slots reserved per instrument type (more than any instrument has params).
+pub const PARAM_TABLE_STRIDE: u32 = 32;
+
+/// Total number of slots in the table.
+pub const PARAM_TABLE_LEN: u32 = INSTRUMENT_COUNT * PARAM_TABLE_STRIDE;
+
+/// Bit pattern of an undriven slot (a quiet NaN).
+const UNDRIVEN: u32 = 0x7FC0_0000;
+
+/// Table slot of a registry parameter, or `None` if the instrument type has
+/// no such parameter.
+pub fn slot_index(instrument: u32, param: u32) -> Option<usize> {
+    if param >= PARAM_TABLE_STRIDE {
+        return None;
+    }
+    param_info(instrument, param)?;
+    Some((instrument * PARAM_TABLE_STRIDE + param) as usize)
+}
+
+/// Parameter values shared between a writer thread and the audio thread.
+///
+/// Any thread may [`set`](Self::set) values; only the audio thread polls
+/// them (see [`poll_slot`](Self::poll_slot)).
+pub struct ParamTable {
+    values: Box<[AtomicU32]>,
+    // Audio thread only: the bits last seen in each slot
+    seen: Box<[u32]>,
+    // Registry entry behind each slot, `None` for reserved slots
+    params: Box<[Option<ParamInfo>]>,
+}
+
+impl ParamTable {
+    pub fn new() -> Self {
+        let params: Box<[Option<ParamInfo>]> = (0..PARAM_TABLE_LEN)
+            .map(|slot| param_info(slot / PARAM_TABLE_STRIDE, slot % PARAM_TABLE_STRIDE))
+            .collect();
+        Self {
+            values: params.iter().map(|_| AtomicU32::new(UNDRIVEN)).collect(),
+            seen: vec![UNDRIVEN; params.len()].into_boxed_slice(),
+            params,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// First slot, for hosts that write through shared memory. Each slot is
+    /// an aligned `f32`; a plain 32-bit store (e.g. a `Float32Array` write)
+    /// is enough.
+    pub fn as_mut_ptr(&self) -> *mut f32 {
+        self.values.as_ptr() as *mut f32
+    }
+
+    /// Drive `slot` with `value`. Returns false for a slot out of range.
+    pub fn set(&self, slot: usize, value: f32) -> bool {
+        let Some(cell) = self.values.get(slot) else {
+            return false;
+        };
+        cell.store(value.to_bits(), Ordering::Relaxed);
+        true
+    }
+
+    /// Stop driving `slot`; the parameter keeps its last value.
+    pub fn release(&self, slot: usize) {
+        self.set(slot, f32::from_bits(UNDRIVEN));
+    }
+
+    /// The value driving `slot`, or `None` if it is not driven.
+    pub fn get(&self, slot: usize) -> Option<f32> {
+        let value = f32::from_bits(self.values.get(slot)?.load(Ordering::Relaxed));
+        value.is_finite().then_some(value)
+    }
+
+    /// If `slot` changed to a finite value since it was last polled, return
+    /// `(instrument_type, param, value)` with the value clamped to the
+    /// parameter's setter range. Audio thread only.
+    pub fn poll_slot(&mut self, slot: usize) -> Option<(u32, u32, f32)> {
+        let info = self.params.get(slot).copied().flatten()?;
+        let bits = self.values[slot].load(Ordering::Relaxed);
+        if bits == self.seen[slot] {
+            return None;
+        }
+        self.seen[slot] = bits;
+        let value = f32::from_bits(bits);
+        value.is_finite().then(|| {
+            (
+                slot as u32 / PARAM_TABLE_STRIDE,
+                info.index,
+                info.clamp(value),
+            )
+        })
+    }
+}
+
+impl Default for ParamTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffi::{
+        HIHAT_PARAM_ARTICULATION, INSTRUMENT_HIHAT, INSTRUMENT_KICK, KICK_PARAM_PUNCH,
+    };
+
+    #[test]
+    fn test_slots_follow_the_registry() {
+        let slot = slot_index(INSTRUMENT_HIHAT, HIHAT_PARAM_ARTICULATION).unwrap();
+        assert_eq!(
+            slot,
+            (INSTRUMENT_HIHAT * PARAM_TABLE_STRIDE + HIHAT_PARAM_ARTICULATION) as usize
+        );
+        assert_eq!(slot_index(INSTRUMENT_KICK, PARAM_TABLE_STRIDE - 1), None);
+        assert_eq!(slot_index(INSTRUMENT_COUNT, 0), None);
+        // Every registry parameter fits in its instrument's stride
+        for instrument in 0..INSTRUMENT_COUNT {
+            for info in crate::param_info::instrument_params(instrument) {
+                assert!(slot_index(instrument, info.index).is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn test_poll_reports_each_finite_change_once() {
+        let mut table = ParamTable::new();
+        let slot = slot_index(INSTRUMENT_KICK, KICK_PARAM_PUNCH).unwrap();
+        assert_eq!(table.poll_slot(slot), None);
+
+        assert!(table.set(slot, 2.0));
+        assert_eq!(
+            table.poll_slot(slot),
+            Some((INSTRUMENT_KICK, KICK_PARAM_PUNCH, 1.0))
+        );
+        assert_eq!(table.poll_slot(slot), None);
+        assert_eq!(table.get(slot), Some(2.0));
+
+        table.release(slot);
+        assert_eq!(table.poll_slot(slot), None);
+        assert_eq!(table.get(slot), None);
+        assert!(!table.set(table.len(), 0.5));
+    }
+}
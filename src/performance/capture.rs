@@ -0,0 +1,149 @@
+//! Retrospective ("always-on") capture of manually played triggers.
+//!
+//! Every manual hit is stamped with the transport beat it sounded on and kept
+//! in a fixed ring, whether or not anything is armed. A capture call then
+//! turns the last few bars of hits into quantized 16-step patterns, so an idea
+//! played in before pressing record is not lost.
+
+/// Hits remembered; the oldest are overwritten first.
+pub const CAPTURE_CAPACITY: usize = 1024;
+
+/// Longest window a capture can look back over, in 4/4 bars.
+pub const CAPTURE_MAX_BARS: u32 = 8;
+
+/// Steps in a captured pattern (one 4/4 bar of sixteenths).
+pub const CAPTURE_PATTERN_STEPS: usize = 16;
+
+/// One manually played hit on the transport timeline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CapturedHit {
+    pub channel: u32,
+    /// Transport position in quarter notes.
+    pub beat: f64,
+    pub velocity: f32,
+}
+
+/// Ring buffer of recent manual hits. Preallocated, so recording from the
+/// audio thread never allocates.
+pub struct TriggerCapture {
+    hits: Vec<CapturedHit>,
+    /// Index the next hit is written to once the ring is full
+    next: usize,
+}
+
+impl Default for TriggerCapture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TriggerCapture {
+    pub fn new() -> Self {
+        Self {
+            hits: Vec::with_capacity(CAPTURE_CAPACITY),
+            next: 0,
+        }
+    }
+
+    /// Remember a hit, dropping the oldest when full.
+    pub fn record(&mut self, hit: CapturedHit) {
+        if self.hits.len() < CAPTURE_CAPACITY {
+            self.hits.push(hit);
+        } else {
+            self.hits[self.next] = hit;
+            self.next = (self.next + 1) % CAPTURE_CAPACITY;
+        }
+    }
+
+    /// Forget everything recorded so far.
+    pub fn clear(&mut self) {
+        self.hits.clear();
+        self.next = 0;
+    }
+
+    /// Hits inside the last `bars` bars before `now` (quarter notes). The
+    /// window is bar-aligned and ends at the end of the bar holding `now`, so
+    /// the bar being played counts as the newest one. Hits after `now` (left
+    /// behind by a seek backwards) are ignored.
+    pub fn window(&self, now: f64, bars: u32) -> impl Iterator<Item = &CapturedHit> + '_ {
+        let bars = bars.clamp(1, CAPTURE_MAX_BARS) as f64;
+        let end = ((now / 4.0).floor() + 1.0) * 4.0;
+        let start = end - bars * 4.0;
+        // Half a step of slack: a hit played just early for the downbeat
+        // still belongs to it
+        let latest = now + 0.125;
+        self.hits
+            .iter()
+            .filter(move |hit| hit.beat >= start - 0.125 && hit.beat < latest)
+    }
+
+    /// Quantize the last `bars` bars of hits on `channel` to sixteenths and
+    /// fold them into one 16-step bar. Returns per-step velocities (`None` =
+    /// rest); where several hits land on a step the loudest wins. `None` if
+    /// the channel has no hits in the window.
+    pub fn pattern(
+        &self,
+        channel: u32,
+        now: f64,
+        bars: u32,
+    ) -> Option<[Option<f32>; CAPTURE_PATTERN_STEPS]> {
+        let mut steps = [None; CAPTURE_PATTERN_STEPS];
+        let mut any = false;
+        for hit in self.window(now, bars).filter(|hit| hit.channel == channel) {
+            let step = ((hit.beat * 4.0).round() as i64).rem_euclid(CAPTURE_PATTERN_STEPS as i64);
+            let slot: &mut Option<f32> = &mut steps[step as usize];
+            *slot = Some(slot.map_or(hit.velocity, |v: f32| v.max(hit.velocity)));
+            any = true;
+        }
+        any.then_some(steps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(channel: u32, beat: f64, velocity: f32) -> CapturedHit {
+        CapturedHit {
+            channel,
+            beat,
+            velocity,
+        }
+    }
+
+    #[test]
+    fn pattern_quantizes_and_folds_bars() {
+        let mut capture = TriggerCapture::new();
+        // Bar 2 (beats 4-8): slightly late downbeat, an early off-beat
+        capture.record(hit(0, 4.03, 0.8));
+        capture.record(hit(0, 5.46, 0.6));
+        // Bar 3: the same downbeat harder, plus a hit on another channel
+        capture.record(hit(0, 8.0, 1.0));
+        capture.record(hit(1, 9.0, 0.7));
+
+        let steps = capture.pattern(0, 9.5, 2).unwrap();
+        assert_eq!(steps[0], Some(1.0));
+        assert_eq!(steps[6], Some(0.6));
+        assert_eq!(steps.iter().flatten().count(), 2);
+
+        // A one-bar window only sees bar 3
+        let steps = capture.pattern(0, 9.5, 1).unwrap();
+        assert_eq!(steps.iter().flatten().count(), 1);
+        assert!(capture.pattern(2, 9.5, 2).is_none());
+    }
+
+    #[test]
+    fn ring_overwrites_oldest_hits() {
+        let mut capture = TriggerCapture::new();
+        for i in 0..CAPTURE_CAPACITY + 10 {
+            capture.record(hit(0, i as f64 * 0.001, 1.0));
+        }
+        assert_eq!(capture.hits.len(), CAPTURE_CAPACITY);
+        let oldest = capture
+            .hits
+            .iter()
+            .map(|h| h.beat)
+            .fold(f64::INFINITY, f64::min);
+        assert!((oldest - 0.010).abs() < 1e-9);
+    }
+}
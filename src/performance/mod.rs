@@ -3,6 +3,10 @@
 //! Stage 1 focuses on chord pad performances: timed pad-parameter events in a
 //! looping clip locked to the engine transport (same beat clock as drum sequencers).
 
+pub mod capture;
+
+pub use capture::{CapturedHit, TriggerCapture, CAPTURE_MAX_BARS, CAPTURE_PATTERN_STEPS};
+
 /// Pulses per quarter note. One sixteenth-note step is `TICKS_PER_QUARTER / 4`.
 pub const TICKS_PER_QUARTER: u32 = 96;
 
@@ -0,0 +1,643 @@
+//! CLAP/VST3 plugin wrapper (requires the `plugin` feature)
+//!
+//! Runs a [`GooeyEngine`] as a DAW instrument via nih-plug:
+//!
+//! - Every parameter in the [`crate::param_info`] registry is exposed as a
+//!   host parameter with the ID `<instrument>_<param>` (e.g. `kick_decay`),
+//!   grouped by instrument. Values are in setter space (normalized 0-1, or a
+//!   choice index), so automation maps straight onto the engine. Only the
+//!   parameters the host changed are passed on, through the engine's shared
+//!   parameter table.
+//! - The host transport drives the sequencers: tempo, start/stop, and the
+//!   beat position (re-seated when the host loops or jumps). Tempo-following
+//!   sampler slots are re-stretched on a background thread.
+//! - MIDI notes on the General MIDI percussion channel trigger the kit (see
+//!   [`note_target`]); notes on other channels play the bass at their pitch.
+//!   Both land sample accurately within the block.
+//! - Plugin state is the parameter set plus an engine snapshot of the kit:
+//!   channel instruments, patterns, mixer and effect settings (see
+//!   `gooey_engine_export_state`).
+
+use std::collections::BTreeMap;
+use std::num::NonZeroU32;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use nih_plug::prelude::*;
+
+use crate::ffi::*;
+use crate::param_info::{instrument_name, instrument_params, ParamInfo, ParamUnit};
+
+/// Beat drift (in quarter notes) past which the sequencers are re-seated on
+/// the host position: an eighth of a 16th step.
+const TRANSPORT_RESYNC_BEATS: f64 = 1.0 / 32.0;
+
+/// Length of the engine's 16-step patterns in quarter notes.
+const PATTERN_BEATS: f64 = 4.0;
+
+/// MIDI channel General MIDI reserves for percussion: channel 10, counted
+/// from zero as nih-plug does.
+pub const GM_PERCUSSION_CHANNEL: u8 = 9;
+
+/// Persisted field holding the engine snapshot.
+const STATE_KEY: &str = "engine_state";
+
+/// What a MIDI note plays.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NoteTarget {
+    /// A kit instrument (`INSTRUMENT_*`)
+    Drum(u32),
+    /// The bass, at this MIDI note
+    Bass(u8),
+}
+
+/// What a note-on plays.
+///
+/// On [`GM_PERCUSSION_CHANNEL`] notes follow the General MIDI percussion key
+/// map (35-81), each sound going to the closest kit instrument:
+///
+/// - 35-36 bass drums: kick
+/// - 37 side stick, 75 claves, 76-77 wood blocks: rimshot
+/// - 38, 40 snares: snare
+/// - 39 hand clap: FM snap
+/// - 41, 43, 45, 47, 48, 50 toms, 60-66 bongos, congas and timbales: tom
+/// - 42, 44, 46 hi-hats, 49, 51-53, 55, 57, 59 cymbals: hi-hat
+/// - 54 tambourine, 69 cabasa, 70 maracas, 73-74 guiros: shaker
+/// - 56 cowbell, 67-68 agogos, 80-81 triangles: cowbell
+///
+/// The remaining percussion notes (vibraslap, whistles, cuicas) and notes
+/// outside the map play nothing. Notes on every other channel play the bass
+/// at their pitch.
+pub fn note_target(channel: u8, note: u8) -> Option<NoteTarget> {
+    if channel != GM_PERCUSSION_CHANNEL {
+        return Some(NoteTarget::Bass(note));
+    }
+    let instrument = match note {
+        35 | 36 => INSTRUMENT_KICK,
+        37 | 75..=77 => INSTRUMENT_RIMSHOT,
+        38 | 40 => INSTRUMENT_SNARE,
+        39 => INSTRUMENT_FM_SNAP,
+        41 | 43 | 45 | 47 | 48 | 50 | 60..=66 => INSTRUMENT_TOM,
+        42 | 44 | 46 | 49 | 51..=53 | 55 | 57 | 59 => INSTRUMENT_HIHAT,
+        54 | 69 | 70 | 73 | 74 => INSTRUMENT_SHAKER,
+        56 | 67 | 68 | 80 | 81 => INSTRUMENT_COWBELL,
+        _ => return None,
+    };
+    Some(NoteTarget::Drum(instrument))
+}
+
+/// Owns the plugin's engine and frees it with the last reference, so a
+/// background task or the persisted state can outlive a re-initialization.
+pub struct EngineHandle(*mut GooeyEngine);
+
+// SAFETY: the C API may be called from any thread; the engine serializes
+// edits made off the audio thread against rendering.
+unsafe impl Send for EngineHandle {}
+unsafe impl Sync for EngineHandle {}
+
+impl EngineHandle {
+    /// Snapshot of the engine's kit, see `gooey_engine_export_state`.
+    fn export_state(&self) -> Option<Vec<u8>> {
+        let mut length = 0;
+        unsafe {
+            let bytes = gooey_engine_export_state(self.0, &mut length);
+            if bytes.is_null() {
+                return None;
+            }
+            let state = std::slice::from_raw_parts(bytes, length as usize).to_vec();
+            gooey_engine_free_state(bytes, length);
+            Some(state)
+        }
+    }
+
+    /// Restore a snapshot from [`export_state`](Self::export_state). A
+    /// damaged one changes nothing.
+    fn import_state(&self, state: &[u8]) {
+        unsafe { gooey_engine_import_state(self.0, state.as_ptr(), state.len() as u32) };
+    }
+}
+
+impl Drop for EngineHandle {
+    fn drop(&mut self) {
+        unsafe { gooey_engine_free(self.0) };
+    }
+}
+
+/// Work the audio thread hands to the host's background thread.
+pub enum PluginTask {
+    /// Re-stretch tempo-following sampler slots after a tempo change
+    FollowTempo(Arc<EngineHandle>),
+}
+
+/// A registry parameter as a host parameter.
+enum HostParam {
+    Float(FloatParam),
+    Choice(IntParam),
+}
+
+impl HostParam {
+    fn value(&self) -> f32 {
+        match self {
+            HostParam::Float(param) => param.value(),
+            HostParam::Choice(param) => param.value() as f32,
+        }
+    }
+
+    fn as_ptr(&self) -> ParamPtr {
+        match self {
+            HostParam::Float(param) => param.as_ptr(),
+            HostParam::Choice(param) => param.as_ptr(),
+        }
+    }
+}
+
+struct RegistryParam {
+    id: String,
+    group: &'static str,
+    /// Engine parameter table slot the value is written to
+    slot: usize,
+    param: HostParam,
+}
+
+/// Host parameters changed since the engine last took them, flagged by the
+/// parameters' change callbacks.
+struct ParamChanges {
+    any: AtomicBool,
+    params: Box<[AtomicBool]>,
+}
+
+impl ParamChanges {
+    fn mark(&self, index: usize) {
+        self.params[index].store(true, Ordering::Release);
+        self.any.store(true, Ordering::Release);
+    }
+
+    fn mark_all(&self) {
+        for changed in self.params.iter() {
+            changed.store(true, Ordering::Release);
+        }
+        self.any.store(true, Ordering::Release);
+    }
+}
+
+/// Kit state persisted next to the parameters.
+#[derive(Default)]
+struct KitState {
+    /// The running engine, once the plugin is initialized
+    engine: Option<Arc<EngineHandle>>,
+    /// A snapshot restored before there was an engine to apply it to
+    pending: Option<Vec<u8>>,
+}
+
+/// Host parameters built from the parameter registry.
+pub struct GooeyParams {
+    params: Vec<RegistryParam>,
+    changes: Arc<ParamChanges>,
+    state: Mutex<KitState>,
+}
+
+impl Default for GooeyParams {
+    fn default() -> Self {
+        let registry: Vec<(&'static str, u32, ParamInfo)> = (0..INSTRUMENT_COUNT)
+            .filter_map(|instrument| Some((instrument_name(instrument)?, instrument)))
+            .flat_map(|(group, instrument)| {
+                instrument_params(instrument)
+                    .iter()
+                    .map(move |info| (group, instrument, *info))
+            })
+            .collect();
+        let changes = Arc::new(ParamChanges {
+            any: AtomicBool::new(false),
+            params: registry.iter().map(|_| AtomicBool::new(false)).collect(),
+        });
+
+        let mut params = Vec::with_capacity(registry.len());
+        for (index, (group, instrument, info)) in registry.into_iter().enumerate() {
+            let name = format!("{group} {}", info.name().replace('_', " "));
+            let on_change = changes.clone();
+            let param = match info.unit {
+                ParamUnit::Choice => HostParam::Choice(
+                    IntParam::new(
+                        name,
+                        info.default as i32,
+                        IntRange::Linear {
+                            min: info.min as i32,
+                            max: info.max as i32,
+                        },
+                    )
+                    .with_callback(Arc::new(move |_| on_change.mark(index))),
+                ),
+                _ => HostParam::Float(
+                    FloatParam::new(
+                        name,
+                        info.default,
+                        FloatRange::Linear { min: 0.0, max: 1.0 },
+                    )
+                    .with_callback(Arc::new(move |_| on_change.mark(index))),
+                ),
+            };
+            let slot = gooey_engine_param_table_slot(instrument, info.index);
+            params.push(RegistryParam {
+                id: format!("{group}_{}", info.name()),
+                group,
+                slot: usize::try_from(slot).expect("registry parameter has a table slot"),
+                param,
+            });
+        }
+        Self {
+            params,
+            changes,
+            state: Mutex::new(KitState::default()),
+        }
+    }
+}
+
+// SAFETY: the parameters live in a Vec that is never resized after
+// construction, so the pointers handed out stay valid for the lifetime of
+// the `GooeyParams`.
+unsafe impl Params for GooeyParams {
+    fn param_map(&self) -> Vec<(String, ParamPtr, String)> {
+        self.params
+            .iter()
+            .map(|p| (p.id.clone(), p.param.as_ptr(), p.group.to_string()))
+            .collect()
+    }
+
+    fn serialize_fields(&self) -> BTreeMap<String, String> {
+        let state = self.state.lock().unwrap();
+        let snapshot = match &state.engine {
+            Some(engine) => engine.export_state(),
+            None => state.pending.clone(),
+        };
+        snapshot
+            .map(|bytes| BTreeMap::from([(STATE_KEY.to_string(), to_hex(&bytes))]))
+            .unwrap_or_default()
+    }
+
+    fn deserialize_fields(&self, serialized: &BTreeMap<String, String>) {
+        let Some(bytes) = serialized.get(STATE_KEY).and_then(|text| from_hex(text)) else {
+            return;
+        };
+        let mut state = self.state.lock().unwrap();
+        match &state.engine {
+            Some(engine) => engine.import_state(&bytes),
+            None => state.pending = Some(bytes),
+        }
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn from_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// The engine as a CLAP/VST3 instrument.
+pub struct GooeyPlugin {
+    params: Arc<GooeyParams>,
+    engine: Arc<EngineHandle>,
+    /// The engine's parameter table (see `gooey_engine_param_table_ptr`)
+    param_table: *mut f32,
+    /// Interleaved render buffer, sized for the host's largest block
+    scratch: Vec<f32>,
+    /// Event read past the end of the current sub-block
+    pending_event: Option<PluginNoteEvent<Self>>,
+    was_playing: bool,
+    /// Host tempo the engine was last retimed to
+    tempo: Option<f64>,
+}
+
+// SAFETY: the engine is owned by the plugin instance and only touched from
+// the host's audio and main threads, which nih-plug serializes, and from
+// background tasks, which go through the engine's thread-safe C API.
+unsafe impl Send for GooeyPlugin {}
+
+impl Default for GooeyPlugin {
+    fn default() -> Self {
+        Self {
+            params: Arc::new(GooeyParams::default()),
+            engine: Arc::new(EngineHandle(std::ptr::null_mut())),
+            param_table: std::ptr::null_mut(),
+            scratch: Vec::new(),
+            pending_event: None,
+            was_playing: false,
+            tempo: None,
+        }
+    }
+}
+
+impl GooeyPlugin {
+    /// Write the host parameters changed since the last block into the
+    /// engine's parameter table, which the next render applies.
+    unsafe fn apply_params(&mut self) {
+        let changes = &self.params.changes;
+        if self.param_table.is_null() || !changes.any.swap(false, Ordering::AcqRel) {
+            return;
+        }
+        for (p, changed) in self.params.params.iter().zip(changes.params.iter()) {
+            if changed.swap(false, Ordering::AcqRel) {
+                *self.param_table.add(p.slot) = p.param.value();
+            }
+        }
+    }
+
+    /// Follow the host tempo, play state, and position.
+    unsafe fn sync_transport(&mut self, context: &impl ProcessContext<Self>) {
+        let transport = context.transport();
+        if let Some(tempo) = transport.tempo.filter(|&tempo| tempo > 0.0) {
+            // Left for the next block while a background re-stretch runs
+            if self.tempo != Some(tempo) && (*self.engine.0).try_retime(tempo as f32) {
+                self.tempo = Some(tempo);
+                context.execute_background(PluginTask::FollowTempo(self.engine.clone()));
+            }
+        }
+
+        let position = transport.pos_beats();
+        match (transport.playing, self.was_playing) {
+            (true, false) => {
+                if let Some(beat) = position {
+                    gooey_engine_sequencer_set_beat_position(self.engine.0, beat);
+                }
+                gooey_engine_sequencer_start(self.engine.0);
+            }
+            (false, true) => gooey_engine_sequencer_stop(self.engine.0),
+            (true, true) => {
+                if let Some(beat) = position {
+                    // Sequencer positions wrap each pattern; compare in-pattern
+                    let local = gooey_engine_sequencer_get_beat_position(self.engine.0);
+                    let mut drift = (beat - local).rem_euclid(PATTERN_BEATS);
+                    if drift > PATTERN_BEATS / 2.0 {
+                        drift -= PATTERN_BEATS;
+                    }
+                    if drift.abs() > TRANSPORT_RESYNC_BEATS {
+                        gooey_engine_sequencer_set_beat_position(self.engine.0, beat);
+                    }
+                }
+            }
+            (false, false) => {}
+        }
+        self.was_playing = transport.playing;
+    }
+
+    unsafe fn handle_event(&mut self, event: PluginNoteEvent<Self>) {
+        let NoteEvent::NoteOn {
+            channel,
+            note,
+            velocity,
+            ..
+        } = event
+        else {
+            return;
+        };
+        match note_target(channel, note) {
+            Some(NoteTarget::Drum(instrument)) => {
+                gooey_engine_trigger_instrument_with_velocity(self.engine.0, instrument, velocity);
+            }
+            Some(NoteTarget::Bass(note)) => {
+                gooey_engine_trigger_instrument_note(
+                    self.engine.0,
+                    INSTRUMENT_BASS,
+                    note,
+                    velocity,
+                );
+            }
+            None => {}
+        }
+    }
+}
+
+impl Plugin for GooeyPlugin {
+    const NAME: &'static str = "Gooey";
+    const VENDOR: &'static str = "gooey-audio";
+    const URL: &'static str = "https://github.com/gooey-audio/libgooey";
+    const EMAIL: &'static str = "";
+    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+    const AUDIO_IO_LAYOUTS: &'static [AudioIOLayout] = &[AudioIOLayout {
+        main_input_channels: None,
+        main_output_channels: NonZeroU32::new(GOOEY_OUTPUT_CHANNELS),
+        ..AudioIOLayout::const_default()
+    }];
+
+    const MIDI_INPUT: MidiConfig = MidiConfig::Basic;
+    const SAMPLE_ACCURATE_AUTOMATION: bool = false;
+
+    type SysExMessage = ();
+    type BackgroundTask = PluginTask;
+
+    fn params(&self) -> Arc<dyn Params> {
+        self.params.clone()
+    }
+
+    fn task_executor(&mut self) -> TaskExecutor<Self> {
+        Box::new(|task| match task {
+            PluginTask::FollowTempo(engine) => unsafe {
+                gooey_engine_sampler_follow_tempo(engine.0)
+            },
+        })
+    }
+
+    fn initialize(
+        &mut self,
+        _audio_io_layout: &AudioIOLayout,
+        buffer_config: &BufferConfig,
+        _context: &mut impl InitContext<Self>,
+    ) -> bool {
+        let engine = Arc::new(EngineHandle(gooey_engine_new(buffer_config.sample_rate)));
+        if engine.0.is_null() {
+            return false;
+        }
+        {
+            // Carry the kit over from the previous engine, or apply a state
+            // the host restored before there was one
+            let mut state = self.params.state.lock().unwrap();
+            let kit = match state.pending.take() {
+                Some(pending) => Some(pending),
+                None => state.engine.as_ref().and_then(|old| old.export_state()),
+            };
+            if let Some(kit) = kit {
+                engine.import_state(&kit);
+            }
+            state.engine = Some(engine.clone());
+        }
+        unsafe {
+            gooey_engine_set_param_table_enabled(engine.0, true);
+            self.param_table = gooey_engine_param_table_ptr(engine.0);
+        }
+        self.engine = engine;
+        self.params.changes.mark_all();
+        self.scratch =
+            vec![0.0; buffer_config.max_buffer_size as usize * GOOEY_OUTPUT_CHANNELS as usize];
+        self.pending_event = None;
+        self.was_playing = false;
+        self.tempo = None;
+        true
+    }
+
+    fn reset(&mut self) {
+        self.pending_event = None;
+        unsafe { gooey_engine_sequencer_reset(self.engine.0) };
+    }
+
+    fn process(
+        &mut self,
+        buffer: &mut Buffer,
+        _aux: &mut AuxiliaryBuffers,
+        context: &mut impl ProcessContext<Self>,
+    ) -> ProcessStatus {
+        let channels = GOOEY_OUTPUT_CHANNELS as usize;
+        let frames = buffer.samples().min(self.scratch.len() / channels);
+        unsafe {
+            self.apply_params();
+            self.sync_transport(context);
+
+            // Render between note events so each trigger lands on its sample
+            let mut start = 0;
+            while start < frames {
+                let mut end = frames;
+                while let Some(event) = self.pending_event.take().or_else(|| context.next_event()) {
+                    let timing = event.timing() as usize;
+                    if timing > start {
+                        self.pending_event = Some(event);
+                        end = timing.min(frames);
+                        break;
+                    }
+                    self.handle_event(event);
+                }
+
+                let len = end - start;
+                gooey_engine_render(self.engine.0, self.scratch.as_mut_ptr(), len as u32);
+                for (ch, out) in buffer.as_slice().iter_mut().enumerate().take(channels) {
+                    for (i, sample) in out[start..end].iter_mut().enumerate() {
+                        *sample = self.scratch[i * channels + ch];
+                    }
+                }
+                start = end;
+            }
+        }
+        ProcessStatus::KeepAlive
+    }
+}
+
+impl ClapPlugin for GooeyPlugin {
+    const CLAP_ID: &'static str = "audio.gooey.engine";
+    const CLAP_DESCRIPTION: Option<&'static str> = Some("Gooey drum and bass engine");
+    const CLAP_MANUAL_URL: Option<&'static str> = Some(Self::URL);
+    const CLAP_SUPPORT_URL: Option<&'static str> = None;
+    const CLAP_FEATURES: &'static [ClapFeature] = &[
+        ClapFeature::Instrument,
+        ClapFeature::DrumMachine,
+        ClapFeature::Stereo,
+    ];
+}
+
+impl Vst3Plugin for GooeyPlugin {
+    const VST3_CLASS_ID: [u8; 16] = *b"GooeyAudioEngine";
+    const VST3_SUBCATEGORIES: &'static [Vst3SubCategory] =
+        &[Vst3SubCategory::Instrument, Vst3SubCategory::Drum];
+}
+
+nih_export_clap!(GooeyPlugin);
+nih_export_vst3!(GooeyPlugin);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_every_registry_param_has_a_unique_host_id() {
+        let params = GooeyParams::default();
+        let expected: usize = (0..INSTRUMENT_COUNT)
+            .map(|i| instrument_params(i).len())
+            .sum();
+        let ids: HashSet<_> = params.param_map().into_iter().map(|(id, ..)| id).collect();
+        assert_eq!(ids.len(), expected);
+        assert!(ids.contains("kick_frequency"));
+        assert!(ids.contains("fm_snap_decay"));
+    }
+
+    #[test]
+    fn test_gm_drum_notes_map_to_instruments() {
+        let drum = |note| note_target(GM_PERCUSSION_CHANNEL, note);
+        assert_eq!(drum(36), Some(NoteTarget::Drum(INSTRUMENT_KICK)));
+        assert_eq!(drum(38), Some(NoteTarget::Drum(INSTRUMENT_SNARE)));
+        assert_eq!(drum(42), Some(NoteTarget::Drum(INSTRUMENT_HIHAT)));
+        assert_eq!(drum(49), Some(NoteTarget::Drum(INSTRUMENT_HIHAT)));
+        assert_eq!(drum(45), Some(NoteTarget::Drum(INSTRUMENT_TOM)));
+        assert_eq!(drum(62), Some(NoteTarget::Drum(INSTRUMENT_TOM)));
+        assert_eq!(drum(37), Some(NoteTarget::Drum(INSTRUMENT_RIMSHOT)));
+        assert_eq!(drum(39), Some(NoteTarget::Drum(INSTRUMENT_FM_SNAP)));
+        assert_eq!(drum(56), Some(NoteTarget::Drum(INSTRUMENT_COWBELL)));
+        assert_eq!(drum(70), Some(NoteTarget::Drum(INSTRUMENT_SHAKER)));
+        assert_eq!(drum(58), None);
+        assert_eq!(drum(24), None);
+    }
+
+    #[test]
+    fn test_other_channels_play_the_bass_at_pitch() {
+        assert_eq!(note_target(0, 36), Some(NoteTarget::Bass(36)));
+        assert_eq!(note_target(3, 43), Some(NoteTarget::Bass(43)));
+    }
+
+    #[test]
+    fn test_only_changed_params_are_flagged() {
+        let params = GooeyParams::default();
+        assert!(!params.changes.any.load(Ordering::Acquire));
+        params.changes.mark(3);
+        let flagged: Vec<usize> = (0..params.params.len())
+            .filter(|&i| params.changes.params[i].load(Ordering::Acquire))
+            .collect();
+        assert_eq!(flagged, [3]);
+    }
+
+    #[test]
+    fn test_kit_state_round_trips_through_the_persisted_fields() {
+        let saved = GooeyParams::default();
+        let engine = Arc::new(EngineHandle(gooey_engine_new(48_000.0)));
+        let pattern = [true, false, false, true].repeat(4);
+        unsafe {
+            gooey_engine_sequencer_set_instrument_pattern(
+                engine.0,
+                INSTRUMENT_SNARE,
+                pattern.as_ptr(),
+            );
+        }
+        saved.state.lock().unwrap().engine = Some(engine);
+        let fields = saved.serialize_fields();
+        assert!(fields.contains_key(STATE_KEY));
+
+        // Restored before the plugin has an engine: kept until it does
+        let restored = GooeyParams::default();
+        restored.deserialize_fields(&fields);
+        let pending = restored.state.lock().unwrap().pending.clone().unwrap();
+        let engine = EngineHandle(gooey_engine_new(48_000.0));
+        engine.import_state(&pending);
+        for (step, &enabled) in pattern.iter().enumerate() {
+            let on = unsafe {
+                gooey_engine_sequencer_get_instrument_step_enabled(
+                    engine.0,
+                    INSTRUMENT_SNARE,
+                    step as u32,
+                )
+            };
+            assert_eq!(on, enabled, "step {step}");
+        }
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        let bytes = [0x00, 0x7f, 0xff, 0x10];
+        assert_eq!(from_hex(&to_hex(&bytes)).unwrap(), bytes);
+        assert_eq!(from_hex("abc"), None);
+        assert_eq!(from_hex("zz"), None);
+    }
+}
@@ -0,0 +1,105 @@
+//! Items `std` brings into scope that `no_std` + `alloc` builds import
+//! explicitly. Core modules pull this in with
+//! `#[cfg(not(feature = "std"))] use crate::prelude::*;`.
+
+pub(crate) use alloc::boxed::Box;
+pub(crate) use alloc::format;
+pub(crate) use alloc::string::{String, ToString};
+pub(crate) use alloc::vec;
+pub(crate) use alloc::vec::Vec;
+
+/// The float methods core lacks, backed by `libm`. Only in scope on `no_std`
+/// builds; with `std` the inherent methods are used.
+pub(crate) trait Float: Sized {
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn tanh(self) -> Self;
+    fn atan(self) -> Self;
+    fn exp(self) -> Self;
+    fn exp2(self) -> Self;
+    fn exp_m1(self) -> Self;
+    fn log10(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn sqrt(self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn round(self) -> Self;
+    fn fract(self) -> Self;
+    fn rem_euclid(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_float {
+    ($t:ty, $sin:ident, $cos:ident, $tan:ident, $tanh:ident, $atan:ident, $exp:ident,
+     $exp2:ident, $expm1:ident, $log10:ident, $pow:ident, $sqrt:ident, $floor:ident,
+     $ceil:ident, $round:ident, $trunc:ident, $fmod:ident) => {
+        impl Float for $t {
+            fn sin(self) -> Self {
+                libm::$sin(self)
+            }
+            fn cos(self) -> Self {
+                libm::$cos(self)
+            }
+            fn tan(self) -> Self {
+                libm::$tan(self)
+            }
+            fn tanh(self) -> Self {
+                libm::$tanh(self)
+            }
+            fn atan(self) -> Self {
+                libm::$atan(self)
+            }
+            fn exp(self) -> Self {
+                libm::$exp(self)
+            }
+            fn exp2(self) -> Self {
+                libm::$exp2(self)
+            }
+            fn exp_m1(self) -> Self {
+                libm::$expm1(self)
+            }
+            fn log10(self) -> Self {
+                libm::$log10(self)
+            }
+            fn powf(self, n: Self) -> Self {
+                libm::$pow(self, n)
+            }
+            fn powi(self, n: i32) -> Self {
+                libm::$pow(self, n as $t)
+            }
+            fn sqrt(self) -> Self {
+                libm::$sqrt(self)
+            }
+            fn floor(self) -> Self {
+                libm::$floor(self)
+            }
+            fn ceil(self) -> Self {
+                libm::$ceil(self)
+            }
+            fn round(self) -> Self {
+                libm::$round(self)
+            }
+            fn fract(self) -> Self {
+                self - libm::$trunc(self)
+            }
+            fn rem_euclid(self, rhs: Self) -> Self {
+                let r = libm::$fmod(self, rhs);
+                if r < 0.0 {
+                    r + rhs.abs()
+                } else {
+                    r
+                }
+            }
+        }
+    };
+}
+
+impl_float!(
+    f32, sinf, cosf, tanf, tanhf, atanf, expf, exp2f, expm1f, log10f, powf, sqrtf, floorf, ceilf,
+    roundf, truncf, fmodf
+);
+impl_float!(
+    f64, sin, cos, tan, tanh, atan, exp, exp2, expm1, log10, pow, sqrt, floor, ceil, round, trunc,
+    fmod
+);
@@ -0,0 +1,407 @@
+//! Real-time capture of the rendered master output
+//!
+//! Unlike [`crate::bounce`], which re-renders the engine offline, the
+//! [`Recorder`] taps the live master bus so a jam — including knob tweaks
+//! that cannot be reproduced offline — can be kept as played.
+
+use crate::error::GooeyError;
+use crate::frame::StereoFrame;
+
+/// Recorder is idle: frames are ignored.
+pub const RECORD_STATE_IDLE: u32 = 0;
+/// Recorder is armed: capture begins on the first non-silent frame.
+pub const RECORD_STATE_ARMED: u32 = 1;
+/// Recorder is capturing every rendered frame.
+pub const RECORD_STATE_RECORDING: u32 = 2;
+
+/// Absolute level above which an armed recorder starts capturing.
+/// Roughly -80 dBFS, comfortably above denormal/smoothing residue.
+pub const ARM_THRESHOLD: f32 = 1.0e-4;
+
+/// Default take length, in seconds, allocated when a take is armed or started.
+pub const DEFAULT_RESERVE_SECONDS: f32 = 60.0;
+
+/// Longest take, in seconds, that can be reserved. Longer requests are
+/// clamped to it (about 660 MB at 48 kHz).
+pub const MAX_RESERVE_SECONDS: f32 = 30.0 * 60.0;
+
+/// Capture state of a [`Recorder`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordState {
+    Idle,
+    Armed,
+    Recording,
+}
+
+impl RecordState {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            RECORD_STATE_IDLE => Some(Self::Idle),
+            RECORD_STATE_ARMED => Some(Self::Armed),
+            RECORD_STATE_RECORDING => Some(Self::Recording),
+            _ => None,
+        }
+    }
+
+    pub fn as_u32(self) -> u32 {
+        match self {
+            Self::Idle => RECORD_STATE_IDLE,
+            Self::Armed => RECORD_STATE_ARMED,
+            Self::Recording => RECORD_STATE_RECORDING,
+        }
+    }
+}
+
+/// Captures master output frames into a fixed-length interleaved stereo
+/// buffer.
+///
+/// Nothing is allocated until a take is armed or started, which happens on
+/// the host thread; the buffer is then zero-filled to the reserved length
+/// (see [`Recorder::reserve_seconds`]) so every page is touched before the
+/// audio thread writes to it. The audio thread never grows it: a take that
+/// fills the buffer stops and reports [`Recorder::overflowed`].
+pub struct Recorder {
+    sample_rate: f32,
+    state: RecordState,
+    /// Interleaved `[l, r]` samples; the first `len` are the take.
+    samples: Vec<f32>,
+    len: usize,
+    /// Interleaved samples a take has room for once allocated.
+    reserve: usize,
+    /// The last take filled the buffer and was stopped.
+    overflowed: bool,
+}
+
+impl Recorder {
+    pub fn new(sample_rate: f32) -> Self {
+        let mut recorder = Self {
+            sample_rate,
+            state: RecordState::Idle,
+            samples: Vec::new(),
+            len: 0,
+            reserve: 0,
+            overflowed: false,
+        };
+        recorder.set_reserve(DEFAULT_RESERVE_SECONDS);
+        recorder
+    }
+
+    /// Set the take length to `seconds` of stereo audio, clamped to
+    /// [`MAX_RESERVE_SECONDS`]. Allocates straight away if a take is already
+    /// allocated, otherwise when the next take is armed or started. Call
+    /// from the host thread.
+    ///
+    /// Fails for a non-positive or non-finite length, or if the buffer can't
+    /// be allocated; the previous buffer is kept either way.
+    pub fn reserve_seconds(&mut self, seconds: f32) -> Result<(), GooeyError> {
+        self.set_take_length(seconds)?;
+        if self.is_allocated() {
+            self.allocate()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// [`reserve_seconds`](Self::reserve_seconds) without the allocation:
+    /// only the length the next [`storage_needed`](Self::storage_needed)
+    /// asks for changes.
+    pub fn set_take_length(&mut self, seconds: f32) -> Result<(), GooeyError> {
+        if !seconds.is_finite() || seconds <= 0.0 {
+            return Err(GooeyError::InvalidValue(format!(
+                "take length must be positive, got {seconds} s"
+            )));
+        }
+        self.set_reserve(seconds);
+        Ok(())
+    }
+
+    /// Whether a take buffer has been allocated.
+    pub fn is_allocated(&self) -> bool {
+        !self.samples.is_empty()
+    }
+
+    fn set_reserve(&mut self, seconds: f32) {
+        let seconds = seconds.min(MAX_RESERVE_SECONDS);
+        let frames = (seconds as f64 * self.sample_rate as f64).ceil() as usize;
+        self.reserve = frames * 2;
+    }
+
+    /// Grow the buffer to the reserved take length.
+    fn allocate(&mut self) -> Result<(), GooeyError> {
+        if let Some(len) = self.storage_needed() {
+            let storage = Self::alloc_storage(len)?;
+            self.install_storage(storage);
+        }
+        Ok(())
+    }
+
+    /// Length, in interleaved samples, of the buffer the next take needs if
+    /// the current one is too short.
+    pub fn storage_needed(&self) -> Option<usize> {
+        (self.samples.len() < self.reserve).then_some(self.reserve)
+    }
+
+    /// A zero-filled take buffer of `len` interleaved samples, every page
+    /// written so the audio thread never faults a fresh one in. Allocates:
+    /// call off the audio thread, then hand it to
+    /// [`install_storage`](Self::install_storage).
+    pub fn alloc_storage(len: usize) -> Result<Vec<f32>, GooeyError> {
+        let mut storage = Vec::new();
+        storage
+            .try_reserve_exact(len)
+            .map_err(|_| GooeyError::OutOfMemory(len * std::mem::size_of::<f32>()))?;
+        storage.resize(len, 0.0);
+        Ok(storage)
+    }
+
+    /// Record into `storage` from now on, copying the take so far over, and
+    /// return the buffer it replaces so the caller frees it. `storage` comes
+    /// straight back if it is no longer than the current buffer.
+    pub fn install_storage(&mut self, mut storage: Vec<f32>) -> Vec<f32> {
+        if storage.len() <= self.samples.len() {
+            return storage;
+        }
+        storage[..self.len].copy_from_slice(&self.samples[..self.len]);
+        std::mem::swap(&mut self.samples, &mut storage);
+        storage
+    }
+
+    /// Arm the recorder: the take starts at the first frame whose level
+    /// exceeds [`ARM_THRESHOLD`]. Has no effect while already recording.
+    /// Fails, leaving the recorder idle, if the buffer can't be allocated.
+    pub fn arm(&mut self) -> Result<(), GooeyError> {
+        if self.state == RecordState::Idle {
+            self.allocate()?;
+            self.overflowed = false;
+            self.state = RecordState::Armed;
+        }
+        Ok(())
+    }
+
+    /// Start capturing immediately, appending to any existing take. Fails,
+    /// leaving the state unchanged, if the buffer can't be allocated.
+    pub fn start(&mut self) -> Result<(), GooeyError> {
+        self.allocate()?;
+        self.overflowed = false;
+        self.state = RecordState::Recording;
+        Ok(())
+    }
+
+    /// Stop capturing (or disarm). The captured audio is kept.
+    pub fn stop(&mut self) {
+        self.state = RecordState::Idle;
+    }
+
+    /// Discard the captured audio, keeping the allocation for the next take.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.overflowed = false;
+    }
+
+    /// Whether the last take ran out of room and was stopped. Cleared by the
+    /// next arm, start or clear.
+    pub fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+
+    pub fn state(&self) -> RecordState {
+        self.state
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.state == RecordState::Recording
+    }
+
+    pub fn sample_rate(&self) -> f32 {
+        self.sample_rate
+    }
+
+    /// Number of stereo frames captured so far.
+    pub fn frame_count(&self) -> usize {
+        self.len / 2
+    }
+
+    /// Length of the captured audio in seconds.
+    pub fn duration_secs(&self) -> f64 {
+        self.frame_count() as f64 / self.sample_rate as f64
+    }
+
+    /// Captured audio as interleaved `[l, r]` samples.
+    pub fn samples(&self) -> &[f32] {
+        &self.samples[..self.len]
+    }
+
+    /// Move the captured audio out, leaving the recorder empty and idle. The
+    /// next take allocates a fresh buffer.
+    pub fn take(&mut self) -> Vec<f32> {
+        self.state = RecordState::Idle;
+        let mut samples = std::mem::take(&mut self.samples);
+        samples.truncate(std::mem::take(&mut self.len));
+        samples
+    }
+
+    /// Feed one rendered master frame. Called once per frame by the engine.
+    /// Never allocates: a full buffer stops the take instead.
+    #[inline]
+    pub fn push_frame(&mut self, frame: StereoFrame) {
+        match self.state {
+            RecordState::Idle => return,
+            RecordState::Armed => {
+                if frame.l.abs() <= ARM_THRESHOLD && frame.r.abs() <= ARM_THRESHOLD {
+                    return;
+                }
+                self.state = RecordState::Recording;
+            }
+            RecordState::Recording => {}
+        }
+        if self.len + 2 > self.reserve.min(self.samples.len()) {
+            self.state = RecordState::Idle;
+            self.overflowed = true;
+            return;
+        }
+        self.samples[self.len] = frame.l;
+        self.samples[self.len + 1] = frame.r;
+        self.len += 2;
+    }
+
+    /// Write the captured audio to a stereo WAV file at the recorder's
+    /// sample rate.
+    #[cfg(feature = "bounce")]
+    pub fn write_wav(
+        &self,
+        path: &std::path::Path,
+        config: crate::bounce::WavConfig,
+    ) -> Result<(), GooeyError> {
+        if config.bit_depth != 16 && config.bit_depth != 24 {
+            return Err(GooeyError::InvalidValue(format!(
+                "Unsupported bit depth: {}. Use 16 or 24.",
+                config.bit_depth
+            )));
+        }
+
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: self.sample_rate as u32,
+            bits_per_sample: config.bit_depth,
+            sample_format: hound::SampleFormat::Int,
+        };
+
+        let mut writer = hound::WavWriter::create(path, spec)
+            .map_err(|e| GooeyError::Io(format!("Failed to create WAV: {e}")))?;
+
+        let scale = match config.bit_depth {
+            16 => i16::MAX as f32,
+            _ => 8_388_607.0_f32, // 2^23 - 1
+        };
+        for &sample in self.samples() {
+            let s = (sample.clamp(-1.0, 1.0) * scale).round() as i32;
+            writer
+                .write_sample(s)
+                .map_err(|e| GooeyError::Io(format!("Failed to write sample: {e}")))?;
+        }
+
+        writer
+            .finalize()
+            .map_err(|e| GooeyError::Io(format!("Failed to finalize WAV: {e}")))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn idle_recorder_ignores_frames() {
+        let mut rec = Recorder::new(48_000.0);
+        rec.push_frame(StereoFrame::mono(0.5));
+        assert_eq!(rec.frame_count(), 0);
+    }
+
+    #[test]
+    fn armed_recorder_waits_for_signal() {
+        let mut rec = Recorder::new(48_000.0);
+        rec.arm().unwrap();
+        rec.push_frame(StereoFrame::mono(0.0));
+        rec.push_frame(StereoFrame::mono(0.0));
+        assert_eq!(rec.state(), RecordState::Armed);
+        assert_eq!(rec.frame_count(), 0);
+
+        rec.push_frame(StereoFrame { l: 0.25, r: -0.5 });
+        rec.push_frame(StereoFrame::mono(0.0));
+        assert_eq!(rec.state(), RecordState::Recording);
+        assert_eq!(rec.samples(), &[0.25, -0.5, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn stop_keeps_take_and_clear_discards_it() {
+        let mut rec = Recorder::new(48_000.0);
+        rec.start().unwrap();
+        rec.push_frame(StereoFrame::mono(0.1));
+        rec.stop();
+        rec.push_frame(StereoFrame::mono(0.2));
+        assert_eq!(rec.frame_count(), 1);
+
+        rec.clear();
+        assert_eq!(rec.frame_count(), 0);
+    }
+
+    #[test]
+    fn reserved_take_does_not_reallocate() {
+        let mut rec = Recorder::new(1_000.0);
+        rec.reserve_seconds(2.0).unwrap();
+        rec.start().unwrap();
+        let capacity = rec.samples.capacity();
+        assert_eq!(rec.samples.len(), 4_000);
+        for _ in 0..2_000 {
+            rec.push_frame(StereoFrame::mono(0.1));
+        }
+        assert_eq!(rec.samples.capacity(), capacity);
+        assert!((rec.duration_secs() - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn nothing_is_allocated_until_a_take_starts() {
+        let mut rec = Recorder::new(48_000.0);
+        assert_eq!(rec.samples.capacity(), 0);
+        rec.arm().unwrap();
+        assert_eq!(
+            rec.samples.len(),
+            48_000 * 2 * DEFAULT_RESERVE_SECONDS as usize
+        );
+    }
+
+    #[test]
+    fn reserve_is_validated_and_clamped() {
+        let mut rec = Recorder::new(10.0);
+        for bad in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            assert!(matches!(
+                rec.reserve_seconds(bad),
+                Err(GooeyError::InvalidValue(_))
+            ));
+        }
+        rec.reserve_seconds(1.0e9).unwrap();
+        rec.start().unwrap();
+        assert_eq!(rec.samples.len(), 10 * 2 * MAX_RESERVE_SECONDS as usize);
+    }
+
+    #[test]
+    fn full_take_stops_and_reports_overflow() {
+        let mut rec = Recorder::new(100.0);
+        rec.reserve_seconds(0.5).unwrap();
+        rec.start().unwrap();
+        let capacity = rec.samples.capacity();
+        let frames = 50;
+        for _ in 0..frames + 10 {
+            rec.push_frame(StereoFrame::mono(0.1));
+        }
+        assert_eq!(rec.samples.capacity(), capacity);
+        assert_eq!(rec.frame_count(), frames);
+        assert_eq!(rec.state(), RecordState::Idle);
+        assert!(rec.overflowed());
+
+        rec.clear();
+        assert!(!rec.overflowed());
+    }
+}
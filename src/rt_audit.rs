@@ -0,0 +1,115 @@
+//! Real-time safety audit
+//!
+//! The render path must never allocate, free or wait on a lock: any of them
+//! can stall the audio thread long enough to drop a buffer. With the
+//! `rt-audit` feature the engine's render entry points (the C API render,
+//! the cpal callback and, through the C API, the plugin) run inside an
+//! [`audio_section`], where every heap call and every blocking lock the
+//! library takes is counted against the current thread. Without the feature
+//! the section is a plain call and nothing is counted.
+//!
+//! Allocations are seen through [`AllocDisabler`], which the binary (or test)
+//! installs as its global allocator:
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOCATOR: gooey::rt_audit::AllocDisabler = gooey::rt_audit::AllocDisabler;
+//!
+//! gooey::rt_audit::reset_violations();
+//! gooey::rt_audit::audio_section(|| render_for_a_while(&mut engine));
+//! assert_eq!(gooey::rt_audit::violations(), Default::default());
+//! ```
+//!
+//! The engine mutex [`EngineOutput`](crate::engine::EngineOutput) takes per
+//! buffer is outside the section: it is the one lock the native stream
+//! shares with the control thread by design.
+
+#[cfg(feature = "rt-audit")]
+pub use assert_no_alloc::AllocDisabler;
+
+#[cfg(feature = "rt-audit")]
+use std::cell::Cell;
+
+/// What the current thread did inside [`audio_section`]s since the last
+/// [`reset_violations`]. Always empty without the `rt-audit` feature.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Violations {
+    /// Heap allocations and frees (needs [`AllocDisabler`] installed).
+    pub allocations: u32,
+    /// Blocking lock acquisitions.
+    pub locks: u32,
+    /// The most recent lock taken, for the failure message.
+    pub last_lock: Option<&'static str>,
+}
+
+#[cfg(feature = "rt-audit")]
+thread_local! {
+    static DEPTH: Cell<u32> = const { Cell::new(0) };
+    static LOCKS: Cell<u32> = const { Cell::new(0) };
+    static LAST_LOCK: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+/// Run `render` as audio-thread code: with `rt-audit`, allocations and
+/// blocking locks inside it count as [`Violations`]. Sections nest.
+#[inline]
+pub fn audio_section<T>(render: impl FnOnce() -> T) -> T {
+    #[cfg(feature = "rt-audit")]
+    {
+        struct Depth;
+        impl Drop for Depth {
+            fn drop(&mut self) {
+                DEPTH.with(|depth| depth.set(depth.get() - 1));
+            }
+        }
+        DEPTH.with(|depth| depth.set(depth.get() + 1));
+        let _depth = Depth;
+        assert_no_alloc::assert_no_alloc(render)
+    }
+    #[cfg(not(feature = "rt-audit"))]
+    render()
+}
+
+/// Whether the current thread is inside an [`audio_section`]. Always false
+/// without `rt-audit`.
+#[inline]
+pub fn in_audio_section() -> bool {
+    #[cfg(feature = "rt-audit")]
+    return DEPTH.with(Cell::get) > 0;
+    #[cfg(not(feature = "rt-audit"))]
+    false
+}
+
+/// Note that the library is about to block on the lock named `what`.
+/// Counted as a violation inside an [`audio_section`].
+#[inline]
+pub(crate) fn lock_acquired(what: &'static str) {
+    #[cfg(feature = "rt-audit")]
+    if in_audio_section() {
+        LOCKS.with(|locks| locks.set(locks.get() + 1));
+        LAST_LOCK.with(|last| last.set(Some(what)));
+    }
+    #[cfg(not(feature = "rt-audit"))]
+    let _ = what;
+}
+
+/// Violations recorded on the current thread.
+pub fn violations() -> Violations {
+    #[cfg(feature = "rt-audit")]
+    return Violations {
+        allocations: assert_no_alloc::violation_count(),
+        locks: LOCKS.with(Cell::get),
+        last_lock: LAST_LOCK.with(Cell::get),
+    };
+    #[cfg(not(feature = "rt-audit"))]
+    Violations::default()
+}
+
+/// Clear the current thread's [`violations`].
+pub fn reset_violations() {
+    #[cfg(feature = "rt-audit")]
+    {
+        assert_no_alloc::reset_violation_count();
+        LOCKS.with(|locks| locks.set(0));
+        LAST_LOCK.with(|last| last.set(None));
+    }
+}
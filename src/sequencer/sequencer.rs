@@ -1,3 +1,6 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
 /// A sample-accurate step sequencer that triggers callbacks on subdivisions of the beat.
 /// Currently supports 8th note subdivisions.
 pub struct Sequencer {
@@ -0,0 +1,532 @@
+//! Compact binary engine snapshots
+//!
+//! A snapshot holds the state a host would otherwise rebuild call by call:
+//! tempo, swing, master gain and tuning, the seed, the global effect chain,
+//! and for each channel its instrument, parameters, tuning, fader, pan,
+//! mute/solo and step pattern. It is meant for moving a session between
+//! engines (a UI thread and an audio worklet, say) or stashing it in a
+//! browser store, so it is a flat little-endian byte string: no text to
+//! parse and nothing to allocate beyond the result.
+//!
+//! Layout (all integers little-endian, floats as IEEE-754 bits):
+//!
+//! ```text
+//! "GOOS" version:u16
+//! bpm swing master_gain a4_hz transpose : f32   seed : u64
+//! order_len:u16 effect:u8*
+//! effect_count:u16 { effect:u8 enabled:u8 param_count:u16 f32* }*
+//! channel_count:u16 {
+//!     channel:u8 instrument:u8 tuning gain_db pan : f32 muted:u8 soloed:u8
+//!     param_count:u16 f32*
+//!     step_count:u16 { flags:u8 velocity:f32 [blend x y:f32] [note:u8]
+//!                      [articulation:u8] [tune:f32] [gate:f32] }*
+//! }*
+//! ```
+//!
+//! Step layers are not included. [`decode`] checks structure and ranges, so
+//! a snapshot that decodes can be applied without further validation.
+
+use crate::engine::{
+    SequencerBlendSetting, SequencerStep, STEP_GATE_MAX_STEPS, STEP_GATE_MIN_STEPS,
+    STEP_TUNE_MAX_SEMITONES,
+};
+use crate::error::GooeyError;
+pub use crate::error::SnapshotError;
+use crate::ffi::{
+    global_effect_param_count, CHANNEL_MAX, EFFECT_COUNT, INSTRUMENT_COUNT, SEQUENCER_PAGE_MAX,
+    SEQUENCER_PAGE_STEPS,
+};
+use crate::param_info::instrument_params;
+
+/// First bytes of every snapshot.
+pub const SNAPSHOT_MAGIC: [u8; 4] = *b"GOOS";
+/// Format version written by [`encode`]; [`decode`] reads this version only.
+pub const SNAPSHOT_VERSION: u16 = 1;
+
+const STEP_ENABLED: u8 = 1 << 0;
+const STEP_BLEND: u8 = 1 << 1;
+const STEP_NOTE: u8 = 1 << 2;
+const STEP_ARTICULATION: u8 = 1 << 3;
+const STEP_TUNE: u8 = 1 << 4;
+const STEP_GATE: u8 = 1 << 5;
+
+/// Longest pattern a channel can hold (every step page).
+const MAX_STEPS: usize = (SEQUENCER_PAGE_STEPS * SEQUENCER_PAGE_MAX) as usize;
+
+/// Engine-wide settings and every channel, as captured by
+/// `gooey_engine_export_state`.
+#[derive(Clone, Debug, Default)]
+pub struct EngineSnapshot {
+    pub bpm: f32,
+    pub swing: f32,
+    pub master_gain: f32,
+    pub a4_hz: f32,
+    pub transpose: f32,
+    pub seed: u64,
+    /// Reorderable effects in processing order (`EFFECT_*` IDs)
+    pub effect_order: Vec<u32>,
+    pub effects: Vec<EffectSnapshot>,
+    /// Occupied channels in ascending order
+    pub channels: Vec<ChannelSnapshot>,
+}
+
+/// One global effect: whether it is on and its parameters by index.
+#[derive(Clone, Debug, Default)]
+pub struct EffectSnapshot {
+    pub effect: u32,
+    pub enabled: bool,
+    pub params: Vec<f32>,
+}
+
+/// One channel: its instrument and parameters (registry order, setter
+/// space), mixer settings and pattern.
+#[derive(Clone, Debug, Default)]
+pub struct ChannelSnapshot {
+    pub channel: u32,
+    pub instrument_type: u32,
+    pub params: Vec<f32>,
+    pub tuning: f32,
+    pub gain_db: f32,
+    pub pan: f32,
+    pub muted: bool,
+    pub soloed: bool,
+    pub steps: Vec<SequencerStep>,
+}
+
+/// Serialize `snapshot` in the layout described in the module docs.
+pub fn encode(snapshot: &EngineSnapshot) -> Vec<u8> {
+    let mut out = Vec::with_capacity(64 + snapshot.channels.len() * 256);
+    out.extend_from_slice(&SNAPSHOT_MAGIC);
+    out.extend_from_slice(&SNAPSHOT_VERSION.to_le_bytes());
+    for value in [
+        snapshot.bpm,
+        snapshot.swing,
+        snapshot.master_gain,
+        snapshot.a4_hz,
+        snapshot.transpose,
+    ] {
+        put_f32(&mut out, value);
+    }
+    out.extend_from_slice(&snapshot.seed.to_le_bytes());
+
+    put_len(&mut out, snapshot.effect_order.len());
+    out.extend(snapshot.effect_order.iter().map(|&effect| effect as u8));
+    put_len(&mut out, snapshot.effects.len());
+    for effect in &snapshot.effects {
+        out.push(effect.effect as u8);
+        out.push(effect.enabled as u8);
+        put_f32s(&mut out, &effect.params);
+    }
+
+    put_len(&mut out, snapshot.channels.len());
+    for channel in &snapshot.channels {
+        out.push(channel.channel as u8);
+        out.push(channel.instrument_type as u8);
+        put_f32(&mut out, channel.tuning);
+        put_f32(&mut out, channel.gain_db);
+        put_f32(&mut out, channel.pan);
+        out.push(channel.muted as u8);
+        out.push(channel.soloed as u8);
+        put_f32s(&mut out, &channel.params);
+        put_len(&mut out, channel.steps.len());
+        for step in &channel.steps {
+            put_step(&mut out, step);
+        }
+    }
+    out
+}
+
+/// Parse a snapshot written by [`encode`]. Rejects trailing bytes,
+/// non-finite values, unknown or repeated effects in the order, unknown
+/// instruments, out-of-range or repeated channels, parameter lists longer
+/// than the effect's or instrument's, patterns longer than 64 steps and
+/// step velocity, tune or gate outside the sequencer's ranges, as
+/// [`GooeyError::InvalidSnapshot`].
+pub fn decode(bytes: &[u8]) -> Result<EngineSnapshot, GooeyError> {
+    Ok(read_snapshot(bytes)?)
+}
+
+fn read_snapshot(bytes: &[u8]) -> Result<EngineSnapshot, SnapshotError> {
+    let mut reader = Reader { bytes };
+    if reader.take(4).ok() != Some(&SNAPSHOT_MAGIC[..]) {
+        return Err(SnapshotError::NotASnapshot);
+    }
+    let version = reader.u16()?;
+    if version != SNAPSHOT_VERSION {
+        return Err(SnapshotError::UnsupportedVersion(version));
+    }
+
+    let mut snapshot = EngineSnapshot {
+        bpm: reader.f32()?,
+        swing: reader.f32()?,
+        master_gain: reader.f32()?,
+        a4_hz: reader.f32()?,
+        transpose: reader.f32()?,
+        seed: u64::from_le_bytes(reader.array()?),
+        ..Default::default()
+    };
+
+    for _ in 0..reader.u16()? {
+        let effect = reader.effect()?;
+        if snapshot.effect_order.contains(&effect) {
+            return Err(invalid(format!("effect {effect} is repeated in the order")));
+        }
+        snapshot.effect_order.push(effect);
+    }
+    for _ in 0..reader.u16()? {
+        let effect = EffectSnapshot {
+            effect: reader.effect()?,
+            enabled: reader.bool()?,
+            params: reader.f32s()?,
+        };
+        let param_count = global_effect_param_count(effect.effect).unwrap_or(0) as usize;
+        if effect.params.len() > param_count {
+            return Err(invalid(format!(
+                "effect {}: {} params for an effect with {param_count}",
+                effect.effect,
+                effect.params.len()
+            )));
+        }
+        snapshot.effects.push(effect);
+    }
+
+    for _ in 0..reader.u16()? {
+        let channel = reader.u8()? as u32;
+        if channel >= CHANNEL_MAX {
+            return Err(invalid(format!("channel {channel} is out of range")));
+        }
+        if snapshot
+            .channels
+            .last()
+            .is_some_and(|previous| previous.channel >= channel)
+        {
+            return Err(invalid(format!("channel {channel} is out of order")));
+        }
+        let instrument_type = reader.u8()? as u32;
+        if instrument_type >= INSTRUMENT_COUNT {
+            return Err(invalid(format!(
+                "channel {channel}: unknown instrument type {instrument_type}"
+            )));
+        }
+        let mut snapshot_channel = ChannelSnapshot {
+            channel,
+            instrument_type,
+            tuning: reader.f32()?,
+            gain_db: reader.f32()?,
+            pan: reader.f32()?,
+            muted: reader.bool()?,
+            soloed: reader.bool()?,
+            params: reader.f32s()?,
+            steps: Vec::new(),
+        };
+        let param_count = instrument_params(instrument_type).len();
+        if snapshot_channel.params.len() > param_count {
+            return Err(invalid(format!(
+                "channel {channel}: {} params for an instrument with {param_count}",
+                snapshot_channel.params.len()
+            )));
+        }
+        let step_count = reader.u16()? as usize;
+        if step_count > MAX_STEPS {
+            return Err(invalid(format!(
+                "channel {channel}: {step_count} steps, at most {MAX_STEPS} fit"
+            )));
+        }
+        for _ in 0..step_count {
+            snapshot_channel.steps.push(reader.step()?);
+        }
+        snapshot.channels.push(snapshot_channel);
+    }
+
+    if !reader.bytes.is_empty() {
+        return Err(invalid(format!(
+            "{} bytes after the end of the snapshot",
+            reader.bytes.len()
+        )));
+    }
+    Ok(snapshot)
+}
+
+fn invalid(message: String) -> SnapshotError {
+    SnapshotError::Invalid(message)
+}
+
+fn put_f32(out: &mut Vec<u8>, value: f32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Lengths are u16; nothing in the engine comes close to 65535 entries.
+fn put_len(out: &mut Vec<u8>, len: usize) {
+    out.extend_from_slice(&(len.min(u16::MAX as usize) as u16).to_le_bytes());
+}
+
+fn put_f32s(out: &mut Vec<u8>, values: &[f32]) {
+    put_len(out, values.len());
+    for &value in values.iter().take(u16::MAX as usize) {
+        put_f32(out, value);
+    }
+}
+
+fn put_step(out: &mut Vec<u8>, step: &SequencerStep) {
+    let mut flags = 0;
+    for (set, flag) in [
+        (step.enabled, STEP_ENABLED),
+        (step.blend.is_some(), STEP_BLEND),
+        (step.note.is_some(), STEP_NOTE),
+        (step.articulation.is_some(), STEP_ARTICULATION),
+        (step.tune.is_some(), STEP_TUNE),
+        (step.gate.is_some(), STEP_GATE),
+    ] {
+        if set {
+            flags |= flag;
+        }
+    }
+    out.push(flags);
+    put_f32(out, step.velocity);
+    if let Some(blend) = step.blend {
+        put_f32(out, blend.x);
+        put_f32(out, blend.y);
+    }
+    out.extend(step.note);
+    out.extend(step.articulation);
+    if let Some(tune) = step.tune {
+        put_f32(out, tune);
+    }
+    if let Some(gate) = step.gate {
+        put_f32(out, gate);
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], SnapshotError> {
+        if self.bytes.len() < len {
+            return Err(SnapshotError::Truncated);
+        }
+        let (head, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], SnapshotError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> Result<u8, SnapshotError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, SnapshotError> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    fn bool(&mut self) -> Result<bool, SnapshotError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            other => Err(invalid(format!("{other} is not a flag"))),
+        }
+    }
+
+    fn f32(&mut self) -> Result<f32, SnapshotError> {
+        let value = f32::from_le_bytes(self.array()?);
+        if value.is_finite() {
+            Ok(value)
+        } else {
+            Err(invalid(format!("{value} is not finite")))
+        }
+    }
+
+    fn f32s(&mut self) -> Result<Vec<f32>, SnapshotError> {
+        (0..self.u16()?).map(|_| self.f32()).collect()
+    }
+
+    fn effect(&mut self) -> Result<u32, SnapshotError> {
+        let effect = self.u8()? as u32;
+        if effect < EFFECT_COUNT {
+            Ok(effect)
+        } else {
+            Err(invalid(format!("unknown effect {effect}")))
+        }
+    }
+
+    /// A finite value inside `min..=max`.
+    fn f32_in(&mut self, what: &str, min: f32, max: f32) -> Result<f32, SnapshotError> {
+        let value = self.f32()?;
+        if (min..=max).contains(&value) {
+            Ok(value)
+        } else {
+            Err(invalid(format!(
+                "step {what} {value} is outside {min}-{max}"
+            )))
+        }
+    }
+
+    fn step(&mut self) -> Result<SequencerStep, SnapshotError> {
+        let flags = self.u8()?;
+        let velocity = self.f32_in("velocity", 0.0, 1.0)?;
+        let mut step = SequencerStep::with_velocity(flags & STEP_ENABLED != 0, velocity);
+        if flags & STEP_BLEND != 0 {
+            step.blend = Some(SequencerBlendSetting::new(self.f32()?, self.f32()?));
+        }
+        if flags & STEP_NOTE != 0 {
+            step.note = Some(self.u8()?.min(127));
+        }
+        if flags & STEP_ARTICULATION != 0 {
+            step.articulation = Some(self.u8()?);
+        }
+        if flags & STEP_TUNE != 0 {
+            step.tune =
+                Some(self.f32_in("tune", -STEP_TUNE_MAX_SEMITONES, STEP_TUNE_MAX_SEMITONES)?);
+        }
+        if flags & STEP_GATE != 0 {
+            step.gate = Some(self.f32_in("gate", STEP_GATE_MIN_STEPS, STEP_GATE_MAX_STEPS)?);
+        }
+        Ok(step)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffi::{EFFECT_DELAY, INSTRUMENT_BASS, INSTRUMENT_KICK};
+
+    fn sample() -> EngineSnapshot {
+        let mut step = SequencerStep::with_velocity(true, 0.5);
+        step.blend = Some(SequencerBlendSetting::new(0.25, 0.75));
+        step.note = Some(36);
+        step.gate = Some(1.5);
+        EngineSnapshot {
+            bpm: 128.0,
+            swing: 0.6,
+            master_gain: 0.3,
+            a4_hz: 432.0,
+            transpose: -2.0,
+            seed: 0xDEAD_BEEF,
+            effect_order: vec![EFFECT_DELAY, 0],
+            effects: vec![EffectSnapshot {
+                effect: EFFECT_DELAY,
+                enabled: true,
+                params: vec![2.0, 0.4],
+            }],
+            channels: vec![
+                ChannelSnapshot {
+                    channel: 0,
+                    instrument_type: INSTRUMENT_KICK,
+                    params: vec![0.1, 0.2],
+                    steps: vec![step, SequencerStep::new(false)],
+                    ..Default::default()
+                },
+                ChannelSnapshot {
+                    channel: 12,
+                    instrument_type: INSTRUMENT_BASS,
+                    gain_db: -6.0,
+                    muted: true,
+                    ..Default::default()
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_round_trip_is_byte_identical() {
+        let bytes = encode(&sample());
+        let decoded = decode(&bytes).unwrap();
+        assert_eq!(encode(&decoded), bytes);
+        assert_eq!(decoded.seed, 0xDEAD_BEEF);
+        let step = decoded.channels[0].steps[0];
+        assert_eq!(step.note, Some(36));
+        assert_eq!(step.tune, None);
+        assert_eq!(decoded.channels[1].channel, 12);
+    }
+
+    #[test]
+    fn test_rejects_damaged_input() {
+        let bytes = encode(&sample());
+        for len in 0..bytes.len() {
+            assert!(decode(&bytes[..len]).is_err(), "prefix of {len} bytes");
+        }
+        let mut extra = bytes.clone();
+        extra.push(0);
+        assert!(matches!(
+            decode(&extra),
+            Err(GooeyError::InvalidSnapshot(SnapshotError::Invalid(_)))
+        ));
+        let mut version = bytes.clone();
+        version[4] = 9;
+        assert_eq!(
+            decode(&version).unwrap_err(),
+            GooeyError::InvalidSnapshot(SnapshotError::UnsupportedVersion(9))
+        );
+        assert_eq!(
+            decode(b"RIFF....").unwrap_err(),
+            GooeyError::InvalidSnapshot(SnapshotError::NotASnapshot)
+        );
+
+        let mut bad = sample();
+        bad.bpm = f32::NAN;
+        assert!(matches!(
+            decode(&encode(&bad)),
+            Err(GooeyError::InvalidSnapshot(SnapshotError::Invalid(_)))
+        ));
+        let mut bad = sample();
+        bad.channels.swap(0, 1);
+        assert!(matches!(
+            decode(&encode(&bad)),
+            Err(GooeyError::InvalidSnapshot(SnapshotError::Invalid(_)))
+        ));
+    }
+
+    fn assert_invalid(snapshot: &EngineSnapshot) {
+        assert!(matches!(
+            decode(&encode(snapshot)),
+            Err(GooeyError::InvalidSnapshot(SnapshotError::Invalid(_)))
+        ));
+    }
+
+    #[test]
+    fn test_rejects_patterns_past_the_last_page() {
+        let mut full = sample();
+        full.channels[1].steps = vec![SequencerStep::new(true); MAX_STEPS];
+        assert_eq!(decode(&encode(&full)).unwrap().channels[1].steps.len(), 64);
+        full.channels[1].steps.push(SequencerStep::new(true));
+        assert_invalid(&full);
+    }
+
+    #[test]
+    fn test_rejects_step_values_out_of_range() {
+        let step = |edit: fn(&mut SequencerStep)| {
+            let mut bad = sample();
+            edit(&mut bad.channels[0].steps[0]);
+            bad
+        };
+        assert_invalid(&step(|s| s.velocity = 1.5));
+        assert_invalid(&step(|s| s.velocity = -0.1));
+        assert_invalid(&step(|s| s.gate = Some(0.0)));
+        assert_invalid(&step(|s| s.gate = Some(STEP_GATE_MAX_STEPS + 1.0)));
+        assert_invalid(&step(|s| s.tune = Some(STEP_TUNE_MAX_SEMITONES + 0.5)));
+        assert_invalid(&step(|s| s.tune = Some(-STEP_TUNE_MAX_SEMITONES - 0.5)));
+        decode(&encode(&step(|s| {
+            s.velocity = 1.0;
+            s.gate = Some(STEP_GATE_MAX_STEPS);
+            s.tune = Some(-STEP_TUNE_MAX_SEMITONES);
+        })))
+        .unwrap();
+    }
+
+    #[test]
+    fn test_rejects_repeated_effects_and_extra_effect_params() {
+        let mut bad = sample();
+        bad.effect_order = vec![EFFECT_DELAY, EFFECT_DELAY];
+        assert_invalid(&bad);
+
+        let mut bad = sample();
+        bad.effects[0].params = vec![0.5; 8];
+        assert_invalid(&bad);
+    }
+}
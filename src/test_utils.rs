@@ -0,0 +1,253 @@
+//! Measurements for asserting on rendered audio in tests
+//!
+//! Renders an instrument to a buffer and pulls out the numbers an instrument
+//! change should be judged by: level and RMS envelope, decay time, zero
+//! crossings, spectral centroid and peak frequency. Tests can then state
+//! "the kick's fundamental sits near 50 Hz and it falls 60 dB within 600 ms"
+//! instead of comparing against numbers read off a plot.
+//!
+//! Everything here is plain arithmetic on `&[f32]` (the spectrum uses a
+//! small built-in FFT), so it needs no extra dependencies. It is built with
+//! the `test-utils` feature, which this crate's own tests turn on through a
+//! dev-dependency on itself.
+
+use crate::engine::Instrument;
+use crate::ffi::{gooey_engine_render, GooeyEngine};
+use crate::utils::SampleClock;
+use core::f32::consts::PI;
+
+/// Level reported for digital silence.
+pub const SILENCE_DB: f32 = -200.0;
+
+/// Trigger `instrument` once at full velocity and render `seconds` of it.
+pub fn render(instrument: &mut dyn Instrument, sample_rate: f32, seconds: f32) -> Vec<f32> {
+    render_with_velocity(instrument, sample_rate, seconds, 1.0)
+}
+
+/// Trigger `instrument` once at `velocity` and render `seconds` of it.
+pub fn render_with_velocity(
+    instrument: &mut dyn Instrument,
+    sample_rate: f32,
+    seconds: f32,
+    velocity: f32,
+) -> Vec<f32> {
+    let mut clock = SampleClock::new(sample_rate);
+    instrument.trigger_at(clock, velocity);
+    (0..(seconds * sample_rate) as usize)
+        .map(|_| {
+            let sample = instrument.tick_at(clock);
+            clock.advance();
+            sample
+        })
+        .collect()
+}
+
+/// Render `frames` stereo frames from an FFI engine, interleaved.
+///
+/// # Safety
+/// `engine` must be a live pointer from `gooey_engine_new`.
+pub unsafe fn render_frames(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    let mut buf = vec![0.0_f32; frames * 2];
+    gooey_engine_render(engine, buf.as_mut_ptr(), frames as u32);
+    buf
+}
+
+/// An amplitude in dBFS ([`SILENCE_DB`] for zero).
+pub fn to_db(amplitude: f32) -> f32 {
+    if amplitude > 0.0 {
+        (20.0 * amplitude.log10()).max(SILENCE_DB)
+    } else {
+        SILENCE_DB
+    }
+}
+
+/// Largest absolute sample.
+pub fn peak(samples: &[f32]) -> f32 {
+    samples.iter().fold(0.0, |m, s| m.max(s.abs()))
+}
+
+/// Root-mean-square level (0.0 for an empty buffer).
+pub fn rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let power: f64 = samples.iter().map(|&s| (s as f64) * (s as f64)).sum();
+    (power / samples.len() as f64).sqrt() as f32
+}
+
+/// Sign changes between neighbouring samples.
+pub fn zero_crossings(samples: &[f32]) -> usize {
+    samples
+        .windows(2)
+        .filter(|w| (w[0] < 0.0) != (w[1] < 0.0))
+        .count()
+}
+
+/// RMS level in dBFS of consecutive `window_ms` windows.
+pub fn rms_envelope(samples: &[f32], sample_rate: f32, window_ms: f32) -> Vec<f32> {
+    let window = ((window_ms * 0.001 * sample_rate) as usize).max(1);
+    samples
+        .chunks(window)
+        .map(|chunk| to_db(rms(chunk)))
+        .collect()
+}
+
+/// How long after its loudest moment the sound takes to fall `drop_db`
+/// below it and stay there, measured on a 1 ms RMS envelope. `None` when it
+/// never gets that quiet within the buffer (or is silent throughout).
+pub fn decay_time_ms(samples: &[f32], sample_rate: f32, drop_db: f32) -> Option<f32> {
+    const WINDOW_MS: f32 = 1.0;
+    let envelope = rms_envelope(samples, sample_rate, WINDOW_MS);
+    let (loudest, &peak_db) = envelope
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))?;
+    if peak_db <= SILENCE_DB {
+        return None;
+    }
+    let threshold = peak_db - drop_db.abs();
+    let last_above = envelope
+        .iter()
+        .rposition(|&level| level > threshold)
+        .unwrap_or(loudest);
+    if last_above + 1 >= envelope.len() {
+        return None;
+    }
+    Some((last_above + 1 - loudest) as f32 * WINDOW_MS)
+}
+
+/// Magnitude spectrum of `samples` through a Hann window, zero-padded to a
+/// power of two. Bin `k` is at `k * sample_rate / (2 * (len - 1))` Hz for a
+/// result of length `len`; see [`bin_frequency`].
+pub fn spectrum(samples: &[f32]) -> Vec<f32> {
+    let size = samples.len().next_power_of_two().max(2);
+    let mut re = vec![0.0_f64; size];
+    let mut im = vec![0.0_f64; size];
+    let denominator = samples.len().saturating_sub(1).max(1) as f32;
+    for (i, &s) in samples.iter().enumerate() {
+        let window = 0.5 - 0.5 * (2.0 * PI * i as f32 / denominator).cos();
+        re[i] = (s * window) as f64;
+    }
+    fft(&mut re, &mut im);
+    re.iter()
+        .zip(&im)
+        .take(size / 2 + 1)
+        .map(|(r, i)| (r * r + i * i).sqrt() as f32)
+        .collect()
+}
+
+/// Frequency of bin `bin` in a [`spectrum`] of `bins` bins.
+pub fn bin_frequency(bin: f32, bins: usize, sample_rate: f32) -> f32 {
+    bin * sample_rate / (2 * (bins - 1).max(1)) as f32
+}
+
+/// Magnitude-weighted mean frequency: where the sound's brightness sits.
+pub fn spectral_centroid(samples: &[f32], sample_rate: f32) -> f32 {
+    let magnitudes = spectrum(samples);
+    let total: f32 = magnitudes.iter().sum();
+    if total <= 0.0 {
+        return 0.0;
+    }
+    let weighted: f32 = magnitudes
+        .iter()
+        .enumerate()
+        .map(|(k, m)| bin_frequency(k as f32, magnitudes.len(), sample_rate) * m)
+        .sum();
+    weighted / total
+}
+
+/// Frequency of the strongest spectral peak, refined between bins by
+/// fitting a parabola through the peak and its neighbours.
+pub fn peak_frequency(samples: &[f32], sample_rate: f32) -> f32 {
+    let magnitudes = spectrum(samples);
+    let Some((k, _)) = magnitudes
+        .iter()
+        .enumerate()
+        .skip(1)
+        .max_by(|a, b| a.1.total_cmp(b.1))
+    else {
+        return 0.0;
+    };
+    let offset = match (magnitudes.get(k - 1), magnitudes.get(k + 1)) {
+        (Some(&left), Some(&right)) => {
+            let (a, b, c) = (left, magnitudes[k], right);
+            let curvature = a - 2.0 * b + c;
+            if curvature.abs() > f32::EPSILON {
+                (0.5 * (a - c) / curvature).clamp(-0.5, 0.5)
+            } else {
+                0.0
+            }
+        }
+        _ => 0.0,
+    };
+    bin_frequency(k as f32 + offset, magnitudes.len(), sample_rate)
+}
+
+/// In-place iterative radix-2 FFT; `re.len()` must be a power of two.
+fn fft(re: &mut [f64], im: &mut [f64]) {
+    let n = re.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+    let mut length = 2;
+    while length <= n {
+        let angle = -2.0 * core::f64::consts::PI / length as f64;
+        let (w_re, w_im) = (angle.cos(), angle.sin());
+        for start in (0..n).step_by(length) {
+            let (mut t_re, mut t_im) = (1.0, 0.0);
+            for k in 0..length / 2 {
+                let (a, b) = (start + k, start + k + length / 2);
+                let u = (re[a], im[a]);
+                let v = (re[b] * t_re - im[b] * t_im, re[b] * t_im + im[b] * t_re);
+                re[a] = u.0 + v.0;
+                im[a] = u.1 + v.1;
+                re[b] = u.0 - v.0;
+                im[b] = u.1 - v.1;
+                (t_re, t_im) = (t_re * w_re - t_im * w_im, t_re * w_im + t_im * w_re);
+            }
+        }
+        length <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(frequency: f32, sample_rate: f32, frames: usize) -> Vec<f32> {
+        (0..frames)
+            .map(|i| (2.0 * PI * frequency * i as f32 / sample_rate).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_peak_frequency_and_centroid_find_a_sine() {
+        let tone = sine(1000.0, 48000.0, 8192);
+        assert!((peak_frequency(&tone, 48000.0) - 1000.0).abs() < 3.0);
+        assert!((spectral_centroid(&tone, 48000.0) - 1000.0).abs() < 50.0);
+        // Ten cycles in 10 ms
+        assert!((19..=20).contains(&zero_crossings(&tone[..480])));
+        assert!((to_db(rms(&tone)) + 3.01).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_decay_time_of_an_exponential_fade() {
+        // 60 dB over 100 ms
+        let fade: Vec<f32> = (0..9600)
+            .map(|i| 10.0_f32.powf(-3.0 * i as f32 / 4800.0) * if i % 2 == 0 { 1.0 } else { -1.0 })
+            .collect();
+        let decay = decay_time_ms(&fade, 48000.0, 60.0).unwrap();
+        assert!((decay - 100.0).abs() < 2.0, "{decay}");
+        assert_eq!(decay_time_ms(&fade[..2400], 48000.0, 60.0), None);
+    }
+}
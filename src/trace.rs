@@ -0,0 +1,141 @@
+//! Engine event log for debugging timing issues
+//!
+//! The audio thread records what it did (hits, step advances, parameter
+//! writes, voice steals, xruns) into a bounded queue stamped with the engine
+//! frame, and any other thread drains it, e.g. to dump a timeline after a
+//! glitch. Recording never allocates or blocks; events that don't fit are
+//! counted and dropped.
+//!
+//! Gated at compile time by the `trace` feature. Without it
+//! [`TraceLog::record`] is an empty inline function, the queue holds no
+//! buffer, and nothing is ever logged.
+
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+
+/// Events held between drains when tracing is compiled in.
+pub const TRACE_CAPACITY: usize = 4096;
+
+/// Something the engine did on the audio thread.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TraceEvent {
+    /// An instrument hit (sequencer step or manual trigger).
+    Trigger { channel: u32, velocity: f32 },
+    /// A running sequencer moved to a new step.
+    Step { channel: u32, step: u32 },
+    /// A parameter write reached an instrument.
+    Param {
+        channel: u32,
+        param: u32,
+        value: f32,
+    },
+    /// The poly synth cut off its oldest voice to play `note` (MIDI).
+    VoiceSteal { note: u32 },
+    /// The host clock skipped ahead of the rendered audio.
+    Xrun { missed_frames: u32 },
+}
+
+/// A [`TraceEvent`] and the engine frame it happened at.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TraceRecord {
+    pub frame: u64,
+    pub event: TraceEvent,
+}
+
+/// Bounded event queue: written by the audio thread, drained by any one
+/// other thread.
+pub struct TraceLog {
+    tx: SyncSender<TraceRecord>,
+    rx: Receiver<TraceRecord>,
+    dropped: AtomicU32,
+}
+
+impl TraceLog {
+    /// Whether tracing is compiled in (the `trace` feature).
+    pub const ENABLED: bool = cfg!(feature = "trace");
+
+    pub fn new() -> Self {
+        let (tx, rx) = sync_channel(if Self::ENABLED { TRACE_CAPACITY } else { 0 });
+        Self {
+            tx,
+            rx,
+            dropped: AtomicU32::new(0),
+        }
+    }
+
+    /// Log `event` at `frame`. A no-op unless tracing is compiled in.
+    #[inline(always)]
+    pub fn record(&self, frame: u64, event: TraceEvent) {
+        if !Self::ENABLED {
+            return;
+        }
+        if self.tx.try_send(TraceRecord { frame, event }).is_err() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// The oldest record not yet drained.
+    pub fn pop(&self) -> Option<TraceRecord> {
+        self.rx.try_recv().ok()
+    }
+
+    /// Records dropped because the queue was full, since the last call.
+    pub fn take_dropped(&self) -> u32 {
+        self.dropped.swap(0, Ordering::Relaxed)
+    }
+
+    /// Drain everything queued as one line per record, oldest first.
+    pub fn dump(&self) -> String {
+        let mut out = String::new();
+        while let Some(TraceRecord { frame, event }) = self.pop() {
+            let _ = writeln!(out, "{frame:>10} {event:?}");
+        }
+        let dropped = self.take_dropped();
+        if dropped > 0 {
+            let _ = writeln!(out, "({dropped} events dropped)");
+        }
+        out
+    }
+}
+
+impl Default for TraceLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn test_records_drain_in_order_and_overflow_is_counted() {
+        let log = TraceLog::new();
+        for frame in 0..TRACE_CAPACITY as u64 + 3 {
+            log.record(
+                frame,
+                TraceEvent::Step {
+                    channel: 0,
+                    step: 1,
+                },
+            );
+        }
+        assert_eq!(log.pop().map(|r| r.frame), Some(0));
+        assert_eq!(log.pop().map(|r| r.frame), Some(1));
+        let dump = log.dump();
+        assert_eq!(dump.lines().count(), TRACE_CAPACITY - 2 + 1);
+        assert!(dump.ends_with("(3 events dropped)\n"), "{dump}");
+        assert_eq!(log.pop(), None);
+    }
+
+    #[cfg(not(feature = "trace"))]
+    #[test]
+    fn test_records_nothing_when_compiled_out() {
+        let log = TraceLog::new();
+        log.record(0, TraceEvent::Xrun { missed_frames: 64 });
+        assert_eq!(log.pop(), None);
+        assert_eq!(log.take_dropped(), 0);
+    }
+}
@@ -16,6 +16,13 @@ pub trait Blendable: Clone + Copy {
     fn lerp(&self, other: &Self, t: f32) -> Self;
 }
 
+impl Blendable for f32 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        self * (1.0 - t) + other * t
+    }
+}
+
 /// 2D preset blender for X/Y pad-style interpolation
 ///
 /// Stores 4 corner presets and blends between them using bilinear interpolation.
@@ -103,6 +110,36 @@ impl<T: Blendable> PresetBlender<T> {
     }
 }
 
+/// Move `current` toward a random point in the space spanned by `presets`.
+///
+/// The random target is a bilinear blend of four presets drawn (with
+/// replacement) from `presets` at a random X/Y position; `current` is then
+/// lerped toward it by `amount` (0.0 = unchanged, 1.0 = the target itself).
+/// Every target lies between existing presets, so repeated calls form a
+/// bounded random walk that keeps the instrument sounding like itself. The
+/// same `seed` always gives the same result. Returns `current` unchanged if
+/// `presets` is empty.
+pub fn random_blend<T: Blendable>(current: &T, presets: &[T], amount: f32, seed: u32) -> T {
+    if presets.is_empty() {
+        return *current;
+    }
+
+    // xorshift32; a zero state would stick at zero
+    let mut state = if seed == 0 { 0x6d2b_79f5 } else { seed };
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state
+    };
+    let mut pick = || presets[next() as usize % presets.len()];
+
+    let blender = PresetBlender::new(pick(), pick(), pick(), pick());
+    let x = next() as f32 / u32::MAX as f32;
+    let y = next() as f32 / u32::MAX as f32;
+    current.lerp(&blender.blend(x, y), amount.clamp(0.0, 1.0))
+}
+
 /// Macro to implement Blendable for a struct with all f32 fields
 ///
 /// # Usage
@@ -236,6 +273,35 @@ mod tests {
         assert_eq!(result.b, 1.0);
     }
 
+    #[test]
+    fn test_random_blend_is_seeded_and_bounded() {
+        let current = TestConfig { a: 0.5, b: 0.5 };
+        let presets = [
+            TestConfig { a: 0.2, b: 0.6 },
+            TestConfig { a: 0.4, b: 0.9 },
+            TestConfig { a: 0.3, b: 0.7 },
+        ];
+
+        assert_eq!(random_blend(&current, &presets, 0.0, 7), current);
+        assert_eq!(
+            random_blend(&current, &presets, 0.8, 7),
+            random_blend(&current, &presets, 0.8, 7)
+        );
+
+        for seed in 1..200 {
+            // Fully randomized configs stay within the presets' range.
+            let result = random_blend(&current, &presets, 1.0, seed);
+            assert!(
+                (0.2 - 1e-6..=0.4 + 1e-6).contains(&result.a),
+                "seed {seed}: {result:?}"
+            );
+            assert!(
+                (0.6 - 1e-6..=0.9 + 1e-6).contains(&result.b),
+                "seed {seed}: {result:?}"
+            );
+        }
+    }
+
     #[test]
     fn test_uniform_blender() {
         let preset = TestConfig { a: 0.5, b: 0.75 };
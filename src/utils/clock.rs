@@ -0,0 +1,109 @@
+//! Sample-counted audio time
+//!
+//! Adding `1.0 / sample_rate` to a running total every sample loses
+//! precision as the total grows: after half an hour an `f32` clock can no
+//! longer resolve a single sample, and envelope timing audibly smears. A
+//! [`SampleClock`] counts whole samples in a `u64` instead and derives
+//! seconds from the count when asked, so the error never accumulates no
+//! matter how long the stream runs.
+
+/// Seconds at `sample` for a stream running at `sample_rate`, derived from
+/// the count rather than accumulated.
+#[inline]
+pub fn sample_to_seconds(sample: u64, sample_rate: f32) -> f64 {
+    sample as f64 / sample_rate as f64
+}
+
+/// A position in an audio stream: samples since it started, plus the rate
+/// they were counted at.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SampleClock {
+    sample: u64,
+    sample_rate: f32,
+}
+
+impl SampleClock {
+    /// A clock at sample zero.
+    pub fn new(sample_rate: f32) -> Self {
+        Self::at(0, sample_rate)
+    }
+
+    /// A clock at `sample`.
+    pub fn at(sample: u64, sample_rate: f32) -> Self {
+        Self {
+            sample,
+            sample_rate,
+        }
+    }
+
+    /// Samples since the stream started.
+    pub fn sample(&self) -> u64 {
+        self.sample
+    }
+
+    pub fn sample_rate(&self) -> f32 {
+        self.sample_rate
+    }
+
+    /// Seconds since the stream started.
+    pub fn seconds(&self) -> f64 {
+        sample_to_seconds(self.sample, self.sample_rate)
+    }
+
+    /// Move on one sample.
+    #[inline]
+    pub fn advance(&mut self) {
+        self.advance_by(1);
+    }
+
+    /// Move on `samples` samples.
+    #[inline]
+    pub fn advance_by(&mut self, samples: u64) {
+        self.sample = self.sample.saturating_add(samples);
+    }
+
+    /// Back to sample zero.
+    pub fn reset(&mut self) {
+        self.sample = 0;
+    }
+
+    /// Samples from `earlier` to now (zero when `earlier` is in the future).
+    pub fn samples_since(&self, earlier: u64) -> u64 {
+        self.sample.saturating_sub(earlier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seconds_are_exact_after_hours_of_samples() {
+        let mut clock = SampleClock::new(48_000.0);
+        // Ten hours in, then one more sample
+        clock.advance_by(48_000 * 3600 * 10);
+        assert_eq!(clock.seconds(), 36_000.0);
+        clock.advance();
+        let one_sample = clock.seconds() - 36_000.0;
+        assert!((one_sample - 1.0 / 48_000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_half_an_hour_in_reads_exact_seconds() {
+        let samples = 44_100 * 1800;
+        let clock = SampleClock::at(samples, 44_100.0);
+        assert_eq!(clock.seconds(), 1800.0);
+        assert_eq!(clock.samples_since(samples - 10), 10);
+        // A mark in the future counts as no time passed
+        assert_eq!(clock.samples_since(samples + 10), 0);
+    }
+
+    #[test]
+    fn test_reset_returns_to_sample_zero() {
+        let mut clock = SampleClock::at(44_100 * 1800, 44_100.0);
+        clock.reset();
+        assert_eq!(clock.sample(), 0);
+        assert_eq!(clock.seconds(), 0.0);
+        assert_eq!(clock.sample_rate(), 44_100.0);
+    }
+}
@@ -0,0 +1,114 @@
+//! Crossfaded application of whole instrument configs
+
+use super::blendable::Blendable;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Default length of a config crossfade.
+pub const CONFIG_FADE_MS: f32 = 12.0;
+
+/// Samples between intermediate config applications during a fade.
+///
+/// Applying a full config touches every parameter (and recomputes some
+/// filter coefficients), so a fade steps through its configs at this rate
+/// rather than every sample; each step is still smoothed per parameter.
+pub const CONFIG_FADE_INTERVAL: u32 = 16;
+
+/// Moves an instrument from one config to another along a single shared ramp.
+///
+/// Setting a whole config at once retargets every parameter with its own
+/// smoothing time, and plain (unsmoothed) fields jump outright, so a preset
+/// load or blend move can still click. A `ConfigFade` instead interpolates the
+/// configs themselves with [`Blendable::lerp`], so every field moves in
+/// lockstep over [`CONFIG_FADE_MS`]. Discrete fields (filter modes, noise
+/// colors) switch halfway through, as they do on the blend pad.
+///
+/// Configs are `Copy`, so starting and running a fade never allocates and is
+/// safe on the audio thread.
+#[derive(Clone, Copy, Debug)]
+pub struct ConfigFade<C: Blendable> {
+    from: C,
+    to: C,
+    /// Samples elapsed
+    elapsed: u32,
+    /// Fade length in samples
+    length: u32,
+    /// Samples until the next intermediate config is due
+    countdown: u32,
+}
+
+impl<C: Blendable> ConfigFade<C> {
+    /// Fade from `from` (usually the instrument's current config) to `to`
+    /// over `fade_ms`.
+    pub fn new(from: C, to: C, fade_ms: f32, sample_rate: f32) -> Self {
+        Self {
+            from,
+            to,
+            elapsed: 0,
+            length: (fade_ms * 0.001 * sample_rate).round().max(1.0) as u32,
+            countdown: 0,
+        }
+    }
+
+    /// The config being faded to.
+    pub fn target(&self) -> C {
+        self.to
+    }
+
+    /// The config at the current fade position.
+    pub fn current(&self) -> C {
+        self.from
+            .lerp(&self.to, self.elapsed as f32 / self.length as f32)
+    }
+
+    /// Whether the fade has reached its target.
+    pub fn is_done(&self) -> bool {
+        self.elapsed >= self.length
+    }
+
+    /// Advance one sample. Returns the config to apply when one is due: at the
+    /// start, every [`CONFIG_FADE_INTERVAL`] samples, and exactly the target
+    /// on the final sample.
+    pub fn tick(&mut self) -> Option<C> {
+        if self.is_done() {
+            return None;
+        }
+        self.elapsed += 1;
+        if self.is_done() {
+            return Some(self.to);
+        }
+        if self.countdown == 0 {
+            self.countdown = CONFIG_FADE_INTERVAL;
+            return Some(self.current());
+        }
+        self.countdown -= 1;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fade_reaches_target_in_time() {
+        let mut fade = ConfigFade::new(0.0_f32, 1.0, 10.0, 1000.0);
+        let applied: Vec<f32> = (0..10).filter_map(|_| fade.tick()).collect();
+        assert!(fade.is_done());
+        assert_eq!(applied.last(), Some(&1.0));
+        assert!(
+            applied.windows(2).all(|w| w[1] >= w[0]),
+            "ramp is monotonic"
+        );
+        assert_eq!(fade.tick(), None);
+    }
+
+    #[test]
+    fn test_intermediate_configs_are_spaced() {
+        let mut fade = ConfigFade::new(0.0_f32, 1.0, 100.0, 1000.0);
+        let due: Vec<usize> = (0..40).filter(|_| fade.tick().is_some()).collect();
+        assert_eq!(due.len(), 3, "first sample plus one per interval");
+        assert!((fade.current() - 0.4).abs() < 1e-4);
+        assert_eq!(fade.target(), 1.0);
+    }
+}
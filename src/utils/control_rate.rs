@@ -0,0 +1,80 @@
+//! Block-rate updates for values that don't need to change every sample
+
+/// Samples between filter coefficient updates in the instruments: 0.33 ms
+/// at 48 kHz, far shorter than any envelope a voice sweeps a filter with.
+pub const CONTROL_INTERVAL: u32 = 16;
+
+/// Counts samples and says when a control value is due for recomputing.
+///
+/// Filter coefficients cost a `tan` or `sin`/`cos` plus a division, and a
+/// voice that sweeps its cutoff with an envelope would otherwise pay that on
+/// every sample. Recomputing every [`CONTROL_INTERVAL`] samples instead
+/// steps the cutoff in increments too small to hear. Call [`reset`] on
+/// trigger so a new hit starts from freshly computed coefficients.
+///
+/// [`reset`]: ControlRate::reset
+#[derive(Clone, Copy, Debug)]
+pub struct ControlRate {
+    interval: u32,
+    countdown: u32,
+}
+
+impl ControlRate {
+    pub fn new(interval: u32) -> Self {
+        Self {
+            interval: interval.max(1),
+            countdown: 0,
+        }
+    }
+
+    /// Advance one sample; true when the control value should be updated.
+    /// The first tick (and the first after a reset) is always due.
+    #[inline]
+    pub fn tick(&mut self) -> bool {
+        if self.countdown == 0 {
+            self.countdown = self.interval - 1;
+            true
+        } else {
+            self.countdown -= 1;
+            false
+        }
+    }
+
+    /// Make the next tick due.
+    pub fn reset(&mut self) {
+        self.countdown = 0;
+    }
+}
+
+impl Default for ControlRate {
+    fn default() -> Self {
+        Self::new(CONTROL_INTERVAL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_due_once_per_interval() {
+        let mut rate = ControlRate::new(4);
+        let due: Vec<bool> = (0..9).map(|_| rate.tick()).collect();
+        assert_eq!(
+            due,
+            [true, false, false, false, true, false, false, false, true]
+        );
+    }
+
+    #[test]
+    fn test_reset_makes_the_next_tick_due() {
+        let mut rate = ControlRate::default();
+        assert!(rate.tick());
+        assert!(!rate.tick());
+        rate.reset();
+        assert!(rate.tick());
+        // An interval of one updates every sample
+        let mut every = ControlRate::new(0);
+        assert!(every.tick() && every.tick());
+    }
+}
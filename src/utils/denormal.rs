@@ -0,0 +1,172 @@
+//! Denormal protection and NaN scrubbing
+//!
+//! Recursive DSP (filter memories, delay/reverb tails) decays towards zero
+//! through the subnormal range, where many CPUs — older x86 in particular —
+//! take a slow microcode path per operation. Two layers of protection:
+//!
+//! - [`DenormalGuard`] switches the FPU to flush-to-zero for the duration of a
+//!   render block on targets that support it (SSE on x86, FPCR on aarch64).
+//! - [`flush_denormal`] flushes state variables manually, which also covers
+//!   targets without hardware FTZ (e.g. wasm).
+//!
+//! Separately, a NaN or infinity that reaches a feedback path would otherwise
+//! stay in the state forever; processors check their output and reset
+//! themselves when it is not finite.
+
+/// Magnitude below which state variables are flushed to zero (about -300 dBFS).
+pub const DENORMAL_THRESHOLD: f32 = 1e-15;
+
+/// Whether [`DenormalGuard`] can enable hardware flush-to-zero on this target.
+pub const HARDWARE_FTZ: bool = cfg!(any(
+    target_arch = "x86_64",
+    all(target_arch = "x86", target_feature = "sse"),
+    target_arch = "aarch64"
+));
+
+/// Flush a tiny value to zero.
+#[inline]
+pub fn flush_denormal(x: f32) -> f32 {
+    if x.abs() < DENORMAL_THRESHOLD {
+        0.0
+    } else {
+        x
+    }
+}
+
+/// Replace a NaN or infinite sample with silence.
+#[inline]
+pub fn scrub(x: f32) -> f32 {
+    if x.is_finite() {
+        x
+    } else {
+        0.0
+    }
+}
+
+/// Enables hardware flush-to-zero (and denormals-are-zero on x86) on the
+/// current thread, restoring the previous floating-point mode on drop.
+///
+/// Create one at the top of each render callback; it is a no-op where
+/// [`HARDWARE_FTZ`] is false.
+pub struct DenormalGuard {
+    previous: u64,
+}
+
+impl DenormalGuard {
+    pub fn new() -> Self {
+        Self {
+            previous: imp::enable(),
+        }
+    }
+}
+
+impl Default for DenormalGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for DenormalGuard {
+    fn drop(&mut self) {
+        imp::restore(self.previous);
+    }
+}
+
+#[cfg(any(
+    target_arch = "x86_64",
+    all(target_arch = "x86", target_feature = "sse")
+))]
+mod imp {
+    use core::arch::asm;
+
+    /// MXCSR flush-to-zero (bit 15) and denormals-are-zero (bit 6).
+    const FTZ_DAZ: u32 = 0x8040;
+
+    pub fn enable() -> u64 {
+        let mut csr: u32 = 0;
+        // SAFETY: stmxcsr/ldmxcsr only read and write the SSE control
+        // register and the local it is stored in.
+        unsafe {
+            asm!("stmxcsr [{}]", in(reg) &mut csr as *mut u32, options(nostack, preserves_flags));
+            let ftz = csr | FTZ_DAZ;
+            asm!("ldmxcsr [{}]", in(reg) &ftz as *const u32, options(nostack, readonly, preserves_flags));
+        }
+        csr as u64
+    }
+
+    pub fn restore(previous: u64) {
+        let csr = previous as u32;
+        // SAFETY: see `enable`.
+        unsafe {
+            asm!("ldmxcsr [{}]", in(reg) &csr as *const u32, options(nostack, readonly, preserves_flags));
+        }
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod imp {
+    use core::arch::asm;
+
+    /// FPCR flush-to-zero bit.
+    const FZ: u64 = 1 << 24;
+
+    pub fn enable() -> u64 {
+        let fpcr: u64;
+        // SAFETY: only the floating-point control register is accessed.
+        unsafe {
+            asm!("mrs {}, fpcr", out(reg) fpcr, options(nomem, nostack, preserves_flags));
+            asm!("msr fpcr, {}", in(reg) fpcr | FZ, options(nomem, nostack, preserves_flags));
+        }
+        fpcr
+    }
+
+    pub fn restore(previous: u64) {
+        // SAFETY: see `enable`.
+        unsafe {
+            asm!("msr fpcr, {}", in(reg) previous, options(nomem, nostack, preserves_flags));
+        }
+    }
+}
+
+#[cfg(not(any(
+    target_arch = "x86_64",
+    all(target_arch = "x86", target_feature = "sse"),
+    target_arch = "aarch64"
+)))]
+mod imp {
+    pub fn enable() -> u64 {
+        0
+    }
+
+    pub fn restore(_previous: u64) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flush_and_scrub() {
+        assert_eq!(flush_denormal(1e-30), 0.0);
+        assert_eq!(flush_denormal(-1e-20), 0.0);
+        assert_eq!(flush_denormal(0.5), 0.5);
+        assert_eq!(scrub(f32::NAN), 0.0);
+        assert_eq!(scrub(f32::NEG_INFINITY), 0.0);
+        assert_eq!(scrub(-0.25), -0.25);
+    }
+
+    #[test]
+    fn guard_flushes_subnormal_results() {
+        let tiny = core::hint::black_box(f32::MIN_POSITIVE);
+        {
+            let _guard = DenormalGuard::new();
+            let product = core::hint::black_box(tiny) * core::hint::black_box(0.5);
+            if HARDWARE_FTZ {
+                assert_eq!(product, 0.0);
+            }
+        }
+        // Mode is restored once the guard is dropped.
+        let product = core::hint::black_box(tiny) * core::hint::black_box(0.5);
+        assert!(product > 0.0 && product.is_subnormal());
+    }
+}
@@ -0,0 +1,120 @@
+//! Offline loudness analysis for level-matching presets.
+//!
+//! A preset is rendered once (a single full-velocity hit), and its loudness
+//! is taken as the loudest short-term RMS window. Comparing that against a
+//! target gives the auto-gain that brings presets of one instrument to the
+//! same perceived level, so switching presets does not jump in volume.
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Length of the short-term RMS window.
+pub const LOUDNESS_WINDOW_SECS: f32 = 0.05;
+
+/// Auto-gain never boosts or cuts by more than this.
+pub const MAX_AUTO_GAIN_DB: f32 = 18.0;
+
+/// Level of silence, in dB (stands in for negative infinity).
+const SILENCE_DB: f32 = -120.0;
+
+/// Loudness of one rendered hit.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Loudness {
+    /// Loudest short-term RMS over the hit, in dBFS
+    pub rms_db: f32,
+    /// Sample peak, in dBFS
+    pub peak_db: f32,
+}
+
+impl Loudness {
+    /// Analyze mono `samples` rendered at `sample_rate`.
+    pub fn measure(samples: &[f32], sample_rate: f32) -> Self {
+        let window = ((LOUDNESS_WINDOW_SECS * sample_rate) as usize).max(1);
+        let peak = samples.iter().fold(0.0_f32, |peak, s| peak.max(s.abs()));
+
+        // Sliding sum of squares, evaluated at every sample position. Windows
+        // overlapping the start are still divided by the full length.
+        let mut sum = 0.0_f64;
+        let mut max_mean = 0.0_f64;
+        for (i, &s) in samples.iter().enumerate() {
+            sum += (s as f64) * (s as f64);
+            if i >= window {
+                let old = samples[i - window] as f64;
+                sum -= old * old;
+            }
+            max_mean = max_mean.max(sum / window as f64);
+        }
+
+        Self {
+            rms_db: amplitude_to_db(max_mean.max(0.0).sqrt() as f32),
+            peak_db: amplitude_to_db(peak),
+        }
+    }
+
+    /// Linear gain that moves this loudness onto `target_rms_db`, limited to
+    /// [`MAX_AUTO_GAIN_DB`] either way. Silence gets unity gain.
+    pub fn gain_to(&self, target_rms_db: f32) -> f32 {
+        db_to_amplitude(self.gain_db_to(target_rms_db))
+    }
+
+    /// [`Loudness::gain_to`] in dB.
+    pub fn gain_db_to(&self, target_rms_db: f32) -> f32 {
+        if self.rms_db <= SILENCE_DB {
+            return 0.0;
+        }
+        (target_rms_db - self.rms_db).clamp(-MAX_AUTO_GAIN_DB, MAX_AUTO_GAIN_DB)
+    }
+}
+
+/// Convert a linear amplitude to dBFS.
+pub fn amplitude_to_db(amplitude: f32) -> f32 {
+    if amplitude <= 0.0 {
+        SILENCE_DB
+    } else {
+        (20.0 * amplitude.log10()).max(SILENCE_DB)
+    }
+}
+
+/// Convert dB to a linear amplitude.
+pub fn db_to_amplitude(db: f32) -> f32 {
+    10.0_f32.powf(db / 20.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sine_rms_and_peak() {
+        let sample_rate = 48000.0;
+        let samples: Vec<f32> = (0..4800)
+            .map(|i| 0.5 * (i as f32 * 440.0 * core::f32::consts::TAU / sample_rate).sin())
+            .collect();
+        let loudness = Loudness::measure(&samples, sample_rate);
+        // 0.5 peak sine: -6.02 dB peak, -9.03 dB RMS
+        assert!((loudness.peak_db + 6.02).abs() < 0.05);
+        assert!((loudness.rms_db + 9.03).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_short_burst_is_measured_over_its_window() {
+        // A 50ms burst inside a second of silence reads like the burst alone
+        let sample_rate = 1000.0;
+        let mut samples = vec![0.0; 1000];
+        samples[200..250].fill(0.25);
+        let loudness = Loudness::measure(&samples, sample_rate);
+        assert!((loudness.rms_db - amplitude_to_db(0.25)).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_gain_is_clamped_and_silence_is_unity() {
+        let quiet = Loudness {
+            rms_db: -60.0,
+            peak_db: -50.0,
+        };
+        assert_eq!(quiet.gain_db_to(-12.0), MAX_AUTO_GAIN_DB);
+        let silent = Loudness::measure(&[0.0; 100], 1000.0);
+        assert_eq!(silent.gain_to(-12.0), 1.0);
+        assert!((db_to_amplitude(amplitude_to_db(0.3)) - 0.3).abs() < 1e-6);
+    }
+}
@@ -1,12 +1,34 @@
 //! Utility modules for audio processing
 
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
 pub mod blendable;
+pub mod clock;
+pub mod config_fade;
+pub mod control_rate;
+pub mod denormal;
+pub mod loudness;
 pub mod oversampler;
+pub mod partial;
+pub mod resampler;
+pub mod retrigger_fade;
+pub mod rng;
 pub mod smoother;
+pub mod time_stretch;
 
-pub use blendable::{Blendable, PresetBlender};
+pub use blendable::{random_blend, Blendable, PresetBlender};
+pub use clock::{sample_to_seconds, SampleClock};
+pub use config_fade::ConfigFade;
+pub use control_rate::{ControlRate, CONTROL_INTERVAL};
+pub use denormal::{flush_denormal, scrub, DenormalGuard, DENORMAL_THRESHOLD};
+pub use loudness::Loudness;
 pub use oversampler::{Oversampler, Oversampler2x, Oversampler4x, OversamplingMode};
+pub use resampler::{Resampler, RESAMPLER_BLOCK};
+pub use retrigger_fade::{RetriggerFade, RETRIGGER_FADE_MS};
+pub use rng::{Rng, RngStream, DEFAULT_RNG_SEED};
 pub use smoother::{ParamSmoother, SmoothedParam, DEFAULT_SMOOTH_TIME_MS};
+pub use time_stretch::wsola_stretch;
 
 /// Convert a normalized tuning value (0.0–1.0) to a frequency multiplier.
 ///
@@ -37,7 +59,7 @@ pub fn cubic_interpolate(p0: f32, p1: f32, p2: f32, p3: f32, t: f32) -> f32 {
 /// WSOLA time-stretcher (analysis/synthesis windows).
 #[inline]
 pub fn raised_sine_window(phase: f32, shape: f32) -> f32 {
-    (std::f32::consts::PI * phase.clamp(0.0, 1.0))
+    (core::f32::consts::PI * phase.clamp(0.0, 1.0))
         .sin()
         .max(0.0)
         .powf(shape)
@@ -181,7 +181,7 @@ impl Default for Oversampler {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::f64::consts::TAU;
+    use core::f64::consts::TAU;
 
     const TEST_SAMPLE_RATE: f32 = 48_000.0;
     const TEST_FREQUENCY: f32 = 10_000.0;
@@ -210,7 +210,7 @@ mod tests {
     }
 
     fn test_input(sample: usize) -> f32 {
-        (std::f32::consts::TAU * TEST_FREQUENCY * sample as f32 / TEST_SAMPLE_RATE).sin() * 0.8
+        (core::f32::consts::TAU * TEST_FREQUENCY * sample as f32 / TEST_SAMPLE_RATE).sin() * 0.8
     }
 
     fn render_base_rate() -> Vec<f32> {
@@ -296,7 +296,7 @@ mod tests {
         let sample_rate = 44100.0_f32;
         let freq = 1000.0;
         for i in 0..44100 {
-            let input = (2.0 * std::f32::consts::PI * freq * i as f32 / sample_rate).sin();
+            let input = (2.0 * core::f32::consts::PI * freq * i as f32 / sample_rate).sin();
             let output = os.process(input, |x| (x * 3.0).tanh());
             assert!(!output.is_nan(), "output should not be NaN");
             assert!(
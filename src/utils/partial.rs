@@ -0,0 +1,138 @@
+//! Partial config updates
+//!
+//! Instrument configs are plain structs, so changing one field through
+//! `set_config` means supplying every other field too. A partial config has
+//! the same fields as `Option`s: `Some` fields are written, `None` fields
+//! are left alone. [`partial_config!`](crate::partial_config) generates the
+//! partial struct for a config along with `apply_partial()` and `diff()`.
+
+/// Declare a partial struct for a config and implement on the config:
+/// - `apply_partial(&mut self, &Partial)`: overwrite the fields that are `Some`
+/// - `with_partial(self, &Partial) -> Self`: the same, by value
+/// - `diff(&self, &Self) -> Partial`: the fields of `other` that differ from `self`
+///
+/// Field types must be `Copy + PartialEq`.
+///
+/// # Usage
+/// ```ignore
+/// partial_config!(
+///     /// Optional KickConfig fields
+///     pub struct KickPartial for KickConfig {
+///         frequency: f32,
+///         punch_amount: f32,
+///     }
+/// );
+///
+/// let mut config = KickConfig::default();
+/// config.apply_partial(&KickPartial {
+///     frequency: Some(0.3),
+///     ..Default::default()
+/// });
+/// ```
+#[macro_export]
+macro_rules! partial_config {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $partial:ident for $config:ty {
+            $($field:ident: $ty:ty),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq)]
+        $vis struct $partial {
+            $(pub $field: Option<$ty>,)*
+        }
+
+        impl $partial {
+            /// True when no field is set, so applying it changes nothing.
+            pub fn is_empty(&self) -> bool {
+                true $(&& self.$field.is_none())*
+            }
+        }
+
+        impl $config {
+            /// Overwrite the fields that are set in `partial`; the rest keep
+            /// their current values.
+            pub fn apply_partial(&mut self, partial: &$partial) {
+                $(
+                    if let Some(value) = partial.$field {
+                        self.$field = value;
+                    }
+                )*
+            }
+
+            /// Copy of this config with `partial` applied.
+            pub fn with_partial(mut self, partial: &$partial) -> Self {
+                self.apply_partial(partial);
+                self
+            }
+
+            /// The fields of `other` that differ from this config: applying the
+            /// result to `self` gives `other`.
+            pub fn diff(&self, other: &Self) -> $partial {
+                $partial {
+                    $($field: (self.$field != other.$field).then_some(other.$field),)*
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct TestConfig {
+        a: f32,
+        b: f32,
+        choice: u8,
+    }
+
+    partial_config!(
+        struct TestPartial for TestConfig {
+            a: f32,
+            b: f32,
+            choice: u8,
+        }
+    );
+
+    const BASE: TestConfig = TestConfig {
+        a: 0.25,
+        b: 0.5,
+        choice: 1,
+    };
+
+    #[test]
+    fn test_apply_partial_writes_only_set_fields() {
+        let partial = TestPartial {
+            b: Some(0.75),
+            ..Default::default()
+        };
+        let config = BASE.with_partial(&partial);
+        assert_eq!(config.a, 0.25);
+        assert_eq!(config.b, 0.75);
+        assert_eq!(config.choice, 1);
+        assert!(!partial.is_empty());
+        assert!(TestPartial::default().is_empty());
+        assert_eq!(BASE.with_partial(&TestPartial::default()), BASE);
+    }
+
+    #[test]
+    fn test_diff_round_trips() {
+        let other = TestConfig {
+            a: 0.25,
+            b: 1.0,
+            choice: 3,
+        };
+        let diff = BASE.diff(&other);
+        assert_eq!(
+            diff,
+            TestPartial {
+                a: None,
+                b: Some(1.0),
+                choice: Some(3),
+            }
+        );
+        assert_eq!(BASE.with_partial(&diff), other);
+        assert!(BASE.diff(&BASE).is_empty());
+    }
+}
@@ -0,0 +1,175 @@
+//! Streaming sample-rate conversion
+//!
+//! Lets the engine run its DSP at one rate while the device plays at
+//! another (e.g. content tuned at 44.1 kHz on a 48 kHz output) without
+//! shifting pitch or tempo. A polyphase windowed-sinc filter reads a pulled
+//! input stream: the resampler asks for fixed-size input blocks as it needs
+//! them, so any output buffer size works and nothing allocates after
+//! construction.
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use core::f32::consts::PI;
+
+/// Filter length in input frames.
+const TAPS: usize = 64;
+
+/// Filter phases per input frame; positions in between are interpolated.
+const PHASES: usize = 256;
+
+/// Passband edge as a fraction of the lower Nyquist frequency. The rest is
+/// the transition band, so the top of the band rolls off slightly instead of
+/// aliasing.
+const ROLLOFF: f32 = 0.9;
+
+/// Input frames requested per call to the fill callback.
+pub const RESAMPLER_BLOCK: usize = 128;
+
+/// Stereo resampler from an input rate to an output rate.
+pub struct Resampler {
+    // Input frames per output frame
+    step: f64,
+    // (PHASES + 1) rows of TAPS coefficients, each row summing to 1
+    table: Vec<f32>,
+    // Interleaved input: history, then frames not yet fully consumed
+    input: Vec<f32>,
+    input_frames: usize,
+    // Position of the next output frame, in frames of `input`
+    position: f64,
+}
+
+impl Resampler {
+    pub fn new(input_rate: f32, output_rate: f32) -> Self {
+        let cutoff = 0.5 * (output_rate / input_rate).min(1.0) * ROLLOFF;
+        let half = (TAPS / 2) as f32;
+        let mut table = vec![0.0; (PHASES + 1) * TAPS];
+        for (phase, row) in table.chunks_exact_mut(TAPS).enumerate() {
+            let frac = phase as f32 / PHASES as f32;
+            for (k, coeff) in row.iter_mut().enumerate() {
+                // Distance from the output position to input frame k
+                let d = k as f32 - (half - 1.0) - frac;
+                let x = 2.0 * cutoff * d;
+                let sinc = if x == 0.0 {
+                    1.0
+                } else {
+                    (PI * x).sin() / (PI * x)
+                };
+                let w = PI * d / half;
+                let blackman = 0.42 + 0.5 * w.cos() + 0.08 * (2.0 * w).cos();
+                *coeff = sinc * blackman.max(0.0);
+            }
+            let sum: f32 = row.iter().sum();
+            row.iter_mut().for_each(|c| *c /= sum);
+        }
+        Self {
+            step: input_rate as f64 / output_rate as f64,
+            table,
+            input: vec![0.0; (TAPS + RESAMPLER_BLOCK) * 2],
+            // Zero history so the first output lands on the first input frame
+            input_frames: TAPS / 2 - 1,
+            position: (TAPS / 2 - 1) as f64,
+        }
+    }
+
+    /// Input frames consumed per output frame.
+    pub fn ratio(&self) -> f64 {
+        self.step
+    }
+
+    /// Fill interleaved stereo `out`, calling `fill` for input whenever more
+    /// is needed. `fill` always gets `RESAMPLER_BLOCK` interleaved frames to
+    /// write.
+    pub fn process(&mut self, out: &mut [f32], mut fill: impl FnMut(&mut [f32])) {
+        for frame in out.chunks_exact_mut(2) {
+            while self.position as usize + TAPS / 2 >= self.input_frames {
+                self.refill(&mut fill);
+            }
+            let index = self.position as usize;
+            let first = index + 1 - TAPS / 2;
+            let row = (self.position - index as f64) as f32 * PHASES as f32;
+            let phase = (row as usize).min(PHASES - 1);
+            let blend = row - phase as f32;
+            let (a, b) = (
+                &self.table[phase * TAPS..(phase + 1) * TAPS],
+                &self.table[(phase + 1) * TAPS..(phase + 2) * TAPS],
+            );
+            let input = &self.input[first * 2..(first + TAPS) * 2];
+            let (mut l, mut r) = (0.0, 0.0);
+            for ((x, ca), cb) in input.chunks_exact(2).zip(a).zip(b) {
+                let coeff = ca + blend * (cb - ca);
+                l += x[0] * coeff;
+                r += x[1] * coeff;
+            }
+            frame[0] = l;
+            frame[1] = r;
+            self.position += self.step;
+        }
+    }
+
+    /// Drop input no longer in reach of the filter and pull another block.
+    fn refill(&mut self, fill: &mut impl FnMut(&mut [f32])) {
+        let keep_from = (self.position as usize + 1)
+            .saturating_sub(TAPS / 2)
+            .min(self.input_frames);
+        self.input
+            .copy_within(keep_from * 2..self.input_frames * 2, 0);
+        self.input_frames -= keep_from;
+        self.position -= keep_from as f64;
+        let start = self.input_frames * 2;
+        fill(&mut self.input[start..start + RESAMPLER_BLOCK * 2]);
+        self.input_frames += RESAMPLER_BLOCK;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f32::consts::TAU;
+
+    /// Resample a stereo sine (right channel inverted) and return the output.
+    fn resample_sine(from: f32, to: f32, freq: f32, frames: usize) -> Vec<f32> {
+        let mut resampler = Resampler::new(from, to);
+        let mut n = 0;
+        let mut out = vec![0.0; frames * 2];
+        // Odd chunk sizes exercise refills landing mid-buffer
+        for chunk in out.chunks_mut(2 * 333) {
+            resampler.process(chunk, |block| {
+                for frame in block.chunks_exact_mut(2) {
+                    let x = (TAU * freq * n as f32 / from).sin();
+                    frame[0] = x;
+                    frame[1] = -x;
+                    n += 1;
+                }
+            });
+        }
+        out
+    }
+
+    #[test]
+    fn test_sine_keeps_its_frequency_across_rates() {
+        for (from, to) in [(44_100.0, 48_000.0), (48_000.0, 44_100.0)] {
+            let out = resample_sine(from, to, 1_000.0, 4_800);
+            let worst = out
+                .chunks_exact(2)
+                .enumerate()
+                .skip(TAPS)
+                .map(|(i, frame)| {
+                    let expected = (TAU * 1_000.0 * i as f32 / to).sin();
+                    (frame[0] - expected).abs().max((frame[1] + expected).abs())
+                })
+                .fold(0.0, f32::max);
+            assert!(worst < 1e-3, "{from} -> {to}: error {worst}");
+        }
+    }
+
+    #[test]
+    fn test_content_above_the_output_nyquist_is_removed() {
+        // 23 kHz is above the 22.05 kHz Nyquist of a 44.1 kHz output
+        let out = resample_sine(48_000.0, 44_100.0, 23_000.0, 4_410);
+        let peak = out
+            .iter()
+            .skip(TAPS * 2)
+            .fold(0.0, |m: f32, x| m.max(x.abs()));
+        assert!(peak < 0.01, "alias peak {peak}");
+    }
+}
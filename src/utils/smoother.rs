@@ -3,6 +3,9 @@
 //! This module provides smoothed parameters for audio synthesis, preventing
 //! discontinuities (clicks/pops) when parameters change during playback.
 
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
 /// Default smoothing time in milliseconds
 pub const DEFAULT_SMOOTH_TIME_MS: f32 = 15.0;
 
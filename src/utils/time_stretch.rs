@@ -0,0 +1,210 @@
+//! Offline WSOLA time-stretch for whole sample buffers.
+//!
+//! The real-time stretcher in the loop mixer works hop by hop on the audio
+//! thread; this is its offline counterpart for material that is rendered once
+//! and then played back as-is (sampler pads following the tempo). Grains of
+//! two hops are read from the source at its own rate, so pitch is untouched,
+//! while the grain starts advance by `1 / factor` hops. Each grain start is
+//! nudged within [`SEARCH_MS`] to the position that best continues the
+//! previous grain (see [`best_grain_start`]), then Hann-windowed and
+//! overlap-added at 50%.
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::utils::raised_sine_window;
+
+/// Output hop length, in milliseconds. Shared with the loop mixer's
+/// real-time stretcher.
+const HOP_MS: f32 = 20.0;
+/// Search tolerance around the ideal grain start, in milliseconds of source
+/// audio.
+const SEARCH_MS: f32 = 10.0;
+/// Candidates in the coarse pass of the correlation search; a fine pass then
+/// checks every frame around the coarse winner.
+const COARSE_STEPS: usize = 64;
+
+/// Output frames per hop at `sample_rate`.
+pub(crate) fn hop_frames(sample_rate: f32) -> usize {
+    ((HOP_MS as f64 / 1000.0) * sample_rate as f64)
+        .round()
+        .max(1.0) as usize
+}
+
+/// [`SEARCH_MS`] in frames of audio at `sample_rate`.
+pub(crate) fn search_radius(sample_rate: f32) -> f64 {
+    ((SEARCH_MS as f64 / 1000.0) * sample_rate as f64)
+        .round()
+        .max(1.0)
+}
+
+/// Coarse-to-fine search for the grain start in `[lo, hi]` whose next
+/// `reference.len()` frames best match `reference`, by normalized
+/// cross-correlation. `mono_at` reads the (mono) source at a position and
+/// `step` is the source distance between consecutive grain frames. Costs
+/// about [`COARSE_STEPS`] plus twice the coarse stride evaluations whatever
+/// the range, and does not allocate. Returns `lo` when `hi < lo`.
+pub(crate) fn best_grain_start(
+    reference: &[f32],
+    lo: f64,
+    hi: f64,
+    step: f64,
+    mono_at: impl Fn(f64) -> f32,
+) -> f64 {
+    let score = |start: f64| {
+        let (mut num, mut ref_energy, mut cand_energy) = (0.0_f32, 0.0_f32, 0.0_f32);
+        for (i, &r) in reference.iter().enumerate() {
+            let cand = mono_at(start + i as f64 * step);
+            num += cand * r;
+            ref_energy += r * r;
+            cand_energy += cand * cand;
+        }
+        if ref_energy <= f32::EPSILON || cand_energy <= f32::EPSILON {
+            0.0
+        } else {
+            num / (ref_energy.sqrt() * cand_energy.sqrt())
+        }
+    };
+
+    let stride = ((hi - lo) / COARSE_STEPS as f64).max(1.0);
+    let mut best = (lo, f32::MIN);
+    let mut candidate = lo;
+    while candidate <= hi {
+        let s = score(candidate);
+        if s > best.1 {
+            best = (candidate, s);
+        }
+        candidate += stride;
+    }
+
+    let mut candidate = (best.0 - stride).max(lo);
+    let refine_hi = (best.0 + stride).min(hi);
+    while candidate <= refine_hi {
+        let s = score(candidate);
+        if s > best.1 {
+            best = (candidate, s);
+        }
+        candidate += 1.0;
+    }
+    best.0
+}
+
+/// Stretch interleaved `samples` (`channels` per frame) to `factor` times
+/// their length without changing pitch. `factor` 2.0 plays twice as long;
+/// returns a copy when `factor` is 1.0. Allocates: call off the audio
+/// thread.
+pub fn wsola_stretch(samples: &[f32], channels: usize, sample_rate: f32, factor: f64) -> Vec<f32> {
+    let channels = channels.max(1);
+    let frames = samples.len() / channels;
+    if frames == 0 || !factor.is_finite() || factor <= 0.0 || factor == 1.0 {
+        return samples.to_vec();
+    }
+
+    let hop = hop_frames(sample_rate);
+    let window_len = 2 * hop;
+    // Periodic Hann: overlapping halves sum to exactly one
+    let window: Vec<f32> = (0..window_len)
+        .map(|i| raised_sine_window(i as f32 / window_len as f32, 2.0))
+        .collect();
+    let radius = search_radius(sample_rate) as isize;
+
+    let out_frames = (frames as f64 * factor).round() as usize;
+    let mut out = vec![0.0_f32; (out_frames + window_len) * channels];
+    // Mono source for the correlation search
+    let mono: Vec<f32> = samples
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum())
+        .collect();
+    let mono_at = |i: isize| {
+        if i >= 0 && (i as usize) < frames {
+            mono[i as usize]
+        } else {
+            0.0
+        }
+    };
+    let mut reference = vec![0.0_f32; hop];
+
+    // Where the previous grain's natural continuation starts in the source
+    let mut continuation: Option<isize> = None;
+    let mut out_pos = 0;
+    while out_pos < out_frames {
+        let ideal = (out_pos as f64 / factor).round() as isize;
+        let start = match continuation {
+            None => ideal,
+            Some(previous) => {
+                for (i, r) in reference.iter_mut().enumerate() {
+                    *r = mono_at(previous + i as isize);
+                }
+                let lo = (ideal - radius).max(0);
+                let hi = (ideal + radius).max(lo);
+                let best = best_grain_start(&reference, lo as f64, hi as f64, 1.0, |pos| {
+                    mono_at(pos.round() as isize)
+                });
+                best.round() as isize
+            }
+        };
+
+        for (i, &w) in window.iter().enumerate() {
+            // The first grain has no predecessor to overlap: no fade-in
+            let w = if out_pos == 0 && i < hop { 1.0 } else { w };
+            let source = start + i as isize;
+            if source < 0 || source as usize >= frames {
+                continue;
+            }
+            let src = source as usize * channels;
+            let dst = (out_pos + i) * channels;
+            for ch in 0..channels {
+                out[dst + ch] += samples[src + ch] * w;
+            }
+        }
+        continuation = Some(start + hop as isize);
+        out_pos += hop;
+    }
+
+    out.truncate(out_frames * channels);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f32::consts::TAU;
+
+    const SR: f32 = 44_100.0;
+
+    fn crossings(samples: &[f32]) -> usize {
+        samples
+            .windows(2)
+            .filter(|w| (w[0] < 0.0) != (w[1] < 0.0))
+            .count()
+    }
+
+    #[test]
+    fn test_stretch_changes_length_but_not_pitch() {
+        let sine: Vec<f32> = (0..SR as usize)
+            .map(|i| (TAU * 220.0 * i as f32 / SR).sin() * 0.5)
+            .collect();
+        for factor in [0.5, 1.5, 2.0] {
+            let out = wsola_stretch(&sine, 1, SR, factor);
+            assert_eq!(out.len(), (sine.len() as f64 * factor).round() as usize);
+            assert!(out.iter().all(|s| s.is_finite() && s.abs() <= 0.6));
+            // Same frequency: zero crossings scale with the length
+            let expected = crossings(&sine) as f64 * factor;
+            let got = crossings(&out) as f64;
+            assert!(
+                (got / expected - 1.0).abs() < 0.05,
+                "factor {factor}: {got} crossings, expected ~{expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_stereo_channels_stay_separate() {
+        let stereo: Vec<f32> = (0..8_820).flat_map(|_| [0.5, -0.25]).collect();
+        let out = wsola_stretch(&stereo, 2, SR, 1.3);
+        // Away from the tail, constant input stays constant per channel
+        for frame in out[..out.len() - 2 * 1_764].chunks_exact(2) {
+            assert!((frame[0] - 0.5).abs() < 1e-3, "{frame:?}");
+            assert!((frame[1] + 0.25).abs() < 1e-3, "{frame:?}");
+        }
+    }
+}
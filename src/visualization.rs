@@ -10,12 +10,18 @@ pub mod waveform_display;
 #[cfg(feature = "visualization")]
 pub mod spectrogram;
 
+#[cfg(feature = "visualization")]
+pub mod sequencer_display;
+
 #[cfg(feature = "visualization")]
 pub use waveform_display::{DisplayEvent, WaveformDisplay};
 
 #[cfg(feature = "visualization")]
 pub use spectrogram::SpectrogramAnalyzer;
 
+#[cfg(feature = "visualization")]
+pub use sequencer_display::SequencerDisplay;
+
 /// Thread-safe circular buffer for storing audio samples
 #[cfg(feature = "visualization")]
 pub struct AudioBuffer {
@@ -35,6 +41,7 @@ impl AudioBuffer {
 
     /// Push a sample into the buffer (thread-safe)
     pub fn push(&self, sample: f32) {
+        crate::rt_audit::lock_acquired("visualization buffer");
         let mut samples = self.samples.lock().unwrap();
         if samples.len() >= self.capacity {
             samples.pop_front();
@@ -0,0 +1,280 @@
+use super::waveform_display::{create_buffers, create_shader_program};
+use super::DisplayEvent;
+use crate::engine::{AudioEvent, Engine};
+use crate::error::GooeyError;
+use glfw::{Action, Context, GlfwReceiver, Key, MouseButton, WindowEvent};
+use std::sync::{Arc, Mutex};
+
+/// Number of sequencer lanes shown (the first four sequencers in the engine)
+pub const SEQUENCER_DISPLAY_LANES: usize = 4;
+
+/// Gap between cells, as a fraction of the cell size
+const CELL_GAP: f32 = 0.1;
+
+/// Step-sequencer debug window using OpenGL.
+///
+/// Draws the first [`SEQUENCER_DISPLAY_LANES`] sequencers of a running
+/// [`Engine`] as rows of steps with the playhead highlighted. Clicking a step
+/// toggles it; Space starts/stops the transport, Escape closes the window.
+///
+/// The playhead is read `lookahead_samples` ahead of the audio thread (see
+/// [`crate::engine::Sequencer::step_at_lookahead`]) so it lines up with what
+/// is heard rather than what has just been rendered into the output buffer.
+pub struct SequencerDisplay {
+    glfw: glfw::Glfw,
+    window: glfw::PWindow,
+    events: GlfwReceiver<(f64, WindowEvent)>,
+    engine: Arc<Mutex<Engine>>,
+    lookahead_samples: u64,
+    width: u32,
+    height: u32,
+    shader_program: u32,
+    vao: u32,
+    vbo: u32,
+}
+
+/// One lane as drawn: its steps and the step under the playhead.
+struct LaneSnapshot {
+    steps: Vec<bool>,
+    playhead: Option<usize>,
+}
+
+impl SequencerDisplay {
+    /// Create a new sequencer display window for `engine`.
+    ///
+    /// `lookahead_samples` is usually the audio output buffer size.
+    pub fn new(
+        engine: Arc<Mutex<Engine>>,
+        width: u32,
+        height: u32,
+        lookahead_samples: u64,
+    ) -> Result<Self, GooeyError> {
+        let mut glfw = glfw::init(glfw::fail_on_errors)
+            .map_err(|e| GooeyError::Window(format!("Failed to initialize GLFW: {:?}", e)))?;
+
+        glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
+        glfw.window_hint(glfw::WindowHint::OpenGlProfile(
+            glfw::OpenGlProfileHint::Core,
+        ));
+        glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
+
+        let (mut window, events) = glfw
+            .create_window(width, height, "Sequencer", glfw::WindowMode::Windowed)
+            .ok_or_else(|| GooeyError::Window("Failed to create GLFW window".to_string()))?;
+
+        window.make_current();
+        window.set_key_polling(true);
+        window.set_mouse_button_polling(true);
+        window.set_framebuffer_size_polling(true);
+
+        gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+
+        let shader_program = unsafe { create_shader_program() }.map_err(GooeyError::Window)?;
+        let (vao, vbo) = unsafe { create_buffers() }.map_err(GooeyError::Window)?;
+
+        Ok(Self {
+            glfw,
+            window,
+            events,
+            engine,
+            lookahead_samples,
+            width,
+            height,
+            shader_program,
+            vao,
+            vbo,
+        })
+    }
+
+    /// Set how far ahead of the audio thread the playhead is drawn.
+    pub fn set_lookahead_samples(&mut self, lookahead_samples: u64) {
+        self.lookahead_samples = lookahead_samples;
+    }
+
+    /// Check if the window should close
+    pub fn should_close(&self) -> bool {
+        self.window.should_close()
+    }
+
+    /// Process events (step toggles, transport) and render, returns list of events
+    pub fn update(&mut self) -> Vec<DisplayEvent> {
+        let mut display_events = Vec::new();
+
+        self.glfw.poll_events();
+        for (_, event) in glfw::flush_messages(&self.events) {
+            match event {
+                WindowEvent::Key(Key::Escape, _, Action::Press, _) => {
+                    self.window.set_should_close(true);
+                    display_events.push(DisplayEvent::Quit);
+                }
+                WindowEvent::Key(Key::Space, _, Action::Press, _) => {
+                    self.toggle_transport();
+                    display_events.push(DisplayEvent::SpacePressed);
+                }
+                WindowEvent::MouseButton(MouseButton::Button1, Action::Press, _) => {
+                    // Cursor positions are in window (not framebuffer) coordinates
+                    let (x, y) = self.window.get_cursor_pos();
+                    let (w, h) = self.window.get_size();
+                    self.toggle_step_at(x as f32 / w as f32, y as f32 / h as f32);
+                }
+                WindowEvent::FramebufferSize(width, height) => {
+                    self.width = width as u32;
+                    self.height = height as u32;
+                    unsafe {
+                        gl::Viewport(0, 0, width, height);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        self.render();
+        self.window.swap_buffers();
+
+        display_events
+    }
+
+    /// Toggle the step under a click at (`x`, `y`), both 0..1 from the top left.
+    fn toggle_step_at(&mut self, x: f32, y: f32) {
+        let mut engine = self.engine.lock().unwrap();
+        let Some(lane) = lane_at(y, SEQUENCER_DISPLAY_LANES) else {
+            return;
+        };
+        let Some(sequencer) = engine.sequencer_mut(lane) else {
+            return;
+        };
+        let step_count = sequencer.pattern_steps().len();
+        if let Some(step) = lane_at(x, step_count) {
+            let enabled = sequencer.get_step_enabled(step);
+            sequencer.set_step(step, !enabled);
+        }
+    }
+
+    fn toggle_transport(&mut self) {
+        let mut engine = self.engine.lock().unwrap();
+        let running = (0..engine.sequencer_count())
+            .any(|i| engine.sequencer(i).is_some_and(|s| s.is_running()));
+        let event = if running {
+            AudioEvent::TransportStop
+        } else {
+            AudioEvent::TransportStart
+        };
+        if let Err(e) = engine.send_event(event) {
+            eprintln!("Warning: {}", e);
+        }
+    }
+
+    /// Copy the lanes out so the engine lock is not held while drawing.
+    fn snapshot(&self) -> Vec<LaneSnapshot> {
+        let engine = self.engine.lock().unwrap();
+        (0..SEQUENCER_DISPLAY_LANES)
+            .map_while(|lane| engine.sequencer(lane))
+            .map(|sequencer| LaneSnapshot {
+                steps: sequencer.pattern(),
+                playhead: sequencer
+                    .is_running()
+                    .then(|| sequencer.step_at_lookahead(self.lookahead_samples)),
+            })
+            .collect()
+    }
+
+    /// Render one row per lane, one cell per step
+    fn render(&mut self) {
+        let lanes = self.snapshot();
+        unsafe {
+            gl::ClearColor(0.05, 0.05, 0.1, 1.0);
+            gl::Clear(gl::COLOR_BUFFER_BIT);
+            gl::UseProgram(self.shader_program);
+            gl::BindVertexArray(self.vao);
+
+            let color_location =
+                gl::GetUniformLocation(self.shader_program, b"color\0".as_ptr() as *const i8);
+
+            for (lane_idx, lane) in lanes.iter().enumerate() {
+                let step_count = lane.steps.len();
+                for (step, &enabled) in lane.steps.iter().enumerate() {
+                    let (r, g, b) = cell_color(enabled, lane.playhead == Some(step));
+                    gl::Uniform3f(color_location, r, g, b);
+                    let quad = cell_quad(lane_idx, SEQUENCER_DISPLAY_LANES, step, step_count);
+                    self.draw_quad(&quad);
+                }
+            }
+
+            gl::BindVertexArray(0);
+        }
+    }
+
+    unsafe fn draw_quad(&self, quad: &[f32; 8]) {
+        gl::BindBuffer(gl::ARRAY_BUFFER, self.vbo);
+        gl::BufferData(
+            gl::ARRAY_BUFFER,
+            (quad.len() * std::mem::size_of::<f32>()) as isize,
+            quad.as_ptr() as *const _,
+            gl::DYNAMIC_DRAW,
+        );
+        gl::DrawArrays(gl::TRIANGLE_FAN, 0, 4);
+    }
+}
+
+impl Drop for SequencerDisplay {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteProgram(self.shader_program);
+            gl::DeleteBuffers(1, &self.vbo);
+            gl::DeleteVertexArrays(1, &self.vao);
+        }
+    }
+}
+
+/// Which of `count` equal bands a 0..1 position falls in.
+fn lane_at(position: f32, count: usize) -> Option<usize> {
+    if !(0.0..1.0).contains(&position) || count == 0 {
+        return None;
+    }
+    Some(((position * count as f32) as usize).min(count - 1))
+}
+
+/// Triangle-fan quad (normalized device coordinates) for a grid cell.
+/// Lane 0 is the top row.
+fn cell_quad(lane: usize, lanes: usize, step: usize, steps: usize) -> [f32; 8] {
+    let cell_w = 2.0 / steps.max(1) as f32;
+    let cell_h = 2.0 / lanes.max(1) as f32;
+    let x0 = -1.0 + step as f32 * cell_w + cell_w * CELL_GAP * 0.5;
+    let x1 = x0 + cell_w * (1.0 - CELL_GAP);
+    let y1 = 1.0 - lane as f32 * cell_h - cell_h * CELL_GAP * 0.5;
+    let y0 = y1 - cell_h * (1.0 - CELL_GAP);
+    [x0, y0, x1, y0, x1, y1, x0, y1]
+}
+
+fn cell_color(enabled: bool, playing: bool) -> (f32, f32, f32) {
+    match (enabled, playing) {
+        (true, true) => (1.0, 0.9, 0.3),
+        (true, false) => (0.2, 1.0, 0.5),
+        (false, true) => (0.35, 0.35, 0.45),
+        (false, false) => (0.15, 0.15, 0.2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lane_at_bands() {
+        assert_eq!(lane_at(0.0, 4), Some(0));
+        assert_eq!(lane_at(0.26, 4), Some(1));
+        assert_eq!(lane_at(0.999, 16), Some(15));
+        assert_eq!(lane_at(1.0, 4), None);
+        assert_eq!(lane_at(-0.1, 4), None);
+        assert_eq!(lane_at(0.5, 0), None);
+    }
+
+    #[test]
+    fn test_cell_quads_tile_top_down() {
+        let top = cell_quad(0, 4, 0, 16);
+        let bottom = cell_quad(3, 4, 15, 16);
+        assert!(top[7] > bottom[7], "lane 0 is drawn above lane 3");
+        assert!(top[0] < bottom[0], "step 0 is drawn left of step 15");
+        assert!(top.iter().chain(&bottom).all(|v| (-1.0..=1.0).contains(v)));
+    }
+}
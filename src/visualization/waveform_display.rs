@@ -1,4 +1,5 @@
 use super::{AudioBuffer, SpectrogramAnalyzer};
+use crate::error::GooeyError;
 use glfw::{Action, Context, GlfwReceiver, Key, WindowEvent};
 use std::ffi::CString;
 
@@ -31,10 +32,10 @@ impl WaveformDisplay {
         width: u32,
         height: u32,
         sample_rate: f32,
-    ) -> Result<Self, String> {
+    ) -> Result<Self, GooeyError> {
         // Initialize GLFW
         let mut glfw = glfw::init(glfw::fail_on_errors)
-            .map_err(|e| format!("Failed to initialize GLFW: {:?}", e))?;
+            .map_err(|e| GooeyError::Window(format!("Failed to initialize GLFW: {:?}", e)))?;
 
         // Request OpenGL 3.3 Core Profile
         glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
@@ -51,7 +52,7 @@ impl WaveformDisplay {
                 "Waveform Display",
                 glfw::WindowMode::Windowed,
             )
-            .ok_or_else(|| "Failed to create GLFW window".to_string())?;
+            .ok_or_else(|| GooeyError::Window("Failed to create GLFW window".to_string()))?;
 
         // Make the window's context current
         window.make_current();
@@ -62,8 +63,8 @@ impl WaveformDisplay {
         gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
 
         // Create shader program and buffers
-        let shader_program = unsafe { create_shader_program()? };
-        let (vao, vbo) = unsafe { create_buffers()? };
+        let shader_program = unsafe { create_shader_program() }.map_err(GooeyError::Window)?;
+        let (vao, vbo) = unsafe { create_buffers() }.map_err(GooeyError::Window)?;
 
         // Create spectrogram analyzer (512-point FFT, 200 history frames)
         let spectrogram = SpectrogramAnalyzer::new(512, sample_rate, 200);
@@ -272,7 +273,7 @@ impl Drop for WaveformDisplay {
 }
 
 /// Create shader program
-unsafe fn create_shader_program() -> Result<u32, String> {
+pub(super) unsafe fn create_shader_program() -> Result<u32, String> {
     // Vertex shader source
     let vertex_shader_source = CString::new(
         r#"
@@ -342,7 +343,7 @@ unsafe fn create_shader_program() -> Result<u32, String> {
 }
 
 /// Create VAO and VBO
-unsafe fn create_buffers() -> Result<(u32, u32), String> {
+pub(super) unsafe fn create_buffers() -> Result<(u32, u32), String> {
     let mut vao = 0;
     let mut vbo = 0;
 
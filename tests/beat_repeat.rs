@@ -0,0 +1,129 @@
+//! Tests for the beat repeat performance effect over FFI.
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+// One beat at the default 120 BPM
+const BEAT: usize = 22_050;
+
+fn render(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    let mut buf = vec![0.0_f32; frames * 2];
+    unsafe { gooey_engine_render(engine, buf.as_mut_ptr(), frames as u32) };
+    buf
+}
+
+fn energy(samples: &[f32]) -> f32 {
+    samples.iter().map(|s| s * s).sum()
+}
+
+/// Hit the kick on beat 0, hold the repeat from beat 1, and return the
+/// output of beats 2..3 — after the repeat latches at the beat-2 boundary.
+fn kick_then_repeat(engine: *mut GooeyEngine, hold: bool) -> Vec<f32> {
+    unsafe {
+        gooey_engine_set_bpm(engine, 120.0);
+        gooey_engine_set_global_effect_param(
+            engine,
+            EFFECT_BEAT_REPEAT,
+            BEAT_REPEAT_PARAM_SLICE,
+            DELAY_TIMING_HALF as f32,
+        );
+        gooey_engine_set_global_effect_param(
+            engine,
+            EFFECT_BEAT_REPEAT,
+            BEAT_REPEAT_PARAM_LENGTH,
+            2.0,
+        );
+        gooey_engine_set_global_effect_enabled(engine, EFFECT_BEAT_REPEAT, true);
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_KICK);
+    }
+    render(engine, BEAT);
+    unsafe { gooey_engine_set_beat_repeat_active(engine, hold) };
+    render(engine, BEAT);
+    render(engine, BEAT)
+}
+
+#[test]
+fn held_repeat_replays_the_captured_beats() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    let reference = gooey_engine_new(SAMPLE_RATE);
+
+    let repeated = kick_then_repeat(engine, true);
+    let live = kick_then_repeat(reference, false);
+    // The kick from beat 0 comes round again; the live signal has decayed.
+    let head = |buf: &[f32]| energy(&buf[..BEAT / 2]);
+    assert!(head(&repeated) > head(&live) * 10.0 + 1e-6);
+
+    // Releasing fades back to the live signal.
+    unsafe { gooey_engine_set_beat_repeat_active(engine, false) };
+    render(engine, 2048);
+    render(reference, 2048);
+    let after = render(engine, 1024);
+    let expected = render(reference, 1024);
+    for (a, b) in after.iter().zip(&expected) {
+        assert!((a - b).abs() < 1e-6);
+    }
+
+    unsafe {
+        gooey_engine_free(engine);
+        gooey_engine_free(reference);
+    }
+}
+
+#[test]
+fn beat_repeat_params_and_source_validation() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        assert!(!gooey_engine_get_beat_repeat_active(engine));
+        assert_eq!(
+            gooey_engine_set_beat_repeat_active(engine, true),
+            GooeyResult::Ok
+        );
+        assert!(gooey_engine_get_beat_repeat_active(engine));
+
+        assert_eq!(
+            gooey_engine_set_global_effect_param(
+                engine,
+                EFFECT_BEAT_REPEAT,
+                BEAT_REPEAT_PARAM_SLICE,
+                42.0
+            ),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_set_global_effect_param(
+                engine,
+                EFFECT_BEAT_REPEAT,
+                BEAT_REPEAT_PARAM_PITCH,
+                24.0
+            ),
+            GooeyResult::Ok
+        );
+        render(engine, 1);
+        assert_eq!(
+            gooey_engine_get_global_effect_param(
+                engine,
+                EFFECT_BEAT_REPEAT,
+                BEAT_REPEAT_PARAM_PITCH
+            ),
+            12.0
+        );
+
+        assert_eq!(
+            gooey_engine_get_beat_repeat_source(engine),
+            BEAT_REPEAT_SOURCE_MASTER
+        );
+        assert_eq!(
+            gooey_engine_set_beat_repeat_source(engine, INSTRUMENT_HIHAT),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_get_beat_repeat_source(engine),
+            INSTRUMENT_HIHAT
+        );
+        assert_eq!(
+            gooey_engine_set_beat_repeat_source(engine, 99),
+            GooeyResult::InvalidInstrument
+        );
+        gooey_engine_free(engine);
+    }
+}
@@ -277,3 +277,65 @@ fn test_invalid_args() {
         gooey_engine_free(engine);
     }
 }
+
+#[test]
+fn test_swap_mid_hit_fades_the_old_tail() {
+    unsafe {
+        let engine = gooey_engine_new(44100.0);
+        gooey_engine_set_kick_param(engine, KICK_PARAM_DECAY, 1.0);
+        gooey_engine_trigger_channel(engine, 0);
+        let mut before = vec![0.0f32; 2048 * 2];
+        gooey_engine_render(engine, before.as_mut_ptr(), 2048);
+        let last = before[before.len() - 2];
+        assert!(last.abs() > 0.001, "kick should still be ringing");
+
+        gooey_engine_set_channel_instrument_type(engine, 0, INSTRUMENT_SNARE);
+        let mut after = vec![0.0f32; 4096 * 2];
+        gooey_engine_render(engine, after.as_mut_ptr(), 4096);
+
+        // No jump to silence at the swap: the tail continues, then fades out
+        // within ~30 ms.
+        assert!((after[0] - last).abs() < 0.05, "{last} -> {}", after[0]);
+        let left: Vec<f32> = after.iter().step_by(2).copied().collect();
+        let peak = |s: &[f32]| s.iter().fold(0.0f32, |m, x| m.max(x.abs()));
+        assert!(peak(&left[..200]) > 0.001);
+        assert!(peak(&left[2048..]) < 1e-5);
+
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn test_second_swap_inside_the_fade_keeps_the_first_tail() {
+    unsafe {
+        let engine = gooey_engine_new(44100.0);
+        gooey_engine_set_kick_param(engine, KICK_PARAM_DECAY, 1.0);
+        gooey_engine_trigger_channel(engine, 0);
+        let mut before = vec![0.0f32; 2048 * 2];
+        gooey_engine_render(engine, before.as_mut_ptr(), 2048);
+
+        // Two swaps 10 ms apart, both inside the kick tail's 30 ms fade
+        gooey_engine_set_channel_instrument_type(engine, 0, INSTRUMENT_SNARE);
+        let mut between = vec![0.0f32; 441 * 2];
+        gooey_engine_render(engine, between.as_mut_ptr(), 441);
+        let last = between[between.len() - 2];
+        assert!(last.abs() > 0.001, "kick tail should still be fading");
+
+        gooey_engine_set_channel_instrument_type(engine, 0, INSTRUMENT_TOM);
+        let mut after = vec![0.0f32; 4096 * 2];
+        gooey_engine_render(engine, after.as_mut_ptr(), 4096);
+
+        // The second swap must not cut the first tail short
+        assert!((after[0] - last).abs() < 0.05, "{last} -> {}", after[0]);
+        let left: Vec<f32> = after.iter().step_by(2).copied().collect();
+        let largest_step = left
+            .windows(2)
+            .fold(0.0f32, |m, w| m.max((w[1] - w[0]).abs()));
+        assert!(largest_step < 0.05, "step of {largest_step}");
+        let peak = |s: &[f32]| s.iter().fold(0.0f32, |m, x| m.max(x.abs()));
+        assert!(peak(&left[..100]) > 0.001);
+        assert!(peak(&left[2048..]) < 1e-5);
+
+        gooey_engine_free(engine);
+    }
+}
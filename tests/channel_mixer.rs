@@ -0,0 +1,157 @@
+//! Integration tests for the channel mixer: dB fader, pan and level meters
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 48000.0;
+
+unsafe fn render_blocks(engine: *mut GooeyEngine, blocks: usize) -> Vec<f32> {
+    let mut buffer = vec![0.0f32; 512 * 2];
+    let mut out = Vec::with_capacity(blocks * buffer.len());
+    for _ in 0..blocks {
+        gooey_engine_render(engine, buffer.as_mut_ptr(), 512);
+        out.extend_from_slice(&buffer);
+    }
+    out
+}
+
+fn peak(samples: &[f32]) -> f32 {
+    samples.iter().fold(0.0f32, |p, s| p.max(s.abs()))
+}
+
+#[test]
+fn test_channel_gain_db_round_trip_and_clamp() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        assert!(gooey_engine_get_channel_gain(engine, INSTRUMENT_KICK).abs() < 1e-4);
+
+        assert_eq!(
+            gooey_engine_set_channel_gain(engine, INSTRUMENT_KICK, -6.0),
+            GooeyResult::Ok
+        );
+        assert!((gooey_engine_get_channel_gain(engine, INSTRUMENT_KICK) + 6.0).abs() < 1e-3);
+
+        gooey_engine_set_channel_gain(engine, INSTRUMENT_KICK, 24.0);
+        assert!(
+            (gooey_engine_get_channel_gain(engine, INSTRUMENT_KICK) - CHANNEL_GAIN_MAX_DB).abs()
+                < 1e-3
+        );
+
+        gooey_engine_set_channel_gain(engine, INSTRUMENT_KICK, -200.0);
+        assert_eq!(
+            gooey_engine_get_channel_gain(engine, INSTRUMENT_KICK),
+            CHANNEL_GAIN_MIN_DB
+        );
+        assert_eq!(
+            gooey_engine_get_instrument_gain(engine, INSTRUMENT_KICK),
+            0.0
+        );
+
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn test_channel_mixer_rejects_bad_input() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        assert_eq!(
+            gooey_engine_set_channel_gain(engine, 99, 0.0),
+            GooeyResult::InvalidChannel
+        );
+        assert_eq!(
+            gooey_engine_set_channel_gain(engine, INSTRUMENT_KICK, f32::NAN),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_set_channel_pan(engine, 99, 0.5),
+            GooeyResult::InvalidChannel
+        );
+        assert_eq!(
+            gooey_engine_set_channel_pan(engine, INSTRUMENT_KICK, 1.5),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_set_channel_gain(std::ptr::null_mut(), 0, 0.0),
+            GooeyResult::NullPointer
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn test_channel_pan_moves_output_to_one_side() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        assert_eq!(
+            gooey_engine_set_channel_pan(engine, INSTRUMENT_KICK, 0.0),
+            GooeyResult::Ok
+        );
+        assert_eq!(gooey_engine_get_channel_pan(engine, INSTRUMENT_KICK), 0.0);
+        render_blocks(engine, 4);
+
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_KICK);
+        let out = render_blocks(engine, 4);
+        let left: Vec<f32> = out.iter().step_by(2).copied().collect();
+        let right: Vec<f32> = out.iter().skip(1).step_by(2).copied().collect();
+        assert!(peak(&left) > 0.01);
+        // Master stereo effects may spread a trace to the other side
+        assert!(
+            peak(&right) < peak(&left) * 0.05,
+            "left {}, right {}",
+            peak(&left),
+            peak(&right)
+        );
+
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn test_pre_meter_ignores_fader_and_post_meter_follows_it() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_set_channel_gain(engine, INSTRUMENT_KICK, -12.0);
+        render_blocks(engine, 4);
+
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_KICK);
+        render_blocks(engine, 2);
+
+        let pre = gooey_engine_get_channel_meter(engine, INSTRUMENT_KICK, CHANNEL_METER_PRE);
+        let post = gooey_engine_get_channel_meter(engine, INSTRUMENT_KICK, CHANNEL_METER_POST);
+        assert!(pre > -40.0, "pre meter should see the kick: {pre} dB");
+        assert!(
+            (pre - post - 12.0).abs() < 0.5,
+            "post meter should sit 12 dB below pre: pre {pre}, post {post}"
+        );
+
+        // Reading does not reset the meter
+        assert_eq!(
+            gooey_engine_get_channel_meter(engine, INSTRUMENT_KICK, CHANNEL_METER_PRE),
+            pre
+        );
+        assert_eq!(
+            gooey_engine_get_channel_meter(engine, INSTRUMENT_KICK, 7),
+            gooey_engine_get_channel_meter(engine, 99, CHANNEL_METER_PRE)
+        );
+
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn test_meter_releases_after_the_hit() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_HIHAT);
+        render_blocks(engine, 2);
+        let hit = gooey_engine_get_channel_meter(engine, INSTRUMENT_HIHAT, CHANNEL_METER_POST);
+
+        // Two seconds on: the short hat is gone and the meter has fallen away
+        render_blocks(engine, 188);
+        let after = gooey_engine_get_channel_meter(engine, INSTRUMENT_HIHAT, CHANNEL_METER_POST);
+        assert!(hit > -40.0, "hit = {hit} dB");
+        assert!(after < hit - 40.0, "hit {hit} dB, after {after} dB");
+
+        gooey_engine_free(engine);
+    }
+}
@@ -0,0 +1,123 @@
+//! Integration tests for crossfaded config application (blend moves and
+//! preset loads).
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+
+unsafe fn render(engine: *mut GooeyEngine, frames: usize) {
+    let mut buffer = vec![0.0_f32; frames * GOOEY_OUTPUT_CHANNELS as usize];
+    gooey_engine_render(engine, buffer.as_mut_ptr(), frames as u32);
+}
+
+/// Tom tune at the bottom-left and bottom-right blend corners. Tom2 keeps its
+/// params as plain fields, so without a crossfade they jump outright.
+unsafe fn corner_tunes(engine: *mut GooeyEngine) -> (f32, f32) {
+    gooey_engine_blend_enable(engine, INSTRUMENT_TOM);
+    gooey_engine_blend_set_position(engine, INSTRUMENT_TOM, 1.0, 0.0);
+    render(engine, 1);
+    let bottom_right = gooey_engine_get_tom_param(engine, TOM_PARAM_TUNE);
+    gooey_engine_blend_set_position(engine, INSTRUMENT_TOM, 0.0, 0.0);
+    render(engine, 1);
+    let bottom_left = gooey_engine_get_tom_param(engine, TOM_PARAM_TUNE);
+    assert!(
+        (bottom_right - bottom_left).abs() > 0.01,
+        "corners should differ in tune"
+    );
+    (bottom_left, bottom_right)
+}
+
+#[test]
+fn blend_move_on_a_sounding_voice_ramps_to_the_new_config() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        let (from, to) = corner_tunes(engine);
+
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_TOM);
+        render(engine, 256);
+        gooey_engine_blend_set_position(engine, INSTRUMENT_TOM, 1.0, 0.0);
+        render(engine, 128);
+
+        let mid = gooey_engine_get_tom_param(engine, TOM_PARAM_TUNE);
+        assert!(
+            (mid - from) * (to - from) > 0.0 && (mid - to) * (from - to) > 0.0,
+            "tune {mid} should be partway from {from} to {to} mid-fade"
+        );
+
+        render(engine, 2_048);
+        let settled = gooey_engine_get_tom_param(engine, TOM_PARAM_TUNE);
+        assert!(
+            (settled - to).abs() < 1e-6,
+            "fade should land on {to}, got {settled}"
+        );
+
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn blend_move_on_a_silent_voice_applies_immediately() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        let (_, to) = corner_tunes(engine);
+
+        gooey_engine_blend_set_position(engine, INSTRUMENT_TOM, 1.0, 0.0);
+        render(engine, 1);
+        assert_eq!(gooey_engine_get_tom_param(engine, TOM_PARAM_TUNE), to);
+
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn param_write_during_a_fade_is_kept() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        corner_tunes(engine);
+
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_TOM);
+        render(engine, 256);
+        gooey_engine_blend_set_position(engine, INSTRUMENT_TOM, 1.0, 0.0);
+        render(engine, 64);
+        gooey_engine_set_tom_param(engine, TOM_PARAM_TUNE, 0.25);
+        render(engine, 2_048);
+
+        assert_eq!(gooey_engine_get_tom_param(engine, TOM_PARAM_TUNE), 0.25);
+
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn preset_load_while_ringing_stays_click_free() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_set_preset_normalization(engine, false);
+        gooey_engine_load_bass_preset(engine, BASS_PRESET_SUB);
+        render(engine, 1);
+
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_BASS);
+        render(engine, 2_048);
+        gooey_engine_load_bass_preset(engine, BASS_PRESET_REESE);
+
+        let frames = 2_048;
+        let mut buffer = vec![0.0_f32; frames * GOOEY_OUTPUT_CHANNELS as usize];
+        gooey_engine_render(engine, buffer.as_mut_ptr(), frames as u32);
+        let left: Vec<f32> = buffer
+            .chunks(GOOEY_OUTPUT_CHANNELS as usize)
+            .map(|frame| frame[0])
+            .collect();
+        let peak = left.iter().fold(0.0_f32, |m, s| m.max(s.abs()));
+        let max_step = left
+            .windows(2)
+            .map(|w| (w[1] - w[0]).abs())
+            .fold(0.0_f32, f32::max);
+        assert!(peak > 0.0, "bass should still be sounding");
+        assert!(
+            max_step < peak * 0.5,
+            "sample-to-sample jump {max_step} is too large for peak {peak}"
+        );
+
+        gooey_engine_free(engine);
+    }
+}
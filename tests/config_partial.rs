@@ -0,0 +1,66 @@
+//! Tests for partial config updates (`apply_partial` / `diff`).
+
+use gooey::instruments::*;
+
+#[test]
+fn partial_changes_only_the_fields_it_sets() {
+    let base = KickConfig::punch();
+    let config = base.with_partial(&PartialKickConfig {
+        frequency: Some(0.1),
+        ..Default::default()
+    });
+    assert_eq!(config.frequency, 0.1);
+    let diff = base.diff(&config);
+    assert_eq!(diff.frequency, Some(0.1));
+    assert!(diff.punch_amount.is_none() && diff.amp_decay.is_none());
+
+    // Choice fields are partial too
+    let hihat = HiHat2Config::short().with_partial(&PartialHiHat2Config {
+        mode: Some(HiHatMode::Classic808),
+        ..Default::default()
+    });
+    assert_eq!(hihat.mode, HiHatMode::Classic808);
+    assert_eq!(hihat.pitch, HiHat2Config::short().pitch);
+}
+
+#[test]
+fn diff_between_presets_round_trips() {
+    macro_rules! round_trip {
+        ($from:expr, $to:expr) => {{
+            let (from, to) = ($from, $to);
+            let diff = from.diff(&to);
+            assert!(!diff.is_empty(), "presets should differ");
+            assert!(from.with_partial(&diff).diff(&to).is_empty());
+            assert!(to.diff(&to).is_empty());
+        }};
+    }
+    round_trip!(KickConfig::tight(), KickConfig::dirt());
+    round_trip!(SnareConfig::tight(), SnareConfig::smack());
+    round_trip!(HiHat2Config::short(), HiHat2Config::classic_loose());
+    round_trip!(Tom2Config::derp(), Tom2Config::ring());
+    round_trip!(BassConfig::acid(), BassConfig::reese());
+    round_trip!(FmSnapConfig::snap(), FmSnapConfig::zap());
+    round_trip!(RimshotConfig::classic(), RimshotConfig::ring());
+    round_trip!(CowbellConfig::classic(), CowbellConfig::dark());
+    round_trip!(ShakerConfig::tight(), ShakerConfig::egg());
+}
+
+#[test]
+fn instrument_apply_partial_keeps_other_params() {
+    let mut tom = Tom2::new(44_100.0);
+    tom.set_config(Tom2Config::ring());
+    tom.apply_partial(&PartialTom2Config {
+        decay: Some(10.0),
+        ..Default::default()
+    });
+    let config = tom.config();
+    assert_eq!(config.decay, 10.0);
+    let changed = Tom2Config::ring().diff(&config);
+    assert_eq!(
+        changed,
+        PartialTom2Config {
+            decay: Some(10.0),
+            ..Default::default()
+        }
+    );
+}
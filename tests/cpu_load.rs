@@ -0,0 +1,57 @@
+//! Integration tests for DSP load metering (`gooey_engine_get_cpu_load`).
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 48_000.0;
+const BLOCK: usize = 256;
+
+unsafe fn render(engine: *mut GooeyEngine, blocks: usize) {
+    let mut buffer = vec![0.0_f32; BLOCK * GOOEY_OUTPUT_CHANNELS as usize];
+    for _ in 0..blocks {
+        gooey_engine_render(engine, buffer.as_mut_ptr(), BLOCK as u32);
+    }
+}
+
+#[test]
+fn renders_report_average_and_peak_load() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        let mut load = GooeyCpuLoad::default();
+        assert!(gooey_engine_get_cpu_load(engine, &mut load));
+        assert_eq!(load, GooeyCpuLoad::default());
+
+        render(engine, 200);
+        assert!(gooey_engine_get_cpu_load(engine, &mut load));
+        assert!(load.average > 0.0 && load.average.is_finite(), "{load:?}");
+        assert!(load.peak >= load.average * 0.5, "{load:?}");
+
+        // The peak is since the previous read
+        let mut again = GooeyCpuLoad::default();
+        gooey_engine_get_cpu_load(engine, &mut again);
+        assert_eq!(again.peak, 0.0);
+
+        assert!(!gooey_engine_get_cpu_load(std::ptr::null(), &mut load));
+        assert!(!gooey_engine_get_cpu_load(engine, std::ptr::null_mut()));
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn channel_breakdown_is_measured_in_debug_builds() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_KICK);
+        render(engine, 200);
+
+        let kick = gooey_engine_get_channel_cpu_load(engine, INSTRUMENT_KICK);
+        if cfg!(debug_assertions) {
+            let mut total = GooeyCpuLoad::default();
+            gooey_engine_get_cpu_load(engine, &mut total);
+            assert!(kick > 0.0 && kick < total.average, "{kick} of {total:?}");
+        } else {
+            assert!(kick.is_nan());
+        }
+        assert!(gooey_engine_get_channel_cpu_load(engine, CHANNEL_MAX).is_nan());
+        gooey_engine_free(engine);
+    }
+}
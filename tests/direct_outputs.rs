@@ -0,0 +1,80 @@
+//! Integration tests for routing instruments to their own output pairs
+//! ([`Engine::set_instrument_output`] / [`Engine::tick_multi`]).
+
+use gooey::engine::{Engine, MAX_OUTPUT_PAIRS};
+use gooey::frame::StereoFrame;
+use gooey::instruments::{KickDrum, SnareDrum};
+
+const SAMPLE_RATE: f32 = 44_100.0;
+
+fn engine() -> Engine {
+    let mut engine = Engine::new(SAMPLE_RATE);
+    engine.add_instrument("kick", Box::new(KickDrum::new(SAMPLE_RATE)));
+    engine.add_instrument("snare", Box::new(SnareDrum::new(SAMPLE_RATE)));
+    engine
+}
+
+/// Trigger `name` and return the energy of each output pair over 4096 frames.
+fn render_energy(engine: &mut Engine, name: &str, pairs: usize) -> Vec<f64> {
+    engine.trigger_instrument(name).unwrap();
+    let mut outputs = vec![StereoFrame::default(); pairs];
+    let mut energy = vec![0.0_f64; pairs];
+    for i in 0..4096 {
+        engine.tick_multi(i as f64 / SAMPLE_RATE as f64, &mut outputs);
+        for (sum, frame) in energy.iter_mut().zip(&outputs) {
+            *sum += (frame.l * frame.l + frame.r * frame.r) as f64;
+        }
+    }
+    energy
+}
+
+#[test]
+fn routes_default_to_the_main_mix_and_clamp() {
+    let mut engine = engine();
+    assert_eq!(engine.instrument_output("kick"), 0);
+    assert_eq!(engine.output_pairs_needed(), 1);
+
+    engine.set_instrument_output("kick", 2);
+    assert_eq!(engine.instrument_output("kick"), 2);
+    assert_eq!(engine.output_pairs_needed(), 3);
+
+    engine.set_instrument_output("snare", 99);
+    assert_eq!(engine.instrument_output("snare"), MAX_OUTPUT_PAIRS - 1);
+
+    engine.set_instrument_output("snare", 0);
+    assert_eq!(engine.instrument_output("snare"), 0);
+    assert_eq!(engine.output_pairs_needed(), 3);
+}
+
+#[test]
+fn routed_instrument_plays_only_on_its_pair() {
+    let mut engine = engine();
+    engine.set_instrument_output("kick", 1);
+
+    let kick = render_energy(&mut engine, "kick", 3);
+    assert!(kick[1] > 0.0, "{kick:?}");
+    assert_eq!(kick[0], 0.0, "{kick:?}");
+    assert_eq!(kick[2], 0.0, "{kick:?}");
+
+    let snare = render_energy(&mut engine, "snare", 3);
+    assert!(snare[0] > 0.0, "{snare:?}");
+    assert_eq!(snare[2], 0.0, "{snare:?}");
+}
+
+#[test]
+fn routes_past_the_device_fold_into_the_main_mix() {
+    let mut folded = engine();
+    folded.set_instrument_output("kick", 3);
+    // On a stereo device only the main pair exists
+    let kick = render_energy(&mut folded, "kick", 1);
+    assert!(kick[0] > 0.0);
+
+    // tick_stereo always plays everything
+    let mut stereo = engine();
+    stereo.set_instrument_output("kick", 1);
+    stereo.trigger_instrument("kick").unwrap();
+    let energy: f32 = (0..4096)
+        .map(|i| stereo.tick_stereo(i as f64 / SAMPLE_RATE as f64).l.abs())
+        .sum();
+    assert!(energy > 0.0);
+}
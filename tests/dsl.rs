@@ -1,4 +1,5 @@
 use gooey::dsl::Program;
+use gooey::GooeyError;
 
 #[test]
 fn parses_and_builds_basic_program() {
@@ -80,3 +81,140 @@ fn legacy_kick_pitch_aliases_migrate_to_tuning() {
         );
     }
 }
+
+#[test]
+fn key_statement_sets_scale_quantize() {
+    use gooey::music::{NoteName, Scale};
+
+    let program = Program::parse("key a minor\ninst kick kick").expect("parse");
+    assert_eq!(program.key(), Some((NoteName::A, Scale::NaturalMinor)));
+    let engine = program.build_engine(44100.0).expect("build engine");
+    assert_eq!(
+        engine.scale_quantize(),
+        Some((NoteName::A, Scale::NaturalMinor))
+    );
+
+    // `#` starts a comment, so sharps are written `s` (or as the enharmonic flat).
+    let program = Program::parse("key Fs").expect("parse");
+    assert_eq!(program.key(), Some((NoteName::Fs, Scale::Major)));
+    let program = Program::parse("key gb dorian").expect("parse");
+    assert_eq!(program.key(), Some((NoteName::Fs, Scale::Dorian)));
+
+    let program = Program::parse("key a minor\nkey off").expect("parse");
+    assert_eq!(program.key(), None);
+
+    assert!(Program::parse("key h minor").is_err());
+    assert!(Program::parse("key a bebop").is_err());
+}
+
+#[test]
+fn delay_accepts_dotted_timing_and_pingpong_flag() {
+    let program = Program::parse(
+        "fx clear\nfx delay 1/8d fb=0.5 mix=0.3 pingpong\nfx delay quarter_dotted 0.2 0.4 lowcut=300",
+    )
+    .expect("parse");
+    let engine = program.build_engine(44100.0).expect("build engine");
+    assert_eq!(engine.global_effect_count(), 2);
+
+    let err = Program::parse("fx delay 1/8x fb=0.5 mix=0.3")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("unknown delay timing"), "{err}");
+}
+
+#[test]
+fn auto_statement_adds_effect_lanes() {
+    let src = r#"
+        fx lowpass 2000 0.3
+        fx delay 1/8 0.4 0.3
+        auto fx.lowpass.cutoff 200 400 . 1600 smooth
+        auto fx.delay.mix 0 - 0.5
+    "#;
+    let engine = Program::parse(src)
+        .expect("parse")
+        .build_engine(44100.0)
+        .expect("build engine");
+
+    let cutoff = engine.effect_lane(0).unwrap();
+    assert_eq!(cutoff.step(0), Some(200.0));
+    assert_eq!(cutoff.step(2), None);
+    assert_eq!(cutoff.step(3), Some(1600.0));
+    assert!(cutoff.interpolate());
+    let mix = engine.effect_lane(1).unwrap();
+    assert_eq!(mix.step(2), Some(0.5));
+    assert!(!mix.interpolate());
+
+    // Lanes need a matching effect and a parameter that can move
+    let no_effect = Program::parse("auto fx.sat.drive 1 2").unwrap();
+    assert!(no_effect.build_engine(44100.0).is_err());
+    assert!(Program::parse("fx limiter 0.9\nauto fx.limiter.threshold 1").is_err());
+    assert!(Program::parse("auto fx.lowpass.cutoff . .").is_err());
+}
+
+fn render_hit(src: &str, instrument: &str) -> Vec<f32> {
+    let sample_rate = 44100.0;
+    let mut engine = Program::parse(src)
+        .expect("parse")
+        .build_engine(sample_rate)
+        .expect("build engine");
+    engine.trigger_instrument(instrument).unwrap();
+    (0..4410)
+        .map(|i| engine.tick(i as f64 / sample_rate as f64))
+        .collect()
+}
+
+#[test]
+fn inline_preset_overrides_and_set_statements() {
+    // Kick frequency is written in Hz and clamped to the kick's 30-120 Hz range
+    let kick = render_hit("inst kick kick punch freq=45 decay=0.8", "kick");
+    assert_ne!(kick, render_hit("inst kick kick punch", "kick"));
+    assert_eq!(
+        render_hit("inst kick kick punch freq=120", "kick"),
+        render_hit("inst kick kick punch freq=500", "kick")
+    );
+    assert_ne!(
+        render_hit("inst kick kick punch freq=120", "kick"),
+        render_hit("inst kick kick punch freq=119", "kick")
+    );
+
+    // `set` applies after the inline overrides, so the later value wins
+    assert_eq!(
+        kick,
+        render_hit(
+            "inst kick kick punch decay=0.8 freq=60\nset kick.frequency 45",
+            "kick"
+        )
+    );
+
+    // Inline and `set` forms build the same instrument, unlike the bare preset
+    let inline = render_hit("inst hat hihat closed tone=6500 decay=300", "hat");
+    let set = render_hit(
+        "inst hat hihat closed\nset hat.tone 6500\nset hat.decay 300",
+        "hat",
+    );
+    assert_eq!(inline, set);
+    assert_ne!(inline, render_hit("inst hat hihat closed", "hat"));
+
+    let tom2 = render_hit("inst t tom2 ring tune=20", "t");
+    assert_ne!(tom2, render_hit("inst t tom2 ring", "t"));
+}
+
+#[test]
+fn preset_overrides_reject_unknown_fields_and_instruments() {
+    let err = Program::parse("inst kick kick punch wobble=1").unwrap_err();
+    assert!(matches!(err, GooeyError::Parse { line: 1, .. }), "{}", err);
+    let err = err.to_string();
+    assert!(
+        err.contains("wobble") && err.contains("frequency"),
+        "{}",
+        err
+    );
+
+    let err = Program::parse("set snare.decay 0.3\ninst snare snare").unwrap_err();
+    assert!(matches!(err, GooeyError::Parse { line: 1, .. }), "{}", err);
+    assert!(err.to_string().contains("snare"), "{}", err);
+
+    assert!(Program::parse("inst kick kick\nset kick.freq").is_err());
+    assert!(Program::parse("inst kick kick\nset kick.freq fast").is_err());
+    assert!(Program::parse("inst kick kick\nset kick 45").is_err());
+}
@@ -0,0 +1,129 @@
+use gooey::dsl::DslSession;
+use gooey::engine::Engine;
+use gooey::GooeyError;
+
+const SAMPLE_RATE: f32 = 44100.0;
+
+fn render(engine: &mut Engine, samples: usize) -> Vec<f32> {
+    (0..samples)
+        .map(|i| engine.tick(i as f64 / SAMPLE_RATE as f64))
+        .collect()
+}
+
+#[test]
+fn statements_apply_to_a_live_engine_one_at_a_time() {
+    let mut engine = Engine::new(SAMPLE_RATE);
+    let mut session = DslSession::new();
+
+    session
+        .execute(&mut engine, "inst kick kick punch")
+        .unwrap();
+    session.execute(&mut engine, "seq kick x...x...").unwrap();
+    assert!(engine.instrument("kick").is_some());
+    assert_eq!(engine.sequencer_count(), 1);
+    assert!(engine.sequencer(0).unwrap().is_running());
+
+    // Blank lines and comments do nothing
+    session.execute(&mut engine, "").unwrap();
+    session.execute(&mut engine, "  # just a note").unwrap();
+
+    // Tempo reaches the sequencers already running
+    session.execute(&mut engine, "bpm 140").unwrap();
+    assert_eq!(engine.bpm(), 140.0);
+    assert_eq!(engine.sequencer(0).unwrap().bpm(), 140.0);
+
+    session
+        .execute_all(&mut engine, "master 0.5\nlfo 1bar kick.tuning amt=0.2")
+        .unwrap();
+    assert_eq!(engine.master_gain(), 0.5);
+    assert_eq!(engine.lfo(0).unwrap().target_instrument, "kick");
+
+    session.execute(&mut engine, "key a minor").unwrap();
+    assert!(engine.scale_quantize().is_some());
+    session.execute(&mut engine, "key off").unwrap();
+    assert!(engine.scale_quantize().is_none());
+}
+
+#[test]
+fn failed_statements_leave_the_engine_untouched() {
+    let mut engine = Engine::new(SAMPLE_RATE);
+    let mut session = DslSession::new();
+    session
+        .execute(&mut engine, "inst hat hihat closed")
+        .unwrap();
+
+    let err = session.execute(&mut engine, "inst hat snare").unwrap_err();
+    assert!(matches!(err, GooeyError::Parse { line: 2, .. }), "{}", err);
+    assert!(session.execute(&mut engine, "wobble 3").is_err());
+    assert!(session.execute(&mut engine, "inst s snare nope").is_err());
+    assert!(engine.instrument("s").is_none());
+
+    // The target is checked before the LFO is added
+    assert!(session.execute(&mut engine, "lfo 1bar hat.nope").is_err());
+    assert!(engine.lfo(0).is_none());
+
+    // Instruments the engine already had can't be redeclared
+    let mut engine = Engine::new(SAMPLE_RATE);
+    engine.add_instrument(
+        "kick",
+        Box::new(gooey::instruments::KickDrum::new(SAMPLE_RATE)),
+    );
+    assert!(DslSession::new()
+        .execute(&mut engine, "inst kick kick")
+        .is_err());
+}
+
+#[test]
+fn set_rebuilds_the_instrument_with_the_override() {
+    let mut live = Engine::new(SAMPLE_RATE);
+    let mut session = DslSession::new();
+    session
+        .execute_all(&mut live, "inst hat hihat closed\nset hat.tone 6500")
+        .unwrap();
+
+    let mut fresh = Engine::new(SAMPLE_RATE);
+    DslSession::new()
+        .execute(&mut fresh, "inst hat hihat closed tone=6500")
+        .unwrap();
+
+    live.trigger_instrument("hat").unwrap();
+    fresh.trigger_instrument("hat").unwrap();
+    assert_eq!(render(&mut live, 2205), render(&mut fresh, 2205));
+
+    assert!(session.execute(&mut live, "set snare.decay 0.3").is_err());
+}
+
+#[test]
+fn fx_clear_drops_the_chain_and_its_lanes() {
+    let mut engine = Engine::new(SAMPLE_RATE);
+    let mut session = DslSession::new();
+
+    // Lanes only see effects added in the session
+    assert!(session
+        .execute(&mut engine, "auto fx.lowpass.cutoff 200 400")
+        .is_err());
+
+    session
+        .execute_all(
+            &mut engine,
+            "fx lowpass 2000 0.3\nauto fx.lowpass.cutoff 200 400 800",
+        )
+        .unwrap();
+    assert!(engine.effect_lane(0).is_some());
+
+    session.execute(&mut engine, "fx clear").unwrap();
+    assert_eq!(engine.global_effect_count(), 0);
+    assert!(engine.effect_lane(0).is_none());
+    assert!(session
+        .execute(&mut engine, "auto fx.lowpass.cutoff 200")
+        .is_err());
+
+    session
+        .execute(&mut engine, "fx delay 1/8 0.4 0.3")
+        .unwrap();
+    session
+        .execute(&mut engine, "auto fx.delay.mix 0 0.5")
+        .unwrap();
+    assert_eq!(engine.global_effect_count(), 1);
+    assert!(engine.effect_lane(0).is_some());
+}
@@ -0,0 +1,151 @@
+//! NaN and denormal injection into every effect and filter: a bad sample must
+//! not stick in processor state, and decaying tails must flush to zero.
+
+use gooey::effects::*;
+use gooey::filters::*;
+
+const SR: f32 = 44_100.0;
+
+fn sine(i: usize) -> f32 {
+    0.5 * (i as f32 * 0.05).sin()
+}
+
+/// Feed NaN and infinity, then a sine: output must stay finite and recover.
+fn assert_recovers(name: &str, mut process: impl FnMut(f32) -> f32) {
+    for i in 0..256 {
+        process(sine(i));
+    }
+    process(f32::NAN);
+    process(f32::INFINITY);
+    process(f32::NEG_INFINITY);
+
+    let mut peak = 0.0_f32;
+    for i in 0..SR as usize {
+        let y = process(sine(i));
+        assert!(y.is_finite(), "{name}: non-finite output {y} at sample {i}");
+        if i > SR as usize / 2 {
+            peak = peak.max(y.abs());
+        }
+    }
+    assert!(peak > 1e-3, "{name}: stuck silent after NaN (peak {peak})");
+}
+
+/// Excite, then feed subnormal input followed by silence: no output may be
+/// subnormal and the tail must reach exactly zero.
+fn assert_flushes(name: &str, seconds: f32, mut process: impl FnMut(f32) -> f32) {
+    for i in 0..256 {
+        process(sine(i));
+    }
+    for _ in 0..64 {
+        process(f32::MIN_POSITIVE * 0.25);
+    }
+    let mut last = f32::NAN;
+    for i in 0..(SR * seconds) as usize {
+        last = process(0.0);
+        assert!(!last.is_subnormal(), "{name}: subnormal output at {i}");
+    }
+    assert_eq!(last, 0.0, "{name}: tail never flushed to zero");
+}
+
+fn check_effect(name: &str, seconds: f32, make: impl Fn() -> Box<dyn Effect>) {
+    let effect = make();
+    assert_recovers(name, |x| effect.process(x));
+    let effect = make();
+    assert_recovers(&format!("{name} (stereo)"), |x| {
+        effect.process_stereo(gooey::frame::StereoFrame::mono(x)).l
+    });
+    let effect = make();
+    assert_flushes(name, seconds, |x| effect.process(x));
+}
+
+#[test]
+fn global_effects_recover_and_flush() {
+    check_effect("lowpass", 1.0, || {
+        Box::new(LowpassFilterEffect::new(SR, 800.0, 0.3))
+    });
+    check_effect("delay", 20.0, || {
+        Box::new(DelayEffect::new(
+            SR,
+            DelayTiming::Eighth,
+            120.0,
+            0.5,
+            0.5,
+            4000.0,
+        ))
+    });
+    check_effect("saturation", 1.0, || {
+        Box::new(TubeSaturation::new(SR, 0.8, 0.5, 1.0))
+    });
+    check_effect("compressor", 1.0, || {
+        Box::new(TubeCompressor::new(SR, -30.0, 8.0, 1.0, 50.0, 1.0))
+    });
+    check_effect("tilt", 1.0, || Box::new(TiltFilterEffect::new(SR)));
+    check_effect("spring reverb", 20.0, || {
+        Box::new(SpringReverbEffect::new(SR, 0.5, 0.5, 0.2))
+    });
+    check_effect("plate reverb", 20.0, || {
+        Box::new(PlateReverbEffect::new(SR, 0.5, 0.5, 0.2))
+    });
+    check_effect("brickwall limiter", 0.1, || {
+        Box::new(BrickWallLimiter::new(0.9))
+    });
+    check_effect("soft limiter", 0.1, || Box::new(SoftLimiter::new(0.9)));
+}
+
+#[test]
+fn waveshapers_recover_and_flush() {
+    let mut shaper = Waveshaper::new(4.0, 1.0);
+    assert_recovers("waveshaper", |x| shaper.process(x));
+
+    let mut fb = FeedbackWaveshaper::new(SR, 8.0, 0.9, 2000.0, 1.0);
+    assert_recovers("feedback waveshaper", |x| fb.process(x));
+    let mut fb = FeedbackWaveshaper::new(SR, 8.0, 0.9, 2000.0, 1.0);
+    assert_flushes("feedback waveshaper", 1.0, |x| fb.process(x));
+}
+
+#[test]
+fn filters_recover_and_flush() {
+    let make_svf = || StateVariableFilter::new(SR, 1000.0, 4.0);
+    let mut f = make_svf();
+    assert_recovers("svf", |x| f.process(x));
+    let mut f = make_svf();
+    assert_recovers("svf all", |x| f.process_all(x).0);
+    let mut f = make_svf();
+    assert_flushes("svf", 1.0, |x| f.process(x));
+
+    let make_tpt = || StateVariableFilterTpt::new(SR, 1000.0, 4.0);
+    let mut f = make_tpt();
+    assert_recovers("tpt svf", |x| f.process_mode(x, 1));
+    let mut f = make_tpt();
+    assert_flushes("tpt svf", 1.0, |x| f.process_mode(x, 0));
+
+    let mut f = ResonantLowpassFilter::new(SR, 1000.0, 8.0);
+    assert_recovers("resonant lowpass", |x| f.process(x));
+    let mut f = ResonantLowpassFilter::new(SR, 1000.0, 8.0);
+    assert_flushes("resonant lowpass", 1.0, |x| f.process(x));
+
+    let mut f = ResonantHighpassFilter::new(SR, 200.0, 1.0);
+    assert_recovers("resonant highpass", |x| f.process(x));
+    let mut f = ResonantHighpassFilter::new(SR, 200.0, 1.0);
+    assert_flushes("resonant highpass", 1.0, |x| f.process(x));
+
+    let make_bp = || {
+        let mut f = BiquadBandpass::new(SR);
+        f.set_params(1000.0, 20.0, 1.0);
+        f
+    };
+    let mut f = make_bp();
+    assert_recovers("biquad bandpass", |x| f.process(x));
+    let mut f = make_bp();
+    assert_flushes("biquad bandpass", 1.0, |x| f.process(x));
+
+    let mut f = BiquadHighpass::new(SR);
+    assert_recovers("biquad highpass", |x| f.process(x));
+    let mut f = BiquadHighpass::new(SR);
+    assert_flushes("biquad highpass", 1.0, |x| f.process(x));
+
+    let mut f = MembraneResonator::new(SR);
+    assert_recovers("membrane", |x| f.process(x));
+    let mut f = MembraneResonator::new(SR);
+    assert_flushes("membrane", 4.0, |x| f.process(x));
+}
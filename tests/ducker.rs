@@ -0,0 +1,135 @@
+//! Tests for trigger-driven ducking, over FFI and on the Rust `Engine`.
+
+use gooey::effects::Ducker;
+use gooey::engine::Engine;
+use gooey::ffi::*;
+use gooey::instruments::{KickDrum, SnareDrum};
+
+const SAMPLE_RATE: f32 = 44_100.0;
+
+fn render(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    let mut buf = vec![0.0_f32; frames * 2];
+    unsafe { gooey_engine_render(engine, buf.as_mut_ptr(), frames as u32) };
+    buf
+}
+
+fn energy(samples: &[f32]) -> f32 {
+    samples.iter().map(|s| s * s).sum()
+}
+
+/// A full-depth ducker keyed off the kick, held well past the first 100 ms.
+fn ducked_engine() -> *mut GooeyEngine {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        for (param, value) in [
+            (DUCKER_PARAM_DEPTH, 1.0),
+            (DUCKER_PARAM_ATTACK, 0.0),
+            (DUCKER_PARAM_HOLD, 200.0),
+            (DUCKER_PARAM_RELEASE, 50.0),
+        ] {
+            assert_eq!(
+                gooey_engine_set_global_effect_param(engine, EFFECT_DUCKER, param, value),
+                GooeyResult::Ok
+            );
+        }
+        assert_eq!(
+            gooey_engine_set_global_effect_enabled(engine, EFFECT_DUCKER, true),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_set_ducker_source(engine, INSTRUMENT_KICK),
+            GooeyResult::Ok
+        );
+    }
+    engine
+}
+
+#[test]
+fn source_trigger_ducks_the_master_bus_then_recovers() {
+    let engine = ducked_engine();
+    let reference = gooey_engine_new(SAMPLE_RATE);
+
+    for e in [engine, reference] {
+        unsafe {
+            gooey_engine_trigger_instrument(e, INSTRUMENT_KICK);
+            gooey_engine_trigger_instrument(e, INSTRUMENT_SNARE);
+        }
+    }
+    let ducked = render(engine, 4410);
+    let dry = render(reference, 4410);
+    assert!(energy(&dry) > 0.0);
+    assert!(energy(&ducked) < energy(&dry) * 1e-6);
+
+    // Past hold + release, a snare hit alone plays untouched.
+    render(engine, SAMPLE_RATE as usize);
+    render(reference, SAMPLE_RATE as usize);
+    for e in [engine, reference] {
+        unsafe { gooey_engine_trigger_instrument(e, INSTRUMENT_SNARE) };
+    }
+    let after = render(engine, 4410);
+    let expected = render(reference, 4410);
+    for (a, b) in after.iter().zip(&expected) {
+        assert!((a - b).abs() < 1e-6);
+    }
+
+    unsafe {
+        gooey_engine_free(engine);
+        gooey_engine_free(reference);
+    }
+}
+
+#[test]
+fn ducker_params_source_and_validation() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        assert_eq!(gooey_engine_get_ducker_source(engine), DUCKER_SOURCE_NONE);
+        assert!(!gooey_engine_get_global_effect_enabled(
+            engine,
+            EFFECT_DUCKER
+        ));
+        assert_eq!(
+            gooey_engine_set_global_effect_param(engine, EFFECT_DUCKER, DUCKER_PARAM_RELEASE, 1.0),
+            GooeyResult::Ok
+        );
+        render(engine, 1);
+        // Clamped to the 5 ms minimum.
+        assert_eq!(
+            gooey_engine_get_global_effect_param(engine, EFFECT_DUCKER, DUCKER_PARAM_RELEASE),
+            5.0
+        );
+        assert_eq!(
+            gooey_engine_set_global_effect_param(engine, EFFECT_DUCKER, 4, 0.0),
+            GooeyResult::InvalidParam
+        );
+        assert_eq!(
+            gooey_engine_set_ducker_source(engine, 99),
+            GooeyResult::InvalidInstrument
+        );
+        assert_eq!(
+            gooey_engine_set_ducker_source(engine, DUCKER_SOURCE_NONE),
+            GooeyResult::Ok
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn engine_ducks_on_named_instrument_triggers() {
+    let ducker = Ducker::new(SAMPLE_RATE, 1.0, 0.0, 200.0, 50.0);
+    let mut engine = Engine::new(SAMPLE_RATE);
+    engine.add_instrument("kick", Box::new(KickDrum::new(SAMPLE_RATE)));
+    engine.add_instrument("snare", Box::new(SnareDrum::new(SAMPLE_RATE)));
+    engine.duck_on_trigger("kick", ducker.trigger_handle());
+    engine.add_global_effect(Box::new(ducker));
+
+    let run = |engine: &mut Engine, name: &str, frames: usize| {
+        engine.trigger_instrument(name).unwrap();
+        let out: Vec<f32> = (0..frames)
+            .map(|i| engine.tick(i as f64 / SAMPLE_RATE as f64))
+            .collect();
+        energy(&out)
+    };
+
+    assert!(run(&mut engine, "snare", 4410) > 0.0);
+    assert_eq!(run(&mut engine, "kick", 4410), 0.0);
+}
@@ -0,0 +1,158 @@
+//! Integration tests for effect lanes stepping effect parameters over FFI
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 48_000.0;
+/// One sixteenth at 120 BPM / 48 kHz.
+const STEP_FRAMES: usize = 6000;
+const BLOCK: usize = 500;
+
+/// Render `frames` frames (a multiple of `BLOCK`).
+unsafe fn render(engine: *mut GooeyEngine, frames: usize) {
+    let mut buffer = vec![0.0f32; BLOCK * 2];
+    for _ in 0..frames / BLOCK {
+        gooey_engine_render(engine, buffer.as_mut_ptr(), BLOCK as u32);
+    }
+}
+
+unsafe fn cutoff(engine: *mut GooeyEngine) -> f32 {
+    gooey_engine_get_global_effect_param(engine, EFFECT_LOWPASS_FILTER, FILTER_PARAM_CUTOFF)
+}
+
+#[test]
+fn test_stepped_lane_follows_the_playhead() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_set_bpm(engine, 120.0);
+        let lane = gooey_engine_effect_lane_create_global(
+            engine,
+            EFFECT_LOWPASS_FILTER,
+            FILTER_PARAM_CUTOFF,
+        );
+        assert_eq!(lane, 0);
+        let lane = lane as u32;
+        assert_eq!(
+            gooey_engine_effect_lane_set_step(engine, lane, 0, 500.0),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_effect_lane_set_step(engine, lane, 2, 2000.0),
+            GooeyResult::Ok
+        );
+
+        // Nothing moves while the transport is stopped
+        let initial = cutoff(engine);
+        render(engine, STEP_FRAMES);
+        assert_eq!(cutoff(engine), initial);
+
+        gooey_engine_sequencer_start(engine);
+        render(engine, STEP_FRAMES);
+        assert_eq!(cutoff(engine), 500.0);
+        render(engine, STEP_FRAMES);
+        assert_eq!(cutoff(engine), 500.0);
+        render(engine, STEP_FRAMES);
+        assert_eq!(cutoff(engine), 2000.0);
+        // Held through the rest of the bar, then back to step 0
+        render(engine, 13 * STEP_FRAMES + BLOCK);
+        assert_eq!(cutoff(engine), 500.0);
+
+        // A removed lane leaves the parameter alone
+        assert_eq!(
+            gooey_engine_effect_lane_remove(engine, lane),
+            GooeyResult::Ok
+        );
+        gooey_engine_set_global_effect_param(
+            engine,
+            EFFECT_LOWPASS_FILTER,
+            FILTER_PARAM_CUTOFF,
+            900.0,
+        );
+        render(engine, 4 * STEP_FRAMES);
+        assert_eq!(cutoff(engine), 900.0);
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn test_interpolated_lane_glides_between_steps() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_set_bpm(engine, 120.0);
+        let lane = gooey_engine_effect_lane_create_global(
+            engine,
+            EFFECT_LOWPASS_FILTER,
+            FILTER_PARAM_CUTOFF,
+        ) as u32;
+        gooey_engine_effect_lane_set_step(engine, lane, 0, 500.0);
+        gooey_engine_effect_lane_set_step(engine, lane, 4, 1500.0);
+        assert_eq!(
+            gooey_engine_effect_lane_set_interpolated(engine, lane, true),
+            GooeyResult::Ok
+        );
+
+        gooey_engine_sequencer_start(engine);
+        render(engine, 2 * STEP_FRAMES);
+        // Half way from step 0 to step 4
+        let halfway = cutoff(engine);
+        assert!((halfway - 1000.0).abs() < 1.0, "cutoff {halfway}");
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn test_lane_validation() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        assert_eq!(gooey_engine_effect_lane_create_global(engine, 999, 0), -1);
+        assert_eq!(
+            gooey_engine_effect_lane_create_global(engine, EFFECT_LOWPASS_FILTER, 99),
+            -1
+        );
+        // No effect on the track yet
+        assert_eq!(gooey_engine_effect_lane_create_track(engine, 0, 0, 0), -1);
+
+        let lane = gooey_engine_effect_lane_create_global(engine, EFFECT_DELAY, DELAY_PARAM_MIX);
+        assert!(lane >= 0);
+        let lane = lane as u32;
+        assert_eq!(
+            gooey_engine_effect_lane_set_step(engine, lane, EFFECT_LANE_STEP_COUNT, 0.5),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_effect_lane_set_step(engine, lane, 3, f32::NAN),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_effect_lane_set_step(engine, lane, 3, 0.25),
+            GooeyResult::Ok
+        );
+        let mut value = 0.0;
+        assert!(gooey_engine_effect_lane_get_step(
+            engine, lane, 3, &mut value
+        ));
+        assert_eq!(value, 0.25);
+        gooey_engine_effect_lane_clear_step(engine, lane, 3);
+        assert!(!gooey_engine_effect_lane_get_step(
+            engine, lane, 3, &mut value
+        ));
+
+        // Every lane in use
+        for _ in 1..EFFECT_LANE_MAX {
+            assert!(gooey_engine_effect_lane_create_global(engine, EFFECT_DELAY, 0) >= 0);
+        }
+        assert_eq!(
+            gooey_engine_effect_lane_create_global(engine, EFFECT_DELAY, 0),
+            -1
+        );
+        gooey_engine_effect_lane_remove(engine, lane);
+        assert_eq!(
+            gooey_engine_effect_lane_remove(engine, lane),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_effect_lane_create_global(engine, EFFECT_DELAY, 0),
+            lane as i32
+        );
+        gooey_engine_free(engine);
+    }
+}
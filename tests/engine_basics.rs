@@ -1,7 +1,11 @@
 // Integration tests for basic Engine functionality
 
-use gooey::engine::{Engine, Sequencer};
+use gooey::effects::SoftLimiter;
+use gooey::engine::{
+    AudioEvent, EffectLane, Engine, ExternalClock, Sequencer, AUDIO_EVENT_CAPACITY,
+};
 use gooey::instruments::{HiHat, KickDrum, SnareDrum};
+use gooey::GooeyError;
 
 #[test]
 fn test_engine_creation() {
@@ -38,7 +42,7 @@ fn test_trigger_instrument() {
     engine.add_instrument("kick", Box::new(KickDrum::new(sample_rate)));
 
     // Trigger the instrument
-    engine.trigger_instrument("kick");
+    engine.trigger_instrument("kick").unwrap();
 
     // Tick the engine and verify we get non-zero audio
     let mut found_audio = false;
@@ -123,8 +127,8 @@ fn test_multiple_instruments_mix() {
     engine.add_instrument("snare", Box::new(SnareDrum::new(sample_rate)));
 
     // Trigger both
-    engine.trigger_instrument("kick");
-    engine.trigger_instrument("snare");
+    engine.trigger_instrument("kick").unwrap();
+    engine.trigger_instrument("snare").unwrap();
 
     // Both should produce audio when mixed
     let mut found_audio = false;
@@ -141,3 +145,229 @@ fn test_multiple_instruments_mix() {
         "Multiple triggered instruments should produce mixed output"
     );
 }
+
+#[test]
+fn test_simultaneous_events_all_apply_on_next_tick() {
+    let sample_rate = 44100.0;
+    let mut engine = Engine::new(sample_rate);
+    engine.add_instrument("kick", Box::new(KickDrum::new(sample_rate)));
+    let snare = engine.add_instrument("snare", Box::new(SnareDrum::new(sample_rate)));
+    engine.add_instrument("hihat", Box::new(HiHat::new(sample_rate)));
+
+    engine.trigger_instrument("kick").unwrap();
+    engine
+        .send_event(AudioEvent::TriggerInstrument {
+            id: snare,
+            velocity: 1.0,
+        })
+        .unwrap();
+    engine.send_event(AudioEvent::SetMasterGain(0.5)).unwrap();
+    assert_eq!(engine.pending_event_count(), 3);
+    assert!(!engine.instrument("kick").unwrap().is_active());
+
+    engine.tick(0.0);
+    assert_eq!(engine.pending_event_count(), 0);
+    assert!(engine.instrument("kick").unwrap().is_active());
+    assert!(engine.instrument("snare").unwrap().is_active());
+    assert!(!engine.instrument("hihat").unwrap().is_active());
+    assert_eq!(engine.master_gain(), 0.5);
+
+    engine
+        .send_event(AudioEvent::TriggerAll { velocity: 1.0 })
+        .unwrap();
+    engine.tick(1.0 / sample_rate as f64);
+    assert!(engine.instrument("hihat").unwrap().is_active());
+}
+
+#[test]
+fn test_param_changes_are_queued_events() {
+    let sample_rate = 44100.0;
+    let mut engine = Engine::new(sample_rate);
+    let kick = engine.add_instrument("kick", Box::new(KickDrum::new(sample_rate)));
+
+    // Volume is smoothed, so each hit is measured after the ramp settles
+    let mut n = 0;
+    let mut hit = |engine: &mut Engine| -> f32 {
+        for _ in 0..8192 {
+            engine.tick(n as f64 / sample_rate as f64);
+            n += 1;
+        }
+        engine
+            .trigger_instrument_with_velocity("kick", 1.0)
+            .unwrap();
+        (0..2048)
+            .map(|_| {
+                n += 1;
+                engine.tick(n as f64 / sample_rate as f64).abs()
+            })
+            .sum()
+    };
+
+    engine.set_instrument_param("kick", "volume", 0.0).unwrap();
+    assert_eq!(engine.pending_event_count(), 1);
+    let quiet = hit(&mut engine);
+
+    engine
+        .send_event(AudioEvent::SetParam {
+            id: kick,
+            param: "volume",
+            value: 1.0,
+        })
+        .unwrap();
+    let loud = hit(&mut engine);
+    assert!(quiet < loud * 0.01, "quiet {quiet}, loud {loud}");
+
+    assert!(matches!(
+        engine.set_instrument_param("kick", "no_such_param", 0.5),
+        Err(GooeyError::NotModulatable { .. })
+    ));
+    assert!(matches!(
+        engine.set_instrument_param("cowbell", "volume", 0.5),
+        Err(GooeyError::UnknownInstrument(_))
+    ));
+    assert!(matches!(
+        engine.trigger_instrument("cowbell"),
+        Err(GooeyError::UnknownInstrument(_))
+    ));
+    assert!(matches!(
+        engine.release_instrument("cowbell"),
+        Err(GooeyError::UnknownInstrument(_))
+    ));
+    assert_eq!(engine.pending_event_count(), 0);
+}
+
+#[test]
+fn test_instrument_ids_are_interned_once() {
+    let sample_rate = 44100.0;
+    let mut engine = Engine::new(sample_rate);
+    let kick = engine.add_instrument("kick", Box::new(KickDrum::new(sample_rate)));
+    let snare = engine.add_instrument("snare", Box::new(SnareDrum::new(sample_rate)));
+    assert_ne!(kick, snare);
+    assert_eq!(engine.instrument_id("snare"), Some(snare));
+    assert_eq!(engine.instrument_name(kick), Some("kick"));
+    assert_eq!(engine.instrument_id("hihat"), None);
+
+    // Replacing an instrument keeps its id
+    let replaced = engine.add_instrument("kick", Box::new(KickDrum::new(sample_rate)));
+    assert_eq!(replaced, kick);
+
+    // Naming an instrument before adding it assigns the id it is added under
+    engine.set_instrument_pan("hihat", 0.2);
+    let hihat = engine.instrument_id("hihat").unwrap();
+    assert!(engine.instrument("hihat").is_none());
+    assert_eq!(
+        engine.add_instrument("hihat", Box::new(HiHat::new(sample_rate))),
+        hihat
+    );
+    assert_eq!(engine.instrument_pan("hihat"), 0.2);
+}
+
+#[test]
+fn test_sequencer_finds_an_instrument_added_later() {
+    let sample_rate = 44100.0;
+    let mut engine = Engine::new(sample_rate);
+    let mut sequencer = Sequencer::with_pattern(120.0, sample_rate, vec![true; 4], "snare");
+    sequencer.start();
+    engine.add_sequencer(sequencer);
+    engine.tick(0.0);
+
+    engine.add_instrument("snare", Box::new(SnareDrum::new(sample_rate)));
+    let step = (sample_rate * 0.125) as usize;
+    for n in 1..step + 64 {
+        engine.tick(n as f64 / sample_rate as f64);
+    }
+    assert!(engine.instrument("snare").unwrap().is_active());
+}
+
+#[test]
+fn test_event_queue_is_bounded() {
+    let mut engine = Engine::new(44100.0);
+    for _ in 0..AUDIO_EVENT_CAPACITY {
+        engine.send_event(AudioEvent::TransportStop).unwrap();
+    }
+    assert_eq!(
+        engine.send_event(AudioEvent::TransportStop),
+        Err(GooeyError::QueueFull(AUDIO_EVENT_CAPACITY))
+    );
+
+    engine.tick(0.0);
+    assert!(engine.send_event(AudioEvent::TransportStop).is_ok());
+}
+
+#[test]
+fn test_effect_lane_needs_a_global_effect() {
+    let mut engine = Engine::new(44100.0);
+    engine.clear_global_effects();
+    assert_eq!(
+        engine.add_effect_lane(0, 0, EffectLane::new()),
+        Err(GooeyError::IndexOutOfRange {
+            what: "global effect",
+            index: 0,
+            count: 0
+        })
+    );
+
+    engine.add_global_effect(Box::new(SoftLimiter::new(1.0)));
+    assert_eq!(engine.add_effect_lane(0, 0, EffectLane::new()), Ok(0));
+}
+
+#[test]
+fn test_transport_events_start_and_stop_sequencers() {
+    let sample_rate = 44100.0;
+    let mut engine = Engine::new(sample_rate);
+    engine.add_instrument("kick", Box::new(KickDrum::new(sample_rate)));
+    engine.add_sequencer(Sequencer::with_pattern(
+        120.0,
+        sample_rate,
+        vec![true; 16],
+        "kick",
+    ));
+
+    engine.send_event(AudioEvent::TransportStart).unwrap();
+    engine.tick(0.0);
+    assert!(engine.sequencer(0).unwrap().is_running());
+
+    engine.send_event(AudioEvent::TransportStop).unwrap();
+    engine.tick(1.0 / sample_rate as f64);
+    assert!(!engine.sequencer(0).unwrap().is_running());
+}
+
+#[test]
+fn test_sync_to_clock_follows_tempo_transport_and_phase() {
+    let sample_rate = 44100.0;
+    let mut engine = Engine::new(sample_rate);
+    engine.add_instrument("kick", Box::new(KickDrum::new(sample_rate)));
+    engine.add_sequencer(Sequencer::with_pattern(
+        120.0,
+        sample_rate,
+        vec![true; 16],
+        "kick",
+    ));
+
+    engine.sync_to_clock(ExternalClock {
+        bpm: 100.0,
+        beat: 2.0,
+        playing: Some(true),
+    });
+    assert_eq!(engine.bpm(), 100.0);
+    let seq = engine.sequencer(0).unwrap();
+    assert!(seq.is_running());
+    assert_eq!(seq.bpm(), 100.0);
+    assert_eq!(seq.current_step(), 8, "starts on the clock's beat");
+
+    // A jump of a whole bar lands on the same step of the pattern
+    engine.sync_to_clock(ExternalClock {
+        bpm: 100.0,
+        beat: 7.0,
+        playing: None,
+    });
+    assert!(engine.sequencer(0).unwrap().is_running());
+    assert_eq!(engine.sequencer(0).unwrap().current_step(), 12);
+
+    engine.sync_to_clock(ExternalClock {
+        bpm: 100.0,
+        beat: 7.5,
+        playing: Some(false),
+    });
+    assert!(!engine.sequencer(0).unwrap().is_running());
+}
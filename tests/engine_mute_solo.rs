@@ -0,0 +1,77 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use gooey::engine::{Engine, Sequencer};
+use gooey::instruments::ClosureInstrument;
+
+const SAMPLE_RATE: f32 = 44100.0;
+
+/// An instrument that only counts its triggers.
+fn counter(hits: Arc<AtomicUsize>) -> ClosureInstrument<Arc<AtomicUsize>> {
+    ClosureInstrument::new(
+        hits,
+        |hits, _time, _velocity| {
+            hits.fetch_add(1, Ordering::Relaxed);
+        },
+        |_, _time| 0.0,
+    )
+}
+
+/// An engine with three instruments, each sequenced on every step.
+fn kit() -> (Engine, [Arc<AtomicUsize>; 3]) {
+    let mut engine = Engine::new(SAMPLE_RATE);
+    let hits: [Arc<AtomicUsize>; 3] = Default::default();
+    for (name, hits) in ["kick", "snare", "hat"].into_iter().zip(&hits) {
+        engine.add_instrument(name, Box::new(counter(hits.clone())));
+        let mut sequencer = Sequencer::with_pattern(120.0, SAMPLE_RATE, vec![true; 16], name);
+        sequencer.start();
+        engine.add_sequencer(sequencer);
+    }
+    (engine, hits)
+}
+
+/// Render one bar and return each instrument's hits during it.
+fn bar(engine: &mut Engine, hits: &[Arc<AtomicUsize>; 3]) -> [usize; 3] {
+    let before = hits.each_ref().map(|h| h.load(Ordering::Relaxed));
+    for i in 0..(SAMPLE_RATE as usize * 2) {
+        engine.tick(i as f64 / SAMPLE_RATE as f64);
+    }
+    let after = hits.each_ref().map(|h| h.load(Ordering::Relaxed));
+    [0, 1, 2].map(|i| after[i] - before[i])
+}
+
+#[test]
+fn mute_skips_sequencer_hits() {
+    let (mut engine, hits) = kit();
+    assert_eq!(bar(&mut engine, &hits), [16, 16, 16]);
+
+    engine.set_instrument_muted("snare", true);
+    assert!(engine.instrument_muted("snare"));
+    assert!(!engine.instrument_audible("snare"));
+    assert_eq!(bar(&mut engine, &hits), [16, 0, 16]);
+
+    // Manual triggers still play, for auditioning
+    engine.trigger_instrument("snare").unwrap();
+    engine.tick(0.0);
+    assert_eq!(hits[1].load(Ordering::Relaxed), 17);
+
+    engine.set_instrument_muted("snare", false);
+    assert_eq!(bar(&mut engine, &hits), [16, 16, 16]);
+}
+
+#[test]
+fn solo_silences_the_rest_and_mute_wins() {
+    let (mut engine, hits) = kit();
+    engine.set_instrument_soloed("kick", true);
+    engine.set_instrument_soloed("hat", true);
+    assert!(engine.instrument_soloed("kick"));
+    assert_eq!(bar(&mut engine, &hits), [16, 0, 16]);
+
+    engine.set_instrument_muted("hat", true);
+    assert_eq!(bar(&mut engine, &hits), [16, 0, 0]);
+
+    engine.set_instrument_soloed("kick", false);
+    engine.set_instrument_soloed("hat", false);
+    engine.set_instrument_muted("hat", false);
+    assert_eq!(bar(&mut engine, &hits), [16, 16, 16]);
+}
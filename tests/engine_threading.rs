@@ -0,0 +1,398 @@
+//! Tests for the control-thread / audio-thread contract of `GooeyEngine`.
+
+use gooey::ffi::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Raw engine pointer that can be moved to the audio thread.
+#[derive(Clone, Copy)]
+struct EnginePtr(*mut GooeyEngine);
+unsafe impl Send for EnginePtr {}
+
+fn render_on_other_thread(engine: EnginePtr, frames: usize) {
+    std::thread::spawn(move || {
+        let engine = engine;
+        let mut buf = vec![0.0_f32; frames * 2];
+        unsafe { gooey_engine_render(engine.0, buf.as_mut_ptr(), frames as u32) };
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn same_thread_writes_apply_immediately() {
+    let engine = gooey_engine_new(44_100.0);
+    let mut buf = vec![0.0_f32; 128];
+    unsafe {
+        gooey_engine_render(engine, buf.as_mut_ptr(), 64);
+        gooey_engine_set_kick_param(engine, KICK_PARAM_DECAY, 0.9);
+        assert!((gooey_engine_get_kick_param(engine, KICK_PARAM_DECAY) - 0.9).abs() < 1e-6);
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn control_thread_writes_apply_on_next_render() {
+    let engine = gooey_engine_new(44_100.0);
+    let ptr = EnginePtr(engine);
+    render_on_other_thread(ptr, 64);
+
+    unsafe {
+        let before = gooey_engine_get_kick_param(engine, KICK_PARAM_DECAY);
+        assert_eq!(
+            gooey_engine_set_kick_param(engine, KICK_PARAM_DECAY, 0.9),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_set_global_effect_param(engine, EFFECT_REVERB, REVERB_PARAM_MIX, 0.4),
+            GooeyResult::Ok
+        );
+        gooey_engine_set_master_gain(engine, 0.5);
+
+        // Queued, not yet visible.
+        assert_eq!(
+            gooey_engine_get_kick_param(engine, KICK_PARAM_DECAY),
+            before
+        );
+        assert_eq!(gooey_engine_get_master_gain(engine), 0.25);
+    }
+
+    render_on_other_thread(ptr, 64);
+
+    unsafe {
+        assert!((gooey_engine_get_kick_param(engine, KICK_PARAM_DECAY) - 0.9).abs() < 1e-6);
+        assert!(
+            (gooey_engine_get_global_effect_param(engine, EFFECT_REVERB, REVERB_PARAM_MIX) - 0.4)
+                .abs()
+                < 1e-6
+        );
+        assert_eq!(gooey_engine_get_master_gain(engine), 0.5);
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn full_control_queue_is_reported() {
+    let engine = gooey_engine_new(44_100.0);
+    render_on_other_thread(EnginePtr(engine), 64);
+
+    unsafe {
+        let mut results = (0..5_000)
+            .map(|_| gooey_engine_set_kick_param(engine, KICK_PARAM_DECAY, 0.5))
+            .collect::<Vec<_>>();
+        assert_eq!(results.remove(0), GooeyResult::Ok);
+        assert_eq!(results.pop(), Some(GooeyResult::QueueFull));
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn concurrent_render_and_control_writes() {
+    let engine = gooey_engine_new(44_100.0);
+    let ptr = EnginePtr(engine);
+    let running = Arc::new(AtomicBool::new(true));
+
+    let audio = {
+        let running = Arc::clone(&running);
+        std::thread::spawn(move || {
+            let ptr = ptr;
+            let mut buf = vec![0.0_f32; 256 * 2];
+            let mut peak = 0.0_f32;
+            while running.load(Ordering::Relaxed) {
+                unsafe { gooey_engine_render(ptr.0, buf.as_mut_ptr(), 256) };
+                peak = buf.iter().fold(peak, |p, s| p.max(s.abs()));
+                assert!(buf.iter().all(|s| s.is_finite()));
+            }
+            peak
+        })
+    };
+
+    unsafe {
+        for i in 0..2_000 {
+            let v = (i % 100) as f32 / 100.0;
+            gooey_engine_set_kick_param(engine, KICK_PARAM_DECAY, v);
+            gooey_engine_set_global_effect_enabled(engine, EFFECT_DELAY, i % 2 == 0);
+            gooey_engine_set_lfo_enabled(engine, 0, i % 3 == 0);
+            gooey_engine_trigger_instrument(engine, INSTRUMENT_KICK);
+            if i % 50 == 0 {
+                std::thread::yield_now();
+            }
+        }
+    }
+    running.store(false, Ordering::Relaxed);
+    let peak = audio.join().unwrap();
+    assert!(peak > 0.0, "triggers from the control thread should sound");
+
+    unsafe { gooey_engine_free(engine) };
+}
+
+#[test]
+fn params_batch_from_control_thread_lands_in_one_render() {
+    let engine = gooey_engine_new(44_100.0);
+    let ptr = EnginePtr(engine);
+    render_on_other_thread(ptr, 64);
+
+    let updates = [
+        GooeyParamUpdate {
+            channel: INSTRUMENT_KICK,
+            param: KICK_PARAM_DECAY,
+            value: 0.9,
+        },
+        GooeyParamUpdate {
+            channel: INSTRUMENT_KICK,
+            param: KICK_PARAM_PUNCH,
+            value: 0.2,
+        },
+    ];
+    unsafe {
+        let before = gooey_engine_get_kick_param(engine, KICK_PARAM_DECAY);
+        assert_eq!(
+            gooey_engine_set_params_batch(engine, updates.as_ptr(), updates.len() as u32),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_get_kick_param(engine, KICK_PARAM_DECAY),
+            before
+        );
+    }
+
+    render_on_other_thread(ptr, 64);
+
+    unsafe {
+        assert!((gooey_engine_get_kick_param(engine, KICK_PARAM_DECAY) - 0.9).abs() < 1e-6);
+        assert!((gooey_engine_get_kick_param(engine, KICK_PARAM_PUNCH) - 0.2).abs() < 1e-6);
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn params_batch_is_all_or_nothing() {
+    let engine = gooey_engine_new(44_100.0);
+    unsafe {
+        let before = gooey_engine_get_kick_param(engine, KICK_PARAM_DECAY);
+        let updates = [
+            GooeyParamUpdate {
+                channel: INSTRUMENT_KICK,
+                param: KICK_PARAM_DECAY,
+                value: 0.9,
+            },
+            GooeyParamUpdate {
+                channel: 99,
+                param: 0,
+                value: 0.5,
+            },
+        ];
+        assert_eq!(
+            gooey_engine_set_params_batch(engine, updates.as_ptr(), 2),
+            GooeyResult::InvalidChannel
+        );
+        assert_eq!(
+            gooey_engine_get_kick_param(engine, KICK_PARAM_DECAY),
+            before
+        );
+        assert_eq!(
+            gooey_engine_set_params_batch(engine, std::ptr::null(), 1),
+            GooeyResult::NullPointer
+        );
+        assert_eq!(
+            gooey_engine_set_params_batch(engine, std::ptr::null(), 0),
+            GooeyResult::Ok
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn params_batch_needs_room_for_every_entry() {
+    let engine = gooey_engine_new(44_100.0);
+    render_on_other_thread(EnginePtr(engine), 64);
+
+    unsafe {
+        for _ in 0..1_000 {
+            gooey_engine_set_kick_param(engine, KICK_PARAM_DECAY, 0.5);
+        }
+        let updates = [GooeyParamUpdate {
+            channel: INSTRUMENT_KICK,
+            param: KICK_PARAM_PUNCH,
+            value: 0.2,
+        }; 100];
+        assert_eq!(
+            gooey_engine_set_params_batch(engine, updates.as_ptr(), 100),
+            GooeyResult::QueueFull
+        );
+        // Nothing from the rejected batch was queued: the queue still has
+        // room for exactly the 24 remaining single writes
+        let results: Vec<_> = (0..25)
+            .map(|_| gooey_engine_set_kick_param(engine, KICK_PARAM_DECAY, 0.5))
+            .collect();
+        assert!(results[..24].iter().all(|r| *r == GooeyResult::Ok));
+        assert_eq!(results[24], GooeyResult::QueueFull);
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn structural_edits_race_free_against_render() {
+    // Sequencer steps, tempo and LFO routes are edited in place rather than
+    // queued. Hammer them while another thread renders; under Miri this
+    // also checks the render gate keeps the two apart.
+    let iterations = if cfg!(miri) { 20 } else { 2_000 };
+    let engine = gooey_engine_new(44_100.0);
+    let ptr = EnginePtr(engine);
+    let running = Arc::new(AtomicBool::new(true));
+    unsafe {
+        gooey_engine_set_lfo_enabled(engine, 0, true);
+        gooey_engine_sequencer_start(engine);
+    }
+
+    let audio = {
+        let running = Arc::clone(&running);
+        std::thread::spawn(move || {
+            let ptr = ptr;
+            let mut buf = vec![0.0_f32; 64 * 2];
+            let mut blocks = 0_u32;
+            loop {
+                unsafe { gooey_engine_render(ptr.0, buf.as_mut_ptr(), 64) };
+                assert!(buf.iter().all(|s| s.is_finite()));
+                blocks += 1;
+                if !running.load(Ordering::Relaxed) {
+                    return blocks;
+                }
+            }
+        })
+    };
+
+    unsafe {
+        for i in 0..iterations {
+            gooey_engine_set_bpm(engine, 80.0 + (i % 100) as f32);
+            if i % 8 == 0 {
+                gooey_engine_clear_lfo_routes(engine, 0);
+            }
+            gooey_engine_add_lfo_route(engine, 0, INSTRUMENT_KICK, KICK_PARAM_DECAY, 0.5);
+            gooey_engine_sequencer_set_step(engine, (i % 16) as u32, i % 3 == 0);
+            gooey_engine_sequencer_set_instrument_step(
+                engine,
+                INSTRUMENT_SNARE,
+                (i % 16) as u32,
+                i % 2 == 0,
+            );
+        }
+    }
+    running.store(false, Ordering::Relaxed);
+    let blocks = audio.join().unwrap();
+    assert!(blocks > 0);
+
+    unsafe {
+        let last = iterations - 1;
+        assert_eq!(gooey_engine_get_bpm(engine), 80.0 + (last % 100) as f32);
+        assert_eq!(
+            gooey_engine_get_lfo_route_count(engine, 0),
+            (last % 8 + 1) as u32
+        );
+        assert_eq!(
+            gooey_engine_sequencer_get_instrument_step_enabled(
+                engine,
+                INSTRUMENT_SNARE,
+                (last % 16) as u32
+            ),
+            last % 2 == 0
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn edits_from_the_audio_thread_do_not_wait_on_themselves() {
+    // A host that renders and edits on one thread (or a trigger callback)
+    // must not deadlock on the render gate.
+    let engine = gooey_engine_new(44_100.0);
+    let mut buf = vec![0.0_f32; 128];
+    unsafe {
+        gooey_engine_render(engine, buf.as_mut_ptr(), 64);
+        gooey_engine_set_bpm(engine, 97.0);
+        gooey_engine_sequencer_set_step(engine, 3, true);
+        gooey_engine_render(engine, buf.as_mut_ptr(), 64);
+        assert_eq!(gooey_engine_get_bpm(engine), 97.0);
+        assert!(gooey_engine_sequencer_get_instrument_step_enabled(
+            engine,
+            INSTRUMENT_KICK,
+            3
+        ));
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn structural_edits_never_silence_a_block() {
+    // Slot, instrument and buffer edits from a control thread are handed to
+    // the audio thread between blocks; a loop playing throughout must come
+    // out of every block.
+    let iterations = if cfg!(miri) { 4 } else { 200 };
+    let engine = gooey_engine_new(44_100.0);
+    let ptr = EnginePtr(engine);
+    let sine: Vec<f32> = (0..44_100)
+        .flat_map(|i| {
+            let s = 0.5 * (i as f32 * 220.0 * std::f32::consts::TAU / 44_100.0).sin();
+            [s, s]
+        })
+        .collect();
+    unsafe {
+        assert!(gooey_engine_loop_load(
+            engine,
+            0,
+            sine.as_ptr(),
+            44_100,
+            2,
+            44_100.0
+        ));
+        gooey_engine_loop_set_playing(engine, 0, true);
+    }
+    let running = Arc::new(AtomicBool::new(true));
+    let started = Arc::new(AtomicBool::new(false));
+
+    let audio = {
+        let running = Arc::clone(&running);
+        let started = Arc::clone(&started);
+        std::thread::spawn(move || {
+            let ptr = ptr;
+            let mut buf = vec![0.0_f32; 256 * 2];
+            loop {
+                unsafe { gooey_engine_render(ptr.0, buf.as_mut_ptr(), 256) };
+                let peak = buf.iter().fold(0.0_f32, |acc, s| acc.max(s.abs()));
+                assert!(peak > 1e-3, "silent block while a control thread edited");
+                started.store(true, Ordering::Release);
+                if !running.load(Ordering::Relaxed) {
+                    return;
+                }
+            }
+        })
+    };
+    while !started.load(Ordering::Acquire) {
+        std::thread::yield_now();
+    }
+
+    unsafe {
+        for i in 0..iterations {
+            let slot = gooey_engine_create_slot(engine, i % INSTRUMENT_COUNT);
+            assert!(slot >= 0);
+            gooey_engine_set_channel_instrument_type(
+                engine,
+                slot as u32,
+                (i + 1) % INSTRUMENT_COUNT,
+            );
+            gooey_engine_set_bpm(engine, 90.0 + (i % 60) as f32);
+            assert!(gooey_engine_loop_load(
+                engine,
+                1,
+                sine.as_ptr(),
+                1_024,
+                2,
+                44_100.0
+            ));
+            gooey_engine_destroy_slot(engine, slot as u32);
+        }
+    }
+    running.store(false, Ordering::Relaxed);
+    audio.join().unwrap();
+    unsafe { gooey_engine_free(engine) };
+}
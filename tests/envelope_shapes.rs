@@ -0,0 +1,111 @@
+//! Tests for envelope curves, retrigger modes and velocity sensitivity.
+
+use gooey::envelope::{ADSRConfig, Envelope, EnvelopeCurve, RetriggerMode};
+use gooey::ffi::*;
+
+fn envelope(config: ADSRConfig) -> Envelope {
+    let mut env = Envelope::with_config(config);
+    env.trigger(0.0);
+    env
+}
+
+#[test]
+fn curved_segments_bend_like_curve_tilde() {
+    let base = ADSRConfig::new(0.1, 0.1, 0.5, 0.1);
+    let at = |config: ADSRConfig, t: f64| envelope(config).get_amplitude(t);
+
+    assert_eq!(at(base, 0.05), 0.5);
+    let slow = at(base.with_attack_curve(EnvelopeCurve::Curved(0.8)), 0.05);
+    let fast = at(base.with_attack_curve(EnvelopeCurve::Curved(-0.8)), 0.05);
+    assert!(slow < 0.3 && fast > 0.7, "{slow} {fast}");
+    assert_eq!(
+        at(base.with_attack_curve(EnvelopeCurve::Curved(0.0)), 0.05),
+        0.5
+    );
+
+    // Release from the sustain level, halfway through
+    let release_at = |config: ADSRConfig| {
+        let mut env = envelope(config);
+        env.get_amplitude(0.3);
+        env.release(0.3);
+        env.get_amplitude(0.35)
+    };
+    assert!((release_at(base) - 0.25).abs() < 1e-4);
+    assert!(release_at(base.with_release_curve(EnvelopeCurve::Curved(-0.8))) < 0.1);
+}
+
+#[test]
+fn legato_retrigger_starts_from_the_current_level() {
+    let config = ADSRConfig::new(0.05, 0.2, 0.6, 0.2);
+    for (mode, expect_continuous) in [(RetriggerMode::Reset, false), (RetriggerMode::Legato, true)]
+    {
+        let mut env = envelope(config.with_retrigger(mode));
+        let before = env.get_amplitude(0.1);
+        env.trigger(0.1);
+        let after = env.get_amplitude(0.1);
+        assert_eq!((after - before).abs() < 1e-3, expect_continuous, "{mode:?}");
+        // Either way the retriggered attack still peaks at full level.
+        assert!((env.get_amplitude(0.15) - 1.0).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn velocity_scales_level_and_stretches_attack() {
+    let config = ADSRConfig::new(0.01, 0.1, 1.0, 0.1).with_velocity_sensitivity(1.0, 1.0);
+    let mut env = Envelope::with_config(config);
+    env.trigger_with_velocity(0.0, 0.5);
+    // Attack stretched to 0.01 * (1 + 0.5 * 3) = 0.025 s, peak at half level
+    assert!((env.get_amplitude(0.0125) - 0.25).abs() < 1e-3);
+    assert!((env.get_amplitude(0.1) - 0.5).abs() < 1e-6);
+
+    env.trigger_with_velocity(0.2, 1.0);
+    assert!((env.get_amplitude(0.205) - 0.5).abs() < 1e-3);
+
+    // Without sensitivity velocity is ignored, as before.
+    let mut plain = Envelope::with_config(ADSRConfig::new(0.01, 0.1, 1.0, 0.1));
+    plain.trigger_with_velocity(0.0, 0.2);
+    assert_eq!(plain.get_amplitude(0.005), 0.5);
+}
+
+#[test]
+fn poly_adsr_ex_validates_and_shapes_notes() {
+    let chord = |setup: &dyn Fn(*mut GooeyEngine)| unsafe {
+        let engine = gooey_engine_new(44_100.0);
+        setup(engine);
+        gooey_engine_poly_trigger_chord(engine, 0, SCALE_MAJOR, 0, 0, POLY_PRESET_PLUCK, 4, 0.3);
+        let mut buf = vec![0.0_f32; 8192 * 2];
+        gooey_engine_render(engine, buf.as_mut_ptr(), 8192);
+        gooey_engine_free(engine);
+        buf.iter().map(|s| s * s).sum::<f32>()
+    };
+    let plain = chord(&|_| {});
+    let sensitive = chord(&|engine| unsafe {
+        let result = gooey_engine_poly_set_adsr_ex(
+            engine,
+            POLY_ENVELOPE_AMP,
+            0.0,
+            -0.5,
+            0.0,
+            ENVELOPE_RETRIGGER_LEGATO,
+            1.0,
+            0.0,
+        );
+        assert_eq!(result, GooeyResult::Ok);
+    });
+    assert!(sensitive < plain * 0.5, "{sensitive} vs {plain}");
+
+    let engine = gooey_engine_new(44_100.0);
+    unsafe {
+        let set = |envelope, retrigger, curve| {
+            gooey_engine_poly_set_adsr_ex(engine, envelope, curve, 0.0, 0.0, retrigger, 0.0, 0.0)
+        };
+        assert_eq!(set(2, 0, 0.0), GooeyResult::InvalidParam);
+        assert_eq!(set(POLY_ENVELOPE_FILTER, 2, 0.0), GooeyResult::InvalidValue);
+        assert_eq!(
+            set(POLY_ENVELOPE_FILTER, 0, f32::NAN),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(set(POLY_ENVELOPE_FILTER, 0, 3.0), GooeyResult::Ok);
+        gooey_engine_free(engine);
+    }
+}
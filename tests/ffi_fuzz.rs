@@ -0,0 +1,736 @@
+//! Property tests that drive the C API with random call sequences.
+//!
+//! Swift calls these functions directly, and a panic inside one aborts the
+//! app, so every entry point has to shrug off any argument a host can pass:
+//! out-of-range indices, NaN and infinite values, calls in any order. Each
+//! case builds an engine, makes a random sequence of calls (mostly plausible
+//! arguments, some arbitrary) interleaved with renders, and checks that
+//!
+//! - nothing panics (a panic in a non-render call aborts this test binary;
+//!   one inside render flags the engine as errored, which is checked),
+//! - every rendered sample is finite and inside the output safety ceiling,
+//! - replaying the same calls doesn't keep growing the heap, and freeing the
+//!   engine gives the memory back.
+//!
+//! Buffers passed by pointer always match their stated length: the harness
+//! fuzzes the values, not the pointer contract.
+//!
+//! For a longer run: `PROPTEST_CASES=5000 cargo test --test ffi_fuzz`.
+
+use gooey::ffi::*;
+use proptest::prelude::*;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Tracks the bytes allocated and not yet freed by the current thread, so
+/// the harness's other threads don't interfere.
+struct LiveBytes;
+
+thread_local! {
+    static LIVE: Cell<isize> = const { Cell::new(0) };
+}
+
+fn adjust(delta: isize) {
+    // `try_with` so thread teardown (after the TLS slot is gone) still works
+    let _ = LIVE.try_with(|live| live.set(live.get() + delta));
+}
+
+unsafe impl GlobalAlloc for LiveBytes {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        adjust(layout.size() as isize);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        adjust(-(layout.size() as isize));
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        adjust(new_size as isize - layout.size() as isize);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: LiveBytes = LiveBytes;
+
+fn live_bytes() -> isize {
+    LIVE.with(Cell::get)
+}
+
+const SAMPLE_RATE: f32 = 48_000.0;
+/// Heap a replayed call sequence may add on top of the first pass: bounded
+/// pools (slots, lanes, routes) filling up, not growth per call.
+const REPLAY_GROWTH_LIMIT: isize = 8 << 20;
+/// Heap left behind after freeing the engine (thread-local error strings).
+const LEAK_LIMIT: isize = 64 << 10;
+
+/// One C API call with its arguments.
+#[derive(Clone, Debug)]
+enum Call {
+    Render(u32),
+    Trigger(u32, f32),
+    TriggerChannel(u32, f32),
+    Release(u32, bool),
+    SetInstrumentParam(u32, u32, f32),
+    SetNamedParam(String, f32),
+    LoadPreset(u32, u32),
+    SetChannelParam(u32, u32, f32),
+    SetChannelTuning(u32, f32),
+    SetChannelType(u32, u32),
+    CreateSlot(u32),
+    DestroySlot(u32),
+    GlobalEffectEnabled(u32, bool),
+    GlobalEffectParam(u32, u32, f32),
+    MoveEffect(u32, u32),
+    OutputSafetyParam(u32, f32),
+    SetBpm(f32),
+    SetSwing(f32),
+    SetMasterGain(f32),
+    SetMasterTuning(f32, f32),
+    SetSeed(u64),
+    Transport(u8),
+    SetBeatPosition(f64),
+    Step(u32, u32, bool, f32),
+    StepNote(u32, u32, u8),
+    StepTune(u32, u32, f32),
+    StepGate(u32, u32, f32),
+    StepArticulation(u32, u32, u8),
+    StepBlend(u32, u32, f32, f32),
+    PatternEdit(u32, u8, i32, f32),
+    PatternStore(u32),
+    PatternLaunch(u32, u32),
+    Lfo(u32, bool, u32, f32, f32),
+    LfoRoute(u32, u32, u32, f32, f32),
+    Mute(u32, bool),
+    Solo(u32, bool),
+    ChannelGain(u32, f32),
+    ChannelPan(u32, f32),
+    FxBypass(u32, u32),
+    PanSpread(u32, f32),
+    Variation(u32, f32, u32, u32),
+    SaturatorModel(u32, u32),
+    Blend(u32, bool, f32, f32),
+    BlendCorner(u32, u32, u32),
+    Randomize(u32, f32, u32),
+    Chord(u32, u32, u32, u32, u32, i32, f32),
+    PolyRelease,
+    PolyParam(u32, f32),
+    ScaleQuantize(u32, u32),
+    EffectLaneGlobal(u32, u32, u32, f32),
+    EffectLaneTrack(u32, u32, u32, u32, f32),
+    EffectLaneRemove(u32),
+    MotionRecording(bool),
+    TrackEffectAdd(u32, u32),
+    TrackEffectParam(u32, u32, u32, f32),
+    TrackEffectRemove(u32, u32),
+    MixerRoute(u32, u32),
+    TrackGain(u32, f32),
+    SamplerRegister,
+    SamplerSlot(u32, u32, Vec<f32>, u32, f32),
+    SamplerTrigger(u32, u32, f32),
+    SamplerSlice(u32, u32, u32, f32),
+    SamplerPitch(u32, u32, f32, f32),
+    SamplerStep(u32, u32, bool, u32, f32),
+    GranulatorBuffer(Vec<f32>, f32),
+    GranulatorParam(u32, f32),
+    GranulatorTrigger(f32),
+    LoopLoad(u32, Vec<f32>, u32, f32),
+    LoopParam(u32, u8, f32),
+    Freeze(u32, bool),
+    /// Export the state and import it back, first overwriting one byte
+    State(Option<(usize, u8)>),
+    Groove(u32, u32, f32, f32),
+    Panic,
+}
+
+/// Mostly valid-looking indices, sometimes anything.
+fn index() -> impl Strategy<Value = u32> {
+    prop_oneof![8 => 0u32..20, 1 => any::<u32>()]
+}
+
+/// Mostly normalized values, sometimes anything, NaN and infinities included.
+fn value() -> impl Strategy<Value = f32> {
+    prop_oneof![8 => -0.25f32..1.25, 1 => proptest::num::f32::ANY]
+}
+
+fn samples() -> impl Strategy<Value = Vec<f32>> {
+    prop::collection::vec(value(), 0..512)
+}
+
+fn call() -> impl Strategy<Value = Call> {
+    // `prop_oneof!` caps its arm count, so the calls are grouped
+    let voices = prop_oneof![
+        (0u32..2048).prop_map(Call::Render),
+        (index(), value()).prop_map(|(i, v)| Call::Trigger(i, v)),
+        (index(), value()).prop_map(|(c, v)| Call::TriggerChannel(c, v)),
+        (index(), index(), value()).prop_map(|(i, p, v)| Call::SetInstrumentParam(i, p, v)),
+        (
+            prop_oneof![3 => "(kick|snare|hihat|bass)\\.[a-zA-Z_]{0,16}", 1 => ".{0,24}"],
+            value()
+        )
+            .prop_map(|(name, v)| Call::SetNamedParam(name, v)),
+        (index(), index()).prop_map(|(i, p)| Call::LoadPreset(i, p)),
+        (index(), index(), value()).prop_map(|(c, p, v)| Call::SetChannelParam(c, p, v)),
+        (index(), value()).prop_map(|(c, v)| Call::SetChannelTuning(c, v)),
+        (index(), index()).prop_map(|(c, t)| Call::SetChannelType(c, t)),
+        index().prop_map(Call::CreateSlot),
+        index().prop_map(Call::DestroySlot),
+        (index(), value(), index(), index()).prop_map(|(i, d, n, m)| Call::Variation(i, d, n, m)),
+        (index(), index()).prop_map(|(i, m)| Call::SaturatorModel(i, m)),
+        (index(), any::<bool>(), value(), value())
+            .prop_map(|(i, on, x, y)| Call::Blend(i, on, x, y)),
+        (index(), index(), index()).prop_map(|(i, c, p)| Call::BlendCorner(i, c, p)),
+        (index(), value(), any::<u32>()).prop_map(|(i, a, s)| Call::Randomize(i, a, s)),
+        (index(), any::<bool>()).prop_map(|(i, channel)| Call::Release(i, channel)),
+        Just(Call::Panic),
+    ];
+    let master = prop_oneof![
+        (index(), any::<bool>()).prop_map(|(e, on)| Call::GlobalEffectEnabled(e, on)),
+        (index(), index(), value()).prop_map(|(e, p, v)| Call::GlobalEffectParam(e, p, v)),
+        (index(), index()).prop_map(|(e, p)| Call::MoveEffect(e, p)),
+        (index(), value()).prop_map(|(p, v)| Call::OutputSafetyParam(p, v)),
+        prop_oneof![20.0f32..300.0, proptest::num::f32::ANY].prop_map(Call::SetBpm),
+        value().prop_map(Call::SetSwing),
+        prop_oneof![0.0f32..2.0, proptest::num::f32::ANY].prop_map(Call::SetMasterGain),
+        (
+            prop_oneof![380.0f32..500.0, proptest::num::f32::ANY],
+            prop_oneof![-30.0f32..30.0, proptest::num::f32::ANY],
+        )
+            .prop_map(|(a4, transpose)| Call::SetMasterTuning(a4, transpose)),
+        any::<u64>().prop_map(Call::SetSeed),
+        (index(), any::<bool>()).prop_map(|(i, m)| Call::Mute(i, m)),
+        (index(), any::<bool>()).prop_map(|(i, s)| Call::Solo(i, s)),
+        (
+            index(),
+            prop_oneof![-60.0f32..12.0, proptest::num::f32::ANY]
+        )
+            .prop_map(|(c, g)| Call::ChannelGain(c, g)),
+        (index(), value()).prop_map(|(c, p)| Call::ChannelPan(c, p)),
+        (index(), any::<u32>()).prop_map(|(c, m)| Call::FxBypass(c, m)),
+        (index(), value()).prop_map(|(i, w)| Call::PanSpread(i, w)),
+        (index(), index(), index(), value())
+            .prop_map(|(t, s, p, v)| Call::TrackEffectParam(t, s, p, v)),
+        (index(), index()).prop_map(|(t, e)| Call::TrackEffectAdd(t, e)),
+        (index(), index()).prop_map(|(t, s)| Call::TrackEffectRemove(t, s)),
+        (index(), index()).prop_map(|(s, t)| Call::MixerRoute(s, t)),
+        (index(), value()).prop_map(|(t, g)| Call::TrackGain(t, g)),
+    ];
+    let sequencing = prop_oneof![
+        (0u8..3).prop_map(Call::Transport),
+        prop_oneof![0.0f64..64.0, any::<f64>()].prop_map(Call::SetBeatPosition),
+        (index(), index(), any::<bool>(), value())
+            .prop_map(|(i, s, on, v)| Call::Step(i, s, on, v)),
+        (index(), index(), any::<u8>()).prop_map(|(i, s, n)| Call::StepNote(i, s, n)),
+        (
+            index(),
+            index(),
+            prop_oneof![-24.0f32..24.0, proptest::num::f32::ANY]
+        )
+            .prop_map(|(i, s, t)| Call::StepTune(i, s, t)),
+        (
+            index(),
+            index(),
+            prop_oneof![0.0f32..16.0, proptest::num::f32::ANY]
+        )
+            .prop_map(|(i, s, g)| Call::StepGate(i, s, g)),
+        (index(), index(), any::<u8>()).prop_map(|(i, s, a)| Call::StepArticulation(i, s, a)),
+        (index(), index(), value(), value()).prop_map(|(i, s, x, y)| Call::StepBlend(i, s, x, y)),
+        (index(), 0u8..8, any::<i32>(), value())
+            .prop_map(|(i, op, n, a)| Call::PatternEdit(i, op, n, a)),
+        index().prop_map(Call::PatternStore),
+        (index(), index()).prop_map(|(s, q)| Call::PatternLaunch(s, q)),
+        (index(), any::<bool>(), index(), value(), value())
+            .prop_map(|(l, on, t, a, o)| Call::Lfo(l, on, t, a, o)),
+        (index(), index(), index(), value(), value())
+            .prop_map(|(l, i, p, d, ph)| Call::LfoRoute(l, i, p, d, ph)),
+        (
+            index(),
+            index(),
+            index(),
+            index(),
+            index(),
+            -4i32..4,
+            value()
+        )
+            .prop_map(|(r, s, d, v, p, o, vel)| Call::Chord(r, s, d, v, p, o, vel)),
+        Just(Call::PolyRelease),
+        (index(), value()).prop_map(|(p, v)| Call::PolyParam(p, v)),
+        (index(), index()).prop_map(|(r, s)| Call::ScaleQuantize(r, s)),
+        (index(), index(), index(), value())
+            .prop_map(|(e, p, s, v)| Call::EffectLaneGlobal(e, p, s, v)),
+        (index(), index(), index(), index(), value())
+            .prop_map(|(t, sl, p, st, v)| Call::EffectLaneTrack(t, sl, p, st, v)),
+        index().prop_map(Call::EffectLaneRemove),
+        any::<bool>().prop_map(Call::MotionRecording),
+        (index(), index(), value(), value()).prop_map(|(c, g, t, v)| Call::Groove(c, g, t, v)),
+    ];
+    let samples_and_loops = prop_oneof![
+        Just(Call::SamplerRegister),
+        (index(), index(), samples(), 0u32..4, value())
+            .prop_map(|(r, s, pcm, ch, sr)| Call::SamplerSlot(r, s, pcm, ch, sr * 96_000.0)),
+        (index(), index(), value()).prop_map(|(r, s, v)| Call::SamplerTrigger(r, s, v)),
+        (index(), index(), index(), value())
+            .prop_map(|(r, s, n, v)| Call::SamplerSlice(r, s, n, v)),
+        (index(), index(), value(), value()).prop_map(|(r, s, p, t)| Call::SamplerPitch(
+            r,
+            s,
+            p * 48.0 - 24.0,
+            t * 300.0
+        )),
+        (index(), index(), any::<bool>(), index(), value())
+            .prop_map(|(r, st, on, sl, v)| Call::SamplerStep(r, st, on, sl, v)),
+        (samples(), value()).prop_map(|(pcm, sr)| Call::GranulatorBuffer(pcm, sr * 96_000.0)),
+        (index(), value()).prop_map(|(p, v)| Call::GranulatorParam(p, v)),
+        value().prop_map(Call::GranulatorTrigger),
+        (index(), samples(), 0u32..4, value()).prop_map(|(c, pcm, ch, sr)| Call::LoopLoad(
+            c,
+            pcm,
+            ch,
+            sr * 96_000.0
+        )),
+        (index(), 0u8..6, value()).prop_map(|(c, p, v)| Call::LoopParam(c, p, v)),
+        (index(), any::<bool>()).prop_map(|(c, on)| Call::Freeze(c, on)),
+        any::<Option<(usize, u8)>>().prop_map(Call::State),
+    ];
+    prop_oneof![3 => voices, 3 => master, 3 => sequencing, 1 => samples_and_loops]
+}
+
+/// Frames in `pcm` at `channels` (0 when there aren't any).
+fn frames(pcm: &[f32], channels: u32) -> u32 {
+    if channels == 0 {
+        0
+    } else {
+        (pcm.len() / channels as usize) as u32
+    }
+}
+
+/// Make `call`, rendering into `buffer` for [`Call::Render`].
+unsafe fn apply(engine: *mut GooeyEngine, call: &Call, buffer: &mut Vec<f32>) {
+    match *call {
+        Call::Render(frames) => {
+            buffer.resize(frames as usize * 2, 0.0);
+            gooey_engine_render(engine, buffer.as_mut_ptr(), frames);
+        }
+        Call::Trigger(i, v) => {
+            gooey_engine_trigger_instrument_with_velocity(engine, i, v);
+        }
+        Call::TriggerChannel(c, v) => {
+            gooey_engine_trigger_channel_with_velocity(engine, c, v);
+        }
+        Call::SetInstrumentParam(i, p, v) => match i % 9 {
+            0 => {
+                gooey_engine_set_kick_param(engine, p, v);
+            }
+            1 => {
+                gooey_engine_set_snare_param(engine, p, v);
+            }
+            2 => {
+                gooey_engine_set_hihat_param(engine, p, v);
+            }
+            3 => {
+                gooey_engine_set_tom_param(engine, p, v);
+            }
+            4 => {
+                gooey_engine_set_bass_param(engine, p, v);
+            }
+            5 => {
+                gooey_engine_set_fm_snap_param(engine, p, v);
+            }
+            6 => {
+                gooey_engine_set_rimshot_param(engine, p, v);
+            }
+            7 => {
+                gooey_engine_set_cowbell_param(engine, p, v);
+            }
+            _ => {
+                gooey_engine_set_shaker_param(engine, p, v);
+            }
+        },
+        Call::SetNamedParam(ref name, v) => {
+            // Names with an interior nul can't cross the C API at all
+            if let Ok(name) = std::ffi::CString::new(name.as_str()) {
+                gooey_engine_set_param(engine, name.as_ptr(), v);
+                gooey_engine_get_param(engine, name.as_ptr());
+            }
+        }
+        Call::LoadPreset(i, p) => match i % 6 {
+            0 => {
+                gooey_engine_load_bass_preset(engine, p);
+            }
+            1 => {
+                gooey_engine_load_fm_snap_preset(engine, p);
+            }
+            2 => {
+                gooey_engine_load_rimshot_preset(engine, p);
+            }
+            3 => {
+                gooey_engine_load_cowbell_preset(engine, p);
+            }
+            4 => {
+                gooey_engine_load_shaker_preset(engine, p);
+            }
+            _ => {
+                gooey_engine_load_early_reflections_preset(engine, p);
+            }
+        },
+        Call::SetChannelParam(c, p, v) => {
+            gooey_engine_set_channel_param(engine, c, p, v);
+        }
+        Call::SetChannelTuning(c, v) => {
+            gooey_engine_set_channel_tuning(engine, c, v);
+        }
+        Call::SetChannelType(c, t) => {
+            gooey_engine_set_channel_instrument_type(engine, c, t);
+        }
+        Call::CreateSlot(t) => {
+            gooey_engine_create_slot(engine, t);
+        }
+        Call::DestroySlot(c) => {
+            gooey_engine_destroy_slot(engine, c);
+        }
+        Call::GlobalEffectEnabled(e, on) => {
+            gooey_engine_set_global_effect_enabled(engine, e, on);
+        }
+        Call::GlobalEffectParam(e, p, v) => {
+            gooey_engine_set_global_effect_param(engine, e, p, v);
+        }
+        Call::MoveEffect(e, p) => {
+            gooey_engine_move_effect(engine, e, p);
+        }
+        Call::OutputSafetyParam(p, v) => {
+            gooey_engine_set_output_safety_param(engine, p, v);
+        }
+        Call::SetBpm(bpm) => {
+            gooey_engine_set_bpm(engine, bpm);
+        }
+        Call::SetSwing(swing) => {
+            gooey_engine_set_swing(engine, swing);
+        }
+        Call::SetMasterGain(gain) => {
+            gooey_engine_set_master_gain(engine, gain);
+        }
+        Call::SetMasterTuning(a4, transpose) => {
+            gooey_engine_set_master_tuning(engine, a4, transpose);
+        }
+        Call::SetSeed(seed) => {
+            gooey_engine_set_seed(engine, seed);
+        }
+        Call::Transport(0) => {
+            gooey_engine_sequencer_start(engine);
+        }
+        Call::Transport(1) => {
+            gooey_engine_sequencer_stop(engine);
+        }
+        Call::Transport(_) => {
+            gooey_engine_sequencer_reset(engine);
+        }
+        Call::SetBeatPosition(beat) => {
+            gooey_engine_sequencer_set_beat_position(engine, beat);
+        }
+        Call::Step(i, s, on, v) => {
+            gooey_engine_sequencer_set_instrument_step_with_velocity(engine, i, s, on, v)
+        }
+        Call::StepNote(i, s, n) => {
+            gooey_engine_sequencer_set_instrument_step_note(engine, i, s, n);
+        }
+        Call::StepTune(i, s, t) => {
+            gooey_engine_sequencer_set_instrument_step_tune(engine, i, s, t);
+        }
+        Call::StepGate(i, s, g) => {
+            gooey_engine_sequencer_set_instrument_step_gate(engine, i, s, g);
+        }
+        Call::StepArticulation(i, s, a) => {
+            gooey_engine_sequencer_set_instrument_step_articulation(engine, i, s, a);
+        }
+        Call::StepBlend(i, s, x, y) => {
+            gooey_engine_sequencer_set_instrument_step_blend(engine, i, s, x, y);
+        }
+        Call::PatternEdit(i, op, n, a) => {
+            match op {
+                0 => gooey_engine_sequencer_copy_instrument_steps(engine, i, n as u32, a.to_bits()),
+                1 => {
+                    gooey_engine_sequencer_paste_instrument_steps(engine, i, n as u32);
+                    GooeyResult::Ok
+                }
+                2 => gooey_engine_sequencer_rotate_instrument_pattern(engine, i, n),
+                3 => gooey_engine_sequencer_reverse_instrument_pattern(engine, i),
+                4 => gooey_engine_sequencer_invert_instrument_pattern(engine, i),
+                5 => gooey_engine_sequencer_humanize_instrument_velocities(engine, i, a),
+                6 => gooey_engine_sequencer_scale_instrument_velocities(engine, i, a),
+                _ => gooey_engine_sequencer_compress_instrument_velocities(engine, i, a),
+            };
+        }
+        Call::PatternStore(slot) => {
+            gooey_engine_pattern_store(engine, slot);
+        }
+        Call::PatternLaunch(slot, quantization) => {
+            gooey_engine_pattern_launch(engine, slot, quantization);
+        }
+        Call::Lfo(l, on, timing, amount, offset) => {
+            gooey_engine_set_lfo_enabled(engine, l, on);
+            gooey_engine_set_lfo_timing(engine, l, timing);
+            gooey_engine_set_lfo_amount(engine, l, amount);
+            gooey_engine_set_lfo_offset(engine, l, offset);
+        }
+        Call::LfoRoute(l, i, p, depth, phase) => {
+            let route = gooey_engine_add_lfo_route(engine, l, i, p, depth);
+            gooey_engine_set_lfo_route_phase(engine, l, route as u32, phase * 720.0);
+            gooey_engine_set_lfo_route_trigger_source(engine, l, route as u32, i);
+        }
+        Call::Mute(i, m) => {
+            gooey_engine_set_instrument_mute(engine, i, m);
+        }
+        Call::Solo(i, s) => {
+            gooey_engine_set_instrument_solo(engine, i, s);
+        }
+        Call::ChannelGain(c, g) => {
+            gooey_engine_set_channel_gain(engine, c, g);
+        }
+        Call::ChannelPan(c, p) => {
+            gooey_engine_set_channel_pan(engine, c, p);
+        }
+        Call::FxBypass(c, mask) => {
+            gooey_engine_set_channel_fx_bypass(engine, c, mask);
+        }
+        Call::PanSpread(i, w) => {
+            gooey_engine_set_instrument_pan_spread(engine, i, w);
+        }
+        Call::Variation(i, depth, variants, mask) => {
+            gooey_engine_set_instrument_variation(engine, i, depth, variants);
+            gooey_engine_set_instrument_variation_params(engine, i, mask);
+        }
+        Call::SaturatorModel(i, m) => {
+            gooey_engine_set_instrument_saturator_model(engine, i, m);
+        }
+        Call::Blend(i, on, x, y) => {
+            if on {
+                gooey_engine_blend_enable(engine, i);
+            } else {
+                gooey_engine_blend_disable(engine, i);
+            }
+            gooey_engine_blend_set_position(engine, i, x, y);
+        }
+        Call::BlendCorner(i, c, p) => {
+            gooey_engine_blend_set_corner_preset(engine, i, c, p);
+        }
+        Call::Randomize(i, amount, seed) => {
+            gooey_engine_randomize_instrument(engine, i, amount, seed);
+        }
+        Call::Chord(root, scale, degree, voicing, preset, octave, velocity) => {
+            gooey_engine_poly_trigger_chord(
+                engine, root, scale, degree, voicing, preset, octave, velocity,
+            );
+        }
+        Call::PolyRelease => {
+            gooey_engine_poly_release(engine);
+        }
+        Call::PolyParam(p, v) => {
+            gooey_engine_poly_set_param(engine, p, v);
+        }
+        Call::ScaleQuantize(root, scale) => {
+            gooey_engine_set_scale_quantize(engine, root, scale);
+        }
+        Call::EffectLaneGlobal(e, p, step, v) => {
+            let lane = gooey_engine_effect_lane_create_global(engine, e, p);
+            gooey_engine_effect_lane_set_step(engine, lane as u32, step, v);
+            gooey_engine_effect_lane_set_interpolated(engine, lane as u32, step % 2 == 0);
+        }
+        Call::EffectLaneTrack(t, slot, p, step, v) => {
+            let lane = gooey_engine_effect_lane_create_track(engine, t, slot, p);
+            gooey_engine_effect_lane_set_step(engine, lane as u32, step, v);
+        }
+        Call::EffectLaneRemove(lane) => {
+            gooey_engine_effect_lane_remove(engine, lane);
+        }
+        Call::MotionRecording(on) => {
+            gooey_engine_set_motion_recording(engine, on);
+        }
+        Call::TrackEffectAdd(t, e) => {
+            gooey_engine_track_effect_add(engine, t, e);
+        }
+        Call::TrackEffectParam(t, s, p, v) => {
+            gooey_engine_track_effect_set_param(engine, t, s, p, v);
+        }
+        Call::TrackEffectRemove(t, s) => {
+            gooey_engine_track_effect_remove(engine, t, s);
+        }
+        Call::MixerRoute(source, track) => {
+            gooey_engine_mixer_route_source(engine, source, track);
+        }
+        Call::TrackGain(t, g) => {
+            gooey_engine_mixer_set_track_gain(engine, t, g);
+        }
+        Call::SamplerRegister => {
+            gooey_engine_sampler_register(engine);
+        }
+        Call::SamplerSlot(rack, slot, ref pcm, channels, sample_rate) => {
+            gooey_engine_sampler_set_slot_buffer(
+                engine,
+                rack,
+                slot,
+                pcm.as_ptr(),
+                frames(pcm, channels),
+                channels,
+                sample_rate,
+            );
+        }
+        Call::SamplerTrigger(rack, slot, v) => {
+            gooey_engine_sampler_trigger(engine, rack, slot, v);
+        }
+        Call::SamplerSlice(rack, slot, count, v) => {
+            gooey_engine_sampler_slice_equal(engine, rack, slot, count);
+            gooey_engine_sampler_slice_transients(engine, rack, slot, v);
+            gooey_engine_sampler_trigger_slice(engine, rack, slot, count / 2, v);
+        }
+        Call::SamplerPitch(rack, slot, semitones, bpm) => {
+            gooey_engine_sampler_set_slot_pitch(engine, rack, slot, semitones);
+            gooey_engine_sampler_set_slot_tempo(engine, rack, slot, bpm);
+        }
+        Call::SamplerStep(rack, step, on, slot, v) => {
+            gooey_engine_sampler_set_step(engine, rack, step, on, slot, v);
+        }
+        Call::GranulatorBuffer(ref pcm, sample_rate) => {
+            gooey_engine_granulator_set_buffer(engine, pcm.as_ptr(), pcm.len() as u32, sample_rate);
+        }
+        Call::GranulatorParam(p, v) => {
+            gooey_engine_granulator_set_param(engine, p, v);
+        }
+        Call::GranulatorTrigger(v) => {
+            gooey_engine_granulator_trigger(engine, v);
+        }
+        Call::LoopLoad(channel, ref pcm, channels, sample_rate) => {
+            gooey_engine_loop_load(
+                engine,
+                channel,
+                pcm.as_ptr(),
+                frames(pcm, channels),
+                channels,
+                sample_rate,
+            );
+            gooey_engine_loop_set_playing(engine, channel, true);
+        }
+        Call::LoopParam(channel, param, v) => {
+            match param {
+                0 => gooey_engine_loop_set_gain(engine, channel, v),
+                1 => gooey_engine_loop_set_start(engine, channel, v),
+                2 => gooey_engine_loop_set_end(engine, channel, v),
+                3 => gooey_engine_loop_set_speed(engine, channel, v * 4.0 - 2.0),
+                4 => gooey_engine_loop_set_position(engine, channel, v),
+                _ => gooey_engine_loop_set_source_bpm(engine, channel, v * 300.0),
+            };
+        }
+        Call::Freeze(channel, on) => {
+            if on {
+                gooey_engine_freeze_channel(engine, channel);
+            } else {
+                gooey_engine_unfreeze_channel(engine, channel);
+            }
+        }
+        Call::Groove(channel, groove, timing, velocity) => {
+            gooey_engine_set_channel_groove(engine, channel, groove, timing, velocity);
+        }
+        Call::Release(i, channel) => {
+            if channel {
+                gooey_engine_release_channel(engine, i);
+            } else {
+                gooey_engine_release_instrument(engine, i);
+            }
+        }
+        Call::Panic => {
+            gooey_engine_panic(engine);
+        }
+        Call::State(damage) => {
+            let mut length = 0;
+            let bytes = gooey_engine_export_state(engine, &mut length);
+            let mut state = std::slice::from_raw_parts(bytes, length as usize).to_vec();
+            gooey_engine_free_state(bytes, length);
+            if let Some((index, byte)) = damage {
+                let index = index % state.len();
+                state[index] = byte;
+            }
+            gooey_engine_import_state(engine, state.as_ptr(), state.len() as u32);
+        }
+    }
+}
+
+/// Check every sample `render` wrote since the last check.
+fn check_output(
+    engine: *mut GooeyEngine,
+    call: &Call,
+    buffer: &[f32],
+) -> Result<(), TestCaseError> {
+    if let Call::Render(_) = call {
+        let ceiling =
+            unsafe { gooey_engine_get_output_safety_param(engine, OUTPUT_SAFETY_PARAM_CEILING) };
+        for &sample in buffer {
+            prop_assert!(sample.is_finite(), "non-finite sample {sample}");
+            prop_assert!(
+                sample.abs() <= ceiling.max(1.0) + 1e-3,
+                "sample {sample} past the {ceiling} ceiling"
+            );
+        }
+        prop_assert!(
+            !unsafe { gooey_engine_has_error(engine) },
+            "render panicked"
+        );
+    }
+    Ok(())
+}
+
+/// Run `calls`, checking every render.
+fn run(
+    engine: *mut GooeyEngine,
+    calls: &[Call],
+    buffer: &mut Vec<f32>,
+) -> Result<(), TestCaseError> {
+    for call in calls {
+        unsafe { apply(engine, call, buffer) };
+        check_output(engine, call, buffer)?;
+    }
+    // Always finish on a render so trailing calls reach the audio path
+    let tail = Call::Render(512);
+    unsafe { apply(engine, &tail, buffer) };
+    check_output(engine, &tail, buffer)
+}
+
+/// 64 cases unless `PROPTEST_CASES` asks for more.
+fn cases() -> u32 {
+    std::env::var("PROPTEST_CASES")
+        .ok()
+        .and_then(|cases| cases.parse().ok())
+        .unwrap_or(64)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        cases: cases(),
+        // Failures print their shrunk call list; nothing to persist
+        failure_persistence: None,
+        ..ProptestConfig::default()
+    })]
+
+    #[test]
+    fn random_call_sequences_stay_safe(calls in prop::collection::vec(call(), 1..48)) {
+        let mut buffer = Vec::with_capacity(4096 * 2);
+        let before = live_bytes();
+        let engine = gooey_engine_new(SAMPLE_RATE);
+
+        run(engine, &calls, &mut buffer)?;
+        let first_pass = live_bytes();
+        for _ in 0..3 {
+            run(engine, &calls, &mut buffer)?;
+        }
+        let growth = live_bytes() - first_pass;
+        prop_assert!(
+            growth < REPLAY_GROWTH_LIMIT,
+            "heap grew {growth} bytes replaying the calls"
+        );
+
+        unsafe { gooey_engine_free(engine) };
+        drop(buffer);
+        let leaked = live_bytes() - before;
+        prop_assert!(leaked < LEAK_LIMIT, "{leaked} bytes still live after free");
+    }
+}
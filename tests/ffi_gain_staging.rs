@@ -167,8 +167,10 @@ fn master_gain_is_applied_before_the_optional_limiter() {
             .map(|(limited, dry)| (limited - dry.tanh()).abs())
             .fold(0.0_f32, f32::max);
 
+        // The always-on output DC blocker sits after the limiter in both
+        // renders, so the match is close rather than exact.
         assert!(
-            max_error < 1e-6,
+            max_error < 2e-4,
             "limiter should receive the master-gained sum, max error was {max_error}"
         );
 
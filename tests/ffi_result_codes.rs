@@ -0,0 +1,148 @@
+//! Tests for FFI result codes and last-error diagnostics.
+
+use gooey::ffi::*;
+use std::ffi::CStr;
+
+fn last_error() -> String {
+    let msg = gooey_engine_last_error_message();
+    assert!(!msg.is_null());
+    unsafe { CStr::from_ptr(msg) }.to_str().unwrap().to_owned()
+}
+
+#[test]
+fn valid_calls_return_ok() {
+    let engine = gooey_engine_new(44_100.0);
+    unsafe {
+        assert_eq!(
+            gooey_engine_set_kick_param(engine, KICK_PARAM_DECAY, 0.5),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_set_channel_param(engine, 1, SNARE_PARAM_DECAY, 0.3),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_trigger_channel_with_velocity(engine, 0, 0.8),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_set_global_effect_param(
+                engine,
+                EFFECT_LIMITER,
+                LIMITER_PARAM_THRESHOLD,
+                0.5
+            ),
+            GooeyResult::Ok
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn null_engine_is_reported() {
+    unsafe {
+        assert_eq!(
+            gooey_engine_set_kick_param(std::ptr::null_mut(), KICK_PARAM_DECAY, 0.5),
+            GooeyResult::NullPointer
+        );
+    }
+    let msg = last_error();
+    assert!(msg.contains("gooey_engine_set_kick_param"), "{msg}");
+    assert!(msg.contains("null"), "{msg}");
+}
+
+#[test]
+fn out_of_range_indices_are_reported() {
+    let engine = gooey_engine_new(44_100.0);
+    unsafe {
+        assert_eq!(
+            gooey_engine_set_channel_param(engine, 99, 0, 0.5),
+            GooeyResult::InvalidChannel
+        );
+        assert!(last_error().contains("channel 99"));
+
+        assert_eq!(
+            gooey_engine_set_kick_param(engine, 999, 0.5),
+            GooeyResult::InvalidParam
+        );
+        assert!(last_error().contains("param 999"));
+
+        // Channel 0 holds a kick, which has no parameter past KICK_PARAM_TUNING.
+        assert_eq!(
+            gooey_engine_set_channel_param(engine, 0, KICK_PARAM_TUNING + 1, 0.5),
+            GooeyResult::InvalidParam
+        );
+
+        assert_eq!(
+            gooey_engine_set_channel_instrument_type(engine, 0, INSTRUMENT_COUNT),
+            GooeyResult::InvalidInstrument
+        );
+        assert_eq!(
+            gooey_engine_trigger_instrument(engine, INSTRUMENT_COUNT),
+            GooeyResult::InvalidInstrument
+        );
+
+        assert_eq!(
+            gooey_engine_set_global_effect_enabled(engine, 99, true),
+            GooeyResult::InvalidEffect
+        );
+        assert_eq!(
+            gooey_engine_set_global_effect_param(engine, EFFECT_LIMITER, 99, 0.5),
+            GooeyResult::InvalidParam
+        );
+        assert_eq!(
+            gooey_engine_set_global_effect_param(engine, EFFECT_DELAY, DELAY_PARAM_TIMING, 99.0),
+            GooeyResult::InvalidValue
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn missing_instrument_is_reported() {
+    let engine = gooey_engine_new(44_100.0);
+    unsafe {
+        // Replace the only kick with a snare.
+        assert_eq!(
+            gooey_engine_set_channel_instrument_type(engine, 0, INSTRUMENT_SNARE),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_set_kick_param(engine, KICK_PARAM_DECAY, 0.5),
+            GooeyResult::InvalidInstrument
+        );
+        assert!(last_error().contains("kick"));
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn rust_api_errors_are_reported() {
+    let engine = gooey_engine_new(44_100.0);
+    unsafe {
+        let samples = [0.0_f32; 16];
+        assert!(!gooey_engine_granulator_set_buffer(
+            engine,
+            samples.as_ptr(),
+            samples.len() as u32,
+            0.0
+        ));
+        let msg = last_error();
+        assert!(msg.contains("gooey_engine_granulator_set_buffer"), "{msg}");
+        assert!(msg.contains("sample rate"), "{msg}");
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn last_error_is_per_thread() {
+    unsafe {
+        gooey_engine_set_kick_param(std::ptr::null_mut(), 0, 0.0);
+    }
+    assert!(!gooey_engine_last_error_message().is_null());
+
+    let other = std::thread::spawn(|| gooey_engine_last_error_message().is_null())
+        .join()
+        .unwrap();
+    assert!(other);
+}
@@ -0,0 +1,113 @@
+//! Tests for the FM snap voice over FFI.
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+
+fn render(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    let mut buf = vec![0.0_f32; frames * 2];
+    unsafe { gooey_engine_render(engine, buf.as_mut_ptr(), frames as u32) };
+    buf
+}
+
+fn peak(samples: &[f32]) -> f32 {
+    samples.iter().fold(0.0, |m, s| m.max(s.abs()))
+}
+
+/// Render one FM snap hit after `setup`.
+fn hit(setup: impl Fn(*mut GooeyEngine)) -> Vec<f32> {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    setup(engine);
+    render(engine, 64);
+    unsafe { gooey_engine_trigger_instrument(engine, INSTRUMENT_FM_SNAP) };
+    let out = render(engine, 4096);
+    unsafe { gooey_engine_free(engine) };
+    out
+}
+
+#[test]
+fn trigger_sounds_and_params_round_trip() {
+    let out = hit(|_| {});
+    assert!(peak(&out) > 0.05, "{}", peak(&out));
+    assert!(out.iter().all(|s| s.is_finite()));
+
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        assert_eq!(
+            gooey_engine_set_fm_snap_param(engine, FM_SNAP_PARAM_INDEX, 0.3),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_set_fm_snap_param(engine, FM_SNAP_PARAM_TUNING, 0.75),
+            GooeyResult::Ok
+        );
+        render(engine, 1);
+        assert!((gooey_engine_get_fm_snap_param(engine, FM_SNAP_PARAM_INDEX) - 0.3).abs() < 1e-6);
+        assert!((gooey_engine_get_fm_snap_param(engine, FM_SNAP_PARAM_TUNING) - 0.75).abs() < 1e-6);
+
+        assert_eq!(
+            gooey_engine_set_fm_snap_param(engine, gooey_engine_fm_snap_param_count(), 0.5),
+            GooeyResult::InvalidParam
+        );
+        assert!(gooey_engine_get_fm_snap_param(engine, 999).is_nan());
+        assert_eq!(
+            gooey_engine_set_fm_snap_param(std::ptr::null_mut(), 0, 0.5),
+            GooeyResult::NullPointer
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn presets_load_and_change_the_sound() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        let snap = gooey_engine_get_fm_snap_param(engine, FM_SNAP_PARAM_FREQUENCY);
+        assert_eq!(
+            gooey_engine_load_fm_snap_preset(engine, FM_SNAP_PRESET_RIM),
+            GooeyResult::Ok
+        );
+        assert!(gooey_engine_get_fm_snap_param(engine, FM_SNAP_PARAM_FREQUENCY) > snap);
+        assert_eq!(
+            gooey_engine_load_fm_snap_preset(engine, 4),
+            GooeyResult::InvalidValue
+        );
+
+        // Replacing the voice leaves nothing for the FM snap calls to address.
+        gooey_engine_set_channel_instrument_type(engine, 5, INSTRUMENT_KICK);
+        assert_eq!(
+            gooey_engine_load_fm_snap_preset(engine, FM_SNAP_PRESET_ZAP),
+            GooeyResult::InvalidInstrument
+        );
+        assert_eq!(
+            gooey_engine_set_fm_snap_param(engine, FM_SNAP_PARAM_VOLUME, 0.5),
+            GooeyResult::InvalidInstrument
+        );
+        gooey_engine_free(engine);
+    }
+
+    let default = hit(|_| {});
+    let zap = hit(|engine| unsafe {
+        gooey_engine_load_fm_snap_preset(engine, FM_SNAP_PRESET_ZAP);
+    });
+    assert!(default.iter().zip(&zap).any(|(a, b)| (a - b).abs() > 1e-3));
+}
+
+#[test]
+fn lfo_routes_modulate_fm_snap_params() {
+    let lfo = |routed: bool| {
+        hit(move |engine| unsafe {
+            gooey_engine_set_lfo_enabled(engine, 0, true);
+            gooey_engine_set_lfo_offset(engine, 0, 1.0);
+            if routed {
+                gooey_engine_add_lfo_route(engine, 0, INSTRUMENT_FM_SNAP, FM_SNAP_PARAM_INDEX, 1.0);
+            }
+        })
+    };
+    let plain = lfo(false);
+    let modulated = lfo(true);
+    assert!(plain
+        .iter()
+        .zip(&modulated)
+        .any(|(a, b)| (a - b).abs() > 1e-3));
+}
@@ -0,0 +1,116 @@
+//! Integration tests for channel freeze (`gooey_engine_freeze_channel`).
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 48_000.0;
+const BLOCK: usize = 512;
+
+/// An engine playing four-on-the-floor kicks on channel 0.
+unsafe fn kick_engine() -> *mut GooeyEngine {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    gooey_engine_set_bpm(engine, 120.0);
+    for step in [0, 4, 8, 12] {
+        gooey_engine_sequencer_set_instrument_step(engine, INSTRUMENT_KICK, step, true);
+    }
+    engine
+}
+
+/// Start the sequencers and render `frames` frames from the top.
+unsafe fn play(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    gooey_engine_sequencer_reset(engine);
+    gooey_engine_sequencer_start(engine);
+    let channels = GOOEY_OUTPUT_CHANNELS as usize;
+    let mut out = vec![0.0_f32; frames.div_ceil(BLOCK) * BLOCK * channels];
+    for chunk in out.chunks_mut(BLOCK * channels) {
+        gooey_engine_render(engine, chunk.as_mut_ptr(), BLOCK as u32);
+    }
+    gooey_engine_sequencer_stop(engine);
+    out
+}
+
+fn peak(samples: &[f32]) -> f32 {
+    samples.iter().fold(0.0, |m: f32, x| m.max(x.abs()))
+}
+
+#[test]
+fn frozen_channel_plays_back_its_pattern() {
+    unsafe {
+        let engine = kick_engine();
+        // Two bars at 120 BPM, so the second one plays the loop seam
+        let frames = (4.0 * SAMPLE_RATE) as usize;
+        // Warm-up pass so both renders start with the same tails
+        play(engine, frames);
+        let live = play(engine, frames);
+
+        assert_eq!(gooey_engine_freeze_channel(engine, 0), GooeyResult::Ok);
+        assert!(gooey_engine_is_channel_frozen(engine, 0));
+        let frozen = play(engine, frames);
+
+        let error = live
+            .iter()
+            .zip(&frozen)
+            .fold(0.0, |m: f32, (a, b)| m.max((a - b).abs()));
+        assert!(peak(&frozen) > 0.1, "frozen peak {}", peak(&frozen));
+        assert!(
+            error < 0.05 * peak(&live),
+            "frozen render drifts from live: {error}"
+        );
+
+        // The instrument is no longer running: retuning it changes nothing
+        // until the channel is unfrozen
+        gooey_engine_set_kick_param(engine, KICK_PARAM_FREQUENCY, 0.9);
+        let still_frozen = play(engine, frames);
+        assert_eq!(frozen, still_frozen);
+
+        assert_eq!(gooey_engine_unfreeze_channel(engine, 0), GooeyResult::Ok);
+        assert!(!gooey_engine_is_channel_frozen(engine, 0));
+        let retuned = play(engine, frames);
+        assert_ne!(frozen, retuned);
+
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn frozen_channel_is_silent_while_stopped() {
+    unsafe {
+        let engine = kick_engine();
+        assert_eq!(gooey_engine_freeze_channel(engine, 0), GooeyResult::Ok);
+        let mut out = vec![1.0_f32; BLOCK * GOOEY_OUTPUT_CHANNELS as usize];
+        gooey_engine_render(engine, out.as_mut_ptr(), BLOCK as u32);
+        assert!(peak(&out) < 1e-4, "stopped peak {}", peak(&out));
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn freeze_rejects_bad_channels_and_empty_patterns() {
+    unsafe {
+        let engine = kick_engine();
+        assert_eq!(
+            gooey_engine_freeze_channel(engine, 999),
+            GooeyResult::InvalidChannel
+        );
+        assert_eq!(
+            gooey_engine_freeze_channel(engine, INSTRUMENT_SNARE),
+            GooeyResult::InvalidValue
+        );
+        assert!(!gooey_engine_is_channel_frozen(engine, INSTRUMENT_SNARE));
+        assert_eq!(
+            gooey_engine_unfreeze_channel(engine, 999),
+            GooeyResult::InvalidChannel
+        );
+        assert_eq!(
+            gooey_engine_freeze_channel(std::ptr::null_mut(), 0),
+            GooeyResult::NullPointer
+        );
+        assert!(!gooey_engine_is_channel_frozen(std::ptr::null(), 0));
+
+        // Swapping the instrument type drops the frozen audio
+        assert_eq!(gooey_engine_freeze_channel(engine, 0), GooeyResult::Ok);
+        gooey_engine_set_channel_instrument_type(engine, 0, INSTRUMENT_SNARE);
+        assert!(!gooey_engine_is_channel_frozen(engine, 0));
+
+        gooey_engine_free(engine);
+    }
+}
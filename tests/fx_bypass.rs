@@ -0,0 +1,96 @@
+//! Tests for per-channel bypass of the master effects over FFI.
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+
+/// Render a kick and a snare hit through the engine after `setup`, with the
+/// delay first in the chain.
+fn render(delay: bool, setup: impl Fn(*mut GooeyEngine)) -> Vec<f32> {
+    let mut buf = vec![0.0_f32; 8192 * 2];
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        let mut order = [0; REORDERABLE_EFFECT_COUNT as usize];
+        gooey_engine_get_effect_order(engine, order.as_mut_ptr(), REORDERABLE_EFFECT_COUNT);
+        order.sort_by_key(|&effect| effect != EFFECT_DELAY);
+        assert!(gooey_engine_set_effect_order(
+            engine,
+            order.as_ptr(),
+            REORDERABLE_EFFECT_COUNT
+        ));
+        gooey_engine_set_global_effect_enabled(engine, EFFECT_DELAY, delay);
+        gooey_engine_set_global_effect_param(engine, EFFECT_DELAY, DELAY_PARAM_MIX, 0.5);
+        setup(engine);
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_KICK);
+        gooey_engine_render(engine, buf.as_mut_ptr(), 8192);
+        gooey_engine_free(engine);
+    }
+    buf
+}
+
+fn max_diff(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).fold(0.0, |m, (x, y)| m.max((x - y).abs()))
+}
+
+#[test]
+fn bypassing_channel_skips_the_effect() {
+    let dry = render(false, |_| {});
+    let wet = render(true, |_| {});
+    let bypassed = render(true, |engine| unsafe {
+        let mask = 1 << EFFECT_DELAY | 1 << EFFECT_REVERB;
+        assert_eq!(
+            gooey_engine_set_channel_fx_bypass(engine, INSTRUMENT_KICK, 1 << EFFECT_DELAY),
+            GooeyResult::Ok
+        );
+        // Bypassing another channel changes nothing for the kick
+        gooey_engine_set_channel_fx_bypass(engine, INSTRUMENT_SNARE, mask);
+        gooey_engine_set_channel_fx_bypass(engine, INSTRUMENT_SNARE, 0);
+    });
+    assert!(max_diff(&dry, &wet) > 1e-3);
+    assert_eq!(bypassed, dry);
+}
+
+#[test]
+fn other_channels_keep_the_effect() {
+    let kick_only = |delay| {
+        render(delay, |engine| unsafe {
+            gooey_engine_set_channel_fx_bypass(engine, INSTRUMENT_KICK, 1 << EFFECT_DELAY);
+            gooey_engine_trigger_instrument(engine, INSTRUMENT_SNARE);
+        })
+    };
+    // The snare still echoes, so the delay makes a difference
+    assert!(max_diff(&kick_only(false), &kick_only(true)) > 1e-3);
+}
+
+#[test]
+fn bypass_mask_round_trips_and_rejects_bad_input() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        assert_eq!(
+            gooey_engine_get_channel_fx_bypass(engine, INSTRUMENT_HIHAT),
+            0
+        );
+        assert_eq!(
+            gooey_engine_set_channel_fx_bypass(engine, INSTRUMENT_HIHAT, FX_BYPASS_ALL),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_get_channel_fx_bypass(engine, INSTRUMENT_HIHAT),
+            FX_BYPASS_ALL
+        );
+        assert_eq!(
+            gooey_engine_set_channel_fx_bypass(engine, INSTRUMENT_HIHAT, 1 << EFFECT_LIMITER),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_set_channel_fx_bypass(engine, 999, 0),
+            GooeyResult::InvalidChannel
+        );
+        assert_eq!(
+            gooey_engine_set_channel_fx_bypass(std::ptr::null_mut(), 0, 0),
+            GooeyResult::NullPointer
+        );
+        assert_eq!(gooey_engine_get_channel_fx_bypass(engine, 999), 0);
+        gooey_engine_free(engine);
+    }
+}
@@ -0,0 +1,308 @@
+//! Golden-audio regression tests.
+//!
+//! Renders every instrument preset and a few DSL programs offline and compares
+//! coarse audio features (per-block RMS and zero crossings, peak level)
+//! against the checked-in files in `tests/golden/`. Features rather than raw
+//! samples are compared so that DSP refactors which only move rounding noise
+//! (block processing, SIMD, filter rewrites) pass, while audible changes in
+//! level, envelope or brightness fail.
+//!
+//! After an intentional sound change, re-record the goldens and review the
+//! diff:
+//!
+//! ```text
+//! cargo test --test golden_audio --features regenerate-goldens
+//! ```
+
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use gooey::dsl::Program;
+use gooey::engine::Instrument;
+use gooey::instruments::*;
+
+const SR: f32 = 44_100.0;
+const BLOCK: usize = 4096;
+
+/// Allowed per-block RMS and peak deviation.
+const LEVEL_TOLERANCE_DB: f32 = 0.5;
+/// Blocks quieter than this (in both renders) are not compared.
+const SILENCE_DB: f32 = -80.0;
+/// Allowed relative deviation in per-block zero crossings (plus 4 absolute).
+const ZCR_TOLERANCE: f32 = 0.05;
+
+struct Features {
+    frames: usize,
+    peak_db: f32,
+    rms_db: Vec<f32>,
+    zero_crossings: Vec<u32>,
+}
+
+fn to_db(x: f32) -> f32 {
+    20.0 * x.max(1e-10).log10()
+}
+
+impl Features {
+    fn measure(samples: &[f32]) -> Self {
+        let peak = samples.iter().fold(0.0_f32, |m, s| m.max(s.abs()));
+        let mut rms_db = Vec::new();
+        let mut zero_crossings = Vec::new();
+        for block in samples.chunks(BLOCK) {
+            let power = block.iter().map(|s| s * s).sum::<f32>() / block.len() as f32;
+            rms_db.push(to_db(power.sqrt()));
+            let crossings = block
+                .windows(2)
+                .filter(|w| (w[0] < 0.0) != (w[1] < 0.0))
+                .count();
+            zero_crossings.push(crossings as u32);
+        }
+        Self {
+            frames: samples.len(),
+            peak_db: to_db(peak),
+            rms_db,
+            zero_crossings,
+        }
+    }
+
+    fn serialize(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "frames {}", self.frames).unwrap();
+        writeln!(out, "peak_db {:.2}", self.peak_db).unwrap();
+        let rms: Vec<String> = self.rms_db.iter().map(|v| format!("{v:.2}")).collect();
+        writeln!(out, "rms_db {}", rms.join(" ")).unwrap();
+        let zcr: Vec<String> = self.zero_crossings.iter().map(u32::to_string).collect();
+        writeln!(out, "zero_crossings {}", zcr.join(" ")).unwrap();
+        out
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        let mut frames = None;
+        let mut peak_db = None;
+        let mut rms_db = None;
+        let mut zero_crossings = None;
+        for line in text.lines().filter(|l| !l.trim().is_empty()) {
+            let (key, values) = line.split_once(' ').unwrap_or((line, ""));
+            match key {
+                "frames" => frames = values.trim().parse().ok(),
+                "peak_db" => peak_db = values.trim().parse().ok(),
+                "rms_db" => {
+                    rms_db = values
+                        .split_whitespace()
+                        .map(str::parse)
+                        .collect::<Result<_, _>>()
+                        .ok()
+                }
+                "zero_crossings" => {
+                    zero_crossings = values
+                        .split_whitespace()
+                        .map(str::parse)
+                        .collect::<Result<_, _>>()
+                        .ok()
+                }
+                other => return Err(format!("unknown golden key '{other}'")),
+            }
+        }
+        Ok(Self {
+            frames: frames.ok_or("missing frames")?,
+            peak_db: peak_db.ok_or("missing peak_db")?,
+            rms_db: rms_db.ok_or("missing rms_db")?,
+            zero_crossings: zero_crossings.ok_or("missing zero_crossings")?,
+        })
+    }
+
+    /// Every mismatch against `golden`, empty if within tolerance.
+    fn diff(&self, golden: &Self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.frames != golden.frames || self.rms_db.len() != golden.rms_db.len() {
+            errors.push(format!(
+                "length {} != golden {}",
+                self.frames, golden.frames
+            ));
+            return errors;
+        }
+        if (self.peak_db - golden.peak_db).abs() > LEVEL_TOLERANCE_DB {
+            errors.push(format!(
+                "peak {:.2} dB != golden {:.2} dB",
+                self.peak_db, golden.peak_db
+            ));
+        }
+        for (i, (&got, &want)) in self.rms_db.iter().zip(&golden.rms_db).enumerate() {
+            if got.max(want) < SILENCE_DB {
+                continue;
+            }
+            if (got - want).abs() > LEVEL_TOLERANCE_DB {
+                errors.push(format!("block {i}: rms {got:.2} dB != golden {want:.2} dB"));
+            }
+            let (got_zc, want_zc) = (self.zero_crossings[i], golden.zero_crossings[i]);
+            let allowed = 4.0 + want_zc as f32 * ZCR_TOLERANCE;
+            if (got_zc as f32 - want_zc as f32).abs() > allowed {
+                errors.push(format!(
+                    "block {i}: {got_zc} zero crossings != golden {want_zc}"
+                ));
+            }
+        }
+        errors
+    }
+}
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.txt"))
+}
+
+/// Compare `samples` against the golden for `name`, or re-record it when the
+/// `regenerate-goldens` feature is enabled. Returns the failure, if any.
+fn check_golden(name: &str, samples: &[f32]) -> Option<String> {
+    assert!(
+        samples.iter().all(|s| s.is_finite()),
+        "{name}: render produced non-finite samples"
+    );
+    let features = Features::measure(samples);
+    let path = golden_path(name);
+
+    if cfg!(feature = "regenerate-goldens") {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, features.serialize()).unwrap();
+        return None;
+    }
+
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(_) => {
+            return Some(format!(
+                "{name}: no golden at {} (run with --features regenerate-goldens)",
+                path.display()
+            ))
+        }
+    };
+    let golden = Features::parse(&text).unwrap_or_else(|e| panic!("{name}: {e}"));
+    let errors = features.diff(&golden);
+    if errors.is_empty() {
+        None
+    } else {
+        Some(format!("{name}:\n    {}", errors.join("\n    ")))
+    }
+}
+
+fn assert_goldens(cases: impl IntoIterator<Item = (String, Vec<f32>)>) {
+    let failures: Vec<String> = cases
+        .into_iter()
+        .filter_map(|(name, samples)| check_golden(&name, &samples))
+        .collect();
+    assert!(
+        failures.is_empty(),
+        "audio differs from goldens:\n{}",
+        failures.join("\n")
+    );
+}
+
+/// Trigger once at full velocity and render `seconds` of audio.
+fn render_one_shot(mut instrument: impl Instrument, seconds: f32) -> Vec<f32> {
+    instrument.trigger(0.0);
+    (0..(SR * seconds) as usize)
+        .map(|i| instrument.tick(i as f64 / SR as f64))
+        .collect()
+}
+
+/// Render a DSL program as `[left, right]` golden cases.
+fn render_program(name: &str, source: &str, seconds: f32) -> [(String, Vec<f32>); 2] {
+    let program = Program::parse(source).expect("parse");
+    let mut engine = program.build_engine(SR).expect("build engine");
+    let (mut left, mut right) = (Vec::new(), Vec::new());
+    for i in 0..(SR * seconds) as usize {
+        let frame = engine.tick_stereo(i as f64 / SR as f64);
+        left.push(frame.l);
+        right.push(frame.r);
+    }
+    [
+        (format!("{name}_left"), left),
+        (format!("{name}_right"), right),
+    ]
+}
+
+#[test]
+fn instrument_presets_match_goldens() {
+    let mut cases = Vec::new();
+    for (preset, config) in [
+        ("tight", KickConfig::tight()),
+        ("punch", KickConfig::punch()),
+        ("loose", KickConfig::loose()),
+        ("dirt", KickConfig::dirt()),
+    ] {
+        let kick = KickDrum::with_config(SR, config);
+        cases.push((format!("kick_{preset}"), render_one_shot(kick, 1.0)));
+    }
+    for (preset, config) in [
+        ("tight", SnareConfig::tight()),
+        ("loose", SnareConfig::loose()),
+        ("hiss", SnareConfig::hiss()),
+        ("smack", SnareConfig::smack()),
+    ] {
+        let snare = SnareDrum::with_config(SR, config);
+        cases.push((format!("snare_{preset}"), render_one_shot(snare, 1.0)));
+    }
+    for (preset, config) in [
+        ("short", HiHat2Config::short()),
+        ("loose", HiHat2Config::loose()),
+        ("dark", HiHat2Config::dark()),
+        ("soft", HiHat2Config::soft()),
+        ("classic", HiHat2Config::classic()),
+        ("classic_loose", HiHat2Config::classic_loose()),
+    ] {
+        let hihat = HiHat2::with_config(SR, config);
+        cases.push((format!("hihat_{preset}"), render_one_shot(hihat, 1.0)));
+    }
+    for (preset, config) in [
+        ("derp", Tom2Config::derp()),
+        ("ring", Tom2Config::ring()),
+        ("brush", Tom2Config::brush()),
+        ("void", Tom2Config::void_preset()),
+    ] {
+        let mut tom = Tom2::new(SR);
+        tom.set_config(config);
+        cases.push((format!("tom_{preset}"), render_one_shot(tom, 1.0)));
+    }
+    for (preset, config) in [
+        ("acid", BassConfig::acid()),
+        ("sub", BassConfig::sub()),
+        ("reese", BassConfig::reese()),
+        ("stab", BassConfig::stab()),
+    ] {
+        let bass = BassSynth::with_config(SR, config);
+        cases.push((format!("bass_{preset}"), render_one_shot(bass, 1.0)));
+    }
+    assert_goldens(cases);
+}
+
+#[test]
+fn dsl_programs_match_goldens() {
+    let basic_beat = r#"
+        bpm 120
+        inst kick kick
+        inst snare snare
+        inst hihat hihat closed
+        seq kick x...x...|x...x...
+        seq snare ....x...|....x...
+        seq hihat x.x.x.x.|x.x.x.x.
+    "#;
+    let filtered_groove = r#"
+        bpm 128
+        master 0.5
+        inst kick kick punch
+        inst hihat hihat
+        inst tom tom2
+        seq kick x..x..x.|x..x..x.
+        seq hihat .x.x.x.x|.x.x.x.x
+        seq tom ......x.|....x.x.
+        lfo 1bar hihat.decay amt=0.8
+        fx lowpass 1800 0.4
+        fx delay 1/8 fb=0.4 mix=0.3
+        fx limiter 0.9
+    "#;
+
+    let mut cases = Vec::new();
+    cases.extend(render_program("dsl_basic_beat", basic_beat, 4.0));
+    cases.extend(render_program("dsl_filtered_groove", filtered_groove, 4.0));
+    assert_goldens(cases);
+}
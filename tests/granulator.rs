@@ -20,7 +20,9 @@ fn engine_can_trigger_granulator() {
         "granulator",
         Box::new(Granulator::new(sample_rate, test_buffer())),
     );
-    engine.trigger_instrument_with_velocity("granulator", 1.0);
+    engine
+        .trigger_instrument_with_velocity("granulator", 1.0)
+        .unwrap();
 
     let mut max_abs = 0.0_f32;
     for i in 0..44100 {
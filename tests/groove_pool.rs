@@ -0,0 +1,156 @@
+//! Integration tests for groove extraction and the groove pool
+
+use std::ffi::c_void;
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 48_000.0;
+/// One sixteenth at 120 BPM / 48 kHz.
+const STEP_FRAMES: usize = 6000;
+const BLOCK: usize = 500;
+
+/// Render `frames` frames (a multiple of `BLOCK`).
+unsafe fn render(engine: *mut GooeyEngine, frames: usize) {
+    let mut buffer = vec![0.0f32; BLOCK * 2];
+    for _ in 0..frames / BLOCK {
+        gooey_engine_render(engine, buffer.as_mut_ptr(), BLOCK as u32);
+    }
+}
+
+#[derive(Default)]
+struct Hits {
+    frame: usize,
+    hits: Vec<(usize, f32)>,
+}
+
+extern "C" fn record(context: *mut c_void, _channel: u32, velocity: f32, sample_offset: u32) {
+    let hits = unsafe { &mut *(context as *mut Hits) };
+    hits.hits
+        .push((hits.frame + sample_offset as usize, velocity));
+}
+
+#[test]
+fn test_groove_extracted_from_played_hits() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_set_bpm(engine, 120.0);
+        gooey_engine_sequencer_set_instrument_pattern(
+            engine,
+            INSTRUMENT_SNARE,
+            [false; 16].as_ptr(),
+        );
+        gooey_engine_sequencer_start(engine);
+        // Bar 2 by hand: a quarter step late on 2, on time and softer on 4
+        render(engine, (16 + 4) * STEP_FRAMES + STEP_FRAMES / 4);
+        gooey_engine_trigger_instrument_with_velocity(engine, INSTRUMENT_SNARE, 1.0);
+        render(engine, 8 * STEP_FRAMES - STEP_FRAMES / 4);
+        gooey_engine_trigger_instrument_with_velocity(engine, INSTRUMENT_SNARE, 0.5);
+        render(engine, 2 * STEP_FRAMES);
+
+        assert_eq!(
+            gooey_engine_groove_extract_capture(engine, INSTRUMENT_SNARE, 1),
+            0
+        );
+        assert_eq!(gooey_engine_groove_count(engine), 1);
+        let (mut timing, mut velocity) = (0.0, 0.0);
+        let mut step = |step| {
+            assert_eq!(
+                gooey_engine_groove_get_step(engine, 0, step, &mut timing, &mut velocity),
+                GooeyResult::Ok
+            );
+            (timing, velocity)
+        };
+        let (late, accent) = step(4);
+        assert!((late - 0.25).abs() < 0.05, "timing {late}");
+        assert!((accent - 1.0 / 0.75).abs() < 1e-4, "velocity {accent}");
+        let (on_time, soft) = step(12);
+        assert!(on_time.abs() < 0.05, "timing {on_time}");
+        assert!((soft - 0.5 / 0.75).abs() < 1e-4, "velocity {soft}");
+        assert_eq!(step(0), (0.0, 1.0));
+
+        // No hits on the kick, and nothing past the pool or the bar
+        assert_eq!(
+            gooey_engine_groove_extract_capture(engine, INSTRUMENT_KICK, 1),
+            -1
+        );
+        assert_eq!(
+            gooey_engine_groove_get_step(engine, 1, 0, &mut timing, &mut velocity),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_groove_get_step(engine, 0, GROOVE_STEPS, &mut timing, &mut velocity),
+            GooeyResult::InvalidValue
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn test_groove_applied_to_another_pattern() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_set_bpm(engine, 120.0);
+        let mut pattern = [false; 16];
+        pattern[0] = true;
+        pattern[2] = true;
+        gooey_engine_sequencer_set_instrument_pattern(engine, INSTRUMENT_KICK, pattern.as_ptr());
+
+        // An imported clip: eighths, the off-beats a fifth of a step late
+        // and at half the velocity of the downbeats
+        let beats = [0.0, 0.55, 1.0, 1.55];
+        let velocities = [1.0, 0.5, 1.0, 0.5];
+        let groove = gooey_engine_groove_extract_hits(
+            engine,
+            beats.as_ptr(),
+            velocities.as_ptr(),
+            beats.len() as u32,
+        );
+        assert_eq!(groove, 0);
+        assert_eq!(
+            gooey_engine_set_channel_groove(engine, INSTRUMENT_KICK, groove as u32, 1.0, 0.5),
+            GooeyResult::Ok
+        );
+
+        let mut hits = Hits::default();
+        gooey_engine_set_trigger_callback(
+            engine,
+            &mut hits as *mut Hits as *mut c_void,
+            1 << INSTRUMENT_KICK,
+            Some(record),
+        );
+        gooey_engine_sequencer_start(engine);
+        let mut buffer = vec![0.0f32; BLOCK * 2];
+        for _ in 0..4 * STEP_FRAMES / BLOCK {
+            gooey_engine_render(engine, buffer.as_mut_ptr(), BLOCK as u32);
+            hits.frame += BLOCK;
+        }
+        // Step 2 lands a fifth of a step late, and with the velocity halfway
+        // to the groove's 0.5 / 0.75 it plays at 5/6
+        assert_eq!(hits.hits.len(), 2);
+        assert_eq!(hits.hits[0].0, 0);
+        let late = hits.hits[1].0 as i64 - 2 * STEP_FRAMES as i64;
+        assert!((late - 1200).abs() <= 1, "step 2 at +{late} frames");
+        assert!((hits.hits[1].1 - 5.0 / 6.0).abs() < 1e-4);
+
+        // Back to straight from the next step on
+        assert_eq!(
+            gooey_engine_clear_channel_groove(engine, INSTRUMENT_KICK),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_set_channel_groove(engine, 999, 0, 1.0, 1.0),
+            GooeyResult::InvalidChannel
+        );
+        assert_eq!(
+            gooey_engine_set_channel_groove(engine, INSTRUMENT_KICK, 5, 1.0, 1.0),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_groove_extract_hits(engine, std::ptr::null(), std::ptr::null(), 0),
+            -1
+        );
+        gooey_engine_groove_clear(engine);
+        assert_eq!(gooey_engine_groove_count(engine), 0);
+        gooey_engine_free(engine);
+    }
+}
@@ -0,0 +1,180 @@
+//! Tests for hi-hat articulations (closed / pedal / open) over FFI.
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+/// One 16th note at the default 120 BPM
+const STEP: usize = 5_512;
+
+fn energy(samples: &[f32]) -> f32 {
+    samples.iter().map(|s| s * s).sum()
+}
+
+/// An engine with a short closed decay and a long open decay.
+fn engine() -> *mut GooeyEngine {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        gooey_engine_set_hihat_param(engine, HIHAT_PARAM_DECAY, 0.01);
+        gooey_engine_set_hihat_param(engine, HIHAT_PARAM_OPEN_DECAY, 0.5);
+    }
+    engine
+}
+
+/// Render `frames` frames and return the left channel.
+fn render(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    let mut buf = vec![0.0_f32; frames * 2];
+    unsafe { gooey_engine_render(engine, buf.as_mut_ptr(), frames as u32) };
+    buf.iter().step_by(2).copied().collect()
+}
+
+/// One manual hit with the default articulation set to `articulation`.
+fn hit(articulation: u8) -> Vec<f32> {
+    let engine = engine();
+    unsafe {
+        gooey_engine_set_hihat_param(engine, HIHAT_PARAM_ARTICULATION, articulation as f32);
+        render(engine, 64);
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_HIHAT);
+        let out = render(engine, 16_384);
+        gooey_engine_free(engine);
+        out
+    }
+}
+
+/// Sequence the given (step, articulation) hits and render two bars' worth
+/// of the first steps.
+fn sequenced(hits: &[(u32, u8)]) -> Vec<f32> {
+    let engine = engine();
+    unsafe {
+        for &(step, articulation) in hits {
+            gooey_engine_sequencer_set_instrument_step(engine, INSTRUMENT_HIHAT, step, true);
+            gooey_engine_sequencer_set_instrument_step_articulation(
+                engine,
+                INSTRUMENT_HIHAT,
+                step,
+                articulation,
+            );
+        }
+        gooey_engine_sequencer_start(engine);
+        let out = render(engine, 4 * STEP);
+        gooey_engine_free(engine);
+        out
+    }
+}
+
+#[test]
+fn articulation_params_and_step_storage_round_trip() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        assert_eq!(
+            gooey_engine_get_hihat_param(engine, HIHAT_PARAM_ARTICULATION),
+            HIHAT_ARTICULATION_CLOSED as f32
+        );
+        gooey_engine_set_hihat_param(engine, HIHAT_PARAM_ARTICULATION, 2.0);
+        assert_eq!(
+            gooey_engine_get_hihat_param(engine, HIHAT_PARAM_ARTICULATION),
+            HIHAT_ARTICULATION_OPEN as f32
+        );
+        gooey_engine_set_hihat_param(engine, HIHAT_PARAM_OPEN_DECAY, 0.6);
+        assert_eq!(
+            gooey_engine_get_hihat_param(engine, HIHAT_PARAM_OPEN_DECAY),
+            0.6
+        );
+
+        let get = |step| {
+            gooey_engine_sequencer_get_instrument_step_articulation(engine, INSTRUMENT_HIHAT, step)
+        };
+        assert_eq!(get(3), STEP_ARTICULATION_NONE);
+        gooey_engine_sequencer_set_instrument_step_articulation(
+            engine,
+            INSTRUMENT_HIHAT,
+            3,
+            HIHAT_ARTICULATION_PEDAL,
+        );
+        assert_eq!(get(3), HIHAT_ARTICULATION_PEDAL);
+        // Setting the step's velocity keeps its articulation
+        gooey_engine_sequencer_set_instrument_step_with_velocity(
+            engine,
+            INSTRUMENT_HIHAT,
+            3,
+            true,
+            0.5,
+        );
+        assert_eq!(get(3), HIHAT_ARTICULATION_PEDAL);
+        gooey_engine_sequencer_set_instrument_step_articulation(
+            engine,
+            INSTRUMENT_HIHAT,
+            3,
+            STEP_ARTICULATION_NONE,
+        );
+        assert_eq!(get(3), STEP_ARTICULATION_NONE);
+        gooey_engine_free(engine);
+
+        assert_eq!(
+            gooey_engine_sequencer_get_instrument_step_articulation(
+                std::ptr::null(),
+                INSTRUMENT_HIHAT,
+                0
+            ),
+            STEP_ARTICULATION_NONE
+        );
+    }
+}
+
+#[test]
+fn open_rings_longer_and_pedal_is_softer_than_closed() {
+    let closed = hit(HIHAT_ARTICULATION_CLOSED);
+    let pedal = hit(HIHAT_ARTICULATION_PEDAL);
+    let open = hit(HIHAT_ARTICULATION_OPEN);
+
+    // 100-370 ms after the hit only the open hat is still sounding
+    let tail = 4_410..16_384;
+    assert!(
+        energy(&open[tail.clone()]) > 100.0 * energy(&closed[tail]),
+        "open tail should outlast closed"
+    );
+    assert!(energy(&pedal) < 0.5 * energy(&closed));
+    assert!(energy(&pedal) > 0.0);
+}
+
+#[test]
+fn closed_step_chokes_a_ringing_open_step() {
+    let ringing = sequenced(&[(0, HIHAT_ARTICULATION_OPEN)]);
+    let choked = sequenced(&[(0, HIHAT_ARTICULATION_OPEN), (1, HIHAT_ARTICULATION_CLOSED)]);
+
+    // Both play the open hit identically up to the second step
+    assert_eq!(&ringing[..STEP], &choked[..STEP]);
+
+    // Well after the closed hit, the open hat has stopped ringing
+    let after = 2 * STEP..4 * STEP;
+    let ringing_tail = energy(&ringing[after.clone()]);
+    let choked_tail = energy(&choked[after]);
+    assert!(
+        choked_tail < 0.01 * ringing_tail,
+        "choked {choked_tail} vs ringing {ringing_tail}"
+    );
+
+    // The choke fades rather than cuts: no jump bigger than the open hat's
+    // own sample-to-sample movement
+    let max_step = |s: &[f32]| {
+        s.windows(2)
+            .map(|w| (w[1] - w[0]).abs())
+            .fold(0.0_f32, f32::max)
+    };
+    let around = STEP - 64..STEP + 64;
+    assert!(max_step(&choked[around.clone()]) <= 1.5 * max_step(&ringing[..STEP]));
+}
+
+#[test]
+fn steps_without_articulation_use_the_default() {
+    let engine = engine();
+    unsafe {
+        gooey_engine_set_hihat_param(engine, HIHAT_PARAM_ARTICULATION, 2.0);
+        gooey_engine_sequencer_set_instrument_step(engine, INSTRUMENT_HIHAT, 0, true);
+        gooey_engine_sequencer_start(engine);
+        let default_open = render(engine, 2 * STEP);
+        gooey_engine_free(engine);
+
+        let open = sequenced(&[(0, HIHAT_ARTICULATION_OPEN)]);
+        assert_eq!(default_open, open[..2 * STEP]);
+    }
+}
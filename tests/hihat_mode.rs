@@ -0,0 +1,79 @@
+//! Tests for the hi-hat's 808 engine and its mode switch.
+
+use gooey::ffi::*;
+use gooey::instruments::{HiHat2, HiHat2Config, HiHatMode};
+use gooey::test_utils::{rms, zero_crossings};
+
+const SAMPLE_RATE: f32 = 44_100.0;
+
+/// One full-velocity hit of `config`, switched to `mode` first.
+fn hit(config: HiHat2Config, mode: HiHatMode) -> Vec<f32> {
+    let mut hat = HiHat2::with_config(SAMPLE_RATE, config);
+    hat.set_mode(mode);
+    hat.trigger(0.0);
+    (0..8192)
+        .map(|i| hat.tick(i as f64 / SAMPLE_RATE as f64))
+        .collect()
+}
+
+#[test]
+fn classic_presets_ab_against_their_phase_mod_twins() {
+    assert_eq!(HiHat2Config::classic().mode, HiHatMode::Classic808);
+    assert_eq!(HiHat2Config::short().mode, HiHatMode::PhaseMod);
+    assert_eq!(
+        hit(HiHat2Config::classic(), HiHatMode::PhaseMod),
+        hit(HiHat2Config::short(), HiHatMode::PhaseMod)
+    );
+    assert_eq!(
+        hit(HiHat2Config::classic_loose(), HiHatMode::Classic808),
+        hit(HiHat2Config::loose(), HiHatMode::Classic808)
+    );
+
+    // Different metal, comparable level
+    let phase_mod = hit(HiHat2Config::loose(), HiHatMode::PhaseMod);
+    let metal = hit(HiHat2Config::loose(), HiHatMode::Classic808);
+    assert_ne!(phase_mod, metal);
+    let level_db = 20.0 * (rms(&metal) / rms(&phase_mod)).log10();
+    assert!(level_db.abs() < 6.0, "808 engine {level_db} dB off");
+}
+
+#[test]
+fn pitch_transposes_the_808_bank() {
+    let at = |pitch| {
+        let mut config = HiHat2Config::loose();
+        config.pitch = pitch;
+        hit(config, HiHatMode::Classic808)
+    };
+    let low = zero_crossings(&at(0.3));
+    let high = zero_crossings(&at(0.9));
+    assert!(high > low, "{high} vs {low} crossings");
+}
+
+#[test]
+fn mode_param_round_trips_over_ffi() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        assert_eq!(gooey_engine_hihat_param_count(), HIHAT_PARAM_MODE + 1);
+        assert_eq!(
+            gooey_engine_get_hihat_param(engine, HIHAT_PARAM_MODE),
+            HIHAT_MODE_PHASE_MOD as f32
+        );
+        gooey_engine_set_hihat_param(engine, HIHAT_PARAM_MODE, HIHAT_MODE_808 as f32);
+        assert_eq!(
+            gooey_engine_get_hihat_param(engine, HIHAT_PARAM_MODE),
+            HIHAT_MODE_808 as f32
+        );
+
+        // The channel sounds, and out-of-range modes clamp to the last one
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_HIHAT);
+        let mut buf = vec![0.0_f32; 2048 * 2];
+        gooey_engine_render(engine, buf.as_mut_ptr(), 2048);
+        assert!(buf.iter().any(|s| s.abs() > 1e-3));
+        gooey_engine_set_hihat_param(engine, HIHAT_PARAM_MODE, 5.0);
+        assert_eq!(
+            gooey_engine_get_hihat_param(engine, HIHAT_PARAM_MODE),
+            HIHAT_MODE_808 as f32
+        );
+        gooey_engine_free(engine);
+    }
+}
@@ -0,0 +1,85 @@
+//! Tests for hi-hat velocity routing (level / decay / tone depths) over FFI.
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+
+/// Render one hi-hat hit at `velocity` after `setup`, returning the left channel.
+fn hit(velocity: f32, setup: impl Fn(*mut GooeyEngine)) -> Vec<f32> {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    let mut buf = vec![0.0_f32; 8192 * 2];
+    unsafe {
+        // A longer decay so shortening it is measurable
+        gooey_engine_set_hihat_param(engine, HIHAT_PARAM_DECAY, 0.05);
+        setup(engine);
+        gooey_engine_render(engine, buf.as_mut_ptr(), 64);
+        gooey_engine_trigger_instrument_with_velocity(engine, INSTRUMENT_HIHAT, velocity);
+        gooey_engine_render(engine, buf.as_mut_ptr(), 8192);
+        gooey_engine_free(engine);
+    }
+    buf.iter().step_by(2).copied().collect()
+}
+
+fn energy(samples: &[f32]) -> f32 {
+    samples.iter().map(|s| s * s).sum()
+}
+
+/// Mean absolute sample-to-sample difference relative to level: a crude
+/// brightness measure that ignores overall loudness.
+fn brightness(samples: &[f32]) -> f32 {
+    let diff: f32 = samples.windows(2).map(|w| (w[1] - w[0]).abs()).sum();
+    diff / samples.iter().map(|s| s.abs()).sum::<f32>()
+}
+
+#[test]
+fn routing_depths_round_trip_with_level_following_velocity_by_default() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        assert_eq!(
+            gooey_engine_get_hihat_param(engine, HIHAT_PARAM_VELOCITY_TO_LEVEL),
+            1.0
+        );
+        assert_eq!(
+            gooey_engine_get_hihat_param(engine, HIHAT_PARAM_VELOCITY_TO_DECAY),
+            0.0
+        );
+        gooey_engine_set_hihat_param(engine, HIHAT_PARAM_VELOCITY_TO_TONE, 0.4);
+        let mut frame = [0.0_f32; 2];
+        gooey_engine_render(engine, frame.as_mut_ptr(), 1);
+        assert_eq!(
+            gooey_engine_get_hihat_param(engine, HIHAT_PARAM_VELOCITY_TO_TONE),
+            0.4
+        );
+        gooey_engine_free(engine);
+    }
+
+    // Level depth 0: a soft hit is as loud as a full one.
+    let flat = |engine| unsafe {
+        gooey_engine_set_hihat_param(engine, HIHAT_PARAM_VELOCITY_TO_LEVEL, 0.0);
+    };
+    let ratio = energy(&hit(0.25, flat)) / energy(&hit(1.0, flat));
+    assert!((ratio - 1.0).abs() < 1e-3, "{ratio}");
+    let ratio = energy(&hit(0.25, |_| {})) / energy(&hit(1.0, |_| {}));
+    assert!(ratio < 0.1, "{ratio}");
+}
+
+#[test]
+fn soft_hits_are_shorter_and_darker_when_routed() {
+    let routed = |engine| unsafe {
+        gooey_engine_set_hihat_param(engine, HIHAT_PARAM_VELOCITY_TO_DECAY, 1.0);
+        gooey_engine_set_hihat_param(engine, HIHAT_PARAM_VELOCITY_TO_TONE, 1.0);
+    };
+    let plain = hit(0.3, |_| {});
+    let humanized = hit(0.3, routed);
+
+    // Shorter: less of the hit's energy lands in the tail.
+    let tail_share = |s: &[f32]| energy(&s[2048..]) / energy(s);
+    assert!(tail_share(&humanized) < tail_share(&plain) * 0.5);
+    // Darker
+    assert!(brightness(&humanized) < brightness(&plain) * 0.9);
+
+    // Full-velocity hits are untouched by the routing.
+    let full_plain = hit(1.0, |_| {});
+    let full_routed = hit(1.0, routed);
+    assert_eq!(full_plain, full_routed);
+}
@@ -0,0 +1,220 @@
+//! Tests for instrument groups: tagging and group gain, mute, effect bypass
+//! and humanize over FFI.
+
+use gooey::ffi::*;
+use gooey::test_utils::{peak, render_frames};
+
+const SAMPLE_RATE: f32 = 44_100.0;
+
+/// Peak of one hit on `channel` after `setup`, with the gain ramps settled.
+fn hit_peak(channel: u32, setup: impl Fn(*mut GooeyEngine)) -> f32 {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    setup(engine);
+    unsafe {
+        render_frames(engine, 2048);
+        assert_eq!(
+            gooey_engine_trigger_instrument(engine, channel),
+            GooeyResult::Ok
+        );
+        let level = peak(&render_frames(engine, 8192));
+        gooey_engine_free(engine);
+        level
+    }
+}
+
+#[test]
+fn channels_start_in_their_default_group() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        for channel in [INSTRUMENT_KICK, INSTRUMENT_HIHAT, INSTRUMENT_SHAKER] {
+            assert_eq!(
+                gooey_engine_get_channel_groups(engine, channel),
+                1 << GROUP_PERCUSSION
+            );
+        }
+        assert_eq!(
+            gooey_engine_get_channel_groups(engine, INSTRUMENT_BASS),
+            1 << GROUP_MELODIC
+        );
+        let slot = gooey_engine_create_slot(engine, INSTRUMENT_BASS) as u32;
+        assert_eq!(
+            gooey_engine_get_channel_groups(engine, slot),
+            1 << GROUP_MELODIC
+        );
+
+        // Tags add and remove independently
+        assert_eq!(
+            gooey_engine_set_channel_group(engine, INSTRUMENT_KICK, 3, true),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_set_channel_group(engine, INSTRUMENT_KICK, GROUP_PERCUSSION, false),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_get_channel_groups(engine, INSTRUMENT_KICK),
+            1 << 3
+        );
+        // Tags stay when the instrument is replaced
+        gooey_engine_set_channel_instrument_type(engine, INSTRUMENT_KICK, INSTRUMENT_TOM);
+        assert_eq!(
+            gooey_engine_get_channel_groups(engine, INSTRUMENT_KICK),
+            1 << 3
+        );
+
+        assert_eq!(
+            gooey_engine_set_channel_group(engine, INSTRUMENT_KICK, GROUP_MAX, true),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_set_channel_group(engine, slot + 1, GROUP_MELODIC, true),
+            GooeyResult::InvalidChannel
+        );
+        assert_eq!(gooey_engine_get_channel_groups(engine, slot + 1), 0);
+        assert_eq!(
+            gooey_engine_set_channel_group(std::ptr::null_mut(), 0, 0, true),
+            GooeyResult::NullPointer
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn group_gain_scales_only_its_members() {
+    let unity = hit_peak(INSTRUMENT_KICK, |_| {});
+    assert!(unity > 0.01);
+
+    let half = hit_peak(INSTRUMENT_KICK, |engine| unsafe {
+        assert_eq!(
+            gooey_engine_set_group_gain(engine, GROUP_PERCUSSION, -6.0206),
+            GooeyResult::Ok
+        );
+    });
+    assert!((half / unity - 0.5).abs() < 0.02, "ratio {}", half / unity);
+
+    // A channel in two groups gets both gains
+    let quarter = hit_peak(INSTRUMENT_KICK, |engine| unsafe {
+        gooey_engine_set_group_gain(engine, GROUP_PERCUSSION, -6.0206);
+        gooey_engine_set_channel_group(engine, INSTRUMENT_KICK, 4, true);
+        gooey_engine_set_group_gain(engine, 4, -6.0206);
+    });
+    assert!(
+        (quarter / unity - 0.25).abs() < 0.02,
+        "ratio {}",
+        quarter / unity
+    );
+
+    // The bass isn't percussion
+    let bass = hit_peak(INSTRUMENT_BASS, |_| {});
+    let bass_with_drums_down = hit_peak(INSTRUMENT_BASS, |engine| unsafe {
+        gooey_engine_set_group_gain(engine, GROUP_PERCUSSION, CHANNEL_GAIN_MIN_DB);
+    });
+    assert!(bass > 0.01);
+    assert!((bass_with_drums_down - bass).abs() < 1e-6);
+
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        assert_eq!(gooey_engine_get_group_gain(engine, GROUP_MELODIC), 0.0);
+        gooey_engine_set_group_gain(engine, GROUP_MELODIC, 20.0);
+        assert_eq!(
+            gooey_engine_get_group_gain(engine, GROUP_MELODIC),
+            CHANNEL_GAIN_MAX_DB
+        );
+        gooey_engine_set_group_gain(engine, GROUP_MELODIC, -100.0);
+        assert_eq!(
+            gooey_engine_get_group_gain(engine, GROUP_MELODIC),
+            CHANNEL_GAIN_MIN_DB
+        );
+        assert_eq!(
+            gooey_engine_set_group_gain(engine, GROUP_MELODIC, f32::NAN),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_set_group_gain(engine, GROUP_MAX, 0.0),
+            GooeyResult::InvalidValue
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn group_mute_and_fx_bypass_fan_out_to_members() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        assert!(!gooey_engine_get_group_mute(engine, GROUP_PERCUSSION));
+        assert_eq!(
+            gooey_engine_set_group_mute(engine, GROUP_PERCUSSION, true),
+            GooeyResult::Ok
+        );
+        assert!(gooey_engine_get_instrument_mute(engine, INSTRUMENT_KICK));
+        assert!(gooey_engine_get_instrument_mute(engine, INSTRUMENT_COWBELL));
+        assert!(!gooey_engine_get_instrument_mute(engine, INSTRUMENT_BASS));
+        assert!(gooey_engine_get_group_mute(engine, GROUP_PERCUSSION));
+        assert!(!gooey_engine_get_group_mute(engine, GROUP_MELODIC));
+
+        // One member back on means the group is no longer fully muted
+        gooey_engine_set_instrument_mute(engine, INSTRUMENT_KICK, false);
+        assert!(!gooey_engine_get_group_mute(engine, GROUP_PERCUSSION));
+        // An empty group is never muted
+        gooey_engine_set_group_mute(engine, 6, true);
+        assert!(!gooey_engine_get_group_mute(engine, 6));
+
+        let reverb = 1 << EFFECT_REVERB;
+        assert_eq!(
+            gooey_engine_set_group_fx_bypass(engine, GROUP_MELODIC, reverb),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_get_channel_fx_bypass(engine, INSTRUMENT_BASS),
+            reverb
+        );
+        assert_eq!(
+            gooey_engine_get_channel_fx_bypass(engine, INSTRUMENT_KICK),
+            0
+        );
+        assert_eq!(
+            gooey_engine_set_group_fx_bypass(engine, GROUP_MELODIC, 1 << EFFECT_LIMITER),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_set_group_mute(engine, GROUP_MAX, true),
+            GooeyResult::InvalidValue
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn group_humanize_touches_only_members() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        for channel in [INSTRUMENT_KICK, INSTRUMENT_BASS] {
+            gooey_engine_sequencer_set_instrument_pattern(engine, channel, [true; 16].as_ptr());
+        }
+        assert_eq!(
+            gooey_engine_sequencer_humanize_group_velocities(engine, GROUP_MELODIC, 0.3),
+            GooeyResult::Ok
+        );
+        let velocities = |channel| -> Vec<f32> {
+            (0..16)
+                .map(|step| {
+                    gooey_engine_sequencer_get_instrument_step_velocity(engine, channel, step)
+                })
+                .collect()
+        };
+        let bass = velocities(INSTRUMENT_BASS);
+        assert!(bass.iter().all(|v| (0.7..=1.0).contains(v)));
+        assert!(bass.iter().any(|v| *v < 0.999));
+        assert!(velocities(INSTRUMENT_KICK).iter().all(|v| *v == 1.0));
+
+        assert_eq!(
+            gooey_engine_sequencer_humanize_group_velocities(engine, GROUP_MELODIC, 1.5),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_sequencer_humanize_group_velocities(engine, GROUP_MAX, 0.1),
+            GooeyResult::InvalidValue
+        );
+        gooey_engine_free(engine);
+    }
+}
@@ -0,0 +1,119 @@
+//! Tests for note-off through the generic `Instrument::release` path.
+
+use gooey::engine::{Engine, Instrument};
+use gooey::ffi::*;
+use gooey::instruments::{BassSynth, FmSnap};
+
+const SAMPLE_RATE: f32 = 44_100.0;
+/// 100 ms
+const BLOCK: usize = 4_410;
+
+fn energy(samples: &[f32]) -> f32 {
+    samples.iter().map(|s| s * s).sum()
+}
+
+/// A long bass note through the engine, released after `release_after`
+/// samples if given.
+fn engine_bass_note(release_after: Option<usize>) -> Vec<f32> {
+    let mut bass = BassSynth::new(SAMPLE_RATE);
+    bass.set_amp_decay(1.0);
+    let mut engine = Engine::new(SAMPLE_RATE);
+    engine.add_instrument("bass", Box::new(bass));
+    engine
+        .trigger_instrument_with_velocity("bass", 1.0)
+        .unwrap();
+    (0..10 * BLOCK)
+        .map(|i| {
+            if Some(i) == release_after {
+                engine.release_instrument("bass").unwrap();
+            }
+            engine.tick(i as f64 / SAMPLE_RATE as f64)
+        })
+        .collect()
+}
+
+/// A long bass note through the C API, released after one block if asked.
+fn ffi_bass_note(release: bool) -> Vec<f32> {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    let mut buf = vec![0.0_f32; 10 * BLOCK * 2];
+    unsafe {
+        gooey_engine_set_bass_param(engine, BASS_PARAM_AMP_DECAY, 1.0);
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_BASS);
+        gooey_engine_render(engine, buf.as_mut_ptr(), BLOCK as u32);
+        if release {
+            assert_eq!(
+                gooey_engine_release_instrument(engine, INSTRUMENT_BASS),
+                GooeyResult::Ok
+            );
+        }
+        gooey_engine_render(engine, buf[BLOCK * 2..].as_mut_ptr(), 9 * BLOCK as u32);
+        gooey_engine_free(engine);
+    }
+    buf.iter().step_by(2).copied().collect()
+}
+
+#[test]
+fn engine_release_ends_a_held_note() {
+    let held = engine_bass_note(None);
+    let released = engine_bass_note(Some(BLOCK));
+
+    assert_eq!(&held[..BLOCK], &released[..BLOCK]);
+    let tail = 5 * BLOCK..;
+    let ringing = energy(&held[tail.clone()]);
+    assert!(ringing > 0.1, "held tail {ringing}");
+    assert!(energy(&released[tail]) < 1e-3 * ringing);
+}
+
+#[test]
+fn release_defaults_to_a_no_op_for_one_shots() {
+    let render = |release: bool| {
+        let mut snap = FmSnap::new(SAMPLE_RATE);
+        snap.trigger(0.0);
+        (0..BLOCK)
+            .map(|i| {
+                let time = i as f64 / SAMPLE_RATE as f64;
+                if release && i == 100 {
+                    Instrument::release(&mut snap, time);
+                }
+                snap.tick(time)
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(render(false), render(true));
+}
+
+#[test]
+fn ffi_release_ends_a_held_note() {
+    let held = ffi_bass_note(false);
+    let released = ffi_bass_note(true);
+
+    assert_eq!(&held[..BLOCK], &released[..BLOCK]);
+    let tail = 5 * BLOCK..;
+    let ringing = energy(&held[tail.clone()]);
+    assert!(ringing > 0.1, "held tail {ringing}");
+    assert!(energy(&released[tail]) < 1e-3 * ringing);
+}
+
+#[test]
+fn ffi_release_rejects_bad_targets() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        assert_eq!(
+            gooey_engine_release_instrument(engine, 999),
+            GooeyResult::InvalidInstrument
+        );
+        assert_eq!(
+            gooey_engine_release_channel(engine, 999),
+            GooeyResult::InvalidChannel
+        );
+        assert_eq!(
+            gooey_engine_release_channel(engine, INSTRUMENT_KICK),
+            GooeyResult::Ok
+        );
+        gooey_engine_free(engine);
+        assert_eq!(
+            gooey_engine_release_instrument(std::ptr::null_mut(), INSTRUMENT_BASS),
+            GooeyResult::NullPointer
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! Tests for host-created instrument slots over FFI.
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+
+fn render(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    let mut buf = vec![0.0_f32; frames * 2];
+    unsafe { gooey_engine_render(engine, buf.as_mut_ptr(), frames as u32) };
+    buf
+}
+
+fn peak(samples: &[f32]) -> f32 {
+    samples.iter().fold(0.0, |m, s| m.max(s.abs()))
+}
+
+#[test]
+fn slots_are_created_replaced_and_destroyed() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        let hat = gooey_engine_create_slot(engine, INSTRUMENT_HIHAT);
+        assert_eq!(hat, INSTRUMENT_COUNT as i32);
+        let snap = gooey_engine_create_slot(engine, INSTRUMENT_FM_SNAP) as u32;
+        assert_eq!(snap, INSTRUMENT_COUNT + 1);
+        assert!(gooey_engine_channel_exists(engine, snap));
+        assert_eq!(
+            gooey_engine_get_channel_instrument_type(engine, hat as u32),
+            INSTRUMENT_HIHAT
+        );
+
+        assert_eq!(
+            gooey_engine_set_channel_instrument_type(engine, snap, INSTRUMENT_TOM),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_get_channel_instrument_type(engine, snap),
+            INSTRUMENT_TOM
+        );
+
+        assert_eq!(
+            gooey_engine_destroy_slot(engine, hat as u32),
+            GooeyResult::Ok
+        );
+        assert!(!gooey_engine_channel_exists(engine, hat as u32));
+        assert_eq!(
+            gooey_engine_trigger_instrument(engine, hat as u32),
+            GooeyResult::InvalidInstrument
+        );
+        assert_eq!(
+            gooey_engine_destroy_slot(engine, hat as u32),
+            GooeyResult::InvalidChannel
+        );
+        assert_eq!(
+            gooey_engine_destroy_slot(engine, INSTRUMENT_KICK),
+            GooeyResult::InvalidChannel
+        );
+        // The freed channel is reused first.
+        assert_eq!(gooey_engine_create_slot(engine, INSTRUMENT_KICK), hat);
+
+        assert_eq!(gooey_engine_create_slot(engine, INSTRUMENT_COUNT), -1);
+        while gooey_engine_create_slot(engine, INSTRUMENT_SNARE) >= 0 {}
+        assert!(gooey_engine_channel_exists(engine, CHANNEL_MAX - 1));
+        assert!(!gooey_engine_channel_exists(engine, CHANNEL_MAX));
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn slot_has_its_own_params_mute_and_meter() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        let hat = gooey_engine_create_slot(engine, INSTRUMENT_HIHAT) as u32;
+        assert_eq!(
+            gooey_engine_set_channel_param(engine, hat, HIHAT_PARAM_DECAY, 0.9),
+            GooeyResult::Ok
+        );
+        render(engine, 1);
+        assert_eq!(
+            gooey_engine_get_channel_param(engine, hat, HIHAT_PARAM_DECAY),
+            0.9
+        );
+        assert_ne!(gooey_engine_get_hihat_param(engine, HIHAT_PARAM_DECAY), 0.9);
+
+        gooey_engine_trigger_instrument(engine, hat);
+        assert!(peak(&render(engine, 2048)) > 0.01);
+        let mut peaks = [0.0_f32; CHANNEL_MAX as usize];
+        gooey_engine_get_channel_peaks(engine, peaks.as_mut_ptr(), CHANNEL_MAX);
+        assert!(peaks[hat as usize] > 0.01);
+        assert_eq!(peaks[INSTRUMENT_HIHAT as usize], 0.0);
+
+        gooey_engine_set_instrument_mute(engine, hat, true);
+        render(engine, 4096);
+        gooey_engine_trigger_instrument(engine, hat);
+        assert!(peak(&render(engine, 2048)) < 1e-4);
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn slot_sequencer_runs_in_time_with_the_kit() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        gooey_engine_sequencer_start(engine);
+        render(engine, 1000);
+        // Created mid-playback: joins at the kit's current position.
+        let snap = gooey_engine_create_slot(engine, INSTRUMENT_FM_SNAP) as u32;
+        assert_eq!(
+            gooey_engine_sequencer_get_instrument_step(engine, snap),
+            gooey_engine_sequencer_get_instrument_step(engine, INSTRUMENT_KICK)
+        );
+        for step in 0..16 {
+            gooey_engine_sequencer_set_instrument_step(engine, snap, step, true);
+        }
+        assert!(peak(&render(engine, 22_050)) > 0.01);
+        gooey_engine_free(engine);
+    }
+}
@@ -3,6 +3,7 @@
 
 use gooey::engine::{Engine, Lfo, Modulatable, MusicalDivision};
 use gooey::instruments::{HiHat, HiHat2, KickDrum, SnareDrum, Tom2, TomDrum};
+use gooey::GooeyError;
 
 #[test]
 fn test_kick_drum_modulation() {
@@ -129,7 +130,7 @@ fn test_invalid_parameter_returns_error() {
     // Test that invalid parameter returns an error
     let result = engine.map_lfo_to_parameter(lfo_idx, "kick", "invalid_param", 1.0);
     assert!(
-        result.is_err(),
+        matches!(result, Err(GooeyError::NotModulatable { .. })),
         "Mapping to invalid parameter should return an error"
     );
 }
@@ -148,11 +149,27 @@ fn test_invalid_instrument_returns_error() {
     // Test that invalid instrument returns an error
     let result = engine.map_lfo_to_parameter(lfo_idx, "nonexistent", "frequency", 1.0);
     assert!(
-        result.is_err(),
+        matches!(result, Err(GooeyError::UnknownInstrument(ref name)) if name == "nonexistent"),
         "Mapping to nonexistent instrument should return an error"
     );
 }
 
+#[test]
+fn test_invalid_lfo_index_returns_error() {
+    let mut engine = Engine::new(44100.0);
+    engine.add_instrument("kick", Box::new(KickDrum::new(44100.0)));
+
+    let result = engine.map_lfo_to_parameter(3, "kick", "frequency", 1.0);
+    assert_eq!(
+        result,
+        Err(GooeyError::IndexOutOfRange {
+            what: "LFO",
+            index: 3,
+            count: 0
+        })
+    );
+}
+
 #[test]
 fn test_multiple_lfos_on_same_instrument() {
     let sample_rate = 44100.0;
@@ -0,0 +1,125 @@
+//! Tests for one-shot LFOs retriggered by their routes' trigger sources.
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+const FRAMES: usize = 8192;
+/// One cycle of a thirty-second-note LFO at 120 BPM
+const CYCLE_FRAMES: usize = 2756;
+
+/// An engine with LFO 0 routed to the kick's frequency at thirty-second
+/// timing, after `setup` configures it; `hit` then triggers channels before
+/// the render.
+fn kick(setup: impl Fn(*mut GooeyEngine, u32), hit: &[u32]) -> Vec<f32> {
+    let mut buf = vec![0.0_f32; FRAMES * 2];
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_set_lfo_enabled(engine, 0, true);
+        gooey_engine_set_lfo_timing(engine, 0, LFO_TIMING_THIRTY_SECOND);
+        let route =
+            gooey_engine_add_lfo_route(engine, 0, INSTRUMENT_KICK, KICK_PARAM_FREQUENCY, 0.5);
+        setup(engine, route);
+        for &channel in hit {
+            gooey_engine_trigger_instrument(engine, channel);
+        }
+        gooey_engine_render(engine, buf.as_mut_ptr(), FRAMES as u32);
+        gooey_engine_free(engine);
+    }
+    buf
+}
+
+fn max_diff(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).fold(0.0, |m, (x, y)| m.max((x - y).abs()))
+}
+
+#[test]
+fn one_shot_runs_one_cycle_from_the_trigger() {
+    let free = kick(|_, _| {}, &[INSTRUMENT_KICK]);
+    let one_shot = kick(
+        |engine, _| unsafe { gooey_engine_set_lfo_one_shot(engine, 0, true) },
+        &[INSTRUMENT_KICK],
+    );
+    // Both start the cycle at the hit; the free LFO carries on past it while
+    // the one-shot holds where the cycle ended
+    let cycle = CYCLE_FRAMES * 2;
+    assert_eq!(one_shot[..cycle], free[..cycle]);
+    assert!(max_diff(&one_shot[cycle..], &free[cycle..]) > 1e-3);
+}
+
+#[test]
+fn one_shot_waits_for_its_trigger_source() {
+    let still = kick(
+        |engine, _| unsafe {
+            gooey_engine_set_lfo_amount(engine, 0, 0.0);
+        },
+        &[INSTRUMENT_KICK],
+    );
+    let from_snare = |hit: &[u32]| {
+        kick(
+            |engine, route| unsafe {
+                gooey_engine_set_lfo_one_shot(engine, 0, true);
+                assert!(gooey_engine_set_lfo_route_trigger_source(
+                    engine,
+                    0,
+                    route,
+                    INSTRUMENT_SNARE
+                ));
+            },
+            hit,
+        )
+    };
+    // The kick alone doesn't start the cycle: the route rests at its end
+    let kick_only = from_snare(&[INSTRUMENT_KICK]);
+    assert!(max_diff(&kick_only, &still) < 1e-4);
+    // The snare does, and then the kick is swept (the snare's own sound is
+    // mixed in, so compare against an unswept kick and snare)
+    let unswept = kick(
+        |engine, _| unsafe {
+            gooey_engine_set_lfo_amount(engine, 0, 0.0);
+        },
+        &[INSTRUMENT_KICK, INSTRUMENT_SNARE],
+    );
+    let swept = from_snare(&[INSTRUMENT_KICK, INSTRUMENT_SNARE]);
+    assert!(max_diff(&swept, &unswept) > 1e-3);
+}
+
+#[test]
+fn one_shot_settings_round_trip() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        assert!(!gooey_engine_get_lfo_one_shot(engine, 3));
+        gooey_engine_set_lfo_one_shot(engine, 3, true);
+        assert!(gooey_engine_get_lfo_one_shot(engine, 3));
+        assert!(!gooey_engine_get_lfo_one_shot(engine, 99));
+
+        let route = gooey_engine_add_lfo_route(engine, 3, INSTRUMENT_HIHAT, 0, 1.0);
+        assert_eq!(
+            gooey_engine_get_lfo_route_trigger_source(engine, 3, route),
+            INSTRUMENT_HIHAT
+        );
+        assert!(gooey_engine_set_lfo_route_trigger_source(
+            engine,
+            3,
+            route,
+            INSTRUMENT_KICK
+        ));
+        assert_eq!(
+            gooey_engine_get_lfo_route_trigger_source(engine, 3, route),
+            INSTRUMENT_KICK
+        );
+        assert!(!gooey_engine_set_lfo_route_trigger_source(
+            engine, 3, route, 9999
+        ));
+        assert!(!gooey_engine_set_lfo_route_trigger_source(
+            engine,
+            3,
+            route + 1,
+            INSTRUMENT_KICK
+        ));
+        assert_eq!(
+            gooey_engine_get_lfo_route_trigger_source(engine, 2, route),
+            LFO_INVALID
+        );
+        gooey_engine_free(engine);
+    }
+}
@@ -0,0 +1,121 @@
+//! Tests for per-route LFO phase, polarity and unipolar mode over FFI.
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+
+/// Render a kick hit with LFO 0 routed to its frequency, after `setup`
+/// configures the LFO and the route.
+fn kick(setup: impl Fn(*mut GooeyEngine, u32)) -> Vec<f32> {
+    let mut buf = vec![0.0_f32; 4096 * 2];
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_set_lfo_enabled(engine, 0, true);
+        let route =
+            gooey_engine_add_lfo_route(engine, 0, INSTRUMENT_KICK, KICK_PARAM_FREQUENCY, 0.5);
+        setup(engine, route);
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_KICK);
+        gooey_engine_render(engine, buf.as_mut_ptr(), 4096);
+        gooey_engine_free(engine);
+    }
+    buf
+}
+
+/// A constant LFO output of `value`.
+unsafe fn hold(engine: *mut GooeyEngine, value: f32) {
+    gooey_engine_set_lfo_amount(engine, 0, 0.0);
+    gooey_engine_set_lfo_offset(engine, 0, value);
+}
+
+fn max_diff(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).fold(0.0, |m, (x, y)| m.max((x - y).abs()))
+}
+
+#[test]
+fn inverted_route_moves_the_other_way() {
+    let down = kick(|engine, _| unsafe { hold(engine, -1.0) });
+    let up = kick(|engine, _| unsafe { hold(engine, 1.0) });
+    let inverted = kick(|engine, route| unsafe {
+        hold(engine, 1.0);
+        assert!(gooey_engine_set_lfo_route_inverted(engine, 0, route, true));
+    });
+    assert!(max_diff(&down, &up) > 1e-3);
+    assert_eq!(inverted, down);
+}
+
+#[test]
+fn unipolar_route_maps_the_swing_to_zero_to_one() {
+    let unipolar = |value| {
+        kick(move |engine, route| unsafe {
+            hold(engine, value);
+            assert!(gooey_engine_set_lfo_route_unipolar(engine, 0, route, true));
+        })
+    };
+    let bipolar = |value| kick(move |engine, _| unsafe { hold(engine, value) });
+    // Bottom, centre and top of the swing land where a bipolar route
+    // outputting 0, 0.5 and 1 would
+    assert_eq!(unipolar(-1.0), bipolar(0.0));
+    assert_eq!(unipolar(0.0), bipolar(0.5));
+    assert_eq!(unipolar(1.0), bipolar(1.0));
+}
+
+#[test]
+fn route_phase_shifts_the_lfo() {
+    let plain = kick(|engine, _| unsafe {
+        gooey_engine_set_lfo_timing(engine, 0, LFO_TIMING_SIXTEENTH);
+    });
+    let opposite = kick(|engine, route| unsafe {
+        gooey_engine_set_lfo_timing(engine, 0, LFO_TIMING_SIXTEENTH);
+        assert!(gooey_engine_set_lfo_route_phase(engine, 0, route, 180.0));
+    });
+    let inverted = kick(|engine, route| unsafe {
+        gooey_engine_set_lfo_timing(engine, 0, LFO_TIMING_SIXTEENTH);
+        gooey_engine_set_lfo_route_inverted(engine, 0, route, true);
+    });
+    // Half a cycle out of phase is a sine turned upside down
+    assert!(max_diff(&plain, &opposite) > 1e-3);
+    assert!(max_diff(&opposite, &inverted) < 1e-4);
+}
+
+#[test]
+fn route_settings_round_trip_and_reject_unknown_routes() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        let route = gooey_engine_add_lfo_route(engine, 2, INSTRUMENT_SNARE, 0, 1.0);
+        assert_eq!(gooey_engine_get_lfo_route_phase(engine, 2, route), 0.0);
+        assert!(!gooey_engine_get_lfo_route_inverted(engine, 2, route));
+        assert!(!gooey_engine_get_lfo_route_unipolar(engine, 2, route));
+
+        assert!(gooey_engine_set_lfo_route_phase(engine, 2, route, 450.0));
+        assert_eq!(gooey_engine_get_lfo_route_phase(engine, 2, route), 90.0);
+        assert!(gooey_engine_set_lfo_route_phase(engine, 2, route, -90.0));
+        assert_eq!(gooey_engine_get_lfo_route_phase(engine, 2, route), 270.0);
+        assert!(!gooey_engine_set_lfo_route_phase(
+            engine,
+            2,
+            route,
+            f32::NAN
+        ));
+        gooey_engine_set_lfo_route_inverted(engine, 2, route, true);
+        gooey_engine_set_lfo_route_unipolar(engine, 2, route, true);
+        assert!(gooey_engine_get_lfo_route_inverted(engine, 2, route));
+        assert!(gooey_engine_get_lfo_route_unipolar(engine, 2, route));
+
+        // Routes are addressed per LFO
+        assert!(!gooey_engine_set_lfo_route_inverted(engine, 1, route, true));
+        assert!(!gooey_engine_set_lfo_route_unipolar(
+            engine,
+            2,
+            route + 1,
+            true
+        ));
+        assert_eq!(gooey_engine_get_lfo_route_phase(engine, 9, route), -1.0);
+        assert!(!gooey_engine_set_lfo_route_phase(
+            std::ptr::null_mut(),
+            2,
+            route,
+            0.0
+        ));
+        gooey_engine_free(engine);
+    }
+}
@@ -0,0 +1,102 @@
+//! Tests for LFO routes that modulate other LFOs' rate and amount.
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+
+/// Render a kick hit with LFO 1 routed to its frequency, after `setup`
+/// configures the pool.
+fn kick(setup: impl Fn(*mut GooeyEngine)) -> Vec<f32> {
+    let mut buf = vec![0.0_f32; 4096 * 2];
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_set_lfo_enabled(engine, 1, true);
+        gooey_engine_set_lfo_timing(engine, 1, LFO_TIMING_SIXTEENTH);
+        gooey_engine_add_lfo_route(engine, 1, INSTRUMENT_KICK, KICK_PARAM_FREQUENCY, 0.5);
+        setup(engine);
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_KICK);
+        gooey_engine_render(engine, buf.as_mut_ptr(), 4096);
+        gooey_engine_free(engine);
+    }
+    buf
+}
+
+/// Route LFO 3, held at `value`, into `param` of LFO 1. LFO 3 comes later
+/// in the pool, so this also checks it is evaluated first.
+unsafe fn modulate_lfo_1(engine: *mut GooeyEngine, param: u32, value: f32, depth: f32) {
+    gooey_engine_set_lfo_enabled(engine, 3, true);
+    gooey_engine_set_lfo_amount(engine, 3, 0.0);
+    gooey_engine_set_lfo_offset(engine, 3, value);
+    let route = gooey_engine_add_lfo_route(engine, 3, LFO_TARGET_LFO_BASE + 1, param, depth);
+    assert_ne!(route, LFO_INVALID);
+}
+
+#[test]
+fn rate_route_speeds_up_the_target() {
+    let plain = kick(|_| {});
+    let octave_up = kick(|engine| unsafe {
+        // +1 at depth 0.5 is one octave up
+        modulate_lfo_1(engine, LFO_PARAM_RATE, 1.0, 0.5);
+    });
+    let thirty_second = kick(|engine| unsafe {
+        gooey_engine_set_lfo_timing(engine, 1, LFO_TIMING_THIRTY_SECOND);
+    });
+    assert_ne!(octave_up, plain);
+    assert_eq!(octave_up, thirty_second);
+}
+
+#[test]
+fn amount_route_scales_the_target() {
+    let silenced = kick(|engine| unsafe {
+        modulate_lfo_1(engine, LFO_PARAM_AMOUNT, -1.0, 1.0);
+    });
+    let flat = kick(|engine| unsafe {
+        gooey_engine_set_lfo_amount(engine, 1, 0.0);
+    });
+    assert_eq!(silenced, flat);
+
+    let doubled = kick(|engine| unsafe {
+        modulate_lfo_1(engine, LFO_PARAM_AMOUNT, 0.5, 1.0);
+        modulate_lfo_1(engine, LFO_PARAM_AMOUNT, 0.5, 1.0);
+    });
+    let loud = kick(|engine| unsafe {
+        gooey_engine_set_lfo_amount(engine, 1, 2.0);
+    });
+    // Two routes into the same parameter add up
+    assert_eq!(doubled, loud);
+}
+
+#[test]
+fn lfo_targets_are_validated() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        let target = |lfo: u32| LFO_TARGET_LFO_BASE + lfo;
+        assert_eq!(
+            gooey_engine_add_lfo_route(engine, 2, target(2), LFO_PARAM_RATE, 1.0),
+            LFO_INVALID
+        );
+        assert_eq!(
+            gooey_engine_add_lfo_route(engine, 2, target(LFO_COUNT as u32), LFO_PARAM_RATE, 1.0),
+            LFO_INVALID
+        );
+        assert_eq!(
+            gooey_engine_add_lfo_route(engine, 2, target(0), LFO_PARAM_AMOUNT + 1, 1.0),
+            LFO_INVALID
+        );
+
+        // A feedback loop is allowed and stays finite
+        for (source, dest) in [(0, 1), (1, 0)] {
+            gooey_engine_set_lfo_enabled(engine, source, true);
+            assert_ne!(
+                gooey_engine_add_lfo_route(engine, source, target(dest), LFO_PARAM_RATE, 1.0),
+                LFO_INVALID
+            );
+        }
+        gooey_engine_add_lfo_route(engine, 0, INSTRUMENT_KICK, KICK_PARAM_FREQUENCY, 1.0);
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_KICK);
+        let mut buf = vec![0.0_f32; 4096 * 2];
+        gooey_engine_render(engine, buf.as_mut_ptr(), 4096);
+        assert!(buf.iter().all(|s| s.is_finite()));
+        gooey_engine_free(engine);
+    }
+}
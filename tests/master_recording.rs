@@ -0,0 +1,188 @@
+//! Integration tests for live master-output recording.
+
+use gooey::engine::Engine;
+use gooey::ffi::*;
+use gooey::instruments::KickDrum;
+use gooey::recorder::RecordState;
+
+fn render_frames(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    let mut buf = vec![0.0_f32; frames * 2];
+    unsafe {
+        gooey_engine_render(engine, buf.as_mut_ptr(), frames as u32);
+    }
+    buf
+}
+
+#[test]
+fn ffi_recorder_defaults_idle_and_empty() {
+    let engine = gooey_engine_new(44_100.0);
+    assert!(!engine.is_null());
+
+    render_frames(engine, 256);
+    unsafe {
+        assert_eq!(gooey_engine_record_get_state(engine), RECORD_STATE_IDLE);
+        assert_eq!(gooey_engine_record_get_frame_count(engine), 0);
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn ffi_reserve_rejects_unusable_lengths() {
+    let engine = gooey_engine_new(44_100.0);
+    unsafe {
+        for seconds in [0.0, -5.0, f32::NAN, f32::INFINITY] {
+            assert_eq!(
+                gooey_engine_record_reserve_seconds(engine, seconds),
+                GooeyResult::InvalidValue
+            );
+        }
+        assert_eq!(
+            gooey_engine_record_reserve_seconds(engine, 0.25),
+            GooeyResult::Ok
+        );
+        assert_eq!(gooey_engine_record_start(engine), GooeyResult::Ok);
+        render_frames(engine, 44_100);
+        assert!(gooey_engine_record_overflowed(engine));
+        assert_eq!(gooey_engine_record_get_frame_count(engine), 11_025);
+        assert_eq!(
+            gooey_engine_record_reserve_seconds(std::ptr::null_mut(), 1.0),
+            GooeyResult::NullPointer
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn ffi_recording_matches_rendered_output() {
+    let engine = gooey_engine_new(44_100.0);
+    assert!(!engine.is_null());
+
+    unsafe {
+        assert_eq!(gooey_engine_record_start(engine), GooeyResult::Ok);
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_KICK);
+    }
+    let rendered = render_frames(engine, 1024);
+    unsafe {
+        gooey_engine_record_stop(engine);
+    }
+    // Frames rendered after stop are not captured.
+    render_frames(engine, 512);
+
+    unsafe {
+        assert_eq!(gooey_engine_record_get_frame_count(engine), 1024);
+        let mut captured = vec![0.0_f32; 1024 * 2];
+        let copied = gooey_engine_record_copy_buffer(engine, captured.as_mut_ptr(), 1024);
+        assert_eq!(copied, 1024);
+        assert_eq!(captured, rendered);
+
+        gooey_engine_record_clear(engine);
+        assert_eq!(gooey_engine_record_get_frame_count(engine), 0);
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn ffi_armed_recording_skips_leading_silence() {
+    let engine = gooey_engine_new(44_100.0);
+    assert!(!engine.is_null());
+
+    unsafe {
+        assert_eq!(gooey_engine_record_arm(engine), GooeyResult::Ok);
+    }
+    render_frames(engine, 512);
+    unsafe {
+        assert_eq!(gooey_engine_record_get_state(engine), RECORD_STATE_ARMED);
+        assert_eq!(gooey_engine_record_get_frame_count(engine), 0);
+
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_KICK);
+    }
+    render_frames(engine, 512);
+    unsafe {
+        assert_eq!(
+            gooey_engine_record_get_state(engine),
+            RECORD_STATE_RECORDING
+        );
+        let frames = gooey_engine_record_get_frame_count(engine);
+        assert!(frames > 0 && frames <= 512);
+
+        let mut captured = vec![0.0_f32; 2];
+        gooey_engine_record_copy_buffer(engine, captured.as_mut_ptr(), 1);
+        assert!(captured[0].abs() > 0.0 || captured[1].abs() > 0.0);
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn ffi_bounce_is_not_captured_by_live_take() {
+    let engine = gooey_engine_new(44_100.0);
+    assert!(!engine.is_null());
+
+    unsafe {
+        assert_eq!(gooey_engine_record_start(engine), GooeyResult::Ok);
+        render_frames(engine, 100);
+
+        let mut len = 0_u32;
+        let buffer = gooey_engine_bounce_to_buffer(engine, 1, &mut len);
+        assert!(!buffer.is_null());
+        gooey_engine_free_buffer(buffer, len);
+
+        assert_eq!(gooey_engine_record_get_frame_count(engine), 100);
+        assert_eq!(
+            gooey_engine_record_get_state(engine),
+            RECORD_STATE_RECORDING
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn engine_tick_stereo_feeds_recorder() {
+    let sample_rate = 44_100.0;
+    let mut engine = Engine::new(sample_rate);
+    engine.add_instrument("kick", Box::new(KickDrum::new(sample_rate)));
+    engine.recorder_mut().start().unwrap();
+    engine.trigger_instrument("kick").unwrap();
+
+    let mut time = 0.0_f64;
+    let mut rendered = Vec::new();
+    for _ in 0..256 {
+        let frame = engine.tick_stereo(time);
+        rendered.push(frame.l);
+        rendered.push(frame.r);
+        time += 1.0 / sample_rate as f64;
+    }
+
+    let take = engine.recorder_mut().take();
+    assert_eq!(take, rendered);
+    assert_eq!(engine.recorder().state(), RecordState::Idle);
+}
+
+#[cfg(feature = "bounce")]
+#[test]
+fn ffi_recording_writes_stereo_wav() {
+    let engine = gooey_engine_new(44_100.0);
+    assert!(!engine.is_null());
+
+    let path = std::env::temp_dir().join(format!("gooey_master_take_{}.wav", std::process::id()));
+    let c_path = std::ffi::CString::new(path.to_str().unwrap()).unwrap();
+
+    unsafe {
+        // Nothing captured yet: writing fails.
+        assert!(!gooey_engine_record_write_wav(engine, c_path.as_ptr()));
+
+        assert_eq!(gooey_engine_record_start(engine), GooeyResult::Ok);
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_KICK);
+        render_frames(engine, 2048);
+        gooey_engine_record_stop(engine);
+
+        assert!(gooey_engine_record_write_wav(engine, c_path.as_ptr()));
+        gooey_engine_free(engine);
+    }
+
+    let reader = hound::WavReader::open(&path).unwrap();
+    let spec = reader.spec();
+    assert_eq!(spec.channels, 2);
+    assert_eq!(spec.sample_rate, 44_100);
+    assert_eq!(reader.len(), 2048 * 2);
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,124 @@
+//! Tests for the kit-wide A4 reference and transpose.
+
+use gooey::engine::{Engine, Instrument};
+use gooey::ffi::*;
+use gooey::instruments::{BassSynth, Cowbell};
+use gooey::music::MasterTuning;
+use gooey::test_utils::{peak_frequency, render};
+
+const SAMPLE_RATE: f32 = 44_100.0;
+/// ~370 ms: fine enough bins for a bass fundamental
+const WINDOW: usize = 16384;
+
+fn bass_pitch(tuning: MasterTuning) -> f32 {
+    let mut bass = BassSynth::new(SAMPLE_RATE);
+    bass.set_amp_decay(1.0);
+    bass.set_pitch_ratio(tuning.ratio());
+    let samples = render(&mut bass, SAMPLE_RATE, 0.5);
+    peak_frequency(&samples[..WINDOW], SAMPLE_RATE)
+}
+
+#[test]
+fn reference_and_transpose_move_the_pitch() {
+    let concert = bass_pitch(MasterTuning::default());
+    let octave_up = bass_pitch(MasterTuning::new(440.0, 12.0));
+    let baroque = bass_pitch(MasterTuning::new(415.0, 0.0));
+
+    let ratio = octave_up / concert;
+    assert!((ratio - 2.0).abs() < 0.05, "octave ratio {ratio}");
+    let ratio = baroque / concert;
+    assert!((ratio - 415.0 / 440.0).abs() < 0.02, "415 Hz ratio {ratio}");
+}
+
+#[test]
+fn engine_retunes_current_and_later_instruments() {
+    let render_engine = |engine: &mut Engine, name: &str| -> Vec<f32> {
+        engine.trigger_instrument(name).unwrap();
+        (0..4096)
+            .map(|i| engine.tick(i as f64 / SAMPLE_RATE as f64))
+            .collect()
+    };
+    let tuning = MasterTuning::new(432.0, -3.0);
+
+    let mut untuned = Engine::new(SAMPLE_RATE);
+    untuned.add_instrument("bell", Box::new(Cowbell::new(SAMPLE_RATE)));
+    let untuned = render_engine(&mut untuned, "bell");
+
+    // Added before the change
+    let mut before = Engine::new(SAMPLE_RATE);
+    before.add_instrument("bell", Box::new(Cowbell::new(SAMPLE_RATE)));
+    before.set_master_tuning(tuning);
+    let retuned = render_engine(&mut before, "bell");
+    assert_ne!(retuned, untuned);
+
+    // Added after it
+    let mut after = Engine::new(SAMPLE_RATE);
+    after.set_master_tuning(tuning);
+    after.add_instrument("bell", Box::new(Cowbell::new(SAMPLE_RATE)));
+    assert_eq!(render_engine(&mut after, "bell"), retuned);
+    assert_eq!(after.master_tuning(), tuning);
+}
+
+/// One bass hit and one hi-hat hit through the C API, as (bass, hihat).
+unsafe fn ffi_hits(a4_hz: f32, transpose: f32) -> (Vec<f32>, Vec<f32>) {
+    let mut hits = Vec::new();
+    for instrument in [INSTRUMENT_BASS, INSTRUMENT_HIHAT] {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        assert_eq!(
+            gooey_engine_set_master_tuning(engine, a4_hz, transpose),
+            GooeyResult::Ok
+        );
+        gooey_engine_trigger_instrument(engine, instrument);
+        let mut buf = vec![0.0_f32; 4096 * 2];
+        gooey_engine_render(engine, buf.as_mut_ptr(), 4096);
+        gooey_engine_free(engine);
+        hits.push(buf);
+    }
+    let hihat = hits.pop().unwrap();
+    (hits.pop().unwrap(), hihat)
+}
+
+#[test]
+fn ffi_retunes_pitched_voices_only() {
+    unsafe {
+        let (bass, hihat) = ffi_hits(440.0, 0.0);
+        let (bass_up, hihat_up) = ffi_hits(440.0, 7.0);
+        assert_ne!(bass, bass_up);
+        assert_eq!(hihat, hihat_up);
+    }
+}
+
+#[test]
+fn ffi_master_tuning_validates_and_reads_back() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        assert_eq!(gooey_engine_get_master_a4(engine), 440.0);
+        assert_eq!(gooey_engine_get_master_transpose(engine), 0.0);
+
+        assert_eq!(
+            gooey_engine_set_master_tuning(engine, 432.0, -2.5),
+            GooeyResult::Ok
+        );
+        assert_eq!(gooey_engine_get_master_a4(engine), 432.0);
+        assert_eq!(gooey_engine_get_master_transpose(engine), -2.5);
+
+        // Out of range clamps; non-finite is rejected and changes nothing
+        assert_eq!(
+            gooey_engine_set_master_tuning(engine, 1000.0, 48.0),
+            GooeyResult::Ok
+        );
+        assert_eq!(gooey_engine_get_master_a4(engine), 480.0);
+        assert_eq!(gooey_engine_get_master_transpose(engine), 24.0);
+        assert_eq!(
+            gooey_engine_set_master_tuning(engine, f32::NAN, 0.0),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(gooey_engine_get_master_a4(engine), 480.0);
+        gooey_engine_free(engine);
+
+        assert_eq!(
+            gooey_engine_set_master_tuning(std::ptr::null_mut(), 440.0, 0.0),
+            GooeyResult::NullPointer
+        );
+    }
+}
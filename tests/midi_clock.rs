@@ -0,0 +1,90 @@
+//! Integration tests for MIDI clock output from the engine transport.
+
+use gooey::engine::{
+    AudioEvent, Engine, MidiClockEvent, MidiClockMessage, Sequencer, MIDI_CLOCK_PPQN,
+};
+use gooey::instruments::KickDrum;
+use std::sync::mpsc::Receiver;
+
+const SAMPLE_RATE: f32 = 48_000.0;
+
+fn engine_with_sequencer() -> Engine {
+    let mut engine = Engine::new(SAMPLE_RATE);
+    engine.add_instrument("kick", Box::new(KickDrum::new(SAMPLE_RATE)));
+    engine.add_sequencer(Sequencer::with_pattern(
+        120.0,
+        SAMPLE_RATE,
+        vec![true; 16],
+        "kick",
+    ));
+    engine
+}
+
+fn run(engine: &mut Engine, samples: usize) {
+    for i in 0..samples {
+        engine.tick(i as f64 / SAMPLE_RATE as f64);
+    }
+}
+
+fn drain(rx: &Receiver<MidiClockEvent>) -> Vec<MidiClockEvent> {
+    rx.try_iter().collect()
+}
+
+#[test]
+fn transport_start_sends_start_then_24_clocks_per_beat() {
+    let mut engine = engine_with_sequencer();
+    let rx = engine.enable_midi_clock();
+    run(&mut engine, 100);
+    assert!(drain(&rx).is_empty(), "no clock while stopped");
+
+    engine.send_event(AudioEvent::TransportStart).unwrap();
+    // 120 BPM at 48 kHz: one beat is 24000 samples
+    run(&mut engine, 24_000);
+    let events = drain(&rx);
+    assert_eq!(events[0].message, MidiClockMessage::Start);
+    let clocks: Vec<u64> = events
+        .iter()
+        .filter(|e| e.message == MidiClockMessage::Clock)
+        .map(|e| e.sample_time)
+        .collect();
+    assert_eq!(clocks.len(), MIDI_CLOCK_PPQN as usize);
+    assert_eq!(
+        clocks[0], events[0].sample_time,
+        "first clock is the downbeat"
+    );
+    assert!(
+        clocks.windows(2).all(|w| w[1] - w[0] == 1000),
+        "clocks are evenly spaced"
+    );
+}
+
+#[test]
+fn stop_and_tempo_changes_follow_the_engine() {
+    let mut engine = engine_with_sequencer();
+    let rx = engine.enable_midi_clock();
+    engine.send_event(AudioEvent::TransportStart).unwrap();
+    run(&mut engine, 24_000);
+
+    engine.set_bpm(60.0);
+    drain(&rx);
+    run(&mut engine, 48_000);
+    let clocks = drain(&rx)
+        .iter()
+        .filter(|e| e.message == MidiClockMessage::Clock)
+        .count();
+    assert!(
+        (MIDI_CLOCK_PPQN as usize - 1..=MIDI_CLOCK_PPQN as usize).contains(&clocks),
+        "one beat at 60 BPM is 24 clocks, got {clocks}"
+    );
+
+    engine.send_event(AudioEvent::TransportStop).unwrap();
+    run(&mut engine, 4_000);
+    let events = drain(&rx);
+    assert_eq!(events.last().unwrap().message, MidiClockMessage::Stop);
+
+    engine.send_event(AudioEvent::TransportStart).unwrap();
+    run(&mut engine, 2);
+    let messages: Vec<_> = drain(&rx).iter().map(|e| e.message).collect();
+    assert!(matches!(messages[0], MidiClockMessage::SongPosition(8)));
+    assert_eq!(messages[1], MidiClockMessage::Continue);
+}
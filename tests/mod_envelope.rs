@@ -0,0 +1,141 @@
+// Integration tests for segment envelopes as an engine modulation source
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+use gooey::engine::{Engine, Instrument, ModEnvelope, Modulatable};
+use gooey::instruments::KickDrum;
+use gooey::max_curve::SegmentEnvelope;
+use gooey::GooeyError;
+
+const SAMPLE_RATE: f32 = 1000.0;
+
+/// Silent instrument that publishes the last modulation value it received.
+struct Probe {
+    value: Arc<AtomicU32>,
+}
+
+impl Instrument for Probe {
+    fn trigger_with_velocity(&mut self, _time: f64, _velocity: f32) {}
+
+    fn tick(&mut self, _current_time: f64) -> f32 {
+        0.0
+    }
+
+    fn is_active(&self) -> bool {
+        false
+    }
+
+    fn as_modulatable(&mut self) -> Option<&mut dyn Modulatable> {
+        Some(self)
+    }
+}
+
+impl Modulatable for Probe {
+    fn modulatable_parameters(&self) -> Vec<&'static str> {
+        vec!["pitch"]
+    }
+
+    fn apply_modulation(&mut self, _parameter: &str, value: f32) -> Result<(), GooeyError> {
+        self.value.store(value.to_bits(), Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn parameter_range(&self, _parameter: &str) -> Option<(f32, f32)> {
+        Some((-1.0, 1.0))
+    }
+}
+
+fn probe_engine() -> (Engine, Arc<AtomicU32>) {
+    let value = Arc::new(AtomicU32::new(0));
+    let mut engine = Engine::new(SAMPLE_RATE);
+    engine.add_instrument(
+        "probe",
+        Box::new(Probe {
+            value: Arc::clone(&value),
+        }),
+    );
+    (engine, value)
+}
+
+fn read(value: &AtomicU32) -> f32 {
+    f32::from_bits(value.load(Ordering::Relaxed))
+}
+
+#[test]
+fn test_mod_envelope_restarts_on_trigger() {
+    let (mut engine, value) = probe_engine();
+    // Pitch drop: start high, fall to the bottom of the range over 10ms
+    let mut env = ModEnvelope::new(
+        SegmentEnvelope::default()
+            .with_initial_value(1.0)
+            .segment(0.0, 10.0, 0.0),
+    );
+    env.offset = -1.0;
+    env.amount = 2.0;
+    let idx = engine.add_mod_envelope(env, "probe", "pitch").unwrap();
+    assert!(engine.mod_envelope(idx).is_some());
+
+    // Idle envelope sits at its resting value
+    engine.tick(0.0);
+    assert_eq!(read(&value), -1.0);
+
+    let mut time = 0.001;
+    engine.trigger_instrument("probe").unwrap();
+    engine.tick(time);
+    assert_eq!(read(&value), 1.0, "trigger restarts at the initial value");
+
+    for _ in 0..5 {
+        time += 0.001;
+        engine.tick(time);
+    }
+    assert!((read(&value) - 0.0).abs() < 1e-3, "halfway down after 5ms");
+
+    for _ in 0..10 {
+        time += 0.001;
+        engine.tick(time);
+    }
+    assert_eq!(read(&value), -1.0);
+}
+
+#[test]
+fn test_mod_envelope_loops_until_released() {
+    let (mut engine, value) = probe_engine();
+    let env = ModEnvelope::new(
+        SegmentEnvelope::default()
+            .segment(1.0, 2.0, 0.0)
+            .segment(-1.0, 2.0, 0.0)
+            .segment(0.5, 0.0, 0.0)
+            .with_loop(0, 1),
+    );
+    engine.add_mod_envelope(env, "probe", "pitch").unwrap();
+    engine.trigger_instrument("probe").unwrap();
+
+    let mut peaks = 0;
+    let mut time = 0.0;
+    for _ in 0..40 {
+        engine.tick(time);
+        if read(&value) > 0.9 {
+            peaks += 1;
+        }
+        time += 0.001;
+    }
+    assert!(peaks >= 5, "loop should keep cycling, saw {} peaks", peaks);
+
+    engine.release_mod_envelopes("probe", time);
+    engine.tick(time);
+    assert_eq!(read(&value), 0.5, "release jumps past the loop");
+}
+
+#[test]
+fn test_mod_envelope_rejects_unknown_targets() {
+    let mut engine = Engine::new(SAMPLE_RATE);
+    engine.add_instrument("kick", Box::new(KickDrum::new(SAMPLE_RATE)));
+    let env = || ModEnvelope::new(SegmentEnvelope::dahdsr(0.0, 1.0, 0.0, 10.0, 0.5, 10.0));
+
+    assert!(engine.add_mod_envelope(env(), "kick", "frequency").is_ok());
+    assert!(engine.add_mod_envelope(env(), "kick", "nope").is_err());
+    assert!(engine
+        .add_mod_envelope(env(), "snare", "frequency")
+        .is_err());
+}
@@ -0,0 +1,110 @@
+//! Tests for motion recording of instrument parameters over FFI.
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 48_000.0;
+/// One sixteenth at 120 BPM / 48 kHz.
+const STEP_FRAMES: usize = 6000;
+const BLOCK: usize = 500;
+
+/// Render `frames` frames (a multiple of `BLOCK`).
+unsafe fn render(engine: *mut GooeyEngine, frames: usize) {
+    let mut buffer = vec![0.0f32; BLOCK * 2];
+    for _ in 0..frames / BLOCK {
+        gooey_engine_render(engine, buffer.as_mut_ptr(), BLOCK as u32);
+    }
+}
+
+unsafe fn punch(engine: *mut GooeyEngine) -> f32 {
+    gooey_engine_get_channel_param(engine, INSTRUMENT_KICK, KICK_PARAM_PUNCH)
+}
+
+#[test]
+fn recorded_moves_replay_each_cycle() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_set_bpm(engine, 120.0);
+        gooey_engine_sequencer_start(engine);
+
+        // Not recording: the change is just a change
+        render(engine, STEP_FRAMES);
+        gooey_engine_set_channel_param(engine, INSTRUMENT_KICK, KICK_PARAM_PUNCH, 0.5);
+        assert_eq!(
+            gooey_engine_motion_lane(engine, INSTRUMENT_KICK, KICK_PARAM_PUNCH),
+            -1
+        );
+
+        assert_eq!(
+            gooey_engine_set_motion_recording(engine, true),
+            GooeyResult::Ok
+        );
+        assert!(gooey_engine_get_motion_recording(engine));
+        // Turn the knob on step 2, and again on step 6
+        render(engine, STEP_FRAMES + STEP_FRAMES / 2);
+        gooey_engine_set_channel_param(engine, INSTRUMENT_KICK, KICK_PARAM_PUNCH, 0.8);
+        render(engine, STEP_FRAMES / 2);
+        // The step being recorded keeps the live value
+        assert_eq!(punch(engine), 0.8);
+        render(engine, 3 * STEP_FRAMES + STEP_FRAMES / 2);
+        gooey_engine_set_channel_param(engine, INSTRUMENT_KICK, KICK_PARAM_PUNCH, 0.2);
+        gooey_engine_set_motion_recording(engine, false);
+
+        let lane = gooey_engine_motion_lane(engine, INSTRUMENT_KICK, KICK_PARAM_PUNCH);
+        assert!(lane >= 0);
+        let mut value = 0.0;
+        assert!(gooey_engine_effect_lane_get_step(
+            engine,
+            lane as u32,
+            2,
+            &mut value
+        ));
+        assert_eq!(value, 0.8);
+        assert!(gooey_engine_effect_lane_get_step(
+            engine,
+            lane as u32,
+            6,
+            &mut value
+        ));
+        assert_eq!(value, 0.2);
+        assert!(!gooey_engine_effect_lane_get_step(
+            engine,
+            lane as u32,
+            4,
+            &mut value
+        ));
+
+        // Next cycle: on 2, gliding through 4, on 6
+        render(engine, 11 * STEP_FRAMES + STEP_FRAMES / 2);
+        assert!((punch(engine) - 0.8).abs() < 1e-3, "{}", punch(engine));
+        render(engine, 2 * STEP_FRAMES);
+        assert!((punch(engine) - 0.5).abs() < 1e-3, "{}", punch(engine));
+        render(engine, 2 * STEP_FRAMES);
+        assert!((punch(engine) - 0.2).abs() < 1e-3, "{}", punch(engine));
+
+        // Removing the lane leaves the knob where the motion left it
+        assert_eq!(
+            gooey_engine_effect_lane_remove(engine, lane as u32),
+            GooeyResult::Ok
+        );
+        render(engine, 4 * STEP_FRAMES);
+        assert!((punch(engine) - 0.2).abs() < 1e-3);
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn nothing_is_recorded_while_stopped() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_set_motion_recording(engine, true);
+        gooey_engine_set_channel_param(engine, INSTRUMENT_SNARE, 0, 0.3);
+        render(engine, BLOCK);
+        assert_eq!(gooey_engine_motion_lane(engine, INSTRUMENT_SNARE, 0), -1);
+        assert_eq!(
+            gooey_engine_set_motion_recording(std::ptr::null_mut(), true),
+            GooeyResult::NullPointer
+        );
+        assert!(!gooey_engine_get_motion_recording(std::ptr::null()));
+        gooey_engine_free(engine);
+    }
+}
@@ -217,3 +217,92 @@ fn test_invalid_instrument_id() {
         gooey_engine_free(engine);
     }
 }
+
+unsafe fn render_frames(engine: *mut GooeyEngine, frames: usize) {
+    let mut buffer = vec![0.0f32; 500 * 2];
+    for _ in 0..frames / 500 {
+        gooey_engine_render(engine, buffer.as_mut_ptr(), 500);
+    }
+}
+
+#[test]
+fn test_quantized_mute_lands_on_next_bar() {
+    unsafe {
+        // 120 BPM at 48 kHz: one 4/4 bar is 96000 samples
+        let engine = gooey_engine_new(48000.0);
+        gooey_engine_set_bpm(engine, 120.0);
+        assert_eq!(
+            gooey_engine_set_mute_quantization(engine, CLIP_QUANTIZE_BAR),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_get_mute_quantization(engine),
+            CLIP_QUANTIZE_BAR
+        );
+        gooey_engine_sequencer_start(engine);
+        render_frames(engine, 10_000);
+
+        gooey_engine_set_instrument_mute(engine, INSTRUMENT_KICK, true);
+        gooey_engine_set_instrument_solo(engine, INSTRUMENT_SNARE, true);
+        assert!(gooey_engine_get_instrument_mute_pending(
+            engine,
+            INSTRUMENT_KICK
+        ));
+        assert!(gooey_engine_get_instrument_solo_pending(
+            engine,
+            INSTRUMENT_SNARE
+        ));
+        assert!(!gooey_engine_get_instrument_mute(engine, INSTRUMENT_KICK));
+
+        render_frames(engine, 85_000);
+        assert!(!gooey_engine_get_instrument_mute(engine, INSTRUMENT_KICK));
+        assert!(gooey_engine_get_instrument_mute_pending(
+            engine,
+            INSTRUMENT_KICK
+        ));
+
+        render_frames(engine, 2_000);
+        assert!(gooey_engine_get_instrument_mute(engine, INSTRUMENT_KICK));
+        assert!(gooey_engine_get_instrument_solo(engine, INSTRUMENT_SNARE));
+        assert!(!gooey_engine_get_instrument_mute_pending(
+            engine,
+            INSTRUMENT_KICK
+        ));
+        assert!(!gooey_engine_get_instrument_solo_pending(
+            engine,
+            INSTRUMENT_SNARE
+        ));
+
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn test_quantized_mute_cancelled_and_stopped_transport() {
+    unsafe {
+        let engine = gooey_engine_new(48000.0);
+        gooey_engine_set_mute_quantization(engine, CLIP_QUANTIZE_QUARTER);
+        gooey_engine_sequencer_start(engine);
+        render_frames(engine, 1_000);
+
+        // Toggling back before the boundary leaves nothing pending
+        gooey_engine_set_instrument_mute(engine, INSTRUMENT_HIHAT, true);
+        gooey_engine_set_instrument_mute(engine, INSTRUMENT_HIHAT, false);
+        assert!(!gooey_engine_get_instrument_mute_pending(
+            engine,
+            INSTRUMENT_HIHAT
+        ));
+
+        // With the transport stopped there is no grid to wait for
+        gooey_engine_sequencer_stop(engine);
+        gooey_engine_set_instrument_mute(engine, INSTRUMENT_HIHAT, true);
+        render_frames(engine, 500);
+        assert!(gooey_engine_get_instrument_mute(engine, INSTRUMENT_HIHAT));
+
+        assert_eq!(
+            gooey_engine_set_mute_quantization(engine, 99),
+            GooeyResult::InvalidValue
+        );
+        gooey_engine_free(engine);
+    }
+}
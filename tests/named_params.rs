@@ -0,0 +1,116 @@
+//! Tests for addressing instrument parameters by dotted name.
+
+use std::ffi::{CStr, CString};
+
+use gooey::ffi::*;
+use gooey::param_info::{instrument_name, instrument_params, ParamUnit};
+
+fn name(path: &str) -> CString {
+    CString::new(path).unwrap()
+}
+
+#[test]
+fn every_registry_param_round_trips_by_name() {
+    unsafe {
+        let engine = gooey_engine_new(44100.0);
+        for instrument in 0..INSTRUMENT_COUNT {
+            let prefix = instrument_name(instrument).unwrap();
+            for info in instrument_params(instrument) {
+                let (min, max) = info.setter_range();
+                let mut value = min + (max - min) * 0.25;
+                if info.unit == ParamUnit::Choice {
+                    value = value.round();
+                }
+                let path = name(&format!("{prefix}.{}", info.name()));
+                assert_eq!(
+                    gooey_engine_set_param(engine, path.as_ptr(), value),
+                    GooeyResult::Ok,
+                    "{path:?}"
+                );
+                let got = gooey_engine_get_param(engine, path.as_ptr());
+                assert!(
+                    (got - value).abs() < 1e-4,
+                    "{path:?}: set {value}, got {got}"
+                );
+
+                let (mut resolved_instrument, mut resolved_param) = (u32::MAX, u32::MAX);
+                assert_eq!(
+                    gooey_engine_resolve_param(
+                        path.as_ptr(),
+                        &mut resolved_instrument,
+                        &mut resolved_param
+                    ),
+                    GooeyResult::Ok
+                );
+                assert_eq!(
+                    (resolved_instrument, resolved_param),
+                    (instrument, info.index)
+                );
+            }
+        }
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn names_match_the_index_api() {
+    unsafe {
+        let engine = gooey_engine_new(44100.0);
+        gooey_engine_set_param(engine, name("snare.filterCutoff").as_ptr(), 0.3);
+        assert_eq!(
+            gooey_engine_get_snare_param(engine, SNARE_PARAM_FILTER_CUTOFF),
+            0.3
+        );
+        gooey_engine_set_kick_param(engine, KICK_PARAM_PUNCH, 0.8);
+        assert_eq!(
+            gooey_engine_get_param(engine, name("kick.punch").as_ptr()),
+            0.8
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn bad_names_are_reported() {
+    unsafe {
+        let engine = gooey_engine_new(44100.0);
+        for (path, expected) in [
+            ("snare", GooeyResult::InvalidValue),
+            ("", GooeyResult::InvalidValue),
+            ("snares.decay", GooeyResult::InvalidInstrument),
+            ("snare.filter", GooeyResult::InvalidParam),
+            ("Snare.decay", GooeyResult::InvalidInstrument),
+        ] {
+            assert_eq!(
+                gooey_engine_set_param(engine, name(path).as_ptr(), 0.5),
+                expected,
+                "{path:?}"
+            );
+            assert!(gooey_engine_get_param(engine, name(path).as_ptr()).is_nan());
+        }
+        let message = CStr::from_ptr(gooey_engine_last_error_message());
+        assert!(message.to_str().unwrap().contains("Snare"), "{message:?}");
+
+        // Values are checked like the index setters'
+        let decay = name("snare.decay");
+        assert_eq!(
+            gooey_engine_set_param(engine, decay.as_ptr(), f32::NAN),
+            GooeyResult::InvalidValue
+        );
+
+        assert_eq!(
+            gooey_engine_set_param(engine, std::ptr::null(), 0.5),
+            GooeyResult::NullPointer
+        );
+        assert_eq!(
+            gooey_engine_set_param(std::ptr::null_mut(), decay.as_ptr(), 0.5),
+            GooeyResult::NullPointer
+        );
+        let mut out = 0;
+        assert_eq!(
+            gooey_engine_resolve_param(decay.as_ptr(), &mut out, std::ptr::null_mut()),
+            GooeyResult::NullPointer
+        );
+        gooey_engine_free(engine);
+    }
+}
@@ -0,0 +1,80 @@
+//! Integration tests for triggering instruments at a MIDI note
+
+use gooey::ffi::*;
+
+unsafe fn render(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    let mut buffer = vec![0.0f32; frames * 2];
+    gooey_engine_render(engine, buffer.as_mut_ptr(), frames as u32);
+    buffer
+}
+
+#[test]
+fn test_note_sets_the_bass_frequency() {
+    unsafe {
+        let engine = gooey_engine_new(48000.0);
+        let before = gooey_engine_get_channel_param(engine, INSTRUMENT_BASS, BASS_PARAM_FREQUENCY);
+
+        // A2, 110 Hz, is 0.47 of the bass's 30-200 Hz range
+        assert_eq!(
+            gooey_engine_trigger_instrument_note(engine, INSTRUMENT_BASS, 45, 1.0),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_get_channel_param(engine, INSTRUMENT_BASS, BASS_PARAM_FREQUENCY),
+            before
+        );
+        let out = render(engine, 4800);
+        let frequency =
+            gooey_engine_get_channel_param(engine, INSTRUMENT_BASS, BASS_PARAM_FREQUENCY);
+        assert!((frequency - 80.0 / 170.0).abs() < 1e-3, "{frequency}");
+        assert!(out.iter().any(|s| s.abs() > 0.01));
+
+        // A plain trigger afterwards leaves the note in place
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_BASS);
+        render(engine, 480);
+        assert_eq!(
+            gooey_engine_get_channel_param(engine, INSTRUMENT_BASS, BASS_PARAM_FREQUENCY),
+            frequency
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn test_unpitched_instruments_ignore_the_note() {
+    unsafe {
+        let engine = gooey_engine_new(48000.0);
+        let decay = gooey_engine_get_snare_param(engine, SNARE_PARAM_DECAY);
+        assert_eq!(
+            gooey_engine_trigger_instrument_note(engine, INSTRUMENT_SNARE, 60, 1.0),
+            GooeyResult::Ok
+        );
+        let out = render(engine, 2400);
+        assert!(out.iter().any(|s| s.abs() > 0.01));
+        assert_eq!(
+            gooey_engine_get_snare_param(engine, SNARE_PARAM_DECAY),
+            decay
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn test_bad_note_or_instrument_is_rejected() {
+    unsafe {
+        let engine = gooey_engine_new(48000.0);
+        assert_eq!(
+            gooey_engine_trigger_instrument_note(engine, INSTRUMENT_BASS, 128, 1.0),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_trigger_instrument_note(engine, CHANNEL_MAX, 40, 1.0),
+            GooeyResult::InvalidInstrument
+        );
+        assert_eq!(
+            gooey_engine_trigger_instrument_note(std::ptr::null_mut(), INSTRUMENT_BASS, 40, 1.0),
+            GooeyResult::NullPointer
+        );
+        gooey_engine_free(engine);
+    }
+}
@@ -0,0 +1,56 @@
+//! Integration tests for the OSC control surface.
+#![cfg(feature = "osc")]
+
+use gooey::ffi::*;
+use gooey::osc::{OscArg, OscMessage, OscServer};
+use std::net::UdpSocket;
+use std::time::{Duration, Instant};
+
+const SAMPLE_RATE: f32 = 44_100.0;
+
+/// Render until `done` holds, or give up after a second.
+unsafe fn render_until(engine: *mut GooeyEngine, mut done: impl FnMut() -> bool) -> bool {
+    let mut buffer = vec![0.0_f32; 64 * GOOEY_OUTPUT_CHANNELS as usize];
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while Instant::now() < deadline {
+        gooey_engine_render(engine, buffer.as_mut_ptr(), 64);
+        if done() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(2));
+    }
+    false
+}
+
+#[test]
+fn udp_messages_set_params_and_trigger() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        // Render once so this thread is the audio thread and OSC writes queue
+        render_until(engine, || true);
+
+        let server = OscServer::bind(engine, "127.0.0.1:0").unwrap();
+        let client = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let send = |address: &str, args: Vec<OscArg>| {
+            let bytes = OscMessage::new(address, args).to_bytes();
+            client.send_to(&bytes, server.local_addr()).unwrap();
+        };
+
+        send("/gooey/kick/decay", vec![OscArg::Float(0.25)]);
+        assert!(render_until(engine, || {
+            gooey_engine_get_kick_param(engine, KICK_PARAM_DECAY) == 0.25
+        }));
+
+        // Unknown addresses are skipped without disturbing later messages
+        send("/gooey/kick/nope", vec![OscArg::Float(1.0)]);
+        send("/gooey/kick/trigger", vec![]);
+        let mut peaks = [0.0_f32; 8];
+        assert!(render_until(engine, || {
+            gooey_engine_get_channel_peaks(engine, peaks.as_mut_ptr(), peaks.len() as u32);
+            peaks[INSTRUMENT_KICK as usize] > 0.0
+        }));
+
+        drop(server);
+        gooey_engine_free(engine);
+    }
+}
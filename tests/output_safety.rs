@@ -0,0 +1,115 @@
+//! Tests for the always-on output safety stage over FFI.
+
+use gooey::ffi::*;
+
+const SR: f32 = 44_100.0;
+
+fn render(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    let mut buf = vec![0.0_f32; frames * 2];
+    unsafe { gooey_engine_render(engine, buf.as_mut_ptr(), frames as u32) };
+    buf
+}
+
+/// An engine playing `pcm` (mono) from a routed sampler slot at full gain.
+unsafe fn playing(pcm: &[f32]) -> *mut GooeyEngine {
+    let engine = gooey_engine_new(SR);
+    let rack = gooey_engine_sampler_register(engine) as u32;
+    let source = gooey_engine_sampler_get_source_id(engine, rack);
+    assert!(gooey_engine_mixer_route_source(engine, source, 3));
+    assert!(gooey_engine_sampler_set_slot_buffer(
+        engine,
+        rack,
+        0,
+        pcm.as_ptr(),
+        pcm.len() as u32,
+        1,
+        SR
+    ));
+    gooey_engine_set_master_gain(engine, 2.0);
+    render(engine, 2_048);
+    assert!(gooey_engine_sampler_trigger(engine, rack, 0, 1.0));
+    engine
+}
+
+#[test]
+fn params_validate_and_read_back() {
+    let engine = gooey_engine_new(SR);
+    unsafe {
+        assert_eq!(
+            gooey_engine_get_output_safety_param(engine, OUTPUT_SAFETY_PARAM_CEILING),
+            1.0
+        );
+        for (param, value, expected) in [
+            (OUTPUT_SAFETY_PARAM_CEILING, 0.5, 0.5),
+            (OUTPUT_SAFETY_PARAM_FAULT_LEVEL, 0.1, 1.0),
+            (OUTPUT_SAFETY_PARAM_FAULT_HOLD, 250.0, 250.0),
+        ] {
+            assert_eq!(
+                gooey_engine_set_output_safety_param(engine, param, value),
+                GooeyResult::Ok
+            );
+            assert_eq!(
+                gooey_engine_get_output_safety_param(engine, param),
+                expected
+            );
+        }
+        assert_eq!(
+            gooey_engine_set_output_safety_param(engine, 3, 1.0),
+            GooeyResult::InvalidParam
+        );
+        assert_eq!(
+            gooey_engine_set_output_safety_param(engine, OUTPUT_SAFETY_PARAM_CEILING, f32::NAN),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(gooey_engine_get_output_safety_param(engine, 3), -1.0);
+        assert!(!gooey_engine_get_output_fault(engine));
+        assert!(!gooey_engine_get_output_fault(std::ptr::null()));
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn dc_is_removed_and_the_ceiling_holds() {
+    // A loud square wave riding on a DC offset
+    let pcm: Vec<f32> = (0..SR as usize)
+        .map(|i| if (i / 100) % 2 == 0 { 1.0 } else { -0.2 })
+        .collect();
+    unsafe {
+        let engine = playing(&pcm);
+        assert_eq!(
+            gooey_engine_set_output_safety_param(engine, OUTPUT_SAFETY_PARAM_CEILING, 0.5),
+            GooeyResult::Ok
+        );
+        let out = render(engine, SR as usize * 3 / 4);
+        assert!(out.iter().all(|s| s.abs() <= 0.5));
+        assert!(out.iter().any(|s| s.abs() > 0.3));
+        let settled = &out[out.len() / 2..];
+        let mean = settled.iter().sum::<f32>() / settled.len() as f32;
+        assert!(mean.abs() < 0.02, "mean {mean}");
+        assert!(!gooey_engine_get_output_fault(engine));
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn sustained_overs_trip_the_mute_until_cleared() {
+    let pcm: Vec<f32> = (0..SR as usize)
+        .map(|i| (std::f32::consts::TAU * 110.0 * i as f32 / SR).sin())
+        .collect();
+    unsafe {
+        let engine = playing(&pcm);
+        gooey_engine_set_output_safety_param(engine, OUTPUT_SAFETY_PARAM_FAULT_LEVEL, 1.0);
+        gooey_engine_set_output_safety_param(engine, OUTPUT_SAFETY_PARAM_FAULT_HOLD, 20.0);
+        let out = render(engine, 4_410);
+        assert!(gooey_engine_get_output_fault(engine));
+        assert!(out[out.len() - 64..].iter().all(|s| *s == 0.0));
+
+        // Raising the level and clearing the fault brings the sound back
+        gooey_engine_set_output_safety_param(engine, OUTPUT_SAFETY_PARAM_FAULT_LEVEL, 16.0);
+        gooey_engine_clear_output_fault(engine);
+        let out = render(engine, 4_410);
+        assert!(!gooey_engine_get_output_fault(engine));
+        assert!(out.iter().any(|s| s.abs() > 0.1));
+        gooey_engine_free(engine);
+    }
+}
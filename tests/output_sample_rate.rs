@@ -0,0 +1,69 @@
+//! Integration tests for engines rendering at a different output rate
+//! (`gooey_engine_new_with_output_rate`).
+
+use gooey::ffi::*;
+
+const OUTPUT_RATE: f32 = 48_000.0;
+
+/// Trigger a kick and render `seconds` at the output rate; returns the left
+/// channel.
+unsafe fn render_kick(engine: *mut GooeyEngine, seconds: f32) -> Vec<f32> {
+    gooey_engine_trigger_instrument(engine, INSTRUMENT_KICK);
+    let frames = (seconds * OUTPUT_RATE) as usize;
+    let mut buffer = vec![0.0_f32; frames * GOOEY_OUTPUT_CHANNELS as usize];
+    // Host-sized blocks that don't line up with the converter's
+    for chunk in buffer.chunks_mut(441 * 2) {
+        gooey_engine_render(engine, chunk.as_mut_ptr(), (chunk.len() / 2) as u32);
+    }
+    buffer.iter().step_by(2).copied().collect()
+}
+
+/// Sample index of the `n`th zero crossing after the onset, ignoring the
+/// converter's faint pre-ringing ahead of it.
+fn nth_crossing(samples: &[f32], n: usize) -> usize {
+    let onset = samples.iter().position(|x| x.abs() > 0.01).unwrap();
+    samples
+        .windows(2)
+        .enumerate()
+        .skip(onset)
+        .filter(|(_, w)| (w[0] < 0.0) != (w[1] < 0.0))
+        .nth(n)
+        .unwrap()
+        .0
+}
+
+#[test]
+fn converted_output_keeps_the_pitch_of_a_native_engine() {
+    unsafe {
+        let native = gooey_engine_new(OUTPUT_RATE);
+        let converted = gooey_engine_new_with_output_rate(44_100.0, OUTPUT_RATE);
+        assert_eq!(gooey_engine_get_output_sample_rate(native), OUTPUT_RATE);
+        assert_eq!(gooey_engine_get_output_sample_rate(converted), OUTPUT_RATE);
+
+        // The kick's pitch sweep sets where its crossings fall
+        let expected = nth_crossing(&render_kick(native, 0.3), 20);
+        let actual = render_kick(converted, 0.3);
+        assert!(actual.iter().all(|x| x.is_finite()));
+        // Playing 44.1 kHz audio at 48 kHz unconverted would be ~9% sharp
+        let ratio = nth_crossing(&actual, 20) as f32 / expected as f32;
+        assert!((ratio - 1.0).abs() < 0.01, "{ratio}");
+
+        gooey_engine_free(native);
+        gooey_engine_free(converted);
+    }
+}
+
+#[test]
+fn equal_rates_need_no_conversion_and_bad_rates_are_rejected() {
+    unsafe {
+        let engine = gooey_engine_new_with_output_rate(OUTPUT_RATE, OUTPUT_RATE);
+        assert!(!engine.is_null());
+        assert_eq!(gooey_engine_get_output_sample_rate(engine), OUTPUT_RATE);
+        gooey_engine_free(engine);
+
+        assert!(gooey_engine_new_with_output_rate(0.0, OUTPUT_RATE).is_null());
+        assert!(gooey_engine_new_with_output_rate(44_100.0, f32::NAN).is_null());
+        assert!(gooey_engine_new_with_output_rate(4_000.0, 192_000.0).is_null());
+        assert_eq!(gooey_engine_get_output_sample_rate(std::ptr::null()), 0.0);
+    }
+}
@@ -0,0 +1,104 @@
+//! Integration tests for overload protection (`gooey_engine_set_overload_protection`).
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 48_000.0;
+const BLOCK: usize = 480;
+
+/// Render `seconds` of audio in 10 ms blocks.
+unsafe fn render(engine: *mut GooeyEngine, seconds: f32) {
+    let mut buffer = vec![0.0_f32; BLOCK * GOOEY_OUTPUT_CHANNELS as usize];
+    let blocks = (seconds * SAMPLE_RATE / BLOCK as f32).round() as usize;
+    for _ in 0..blocks {
+        gooey_engine_render(engine, buffer.as_mut_ptr(), BLOCK as u32);
+    }
+}
+
+#[test]
+fn sustained_overload_sheds_in_priority_order_and_recovery_restores() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        // Any measurable load counts as overload at a 0% threshold
+        gooey_engine_set_overload_protection(engine, true, 0.0);
+        render(engine, 0.5);
+        assert_eq!(gooey_engine_get_degradation(engine), 0);
+
+        render(engine, 0.7);
+        assert_eq!(
+            gooey_engine_get_degradation(engine),
+            1 << OVERLOAD_STAGE_OVERSAMPLING
+        );
+        render(engine, 1.0);
+        assert_eq!(
+            gooey_engine_get_degradation(engine),
+            1 << OVERLOAD_STAGE_OVERSAMPLING | 1 << OVERLOAD_STAGE_VOICES
+        );
+
+        // Recovery brings back the most recently shed stage first
+        gooey_engine_set_overload_protection(engine, true, 1000.0);
+        render(engine, 3.1);
+        assert_eq!(
+            gooey_engine_get_degradation(engine),
+            1 << OVERLOAD_STAGE_OVERSAMPLING
+        );
+        render(engine, 3.0);
+        assert_eq!(gooey_engine_get_degradation(engine), 0);
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn only_listed_stages_are_shed_and_disabling_restores_them() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        let stages = [OVERLOAD_STAGE_REVERB];
+        assert_eq!(
+            gooey_engine_set_overload_priority(engine, stages.as_ptr(), 1),
+            GooeyResult::Ok
+        );
+        gooey_engine_set_overload_protection(engine, true, 0.0);
+        render(engine, 3.5);
+        assert_eq!(
+            gooey_engine_get_degradation(engine),
+            1 << OVERLOAD_STAGE_REVERB
+        );
+
+        gooey_engine_set_overload_protection(engine, false, 0.0);
+        render(engine, 0.01);
+        assert_eq!(gooey_engine_get_degradation(engine), 0);
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn invalid_priorities_are_rejected() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        let repeated = [OVERLOAD_STAGE_VOICES, OVERLOAD_STAGE_VOICES];
+        let unknown = [OVERLOAD_STAGE_COUNT];
+        let too_many = [0, 1, 2, 0];
+        assert_eq!(
+            gooey_engine_set_overload_priority(engine, repeated.as_ptr(), 2),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_set_overload_priority(engine, unknown.as_ptr(), 1),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_set_overload_priority(engine, too_many.as_ptr(), 4),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_set_overload_priority(engine, std::ptr::null(), 1),
+            GooeyResult::NullPointer
+        );
+        // An empty list turns shedding off without disabling protection
+        assert_eq!(
+            gooey_engine_set_overload_priority(engine, std::ptr::null(), 0),
+            GooeyResult::Ok
+        );
+        assert_eq!(gooey_engine_get_degradation(std::ptr::null()), 0);
+        gooey_engine_free(engine);
+    }
+}
@@ -0,0 +1,123 @@
+//! Tests for the engine panic over FFI.
+
+use gooey::ffi::*;
+
+const SR: f32 = 44_100.0;
+
+fn render(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    let mut buf = vec![0.0_f32; frames * 2];
+    unsafe { gooey_engine_render(engine, buf.as_mut_ptr(), frames as u32) };
+    buf
+}
+
+fn peak(buf: &[f32]) -> f32 {
+    buf.iter().fold(0.0_f32, |acc, s| acc.max(s.abs()))
+}
+
+/// An engine with a routed sampler rack holding `seconds` of a 220 Hz tone.
+unsafe fn with_tone(seconds: f32) -> (*mut GooeyEngine, u32) {
+    let engine = gooey_engine_new(SR);
+    let rack = gooey_engine_sampler_register(engine) as u32;
+    let source = gooey_engine_sampler_get_source_id(engine, rack);
+    assert!(gooey_engine_mixer_route_source(engine, source, 3));
+    let pcm: Vec<f32> = (0..(seconds * SR) as usize)
+        .map(|i| (core::f32::consts::TAU * 220.0 * i as f32 / SR).sin() * 0.5)
+        .collect();
+    assert!(gooey_engine_sampler_set_slot_buffer(
+        engine,
+        rack,
+        0,
+        pcm.as_ptr(),
+        pcm.len() as u32,
+        1,
+        SR
+    ));
+    (engine, rack)
+}
+
+#[test]
+fn null_engine_is_rejected() {
+    assert_eq!(
+        unsafe { gooey_engine_panic(std::ptr::null_mut()) },
+        GooeyResult::NullPointer
+    );
+}
+
+#[test]
+fn panic_fades_out_sounding_samples_and_the_next_hit_plays() {
+    unsafe {
+        let (engine, rack) = with_tone(2.0);
+        assert!(gooey_engine_sampler_trigger(engine, rack, 0, 1.0));
+        let before = peak(&render(engine, 2_048));
+        assert!(before > 0.1, "tone peak {before}");
+
+        assert_eq!(gooey_engine_panic(engine), GooeyResult::Ok);
+        let fade = render(engine, 512);
+        // No click: the fade never jumps by more than the tone itself can
+        let steps = fade.chunks_exact(2).collect::<Vec<_>>();
+        let max_step = steps
+            .windows(2)
+            .map(|w| (w[1][0] - w[0][0]).abs())
+            .fold(0.0_f32, f32::max);
+        assert!(max_step < 0.05, "step {max_step}");
+
+        let after = peak(&render(engine, 4_096));
+        assert!(after < 1e-3, "still sounding: {after}");
+
+        assert!(gooey_engine_sampler_trigger(engine, rack, 0, 1.0));
+        let again = peak(&render(engine, 2_048));
+        assert!(again > 0.1, "retrigger peak {again}");
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn panic_clears_effect_tails() {
+    unsafe {
+        let (engine, rack) = with_tone(0.05);
+        assert_eq!(
+            gooey_engine_set_global_effect_enabled(engine, EFFECT_DELAY, true),
+            GooeyResult::Ok
+        );
+        for (param, value) in [(DELAY_PARAM_FEEDBACK, 0.7), (DELAY_PARAM_MIX, 0.5)] {
+            assert_eq!(
+                gooey_engine_set_global_effect_param(engine, EFFECT_DELAY, param, value),
+                GooeyResult::Ok
+            );
+        }
+        assert!(gooey_engine_sampler_trigger(engine, rack, 0, 1.0));
+        render(engine, 4_096);
+        // Without a panic the delay keeps ringing after the sample ends
+        let tail = peak(&render(engine, SR as usize));
+        assert!(tail > 0.01, "no delay tail to clear: {tail}");
+
+        assert_eq!(gooey_engine_panic(engine), GooeyResult::Ok);
+        render(engine, 512);
+        let after = peak(&render(engine, SR as usize));
+        assert!(after < 1e-3, "tail survived: {after}");
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn pending_manual_triggers_are_dropped() {
+    unsafe {
+        let engine = gooey_engine_new(SR);
+        for instrument in [INSTRUMENT_KICK, INSTRUMENT_SNARE, INSTRUMENT_HIHAT] {
+            assert_eq!(
+                gooey_engine_trigger_instrument(engine, instrument),
+                GooeyResult::Ok
+            );
+        }
+        assert_eq!(gooey_engine_panic(engine), GooeyResult::Ok);
+        let out = peak(&render(engine, 4_096));
+        assert!(out < 1e-3, "dropped triggers played: {out}");
+
+        assert_eq!(
+            gooey_engine_trigger_instrument(engine, INSTRUMENT_SNARE),
+            GooeyResult::Ok
+        );
+        assert!(peak(&render(engine, 2_048)) > 0.01);
+        gooey_engine_free(engine);
+    }
+}
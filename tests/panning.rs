@@ -26,7 +26,7 @@ fn render_kick_energy(pan: f32) -> (f64, f64) {
         t += step;
     }
 
-    engine.trigger_instrument("kick");
+    engine.trigger_instrument("kick").unwrap();
 
     let mut left = 0.0_f64;
     let mut right = 0.0_f64;
@@ -100,6 +100,31 @@ fn tom_param_round_trip() {
         approx_eq(gooey_engine_get_tom_param(engine, TOM_PARAM_DECAY), 0.9);
         approx_eq(gooey_engine_get_tom_param(engine, TOM_PARAM_TUNING), 0.7);
 
+        // The A/B controls: the triangle toggle reads back as 0 or 1
+        approx_eq(
+            gooey_engine_get_tom_param(engine, TOM_PARAM_TRIANGLE_ENABLE),
+            1.0,
+        );
+        gooey_engine_set_tom_param(engine, TOM_PARAM_TRIANGLE_ENABLE, 0.0);
+        gooey_engine_set_tom_param(engine, TOM_PARAM_MORPH_LEVEL, 0.25);
+        gooey_engine_set_tom_param(engine, TOM_PARAM_MEMBRANE_GAIN, 0.8);
+        approx_eq(
+            gooey_engine_get_tom_param(engine, TOM_PARAM_TRIANGLE_ENABLE),
+            0.0,
+        );
+        approx_eq(
+            gooey_engine_get_tom_param(engine, TOM_PARAM_TRIANGLE_LEVEL),
+            0.5,
+        );
+        approx_eq(
+            gooey_engine_get_tom_param(engine, TOM_PARAM_MORPH_LEVEL),
+            0.25,
+        );
+        approx_eq(
+            gooey_engine_get_tom_param(engine, TOM_PARAM_MEMBRANE_GAIN),
+            0.8,
+        );
+
         assert!(gooey_engine_get_tom_param(engine, 999).is_nan());
 
         gooey_engine_free(engine);
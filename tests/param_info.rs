@@ -0,0 +1,143 @@
+//! Tests for the parameter metadata registry exposed over FFI.
+
+use gooey::ffi::*;
+use std::ffi::CStr;
+use std::mem::MaybeUninit;
+
+fn info(instrument: u32, param: u32) -> Option<GooeyParamInfo> {
+    let mut out = MaybeUninit::<GooeyParamInfo>::uninit();
+    unsafe {
+        if gooey_engine_get_param_info(instrument, param, out.as_mut_ptr()) {
+            Some(out.assume_init())
+        } else {
+            None
+        }
+    }
+}
+
+fn name_of(info: &GooeyParamInfo) -> &'static str {
+    unsafe { CStr::from_ptr(info.name) }.to_str().unwrap()
+}
+
+#[test]
+fn param_counts_match_ffi_constants() {
+    assert_eq!(
+        gooey_engine_get_param_count(INSTRUMENT_KICK),
+        KICK_PARAM_TUNING + 1
+    );
+    assert_eq!(
+        gooey_engine_get_param_count(INSTRUMENT_SNARE),
+        SNARE_PARAM_CRACK_VELVET + 1
+    );
+    assert_eq!(
+        gooey_engine_get_param_count(INSTRUMENT_HIHAT),
+        HIHAT_PARAM_MODE + 1
+    );
+    assert_eq!(
+        gooey_engine_get_param_count(INSTRUMENT_TOM),
+        TOM_PARAM_MEMBRANE_GAIN + 1
+    );
+    assert_eq!(
+        gooey_engine_get_param_count(INSTRUMENT_BASS),
+        BASS_PARAM_TUNING + 1
+    );
+    assert_eq!(
+        gooey_engine_get_param_count(INSTRUMENT_FM_SNAP),
+        FM_SNAP_PARAM_TUNING + 1
+    );
+    assert_eq!(
+        gooey_engine_get_param_count(INSTRUMENT_RIMSHOT),
+        RIMSHOT_PARAM_TUNING + 1
+    );
+    assert_eq!(
+        gooey_engine_get_param_count(INSTRUMENT_COWBELL),
+        COWBELL_PARAM_TUNING + 1
+    );
+    assert_eq!(
+        gooey_engine_get_param_count(INSTRUMENT_SHAKER),
+        SHAKER_PARAM_TUNING + 1
+    );
+    assert_eq!(gooey_engine_get_param_count(INSTRUMENT_COUNT), 0);
+}
+
+#[test]
+fn param_info_describes_units_and_ranges() {
+    let freq = info(INSTRUMENT_KICK, KICK_PARAM_FREQUENCY).unwrap();
+    assert_eq!(name_of(&freq), "frequency");
+    assert_eq!(freq.unit, PARAM_UNIT_HZ);
+    assert_eq!((freq.min, freq.max), (30.0, 120.0));
+    assert!(freq.modulatable);
+
+    let pitch_env = info(INSTRUMENT_KICK, KICK_PARAM_PITCH_ENVELOPE).unwrap();
+    assert!(!pitch_env.modulatable);
+
+    let filter_type = info(INSTRUMENT_SNARE, SNARE_PARAM_FILTER_TYPE).unwrap();
+    assert_eq!(filter_type.unit, PARAM_UNIT_CHOICE);
+    assert_eq!(filter_type.max, 3.0);
+
+    let tuning = info(INSTRUMENT_BASS, BASS_PARAM_TUNING).unwrap();
+    assert_eq!(tuning.unit, PARAM_UNIT_SEMITONES);
+    assert_eq!(tuning.default_value, 0.5);
+}
+
+#[test]
+fn param_info_rejects_unknown_indices() {
+    assert!(info(INSTRUMENT_KICK, 999).is_none());
+    assert!(info(INSTRUMENT_COUNT, 0).is_none());
+    assert!(!unsafe { gooey_engine_get_param_info(INSTRUMENT_KICK, 0, std::ptr::null_mut()) });
+}
+
+#[test]
+fn param_defaults_match_fresh_engine() {
+    type Getter = unsafe extern "C" fn(*const GooeyEngine, u32) -> f32;
+    let getters: [(u32, Getter); 4] = [
+        (INSTRUMENT_KICK, gooey_engine_get_kick_param),
+        (INSTRUMENT_SNARE, gooey_engine_get_snare_param),
+        (INSTRUMENT_HIHAT, gooey_engine_get_hihat_param),
+        (INSTRUMENT_TOM, gooey_engine_get_tom_param),
+    ];
+
+    let engine = gooey_engine_new(44_100.0);
+    for (instrument, get) in getters {
+        for param in 0..gooey_engine_get_param_count(instrument) {
+            let meta = info(instrument, param).unwrap();
+            let actual = unsafe { get(engine, param) };
+            assert!(
+                (actual - meta.default_value).abs() < 1e-6,
+                "instrument {instrument} {}: registry default {} != engine {}",
+                name_of(&meta),
+                meta.default_value,
+                actual
+            );
+        }
+    }
+    unsafe { gooey_engine_free(engine) };
+}
+
+#[test]
+fn registry_json_lists_every_instrument() {
+    let json = unsafe { CStr::from_ptr(gooey_engine_get_param_registry_json()) }
+        .to_str()
+        .unwrap();
+    for name in ["kick", "snare", "hihat", "tom", "bass"] {
+        assert!(
+            json.contains(&format!("\"name\":\"{name}\"")),
+            "{name} missing"
+        );
+    }
+}
+
+#[test]
+fn typescript_definitions_are_checked_in() {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("include/gooey.d.ts");
+    let generated = gooey::param_info::typescript_definitions();
+    if cfg!(feature = "regenerate-goldens") {
+        std::fs::write(&path, &generated).unwrap();
+        return;
+    }
+    let checked_in = std::fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        checked_in == generated,
+        "include/gooey.d.ts is stale (run with --features regenerate-goldens)"
+    );
+}
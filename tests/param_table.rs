@@ -0,0 +1,67 @@
+//! Integration tests for the shared parameter table (`gooey_engine_param_table_ptr`).
+
+use std::thread;
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+const BLOCK: usize = 128;
+
+unsafe fn render(engine: *mut GooeyEngine) {
+    let mut buffer = vec![0.0_f32; BLOCK * GOOEY_OUTPUT_CHANNELS as usize];
+    gooey_engine_render(engine, buffer.as_mut_ptr(), BLOCK as u32);
+}
+
+#[test]
+fn slots_are_laid_out_by_stride() {
+    assert_eq!(
+        gooey_engine_param_table_slot(INSTRUMENT_SNARE, SNARE_PARAM_DECAY),
+        (INSTRUMENT_SNARE * PARAM_TABLE_STRIDE + SNARE_PARAM_DECAY) as i32
+    );
+    assert_eq!(gooey_engine_param_table_slot(INSTRUMENT_COUNT, 0), -1);
+    assert_eq!(gooey_engine_param_table_slot(INSTRUMENT_KICK, 31), -1);
+}
+
+#[test]
+fn table_writes_apply_on_the_next_render_while_enabled() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        assert!(!gooey_engine_get_param_table_enabled(engine));
+        let table = gooey_engine_param_table_ptr(engine);
+        assert!(!table.is_null());
+        let slot = gooey_engine_param_table_slot(INSTRUMENT_KICK, KICK_PARAM_PUNCH) as usize;
+        let before = gooey_engine_get_kick_param(engine, KICK_PARAM_PUNCH);
+        assert!(table.add(slot).read().is_nan(), "slots start undriven");
+
+        // Written from another thread, as a UI would
+        let address = table as usize;
+        thread::spawn(move || (address as *mut f32).add(slot).write_volatile(0.9))
+            .join()
+            .unwrap();
+        render(engine);
+        assert_eq!(
+            gooey_engine_get_kick_param(engine, KICK_PARAM_PUNCH),
+            before
+        );
+
+        gooey_engine_set_param_table_enabled(engine, true);
+        render(engine);
+        assert_eq!(gooey_engine_get_kick_param(engine, KICK_PARAM_PUNCH), 0.9);
+
+        // Unchanged slots don't fight the regular setters
+        gooey_engine_set_kick_param(engine, KICK_PARAM_PUNCH, 0.2);
+        render(engine);
+        assert_eq!(gooey_engine_get_kick_param(engine, KICK_PARAM_PUNCH), 0.2);
+
+        // Out-of-range writes are clamped; NaN stops driving the slot
+        table.add(slot).write_volatile(5.0);
+        render(engine);
+        assert_eq!(gooey_engine_get_kick_param(engine, KICK_PARAM_PUNCH), 1.0);
+        table.add(slot).write_volatile(f32::NAN);
+        render(engine);
+        assert_eq!(gooey_engine_get_kick_param(engine, KICK_PARAM_PUNCH), 1.0);
+
+        gooey_engine_free(engine);
+        assert!(gooey_engine_param_table_ptr(std::ptr::null_mut()).is_null());
+    }
+}
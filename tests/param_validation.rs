@@ -0,0 +1,137 @@
+//! Tests for FFI value validation: clamping, warnings, and non-finite rejection.
+
+use gooey::ffi::*;
+use std::ffi::CStr;
+
+fn warnings() -> Vec<String> {
+    (0..gooey_engine_warning_count())
+        .map(|i| {
+            let ptr = gooey_engine_get_warning(i);
+            unsafe { CStr::from_ptr(ptr) }
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect()
+}
+
+#[test]
+fn out_of_range_value_is_clamped_with_warning() {
+    let engine = gooey_engine_new(44_100.0);
+    gooey_engine_clear_warnings();
+    unsafe {
+        assert_eq!(
+            gooey_engine_set_kick_param(engine, KICK_PARAM_FREQUENCY, 3.0),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_get_kick_param(engine, KICK_PARAM_FREQUENCY),
+            1.0
+        );
+    }
+    let warnings = warnings();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("frequency"), "{}", warnings[0]);
+
+    gooey_engine_clear_warnings();
+    assert_eq!(gooey_engine_warning_count(), 0);
+    assert!(gooey_engine_get_warning(0).is_null());
+    unsafe { gooey_engine_free(engine) };
+}
+
+#[test]
+fn in_range_value_records_no_warning() {
+    let engine = gooey_engine_new(44_100.0);
+    gooey_engine_clear_warnings();
+    unsafe {
+        assert_eq!(
+            gooey_engine_set_channel_param(engine, 0, KICK_PARAM_VOLUME, 0.25),
+            GooeyResult::Ok
+        );
+        gooey_engine_free(engine);
+    }
+    assert_eq!(gooey_engine_warning_count(), 0);
+}
+
+#[test]
+fn non_finite_values_are_rejected() {
+    let engine = gooey_engine_new(44_100.0);
+    unsafe {
+        let before = gooey_engine_get_snare_param(engine, SNARE_PARAM_DECAY);
+        assert_eq!(
+            gooey_engine_set_snare_param(engine, SNARE_PARAM_DECAY, f32::NAN),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_get_snare_param(engine, SNARE_PARAM_DECAY),
+            before
+        );
+        assert_eq!(
+            gooey_engine_set_global_effect_param(engine, EFFECT_LOWPASS_FILTER, 0, f32::INFINITY),
+            GooeyResult::InvalidValue
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn warnings_are_bounded() {
+    let engine = gooey_engine_new(44_100.0);
+    gooey_engine_clear_warnings();
+    unsafe {
+        for _ in 0..MAX_WARNINGS + 5 {
+            gooey_engine_set_hihat_param(engine, HIHAT_PARAM_DECAY, -1.0);
+        }
+        gooey_engine_free(engine);
+    }
+    assert_eq!(gooey_engine_warning_count() as usize, MAX_WARNINGS);
+}
+
+#[test]
+fn json_params_apply_together_or_not_at_all() {
+    let engine = gooey_engine_new(44_100.0);
+    gooey_engine_clear_warnings();
+    unsafe {
+        let set = |json: &CStr| {
+            gooey_engine_set_channel_params_json(engine, INSTRUMENT_KICK, json.as_ptr())
+        };
+        let decay = gooey_engine_get_kick_param(engine, KICK_PARAM_DECAY);
+        assert_eq!(
+            set(c"{\"frequency\": 0.25, \"pitchEnvelope\": 2}"),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_get_kick_param(engine, KICK_PARAM_FREQUENCY),
+            0.25
+        );
+        assert_eq!(
+            gooey_engine_get_kick_param(engine, KICK_PARAM_PITCH_ENVELOPE),
+            1.0
+        );
+        assert_eq!(gooey_engine_get_kick_param(engine, KICK_PARAM_DECAY), decay);
+        assert_eq!(warnings().len(), 1);
+
+        // One bad entry rejects the whole object
+        assert_eq!(
+            set(c"{\"frequency\": 0.75, \"cutoff\": 0.5}"),
+            GooeyResult::InvalidParam
+        );
+        assert_eq!(
+            set(c"{\"frequency\": 0.75, \"punch\": \"hard\"}"),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_get_kick_param(engine, KICK_PARAM_FREQUENCY),
+            0.25
+        );
+
+        assert_eq!(
+            gooey_engine_set_channel_params_json(engine, INSTRUMENT_KICK, std::ptr::null()),
+            GooeyResult::NullPointer
+        );
+        assert_eq!(
+            gooey_engine_set_channel_params_json(engine, CHANNEL_MAX, c"{}".as_ptr()),
+            GooeyResult::InvalidChannel
+        );
+        gooey_engine_free(engine);
+    }
+}
@@ -0,0 +1,97 @@
+//! Integration tests for pattern slots and quantized pattern launch
+
+use gooey::ffi::*;
+
+unsafe fn render_frames(engine: *mut GooeyEngine, frames: usize) {
+    let mut buffer = vec![0.0f32; 500 * 2];
+    for _ in 0..frames / 500 {
+        gooey_engine_render(engine, buffer.as_mut_ptr(), 500);
+    }
+}
+
+unsafe fn store_kick_pattern(engine: *mut GooeyEngine, slot: u32, enabled: bool) {
+    let pattern = [enabled; 16];
+    gooey_engine_sequencer_set_instrument_pattern(engine, INSTRUMENT_KICK, pattern.as_ptr());
+    assert_eq!(gooey_engine_pattern_store(engine, slot), GooeyResult::Ok);
+}
+
+#[test]
+fn test_pattern_launch_waits_for_the_bar() {
+    unsafe {
+        // 120 BPM at 48 kHz: 6000 samples per step, 96000 per bar
+        let engine = gooey_engine_new(48000.0);
+        gooey_engine_set_bpm(engine, 120.0);
+        store_kick_pattern(engine, 0, true);
+        store_kick_pattern(engine, 1, false);
+
+        assert_eq!(
+            gooey_engine_pattern_launch(engine, 0, CLIP_QUANTIZE_IMMEDIATE),
+            GooeyResult::Ok
+        );
+        assert_eq!(gooey_engine_get_active_pattern(engine), 0);
+        assert_eq!(gooey_engine_get_queued_pattern(engine), -1);
+
+        gooey_engine_sequencer_start(engine);
+        render_frames(engine, 10_000);
+        assert_eq!(
+            gooey_engine_pattern_launch(engine, 1, CLIP_QUANTIZE_BAR),
+            GooeyResult::Ok
+        );
+        assert_eq!(gooey_engine_get_queued_pattern(engine), 1);
+        assert_eq!(gooey_engine_get_active_pattern(engine), 0);
+
+        render_frames(engine, 85_000);
+        assert_eq!(gooey_engine_get_queued_pattern(engine), 1);
+        assert!(gooey_engine_sequencer_get_instrument_step_enabled(
+            engine,
+            INSTRUMENT_KICK,
+            3
+        ));
+
+        // The switch happens with the step boundary at sample 96000
+        render_frames(engine, 1_500);
+        assert_eq!(gooey_engine_get_queued_pattern(engine), -1);
+        assert_eq!(gooey_engine_get_active_pattern(engine), 1);
+        assert!(!gooey_engine_sequencer_get_instrument_step_enabled(
+            engine,
+            INSTRUMENT_KICK,
+            3
+        ));
+        assert_eq!(
+            gooey_engine_sequencer_get_instrument_step(engine, INSTRUMENT_KICK),
+            0
+        );
+
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn test_pattern_launch_cancel_and_errors() {
+    unsafe {
+        let engine = gooey_engine_new(48000.0);
+        store_kick_pattern(engine, 2, true);
+        gooey_engine_sequencer_start(engine);
+        render_frames(engine, 1_000);
+
+        gooey_engine_pattern_launch(engine, 2, CLIP_QUANTIZE_BAR);
+        assert_eq!(gooey_engine_get_queued_pattern(engine), 2);
+        gooey_engine_pattern_cancel(engine);
+        assert_eq!(gooey_engine_get_queued_pattern(engine), -1);
+        assert_eq!(gooey_engine_get_active_pattern(engine), -1);
+
+        assert_eq!(
+            gooey_engine_pattern_launch(engine, 3, CLIP_QUANTIZE_BAR),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_pattern_launch(engine, 2, 42),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_pattern_store(engine, PATTERN_SLOT_COUNT),
+            GooeyResult::InvalidValue
+        );
+        gooey_engine_free(engine);
+    }
+}
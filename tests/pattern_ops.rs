@@ -0,0 +1,150 @@
+//! Tests for the sequencer pattern operations over FFI.
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+
+fn pattern(steps: &[usize]) -> [bool; 16] {
+    let mut pattern = [false; 16];
+    for &step in steps {
+        pattern[step] = true;
+    }
+    pattern
+}
+
+unsafe fn enabled_steps(engine: *mut GooeyEngine, instrument: u32) -> Vec<usize> {
+    (0..16)
+        .filter(|&step| {
+            gooey_engine_sequencer_get_instrument_step_enabled(engine, instrument, step)
+        })
+        .map(|step| step as usize)
+        .collect()
+}
+
+#[test]
+fn copy_and_paste_between_instruments() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_sequencer_set_instrument_pattern(
+            engine,
+            INSTRUMENT_SNARE,
+            pattern(&[4, 12]).as_ptr(),
+        );
+        gooey_engine_sequencer_set_instrument_pattern(
+            engine,
+            INSTRUMENT_KICK,
+            [false; 16].as_ptr(),
+        );
+
+        // Nothing to paste yet
+        assert_eq!(
+            gooey_engine_sequencer_paste_instrument_steps(engine, INSTRUMENT_KICK, 0),
+            -1
+        );
+        assert_eq!(
+            gooey_engine_sequencer_copy_instrument_steps(engine, INSTRUMENT_SNARE, 0, 8),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_sequencer_paste_instrument_steps(engine, INSTRUMENT_KICK, 0),
+            8
+        );
+        // The clipboard stays for another paste, cut short by the pattern end
+        assert_eq!(
+            gooey_engine_sequencer_paste_instrument_steps(engine, INSTRUMENT_KICK, 10),
+            6
+        );
+        assert_eq!(enabled_steps(engine, INSTRUMENT_KICK), [4, 14]);
+
+        assert_eq!(
+            gooey_engine_sequencer_copy_instrument_steps(engine, 999, 0, 8),
+            GooeyResult::InvalidInstrument
+        );
+        assert_eq!(
+            gooey_engine_sequencer_paste_instrument_steps(engine, 999, 0),
+            -1
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn rotate_reverse_and_invert() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        let kick = INSTRUMENT_KICK;
+        gooey_engine_sequencer_set_instrument_pattern(engine, kick, pattern(&[0, 3]).as_ptr());
+
+        assert_eq!(
+            gooey_engine_sequencer_rotate_instrument_pattern(engine, kick, 2),
+            GooeyResult::Ok
+        );
+        assert_eq!(enabled_steps(engine, kick), [2, 5]);
+        gooey_engine_sequencer_rotate_instrument_pattern(engine, kick, -3);
+        assert_eq!(enabled_steps(engine, kick), [2, 15]);
+        assert_eq!(
+            gooey_engine_sequencer_reverse_instrument_pattern(engine, kick),
+            GooeyResult::Ok
+        );
+        assert_eq!(enabled_steps(engine, kick), [0, 13]);
+        assert_eq!(
+            gooey_engine_sequencer_invert_instrument_pattern(engine, kick),
+            GooeyResult::Ok
+        );
+        assert_eq!(enabled_steps(engine, kick).len(), 14);
+        assert!(!enabled_steps(engine, kick).contains(&13));
+
+        assert_eq!(
+            gooey_engine_sequencer_reverse_instrument_pattern(std::ptr::null_mut(), kick),
+            GooeyResult::NullPointer
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn velocity_edits() {
+    unsafe {
+        let velocities = |seed| {
+            let engine = gooey_engine_new(SAMPLE_RATE);
+            gooey_engine_set_seed(engine, seed);
+            let kick = INSTRUMENT_KICK;
+            gooey_engine_sequencer_set_instrument_pattern(engine, kick, [true; 16].as_ptr());
+            gooey_engine_sequencer_set_instrument_step_velocity(engine, kick, 0, 0.2);
+            assert_eq!(
+                gooey_engine_sequencer_compress_instrument_velocities(engine, kick, 1.0),
+                GooeyResult::Ok
+            );
+            assert_eq!(
+                gooey_engine_sequencer_scale_instrument_velocities(engine, kick, 0.5),
+                GooeyResult::Ok
+            );
+            assert_eq!(
+                gooey_engine_sequencer_humanize_instrument_velocities(engine, kick, 0.2),
+                GooeyResult::Ok
+            );
+            assert_eq!(
+                gooey_engine_sequencer_humanize_instrument_velocities(engine, kick, 1.5),
+                GooeyResult::InvalidValue
+            );
+            assert_eq!(
+                gooey_engine_sequencer_scale_instrument_velocities(engine, kick, -1.0),
+                GooeyResult::InvalidValue
+            );
+            let velocities: Vec<f32> = (0..16)
+                .map(|step| gooey_engine_sequencer_get_instrument_step_velocity(engine, kick, step))
+                .collect();
+            gooey_engine_free(engine);
+            velocities
+        };
+
+        // Compressed to the average of 0.95, halved, then spread by ±0.2
+        let first = velocities(1);
+        let average = (0.2 + 15.0) / 16.0 * 0.5;
+        assert!(first.iter().all(|v| (v - average).abs() <= 0.2 + 1e-6));
+        assert!(first.iter().any(|v| (v - average).abs() > 1e-3));
+        // The seed decides the spread
+        assert_eq!(velocities(1), first);
+        assert_ne!(velocities(2), first);
+    }
+}
@@ -0,0 +1,201 @@
+// Every stock preset keeps the character its instrument is built for:
+// where its pitch sits, how bright it is and how fast it dies away.
+
+use gooey::engine::Instrument;
+use gooey::instruments::*;
+use gooey::test_utils::{decay_time_ms, peak, peak_frequency, render, spectral_centroid, to_db};
+
+const SR: f32 = 44_100.0;
+/// Analysis window for brightness (~186 ms)
+const WINDOW: usize = 8192;
+/// Analysis window for low pitches (~370 ms)
+const LONG_WINDOW: usize = 16384;
+
+fn hit(mut instrument: impl Instrument, seconds: f32) -> Vec<f32> {
+    render(&mut instrument, SR, seconds)
+}
+
+fn assert_audible(name: &str, samples: &[f32]) {
+    let level = to_db(peak(samples));
+    assert!(level > -20.0, "{name}: peak {level:.1} dB");
+}
+
+fn assert_decays_within(name: &str, samples: &[f32], ms: f32) {
+    let decay = decay_time_ms(samples, SR, 60.0);
+    assert!(
+        decay.is_some_and(|d| d <= ms),
+        "{name}: fell 60 dB in {decay:?} ms, expected within {ms} ms"
+    );
+}
+
+fn assert_centroid(name: &str, samples: &[f32], low: f32, high: f32) {
+    let centroid = spectral_centroid(&samples[..WINDOW], SR);
+    assert!(
+        (low..=high).contains(&centroid),
+        "{name}: centroid {centroid:.0} Hz outside {low}-{high} Hz"
+    );
+}
+
+#[test]
+fn kick_presets_sit_low_and_sweep_down_to_their_tuning() {
+    for (name, config) in [
+        ("tight", KickConfig::tight()),
+        ("punch", KickConfig::punch()),
+        ("loose", KickConfig::loose()),
+        ("dirt", KickConfig::dirt()),
+    ] {
+        let samples = hit(KickDrum::with_config(SR, config), 1.0);
+        assert_audible(name, &samples);
+        // The pitch sweep lands on the tuned frequency from at most an
+        // octave above it
+        let tuned = config.frequency_hz();
+        let fundamental = peak_frequency(&samples[..LONG_WINDOW], SR);
+        assert!(
+            (tuned * 0.95..=tuned * 2.0).contains(&fundamental),
+            "kick {name}: fundamental {fundamental:.1} Hz, tuned to {tuned:.1} Hz"
+        );
+        assert_centroid(name, &samples, 0.0, 600.0);
+        assert_decays_within(name, &samples, 400.0);
+    }
+}
+
+#[test]
+fn snare_presets_are_bright_and_short() {
+    for (name, config) in [
+        ("tight", SnareConfig::tight()),
+        ("loose", SnareConfig::loose()),
+        ("hiss", SnareConfig::hiss()),
+        ("smack", SnareConfig::smack()),
+    ] {
+        let samples = hit(SnareDrum::with_config(SR, config), 1.0);
+        assert_audible(name, &samples);
+        assert_centroid(name, &samples, 5000.0, 20000.0);
+        assert_decays_within(name, &samples, 500.0);
+    }
+}
+
+#[test]
+fn hihat_presets_are_brightest_and_only_loose_ones_ring() {
+    for (name, config, open) in [
+        ("short", HiHat2Config::short(), false),
+        ("loose", HiHat2Config::loose(), true),
+        ("dark", HiHat2Config::dark(), false),
+        ("soft", HiHat2Config::soft(), false),
+        ("classic", HiHat2Config::classic(), false),
+        ("classic_loose", HiHat2Config::classic_loose(), true),
+    ] {
+        let samples = hit(HiHat2::with_config(SR, config), 1.5);
+        assert_audible(name, &samples);
+        assert_centroid(name, &samples, 9000.0, 20000.0);
+        if open {
+            let decay = decay_time_ms(&samples, SR, 60.0).unwrap_or(f32::MAX);
+            assert!(decay > 500.0, "hihat {name}: closed after {decay} ms");
+        } else {
+            assert_decays_within(name, &samples, 250.0);
+        }
+    }
+}
+
+#[test]
+fn tom_presets_ring_at_their_tuning() {
+    for (name, config) in [
+        ("derp", Tom2Config::derp()),
+        ("ring", Tom2Config::ring()),
+        ("brush", Tom2Config::brush()),
+        ("void", Tom2Config::void_preset()),
+    ] {
+        let mut tom = Tom2::new(SR);
+        tom.set_config(config);
+        let tuned = tom.frequency();
+        let samples = hit(tom, 1.0);
+        assert_audible(name, &samples);
+        // Bend starts the pitch high; over the hit it averages just above
+        let fundamental = peak_frequency(&samples[..LONG_WINDOW], SR);
+        assert!(
+            (tuned..=tuned * 1.15).contains(&fundamental),
+            "tom {name}: fundamental {fundamental:.1} Hz, tuned to {tuned:.1} Hz"
+        );
+        assert_centroid(name, &samples, 0.0, 1000.0);
+    }
+}
+
+#[test]
+fn bass_presets_peak_on_a_harmonic_of_their_note() {
+    for (name, config) in [
+        ("acid", BassConfig::acid()),
+        ("sub", BassConfig::sub()),
+        ("reese", BassConfig::reese()),
+        ("stab", BassConfig::stab()),
+    ] {
+        let note = config.frequency_hz();
+        let samples = hit(BassSynth::with_config(SR, config), 1.0);
+        assert_audible(name, &samples);
+        // Resonant presets can lift an upper harmonic over the fundamental
+        let strongest = peak_frequency(&samples[..LONG_WINDOW], SR);
+        let harmonic = (strongest / note).round().max(1.0);
+        assert!(
+            harmonic <= 4.0 && (strongest / (note * harmonic) - 1.0).abs() < 0.03,
+            "bass {name}: strongest partial {strongest:.1} Hz, note {note:.1} Hz"
+        );
+    }
+}
+
+#[test]
+fn snap_and_rimshot_presets_are_short_mid_range_clicks() {
+    for (name, config) in [
+        ("snap", FmSnapConfig::snap()),
+        ("rim", FmSnapConfig::rim()),
+        ("wood", FmSnapConfig::wood()),
+        ("zap", FmSnapConfig::zap()),
+    ] {
+        let samples = hit(FmSnap::with_config(SR, config), 0.5);
+        assert_audible(name, &samples);
+        assert_centroid(name, &samples, 300.0, 5000.0);
+        assert_decays_within(name, &samples, 150.0);
+    }
+    for (name, config) in [
+        ("classic", RimshotConfig::classic()),
+        ("tight", RimshotConfig::tight()),
+        ("woody", RimshotConfig::woody()),
+        ("ring", RimshotConfig::ring()),
+    ] {
+        let samples = hit(Rimshot::with_config(SR, config), 0.5);
+        assert_audible(name, &samples);
+        assert_centroid(name, &samples, 300.0, 5000.0);
+        assert_decays_within(name, &samples, 150.0);
+    }
+}
+
+#[test]
+fn cowbell_presets_clang_in_the_upper_mids() {
+    let mut decays = Vec::new();
+    for (name, config) in [
+        ("classic", CowbellConfig::classic()),
+        ("bright", CowbellConfig::bright()),
+        ("dark", CowbellConfig::dark()),
+        ("short", CowbellConfig::short()),
+    ] {
+        let samples = hit(Cowbell::with_config(SR, config), 1.5);
+        assert_audible(name, &samples);
+        assert_centroid(name, &samples, 1500.0, 5000.0);
+        assert_decays_within(name, &samples, 1000.0);
+        decays.push(decay_time_ms(&samples, SR, 60.0).unwrap());
+    }
+    // short really is the shortest
+    assert!(decays[3] < decays[..3].iter().cloned().fold(f32::MAX, f32::min));
+}
+
+#[test]
+fn shaker_presets_are_bright_bursts() {
+    for (name, config) in [
+        ("tight", ShakerConfig::tight()),
+        ("swish", ShakerConfig::swish()),
+        ("cabasa", ShakerConfig::cabasa()),
+        ("egg", ShakerConfig::egg()),
+    ] {
+        let samples = hit(Shaker::with_config(SR, config), 0.5);
+        assert_audible(name, &samples);
+        assert_centroid(name, &samples, 8000.0, 20000.0);
+        assert_decays_within(name, &samples, 150.0);
+    }
+}
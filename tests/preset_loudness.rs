@@ -0,0 +1,196 @@
+//! Integration tests for preset loudness normalization.
+
+use gooey::ffi::*;
+use gooey::utils::Loudness;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+const SETTLE_FRAMES: usize = 4_096;
+const HIT_FRAMES: usize = 22_050;
+
+const PRESETS: [(u32, [u32; 4]); 6] = [
+    (
+        INSTRUMENT_KICK,
+        [
+            KICK_PRESET_TIGHT,
+            KICK_PRESET_PUNCH,
+            KICK_PRESET_LOOSE,
+            KICK_PRESET_DIRT,
+        ],
+    ),
+    (
+        INSTRUMENT_SNARE,
+        [
+            SNARE_PRESET_TIGHT,
+            SNARE_PRESET_LOOSE,
+            SNARE_PRESET_HISS,
+            SNARE_PRESET_SMACK,
+        ],
+    ),
+    (
+        INSTRUMENT_HIHAT,
+        [
+            HIHAT_PRESET_SHORT,
+            HIHAT_PRESET_LOOSE,
+            HIHAT_PRESET_DARK,
+            HIHAT_PRESET_SOFT,
+        ],
+    ),
+    (
+        INSTRUMENT_TOM,
+        [
+            TOM_PRESET_DERP,
+            TOM_PRESET_RING,
+            TOM_PRESET_BRUSH,
+            TOM_PRESET_VOID,
+        ],
+    ),
+    (
+        INSTRUMENT_BASS,
+        [
+            BASS_PRESET_ACID,
+            BASS_PRESET_SUB,
+            BASS_PRESET_REESE,
+            BASS_PRESET_STAB,
+        ],
+    ),
+    (
+        INSTRUMENT_FM_SNAP,
+        [
+            FM_SNAP_PRESET_SNAP,
+            FM_SNAP_PRESET_RIM,
+            FM_SNAP_PRESET_WOOD,
+            FM_SNAP_PRESET_ZAP,
+        ],
+    ),
+];
+
+unsafe fn measure(engine: *mut GooeyEngine, instrument_type: u32, preset_id: u32) -> (f32, f32) {
+    let (mut rms_db, mut peak_db, mut gain_db) = (0.0, 0.0, 0.0);
+    let result = gooey_engine_measure_preset_loudness(
+        engine,
+        instrument_type,
+        preset_id,
+        &mut rms_db,
+        &mut peak_db,
+        &mut gain_db,
+    );
+    assert_eq!(result, GooeyResult::Ok);
+    assert!(peak_db >= rms_db, "peak {peak_db} dB below rms {rms_db} dB");
+    (rms_db, gain_db)
+}
+
+/// Render one bass hit after letting the preset gain settle.
+unsafe fn render_bass_hit(engine: *mut GooeyEngine) -> Loudness {
+    let mut buffer = vec![0.0_f32; SETTLE_FRAMES * GOOEY_OUTPUT_CHANNELS as usize];
+    gooey_engine_render(engine, buffer.as_mut_ptr(), SETTLE_FRAMES as u32);
+    gooey_engine_trigger_instrument(engine, INSTRUMENT_BASS);
+    let mut buffer = vec![0.0_f32; HIT_FRAMES * GOOEY_OUTPUT_CHANNELS as usize];
+    gooey_engine_render(engine, buffer.as_mut_ptr(), HIT_FRAMES as u32);
+    let left: Vec<f32> = buffer
+        .chunks(GOOEY_OUTPUT_CHANNELS as usize)
+        .map(|frame| frame[0])
+        .collect();
+    Loudness::measure(&left, SAMPLE_RATE)
+}
+
+#[test]
+fn normalized_presets_match_the_default_sound_within_1_db() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        assert!(gooey_engine_get_preset_normalization(engine));
+
+        for (instrument_type, presets) in PRESETS {
+            let (reference_db, reference_gain) =
+                measure(engine, instrument_type, PRESET_DEFAULT_SOUND);
+            assert_eq!(reference_gain, 0.0);
+            for preset in presets {
+                let (rms_db, gain_db) = measure(engine, instrument_type, preset);
+                let normalized_db = rms_db + gain_db;
+                assert!(
+                    (normalized_db - reference_db).abs() <= 1.0,
+                    "instrument {instrument_type} preset {preset}: {rms_db:.1} dB + {gain_db:.1} dB \
+                     vs default {reference_db:.1} dB"
+                );
+            }
+        }
+
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn loading_presets_is_level_matched_only_when_enabled() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+
+        let spread = |enabled: bool| {
+            gooey_engine_set_preset_normalization(engine, enabled);
+            let levels: Vec<f32> = [BASS_PRESET_SUB, BASS_PRESET_STAB]
+                .into_iter()
+                .map(|preset| {
+                    gooey_engine_load_bass_preset(engine, preset);
+                    render_bass_hit(engine).rms_db
+                })
+                .collect();
+            (levels[0] - levels[1]).abs()
+        };
+
+        let raw = spread(false);
+        let normalized = spread(true);
+        assert!(
+            normalized <= 1.0,
+            "normalized presets differ by {normalized:.1} dB"
+        );
+        assert!(
+            raw > normalized,
+            "normalization should narrow the {raw:.1} dB spread between presets"
+        );
+
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn measure_preset_loudness_rejects_bad_input() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        let (mut rms_db, mut peak_db, mut gain_db) = (0.0, 0.0, 0.0);
+
+        let result = gooey_engine_measure_preset_loudness(
+            std::ptr::null_mut(),
+            INSTRUMENT_KICK,
+            KICK_PRESET_TIGHT,
+            &mut rms_db,
+            &mut peak_db,
+            &mut gain_db,
+        );
+        assert_eq!(result, GooeyResult::NullPointer);
+
+        let result = gooey_engine_measure_preset_loudness(
+            engine,
+            INSTRUMENT_KICK,
+            99,
+            &mut rms_db,
+            &mut peak_db,
+            &mut gain_db,
+        );
+        assert_eq!(result, GooeyResult::InvalidValue);
+
+        let result = gooey_engine_measure_preset_loudness(
+            engine,
+            INSTRUMENT_COUNT,
+            KICK_PRESET_TIGHT,
+            &mut rms_db,
+            &mut peak_db,
+            &mut gain_db,
+        );
+        assert_eq!(result, GooeyResult::InvalidInstrument);
+
+        gooey_engine_set_preset_normalization(engine, false);
+        assert!(!gooey_engine_get_preset_normalization(engine));
+        let (_, gain_db) = measure(engine, INSTRUMENT_KICK, KICK_PRESET_DIRT);
+        assert_eq!(gain_db, 0.0);
+
+        gooey_engine_free(engine);
+    }
+}
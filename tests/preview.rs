@@ -0,0 +1,162 @@
+//! Tests for the preview voices that audition configs over FFI.
+
+use gooey::ffi::*;
+use gooey::test_utils::{peak, render_frames};
+
+const SAMPLE_RATE: f32 = 44_100.0;
+const FRAMES: usize = 16_384;
+
+/// A cowbell config that differs from the default sound.
+const COWBELL: [f32; 3] = [0.8, 0.3, 0.6];
+
+/// Render `FRAMES` frames, optionally with a kick pattern running through the
+/// delay, optionally previewing the cowbell config at the start.
+fn render(sequence: bool, preview: bool) -> Vec<f32> {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        if sequence {
+            let pattern = [true, false, false, false].repeat(4);
+            gooey_engine_sequencer_set_instrument_pattern(
+                engine,
+                INSTRUMENT_KICK,
+                pattern.as_ptr(),
+            );
+            gooey_engine_set_global_effect_enabled(engine, EFFECT_DELAY, true);
+            gooey_engine_sequencer_start(engine);
+        }
+        if preview {
+            assert_eq!(
+                gooey_engine_preview_trigger_with_config(
+                    engine,
+                    INSTRUMENT_COWBELL,
+                    COWBELL.as_ptr(),
+                    COWBELL.len() as u32,
+                    1.0,
+                ),
+                GooeyResult::Ok
+            );
+        }
+        let buf = render_frames(engine, FRAMES);
+        gooey_engine_free(engine);
+        buf
+    }
+}
+
+#[test]
+fn preview_is_mixed_in_without_disturbing_the_sequence() {
+    let kit = render(true, false);
+    let both = render(true, true);
+    let alone = render(false, true);
+    assert!(peak(&alone) > 0.01);
+    // The running kit (and its delay) is unchanged by the preview: the mix is
+    // the kit plus the dry preview.
+    for ((both, kit), alone) in both.iter().zip(&kit).zip(&alone) {
+        assert!((both - kit - alone).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn preview_leaves_channels_alone() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        let before =
+            gooey_engine_get_channel_param(engine, INSTRUMENT_COWBELL, COWBELL_PARAM_PITCH);
+        gooey_engine_preview_trigger_with_config(
+            engine,
+            INSTRUMENT_COWBELL,
+            COWBELL.as_ptr(),
+            COWBELL.len() as u32,
+            1.0,
+        );
+        assert!(peak(&render_frames(engine, FRAMES)) > 0.01);
+        assert_eq!(
+            gooey_engine_get_channel_param(engine, INSTRUMENT_COWBELL, COWBELL_PARAM_PITCH),
+            before
+        );
+        let mut peaks = [1.0_f32; CHANNEL_MAX as usize];
+        gooey_engine_get_channel_peaks(engine, peaks.as_mut_ptr(), CHANNEL_MAX);
+        assert!(peaks.iter().all(|&p| p == 0.0));
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn preview_follows_the_config_and_gain() {
+    let preview = |params: &[f32], gain: f32| unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_set_preview_gain(engine, gain);
+        assert_eq!(gooey_engine_get_preview_gain(engine), gain);
+        // Let the gain ramp settle
+        render_frames(engine, 8192);
+        gooey_engine_preview_trigger_with_config(
+            engine,
+            INSTRUMENT_COWBELL,
+            params.as_ptr(),
+            params.len() as u32,
+            1.0,
+        );
+        let buf = render_frames(engine, FRAMES);
+        gooey_engine_free(engine);
+        buf
+    };
+    let edited = preview(&COWBELL, 1.0);
+    let default = preview(&[], 1.0);
+    assert!(peak(&default) > 0.01);
+    assert_ne!(edited, default);
+    assert!(peak(&preview(&COWBELL, 0.0)) < 1e-4);
+    let half = peak(&preview(&COWBELL, 0.5));
+    assert!((half / peak(&edited) - 0.5).abs() < 0.01);
+}
+
+#[test]
+fn preview_rejects_bad_configs() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        let nan = [f32::NAN];
+        let too_many = [0.5; 32];
+        assert_eq!(
+            gooey_engine_preview_trigger_with_config(
+                engine,
+                INSTRUMENT_COUNT,
+                nan.as_ptr(),
+                0,
+                1.0
+            ),
+            GooeyResult::InvalidInstrument
+        );
+        assert_eq!(
+            gooey_engine_preview_trigger_with_config(
+                engine,
+                INSTRUMENT_KICK,
+                std::ptr::null(),
+                1,
+                1.0
+            ),
+            GooeyResult::NullPointer
+        );
+        assert_eq!(
+            gooey_engine_preview_trigger_with_config(
+                engine,
+                INSTRUMENT_KICK,
+                too_many.as_ptr(),
+                too_many.len() as u32,
+                1.0
+            ),
+            GooeyResult::InvalidParam
+        );
+        assert_eq!(
+            gooey_engine_preview_trigger_with_config(engine, INSTRUMENT_KICK, nan.as_ptr(), 1, 1.0),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_set_preview_gain(engine, f32::INFINITY),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(gooey_engine_preview_release(engine), GooeyResult::Ok);
+        assert_eq!(
+            gooey_engine_preview_release(std::ptr::null_mut()),
+            GooeyResult::NullPointer
+        );
+        gooey_engine_free(engine);
+    }
+}
@@ -0,0 +1,81 @@
+//! Tests for seeded instrument randomization.
+
+use gooey::ffi::*;
+
+fn kick_params_after(randomize: impl Fn(*mut GooeyEngine)) -> Vec<f32> {
+    let engine = gooey_engine_new(44_100.0);
+    let mut buf = vec![0.0_f32; 4096 * 2];
+    randomize(engine);
+    unsafe {
+        // Let the smoothed parameters settle on their new targets.
+        gooey_engine_render(engine, buf.as_mut_ptr(), 4096);
+        let params = (0..gooey_engine_get_param_count(INSTRUMENT_KICK))
+            .map(|p| gooey_engine_get_kick_param(engine, p))
+            .collect();
+        gooey_engine_free(engine);
+        params
+    }
+}
+
+fn randomize_kick(amount: f32, seed: u32) -> Vec<f32> {
+    kick_params_after(|engine| unsafe {
+        assert_eq!(
+            gooey_engine_randomize_instrument(engine, INSTRUMENT_KICK, amount, seed),
+            GooeyResult::Ok
+        );
+    })
+}
+
+#[test]
+fn same_seed_gives_same_sound() {
+    assert_eq!(randomize_kick(0.7, 42), randomize_kick(0.7, 42));
+    assert_ne!(randomize_kick(0.7, 42), randomize_kick(0.7, 43));
+}
+
+#[test]
+fn zero_amount_leaves_sound_unchanged() {
+    let untouched = kick_params_after(|_| {});
+    assert_eq!(randomize_kick(0.0, 42), untouched);
+    assert_ne!(randomize_kick(1.0, 42), untouched);
+}
+
+#[test]
+fn randomized_params_stay_in_range() {
+    for seed in 1..32 {
+        for (param, value) in randomize_kick(1.0, seed).into_iter().enumerate() {
+            let info = gooey::param_info::param_info(INSTRUMENT_KICK, param as u32).unwrap();
+            assert!(
+                (0.0..=1.0).contains(&value) || (info.min..=info.max).contains(&value),
+                "seed {seed} {}: {value}",
+                info.name()
+            );
+        }
+    }
+}
+
+#[test]
+fn every_instrument_type_can_be_randomized() {
+    let engine = gooey_engine_new(44_100.0);
+    unsafe {
+        for channel in 0..gooey_engine_instrument_count() {
+            assert_eq!(
+                gooey_engine_randomize_instrument(engine, channel, 0.5, channel + 1),
+                GooeyResult::Ok
+            );
+        }
+        assert_eq!(
+            gooey_engine_randomize_instrument(engine, 99, 0.5, 1),
+            GooeyResult::InvalidChannel
+        );
+        assert_eq!(
+            gooey_engine_randomize_instrument(engine, INSTRUMENT_KICK, f32::NAN, 1),
+            GooeyResult::InvalidValue
+        );
+
+        let mut buf = vec![0.0_f32; 512 * 2];
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_SNARE);
+        gooey_engine_render(engine, buf.as_mut_ptr(), 512);
+        assert!(buf.iter().all(|s| s.is_finite()));
+        gooey_engine_free(engine);
+    }
+}
@@ -0,0 +1,110 @@
+//! Verifies that steady-state rendering never touches the heap.
+
+use gooey::ffi::*;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Counts heap calls made by the current thread while counting is on, so the
+/// test harness's own threads do not interfere.
+struct CountingAllocator;
+
+thread_local! {
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+    static HEAP_CALLS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn count() {
+    if COUNTING.with(Cell::get) {
+        HEAP_CALLS.with(|calls| calls.set(calls.get() + 1));
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count();
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        count();
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count();
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Heap calls made by `f` on this thread.
+fn heap_calls(f: impl FnOnce()) -> usize {
+    HEAP_CALLS.with(|calls| calls.set(0));
+    COUNTING.with(|counting| counting.set(true));
+    f();
+    COUNTING.with(|counting| counting.set(false));
+    HEAP_CALLS.with(Cell::get)
+}
+
+/// An engine with the sequencer, every global effect, an LFO and blending
+/// running, warmed up past any lazy first-render setup.
+unsafe fn busy_engine() -> *mut GooeyEngine {
+    let engine = gooey_engine_new(48_000.0);
+    for effect in 0..EFFECT_COUNT {
+        gooey_engine_set_global_effect_enabled(engine, effect, true);
+    }
+    gooey_engine_set_lfo_enabled(engine, 0, true);
+    gooey_engine_blend_enable(engine, INSTRUMENT_KICK);
+    gooey_engine_sequencer_start(engine);
+    let mut block = [0.0f32; RENDER_BLOCK_FRAMES as usize * 2];
+    for _ in 0..200 {
+        gooey_engine_render_block(engine, block.as_mut_ptr());
+    }
+    engine
+}
+
+#[test]
+fn test_render_block_does_not_allocate() {
+    unsafe {
+        // The counter itself works
+        assert_eq!(heap_calls(|| drop(std::hint::black_box(vec![0u8; 16]))), 2);
+
+        let engine = busy_engine();
+        let mut block = [[0.0f32; 2]; 128];
+        let mut calls = 0;
+        for i in 0..2_000 {
+            // Control writes may allocate (diagnostics); only renders count
+            gooey_engine_set_kick_param(engine, KICK_PARAM_DECAY, (i % 10) as f32 / 10.0);
+            gooey_engine_blend_set_position(engine, INSTRUMENT_KICK, (i % 7) as f32 / 7.0, 0.3);
+            if i % 37 == 0 {
+                gooey_engine_trigger_instrument(engine, INSTRUMENT_SNARE);
+            }
+            calls += heap_calls(|| (*engine).render_block(&mut block));
+        }
+        assert_eq!(calls, 0, "render path touched the heap");
+        assert!(block.iter().flatten().all(|s| s.is_finite()));
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn test_render_block_matches_render() {
+    unsafe {
+        let a = gooey_engine_new(48_000.0);
+        let b = gooey_engine_new(48_000.0);
+        gooey_engine_trigger_instrument(a, INSTRUMENT_KICK);
+        gooey_engine_trigger_instrument(b, INSTRUMENT_KICK);
+
+        let mut block = [[0.0f32; 2]; RENDER_BLOCK_FRAMES as usize];
+        let mut flat = vec![0.0f32; RENDER_BLOCK_FRAMES as usize * 2];
+        for _ in 0..8 {
+            (*a).render_block(&mut block);
+            gooey_engine_render(b, flat.as_mut_ptr(), RENDER_BLOCK_FRAMES);
+            assert_eq!(block.as_flattened(), &flat[..]);
+        }
+        gooey_engine_free(a);
+        gooey_engine_free(b);
+    }
+}
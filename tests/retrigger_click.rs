@@ -0,0 +1,71 @@
+// Retriggering a voice mid-decay releases the old hit instead of stepping
+
+use gooey::engine::Instrument;
+use gooey::instruments::{KickDrum, Tom2, TomDrum};
+use gooey::utils::RETRIGGER_FADE_MS;
+
+const SAMPLE_RATE: f32 = 48000.0;
+
+fn largest_step(samples: &[f32]) -> f32 {
+    samples
+        .windows(2)
+        .map(|w| (w[1] - w[0]).abs())
+        .fold(0.0, f32::max)
+}
+
+/// The largest step of one clean hit, and the largest step across a
+/// retrigger landing where the first hit is loudest.
+fn steps(make: impl Fn() -> Box<dyn Instrument>) -> (f32, f32) {
+    let frames = 4800;
+    let mut clean = make();
+    clean.trigger(0.0);
+    let hit: Vec<f32> = (0..frames)
+        .map(|i| clean.tick(i as f64 / SAMPLE_RATE as f64))
+        .collect();
+    let fade = (RETRIGGER_FADE_MS * 0.001 * SAMPLE_RATE) as usize;
+    // Skip the attack so the retrigger lands mid-decay
+    let skip = (0.005 * SAMPLE_RATE) as usize;
+    let loudest = skip
+        + hit[skip..frames - fade]
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+            .unwrap()
+            .0;
+
+    let mut voice = make();
+    voice.trigger(0.0);
+    let mut rendered = Vec::with_capacity(frames);
+    for i in 0..frames {
+        let time = i as f64 / SAMPLE_RATE as f64;
+        if i == loudest {
+            voice.trigger(time);
+        }
+        rendered.push(voice.tick(time));
+    }
+    (
+        largest_step(&hit),
+        largest_step(&rendered[loudest - 1..loudest + fade]),
+    )
+}
+
+fn assert_click_free(name: &str, make: impl Fn() -> Box<dyn Instrument>) {
+    let (clean, retrigger) = steps(make);
+    // The fade's own ramp adds a little slope; an unfaded retrigger steps
+    // several times further than the hit ever does
+    assert!(
+        retrigger <= clean * 1.5,
+        "{name}: retrigger stepped {retrigger}, a clean hit at most {clean}"
+    );
+}
+
+#[test]
+fn test_kick_retrigger_is_click_free() {
+    assert_click_free("kick", || Box::new(KickDrum::new(SAMPLE_RATE)));
+}
+
+#[test]
+fn test_tom_retrigger_is_click_free() {
+    assert_click_free("tom", || Box::new(TomDrum::new(SAMPLE_RATE)));
+    assert_click_free("tom2", || Box::new(Tom2::new(SAMPLE_RATE)));
+}
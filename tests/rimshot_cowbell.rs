@@ -0,0 +1,126 @@
+//! Tests for the rimshot and cowbell voices over FFI.
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+
+fn render(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    let mut buf = vec![0.0_f32; frames * 2];
+    unsafe { gooey_engine_render(engine, buf.as_mut_ptr(), frames as u32) };
+    buf
+}
+
+fn peak(samples: &[f32]) -> f32 {
+    samples.iter().fold(0.0, |m, s| m.max(s.abs()))
+}
+
+/// Render one hit of `instrument` after `setup`.
+fn hit(instrument: u32, setup: impl Fn(*mut GooeyEngine)) -> Vec<f32> {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    setup(engine);
+    render(engine, 64);
+    unsafe { gooey_engine_trigger_instrument(engine, instrument) };
+    let out = render(engine, 8192);
+    unsafe { gooey_engine_free(engine) };
+    out
+}
+
+#[test]
+fn both_voices_sound_on_their_own_channels() {
+    for instrument in [INSTRUMENT_RIMSHOT, INSTRUMENT_COWBELL] {
+        let out = hit(instrument, |_| {});
+        assert!(peak(&out) > 0.05, "{instrument}: {}", peak(&out));
+        assert!(out.iter().all(|s| s.is_finite()));
+    }
+    // The rimshot is over well before the cowbell's tail
+    let rim = hit(INSTRUMENT_RIMSHOT, |_| {});
+    let bell = hit(INSTRUMENT_COWBELL, |_| {});
+    assert!(peak(&rim[8192..]) < peak(&bell[8192..]) * 0.1);
+}
+
+#[test]
+fn params_round_trip_and_reject_bad_input() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        assert_eq!(
+            gooey_engine_set_rimshot_param(engine, RIMSHOT_PARAM_CLICK, 0.3),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_set_cowbell_param(engine, COWBELL_PARAM_TUNING, 0.75),
+            GooeyResult::Ok
+        );
+        render(engine, 1);
+        assert!((gooey_engine_get_rimshot_param(engine, RIMSHOT_PARAM_CLICK) - 0.3).abs() < 1e-6);
+        assert!((gooey_engine_get_cowbell_param(engine, COWBELL_PARAM_TUNING) - 0.75).abs() < 1e-6);
+
+        assert_eq!(
+            gooey_engine_set_rimshot_param(engine, gooey_engine_rimshot_param_count(), 0.5),
+            GooeyResult::InvalidParam
+        );
+        assert_eq!(
+            gooey_engine_set_cowbell_param(engine, gooey_engine_cowbell_param_count(), 0.5),
+            GooeyResult::InvalidParam
+        );
+        assert!(gooey_engine_get_cowbell_param(engine, 999).is_nan());
+        assert_eq!(
+            gooey_engine_set_rimshot_param(std::ptr::null_mut(), 0, 0.5),
+            GooeyResult::NullPointer
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn presets_load_and_change_the_sound() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        let classic = gooey_engine_get_cowbell_param(engine, COWBELL_PARAM_PITCH);
+        assert_eq!(
+            gooey_engine_load_cowbell_preset(engine, COWBELL_PRESET_BRIGHT),
+            GooeyResult::Ok
+        );
+        assert!(gooey_engine_get_cowbell_param(engine, COWBELL_PARAM_PITCH) > classic);
+        assert_eq!(
+            gooey_engine_load_rimshot_preset(engine, RIMSHOT_PRESET_RING),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_load_rimshot_preset(engine, 4),
+            GooeyResult::InvalidValue
+        );
+
+        // Replacing the voice leaves nothing for the rimshot calls to address.
+        gooey_engine_set_channel_instrument_type(engine, INSTRUMENT_RIMSHOT, INSTRUMENT_KICK);
+        assert_eq!(
+            gooey_engine_load_rimshot_preset(engine, RIMSHOT_PRESET_TIGHT),
+            GooeyResult::InvalidInstrument
+        );
+        gooey_engine_free(engine);
+    }
+
+    let default = hit(INSTRUMENT_RIMSHOT, |_| {});
+    let woody = hit(INSTRUMENT_RIMSHOT, |engine| unsafe {
+        gooey_engine_load_rimshot_preset(engine, RIMSHOT_PRESET_WOODY);
+    });
+    assert!(default
+        .iter()
+        .zip(&woody)
+        .any(|(a, b)| (a - b).abs() > 1e-3));
+}
+
+#[test]
+fn any_channel_can_hold_a_cowbell() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        gooey_engine_set_channel_instrument_type(engine, INSTRUMENT_KICK, INSTRUMENT_COWBELL);
+        assert_eq!(
+            gooey_engine_get_channel_instrument_type(engine, INSTRUMENT_KICK),
+            INSTRUMENT_COWBELL
+        );
+        render(engine, 64);
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_KICK);
+        assert!(peak(&render(engine, 4096)) > 0.05);
+        gooey_engine_free(engine);
+    }
+}
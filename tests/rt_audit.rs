@@ -0,0 +1,229 @@
+//! Real-time safety audit: a busy kit rendered for several seconds under
+//! the `rt-audit` checker must not allocate, free or block on a lock.
+//!
+//! Run with `cargo test --features rt-audit --test rt_audit`.
+#![cfg(feature = "rt-audit")]
+
+use gooey::engine::{Engine, Instrument, Lfo, MusicalDivision, Sequencer};
+use gooey::ffi::*;
+use gooey::instruments::{Cowbell, HiHat, KickDrum, Rimshot, Shaker, SnareDrum, Tom2};
+use gooey::rt_audit::{self, AllocDisabler, Violations};
+
+#[global_allocator]
+static ALLOCATOR: AllocDisabler = AllocDisabler;
+
+const SAMPLE_RATE: f32 = 48_000.0;
+
+/// Seconds of audio each busy render covers.
+const SECONDS: usize = 8;
+
+/// One 16-step pattern per kit voice, busy enough that several voices
+/// retrigger mid-hit.
+const PATTERNS: [(u32, &str); 9] = [
+    (INSTRUMENT_KICK, "x..xx.x.x..x..x."),
+    (INSTRUMENT_SNARE, "....x..x....x.xx"),
+    (INSTRUMENT_HIHAT, "xxxxxxxxxxxxxxxx"),
+    (INSTRUMENT_TOM, "..x...x...x..xxx"),
+    (INSTRUMENT_BASS, "x.x.x.x.x.x.x.x."),
+    (INSTRUMENT_FM_SNAP, ".x...x...x...x.."),
+    (INSTRUMENT_RIMSHOT, "...x..x....x..x."),
+    (INSTRUMENT_COWBELL, "x.......x..x...."),
+    (INSTRUMENT_SHAKER, ".xxx.xxx.xxx.xxx"),
+];
+
+fn steps(pattern: &str) -> Vec<bool> {
+    pattern.chars().map(|c| c == 'x').collect()
+}
+
+fn assert_clean(violations: Violations) {
+    assert_eq!(
+        violations,
+        Violations::default(),
+        "render path allocated or locked (last lock: {:?})",
+        violations.last_lock
+    );
+}
+
+#[test]
+fn test_checker_sees_allocations() {
+    rt_audit::reset_violations();
+    assert!(!rt_audit::in_audio_section());
+    rt_audit::audio_section(|| {
+        assert!(rt_audit::in_audio_section());
+        drop(std::hint::black_box(vec![0u8; 16]));
+    });
+    assert!(!rt_audit::in_audio_section());
+    assert_eq!(rt_audit::violations().allocations, 2);
+
+    // Outside a section nothing counts
+    rt_audit::reset_violations();
+    drop(std::hint::black_box(vec![0u8; 16]));
+    assert_clean(rt_audit::violations());
+}
+
+#[test]
+fn test_busy_ffi_kit_is_real_time_safe() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_set_bpm(engine, 174.0);
+        for (instrument, pattern) in PATTERNS {
+            gooey_engine_sequencer_set_instrument_pattern(
+                engine,
+                instrument,
+                steps(pattern).as_ptr(),
+            );
+        }
+        for effect in 0..EFFECT_COUNT {
+            gooey_engine_set_global_effect_enabled(engine, effect, true);
+        }
+        gooey_engine_set_lfo_enabled(engine, 0, true);
+        gooey_engine_blend_enable(engine, INSTRUMENT_KICK);
+        gooey_engine_sequencer_start(engine);
+
+        let mut block = [[0.0f32; 2]; RENDER_BLOCK_FRAMES as usize];
+        // Warm up past any lazy first-render setup
+        for _ in 0..50 {
+            (*engine).render_block(&mut block);
+        }
+
+        rt_audit::reset_violations();
+        let blocks = SECONDS * SAMPLE_RATE as usize / RENDER_BLOCK_FRAMES as usize;
+        for i in 0..blocks {
+            // Control writes between renders, as a UI would make them
+            gooey_engine_set_snare_param(engine, SNARE_PARAM_DECAY, (i % 10) as f32 / 10.0);
+            if i % 53 == 0 {
+                gooey_engine_trigger_instrument(engine, INSTRUMENT_TOM);
+            }
+            if i % 97 == 0 {
+                let cowbell = [(i % 7) as f32 / 7.0];
+                gooey_engine_preview_trigger_with_config(
+                    engine,
+                    INSTRUMENT_COWBELL,
+                    cowbell.as_ptr(),
+                    1,
+                    1.0,
+                );
+            }
+            (*engine).render_block(&mut block);
+        }
+        assert_clean(rt_audit::violations());
+        assert!(block.iter().flatten().all(|s| s.is_finite()));
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn test_recording_past_the_reserve_is_real_time_safe() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_sequencer_set_instrument_pattern(
+            engine,
+            INSTRUMENT_HIHAT,
+            steps(PATTERNS[2].1).as_ptr(),
+        );
+        gooey_engine_sequencer_start(engine);
+        assert_eq!(
+            gooey_engine_record_reserve_seconds(engine, 0.5),
+            GooeyResult::Ok
+        );
+        assert_eq!(gooey_engine_record_start(engine), GooeyResult::Ok);
+
+        // Two seconds into a half-second take
+        let mut block = [[0.0f32; 2]; RENDER_BLOCK_FRAMES as usize];
+        rt_audit::reset_violations();
+        for _ in 0..2 * SAMPLE_RATE as usize / RENDER_BLOCK_FRAMES as usize {
+            (*engine).render_block(&mut block);
+        }
+        assert_clean(rt_audit::violations());
+        assert!(gooey_engine_record_overflowed(engine));
+        assert_eq!(gooey_engine_record_get_state(engine), RECORD_STATE_IDLE);
+        assert!(gooey_engine_record_get_frame_count(engine) >= SAMPLE_RATE as u32 / 2);
+        assert!(gooey_engine_record_get_frame_count(engine) < SAMPLE_RATE as u32);
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn test_channel_swaps_fade_out_without_freeing_on_render() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_set_kick_param(engine, KICK_PARAM_DECAY, 1.0);
+        let mut block = [[0.0f32; 2]; RENDER_BLOCK_FRAMES as usize];
+        (*engine).render_block(&mut block);
+
+        // Swap every few blocks, faster than a tail fades out, so tails pile
+        // up, finish and get cut inside the render
+        let kinds = [INSTRUMENT_SNARE, INSTRUMENT_TOM, INSTRUMENT_KICK];
+        rt_audit::reset_violations();
+        for i in 0..SAMPLE_RATE as usize / RENDER_BLOCK_FRAMES as usize {
+            if i % 3 == 0 {
+                gooey_engine_trigger_channel(engine, 0);
+                gooey_engine_set_channel_instrument_type(engine, 0, kinds[i / 3 % 3]);
+            }
+            (*engine).render_block(&mut block);
+        }
+        assert_clean(rt_audit::violations());
+        assert!(block.iter().flatten().all(|s| s.is_finite()));
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn test_busy_engine_kit_is_real_time_safe() {
+    let voices: [(&str, Box<dyn Instrument>, &str); 7] = [
+        ("kick", Box::new(KickDrum::new(SAMPLE_RATE)), PATTERNS[0].1),
+        (
+            "snare",
+            Box::new(SnareDrum::new(SAMPLE_RATE)),
+            PATTERNS[1].1,
+        ),
+        ("hihat", Box::new(HiHat::new(SAMPLE_RATE)), PATTERNS[2].1),
+        ("tom", Box::new(Tom2::new(SAMPLE_RATE)), PATTERNS[3].1),
+        (
+            "rimshot",
+            Box::new(Rimshot::new(SAMPLE_RATE)),
+            PATTERNS[6].1,
+        ),
+        (
+            "cowbell",
+            Box::new(Cowbell::new(SAMPLE_RATE)),
+            PATTERNS[7].1,
+        ),
+        ("shaker", Box::new(Shaker::new(SAMPLE_RATE)), PATTERNS[8].1),
+    ];
+    let bpm = 174.0;
+    let mut engine = Engine::new(SAMPLE_RATE);
+    engine.set_bpm(bpm);
+    for (name, instrument, pattern) in voices {
+        engine.add_instrument(name, instrument);
+        engine.add_sequencer(Sequencer::with_pattern(
+            bpm,
+            SAMPLE_RATE,
+            steps(pattern),
+            name,
+        ));
+    }
+    let lfo = engine.add_lfo(Lfo::new_synced(MusicalDivision::OneBar, bpm, SAMPLE_RATE));
+    engine
+        .map_lfo_to_parameter(lfo, "snare", "decay", 0.5)
+        .unwrap();
+    for index in 0..engine.sequencer_count() {
+        engine.sequencer_mut(index).unwrap().start();
+    }
+
+    let frames = SECONDS * SAMPLE_RATE as usize;
+    let mut peak = 0.0f32;
+    rt_audit::reset_violations();
+    rt_audit::audio_section(|| {
+        for n in 0..frames {
+            // Manual triggers go through the event queue by instrument id
+            if n % 4_800 == 0 {
+                engine.trigger_instrument("tom").unwrap();
+            }
+            let frame = engine.tick_stereo(n as f64 / SAMPLE_RATE as f64);
+            peak = peak.max(frame.l.abs()).max(frame.r.abs());
+        }
+    });
+    assert_clean(rt_audit::violations());
+    assert!(peak > 0.0 && peak.is_finite());
+}
@@ -0,0 +1,66 @@
+// Sample-counted time: hits late in a long session render like the first
+
+use gooey::engine::{Instrument, InstrumentRegistry};
+use gooey::instruments::{KickDrum, SnareDrum};
+use gooey::utils::SampleClock;
+
+const SAMPLE_RATE: f32 = 48000.0;
+const TEN_HOURS: u64 = 48000 * 3600 * 10;
+
+fn render_hit(instrument: &mut dyn Instrument, start: u64, frames: usize) -> Vec<f32> {
+    let mut clock = SampleClock::at(start, SAMPLE_RATE);
+    instrument.trigger_at(clock, 1.0);
+    (0..frames)
+        .map(|_| {
+            let sample = instrument.tick_at(clock);
+            clock.advance();
+            sample
+        })
+        .collect()
+}
+
+fn max_difference(a: &[f32], b: &[f32]) -> f32 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x - y).abs())
+        .fold(0.0, f32::max)
+}
+
+#[test]
+fn test_kick_ten_hours_in_matches_a_kick_at_zero() {
+    let first = render_hit(&mut KickDrum::new(SAMPLE_RATE), 0, 24000);
+    let late = render_hit(&mut KickDrum::new(SAMPLE_RATE), TEN_HOURS, 24000);
+    assert!(first.iter().any(|s| s.abs() > 0.1));
+    let difference = max_difference(&first, &late);
+    assert!(difference < 1e-4, "kick drifted by {difference}");
+}
+
+#[test]
+fn test_snare_envelope_holds_its_length_late_in_a_session() {
+    let mut early = SnareDrum::new(SAMPLE_RATE);
+    let mut late = SnareDrum::new(SAMPLE_RATE);
+    early.reseed(7);
+    late.reseed(7);
+    let first = render_hit(&mut early, 0, 24000);
+    let later = render_hit(&mut late, TEN_HOURS, 24000);
+    let difference = max_difference(&first, &later);
+    assert!(difference < 1e-4, "snare drifted by {difference}");
+}
+
+#[test]
+fn test_registry_ticks_on_the_sample_clock() {
+    let mut registry = InstrumentRegistry::<2>::new();
+    assert!(registry
+        .add("kick", Box::new(KickDrum::new(SAMPLE_RATE)))
+        .is_ok());
+    let mut clock = SampleClock::at(TEN_HOURS, SAMPLE_RATE);
+    registry.trigger("kick", clock.seconds(), 1.0);
+    let peak = (0..4800)
+        .map(|_| {
+            let sample = registry.tick_at(clock);
+            clock.advance();
+            sample.abs()
+        })
+        .fold(0.0, f32::max);
+    assert!(peak > 0.1);
+}
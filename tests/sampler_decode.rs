@@ -0,0 +1,134 @@
+//! Loading sampler slots from encoded audio files. Gated behind the `decode`
+//! feature, like the FFI functions themselves.
+#![cfg(feature = "decode")]
+
+use gooey::ffi::*;
+use std::ffi::CString;
+use std::io::Cursor;
+
+const SR: f32 = 44_100.0;
+
+/// A 24-bit stereo WAV: a full-scale square on the left, silence on the right.
+fn stereo_wav() -> Vec<u8> {
+    let spec = hound::WavSpec {
+        channels: 2,
+        sample_rate: 22_050,
+        bits_per_sample: 24,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut bytes = Vec::new();
+    let mut writer = hound::WavWriter::new(Cursor::new(&mut bytes), spec).unwrap();
+    for i in 0..2_205 {
+        let left = if (i / 50) % 2 == 0 {
+            4_194_303
+        } else {
+            -4_194_303
+        };
+        writer.write_sample(left).unwrap();
+        writer.write_sample(0).unwrap();
+    }
+    writer.finalize().unwrap();
+    bytes
+}
+
+fn render(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    let mut output = vec![0.0; frames * 2];
+    unsafe { gooey_engine_render(engine, output.as_mut_ptr(), frames as u32) };
+    output
+}
+
+#[test]
+fn wav_bytes_and_files_load_into_slots() {
+    let bytes = stereo_wav();
+    let path = std::env::temp_dir().join(format!("gooey_decode_{}.wav", std::process::id()));
+    std::fs::write(&path, &bytes).unwrap();
+    let c_path = CString::new(path.to_str().unwrap()).unwrap();
+    unsafe {
+        let engine = gooey_engine_new(SR);
+        let rack = gooey_engine_sampler_register(engine) as u32;
+        let source = gooey_engine_sampler_get_source_id(engine, rack);
+        assert!(gooey_engine_mixer_route_source(engine, source, 3));
+
+        assert!(gooey_engine_sampler_load_slot_bytes(
+            engine,
+            rack,
+            0,
+            bytes.as_ptr(),
+            bytes.len()
+        ));
+        assert!(gooey_engine_sampler_load_slot_file(
+            engine,
+            rack,
+            1,
+            c_path.as_ptr()
+        ));
+        for slot in [0, 1] {
+            assert_eq!(gooey_engine_sampler_slot_frames(engine, rack, slot), 2_205);
+            assert_eq!(gooey_engine_sampler_slot_channels(engine, rack, slot), 2);
+            assert_eq!(
+                gooey_engine_sampler_slot_sample_rate(engine, rack, slot),
+                22_050.0
+            );
+        }
+
+        assert!(gooey_engine_sampler_trigger(engine, rack, 1, 1.0));
+        let out = render(engine, 1_024);
+        let left = out.iter().step_by(2).fold(0.0_f32, |m, s| m.max(s.abs()));
+        let right = out
+            .iter()
+            .skip(1)
+            .step_by(2)
+            .fold(0.0_f32, |m, s| m.max(s.abs()));
+        assert!(left > 0.1, "left {left}");
+        assert!(right < left * 0.1, "right {right}");
+        gooey_engine_free(engine);
+    }
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn unrecognised_or_truncated_data_is_rejected() {
+    let bytes = stereo_wav();
+    let junk = b"ID3\x04 not a supported format";
+    let missing = CString::new("/nonexistent/gooey/pad.flac").unwrap();
+    unsafe {
+        let engine = gooey_engine_new(SR);
+        let rack = gooey_engine_sampler_register(engine) as u32;
+        assert!(!gooey_engine_sampler_load_slot_bytes(
+            engine,
+            rack,
+            0,
+            junk.as_ptr(),
+            junk.len()
+        ));
+        assert!(!gooey_engine_sampler_load_slot_bytes(
+            engine,
+            rack,
+            0,
+            bytes.as_ptr(),
+            20
+        ));
+        assert!(!gooey_engine_sampler_load_slot_bytes(
+            engine,
+            rack,
+            0,
+            std::ptr::null(),
+            0
+        ));
+        assert!(!gooey_engine_sampler_load_slot_file(
+            engine,
+            rack,
+            0,
+            missing.as_ptr()
+        ));
+        assert!(!gooey_engine_sampler_load_slot_bytes(
+            engine,
+            rack,
+            SAMPLER_SLOT_COUNT,
+            bytes.as_ptr(),
+            bytes.len()
+        ));
+        assert!(!gooey_engine_sampler_slot_is_loaded(engine, rack, 0));
+        gooey_engine_free(engine);
+    }
+}
@@ -0,0 +1,207 @@
+//! Sampler slice mode: chopping a slot and addressing slices from pads and
+//! pattern steps.
+
+use gooey::ffi::*;
+use std::f32::consts::TAU;
+
+const SR: f32 = 44_100.0;
+/// Frames per quarter of the test loop.
+const QUARTER: usize = 11_025;
+
+fn render(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    let mut output = vec![0.0; frames * 2];
+    unsafe { gooey_engine_render(engine, output.as_mut_ptr(), frames as u32) };
+    output
+}
+
+fn peak(samples: &[f32]) -> f32 {
+    samples
+        .iter()
+        .map(|sample| sample.abs())
+        .fold(0.0_f32, f32::max)
+}
+
+/// Frames until the last audible sample of interleaved stereo.
+fn sounding_frames(samples: &[f32]) -> usize {
+    samples
+        .iter()
+        .rposition(|sample| sample.abs() > 1e-3)
+        .map_or(0, |i| i / 2 + 1)
+}
+
+/// A rack whose slot 0 holds four quarter-second tones, each louder than the
+/// one before.
+unsafe fn stepped_rack() -> (*mut GooeyEngine, u32) {
+    let engine = gooey_engine_new(SR);
+    let rack = gooey_engine_sampler_register(engine) as u32;
+    let source = gooey_engine_sampler_get_source_id(engine, rack);
+    assert!(gooey_engine_mixer_route_source(engine, source, 3));
+    let pcm: Vec<f32> = (0..4 * QUARTER)
+        .map(|i| {
+            let level = 0.1 * (i / QUARTER + 1) as f32;
+            (TAU * 440.0 * i as f32 / SR).sin() * level
+        })
+        .collect();
+    assert!(gooey_engine_sampler_set_slot_buffer(
+        engine,
+        rack,
+        0,
+        pcm.as_ptr(),
+        pcm.len() as u32,
+        1,
+        SR
+    ));
+    (engine, rack)
+}
+
+#[test]
+fn equal_slices_play_their_own_region() {
+    unsafe {
+        let (engine, rack) = stepped_rack();
+        assert_eq!(gooey_engine_sampler_get_slice_count(engine, rack, 0), 0);
+        assert!(!gooey_engine_sampler_trigger_slice(engine, rack, 0, 0, 1.0));
+        assert!(!gooey_engine_sampler_slice_equal(
+            engine,
+            rack,
+            0,
+            SAMPLER_MAX_SLICES + 1
+        ));
+        assert!(gooey_engine_sampler_slice_equal(engine, rack, 0, 4));
+        assert_eq!(gooey_engine_sampler_get_slice_count(engine, rack, 0), 4);
+        for slice in 0..4 {
+            assert_eq!(
+                gooey_engine_sampler_get_slice_marker(engine, rack, 0, slice),
+                slice * QUARTER as u32
+            );
+        }
+        assert_eq!(
+            gooey_engine_sampler_get_slice_marker(engine, rack, 0, 4),
+            u32::MAX
+        );
+
+        assert!(gooey_engine_sampler_trigger_slice(engine, rack, 0, 0, 1.0));
+        let first = render(engine, 2 * QUARTER);
+        assert!(gooey_engine_sampler_trigger_slice(engine, rack, 0, 2, 1.0));
+        let third = render(engine, 2 * QUARTER);
+        for out in [&first, &third] {
+            let frames = sounding_frames(out);
+            assert!(frames.abs_diff(QUARTER) < 64, "{frames} frames");
+        }
+        let ratio = peak(&third) / peak(&first);
+        assert!((ratio - 3.0).abs() < 0.2, "ratio {ratio}");
+        assert!(!gooey_engine_sampler_trigger_slice(engine, rack, 0, 4, 1.0));
+
+        assert!(gooey_engine_sampler_slice_equal(engine, rack, 0, 0));
+        assert_eq!(gooey_engine_sampler_get_slice_count(engine, rack, 0), 0);
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn markers_from_a_ui_are_sorted_and_start_at_zero() {
+    unsafe {
+        let (engine, rack) = stepped_rack();
+        let markers = [30_000_u32, 5_000];
+        assert!(gooey_engine_sampler_set_slice_markers(
+            engine,
+            rack,
+            0,
+            markers.as_ptr(),
+            2
+        ));
+        assert_eq!(gooey_engine_sampler_get_slice_count(engine, rack, 0), 3);
+        let read: Vec<u32> = (0..3)
+            .map(|i| gooey_engine_sampler_get_slice_marker(engine, rack, 0, i))
+            .collect();
+        assert_eq!(read, [0, 5_000, 30_000]);
+
+        let past_end = [4 * QUARTER as u32];
+        assert!(!gooey_engine_sampler_set_slice_markers(
+            engine,
+            rack,
+            0,
+            past_end.as_ptr(),
+            1
+        ));
+        assert!(!gooey_engine_sampler_set_slice_markers(
+            engine,
+            rack,
+            0,
+            std::ptr::null(),
+            1
+        ));
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn transient_slicing_finds_each_hit() {
+    unsafe {
+        let engine = gooey_engine_new(SR);
+        let rack = gooey_engine_sampler_register(engine) as u32;
+        // Three decaying hits 0.3 s apart
+        let hits = [0, 13_230, 26_460];
+        let mut pcm = vec![0.0_f32; 40_000];
+        for &start in &hits {
+            for i in 0..4_000 {
+                pcm[start + i] = (TAU * 200.0 * i as f32 / SR).sin() * (-(i as f32) / 800.0).exp();
+            }
+        }
+        assert!(gooey_engine_sampler_set_slot_buffer(
+            engine,
+            rack,
+            0,
+            pcm.as_ptr(),
+            pcm.len() as u32,
+            1,
+            SR
+        ));
+        assert_eq!(
+            gooey_engine_sampler_slice_transients(engine, rack, 0, 0.5),
+            3
+        );
+        for (slice, &start) in hits.iter().enumerate() {
+            let marker = gooey_engine_sampler_get_slice_marker(engine, rack, 0, slice as u32);
+            assert!(marker.abs_diff(start as u32) <= 256, "{marker} vs {start}");
+        }
+        assert_eq!(
+            gooey_engine_sampler_slice_transients(engine, rack, 1, 0.5),
+            0
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn pattern_steps_address_slices() {
+    unsafe {
+        let (engine, rack) = stepped_rack();
+        assert!(gooey_engine_sampler_slice_equal(engine, rack, 0, 4));
+        assert!(gooey_engine_sampler_set_step(engine, rack, 0, true, 0, 1.0));
+        assert_eq!(gooey_engine_sampler_get_step_slice(engine, rack, 0), -1);
+        assert!(gooey_engine_sampler_set_step_slice(engine, rack, 0, 3));
+        assert_eq!(gooey_engine_sampler_get_step_slice(engine, rack, 0), 3);
+        assert!(!gooey_engine_sampler_set_step_slice(
+            engine,
+            rack,
+            SAMPLER_SLOT_COUNT,
+            0
+        ));
+
+        // Reference level: the quietest quarter, played by hand
+        assert!(gooey_engine_sampler_trigger_slice(engine, rack, 0, 0, 1.0));
+        let quietest = peak(&render(engine, 2 * QUARTER));
+
+        assert!(gooey_engine_sampler_start_pattern(
+            engine,
+            rack,
+            CLIP_QUANTIZE_BAR
+        ));
+        gooey_engine_sequencer_start(engine);
+        let out = render(engine, QUARTER / 2);
+        // The loudest quarter plays, not the start of the loop
+        let ratio = peak(&out) / quietest;
+        assert!((ratio - 4.0).abs() < 0.2, "ratio {ratio}");
+        gooey_engine_free(engine);
+    }
+}
@@ -0,0 +1,198 @@
+//! Disk-streamed sampler slots: playback through the prefetch thread must
+//! match the same audio loaded into memory. Gated behind the `stream`
+//! feature, like the FFI function itself.
+#![cfg(feature = "stream")]
+
+use gooey::ffi::*;
+use std::f32::consts::TAU;
+use std::ffi::CString;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+const SR: f32 = 44_100.0;
+const BLOCK: usize = 4_410;
+
+/// A process-unique temp path so parallel tests never collide.
+fn temp_wav() -> PathBuf {
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    std::env::temp_dir().join(format!(
+        "gooey_sampler_stream_{}_{}.wav",
+        std::process::id(),
+        n
+    ))
+}
+
+/// Write `seconds` of a stereo tone (220 Hz left, 330 Hz right) as 16-bit
+/// PCM, returning the samples as the engine will read them back.
+fn write_tone(path: &PathBuf, seconds: f32) -> Vec<f32> {
+    let spec = hound::WavSpec {
+        channels: 2,
+        sample_rate: SR as u32,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(path, spec).unwrap();
+    let mut samples = Vec::new();
+    for i in 0..(seconds * SR) as usize {
+        let t = i as f32 / SR;
+        for hz in [220.0, 330.0] {
+            let value = ((TAU * hz * t).sin() * 0.5 * 32_767.0) as i16;
+            writer.write_sample(value).unwrap();
+            samples.push(value as f32 / 32_767.0);
+        }
+    }
+    writer.finalize().unwrap();
+    samples
+}
+
+unsafe fn routed_rack(engine: *mut GooeyEngine) -> u32 {
+    let rack = gooey_engine_sampler_register(engine) as u32;
+    let source = gooey_engine_sampler_get_source_id(engine, rack);
+    assert!(gooey_engine_mixer_route_source(engine, source, 3));
+    rack
+}
+
+/// Render `frames` in blocks, pausing between blocks so the prefetch thread
+/// keeps up the way it would against a real-time audio callback.
+unsafe fn render_paced(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    let mut output = vec![0.0; frames * 2];
+    for block in output.chunks_mut(BLOCK * 2) {
+        gooey_engine_render(engine, block.as_mut_ptr(), (block.len() / 2) as u32);
+        std::thread::sleep(Duration::from_millis(2));
+    }
+    output
+}
+
+#[test]
+fn streamed_slot_matches_in_memory_playback() {
+    let path = temp_wav();
+    let pcm = write_tone(&path, 3.0);
+    let frames = pcm.len() / 2;
+    let c_path = CString::new(path.to_str().unwrap()).unwrap();
+    unsafe {
+        let memory = gooey_engine_new(SR);
+        let memory_rack = routed_rack(memory);
+        assert!(gooey_engine_sampler_set_slot_buffer(
+            memory,
+            memory_rack,
+            0,
+            pcm.as_ptr(),
+            frames as u32,
+            2,
+            SR
+        ));
+
+        let streamed = gooey_engine_new(SR);
+        let rack = routed_rack(streamed);
+        assert!(gooey_engine_sampler_stream_slot_file(
+            streamed,
+            rack,
+            0,
+            c_path.as_ptr(),
+            false
+        ));
+        assert!(gooey_engine_sampler_slot_is_streamed(streamed, rack, 0));
+        assert!(gooey_engine_sampler_slot_is_loaded(streamed, rack, 0));
+        assert_eq!(
+            gooey_engine_sampler_slot_frames(streamed, rack, 0),
+            frames as u32
+        );
+        assert_eq!(gooey_engine_sampler_slot_channels(streamed, rack, 0), 2);
+        // Streams don't stretch or slice
+        assert!(!gooey_engine_sampler_set_slot_pitch(streamed, rack, 0, 3.0));
+        assert!(!gooey_engine_sampler_slice_equal(streamed, rack, 0, 4));
+
+        assert!(gooey_engine_sampler_trigger(memory, memory_rack, 0, 1.0));
+        assert!(gooey_engine_sampler_trigger(streamed, rack, 0, 1.0));
+        let expected = render_paced(memory, frames + BLOCK);
+        let got = render_paced(streamed, frames + BLOCK);
+
+        assert_eq!(
+            gooey_engine_sampler_get_stream_underruns(streamed, rack, 0),
+            0
+        );
+        // Both fade in identically; only the in-memory voice fades out
+        let compare = (frames - 64) * 2;
+        for (i, (a, b)) in expected[..compare].iter().zip(&got[..compare]).enumerate() {
+            assert!((a - b).abs() < 1e-4, "sample {i}: {a} vs {b}");
+        }
+        // Silent apart from the output DC blocker settling
+        assert!(got[frames * 2..].iter().all(|sample| sample.abs() < 0.01));
+
+        gooey_engine_free(memory);
+        gooey_engine_free(streamed);
+    }
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn looping_stream_repeats_until_stopped() {
+    let path = temp_wav();
+    write_tone(&path, 0.5);
+    let c_path = CString::new(path.to_str().unwrap()).unwrap();
+    unsafe {
+        let engine = gooey_engine_new(SR);
+        let rack = routed_rack(engine);
+        assert!(gooey_engine_sampler_stream_slot_file(
+            engine,
+            rack,
+            0,
+            c_path.as_ptr(),
+            true
+        ));
+        assert!(gooey_engine_sampler_trigger(engine, rack, 0, 1.0));
+        let out = render_paced(engine, (1.6 * SR) as usize);
+        let tail = &out[(1.5 * SR) as usize * 2..];
+        assert!(tail.iter().any(|sample| sample.abs() > 0.1));
+        assert_eq!(
+            gooey_engine_sampler_get_stream_underruns(engine, rack, 0),
+            0
+        );
+
+        gooey_engine_sequencer_stop(engine);
+        let out = render_paced(engine, BLOCK);
+        assert!(out.iter().all(|sample| sample.abs() < 0.01));
+
+        // Loading a buffer over the slot replaces the stream
+        let pcm = [0.5_f32; 64];
+        assert!(gooey_engine_sampler_set_slot_buffer(
+            engine,
+            rack,
+            0,
+            pcm.as_ptr(),
+            64,
+            1,
+            SR
+        ));
+        assert!(!gooey_engine_sampler_slot_is_streamed(engine, rack, 0));
+        gooey_engine_free(engine);
+    }
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn missing_file_is_rejected() {
+    let path = CString::new("/nonexistent/gooey/loop.wav").unwrap();
+    unsafe {
+        let engine = gooey_engine_new(SR);
+        let rack = routed_rack(engine);
+        assert!(!gooey_engine_sampler_stream_slot_file(
+            engine,
+            rack,
+            0,
+            path.as_ptr(),
+            false
+        ));
+        assert!(!gooey_engine_sampler_stream_slot_file(
+            engine,
+            rack,
+            0,
+            std::ptr::null(),
+            false
+        ));
+        assert!(!gooey_engine_sampler_slot_is_loaded(engine, rack, 0));
+        gooey_engine_free(engine);
+    }
+}
@@ -0,0 +1,103 @@
+//! Sampler slots following the tempo and transposing independently of speed.
+
+use gooey::ffi::*;
+use std::f32::consts::TAU;
+
+const SR: f32 = 44_100.0;
+
+fn render(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    let mut output = vec![0.0; frames * 2];
+    unsafe { gooey_engine_render(engine, output.as_mut_ptr(), frames as u32) };
+    output
+}
+
+/// Left channel of interleaved stereo.
+fn left(samples: &[f32]) -> Vec<f32> {
+    samples.chunks_exact(2).map(|frame| frame[0]).collect()
+}
+
+/// Frames until the last audible sample.
+fn sounding_frames(samples: &[f32]) -> usize {
+    samples
+        .iter()
+        .rposition(|sample| sample.abs() > 1e-3)
+        .map_or(0, |i| i + 1)
+}
+
+fn crossings(samples: &[f32]) -> usize {
+    samples
+        .windows(2)
+        .filter(|w| (w[0] < 0.0) != (w[1] < 0.0))
+        .count()
+}
+
+/// A rack with one second of a 220 Hz sine in slot 0, routed to a track.
+unsafe fn sine_rack() -> (*mut GooeyEngine, u32) {
+    let engine = gooey_engine_new(SR);
+    gooey_engine_set_bpm(engine, 120.0);
+    let rack = gooey_engine_sampler_register(engine) as u32;
+    let source = gooey_engine_sampler_get_source_id(engine, rack);
+    assert!(gooey_engine_mixer_route_source(engine, source, 3));
+    let pcm: Vec<f32> = (0..SR as usize)
+        .map(|i| (TAU * 220.0 * i as f32 / SR).sin() * 0.5)
+        .collect();
+    assert!(gooey_engine_sampler_set_slot_buffer(
+        engine,
+        rack,
+        0,
+        pcm.as_ptr(),
+        pcm.len() as u32,
+        1,
+        SR
+    ));
+    (engine, rack)
+}
+
+#[test]
+fn tempo_following_slot_stretches_with_the_bpm() {
+    unsafe {
+        let (engine, rack) = sine_rack();
+        assert_eq!(gooey_engine_sampler_get_slot_tempo(engine, rack, 0), 0.0);
+        assert!(!gooey_engine_sampler_set_slot_tempo(engine, rack, 0, -1.0));
+        assert!(!gooey_engine_sampler_set_slot_tempo(engine, rack, 1, 120.0));
+        assert!(gooey_engine_sampler_set_slot_tempo(engine, rack, 0, 120.0));
+        assert_eq!(gooey_engine_sampler_get_slot_tempo(engine, rack, 0), 120.0);
+
+        // Half the tempo: the loop lasts twice as long at the same pitch
+        gooey_engine_set_bpm(engine, 60.0);
+        assert!(gooey_engine_sampler_trigger(engine, rack, 0, 1.0));
+        let out = left(&render(engine, 3 * SR as usize));
+        let length = sounding_frames(&out) as f32 / SR;
+        assert!((length - 2.0).abs() < 0.05, "length {length}s");
+        let hz = crossings(&out[..2 * SR as usize]) as f32 / 4.0;
+        assert!((hz - 220.0).abs() < 10.0, "{hz} Hz");
+
+        // Back to the source tempo: played as loaded
+        gooey_engine_set_bpm(engine, 120.0);
+        assert!(gooey_engine_sampler_trigger(engine, rack, 0, 1.0));
+        let out = left(&render(engine, 2 * SR as usize));
+        let length = sounding_frames(&out) as f32 / SR;
+        assert!((length - 1.0).abs() < 0.05, "length {length}s");
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn pitch_shift_keeps_the_length() {
+    unsafe {
+        let (engine, rack) = sine_rack();
+        assert!(gooey_engine_sampler_set_slot_pitch(engine, rack, 0, 12.0));
+        assert_eq!(gooey_engine_sampler_get_slot_pitch(engine, rack, 0), 12.0);
+        assert!(gooey_engine_sampler_set_slot_pitch(engine, rack, 0, 40.0));
+        assert_eq!(gooey_engine_sampler_get_slot_pitch(engine, rack, 0), 24.0);
+        assert!(gooey_engine_sampler_set_slot_pitch(engine, rack, 0, 12.0));
+
+        assert!(gooey_engine_sampler_trigger(engine, rack, 0, 1.0));
+        let out = left(&render(engine, 2 * SR as usize));
+        let length = sounding_frames(&out) as f32 / SR;
+        assert!((length - 1.0).abs() < 0.05, "length {length}s");
+        let hz = crossings(&out[..SR as usize / 2]) as f32;
+        assert!((hz - 440.0).abs() < 20.0, "{hz} Hz");
+        gooey_engine_free(engine);
+    }
+}
@@ -0,0 +1,226 @@
+//! Tests for selectable saturation models over FFI.
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+
+fn render(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    let mut buf = vec![0.0_f32; frames * 2];
+    unsafe { gooey_engine_render(engine, buf.as_mut_ptr(), frames as u32) };
+    buf
+}
+
+/// Render a hot snare hit through `engine` after `setup`.
+fn snare_hit(setup: impl Fn(*mut GooeyEngine)) -> Vec<f32> {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    setup(engine);
+    unsafe { gooey_engine_trigger_instrument(engine, INSTRUMENT_SNARE) };
+    let out = render(engine, 4096);
+    unsafe { gooey_engine_free(engine) };
+    out
+}
+
+#[test]
+fn instrument_overdrive_model_is_selectable_where_supported() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        assert_eq!(
+            gooey_engine_get_instrument_saturator_model(engine, INSTRUMENT_SNARE),
+            SATURATOR_MODEL_SOFT
+        );
+        assert_eq!(
+            gooey_engine_set_instrument_saturator_model(
+                engine,
+                INSTRUMENT_SNARE,
+                SATURATOR_MODEL_FOLDBACK
+            ),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_get_instrument_saturator_model(engine, INSTRUMENT_SNARE),
+            SATURATOR_MODEL_FOLDBACK
+        );
+
+        assert_eq!(
+            gooey_engine_get_instrument_saturator_model(engine, INSTRUMENT_KICK),
+            SATURATOR_MODEL_NONE
+        );
+        assert_eq!(
+            gooey_engine_set_instrument_saturator_model(
+                engine,
+                INSTRUMENT_KICK,
+                SATURATOR_MODEL_TAPE
+            ),
+            GooeyResult::InvalidInstrument
+        );
+        assert_eq!(
+            gooey_engine_set_instrument_saturator_model(engine, INSTRUMENT_BASS, 6),
+            GooeyResult::InvalidValue
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn instrument_model_changes_the_overdriven_sound() {
+    let drive = |engine| unsafe {
+        gooey_engine_set_snare_param(engine, SNARE_PARAM_OVERDRIVE, 1.0);
+    };
+    let soft = snare_hit(drive);
+    let hard = snare_hit(|engine| {
+        drive(engine);
+        unsafe {
+            gooey_engine_set_instrument_saturator_model(
+                engine,
+                INSTRUMENT_SNARE,
+                SATURATOR_MODEL_HARD_CLIP,
+            );
+        }
+    });
+    assert!(soft.iter().zip(&hard).any(|(a, b)| (a - b).abs() > 1e-4));
+}
+
+#[test]
+fn global_saturation_model_param() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        let get = || {
+            gooey_engine_get_global_effect_param(engine, EFFECT_SATURATION, SATURATION_PARAM_MODEL)
+        };
+        assert_eq!(get(), SATURATOR_MODEL_TUBE as f32);
+        assert_eq!(
+            gooey_engine_set_global_effect_param(
+                engine,
+                EFFECT_SATURATION,
+                SATURATION_PARAM_MODEL,
+                SATURATOR_MODEL_DIODE as f32
+            ),
+            GooeyResult::Ok
+        );
+        render(engine, 1);
+        assert_eq!(get(), SATURATOR_MODEL_DIODE as f32);
+        assert_eq!(
+            gooey_engine_set_global_effect_param(
+                engine,
+                EFFECT_SATURATION,
+                SATURATION_PARAM_MODEL,
+                9.0
+            ),
+            GooeyResult::InvalidValue
+        );
+        gooey_engine_free(engine);
+    }
+
+    let saturated = |model: u32| {
+        snare_hit(move |engine| unsafe {
+            gooey_engine_set_global_effect_enabled(engine, EFFECT_SATURATION, true);
+            for (param, value) in [
+                (SATURATION_PARAM_DRIVE, 1.0),
+                (SATURATION_PARAM_MIX, 1.0),
+                (SATURATION_PARAM_MODEL, model as f32),
+            ] {
+                gooey_engine_set_global_effect_param(engine, EFFECT_SATURATION, param, value);
+            }
+        })
+    };
+    let tube = saturated(SATURATOR_MODEL_TUBE);
+    let fold = saturated(SATURATOR_MODEL_FOLDBACK);
+    assert!(tube.iter().zip(&fold).any(|(a, b)| (a - b).abs() > 1e-4));
+}
+
+fn rms(buf: &[f32]) -> f32 {
+    (buf.iter().map(|s| s * s).sum::<f32>() / buf.len() as f32).sqrt()
+}
+
+#[test]
+fn instrument_overdrive_auto_gain_where_supported() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        assert!(!gooey_engine_get_instrument_overdrive_auto_gain(
+            engine,
+            INSTRUMENT_BASS
+        ));
+        assert_eq!(
+            gooey_engine_set_instrument_overdrive_auto_gain(engine, INSTRUMENT_BASS, true),
+            GooeyResult::Ok
+        );
+        render(engine, 1);
+        assert!(gooey_engine_get_instrument_overdrive_auto_gain(
+            engine,
+            INSTRUMENT_BASS
+        ));
+        assert_eq!(
+            gooey_engine_set_instrument_overdrive_auto_gain(engine, INSTRUMENT_KICK, true),
+            GooeyResult::InvalidInstrument
+        );
+        assert!(!gooey_engine_get_instrument_overdrive_auto_gain(
+            engine,
+            INSTRUMENT_KICK
+        ));
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn instrument_overdrive_auto_gain_holds_level_across_drive() {
+    let hit_rms = |overdrive: f32, auto_gain: bool| {
+        rms(&snare_hit(move |engine| unsafe {
+            gooey_engine_set_snare_param(engine, SNARE_PARAM_OVERDRIVE, overdrive);
+            gooey_engine_set_instrument_overdrive_auto_gain(engine, INSTRUMENT_SNARE, auto_gain);
+            render(engine, 4096);
+        }))
+    };
+    let spread = |auto_gain| 20.0 * (hit_rms(1.0, auto_gain) / hit_rms(0.2, auto_gain)).log10();
+    let (plain, matched) = (spread(false), spread(true));
+    assert!(
+        matched.abs() < plain.abs() - 1.0,
+        "{matched:.2} dB vs {plain:.2} dB"
+    );
+}
+
+#[test]
+fn global_saturation_auto_gain_param() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        let get = || {
+            gooey_engine_get_global_effect_param(
+                engine,
+                EFFECT_SATURATION,
+                SATURATION_PARAM_AUTO_GAIN,
+            )
+        };
+        assert_eq!(get(), 0.0);
+        assert_eq!(
+            gooey_engine_set_global_effect_param(
+                engine,
+                EFFECT_SATURATION,
+                SATURATION_PARAM_AUTO_GAIN,
+                1.0
+            ),
+            GooeyResult::Ok
+        );
+        render(engine, 1);
+        assert_eq!(get(), 1.0);
+        gooey_engine_free(engine);
+    }
+
+    let hit_rms = |drive: f32, auto_gain: f32| {
+        rms(&snare_hit(move |engine| unsafe {
+            gooey_engine_set_global_effect_enabled(engine, EFFECT_SATURATION, true);
+            for (param, value) in [
+                (SATURATION_PARAM_DRIVE, drive),
+                (SATURATION_PARAM_MIX, 1.0),
+                (SATURATION_PARAM_AUTO_GAIN, auto_gain),
+            ] {
+                gooey_engine_set_global_effect_param(engine, EFFECT_SATURATION, param, value);
+            }
+            render(engine, 4096);
+        }))
+    };
+    let spread = |auto_gain| 20.0 * (hit_rms(1.0, auto_gain) / hit_rms(0.1, auto_gain)).log10();
+    let (plain, matched) = (spread(0.0), spread(1.0));
+    assert!(
+        matched.abs() < plain.abs() - 1.0,
+        "{matched:.2} dB vs {plain:.2} dB"
+    );
+}
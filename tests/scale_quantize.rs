@@ -0,0 +1,91 @@
+//! Tests for snapping sequenced per-step notes to a key.
+
+use gooey::ffi::*;
+
+/// Play a single kick step carrying `note` and return the kick frequency the
+/// step left behind.
+fn kick_freq_after_step(note: u8, quantize: Option<(u32, u32)>) -> f32 {
+    let engine = gooey_engine_new(44_100.0);
+    let mut buf = vec![0.0_f32; 512 * 2];
+    unsafe {
+        gooey_engine_sequencer_set_instrument_step(engine, INSTRUMENT_KICK, 0, true);
+        gooey_engine_sequencer_set_instrument_step_note(engine, INSTRUMENT_KICK, 0, note);
+        if let Some((root, scale)) = quantize {
+            assert_eq!(
+                gooey_engine_set_scale_quantize(engine, root, scale),
+                GooeyResult::Ok
+            );
+        }
+        gooey_engine_sequencer_start(engine);
+        gooey_engine_render(engine, buf.as_mut_ptr(), 512);
+        let freq = gooey_engine_get_kick_param(engine, KICK_PARAM_FREQUENCY);
+        gooey_engine_free(engine);
+        freq
+    }
+}
+
+#[test]
+fn quantized_step_note_matches_in_scale_note() {
+    const A: u32 = 9;
+    // C#2 (37) is not in A minor; it snaps down to C2 (36).
+    let quantized = kick_freq_after_step(37, Some((A, SCALE_MINOR)));
+    let in_scale = kick_freq_after_step(36, None);
+    let raw = kick_freq_after_step(37, None);
+    assert_eq!(quantized, in_scale);
+    assert_ne!(quantized, raw);
+}
+
+#[test]
+fn scale_quantize_getters_and_validation() {
+    let engine = gooey_engine_new(44_100.0);
+    unsafe {
+        assert_eq!(
+            gooey_engine_get_scale_quantize_root(engine),
+            SCALE_QUANTIZE_OFF
+        );
+        assert_eq!(
+            gooey_engine_get_scale_quantize_scale(engine),
+            SCALE_QUANTIZE_OFF
+        );
+
+        assert_eq!(
+            gooey_engine_set_scale_quantize(engine, 2, SCALE_DORIAN),
+            GooeyResult::Ok
+        );
+        assert_eq!(gooey_engine_get_scale_quantize_root(engine), 2);
+        assert_eq!(gooey_engine_get_scale_quantize_scale(engine), SCALE_DORIAN);
+
+        assert_eq!(
+            gooey_engine_set_scale_quantize(engine, 12, SCALE_MAJOR),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_set_scale_quantize(engine, 0, SCALE_COUNT),
+            GooeyResult::InvalidValue
+        );
+        // Failed calls leave the previous key in place.
+        assert_eq!(gooey_engine_get_scale_quantize_scale(engine), SCALE_DORIAN);
+
+        gooey_engine_clear_scale_quantize(engine);
+        assert_eq!(
+            gooey_engine_get_scale_quantize_root(engine),
+            SCALE_QUANTIZE_OFF
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn quantize_note_helper() {
+    // C major pentatonic: F4 (65) -> E4 (64), B4 (71) -> C5 (72)
+    assert_eq!(
+        gooey_engine_quantize_note(65, 0, SCALE_MAJOR_PENTATONIC),
+        64
+    );
+    assert_eq!(
+        gooey_engine_quantize_note(71, 0, SCALE_MAJOR_PENTATONIC),
+        72
+    );
+    assert_eq!(gooey_engine_quantize_note(61, 0, SCALE_CHROMATIC), 61);
+    assert_eq!(gooey_engine_quantize_note(61, 0, SCALE_COUNT), 61);
+}
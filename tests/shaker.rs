@@ -0,0 +1,108 @@
+//! Tests for the shaker voice over FFI.
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+/// One sixteenth at 120 BPM.
+const STEP_FRAMES: usize = 5512;
+
+fn render(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    let mut buf = vec![0.0_f32; frames * 2];
+    unsafe { gooey_engine_render(engine, buf.as_mut_ptr(), frames as u32) };
+    buf
+}
+
+fn energy(samples: &[f32]) -> f32 {
+    samples.iter().map(|s| s * s).sum()
+}
+
+/// Render one shaker hit at `velocity` after `setup`.
+fn hit(velocity: f32, setup: impl Fn(*mut GooeyEngine)) -> Vec<f32> {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    setup(engine);
+    render(engine, 64);
+    unsafe { gooey_engine_trigger_instrument_with_velocity(engine, INSTRUMENT_SHAKER, velocity) };
+    let out = render(engine, 4096);
+    unsafe { gooey_engine_free(engine) };
+    out
+}
+
+#[test]
+fn trigger_sounds_and_params_round_trip() {
+    let out = hit(1.0, |_| {});
+    assert!(energy(&out) > 0.05, "{}", energy(&out));
+    assert!(out.iter().all(|s| s.is_finite()));
+
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        assert_eq!(
+            gooey_engine_set_shaker_param(engine, SHAKER_PARAM_DENSITY, 0.3),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_set_shaker_param(engine, SHAKER_PARAM_ACCENT, 0.9),
+            GooeyResult::Ok
+        );
+        render(engine, 1);
+        assert!((gooey_engine_get_shaker_param(engine, SHAKER_PARAM_DENSITY) - 0.3).abs() < 1e-6);
+        assert!((gooey_engine_get_shaker_param(engine, SHAKER_PARAM_ACCENT) - 0.9).abs() < 1e-6);
+        assert_eq!(
+            gooey_engine_set_shaker_param(engine, gooey_engine_shaker_param_count(), 0.5),
+            GooeyResult::InvalidParam
+        );
+        assert_eq!(
+            gooey_engine_load_shaker_preset(engine, SHAKER_PRESET_EGG),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_load_shaker_preset(engine, 4),
+            GooeyResult::InvalidValue
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn accented_steps_stand_out_in_a_pattern() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        gooey_engine_set_bpm(engine, 120.0);
+        gooey_engine_set_shaker_param(engine, SHAKER_PARAM_DECAY, 0.1);
+        // Sixteenths with the off-beat eighths accented
+        for step in 0..16 {
+            let velocity = if step % 4 == 2 { 1.0 } else { 0.4 };
+            gooey_engine_sequencer_set_instrument_step_with_velocity(
+                engine,
+                INSTRUMENT_SHAKER,
+                step,
+                true,
+                velocity,
+            );
+        }
+        gooey_engine_sequencer_start(engine);
+        let out = render(engine, 4 * STEP_FRAMES);
+        gooey_engine_free(engine);
+
+        let step = |i: usize| energy(&out[i * STEP_FRAMES * 2..(i + 1) * STEP_FRAMES * 2]);
+        assert!(step(2) > step(1) * 4.0, "{} vs {}", step(2), step(1));
+        assert!(step(2) > step(3) * 4.0, "{} vs {}", step(2), step(3));
+    }
+}
+
+#[test]
+fn accent_changes_more_than_the_level() {
+    let accent = |amount: f32| {
+        move |engine| unsafe {
+            gooey_engine_set_shaker_param(engine, SHAKER_PARAM_ACCENT, amount);
+        }
+    };
+    // Scaled back to the same energy, a soft stroke with accent still
+    // differs from one without
+    let plain = hit(0.3, accent(0.0));
+    let accented = hit(0.3, accent(1.0));
+    let scale = (energy(&plain) / energy(&accented)).sqrt();
+    assert!(plain
+        .iter()
+        .zip(&accented)
+        .any(|(a, b)| (a - b * scale).abs() > 1e-3));
+}
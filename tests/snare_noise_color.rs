@@ -0,0 +1,102 @@
+//! Tests for the snare's noise color and velvet crack over FFI.
+
+use gooey::ffi::*;
+use gooey::test_utils::{peak, rms, zero_crossings};
+
+const SAMPLE_RATE: f32 = 44_100.0;
+
+/// Render `frames` frames and return the left channel.
+fn render(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    let mut buf = vec![0.0_f32; frames * 2];
+    unsafe { gooey_engine_render(engine, buf.as_mut_ptr(), frames as u32) };
+    buf.iter().step_by(2).copied().collect()
+}
+
+/// One snare hit after `params` are set and have settled.
+fn hit(params: &[(u32, f32)]) -> Vec<f32> {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        for &(param, value) in params {
+            gooey_engine_set_snare_param(engine, param, value);
+        }
+        render(engine, 4096);
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_SNARE);
+        let out = render(engine, 4096);
+        gooey_engine_free(engine);
+        out
+    }
+}
+
+/// The noise body alone (no tone or crack), through a lowpass.
+fn noise_body(color: f32) -> Vec<f32> {
+    hit(&[
+        (SNARE_PARAM_TONAL, 0.0),
+        (SNARE_PARAM_BRIGHTNESS, 0.0),
+        (SNARE_PARAM_XFADE, 1.0),
+        (SNARE_PARAM_FILTER_TYPE, 0.0),
+        (SNARE_PARAM_FILTER_CUTOFF, 0.5),
+        (SNARE_PARAM_NOISE_COLOR, color),
+    ])
+}
+
+fn crest(samples: &[f32]) -> f32 {
+    peak(samples) / rms(samples)
+}
+
+#[test]
+fn darker_colors_cross_zero_less_often() {
+    let white = zero_crossings(&noise_body(0.0));
+    let pink = zero_crossings(&noise_body(0.5));
+    let brown = zero_crossings(&noise_body(1.0));
+    assert!(white > pink && pink > brown, "{white} {pink} {brown}");
+}
+
+#[test]
+fn velvet_crack_is_sparser_than_white() {
+    let crack = |velvet: f32| {
+        hit(&[
+            (SNARE_PARAM_TONAL, 0.0),
+            (SNARE_PARAM_NOISE, 0.0),
+            (SNARE_PARAM_CRACK_VELVET, velvet),
+        ])
+    };
+    let white = crack(0.0);
+    let velvet = crack(1.0);
+    assert_ne!(white, velvet);
+    let (white, velvet) = (crest(&white[32..288]), crest(&velvet[32..288]));
+    assert!(velvet > white * 1.4, "crest {velvet} vs {white}");
+}
+
+#[test]
+fn color_and_velvet_round_trip_and_color_is_modulatable() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        assert_eq!(
+            gooey_engine_get_snare_param(engine, SNARE_PARAM_NOISE_COLOR),
+            0.0
+        );
+        assert_eq!(
+            gooey_engine_get_snare_param(engine, SNARE_PARAM_CRACK_VELVET),
+            0.0
+        );
+        gooey_engine_set_snare_param(engine, SNARE_PARAM_NOISE_COLOR, 0.7);
+        gooey_engine_set_snare_param(engine, SNARE_PARAM_CRACK_VELVET, 1.0);
+        assert_eq!(
+            gooey_engine_get_snare_param(engine, SNARE_PARAM_NOISE_COLOR),
+            0.7
+        );
+        assert_eq!(
+            gooey_engine_get_snare_param(engine, SNARE_PARAM_CRACK_VELVET),
+            1.0
+        );
+        assert_eq!(
+            gooey_engine_snare_param_count(),
+            SNARE_PARAM_CRACK_VELVET + 1
+        );
+
+        let route =
+            gooey_engine_add_lfo_route(engine, 0, INSTRUMENT_SNARE, SNARE_PARAM_NOISE_COLOR, 0.5);
+        assert_ne!(route, LFO_INVALID);
+        gooey_engine_free(engine);
+    }
+}
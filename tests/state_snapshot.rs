@@ -0,0 +1,133 @@
+//! Tests for binary engine snapshots (`gooey_engine_export_state` /
+//! `gooey_engine_import_state`).
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+
+unsafe fn export(engine: *mut GooeyEngine) -> Vec<u8> {
+    let mut length = 0;
+    let bytes = gooey_engine_export_state(engine, &mut length);
+    assert!(!bytes.is_null());
+    let copy = std::slice::from_raw_parts(bytes, length as usize).to_vec();
+    gooey_engine_free_state(bytes, length);
+    copy
+}
+
+unsafe fn render(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    let mut buffer = vec![0.0; frames * 2];
+    gooey_engine_render(engine, buffer.as_mut_ptr(), frames as u32);
+    buffer
+}
+
+/// An engine with something changed in every part a snapshot covers.
+unsafe fn configured_engine() -> *mut GooeyEngine {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    gooey_engine_set_bpm(engine, 133.0);
+    gooey_engine_set_swing(engine, 0.6);
+    gooey_engine_set_master_gain(engine, 0.4);
+    gooey_engine_set_master_tuning(engine, 432.0, 2.0);
+    gooey_engine_set_seed(engine, 77);
+
+    gooey_engine_set_global_effect_enabled(engine, EFFECT_DELAY, true);
+    gooey_engine_set_global_effect_param(engine, EFFECT_DELAY, DELAY_PARAM_FEEDBACK, 0.7);
+    gooey_engine_move_effect(engine, EFFECT_DELAY, 0);
+
+    gooey_engine_set_snare_param(engine, SNARE_PARAM_DECAY, 0.2);
+    gooey_engine_set_channel_instrument_type(engine, INSTRUMENT_TOM, INSTRUMENT_COWBELL);
+    gooey_engine_set_channel_gain(engine, INSTRUMENT_KICK, -4.0);
+    gooey_engine_set_channel_pan(engine, INSTRUMENT_SNARE, 0.2);
+    gooey_engine_set_instrument_mute(engine, INSTRUMENT_HIHAT, true);
+
+    // Leave a hole in the slots so channel numbers have to survive
+    let first = gooey_engine_create_slot(engine, INSTRUMENT_KICK);
+    let second = gooey_engine_create_slot(engine, INSTRUMENT_SHAKER) as u32;
+    gooey_engine_destroy_slot(engine, first as u32);
+    gooey_engine_set_channel_param(engine, second, SHAKER_PARAM_COLOR, 0.9);
+
+    for step in [0, 4, 8, 12] {
+        gooey_engine_sequencer_set_instrument_step_with_velocity(
+            engine,
+            INSTRUMENT_KICK,
+            step,
+            true,
+            0.8,
+        );
+        gooey_engine_sequencer_set_instrument_step(engine, second, step + 2, true);
+    }
+    gooey_engine_sequencer_set_instrument_step_note(engine, INSTRUMENT_BASS, 3, 40);
+    gooey_engine_sequencer_set_instrument_step_gate(engine, INSTRUMENT_BASS, 3, 1.5);
+    engine
+}
+
+#[test]
+fn import_reproduces_the_exported_engine() {
+    unsafe {
+        let source = configured_engine();
+        render(source, 256);
+        let state = export(source);
+
+        let target = gooey_engine_new(SAMPLE_RATE);
+        // A slot the snapshot doesn't have must go
+        gooey_engine_create_slot(target, INSTRUMENT_BASS);
+        assert_eq!(
+            gooey_engine_import_state(target, state.as_ptr(), state.len() as u32),
+            GooeyResult::Ok
+        );
+        render(target, 256);
+        assert_eq!(export(target), state);
+
+        assert_eq!(gooey_engine_get_bpm(target), 133.0);
+        assert_eq!(gooey_engine_get_master_a4(target), 432.0);
+        assert_eq!(
+            gooey_engine_get_channel_instrument_type(target, INSTRUMENT_TOM),
+            INSTRUMENT_COWBELL
+        );
+        assert!(!gooey_engine_channel_exists(target, INSTRUMENT_COUNT));
+        assert!(gooey_engine_channel_exists(target, INSTRUMENT_COUNT + 1));
+        assert_eq!(
+            gooey_engine_sequencer_get_instrument_step_note(target, INSTRUMENT_BASS, 3),
+            40
+        );
+
+        // Both play the same groove from here on
+        gooey_engine_sequencer_start(source);
+        gooey_engine_sequencer_start(target);
+        assert_eq!(render(source, 8192), render(target, 8192));
+
+        gooey_engine_free(source);
+        gooey_engine_free(target);
+    }
+}
+
+#[test]
+fn damaged_snapshots_change_nothing() {
+    unsafe {
+        let source = configured_engine();
+        let mut state = export(source);
+        gooey_engine_free(source);
+
+        let target = gooey_engine_new(SAMPLE_RATE);
+        let before = export(target);
+        state.truncate(state.len() - 1);
+        assert_eq!(
+            gooey_engine_import_state(target, state.as_ptr(), state.len() as u32),
+            GooeyResult::InvalidValue
+        );
+        let garbage = [0x47_u8; 64];
+        assert_eq!(
+            gooey_engine_import_state(target, garbage.as_ptr(), garbage.len() as u32),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(export(target), before);
+
+        assert_eq!(
+            gooey_engine_import_state(target, std::ptr::null(), 0),
+            GooeyResult::NullPointer
+        );
+        let mut length = 0;
+        assert!(gooey_engine_export_state(std::ptr::null_mut(), &mut length).is_null());
+        assert!(gooey_engine_export_state(target, std::ptr::null_mut()).is_null());
+        gooey_engine_free(target);
+    }
+}
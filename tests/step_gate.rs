@@ -0,0 +1,67 @@
+//! Tests for per-step gate lengths releasing sequenced notes.
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+/// One 16th note at the default 120 BPM
+const STEP: usize = 5_512;
+
+fn energy(samples: &[f32]) -> f32 {
+    samples.iter().map(|s| s * s).sum()
+}
+
+/// Play one long bass note on step 0 with the given gate (0 = none) and
+/// return six steps of the left channel.
+fn bass_note(gate: f32) -> Vec<f32> {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    let mut buf = vec![0.0_f32; 6 * STEP * 2];
+    unsafe {
+        gooey_engine_set_bass_param(engine, BASS_PARAM_AMP_DECAY, 1.0);
+        gooey_engine_sequencer_set_instrument_step(engine, INSTRUMENT_BASS, 0, true);
+        gooey_engine_sequencer_set_instrument_step_gate(engine, INSTRUMENT_BASS, 0, gate);
+        gooey_engine_sequencer_start(engine);
+        gooey_engine_render(engine, buf.as_mut_ptr(), 6 * STEP as u32);
+        gooey_engine_free(engine);
+    }
+    buf.iter().step_by(2).copied().collect()
+}
+
+#[test]
+fn step_gate_round_trips_and_clears() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        let get =
+            |step| gooey_engine_sequencer_get_instrument_step_gate(engine, INSTRUMENT_BASS, step);
+        assert_eq!(get(2), 0.0);
+        gooey_engine_sequencer_set_instrument_step_gate(engine, INSTRUMENT_BASS, 2, 0.75);
+        assert_eq!(get(2), 0.75);
+        gooey_engine_sequencer_set_instrument_step_gate(engine, INSTRUMENT_BASS, 2, 99.0);
+        assert_eq!(get(2), 16.0);
+        gooey_engine_sequencer_set_instrument_step_gate(engine, INSTRUMENT_BASS, 2, 0.0);
+        assert_eq!(get(2), 0.0);
+        gooey_engine_free(engine);
+        assert_eq!(
+            gooey_engine_sequencer_get_instrument_step_gate(std::ptr::null(), INSTRUMENT_BASS, 0),
+            0.0
+        );
+    }
+}
+
+#[test]
+fn gated_bass_step_is_released_after_its_gate() {
+    let one_shot = bass_note(0.0);
+    let gated = bass_note(0.5);
+
+    // Identical until the gate closes half a step in
+    assert_eq!(&one_shot[..STEP / 2], &gated[..STEP / 2]);
+
+    // The gated note fades over its release (a tenth of the 4 s decay); the
+    // ungated one is still ringing once that is over
+    let fading = STEP..2 * STEP;
+    assert!(energy(&gated[fading.clone()]) < 0.5 * energy(&one_shot[fading]));
+    let later = 4 * STEP..6 * STEP;
+    let ringing = energy(&one_shot[later.clone()]);
+    let released = energy(&gated[later]);
+    assert!(ringing > 0.1, "one-shot tail {ringing}");
+    assert!(released < 1e-3 * ringing, "gated tail {released}");
+}
@@ -0,0 +1,111 @@
+// Integration tests for step layers: one sequencer step firing several hits
+
+use std::sync::{Arc, Mutex};
+
+use gooey::engine::{Engine, Instrument, Sequencer, STEP_MAX_LAYERS};
+
+const SAMPLE_RATE: f32 = 1000.0;
+
+/// Hits as (instrument, note, velocity), shared by every probe.
+type Log = Arc<Mutex<Vec<(&'static str, Option<u8>, f32)>>>;
+
+/// Silent instrument that logs its hits and the note set before each.
+struct Probe {
+    name: &'static str,
+    note: Option<u8>,
+    log: Log,
+}
+
+impl Instrument for Probe {
+    fn trigger_with_velocity(&mut self, _time: f64, velocity: f32) {
+        let note = self.note.take();
+        self.log.lock().unwrap().push((self.name, note, velocity));
+    }
+
+    fn tick(&mut self, _current_time: f64) -> f32 {
+        0.0
+    }
+
+    fn is_active(&self) -> bool {
+        false
+    }
+
+    fn set_midi_note(&mut self, note: u8) {
+        self.note = Some(note);
+    }
+}
+
+fn probe_engine(names: &[&'static str]) -> (Engine, Log) {
+    let log = Log::default();
+    let mut engine = Engine::new(SAMPLE_RATE);
+    for &name in names {
+        engine.add_instrument(
+            name,
+            Box::new(Probe {
+                name,
+                note: None,
+                log: Arc::clone(&log),
+            }),
+        );
+    }
+    (engine, log)
+}
+
+fn run(engine: &mut Engine, samples: usize) {
+    for index in 0..engine.sequencer_count() {
+        engine.sequencer_mut(index).unwrap().start();
+    }
+    for i in 0..samples {
+        engine.tick(i as f64 / SAMPLE_RATE as f64);
+    }
+}
+
+#[test]
+fn test_layered_hits_fire_from_one_lane() {
+    let (mut engine, log) = probe_engine(&["kick", "clap"]);
+    // Kick on steps 0 and 2; step 2 also claps
+    let mut sequencer =
+        Sequencer::with_pattern(120.0, SAMPLE_RATE, vec![true, false, true, false], "kick");
+    sequencer.set_step_velocity(2, 0.8);
+    assert!(sequencer.add_step_layer(2, "clap", None, 0.5));
+    engine.add_sequencer(sequencer);
+
+    // 125 samples per step; stop before the pattern wraps
+    run(&mut engine, 500);
+    let hits = log.lock().unwrap().clone();
+    assert_eq!(
+        hits,
+        [
+            ("kick", None, 1.0),
+            ("kick", None, 0.8),
+            ("clap", None, 0.4)
+        ]
+    );
+}
+
+#[test]
+fn test_layered_notes_play_a_chord() {
+    let (mut engine, log) = probe_engine(&["pad"]);
+    let mut sequencer = Sequencer::with_pattern(120.0, SAMPLE_RATE, vec![true], "pad");
+    sequencer.set_step_note(0, 60);
+    assert!(sequencer.add_step_layer(0, "pad", Some(64), 1.0));
+    assert!(sequencer.add_step_layer(0, "pad", Some(67), 1.0));
+    for _ in 2..STEP_MAX_LAYERS {
+        assert!(sequencer.add_step_layer(0, "pad", Some(72), 1.0));
+    }
+    assert!(!sequencer.add_step_layer(0, "pad", Some(76), 1.0));
+    assert!(!sequencer.add_step_layer(1, "pad", Some(76), 1.0));
+    assert_eq!(
+        sequencer.step_layers(0).take(2).collect::<Vec<_>>(),
+        [("pad", Some(64), 1.0), ("pad", Some(67), 1.0)]
+    );
+    engine.add_sequencer(sequencer);
+
+    run(&mut engine, 1);
+    let notes: Vec<_> = log.lock().unwrap().iter().map(|hit| hit.1).collect();
+    assert_eq!(notes, [Some(60), Some(64), Some(67), Some(72), Some(72)]);
+
+    // Cleared layers leave the step's own hit
+    engine.sequencer_mut(0).unwrap().clear_step_layers(0);
+    assert_eq!(engine.sequencer(0).unwrap().step_layers(0).count(), 0);
+}
@@ -0,0 +1,184 @@
+//! Tests for sequencer step pages over FFI.
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 48_000.0;
+
+fn render(engine: *mut GooeyEngine, frames: usize) {
+    let mut buf = vec![0.0_f32; frames * 2];
+    unsafe { gooey_engine_render(engine, buf.as_mut_ptr(), frames as u32) };
+}
+
+#[test]
+fn page_count_sets_the_pattern_length() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        assert_eq!(
+            gooey_engine_sequencer_get_instrument_page_count(engine, INSTRUMENT_KICK),
+            1
+        );
+        assert_eq!(
+            gooey_engine_sequencer_set_page_count(engine, SEQUENCER_PAGE_MAX),
+            GooeyResult::Ok
+        );
+        for instrument in [INSTRUMENT_KICK, INSTRUMENT_SNARE, INSTRUMENT_BASS] {
+            assert_eq!(
+                gooey_engine_sequencer_get_instrument_page_count(engine, instrument),
+                SEQUENCER_PAGE_MAX
+            );
+        }
+        assert_eq!(
+            gooey_engine_sequencer_set_instrument_page_count(engine, INSTRUMENT_SNARE, 2),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_sequencer_get_instrument_page_count(engine, INSTRUMENT_SNARE),
+            2
+        );
+
+        for pages in [0, SEQUENCER_PAGE_MAX + 1] {
+            assert_eq!(
+                gooey_engine_sequencer_set_page_count(engine, pages),
+                GooeyResult::InvalidValue
+            );
+        }
+        assert_eq!(
+            gooey_engine_sequencer_set_instrument_page_count(engine, 99, 2),
+            GooeyResult::InvalidInstrument
+        );
+        assert_eq!(
+            gooey_engine_sequencer_get_instrument_page_count(engine, 99),
+            0
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn page_steps_map_onto_the_long_pattern() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        gooey_engine_sequencer_set_instrument_page_count(engine, INSTRUMENT_KICK, 3);
+        assert_eq!(
+            gooey_engine_sequencer_set_instrument_page_step(
+                engine,
+                INSTRUMENT_KICK,
+                2,
+                3,
+                true,
+                0.7
+            ),
+            GooeyResult::Ok
+        );
+        assert!(gooey_engine_sequencer_get_instrument_step_enabled(
+            engine,
+            INSTRUMENT_KICK,
+            35
+        ));
+
+        let mut enabled = [true; SEQUENCER_PAGE_STEPS as usize];
+        let mut velocities = [1.0; SEQUENCER_PAGE_STEPS as usize];
+        assert_eq!(
+            gooey_engine_sequencer_get_instrument_page(
+                engine,
+                INSTRUMENT_KICK,
+                2,
+                enabled.as_mut_ptr(),
+                velocities.as_mut_ptr()
+            ),
+            GooeyResult::Ok
+        );
+        let on: Vec<usize> = (0..enabled.len()).filter(|&pad| enabled[pad]).collect();
+        assert_eq!(on, [3]);
+        assert_eq!(velocities[3], 0.7);
+        assert_eq!(
+            gooey_engine_sequencer_get_instrument_page(
+                engine,
+                INSTRUMENT_KICK,
+                1,
+                enabled.as_mut_ptr(),
+                core::ptr::null_mut()
+            ),
+            GooeyResult::Ok
+        );
+        assert!(enabled.iter().all(|&on| !on));
+
+        // Past the last page, or past the last pad of a page
+        assert_eq!(
+            gooey_engine_sequencer_set_instrument_page_step(
+                engine,
+                INSTRUMENT_KICK,
+                3,
+                0,
+                true,
+                1.0
+            ),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_sequencer_set_instrument_page_step(
+                engine,
+                INSTRUMENT_KICK,
+                0,
+                SEQUENCER_PAGE_STEPS,
+                true,
+                1.0
+            ),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_sequencer_get_instrument_page(
+                engine,
+                INSTRUMENT_KICK,
+                3,
+                enabled.as_mut_ptr(),
+                velocities.as_mut_ptr()
+            ),
+            GooeyResult::InvalidValue
+        );
+
+        // Shrinking drops the page that held the step
+        gooey_engine_sequencer_set_instrument_page_count(engine, INSTRUMENT_KICK, 2);
+        gooey_engine_sequencer_set_instrument_page_count(engine, INSTRUMENT_KICK, 3);
+        assert!(!gooey_engine_sequencer_get_instrument_step_enabled(
+            engine,
+            INSTRUMENT_KICK,
+            35
+        ));
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn current_page_follows_the_playhead() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        gooey_engine_sequencer_stop(engine);
+        gooey_engine_sequencer_set_page_count(engine, SEQUENCER_PAGE_MAX);
+        assert_eq!(gooey_engine_sequencer_get_current_page(engine, 0), -1);
+
+        gooey_engine_sequencer_set_instrument_page_count(engine, INSTRUMENT_SNARE, 2);
+        gooey_engine_sequencer_start(engine);
+        let mut pages = Vec::new();
+        for _ in 0..(9 * SAMPLE_RATE as usize / 512) {
+            render(engine, 512);
+            let step = gooey_engine_sequencer_get_step_with_lookahead(engine, 0);
+            let page = gooey_engine_sequencer_get_current_page(engine, 0);
+            assert_eq!(page, step / SEQUENCER_PAGE_STEPS as i32);
+            let snare_step = gooey_engine_sequencer_get_instrument_step_with_lookahead(
+                engine,
+                INSTRUMENT_SNARE,
+                0,
+            );
+            assert_eq!(
+                gooey_engine_sequencer_get_instrument_current_page(engine, INSTRUMENT_SNARE, 0),
+                snare_step / SEQUENCER_PAGE_STEPS as i32
+            );
+            if pages.last() != Some(&page) {
+                pages.push(page);
+            }
+        }
+        assert_eq!(pages, [0, 1, 2, 3, 0]);
+        gooey_engine_free(engine);
+    }
+}
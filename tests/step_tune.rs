@@ -0,0 +1,83 @@
+//! Tests for per-step tuning offsets (one tom lane playing several pitches).
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+/// One 16th note at the default 120 BPM
+const STEP: usize = 5_512;
+
+/// Zero crossings in the left channel of `frames`, a crude pitch measure.
+fn crossings(buf: &[f32]) -> usize {
+    let left: Vec<f32> = buf.iter().step_by(2).copied().collect();
+    left.windows(2)
+        .filter(|w| (w[0] < 0.0) != (w[1] < 0.0))
+        .count()
+}
+
+#[test]
+fn step_tune_round_trips_and_clamps() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        assert!(
+            gooey_engine_sequencer_get_instrument_step_tune(engine, INSTRUMENT_TOM, 0).is_nan()
+        );
+        gooey_engine_sequencer_set_instrument_step_tune(engine, INSTRUMENT_TOM, 0, -5.0);
+        assert_eq!(
+            gooey_engine_sequencer_get_instrument_step_tune(engine, INSTRUMENT_TOM, 0),
+            -5.0
+        );
+        gooey_engine_sequencer_set_instrument_step_tune(engine, INSTRUMENT_TOM, 1, 40.0);
+        assert_eq!(
+            gooey_engine_sequencer_get_instrument_step_tune(engine, INSTRUMENT_TOM, 1),
+            12.0
+        );
+        gooey_engine_sequencer_clear_instrument_step_tune(engine, INSTRUMENT_TOM, 0);
+        assert!(
+            gooey_engine_sequencer_get_instrument_step_tune(engine, INSTRUMENT_TOM, 0).is_nan()
+        );
+        gooey_engine_free(engine);
+
+        assert!(gooey_engine_sequencer_get_instrument_step_tune(
+            std::ptr::null(),
+            INSTRUMENT_TOM,
+            0
+        )
+        .is_nan());
+    }
+}
+
+#[test]
+fn one_tom_lane_plays_a_rising_fill_then_restores_its_tuning() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    let mut buf = vec![0.0_f32; STEP * 2];
+    unsafe {
+        // Floor, low, mid, high, then a plain hit
+        for (step, tune) in [(0, -12.0), (1, -5.0), (2, 0.0), (3, 7.0)] {
+            gooey_engine_sequencer_set_instrument_step(engine, INSTRUMENT_TOM, step, true);
+            gooey_engine_sequencer_set_instrument_step_tune(engine, INSTRUMENT_TOM, step, tune);
+        }
+        gooey_engine_sequencer_set_instrument_step(engine, INSTRUMENT_TOM, 4, true);
+        gooey_engine_sequencer_start(engine);
+
+        let mut counts = Vec::new();
+        for step in 0..5 {
+            gooey_engine_render(engine, buf.as_mut_ptr(), STEP as u32);
+            counts.push(crossings(&buf));
+            if step == 0 {
+                assert_eq!(gooey_engine_get_channel_tuning(engine, INSTRUMENT_TOM), 0.0);
+            }
+        }
+        assert!(
+            counts[..4].windows(2).all(|w| w[1] > w[0]),
+            "fill should rise: {counts:?}"
+        );
+        // An octave down from the untuned hit roughly halves the pitch
+        let ratio = counts[2] as f32 / counts[0] as f32;
+        assert!((1.6..2.4).contains(&ratio), "octave ratio {ratio}");
+
+        // The plain step plays at the channel's own tuning again
+        assert_eq!(gooey_engine_get_channel_tuning(engine, INSTRUMENT_TOM), 0.5);
+        assert_eq!(counts[4], counts[2]);
+        gooey_engine_free(engine);
+    }
+}
@@ -0,0 +1,113 @@
+//! Integration tests for the UI event timeline (`gooey_engine_drain_timeline_events`).
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 48_000.0;
+const BLOCK: usize = 512;
+
+unsafe fn render(engine: *mut GooeyEngine, frames: usize) {
+    let mut buffer = vec![0.0_f32; frames * GOOEY_OUTPUT_CHANNELS as usize];
+    for chunk in buffer.chunks_mut(BLOCK * GOOEY_OUTPUT_CHANNELS as usize) {
+        let chunk_frames = chunk.len() / GOOEY_OUTPUT_CHANNELS as usize;
+        gooey_engine_render(engine, chunk.as_mut_ptr(), chunk_frames as u32);
+    }
+}
+
+unsafe fn drain(engine: *mut GooeyEngine) -> Vec<GooeyTimelineEvent> {
+    let mut events = vec![GooeyTimelineEvent::default(); 32];
+    let count = gooey_engine_drain_timeline_events(engine, events.as_mut_ptr(), 32) as usize;
+    events.truncate(count);
+    events
+}
+
+#[test]
+fn timeline_is_off_by_default() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        assert!(!gooey_engine_get_timeline_enabled(engine));
+
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_SNARE);
+        render(engine, BLOCK);
+        assert!(drain(engine).is_empty());
+
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn sequencer_hits_carry_step_and_sample_accurate_times() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_set_timeline_enabled(engine, true);
+        gooey_engine_set_bpm(engine, 120.0);
+        gooey_engine_sequencer_set_instrument_step(engine, INSTRUMENT_KICK, 0, true);
+        gooey_engine_sequencer_set_instrument_step(engine, INSTRUMENT_KICK, 4, true);
+        gooey_engine_sequencer_start(engine);
+
+        // One bar at 120 BPM is two seconds
+        render(engine, SAMPLE_RATE as usize * 2);
+        let events = drain(engine);
+
+        let steps: Vec<u32> = events.iter().map(|e| e.step).collect();
+        assert_eq!(steps, [0, 4]);
+        assert!(events.iter().all(|e| e.channel == INSTRUMENT_KICK));
+        for event in &events {
+            assert_eq!(
+                event.sample_time % BLOCK as u64,
+                event.sample_offset as u64,
+                "sample_time should be the render's first frame plus the offset"
+            );
+        }
+        // A beat (four sixteenths) apart
+        let beat = (SAMPLE_RATE / 2.0) as i64;
+        let gap = events[1].sample_time as i64 - events[0].sample_time as i64;
+        assert!((gap - beat).abs() <= 1, "hits {gap} frames apart");
+        assert!(drain(engine).is_empty(), "events are drained once");
+
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn manual_hits_use_the_host_clock_plus_latency() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_set_timeline_enabled(engine, true);
+        gooey_engine_set_timeline_latency(engine, 100);
+
+        render(engine, BLOCK);
+        gooey_engine_set_render_host_time(engine, 1_000_000, 2.0);
+        render(engine, BLOCK);
+        // Host clock not refreshed: extrapolated from the last anchor
+        gooey_engine_trigger_instrument_with_velocity(engine, INSTRUMENT_HIHAT, 0.5);
+        render(engine, BLOCK);
+
+        let events = drain(engine);
+        assert_eq!(events.len(), 1);
+        let hit = events[0];
+        assert_eq!(hit.channel, INSTRUMENT_HIHAT);
+        assert_eq!(hit.step, TIMELINE_STEP_NONE);
+        assert_eq!(hit.velocity, 0.5);
+        assert_eq!(hit.sample_offset, 0);
+        assert_eq!(hit.sample_time, 2 * BLOCK as u64);
+        assert_eq!(hit.host_time, 1_000_000 + (BLOCK as u64 + 100) * 2);
+
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn disabling_discards_undrained_events() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_set_timeline_enabled(engine, true);
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_KICK);
+        render(engine, BLOCK);
+
+        gooey_engine_set_timeline_enabled(engine, false);
+        gooey_engine_set_timeline_enabled(engine, true);
+        assert!(drain(engine).is_empty());
+
+        gooey_engine_free(engine);
+    }
+}
@@ -0,0 +1,96 @@
+//! Integration tests for the debug event log (`gooey_engine_drain_trace_events`).
+//! Run with `--features trace`; without it the log must stay empty.
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 48_000.0;
+const BLOCK: usize = 512;
+
+unsafe fn render(engine: *mut GooeyEngine, frames: usize) {
+    let mut buffer = vec![0.0_f32; BLOCK * GOOEY_OUTPUT_CHANNELS as usize];
+    for _ in 0..frames.div_ceil(BLOCK) {
+        gooey_engine_render(engine, buffer.as_mut_ptr(), BLOCK as u32);
+    }
+}
+
+unsafe fn drain(engine: *mut GooeyEngine) -> Vec<GooeyTraceEvent> {
+    let mut events = vec![GooeyTraceEvent::default(); 256];
+    let count = gooey_engine_drain_trace_events(engine, events.as_mut_ptr(), 256) as usize;
+    events.truncate(count);
+    events
+}
+
+#[test]
+fn log_is_compiled_in_only_with_the_trace_feature() {
+    assert_eq!(gooey_engine_trace_available(), cfg!(feature = "trace"));
+}
+
+#[test]
+fn hits_steps_and_params_are_logged_in_frame_order() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        gooey_engine_set_bpm(engine, 120.0);
+        gooey_engine_sequencer_set_instrument_step(engine, INSTRUMENT_KICK, 0, true);
+        gooey_engine_set_kick_param(engine, KICK_PARAM_PUNCH, 0.4);
+        gooey_engine_sequencer_start(engine);
+        // Into the third sixteenth at 120 BPM
+        render(engine, SAMPLE_RATE as usize / 4);
+        let events = drain(engine);
+
+        if !gooey_engine_trace_available() {
+            assert!(events.is_empty());
+            gooey_engine_free(engine);
+            return;
+        }
+        assert!(events.windows(2).all(|w| w[0].frame <= w[1].frame));
+        let param = events.iter().find(|e| e.kind == TRACE_EVENT_PARAM).unwrap();
+        assert_eq!(
+            (param.channel, param.index, param.value),
+            (INSTRUMENT_KICK, KICK_PARAM_PUNCH, 0.4)
+        );
+        let hits: Vec<_> = events
+            .iter()
+            .filter(|e| e.kind == TRACE_EVENT_TRIGGER)
+            .map(|e| (e.channel, e.frame))
+            .collect();
+        assert_eq!(hits, [(INSTRUMENT_KICK, 0)]);
+        let kick_steps: Vec<_> = events
+            .iter()
+            .filter(|e| e.kind == TRACE_EVENT_STEP && e.channel == INSTRUMENT_KICK)
+            .map(|e| (e.index, e.frame))
+            .collect();
+        assert_eq!(kick_steps, [(0, 0), (1, 6_000), (2, 12_000)]);
+        assert_eq!(gooey_engine_trace_take_dropped(engine), 0);
+
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn host_clock_gaps_are_logged_as_xruns() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        let mut host_time = 1_000_000;
+        for _ in 0..3 {
+            gooey_engine_set_render_host_time(engine, host_time, 1.0);
+            render(engine, BLOCK);
+            host_time += BLOCK as u64;
+        }
+        // The host skipped two buffers
+        host_time += 2 * BLOCK as u64;
+        gooey_engine_set_render_host_time(engine, host_time, 1.0);
+        render(engine, BLOCK);
+
+        let xruns: Vec<_> = drain(engine)
+            .into_iter()
+            .filter(|e| e.kind == TRACE_EVENT_XRUN)
+            .map(|e| (e.frame, e.index))
+            .collect();
+        if gooey_engine_trace_available() {
+            assert_eq!(xruns, [(3 * BLOCK as u64, 2 * BLOCK as u32)]);
+        } else {
+            assert!(xruns.is_empty());
+        }
+        gooey_engine_free(engine);
+    }
+}
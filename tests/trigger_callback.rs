@@ -0,0 +1,115 @@
+//! Integration tests for the trigger callback (`gooey_engine_set_trigger_callback`).
+
+use std::ffi::c_void;
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 48_000.0;
+const BLOCK: usize = 512;
+
+#[derive(Debug, PartialEq)]
+struct Hit {
+    channel: u32,
+    velocity: f32,
+    sample_offset: u32,
+    render: usize,
+}
+
+#[derive(Default)]
+struct Recorder {
+    hits: Vec<Hit>,
+    render: usize,
+}
+
+extern "C" fn record(context: *mut c_void, channel: u32, velocity: f32, sample_offset: u32) {
+    let recorder = unsafe { &mut *(context as *mut Recorder) };
+    recorder.hits.push(Hit {
+        channel,
+        velocity,
+        sample_offset,
+        render: recorder.render,
+    });
+}
+
+unsafe fn render(engine: *mut GooeyEngine, recorder: &mut Recorder, frames: usize) {
+    let mut buffer = vec![0.0_f32; BLOCK * GOOEY_OUTPUT_CHANNELS as usize];
+    for _ in 0..frames.div_ceil(BLOCK) {
+        gooey_engine_render(engine, buffer.as_mut_ptr(), BLOCK as u32);
+        recorder.render += 1;
+    }
+}
+
+#[test]
+fn kick_hits_report_their_frame_offset() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        let mut recorder = Recorder::default();
+        gooey_engine_set_trigger_callback(
+            engine,
+            &mut recorder as *mut Recorder as *mut c_void,
+            1 << INSTRUMENT_KICK,
+            Some(record),
+        );
+        gooey_engine_set_bpm(engine, 120.0);
+        gooey_engine_sequencer_set_instrument_step(engine, INSTRUMENT_KICK, 0, true);
+        gooey_engine_sequencer_set_instrument_step(engine, INSTRUMENT_KICK, 4, true);
+        // Not in the mask
+        gooey_engine_sequencer_set_instrument_step(engine, INSTRUMENT_SNARE, 2, true);
+        gooey_engine_sequencer_start(engine);
+
+        // One bar at 120 BPM is two seconds
+        render(engine, &mut recorder, SAMPLE_RATE as usize * 2 - BLOCK);
+
+        assert_eq!(recorder.hits.len(), 2, "{:?}", recorder.hits);
+        assert!(recorder.hits.iter().all(|h| h.channel == INSTRUMENT_KICK));
+        // Step 4 is half a second in
+        let frames: Vec<usize> = recorder
+            .hits
+            .iter()
+            .map(|h| h.render * BLOCK + h.sample_offset as usize)
+            .collect();
+        assert_eq!(frames[0], 0);
+        assert!(
+            frames[1].abs_diff(SAMPLE_RATE as usize / 2) <= 1,
+            "step 4 at frame {}",
+            frames[1]
+        );
+
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn manual_triggers_report_offset_zero_and_null_unregisters() {
+    unsafe {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        let mut recorder = Recorder::default();
+        gooey_engine_set_trigger_callback(
+            engine,
+            &mut recorder as *mut Recorder as *mut c_void,
+            (1 << INSTRUMENT_KICK) | (1 << INSTRUMENT_SNARE),
+            Some(record),
+        );
+
+        gooey_engine_trigger_instrument_with_velocity(engine, INSTRUMENT_SNARE, 0.5);
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_HIHAT);
+        render(engine, &mut recorder, BLOCK);
+        assert_eq!(
+            recorder.hits,
+            [Hit {
+                channel: INSTRUMENT_SNARE,
+                velocity: 0.5,
+                sample_offset: 0,
+                render: 0,
+            }]
+        );
+
+        gooey_engine_set_trigger_callback(engine, std::ptr::null_mut(), u32::MAX, None);
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_KICK);
+        render(engine, &mut recorder, BLOCK);
+        assert_eq!(recorder.hits.len(), 1);
+
+        gooey_engine_set_trigger_callback(std::ptr::null_mut(), std::ptr::null_mut(), 1, None);
+        gooey_engine_free(engine);
+    }
+}
@@ -0,0 +1,61 @@
+//! Integration tests for retrospective capture of manual hits
+
+use gooey::ffi::*;
+
+/// Render `steps` sixteenths at 120 BPM / 48 kHz (6000 frames each).
+unsafe fn render_steps(engine: *mut GooeyEngine, steps: usize) {
+    let mut buffer = vec![0.0f32; 500 * 2];
+    for _ in 0..steps * 12 {
+        gooey_engine_render(engine, buffer.as_mut_ptr(), 500);
+    }
+}
+
+#[test]
+fn test_capture_turns_played_hits_into_a_pattern() {
+    unsafe {
+        let engine = gooey_engine_new(48000.0);
+        gooey_engine_set_bpm(engine, 120.0);
+        gooey_engine_sequencer_set_instrument_pattern(
+            engine,
+            INSTRUMENT_SNARE,
+            [false; 16].as_ptr(),
+        );
+        gooey_engine_sequencer_start(engine);
+        // Play bar 2 by hand: snare on 2 and 4, one ghost note
+        render_steps(engine, 16 + 4);
+        gooey_engine_trigger_instrument_with_velocity(engine, INSTRUMENT_SNARE, 0.9);
+        render_steps(engine, 3);
+        gooey_engine_trigger_instrument_with_velocity(engine, INSTRUMENT_SNARE, 0.3);
+        render_steps(engine, 5);
+        gooey_engine_trigger_instrument_with_velocity(engine, INSTRUMENT_SNARE, 0.9);
+        render_steps(engine, 2);
+
+        assert_eq!(gooey_engine_capture_hit_count(engine, 1), 3);
+        assert_eq!(gooey_engine_capture_to_patterns(engine, 1), GooeyResult::Ok);
+        let enabled: Vec<u32> = (0..16)
+            .filter(|&step| {
+                gooey_engine_sequencer_get_instrument_step_enabled(engine, INSTRUMENT_SNARE, step)
+            })
+            .collect();
+        assert_eq!(enabled, [4, 7, 12]);
+
+        gooey_engine_capture_clear(engine);
+        assert_eq!(gooey_engine_capture_hit_count(engine, 1), 0);
+        assert_eq!(
+            gooey_engine_capture_to_patterns(engine, CAPTURE_MAX_BARS + 1),
+            GooeyResult::InvalidValue
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn test_hits_with_transport_stopped_are_not_captured() {
+    unsafe {
+        let engine = gooey_engine_new(48000.0);
+        gooey_engine_trigger_instrument(engine, INSTRUMENT_KICK);
+        render_steps(engine, 1);
+        assert_eq!(gooey_engine_capture_hit_count(engine, 1), 0);
+        gooey_engine_free(engine);
+    }
+}
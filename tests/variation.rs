@@ -0,0 +1,149 @@
+//! Tests for per-hit parameter variation over FFI.
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+const HIT_FRAMES: usize = 8192;
+
+/// Trigger `instrument` `count` times, rendering each hit separately, and
+/// return each hit's left channel.
+fn hits(engine: *mut GooeyEngine, instrument: u32, count: usize) -> Vec<Vec<f32>> {
+    let mut buf = vec![0.0_f32; HIT_FRAMES * 2];
+    (0..count)
+        .map(|_| unsafe {
+            gooey_engine_trigger_instrument(engine, instrument);
+            gooey_engine_render(engine, buf.as_mut_ptr(), HIT_FRAMES as u32);
+            buf.iter().step_by(2).copied().collect()
+        })
+        .collect()
+}
+
+/// Largest sample difference between two hits. Consecutive plain hits are
+/// not bit-identical (the previous tail is still ringing out), so hits are
+/// compared with a tolerance.
+fn max_diff(a: &[f32], b: &[f32]) -> f32 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x - y).abs())
+        .fold(0.0, f32::max)
+}
+
+#[test]
+fn variation_defaults_off_and_rejects_bad_input() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        assert_eq!(
+            gooey_engine_get_instrument_variation_depth(engine, INSTRUMENT_SNARE),
+            0.0
+        );
+        assert_eq!(
+            gooey_engine_get_instrument_variation_variants(engine, INSTRUMENT_SNARE),
+            0
+        );
+        assert_eq!(
+            gooey_engine_get_instrument_variation_params(engine, INSTRUMENT_SNARE),
+            0
+        );
+
+        for (depth, variants) in [(1.5, 0), (0.5, 1), (0.5, 5)] {
+            assert_eq!(
+                gooey_engine_set_instrument_variation(engine, INSTRUMENT_SNARE, depth, variants),
+                GooeyResult::InvalidValue
+            );
+        }
+        assert_eq!(
+            gooey_engine_set_instrument_variation(engine, 99, 0.5, 0),
+            GooeyResult::InvalidInstrument
+        );
+        // Filter type is a choice, and hi-hats have no parameter 20.
+        assert_eq!(
+            gooey_engine_set_instrument_variation_params(
+                engine,
+                INSTRUMENT_SNARE,
+                1 << SNARE_PARAM_FILTER_TYPE
+            ),
+            GooeyResult::InvalidParam
+        );
+        assert_eq!(
+            gooey_engine_set_instrument_variation_params(engine, INSTRUMENT_HIHAT, 1 << 20),
+            GooeyResult::InvalidParam
+        );
+        assert_eq!(
+            gooey_engine_set_instrument_variation_params(
+                engine,
+                INSTRUMENT_BASS,
+                1 << BASS_PARAM_FILTER_CUTOFF
+            ),
+            GooeyResult::InvalidInstrument
+        );
+
+        let mask = (1 << SNARE_PARAM_DECAY) | (1 << SNARE_PARAM_TUNING);
+        assert_eq!(
+            gooey_engine_set_instrument_variation_params(engine, INSTRUMENT_SNARE, mask),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_get_instrument_variation_params(engine, INSTRUMENT_SNARE),
+            mask
+        );
+        // Reassigning the channel's instrument clears the selection.
+        gooey_engine_set_channel_instrument_type(engine, INSTRUMENT_SNARE, INSTRUMENT_TOM);
+        assert_eq!(
+            gooey_engine_get_instrument_variation_params(engine, INSTRUMENT_SNARE),
+            0
+        );
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn random_variation_changes_every_hit_but_not_the_set_values() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        let plain = hits(engine, INSTRUMENT_KICK, 3);
+        assert!(max_diff(&plain[1], &plain[2]) < 1e-4);
+
+        let decay = gooey_engine_get_kick_param(engine, KICK_PARAM_DECAY);
+        gooey_engine_set_instrument_variation(engine, INSTRUMENT_KICK, 1.0, 0);
+        gooey_engine_set_instrument_variation_params(
+            engine,
+            INSTRUMENT_KICK,
+            (1 << KICK_PARAM_DECAY) | (1 << KICK_PARAM_TUNING),
+        );
+        let varied = hits(engine, INSTRUMENT_KICK, 3);
+        assert!(max_diff(&varied[0], &varied[1]) > 1e-2);
+        assert!(max_diff(&varied[1], &varied[2]) > 1e-2);
+        assert_eq!(gooey_engine_get_kick_param(engine, KICK_PARAM_DECAY), decay);
+
+        // A new value set between hits becomes the new base.
+        gooey_engine_set_kick_param(engine, KICK_PARAM_DECAY, 0.2);
+        hits(engine, INSTRUMENT_KICK, 1);
+        assert_eq!(gooey_engine_get_kick_param(engine, KICK_PARAM_DECAY), 0.2);
+
+        // Turning variation off restores the plain hit.
+        gooey_engine_set_kick_param(engine, KICK_PARAM_DECAY, decay);
+        gooey_engine_set_instrument_variation(engine, INSTRUMENT_KICK, 0.0, 0);
+        let restored = hits(engine, INSTRUMENT_KICK, 2);
+        assert!(max_diff(&restored[1], &plain[2]) < 1e-4);
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn round_robin_cycles_through_fixed_variants() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        gooey_engine_set_instrument_variation(engine, INSTRUMENT_KICK, 0.8, 3);
+        gooey_engine_set_instrument_variation_params(
+            engine,
+            INSTRUMENT_KICK,
+            1 << KICK_PARAM_TUNING,
+        );
+        let cycle = hits(engine, INSTRUMENT_KICK, 7);
+        assert!(max_diff(&cycle[1], &cycle[2]) > 1e-2);
+        assert!(max_diff(&cycle[2], &cycle[3]) > 1e-2);
+        assert!(max_diff(&cycle[1], &cycle[4]) < 1e-4);
+        assert!(max_diff(&cycle[3], &cycle[6]) < 1e-4);
+        gooey_engine_free(engine);
+    }
+}
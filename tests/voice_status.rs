@@ -0,0 +1,98 @@
+//! Tests for voice-status introspection and per-hit pan spread over FFI.
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+
+fn render(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    let mut buf = vec![0.0_f32; frames * 2];
+    unsafe { gooey_engine_render(engine, buf.as_mut_ptr(), frames as u32) };
+    buf
+}
+
+fn status(engine: *const GooeyEngine, instrument: u32) -> GooeyVoiceStatus {
+    let mut out = GooeyVoiceStatus::default();
+    let result = unsafe { gooey_engine_get_voice_status(engine, instrument, &mut out) };
+    assert_eq!(result, GooeyResult::Ok);
+    out
+}
+
+#[test]
+fn voice_status_tracks_activity_and_age() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+
+    let idle = status(engine, INSTRUMENT_HIHAT);
+    assert_eq!(idle.active_voices, 0);
+    assert_eq!(idle.age_seconds, -1.0);
+    assert_eq!(idle.pan, 0.5);
+
+    unsafe { gooey_engine_trigger_instrument(engine, INSTRUMENT_HIHAT) };
+    render(engine, 4410);
+    let sounding = status(engine, INSTRUMENT_HIHAT);
+    assert_eq!(sounding.active_voices, 1);
+    assert!((sounding.age_seconds - 0.1).abs() < 1e-3);
+
+    // Well past the hat's decay.
+    render(engine, SAMPLE_RATE as usize * 3);
+    let finished = status(engine, INSTRUMENT_HIHAT);
+    assert_eq!(finished.active_voices, 0);
+    assert!(finished.age_seconds > 3.0);
+
+    unsafe { gooey_engine_free(engine) };
+}
+
+#[test]
+fn pan_spread_moves_each_hit() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        assert_eq!(
+            gooey_engine_set_instrument_pan_spread(engine, INSTRUMENT_HIHAT, 1.0),
+            GooeyResult::Ok
+        );
+        assert_eq!(
+            gooey_engine_get_instrument_pan_spread(engine, INSTRUMENT_HIHAT),
+            1.0
+        );
+    }
+
+    let mut balances = Vec::new();
+    for _ in 0..8 {
+        unsafe { gooey_engine_trigger_instrument(engine, INSTRUMENT_HIHAT) };
+        let buf = render(engine, 2048);
+        let (left, right) = buf
+            .chunks(2)
+            .fold((0.0, 0.0), |(l, r), f| (l + f[0] * f[0], r + f[1] * f[1]));
+        balances.push(right / (left + right));
+        let pan = status(engine, INSTRUMENT_HIHAT).pan;
+        assert!((0.0..=1.0).contains(&pan));
+    }
+    let min = balances.iter().cloned().fold(f32::MAX, f32::min);
+    let max = balances.iter().cloned().fold(f32::MIN, f32::max);
+    assert!(max - min > 0.2, "hits should spread: {balances:?}");
+
+    unsafe { gooey_engine_free(engine) };
+}
+
+#[test]
+fn pan_spread_defaults_off_and_rejects_bad_input() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        assert_eq!(
+            gooey_engine_get_instrument_pan_spread(engine, INSTRUMENT_KICK),
+            0.0
+        );
+        assert_eq!(
+            gooey_engine_set_instrument_pan_spread(engine, INSTRUMENT_KICK, 1.5),
+            GooeyResult::InvalidValue
+        );
+        assert_eq!(
+            gooey_engine_set_instrument_pan_spread(engine, 99, 0.5),
+            GooeyResult::InvalidInstrument
+        );
+        assert_eq!(
+            gooey_engine_get_voice_status(engine, INSTRUMENT_KICK, std::ptr::null_mut()),
+            GooeyResult::NullPointer
+        );
+        gooey_engine_free(engine);
+    }
+}
@@ -0,0 +1,80 @@
+//! Tests for the stereo widener over FFI.
+
+use gooey::ffi::*;
+
+const SAMPLE_RATE: f32 = 44_100.0;
+
+fn render(engine: *mut GooeyEngine, frames: usize) -> Vec<f32> {
+    let mut buf = vec![0.0_f32; frames * 2];
+    unsafe { gooey_engine_render(engine, buf.as_mut_ptr(), frames as u32) };
+    buf
+}
+
+#[test]
+fn params_validate_and_read_back() {
+    let engine = gooey_engine_new(SAMPLE_RATE);
+    unsafe {
+        assert!(!gooey_engine_get_global_effect_enabled(
+            engine,
+            EFFECT_WIDENER
+        ));
+        assert_eq!(
+            gooey_engine_get_global_effect_param(engine, EFFECT_WIDENER, WIDENER_PARAM_WIDTH),
+            1.0
+        );
+        for (param, value, expected) in [
+            (WIDENER_PARAM_WIDTH, 1.6, 1.6),
+            (WIDENER_PARAM_DELAY, 50.0, 30.0),
+            (WIDENER_PARAM_CROSSOVER, 220.0, 220.0),
+        ] {
+            assert_eq!(
+                gooey_engine_set_global_effect_param(engine, EFFECT_WIDENER, param, value),
+                GooeyResult::Ok
+            );
+            render(engine, 1);
+            assert_eq!(
+                gooey_engine_get_global_effect_param(engine, EFFECT_WIDENER, param),
+                expected
+            );
+        }
+        assert_eq!(
+            gooey_engine_set_global_effect_param(engine, EFFECT_WIDENER, 3, 0.5),
+            GooeyResult::InvalidParam
+        );
+        assert_eq!(gooey_engine_get_widener_correlation(engine), 1.0);
+        assert_eq!(gooey_engine_get_widener_correlation(std::ptr::null()), 1.0);
+        gooey_engine_free(engine);
+    }
+}
+
+#[test]
+fn width_decorrelates_a_centered_hit_and_the_meter_follows() {
+    let hit = |width: f32| {
+        let engine = gooey_engine_new(SAMPLE_RATE);
+        unsafe {
+            gooey_engine_set_global_effect_enabled(engine, EFFECT_WIDENER, true);
+            gooey_engine_set_global_effect_param(
+                engine,
+                EFFECT_WIDENER,
+                WIDENER_PARAM_WIDTH,
+                width,
+            );
+            render(engine, 2_048);
+            gooey_engine_trigger_instrument(engine, INSTRUMENT_HIHAT);
+        }
+        let out = render(engine, 4_096);
+        let correlation = unsafe { gooey_engine_get_widener_correlation(engine) };
+        unsafe { gooey_engine_free(engine) };
+        (out, correlation)
+    };
+    let (unity, unity_corr) = hit(1.0);
+    let (wide, wide_corr) = hit(2.0);
+    assert!(unity.chunks(2).all(|f| (f[0] - f[1]).abs() < 1e-4));
+    assert!(unity_corr > 0.99, "{unity_corr}");
+    assert!(wide.chunks(2).any(|f| (f[0] - f[1]).abs() > 1e-3));
+    assert!(wide_corr < 0.95, "{wide_corr}");
+    // The added width is all side: the mono sum is unchanged
+    for (a, b) in unity.chunks(2).zip(wide.chunks(2)) {
+        assert!(((a[0] + a[1]) - (b[0] + b[1])).abs() < 1e-4);
+    }
+}